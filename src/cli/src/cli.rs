@@ -3,8 +3,14 @@
 
 pub mod command_context;
 pub mod commands;
+pub mod timings;
+pub mod update_check;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use commands::dev::watch::{DEFAULT_DEBOUNCE_MS, DEFAULT_POLL_INTERVAL_MS};
+use commands::dry_run_sql::DryRunSqlMode;
+use commands::inspect::DEFAULT_MAX_SCAN_ROWS;
+use commands::status::StatusGate;
 use std::path::PathBuf;
 
 /// 出力フォーマット
@@ -25,6 +31,38 @@ pub struct DryRunArg {
     pub dry_run: bool,
 }
 
+/// dry-run のSQL出力オプション
+#[derive(Args, Debug, Clone)]
+pub struct DryRunSqlArgs {
+    /// With --dry-run, print only the executable SQL statements (e.g. to pipe into psql)
+    #[arg(long, requires = "dry_run", conflicts_with = "annotate")]
+    pub sql_only: bool,
+
+    /// With --sql-only, keep the comments of the migration files
+    #[arg(long, requires = "sql_only")]
+    pub explain: bool,
+
+    /// With --dry-run, print each SQL statement with review comments
+    /// (affected object, source file, destructive flag)
+    #[arg(long, requires = "dry_run")]
+    pub annotate: bool,
+}
+
+impl DryRunSqlArgs {
+    /// dry-run のSQL出力モード
+    pub fn mode(&self) -> DryRunSqlMode {
+        if self.sql_only {
+            DryRunSqlMode::SqlOnly {
+                explain: self.explain,
+            }
+        } else if self.annotate {
+            DryRunSqlMode::Annotated
+        } else {
+            DryRunSqlMode::Report
+        }
+    }
+}
+
 /// 破壊的変更許可オプション
 #[derive(Args, Debug, Clone)]
 pub struct AllowDestructiveArg {
@@ -33,6 +71,23 @@ pub struct AllowDestructiveArg {
     pub allow_destructive: bool,
 }
 
+/// 中断されたマイグレーションのロック解放オプション
+#[derive(Args, Debug, Clone)]
+pub struct BreakStaleLockArg {
+    /// Release locks left by an interrupted migration: terminate sessions idle
+    /// in an open transaction and recover an unclean SQLite database
+    #[arg(long)]
+    pub break_stale_lock: bool,
+}
+
+/// 重複定義上書き許可オプション
+#[derive(Args, Debug, Clone)]
+pub struct AllowDuplicateOverrideArg {
+    /// Allow a table, enum or view to be defined in multiple schema files (the later file wins)
+    #[arg(long)]
+    pub allow_duplicate_override: bool,
+}
+
 /// 環境指定オプション
 #[derive(Args, Debug, Clone)]
 pub struct EnvArg {
@@ -41,6 +96,14 @@ pub struct EnvArg {
     pub env: String,
 }
 
+/// Shared --wait-timeout argument for commands that connect to the database
+#[derive(Args, Debug, Clone)]
+pub struct WaitTimeoutArg {
+    /// Keep retrying transient connection errors (e.g. connection refused) for up to this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub wait_timeout: Option<u64>,
+}
+
 /// Strata - Database Schema Management CLI
 ///
 /// Infrastructure as Code for database schemas.
@@ -89,6 +152,14 @@ pub struct Cli {
     #[arg(long, global = true, value_enum, default_value = "text")]
     pub format: OutputFormat,
 
+    /// Print how long each phase (config, parse, diff, db, ...) took to stderr
+    #[arg(long, global = true)]
+    pub timings: bool,
+
+    /// Project directory to run in (default: current directory)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub project: Option<PathBuf>,
+
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Commands,
@@ -102,7 +173,13 @@ pub enum Commands {
     /// Creates the necessary directory structure and configuration files
     /// for managing database schemas with Strata.
     ///
+    /// Without --dialect, and when stdin is a terminal, an interactive wizard
+    /// asks for the dialect, environments, connection settings and directories.
+    ///
     /// EXAMPLES:
+    ///   # Answer the questions interactively
+    ///   strata init
+    ///
     ///   # Initialize with SQLite
     ///   strata init --dialect sqlite
     ///
@@ -111,10 +188,13 @@ pub enum Commands {
     ///
     ///   # Force re-initialization
     ///   strata init --force
+    ///
+    ///   # Start from an example schema and migration
+    ///   strata init --dialect postgresql --with-example
     Init {
-        /// Database dialect (postgresql, mysql, sqlite)
+        /// Database dialect (postgresql, mysql, sqlite). Required when stdin is not a terminal
         #[arg(short, long, value_name = "DIALECT")]
-        dialect: String,
+        dialect: Option<String>,
 
         /// Force initialization even if config exists
         #[arg(short, long)]
@@ -123,6 +203,11 @@ pub enum Commands {
         /// Add .strata.yaml to .gitignore automatically
         #[arg(long)]
         add_gitignore: bool,
+
+        /// Write an example schema (schema/example.yaml) and its migration
+        /// (migrations/examples/, never applied)
+        #[arg(long)]
+        with_example: bool,
     },
 
     /// Generate migration files from schema changes
@@ -139,6 +224,31 @@ pub enum Commands {
     ///
     ///   # Dry run to preview SQL and type changes
     ///   strata generate --dry-run
+    ///
+    ///   # Generate even if the migration exceeds the configured policy limits
+    ///   strata generate --override-policy
+    ///
+    ///   # Attach metadata for external tooling to .meta.yaml
+    ///   strata generate --meta team=payments --meta ticket=PAY-123
+    ///
+    ///   # Print only a one-line-per-change summary (e.g. for PR descriptions)
+    ///   strata generate --dry-run --summary-only
+    ///
+    ///   # Refuse to generate while migrations are still unapplied
+    ///   strata generate --require-clean-pending
+    ///
+    ///   # Print only the SQL that would be written to up.sql (or down.sql)
+    ///   strata generate --dry-run --sql-only | psql "$SANDBOX_URL"
+    ///   strata generate --dry-run --sql-only --down
+    ///
+    ///   # Print each statement with review comments
+    ///   strata generate --dry-run --annotate
+    ///
+    ///   # Write queries that count rows violating the new constraints
+    ///   strata generate --with-preflight-queries
+    ///
+    ///   # Review and edit up.sql / down.sql in $EDITOR before they are written
+    ///   strata generate --edit
     Generate {
         /// Description for the migration
         #[arg(short, long, value_name = "DESCRIPTION")]
@@ -147,8 +257,93 @@ pub enum Commands {
         #[command(flatten)]
         dry_run: DryRunArg,
 
+        #[command(flatten)]
+        dry_run_sql: DryRunSqlArgs,
+
+        /// With --sql-only or --annotate, print the down script instead of the up script
+        #[arg(long, requires = "DryRunSqlArgs")]
+        down: bool,
+
         #[command(flatten)]
         allow_destructive: AllowDestructiveArg,
+
+        /// Generate the migration even if it violates the configured policy (violations become warnings)
+        #[arg(long)]
+        override_policy: bool,
+
+        #[command(flatten)]
+        allow_duplicate_override: AllowDuplicateOverrideArg,
+
+        /// Metadata to record in the migration's .meta.yaml (repeatable)
+        #[arg(long = "meta", value_name = "KEY=VALUE", value_parser = parse_meta_entry)]
+        meta: Vec<(String, String)>,
+
+        /// Print only a compact one-line-per-change summary (`+`, `-`, `~`, `! destructive:`)
+        #[arg(long, conflicts_with_all = ["sql_only", "annotate"])]
+        summary_only: bool,
+
+        /// Refuse to generate while previously generated migrations have not been applied
+        /// (same as `require_clean_pending: true` in the config)
+        #[arg(long)]
+        require_clean_pending: bool,
+
+        /// Generate on top of unapplied migrations even if clean pending is required
+        #[arg(long)]
+        allow_stacked: bool,
+
+        /// Also write preflight.sql: read-only queries counting existing rows that
+        /// would violate the CHECK, NOT NULL and foreign key constraints this migration adds
+        #[arg(long)]
+        with_preflight_queries: bool,
+
+        /// Open the generated up.sql and down.sql in $STRATA_EDITOR, $VISUAL or $EDITOR
+        /// before writing them (empty a file to cancel)
+        #[arg(long, conflicts_with = "dry_run")]
+        edit: bool,
+    },
+
+    /// List contract_pending columns and drop the ones that are old enough
+    ///
+    /// Columns marked `lifecycle: contract_pending` stay in the database when
+    /// they are removed from the schema files. `strata generate` records the
+    /// date each column became contract_pending in the schema snapshot.
+    /// Without --columns or --all, lists those columns and whether they can
+    /// be dropped. With a selection, generates the migration that drops them.
+    ///
+    /// EXAMPLES:
+    ///   # List contract_pending columns
+    ///   strata contract
+    ///
+    ///   # Drop one column that has been contract_pending long enough
+    ///   strata contract --columns users.legacy_email
+    ///
+    ///   # Preview dropping every column pending for at least 30 days
+    ///   strata contract --all --older-than 30 --dry-run
+    Contract {
+        /// Days a column must have been contract_pending before it can be dropped
+        /// (default: contract_min_age_days in the config, or 14)
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+
+        /// Columns to drop (comma-separated TABLE.COLUMN)
+        #[arg(
+            long,
+            value_name = "COLUMNS",
+            value_delimiter = ',',
+            conflicts_with = "all"
+        )]
+        columns: Vec<String>,
+
+        /// Drop every column that is ready to be dropped
+        #[arg(long)]
+        all: bool,
+
+        /// Description for the migration
+        #[arg(short, long, value_name = "DESCRIPTION")]
+        description: Option<String>,
+
+        #[command(flatten)]
+        dry_run: DryRunArg,
     },
 
     /// Apply pending migrations to the database
@@ -163,21 +358,148 @@ pub enum Commands {
     ///   # Dry run to preview SQL
     ///   strata apply --dry-run
     ///
+    ///   # Print only the pending SQL (e.g. to run it against a sandbox)
+    ///   strata apply --dry-run --sql-only | psql "$SANDBOX_URL"
+    ///
     ///   # Apply to production with timeout
     ///   strata apply --env production --timeout 30
+    ///
+    ///   # Fail fast instead of queueing behind other sessions' locks
+    ///   strata apply --env production --lock-timeout 5 --statement-timeout 600
+    ///
+    ///   # Apply pending migrations up to and including a specific version
+    ///   strata apply --target 20240301120000
+    ///
+    ///   # Continue auto-increment counters from values exported from production
+    ///   strata apply --env loadtest --restore-sequence-values ./prod-schema/sequence_values.yaml
+    ///
+    ///   # Apply even though an applied migration's files were edited (emergencies only)
+    ///   strata apply --skip-checksum-verification
+    ///
+    ///   # Release locks left by a migration run that was killed
+    ///   strata apply --break-stale-lock
+    ///
+    ///   # Apply destructive changes from a script (no confirmation prompt)
+    ///   strata apply --env production --allow-destructive --yes
+    ///
+    ///   # Abort if existing rows violate constraints added by pending migrations
+    ///   strata apply --preflight
+    ///
+    ///   # Apply all pending migrations or none of them (PostgreSQL/SQLite)
+    ///   strata apply --single-transaction
+    ///
+    /// Each migration is applied and recorded in its own transaction, so a
+    /// failure stops the run and keeps the migrations applied before it.
+    ///
+    /// When run from a terminal, migrations with destructive changes are listed
+    /// and you are asked to type the environment name to continue.
     Apply {
         #[command(flatten)]
         dry_run: DryRunArg,
 
+        #[command(flatten)]
+        dry_run_sql: DryRunSqlArgs,
+
         #[command(flatten)]
         env: EnvArg,
 
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+
+        /// Stop after applying this migration version
+        #[arg(long, value_name = "VERSION")]
+        target: Option<String>,
+
         /// Timeout for database operations (in seconds)
         #[arg(long, value_name = "SECONDS")]
         timeout: Option<u64>,
 
+        /// Give up a statement that waits longer than this for a lock
+        /// (PostgreSQL lock_timeout, MySQL lock_wait_timeout; ignored on SQLite)
+        /// [default: lock_timeout of the environment]
+        #[arg(long, value_name = "SECONDS")]
+        lock_timeout: Option<u64>,
+
+        /// Give up a statement that runs longer than this
+        /// (PostgreSQL statement_timeout; ignored on SQLite. MySQL max_execution_time
+        /// only limits read-only SELECT statements and does not bound DDL or DML)
+        /// [default: statement_timeout of the environment]
+        #[arg(long, value_name = "SECONDS")]
+        statement_timeout: Option<u64>,
+
         #[command(flatten)]
         allow_destructive: AllowDestructiveArg,
+
+        /// Skip the confirmation prompt for destructive changes
+        /// (--allow-destructive is still required to apply them)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Apply even if the files of applied migrations were modified after
+        /// they were applied (the mismatches are reported as warnings)
+        #[arg(long)]
+        skip_checksum_verification: bool,
+
+        #[command(flatten)]
+        break_stale_lock: BreakStaleLockArg,
+
+        /// After applying migrations, set auto-increment counters and sequences
+        /// to the values in a file written by `export --include-sequence-values`
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["sql_only", "annotate"])]
+        restore_sequence_values: Option<PathBuf>,
+
+        /// Before applying each migration, run its preflight.sql and abort if
+        /// existing rows violate a constraint it adds
+        #[arg(long, conflicts_with_all = ["sql_only", "annotate"])]
+        preflight: bool,
+
+        /// With --preflight, the number of violating rows allowed per constraint
+        #[arg(long, value_name = "ROWS", default_value_t = 0, requires = "preflight")]
+        preflight_threshold: u64,
+
+        /// Apply all pending migrations in one transaction, rolling all of them
+        /// back if one fails (PostgreSQL/SQLite; ignored with a warning on MySQL)
+        #[arg(long)]
+        single_transaction: bool,
+
+        /// Apply only this pending migration, leaving earlier pending ones
+        /// unapplied (refused if an earlier pending migration touches the same tables)
+        #[arg(long, value_name = "VERSION", conflicts_with = "target")]
+        only: Option<String>,
+
+        /// With --only, apply the migration even if earlier pending migrations
+        /// touch the same tables
+        #[arg(long, requires = "only")]
+        force_out_of_order: bool,
+    },
+
+    /// Load reference data from the seeds directory
+    ///
+    /// Runs the files in `seeds/` in file name order inside a single
+    /// transaction. `.sql` files are executed as written; `.yaml` files
+    /// list a table and its rows and are turned into INSERT statements that
+    /// ignore or update rows that already exist, so seeding can be re-run
+    /// safely. Seeds are not recorded in the migration history.
+    ///
+    /// EXAMPLES:
+    ///   # Seed the development database
+    ///   strata seed
+    ///
+    ///   # Seed staging
+    ///   strata seed --env staging
+    ///
+    ///   # Run a single seed file
+    ///   strata seed --only 01_roles.yaml
+    Seed {
+        #[command(flatten)]
+        env: EnvArg,
+
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+
+        /// Run only this seed file (file name, with or without extension)
+        #[arg(long, value_name = "FILE")]
+        only: Option<String>,
     },
 
     /// Rollback applied migrations
@@ -192,6 +514,9 @@ pub enum Commands {
     ///   # Rollback last 3 migrations
     ///   strata rollback --steps 3
     ///
+    ///   # Rollback until a specific version is the latest applied migration
+    ///   strata rollback --to-version 20240301120000
+    ///
     ///   # Rollback in production
     ///   strata rollback --env production --steps 1
     ///
@@ -200,19 +525,36 @@ pub enum Commands {
     ///
     ///   # Allow destructive rollback
     ///   strata rollback --allow-destructive
+    ///
+    ///   # Roll back migrations whose down.sql has only comments
+    ///   strata rollback --allow-empty-down
     Rollback {
         /// Number of migrations to rollback
-        #[arg(long, value_name = "N")]
+        #[arg(long, value_name = "N", conflicts_with = "target")]
         steps: Option<u32>,
 
+        /// Roll back until this migration version is the latest applied one
+        #[arg(long, visible_alias = "to-version", value_name = "VERSION")]
+        target: Option<String>,
+
         #[command(flatten)]
         env: EnvArg,
 
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+
         #[command(flatten)]
         dry_run: DryRunArg,
 
         #[command(flatten)]
         allow_destructive: AllowDestructiveArg,
+
+        #[command(flatten)]
+        break_stale_lock: BreakStaleLockArg,
+
+        /// Roll back migrations whose down.sql has no executable statements (only comments)
+        #[arg(long)]
+        allow_empty_down: bool,
     },
 
     /// Check schema validity and preview migration changes
@@ -230,6 +572,9 @@ pub enum Commands {
         /// Path to schema directory
         #[arg(short, long, value_name = "DIR")]
         schema_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        allow_duplicate_override: AllowDuplicateOverrideArg,
     },
 
     /// Validate schema definitions
@@ -243,10 +588,44 @@ pub enum Commands {
     ///
     ///   # Validate specific directory
     ///   strata validate --schema-dir ./custom-schema
+    ///
+    ///   # Report only issues related to files changed since origin/main
+    ///   strata validate --changed-since origin/main
+    ///
+    ///   # Check a single file in isolation (editor integration)
+    ///   strata validate --file schema/users.yaml --format json
+    ///
+    ///   # Show fixes for common errors as a patch, then apply the safe ones
+    ///   strata validate --suggest-fixes
+    ///   strata validate --apply-fixes
     Validate {
         /// Path to schema directory
         #[arg(short, long, value_name = "DIR")]
         schema_dir: Option<PathBuf>,
+
+        #[command(flatten)]
+        allow_duplicate_override: AllowDuplicateOverrideArg,
+
+        /// Report only issues related to schema files changed since this git ref
+        #[arg(long, value_name = "REF", conflicts_with = "files")]
+        changed_since: Option<String>,
+
+        /// Report only issues related to these schema files (no git required)
+        #[arg(long, value_name = "FILE", num_args = 1..)]
+        files: Vec<PathBuf>,
+
+        /// Validate only this file, deferring references to other files
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["changed_since", "files", "schema_dir"])]
+        file: Option<PathBuf>,
+
+        /// Show fixes for common errors as a unified diff against the schema files (nothing is changed)
+        #[arg(long, conflicts_with = "file")]
+        suggest_fixes: bool,
+
+        /// Write the safe fixes (additions only) after checking that they resolve their error
+        /// without introducing new ones
+        #[arg(long, conflicts_with = "file")]
+        apply_fixes: bool,
     },
 
     /// Show migration status
@@ -261,15 +640,158 @@ pub enum Commands {
     ///
     ///   # Show status for production
     ///   strata status --env production
+    ///
+    ///   # Also show when, how long and by whom each migration was applied
+    ///   strata status --verbose
+    ///
+    ///   # Deployment preflight: fail on pending migrations, checksum
+    ///   # drift or tables created outside migrations
+    ///   strata status --env production --gate pending,drift,unmanaged
     Status {
         #[command(flatten)]
         env: EnvArg,
+
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+
+        /// Fail with a gate-specific exit code if a check does not pass
+        /// (comma-separated; exit codes: pending=4, drift=5, unmanaged=6;
+        /// the first failing gate in the given order decides the exit code)
+        #[arg(long, value_name = "GATES", value_delimiter = ',')]
+        gate: Vec<StatusGate>,
+    },
+
+    /// Compare the schema directory against the database
+    ///
+    /// Introspects the target environment and reports tables, columns,
+    /// indexes, constraints, ENUM types and views that differ from the
+    /// schema definition. Exits with status 7 when differences are
+    /// found, so CI can fail on schema drift. The migration history
+    /// table, `external_tables` and tables kept by rollback protection
+    /// are not compared; view definitions rewritten by the database are
+    /// reported as notes only.
+    ///
+    /// EXAMPLES:
+    ///   # Compare with the development database
+    ///   strata diff
+    ///
+    ///   # Fail a CI job when production has drifted
+    ///   strata diff --env production --format json
+    Diff {
+        #[command(flatten)]
+        env: EnvArg,
+    },
+
+    /// Show applied migration history
+    ///
+    /// Lists the migrations recorded in the database. With
+    /// `store_applied_sql: true`, the up.sql / down.sql stored when each
+    /// migration was applied can be shown with --show-sql.
+    ///
+    /// EXAMPLES:
+    ///   # List applied migrations
+    ///   strata history
+    ///
+    ///   # Show the SQL stored for a migration
+    ///   strata history --show-sql 20240101120000
+    History {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Show the up.sql / down.sql stored when this version was applied
+        #[arg(long, value_name = "VERSION")]
+        show_sql: Option<String>,
+    },
+
+    /// Verify that a migration was executed exactly as its up.sql reads
+    ///
+    /// Recomputes the checksum of every statement in the local up.sql,
+    /// in order, and compares them with the statement checksums that
+    /// `apply` recorded in the migration history table. Migrations
+    /// applied before the checksums were recorded are compared with the
+    /// up.sql stored by `store_applied_sql: true`. Exits with status 8
+    /// when a statement differs, is missing, or is in a different position.
+    ///
+    /// EXAMPLES:
+    ///   # Verify a migration in production
+    ///   strata verify-execution 20240101120000 --env production
+    ///
+    ///   # Machine-readable report
+    ///   strata verify-execution 20240101120000 --format json
+    VerifyExecution {
+        /// Version of the applied migration to verify
+        #[arg(value_name = "VERSION")]
+        migration_version: String,
+
+        #[command(flatten)]
+        env: EnvArg,
+    },
+
+    /// Repair the local project from the database
+    ///
+    /// Recovers lost migration files from the SQL stored in the
+    /// migration history table (requires `store_applied_sql: true`
+    /// at the time the migrations were applied), or renumbers a
+    /// migration generated on a machine with a wrong clock.
+    ///
+    /// EXAMPLES:
+    ///   # Restore applied migrations into the migrations directory
+    ///   strata repair --export-applied-sql ./migrations
+    ///
+    ///   # Give a future-dated migration a current timestamp
+    ///   strata repair --renumber 20991231235959
+    Repair {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Write the stored up.sql / down.sql of applied migrations to DIR
+        #[arg(long, value_name = "DIR", conflicts_with = "renumber")]
+        export_applied_sql: Option<PathBuf>,
+
+        /// Rename the migration VERSION to a current timestamp
+        #[arg(long, value_name = "VERSION")]
+        renumber: Option<String>,
+
+        /// Renumber even if the migration is already applied
+        /// (schema_migrations must then be updated in every environment)
+        #[arg(long, requires = "renumber")]
+        force: bool,
+    },
+
+    /// Rename the description of a generated migration
+    ///
+    /// Renames the migration directory and updates the description in
+    /// .meta.yaml. Checksums cover the schema only, so they stay valid.
+    /// If the migration was already applied, schema_migrations still holds
+    /// the old description: the UPDATE statement for each environment is
+    /// printed, and with --env it is run in that environment.
+    ///
+    /// EXAMPLES:
+    ///   # Fix a typo in a migration name
+    ///   strata rename-migration 20240101120000 add_users_table
+    ///
+    ///   # Also update schema_migrations in development
+    ///   strata rename-migration 20240101120000 add_users_table --env development
+    RenameMigration {
+        /// Version of the migration to rename
+        #[arg(value_name = "VERSION")]
+        migration_version: String,
+
+        /// New description for the migration
+        #[arg(value_name = "NEW_DESCRIPTION")]
+        new_description: String,
+
+        /// Update schema_migrations in this environment if the migration is applied there
+        #[arg(short, long, value_name = "ENV")]
+        env: Option<String>,
     },
 
     /// Export existing database schema to code
     ///
     /// Reads the current database schema structure and generates
-    /// schema definition files in YAML format.
+    /// schema definition files in YAML format. With --from-schema, writes the
+    /// SQL that creates the schema definitions instead, without connecting to
+    /// a database.
     ///
     /// EXAMPLES:
     ///   # Export to default schema directory
@@ -283,6 +805,18 @@ pub enum Commands {
     ///
     ///   # Overwrite existing files
     ///   strata export --force
+    ///
+    ///   # Export what a restricted user can read (exit code 3 without --allow-partial)
+    ///   strata export --output ./schema --allow-partial
+    ///
+    ///   # Summarize what changed since last week's export (CHANGES.md, changes.json)
+    ///   strata export --output ./schema-today --diff-against ./schema-last-week
+    ///
+    ///   # Also save auto-increment counters and sequence values (sequence_values.yaml)
+    ///   strata export --env production --output ./prod-schema --include-sequence-values
+    ///
+    ///   # Write CREATE statements for the schema definitions to ./ddl/schema.sql (no database)
+    ///   strata export --from-schema --output ./ddl
     Export {
         /// Output directory for schema files
         #[arg(short, long, value_name = "DIR")]
@@ -291,6 +825,9 @@ pub enum Commands {
         #[command(flatten)]
         env: EnvArg,
 
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+
         /// Overwrite existing files without confirmation
         #[arg(long)]
         force: bool,
@@ -306,7 +843,336 @@ pub enum Commands {
         /// Exclude specified tables from export (comma-separated)
         #[arg(long, value_name = "TABLES", value_delimiter = ',')]
         exclude_tables: Vec<String>,
+
+        /// Exit successfully even if some tables, views or enums were skipped
+        /// because the database user lacks permission to read them
+        #[arg(long)]
+        allow_partial: bool,
+
+        /// Compare with a previous export directory and write CHANGES.md and
+        /// changes.json to the output directory (requires --output)
+        #[arg(long, value_name = "DIR")]
+        diff_against: Option<PathBuf>,
+
+        /// Write the current auto-increment and sequence values to
+        /// sequence_values.yaml in the output directory (requires --output)
+        #[arg(long)]
+        include_sequence_values: bool,
+
+        /// Write the DDL that creates the schema defined in the schema directory
+        /// instead of reading the database (schema.sql, or one file per table
+        /// with --split)
+        #[arg(long)]
+        from_schema: bool,
+    },
+
+    /// Generate markdown documentation from the schema definitions
+    ///
+    /// Writes one page per table (columns, indexes, constraints with links
+    /// to the referenced tables), view and ENUM type, and an index page with
+    /// a mermaid erDiagram of the tables and their foreign keys. No database
+    /// connection is needed, and the output only changes when the schema
+    /// does. Pages of objects removed from the schema are deleted. With
+    /// --check, nothing is written and the command exits with status 9 when
+    /// the documentation is missing or out of date.
+    ///
+    /// EXAMPLES:
+    ///   # Write the documentation to docs/schema
+    ///   strata docs
+    ///
+    ///   # Write to another directory
+    ///   strata docs --out wiki/schema
+    ///
+    ///   # Fail a CI job when the committed documentation is stale
+    ///   strata docs --check
+    Docs {
+        /// Output directory (relative paths are resolved from the project root)
+        #[arg(long, value_name = "DIR", default_value = "docs/schema")]
+        out: PathBuf,
+
+        /// Check that the documentation in the output directory is up to date
+        /// instead of writing it
+        #[arg(long)]
+        check: bool,
     },
+
+    /// Adopt Strata in an existing database without replaying its history
+    ///
+    /// Reads the schema of the target database, writes it to the schema
+    /// directory and the schema snapshot, and creates an initial migration
+    /// that is recorded as applied without being executed. Refuses to run when
+    /// the database already has applied migrations.
+    ///
+    /// EXAMPLES:
+    ///   # Adopt the production database
+    ///   strata baseline --env production
+    ///
+    ///   # Show what would be written without changing anything
+    ///   strata baseline --env production --dry-run
+    ///
+    ///   # Overwrite an existing schema snapshot and schema files
+    ///   strata baseline --env production --force
+    Baseline {
+        #[command(flatten)]
+        env: EnvArg,
+
+        #[command(flatten)]
+        dry_run: DryRunArg,
+
+        /// Overwrite an existing schema snapshot and schema files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Inspect live data to help tune the schema definition
+    ///
+    /// With --suggest-sizes, compares the declared length of every
+    /// VARCHAR/CHAR/TEXT column in the schema with the longest value stored
+    /// in the database, and reports oversized and tight columns together with
+    /// suggested lengths. Tables larger than --max-rows are skipped unless
+    /// --allow-full-scan is given.
+    ///
+    /// EXAMPLES:
+    ///   # Suggest column sizes from production data
+    ///   strata inspect --suggest-sizes --env production
+    ///
+    ///   # Only check some columns
+    ///   strata inspect --suggest-sizes --tables users --columns email,users.name
+    Inspect {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Compare declared string column lengths with the stored data
+        #[arg(long, required = true)]
+        suggest_sizes: bool,
+
+        /// Inspect only specified tables (comma-separated)
+        #[arg(long, value_name = "TABLES", value_delimiter = ',')]
+        tables: Vec<String>,
+
+        /// Inspect only specified columns (comma-separated, `column` or `table.column`)
+        #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+        columns: Vec<String>,
+
+        /// Scan tables even if they have more rows than --max-rows
+        #[arg(long)]
+        allow_full_scan: bool,
+
+        /// Largest estimated row count to scan without --allow-full-scan
+        #[arg(long, value_name = "ROWS", default_value_t = DEFAULT_MAX_SCAN_ROWS)]
+        max_rows: u64,
+    },
+
+    /// Show raw introspection results next to the converted schema
+    ///
+    /// Connects to the database and prints what the introspector returns
+    /// (data types, lengths, udt names, index and constraint rows) alongside
+    /// the converted table definition. Useful for debugging type mapping issues.
+    ///
+    /// EXAMPLES:
+    ///   # Introspect a single table
+    ///   strata introspect --table users
+    ///
+    ///   # Introspect all tables in production as JSON
+    ///   strata introspect --env production --all --format json
+    Introspect {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Table to introspect
+        #[arg(
+            short,
+            long,
+            value_name = "TABLE",
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        table: Option<String>,
+
+        /// Introspect all tables
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Show the strata version
+    ///
+    /// With --check-latest, compares the running version with the latest
+    /// release on GitHub. The check never fails the command: if the network
+    /// is unavailable, the error is reported and the exit code stays 0.
+    ///
+    /// Set `update_check: true` in the config file to run the same check
+    /// (at most once per day) after every command.
+    ///
+    /// EXAMPLES:
+    ///   # Show the version
+    ///   strata version
+    ///
+    ///   # Check whether a newer release is available
+    ///   strata version --check-latest
+    ///
+    ///   # List the database drivers compiled into this binary
+    ///   strata version --features
+    Version {
+        /// Check whether a newer release is available
+        #[arg(long)]
+        check_latest: bool,
+
+        /// List the database drivers compiled into this binary
+        #[arg(long)]
+        features: bool,
+    },
+
+    /// Run a command for every project in a workspace
+    ///
+    /// Reads `strata-workspace.yaml` in the current directory (or --project)
+    /// and runs the command for each listed member project in order.
+    /// All members are run even if one fails; the command then fails with
+    /// a summary of the failed members.
+    ///
+    /// EXAMPLES:
+    ///   # strata-workspace.yaml
+    ///   members:
+    ///     - services/auth
+    ///     - services/billing
+    ///
+    ///   # Show status for every member in production
+    ///   strata workspace status --env production
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+
+    /// Reset a development database to an empty state
+    ///
+    /// Rolls back every applied migration with its down script and drops
+    /// the migration history table. With --hard, drops the tables in the
+    /// schema snapshot and the history table in foreign key order instead.
+    /// Environments listed in `protected_environments` are refused, and the
+    /// reset must be confirmed by typing the environment name or with --yes.
+    ///
+    /// EXAMPLES:
+    ///   # Roll back everything in the development database
+    ///   strata reset
+    ///
+    ///   # Start over and apply all migrations again (no prompt)
+    ///   strata reset --reapply --yes
+    ///
+    ///   # Drop the known tables when the down scripts cannot be used
+    ///   strata reset --hard
+    ///
+    ///   # Delete a file-based SQLite database
+    ///   strata reset --hard --delete-file
+    Reset {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Drop the tables in the schema snapshot and the migration history
+        /// table instead of running down scripts
+        #[arg(long)]
+        hard: bool,
+
+        /// With --hard, delete the SQLite database file instead of dropping tables
+        #[arg(long, requires = "hard")]
+        delete_file: bool,
+
+        /// Apply all migrations again after the reset
+        #[arg(long)]
+        reapply: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+
+        #[command(flatten)]
+        wait_timeout: WaitTimeoutArg,
+    },
+
+    /// Commands for the local development loop
+    ///
+    /// EXAMPLES:
+    ///   # Generate a migration and apply it to the development database
+    ///   strata dev migrate
+    ///
+    ///   # Repeat on every change to the schema directory
+    ///   strata dev migrate --watch
+    Dev {
+        #[command(subcommand)]
+        command: DevCommands,
+    },
+}
+
+/// workspace のサブコマンド
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommands {
+    /// Show migration status for every member project
+    ///
+    /// EXAMPLES:
+    ///   # Show status for every member
+    ///   strata workspace status
+    ///
+    ///   # Deployment preflight across all members
+    ///   strata workspace status --env production --gate pending,drift
+    Status {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Fail if a check does not pass in any member (same gates as `strata status --gate`)
+        #[arg(long, value_name = "GATES", value_delimiter = ',')]
+        gate: Vec<StatusGate>,
+    },
+}
+
+/// dev のサブコマンド
+#[derive(Subcommand, Debug)]
+pub enum DevCommands {
+    /// Generate a migration and apply it in one step
+    ///
+    /// Runs `generate` and, if a migration was produced, applies it to
+    /// the environment with `apply`. Destructive changes are never
+    /// allowed implicitly: use `strata generate --allow-destructive` and
+    /// `strata apply --allow-destructive` for those. Environments listed
+    /// in `protected_environments` are refused.
+    ///
+    /// With --watch, the schema directory is polled and the cycle runs
+    /// again after each change. Edits saved in quick succession are
+    /// batched into one migration. A failed cycle (e.g. a half-edited
+    /// file) is reported and watching continues. Press Ctrl+C to stop;
+    /// a running cycle is finished first.
+    ///
+    /// EXAMPLES:
+    ///   # Generate and apply once
+    ///   strata dev migrate -d add_user_email
+    ///
+    ///   # Keep the development database in sync while editing
+    ///   strata dev migrate --watch
+    Migrate {
+        #[command(flatten)]
+        env: EnvArg,
+
+        /// Description for the migration
+        #[arg(short, long, value_name = "DESCRIPTION")]
+        description: Option<String>,
+
+        /// Watch the schema directory and repeat on every change
+        #[arg(long)]
+        watch: bool,
+
+        /// How often to check the schema directory for changes (milliseconds)
+        #[arg(long, value_name = "MS", default_value_t = DEFAULT_POLL_INTERVAL_MS, requires = "watch")]
+        poll_interval: u64,
+
+        /// How long the schema directory must stay unchanged before a cycle runs (milliseconds)
+        #[arg(long, value_name = "MS", default_value_t = DEFAULT_DEBOUNCE_MS, requires = "watch")]
+        debounce: u64,
+    },
+}
+
+/// `--meta key=value` の値を (キー, 値) に分解する
+fn parse_meta_entry(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, val)) if !key.trim().is_empty() => Ok((key.trim().to_string(), val.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{}'", value)),
+    }
 }
 
 #[cfg(test)]
@@ -318,4 +1184,18 @@ mod tests {
         use clap::CommandFactory;
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn test_parse_meta_entry() {
+        assert_eq!(
+            parse_meta_entry("ticket=PAY-123"),
+            Ok(("ticket".to_string(), "PAY-123".to_string()))
+        );
+        assert_eq!(
+            parse_meta_entry("note=a=b"),
+            Ok(("note".to_string(), "a=b".to_string()))
+        );
+        assert!(parse_meta_entry("ticket").is_err());
+        assert!(parse_meta_entry("=value").is_err());
+    }
 }
@@ -1,24 +1,76 @@
 // コマンド共通コンテキスト
 //
 // 設定ファイル読み込みやパス解決の重複をCLI層で集約する。
+//
+// コマンドごとに必要な分だけ読み込めるよう、ローダーを段階的に分けている。
+// - 設定のみ（`load_with_config`）: status, history, apply, rollback, repair, export, introspect
+// - 設定 + スキーマ（`load_with_schema` / `load_schema_with_sources`）: validate, check, inspect
+// - 設定 + スキーマ + スナップショット（`load_with_schema_and_snapshot`）: generate, contract
 
-use crate::adapters::database::DatabaseConnectionService;
+use crate::adapters::database::ConnectionManager;
 use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::commands::migration_loader;
+use crate::cli::timings::Timings;
 use crate::core::config::{Config, DatabaseConfig, Dialect};
 use crate::core::migration::MigrationRecord;
+use crate::core::schema::Schema;
 use crate::services::config_loader::ConfigLoader;
 use crate::services::database_config_resolver::DatabaseConfigResolver;
+use crate::services::schema_io::schema_parser::SchemaParserService;
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
+use crate::services::schema_io::schema_sources::SchemaSources;
 use anyhow::{anyhow, Context, Result};
 use sqlx::AnyPool;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use tracing::debug;
 
+/// スキーマ定義の読み込みオプション
+#[derive(Debug, Clone, Default)]
+pub struct SchemaLoadOptions {
+    /// スキーマディレクトリの上書き（未指定の場合は設定ファイルのschema_dir）
+    pub schema_dir: Option<PathBuf>,
+    /// 重複定義の上書きを許可するか
+    pub allow_duplicate_override: bool,
+    /// ディスクの代わりに読み込むスキーマファイルの内容（修正案を書き込む前の検証用）
+    pub file_contents: HashMap<PathBuf, String>,
+}
+
+/// スキーマスナップショットのパス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotPath {
+    /// マイグレーションディレクトリ内のスナップショット
+    PerMigration(PathBuf),
+    /// マイグレーションディレクトリ直下のグローバルスナップショット
+    Global(PathBuf),
+}
+
+impl SnapshotPath {
+    /// ファイルのパス
+    pub fn path(&self) -> &Path {
+        match self {
+            SnapshotPath::PerMigration(path) | SnapshotPath::Global(path) => path,
+        }
+    }
+}
+
 /// CLIコマンド共通の実行コンテキスト
 #[derive(Debug, Clone)]
 pub struct CommandContext {
     pub project_path: PathBuf,
     pub config_path: PathBuf,
     pub config: Config,
+    /// コマンド実行中に共有する接続プール
+    connections: ConnectionManager,
+    /// スキーマディレクトリを読み込んだ回数
+    schema_loads: Arc<AtomicUsize>,
+    /// フェーズ別の実行時間
+    timings: Timings,
+    /// 接続できるようになるまで待つ最大秒数（コマンドラインで指定された場合のみ）
+    wait_timeout: Option<u64>,
 }
 
 impl CommandContext {
@@ -28,10 +80,13 @@ impl CommandContext {
     }
 
     /// カスタム設定ファイルパスを指定してコンテキストを作成
+    ///
+    /// 設定ファイルのみを読み込み、スキーマ定義には触れない。
     pub fn load_with_config(
         project_path: PathBuf,
         custom_config_path: Option<PathBuf>,
     ) -> Result<Self> {
+        let timings = Timings::new();
         let config_path =
             custom_config_path.unwrap_or_else(|| project_path.join(Config::DEFAULT_CONFIG_PATH));
 
@@ -44,8 +99,9 @@ impl CommandContext {
             ));
         }
 
-        let config =
-            ConfigLoader::from_file(&config_path).with_context(|| "Failed to read config file")?;
+        let config = timings
+            .measure("config", || ConfigLoader::from_file(&config_path))
+            .with_context(|| "Failed to read config file")?;
 
         debug!(dialect = ?config.dialect, schema_dir = %config.schema_dir.display(), migrations_dir = %config.migrations_dir.display(), "Config loaded successfully");
 
@@ -53,9 +109,166 @@ impl CommandContext {
             project_path,
             config_path,
             config,
+            connections: ConnectionManager::new(),
+            schema_loads: Arc::new(AtomicUsize::new(0)),
+            timings,
+            wait_timeout: None,
         })
     }
 
+    /// 実行時間の計測先を差し替える
+    ///
+    /// コンテキスト作成時に計測した結果（設定読み込みなど）は差し替え先に引き継ぐ。
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        timings.absorb(&self.timings);
+        self.timings = timings;
+        self
+    }
+
+    /// 接続待ちの最大秒数を設定する
+    ///
+    /// `Some` の場合は環境設定の `wait_timeout` より優先する。
+    pub fn with_wait_timeout(mut self, wait_timeout: Option<u64>) -> Self {
+        self.wait_timeout = wait_timeout;
+        self
+    }
+
+    /// フェーズ別の実行時間
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// 設定とスキーマ定義を読み込んでコンテキストを作成
+    pub fn load_with_schema(
+        project_path: PathBuf,
+        custom_config_path: Option<PathBuf>,
+        options: &SchemaLoadOptions,
+    ) -> Result<(Self, Schema)> {
+        let context = Self::load_with_config(project_path, custom_config_path)?;
+        let schema = context.load_schema(options)?;
+        Ok((context, schema))
+    }
+
+    /// 設定・スキーマ定義（定義元情報つき）・前回のスキーマスナップショットを読み込んでコンテキストを作成
+    pub fn load_with_schema_and_snapshot(
+        project_path: PathBuf,
+        custom_config_path: Option<PathBuf>,
+        options: &SchemaLoadOptions,
+    ) -> Result<(Self, Schema, SchemaSources, Schema)> {
+        let context = Self::load_with_config(project_path, custom_config_path)?;
+        let (schema, _, sources) = context.load_schema_with_sources(options)?;
+        let snapshot = context.load_schema_snapshot()?;
+        Ok((context, schema, sources, snapshot))
+    }
+
+    /// スキーマ定義を読み込む
+    pub fn load_schema(&self, options: &SchemaLoadOptions) -> Result<Schema> {
+        let (schema, _, _) = self.load_schema_with_sources(options)?;
+        Ok(schema)
+    }
+
+    /// スキーマ定義を、読み込んだファイル一覧と定義元情報とともに読み込む
+    pub fn load_schema_with_sources(
+        &self,
+        options: &SchemaLoadOptions,
+    ) -> Result<(Schema, Vec<PathBuf>, SchemaSources)> {
+        let schema_dir = self.resolve_schema_dir(options.schema_dir.as_ref())?;
+        debug!(schema_dir = %schema_dir.display(), "Loading schema definitions");
+
+        self.schema_loads.fetch_add(1, Ordering::SeqCst);
+        let parser = SchemaParserService::new()
+            .with_allow_duplicate_override(options.allow_duplicate_override)
+            .with_file_contents(options.file_contents.clone());
+        let loaded = self
+            .timings
+            .measure("parse", || {
+                parser.parse_schema_directory_with_sources(&schema_dir)
+            })
+            .with_context(|| "Failed to parse schema")?;
+        debug!(
+            tables = loaded.0.table_count(),
+            "Schema parsed successfully"
+        );
+        Ok(loaded)
+    }
+
+    /// このコンテキストでスキーマディレクトリを読み込んだ回数
+    pub fn schema_loads(&self) -> usize {
+        self.schema_loads.load(Ordering::SeqCst)
+    }
+
+    /// スナップショットから前回のスキーマ状態を読み込む
+    ///
+    /// マイグレーションディレクトリ内のper-migrationスナップショットから前回のスキーマを復元する。
+    /// 最新のマイグレーションディレクトリにある `.schema_snapshot.yaml` を優先的に使用し、
+    /// 存在しない場合はグローバルスナップショットにフォールバックする。
+    /// これにより、失敗したマイグレーションのディレクトリが削除された場合でも
+    /// 正しいスキーマ状態を復元できる。
+    pub fn load_schema_snapshot(&self) -> Result<Schema> {
+        self.timings
+            .measure("snapshot", || self.read_schema_snapshot())
+    }
+
+    /// 前回のスキーマ状態を読み込む（計測なし）
+    ///
+    /// 新しい strata が書き込んだスナップショットや壊れたスナップショットは、
+    /// 対処方法を含むエラー（`SnapshotError`）になる。
+    fn read_schema_snapshot(&self) -> Result<Schema> {
+        match self.schema_snapshot_path()? {
+            Some(SnapshotPath::PerMigration(path)) => {
+                debug!(
+                    snapshot = %path.display(),
+                    "Loading previous schema from per-migration snapshot"
+                );
+                schema_snapshot::read_snapshot(&path)
+            }
+            Some(SnapshotPath::Global(path)) => {
+                debug!("Falling back to global schema snapshot");
+                schema_snapshot::read_snapshot(&path)
+            }
+            None => {
+                // 初回の場合は空のスキーマを返す
+                debug!("No schema snapshot found, using empty schema");
+                Ok(Schema::new("1.0".to_string()))
+            }
+        }
+    }
+
+    /// 前回のスキーマ状態の読み込み元となるスナップショットのパス
+    ///
+    /// 最新のマイグレーションディレクトリにある `.schema_snapshot.yaml` を優先し、
+    /// 存在しない場合はグローバルスナップショットを返す。どちらもなければ None。
+    pub fn schema_snapshot_path(&self) -> Result<Option<SnapshotPath>> {
+        let migrations_dir = self.migrations_dir();
+
+        // マイグレーションディレクトリが存在する場合、per-migrationスナップショットを探す
+        if migrations_dir.exists() {
+            let migrations = migration_loader::load_available_migrations(&migrations_dir)
+                .with_context(|| {
+                    format!(
+                        "Failed to load available migrations from: {:?}",
+                        migrations_dir
+                    )
+                })?;
+
+            // 最新のマイグレーションから順にper-migrationスナップショットを探す
+            for (_version, _description, migration_path) in migrations.iter().rev() {
+                let per_migration_snapshot = migration_path.join(SNAPSHOT_FILE);
+                if per_migration_snapshot.exists() {
+                    return Ok(Some(SnapshotPath::PerMigration(per_migration_snapshot)));
+                }
+            }
+        }
+
+        // per-migrationスナップショットが見つからない場合、グローバルスナップショットにフォールバック
+        let global_snapshot_path = migrations_dir.join(SNAPSHOT_FILE);
+        if global_snapshot_path.exists() {
+            return Ok(Some(SnapshotPath::Global(global_snapshot_path)));
+        }
+
+        Ok(None)
+    }
+
     /// スキーマディレクトリの絶対パス
     pub fn schema_dir(&self) -> PathBuf {
         self.project_path.join(&self.config.schema_dir)
@@ -102,7 +315,11 @@ impl CommandContext {
             .config
             .get_database_config(env)
             .with_context(|| format!("Config for environment '{}' not found", env))?;
-        Ok(DatabaseConfigResolver::apply_env_overrides(&config))
+        let mut db_config = DatabaseConfigResolver::apply_env_overrides(&config);
+        if self.wait_timeout.is_some() {
+            db_config.wait_timeout = self.wait_timeout;
+        }
+        Ok(db_config)
     }
 
     /// データベース方言を取得
@@ -110,12 +327,21 @@ impl CommandContext {
         self.config.dialect
     }
 
-    /// 接続プールを作成
+    /// 接続プールマネージャーを取得
+    pub fn connections(&self) -> &ConnectionManager {
+        &self.connections
+    }
+
+    /// 環境の接続プールを取得
+    ///
+    /// 同じ環境への2回目以降の呼び出しでは、最初に作成したプールを共有する。
     pub async fn connect_pool(&self, env: &str) -> Result<AnyPool> {
         self.connect_pool_with_timeout(env, None).await
     }
 
-    /// タイムアウト付きで接続プールを作成
+    /// タイムアウト付きで環境の接続プールを取得
+    ///
+    /// `timeout` はプールを新しく作成する場合のみ反映される。
     pub async fn connect_pool_with_timeout(
         &self,
         env: &str,
@@ -124,11 +350,15 @@ impl CommandContext {
         let mut db_config = self.database_config(env)?;
         if let Some(t) = timeout {
             db_config.timeout = Some(t);
+            db_config.acquire_timeout = Some(t);
         }
         debug!(env = %env, dialect = ?self.config.dialect, host = %db_config.host, database = %db_config.database, "Connecting to database");
-        let db_service = DatabaseConnectionService::new();
-        let pool = db_service
-            .create_pool(self.config.dialect, &db_config)
+        let pool = self
+            .timings
+            .measure_async(
+                "connect",
+                self.connections.get_pool(self.config.dialect, &db_config),
+            )
             .await
             .with_context(|| "Failed to connect to database")?;
         debug!("Database connection established");
@@ -153,16 +383,37 @@ impl CommandContext {
         let pool = self.connect_pool_with_timeout(env, timeout).await?;
 
         let migrator = DatabaseMigratorService::new();
-        migrator
-            .create_migration_table(&pool, self.config.dialect)
-            .await
-            .with_context(|| "Failed to create migration history table")?;
+        let applied_migrations = self
+            .timings
+            .measure_async("history", async {
+                migrator
+                    .create_migration_table(&pool, self.config.dialect)
+                    .await
+                    .with_context(|| "Failed to create migration history table")?;
 
-        let applied_migrations = migrator
-            .get_migrations(&pool, self.config.dialect)
-            .await
-            .with_context(|| "Failed to get applied migration history")?;
+                migrator
+                    .get_migrations(&pool, self.config.dialect)
+                    .await
+                    .with_context(|| "Failed to get applied migration history")
+            })
+            .await?;
 
         Ok((pool, applied_migrations))
     }
+
+    /// コマンド実行中に作成した接続プールを全て閉じる
+    pub async fn close_pools(&self) {
+        self.connections.close_all().await;
+    }
+
+    /// コマンド本体を実行し、終了後に接続プールを閉じる
+    ///
+    /// 本体がエラーを返した場合も必ず閉じる。接続プールはコマンド全体で共有しているため、
+    /// 閉じずに戻ると接続がサーバー側に残り、SQLiteではファイルのロックも解放されない。
+    /// データベースに接続するコマンドは、本体をこのメソッド経由で実行する。
+    pub async fn run_with_pools<T>(&self, body: impl Future<Output = Result<T>>) -> Result<T> {
+        let result = body.await;
+        self.close_pools().await;
+        result
+    }
 }
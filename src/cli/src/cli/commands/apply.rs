@@ -3,29 +3,48 @@
 // マイグレーションの適用機能を実装します。
 // - データベース接続の確立
 // - 未適用マイグレーションの検出
-// - マイグレーションの順次実行（トランザクション内）
+// - マイグレーションの順次実行（マイグレーションごとのトランザクション内、
+//   --single-transaction で PostgreSQL/SQLite では全体を1つのトランザクション内）
+// - マイグレーションごとの進捗の表示と、失敗時の適用済み・未適用の一覧
 // - 実行結果の記録とチェックサムの保存
 // - 実行ログの表示
+// - export で書き出した自動採番の現在値の復元（--restore-sequence-values）
+// - 破壊的変更を含む場合の環境名による確認（端末から実行した場合）
+// - 事前確認クエリ（preflight.sql）による制約違反の確認（--preflight）
 
-use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::adapters::sequence_values::{SequenceValue, SequenceValueCollector, SequenceValuesFile};
 use crate::cli::command_context::CommandContext;
+use crate::cli::commands::confirm::Confirm;
 use crate::cli::commands::destructive_change_formatter::DestructiveChangeFormatter;
-use crate::cli::commands::migration_loader;
-use crate::cli::commands::split_sql_statements;
+use crate::cli::commands::dry_run_sql::{self, DryRunSqlMode};
+use crate::cli::commands::policy_violation_formatter::PolicyViolationFormatter;
+use crate::cli::commands::sql_output::{migration_sql_output, read_sql_file};
 use crate::cli::commands::DESTRUCTIVE_SQL_REGEX;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::commands::{
+    render_output, render_output_with_timings, split_sql_statements, CommandOutput,
+    CompletedWithFailure,
+};
+use crate::cli::timings::Timings;
 use crate::cli::OutputFormat;
-use crate::core::config::Dialect;
-use crate::core::migration::{
-    AppliedMigration, DestructiveChangeStatus, Migration, MigrationMetadata, MigrationRecord,
+use crate::core::config::{Dialect, PolicyConfig, SqlOutputConfig};
+use crate::core::migration::{AppliedMigration, DestructiveChangeStatus, MigrationMetadata};
+use crate::services::migration_policy_checker::MigrationPolicyChecker;
+use crate::services::migration_runner::{
+    read_metadata, ApplyPlan, ApplyProgress, LocalMigration, MigrationRunner, MigrationRunnerError,
 };
 use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
 use colored::Colorize;
 use serde::Serialize;
 use std::fs;
-use std::path::PathBuf;
-use tracing::{debug, info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+/// 破壊的変更を含むマイグレーションを端末から適用する際の確認プロンプト
+pub const DESTRUCTIVE_APPLY_PROMPT: &str = "Type the environment name to continue:";
+
+/// JSON出力モードで破壊的変更の適用を拒否したときのエラーコード
+pub const DESTRUCTIVE_CHANGES_NOT_ALLOWED: &str = "destructive_changes_not_allowed";
 
 /// applyコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
@@ -40,6 +59,9 @@ pub struct ApplyOutput {
     pub total_duration_ms: i64,
     /// 警告メッセージ
     pub warnings: Vec<String>,
+    /// --restore-sequence-values 指定時の復元結果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_values: Option<SequenceRestoreResult>,
     /// メッセージ
     #[serde(skip)]
     pub message: String,
@@ -50,9 +72,54 @@ pub struct ApplyOutput {
 pub struct MigrationResult {
     pub version: String,
     pub description: String,
+    /// 適用結果
+    pub status: MigrationApplyStatus,
     pub duration_ms: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sql: Option<String>,
+    /// 実行したステートメントのチェックサム（実行順）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_checksums: Option<Vec<String>>,
+    /// 一時的なエラーでステートメントを再試行した回数の合計
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+}
+
+/// マイグレーション1件の適用結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationApplyStatus {
+    /// 適用した
+    Applied,
+    /// 適用に失敗した
+    Failed,
+    /// 実行したが、--single-transaction で失敗したマイグレーションとともにロールバックした
+    RolledBack,
+    /// 適用していない（dry-run、または失敗したマイグレーションより後のもの）
+    Pending,
+}
+
+/// 自動採番の現在値の復元結果
+#[derive(Debug, Clone, Serialize)]
+pub struct SequenceRestoreResult {
+    /// 値ファイルのパス
+    pub file: String,
+    /// 復元した値（dry-run では復元する値）
+    pub restored: Vec<SequenceValue>,
+    /// 復元の対象外としたテーブル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedSequenceValue>,
+    /// 実行したSQL（dry-run では実行するSQL）
+    pub statements: Vec<String>,
+}
+
+/// 復元の対象外とした値
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedSequenceValue {
+    /// テーブル名
+    pub table: String,
+    /// スキップ理由
+    pub reason: String,
 }
 
 impl CommandOutput for ApplyOutput {
@@ -61,6 +128,36 @@ impl CommandOutput for ApplyOutput {
     }
 }
 
+/// 破壊的変更を含む未適用のマイグレーション
+#[derive(Debug, Clone, Serialize)]
+pub struct DestructiveMigration {
+    pub version: String,
+    pub description: String,
+    /// `.meta.yaml` に記録された破壊的変更
+    pub changes: Vec<String>,
+    /// up.sql 内の破壊的なステートメント（先頭行）
+    pub statements: Vec<String>,
+}
+
+/// JSON出力モードで破壊的変更の適用を拒否したときの出力
+#[derive(Debug, Clone, Serialize)]
+pub struct DestructiveChangesNotAllowedOutput {
+    /// エラーコード（`destructive_changes_not_allowed`）
+    pub error: String,
+    /// メッセージ
+    pub message: String,
+    /// 対象環境
+    pub environment: String,
+    /// 破壊的変更を含むマイグレーション
+    pub migrations: Vec<DestructiveMigration>,
+}
+
+impl CommandOutput for DestructiveChangesNotAllowedOutput {
+    fn to_text(&self) -> String {
+        self.message.clone()
+    }
+}
+
 /// applyコマンドの入力パラメータ
 #[derive(Debug, Clone)]
 pub struct ApplyCommand {
@@ -72,22 +169,66 @@ pub struct ApplyCommand {
     pub dry_run: bool,
     /// 対象環境
     pub env: String,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
+    /// このバージョンまで適用する（Noneの場合は未適用のものをすべて適用）
+    pub target: Option<String>,
     /// タイムアウト（秒）
     pub timeout: Option<u64>,
+    /// ロック待ちの上限（秒、Noneの場合は環境設定の `lock_timeout` に従う）
+    pub lock_timeout: Option<u64>,
+    /// 1ステートメントの実行時間の上限（秒、Noneの場合は環境設定の `statement_timeout` に従う）
+    pub statement_timeout: Option<u64>,
     /// 破壊的変更を許可
     pub allow_destructive: bool,
+    /// 破壊的変更の確認プロンプトを表示しない（--allow-destructive の要否は変わらない）
+    pub yes: bool,
+    /// 適用済みマイグレーションのチェックサム不一致を警告に留めて適用する
+    pub skip_checksum_verification: bool,
+    /// 中断されたマイグレーションが残したロックを解放する
+    pub break_stale_lock: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
+    /// dry-run のSQL出力モード（`--sql-only` / `--annotate`）
+    pub dry_run_sql: DryRunSqlMode,
+    /// マイグレーションの適用後に復元する自動採番の現在値のファイル
+    pub restore_sequence_values: Option<PathBuf>,
+    /// 適用前に事前確認クエリ（preflight.sql）を実行し、制約ごとの違反件数がこの値を
+    /// 超えたら中断する（Noneの場合は実行しない）
+    pub preflight: Option<u64>,
+    /// すべてのマイグレーションを1つのトランザクションで適用する（MySQLでは無視して警告する）
+    pub single_transaction: bool,
+    /// このバージョンのマイグレーションだけを適用する（より前の未適用マイグレーションは適用しない）
+    pub only: Option<String>,
+    /// `only` の指定時、より前の未適用マイグレーションが同じテーブルを変更していても適用する
+    pub force_out_of_order: bool,
 }
 
 /// applyコマンドハンドラー
 #[derive(Debug, Default)]
-pub struct ApplyCommandHandler {}
+pub struct ApplyCommandHandler {
+    timings: Timings,
+    confirm: Option<Arc<dyn Confirm>>,
+}
 
 impl ApplyCommandHandler {
     /// 新しいApplyCommandHandlerを作成
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// フェーズ別の実行時間の計測先を設定
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// 破壊的変更の確認プロンプトを設定
+    ///
+    /// 設定しない場合や対話的でない場合は確認せず、`allow_destructive` のみで判断します。
+    pub fn with_confirm(mut self, confirm: Arc<dyn Confirm>) -> Self {
+        self.confirm = Some(confirm);
+        self
     }
 
     /// applyコマンドを実行
@@ -104,165 +245,254 @@ impl ApplyCommandHandler {
         let context = CommandContext::load_with_config(
             command.project_path.clone(),
             command.config_path.clone(),
-        )?;
+        )?
+        .with_timings(self.timings.clone())
+        .with_wait_timeout(command.wait_timeout);
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    /// 読み込み済みのコンテキストでapplyコマンドを実行
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &ApplyCommand,
+    ) -> Result<String> {
         let config = &context.config;
+        let prints_sql = command.dry_run && command.dry_run_sql.prints_sql();
+        if command.dry_run {
+            command.dry_run_sql.check_format(&command.format)?;
+        }
+        if prints_sql && command.restore_sequence_values.is_some() {
+            return Err(anyhow!(
+                "--restore-sequence-values cannot be combined with --sql-only or --annotate."
+            ));
+        }
+
+        // 値ファイルの誤りでマイグレーションの適用後に止まらないよう、先に読み込んでおく
+        let sequence_values = command
+            .restore_sequence_values
+            .as_deref()
+            .map(load_sequence_values)
+            .transpose()?;
 
         // マイグレーションディレクトリのパスを解決
         let migrations_dir = context.require_migrations_dir()?;
         debug!(migrations_dir = %migrations_dir.display(), "Resolved migrations directory");
 
+        let mut runner = MigrationRunner::new(config.clone(), command.env.clone(), migrations_dir)
+            .allow_destructive(command.allow_destructive)
+            .skip_checksum_verification(command.skip_checksum_verification)
+            .break_stale_lock(command.break_stale_lock)
+            .preflight(command.preflight)
+            .single_transaction(command.single_transaction)
+            .with_lock_timeout(command.lock_timeout)
+            .with_statement_timeout(command.statement_timeout);
+        // JSON出力では標準エラーに進捗行を混ぜない
+        if matches!(command.format, OutputFormat::Text) {
+            runner = runner.with_progress(Arc::new(ProgressPrinter));
+        }
+
         // 利用可能なマイグレーションファイルを読み込む
-        let available_migrations = migration_loader::load_available_migrations(&migrations_dir)?;
-        debug!(
-            count = available_migrations.len(),
-            "Loaded available migrations"
-        );
+        // 存在しないバージョンはデータベースに接続する前に拒否する
+        let local =
+            runner.local_migrations(command.target.as_deref().or(command.only.as_deref()))?;
 
-        if available_migrations.is_empty() {
+        // 時刻のずれたマシンで生成された未来の日時のマイグレーションを警告
+        for warning in &local.future_warnings {
+            eprintln!("{}", warning.yellow());
+        }
+
+        // SQLのみを出力する場合、実行するSQLがなければ何も出力しない
+        if local.is_empty() && prints_sql {
+            return Ok(String::new());
+        }
+        if local.is_empty() {
             let output = ApplyOutput {
                 dry_run: command.dry_run,
                 applied_count: 0,
                 migrations: vec![],
                 total_duration_ms: 0,
                 warnings: vec![],
+                sequence_values: None,
                 message: "No migration files found.".to_string(),
             };
-            return render_output(&output, &command.format);
+            return self
+                .finish(context, command, sequence_values.as_ref(), output)
+                .await;
         }
 
-        // データベース接続を確立し、マイグレーション履歴を取得
+        // 前回の実行が中断された痕跡を検出（SQLiteは接続すると復旧が始まるため接続前に確認）
+        let leftover = runner.find_leftover_files();
+        for issue in &leftover {
+            eprintln!("{}", format!("Warning: {}", issue).yellow());
+        }
+
+        // データベース接続を確立し、マイグレーション履歴から適用するマイグレーションを決める
         // dry-run モードでも DB に接続して適用済みマイグレーションを確認する
-        let (pool, applied_migrations) = context
-            .connect_and_load_migrations_with_timeout(&command.env, command.timeout)
+        let pool = context
+            .connect_pool_with_timeout(&command.env, command.timeout)
             .await?;
+        let runner = runner.with_pool(pool);
+        for message in runner.check_stale_locks(&leftover).await? {
+            eprintln!("{}", message);
+        }
+        let plan = match &command.only {
+            Some(version) => {
+                self.timings
+                    .measure_async(
+                        "history",
+                        runner.plan_apply_only(&local, version, command.force_out_of_order),
+                    )
+                    .await?
+            }
+            None => {
+                self.timings
+                    .measure_async(
+                        "history",
+                        runner.plan_apply(&local, command.target.as_deref()),
+                    )
+                    .await?
+            }
+        };
+        for warning in plan.checksum_warnings.iter().chain(&plan.warnings) {
+            eprintln!("{}", warning.yellow());
+        }
+        let mut warnings = local.future_warnings.clone();
+        warnings.extend(plan.warnings.iter().cloned());
+        if command.single_transaction && config.dialect == Dialect::MySQL && !command.dry_run {
+            let warning = SINGLE_TRANSACTION_MYSQL_WARNING.to_string();
+            eprintln!("{}", warning.yellow());
+            warnings.push(warning);
+        }
 
-        // 未適用のマイグレーションを特定
-        let pending_migrations: Vec<_> = available_migrations
-            .iter()
-            .filter(|(version, _, _)| {
-                !applied_migrations
-                    .iter()
-                    .any(|record| &record.version == version)
-            })
-            .collect();
-        debug!(
-            pending = pending_migrations.len(),
-            applied = applied_migrations.len(),
-            "Migration status"
-        );
-
-        if pending_migrations.is_empty() {
+        if plan.pending.is_empty() && prints_sql {
+            return Ok(String::new());
+        }
+        if plan.pending.is_empty() {
+            // 他の方法で適用されたマイグレーションも generate が未適用と報告しないよう記録から外す
+            if !command.dry_run {
+                runner.record_applied(&plan)?;
+            }
             let output = ApplyOutput {
                 dry_run: command.dry_run,
                 applied_count: 0,
                 migrations: vec![],
                 total_duration_ms: 0,
-                warnings: vec![],
-                message: "No pending migrations to apply. Database is up to date.".to_string(),
+                warnings: plan.checksum_warnings.into_iter().chain(warnings).collect(),
+                sequence_values: None,
+                message: match &command.target {
+                    Some(target) => format!(
+                        "Database is already at version {}. No migrations to apply.",
+                        target
+                    ),
+                    None => "No pending migrations to apply. Database is up to date.".to_string(),
+                },
             };
-            return render_output(&output, &command.format);
-        }
-
-        // 適用済みマイグレーションのチェックサム検証
-        let checksum_warnings =
-            self.verify_applied_checksums(&available_migrations, &applied_migrations);
-        for warning in &checksum_warnings {
-            warn!("{}", warning);
-            eprintln!("{}", warning.yellow());
+            return self
+                .finish(context, command, sequence_values.as_ref(), output)
+                .await;
         }
 
         // Dry run モードの場合は SQL を表示して終了
+        if prints_sql {
+            return self.execute_dry_run_sql(
+                &plan.pending,
+                &config.sql_output,
+                command.dry_run_sql,
+            );
+        }
         if command.dry_run {
-            return self.execute_dry_run(&pending_migrations, &command.format);
+            let output = self.execute_dry_run(&plan.pending, &config.sql_output)?;
+            return self
+                .finish(context, command, sequence_values.as_ref(), output)
+                .await;
         }
 
-        let migrator = DatabaseMigratorService::new();
-
-        // マイグレーションを順次適用
-        let mut applied = Vec::new();
-        let mut warnings = Vec::new();
-        for (version, description, migration_dir) in pending_migrations {
-            let start_time = Utc::now();
-            info!(version = %version, description = %description, "Applying migration");
-
-            // up.sqlを読み込み
-            let up_sql_path = migration_dir.join("up.sql");
-            let up_sql = fs::read_to_string(&up_sql_path)
-                .with_context(|| format!("Failed to read migration file: {:?}", up_sql_path))?;
-
-            // メタデータを読み込み
-            let meta_path = migration_dir.join(".meta.yaml");
-            let meta_content = fs::read_to_string(&meta_path)
-                .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
-            let metadata: MigrationMetadata = serde_saphyr::from_str(&meta_content)
-                .with_context(|| "Failed to parse metadata")?;
-
-            // 破壊的変更の判定
-            match metadata.destructive_change_status() {
-                DestructiveChangeStatus::Present => {
-                    let report = &metadata.destructive_changes;
-                    if !command.allow_destructive {
-                        let formatter = DestructiveChangeFormatter::new();
-                        let mut message = String::new();
-                        message.push_str(&format!("Migration: {}\n\n", version));
-                        message.push_str(&formatter.format_error(report, "strata apply"));
-                        return Err(anyhow!(message));
-                    }
-                    warnings.push(DestructiveChangeFormatter::new().format_warning(report));
-                }
-                DestructiveChangeStatus::None => {}
+        // 記録されたポリシーの判定を現在のポリシーで再確認する（適用前に警告する）
+        for migration in &plan.pending {
+            let metadata = read_metadata(&migration.dir)?;
+            if let Some(warning) =
+                self.recheck_policy(&migration.version, &metadata, &config.policy)
+            {
+                eprintln!("{}", warning);
+                warnings.push(warning);
             }
+        }
 
-            let checksum = metadata.checksum.clone();
+        // 破壊的変更を含む場合、端末では環境名の入力で確認し、JSON出力では確認せずに拒否する
+        let allow_destructive =
+            self.confirm_destructive(command, &plan.pending, &config.sql_output)?;
+        let runner = runner.allow_destructive(allow_destructive);
 
-            // トランザクション内でマイグレーションを実行
-            let result = self
-                .apply_migration_with_transaction(
-                    &pool,
-                    &migrator,
-                    version,
-                    description,
-                    &up_sql,
-                    &checksum,
-                    config.dialect,
-                )
-                .await;
-
-            if let Err(e) = result {
+        // マイグレーションを順次適用（各マイグレーションはトランザクション内で実行）
+        let report = match self
+            .timings
+            .measure_async("execute", runner.apply(&plan))
+            .await
+        {
+            Ok(report) => report,
+            Err(MigrationRunnerError::DestructiveChanges {
+                version, report, ..
+            }) => {
+                let formatter = DestructiveChangeFormatter::new();
                 return Err(anyhow!(
-                    "Failed to apply migration {} ({} applied, failed on migration #{}): {}",
+                    "Migration: {}\n\n{}",
                     version,
-                    applied.len(),
-                    applied.len() + 1,
+                    formatter.format_error(&report, "strata apply")
+                ));
+            }
+            Err(e @ MigrationRunnerError::PreflightViolations { .. }) => {
+                return Err(anyhow!(
+                    "{}\n\nFix the violating rows before applying, or raise the limit with --preflight-threshold.",
                     e
                 ));
             }
+            Err(e @ MigrationRunnerError::Apply { .. }) => {
+                return Err(self.apply_failure(command, &plan, warnings, e)?);
+            }
+            Err(e) => return Err(e.into()),
+        };
 
-            let end_time = Utc::now();
-            let duration = end_time.signed_duration_since(start_time);
-
-            applied.push(AppliedMigration::new(
-                version.clone(),
-                description.clone(),
-                end_time,
-                duration,
-            ));
+        // 適用したマイグレーションごとの警告（許可された破壊的変更・SQLの切り詰め）
+        for result in &report.migrations {
+            let metadata = &result.metadata;
+            if metadata.destructive_change_status() == DestructiveChangeStatus::Present {
+                warnings.push(
+                    DestructiveChangeFormatter::new().format_warning(&metadata.destructive_changes),
+                );
+            }
+            for warning in &result.warnings {
+                eprintln!("{}", warning.yellow());
+                warnings.push(warning.clone());
+            }
         }
 
         // 結果サマリーを生成
-        let migration_results: Vec<MigrationResult> = applied
+        let applied: Vec<AppliedMigration> = report
+            .migrations
             .iter()
-            .map(|m| MigrationResult {
-                version: m.version.clone(),
-                description: m.description.clone(),
-                duration_ms: m.duration.num_milliseconds(),
+            .map(|result| result.migration.clone())
+            .collect();
+        let migration_results: Vec<MigrationResult> = report
+            .migrations
+            .into_iter()
+            .map(|result| MigrationResult {
+                version: result.migration.version,
+                description: result.migration.description,
+                status: MigrationApplyStatus::Applied,
+                duration_ms: result.migration.duration.num_milliseconds(),
                 sql: None,
+                statement_checksums: Some(result.statement_checksums),
+                retries: Some(result.retries),
             })
             .collect();
 
         let total_duration: i64 = applied.iter().map(|m| m.duration.num_milliseconds()).sum();
 
-        let text_summary = self.generate_summary(&applied);
+        let text_summary = self.generate_summary(&migration_results);
         let text_message = if warnings.is_empty() {
             text_summary
         } else {
@@ -274,93 +504,274 @@ impl ApplyCommandHandler {
             applied_count: applied.len(),
             migrations: migration_results,
             total_duration_ms: total_duration,
-            warnings: checksum_warnings,
+            warnings: plan.checksum_warnings.into_iter().chain(warnings).collect(),
+            sequence_values: None,
             message: text_message,
         };
 
-        render_output(&output, &command.format)
+        self.finish(context, command, sequence_values.as_ref(), output)
+            .await
     }
 
-    /// マイグレーションをトランザクション内で適用
-    #[allow(clippy::too_many_arguments)]
-    async fn apply_migration_with_transaction(
+    /// マイグレーションの適用に失敗したときの出力を作る
+    ///
+    /// 失敗したマイグレーションで中断したことと、適用済み・未適用のマイグレーションを一覧にし、
+    /// 出力を表示したうえで失敗として終了するエラーを返します。
+    fn apply_failure(
         &self,
-        pool: &sqlx::AnyPool,
-        migrator: &DatabaseMigratorService,
-        version: &str,
-        description: &str,
-        up_sql: &str,
-        checksum: &str,
-        dialect: Dialect,
-    ) -> Result<()> {
-        // トランザクションを開始
-        let mut tx = pool
-            .begin()
-            .await
-            .with_context(|| "Failed to start transaction")?;
-
-        // マイグレーションSQLを文単位で実行
-        for statement in split_sql_statements(up_sql) {
-            // SQLite: 既にトランザクション内なので、ネストを防ぐために
-            // BEGIN TRANSACTION/COMMIT をスキップする
-            // (sqlite_table_recreator がこれらを生成するが、apply は既にトランザクションを開始している)
-            if dialect == Dialect::SQLite {
-                let stmt_upper = statement.trim().to_uppercase();
-                if stmt_upper == "BEGIN TRANSACTION" || stmt_upper == "COMMIT" {
-                    debug!(
-                        statement = %statement,
-                        "Skipping transaction control statement (already in transaction)"
-                    );
-                    continue;
+        command: &ApplyCommand,
+        plan: &ApplyPlan,
+        mut warnings: Vec<String>,
+        error: MigrationRunnerError,
+    ) -> Result<anyhow::Error> {
+        let MigrationRunnerError::Apply {
+            version,
+            completed,
+            rolled_back,
+            ..
+        } = &error
+        else {
+            return Ok(error.into());
+        };
+
+        let migrations: Vec<MigrationResult> = plan
+            .pending
+            .iter()
+            .map(|migration| {
+                let executed = completed
+                    .iter()
+                    .map(|m| (m, MigrationApplyStatus::Applied))
+                    .chain(
+                        rolled_back
+                            .iter()
+                            .map(|m| (m, MigrationApplyStatus::RolledBack)),
+                    )
+                    .find(|(m, _)| m.version == migration.version);
+                let (status, duration_ms) = match executed {
+                    Some((m, status)) => (status, m.duration.num_milliseconds()),
+                    None if &migration.version == version => (MigrationApplyStatus::Failed, 0),
+                    None => (MigrationApplyStatus::Pending, 0),
+                };
+                MigrationResult {
+                    version: migration.version.clone(),
+                    description: migration.description.clone(),
+                    status,
+                    duration_ms,
+                    sql: None,
+                    statement_checksums: None,
+                    retries: None,
                 }
+            })
+            .collect();
+
+        let summary = format_failure_summary(&migrations);
+        if !warnings.is_empty() {
+            warnings.push(String::new());
+        }
+        let output = ApplyOutput {
+            dry_run: false,
+            applied_count: completed.len(),
+            total_duration_ms: completed
+                .iter()
+                .map(|m| m.duration.num_milliseconds())
+                .sum(),
+            migrations,
+            warnings: plan.checksum_warnings.clone(),
+            sequence_values: None,
+            message: format!("{}{}", warnings.join("\n"), summary),
+        };
+        Ok(CompletedWithFailure {
+            output: render_output(&output, &command.format)?,
+            message: format!("{:#}", anyhow::Error::from(error)),
+            exit_code: 1,
+        }
+        .into())
+    }
+
+    /// 破壊的変更を含むマイグレーションを適用してよいか判断する
+    ///
+    /// - JSON出力: 確認せず、`--allow-destructive` がなければ構造化したエラーで失敗する
+    /// - 端末から実行（`--yes` なし）: 対象を表示し、環境名の入力で確認する
+    /// - それ以外: `--allow-destructive` の指定に従う
+    fn confirm_destructive(
+        &self,
+        command: &ApplyCommand,
+        pending: &[LocalMigration],
+        sql_output: &SqlOutputConfig,
+    ) -> Result<bool> {
+        if matches!(command.format, OutputFormat::Json) {
+            if command.allow_destructive {
+                return Ok(true);
+            }
+            let migrations = self.collect_destructive_migrations(pending, sql_output)?;
+            if migrations.is_empty() {
+                return Ok(false);
+            }
+            let versions: Vec<&str> = migrations.iter().map(|m| m.version.as_str()).collect();
+            let output = DestructiveChangesNotAllowedOutput {
+                error: DESTRUCTIVE_CHANGES_NOT_ALLOWED.to_string(),
+                message: format!(
+                    "Migration(s) {} contain destructive changes. JSON output never prompts for confirmation; pass --allow-destructive to apply them.",
+                    versions.join(", ")
+                ),
+                environment: command.env.clone(),
+                migrations,
+            };
+            return Err(CompletedWithFailure {
+                output: render_output(&output, &command.format)?,
+                message: output.message,
+                exit_code: 1,
             }
+            .into());
+        }
 
-            sqlx::query(&statement)
-                .execute(&mut *tx)
-                .await
-                .with_context(|| {
-                    format!(
-                        "Failed to execute migration SQL: {}\nSQL: {}",
-                        version, statement
-                    )
-                })?;
+        let Some(confirm) = self
+            .confirm
+            .as_ref()
+            .filter(|confirm| !command.yes && confirm.is_interactive())
+        else {
+            return Ok(command.allow_destructive);
+        };
+        let migrations = self.collect_destructive_migrations(pending, sql_output)?;
+        if migrations.is_empty() {
+            return Ok(command.allow_destructive);
         }
 
-        // マイグレーション履歴を記録（パラメータバインディング使用）
-        let migration = Migration::new(
-            version.to_string(),
-            description.to_string(),
-            checksum.to_string(),
-        );
-        let (record_sql, params) = migrator.generate_record_migration_query(&migration, dialect);
+        let answer = confirm.ask(
+            &format_destructive_summary(&migrations, &command.env),
+            DESTRUCTIVE_APPLY_PROMPT,
+        )?;
+        if answer != command.env {
+            return Err(anyhow!(
+                "Apply cancelled: '{}' does not match the environment name '{}'. No migrations were applied.",
+                answer,
+                command.env
+            ));
+        }
+        Ok(true)
+    }
 
-        let mut query = sqlx::query(&record_sql);
-        for param in &params {
-            query = query.bind(param);
+    /// 未適用のマイグレーションのうち破壊的変更を含むものを集める
+    ///
+    /// 判定は `.meta.yaml` の記録によります（適用時の判定と同じ）。
+    /// 表示用に、up.sql 内の破壊的なステートメントも合わせて返します。
+    fn collect_destructive_migrations(
+        &self,
+        pending: &[LocalMigration],
+        sql_output: &SqlOutputConfig,
+    ) -> Result<Vec<DestructiveMigration>> {
+        let mut migrations = Vec::new();
+        for migration in pending {
+            let metadata = read_metadata(&migration.dir)?;
+            if metadata.destructive_change_status() != DestructiveChangeStatus::Present {
+                continue;
+            }
+            let up_sql = read_sql_file(
+                &migration.dir.join("up.sql"),
+                &metadata.sql_output(sql_output),
+            )?;
+            let statements = split_sql_statements(&up_sql)
+                .iter()
+                .filter(|statement| DESTRUCTIVE_SQL_REGEX.is_match(statement))
+                .filter_map(|statement| {
+                    statement
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty() && !line.starts_with("--"))
+                        .map(str::to_string)
+                })
+                .collect();
+            migrations.push(DestructiveMigration {
+                version: migration.version.clone(),
+                description: migration.description.clone(),
+                changes: DestructiveChangeFormatter::new()
+                    .change_lines(&metadata.destructive_changes),
+                statements,
+            });
         }
+        Ok(migrations)
+    }
 
-        query.execute(&mut *tx).await.map_err(|e| {
-            anyhow!(
-                "Failed to record migration history: SQL={}, Error={}",
-                record_sql,
-                e
-            )
-        })?;
+    /// 自動採番の現在値を復元してから結果を出力
+    async fn finish(
+        &self,
+        context: &CommandContext,
+        command: &ApplyCommand,
+        sequence_values: Option<&(PathBuf, SequenceValuesFile)>,
+        mut output: ApplyOutput,
+    ) -> Result<String> {
+        if let Some((path, file)) = sequence_values {
+            let result = self
+                .restore_sequence_values(context, command, path, file)
+                .await?;
+            output
+                .message
+                .push_str(&format_sequence_restore(&result, command.dry_run));
+            output.sequence_values = Some(result);
+        }
 
-        // トランザクションをコミット
-        tx.commit()
-            .await
-            .with_context(|| "Failed to commit transaction")?;
+        render_output_with_timings(&output, &command.format, &self.timings)
+    }
 
-        Ok(())
+    /// 値ファイルの自動採番の現在値を復元
+    ///
+    /// データベースに存在しないテーブルの値はスキップします。
+    /// 値を設定するだけのSQLのため、途中で失敗しても再実行できます。
+    async fn restore_sequence_values(
+        &self,
+        context: &CommandContext,
+        command: &ApplyCommand,
+        path: &Path,
+        file: &SequenceValuesFile,
+    ) -> Result<SequenceRestoreResult> {
+        let collector = SequenceValueCollector::new(context.config.dialect);
+        let pool = context
+            .connect_pool_with_timeout(&command.env, command.timeout)
+            .await?;
+        let tables = collector.fetch_restorable_tables(&pool).await?;
+
+        let mut restored = Vec::new();
+        let mut skipped = Vec::new();
+        let mut statements = Vec::new();
+        for sequence in &file.sequences {
+            if !tables.contains(&sequence.table) {
+                debug!(table = %sequence.table, "Skipping sequence value");
+                skipped.push(SkippedSequenceValue {
+                    table: sequence.table.clone(),
+                    reason: collector.unrestorable_reason().to_string(),
+                });
+                continue;
+            }
+
+            let sequence_statements = collector.build_restore_statements(sequence)?;
+            if !command.dry_run {
+                for sql in &sequence_statements {
+                    sqlx::query(sql).execute(&pool).await.with_context(|| {
+                        format!(
+                            "Failed to restore sequence value for '{}'",
+                            sequence.display_name()
+                        )
+                    })?;
+                }
+            }
+            statements.extend(sequence_statements);
+            restored.push(sequence.clone());
+        }
+
+        Ok(SequenceRestoreResult {
+            file: path.display().to_string(),
+            restored,
+            skipped,
+            statements,
+        })
     }
 
     /// Dry runモードの実行
     fn execute_dry_run(
         &self,
-        pending_migrations: &[&(String, String, PathBuf)],
-        format: &OutputFormat,
-    ) -> Result<String> {
+        pending_migrations: &[LocalMigration],
+        sql_output: &SqlOutputConfig,
+    ) -> Result<ApplyOutput> {
         let mut text_output = String::from("=== DRY RUN MODE ===\n");
         text_output.push_str(&format!(
             "The following {} migration(s) will be applied:\n\n",
@@ -370,16 +781,13 @@ impl ApplyCommandHandler {
         let mut has_destructive = false;
         let mut migration_results = Vec::new();
 
-        for (version, description, migration_dir) in pending_migrations {
-            let up_sql_path = migration_dir.join("up.sql");
-            let up_sql = fs::read_to_string(&up_sql_path)
-                .with_context(|| format!("Failed to read migration file: {:?}", up_sql_path))?;
-
-            let meta_path = migration_dir.join(".meta.yaml");
-            let meta_content = fs::read_to_string(&meta_path)
-                .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
-            let metadata: MigrationMetadata = serde_saphyr::from_str(&meta_content)
-                .with_context(|| format!("Failed to parse metadata: {:?}", meta_path))?;
+        for migration in pending_migrations {
+            let (version, description) = (&migration.version, &migration.description);
+            let metadata = read_metadata(&migration.dir)?;
+            let up_sql = read_sql_file(
+                &migration.dir.join("up.sql"),
+                &metadata.sql_output(sql_output),
+            )?;
             let destructive_status = metadata.destructive_change_status();
 
             text_output.push_str(&format!("\u{25b6} {} - {}\n", version, description));
@@ -405,8 +813,11 @@ impl ApplyCommandHandler {
             migration_results.push(MigrationResult {
                 version: version.clone(),
                 description: description.clone(),
+                status: MigrationApplyStatus::Pending,
                 duration_ms: 0,
                 sql: Some(up_sql),
+                statement_checksums: None,
+                retries: None,
             });
         }
 
@@ -420,10 +831,46 @@ impl ApplyCommandHandler {
             migrations: migration_results,
             total_duration_ms: 0,
             warnings: vec![],
+            sequence_values: None,
             message: text_output,
         };
 
-        render_output(&output, format)
+        Ok(output)
+    }
+
+    /// Dry runモードでSQLのみを出力（--sql-only / --annotate）
+    fn execute_dry_run_sql(
+        &self,
+        pending_migrations: &[LocalMigration],
+        sql_output: &SqlOutputConfig,
+        mode: DryRunSqlMode,
+    ) -> Result<String> {
+        let mut sections = Vec::new();
+        for migration in pending_migrations {
+            let up_sql = read_sql_file(
+                &migration.dir.join("up.sql"),
+                &migration_sql_output(&migration.dir, sql_output)?,
+            )?;
+            let migration_file = migration
+                .dir
+                .file_name()
+                .map(|name| Path::new(name).join("up.sql"))
+                .unwrap_or_else(|| migration.dir.join("up.sql"));
+            let section = dry_run_sql::render_migration_sql(
+                mode,
+                &migration.version,
+                &migration.description,
+                &migration_file.display().to_string(),
+                &up_sql,
+                sql_output,
+            );
+            if !section.is_empty() {
+                sections.push(section);
+            }
+        }
+
+        let separator = sql_output.line_endings.as_str().repeat(2);
+        Ok(sections.join(&separator))
     }
 
     fn highlight_destructive_sql(&self, sql: &str) -> String {
@@ -441,75 +888,227 @@ impl ApplyCommandHandler {
     }
 
     /// 適用結果のサマリーを生成
-    fn generate_summary(&self, applied: &[AppliedMigration]) -> String {
+    fn generate_summary(&self, applied: &[MigrationResult]) -> String {
         let mut summary = String::from("=== Migration Apply Complete ===\n");
         summary.push_str(&format!("{} migration(s) applied:\n\n", applied.len()));
 
         for migration in applied {
+            let retries = match migration.retries {
+                Some(retries) if retries > 0 => {
+                    format!(", {} retry(ies) after transient errors", retries)
+                }
+                _ => String::new(),
+            };
             summary.push_str(&format!(
-                "✓ {} - {} ({}ms)\n",
-                migration.version,
-                migration.description,
-                migration.duration.num_milliseconds()
+                "✓ {} - {} ({}ms{})\n",
+                migration.version, migration.description, migration.duration_ms, retries
             ));
         }
 
-        let total_duration: i64 = applied.iter().map(|m| m.duration.num_milliseconds()).sum();
+        let total_duration: i64 = applied.iter().map(|m| m.duration_ms).sum();
         summary.push_str(&format!("\nTotal execution time: {}ms\n", total_duration));
 
         summary
     }
 
-    /// 適用済みマイグレーションのチェックサム検証
+    /// 記録されたポリシー評価結果を再確認
     ///
-    /// ローカルファイルのチェックサムと DB 記録のチェックサムを比較し、
-    /// 不一致がある場合は警告を返す。
-    fn verify_applied_checksums(
+    /// `--override-policy` で生成されたマイグレーション、または現在のポリシーに
+    /// 違反するマイグレーションの場合は警告を返す。適用自体は妨げない。
+    fn recheck_policy(
         &self,
-        available_migrations: &[(String, String, PathBuf)],
-        applied_migrations: &[MigrationRecord],
-    ) -> Vec<String> {
-        let mut warnings = Vec::new();
-
-        for record in applied_migrations {
-            // ローカルにファイルがあるか確認
-            if let Some((_, _, migration_dir)) = available_migrations
-                .iter()
-                .find(|(v, _, _)| v == &record.version)
-            {
-                let meta_path = migration_dir.join(".meta.yaml");
-                if meta_path.exists() {
-                    if let Ok(meta_content) = fs::read_to_string(&meta_path) {
-                        if let Ok(metadata) =
-                            serde_saphyr::from_str::<MigrationMetadata>(&meta_content)
-                        {
-                            if metadata.checksum != record.checksum {
-                                warnings.push(format!(
-                                    "Warning: Checksum mismatch for migration {}: local={}, applied={}",
-                                    record.version,
-                                    metadata.checksum,
-                                    record.checksum
-                                ));
-                            }
-                        }
-                    }
-                }
+        version: &str,
+        metadata: &MigrationMetadata,
+        policy: &PolicyConfig,
+    ) -> Option<String> {
+        let report = metadata.policy.as_ref()?;
+
+        if report.overridden && report.has_violations() {
+            return Some(format!(
+                "Migration {} was generated with --override-policy\n{}",
+                version,
+                PolicyViolationFormatter::new().format_warning(report)
+            ));
+        }
+
+        let violations = MigrationPolicyChecker::new().evaluate(policy, report);
+        if violations.is_empty() {
+            return None;
+        }
+
+        let mut message = format!(
+            "{}\n",
+            format!(
+                "Warning: Migration {} violates the current migration policy",
+                version
+            )
+            .yellow()
+            .bold()
+        );
+        for violation in &violations {
+            message.push_str(&format!("  {}\n", violation.message.yellow()));
+        }
+        Some(message)
+    }
+}
+
+/// MySQLで --single-transaction が指定されたときの警告
+pub const SINGLE_TRANSACTION_MYSQL_WARNING: &str = "Warning: --single-transaction is ignored on MySQL because DDL statements commit implicitly. Each migration is applied in its own transaction.";
+
+/// マイグレーションごとの進捗行を標準エラー出力に表示するApplyProgress
+#[derive(Debug)]
+struct ProgressPrinter;
+
+impl ApplyProgress for ProgressPrinter {
+    fn applied(&self, migration: &AppliedMigration) {
+        eprintln!(
+            "Applying {}_{} ... {} ({}ms)",
+            migration.version,
+            migration.description,
+            "ok".green(),
+            migration.duration.num_milliseconds()
+        );
+    }
+
+    fn failed(&self, migration: &LocalMigration) {
+        eprintln!(
+            "Applying {}_{} ... {}",
+            migration.version,
+            migration.description,
+            "failed".red()
+        );
+    }
+}
+
+/// 適用に失敗したときの、適用済み・未適用のマイグレーションの一覧
+fn format_failure_summary(migrations: &[MigrationResult]) -> String {
+    let count = |status| migrations.iter().filter(|m| m.status == status).count();
+    let (applied, rolled_back, pending) = (
+        count(MigrationApplyStatus::Applied),
+        count(MigrationApplyStatus::RolledBack),
+        count(MigrationApplyStatus::Pending),
+    );
+
+    let mut summary = String::from("=== Migration Apply Failed ===\n");
+    if rolled_back > 0 {
+        summary.push_str(&format!(
+            "{} migration(s) rolled back with the failed migration (--single-transaction), {} remaining:\n\n",
+            rolled_back, pending
+        ));
+    } else {
+        summary.push_str(&format!(
+            "{} migration(s) applied, {} remaining:\n\n",
+            applied, pending
+        ));
+    }
+    for migration in migrations {
+        let line = match migration.status {
+            MigrationApplyStatus::Applied => format!(
+                "✓ {} - {} ({}ms)",
+                migration.version, migration.description, migration.duration_ms
+            ),
+            MigrationApplyStatus::RolledBack => format!(
+                "↺ {} - {} (rolled back)",
+                migration.version, migration.description
+            ),
+            MigrationApplyStatus::Failed => format!(
+                "✗ {} - {} (failed)",
+                migration.version, migration.description
+            ),
+            MigrationApplyStatus::Pending => format!(
+                "  {} - {} (not applied)",
+                migration.version, migration.description
+            ),
+        };
+        summary.push_str(&line);
+        summary.push('\n');
+    }
+    summary
+}
+
+/// 確認プロンプトの前に表示する、破壊的変更のマイグレーションごとの一覧
+fn format_destructive_summary(migrations: &[DestructiveMigration], env: &str) -> String {
+    let mut output = format!(
+        "{}\n\n",
+        format!(
+            "The following migration(s) contain destructive changes for environment '{}':",
+            env
+        )
+        .red()
+        .bold()
+    );
+    for migration in migrations {
+        output.push_str(&format!(
+            "\u{25b6} {} - {}\n",
+            migration.version, migration.description
+        ));
+        for line in &migration.changes {
+            output.push_str(&format!("  {}\n", line.yellow()));
+        }
+        if !migration.statements.is_empty() {
+            output.push_str("  Statements:\n");
+            for statement in &migration.statements {
+                output.push_str(&format!("    {}\n", statement));
             }
         }
+        output.push('\n');
+    }
+    output
+}
+
+/// --restore-sequence-values で指定された値ファイルを読み込む
+fn load_sequence_values(path: &Path) -> Result<(PathBuf, SequenceValuesFile)> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read sequence values file: {:?}", path))?;
+    let file = SequenceValuesFile::from_yaml(&content)
+        .with_context(|| format!("Invalid sequence values file: {:?}", path))?;
+    Ok((path.to_path_buf(), file))
+}
 
-        warnings
+/// 復元結果を整形
+fn format_sequence_restore(result: &SequenceRestoreResult, dry_run: bool) -> String {
+    let mut output = String::new();
+    if dry_run {
+        output.push_str(&format!(
+            "\nSequence values to restore from {}: {}\n",
+            result.file,
+            result.restored.len()
+        ));
+        for sql in &result.statements {
+            output.push_str(&format!("  {};\n", sql));
+        }
+    } else {
+        output.push_str(&format!(
+            "\nRestored sequence values from {}: {}\n",
+            result.file,
+            result.restored.len()
+        ));
+        for sequence in &result.restored {
+            output.push_str(&format!(
+                "  - {} = {}\n",
+                sequence.display_name(),
+                sequence.value
+            ));
+        }
+    }
+    for skipped in &result.skipped {
+        output.push_str(&format!(
+            "{}\n",
+            format!(
+                "Skipped sequence value for {}: {}",
+                skipped.table, skipped.reason
+            )
+            .yellow()
+        ));
     }
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::config::Dialect;
-    use chrono::Duration;
-    use sqlx::any::install_default_drivers;
-    use sqlx::any::AnyPoolOptions;
-    use sqlx::Row;
-    use tempfile::TempDir;
+    use crate::core::policy_report::{PolicyReport, PolicyRule, PolicyViolation};
 
     #[test]
     fn test_new_handler() {
@@ -522,27 +1121,111 @@ mod tests {
         let handler = ApplyCommandHandler::new();
 
         let applied = vec![
-            AppliedMigration::new(
-                "20260121120000".to_string(),
-                "create_users".to_string(),
-                Utc::now(),
-                Duration::milliseconds(100),
-            ),
-            AppliedMigration::new(
-                "20260121120001".to_string(),
-                "create_posts".to_string(),
-                Utc::now(),
-                Duration::milliseconds(200),
-            ),
+            MigrationResult {
+                version: "20260121120000".to_string(),
+                description: "create_users".to_string(),
+                status: MigrationApplyStatus::Applied,
+                duration_ms: 100,
+                sql: None,
+                statement_checksums: None,
+                retries: Some(0),
+            },
+            MigrationResult {
+                version: "20260121120001".to_string(),
+                description: "create_posts".to_string(),
+                status: MigrationApplyStatus::Applied,
+                duration_ms: 200,
+                sql: None,
+                statement_checksums: None,
+                retries: Some(2),
+            },
         ];
 
         let summary = handler.generate_summary(&applied);
         assert!(summary.contains("2 migration(s) applied"));
-        assert!(summary.contains("20260121120000"));
-        assert!(summary.contains("20260121120001"));
+        assert!(summary.contains("✓ 20260121120000 - create_users (100ms)\n"));
+        assert!(summary.contains(
+            "✓ 20260121120001 - create_posts (200ms, 2 retry(ies) after transient errors)"
+        ));
         assert!(summary.contains("300ms")); // 100 + 200
     }
 
+    fn metadata_with_policy(policy: Option<PolicyReport>) -> MigrationMetadata {
+        MigrationMetadata {
+            version: "20260121120000".to_string(),
+            description: "large_change".to_string(),
+            dialect: Dialect::SQLite,
+            checksum: "abc123".to_string(),
+            up_sql_checksum: None,
+            statement_terminator: None,
+            destructive_changes: Default::default(),
+            policy,
+            rollback_protected_tables: Vec::new(),
+            touched_tables: None,
+            metadata: std::collections::BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_recheck_policy_without_recorded_report() {
+        let handler = ApplyCommandHandler::new();
+        let metadata = metadata_with_policy(None);
+
+        let policy = PolicyConfig {
+            max_statements: Some(1),
+            ..Default::default()
+        };
+        assert!(handler
+            .recheck_policy("20260121120000", &metadata, &policy)
+            .is_none());
+    }
+
+    #[test]
+    fn test_recheck_policy_warns_when_overridden() {
+        let handler = ApplyCommandHandler::new();
+        let metadata = metadata_with_policy(Some(PolicyReport {
+            statement_count: 60,
+            violations: vec![PolicyViolation {
+                rule: PolicyRule::MaxStatements,
+                message: "Migration contains 60 statements".to_string(),
+            }],
+            overridden: true,
+            ..Default::default()
+        }));
+
+        let warning = handler
+            .recheck_policy("20260121120000", &metadata, &PolicyConfig::default())
+            .expect("overridden migration should produce a warning");
+        assert!(warning.contains("--override-policy"));
+        assert!(warning.contains("60 statements"));
+    }
+
+    #[test]
+    fn test_recheck_policy_against_current_policy() {
+        let handler = ApplyCommandHandler::new();
+        let metadata = metadata_with_policy(Some(PolicyReport {
+            statement_count: 20,
+            ..Default::default()
+        }));
+
+        let relaxed = PolicyConfig {
+            max_statements: Some(50),
+            ..Default::default()
+        };
+        assert!(handler
+            .recheck_policy("20260121120000", &metadata, &relaxed)
+            .is_none());
+
+        let strict = PolicyConfig {
+            max_statements: Some(10),
+            ..Default::default()
+        };
+        let warning = handler
+            .recheck_policy("20260121120000", &metadata, &strict)
+            .expect("stricter policy should produce a warning");
+        assert!(warning.contains("violates the current migration policy"));
+    }
+
     #[test]
     fn test_highlight_destructive_sql_marks_drop() {
         use colored::control;
@@ -558,47 +1241,6 @@ mod tests {
         assert!(rendered.contains("DROP TABLE users;"));
     }
 
-    #[tokio::test]
-    async fn test_apply_migration_failure_does_not_record() {
-        install_default_drivers();
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let connection_string = format!("sqlite://{}?mode=rwc", db_path.to_str().unwrap());
-        let pool = AnyPoolOptions::new()
-            .max_connections(1)
-            .connect(&connection_string)
-            .await
-            .unwrap();
-
-        let migrator = DatabaseMigratorService::new();
-        migrator
-            .create_migration_table(&pool, Dialect::SQLite)
-            .await
-            .unwrap();
-
-        let handler = ApplyCommandHandler::new();
-        let result = handler
-            .apply_migration_with_transaction(
-                &pool,
-                &migrator,
-                "20260122120000",
-                "invalid_sql",
-                "INVALID SQL",
-                "checksum",
-                Dialect::SQLite,
-            )
-            .await;
-
-        assert!(result.is_err());
-
-        let row = sqlx::query("SELECT COUNT(*) FROM schema_migrations")
-            .fetch_one(&pool)
-            .await
-            .unwrap();
-        let count: i64 = row.get(0);
-        assert_eq!(count, 0);
-    }
-
     #[test]
     fn test_apply_output_json_serialization() {
         let output = ApplyOutput {
@@ -608,18 +1250,25 @@ mod tests {
                 MigrationResult {
                     version: "20260121120000".to_string(),
                     description: "create_users".to_string(),
+                    status: MigrationApplyStatus::Applied,
                     duration_ms: 100,
                     sql: None,
+                    statement_checksums: None,
+                    retries: None,
                 },
                 MigrationResult {
                     version: "20260121120001".to_string(),
                     description: "create_posts".to_string(),
+                    status: MigrationApplyStatus::Pending,
                     duration_ms: 200,
                     sql: Some("CREATE TABLE posts ...".to_string()),
+                    statement_checksums: None,
+                    retries: None,
                 },
             ],
             total_duration_ms: 300,
             warnings: vec!["checksum warning".to_string()],
+            sequence_values: None,
             message: "should not appear in JSON".to_string(),
         };
 
@@ -633,10 +1282,49 @@ mod tests {
         assert_eq!(parsed["applied_count"], 2);
         assert_eq!(parsed["total_duration_ms"], 300);
         assert_eq!(parsed["migrations"][0]["version"], "20260121120000");
+        assert_eq!(parsed["migrations"][0]["status"], "applied");
+        assert_eq!(parsed["migrations"][1]["status"], "pending");
         // sql が None のエントリは sql フィールドが含まれない
         assert!(parsed["migrations"][0].get("sql").is_none());
         // sql が Some のエントリは sql フィールドが含まれる
         assert_eq!(parsed["migrations"][1]["sql"], "CREATE TABLE posts ...");
         assert_eq!(parsed["warnings"][0], "checksum warning");
     }
+
+    fn failure_result(version: &str, status: MigrationApplyStatus) -> MigrationResult {
+        MigrationResult {
+            version: version.to_string(),
+            description: "create_users".to_string(),
+            status,
+            duration_ms: 12,
+            sql: None,
+            statement_checksums: None,
+            retries: None,
+        }
+    }
+
+    #[test]
+    fn test_format_failure_summary_lists_applied_and_remaining() {
+        let summary = format_failure_summary(&[
+            failure_result("20260121120000", MigrationApplyStatus::Applied),
+            failure_result("20260121120001", MigrationApplyStatus::Failed),
+            failure_result("20260121120002", MigrationApplyStatus::Pending),
+        ]);
+
+        assert!(summary.contains("1 migration(s) applied, 1 remaining"));
+        assert!(summary.contains("✓ 20260121120000 - create_users (12ms)"));
+        assert!(summary.contains("✗ 20260121120001 - create_users (failed)"));
+        assert!(summary.contains("  20260121120002 - create_users (not applied)"));
+    }
+
+    #[test]
+    fn test_format_failure_summary_single_transaction() {
+        let summary = format_failure_summary(&[
+            failure_result("20260121120000", MigrationApplyStatus::RolledBack),
+            failure_result("20260121120001", MigrationApplyStatus::Failed),
+        ]);
+
+        assert!(summary.contains("1 migration(s) rolled back with the failed migration"));
+        assert!(summary.contains("↺ 20260121120000 - create_users (rolled back)"));
+    }
 }
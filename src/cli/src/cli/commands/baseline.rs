@@ -0,0 +1,541 @@
+// baselineコマンドハンドラー
+//
+// 既存のデータベースにStrataを導入するための初期マイグレーションを作成します。
+// - 対象環境のデータベースからスキーマを取得し、スキーマ定義ファイルとスナップショットを書き出す
+// - 取得したスキーマを作成するSQLを初期マイグレーションとして書き出す
+// - 初期マイグレーションはSQLを実行せず、適用済みとして履歴テーブルに記録する
+
+use crate::adapters::database_introspector::create_introspector_for_config;
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::export::{
+    ExportCommandHandler, SPLIT_ENUMS_FILE_STEM, SPLIT_EXTERNAL_TABLES_FILE_STEM,
+    SPLIT_VIEWS_FILE_STEM,
+};
+use crate::cli::commands::sql_output::{
+    calculate_sql_file_content_checksum, render_sql_file, write_sql_file,
+};
+use crate::cli::commands::{migration_loader, migration_writer};
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::migration::Migration;
+use crate::core::schema::Schema;
+use crate::services::migration_generator::MigrationGeneratorService;
+use crate::services::schema_checksum::SchemaChecksumService;
+use crate::services::schema_diff_detector::{SchemaDiffDetectorService, SchemaDiffOptions};
+use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// 初期マイグレーションの説明
+const BASELINE_DESCRIPTION: &str = "baseline";
+
+/// baselineコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct BaselineOutput {
+    /// Dry runモードかどうか
+    pub dry_run: bool,
+    /// 対象環境
+    pub environment: String,
+    /// 初期マイグレーション名
+    pub migration_name: String,
+    /// 初期マイグレーションのパス（dry-runでは None）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migration_path: Option<String>,
+    /// 取り込んだテーブル一覧
+    pub tables: Vec<String>,
+    /// 取り込んだビュー一覧
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<String>,
+    /// 警告メッセージ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for BaselineOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// baselineコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct BaselineCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 対象環境
+    pub env: String,
+    /// ドライラン（ファイルの書き出しと履歴の記録を行わない）
+    pub dry_run: bool,
+    /// 既存のスナップショット・スキーマ定義ファイルを上書きする
+    pub force: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// 初期マイグレーションの内容
+struct BaselineMigration {
+    name: String,
+    version: String,
+    up_sql: String,
+    down_sql: String,
+    checksum: String,
+    metadata: String,
+}
+
+/// baselineコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct BaselineCommandHandler {}
+
+impl BaselineCommandHandler {
+    /// 新しいBaselineCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// baselineコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - baselineコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は取り込み結果のサマリー、失敗時はエラーメッセージ
+    pub async fn execute(&self, command: &BaselineCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &BaselineCommand,
+    ) -> Result<String> {
+        let config = &context.config;
+        let migrations_dir = context.migrations_dir();
+        let schema_dir = context.schema_dir();
+
+        // データベースに触れる前に、ローカルの状態で判定できる条件を確認する
+        self.check_migrations_dir(&migrations_dir, command.force)?;
+        let existing_schema_files = self.existing_schema_files(&schema_dir)?;
+        if !existing_schema_files.is_empty() && !command.force {
+            return Err(anyhow!(
+                "Schema directory already contains schema files:\n{}\nUse --force to overwrite them with the schema read from the database.",
+                format_file_list(&existing_schema_files)
+            ));
+        }
+
+        // 既にStrataで管理されているデータベースには実行しない
+        let (pool, applied_migrations) = context.connect_and_load_migrations(&command.env).await?;
+        if !applied_migrations.is_empty() {
+            return Err(anyhow!(
+                "The '{}' database already has {} applied migration(s) in schema_migrations. baseline only adopts databases that are not managed by Strata yet.",
+                command.env,
+                applied_migrations.len()
+            ));
+        }
+
+        let schema = self.read_database_schema(context, &pool).await?;
+        if schema.table_count() == 0 && schema.view_count() == 0 && schema.enums.is_empty() {
+            return Err(anyhow!(
+                "The '{}' database has no tables, views or ENUM types to baseline. Use `strata generate` and `strata apply` for a new database.",
+                command.env
+            ));
+        }
+
+        let baseline = self.build_migration(context, &schema)?;
+
+        let mut table_names: Vec<String> = schema.tables.keys().cloned().collect();
+        table_names.sort();
+        let mut view_names: Vec<String> = schema.views.keys().cloned().collect();
+        view_names.sort();
+
+        if command.dry_run {
+            let text_message =
+                self.format_summary(command, &baseline.name, &table_names, &view_names, &[]);
+            let output = BaselineOutput {
+                dry_run: true,
+                environment: command.env.clone(),
+                migration_name: baseline.name,
+                migration_path: None,
+                tables: table_names,
+                views: view_names,
+                warnings: Vec::new(),
+                text_message,
+            };
+            return render_output(&output, &command.format);
+        }
+
+        // スキーマ定義ファイル（テーブルごと）を書き出す
+        fs::create_dir_all(&schema_dir)
+            .with_context(|| format!("Failed to create schema directory: {:?}", schema_dir))?;
+        let serializer = SchemaSerializerService::new();
+        ExportCommandHandler::new()
+            .write_split_files(&schema, &serializer, &schema_dir, true)
+            .with_context(|| "Failed to write schema files")?;
+
+        // 上書きしなかった既存ファイルも読み込み時に合成されるため、警告する
+        let warnings: Vec<String> = existing_schema_files
+            .iter()
+            .filter(|path| !self.was_written(&schema, path))
+            .map(|path| {
+                format!(
+                    "{:?} was not written by baseline and is still loaded with the schema; remove it if it defines the same objects",
+                    path
+                )
+            })
+            .collect();
+
+        let migration_dir = self.write_migration(&migrations_dir, config, &baseline, &schema)?;
+
+        // 初期マイグレーションを実行せずに適用済みとして記録する
+        // 記録に失敗した場合は、再実行できるよう作成したマイグレーションを削除する
+        if let Err(e) = self.record_as_applied(&pool, context, &baseline).await {
+            let _ = fs::remove_dir_all(&migration_dir);
+            return Err(e);
+        }
+
+        let text_message = self.format_summary(
+            command,
+            &baseline.name,
+            &table_names,
+            &view_names,
+            &warnings,
+        );
+        let output = BaselineOutput {
+            dry_run: false,
+            environment: command.env.clone(),
+            migration_name: baseline.name,
+            migration_path: Some(migration_dir.to_string_lossy().to_string()),
+            tables: table_names,
+            views: view_names,
+            warnings,
+            text_message,
+        };
+        render_output(&output, &command.format)
+    }
+
+    /// マイグレーションディレクトリが初期マイグレーションを置ける状態か確認
+    ///
+    /// 既存のマイグレーションがあると、初期マイグレーションの後に未適用として実行されてしまうため拒否します。
+    /// スナップショットだけが残っている場合は --force で上書きできます。
+    fn check_migrations_dir(&self, migrations_dir: &Path, force: bool) -> Result<()> {
+        if !migrations_dir.exists() {
+            return Ok(());
+        }
+
+        let migrations = migration_loader::load_available_migrations(migrations_dir)?;
+        if !migrations.is_empty() {
+            let list: Vec<String> = migrations
+                .iter()
+                .map(|(version, description, _)| format!("  - {} {}", version, description))
+                .collect();
+            return Err(anyhow!(
+                "Migrations directory already contains {} migration(s):\n{}\nbaseline must create the first migration. Remove them, or use `strata apply` if they describe this database.",
+                migrations.len(),
+                list.join("\n")
+            ));
+        }
+
+        let snapshot_path = migrations_dir.join(SNAPSHOT_FILE);
+        if snapshot_path.exists() && !force {
+            return Err(anyhow!(
+                "Schema snapshot already exists: {:?}\nUse --force to overwrite.",
+                snapshot_path
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// スキーマディレクトリ直下のYAMLファイル一覧
+    fn existing_schema_files(&self, schema_dir: &Path) -> Result<Vec<PathBuf>> {
+        if !schema_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(schema_dir)
+            .with_context(|| format!("Failed to read schema directory: {:?}", schema_dir))?
+        {
+            let path = entry?.path();
+            let is_yaml = path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml");
+            if is_yaml && path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// 既存ファイルが今回の書き出しで上書きされたか（`<テーブル名>.yaml` など）
+    fn was_written(&self, schema: &Schema, path: &Path) -> bool {
+        let is_yaml = path.extension().is_some_and(|ext| ext == "yaml");
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            return false;
+        };
+        is_yaml
+            && (schema.tables.contains_key(stem)
+                || (stem == SPLIT_VIEWS_FILE_STEM && !schema.views.is_empty())
+                || (stem == SPLIT_ENUMS_FILE_STEM && !schema.enums.is_empty())
+                || (stem == SPLIT_EXTERNAL_TABLES_FILE_STEM && !schema.external_tables.is_empty()))
+    }
+
+    /// データベースからスキーマを取得
+    ///
+    /// export と同じく `external_tables` として宣言されたテーブルは取り込みません。
+    /// 権限不足で読み取れないオブジェクトがある場合、初期マイグレーションが不完全になるため中止します。
+    async fn read_database_schema(
+        &self,
+        context: &CommandContext,
+        pool: &AnyPool,
+    ) -> Result<Schema> {
+        let exporter = ExportCommandHandler::new();
+        debug!(dialect = ?context.dialect(), "Extracting schema from database");
+        let introspector = create_introspector_for_config(&context.config);
+        let (mut schema, skipped) = exporter
+            .extract_schema_from_database(introspector.as_ref(), pool, context.dialect())
+            .await
+            .with_context(|| "Failed to get schema information")?;
+
+        if !skipped.is_empty() {
+            let names: Vec<String> = skipped
+                .tables
+                .iter()
+                .chain(&skipped.views)
+                .chain(&skipped.enums)
+                .map(|object| format!("  - {}: {}", object.name, object.reason))
+                .collect();
+            return Err(anyhow!(
+                "{} object(s) could not be read because of insufficient privileges:\n{}\nbaseline needs the complete schema. Run it with a user that can read every object.",
+                skipped.len(),
+                names.join("\n")
+            ));
+        }
+
+        exporter.exclude_external_tables(&mut schema, &exporter.load_external_tables(context));
+        Ok(schema)
+    }
+
+    /// 空のスキーマから取得したスキーマを作成する初期マイグレーションを組み立てる
+    fn build_migration(
+        &self,
+        context: &CommandContext,
+        schema: &Schema,
+    ) -> Result<BaselineMigration> {
+        let config = &context.config;
+        let empty = Schema::new(schema.version.clone());
+        let (diff, _) = SchemaDiffDetectorService::new()
+            .with_options(SchemaDiffOptions::from_config(config))
+            .detect_diff_with_warnings(&empty, schema);
+
+        let generator = MigrationGeneratorService::new();
+        let (up_sql, _) = generator
+            .generate_up_sql_with_schemas(&diff, &empty, schema, config.dialect, false)
+            .with_context(|| "Failed to generate UP SQL")?;
+        let (down_sql, _) = generator
+            .generate_down_sql_with_schemas(&diff, &empty, schema, config.dialect, false)
+            .with_context(|| "Failed to generate DOWN SQL")?;
+
+        let version = generator.generate_timestamp();
+        let description = generator.sanitize_description(BASELINE_DESCRIPTION);
+        let name = generator.generate_migration_filename(&version, &description);
+        let checksum = SchemaChecksumService::new().calculate_checksum(schema);
+        let up_sql_checksum = calculate_sql_file_content_checksum(
+            &render_sql_file(&up_sql, &config.sql_output),
+            &config.sql_output,
+        );
+        let metadata = generator.generate_migration_metadata(
+            &version,
+            &description,
+            config.dialect,
+            &checksum,
+            Some(up_sql_checksum),
+            Some(config.sql_output.statement_terminator.clone()),
+            DestructiveChangeReport::new(),
+            None,
+            Vec::new(),
+            Some(diff.touched_tables()),
+            BTreeMap::new(),
+        )?;
+
+        Ok(BaselineMigration {
+            name,
+            version,
+            up_sql,
+            down_sql,
+            checksum,
+            metadata,
+        })
+    }
+
+    /// 初期マイグレーションとスナップショットを書き出す
+    fn write_migration(
+        &self,
+        migrations_dir: &Path,
+        config: &crate::core::config::Config,
+        baseline: &BaselineMigration,
+        schema: &Schema,
+    ) -> Result<PathBuf> {
+        let snapshot = schema_snapshot::render_snapshot(schema)?;
+
+        let migration_dir = migration_writer::write_migration_dir_atomically(
+            migrations_dir,
+            &baseline.name,
+            |dir| {
+                write_sql_file(&dir.join("up.sql"), &baseline.up_sql, &config.sql_output)?;
+                write_sql_file(
+                    &dir.join("down.sql"),
+                    &baseline.down_sql,
+                    &config.sql_output,
+                )?;
+
+                let meta_path = dir.join(".meta.yaml");
+                fs::write(&meta_path, &baseline.metadata)
+                    .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
+
+                // per-migration スナップショット
+                schema_snapshot::write_snapshot_content(&dir.join(SNAPSHOT_FILE), &snapshot)
+            },
+        )?;
+
+        // 後方互換のグローバルスナップショット
+        schema_snapshot::write_snapshot_content(&migrations_dir.join(SNAPSHOT_FILE), &snapshot)?;
+
+        Ok(migration_dir)
+    }
+
+    /// 初期マイグレーションを適用済みとして履歴テーブルに記録
+    ///
+    /// `store_applied_sql: true` の場合は、apply と同じく up.sql / down.sql も保存します。
+    async fn record_as_applied(
+        &self,
+        pool: &AnyPool,
+        context: &CommandContext,
+        baseline: &BaselineMigration,
+    ) -> Result<()> {
+        let config = &context.config;
+        let migrator = DatabaseMigratorService::new();
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        let migration = Migration::new(
+            baseline.version.clone(),
+            BASELINE_DESCRIPTION.to_string(),
+            baseline.checksum.clone(),
+        );
+        let (record_sql, params) =
+            migrator.generate_record_migration_query(&migration, config.dialect);
+        let mut query = sqlx::query(&record_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        query
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "Failed to record the baseline migration")?;
+
+        if config.store_applied_sql {
+            let (store_sql, params) = migrator.generate_store_applied_sql_query(
+                config.dialect,
+                &baseline.version,
+                &baseline.up_sql,
+                Some(&baseline.down_sql),
+            );
+            let mut query = sqlx::query(&store_sql);
+            for param in params {
+                query = query.bind(param);
+            }
+            query
+                .execute(&mut *tx)
+                .await
+                .with_context(|| "Failed to store the baseline migration SQL")?;
+        }
+
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// 結果のサマリーをフォーマット
+    fn format_summary(
+        &self,
+        command: &BaselineCommand,
+        migration_name: &str,
+        table_names: &[String],
+        view_names: &[String],
+        warnings: &[String],
+    ) -> String {
+        let mut output = String::new();
+        if command.dry_run {
+            output.push_str("=== Dry Run: Baseline ===\n\n");
+        } else {
+            output.push_str("=== Baseline Complete ===\n\n");
+        }
+
+        output.push_str(&format!("Environment: {}\n", command.env));
+        output.push_str(&format!("Tables: {}\n", table_names.len()));
+        for table_name in table_names {
+            output.push_str(&format!("  - {}\n", table_name));
+        }
+        if !view_names.is_empty() {
+            output.push_str(&format!("Views: {}\n", view_names.len()));
+            for view_name in view_names {
+                output.push_str(&format!("  - {}\n", view_name));
+            }
+        }
+        output.push('\n');
+
+        if command.dry_run {
+            output.push_str(&format!(
+                "Would write schema files and migration {}, and record it as applied without executing it.\n",
+                migration_name
+            ));
+        } else {
+            output.push_str(&format!(
+                "Migration {} was recorded as applied without executing it.\n",
+                migration_name
+            ));
+        }
+
+        for warning in warnings {
+            output.push_str(&format!("Warning: {}\n", warning));
+        }
+
+        output
+    }
+}
+
+/// ファイル一覧を箇条書きにフォーマット
+fn format_file_list(files: &[PathBuf]) -> String {
+    files
+        .iter()
+        .map(|f| format!("  - {:?}", f))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
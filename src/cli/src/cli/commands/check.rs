@@ -5,7 +5,8 @@
 // - validate成功時にgenerate dry-run相当の処理を実行
 // - 結果の統合出力（Text/JSON）
 
-use crate::cli::command_context::CommandContext;
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::dry_run_sql::DryRunSqlMode;
 use crate::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
 use crate::cli::commands::validate::{
     ValidateCommand, ValidateCommandHandler, ValidationStatistics,
@@ -14,6 +15,7 @@ use crate::cli::commands::{render_output, CommandOutput};
 use crate::cli::OutputFormat;
 use anyhow::{anyhow, Result};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tracing::debug;
 
@@ -26,6 +28,8 @@ pub struct CheckCommand {
     pub config_path: Option<PathBuf>,
     /// スキーマディレクトリのパス（指定されない場合は設定ファイルから取得）
     pub schema_dir: Option<PathBuf>,
+    /// 複数ファイルでの重複定義を許可し、後のファイルの定義を採用する
+    pub allow_duplicate_override: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
 }
@@ -114,6 +118,12 @@ impl CheckCommandHandler {
             project_path: command.project_path.clone(),
             config_path: command.config_path.clone(),
             schema_dir: command.schema_dir.clone(),
+            allow_duplicate_override: command.allow_duplicate_override,
+            changed_since: None,
+            files: Vec::new(),
+            file: None,
+            suggest_fixes: false,
+            apply_fixes: false,
             format: OutputFormat::Text, // 内部実行はText固定（出力を自前で統合するため）
         };
 
@@ -137,6 +147,7 @@ impl CheckCommandHandler {
                     schema_files: vec![],
                     errors: vec![crate::cli::commands::validate::ValidationIssue {
                         message: build_err.to_string(),
+                        file: None,
                         table: None,
                         column: None,
                         suggestion: None,
@@ -168,11 +179,21 @@ impl CheckCommandHandler {
             project_path: command.project_path.clone(),
             config_path: command.config_path.clone(),
             schema_dir: command.schema_dir.clone(),
+            allow_duplicate_override: command.allow_duplicate_override,
             description: None,
             dry_run: true,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: true,
+            dry_run_sql: DryRunSqlMode::Report,
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let generate_result = generate_handler.execute(&generate_command);
@@ -282,13 +303,22 @@ impl CheckCommandHandler {
             command.config_path.clone(),
         )?;
         let config = &context.config;
-        let schema_dir = context.resolve_schema_dir(command.schema_dir.as_ref())?;
-
-        let parser = crate::services::schema_io::schema_parser::SchemaParserService::new();
-        let (schema, schema_files) = parser.parse_schema_directory_with_files(&schema_dir)?;
-
-        let validator = crate::services::schema_validator::SchemaValidatorService::new();
-        let validation_result = validator.validate_with_dialect(&schema, config.dialect);
+        let (schema, schema_files, sources) =
+            context.load_schema_with_sources(&SchemaLoadOptions {
+                schema_dir: command.schema_dir.clone(),
+                allow_duplicate_override: command.allow_duplicate_override,
+                ..Default::default()
+            })?;
+
+        let validator = crate::services::schema_validator::SchemaValidatorService::new()
+            .with_mysql_minimum_version(config.mysql.parsed_minimum_version())
+            .with_required_metadata(config.required_metadata.clone())
+            .with_naming(config.naming.clone())
+            .with_identifiers(config.identifiers.clone());
+        let mut validation_result = validator.validate_with_dialect(&schema, config.dialect);
+        sources
+            .relative_to(&command.project_path)
+            .attach_files(&mut validation_result);
 
         let file_names: Vec<String> = schema_files
             .iter()
@@ -308,6 +338,9 @@ impl CheckCommandHandler {
                 let location = validate_handler.get_error_location(error);
                 crate::cli::commands::validate::ValidationIssue {
                     message: format!("{}", error),
+                    file: location
+                        .and_then(|l| l.file())
+                        .map(|f| f.display().to_string()),
                     table: location.and_then(|l| l.table.clone()),
                     column: location.and_then(|l| l.column.clone()),
                     suggestion: validate_handler
@@ -324,6 +357,10 @@ impl CheckCommandHandler {
                 let loc = &warning.location;
                 crate::cli::commands::validate::ValidationIssue {
                     message: warning.message.clone(),
+                    file: loc
+                        .as_ref()
+                        .and_then(|l| l.file())
+                        .map(|f| f.display().to_string()),
                     table: loc.as_ref().and_then(|l| l.table.clone()),
                     column: loc.as_ref().and_then(|l| l.column.clone()),
                     suggestion: None,
@@ -362,11 +399,21 @@ impl CheckCommandHandler {
             project_path: command.project_path.clone(),
             config_path: command.config_path.clone(),
             schema_dir: command.schema_dir.clone(),
+            allow_duplicate_override: command.allow_duplicate_override,
             description: None,
             dry_run: true,
             allow_destructive: true,
+            override_policy: false,
             verbose: false,
             format: OutputFormat::Json,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: true,
+            dry_run_sql: DryRunSqlMode::Report,
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let json_output = generate_handler.execute(&generate_command)?;
@@ -461,6 +508,7 @@ mod tests {
             project_path: PathBuf::from("/test/path"),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             format: OutputFormat::Text,
         };
         assert_eq!(command.project_path, PathBuf::from("/test/path"));
@@ -550,12 +598,14 @@ mod tests {
                 schema_files: vec!["users.yaml".to_string()],
                 errors: vec![ValidationIssue {
                     message: "No primary key defined".to_string(),
+                    file: None,
                     table: Some("users".to_string()),
                     column: None,
                     suggestion: Some("Add a primary key constraint".to_string()),
                 }],
                 warnings: vec![ValidationIssue {
                     message: "Wide column detected".to_string(),
+                    file: None,
                     table: Some("users".to_string()),
                     column: Some("bio".to_string()),
                     suggestion: None,
@@ -0,0 +1,97 @@
+// 破壊的な操作の確認プロンプト
+//
+// 破壊的変更を含むマイグレーションを端末から適用する際に、環境名の入力による確認を求めます。
+// 端末入出力は `Confirm` トレイトで抽象化しており、テストでは `ScriptedConfirm` で回答を差し替えます。
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::Mutex;
+
+/// 確認プロンプトの入出力
+pub trait Confirm: std::fmt::Debug + Send + Sync {
+    /// 対話的に確認できるか
+    fn is_interactive(&self) -> bool;
+
+    /// 確認内容を表示し、プロンプトへの回答を1行受け取る
+    fn ask(&self, summary: &str, prompt: &str) -> Result<String>;
+}
+
+/// 標準入力から回答を読み込み、確認内容を標準エラー出力に表示するConfirm
+///
+/// 標準入力が端末の場合のみ対話的とみなします。
+#[derive(Debug, Default)]
+pub struct TerminalConfirm;
+
+impl TerminalConfirm {
+    /// 新しいTerminalConfirmを作成
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Confirm for TerminalConfirm {
+    fn is_interactive(&self) -> bool {
+        io::stdin().is_terminal()
+    }
+
+    fn ask(&self, summary: &str, prompt: &str) -> Result<String> {
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{}\n{} ", summary, prompt);
+        let _ = stderr.flush();
+
+        let mut line = String::new();
+        let read = io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .with_context(|| "Failed to read from stdin")?;
+        if read == 0 {
+            return Err(anyhow!("Input ended before the confirmation was answered."));
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+/// あらかじめ用意した回答を順に返すConfirm（常に対話的として扱う）
+#[derive(Debug, Default)]
+pub struct ScriptedConfirm {
+    answers: Mutex<VecDeque<String>>,
+    shown: Mutex<Vec<String>>,
+}
+
+impl ScriptedConfirm {
+    /// 回答を指定してScriptedConfirmを作成
+    pub fn new<I, S>(answers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            answers: Mutex::new(answers.into_iter().map(Into::into).collect()),
+            shown: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// これまでに表示された確認内容（表示順）
+    pub fn shown(&self) -> Vec<String> {
+        self.shown.lock().expect("confirm lock poisoned").clone()
+    }
+}
+
+impl Confirm for ScriptedConfirm {
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
+    fn ask(&self, summary: &str, prompt: &str) -> Result<String> {
+        self.shown
+            .lock()
+            .expect("confirm lock poisoned")
+            .push(summary.to_string());
+        self.answers
+            .lock()
+            .expect("confirm lock poisoned")
+            .pop_front()
+            .ok_or_else(|| anyhow!("No scripted answer for prompt: {}", prompt))
+    }
+}
@@ -0,0 +1,466 @@
+// contractコマンドハンドラー
+//
+// expand/contract による移行の最後の段階として、削除待ち（`lifecycle: contract_pending`）の
+// カラムを一覧表示し、選択したカラムを削除するマイグレーションを生成します。
+// - 削除待ちのカラムと削除待ちになった日付は、generate がスキーマスナップショットに記録したものを使う
+// - 経過日数が足りないカラムと、スキーマ定義にまだ残っているカラムは削除できない
+
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::dry_run_sql::DryRunSqlMode;
+use crate::cli::commands::generate::{GenerateCommand, GenerateCommandHandler, GenerateSchemas};
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::schema::Schema;
+use crate::services::column_lifecycle::{self, ContractPendingColumn};
+use crate::services::identifier_case;
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+/// contractコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct ContractCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 削除できるようになるまでの日数（Noneの場合は設定 `contract_min_age_days` に従う）
+    pub older_than: Option<u64>,
+    /// 削除するカラム（`table.column` 形式）
+    pub columns: Vec<String>,
+    /// 削除できるカラムをすべて削除する
+    pub all: bool,
+    /// マイグレーションの説明（オプション）
+    pub description: Option<String>,
+    /// ドライラン（SQLを表示するがファイルは作成しない）
+    pub dry_run: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// 削除待ちのカラムの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractStatus {
+    /// 削除できる
+    Ready,
+    /// 削除待ちになってからの日数が足りない
+    TooRecent,
+    /// スキーマ定義にまだ残っている
+    StillDefined,
+    /// 削除待ちになった日付がスナップショットに記録されていない（generate 未実行）
+    NotRecorded,
+}
+
+impl ContractStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Ready => "ready",
+            Self::TooRecent => "too recent",
+            Self::StillDefined => "still in schema files",
+            Self::NotRecorded => "not recorded (run `strata generate`)",
+        }
+    }
+
+    /// 削除できない理由
+    fn reason(&self, entry: &ContractColumnEntry, min_age_days: u64) -> String {
+        match self {
+            Self::Ready => String::new(),
+            Self::TooRecent => format!(
+                "has been contract_pending for {} day(s), less than {}",
+                entry.age_days.unwrap_or_default(),
+                min_age_days
+            ),
+            Self::StillDefined => {
+                "is still defined in the schema files; remove it from the table definition first"
+                    .to_string()
+            }
+            Self::NotRecorded => {
+                "is not recorded as contract_pending in the schema snapshot yet; run `strata generate` first"
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// 削除待ちのカラムのエントリ
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractColumnEntry {
+    pub table: String,
+    pub column: String,
+    /// 削除待ちになった日付
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<NaiveDate>,
+    /// 削除待ちになってからの経過日数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<i64>,
+    pub status: ContractStatus,
+}
+
+impl ContractColumnEntry {
+    /// `table.column` 形式の名前
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", self.table, self.column)
+    }
+}
+
+/// contractコマンドの出力構造体（一覧表示）
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractOutput {
+    /// 削除できるようになるまでの日数
+    pub min_age_days: u64,
+    /// 削除待ちのカラム一覧
+    pub columns: Vec<ContractColumnEntry>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for ContractOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// contractコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct ContractCommandHandler {}
+
+impl ContractCommandHandler {
+    /// 新しいContractCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// contractコマンドを実行
+    ///
+    /// `--columns` / `--all` の指定がない場合は削除待ちのカラムを一覧表示する。
+    /// 指定がある場合は、選択したカラムを削除するマイグレーションを generate と同じ手順で生成する。
+    pub fn execute(&self, command: &ContractCommand) -> Result<String> {
+        let (context, declared_schema, sources, snapshot) =
+            CommandContext::load_with_schema_and_snapshot(
+                command.project_path.clone(),
+                command.config_path.clone(),
+                &SchemaLoadOptions::default(),
+            )?;
+        let config = &context.config;
+        let generator = GenerateCommandHandler::new();
+        let previous_schema = generator.merge_retained_into_previous(&context, snapshot)?;
+        let current_schema =
+            identifier_case::fold_schema_identifiers(&declared_schema, config.identifier_case);
+
+        let min_age_days = command
+            .older_than
+            .unwrap_or_else(|| config.contract_min_age_days());
+        let entries = self.collect_entries(
+            &previous_schema,
+            &current_schema,
+            min_age_days,
+            Utc::now().date_naive(),
+        );
+
+        if command.columns.is_empty() && !command.all {
+            let output = ContractOutput {
+                min_age_days,
+                text_message: self.format_list(&entries, min_age_days),
+                columns: entries,
+            };
+            return render_output(&output, &command.format);
+        }
+
+        let selected = self.select(command, &entries, min_age_days)?;
+        if selected.is_empty() {
+            let output = ContractOutput {
+                min_age_days,
+                text_message: format!(
+                    "No contract_pending columns are ready to drop (minimum age: {} days).",
+                    min_age_days
+                ),
+                columns: entries,
+            };
+            return render_output(&output, &command.format);
+        }
+
+        let contracted_schema = drop_columns(&previous_schema, &selected);
+        let generate_command = GenerateCommand {
+            project_path: command.project_path.clone(),
+            config_path: command.config_path.clone(),
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some(
+                command
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| default_description(&selected)),
+            ),
+            dry_run: command.dry_run,
+            // 削除するカラムを明示的に選択しているため、破壊的変更として拒否しない
+            allow_destructive: true,
+            override_policy: false,
+            verbose: false,
+            format: command.format.clone(),
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: DryRunSqlMode::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        };
+        generator.execute_with_schemas(
+            &generate_command,
+            &context,
+            GenerateSchemas {
+                declared: &declared_schema,
+                sources: &sources,
+                current: &contracted_schema,
+                previous: &previous_schema,
+            },
+            Vec::new(),
+        )
+    }
+
+    /// 削除待ちのカラムとその状態を求める
+    ///
+    /// スナップショットで削除待ちのカラムに加え、スキーマ定義で削除待ちにしたが
+    /// まだスナップショットに記録されていないカラムも含める。
+    fn collect_entries(
+        &self,
+        previous_schema: &Schema,
+        current_schema: &Schema,
+        min_age_days: u64,
+        today: NaiveDate,
+    ) -> Vec<ContractColumnEntry> {
+        let recorded = column_lifecycle::contract_pending_columns(previous_schema);
+        let recorded_names: HashSet<String> = recorded.iter().map(|c| c.qualified_name()).collect();
+        let unrecorded = column_lifecycle::contract_pending_columns(current_schema)
+            .into_iter()
+            .filter(|c| !recorded_names.contains(&c.qualified_name()))
+            .map(|c| ContractPendingColumn { since: None, ..c });
+
+        let mut entries: Vec<ContractColumnEntry> = recorded
+            .into_iter()
+            .chain(unrecorded)
+            .map(|pending| {
+                let still_defined = current_schema
+                    .get_table(&pending.table)
+                    .and_then(|table| table.get_column(&pending.column))
+                    .is_some();
+                let status = if pending.since.is_none() {
+                    ContractStatus::NotRecorded
+                } else if !pending.is_older_than(min_age_days, today) {
+                    ContractStatus::TooRecent
+                } else if still_defined {
+                    ContractStatus::StillDefined
+                } else {
+                    ContractStatus::Ready
+                };
+                ContractColumnEntry {
+                    age_days: pending.age_days(today),
+                    since: pending.since,
+                    table: pending.table,
+                    column: pending.column,
+                    status,
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.table, &a.column).cmp(&(&b.table, &b.column)));
+        entries
+    }
+
+    /// 削除するカラムを選択する
+    ///
+    /// `--all` の場合は削除できるカラムをすべて選択する。
+    /// `--columns` で指定したカラムが削除できない場合は、理由を一覧にしてエラーを返す。
+    fn select(
+        &self,
+        command: &ContractCommand,
+        entries: &[ContractColumnEntry],
+        min_age_days: u64,
+    ) -> Result<Vec<ContractColumnEntry>> {
+        if command.all {
+            return Ok(entries
+                .iter()
+                .filter(|e| e.status == ContractStatus::Ready)
+                .cloned()
+                .collect());
+        }
+
+        let mut selected = Vec::new();
+        let mut problems = Vec::new();
+        for name in &command.columns {
+            match entries.iter().find(|e| e.qualified_name() == *name) {
+                Some(entry) if entry.status == ContractStatus::Ready => {
+                    if !selected
+                        .iter()
+                        .any(|s: &ContractColumnEntry| s.qualified_name() == *name)
+                    {
+                        selected.push(entry.clone());
+                    }
+                }
+                Some(entry) => problems.push(format!(
+                    "  - {} {}",
+                    name,
+                    entry.status.reason(entry, min_age_days)
+                )),
+                None => problems.push(format!(
+                    "  - {} is not a contract_pending column (expected TABLE.COLUMN)",
+                    name
+                )),
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(anyhow!(
+                "Cannot drop the following columns:\n{}",
+                problems.join("\n")
+            ));
+        }
+        Ok(selected)
+    }
+
+    /// 一覧表示のテキストを整形
+    fn format_list(&self, entries: &[ContractColumnEntry], min_age_days: u64) -> String {
+        if entries.is_empty() {
+            return "No contract_pending columns found.".to_string();
+        }
+
+        let names: Vec<String> = entries.iter().map(|e| e.qualified_name()).collect();
+        let since: Vec<String> = entries
+            .iter()
+            .map(|e| match (e.since, e.age_days) {
+                (Some(since), Some(age)) => format!("since {} ({} days)", since, age),
+                _ => "-".to_string(),
+            })
+            .collect();
+        let name_width = names.iter().map(|n| n.len()).max().unwrap_or(0);
+        let since_width = since.iter().map(|s| s.len()).max().unwrap_or(0);
+
+        let mut message = format!(
+            "contract_pending columns (minimum age: {} days):\n",
+            min_age_days
+        );
+        for ((name, since), entry) in names.iter().zip(&since).zip(entries) {
+            message.push_str(&format!(
+                "  {:<name_width$}  {:<since_width$}  {}\n",
+                name,
+                since,
+                entry.status.label(),
+            ));
+        }
+        if entries.iter().any(|e| e.status == ContractStatus::Ready) {
+            message.push_str(
+                "\nGenerate the migration that drops them with `strata contract --columns <TABLE.COLUMN>` or `strata contract --all`.",
+            );
+        }
+        message.trim_end().to_string()
+    }
+}
+
+/// 選択したカラムと、それを参照するインデックス・制約を取り除いたスキーマ
+fn drop_columns(schema: &Schema, selected: &[ContractColumnEntry]) -> Schema {
+    let mut contracted = schema.clone();
+    for entry in selected {
+        let Some(table) = contracted.tables.get_mut(&entry.table) else {
+            continue;
+        };
+        table.columns.retain(|c| c.name != entry.column);
+        table
+            .indexes
+            .retain(|index| !index.columns.iter().any(|c| c.name == entry.column));
+        table
+            .constraints
+            .retain(|constraint| !constraint.columns().contains(&entry.column));
+    }
+    contracted
+}
+
+/// マイグレーションの説明のデフォルト
+fn default_description(selected: &[ContractColumnEntry]) -> String {
+    match selected {
+        [entry] => format!("contract {} {}", entry.table, entry.column),
+        _ => format!("contract {} columns", selected.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnLifecycle, ColumnType, Index, Table};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn schema(columns: &[(&str, Option<NaiveDate>)]) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        for (name, since) in columns {
+            let mut column = Column::new(name.to_string(), ColumnType::TEXT, true);
+            column.lifecycle = Some(ColumnLifecycle::ContractPending);
+            column.contract_pending_since = *since;
+            table.add_column(column);
+        }
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_collect_entries_statuses() {
+        let previous = schema(&[
+            ("old_email", Some(date(1))),
+            ("old_phone", Some(date(10))),
+            ("old_name", Some(date(1))),
+        ]);
+        let current = schema(&[("old_name", Some(date(1))), ("old_code", None)]);
+
+        let entries =
+            ContractCommandHandler::new().collect_entries(&previous, &current, 7, date(12));
+        let statuses: Vec<(String, ContractStatus)> = entries
+            .iter()
+            .map(|e| (e.column.clone(), e.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("old_code".to_string(), ContractStatus::NotRecorded),
+                ("old_email".to_string(), ContractStatus::Ready),
+                ("old_name".to_string(), ContractStatus::StillDefined),
+                ("old_phone".to_string(), ContractStatus::TooRecent),
+            ]
+        );
+        assert_eq!(entries[1].age_days, Some(11));
+    }
+
+    #[test]
+    fn test_drop_columns_removes_dependent_indexes() {
+        let mut previous = schema(&[("old_email", Some(date(1)))]);
+        previous
+            .tables
+            .get_mut("users")
+            .unwrap()
+            .add_index(Index::new(
+                "idx_users_old_email".to_string(),
+                vec!["old_email".to_string()],
+                false,
+            ));
+        let entries =
+            ContractCommandHandler::new().collect_entries(&previous, &schema(&[]), 7, date(12));
+
+        let contracted = drop_columns(&previous, &entries);
+
+        let table = &contracted.tables["users"];
+        assert!(table.get_column("old_email").is_none());
+        assert!(table.indexes.is_empty());
+        assert_eq!(default_description(&entries), "contract users old_email");
+    }
+}
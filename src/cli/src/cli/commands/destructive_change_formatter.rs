@@ -1,7 +1,11 @@
 use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::services::schema_io::schema_sources::SchemaSources;
 use colored::Colorize;
 
-pub struct DestructiveChangeFormatter;
+pub struct DestructiveChangeFormatter {
+    /// 変更対象の定義元（スキーマファイルに残っている要素に付記する）
+    sources: Option<SchemaSources>,
+}
 
 impl Default for DestructiveChangeFormatter {
     fn default() -> Self {
@@ -11,7 +15,13 @@ impl Default for DestructiveChangeFormatter {
 
 impl DestructiveChangeFormatter {
     pub fn new() -> Self {
-        Self
+        Self { sources: None }
+    }
+
+    /// 変更対象の定義元を付記するためのスキーマ要素の定義元を設定
+    pub fn with_sources(mut self, sources: SchemaSources) -> Self {
+        self.sources = Some(sources);
+        self
     }
 
     pub fn format_error(&self, report: &DestructiveChangeReport, command: &str) -> String {
@@ -19,7 +29,7 @@ impl DestructiveChangeFormatter {
 
         output.push_str(format!("{}\n\n", "Destructive changes detected".red().bold()).as_str());
 
-        for line in format_change_lines(report) {
+        for line in format_change_lines(report, self.sources.as_ref()) {
             output.push_str(line.red().to_string().as_str());
             output.push('\n');
         }
@@ -36,6 +46,11 @@ impl DestructiveChangeFormatter {
         output
     }
 
+    /// 破壊的変更の一覧（装飾なし、1行ずつ）
+    pub fn change_lines(&self, report: &DestructiveChangeReport) -> Vec<String> {
+        format_change_lines(report, self.sources.as_ref())
+    }
+
     pub fn format_warning(&self, report: &DestructiveChangeReport) -> String {
         let mut output = String::new();
 
@@ -47,7 +62,7 @@ impl DestructiveChangeFormatter {
             .as_str(),
         );
 
-        let summary_lines = format_change_lines(report);
+        let summary_lines = format_change_lines(report, self.sources.as_ref());
         if summary_lines.is_empty() {
             output.push_str("  No destructive changes were listed.\n");
             return output;
@@ -61,8 +76,20 @@ impl DestructiveChangeFormatter {
     }
 }
 
-fn format_change_lines(report: &DestructiveChangeReport) -> Vec<String> {
+/// 定義元が分かる場合は `名前 (schema/users.yaml:3)` の形式にする
+fn located(text: String, source: Option<String>) -> String {
+    match source {
+        Some(source) => format!("{} ({})", text, source),
+        None => text,
+    }
+}
+
+fn format_change_lines(
+    report: &DestructiveChangeReport,
+    sources: Option<&SchemaSources>,
+) -> Vec<String> {
     let mut lines = Vec::new();
+    let source = |describe: &dyn Fn(&SchemaSources) -> Option<String>| sources.and_then(describe);
 
     if !report.tables_dropped.is_empty() {
         lines.push(format!(
@@ -74,16 +101,22 @@ fn format_change_lines(report: &DestructiveChangeReport) -> Vec<String> {
     if !report.columns_dropped.is_empty() {
         lines.push("Columns to be dropped:".to_string());
         for entry in &report.columns_dropped {
-            lines.push(format!("  - {}: {}", entry.table, entry.columns.join(", ")));
+            lines.push(located(
+                format!("  - {}: {}", entry.table, entry.columns.join(", ")),
+                source(&|s| s.table_source(&entry.table)),
+            ));
         }
     }
 
     if !report.columns_renamed.is_empty() {
         lines.push("Columns to be renamed:".to_string());
         for entry in &report.columns_renamed {
-            lines.push(format!(
-                "  - {}: {} -> {}",
-                entry.table, entry.old_name, entry.new_name
+            lines.push(located(
+                format!(
+                    "  - {}: {} -> {}",
+                    entry.table, entry.old_name, entry.new_name
+                ),
+                source(&|s| s.column_source(&entry.table, &entry.new_name)),
             ));
         }
     }
@@ -96,10 +129,27 @@ fn format_change_lines(report: &DestructiveChangeReport) -> Vec<String> {
     }
 
     if !report.enums_recreated.is_empty() {
-        lines.push(format!(
-            "Enums to be recreated: {}",
-            report.enums_recreated.join(", ")
-        ));
+        let enums: Vec<String> = report
+            .enums_recreated
+            .iter()
+            .map(|name| located(name.clone(), source(&|s| s.enum_source(name))))
+            .collect();
+        lines.push(format!("Enums to be recreated: {}", enums.join(", ")));
+    }
+
+    if !report.enums_reordered.is_empty() {
+        lines.push("Enums to be recreated to reorder values:".to_string());
+        for entry in &report.enums_reordered {
+            lines.push(located(
+                format!(
+                    "  - {}: [{}] -> [{}]",
+                    entry.name,
+                    entry.old_values.join(", "),
+                    entry.new_values.join(", ")
+                ),
+                source(&|s| s.enum_source(&entry.name)),
+            ));
+        }
     }
 
     if !report.views_dropped.is_empty() {
@@ -110,12 +160,37 @@ fn format_change_lines(report: &DestructiveChangeReport) -> Vec<String> {
     }
 
     if !report.views_modified.is_empty() {
+        let views: Vec<String> = report
+            .views_modified
+            .iter()
+            .map(|name| located(name.clone(), source(&|s| s.view_source(name))))
+            .collect();
         lines.push(format!(
             "Views with definition changes: {}",
-            report.views_modified.join(", ")
+            views.join(", ")
         ));
     }
 
+    if !report.columns_narrowed.is_empty() {
+        lines.push("Columns with lossy type changes:".to_string());
+        for entry in &report.columns_narrowed {
+            lines.push(located(
+                format!(
+                    "  - {}.{}: {} -> {} ({})",
+                    entry.table, entry.column, entry.old_type, entry.new_type, entry.reason
+                ),
+                source(&|s| s.column_source(&entry.table, &entry.column)),
+            ));
+        }
+    }
+
+    if !report.edited_statements.is_empty() {
+        lines.push("Destructive statements added by editing the SQL:".to_string());
+        for statement in &report.edited_statements {
+            lines.push(format!("  - {}", statement));
+        }
+    }
+
     lines
 }
 
@@ -123,7 +198,7 @@ fn format_change_lines(report: &DestructiveChangeReport) -> Vec<String> {
 mod tests {
     use super::DestructiveChangeFormatter;
     use crate::core::destructive_change_report::{
-        DestructiveChangeReport, DroppedColumn, RenamedColumnInfo,
+        DestructiveChangeReport, DroppedColumn, NarrowedColumn, RenamedColumnInfo, ReorderedEnum,
     };
 
     fn sample_report() -> DestructiveChangeReport {
@@ -140,8 +215,21 @@ mod tests {
             }],
             enums_dropped: vec!["old_status".to_string()],
             enums_recreated: vec!["priority".to_string()],
+            enums_reordered: vec![ReorderedEnum {
+                name: "level".to_string(),
+                old_values: vec!["high".to_string(), "low".to_string()],
+                new_values: vec!["low".to_string(), "high".to_string()],
+            }],
             views_dropped: vec!["old_summary".to_string()],
             views_modified: vec!["active_users".to_string()],
+            columns_narrowed: vec![NarrowedColumn {
+                table: "products".to_string(),
+                column: "price".to_string(),
+                old_type: "DECIMAL(10, 2)".to_string(),
+                new_type: "DECIMAL(8, 2)".to_string(),
+                reason: "existing values may overflow".to_string(),
+            }],
+            edited_statements: vec!["DELETE FROM sessions".to_string()],
         }
     }
 
@@ -158,8 +246,15 @@ mod tests {
         assert!(output.contains("orders: old_status -> status"));
         assert!(output.contains("Enums to be dropped: old_status"));
         assert!(output.contains("Enums to be recreated: priority"));
+        assert!(output.contains("Enums to be recreated to reorder values:"));
+        assert!(output.contains("level: [high, low] -> [low, high]"));
         assert!(output.contains("Views to be dropped: old_summary"));
         assert!(output.contains("Views with definition changes: active_users"));
+        assert!(output.contains(
+            "products.price: DECIMAL(10, 2) -> DECIMAL(8, 2) (existing values may overflow)"
+        ));
+        assert!(output.contains("Destructive statements added by editing the SQL:"));
+        assert!(output.contains("  - DELETE FROM sessions"));
         assert!(output.contains("Review changes: strata generate --dry-run"));
         assert!(output.contains("Allow destructive changes: strata generate --allow-destructive"));
     }
@@ -174,4 +269,33 @@ mod tests {
         assert!(output.contains("Columns to be dropped:"));
         assert!(output.contains("products: legacy_field, unused"));
     }
+
+    #[test]
+    fn format_error_appends_sources_of_objects_still_defined() {
+        use crate::core::schema::{Schema, Table};
+        use crate::services::schema_io::schema_sources::SchemaSources;
+        use crate::services::schema_io::source_lines;
+        use std::path::Path;
+
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("products".to_string()));
+        let mut sources = SchemaSources::new();
+        sources.record(Path::new("schema/products.yaml"), &schema);
+        sources.record_lines(source_lines::scan(
+            "tables:\n  products:\n    columns:\n      - name: id\n      - name: price\n",
+        ));
+
+        let lines = DestructiveChangeFormatter::new()
+            .with_sources(sources)
+            .change_lines(&sample_report());
+
+        assert!(lines
+            .contains(&"  - products: legacy_field, unused (schema/products.yaml:2)".to_string()));
+        assert!(lines.contains(
+            &"  - products.price: DECIMAL(10, 2) -> DECIMAL(8, 2) (existing values may overflow) (schema/products.yaml:5)".to_string()
+        ));
+        // スキーマファイルにない要素には付記しない
+        assert!(lines.contains(&"Tables to be dropped: users".to_string()));
+        assert!(lines.contains(&"  - orders: old_status -> status".to_string()));
+    }
 }
@@ -0,0 +1,395 @@
+// devコマンドハンドラー
+//
+// ローカル開発のループ向けに generate と apply をまとめて実行します。
+// - protected_environments に含まれる環境での実行を拒否
+// - generate（dry-runなし、破壊的変更は許可しない）でマイグレーションを生成
+// - マイグレーションが生成された場合のみ apply を実行
+// - --watch ではスキーマディレクトリの変更を待ってこのサイクルを繰り返す
+
+pub mod watch;
+
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use crate::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+use crate::cli::commands::migration_loader;
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::services::migration_generator::MigrationGeneratorService;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+use watch::{SchemaFingerprint, WatchOptions};
+
+/// dev migrate の入力パラメータ
+#[derive(Debug, Clone)]
+pub struct DevMigrateCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 適用先の環境名
+    pub env: String,
+    /// マイグレーションの説明（オプション）
+    pub description: Option<String>,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// dev migrate の1サイクルの出力
+#[derive(Debug, Clone, Serialize)]
+pub struct DevMigrateOutput {
+    /// 適用先の環境名
+    pub environment: String,
+    /// このサイクルで生成されたマイグレーション
+    pub migrations: Vec<String>,
+    /// generate の出力（JSON出力時）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate: Option<serde_json::Value>,
+    /// apply の出力（JSON出力時、apply を実行した場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply: Option<serde_json::Value>,
+    /// apply のエラーメッセージ（失敗時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// generate のテキスト出力
+    #[serde(skip)]
+    pub generate_text: String,
+    /// apply のテキスト出力（apply を実行した場合のみ）
+    #[serde(skip)]
+    pub apply_text: Option<String>,
+}
+
+impl CommandOutput for DevMigrateOutput {
+    fn to_text(&self) -> String {
+        let mut text = String::from("=== generate ===\n");
+        text.push_str(self.generate_text.trim_end());
+
+        if let Some(ref apply_text) = self.apply_text {
+            text.push_str(&format!("\n\n=== apply ({}) ===\n", self.environment));
+            if !apply_text.is_empty() {
+                text.push_str(apply_text.trim_end());
+                text.push('\n');
+            }
+            if let Some(ref error) = self.error {
+                text.push_str(&format!("Error: {}\n", error));
+            }
+        }
+        text.trim_end().to_string()
+    }
+}
+
+/// watch 中に発生するイベント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// 監視を開始した（監視対象のスキーマディレクトリ）
+    Started(PathBuf),
+    /// サイクルが完了した（レンダリング済みの出力）
+    Completed(String),
+    /// サイクルが失敗した（レンダリング済みの出力とエラーメッセージ）
+    Failed { output: String, error: String },
+}
+
+/// devコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct DevCommandHandler {}
+
+impl DevCommandHandler {
+    /// 新しいDevCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// generate を実行し、マイグレーションが生成されたら apply する
+    ///
+    /// 破壊的変更を含む場合は generate が失敗するため、apply は実行されません。
+    /// apply が失敗した場合は、generate の出力を含む `CompletedWithFailure` を返します。
+    pub async fn execute_migrate(&self, command: &DevMigrateCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+        check_environment(&context.config.protected_environments, &command.env)?;
+
+        self.run_cycle(command, &context.migrations_dir())
+            .await
+            .map(|(rendered, _)| rendered)
+    }
+
+    /// スキーマディレクトリの変更を監視し、変更のたびに generate + apply を実行する
+    ///
+    /// 開始時に1回実行した後は、変更が落ち着くのを待ってから実行します。サイクルの失敗
+    /// （スキーマの編集途中のエラーなど）では停止せず、`on_event` に通知して監視を続けます。
+    /// `shutdown` が完了すると、実行中のサイクルを最後まで実行してから終了します。
+    pub async fn watch_migrate<S, F>(
+        &self,
+        command: &DevMigrateCommand,
+        options: &WatchOptions,
+        shutdown: S,
+        mut on_event: F,
+    ) -> Result<String>
+    where
+        S: Future<Output = ()>,
+        F: FnMut(WatchEvent),
+    {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+        check_environment(&context.config.protected_environments, &command.env)?;
+        let schema_dir = context.require_schema_dir()?;
+        let migrations_dir = context.migrations_dir();
+
+        tokio::pin!(shutdown);
+        let mut stopping = false;
+        let mut fingerprint = SchemaFingerprint::scan(&schema_dir)?;
+        let mut last_version: Option<String> = None;
+        let mut cycles = 0;
+        on_event(WatchEvent::Started(schema_dir.clone()));
+
+        loop {
+            if let Some(ref version) = last_version {
+                wait_for_next_version(version).await;
+            }
+
+            // サイクルの途中で停止要求を受けても、サイクルは中断せずに最後まで実行する
+            let cycle = self.run_cycle(command, &migrations_dir);
+            tokio::pin!(cycle);
+            let result = loop {
+                tokio::select! {
+                    result = &mut cycle => break result,
+                    _ = shutdown.as_mut(), if !stopping => stopping = true,
+                }
+            };
+            cycles += 1;
+
+            match result {
+                Ok((rendered, migrations)) => {
+                    if let Some(version) = migrations.iter().filter_map(|m| version_of(m)).max() {
+                        last_version = Some(version.to_string());
+                    }
+                    on_event(WatchEvent::Completed(rendered));
+                }
+                Err(e) => on_event(cycle_failure(e)),
+            }
+
+            if stopping {
+                break;
+            }
+            match watch::wait_for_change(&schema_dir, &fingerprint, options, shutdown.as_mut())
+                .await?
+            {
+                Some(changed) => {
+                    debug!(files = changed.file_count(), "Schema directory changed");
+                    fingerprint = changed;
+                }
+                None => break,
+            }
+        }
+
+        Ok(match command.format {
+            OutputFormat::Text => format!("Stopped watching after {} cycle(s)", cycles),
+            OutputFormat::Json => String::new(),
+        })
+    }
+
+    /// generate + apply を1回実行し、出力と生成されたマイグレーション名を返す
+    async fn run_cycle(
+        &self,
+        command: &DevMigrateCommand,
+        migrations_dir: &Path,
+    ) -> Result<(String, Vec<String>)> {
+        let before = migration_names(migrations_dir)?;
+        let generate_command = GenerateCommand {
+            project_path: command.project_path.clone(),
+            config_path: command.config_path.clone(),
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: command.description.clone(),
+            dry_run: false,
+            allow_destructive: false,
+            override_policy: false,
+            verbose: false,
+            format: command.format.clone(),
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        };
+        let generated = GenerateCommandHandler::new().execute(&generate_command)?;
+        let migrations: Vec<String> = migration_names(migrations_dir)?
+            .difference(&before)
+            .cloned()
+            .collect();
+
+        let mut output = DevMigrateOutput {
+            environment: command.env.clone(),
+            migrations: migrations.clone(),
+            generate: None,
+            apply: None,
+            error: None,
+            generate_text: String::new(),
+            apply_text: None,
+        };
+        embed_output(
+            &command.format,
+            generated,
+            &mut output.generate,
+            &mut output.generate_text,
+        );
+
+        if migrations.is_empty() {
+            return Ok((render_output(&output, &command.format)?, migrations));
+        }
+
+        debug!(migrations = ?migrations, env = %command.env, "Applying generated migrations");
+        let apply_command = ApplyCommand {
+            project_path: command.project_path.clone(),
+            config_path: command.config_path.clone(),
+            dry_run: false,
+            env: command.env.clone(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            allow_destructive: false,
+            yes: false,
+            break_stale_lock: false,
+            skip_checksum_verification: false,
+            format: command.format.clone(),
+            dry_run_sql: Default::default(),
+            restore_sequence_values: None,
+            target: None,
+            wait_timeout: None,
+            preflight: None,
+            single_transaction: false,
+            only: None,
+            force_out_of_order: false,
+        };
+        let (applied, failure) = match ApplyCommandHandler::new().execute(&apply_command).await {
+            Ok(rendered) => (rendered, None),
+            Err(e) => match e.downcast_ref::<CompletedWithFailure>() {
+                Some(completed) => (
+                    completed.output.clone(),
+                    Some((completed.message.clone(), completed.exit_code)),
+                ),
+                None => (String::new(), Some((format!("{:#}", e), 1))),
+            },
+        };
+        let mut apply_text = String::new();
+        embed_output(&command.format, applied, &mut output.apply, &mut apply_text);
+        output.apply_text = Some(apply_text);
+        output.error = failure.as_ref().map(|(message, _)| message.clone());
+
+        let rendered = render_output(&output, &command.format)?;
+        match failure {
+            None => Ok((rendered, migrations)),
+            Some((message, exit_code)) => Err(CompletedWithFailure {
+                output: rendered,
+                message: format!(
+                    "Generated {} but failed to apply it to '{}': {}",
+                    migrations.join(", "),
+                    command.env,
+                    message
+                ),
+                exit_code,
+            }
+            .into()),
+        }
+    }
+}
+
+/// protected_environments に含まれる環境なら拒否する
+fn check_environment(protected_environments: &[String], env: &str) -> Result<()> {
+    if protected_environments.iter().any(|e| e == env) {
+        return Err(anyhow!(
+            "Environment '{}' is listed in protected_environments; `strata dev migrate` only runs against development databases. Use `strata generate` and `strata apply --env {}` instead.",
+            env,
+            env
+        ));
+    }
+    Ok(())
+}
+
+/// マイグレーションディレクトリにあるマイグレーション名（`{version}_{description}`）
+fn migration_names(migrations_dir: &Path) -> Result<BTreeSet<String>> {
+    if !migrations_dir.exists() {
+        return Ok(BTreeSet::new());
+    }
+    Ok(migration_loader::load_available_migrations(migrations_dir)?
+        .into_iter()
+        .map(|(version, description, _)| format!("{}_{}", version, description))
+        .collect())
+}
+
+/// マイグレーション名からバージョンを取り出す
+fn version_of(migration: &str) -> Option<&str> {
+    migration.split_once('_').map(|(version, _)| version)
+}
+
+/// 次に生成するマイグレーションのバージョンが `last_version` と重ならなくなるまで待つ
+///
+/// バージョンは秒単位のタイムスタンプのため、同じ秒に続けて生成すると重複してしまう。
+async fn wait_for_next_version(last_version: &str) {
+    let generator = MigrationGeneratorService::new();
+    while generator.generate_timestamp().as_str() <= last_version {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// サブコマンドの出力を、出力フォーマットに応じて JSON 値またはテキストとして格納する
+fn embed_output(
+    format: &OutputFormat,
+    rendered: String,
+    json: &mut Option<serde_json::Value>,
+    text: &mut String,
+) {
+    match format {
+        OutputFormat::Json => *json = serde_json::from_str(&rendered).ok(),
+        OutputFormat::Text => *text = rendered,
+    }
+}
+
+/// 失敗したサイクルのエラーを watch のイベントに変換
+fn cycle_failure(e: anyhow::Error) -> WatchEvent {
+    match e.downcast_ref::<CompletedWithFailure>() {
+        Some(completed) => WatchEvent::Failed {
+            output: completed.output.clone(),
+            error: completed.message.clone(),
+        },
+        None => WatchEvent::Failed {
+            output: String::new(),
+            error: format!("{:#}", e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_environment() {
+        let protected = vec!["staging".to_string(), "production".to_string()];
+
+        assert!(check_environment(&protected, "development").is_ok());
+        assert!(check_environment(&[], "production").is_ok());
+        let err = check_environment(&protected, "production").unwrap_err();
+        assert!(err.to_string().contains("protected_environments"));
+    }
+
+    #[test]
+    fn test_version_of() {
+        assert_eq!(
+            version_of("20260122120000_create_users"),
+            Some("20260122120000")
+        );
+        assert_eq!(version_of("invalid"), None);
+    }
+}
@@ -0,0 +1,213 @@
+// スキーマディレクトリの変更監視
+//
+// ファイルイベントではなくポーリングで変更を検出します。
+// - スキーマディレクトリ直下の YAML ファイル（.yaml / .yml）の内容をハッシュ化して比較
+// - 変更を検出したら、ディレクトリの内容が debounce の間変化しなくなるまで待つ
+// - 停止要求（Ctrl+C など）を受けたら待機を中断する
+
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::Duration;
+
+/// 監視のポーリング間隔のデフォルト（ミリ秒）
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 500;
+
+/// 変更が落ち着くまで待つ時間のデフォルト（ミリ秒）
+pub const DEFAULT_DEBOUNCE_MS: u64 = 300;
+
+/// 監視のオプション
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchOptions {
+    /// スキーマディレクトリを確認する間隔
+    pub poll_interval: Duration,
+    /// 最後の変更からこの時間だけ変化がなければ、一連の編集が終わったとみなす
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+            debounce: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+        }
+    }
+}
+
+/// スキーマディレクトリの内容のスナップショット（ファイルパスごとの内容のハッシュ）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaFingerprint {
+    files: BTreeMap<PathBuf, u64>,
+}
+
+impl SchemaFingerprint {
+    /// スキーマディレクトリを読み込んでスナップショットを作成
+    ///
+    /// スキーマの読み込みと同じく、ディレクトリ直下の .yaml / .yml ファイルのみを対象とします。
+    /// エディタの保存処理で読み込み中にファイルが消えた場合は、そのファイルを無視します。
+    pub fn scan(dir: &Path) -> Result<Self> {
+        let entries = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read schema directory: {:?}", dir))?;
+
+        let mut files = BTreeMap::new();
+        for entry in entries {
+            let path = entry
+                .with_context(|| format!("Failed to read schema directory: {:?}", dir))?
+                .path();
+            let is_yaml = path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml");
+            if !is_yaml || !path.is_file() {
+                continue;
+            }
+
+            let content = match fs::read(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to read file: {:?}", path))
+                }
+            };
+            let mut hasher = DefaultHasher::new();
+            content.hash(&mut hasher);
+            files.insert(path, hasher.finish());
+        }
+
+        Ok(Self { files })
+    }
+
+    /// 対象のファイル数
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// スキーマディレクトリが `last` から変化し、その後落ち着くまで待つ
+///
+/// 変化を検出した後も、debounce の間に新たな変化があれば待ち続けるため、
+/// 短時間に続けて保存された複数の編集は1回の変化として扱われます。
+/// 落ち着いた時点のスナップショットを返し、停止要求を受けた場合は `None` を返します。
+pub async fn wait_for_change<S>(
+    dir: &Path,
+    last: &SchemaFingerprint,
+    options: &WatchOptions,
+    mut shutdown: Pin<&mut S>,
+) -> Result<Option<SchemaFingerprint>>
+where
+    S: Future<Output = ()>,
+{
+    // 変化を検出するまでポーリング
+    let mut current = loop {
+        tokio::select! {
+            _ = shutdown.as_mut() => return Ok(None),
+            _ = tokio::time::sleep(options.poll_interval) => {}
+        }
+        let scanned = SchemaFingerprint::scan(dir)?;
+        if scanned != *last {
+            break scanned;
+        }
+    };
+
+    // debounce の間変化がなくなるまで待つ
+    loop {
+        tokio::select! {
+            _ = shutdown.as_mut() => return Ok(None),
+            _ = tokio::time::sleep(options.debounce) => {}
+        }
+        let scanned = SchemaFingerprint::scan(dir)?;
+        if scanned == current {
+            return Ok(Some(current));
+        }
+        current = scanned;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fast_options() -> WatchOptions {
+        WatchOptions {
+            poll_interval: Duration::from_millis(10),
+            debounce: Duration::from_millis(150),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_yaml_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("users.yaml"), "tables: {}\n").unwrap();
+
+        let initial = SchemaFingerprint::scan(dir).unwrap();
+        assert_eq!(initial.file_count(), 1);
+
+        // YAML以外のファイルとサブディレクトリは無視する
+        fs::write(dir.join("notes.txt"), "memo").unwrap();
+        fs::create_dir(dir.join("drafts")).unwrap();
+        fs::write(dir.join("drafts").join("posts.yaml"), "tables: {}\n").unwrap();
+        assert_eq!(SchemaFingerprint::scan(dir).unwrap(), initial);
+
+        fs::write(dir.join("users.yaml"), "tables:\n  users: {}\n").unwrap();
+        assert_ne!(SchemaFingerprint::scan(dir).unwrap(), initial);
+
+        fs::write(dir.join("users.yaml"), "tables: {}\n").unwrap();
+        fs::write(dir.join("posts.yml"), "tables: {}\n").unwrap();
+        assert_eq!(SchemaFingerprint::scan(dir).unwrap().file_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_batches_rapid_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+        fs::write(dir.join("users.yaml"), "v0").unwrap();
+        let initial = SchemaFingerprint::scan(&dir).unwrap();
+
+        let editor_dir = dir.clone();
+        let editor = tokio::spawn(async move {
+            for i in 1..=3 {
+                tokio::time::sleep(Duration::from_millis(40)).await;
+                fs::write(editor_dir.join("users.yaml"), format!("v{}", i)).unwrap();
+            }
+        });
+
+        let shutdown = std::future::pending::<()>();
+        tokio::pin!(shutdown);
+        let changed = wait_for_change(&dir, &initial, &fast_options(), shutdown)
+            .await
+            .unwrap()
+            .expect("change should be detected");
+        editor.await.unwrap();
+
+        // 途中の状態ではなく、最後の編集まで反映された状態で1回だけ返る
+        assert_eq!(changed, SchemaFingerprint::scan(&dir).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_stops_on_shutdown() {
+        let temp_dir = TempDir::new().unwrap();
+        let initial = SchemaFingerprint::scan(temp_dir.path()).unwrap();
+
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let shutdown = async {
+            let _ = stop_rx.await;
+        };
+        tokio::pin!(shutdown);
+        stop_tx.send(()).unwrap();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            wait_for_change(temp_dir.path(), &initial, &fast_options(), shutdown),
+        )
+        .await
+        .expect("shutdown should stop waiting");
+        assert_eq!(result.unwrap(), None);
+    }
+}
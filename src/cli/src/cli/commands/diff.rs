@@ -0,0 +1,645 @@
+// diffコマンドハンドラー
+//
+// スキーマディレクトリの定義と、環境のデータベースの実際のスキーマを比較します。
+// - DB introspection: export と同じ抽出処理（DatabaseIntrospector + SchemaConversionService）
+// - 差分検出: SchemaDiffDetectorService（データベースを変更前、スキーマディレクトリを変更後として比較）
+// - 差分がある場合は出力とともに EXIT_CODE_SCHEMA_DIFF で終了する（CIでのゲート用）
+
+use crate::adapters::database_introspector::create_introspector_for_config;
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::export::{ExportCommandHandler, SkippedObjects};
+use crate::cli::commands::generate::summary::{
+    column_change_detail, comment_change_detail, constraint_signature,
+};
+use crate::cli::commands::retained_tables;
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use crate::core::schema::Schema;
+use crate::core::schema_diff::SchemaDiff;
+use crate::services::column_lifecycle;
+use crate::services::identifier_case;
+use crate::services::schema_diff_detector::{SchemaDiffDetectorService, SchemaDiffOptions};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// スキーマディレクトリとデータベースに差分がある場合の終了コード
+pub const EXIT_CODE_SCHEMA_DIFF: i32 = 7;
+
+/// 差分のあったオブジェクトの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffObjectKind {
+    Table,
+    Column,
+    Index,
+    Constraint,
+    Enum,
+    View,
+}
+
+impl DiffObjectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffObjectKind::Table => "table",
+            DiffObjectKind::Column => "column",
+            DiffObjectKind::Index => "index",
+            DiffObjectKind::Constraint => "constraint",
+            DiffObjectKind::Enum => "enum",
+            DiffObjectKind::View => "view",
+        }
+    }
+}
+
+/// 差分のあったオブジェクト
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct DiffEntry {
+    /// オブジェクトの所属するテーブル（カラム・インデックス・制約のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    /// オブジェクトの種類
+    pub kind: DiffObjectKind,
+    /// オブジェクト名（制約は `<種別>(<カラム>)`）
+    pub name: String,
+    /// 変更内容（変更されたオブジェクトのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl DiffEntry {
+    fn new(kind: DiffObjectKind, name: impl Into<String>) -> Self {
+        Self {
+            table: None,
+            kind,
+            name: name.into(),
+            detail: None,
+        }
+    }
+
+    fn in_table(mut self, table: &str) -> Self {
+        self.table = Some(table.to_string());
+        self
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// テキスト出力用の表記（例: `column users.email`, `index idx_users_name on users`）
+    fn label(&self) -> String {
+        let name = match (&self.table, self.kind) {
+            (Some(table), DiffObjectKind::Column) => format!("{}.{}", table, self.name),
+            (Some(table), DiffObjectKind::Constraint) => format!("{} {}", table, self.name),
+            (Some(table), _) => format!("{} on {}", self.name, table),
+            (None, _) => self.name.clone(),
+        };
+        match &self.detail {
+            Some(detail) => format!("{} {} ({})", self.kind.as_str(), name, detail),
+            None => format!("{} {}", self.kind.as_str(), name),
+        }
+    }
+}
+
+/// スキーマディレクトリとデータベースの差分の一覧
+///
+/// 「追加」はスキーマディレクトリにありデータベースにないもの、
+/// 「削除」はデータベースにありスキーマディレクトリにないものを表します。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffSummary {
+    /// スキーマディレクトリにのみあるオブジェクト
+    pub added: Vec<DiffEntry>,
+    /// データベースにのみあるオブジェクト
+    pub removed: Vec<DiffEntry>,
+    /// 定義が異なるオブジェクト
+    pub modified: Vec<DiffEntry>,
+}
+
+impl DiffSummary {
+    /// スキーマ差分から一覧を作成
+    ///
+    /// ビュー定義はデータベースが書き換えて保存するため（列の展開や括弧の付与など）、
+    /// 定義の違いは差分に含めず `notes` に記録します。ビューの有無は差分として扱います。
+    pub fn from_diff(diff: &SchemaDiff, notes: &mut Vec<String>) -> Self {
+        let mut summary = Self::default();
+
+        for table in &diff.added_tables {
+            summary
+                .added
+                .push(DiffEntry::new(DiffObjectKind::Table, &table.name));
+        }
+        for table_name in &diff.removed_tables {
+            summary
+                .removed
+                .push(DiffEntry::new(DiffObjectKind::Table, table_name));
+        }
+        for renamed in &diff.renamed_tables {
+            summary.modified.push(
+                DiffEntry::new(DiffObjectKind::Table, &renamed.new_table.name)
+                    .with_detail(format!("renamed from {}", renamed.old_name)),
+            );
+        }
+
+        for table_diff in &diff.modified_tables {
+            let table = table_diff.table_name.as_str();
+            for column in &table_diff.added_columns {
+                summary
+                    .added
+                    .push(DiffEntry::new(DiffObjectKind::Column, &column.name).in_table(table));
+            }
+            for column_name in &table_diff.removed_columns {
+                summary
+                    .removed
+                    .push(DiffEntry::new(DiffObjectKind::Column, column_name).in_table(table));
+            }
+            for column_diff in &table_diff.modified_columns {
+                let details: Vec<String> = column_diff
+                    .changes
+                    .iter()
+                    .filter_map(column_change_detail)
+                    .collect();
+                let mut entry = DiffEntry::new(DiffObjectKind::Column, &column_diff.column_name)
+                    .in_table(table);
+                if !details.is_empty() {
+                    entry = entry.with_detail(details.join(", "));
+                }
+                summary.modified.push(entry);
+            }
+            for renamed in &table_diff.renamed_columns {
+                summary.modified.push(
+                    DiffEntry::new(DiffObjectKind::Column, &renamed.new_column.name)
+                        .in_table(table)
+                        .with_detail(format!("renamed from {}", renamed.old_name)),
+                );
+            }
+            for index in &table_diff.added_indexes {
+                summary
+                    .added
+                    .push(DiffEntry::new(DiffObjectKind::Index, &index.name).in_table(table));
+            }
+            for index_name in &table_diff.removed_indexes {
+                summary
+                    .removed
+                    .push(DiffEntry::new(DiffObjectKind::Index, index_name).in_table(table));
+            }
+            for index_diff in &table_diff.modified_indexes {
+                summary.modified.push(
+                    DiffEntry::new(DiffObjectKind::Index, &index_diff.index_name).in_table(table),
+                );
+            }
+            for renamed in &table_diff.renamed_indexes {
+                summary.modified.push(
+                    DiffEntry::new(DiffObjectKind::Index, &renamed.new_index.name)
+                        .in_table(table)
+                        .with_detail(format!("renamed from {}", renamed.old_name)),
+                );
+            }
+            for constraint in &table_diff.added_constraints {
+                summary.added.push(
+                    DiffEntry::new(DiffObjectKind::Constraint, constraint_signature(constraint))
+                        .in_table(table),
+                );
+            }
+            for constraint in &table_diff.removed_constraints {
+                summary.removed.push(
+                    DiffEntry::new(DiffObjectKind::Constraint, constraint_signature(constraint))
+                        .in_table(table),
+                );
+            }
+            if let Some(change) = &table_diff.comment_change {
+                summary
+                    .modified
+                    .push(DiffEntry::new(DiffObjectKind::Table, table).with_detail(
+                        comment_change_detail(&change.old_comment, &change.new_comment),
+                    ));
+            }
+        }
+
+        for enum_def in &diff.added_enums {
+            summary
+                .added
+                .push(DiffEntry::new(DiffObjectKind::Enum, &enum_def.name));
+        }
+        for enum_name in &diff.removed_enums {
+            summary
+                .removed
+                .push(DiffEntry::new(DiffObjectKind::Enum, enum_name));
+        }
+        for enum_diff in &diff.modified_enums {
+            summary.modified.push(
+                DiffEntry::new(DiffObjectKind::Enum, &enum_diff.enum_name).with_detail(format!(
+                    "[{}]->[{}]",
+                    enum_diff.old_values.join(", "),
+                    enum_diff.new_values.join(", ")
+                )),
+            );
+        }
+        for renamed in &diff.renamed_enums {
+            summary.modified.push(
+                DiffEntry::new(DiffObjectKind::Enum, &renamed.new_enum.name)
+                    .with_detail(format!("renamed from {}", renamed.old_name)),
+            );
+        }
+
+        for view in &diff.added_views {
+            summary
+                .added
+                .push(DiffEntry::new(DiffObjectKind::View, &view.name));
+        }
+        for view_name in &diff.removed_views {
+            summary
+                .removed
+                .push(DiffEntry::new(DiffObjectKind::View, view_name));
+        }
+        for renamed in &diff.renamed_views {
+            summary.modified.push(
+                DiffEntry::new(DiffObjectKind::View, &renamed.new_view.name)
+                    .with_detail(format!("renamed from {}", renamed.old_name)),
+            );
+        }
+        for view_diff in &diff.modified_views {
+            notes.push(format!(
+                "View '{}' is stored with a different definition in the database; view definitions are rewritten by the database, so this is not reported as a difference.",
+                view_diff.view_name
+            ));
+        }
+
+        summary.added.sort();
+        summary.removed.sort();
+        summary.modified.sort();
+        summary
+    }
+
+    /// 差分がないか
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// 差分の総数
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+}
+
+/// diffコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffOutput {
+    /// 比較した環境名
+    pub environment: String,
+    /// 差分があるか
+    pub has_differences: bool,
+    /// 差分の一覧
+    #[serde(flatten)]
+    pub summary: DiffSummary,
+    /// 差分に含めなかった違いや比較対象外としたオブジェクトに関する注記
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// 差分検出時の警告
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl CommandOutput for DiffOutput {
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "Comparing schema directory with the '{}' database\n",
+            self.environment
+        );
+
+        let sections = [
+            (
+                "Defined in the schema directory, missing from the database:",
+                "+",
+                &self.summary.added,
+            ),
+            (
+                "Present in the database, not in the schema directory:",
+                "-",
+                &self.summary.removed,
+            ),
+            ("Different in the database:", "~", &self.summary.modified),
+        ];
+        for (heading, symbol, entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            text.push('\n');
+            text.push_str(heading);
+            text.push('\n');
+            for entry in entries {
+                text.push_str(&format!("  {} {}\n", symbol, entry.label()));
+            }
+        }
+
+        if !self.notes.is_empty() || !self.warnings.is_empty() {
+            text.push('\n');
+        }
+        for note in &self.notes {
+            text.push_str(&format!("Note: {}\n", note));
+        }
+        for warning in &self.warnings {
+            text.push_str(&format!("Warning: {}\n", warning));
+        }
+
+        text.push('\n');
+        if self.has_differences {
+            text.push_str(&format!(
+                "{} difference(s) between the schema directory and the database",
+                self.summary.len()
+            ));
+        } else {
+            text.push_str("The database matches the schema directory");
+        }
+        text
+    }
+}
+
+/// diffコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct DiffCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// diffコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct DiffCommandHandler {}
+
+impl DiffCommandHandler {
+    /// 新しいDiffCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// diffコマンドを実行
+    ///
+    /// 差分がある場合は、出力とともに `EXIT_CODE_SCHEMA_DIFF` の `CompletedWithFailure` を返します。
+    pub async fn execute(&self, command: &DiffCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &DiffCommand,
+    ) -> Result<String> {
+        context.require_schema_dir()?;
+        // データベースは identifier_case を適用した名前で作成されているため、同じ規則で比較する
+        let mut schema = identifier_case::fold_schema_identifiers(
+            &context.load_schema(&SchemaLoadOptions::default())?,
+            context.config.identifier_case,
+        );
+
+        let pool = context.connect_pool(&command.env).await?;
+        debug!(dialect = ?context.dialect(), env = %command.env, "Extracting schema from database");
+        let introspector = create_introspector_for_config(&context.config);
+        let (mut database_schema, skipped) = ExportCommandHandler::new()
+            .extract_schema_from_database(introspector.as_ref(), &pool, context.dialect())
+            .await
+            .with_context(|| "Failed to get schema information")?;
+
+        let mut notes = Vec::new();
+        self.exclude_unmanaged_objects(context, &mut database_schema, &schema)?;
+        self.exclude_skipped_objects(&mut schema, &mut database_schema, &skipped, &mut notes);
+        // SQLite はコメントを保存しないため、コメントの違いは比較しない
+        if matches!(context.dialect(), Dialect::SQLite) {
+            clear_comments(&mut schema);
+        }
+        // ON UPDATE CURRENT_TIMESTAMP はMySQL以外では無視されるため比較しない
+        if !matches!(context.dialect(), Dialect::MySQL) {
+            clear_on_update_current_timestamp(&mut schema);
+        }
+
+        let (diff, diff_warnings) = SchemaDiffDetectorService::new()
+            .with_options(SchemaDiffOptions::from_config(&context.config))
+            .detect_diff_with_warnings(&database_schema, &schema);
+
+        let summary = DiffSummary::from_diff(&diff, &mut notes);
+        let output = DiffOutput {
+            environment: command.env.clone(),
+            has_differences: !summary.is_empty(),
+            summary,
+            notes,
+            warnings: diff_warnings.into_iter().map(|w| w.message).collect(),
+        };
+        let rendered = render_output(&output, &command.format)?;
+
+        if !output.has_differences {
+            return Ok(rendered);
+        }
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "{} difference(s) between the schema directory and the '{}' database",
+                output.summary.len(),
+                command.env
+            ),
+            exit_code: EXIT_CODE_SCHEMA_DIFF,
+        }
+        .into())
+    }
+
+    /// スキーマディレクトリで管理しないテーブルをデータベース側から除外する
+    ///
+    /// `external_tables` として宣言されたテーブルと、ロールバック保護で残されたテーブル、
+    /// スキーマ定義から取り除かれたが `strata contract` で削除されるまで残る削除待ちのカラムが対象です。
+    /// マイグレーション管理テーブル（schema_migrations）は introspection の時点で除外されています。
+    fn exclude_unmanaged_objects(
+        &self,
+        context: &CommandContext,
+        database_schema: &mut Schema,
+        schema: &Schema,
+    ) -> Result<()> {
+        for table_name in &schema.external_tables {
+            database_schema.tables.remove(table_name);
+        }
+
+        let migrations_dir = context.migrations_dir();
+        if migrations_dir.exists() {
+            for table in retained_tables::load_retained_tables(&migrations_dir)? {
+                if !schema.has_table(&table.name) {
+                    database_schema.tables.remove(&table.name);
+                }
+            }
+        }
+
+        let snapshot = context.load_schema_snapshot()?;
+        for pending in column_lifecycle::contract_pending_columns(&snapshot) {
+            let defined = schema
+                .get_table(&pending.table)
+                .is_some_and(|table| table.get_column(&pending.column).is_some());
+            if defined {
+                continue;
+            }
+            if let Some(table) = database_schema.tables.get_mut(&pending.table) {
+                table.columns.retain(|column| column.name != pending.column);
+            }
+        }
+        database_schema.external_tables = schema.external_tables.clone();
+        Ok(())
+    }
+
+    /// 権限不足で読み取れなかったオブジェクトを両側から除外し、注記に記録する
+    fn exclude_skipped_objects(
+        &self,
+        schema: &mut Schema,
+        database_schema: &mut Schema,
+        skipped: &SkippedObjects,
+        notes: &mut Vec<String>,
+    ) {
+        for table in &skipped.tables {
+            schema.tables.remove(&table.name);
+            notes.push(format!(
+                "Table '{}' was not compared: {}",
+                table.name, table.reason
+            ));
+        }
+        if let Some(views) = skipped.views.first() {
+            schema.views.clear();
+            database_schema.views.clear();
+            notes.push(format!("Views were not compared: {}", views.reason));
+        }
+        if let Some(enums) = skipped.enums.first() {
+            schema.enums.clear();
+            database_schema.enums.clear();
+            notes.push(format!("ENUM types were not compared: {}", enums.reason));
+        }
+    }
+}
+
+/// テーブル・カラムのコメントを取り除く
+fn clear_comments(schema: &mut Schema) {
+    for table in schema.tables.values_mut() {
+        table.comment = None;
+        for column in &mut table.columns {
+            column.comment = None;
+        }
+    }
+}
+
+/// カラムの ON UPDATE CURRENT_TIMESTAMP 指定を取り除く
+fn clear_on_update_current_timestamp(schema: &mut Schema) {
+    for table in schema.tables.values_mut() {
+        for column in &mut table.columns {
+            column.on_update_current_timestamp = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Constraint, Table, View};
+    use crate::core::schema_diff::{ColumnChange, ColumnDiff, TableDiff, ViewDiff};
+
+    #[test]
+    fn test_summary_from_diff() {
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(Table::new("posts".to_string()));
+        diff.removed_tables.push("legacy".to_string());
+
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff
+            .added_columns
+            .push(Column::new("email".to_string(), ColumnType::TEXT, false));
+        table_diff.modified_columns.push(ColumnDiff {
+            column_name: "name".to_string(),
+            old_column: Column::new("name".to_string(), ColumnType::TEXT, true),
+            new_column: Column::new("name".to_string(), ColumnType::TEXT, false),
+            changes: vec![ColumnChange::NullableChanged {
+                old_nullable: true,
+                new_nullable: false,
+            }],
+        });
+        table_diff
+            .removed_indexes
+            .push("idx_users_name".to_string());
+        table_diff.added_constraints.push(Constraint::UNIQUE {
+            columns: vec!["email".to_string()],
+        });
+        diff.modified_tables.push(table_diff);
+        diff.modified_views.push(ViewDiff {
+            view_name: "active_users".to_string(),
+            old_definition: "SELECT users.id FROM users".to_string(),
+            new_definition: "SELECT id FROM users".to_string(),
+            old_view: View::new(
+                "active_users".to_string(),
+                "SELECT users.id FROM users".to_string(),
+            ),
+            new_view: View::new(
+                "active_users".to_string(),
+                "SELECT id FROM users".to_string(),
+            ),
+            auto_rewritten: false,
+        });
+
+        let mut notes = Vec::new();
+        let summary = DiffSummary::from_diff(&diff, &mut notes);
+
+        let labels = |entries: &[DiffEntry]| -> Vec<String> {
+            entries.iter().map(DiffEntry::label).collect()
+        };
+        assert_eq!(
+            labels(&summary.added),
+            vec![
+                "table posts",
+                "column users.email",
+                "constraint users UNIQUE(email)"
+            ]
+        );
+        assert_eq!(
+            labels(&summary.removed),
+            vec!["table legacy", "index idx_users_name on users"]
+        );
+        assert_eq!(
+            labels(&summary.modified),
+            vec!["column users.name (NULL->NOT NULL)"]
+        );
+        // ビュー定義の違いは差分に含めず注記のみ
+        assert_eq!(summary.len(), 6);
+        assert_eq!(notes.len(), 1);
+        assert!(notes[0].contains("active_users"));
+    }
+
+    #[test]
+    fn test_diff_output_json_serialization() {
+        let output = DiffOutput {
+            environment: "development".to_string(),
+            has_differences: true,
+            summary: DiffSummary {
+                added: vec![DiffEntry::new(DiffObjectKind::Column, "email").in_table("users")],
+                removed: vec![],
+                modified: vec![],
+            },
+            notes: vec![],
+            warnings: vec![],
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_output(&output, &OutputFormat::Json).unwrap()).unwrap();
+
+        assert_eq!(json["environment"], "development");
+        assert_eq!(json["has_differences"], true);
+        assert_eq!(
+            json["added"][0],
+            serde_json::json!({"table": "users", "kind": "column", "name": "email"})
+        );
+        assert_eq!(json["removed"], serde_json::json!([]));
+        assert!(json.get("notes").is_none());
+    }
+}
@@ -0,0 +1,274 @@
+// docsコマンドハンドラー
+//
+// スキーマ定義からMarkdownのスキーマドキュメントを生成します（データベースには接続しません）。
+// - 描画: schema_docs サービス（テーブル・ビュー・ENUMのページ、一覧ページと erDiagram）
+// - 書き込み: 内容が変わったページのみ書き込み、定義から消えたオブジェクトのページを削除する
+// - --check: 書き込まずに比較し、古いページがある場合は出力とともに EXIT_CODE_STALE_DOCS で終了する
+
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::services::schema_docs::{render_schema_docs, INDEX_PAGE, PAGE_DIRS};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// --check でドキュメントが古い場合の終了コード
+pub const EXIT_CODE_STALE_DOCS: i32 = 9;
+
+/// docsコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct DocsCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 出力ディレクトリ（相対パスはプロジェクトのルートから）
+    pub out_dir: PathBuf,
+    /// 書き込まずに、出力ディレクトリのドキュメントが最新かを確認する
+    pub check: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// 生成結果と出力ディレクトリのページの違い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocsPageStatus {
+    /// 出力ディレクトリにない
+    Missing,
+    /// 内容が異なる
+    Outdated,
+    /// スキーマ定義にないオブジェクトのページ
+    Extra,
+}
+
+impl DocsPageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocsPageStatus::Missing => "missing",
+            DocsPageStatus::Outdated => "outdated",
+            DocsPageStatus::Extra => "extra",
+        }
+    }
+}
+
+/// 生成結果と異なるページ
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct DocsPageChange {
+    /// 出力ディレクトリからの相対パス
+    pub path: String,
+    /// 違いの種類
+    pub status: DocsPageStatus,
+}
+
+/// docsコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct DocsOutput {
+    /// 出力ディレクトリ
+    pub out_dir: String,
+    /// --check で実行したか
+    pub check: bool,
+    /// 生成したページ数
+    pub pages: usize,
+    /// 生成結果と異なっていたページ（--check 以外では書き込み・削除したページ）
+    pub changes: Vec<DocsPageChange>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for DocsOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// docsコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct DocsCommandHandler {}
+
+impl DocsCommandHandler {
+    /// 新しいDocsCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// docsコマンドを実行
+    ///
+    /// --check でドキュメントが古い場合は、出力とともに `EXIT_CODE_STALE_DOCS` の `CompletedWithFailure` を返します。
+    pub fn execute(&self, command: &DocsCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+        context.require_schema_dir()?;
+        let schema = context.load_schema(&SchemaLoadOptions::default())?;
+
+        let out_dir = if command.out_dir.is_absolute() {
+            command.out_dir.clone()
+        } else {
+            command.project_path.join(&command.out_dir)
+        };
+        debug!(out_dir = ?out_dir, check = command.check, "Rendering schema documentation");
+
+        let pages = render_schema_docs(&schema);
+        let changes = compare_pages(&out_dir, &pages)?;
+
+        if !command.check {
+            apply_changes(&out_dir, &pages, &changes)?;
+        }
+
+        let output = DocsOutput {
+            out_dir: command.out_dir.display().to_string(),
+            check: command.check,
+            pages: pages.len(),
+            text_message: format_text(&command.out_dir, command.check, pages.len(), &changes),
+            changes,
+        };
+        let rendered = render_output(&output, &command.format)?;
+
+        if !command.check || output.changes.is_empty() {
+            return Ok(rendered);
+        }
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "{} page(s) in {} are stale; run `strata docs` to update them",
+                output.changes.len(),
+                output.out_dir
+            ),
+            exit_code: EXIT_CODE_STALE_DOCS,
+        }
+        .into())
+    }
+}
+
+/// 生成結果と出力ディレクトリのページを比較する
+///
+/// 比較の対象は一覧ページと、テーブル・ビュー・ENUMのディレクトリ直下の `.md` ファイルです。
+/// 出力ディレクトリのそれ以外のファイルには触れません。
+fn compare_pages(out_dir: &Path, pages: &BTreeMap<String, String>) -> Result<Vec<DocsPageChange>> {
+    let existing = existing_pages(out_dir)?;
+    let mut changes = Vec::new();
+
+    for (path, content) in pages {
+        let status = if !existing.contains(path) {
+            Some(DocsPageStatus::Missing)
+        } else {
+            let file_path = out_dir.join(path);
+            let current = fs::read_to_string(&file_path)
+                .with_context(|| format!("Failed to read {:?}", file_path))?;
+            (&current != content).then_some(DocsPageStatus::Outdated)
+        };
+        if let Some(status) = status {
+            changes.push(DocsPageChange {
+                path: path.clone(),
+                status,
+            });
+        }
+    }
+    for path in existing {
+        if !pages.contains_key(&path) {
+            changes.push(DocsPageChange {
+                path,
+                status: DocsPageStatus::Extra,
+            });
+        }
+    }
+
+    changes.sort();
+    Ok(changes)
+}
+
+/// 出力ディレクトリにある生成対象のページ（相対パス）
+fn existing_pages(out_dir: &Path) -> Result<BTreeSet<String>> {
+    let mut pages = BTreeSet::new();
+    if out_dir.join(INDEX_PAGE).is_file() {
+        pages.insert(INDEX_PAGE.to_string());
+    }
+    for dir in PAGE_DIRS {
+        let dir_path = out_dir.join(dir);
+        if !dir_path.is_dir() {
+            continue;
+        }
+        let entries = fs::read_dir(&dir_path)
+            .with_context(|| format!("Failed to read directory {:?}", dir_path))?;
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            if let Some(file_name) = path.file_name().and_then(|name| name.to_str()) {
+                pages.insert(format!("{}/{}", dir, file_name));
+            }
+        }
+    }
+    Ok(pages)
+}
+
+/// 差分のあるページを書き込み、不要になったページを削除する
+fn apply_changes(
+    out_dir: &Path,
+    pages: &BTreeMap<String, String>,
+    changes: &[DocsPageChange],
+) -> Result<()> {
+    for change in changes {
+        let path = out_dir.join(&change.path);
+        match change.status {
+            DocsPageStatus::Missing | DocsPageStatus::Outdated => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create directory {:?}", parent))?;
+                }
+                fs::write(&path, &pages[&change.path])
+                    .with_context(|| format!("Failed to write {:?}", path))?;
+            }
+            DocsPageStatus::Extra => {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {:?}", path))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn format_text(out_dir: &Path, check: bool, pages: usize, changes: &[DocsPageChange]) -> String {
+    let out_dir = out_dir.display();
+    let mut lines = Vec::new();
+    if check {
+        if changes.is_empty() {
+            lines.push(format!(
+                "Schema documentation in {} is up to date ({} page(s))",
+                out_dir, pages
+            ));
+        } else {
+            lines.push(format!("Schema documentation in {} is stale:", out_dir));
+            for change in changes {
+                lines.push(format!("  {}: {}", change.status.as_str(), change.path));
+            }
+            lines.push("Run `strata docs` to update it.".to_string());
+        }
+    } else if changes.is_empty() {
+        lines.push(format!(
+            "Schema documentation in {} is already up to date ({} page(s))",
+            out_dir, pages
+        ));
+    } else {
+        lines.push(format!(
+            "Updated schema documentation in {} ({} page(s))",
+            out_dir, pages
+        ));
+        for change in changes {
+            let action = match change.status {
+                DocsPageStatus::Missing => "created",
+                DocsPageStatus::Outdated => "updated",
+                DocsPageStatus::Extra => "removed",
+            };
+            lines.push(format!("  {}: {}", action, change.path));
+        }
+    }
+    lines.join("\n")
+}
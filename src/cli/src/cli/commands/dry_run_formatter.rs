@@ -45,7 +45,10 @@ impl DryRunFormatter {
         Self::append_header(&mut output, migration_name);
 
         let rename_changes = Self::collect_rename_changes(diff);
-        Self::append_rename_section(&mut output, &rename_changes);
+        Self::append_rename_section(&mut output, "--- Column Renames ---", &rename_changes);
+
+        let index_rename_changes = Self::collect_index_rename_changes(diff);
+        Self::append_rename_section(&mut output, "--- Index Renames ---", &index_rename_changes);
 
         let type_changes = Self::collect_type_changes(diff);
         Self::append_type_change_section(&mut output, &type_changes);
@@ -86,11 +89,11 @@ impl DryRunFormatter {
         writeln!(output).unwrap();
     }
 
-    fn append_rename_section(output: &mut String, rename_changes: &[RenameInfo]) {
+    fn append_rename_section(output: &mut String, title: &str, rename_changes: &[RenameInfo]) {
         if rename_changes.is_empty() {
             return;
         }
-        writeln!(output, "{}", "--- Column Renames ---".bold()).unwrap();
+        writeln!(output, "{}", title.bold()).unwrap();
         for rename in rename_changes {
             let table = rename.table.cyan();
             let arrow = "→".bold();
@@ -177,6 +180,19 @@ impl DryRunFormatter {
             writeln!(output, "  {}", format!("MODIFY VIEW: {}", view_name).red()).unwrap();
         }
 
+        for entry in &destructive_report.columns_narrowed {
+            writeln!(
+                output,
+                "  {}",
+                format!(
+                    "LOSSY TYPE CHANGE: {}.{} {} -> {} ({})",
+                    entry.table, entry.column, entry.old_type, entry.new_type, entry.reason
+                )
+                .red()
+            )
+            .unwrap();
+        }
+
         let dropped_column_count: usize = destructive_report
             .columns_dropped
             .iter()
@@ -187,14 +203,15 @@ impl DryRunFormatter {
             output,
             "  {}",
             format!(
-                "Impact summary: tables dropped={}, columns dropped={}, columns renamed={}, enums dropped={}, enums recreated={}, views dropped={}, views modified={}",
+                "Impact summary: tables dropped={}, columns dropped={}, columns renamed={}, enums dropped={}, enums recreated={}, views dropped={}, views modified={}, lossy type changes={}",
                 destructive_report.tables_dropped.len(),
                 dropped_column_count,
                 destructive_report.columns_renamed.len(),
                 destructive_report.enums_dropped.len(),
                 destructive_report.enums_recreated.len(),
                 destructive_report.views_dropped.len(),
-                destructive_report.views_modified.len()
+                destructive_report.views_modified.len(),
+                destructive_report.columns_narrowed.len()
             )
             .red()
         )
@@ -378,6 +395,23 @@ impl DryRunFormatter {
 
         renames
     }
+
+    /// インデックスのリネーム情報を収集
+    pub(crate) fn collect_index_rename_changes(diff: &SchemaDiff) -> Vec<RenameInfo> {
+        let mut renames = Vec::new();
+
+        for table_diff in &diff.modified_tables {
+            for renamed in &table_diff.renamed_indexes {
+                renames.push(RenameInfo {
+                    table: table_diff.table_name.clone(),
+                    old_name: renamed.old_name.clone(),
+                    new_name: renamed.new_index.name.clone(),
+                });
+            }
+        }
+
+        renames
+    }
 }
 
 #[cfg(test)]
@@ -385,8 +419,10 @@ mod tests {
     use super::*;
     use crate::core::destructive_change_report::{DroppedColumn, RenamedColumnInfo};
     use crate::core::error::{ErrorLocation, ValidationWarning, WarningKind};
-    use crate::core::schema::{Column, ColumnType};
-    use crate::core::schema_diff::{ColumnChange, ColumnDiff, RenamedColumn, TableDiff};
+    use crate::core::schema::{Column, ColumnType, Index};
+    use crate::core::schema_diff::{
+        ColumnChange, ColumnDiff, RenamedColumn, RenamedIndex, TableDiff,
+    };
 
     fn make_column(name: &str, col_type: ColumnType) -> Column {
         Column::new(name.to_string(), col_type, false)
@@ -472,6 +508,28 @@ mod tests {
         assert_eq!(renames[0].new_name, "display_name");
     }
 
+    #[test]
+    fn test_collect_index_rename_changes() {
+        let mut diff = SchemaDiff::new();
+        let mut table_diff = TableDiff::new("users".to_string());
+        let old_index = Index::new("idx_email".to_string(), vec!["email".to_string()], true);
+        let mut new_index = old_index.clone();
+        new_index.name = "users_email_key".to_string();
+        table_diff.renamed_indexes.push(RenamedIndex {
+            old_name: "idx_email".to_string(),
+            old_index,
+            new_index,
+        });
+        diff.modified_tables.push(table_diff);
+
+        let renames = DryRunFormatter::collect_index_rename_changes(&diff);
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames[0].table, "users");
+        assert_eq!(renames[0].old_name, "idx_email");
+        assert_eq!(renames[0].new_name, "users_email_key");
+        assert!(DryRunFormatter::collect_rename_changes(&diff).is_empty());
+    }
+
     #[test]
     fn test_collect_rename_changes_only_no_type_change() {
         let mut diff = SchemaDiff::new();
@@ -573,6 +631,7 @@ mod tests {
                 table: Some("users".to_string()),
                 column: Some("name".to_string()),
                 line: None,
+                file: None,
             }),
             kind: WarningKind::DialectSpecific,
         });
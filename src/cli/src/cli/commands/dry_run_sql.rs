@@ -0,0 +1,365 @@
+// dry-run のSQL出力（--sql-only / --annotate）
+//
+// generate / apply の dry-run 結果を、人が読むためのレポートではなくSQLとして出力します。
+// - --sql-only: 実行可能なステートメントのみ（psql などにそのままパイプできる）
+// - --sql-only --explain: マイグレーションファイルのコメント（トランザクションの注意書きなど）も残す
+// - --annotate: 各ステートメントの前に、対象オブジェクト・定義元・破壊的変更かどうかをコメントで付ける
+//
+// いずれもマイグレーションファイルに書き出されるものと同じステートメントを、
+// `sql_output` 設定の終端文字列・改行コードで出力します（BOMは付けません）。
+
+use crate::cli::commands::sql_output::render_sql_file;
+use crate::cli::commands::{split_sql_statements, DESTRUCTIVE_SQL_REGEX};
+use crate::cli::OutputFormat;
+use crate::core::config::SqlOutputConfig;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// dry-run のSQL出力モード
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DryRunSqlMode {
+    /// 人が読むためのレポート（既定）
+    #[default]
+    Report,
+    /// 実行可能なSQLのみ
+    SqlOnly {
+        /// マイグレーションファイルのコメントを残す
+        explain: bool,
+    },
+    /// 各ステートメントにレビュー用のコメントを付ける
+    Annotated,
+}
+
+impl DryRunSqlMode {
+    /// レポートではなくSQLとして出力するか
+    pub fn prints_sql(&self) -> bool {
+        !matches!(self, DryRunSqlMode::Report)
+    }
+
+    /// 出力フォーマットと組み合わせられるか検証する
+    ///
+    /// SQLとして出力するモードはJSON出力と組み合わせられない。
+    pub(crate) fn check_format(&self, format: &OutputFormat) -> Result<()> {
+        if self.prints_sql() && matches!(format, OutputFormat::Json) {
+            return Err(anyhow!(
+                "--sql-only and --annotate print SQL and cannot be combined with --format json"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// CREATE / ALTER / DROP の対象オブジェクト
+static OBJECT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?is)^(?:CREATE|ALTER|DROP)\s+(?:OR\s+REPLACE\s+)?(?:UNIQUE\s+)?(?:MATERIALIZED\s+)?(TABLE|INDEX|TYPE|VIEW)\s+(?:CONCURRENTLY\s+)?(?:IF\s+(?:NOT\s+)?EXISTS\s+)?([`"\w.]+)"#,
+    )
+    .expect("Invalid SQL object regex pattern")
+});
+
+/// INSERT INTO / RENAME TABLE の対象テーブル
+static TABLE_DATA_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)^(?:INSERT\s+INTO|RENAME\s+TABLE)\s+([`"\w.]+)"#)
+        .expect("Invalid SQL table regex pattern")
+});
+
+/// インデックスの対象テーブル（`... ON <table>`）
+static INDEX_TABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)\bON\s+([`"\w.]+)"#).expect("Invalid SQL index table regex pattern")
+});
+
+/// ステートメントが対象とするオブジェクト
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SqlObject {
+    /// 種類（table, index, type, view）
+    pub kind: &'static str,
+    /// オブジェクト名
+    pub name: String,
+    /// インデックスの対象テーブル
+    pub table: Option<String>,
+}
+
+impl SqlObject {
+    /// ステートメント（先頭のコメントを除いたもの）から対象オブジェクトを推定する
+    pub(crate) fn from_statement(statement: &str) -> Option<Self> {
+        if let Some(captures) = OBJECT_REGEX.captures(statement) {
+            let kind = match captures[1].to_ascii_uppercase().as_str() {
+                "TABLE" => "table",
+                "INDEX" => "index",
+                "TYPE" => "type",
+                _ => "view",
+            };
+            let table = (kind == "index")
+                .then(|| INDEX_TABLE_REGEX.captures(statement))
+                .flatten()
+                .map(|c| unquote_identifier(&c[1]));
+            return Some(Self {
+                kind,
+                name: unquote_identifier(&captures[2]),
+                table,
+            });
+        }
+
+        TABLE_DATA_REGEX.captures(statement).map(|captures| Self {
+            kind: "table",
+            name: unquote_identifier(&captures[1]),
+            table: None,
+        })
+    }
+}
+
+impl fmt::Display for SqlObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)?;
+        if let Some(ref table) = self.table {
+            write!(f, " on {}", table)?;
+        }
+        Ok(())
+    }
+}
+
+/// 識別子の引用符（`"` / `` ` ``）を外す
+fn unquote_identifier(identifier: &str) -> String {
+    identifier.replace(['"', '`'], "")
+}
+
+/// ステートメント先頭のコメント行を取り除く
+pub(crate) fn strip_leading_comments(statement: &str) -> &str {
+    let mut rest = statement.trim_start();
+    while rest.starts_with("--") {
+        rest = rest
+            .split_once('\n')
+            .map(|(_, remaining)| remaining.trim_start())
+            .unwrap_or("");
+    }
+    rest
+}
+
+/// ステートメントを正規形（セミコロン終端・空行区切り）で結合する
+pub(crate) fn join_statements(statements: &[String]) -> String {
+    if statements.is_empty() {
+        return String::new();
+    }
+    format!("{};", statements.join(";\n\n"))
+}
+
+/// 正規形のSQLをマイグレーションファイルと同じ終端文字列・改行コードで出力する（BOMなし）
+fn render_for_output(sql: &str, config: &SqlOutputConfig) -> String {
+    let config = SqlOutputConfig {
+        bom: false,
+        ..config.clone()
+    };
+    render_sql_file(sql, &config)
+}
+
+/// 実行可能なSQLを出力する（--sql-only）
+///
+/// `explain` が指定された場合はマイグレーションファイルのコメントを残し、
+/// ファイルに書き出される内容と同じものを出力する。
+pub(crate) fn render_sql_only(sql: &str, config: &SqlOutputConfig, explain: bool) -> String {
+    if explain {
+        return render_for_output(sql, config);
+    }
+
+    let statements: Vec<String> = split_sql_statements(sql)
+        .iter()
+        .map(|statement| strip_leading_comments(statement).to_string())
+        .filter(|statement| !statement.is_empty())
+        .collect();
+    render_for_output(&join_statements(&statements), config)
+}
+
+/// 各ステートメントにレビュー用のコメントを付けて出力する（--annotate）
+///
+/// `source_of` は対象オブジェクトの定義元（スキーマファイルなど）を返す。
+/// 付けるのはコメントのみのため、コメントを除けば `--sql-only` と同じSQLになる。
+pub(crate) fn render_annotated(
+    sql: &str,
+    config: &SqlOutputConfig,
+    source_of: impl Fn(&SqlObject) -> Option<String>,
+) -> String {
+    let statements: Vec<String> = split_sql_statements(sql)
+        .into_iter()
+        .filter(|statement| !strip_leading_comments(statement).is_empty())
+        .collect();
+    let total = statements.len();
+
+    let annotated: Vec<String> = statements
+        .iter()
+        .enumerate()
+        .map(|(i, statement)| {
+            // マイグレーションファイルのコメントは残し、注釈はステートメントの直前に置く
+            let body = strip_leading_comments(statement);
+            let comments = statement[..statement.len() - body.len()].trim_end();
+            let mut lines = Vec::new();
+            if !comments.is_empty() {
+                lines.push(comments.to_string());
+            }
+            lines.push(format!("-- statement {}/{}", i + 1, total));
+            match SqlObject::from_statement(body) {
+                Some(object) => {
+                    let source = source_of(&object).unwrap_or_else(|| "(none)".to_string());
+                    lines.push(format!("-- object: {}", object));
+                    lines.push(format!("-- source: {}", source));
+                }
+                None => lines.push("-- object: (unknown)".to_string()),
+            }
+            let destructive = if DESTRUCTIVE_SQL_REGEX.is_match(body) {
+                "yes"
+            } else {
+                "no"
+            };
+            lines.push(format!("-- destructive: {}", destructive));
+            lines.push(body.to_string());
+            lines.join("\n")
+        })
+        .collect();
+    render_for_output(&join_statements(&annotated), config)
+}
+
+/// 未適用のマイグレーションのSQLを出力する（apply --dry-run）
+///
+/// `--explain` / `--annotate` では各マイグレーションの前にバージョンと説明をコメントで付け、
+/// `--annotate` の定義元にはマイグレーションファイルを示す。
+pub(crate) fn render_migration_sql(
+    mode: DryRunSqlMode,
+    version: &str,
+    description: &str,
+    migration_file: &str,
+    sql: &str,
+    config: &SqlOutputConfig,
+) -> String {
+    let header = format!(
+        "-- Migration: {} - {}{}",
+        version,
+        description,
+        config.line_endings.as_str()
+    );
+    match mode {
+        DryRunSqlMode::Annotated => format!(
+            "{}{}",
+            header,
+            render_annotated(sql, config, |_| Some(migration_file.to_string()))
+        ),
+        DryRunSqlMode::SqlOnly { explain: true } => {
+            format!("{}{}", header, render_sql_only(sql, config, true))
+        }
+        DryRunSqlMode::SqlOnly { explain: false } | DryRunSqlMode::Report => {
+            render_sql_only(sql, config, false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LineEnding;
+
+    const SQL: &str = "-- Transaction: strata apply wraps this in a transaction automatically.\n\nCREATE TABLE users (id INTEGER);\n\n-- NOTE: keep ';' here\nCREATE INDEX idx_users_id ON users (id);\n\nALTER TABLE posts DROP COLUMN body;";
+
+    #[test]
+    fn test_sql_only_strips_comments() {
+        let sql = render_sql_only(SQL, &SqlOutputConfig::default(), false);
+
+        assert_eq!(
+            sql,
+            "CREATE TABLE users (id INTEGER);\n\nCREATE INDEX idx_users_id ON users (id);\n\nALTER TABLE posts DROP COLUMN body;"
+        );
+    }
+
+    #[test]
+    fn test_sql_only_explain_matches_file_without_bom() {
+        let config = SqlOutputConfig {
+            line_endings: LineEnding::Crlf,
+            bom: true,
+            statement_terminator: ";".to_string(),
+        };
+
+        let sql = render_sql_only(SQL, &config, true);
+
+        let file = render_sql_file(SQL, &config);
+        assert_eq!(sql, file.trim_start_matches('\u{feff}'));
+    }
+
+    #[test]
+    fn test_sql_only_uses_configured_terminator() {
+        let config = SqlOutputConfig {
+            statement_terminator: "\nGO".to_string(),
+            ..SqlOutputConfig::default()
+        };
+
+        let sql = render_sql_only(
+            "CREATE TABLE a (id INTEGER);\n\nDROP TABLE b;",
+            &config,
+            false,
+        );
+
+        assert_eq!(sql, "CREATE TABLE a (id INTEGER)\nGO\n\nDROP TABLE b\nGO");
+    }
+
+    #[test]
+    fn test_annotated_describes_each_statement() {
+        let annotated = render_annotated(SQL, &SqlOutputConfig::default(), |object| {
+            (object.kind == "table" || object.table.is_some())
+                .then(|| "schema/users.yaml".to_string())
+        });
+
+        assert!(annotated.contains(
+            "-- NOTE: keep ';' here\n-- statement 2/3\n-- object: index idx_users_id on users\n-- source: schema/users.yaml\n-- destructive: no\nCREATE INDEX"
+        ));
+        assert!(annotated.contains(
+            "-- statement 3/3\n-- object: table posts\n-- source: schema/users.yaml\n-- destructive: yes\nALTER TABLE posts DROP COLUMN body;"
+        ));
+
+        // コメントを除けば --sql-only と同じSQL
+        let without_comments: Vec<&str> = annotated
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("--"))
+            .collect();
+        assert_eq!(
+            without_comments.join("\n").trim_start(),
+            render_sql_only(SQL, &SqlOutputConfig::default(), false)
+        );
+    }
+
+    #[test]
+    fn test_sql_object_from_statement() {
+        let cases = [
+            (
+                "CREATE TABLE IF NOT EXISTS \"users\" (id INTEGER)",
+                Some("table users"),
+            ),
+            (
+                "ALTER TABLE `posts` ADD COLUMN title TEXT",
+                Some("table posts"),
+            ),
+            (
+                "CREATE UNIQUE INDEX idx_email ON users (email)",
+                Some("index idx_email on users"),
+            ),
+            ("CREATE TYPE status AS ENUM ('a')", Some("type status")),
+            (
+                "CREATE OR REPLACE VIEW active_users AS SELECT 1",
+                Some("view active_users"),
+            ),
+            (
+                "INSERT INTO new_users (id) SELECT id FROM users",
+                Some("table new_users"),
+            ),
+            ("PRAGMA foreign_keys = ON", None),
+        ];
+
+        for (statement, expected) in cases {
+            assert_eq!(
+                SqlObject::from_statement(statement)
+                    .map(|o| o.to_string())
+                    .as_deref(),
+                expected,
+                "{}",
+                statement
+            );
+        }
+    }
+}
@@ -6,21 +6,88 @@
 // - 変換: SchemaConversionService（services層）
 // - 出力: このモジュール（CLI層、YAMLシリアライズとファイル/標準出力）
 
-use crate::adapters::database_introspector::{create_introspector, DatabaseIntrospector};
-use crate::cli::command_context::CommandContext;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::adapters::database_introspector::{
+    create_introspector_for_config, is_permission_denied_error, DatabaseIntrospector,
+};
+use crate::adapters::sequence_values::{
+    SequenceValue, SequenceValueCollector, SequenceValuesFile, SEQUENCE_VALUES_FILE,
+};
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::export_changes::{check_change_files_writable, ExportChanges};
+use crate::cli::commands::export_ddl::{render_ddl_file, SchemaDdl, DDL_FILE_NAME};
+use crate::cli::commands::introspect::fetch_raw_table_info;
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
 use crate::cli::OutputFormat;
-use crate::core::config::Dialect;
+use crate::core::config::{Config, Dialect};
 use crate::core::schema::Schema;
-use crate::services::schema_conversion::{RawTableInfo, SchemaConversionService};
+use crate::services::identifier_case;
+use crate::services::schema_conversion::SchemaConversionService;
+use crate::services::schema_io::dto_converter::DtoConverterService;
+use crate::services::schema_io::schema_parser::SchemaParserService;
 use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+use crate::services::schema_validator::SchemaValidatorService;
 use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
 use serde::Serialize;
 use sqlx::AnyPool;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use tracing::{debug, warn};
+
+/// --split 時にビュー定義をまとめて出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_VIEWS_FILE_STEM: &str = "_views";
+
+/// --split 時にENUM定義をまとめて出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_ENUMS_FILE_STEM: &str = "_enums";
+
+/// --split 時に外部テーブルの宣言を出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_EXTERNAL_TABLES_FILE_STEM: &str = "_external_tables";
+
+/// 権限不足でオブジェクトをスキップし、--allow-partial が指定されていない場合の終了コード
+pub const EXIT_CODE_PARTIAL_EXPORT: i32 = 3;
+
+/// 権限不足によりエクスポートできなかったオブジェクト
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedObject {
+    /// オブジェクト名（ビュー・ENUMを一括で取得できなかった場合は `*`）
+    pub name: String,
+    /// スキップした理由（データベースのエラーメッセージ）
+    pub reason: String,
+}
+
+impl SkippedObject {
+    /// スキップしたオブジェクトを作成
+    pub fn new(name: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// データベースからのスキーマ抽出でスキップしたオブジェクト
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkippedObjects {
+    /// スキップしたテーブル
+    pub tables: Vec<SkippedObject>,
+    /// スキップしたビュー
+    pub views: Vec<SkippedObject>,
+    /// スキップしたENUM
+    pub enums: Vec<SkippedObject>,
+}
+
+impl SkippedObjects {
+    /// スキップしたオブジェクトがないか
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.views.is_empty() && self.enums.is_empty()
+    }
+
+    /// スキップしたオブジェクトの総数
+    pub fn len(&self) -> usize {
+        self.tables.len() + self.views.len() + self.enums.len()
+    }
+}
 
 /// exportコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
@@ -30,9 +97,30 @@ pub struct ExportOutput {
     /// エクスポートされたビュー一覧
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub views: Vec<String>,
+    /// 権限不足でスキップしたテーブル一覧
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_tables: Vec<SkippedObject>,
+    /// 権限不足でスキップしたビュー一覧
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_views: Vec<SkippedObject>,
+    /// 権限不足でスキップしたENUM一覧
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_enums: Vec<SkippedObject>,
     /// 出力先パス（Noneの場合はstdout）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_path: Option<String>,
+    /// --diff-against 指定時の前回のエクスポート結果からの変更
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<ExportChanges>,
+    /// --include-sequence-values 指定時に書き出した自動採番の現在値
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_values: Option<Vec<SequenceValue>>,
+    /// --from-schema --split 指定時に書き出したSQLファイル（実行順）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// データベースから読み込んだ名前のうち、識別子の規則に合わないものの警告
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
     /// テキスト出力メッセージ
     #[serde(skip)]
     pub text_message: String,
@@ -53,6 +141,8 @@ pub struct ExportCommand {
     pub config_path: Option<PathBuf>,
     /// 環境名
     pub env: String,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
     /// 出力先ディレクトリ（Noneの場合は標準出力）
     pub output_dir: Option<PathBuf>,
     /// 既存ファイルを確認なしで上書き
@@ -65,6 +155,14 @@ pub struct ExportCommand {
     pub tables: Vec<String>,
     /// エクスポートから除外するテーブル
     pub exclude_tables: Vec<String>,
+    /// 権限不足でスキップしたオブジェクトがあっても成功として終了する
+    pub allow_partial: bool,
+    /// 比較対象とする前回のエクスポート結果のディレクトリ
+    pub diff_against: Option<PathBuf>,
+    /// 自動採番の現在値を sequence_values.yaml に書き出す
+    pub include_sequence_values: bool,
+    /// データベースではなくスキーマ定義から、スキーマを作成するDDLを出力する
+    pub from_schema: bool,
 }
 
 /// exportコマンドハンドラー
@@ -104,25 +202,111 @@ impl ExportCommandHandler {
             ));
         }
 
+        // --diff-against は変更サマリーの出力先として --output が必要
+        if command.diff_against.is_some() && command.output_dir.is_none() {
+            return Err(anyhow!(
+                "--diff-against requires --output to specify the output directory."
+            ));
+        }
+
+        // --include-sequence-values は値ファイルの出力先として --output が必要
+        if command.include_sequence_values && command.output_dir.is_none() {
+            return Err(anyhow!(
+                "--include-sequence-values requires --output to specify the output directory."
+            ));
+        }
+
+        // --from-schema はデータベースを読まないため、データベースの内容に関するオプションは使えない
+        if command.from_schema {
+            for (enabled, option) in [
+                (command.allow_partial, "--allow-partial"),
+                (command.diff_against.is_some(), "--diff-against"),
+                (command.include_sequence_values, "--include-sequence-values"),
+            ] {
+                if enabled {
+                    return Err(anyhow!(
+                        "{} cannot be used with --from-schema, which does not read the database.",
+                        option
+                    ));
+                }
+            }
+        }
+
         // 設定ファイルを読み込む
         let context = CommandContext::load_with_config(
             command.project_path.clone(),
             command.config_path.clone(),
-        )?;
+        )?
+        .with_wait_timeout(command.wait_timeout);
+
+        // --from-schema はデータベースに接続しない
+        if command.from_schema {
+            return self.execute_from_schema(&context, command);
+        }
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    /// 読み込み済みのコンテキストでexportコマンドを実行
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &ExportCommand,
+    ) -> Result<String> {
         let config = &context.config;
 
+        // 出力先が前回のエクスポート結果と同じ場合に備え、書き込み前に読み込んでおく
+        let previous = match (&command.diff_against, &command.output_dir) {
+            (Some(previous_dir), Some(output_dir)) => {
+                check_change_files_writable(output_dir, command.force)?;
+                Some(self.load_previous_export(previous_dir)?)
+            }
+            _ => None,
+        };
+
+        if command.include_sequence_values {
+            if let Some(output_dir) = &command.output_dir {
+                let values_file = output_dir.join(SEQUENCE_VALUES_FILE);
+                if values_file.exists() && !command.force {
+                    return Err(anyhow!(
+                        "Output file already exists: {:?}\nUse --force to overwrite.",
+                        values_file
+                    ));
+                }
+            }
+        }
+
         // データベースに接続
         let pool = context.connect_pool(&command.env).await?;
 
         // データベースからスキーマ情報を取得
         debug!(dialect = ?config.dialect, "Extracting schema from database");
-        let mut schema = self
-            .extract_schema_from_database(&pool, config.dialect)
+        let introspector = create_introspector_for_config(config);
+        let (mut schema, mut skipped) = self
+            .extract_schema_from_database(introspector.as_ref(), &pool, config.dialect)
             .await
             .with_context(|| "Failed to get schema information")?;
 
+        // 外部テーブルとして宣言されたテーブルはエクスポートしない
+        self.exclude_external_tables(&mut schema, &self.load_external_tables(context));
+
         // テーブルフィルタリング
-        self.filter_tables(&mut schema, &command.tables, &command.exclude_tables)?;
+        self.filter_tables(
+            &mut schema,
+            &mut skipped.tables,
+            &command.tables,
+            &command.exclude_tables,
+            "database",
+        )?;
+
+        // データベースから読み込んだ名前も識別子の規則で検証する。
+        // 既存のデータベースをエクスポートできなくならないよう、エラーではなく警告にする
+        let identifier_warnings = self.identifier_warnings(&schema, config);
+        for warning in &identifier_warnings {
+            warn!("{}", warning);
+        }
 
         // テーブル名のリストを取得
         let mut table_names: Vec<String> = schema.tables.keys().cloned().collect();
@@ -135,35 +319,26 @@ impl ExportCommandHandler {
         debug!(
             tables = table_names.len(),
             views = view_names.len(),
+            skipped = skipped.len(),
             "Schema extracted successfully"
         );
 
         let serializer = SchemaSerializerService::new();
+        let mut changes = None;
+        let mut sequence_values = None;
 
         // 出力先に応じて処理
-        if let Some(output_dir) = &command.output_dir {
+        let (output_path, text_message) = if let Some(output_dir) = &command.output_dir {
             // ディレクトリに出力
             fs::create_dir_all(output_dir)
                 .with_context(|| format!("Failed to create output directory: {:?}", output_dir))?;
 
-            if command.split {
+            let output_path = if command.split {
                 // テーブルごとに個別ファイルに出力
                 self.write_split_files(&schema, &serializer, output_dir, command.force)
                     .with_context(|| "Failed to write split schema files")?;
 
-                let output = ExportOutput {
-                    tables: table_names.clone(),
-                    views: view_names.clone(),
-                    output_path: Some(output_dir.to_string_lossy().to_string()),
-                    text_message: self.format_export_summary(
-                        &table_names,
-                        &view_names,
-                        Some(output_dir),
-                        true,
-                    ),
-                };
-
-                render_output(&output, &command.format)
+                output_dir.clone()
             } else {
                 // 単一ファイルに出力
                 let yaml_content = serializer
@@ -183,72 +358,423 @@ impl ExportCommandHandler {
                 fs::write(&output_file, &yaml_content)
                     .with_context(|| format!("Failed to write schema file: {:?}", output_file))?;
 
-                let output = ExportOutput {
-                    tables: table_names.clone(),
-                    views: view_names.clone(),
-                    output_path: Some(output_file.to_string_lossy().to_string()),
-                    text_message: self.format_export_summary(
-                        &table_names,
-                        &view_names,
-                        Some(output_dir),
-                        false,
-                    ),
-                };
+                output_file
+            };
+
+            let mut text_message = self.format_export_summary(
+                &table_names,
+                &view_names,
+                Some(output_dir),
+                command.split,
+            );
+            text_message.push_str(&self.format_skipped_summary(&skipped));
+            text_message.push_str(&self.format_identifier_warnings(&identifier_warnings));
+
+            if command.include_sequence_values {
+                let values = self
+                    .write_sequence_values(&pool, config.dialect, &schema, output_dir)
+                    .await?;
+                text_message.push_str(&format!(
+                    "Sequence values: {} ({} table(s))\n",
+                    output_dir.join(SEQUENCE_VALUES_FILE).display(),
+                    values.len()
+                ));
+                sequence_values = Some(values);
+            }
 
-                render_output(&output, &command.format)
+            if let (Some((previous_schema, warnings)), Some(previous_dir)) =
+                (previous, &command.diff_against)
+            {
+                // 前回のファイルと同じ条件で比較するため、YAMLと同じDTO変換を通す
+                let converter = DtoConverterService::new();
+                let current_schema = converter.dto_to_schema(&converter.schema_to_dto(&schema));
+
+                let export_changes = ExportChanges::compare(
+                    previous_dir,
+                    &previous_schema,
+                    &current_schema,
+                    warnings,
+                );
+                export_changes.write_to(output_dir)?;
+                text_message.push_str(&self.format_changes_summary(&export_changes));
+                changes = Some(export_changes);
             }
+
+            (
+                Some(output_path.to_string_lossy().to_string()),
+                text_message,
+            )
         } else {
             // 標準出力に出力
             let yaml_content = serializer
                 .serialize_to_string(&schema)
                 .with_context(|| "Failed to serialize schema to YAML")?;
 
-            let output = ExportOutput {
-                tables: table_names,
-                views: view_names,
-                output_path: None,
-                text_message: yaml_content,
+            // YAMLを汚さないよう、スキップしたオブジェクトは標準エラーに表示する
+            if !skipped.is_empty() && matches!(command.format, OutputFormat::Text) {
+                eprint!("{}", self.format_skipped_summary(&skipped).yellow());
+            }
+            if !identifier_warnings.is_empty() && matches!(command.format, OutputFormat::Text) {
+                eprint!(
+                    "{}",
+                    self.format_identifier_warnings(&identifier_warnings)
+                        .yellow()
+                );
+            }
+
+            (None, yaml_content)
+        };
+
+        let output = ExportOutput {
+            tables: table_names,
+            views: view_names,
+            skipped_tables: skipped.tables.clone(),
+            skipped_views: skipped.views.clone(),
+            skipped_enums: skipped.enums.clone(),
+            output_path,
+            changes,
+            sequence_values,
+            files: Vec::new(),
+            warnings: identifier_warnings,
+            text_message,
+        };
+        let rendered = render_output(&output, &command.format)?;
+
+        self.check_partial_export(rendered, &skipped, command.allow_partial)
+    }
+
+    /// スキーマ定義からスキーマを作成するDDLを出力する（--from-schema）
+    ///
+    /// 識別子は generate と同じく `identifier_case` に従って変換し、
+    /// 生成したマイグレーションで作成されるものと同じ名前で出力します。
+    fn execute_from_schema(
+        &self,
+        context: &CommandContext,
+        command: &ExportCommand,
+    ) -> Result<String> {
+        let config = &context.config;
+
+        debug!(dialect = ?config.dialect, "Generating DDL from schema definitions");
+        let mut schema = context.load_schema(&SchemaLoadOptions::default())?;
+        self.filter_tables(
+            &mut schema,
+            &mut Vec::new(),
+            &command.tables,
+            &command.exclude_tables,
+            "schema definitions",
+        )?;
+        let schema = identifier_case::fold_schema_identifiers(&schema, config.identifier_case);
+
+        let ddl = SchemaDdl::generate(&schema, config)?;
+        let table_names: Vec<String> = schema.tables.keys().cloned().collect();
+        let view_names: Vec<String> = schema.views.keys().cloned().collect();
+
+        let mut files = Vec::new();
+        let (output_path, text_message) = if let Some(output_dir) = &command.output_dir {
+            let contents: Vec<(PathBuf, String)> = if command.split {
+                ddl.split_by_object()
+                    .into_iter()
+                    .map(|(stem, statements)| {
+                        (
+                            output_dir.join(format!("{}.sql", stem)),
+                            render_ddl_file(&statements, config),
+                        )
+                    })
+                    .collect()
+            } else {
+                vec![(
+                    output_dir.join(DDL_FILE_NAME),
+                    render_ddl_file(&ddl.statements, config),
+                )]
+            };
+
+            // --force でない場合、書き込み前に全ファイルの存在を一括チェック
+            if !command.force {
+                let existing_files: Vec<String> = contents
+                    .iter()
+                    .filter(|(path, _)| path.exists())
+                    .map(|(path, _)| format!("  - {:?}", path))
+                    .collect();
+                if !existing_files.is_empty() {
+                    return Err(anyhow!(
+                        "Output files already exist:\n{}\nUse --force to overwrite.",
+                        existing_files.join("\n")
+                    ));
+                }
+            }
+
+            fs::create_dir_all(output_dir)
+                .with_context(|| format!("Failed to create output directory: {:?}", output_dir))?;
+            for (path, content) in &contents {
+                fs::write(path, content)
+                    .with_context(|| format!("Failed to write DDL file: {:?}", path))?;
+                debug!(file = ?path, "Wrote DDL file");
+            }
+
+            if command.split {
+                files = contents
+                    .iter()
+                    .filter_map(|(path, _)| path.file_name())
+                    .map(|name| name.to_string_lossy().to_string())
+                    .collect();
+            }
+            let output_path = if command.split {
+                output_dir.clone()
+            } else {
+                output_dir.join(DDL_FILE_NAME)
             };
 
-            render_output(&output, &command.format)
+            (
+                Some(output_path.to_string_lossy().to_string()),
+                self.format_ddl_summary(&table_names, &view_names, output_dir, &files),
+            )
+        } else {
+            (None, render_ddl_file(&ddl.statements, config))
+        };
+
+        let output = ExportOutput {
+            tables: table_names,
+            views: view_names,
+            skipped_tables: Vec::new(),
+            skipped_views: Vec::new(),
+            skipped_enums: Vec::new(),
+            output_path,
+            changes: None,
+            sequence_values: None,
+            files,
+            warnings: Vec::new(),
+            text_message,
+        };
+        render_output(&output, &command.format)
+    }
+
+    /// --from-schema の出力結果のサマリーをフォーマット
+    ///
+    /// --split の場合は、ファイルを実行すべき順に一覧表示します。
+    pub fn format_ddl_summary(
+        &self,
+        table_names: &[String],
+        view_names: &[String],
+        output_dir: &Path,
+        files: &[String],
+    ) -> String {
+        let mut output = String::new();
+
+        output.push_str("=== Schema DDL Export Complete ===\n\n");
+        output.push_str(&format!(
+            "Tables: {}, views: {}\n\n",
+            table_names.len(),
+            view_names.len()
+        ));
+
+        if files.is_empty() {
+            output.push_str(&format!("Output: {:?}\n", output_dir.join(DDL_FILE_NAME)));
+        } else {
+            output.push_str(&format!(
+                "Output: {:?} (split mode, run the files in this order)\n",
+                output_dir
+            ));
+            for (i, file) in files.iter().enumerate() {
+                output.push_str(&format!("  {}. {}\n", i + 1, file));
+            }
+        }
+
+        output
+    }
+
+    /// エクスポートしたテーブルの自動採番の現在値を sequence_values.yaml に書き出す
+    ///
+    /// スキーマとは別のファイルのため、通常の差分検出には影響しません。
+    async fn write_sequence_values(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        schema: &Schema,
+        output_dir: &Path,
+    ) -> Result<Vec<SequenceValue>> {
+        let values: Vec<SequenceValue> = SequenceValueCollector::new(dialect)
+            .fetch_sequence_values(pool)
+            .await?
+            .into_iter()
+            .filter(|value| schema.tables.contains_key(&value.table))
+            .collect();
+
+        let output_file = output_dir.join(SEQUENCE_VALUES_FILE);
+        let yaml = SequenceValuesFile::new(values.clone()).to_yaml()?;
+        fs::write(&output_file, yaml)
+            .with_context(|| format!("Failed to write sequence values: {:?}", output_file))?;
+        debug!(file = ?output_file, count = values.len(), "Wrote sequence values");
+
+        Ok(values)
+    }
+
+    /// --diff-against で指定された前回のエクスポート結果を読み込む
+    ///
+    /// 別バージョンのstrataが出力したファイルも比較できるよう、未知のフィールドは
+    /// 無視して警告として返します。
+    ///
+    /// # Returns
+    ///
+    /// (前回のスキーマ, 警告のリスト)
+    fn load_previous_export(&self, previous_dir: &Path) -> Result<(Schema, Vec<String>)> {
+        if !previous_dir.is_dir() {
+            return Err(anyhow!(
+                "--diff-against must point to a previous export directory: {:?}",
+                previous_dir
+            ));
+        }
+
+        let parser = SchemaParserService::new();
+        let schema = parser
+            .parse_schema_directory(previous_dir)
+            .with_context(|| format!("Failed to read previous export: {:?}", previous_dir))?;
+
+        let warnings: Vec<String> = parser
+            .find_unknown_fields(previous_dir)?
+            .into_iter()
+            .map(|field| format!("Ignored unknown field in previous export: {}", field))
+            .collect();
+        for warning in &warnings {
+            warn!("{}", warning);
+        }
+
+        Ok((schema, warnings))
+    }
+
+    /// 前回のエクスポート結果からの変更件数を整形
+    pub fn format_changes_summary(&self, changes: &ExportChanges) -> String {
+        let mut output = format!("\nChanges since {}: ", changes.previous);
+        if changes.is_empty() {
+            output.push_str("none\n");
+        } else {
+            output.push_str(&format!(
+                "{} added, {} removed, {} modified\n",
+                changes.summary.added, changes.summary.removed, changes.summary.modified
+            ));
+        }
+        if !changes.warnings.is_empty() {
+            output.push_str(&format!(
+                "{}\n",
+                format!(
+                    "Warning: ignored {} unknown field(s) in the previous export (see CHANGES.md)",
+                    changes.warnings.len()
+                )
+                .yellow()
+            ));
+        }
+        output.push_str("Change summary: CHANGES.md, changes.json\n");
+        output
+    }
+
+    /// プロジェクトのスキーマ定義から `external_tables` の宣言を読み込む
+    ///
+    /// スキーマディレクトリがない、または解析できない場合は宣言なしとして扱い、
+    /// エクスポート自体は継続します。
+    pub(crate) fn load_external_tables(&self, context: &CommandContext) -> BTreeSet<String> {
+        let schema_dir = context.schema_dir();
+        if !schema_dir.exists() {
+            return BTreeSet::new();
+        }
+
+        match context.load_schema(&SchemaLoadOptions::default()) {
+            Ok(project_schema) => project_schema.external_tables,
+            Err(e) => {
+                warn!(
+                    error = format!("{:#}", e),
+                    "Could not read external_tables from schema directory"
+                );
+                BTreeSet::new()
+            }
+        }
+    }
+
+    /// 外部テーブルをエクスポート対象から除外し、宣言のみを出力に引き継ぐ
+    ///
+    /// 宣言を残すことで、外部テーブルを参照する外部キーがエクスポート後も検証を通ります。
+    pub(crate) fn exclude_external_tables(
+        &self,
+        schema: &mut Schema,
+        external_tables: &BTreeSet<String>,
+    ) {
+        for table_name in external_tables {
+            if schema.tables.remove(table_name).is_some() {
+                debug!(table = %table_name, "Skipping external table");
+            }
         }
+        schema.external_tables = external_tables.clone();
+    }
+
+    /// スキップしたオブジェクトがあれば、--allow-partial がない限り失敗として扱う
+    ///
+    /// 出力は表示したうえで `EXIT_CODE_PARTIAL_EXPORT` で終了させるため、
+    /// `CompletedWithFailure` エラーとして返します。
+    fn check_partial_export(
+        &self,
+        rendered: String,
+        skipped: &SkippedObjects,
+        allow_partial: bool,
+    ) -> Result<String> {
+        if skipped.is_empty() || allow_partial {
+            return Ok(rendered);
+        }
+
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "{} object(s) were skipped because of insufficient privileges, so the export is incomplete. Use --allow-partial to accept a partial export.",
+                skipped.len()
+            ),
+            exit_code: EXIT_CODE_PARTIAL_EXPORT,
+        }
+        .into())
     }
 
     /// テーブルフィルタリングを適用
+    ///
+    /// 権限不足でスキップしたテーブルも存在するテーブルとして扱い、同じ条件で絞り込みます。
+    /// `source` はテーブルが見つからない場合のエラーメッセージに表示する読み込み元です。
     fn filter_tables(
         &self,
         schema: &mut Schema,
+        skipped_tables: &mut Vec<SkippedObject>,
         tables: &[String],
         exclude_tables: &[String],
+        source: &str,
     ) -> Result<()> {
+        let exists = |schema: &Schema, name: &str| {
+            schema.tables.contains_key(name) || skipped_tables.iter().any(|t| t.name == name)
+        };
+
         if !tables.is_empty() {
             // --tables: 指定テーブルのみ残す
             let include_set: HashSet<&str> = tables.iter().map(|s| s.as_str()).collect();
 
             // 指定されたテーブルが存在するか確認
             for name in tables {
-                if !schema.tables.contains_key(name) {
-                    return Err(anyhow!("Table '{}' not found in database.", name));
+                if !exists(schema, name) {
+                    return Err(anyhow!("Table '{}' not found in {}.", name, source));
                 }
             }
 
             schema
                 .tables
                 .retain(|name, _| include_set.contains(name.as_str()));
+            skipped_tables.retain(|t| include_set.contains(t.name.as_str()));
         } else if !exclude_tables.is_empty() {
             // --exclude-tables: 指定テーブルを除外
             let exclude_set: HashSet<&str> = exclude_tables.iter().map(|s| s.as_str()).collect();
 
             // 指定されたテーブルが存在するか確認
             for name in exclude_tables {
-                if !schema.tables.contains_key(name) {
-                    return Err(anyhow!("Table '{}' not found in database.", name));
+                if !exists(schema, name) {
+                    return Err(anyhow!("Table '{}' not found in {}.", name, source));
                 }
             }
 
             schema
                 .tables
                 .retain(|name, _| !exclude_set.contains(name.as_str()));
+            skipped_tables.retain(|t| !exclude_set.contains(t.name.as_str()));
         }
 
         Ok(())
@@ -256,9 +782,11 @@ impl ExportCommandHandler {
 
     /// テーブルごとに個別YAMLファイルに出力
     ///
+    /// ビューはテーブル名と衝突しないよう `_views.yaml` にまとめて出力します。
+    /// ENUMは複数ファイルでの重複定義とならないよう `_enums.yaml` に一度だけ出力します。
     /// --force でない場合、書き込みを開始する前に全出力ファイルの存在を確認し、
     /// 一部だけ書き換わる不整合状態を防ぎます。
-    fn write_split_files(
+    pub(crate) fn write_split_files(
         &self,
         schema: &Schema,
         serializer: &SchemaSerializerService,
@@ -269,15 +797,44 @@ impl ExportCommandHandler {
         let mut table_names: Vec<&String> = schema.tables.keys().collect();
         table_names.sort();
 
+        let mut files: Vec<(String, Schema)> = Vec::new();
+        for table_name in table_names {
+            let table = schema.tables.get(table_name).unwrap();
+
+            // テーブル単体のSchemaを作成
+            let mut single_schema = Schema::new(schema.version.clone());
+            single_schema.enum_recreate_allowed = schema.enum_recreate_allowed;
+            single_schema.add_table(table.clone());
+
+            files.push((table_name.clone(), single_schema));
+        }
+
+        if !schema.enums.is_empty() {
+            let mut enums_schema = Schema::new(schema.version.clone());
+            enums_schema.enum_recreate_allowed = schema.enum_recreate_allowed;
+            enums_schema.enums = schema.enums.clone();
+            files.push((SPLIT_ENUMS_FILE_STEM.to_string(), enums_schema));
+        }
+
+        if !schema.views.is_empty() {
+            let mut views_schema = Schema::new(schema.version.clone());
+            views_schema.views = schema.views.clone();
+            files.push((SPLIT_VIEWS_FILE_STEM.to_string(), views_schema));
+        }
+
+        if !schema.external_tables.is_empty() {
+            let mut external_schema = Schema::new(schema.version.clone());
+            external_schema.external_tables = schema.external_tables.clone();
+            files.push((SPLIT_EXTERNAL_TABLES_FILE_STEM.to_string(), external_schema));
+        }
+
         // --force でない場合、書き込み前に全ファイルの存在を一括チェック
         if !force {
-            let mut existing_files = Vec::new();
-            for table_name in &table_names {
-                let output_file = output_dir.join(format!("{}.yaml", table_name));
-                if output_file.exists() {
-                    existing_files.push(output_file);
-                }
-            }
+            let existing_files: Vec<PathBuf> = files
+                .iter()
+                .map(|(stem, _)| output_dir.join(format!("{}.yaml", stem)))
+                .filter(|path| path.exists())
+                .collect();
             if !existing_files.is_empty() {
                 let file_list: Vec<String> = existing_files
                     .iter()
@@ -290,25 +847,17 @@ impl ExportCommandHandler {
             }
         }
 
-        for table_name in table_names {
-            let table = schema.tables.get(table_name).unwrap();
-
-            // テーブル単体のSchemaを作成
-            let mut single_schema = Schema::new(schema.version.clone());
-            single_schema.enum_recreate_allowed = schema.enum_recreate_allowed;
-            single_schema.enums = schema.enums.clone();
-            single_schema.add_table(table.clone());
-
+        for (stem, file_schema) in files {
             let yaml_content = serializer
-                .serialize_to_string(&single_schema)
-                .with_context(|| format!("Failed to serialize table '{}' to YAML", table_name))?;
+                .serialize_to_string(&file_schema)
+                .with_context(|| format!("Failed to serialize '{}' to YAML", stem))?;
 
-            let output_file = output_dir.join(format!("{}.yaml", table_name));
+            let output_file = output_dir.join(format!("{}.yaml", stem));
 
             fs::write(&output_file, &yaml_content)
                 .with_context(|| format!("Failed to write schema file: {:?}", output_file))?;
 
-            debug!(table = table_name, file = ?output_file, "Wrote split schema file");
+            debug!(file = ?output_file, "Wrote split schema file");
         }
 
         Ok(())
@@ -318,19 +867,27 @@ impl ExportCommandHandler {
     ///
     /// DatabaseIntrospector と SchemaConversionService を使用して
     /// データベースからスキーマ情報を取得し、内部モデルに変換します。
-    async fn extract_schema_from_database(
+    /// 権限不足で読み取れないテーブル・ビュー・ENUMはスキップして記録し、
+    /// それ以外のエラーが発生した場合は中断します。
+    pub(crate) async fn extract_schema_from_database(
         &self,
+        introspector: &dyn DatabaseIntrospector,
         pool: &AnyPool,
         dialect: Dialect,
-    ) -> Result<Schema> {
-        // イントロスペクターを作成
-        let introspector = create_introspector(dialect);
+    ) -> Result<(Schema, SkippedObjects)> {
+        let mut skipped = SkippedObjects::default();
 
         // ENUM定義を取得（PostgreSQLのみ）
-        let raw_enums = introspector
-            .get_enums(pool)
-            .await
-            .with_context(|| "Failed to get ENUM definitions")?;
+        let raw_enums = match introspector.get_enums(pool).await {
+            Ok(raw_enums) => raw_enums,
+            Err(e) => {
+                let reason = permission_denied_reason(dialect, e)
+                    .with_context(|| "Failed to get ENUM definitions")?;
+                warn!(reason = %reason, "Skipping ENUM definitions due to insufficient privileges");
+                skipped.enums.push(SkippedObject::new("*", reason));
+                Vec::new()
+            }
+        };
 
         // ENUM名のセットを作成（型変換で使用）
         let enum_names: HashSet<String> = raw_enums.iter().map(|e| e.name.clone()).collect();
@@ -347,18 +904,29 @@ impl ExportCommandHandler {
         // 各テーブルの情報を取得
         let mut raw_tables = Vec::new();
         for table_name in table_names {
-            let raw_table = self
-                .get_raw_table_info(introspector.as_ref(), pool, &table_name)
-                .await
-                .with_context(|| format!("Failed to get table info for '{}'", table_name))?;
-            raw_tables.push(raw_table);
+            match fetch_raw_table_info(introspector, pool, &table_name).await {
+                Ok(raw_table) => raw_tables.push(raw_table),
+                Err(e) => {
+                    let reason = permission_denied_reason(dialect, e).with_context(|| {
+                        format!("Failed to get table info for '{}'", table_name)
+                    })?;
+                    warn!(table = %table_name, reason = %reason, "Skipping table due to insufficient privileges");
+                    skipped.tables.push(SkippedObject::new(table_name, reason));
+                }
+            }
         }
 
         // View定義を取得
-        let raw_views = introspector
-            .get_views(pool)
-            .await
-            .with_context(|| "Failed to get view definitions")?;
+        let raw_views = match introspector.get_views(pool).await {
+            Ok(raw_views) => raw_views,
+            Err(e) => {
+                let reason = permission_denied_reason(dialect, e)
+                    .with_context(|| "Failed to get view definitions")?;
+                warn!(reason = %reason, "Skipping view definitions due to insufficient privileges");
+                skipped.views.push(SkippedObject::new("*", reason));
+                Vec::new()
+            }
+        };
 
         // マテリアライズドビューの警告を出力
         let materialized_views: Vec<&str> = raw_views
@@ -374,39 +942,11 @@ impl ExportCommandHandler {
         }
 
         // スキーマを構築（マテリアライズドビューは内部でフィルタリング）
-        conversion_service
+        let schema = conversion_service
             .build_schema_with_views(raw_tables, raw_enums, raw_views)
-            .with_context(|| "Failed to build schema from raw data")
-    }
+            .with_context(|| "Failed to build schema from raw data")?;
 
-    /// 単一テーブルの生情報を取得
-    async fn get_raw_table_info(
-        &self,
-        introspector: &dyn DatabaseIntrospector,
-        pool: &AnyPool,
-        table_name: &str,
-    ) -> Result<RawTableInfo> {
-        let columns = introspector
-            .get_columns(pool, table_name)
-            .await
-            .with_context(|| format!("Failed to get columns for '{}'", table_name))?;
-
-        let indexes = introspector
-            .get_indexes(pool, table_name)
-            .await
-            .with_context(|| format!("Failed to get indexes for '{}'", table_name))?;
-
-        let constraints = introspector
-            .get_constraints(pool, table_name)
-            .await
-            .with_context(|| format!("Failed to get constraints for '{}'", table_name))?;
-
-        Ok(RawTableInfo {
-            name: table_name.to_string(),
-            columns,
-            indexes,
-            constraints,
-        })
+        Ok((schema, skipped))
     }
 
     /// エクスポート結果のサマリーをフォーマット
@@ -452,6 +992,72 @@ impl ExportCommandHandler {
 
         output
     }
+
+    /// スキーマの名前を識別子の規則（`identifiers`）で検証し、違反を警告メッセージとして返す
+    fn identifier_warnings(&self, schema: &Schema, config: &Config) -> Vec<String> {
+        SchemaValidatorService::new()
+            .validate_identifiers(schema, Some(config.dialect), &config.identifiers)
+            .errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    /// 識別子の規則に合わない名前の警告を整形
+    pub fn format_identifier_warnings(&self, warnings: &[String]) -> String {
+        if warnings.is_empty() {
+            return String::new();
+        }
+        let mut output = format!(
+            "\nIdentifiers that will be rejected by validate and generate: {}\n\n",
+            warnings.len()
+        );
+        for warning in warnings {
+            output.push_str(&format!("  - {}\n", warning));
+        }
+        output
+    }
+
+    /// 権限不足でスキップしたオブジェクトのサマリーをフォーマット
+    ///
+    /// スキップしたオブジェクトがない場合は空文字列を返します。
+    pub fn format_skipped_summary(&self, skipped: &SkippedObjects) -> String {
+        let mut output = String::new();
+
+        for (label, objects) in [
+            ("tables", &skipped.tables),
+            ("views", &skipped.views),
+            ("enums", &skipped.enums),
+        ] {
+            if objects.is_empty() {
+                continue;
+            }
+            output.push_str(&format!(
+                "\nSkipped {} (insufficient privileges): {}\n\n",
+                label,
+                objects.len()
+            ));
+            for object in objects {
+                let name = if object.name == "*" {
+                    format!("(all {})", label)
+                } else {
+                    object.name.clone()
+                };
+                output.push_str(&format!("  - {}: {}\n", name, object.reason));
+            }
+        }
+
+        output
+    }
+}
+
+/// 権限不足のエラーであればスキップ理由を返し、それ以外のエラーはそのまま返す
+fn permission_denied_reason(dialect: Dialect, error: anyhow::Error) -> Result<String> {
+    if is_permission_denied_error(dialect, &error) {
+        Ok(error.root_cause().to_string())
+    } else {
+        Err(error)
+    }
 }
 
 #[cfg(test)]
@@ -582,8 +1188,10 @@ mod tests {
         handler
             .filter_tables(
                 &mut schema,
+                &mut Vec::new(),
                 &["users".to_string(), "posts".to_string()],
                 &[],
+                "database",
             )
             .unwrap();
 
@@ -603,7 +1211,13 @@ mod tests {
         schema.add_table(Table::new("comments".to_string()));
 
         handler
-            .filter_tables(&mut schema, &[], &["comments".to_string()])
+            .filter_tables(
+                &mut schema,
+                &mut Vec::new(),
+                &[],
+                &["comments".to_string()],
+                "database",
+            )
             .unwrap();
 
         assert_eq!(schema.tables.len(), 2);
@@ -619,10 +1233,19 @@ mod tests {
         let mut schema = Schema::new("1.0".to_string());
         schema.add_table(Table::new("users".to_string()));
 
-        let result = handler.filter_tables(&mut schema, &["nonexistent".to_string()], &[]);
+        let result = handler.filter_tables(
+            &mut schema,
+            &mut Vec::new(),
+            &["nonexistent".to_string()],
+            &[],
+            "schema definitions",
+        );
 
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("nonexistent"));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Table 'nonexistent' not found in schema definitions."
+        );
     }
 
     #[test]
@@ -633,11 +1256,52 @@ mod tests {
         schema.add_table(Table::new("users".to_string()));
         schema.add_table(Table::new("posts".to_string()));
 
-        handler.filter_tables(&mut schema, &[], &[]).unwrap();
+        handler
+            .filter_tables(&mut schema, &mut Vec::new(), &[], &[], "database")
+            .unwrap();
 
         assert_eq!(schema.tables.len(), 2);
     }
 
+    #[test]
+    fn test_filter_tables_applies_to_skipped_tables() {
+        use crate::core::schema::Table;
+        let handler = ExportCommandHandler::new();
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("users".to_string()));
+        let skipped = vec![
+            SkippedObject::new("secrets", "permission denied for table secrets"),
+            SkippedObject::new("audit", "permission denied for table audit"),
+        ];
+
+        // スキップしたテーブルも --tables で指定できる
+        let mut include_skipped = skipped.clone();
+        handler
+            .filter_tables(
+                &mut schema.clone(),
+                &mut include_skipped,
+                &["users".to_string(), "secrets".to_string()],
+                &[],
+                "database",
+            )
+            .unwrap();
+        assert_eq!(include_skipped, vec![skipped[0].clone()]);
+
+        // --exclude-tables で除外したテーブルはスキップ扱いにならない
+        let mut exclude_skipped = skipped.clone();
+        handler
+            .filter_tables(
+                &mut schema,
+                &mut exclude_skipped,
+                &[],
+                &["audit".to_string()],
+                "database",
+            )
+            .unwrap();
+        assert_eq!(exclude_skipped, vec![skipped[0].clone()]);
+        assert!(schema.tables.contains_key("users"));
+    }
+
     #[test]
     fn test_write_split_files_creates_per_table_files() {
         use crate::core::schema::Table;
@@ -717,12 +1381,89 @@ mod tests {
         assert!(content.contains("version:"));
     }
 
+    #[test]
+    fn test_write_split_files_preserves_views() {
+        use crate::core::schema::{Table, View};
+        use crate::services::schema_io::schema_parser::SchemaParserService;
+        use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+        use tempfile::TempDir;
+
+        let handler = ExportCommandHandler::new();
+        let serializer = SchemaSerializerService::new();
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().to_path_buf();
+
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("users".to_string()));
+        let mut view = View::new(
+            "active_users".to_string(),
+            "SELECT * FROM users".to_string(),
+        );
+        view.depends_on = vec!["users".to_string()];
+        schema.add_view(view);
+
+        handler
+            .write_split_files(&schema, &serializer, &output_dir, false)
+            .unwrap();
+
+        assert!(output_dir.join("_views.yaml").exists());
+        let restored = SchemaParserService::new()
+            .parse_schema_directory(&output_dir)
+            .unwrap();
+        assert_eq!(restored.views, schema.views);
+        assert_eq!(restored.tables, schema.tables);
+    }
+
+    #[test]
+    fn test_write_split_files_writes_enums_once() {
+        use crate::core::schema::{EnumDefinition, Table};
+        use crate::services::schema_io::schema_parser::SchemaParserService;
+        use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+        use tempfile::TempDir;
+
+        let handler = ExportCommandHandler::new();
+        let serializer = SchemaSerializerService::new();
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().to_path_buf();
+
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("users".to_string()));
+        schema.add_table(Table::new("posts".to_string()));
+        schema.add_enum(EnumDefinition {
+            name: "status".to_string(),
+            values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
+        });
+
+        handler
+            .write_split_files(&schema, &serializer, &output_dir, false)
+            .unwrap();
+
+        assert!(output_dir.join("_enums.yaml").exists());
+        let users = fs::read_to_string(output_dir.join("users.yaml")).unwrap();
+        assert!(!users.contains("enums:"));
+
+        // ENUMが重複定義とならずにディレクトリとして読み込める
+        let restored = SchemaParserService::new()
+            .parse_schema_directory(&output_dir)
+            .unwrap();
+        assert_eq!(restored.enums, schema.enums);
+        assert_eq!(restored.tables, schema.tables);
+    }
+
     #[test]
     fn test_export_output_json_serialization() {
         let output = ExportOutput {
             tables: vec!["users".to_string(), "posts".to_string()],
             views: Vec::new(),
+            skipped_tables: Vec::new(),
+            skipped_views: Vec::new(),
+            skipped_enums: Vec::new(),
             output_path: Some("/output/schema.yaml".to_string()),
+            changes: None,
+            sequence_values: None,
+            files: Vec::new(),
+            warnings: Vec::new(),
             text_message: "should not appear in JSON".to_string(),
         };
 
@@ -739,11 +1480,347 @@ mod tests {
         let output_no_path = ExportOutput {
             tables: vec!["users".to_string()],
             views: Vec::new(),
+            skipped_tables: Vec::new(),
+            skipped_views: Vec::new(),
+            skipped_enums: Vec::new(),
             output_path: None,
+            changes: None,
+            sequence_values: None,
+            files: Vec::new(),
+            warnings: Vec::new(),
             text_message: "text".to_string(),
         };
         let json2 = serde_json::to_string_pretty(&output_no_path).unwrap();
         let parsed2: serde_json::Value = serde_json::from_str(&json2).unwrap();
         assert!(parsed2.get("output_path").is_none());
     }
+
+    #[test]
+    fn test_export_output_json_includes_skipped_objects() {
+        let output = ExportOutput {
+            tables: vec!["users".to_string()],
+            views: Vec::new(),
+            skipped_tables: vec![SkippedObject::new(
+                "secrets",
+                "permission denied for table secrets",
+            )],
+            skipped_views: Vec::new(),
+            skipped_enums: vec![SkippedObject::new("*", "permission denied for schema app")],
+            output_path: None,
+            changes: None,
+            sequence_values: None,
+            files: Vec::new(),
+            warnings: Vec::new(),
+            text_message: String::new(),
+        };
+
+        let json = serde_json::to_string_pretty(&output).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed["skipped_tables"][0]["name"], "secrets");
+        assert_eq!(
+            parsed["skipped_tables"][0]["reason"],
+            "permission denied for table secrets"
+        );
+        assert_eq!(parsed["skipped_enums"][0]["name"], "*");
+        assert!(parsed.get("skipped_views").is_none());
+    }
+
+    #[test]
+    fn test_format_skipped_summary() {
+        let handler = ExportCommandHandler::new();
+        assert_eq!(
+            handler.format_skipped_summary(&SkippedObjects::default()),
+            ""
+        );
+
+        let skipped = SkippedObjects {
+            tables: vec![SkippedObject::new(
+                "secrets",
+                "permission denied for table secrets",
+            )],
+            views: vec![SkippedObject::new("*", "permission denied for view v")],
+            enums: Vec::new(),
+        };
+        let summary = handler.format_skipped_summary(&skipped);
+
+        assert!(summary.contains("Skipped tables (insufficient privileges): 1"));
+        assert!(summary.contains("  - secrets: permission denied for table secrets"));
+        assert!(summary.contains("Skipped views (insufficient privileges): 1"));
+        assert!(summary.contains("  - (all views): permission denied for view v"));
+        assert!(!summary.contains("Skipped enums"));
+    }
+
+    #[test]
+    fn test_check_partial_export() {
+        let handler = ExportCommandHandler::new();
+        let skipped = SkippedObjects {
+            tables: vec![SkippedObject::new("secrets", "permission denied")],
+            ..Default::default()
+        };
+
+        // スキップなし、または --allow-partial 指定時はそのまま成功
+        assert_eq!(
+            handler
+                .check_partial_export("out".to_string(), &SkippedObjects::default(), false)
+                .unwrap(),
+            "out"
+        );
+        assert_eq!(
+            handler
+                .check_partial_export("out".to_string(), &skipped, true)
+                .unwrap(),
+            "out"
+        );
+
+        // それ以外は出力を保持したまま専用の終了コードで失敗する
+        let error = handler
+            .check_partial_export("out".to_string(), &skipped, false)
+            .unwrap_err();
+        let completed = error.downcast_ref::<CompletedWithFailure>().unwrap();
+        assert_eq!(completed.output, "out");
+        assert_eq!(completed.exit_code, EXIT_CODE_PARTIAL_EXPORT);
+        assert!(completed.message.contains("--allow-partial"));
+    }
+
+    // ======================================
+    // 権限不足時のスキップ（モックイントロスペクター）
+    // ======================================
+
+    mod permission_denied {
+        use super::*;
+        use crate::adapters::database_introspector::{
+            RawColumnInfo, RawConstraintInfo, RawEnumInfo, RawIndexInfo, RawViewInfo,
+        };
+        use async_trait::async_trait;
+        use std::borrow::Cow;
+
+        /// SQLSTATE を返すテスト用のデータベースエラー
+        #[derive(Debug)]
+        struct MockDatabaseError {
+            code: &'static str,
+            message: &'static str,
+        }
+
+        impl std::fmt::Display for MockDatabaseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.message)
+            }
+        }
+
+        impl std::error::Error for MockDatabaseError {}
+
+        impl sqlx::error::DatabaseError for MockDatabaseError {
+            fn message(&self) -> &str {
+                self.message
+            }
+
+            fn code(&self) -> Option<Cow<'_, str>> {
+                Some(Cow::Borrowed(self.code))
+            }
+
+            fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+                self
+            }
+
+            fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+                self
+            }
+
+            fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+                self
+            }
+
+            fn kind(&self) -> sqlx::error::ErrorKind {
+                sqlx::error::ErrorKind::Other
+            }
+        }
+
+        fn database_error(code: &'static str, message: &'static str) -> anyhow::Error {
+            sqlx::Error::Database(Box::new(MockDatabaseError { code, message })).into()
+        }
+
+        fn permission_denied(message: &'static str) -> anyhow::Error {
+            database_error("42501", message)
+        }
+
+        /// 指定したテーブル・ビュー・ENUMの取得に失敗するイントロスペクター
+        #[derive(Default)]
+        struct MockIntrospector {
+            tables: Vec<&'static str>,
+            denied_tables: Vec<&'static str>,
+            broken_table: Option<&'static str>,
+            deny_views: bool,
+            deny_enums: bool,
+        }
+
+        #[async_trait]
+        impl DatabaseIntrospector for MockIntrospector {
+            async fn get_table_names(&self, _pool: &AnyPool) -> Result<Vec<String>> {
+                Ok(self.tables.iter().map(|t| t.to_string()).collect())
+            }
+
+            async fn get_columns(
+                &self,
+                _pool: &AnyPool,
+                table_name: &str,
+            ) -> Result<Vec<RawColumnInfo>> {
+                if self.denied_tables.contains(&table_name) {
+                    return Err(permission_denied("permission denied for table"));
+                }
+                if self.broken_table == Some(table_name) {
+                    return Err(database_error("08006", "connection failure"));
+                }
+                Ok(vec![RawColumnInfo {
+                    name: "id".to_string(),
+                    data_type: "integer".to_string(),
+                    is_nullable: false,
+                    default_value: None,
+                    char_max_length: None,
+                    numeric_precision: None,
+                    numeric_scale: None,
+                    datetime_precision: None,
+                    udt_name: Some("int4".to_string()),
+                    auto_increment: None,
+                    enum_values: None,
+                    set_values: None,
+                    is_unsigned: false,
+                    generation_expression: None,
+                    generated_stored: false,
+                    on_update_current_timestamp: false,
+                    comment: None,
+                }])
+            }
+
+            async fn get_indexes(
+                &self,
+                _pool: &AnyPool,
+                _table_name: &str,
+            ) -> Result<Vec<RawIndexInfo>> {
+                Ok(Vec::new())
+            }
+
+            async fn get_constraints(
+                &self,
+                _pool: &AnyPool,
+                _table_name: &str,
+            ) -> Result<Vec<RawConstraintInfo>> {
+                Ok(Vec::new())
+            }
+
+            async fn get_enums(&self, _pool: &AnyPool) -> Result<Vec<RawEnumInfo>> {
+                if self.deny_enums {
+                    return Err(permission_denied("permission denied for schema pg_catalog"));
+                }
+                Ok(Vec::new())
+            }
+
+            async fn get_views(&self, _pool: &AnyPool) -> Result<Vec<RawViewInfo>> {
+                if self.deny_views {
+                    return Err(permission_denied("permission denied for view reports"));
+                }
+                Ok(Vec::new())
+            }
+        }
+
+        /// 実際には接続しないプール（モックイントロスペクターは使用しない）
+        fn lazy_pool() -> AnyPool {
+            sqlx::any::install_default_drivers();
+            sqlx::any::AnyPoolOptions::new()
+                .connect_lazy("sqlite::memory:")
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_extract_schema_skips_permission_denied_tables() {
+            let introspector = MockIntrospector {
+                tables: vec!["users", "secrets", "posts"],
+                denied_tables: vec!["secrets"],
+                ..Default::default()
+            };
+
+            let (schema, skipped) = ExportCommandHandler::new()
+                .extract_schema_from_database(&introspector, &lazy_pool(), Dialect::PostgreSQL)
+                .await
+                .unwrap();
+
+            assert!(schema.tables.contains_key("users"));
+            assert!(schema.tables.contains_key("posts"));
+            assert!(!schema.tables.contains_key("secrets"));
+            assert_eq!(
+                skipped.tables,
+                vec![SkippedObject::new("secrets", "permission denied for table")]
+            );
+            assert!(skipped.views.is_empty());
+            assert!(skipped.enums.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_extract_schema_skips_permission_denied_views_and_enums() {
+            let introspector = MockIntrospector {
+                tables: vec!["users"],
+                deny_views: true,
+                deny_enums: true,
+                ..Default::default()
+            };
+
+            let (schema, skipped) = ExportCommandHandler::new()
+                .extract_schema_from_database(&introspector, &lazy_pool(), Dialect::PostgreSQL)
+                .await
+                .unwrap();
+
+            assert!(schema.tables.contains_key("users"));
+            assert!(skipped.tables.is_empty());
+            assert_eq!(
+                skipped.views,
+                vec![SkippedObject::new(
+                    "*",
+                    "permission denied for view reports"
+                )]
+            );
+            assert_eq!(
+                skipped.enums,
+                vec![SkippedObject::new(
+                    "*",
+                    "permission denied for schema pg_catalog"
+                )]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_extract_schema_aborts_on_other_errors() {
+            let introspector = MockIntrospector {
+                tables: vec!["users", "posts"],
+                broken_table: Some("posts"),
+                ..Default::default()
+            };
+
+            let error = ExportCommandHandler::new()
+                .extract_schema_from_database(&introspector, &lazy_pool(), Dialect::PostgreSQL)
+                .await
+                .unwrap_err();
+
+            assert!(
+                format!("{:#}", error).contains("Failed to get table info for 'posts'"),
+                "{:#}",
+                error
+            );
+        }
+
+        #[tokio::test]
+        async fn test_extract_schema_uses_dialect_specific_codes() {
+            // SQLSTATE 42501 は PostgreSQL 以外では権限不足として扱わない
+            let introspector = MockIntrospector {
+                tables: vec!["secrets"],
+                denied_tables: vec!["secrets"],
+                ..Default::default()
+            };
+
+            let result = ExportCommandHandler::new()
+                .extract_schema_from_database(&introspector, &lazy_pool(), Dialect::MySQL)
+                .await;
+
+            assert!(result.is_err());
+        }
+    }
 }
@@ -0,0 +1,400 @@
+// export --diff-against の変更サマリー
+//
+// 前回のエクスポート結果と今回のエクスポート結果を比較し、
+// 追加・削除・変更されたオブジェクトを CHANGES.md と changes.json に出力します。
+
+use crate::cli::commands::generate::summary::column_change_detail;
+use crate::core::schema::{Constraint, Schema};
+use crate::core::schema_diff::SchemaDiff;
+use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 人が読むための変更サマリーのファイル名
+pub const CHANGES_MARKDOWN_FILE: &str = "CHANGES.md";
+
+/// 機械可読な変更サマリーのファイル名
+pub const CHANGES_JSON_FILE: &str = "changes.json";
+
+/// 変更されたオブジェクトの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangedObjectType {
+    Table,
+    Enum,
+    View,
+}
+
+impl ChangedObjectType {
+    /// 種類名（出力用）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangedObjectType::Table => "table",
+            ChangedObjectType::Enum => "enum",
+            ChangedObjectType::View => "view",
+        }
+    }
+}
+
+/// 追加・削除・変更されたオブジェクト
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedObject {
+    /// オブジェクトの種類
+    #[serde(rename = "type")]
+    pub object_type: ChangedObjectType,
+    /// オブジェクト名
+    pub name: String,
+    /// 変更内容（変更されたオブジェクトのみ、1変更1行）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+impl ChangedObject {
+    fn new(object_type: ChangedObjectType, name: impl Into<String>) -> Self {
+        Self {
+            object_type,
+            name: name.into(),
+            details: Vec::new(),
+        }
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+
+    fn sort_key(&self) -> (&str, ChangedObjectType) {
+        (&self.name, self.object_type)
+    }
+}
+
+/// 変更されたオブジェクトの件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChangeCounts {
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+}
+
+/// 前回のエクスポート結果からの変更サマリー
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportChanges {
+    /// 比較対象とした前回のエクスポート結果のディレクトリ
+    pub previous: String,
+    /// 件数
+    pub summary: ChangeCounts,
+    /// 追加されたオブジェクト
+    pub added: Vec<ChangedObject>,
+    /// 削除されたオブジェクト
+    pub removed: Vec<ChangedObject>,
+    /// 変更されたオブジェクト
+    pub modified: Vec<ChangedObject>,
+    /// 前回のエクスポート結果を読み込む際の警告（無視したフィールドなど）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl ExportChanges {
+    /// 前回と今回のスキーマを比較して変更サマリーを作成
+    ///
+    /// 各一覧はオブジェクト名順に並べるため、同じ入力からは常に同じ出力になります。
+    pub fn compare(
+        previous_dir: &Path,
+        previous: &Schema,
+        current: &Schema,
+        warnings: Vec<String>,
+    ) -> Self {
+        let diff = SchemaDiffDetectorService::new().detect_diff(previous, current);
+        Self::from_diff(previous_dir, &diff, warnings)
+    }
+
+    /// スキーマ差分から変更サマリーを作成
+    pub fn from_diff(previous_dir: &Path, diff: &SchemaDiff, warnings: Vec<String>) -> Self {
+        use ChangedObjectType::{Enum, Table, View};
+
+        let mut added: Vec<ChangedObject> = Vec::new();
+        let mut removed: Vec<ChangedObject> = Vec::new();
+        let mut modified: Vec<ChangedObject> = Vec::new();
+
+        added.extend(
+            diff.added_tables
+                .iter()
+                .map(|t| ChangedObject::new(Table, &t.name)),
+        );
+        added.extend(
+            diff.added_enums
+                .iter()
+                .map(|e| ChangedObject::new(Enum, &e.name)),
+        );
+        added.extend(
+            diff.added_views
+                .iter()
+                .map(|v| ChangedObject::new(View, &v.name)),
+        );
+
+        removed.extend(
+            diff.removed_tables
+                .iter()
+                .map(|n| ChangedObject::new(Table, n)),
+        );
+        removed.extend(
+            diff.removed_enums
+                .iter()
+                .map(|n| ChangedObject::new(Enum, n)),
+        );
+        removed.extend(
+            diff.removed_views
+                .iter()
+                .map(|n| ChangedObject::new(View, n)),
+        );
+
+        for renamed in &diff.renamed_tables {
+            let details = vec![format!("~ renamed from {}", renamed.old_name)];
+            modified.push(ChangedObject::new(Table, &renamed.new_table.name).with_details(details));
+        }
+        for table_diff in &diff.modified_tables {
+            modified.push(
+                ChangedObject::new(Table, &table_diff.table_name)
+                    .with_details(table_change_details(table_diff)),
+            );
+        }
+        for enum_diff in &diff.modified_enums {
+            let mut details: Vec<String> = enum_diff
+                .added_values
+                .iter()
+                .map(|value| format!("+ value {}", value))
+                .collect();
+            details.extend(
+                enum_diff
+                    .removed_values
+                    .iter()
+                    .map(|value| format!("- value {}", value)),
+            );
+            if details.is_empty() {
+                details.push("~ values reordered".to_string());
+            }
+            modified.push(ChangedObject::new(Enum, &enum_diff.enum_name).with_details(details));
+        }
+        for renamed in &diff.renamed_enums {
+            let details = vec![format!("~ renamed from {}", renamed.old_name)];
+            modified.push(ChangedObject::new(Enum, &renamed.new_enum.name).with_details(details));
+        }
+        for renamed in &diff.renamed_views {
+            let details = vec![format!("~ renamed from {}", renamed.old_name)];
+            modified.push(ChangedObject::new(View, &renamed.new_view.name).with_details(details));
+        }
+        for view_diff in &diff.modified_views {
+            let details = vec!["~ definition".to_string()];
+            modified.push(ChangedObject::new(View, &view_diff.view_name).with_details(details));
+        }
+
+        for objects in [&mut added, &mut removed, &mut modified] {
+            objects.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        }
+
+        Self {
+            previous: previous_dir.display().to_string(),
+            summary: ChangeCounts {
+                added: added.len(),
+                removed: removed.len(),
+                modified: modified.len(),
+            },
+            added,
+            removed,
+            modified,
+            warnings,
+        }
+    }
+
+    /// 変更がないか
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// CHANGES.md の内容を生成
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Schema Changes\n\n");
+        md.push_str(&format!("Compared against: `{}`\n\n", self.previous));
+
+        if self.is_empty() {
+            md.push_str("No changes.\n");
+        } else {
+            md.push_str(&format!(
+                "{} added, {} removed, {} modified.\n",
+                self.summary.added, self.summary.removed, self.summary.modified
+            ));
+
+            for (heading, objects) in [
+                ("Added", &self.added),
+                ("Removed", &self.removed),
+                ("Modified", &self.modified),
+            ] {
+                if objects.is_empty() {
+                    continue;
+                }
+                md.push_str(&format!("\n## {}\n\n", heading));
+                for object in objects {
+                    md.push_str(&format!(
+                        "- {} `{}`\n",
+                        object.object_type.as_str(),
+                        object.name
+                    ));
+                    for detail in &object.details {
+                        md.push_str(&format!("  - {}\n", detail));
+                    }
+                }
+            }
+        }
+
+        if !self.warnings.is_empty() {
+            md.push_str("\n## Warnings\n\n");
+            for warning in &self.warnings {
+                md.push_str(&format!("- {}\n", warning));
+            }
+        }
+
+        md
+    }
+
+    /// 出力先ディレクトリに CHANGES.md と changes.json を書き込む
+    ///
+    /// # Returns
+    ///
+    /// 書き込んだファイルのパス
+    pub fn write_to(&self, output_dir: &Path) -> Result<Vec<PathBuf>> {
+        let markdown_path = output_dir.join(CHANGES_MARKDOWN_FILE);
+        fs::write(&markdown_path, self.to_markdown())
+            .with_context(|| format!("Failed to write {:?}", markdown_path))?;
+
+        let json_path = output_dir.join(CHANGES_JSON_FILE);
+        let json = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize change summary")?;
+        fs::write(&json_path, format!("{}\n", json))
+            .with_context(|| format!("Failed to write {:?}", json_path))?;
+
+        Ok(vec![markdown_path, json_path])
+    }
+}
+
+/// 変更サマリーの出力先に既存のファイルがないか確認
+///
+/// `force` が指定されていない場合、既存のファイルがあればエラーを返します。
+pub fn check_change_files_writable(output_dir: &Path, force: bool) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+    for file_name in [CHANGES_MARKDOWN_FILE, CHANGES_JSON_FILE] {
+        let path = output_dir.join(file_name);
+        if path.exists() {
+            return Err(anyhow!(
+                "Output file already exists: {:?}\nUse --force to overwrite.",
+                path
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// テーブルの変更内容を1変更1行で表す
+fn table_change_details(table_diff: &crate::core::schema_diff::TableDiff) -> Vec<String> {
+    let mut details = Vec::new();
+
+    for column in &table_diff.added_columns {
+        details.push(format!("+ column {}", column.name));
+    }
+    for column_name in &table_diff.removed_columns {
+        details.push(format!("- column {}", column_name));
+    }
+    for column_diff in &table_diff.modified_columns {
+        let changes: Vec<String> = column_diff
+            .changes
+            .iter()
+            .filter_map(column_change_detail)
+            .collect();
+        if changes.is_empty() {
+            details.push(format!("~ column {}", column_diff.column_name));
+        } else {
+            details.push(format!(
+                "~ column {} {}",
+                column_diff.column_name,
+                changes.join(", ")
+            ));
+        }
+    }
+    for renamed in &table_diff.renamed_columns {
+        details.push(format!(
+            "~ column {} -> {}",
+            renamed.old_name, renamed.new_column.name
+        ));
+    }
+    for index in &table_diff.added_indexes {
+        details.push(format!("+ index {}", index.name));
+    }
+    for index_name in &table_diff.removed_indexes {
+        details.push(format!("- index {}", index_name));
+    }
+    for index_diff in &table_diff.modified_indexes {
+        details.push(format!("~ index {}", index_diff.index_name));
+    }
+    for renamed in &table_diff.renamed_indexes {
+        details.push(format!(
+            "~ index {} -> {}",
+            renamed.old_name, renamed.new_index.name
+        ));
+    }
+    for constraint in &table_diff.added_constraints {
+        details.push(format!("+ constraint {}", constraint_label(constraint)));
+    }
+    for constraint in &table_diff.removed_constraints {
+        details.push(format!("- constraint {}", constraint_label(constraint)));
+    }
+
+    details
+}
+
+/// 制約を `<種別>(<カラム>)` 形式で表す
+fn constraint_label(constraint: &Constraint) -> String {
+    format!("{}({})", constraint.kind(), constraint.columns().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        let mut table = Table::new(name.to_string());
+        for column in columns {
+            table.add_column(Column::new(column.to_string(), ColumnType::TEXT, true));
+        }
+        table
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(table("users", &["id"]));
+
+        let changes = ExportChanges::compare(Path::new("prev"), &schema, &schema, vec![]);
+
+        assert!(changes.is_empty());
+        assert_eq!(changes.summary, ChangeCounts::default());
+        assert!(changes.to_markdown().contains("No changes."));
+    }
+
+    #[test]
+    fn test_markdown_lists_warnings() {
+        let schema = Schema::new("1.0".to_string());
+        let changes = ExportChanges::compare(
+            Path::new("prev"),
+            &schema,
+            &schema,
+            vec!["schema.yaml: generated_by".to_string()],
+        );
+
+        let markdown = changes.to_markdown();
+        assert!(markdown.contains("## Warnings\n\n- schema.yaml: generated_by\n"));
+    }
+}
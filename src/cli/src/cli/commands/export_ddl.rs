@@ -0,0 +1,140 @@
+// export --from-schema のDDL出力
+//
+// スキーマ定義（YAML）から、データベースに接続せずにスキーマ全体を作成するDDLを生成します。
+// 空のスキーマとの差分を通常のマイグレーション生成と同じパイプラインに通すため、
+// ENUM → テーブル（外部キーの依存順）→ インデックス → 循環参照の外部キー → ビュー の順に並び、
+// 同じスキーマからは常に同じSQLが出力されます。
+
+use crate::cli::commands::dry_run_sql::{
+    join_statements, render_sql_only, strip_leading_comments, SqlObject,
+};
+use crate::cli::commands::split_sql_statements;
+use crate::core::config::Config;
+use crate::core::schema::Schema;
+use crate::services::migration_generator::MigrationGeneratorService;
+use crate::services::schema_diff_detector::{SchemaDiffDetectorService, SchemaDiffOptions};
+use anyhow::{anyhow, Context, Result};
+
+/// --from-schema で単一ファイルに出力する際のファイル名
+pub(crate) const DDL_FILE_NAME: &str = "schema.sql";
+
+/// --split 時にENUM型の作成をまとめて出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_DDL_ENUMS_FILE_STEM: &str = "_enums";
+
+/// --split 時に循環参照のため後から追加する外部キーを出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_DDL_CONSTRAINTS_FILE_STEM: &str = "_constraints";
+
+/// --split 時にビューの作成をまとめて出力するファイル名（拡張子なし）
+pub(crate) const SPLIT_DDL_VIEWS_FILE_STEM: &str = "_views";
+
+/// スキーマ全体を作成するDDL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SchemaDdl {
+    /// 実行順に並んだステートメント（終端文字なし、コメントなし）
+    pub statements: Vec<String>,
+}
+
+impl SchemaDdl {
+    /// スキーマ定義からDDLを生成する
+    ///
+    /// `populate_from` は既存テーブルからデータを移すためのものなので、
+    /// スキーマを作成するだけのDDLには含めません。
+    pub(crate) fn generate(schema: &Schema, config: &Config) -> Result<Self> {
+        let mut schema = schema.clone();
+        for table in schema.tables.values_mut() {
+            table.populate_from = None;
+        }
+
+        let empty = Schema::new(schema.version.clone());
+        let (diff, _) = SchemaDiffDetectorService::new()
+            .with_options(SchemaDiffOptions::from_config(config))
+            .detect_diff_with_warnings(&empty, &schema);
+
+        let (up_sql, _) = MigrationGeneratorService::new()
+            .generate_up_sql_with_schemas(&diff, &empty, &schema, config.dialect, false)
+            .map_err(|e| anyhow!(e))
+            .with_context(|| "Failed to generate DDL from the schema definitions")?;
+
+        let statements = split_sql_statements(&up_sql)
+            .iter()
+            .map(|statement| strip_leading_comments(statement).to_string())
+            .filter(|statement| !statement.is_empty())
+            .collect();
+
+        Ok(Self { statements })
+    }
+
+    /// ステートメントを出力ファイルごとに振り分ける（--split）
+    ///
+    /// 返り値はファイル名（拡張子なし）とステートメントの組で、実行順に並びます。
+    /// - ENUM型: `_enums`
+    /// - テーブルとそのインデックス・外部キー: テーブル名
+    /// - 循環参照のため全テーブルの作成後に追加する外部キー: `_constraints`
+    /// - ビュー: `_views`
+    ///
+    /// 対象を判別できないステートメント（コメントの設定など）は直前のステートメントと同じファイルに出力します。
+    pub(crate) fn split_by_object(&self) -> Vec<(String, Vec<String>)> {
+        let mut enums = Vec::new();
+        let mut tables: Vec<(String, Vec<String>)> = Vec::new();
+        let mut constraints = Vec::new();
+        let mut views = Vec::new();
+        let mut last_stem: Option<String> = None;
+
+        for statement in &self.statements {
+            let stem = match SqlObject::from_statement(statement) {
+                Some(object) if object.kind == "type" => SPLIT_DDL_ENUMS_FILE_STEM.to_string(),
+                Some(object) if object.kind == "view" => SPLIT_DDL_VIEWS_FILE_STEM.to_string(),
+                Some(object) if object.kind == "index" => object.table.unwrap_or(object.name),
+                // テーブル作成の直後に続かない ALTER TABLE は、循環参照のため全テーブルの作成後に追加する外部キー
+                Some(object)
+                    if statement.to_ascii_uppercase().starts_with("ALTER")
+                        && last_stem.as_deref() != Some(object.name.as_str()) =>
+                {
+                    SPLIT_DDL_CONSTRAINTS_FILE_STEM.to_string()
+                }
+                Some(object) => object.name,
+                None => last_stem
+                    .clone()
+                    .unwrap_or_else(|| SPLIT_DDL_ENUMS_FILE_STEM.to_string()),
+            };
+
+            match stem.as_str() {
+                SPLIT_DDL_ENUMS_FILE_STEM => enums.push(statement.clone()),
+                SPLIT_DDL_CONSTRAINTS_FILE_STEM => constraints.push(statement.clone()),
+                SPLIT_DDL_VIEWS_FILE_STEM => views.push(statement.clone()),
+                table => match tables.iter_mut().find(|(name, _)| name == table) {
+                    Some((_, statements)) => statements.push(statement.clone()),
+                    None => tables.push((table.to_string(), vec![statement.clone()])),
+                },
+            }
+            last_stem = Some(stem);
+        }
+
+        let mut files = Vec::new();
+        if !enums.is_empty() {
+            files.push((SPLIT_DDL_ENUMS_FILE_STEM.to_string(), enums));
+        }
+        files.extend(tables);
+        if !constraints.is_empty() {
+            files.push((SPLIT_DDL_CONSTRAINTS_FILE_STEM.to_string(), constraints));
+        }
+        if !views.is_empty() {
+            files.push((SPLIT_DDL_VIEWS_FILE_STEM.to_string(), views));
+        }
+        files
+    }
+}
+
+/// ステートメントを `sql_output` 設定に従ってSQLファイルの内容にする
+///
+/// 生成日時などは含めず、同じステートメントからは常に同じ内容を出力します。
+pub(crate) fn render_ddl_file(statements: &[String], config: &Config) -> String {
+    let line_ending = config.sql_output.line_endings.as_str();
+    format!(
+        "-- Generated from the schema definitions by `strata export --from-schema` (dialect: {}).{}{}{}",
+        config.dialect,
+        line_ending,
+        line_ending,
+        render_sql_only(&join_statements(statements), &config.sql_output, false)
+    )
+}
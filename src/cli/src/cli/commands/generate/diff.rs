@@ -1,39 +1,95 @@
 use super::{DiffValidationResult, GenerateCommandHandler};
 use crate::cli::commands::destructive_change_formatter::DestructiveChangeFormatter;
+use crate::core::config::{Config, ViewRenamePropagation};
+use crate::core::error::{ErrorLocation, ValidationWarning, WarningKind};
 use crate::core::schema::Schema;
 use crate::services::destructive_change_detector::DestructiveChangeDetector;
+use crate::services::schema_diff_detector::SchemaDiffOptions;
+use crate::services::schema_io::schema_sources::SchemaSources;
+use crate::services::view_rename_propagation::{self, StaleView};
 use anyhow::{anyhow, Result};
 
 impl GenerateCommandHandler {
     /// 差分検出・バリデーション
     ///
-    /// 差分がない場合は `Ok(None)` を返す
+    /// 差分がない場合は `Ok(None)` を返す。
+    /// 検証エラーと破壊的変更の一覧には `sources` から定義元のファイルと行を付記する。
+    /// `declared_schema` はスキーマ定義ファイルに記述されたままのスキーマで、
+    /// 命名規約と renamed_from の警告に使用する（`current_schema` は identifier_case 適用後）。
     pub(super) fn detect_and_validate_diff(
         &self,
         command: &super::GenerateCommand,
+        config: &Config,
+        sources: &SchemaSources,
+        declared_schema: &Schema,
         current_schema: &Schema,
         previous_schema: &Schema,
     ) -> Result<Option<DiffValidationResult>> {
-        let (diff, diff_warnings) = self
-            .services
-            .diff_detector
-            .detect_diff_with_warnings(previous_schema, current_schema);
+        let diff_options = SchemaDiffOptions::from_config(config);
+        let (mut diff, diff_warnings) = self.timings.measure("diff", || {
+            self.services.diff_detector.detect_diff_with_warnings(
+                previous_schema,
+                current_schema,
+                &diff_options,
+            )
+        });
 
         if diff.is_empty() {
             return Ok(None);
         }
 
+        // リネームされたテーブル・カラムを旧名で参照しているビューの検出
+        let mut view_rewrite_warnings = Vec::new();
+        let stale_views =
+            view_rename_propagation::find_stale_views(previous_schema, current_schema, &diff);
+        if !stale_views.is_empty() {
+            let must_fail = config.view_rename_propagation == ViewRenamePropagation::Fail
+                || stale_views.iter().any(|stale| !stale.can_rewrite());
+            if must_fail {
+                return Err(anyhow!(format_stale_views_error(&stale_views)));
+            }
+            view_rename_propagation::apply_rewrites(
+                &mut diff,
+                previous_schema,
+                current_schema,
+                &stale_views,
+            );
+            view_rewrite_warnings.extend(stale_views.iter().map(|stale| {
+                ValidationWarning::new(
+                    format!(
+                        "View '{}' was rewritten automatically to follow renames ({}). Review the generated SQL and update the view definition in the schema file.",
+                        stale.view_name,
+                        stale.references.join(", ")
+                    ),
+                    Some(ErrorLocation::with_view(&stale.view_name)),
+                    WarningKind::Compatibility,
+                )
+            }));
+        }
+
         // 破壊的変更の検出
         let destructive_detector = DestructiveChangeDetector::new();
         let destructive_report = destructive_detector.detect(&diff);
 
         // リネーム検証
-        let rename_validation = self
-            .services
-            .validator
-            .validate_renames_with_old_schema(previous_schema, current_schema);
+        let mut rename_validation = self.timings.measure("validate", || {
+            self.services
+                .validator
+                .validate_renames_with_old_schema(previous_schema, current_schema)
+        });
+        sources.attach_files(&mut rename_validation);
+
+        let renamed_from_warnings = self.generate_renamed_from_remove_warnings(declared_schema);
 
-        let renamed_from_warnings = self.generate_renamed_from_remove_warnings(current_schema);
+        // 命名規約の違反は警告として表示（生成は続行）
+        let naming_warnings = if config.naming.enabled {
+            self.services
+                .validator
+                .validate_naming_conventions(declared_schema, &config.naming)
+                .warnings
+        } else {
+            Vec::new()
+        };
 
         // マイグレーション名の生成
         let timestamp = self.services.generator.generate_timestamp();
@@ -55,12 +111,101 @@ impl GenerateCommandHandler {
             ));
         }
 
+        // 外部テーブルとして宣言したテーブルが通常のテーブルとしても定義されている場合は処理を中止
+        let mut external_validation = self.timings.measure("validate", || {
+            self.services
+                .validator
+                .validate_external_tables(current_schema)
+        });
+        sources.attach_files(&mut external_validation);
+        if !external_validation.is_valid() {
+            return Err(anyhow!(
+                "External table validation errors:\n{}",
+                external_validation.errors_to_string()
+            ));
+        }
+
+        // 識別子に使用できない文字が含まれる、または方言の最大長を超える場合は処理を中止
+        let mut identifier_validation = self.timings.measure("validate", || {
+            self.services.validator.validate_identifiers(
+                current_schema,
+                config.dialect,
+                &config.identifiers,
+            )
+        });
+        sources.attach_files(&mut identifier_validation);
+        if !identifier_validation.is_valid() {
+            return Err(anyhow!(
+                "Identifier validation errors:\n{}",
+                identifier_validation.errors_to_string()
+            ));
+        }
+
+        // 部分インデックスの条件式が不正、または方言が部分インデックスに対応していない場合は処理を中止
+        let mut index_validation = self.timings.measure("validate", || {
+            self.services
+                .validator
+                .validate_index_predicates(current_schema, config.dialect)
+        });
+        sources.attach_files(&mut index_validation);
+        if !index_validation.is_valid() {
+            return Err(anyhow!(
+                "Index validation errors:\n{}",
+                index_validation.errors_to_string()
+            ));
+        }
+
+        // 方言が受け付けないデフォルト値の式がある場合は処理を中止（適用時のエラーを防ぐ）
+        let mut default_validation = self.timings.measure("validate", || {
+            let validator = &self.services.validator;
+            let mut result = validator.validate_default_expressions(current_schema, config.dialect);
+            result.merge(
+                validator.validate_current_timestamp_defaults(current_schema, config.dialect),
+            );
+            result
+        });
+        sources.attach_files(&mut default_validation);
+        if !default_validation.is_valid() {
+            return Err(anyhow!(
+                "Default value validation errors:\n{}",
+                default_validation.errors_to_string()
+            ));
+        }
+
+        // 生成カラムの定義が不正な場合は処理を中止
+        let mut generated_validation = self.timings.measure("validate", || {
+            self.services
+                .validator
+                .validate_generated_columns(current_schema, config.dialect)
+        });
+        sources.attach_files(&mut generated_validation);
+        if !generated_validation.is_valid() {
+            return Err(anyhow!(
+                "Generated column validation errors:\n{}",
+                generated_validation.errors_to_string()
+            ));
+        }
+
+        // 設定で必須とされたメタデータがないテーブルがある場合は処理を中止
+        let mut metadata_validation = self.timings.measure("validate", || {
+            self.services
+                .validator
+                .validate_required_metadata(current_schema, &config.required_metadata)
+        });
+        sources.attach_files(&mut metadata_validation);
+        if !metadata_validation.is_valid() {
+            return Err(anyhow!(
+                "Required metadata validation errors:\n{}",
+                metadata_validation.errors_to_string()
+            ));
+        }
+
         // 破壊的変更がある場合はデフォルト拒否
         if destructive_report.has_destructive_changes()
             && !command.allow_destructive
             && !command.dry_run
         {
-            let formatter = DestructiveChangeFormatter::new();
+            let formatter = DestructiveChangeFormatter::new().with_sources(sources.clone());
             return Err(anyhow!(
                 formatter.format_error(&destructive_report, "strata generate")
             ));
@@ -72,6 +217,8 @@ impl GenerateCommandHandler {
             destructive_report,
             rename_validation,
             renamed_from_warnings,
+            naming_warnings,
+            view_rewrite_warnings,
             migration_name,
             timestamp,
             sanitized_description,
@@ -103,6 +250,29 @@ impl GenerateCommandHandler {
                     ));
                 }
             }
+            for index in &table.indexes {
+                if index.renamed_from.is_some() {
+                    warnings.push(ValidationWarning::renamed_from_remove_recommendation(
+                        format!(
+                            "Index '{}' on table '{}' still has 'renamed_from' attribute. Consider removing it after migration is applied.",
+                            index.name, table_name
+                        ),
+                        Some(ErrorLocation::with_table(table_name.clone())),
+                    ));
+                }
+            }
+        }
+
+        for enum_def in schema.enums.values() {
+            if enum_def.renamed_from.is_some() {
+                warnings.push(ValidationWarning::renamed_from_remove_recommendation(
+                    format!(
+                        "ENUM '{}' still has 'renamed_from' attribute. Consider removing it after migration is applied.",
+                        enum_def.name
+                    ),
+                    Some(ErrorLocation::with_enum(&enum_def.name)),
+                ));
+            }
         }
 
         warnings
@@ -126,3 +296,25 @@ impl GenerateCommandHandler {
         }
     }
 }
+
+/// 旧名を参照したままのビューの一覧をエラーメッセージに整形
+fn format_stale_views_error(stale_views: &[StaleView]) -> String {
+    let mut message = String::from(
+        "The following views still reference renamed tables or columns. Update their definitions in the same change:\n",
+    );
+    for stale in stale_views {
+        message.push_str(&format!("  - {}\n", stale.view_name));
+        for reference in &stale.references {
+            message.push_str(&format!("      {}\n", reference));
+        }
+        for blocker in &stale.blockers {
+            message.push_str(&format!("      cannot rewrite: {}\n", blocker));
+        }
+    }
+    if stale_views.iter().all(|stale| stale.can_rewrite()) {
+        message.push_str(
+            "Alternatively, set 'view_rename_propagation: rewrite' in the config file to rewrite these views automatically.",
+        );
+    }
+    message.trim_end().to_string()
+}
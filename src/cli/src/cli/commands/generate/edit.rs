@@ -0,0 +1,94 @@
+use super::{DiffValidationResult, GenerateCommand, GenerateCommandHandler, GeneratedSql};
+use crate::cli::commands::sql_editor::EditOutcome;
+use crate::cli::commands::sql_parser::find_unterminated_sql;
+use crate::cli::commands::{split_sql_statements, DESTRUCTIVE_SQL_REGEX};
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+
+impl GenerateCommandHandler {
+    /// 生成したSQLをエディタで編集し、編集後の内容で置き換える（`--edit`）
+    ///
+    /// 編集が取りやめられた場合はその理由を返します。
+    /// 編集で追加された破壊的なステートメントは `--allow-destructive` を必要とし、
+    /// 破壊的変更レポートに記録します（適用時にも `--allow-destructive` が必要になる）。
+    pub(super) fn edit_generated_sql(
+        &self,
+        command: &GenerateCommand,
+        dvr: &mut DiffValidationResult,
+        generated: &mut GeneratedSql,
+    ) -> Result<Option<String>> {
+        let (up_sql, down_sql) = match self.sql_editor.edit(
+            &dvr.migration_name,
+            &generated.up_sql,
+            &generated.down_sql,
+        )? {
+            EditOutcome::Edited { up_sql, down_sql } => (up_sql, down_sql),
+            EditOutcome::Abandoned(reason) => return Ok(Some(reason)),
+        };
+
+        for (name, sql) in [("up.sql", &up_sql), ("down.sql", &down_sql)] {
+            if let Some(kind) = find_unterminated_sql(sql) {
+                return Err(anyhow!(
+                    "The edited {} has an unterminated {}. No migration files were written.",
+                    name,
+                    kind
+                ));
+            }
+            if split_sql_statements(sql).is_empty() {
+                return Ok(Some(format!("{} has no executable statements", name)));
+            }
+        }
+
+        let added = added_destructive_statements(&generated.up_sql, &up_sql);
+        if !added.is_empty() && !command.allow_destructive {
+            return Err(anyhow!(
+                "The edited up.sql contains destructive statements that were not generated:\n{}\n\nTo proceed, run again with --allow-destructive.",
+                added
+                    .iter()
+                    .map(|statement| format!("  - {}", statement))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+        dvr.destructive_report.edited_statements = added;
+
+        generated.up_sql = up_sql;
+        generated.down_sql = down_sql;
+        Ok(None)
+    }
+}
+
+/// 編集で追加された破壊的なステートメント（生成時のステートメントにないもの）
+fn added_destructive_statements(generated: &str, edited: &str) -> Vec<String> {
+    let generated: HashSet<String> = split_sql_statements(generated)
+        .iter()
+        .map(|statement| normalize(statement))
+        .collect();
+    split_sql_statements(edited)
+        .iter()
+        .map(|statement| normalize(statement))
+        .filter(|statement| DESTRUCTIVE_SQL_REGEX.is_match(statement))
+        .filter(|statement| !generated.contains(statement))
+        .collect()
+}
+
+/// 空白の違いを無視して比較するため、連続する空白を1つにまとめる
+fn normalize(statement: &str) -> String {
+    statement.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_added_destructive_statements_ignores_generated_ones() {
+        let generated = "ALTER TABLE users DROP COLUMN age;\nCREATE INDEX idx ON users (name);";
+        let edited = "ALTER TABLE users\n    DROP COLUMN age;\nDELETE FROM sessions;\nCREATE INDEX idx ON users (name);";
+
+        assert_eq!(
+            added_destructive_statements(generated, edited),
+            vec!["DELETE FROM sessions".to_string()]
+        );
+    }
+}
@@ -1,104 +1,132 @@
 use super::{DiffValidationResult, GenerateCommand, GenerateCommandHandler, GeneratedSql};
 use crate::cli::command_context::CommandContext;
-use crate::cli::commands::migration_loader;
+use crate::cli::commands::sql_output::{
+    calculate_sql_file_content_checksum, render_sql_file, write_sql_file,
+};
+use crate::cli::commands::stacked_migrations::{self, PendingMigration};
+use crate::cli::commands::{migration_loader, migration_writer, retained_tables};
 use crate::core::config::Config;
 use crate::core::schema::Schema;
+use crate::services::identifier_case;
+use crate::services::preflight_queries::{self, PREFLIGHT_FILE};
 use crate::services::schema_checksum::SchemaChecksumService;
-use crate::services::schema_io::schema_parser::SchemaParserService;
-use crate::services::schema_io::schema_serializer::SchemaSerializerService;
-use anyhow::{Context, Result};
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
+use anyhow::{anyhow, Context, Result};
+use colored::Colorize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
 impl GenerateCommandHandler {
-    /// スキーマの読み込み
+    /// 未来の日時のバージョンを持つマイグレーションを検出し、警告メッセージを返す
     ///
-    /// `schema_dir_override` が指定されている場合はそちらを優先する。
-    /// 指定されていない場合は設定ファイルのschema_dirを使用する。
-    pub(super) fn load_schemas(
+    /// マイグレーションディレクトリが存在しない場合は空のリストを返す。
+    pub(super) fn check_future_migrations(&self, context: &CommandContext) -> Result<Vec<String>> {
+        let migrations_dir = context.migrations_dir();
+        if !migrations_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let migrations = migration_loader::load_available_migrations(&migrations_dir)?;
+        Ok(migration_loader::check_future_migrations(
+            &migrations,
+            context.config.future_version_tolerance(),
+        ))
+    }
+
+    /// スナップショットの識別子が `identifier_case` に従っているかを確認し、警告メッセージを返す
+    ///
+    /// 規則を途中で変更した場合、既存のオブジェクトは以前の規則の名前のまま残っているため、
+    /// 差分ではそれらを削除して作り直すことになる。
+    pub(super) fn check_identifier_case(
         &self,
-        context: &CommandContext,
-        project_path: &Path,
         config: &Config,
-        schema_dir_override: Option<&PathBuf>,
-    ) -> Result<(Schema, Schema)> {
-        let schema_dir = if let Some(override_dir) = schema_dir_override {
-            if !override_dir.exists() {
-                return Err(anyhow::anyhow!(
-                    "Schema directory not found: {:?}",
-                    override_dir
-                ));
-            }
-            override_dir.clone()
-        } else {
-            context.require_schema_dir()?
-        };
-        let parser = SchemaParserService::new();
-        let current_schema = parser
-            .parse_schema_directory(&schema_dir)
-            .with_context(|| "Failed to read schema")?;
-        let previous_schema = self.load_previous_schema(project_path, config)?;
-        Ok((current_schema, previous_schema))
+        previous_schema: &Schema,
+    ) -> Option<String> {
+        let mismatched =
+            identifier_case::find_mismatched_identifiers(previous_schema, config.identifier_case);
+        if mismatched.is_empty() {
+            return None;
+        }
+
+        const MAX_SHOWN: usize = 5;
+        let mut examples = mismatched
+            .iter()
+            .take(MAX_SHOWN)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if mismatched.len() > MAX_SHOWN {
+            examples.push_str(", ...");
+        }
+        Some(format!(
+            "Warning: identifier_case is '{}', but {} identifier(s) in the schema snapshot do not follow it ({}).\nThey were probably created under a different identifier_case, so this migration will drop and recreate them.\nRename the existing objects in a manual migration and refresh the snapshot with `strata baseline --force`, or set identifier_case back.",
+            config.identifier_case,
+            mismatched.len(),
+            examples
+        ))
     }
 
-    /// 前回のスキーマ状態を読み込む
+    /// 生成後にまだ適用していないマイグレーションを検出し、標準エラーに一覧を表示する
     ///
-    /// マイグレーションディレクトリ内のper-migrationスナップショットから前回のスキーマを復元する。
-    /// 最新のマイグレーションディレクトリにある `.schema_snapshot.yaml` を優先的に使用し、
-    /// 存在しない場合はグローバルスナップショットにフォールバックする。
-    /// これにより、失敗したマイグレーションのディレクトリが削除された場合でも
-    /// 正しいスキーマ状態を復元できる。
-    pub(super) fn load_previous_schema(
+    /// 判定はデータベースに接続せず、generate が記録し apply / rollback が更新する
+    /// `.pending_migrations.yaml` とマイグレーションディレクトリを突き合わせて行う。
+    /// `require_clean_pending` が有効で `--allow-stacked` が指定されていない場合はエラーを返す。
+    pub(super) fn check_pending_migrations(
         &self,
-        project_path: &Path,
-        config: &Config,
-    ) -> Result<Schema> {
-        let migrations_dir = project_path.join(&config.migrations_dir);
-        let parser = SchemaParserService::new();
-
-        // マイグレーションディレクトリが存在する場合、per-migrationスナップショットを探す
-        if migrations_dir.exists() {
-            let migrations = migration_loader::load_available_migrations(&migrations_dir)
-                .with_context(|| {
-                    format!(
-                        "Failed to load available migrations from: {:?}",
-                        migrations_dir
-                    )
-                })?;
-
-            // 最新のマイグレーションから順にper-migrationスナップショットを探す
-            for (_version, _description, migration_path) in migrations.iter().rev() {
-                let per_migration_snapshot = migration_path.join(".schema_snapshot.yaml");
-                if per_migration_snapshot.exists() {
-                    debug!(
-                        snapshot = %per_migration_snapshot.display(),
-                        "Loading previous schema from per-migration snapshot"
-                    );
-                    return parser
-                        .parse_schema_file(&per_migration_snapshot)
-                        .with_context(|| {
-                            format!(
-                                "Failed to parse per-migration schema snapshot: {:?}",
-                                per_migration_snapshot
-                            )
-                        });
-                }
-            }
+        context: &CommandContext,
+        command: &GenerateCommand,
+    ) -> Result<Vec<PendingMigration>> {
+        let pending = stacked_migrations::find_pending_migrations(&context.migrations_dir())?;
+        if pending.is_empty() {
+            return Ok(pending);
         }
 
-        // per-migrationスナップショットが見つからない場合、グローバルスナップショットにフォールバック
-        let global_snapshot_path = migrations_dir.join(".schema_snapshot.yaml");
-        if global_snapshot_path.exists() {
-            debug!("Falling back to global schema snapshot");
-            return parser
-                .parse_schema_file(&global_snapshot_path)
-                .with_context(|| "Failed to parse schema snapshot");
+        let list: String = pending
+            .iter()
+            .map(|m| format!("  - {} {}\n", m.version, m.description))
+            .collect();
+
+        let require_clean = command.require_clean_pending || context.config.require_clean_pending;
+        if require_clean && !command.allow_stacked {
+            return Err(anyhow!(
+                "{} migration(s) have been generated but not applied yet:\n{}\nApply them first, or pass --allow-stacked to generate a new migration on top of them.",
+                pending.len(),
+                list
+            ));
         }
 
-        // 初回の場合は空のスキーマを返す
-        debug!("No schema snapshot found, using empty schema");
-        Ok(Schema::new("1.0".to_string()))
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: {} migration(s) have been generated but not applied yet:\n{}The schema snapshot already includes their changes, so the new migration will only contain changes made after them.",
+                pending.len(),
+                list
+            )
+            .yellow()
+            .bold()
+        );
+
+        Ok(pending)
+    }
+
+    /// 前回のスキーマ状態を組み立てる
+    ///
+    /// スナップショットから復元したスキーマに、ロールバック保護によりデータベースに
+    /// 残されたテーブル（`.rollback_retained.yaml`）を加える。
+    /// これにより、ロールバック後に再生成しても残っているテーブルを再作成しない。
+    pub(crate) fn merge_retained_into_previous(
+        &self,
+        context: &CommandContext,
+        mut snapshot: Schema,
+    ) -> Result<Schema> {
+        let retained = retained_tables::load_retained_tables(&context.migrations_dir())?;
+        let merged = retained_tables::merge_retained_tables(&mut snapshot, retained);
+        if !merged.is_empty() {
+            debug!(tables = ?merged, "Added tables retained by rollback protection");
+        }
+
+        Ok(snapshot)
     }
 
     /// マイグレーションディレクトリ内にスキーマスナップショットを保存
@@ -106,24 +134,12 @@ impl GenerateCommandHandler {
     /// 各マイグレーションディレクトリに `.schema_snapshot.yaml` を保存することで、
     /// マイグレーションディレクトリが削除された場合にも正しいスキーマ状態を復元できる。
     fn save_migration_schema_snapshot(&self, migration_dir: &Path, schema: &Schema) -> Result<()> {
-        let snapshot_path = migration_dir.join(".schema_snapshot.yaml");
-
-        let serializer = SchemaSerializerService::new();
-        let yaml = serializer
-            .serialize_to_string(schema)
-            .with_context(|| "Failed to serialize schema for per-migration snapshot")?;
-
-        fs::write(&snapshot_path, yaml).with_context(|| {
-            format!(
-                "Failed to write per-migration schema snapshot: {:?}",
-                snapshot_path
-            )
-        })?;
-
-        Ok(())
+        schema_snapshot::write_snapshot(&migration_dir.join(SNAPSHOT_FILE), schema)
     }
 
     /// 現在のスキーマを保存（新構文形式を使用）
+    ///
+    /// 中断しても前回のスナップショットが壊れないよう、一時ファイルからリネームして置き換える。
     pub(super) fn save_current_schema(
         &self,
         project_path: &Path,
@@ -132,18 +148,8 @@ impl GenerateCommandHandler {
     ) -> Result<()> {
         let snapshot_path = project_path
             .join(&config.migrations_dir)
-            .join(".schema_snapshot.yaml");
-
-        // SchemaSerializerServiceを使用して新構文形式でシリアライズ
-        let serializer = SchemaSerializerService::new();
-        let yaml = serializer
-            .serialize_to_string(schema)
-            .with_context(|| "Failed to serialize schema")?;
-
-        fs::write(&snapshot_path, yaml)
-            .with_context(|| format!("Failed to write schema snapshot: {:?}", snapshot_path))?;
-
-        Ok(())
+            .join(SNAPSHOT_FILE);
+        schema_snapshot::write_snapshot(&snapshot_path, schema)
     }
 
     /// マイグレーションファイルの書き出し
@@ -157,42 +163,70 @@ impl GenerateCommandHandler {
         command: &GenerateCommand,
     ) -> Result<(String, PathBuf)> {
         let migrations_dir = context.migrations_dir();
-        let migration_dir = migrations_dir.join(&dvr.migration_name);
-        fs::create_dir_all(&migration_dir).with_context(|| {
-            format!("Failed to create migration directory: {:?}", migration_dir)
-        })?;
 
-        // UP SQL
-        let up_sql_path = migration_dir.join("up.sql");
-        fs::write(&up_sql_path, &generated.up_sql)
-            .with_context(|| format!("Failed to write up.sql: {:?}", up_sql_path))?;
-
-        // DOWN SQL
-        let down_sql_path = migration_dir.join("down.sql");
-        fs::write(&down_sql_path, &generated.down_sql)
-            .with_context(|| format!("Failed to write down.sql: {:?}", down_sql_path))?;
-
-        // チェックサム・メタデータ
-        let checksum_calculator = SchemaChecksumService::new();
-        let checksum = checksum_calculator.calculate_checksum(current_schema);
+        // チェックサム・メタデータ（スキーマから計算するため出力設定の影響を受けない）
+        let checksum = SchemaChecksumService::new().calculate_checksum(current_schema);
+        // up.sql の改変検出用に、書き出す内容を正規形に戻したチェックサムも記録する
+        let up_sql_checksum = calculate_sql_file_content_checksum(
+            &render_sql_file(&generated.up_sql, &config.sql_output),
+            &config.sql_output,
+        );
 
         let metadata = self.services.generator.generate_migration_metadata(
             &dvr.timestamp,
             &dvr.sanitized_description,
             config.dialect,
             &checksum,
+            Some(up_sql_checksum),
+            Some(config.sql_output.statement_terminator.clone()),
             dvr.destructive_report.clone(),
+            generated.policy_report.clone(),
+            dvr.diff.rollback_protected_tables(),
+            Some(dvr.diff.touched_tables()),
+            command.metadata.clone(),
         )?;
-        let meta_path = migration_dir.join(".meta.yaml");
-        fs::write(&meta_path, metadata)
-            .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
 
-        // per-migrationスナップショット保存（マイグレーションディレクトリ内）
-        self.save_migration_schema_snapshot(&migration_dir, current_schema)?;
+        // 途中で失敗しても不完全なディレクトリが残らないよう、一時ディレクトリに
+        // すべて書き込んでからリネームする
+        let migration_dir = migration_writer::write_migration_dir_atomically(
+            &migrations_dir,
+            &dvr.migration_name,
+            |dir| {
+                // UP SQL / DOWN SQL（sql_output設定に従って出力）
+                write_sql_file(&dir.join("up.sql"), &generated.up_sql, &config.sql_output)?;
+                write_sql_file(
+                    &dir.join("down.sql"),
+                    &generated.down_sql,
+                    &config.sql_output,
+                )?;
+
+                // 事前確認クエリ（apply --preflight で適用前に実行する）
+                if !generated.preflight_queries.is_empty() {
+                    write_sql_file(
+                        &dir.join(PREFLIGHT_FILE),
+                        &preflight_queries::render_preflight_file(&generated.preflight_queries),
+                        &config.sql_output,
+                    )?;
+                }
+
+                let meta_path = dir.join(".meta.yaml");
+                fs::write(&meta_path, &metadata)
+                    .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
+
+                // per-migrationスナップショット保存（マイグレーションディレクトリ内）
+                self.save_migration_schema_snapshot(dir, current_schema)
+            },
+        )?;
 
         // グローバルスナップショット保存（後方互換性のため維持）
         self.save_current_schema(&command.project_path, config, current_schema)?;
 
+        // ロールバックで残されたテーブルはスナップショットに反映されたため記録を削除
+        retained_tables::clear_retained_tables(&migrations_dir)?;
+
+        // 次の generate が重ねて生成しようとしたときに報告できるよう、適用されるまで未適用として記録
+        stacked_migrations::mark_pending(&migrations_dir, [dvr.timestamp.as_str()])?;
+
         Ok((dvr.migration_name.clone(), migration_dir))
     }
 }
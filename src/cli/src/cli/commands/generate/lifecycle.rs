@@ -0,0 +1,67 @@
+use super::GenerateCommandHandler;
+use crate::cli::command_context::{CommandContext, SnapshotPath};
+use crate::core::schema::Schema;
+use crate::services::column_lifecycle::LifecycleReconciliation;
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
+use anyhow::Result;
+
+impl GenerateCommandHandler {
+    /// スキーマ定義から取り除かれた削除待ちカラムについての通知
+    ///
+    /// 削除待ちのカラムは `strata contract` で削除するまでデータベースに残るため、
+    /// NOT NULL でデフォルト値がない場合は、値を書き込まなくなったアプリケーションのINSERTが失敗する。
+    pub(super) fn lifecycle_notices(
+        &self,
+        lifecycle: &LifecycleReconciliation,
+        current_schema: &Schema,
+    ) -> Vec<String> {
+        lifecycle
+            .retained
+            .iter()
+            .map(|name| {
+                let mut notice = format!(
+                    "Column '{}' was removed from the schema but is contract_pending, so it is kept in the database. Drop it with `strata contract`.",
+                    name
+                );
+                let requires_value = name
+                    .split_once('.')
+                    .and_then(|(table, column)| current_schema.get_table(table)?.get_column(column))
+                    .is_some_and(|column| {
+                        !column.nullable
+                            && column.default_value.is_none()
+                            && !column.is_auto_increment()
+                            && !column.is_generated()
+                    });
+                if requires_value {
+                    notice.push_str(
+                        "\nIt is NOT NULL without a default, so inserts that omit it will fail until it is dropped.",
+                    );
+                }
+                notice
+            })
+            .collect()
+    }
+
+    /// カラムのライフサイクルの変更をスキーマスナップショットに記録する
+    ///
+    /// ライフサイクルはSQLに影響しないため、スキーマの差分がない場合もマイグレーションは作成せず、
+    /// 前回のスキーマの読み込み元のスナップショット（とグローバルスナップショット）を更新する。
+    pub(super) fn record_lifecycle_changes(
+        &self,
+        context: &CommandContext,
+        current_schema: &Schema,
+    ) -> Result<()> {
+        let content = schema_snapshot::render_snapshot(current_schema)?;
+
+        let global_snapshot_path = context.migrations_dir().join(SNAPSHOT_FILE);
+        let mut paths = vec![global_snapshot_path];
+        if let Some(SnapshotPath::PerMigration(path)) = context.schema_snapshot_path()? {
+            paths.push(path);
+        }
+        for path in paths {
+            schema_snapshot::write_snapshot_content(&path, &content)?;
+        }
+
+        Ok(())
+    }
+}
@@ -7,24 +7,41 @@
 // - 生成されたファイルパスの表示
 
 mod diff;
+mod edit;
 mod io;
+mod lifecycle;
 mod output;
+mod policy;
 mod sql;
-mod summary;
+pub(crate) mod summary;
 
 #[cfg(test)]
 mod tests;
 
-use crate::cli::command_context::CommandContext;
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
 use crate::cli::commands::destructive_change_formatter::DestructiveChangeFormatter;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::commands::dry_run_sql::DryRunSqlMode;
+use crate::cli::commands::sql_editor::{self, SqlEditor};
+use crate::cli::commands::stacked_migrations::PendingMigration;
+use crate::cli::commands::{render_output_with_timings, CommandOutput};
+use crate::cli::timings::Timings;
 use crate::cli::OutputFormat;
+use crate::core::policy_report::PolicyReport;
+use crate::core::schema::Schema;
+use crate::core::type_category::TypeChangeClass;
+use crate::services::column_lifecycle;
+use crate::services::identifier_case;
 use crate::services::migration_generator::MigrationGeneratorService;
+use crate::services::preflight_queries::{self, PreflightQuery, PREFLIGHT_FILE};
 use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+use crate::services::schema_io::schema_sources::SchemaSources;
 use crate::services::schema_validator::SchemaValidatorService;
 use crate::services::traits::{MigrationGenerator, SchemaDiffDetector, SchemaValidator};
 use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tracing::debug;
 
@@ -45,13 +62,47 @@ pub struct GenerateOutput {
     /// DOWN SQL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub down_sql: Option<String>,
+    /// 事前確認クエリ（`--with-preflight-queries` 指定時に preflight.sql に書き出す内容）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preflight_sql: Option<String>,
     /// 警告メッセージ
     pub warnings: Vec<String>,
+    /// マイグレーションポリシーの評価結果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyReport>,
+    /// 変更の一覧（例: `~ MODIFY VIEW active_users`）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<String>,
+    /// ビューの変更のみを含むか（テーブルのデータに影響しない）
+    pub view_only: bool,
+    /// `--summary-only` 指定時の1変更1行のサマリ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_lines: Option<Vec<String>>,
+    /// 生成時点でスキーマスナップショットより新しかったマイグレーション（未適用の可能性がある）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending_migrations: Vec<PendingMigration>,
+    /// 型が変更されたカラムとその分類
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub type_changes: Vec<TypeChangeEntry>,
     /// メッセージ
     #[serde(skip)]
     pub message: String,
 }
 
+/// 型が変更されたカラム（JSON出力用）
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeChangeEntry {
+    pub table: String,
+    pub column: String,
+    pub old_type: String,
+    pub new_type: String,
+    /// 変更の分類（`safe` / `needs_using` / `lossy`）
+    pub classification: TypeChangeClass,
+    /// 値が失われる理由（`lossy` の場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
 impl CommandOutput for GenerateOutput {
     fn to_text(&self) -> String {
         self.message.clone()
@@ -67,16 +118,48 @@ pub struct GenerateCommand {
     pub config_path: Option<PathBuf>,
     /// スキーマディレクトリのパス（指定されない場合は設定ファイルから取得）
     pub schema_dir: Option<PathBuf>,
+    /// 複数ファイルでの重複定義を許可し、後のファイルの定義を採用する
+    pub allow_duplicate_override: bool,
     /// マイグレーションの説明（オプション）
     pub description: Option<String>,
     /// ドライラン（SQLを表示するがファイルは作成しない）
     pub dry_run: bool,
     /// 破壊的変更を許可
     pub allow_destructive: bool,
+    /// ポリシー違反を警告として扱い生成を続行
+    pub override_policy: bool,
     /// 詳細出力モード
     pub verbose: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
+    /// .meta.yaml に記録するメタデータ（`--meta key=value`）
+    pub metadata: BTreeMap<String, String>,
+    /// 1変更1行の簡潔なサマリのみを出力
+    pub summary_only: bool,
+    /// スナップショットより新しいマイグレーションがある間は生成を拒否する（設定 `require_clean_pending` と同じ）
+    pub require_clean_pending: bool,
+    /// スナップショットより新しいマイグレーションがあっても、その上に重ねて生成する
+    pub allow_stacked: bool,
+    /// dry-run のSQL出力モード（`--sql-only` / `--annotate`）
+    pub dry_run_sql: DryRunSqlMode,
+    /// dry-run のSQL出力で up ではなく down のスクリプトを出力する
+    pub down: bool,
+    /// 追加する制約に違反する既存の行数を数えるクエリを preflight.sql に書き出す
+    pub with_preflight_queries: bool,
+    /// 書き出す前に up.sql / down.sql をエディタで編集する
+    pub edit: bool,
+}
+
+/// マイグレーション生成の入力となるスキーマ
+pub(crate) struct GenerateSchemas<'a> {
+    /// スキーマ定義ファイルに記述されたままのスキーマ（命名規約と renamed_from の警告に使用）
+    pub declared: &'a Schema,
+    /// スキーマ定義の定義元ファイル（`--annotate` の出力に使用）
+    pub sources: &'a SchemaSources,
+    /// identifier_case とカラムのライフサイクルを反映済みのスキーマ
+    pub current: &'a Schema,
+    /// 前回のスキーマ状態
+    pub previous: &'a Schema,
 }
 
 /// 差分検出・バリデーション結果
@@ -86,6 +169,8 @@ struct DiffValidationResult {
     destructive_report: crate::core::destructive_change_report::DestructiveChangeReport,
     rename_validation: crate::core::error::ValidationResult,
     renamed_from_warnings: Vec<crate::core::error::ValidationWarning>,
+    naming_warnings: Vec<crate::core::error::ValidationWarning>,
+    view_rewrite_warnings: Vec<crate::core::error::ValidationWarning>,
     migration_name: String,
     timestamp: String,
     sanitized_description: String,
@@ -95,7 +180,10 @@ struct DiffValidationResult {
 struct GeneratedSql {
     up_sql: String,
     down_sql: String,
+    /// 事前確認クエリ（`--with-preflight-queries` 指定時のみ）
+    preflight_queries: Vec<PreflightQuery>,
     validation_result: crate::core::error::ValidationResult,
+    policy_report: Option<PolicyReport>,
 }
 
 /// サービスプロバイダー
@@ -122,6 +210,8 @@ impl ServiceProvider {
 /// generateコマンドハンドラー
 pub struct GenerateCommandHandler {
     services: ServiceProvider,
+    timings: Timings,
+    sql_editor: SqlEditor,
 }
 
 impl std::fmt::Debug for GenerateCommandHandler {
@@ -133,14 +223,28 @@ impl std::fmt::Debug for GenerateCommandHandler {
 impl GenerateCommandHandler {
     /// 新しいGenerateCommandHandlerを作成
     pub fn new() -> Self {
-        Self {
-            services: ServiceProvider::default_services(),
-        }
+        Self::with_services(ServiceProvider::default_services())
     }
 
     /// カスタムサービスプロバイダーを注入してハンドラーを作成
     pub fn with_services(services: ServiceProvider) -> Self {
-        Self { services }
+        Self {
+            services,
+            timings: Timings::new(),
+            sql_editor: SqlEditor::new(),
+        }
+    }
+
+    /// フェーズ別の実行時間の計測先を設定
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// `--edit` で使用するエディタを設定
+    pub fn with_sql_editor(mut self, sql_editor: SqlEditor) -> Self {
+        self.sql_editor = sql_editor;
+        self
     }
 
     /// generateコマンドを実行
@@ -153,20 +257,99 @@ impl GenerateCommandHandler {
     ///
     /// 成功時は生成されたマイグレーションディレクトリのパス、失敗時はエラーメッセージ
     pub fn execute(&self, command: &GenerateCommand) -> Result<String> {
-        let context = CommandContext::load_with_config(
-            command.project_path.clone(),
-            command.config_path.clone(),
-        )?;
-        let config = &context.config;
+        if command.dry_run {
+            command.dry_run_sql.check_format(&command.format)?;
+        }
 
-        // スキーマの読み込み
+        // generateは現在のスキーマ定義と前回のスナップショットの両方を必要とする
+        // 定義元ファイルは --annotate の出力に使用する
         debug!("Loading current and previous schemas");
-        let (current_schema, previous_schema) = self.load_schemas(
+        let (context, current_schema, sources, snapshot) =
+            CommandContext::load_with_schema_and_snapshot(
+                command.project_path.clone(),
+                command.config_path.clone(),
+                &SchemaLoadOptions {
+                    schema_dir: command.schema_dir.clone(),
+                    allow_duplicate_override: command.allow_duplicate_override,
+                    ..Default::default()
+                },
+            )?;
+        let context = context.with_timings(self.timings.clone());
+        let config = &context.config;
+        let previous_schema = self.merge_retained_into_previous(&context, snapshot)?;
+
+        // 識別子の大文字・小文字の規則は差分検出とSQL生成にのみ適用する
+        // （スナップショットも変換後の名前で保存し、データベース上の名前と一致させる）
+        let declared_schema = current_schema;
+        let mut current_schema =
+            identifier_case::fold_schema_identifiers(&declared_schema, config.identifier_case);
+
+        // 削除待ちのカラムはスキーマ定義から取り除いても削除せず、削除待ちになった日付を記録する
+        let lifecycle = column_lifecycle::reconcile(
+            &previous_schema,
+            &mut current_schema,
+            Utc::now().date_naive(),
+        );
+        let lifecycle_notices = self.lifecycle_notices(&lifecycle, &current_schema);
+
+        self.execute_with_schemas(
+            command,
             &context,
-            &command.project_path,
-            config,
-            command.schema_dir.as_ref(),
-        )?;
+            GenerateSchemas {
+                declared: &declared_schema,
+                sources: &sources,
+                current: &current_schema,
+                previous: &previous_schema,
+            },
+            lifecycle_notices,
+        )
+    }
+
+    /// 読み込み済みのスキーマからマイグレーションを生成する
+    ///
+    /// `notices` は警告の先頭に表示するメッセージ。
+    /// `strata contract` も、削除待ちのカラムを取り除いたスキーマを渡してこの処理を使う。
+    pub(crate) fn execute_with_schemas(
+        &self,
+        command: &GenerateCommand,
+        context: &CommandContext,
+        schemas: GenerateSchemas<'_>,
+        notices: Vec<String>,
+    ) -> Result<String> {
+        let config = &context.config;
+        let GenerateSchemas {
+            declared: declared_schema,
+            sources,
+            current: current_schema,
+            previous: previous_schema,
+        } = schemas;
+        // 定義元はプロジェクトからの相対パスで、生成SQL（identifier_case 適用後）の名前から引けるようにする
+        let sources = sources
+            .relative_to(&command.project_path)
+            .with_identifier_case(config.identifier_case);
+
+        if command.edit {
+            sql_editor::check_edit_allowed(
+                &self.sql_editor,
+                matches!(command.format, OutputFormat::Json),
+                command.dry_run,
+            )?;
+        }
+
+        // 時刻のずれたマシンで生成された未来の日時のマイグレーションと、
+        // 以前の identifier_case で作成された識別子を警告
+        // （新しいマイグレーションがそれより前に並んでしまう / 削除・再作成になってしまう）
+        let mut future_warnings = notices;
+        future_warnings.extend(self.check_future_migrations(context)?);
+        future_warnings.extend(self.check_identifier_case(config, previous_schema));
+        for warning in &future_warnings {
+            eprintln!("{}", warning.yellow());
+        }
+
+        // 前回のスキーマはそれらのマイグレーションを含む最新のスナップショットから復元するため、
+        // 新しいマイグレーションはその差分だけになることを知らせる
+        let pending_migrations = self.check_pending_migrations(context, command)?;
+
         debug!(
             current_tables = current_schema.table_count(),
             current_views = current_schema.view_count(),
@@ -177,25 +360,73 @@ impl GenerateCommandHandler {
 
         // 差分検出・バリデーション
         debug!("Detecting schema differences");
-        let dvr = match self.detect_and_validate_diff(command, &current_schema, &previous_schema)? {
+        let mut dvr = match self.detect_and_validate_diff(
+            command,
+            config,
+            &sources,
+            declared_schema,
+            current_schema,
+            previous_schema,
+        )? {
             Some(dvr) => dvr,
+            // SQLのみを出力する場合、変更がなければ何も出力しない
+            None if command.dry_run && command.dry_run_sql.prints_sql() => {
+                return Ok(String::new());
+            }
             None => {
-                let output = GenerateOutput {
+                // スキーマの差分はないが、カラムのライフサイクルが変わった場合はスナップショットに記録する
+                let lifecycle_changes =
+                    column_lifecycle::lifecycle_changes(previous_schema, current_schema);
+                let message = if lifecycle_changes.is_empty() || command.dry_run {
+                    "No schema changes found. Schema is up to date.".to_string()
+                } else {
+                    self.record_lifecycle_changes(context, current_schema)?;
+                    format!(
+                        "No schema changes found. Recorded column lifecycle changes in the schema snapshot:\n{}",
+                        lifecycle_changes
+                            .iter()
+                            .map(|change| format!("  {}", change))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    )
+                };
+                let mut output = GenerateOutput {
                     dry_run: command.dry_run,
                     migration_name: None,
                     migration_path: None,
                     up_sql: None,
                     down_sql: None,
-                    warnings: vec![],
-                    message: "No schema changes found. Schema is up to date.".to_string(),
+                    preflight_sql: None,
+                    warnings: future_warnings,
+                    policy: None,
+                    changes: Vec::new(),
+                    view_only: false,
+                    summary_lines: None,
+                    pending_migrations,
+                    type_changes: Vec::new(),
+                    message,
                 };
-                return render_output(&output, &command.format);
+                self.apply_summary_only(command, &mut output, Vec::new());
+                return render_output_with_timings(&output, &command.format, &self.timings);
             }
         };
 
         // SQL生成
-        let generated =
-            self.generate_migration_sql(command, config, &dvr, &current_schema, &previous_schema)?;
+        let mut generated =
+            self.generate_migration_sql(command, config, &dvr, current_schema, previous_schema)?;
+
+        // 命名規約の違反と自動で書き換えたビュー（テキスト出力では dry-run の検証結果、または生成結果の前に表示）
+        let naming_warnings: Vec<String> = dvr
+            .naming_warnings
+            .iter()
+            .chain(&dvr.view_rewrite_warnings)
+            .map(|w| w.format())
+            .collect();
+
+        // --sql-only / --annotate の場合はマイグレーションファイルと同じSQLを出力して終了
+        if command.dry_run && command.dry_run_sql.prints_sql() {
+            return Ok(self.render_dry_run_sql(command, config, &generated, &sources));
+        }
 
         // dry-runモードの場合はSQLを表示して終了
         if command.dry_run {
@@ -208,59 +439,147 @@ impl GenerateCommandHandler {
                 &dvr.destructive_report,
             )?;
 
-            let output = GenerateOutput {
+            let policy_warning = generated
+                .policy_report
+                .as_ref()
+                .filter(|report| report.has_violations())
+                .map(|report| self.format_policy_message(report));
+            let text_output = match policy_warning {
+                Some(ref warning) => format!("{}\n{}", text_output, warning),
+                None => text_output,
+            };
+            let view_only = dvr.diff.is_view_only();
+            let text_output = if view_only {
+                format!("{}\n{}", text_output, summary::VIEW_ONLY_NOTE)
+            } else {
+                text_output
+            };
+            let preflight_sql = (!generated.preflight_queries.is_empty())
+                .then(|| preflight_queries::render_preflight_file(&generated.preflight_queries));
+            let text_output = match &preflight_sql {
+                Some(sql) => format!("{}\n\n=== {} ===\n{}", text_output, PREFLIGHT_FILE, sql),
+                None => text_output,
+            };
+
+            let mut output = GenerateOutput {
                 dry_run: true,
                 migration_name: Some(dvr.migration_name.clone()),
                 migration_path: None,
                 up_sql: Some(generated.up_sql.clone()),
                 down_sql: Some(generated.down_sql.clone()),
-                warnings: vec![],
+                preflight_sql,
+                warnings: future_warnings
+                    .into_iter()
+                    .chain(naming_warnings)
+                    .chain(policy_warning)
+                    .collect(),
+                policy: generated.policy_report.clone(),
+                changes: self.change_list(&dvr.diff),
+                view_only,
+                summary_lines: None,
+                pending_migrations,
+                type_changes: self.type_changes(&dvr.diff),
                 message: text_output,
             };
-            return render_output(&output, &command.format);
+            let lines = self.summary_lines(&dvr.diff, &dvr.destructive_report, &sources);
+            self.apply_summary_only(command, &mut output, lines);
+            return render_output_with_timings(&output, &command.format, &self.timings);
+        }
+
+        // --edit の場合は書き出す前にエディタで編集する
+        if command.edit {
+            if let Some(reason) = self.edit_generated_sql(command, &mut dvr, &mut generated)? {
+                return Ok(format!(
+                    "Generation cancelled: {}. No migration files were written.",
+                    reason
+                ));
+            }
         }
 
         // ファイル書き出し
         debug!(migration_name = %dvr.migration_name, "Writing migration files");
-        let (migration_name, migration_dir) = self.write_migration_files(
-            &context,
-            config,
-            &dvr,
-            &generated,
-            &current_schema,
-            command,
-        )?;
+        let (migration_name, migration_dir) = self.timings.measure("write", || {
+            self.write_migration_files(context, config, &dvr, &generated, current_schema, command)
+        })?;
 
         let destructive_warning =
             if dvr.destructive_report.has_destructive_changes() && command.allow_destructive {
-                Some(DestructiveChangeFormatter::new().format_warning(&dvr.destructive_report))
+                Some(
+                    DestructiveChangeFormatter::new()
+                        .with_sources(sources.clone())
+                        .format_warning(&dvr.destructive_report),
+                )
             } else {
                 None
             };
 
+        let policy_warning = generated
+            .policy_report
+            .as_ref()
+            .filter(|report| report.has_violations())
+            .map(|report| self.format_policy_message(report));
+
         let change_summary = self.format_change_summary(&dvr.diff, command.verbose);
 
         let mut text_message = String::new();
+        for warning in &naming_warnings {
+            text_message.push_str(warning);
+            text_message.push('\n');
+        }
         if let Some(ref warning) = destructive_warning {
             text_message.push_str(warning);
             text_message.push('\n');
         }
+        if let Some(ref warning) = policy_warning {
+            text_message.push_str(warning);
+            text_message.push('\n');
+        }
         text_message.push_str(&migration_name);
         if !change_summary.is_empty() {
             text_message.push_str("\n\nChanges:\n");
             text_message.push_str(&change_summary);
         }
+        let view_only = dvr.diff.is_view_only();
+        if view_only {
+            text_message.push_str("\n\n");
+            text_message.push_str(summary::VIEW_ONLY_NOTE);
+        }
+        if command.with_preflight_queries {
+            text_message.push_str(&format!(
+                "\n\nPreflight queries: {} ({})",
+                generated.preflight_queries.len(),
+                if generated.preflight_queries.is_empty() {
+                    "no constraints added to existing tables".to_string()
+                } else {
+                    format!("written to {}", PREFLIGHT_FILE)
+                }
+            ));
+        }
 
-        let output = GenerateOutput {
+        let mut output = GenerateOutput {
             dry_run: false,
             migration_name: Some(migration_name),
             migration_path: Some(migration_dir.to_string_lossy().to_string()),
             up_sql: None,
             down_sql: None,
-            warnings: destructive_warning.into_iter().collect(),
+            preflight_sql: None,
+            warnings: future_warnings
+                .into_iter()
+                .chain(naming_warnings)
+                .chain(destructive_warning)
+                .chain(policy_warning)
+                .collect(),
+            policy: generated.policy_report,
+            changes: self.change_list(&dvr.diff),
+            view_only,
+            summary_lines: None,
+            pending_migrations,
+            type_changes: self.type_changes(&dvr.diff),
             message: text_message,
         };
-        render_output(&output, &command.format)
+        let lines = self.summary_lines(&dvr.diff, &dvr.destructive_report, &sources);
+        self.apply_summary_only(command, &mut output, lines);
+        render_output_with_timings(&output, &command.format, &self.timings)
     }
 }
 
@@ -1,5 +1,8 @@
-use super::GenerateCommandHandler;
+use super::{GenerateCommand, GenerateCommandHandler, GeneratedSql};
 use crate::cli::commands::dry_run_formatter::DryRunFormatter;
+use crate::cli::commands::dry_run_sql::{self, DryRunSqlMode, SqlObject};
+use crate::core::config::Config;
+use crate::services::schema_io::schema_sources::SchemaSources;
 use anyhow::{anyhow, Result};
 
 impl GenerateCommandHandler {
@@ -23,6 +26,35 @@ impl GenerateCommandHandler {
         ))
     }
 
+    /// dry-runモードでマイグレーションファイルと同じSQLを出力（--sql-only / --annotate）
+    ///
+    /// `--down` の場合は down.sql に書き出されるSQLを出力する。
+    pub(super) fn render_dry_run_sql(
+        &self,
+        command: &GenerateCommand,
+        config: &Config,
+        generated: &GeneratedSql,
+        sources: &SchemaSources,
+    ) -> String {
+        let sql = if command.down {
+            &generated.down_sql
+        } else {
+            &generated.up_sql
+        };
+
+        match command.dry_run_sql {
+            DryRunSqlMode::Annotated => {
+                dry_run_sql::render_annotated(sql, &config.sql_output, |object| {
+                    source_file(sources, object)
+                })
+            }
+            DryRunSqlMode::SqlOnly { explain } => {
+                dry_run_sql::render_sql_only(sql, &config.sql_output, explain)
+            }
+            DryRunSqlMode::Report => sql.clone(),
+        }
+    }
+
     /// dry-runモードでのエラー表示
     pub(super) fn execute_dry_run_with_error(
         &self,
@@ -36,3 +68,13 @@ impl GenerateCommandHandler {
         ))
     }
 }
+
+/// ステートメントの対象オブジェクトを定義しているスキーマファイル
+fn source_file(sources: &SchemaSources, object: &SqlObject) -> Option<String> {
+    let file = match object.kind {
+        "type" => sources.enum_file(&object.name),
+        "view" => sources.view_file(&object.name),
+        _ => sources.table_file(object.table.as_deref().unwrap_or(&object.name)),
+    };
+    file.map(|file| file.display().to_string())
+}
@@ -0,0 +1,55 @@
+use super::{DiffValidationResult, GenerateCommand, GenerateCommandHandler};
+use crate::cli::commands::policy_violation_formatter::PolicyViolationFormatter;
+use crate::cli::commands::split_sql_statements;
+use crate::core::config::Config;
+use crate::core::policy_report::PolicyReport;
+use crate::services::migration_policy_checker::MigrationPolicyChecker;
+use anyhow::{anyhow, Result};
+
+impl GenerateCommandHandler {
+    /// マイグレーションポリシーの評価
+    ///
+    /// ポリシーが設定されていない場合は `Ok(None)` を返す。
+    /// 違反がある場合は `--override-policy` 指定時のみ続行し、それ以外はエラーとする。
+    pub(super) fn evaluate_policy(
+        &self,
+        command: &GenerateCommand,
+        config: &Config,
+        dvr: &DiffValidationResult,
+        up_sql: &str,
+    ) -> Result<Option<PolicyReport>> {
+        if config.policy.is_default() {
+            return Ok(None);
+        }
+
+        let statement_count = split_sql_statements(up_sql).len();
+        let mut report = MigrationPolicyChecker::new().check(
+            &config.policy,
+            &dvr.diff,
+            &dvr.destructive_report,
+            statement_count,
+        );
+
+        if report.has_violations() {
+            if !command.override_policy && !command.dry_run {
+                let formatter = PolicyViolationFormatter::new();
+                return Err(anyhow!(formatter.format_error(&report, "strata generate")));
+            }
+            report.overridden = command.override_policy;
+        }
+
+        Ok(Some(report))
+    }
+
+    /// ポリシー違反の表示メッセージを生成
+    ///
+    /// `--override-policy` で続行した場合は警告、dry-runで違反を検出した場合はエラー内容を表示する。
+    pub(super) fn format_policy_message(&self, report: &PolicyReport) -> String {
+        let formatter = PolicyViolationFormatter::new();
+        if report.overridden {
+            formatter.format_warning(report)
+        } else {
+            formatter.format_error(report, "strata generate")
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::{DiffValidationResult, GenerateCommand, GenerateCommandHandler, GeneratedSql};
 use crate::core::config::Config;
 use crate::core::schema::Schema;
+use crate::services::preflight_queries::PreflightQueryBuilder;
 use anyhow::{anyhow, Context, Result};
 
 impl GenerateCommandHandler {
@@ -15,13 +16,15 @@ impl GenerateCommandHandler {
     ) -> Result<GeneratedSql> {
         let allow_destructive_for_sql = command.allow_destructive || command.dry_run;
 
-        let sql_result = self.services.generator.generate_up_sql_with_schemas(
-            &dvr.diff,
-            previous_schema,
-            current_schema,
-            config.dialect,
-            allow_destructive_for_sql,
-        );
+        let sql_result = self.timings.measure("generate", || {
+            self.services.generator.generate_up_sql_with_schemas(
+                &dvr.diff,
+                previous_schema,
+                current_schema,
+                config.dialect,
+                allow_destructive_for_sql,
+            )
+        });
 
         // 型変更検証エラーの処理
         if let Err(e) = &sql_result {
@@ -46,26 +49,45 @@ impl GenerateCommandHandler {
         for warning in &dvr.renamed_from_warnings {
             validation_result.add_warning(warning.clone());
         }
+        for warning in &dvr.naming_warnings {
+            validation_result.add_warning(warning.clone());
+        }
+        for warning in &dvr.view_rewrite_warnings {
+            validation_result.add_warning(warning.clone());
+        }
         if let Some(warning) = self.generate_enum_recreate_deprecation_warning(current_schema) {
             validation_result.add_warning(warning);
         }
 
         let (down_sql, _) = self
-            .services
-            .generator
-            .generate_down_sql_with_schemas(
-                &dvr.diff,
-                previous_schema,
-                current_schema,
-                config.dialect,
-                allow_destructive_for_sql,
-            )
+            .timings
+            .measure("generate", || {
+                self.services.generator.generate_down_sql_with_schemas(
+                    &dvr.diff,
+                    previous_schema,
+                    current_schema,
+                    config.dialect,
+                    allow_destructive_for_sql,
+                )
+            })
             .context("Failed to generate DOWN SQL")?;
 
+        // ポリシー評価
+        let policy_report = self.evaluate_policy(command, config, dvr, &up_sql)?;
+
+        // 既存のテーブルに追加する制約ごとの事前確認クエリ
+        let preflight_queries = if command.with_preflight_queries {
+            PreflightQueryBuilder::new(config.dialect).build(&dvr.diff)
+        } else {
+            Vec::new()
+        };
+
         Ok(GeneratedSql {
             up_sql,
             down_sql,
+            preflight_queries,
             validation_result,
+            policy_report,
         })
     }
 }
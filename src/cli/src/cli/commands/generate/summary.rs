@@ -1,6 +1,428 @@
-use super::GenerateCommandHandler;
+use super::{GenerateCommand, GenerateCommandHandler, GenerateOutput, TypeChangeEntry};
+use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::schema::{Constraint, GeneratedColumn};
+use crate::core::schema_diff::{ColumnChange, EnumChangeKind, SchemaDiff};
+use crate::core::type_category::TypeChangeClassification;
+use crate::services::schema_io::schema_sources::SchemaSources;
+
+/// ビューの変更のみを含むマイグレーションに付記する注記
+pub(super) const VIEW_ONLY_NOTE: &str =
+    "Note: only views changed. No table data is affected by this migration.";
+
+/// `--summary-only` の1行を組み立てる
+///
+/// 破壊的変更は操作に関わらず `! destructive: <action> <kind> <name>` とし、
+/// それ以外は追加を `+`、削除を `-`、変更・リネームを `~` で表します。
+fn summary_line(action: &str, kind: &str, name: &str, destructive: bool) -> String {
+    if destructive {
+        return format!("! destructive: {} {} {}", action, kind, name);
+    }
+    let symbol = match action {
+        "add" => "+",
+        "drop" => "-",
+        _ => "~",
+    };
+    format!("{} {} {}", symbol, kind, name)
+}
+
+/// 定義元が分かる場合は行末に `(schema/orders.yaml:3)` を付記する
+fn with_source(line: String, source: Option<String>) -> String {
+    match source {
+        Some(source) => format!("{} ({})", line, source),
+        None => line,
+    }
+}
+
+/// カラム属性の変更を `旧->新` 形式で表す
+pub(crate) fn column_change_detail(change: &ColumnChange) -> Option<String> {
+    let nullability = |nullable: bool| if nullable { "NULL" } else { "NOT NULL" };
+    match change {
+        ColumnChange::TypeChanged { old_type, new_type } => {
+            Some(format!("{}->{}", old_type, new_type))
+        }
+        ColumnChange::NullableChanged {
+            old_nullable,
+            new_nullable,
+        } => Some(format!(
+            "{}->{}",
+            nullability(*old_nullable),
+            nullability(*new_nullable)
+        )),
+        ColumnChange::DefaultValueChanged {
+            old_default,
+            new_default,
+        } => Some(format!(
+            "default {}->{}",
+            old_default.as_deref().unwrap_or("none"),
+            new_default.as_deref().unwrap_or("none")
+        )),
+        ColumnChange::AutoIncrementChanged {
+            old_auto_increment,
+            new_auto_increment,
+        } => Some(format!(
+            "auto_increment {}->{}",
+            old_auto_increment.unwrap_or(false),
+            new_auto_increment.unwrap_or(false)
+        )),
+        ColumnChange::GeneratedChanged {
+            old_generated,
+            new_generated,
+        } => {
+            let generated = |generated: &Option<GeneratedColumn>| {
+                generated
+                    .as_ref()
+                    .map(|g| format!("({}) {}", g.expression, g.storage_keyword()))
+                    .unwrap_or_else(|| "none".to_string())
+            };
+            Some(format!(
+                "generated {}->{}",
+                generated(old_generated),
+                generated(new_generated)
+            ))
+        }
+        ColumnChange::OnUpdateCurrentTimestampChanged {
+            old_on_update,
+            new_on_update,
+        } => Some(format!(
+            "on_update_current_timestamp {}->{}",
+            old_on_update, new_on_update
+        )),
+        ColumnChange::CommentChanged {
+            old_comment,
+            new_comment,
+        } => Some(comment_change_detail(old_comment, new_comment).to_string()),
+        ColumnChange::Renamed { .. } => None,
+        _ => None,
+    }
+}
+
+/// コメントの変更を `comment added` などの形式で表す（コメント本文は長くなるため表示しない）
+pub(crate) fn comment_change_detail(
+    old_comment: &Option<String>,
+    new_comment: &Option<String>,
+) -> &'static str {
+    match (old_comment, new_comment) {
+        (None, Some(_)) => "comment added",
+        (Some(_), None) => "comment removed",
+        _ => "comment changed",
+    }
+}
+
+/// 制約を `<種別>(<カラム>)` 形式で表す
+pub(crate) fn constraint_signature(constraint: &Constraint) -> String {
+    format!("{}({})", constraint.kind(), constraint.columns().join(", "))
+}
+
+/// 制約を `<テーブル> <種別>(<カラム>)` 形式で表す
+fn constraint_label(table_name: &str, constraint: &Constraint) -> String {
+    format!("{} {}", table_name, constraint_signature(constraint))
+}
 
 impl GenerateCommandHandler {
+    /// 差分から `--summary-only` 用の簡潔な変更一覧を生成（1変更1行）
+    ///
+    /// オブジェクト名順に並べるため、差分の検出順序に関わらず同じ出力になります。
+    /// 破壊的かどうかは `report` に従います。
+    /// スキーマファイルに定義が残っている要素には `sources` から定義元を付記します
+    /// （削除された要素は、テーブル内の要素であればテーブルの定義元）。
+    pub(super) fn summary_lines(
+        &self,
+        diff: &SchemaDiff,
+        report: &DestructiveChangeReport,
+        sources: &SchemaSources,
+    ) -> Vec<String> {
+        // (並び替えに使うオブジェクト名, 表示行)
+        let mut entries: Vec<(String, String)> = Vec::new();
+        let mut push = |object: &str, line: String, source: Option<String>| {
+            entries.push((object.to_string(), with_source(line, source)))
+        };
+
+        for table in &diff.added_tables {
+            push(
+                &table.name,
+                summary_line("add", "table", &table.name, false),
+                sources.table_source(&table.name),
+            );
+        }
+        for table_name in &diff.removed_tables {
+            let destructive = report.tables_dropped.contains(table_name);
+            push(
+                table_name,
+                summary_line("drop", "table", table_name, destructive),
+                None,
+            );
+        }
+        for renamed in &diff.renamed_tables {
+            let name = format!("{} -> {}", renamed.old_name, renamed.new_table.name);
+            push(
+                &renamed.new_table.name,
+                summary_line("rename", "table", &name, false),
+                sources.table_source(&renamed.new_table.name),
+            );
+        }
+
+        for table_diff in &diff.modified_tables {
+            let table_name = &table_diff.table_name;
+            for col in &table_diff.added_columns {
+                let object = format!("{}.{}", table_name, col.name);
+                push(
+                    &object,
+                    summary_line("add", "column", &object, false),
+                    sources.column_source(table_name, &col.name),
+                );
+            }
+            for col_name in &table_diff.removed_columns {
+                let object = format!("{}.{}", table_name, col_name);
+                let destructive = report
+                    .columns_dropped
+                    .iter()
+                    .any(|d| &d.table == table_name && d.columns.contains(col_name));
+                push(
+                    &object,
+                    summary_line("drop", "column", &object, destructive),
+                    sources.table_source(table_name),
+                );
+            }
+            for col_diff in &table_diff.modified_columns {
+                let object = format!("{}.{}", table_name, col_diff.column_name);
+                let details: Vec<String> = col_diff
+                    .changes
+                    .iter()
+                    .filter_map(column_change_detail)
+                    .collect();
+                let name = if details.is_empty() {
+                    object.clone()
+                } else {
+                    format!("{} {}", object, details.join(", "))
+                };
+                let destructive = report
+                    .columns_narrowed
+                    .iter()
+                    .any(|n| &n.table == table_name && n.column == col_diff.column_name);
+                push(
+                    &object,
+                    summary_line("modify", "column", &name, destructive),
+                    sources.column_source(table_name, &col_diff.column_name),
+                );
+            }
+            for renamed in &table_diff.renamed_columns {
+                let object = format!("{}.{}", table_name, renamed.old_name);
+                let name = format!("{} -> {}", object, renamed.new_column.name);
+                let destructive = report
+                    .columns_renamed
+                    .iter()
+                    .any(|r| &r.table == table_name && r.old_name == renamed.old_name);
+                push(
+                    &object,
+                    summary_line("rename", "column", &name, destructive),
+                    sources.column_source(table_name, &renamed.new_column.name),
+                );
+            }
+            for idx in &table_diff.added_indexes {
+                push(
+                    &idx.name,
+                    summary_line("add", "index", &idx.name, false),
+                    sources.index_source(table_name, &idx.name),
+                );
+            }
+            for idx_name in &table_diff.removed_indexes {
+                push(
+                    idx_name,
+                    summary_line("drop", "index", idx_name, false),
+                    sources.table_source(table_name),
+                );
+            }
+            for idx_diff in &table_diff.modified_indexes {
+                push(
+                    &idx_diff.index_name,
+                    summary_line("modify", "index", &idx_diff.index_name, false),
+                    sources.index_source(table_name, &idx_diff.index_name),
+                );
+            }
+            for renamed in &table_diff.renamed_indexes {
+                let name = format!("{} -> {}", renamed.old_name, renamed.new_index.name);
+                push(
+                    &renamed.old_name,
+                    summary_line("rename", "index", &name, false),
+                    sources.index_source(table_name, &renamed.new_index.name),
+                );
+            }
+            for constraint in &table_diff.added_constraints {
+                let name = constraint_label(table_name, constraint);
+                push(
+                    table_name,
+                    summary_line("add", "constraint", &name, false),
+                    sources.constraint_source(table_name, constraint),
+                );
+            }
+            for constraint in &table_diff.removed_constraints {
+                let name = constraint_label(table_name, constraint);
+                push(
+                    table_name,
+                    summary_line("drop", "constraint", &name, false),
+                    sources.table_source(table_name),
+                );
+            }
+            if let Some(change) = &table_diff.comment_change {
+                let name = format!(
+                    "{} {}",
+                    table_name,
+                    comment_change_detail(&change.old_comment, &change.new_comment)
+                );
+                push(
+                    table_name,
+                    summary_line("modify", "table", &name, false),
+                    sources.table_source(table_name),
+                );
+            }
+        }
+
+        for enum_def in &diff.added_enums {
+            push(
+                &enum_def.name,
+                summary_line("add", "enum", &enum_def.name, false),
+                sources.enum_source(&enum_def.name),
+            );
+        }
+        for enum_name in &diff.removed_enums {
+            let destructive = report.enums_dropped.contains(enum_name);
+            push(
+                enum_name,
+                summary_line("drop", "enum", enum_name, destructive),
+                None,
+            );
+        }
+        for enum_diff in &diff.modified_enums {
+            let name = &enum_diff.enum_name;
+            let line = if enum_diff.change_kind == EnumChangeKind::ReorderOnly {
+                summary_line(
+                    "reorder",
+                    "enum",
+                    name,
+                    report.enums_reordered.iter().any(|r| &r.name == name),
+                )
+            } else if enum_diff.change_kind == EnumChangeKind::Recreate {
+                summary_line(
+                    "recreate",
+                    "enum",
+                    name,
+                    report.enums_recreated.contains(name),
+                )
+            } else {
+                summary_line("modify", "enum", name, false)
+            };
+            push(name, line, sources.enum_source(name));
+        }
+        for renamed in &diff.renamed_enums {
+            let name = format!("{} -> {}", renamed.old_name, renamed.new_enum.name);
+            push(
+                &renamed.new_enum.name,
+                summary_line("rename", "enum", &name, false),
+                sources.enum_source(&renamed.new_enum.name),
+            );
+        }
+
+        for view in &diff.added_views {
+            push(
+                &view.name,
+                summary_line("add", "view", &view.name, false),
+                sources.view_source(&view.name),
+            );
+        }
+        for view_name in &diff.removed_views {
+            let destructive = report.views_dropped.contains(view_name);
+            push(
+                view_name,
+                summary_line("drop", "view", view_name, destructive),
+                None,
+            );
+        }
+        for view_diff in &diff.modified_views {
+            let destructive = report.views_modified.contains(&view_diff.view_name);
+            push(
+                &view_diff.view_name,
+                summary_line("modify", "view", &view_diff.view_name, destructive),
+                sources.view_source(&view_diff.view_name),
+            );
+        }
+        for renamed in &diff.renamed_views {
+            let name = format!("{} -> {}", renamed.old_name, renamed.new_view.name);
+            push(
+                &renamed.new_view.name,
+                summary_line("rename", "view", &name, false),
+                sources.view_source(&renamed.new_view.name),
+            );
+        }
+
+        entries.sort();
+        entries.into_iter().map(|(_, line)| line).collect()
+    }
+
+    /// `--summary-only` 指定時に出力をサマリのみに差し替える
+    ///
+    /// テキスト出力はサマリ行のみとし、JSON出力には `summary_lines` を追加します。
+    pub(super) fn apply_summary_only(
+        &self,
+        command: &GenerateCommand,
+        output: &mut GenerateOutput,
+        lines: Vec<String>,
+    ) {
+        if !command.summary_only {
+            return;
+        }
+        output.message = lines.join("\n");
+        output.summary_lines = Some(lines);
+    }
+
+    /// 差分から変更の一覧を生成（JSON出力用、1変更1行）
+    pub(super) fn change_list(&self, diff: &crate::core::schema_diff::SchemaDiff) -> Vec<String> {
+        self.format_change_summary(diff, false)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    /// 型が変更されたカラムの分類一覧を生成（JSON出力用）
+    ///
+    /// 方言上等価として除外された型変更（`changes` に TypeChanged が残らないもの）は含めません。
+    pub(super) fn type_changes(&self, diff: &SchemaDiff) -> Vec<TypeChangeEntry> {
+        let mut entries = Vec::new();
+        for table_diff in &diff.modified_tables {
+            let columns = table_diff
+                .modified_columns
+                .iter()
+                .map(|c| (&c.old_column, &c.new_column, &c.changes))
+                .chain(
+                    table_diff
+                        .renamed_columns
+                        .iter()
+                        .map(|r| (&r.old_column, &r.new_column, &r.changes)),
+                );
+            for (old_column, new_column, changes) in columns {
+                if !changes
+                    .iter()
+                    .any(|change| matches!(change, ColumnChange::TypeChanged { .. }))
+                {
+                    continue;
+                }
+                let classification = TypeChangeClassification::classify(
+                    &old_column.column_type,
+                    &new_column.column_type,
+                );
+                entries.push(TypeChangeEntry {
+                    table: table_diff.table_name.clone(),
+                    column: new_column.name.clone(),
+                    old_type: old_column.column_type.to_string(),
+                    new_type: new_column.column_type.to_string(),
+                    classification: classification.class,
+                    reason: classification.reason,
+                });
+            }
+        }
+        entries
+    }
+
     /// 差分から変更サマリを生成
     pub(super) fn format_change_summary(
         &self,
@@ -63,6 +485,12 @@ impl GenerateCommandHandler {
                     idx_name, table_diff.table_name
                 ));
             }
+            for renamed in &table_diff.renamed_indexes {
+                lines.push(format!(
+                    "  ~ RENAME INDEX {} -> {} ON {}",
+                    renamed.old_name, renamed.new_index.name, table_diff.table_name
+                ));
+            }
             for constraint in &table_diff.added_constraints {
                 lines.push(format!(
                     "  + ADD {} ON {}",
@@ -87,6 +515,13 @@ impl GenerateCommandHandler {
             lines.push(format!("  - DROP ENUM {}", enum_name));
         }
 
+        for renamed in &diff.renamed_enums {
+            lines.push(format!(
+                "  ~ RENAME ENUM {} -> {}",
+                renamed.old_name, renamed.new_enum.name
+            ));
+        }
+
         for view in &diff.added_views {
             lines.push(format!("  + CREATE VIEW {}", view.name));
         }
@@ -136,6 +571,15 @@ impl GenerateCommandHandler {
             parts.push(format!("modify tables {}", table_names.join(", ")));
         }
 
+        if !diff.renamed_enums.is_empty() {
+            let renamed_descs: Vec<String> = diff
+                .renamed_enums
+                .iter()
+                .map(|r| format!("{} -> {}", r.old_name, r.new_enum.name))
+                .collect();
+            parts.push(format!("rename enums {}", renamed_descs.join(", ")));
+        }
+
         if !diff.added_views.is_empty() {
             let view_names: Vec<&str> = diff.added_views.iter().map(|v| v.name.as_str()).collect();
             parts.push(format!("add views {}", view_names.join(", ")));
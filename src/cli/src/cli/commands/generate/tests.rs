@@ -28,11 +28,21 @@ fn test_generate_command_has_dry_run_field() {
         project_path: std::path::PathBuf::from("/tmp"),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         description: Some("test".to_string()),
         dry_run: true,
         allow_destructive: false,
+        override_policy: false,
         verbose: false,
         format: crate::cli::OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
     };
     assert!(command.dry_run);
 }
@@ -85,8 +95,11 @@ fn test_execute_dry_run_includes_destructive_preview() {
         columns_renamed: Vec::new(),
         enums_dropped: Vec::new(),
         enums_recreated: Vec::new(),
+        enums_reordered: Vec::new(),
         views_dropped: Vec::new(),
         views_modified: Vec::new(),
+        columns_narrowed: Vec::new(),
+        edited_statements: Vec::new(),
     };
 
     let result = handler.execute_dry_run(
@@ -121,6 +134,7 @@ fn test_execute_dry_run_with_warnings() {
             table: Some("users".to_string()),
             column: Some("email".to_string()),
             line: None,
+            file: None,
         }),
     ));
 
@@ -326,6 +340,74 @@ fn test_generate_renamed_from_remove_warnings() {
     assert!(warnings[0].message.contains("renamed_from"));
 }
 
+#[test]
+fn test_generate_renamed_from_remove_warnings_for_index() {
+    use crate::core::schema::{Index, Table};
+
+    let handler = GenerateCommandHandler::new();
+
+    let mut schema = Schema::new("1.0".to_string());
+    let mut table = Table::new("users".to_string());
+    let mut index = Index::new(
+        "users_email_idx".to_string(),
+        vec!["email".to_string()],
+        false,
+    );
+    index.renamed_from = Some("idx_email".to_string());
+    table.add_index(index);
+    schema.add_table(table);
+
+    let warnings = handler.generate_renamed_from_remove_warnings(&schema);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("Index 'users_email_idx'"));
+}
+
+#[test]
+fn test_generate_renamed_from_remove_warnings_for_enum() {
+    use crate::core::schema::EnumDefinition;
+
+    let handler = GenerateCommandHandler::new();
+
+    let mut schema = Schema::new("1.0".to_string());
+    schema.add_enum(EnumDefinition {
+        name: "user_status".to_string(),
+        values: vec!["active".to_string()],
+        renamed_from: Some("status".to_string()),
+    });
+
+    let warnings = handler.generate_renamed_from_remove_warnings(&schema);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("ENUM 'user_status'"));
+}
+
+#[test]
+fn test_check_identifier_case_warns_about_mixed_usage() {
+    use crate::core::config::{Config, IdentifierCase};
+    use crate::core::schema::Table;
+
+    let handler = GenerateCommandHandler::new();
+    let mut snapshot = Schema::new("1.0".to_string());
+    snapshot.add_table(Table::new("users".to_string()));
+
+    let mut config: Config = serde_saphyr::from_str(
+        "version: \"1.0\"\ndialect: postgresql\nenvironments:\n  development:\n    database: app\n",
+    )
+    .unwrap();
+    assert!(handler.check_identifier_case(&config, &snapshot).is_none());
+
+    config.identifier_case = IdentifierCase::Lower;
+    assert!(handler.check_identifier_case(&config, &snapshot).is_none());
+
+    // 以前は小文字で作成したオブジェクトが、大文字の規則に従っていない
+    config.identifier_case = IdentifierCase::Upper;
+    let warning = handler
+        .check_identifier_case(&config, &snapshot)
+        .expect("warning should exist");
+    assert!(warning.contains("identifier_case is 'upper'"));
+    assert!(warning.contains("(users)"));
+    assert!(warning.contains("strata baseline --force"));
+}
+
 #[test]
 fn test_generate_enum_recreate_deprecation_warning() {
     use crate::core::error::WarningKind;
@@ -397,7 +479,18 @@ fn test_generate_output_json_serialization() {
         migration_path: Some("/path/to/migrations/20260121120000_create_users".to_string()),
         up_sql: Some("CREATE TABLE users (id INTEGER PRIMARY KEY);".to_string()),
         down_sql: Some("DROP TABLE users;".to_string()),
+        preflight_sql: None,
         warnings: vec!["destructive change".to_string()],
+        policy: Some(crate::core::policy_report::PolicyReport {
+            statement_count: 1,
+            tables_touched: vec!["users".to_string()],
+            ..Default::default()
+        }),
+        changes: vec!["~ MODIFY VIEW active_users".to_string()],
+        view_only: true,
+        summary_lines: Some(vec!["! destructive: modify view active_users".to_string()]),
+        pending_migrations: vec![],
+        type_changes: vec![],
         message: "should not appear in JSON".to_string(),
     };
 
@@ -414,6 +507,14 @@ fn test_generate_output_json_serialization() {
     );
     assert!(parsed["up_sql"].as_str().unwrap().contains("CREATE TABLE"));
     assert_eq!(parsed["warnings"][0], "destructive change");
+    assert_eq!(parsed["policy"]["statement_count"], 1);
+    assert_eq!(parsed["policy"]["tables_touched"][0], "users");
+    assert_eq!(parsed["changes"][0], "~ MODIFY VIEW active_users");
+    assert_eq!(parsed["view_only"], true);
+    assert_eq!(
+        parsed["summary_lines"][0],
+        "! destructive: modify view active_users"
+    );
 
     // None フィールドはスキップされる
     let output_minimal = GenerateOutput {
@@ -422,7 +523,14 @@ fn test_generate_output_json_serialization() {
         migration_path: None,
         up_sql: None,
         down_sql: None,
+        preflight_sql: None,
         warnings: vec![],
+        policy: None,
+        changes: vec![],
+        view_only: false,
+        summary_lines: None,
+        pending_migrations: vec![],
+        type_changes: vec![],
         message: "text".to_string(),
     };
     let json2 = serde_json::to_string_pretty(&output_minimal).unwrap();
@@ -431,4 +539,170 @@ fn test_generate_output_json_serialization() {
     assert!(parsed2.get("migration_path").is_none());
     assert!(parsed2.get("up_sql").is_none());
     assert!(parsed2.get("down_sql").is_none());
+    assert!(parsed2.get("policy").is_none());
+    assert!(parsed2.get("changes").is_none());
+    assert_eq!(parsed2["view_only"], false);
+    assert!(parsed2.get("summary_lines").is_none());
+}
+
+/// `--summary-only` のサマリ形式を固定するスナップショットテスト
+#[test]
+fn test_summary_lines_snapshot() {
+    use crate::core::schema::{Column, ColumnType, Constraint, Index, Table};
+    use crate::core::schema_diff::{ColumnChange, ColumnDiff, SchemaDiff, TableDiff};
+    use crate::services::destructive_change_detector::DestructiveChangeDetector;
+
+    let handler = GenerateCommandHandler::new();
+    let varchar = |length| ColumnType::VARCHAR { length };
+
+    let mut diff = SchemaDiff::new();
+
+    let mut orders = Table::new("orders".to_string());
+    orders.add_column(Column::new(
+        "id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    diff.added_tables.push(orders);
+
+    let mut users = TableDiff::new("users".to_string());
+    users.removed_columns.push("legacy_flag".to_string());
+    users.modified_columns.push(ColumnDiff {
+        column_name: "email".to_string(),
+        old_column: Column::new("email".to_string(), varchar(255), false),
+        new_column: Column::new("email".to_string(), varchar(320), false),
+        changes: vec![ColumnChange::TypeChanged {
+            old_type: "VARCHAR(255)".to_string(),
+            new_type: "VARCHAR(320)".to_string(),
+        }],
+    });
+    users
+        .added_columns
+        .push(Column::new("nickname".to_string(), varchar(50), true));
+    diff.modified_tables.push(users);
+
+    let mut sessions = TableDiff::new("sessions".to_string());
+    sessions
+        .removed_indexes
+        .push("idx_sessions_token".to_string());
+    sessions.added_indexes.push(Index::new(
+        "idx_sessions_user".to_string(),
+        vec!["user_id".to_string()],
+        false,
+    ));
+    sessions.added_constraints.push(Constraint::FOREIGN_KEY {
+        columns: vec!["user_id".to_string()],
+        referenced_table: "users".to_string(),
+        referenced_columns: vec!["id".to_string()],
+        on_delete: None,
+        on_update: None,
+    });
+    diff.modified_tables.push(sessions);
+
+    diff.removed_tables.push("audit_log".to_string());
+
+    let report = DestructiveChangeDetector::new().detect(&diff);
+    let lines = handler.summary_lines(&diff, &report, &SchemaSources::new());
+
+    assert_eq!(
+        lines.join("\n"),
+        "\
+! destructive: drop table audit_log
+- index idx_sessions_token
++ index idx_sessions_user
++ table orders
++ constraint sessions FOREIGN_KEY(user_id)
+~ column users.email VARCHAR(255)->VARCHAR(320)
+! destructive: drop column users.legacy_flag
++ column users.nickname"
+    );
+}
+
+/// `--summary-only` のサマリはビュー・ENUM・リネームも1行ずつ表す
+#[test]
+fn test_summary_lines_snapshot_views_enums_and_renames() {
+    use crate::core::schema::{Column, ColumnType, EnumDefinition, Table, View};
+    use crate::core::schema_diff::{
+        ColumnChange, ColumnDiff, EnumChangeKind, EnumDiff, RenamedColumn, RenamedTable,
+        SchemaDiff, TableDiff, ViewDiff,
+    };
+    use crate::services::destructive_change_detector::DestructiveChangeDetector;
+
+    let handler = GenerateCommandHandler::new();
+    let mut diff = SchemaDiff::new();
+    diff.enum_recreate_allowed = true;
+
+    diff.added_enums.push(EnumDefinition {
+        name: "mood".to_string(),
+        values: vec!["happy".to_string()],
+        renamed_from: None,
+    });
+    diff.modified_enums.push(EnumDiff {
+        enum_name: "status".to_string(),
+        old_values: vec!["active".to_string(), "inactive".to_string()],
+        new_values: vec!["active".to_string()],
+        added_values: vec![],
+        removed_values: vec!["inactive".to_string()],
+        change_kind: EnumChangeKind::Recreate,
+        columns: vec![],
+    });
+
+    diff.renamed_tables.push(RenamedTable {
+        old_name: "people".to_string(),
+        new_table: Table::new("members".to_string()),
+    });
+
+    let int = ColumnType::INTEGER { precision: None };
+    let mut members = TableDiff::new("members".to_string());
+    members.renamed_columns.push(RenamedColumn {
+        old_name: "name".to_string(),
+        old_column: Column::new("name".to_string(), ColumnType::TEXT, false),
+        new_column: Column::new("full_name".to_string(), ColumnType::TEXT, false),
+        changes: vec![],
+    });
+    members.modified_columns.push(ColumnDiff {
+        column_name: "age".to_string(),
+        old_column: Column::new("age".to_string(), int.clone(), true),
+        new_column: Column::new("age".to_string(), int, false),
+        changes: vec![
+            ColumnChange::NullableChanged {
+                old_nullable: true,
+                new_nullable: false,
+            },
+            ColumnChange::DefaultValueChanged {
+                old_default: None,
+                new_default: Some("0".to_string()),
+            },
+        ],
+    });
+    diff.modified_tables.push(members);
+
+    diff.added_views.push(View::new(
+        "active_members".to_string(),
+        "SELECT * FROM members".to_string(),
+    ));
+    diff.modified_views.push(ViewDiff {
+        view_name: "recent_members".to_string(),
+        old_definition: "SELECT 1".to_string(),
+        new_definition: "SELECT 2".to_string(),
+        old_view: View::new("recent_members".to_string(), "SELECT 1".to_string()),
+        new_view: View::new("recent_members".to_string(), "SELECT 2".to_string()),
+        auto_rewritten: false,
+    });
+
+    let report = DestructiveChangeDetector::new().detect(&diff);
+    let lines = handler.summary_lines(&diff, &report, &SchemaSources::new());
+
+    assert_eq!(
+        lines,
+        vec![
+            "+ view active_members",
+            "~ table people -> members",
+            "~ column members.age NULL->NOT NULL, default none->0",
+            "! destructive: rename column members.name -> full_name",
+            "+ enum mood",
+            "! destructive: modify view recent_members",
+            "! destructive: recreate enum status",
+        ]
+    );
 }
@@ -0,0 +1,53 @@
+// gitコマンド連携
+//
+// 変更されたファイルの一覧取得など、gitを呼び出す処理を集約します。
+
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 指定したgit参照以降に変更されたファイルを取得
+///
+/// `git diff --name-only <ref>` の結果（作業ツリーの未コミット変更を含む）に
+/// 未追跡のファイルを加え、絶対パスで返します。
+///
+/// # Arguments
+///
+/// * `repo_path` - リポジトリ内のディレクトリ
+/// * `git_ref` - 比較対象のgit参照（例: `origin/main`）
+pub(crate) fn changed_files_since(repo_path: &Path, git_ref: &str) -> Result<Vec<PathBuf>> {
+    let toplevel = run_git(repo_path, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(toplevel.trim());
+
+    let diff = run_git(repo_path, &["diff", "--name-only", git_ref, "--"])?;
+    let untracked = run_git(
+        repo_path,
+        &["ls-files", "--others", "--exclude-standard", "--full-name"],
+    )?;
+
+    Ok(diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| root.join(line.trim()))
+        .collect())
+}
+
+/// gitコマンドを実行し、標準出力を返す
+fn run_git(repo_path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .with_context(|| "Failed to run git. Use --files to pass the changed files explicitly")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,339 @@
+// historyコマンドハンドラー
+//
+// データベースに記録された適用済みマイグレーションの履歴を表示します。
+// `--show-sql` を指定すると、`store_applied_sql: true` で保存された
+// 適用時の up.sql / down.sql を表示します。
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use crate::core::migration::{is_truncated_sql, AppliedSql, MigrationRecord};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// 適用SQLの保存状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoredSqlStatus {
+    /// 保存されていない
+    NotStored,
+    /// 保存済み
+    Stored,
+    /// 上限を超えたため切り詰めて保存
+    Truncated,
+}
+
+impl StoredSqlStatus {
+    fn of(applied_sql: Option<&AppliedSql>) -> Self {
+        match applied_sql {
+            Some(sql) if sql.up_sql.is_none() => Self::NotStored,
+            Some(sql) if sql.is_truncated() => Self::Truncated,
+            Some(_) => Self::Stored,
+            None => Self::NotStored,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::NotStored => "-",
+            Self::Stored => "stored",
+            Self::Truncated => "truncated",
+        }
+    }
+}
+
+/// historyコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryOutput {
+    /// 適用済みマイグレーション一覧（バージョン順）
+    pub migrations: Vec<HistoryEntry>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+/// 適用済みマイグレーションのエントリ
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    pub version: String,
+    pub description: String,
+    pub applied_at: DateTime<Utc>,
+    pub checksum: String,
+    /// 適用SQLの保存状態
+    pub sql: StoredSqlStatus,
+}
+
+impl CommandOutput for HistoryOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// `history --show-sql` の出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedSqlOutput {
+    pub version: String,
+    pub description: String,
+    /// 適用時のup.sql
+    pub up_sql: String,
+    /// 適用時のdown.sql（適用時に存在しなかった場合はNone）
+    pub down_sql: Option<String>,
+    /// 上限を超えたため切り詰めて保存されているか
+    pub truncated: bool,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for AppliedSqlOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// historyコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct HistoryCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// 保存されたSQLを表示するバージョン
+    pub show_sql: Option<String>,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// historyコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct HistoryCommandHandler {}
+
+impl HistoryCommandHandler {
+    /// 新しいHistoryCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// historyコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - historyコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は適用済みマイグレーションの一覧、または保存されたSQL
+    pub async fn execute(&self, command: &HistoryCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    /// 読み込み済みのコンテキストでhistoryコマンドを実行
+    pub async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &HistoryCommand,
+    ) -> Result<String> {
+        let (pool, applied_migrations) = context.connect_and_load_migrations(&command.env).await?;
+
+        match &command.show_sql {
+            Some(version) => {
+                let output = self.show_sql(&pool, context.dialect(), version).await?;
+                render_output(&output, &command.format)
+            }
+            None => {
+                let output = self
+                    .history(&pool, context.dialect(), &applied_migrations)
+                    .await?;
+                render_output(&output, &command.format)
+            }
+        }
+    }
+
+    /// 適用済みマイグレーションの一覧を作成
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `applied_migrations` - 適用済みマイグレーションの記録
+    pub async fn history(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        applied_migrations: &[MigrationRecord],
+    ) -> Result<HistoryOutput> {
+        let applied_sql = DatabaseMigratorService::new()
+            .get_applied_sql(pool, dialect, None)
+            .await
+            .with_context(|| "Failed to get applied migration SQL")?;
+        let applied_sql: HashMap<&str, &AppliedSql> = applied_sql
+            .iter()
+            .map(|sql| (sql.version.as_str(), sql))
+            .collect();
+
+        let migrations: Vec<HistoryEntry> = applied_migrations
+            .iter()
+            .map(|record| HistoryEntry {
+                version: record.version.clone(),
+                description: record.description.clone(),
+                applied_at: record.applied_at,
+                checksum: record.checksum.clone(),
+                sql: StoredSqlStatus::of(applied_sql.get(record.version.as_str()).copied()),
+            })
+            .collect();
+
+        let text_message = self.format_history(&migrations);
+        Ok(HistoryOutput {
+            migrations,
+            text_message,
+        })
+    }
+
+    /// 指定バージョンの保存されたSQLを取得
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `version` - マイグレーションバージョン
+    pub async fn show_sql(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        version: &str,
+    ) -> Result<AppliedSqlOutput> {
+        let applied_sql = DatabaseMigratorService::new()
+            .get_applied_sql(pool, dialect, Some(version))
+            .await
+            .with_context(|| "Failed to get applied migration SQL")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("Migration {} has not been applied.", version))?;
+
+        let truncated = applied_sql.is_truncated();
+        let AppliedSql {
+            version,
+            description,
+            up_sql,
+            down_sql,
+            ..
+        } = applied_sql;
+        let up_sql = up_sql.ok_or_else(|| {
+            anyhow!(
+                "SQL of migration {} was not stored. Set `store_applied_sql: true` in the config to store the SQL of migrations applied from now on.",
+                version
+            )
+        })?;
+
+        let text_message = format_applied_sql(&version, &description, &up_sql, down_sql.as_deref());
+        Ok(AppliedSqlOutput {
+            version,
+            description,
+            up_sql,
+            down_sql,
+            truncated,
+            text_message,
+        })
+    }
+
+    /// 適用済みマイグレーションの一覧をフォーマット
+    fn format_history(&self, migrations: &[HistoryEntry]) -> String {
+        let mut output = String::from("=== Migration History ===\n\n");
+
+        if migrations.is_empty() {
+            output.push_str("No migrations have been applied.\n");
+            return output;
+        }
+
+        output.push_str(&format!(
+            "{:<20} {:<40} {:<27} {:<10}\n",
+            "Version", "Description", "Applied At", "SQL"
+        ));
+        output.push_str(&format!("{}\n", "-".repeat(100)));
+
+        for migration in migrations {
+            output.push_str(&format!(
+                "{:<20} {:<40} {:<27} {:<10}\n",
+                migration.version,
+                migration.description,
+                migration.applied_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                migration.sql.label()
+            ));
+        }
+
+        output.push_str(&format!("\nTotal: {}\n", migrations.len()));
+        if migrations
+            .iter()
+            .any(|m| m.sql != StoredSqlStatus::NotStored)
+        {
+            output
+                .push_str("\nUse `strata history --show-sql <VERSION>` to show the stored SQL.\n");
+        }
+
+        output
+    }
+}
+
+/// 保存されたSQLをフォーマット
+fn format_applied_sql(
+    version: &str,
+    description: &str,
+    up_sql: &str,
+    down_sql: Option<&str>,
+) -> String {
+    let mut output = format!("=== Migration {} - {} ===\n", version, description);
+
+    for (file, sql) in [("up.sql", Some(up_sql)), ("down.sql", down_sql)] {
+        output.push_str(&format!("\n--- {} ---\n", file));
+        match sql {
+            Some(sql) => {
+                output.push_str(sql.trim_end());
+                output.push('\n');
+                if is_truncated_sql(sql) {
+                    output.push_str(&format!(
+                        "\n⚠️  Warning: {} exceeded the storage limit and was truncated.\n",
+                        file
+                    ));
+                }
+            }
+            None => output.push_str("(not present when applied)\n"),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::migration::TRUNCATED_SQL_MARKER;
+
+    #[test]
+    fn test_format_applied_sql_marks_truncated_file() {
+        let up_sql = format!(
+            "CREATE TABLE users (id INTEGER);\n{} (original size: 2000000 bytes)\n",
+            TRUNCATED_SQL_MARKER
+        );
+        let text = format_applied_sql("20240101120000", "create_users", &up_sql, None);
+
+        assert!(text.contains("=== Migration 20240101120000 - create_users ==="));
+        assert!(text.contains("--- up.sql ---\nCREATE TABLE users (id INTEGER);"));
+        assert!(text.contains("up.sql exceeded the storage limit and was truncated"));
+        assert!(text.contains("--- down.sql ---\n(not present when applied)"));
+    }
+}
@@ -4,8 +4,15 @@
 // - ディレクトリ構造の作成（schema/, migrations/）
 // - デフォルト設定ファイルの生成（.strata.yaml）
 // - 初期化済みプロジェクトの検出と警告
-
-use crate::cli::commands::{render_output, CommandOutput};
+// - サンプルスキーマ・サンプルマイグレーションの生成（--with-example）
+// - 対話モード（--dialect なしで端末から実行した場合、init_wizard を参照）
+
+use crate::adapters::database::DatabaseConnectionService;
+use crate::cli::commands::init_example::ExampleProjectWriter;
+use crate::cli::commands::init_wizard::{
+    password_env_var_name, resolve_env_reference, InitWizard, InitWizardAnswers, Prompter,
+};
+use crate::cli::commands::{render_output, stacked_migrations, CommandOutput};
 use crate::cli::OutputFormat;
 use crate::core::config::{Config, DatabaseConfig, Dialect};
 use crate::services::config_loader::ConfigLoader;
@@ -17,6 +24,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
+/// 対話モードの接続テストで待つ最大秒数
+///
+/// 接続できない場合に設定の `timeout`（既定30秒）まで待たせないよう、短く区切ります。
+const CONNECTION_TEST_TIMEOUT_SECS: u64 = 5;
+
 /// initコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
 pub struct InitOutput {
@@ -28,6 +40,9 @@ pub struct InitOutput {
     pub config_file: String,
     /// 使用されたDialect
     pub dialect: String,
+    /// 生成されたサンプルファイル（--with-example時）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub example_files: Vec<String>,
 }
 
 impl CommandOutput for InitOutput {
@@ -68,6 +83,25 @@ pub struct InitCommand {
     pub password: Option<String>,
     /// .gitignoreに自動追記
     pub add_gitignore: bool,
+    /// サンプルスキーマとサンプルマイグレーションを生成
+    pub with_example: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// initコマンド（対話モード）の入力パラメータ
+///
+/// Dialectや接続先は対話的に入力するため、ここには含みません。
+#[derive(Debug, Clone)]
+pub struct InitInteractiveCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// 強制的に初期化（既存の設定を上書き）
+    pub force: bool,
+    /// .gitignoreに自動追記
+    pub add_gitignore: bool,
+    /// サンプルスキーマとサンプルマイグレーションを生成
+    pub with_example: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
 }
@@ -125,21 +159,183 @@ impl InitCommandHandler {
             existing_config.as_ref(),
         )?;
 
+        self.finish(
+            &command.project_path,
+            command.dialect,
+            Path::new("schema"),
+            Path::new("migrations"),
+            command.add_gitignore,
+            command.with_example,
+            &command.format,
+        )
+    }
+
+    /// initコマンドを対話モードで実行
+    ///
+    /// 入力が完了し、（選択した場合は）接続テストを終えるまでファイルは書き込みません。
+    /// 書き込む設定は、通常の設定ファイル読み込みと同じ検証を通過したものに限ります。
+    pub async fn execute_interactive<P: Prompter>(
+        &self,
+        command: &InitInteractiveCommand,
+        prompter: &mut P,
+    ) -> Result<String> {
+        debug!(project_path = %command.project_path.display(), force = command.force, "Initializing project interactively");
+        let existing_config = if command.force {
+            self.load_existing_config(&command.project_path)
+        } else {
+            None
+        };
+
+        if self.is_already_initialized(&command.project_path) && !command.force {
+            return Err(anyhow!(
+                "Project is already initialized. Use --force option to force re-initialization."
+            ));
+        }
+
+        let answers = InitWizard::new(prompter).run()?;
+
+        if answers.test_connection && !self.test_connections(&answers, prompter).await? {
+            return Err(anyhow!("Initialization cancelled. No files were written."));
+        }
+
+        let config = self.build_config(
+            answers.dialect,
+            answers.environments.clone(),
+            Some(answers.schema_dir.clone()),
+            Some(answers.migrations_dir.clone()),
+            existing_config.as_ref(),
+        );
+        config
+            .validate()
+            .with_context(|| "The entered configuration is invalid")?;
+        self.write_config(&command.project_path, &config)?;
+
+        self.create_directories(
+            &command.project_path,
+            &answers.schema_dir,
+            &answers.migrations_dir,
+        )?;
+
+        self.finish(
+            &command.project_path,
+            answers.dialect,
+            &answers.schema_dir,
+            &answers.migrations_dir,
+            command.add_gitignore,
+            command.with_example,
+            &command.format,
+        )
+    }
+
+    /// 入力された各環境への接続をテストする
+    ///
+    /// 失敗した環境がある場合は、そのまま設定を書き込むかを確認します。
+    /// 書き込みを続ける場合はtrueを返します。
+    async fn test_connections<P: Prompter>(
+        &self,
+        answers: &InitWizardAnswers,
+        prompter: &mut P,
+    ) -> Result<bool> {
+        let service = DatabaseConnectionService::new();
+        let mut failed = 0;
+
+        prompter.message("\nTesting connections...");
+        for (name, db_config) in &answers.environments {
+            let mut resolved = db_config.clone();
+            resolved.password = resolved.password.as_deref().map(resolve_env_reference);
+            resolved.acquire_timeout = Some(CONNECTION_TEST_TIMEOUT_SECS);
+
+            let result = match service.create_pool(answers.dialect, &resolved).await {
+                Ok(pool) => {
+                    let result = service.test_connection(&pool).await;
+                    service.close_pool(pool).await;
+                    result
+                }
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => prompter.message(&format!("✓ {}: connected", name)),
+                Err(e) => {
+                    failed += 1;
+                    prompter.message(&format!("✗ {}: {}", name, e));
+                }
+            }
+        }
+
+        if failed == 0 {
+            return Ok(true);
+        }
+        prompter.confirm(
+            &format!(
+                "{} environment(s) could not be reached. Write the config anyway?",
+                failed
+            ),
+            false,
+        )
+    }
+
+    /// 設定ファイル書き込み後の共通処理（.gitignore、サンプル生成、出力）
+    #[allow(clippy::too_many_arguments)]
+    fn finish(
+        &self,
+        project_path: &Path,
+        dialect: Dialect,
+        schema_dir: &Path,
+        migrations_dir: &Path,
+        add_gitignore: bool,
+        with_example: bool,
+        format: &OutputFormat,
+    ) -> Result<String> {
         // .gitignoreに設定ファイルを自動追記 or 警告
-        if command.add_gitignore {
-            self.add_to_gitignore(&command.project_path)?;
+        if add_gitignore {
+            self.add_to_gitignore(project_path, Config::DEFAULT_CONFIG_PATH)?;
         } else {
-            self.warn_gitignore(&command.project_path);
+            self.warn_gitignore(project_path);
+        }
+
+        // 未適用マイグレーションの記録はマシンごとの状態なので、常にコミット対象から外す
+        let pending_file = migrations_dir.join(stacked_migrations::PENDING_MIGRATIONS_FILE);
+        self.add_to_gitignore(
+            project_path,
+            &pending_file.to_string_lossy().replace('\\', "/"),
+        )?;
+
+        // サンプルスキーマ・サンプルマイグレーションを生成
+        let example_files: Vec<String> = if with_example {
+            ExampleProjectWriter::new()
+                .write(project_path, schema_dir, migrations_dir, dialect)?
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut message = "Project initialized.".to_string();
+        if !example_files.is_empty() {
+            message.push_str("\n\nExample files:\n");
+            for file in &example_files {
+                message.push_str(&format!("  {}\n", file));
+            }
+            message.push_str(&format!(
+                "\nSee {} for the workflow.",
+                schema_dir.join("README.md").display()
+            ));
         }
 
         let output = InitOutput {
-            message: "Project initialized.".to_string(),
-            created_dirs: vec!["schema/".to_string(), "migrations/".to_string()],
+            message,
+            created_dirs: vec![
+                format!("{}/", schema_dir.display()),
+                format!("{}/", migrations_dir.display()),
+            ],
             config_file: Config::DEFAULT_CONFIG_PATH.to_string(),
-            dialect: format!("{}", command.dialect),
+            dialect: format!("{}", dialect),
+            example_files,
         };
 
-        render_output(&output, &command.format)
+        render_output(&output, format)
     }
 
     /// プロジェクトが既に初期化されているかチェック
@@ -162,19 +358,22 @@ impl InitCommandHandler {
     ///
     /// * `project_path` - プロジェクトのルートパス
     pub fn create_directory_structure(&self, project_path: &Path) -> Result<()> {
-        // schema/ディレクトリを作成
-        let schema_dir = project_path.join("schema");
-        fs::create_dir_all(&schema_dir)
-            .with_context(|| format!("Failed to create schema/ directory: {:?}", schema_dir))?;
-
-        // migrations/ディレクトリを作成
-        let migrations_dir = project_path.join("migrations");
-        fs::create_dir_all(&migrations_dir).with_context(|| {
-            format!(
-                "Failed to create migrations/ directory: {:?}",
-                migrations_dir
-            )
-        })?;
+        self.create_directories(project_path, Path::new("schema"), Path::new("migrations"))
+    }
+
+    /// スキーマ・マイグレーションディレクトリを作成
+    fn create_directories(
+        &self,
+        project_path: &Path,
+        schema_dir: &Path,
+        migrations_dir: &Path,
+    ) -> Result<()> {
+        for dir in [schema_dir, migrations_dir] {
+            let path = project_path.join(dir);
+            fs::create_dir_all(&path).with_context(|| {
+                format!("Failed to create {}/ directory: {:?}", dir.display(), path)
+            })?;
+        }
 
         Ok(())
     }
@@ -201,9 +400,8 @@ impl InitCommandHandler {
         );
     }
 
-    /// .gitignoreに設定ファイルを追記
-    fn add_to_gitignore(&self, project_path: &Path) -> Result<()> {
-        let config_file_name = Config::DEFAULT_CONFIG_PATH;
+    /// .gitignoreにエントリを追記（既に含まれている場合は何もしない）
+    fn add_to_gitignore(&self, project_path: &Path, entry: &str) -> Result<()> {
         let gitignore_path = project_path.join(".gitignore");
 
         // 既に含まれているかチェック
@@ -211,7 +409,7 @@ impl InitCommandHandler {
             if let Ok(content) = fs::read_to_string(&gitignore_path) {
                 if content.lines().any(|line| {
                     let trimmed = line.trim();
-                    trimmed == config_file_name || trimmed == format!("/{}", config_file_name)
+                    trimmed == entry || trimmed == format!("/{}", entry)
                 }) {
                     return Ok(()); // 既に含まれている
                 }
@@ -219,15 +417,15 @@ impl InitCommandHandler {
             // 既存の .gitignore に追記
             let mut content =
                 fs::read_to_string(&gitignore_path).with_context(|| "Failed to read .gitignore")?;
-            if !content.ends_with('\n') {
+            if !content.is_empty() && !content.ends_with('\n') {
                 content.push('\n');
             }
-            content.push_str(config_file_name);
+            content.push_str(entry);
             content.push('\n');
             fs::write(&gitignore_path, content).with_context(|| "Failed to write .gitignore")?;
         } else {
             // 新しい .gitignore を作成
-            fs::write(&gitignore_path, format!("{}\n", config_file_name))
+            fs::write(&gitignore_path, format!("{}\n", entry))
                 .with_context(|| "Failed to create .gitignore")?;
         }
 
@@ -235,12 +433,14 @@ impl InitCommandHandler {
     }
 
     /// 既存の設定ファイルを読み込む
+    ///
+    /// 書き戻したときに環境変数の値が設定ファイルに残らないよう、`${ENV_VAR}` は展開しません。
     fn load_existing_config(&self, project_path: &Path) -> Option<Config> {
         let config_path = project_path.join(Config::DEFAULT_CONFIG_PATH);
         if !config_path.exists() {
             return None;
         }
-        match ConfigLoader::from_file(&config_path) {
+        match ConfigLoader::from_file_without_interpolation(&config_path) {
             Ok(config) => Some(config),
             Err(e) => {
                 warn!("Failed to load existing config file, will overwrite: {}", e);
@@ -289,13 +489,14 @@ impl InitCommandHandler {
             Some(params.user.unwrap_or_else(|| "your_user".to_string()))
         };
 
+        // パスワードは設定ファイルに書かず、環境変数の参照にする
         let password = if is_sqlite {
             params.password
         } else {
             Some(
                 params
                     .password
-                    .unwrap_or_else(|| "your_password".to_string()),
+                    .unwrap_or_else(|| format!("${{{}}}", password_env_var_name("development"))),
             )
         };
 
@@ -310,34 +511,106 @@ impl InitCommandHandler {
             ssl_mode: None,
             max_connections: None,
             min_connections: None,
+            acquire_timeout: None,
             idle_timeout: None,
+            wait_timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
             options: None,
         };
 
+        let config = self.build_config(
+            params.dialect,
+            vec![("development".to_string(), db_config)],
+            None,
+            None,
+            existing_config,
+        );
+        self.write_config(project_path, &config)
+    }
+
+    /// 設定オブジェクトを作成
+    ///
+    /// 既存の設定（--force時）がある場合は、指定した環境のみ上書きし、
+    /// それ以外の環境と設定項目は既存の値を引き継ぎます。
+    /// ディレクトリを指定しない場合は既存の値、なければ `schema` / `migrations` を使います。
+    fn build_config(
+        &self,
+        dialect: Dialect,
+        new_environments: Vec<(String, DatabaseConfig)>,
+        schema_dir: Option<PathBuf>,
+        migrations_dir: Option<PathBuf>,
+        existing_config: Option<&Config>,
+    ) -> Config {
         // 環境設定を作成
         let mut environments = if let Some(existing) = existing_config {
-            // 既存の環境設定を保持し、developmentのみ新しい設定で上書き
+            // 既存の環境設定を保持し、指定した環境のみ新しい設定で上書き
             existing.environments.clone()
         } else {
             HashMap::new()
         };
-        environments.insert("development".to_string(), db_config);
+        environments.extend(new_environments);
 
         // 設定オブジェクトを作成
-        let config = Config {
+        Config {
             version: "1.0".to_string(),
-            dialect: params.dialect,
-            schema_dir: existing_config
-                .map(|c| c.schema_dir.clone())
+            dialect,
+            schema_dir: schema_dir
+                .or_else(|| existing_config.map(|c| c.schema_dir.clone()))
                 .unwrap_or_else(|| PathBuf::from("schema")),
-            migrations_dir: existing_config
-                .map(|c| c.migrations_dir.clone())
+            migrations_dir: migrations_dir
+                .or_else(|| existing_config.map(|c| c.migrations_dir.clone()))
                 .unwrap_or_else(|| PathBuf::from("migrations")),
             environments,
-        };
+            sqlite: existing_config
+                .map(|c| c.sqlite.clone())
+                .unwrap_or_default(),
+            mysql: existing_config.map(|c| c.mysql.clone()).unwrap_or_default(),
+            postgres: existing_config
+                .map(|c| c.postgres.clone())
+                .unwrap_or_default(),
+            policy: existing_config
+                .map(|c| c.policy.clone())
+                .unwrap_or_default(),
+            sql_output: existing_config
+                .map(|c| c.sql_output.clone())
+                .unwrap_or_default(),
+            store_applied_sql: existing_config.is_some_and(|c| c.store_applied_sql),
+            future_version_tolerance_minutes: existing_config
+                .and_then(|c| c.future_version_tolerance_minutes),
+            required_metadata: existing_config
+                .map(|c| c.required_metadata.clone())
+                .unwrap_or_default(),
+            require_clean_pending: existing_config.is_some_and(|c| c.require_clean_pending),
+            enum_order_sensitive: existing_config.is_none_or(|c| c.enum_order_sensitive),
+            protected_environments: existing_config
+                .map(|c| c.protected_environments.clone())
+                .unwrap_or_default(),
+            stream_sql_threshold_bytes: existing_config.and_then(|c| c.stream_sql_threshold_bytes),
+            naming: existing_config
+                .map(|c| c.naming.clone())
+                .unwrap_or_default(),
+            identifiers: existing_config
+                .map(|c| c.identifiers.clone())
+                .unwrap_or_default(),
+            identifier_case: existing_config
+                .map(|c| c.identifier_case)
+                .unwrap_or_default(),
+            update_check: existing_config.is_some_and(|c| c.update_check),
+            view_rename_propagation: existing_config
+                .map(|c| c.view_rename_propagation)
+                .unwrap_or_default(),
+            contract_min_age_days: existing_config.and_then(|c| c.contract_min_age_days),
+            statement_retry: existing_config
+                .map(|c| c.statement_retry.clone())
+                .unwrap_or_default(),
+        }
+    }
 
+    /// 設定ファイルを書き込む
+    fn write_config(&self, project_path: &Path, config: &Config) -> Result<()> {
         // YAMLにシリアライズ
-        let yaml = ConfigSerializer::to_yaml(&config)?;
+        let yaml = ConfigSerializer::to_yaml(config)?;
 
         // ファイルに書き込み
         let config_path = project_path.join(Config::DEFAULT_CONFIG_PATH);
@@ -500,6 +773,31 @@ mod tests {
         assert_eq!(result.environments["development"].host, "new-host");
     }
 
+    #[test]
+    fn test_force_init_keeps_password_references() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let handler = InitCommandHandler::new();
+        let params = ConfigFileParams {
+            dialect: Dialect::PostgreSQL,
+            database_name: "mydb".to_string(),
+            host: None,
+            port: None,
+            user: None,
+            password: None,
+        };
+        handler
+            .generate_config_file(project_path, params, None)
+            .unwrap();
+
+        // パスワードは環境変数の参照として書かれ、--force でも展開されずに引き継がれる
+        let existing = handler.load_existing_config(project_path).unwrap();
+        assert_eq!(
+            existing.environments["development"].password.as_deref(),
+            Some("${STRATA_DEVELOPMENT_PASSWORD}")
+        );
+    }
+
     #[test]
     fn test_init_output_json_serialization() {
         let output = InitOutput {
@@ -507,6 +805,7 @@ mod tests {
             created_dirs: vec!["schema/".to_string(), "migrations/".to_string()],
             config_file: ".strata.yaml".to_string(),
             dialect: "sqlite".to_string(),
+            example_files: Vec::new(),
         };
 
         let json = serde_json::to_string_pretty(&output).unwrap();
@@ -517,5 +816,6 @@ mod tests {
         assert_eq!(parsed["created_dirs"][1], "migrations/");
         assert_eq!(parsed["config_file"], ".strata.yaml");
         assert_eq!(parsed["dialect"], "sqlite");
+        assert!(parsed.get("example_files").is_none());
     }
 }
@@ -0,0 +1,439 @@
+// initコマンドのサンプルプロジェクト生成
+//
+// `strata init --with-example` で生成するサンプルスキーマ・サンプルマイグレーション・
+// スキーマディレクトリ向けREADMEを提供します。
+// サンプルスキーマは実際のSchema構造体をシリアライザーに通して出力するため、
+// サポートされるYAML形式から乖離しません。
+
+use crate::cli::commands::migration_loader::EXAMPLES_DIR_NAME;
+use crate::core::config::Dialect;
+use crate::core::schema::{
+    Column, ColumnType, Constraint, EnumDefinition, Index, IndexColumn, NullsOrder,
+    ReferentialAction, Schema, SortOrder, Table, View,
+};
+use crate::services::migration_generator::MigrationGeneratorService;
+use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// サンプルスキーマのファイル名
+pub const EXAMPLE_SCHEMA_FILE: &str = "example.yaml";
+
+/// サンプルマイグレーションのディレクトリ名（`migrations/examples/` 配下）
+pub const EXAMPLE_MIGRATION_NAME: &str = "00000000000000_create_example_schema";
+
+/// スキーマディレクトリに置くREADMEのファイル名
+pub const SCHEMA_README_FILE: &str = "README.md";
+
+/// サンプルスキーマの先頭に付与するコメント
+const EXAMPLE_SCHEMA_HEADER: &str = "\
+# Example schema generated by `strata init --with-example`.
+#
+# It demonstrates:
+#   - every portable column kind (INTEGER, VARCHAR, CHAR, TEXT, BOOLEAN, DECIMAL,
+#     FLOAT, DOUBLE, DATE, TIME, TIMESTAMP, BLOB, UUID, JSON)
+#   - an enum, indexes with sort order options, a foreign key with
+#     ON DELETE / ON UPDATE actions, a CHECK constraint and a view
+#
+# These tables are picked up by `strata generate` like any other schema file.
+# Delete this file if you do not want them in your database.
+#
+# To rename a column or table later, keep the data by adding `renamed_from`
+# instead of dropping and re-creating it:
+#
+#   users:
+#     columns:
+#       - name: full_name
+#         renamed_from: display_name
+#         type:
+#           kind: VARCHAR
+#           length: 100
+#         nullable: true
+#
+";
+
+/// スキーマディレクトリに置くREADME
+const SCHEMA_README: &str = "\
+# Schema
+
+This directory holds the YAML schema definition. Every `*.yaml` / `*.yml` file
+in it is merged into a single schema.
+
+## Workflow
+
+1. Edit the YAML files in this directory.
+2. Check them with `strata validate`.
+3. Create a migration with `strata generate --description \"...\"`.
+4. Apply it with `strata apply`, and check progress with `strata status`.
+
+## Examples
+
+`example.yaml` is a starter schema that uses every supported feature.
+`migrations/examples/` contains the migration generated from it, for reference.
+Nothing under `migrations/examples/` is ever applied by `strata apply`.
+";
+
+/// サンプル生成のヘルパー
+#[derive(Debug, Default)]
+pub struct ExampleProjectWriter {}
+
+impl ExampleProjectWriter {
+    /// 新しいExampleProjectWriterを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// サンプルスキーマを構築
+    ///
+    /// ENUMはPostgreSQLのみがスキーマレベルの定義をサポートするため、
+    /// MySQLではカラム単位のENUM、SQLiteではCHECK制約で表現します。
+    pub fn example_schema(&self, dialect: Dialect) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+
+        // users テーブル
+        let mut users = Table::new("users".to_string());
+        let mut id = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        id.auto_increment = Some(true);
+        users.add_column(id);
+        users.add_column(Column::new(
+            "email".to_string(),
+            ColumnType::VARCHAR { length: 255 },
+            false,
+        ));
+        users.add_column(Column::new(
+            "country_code".to_string(),
+            ColumnType::CHAR { length: 2 },
+            true,
+        ));
+        users.add_column(Column::new("bio".to_string(), ColumnType::TEXT, true));
+        users.add_column(with_default(
+            Column::new("is_active".to_string(), ColumnType::BOOLEAN, false),
+            "true",
+        ));
+        users.add_column(with_default(
+            Column::new(
+                "balance".to_string(),
+                ColumnType::DECIMAL {
+                    precision: 10,
+                    scale: 2,
+                },
+                false,
+            ),
+            "0",
+        ));
+        users.add_column(Column::new("rating".to_string(), ColumnType::FLOAT, true));
+        users.add_column(Column::new("score".to_string(), ColumnType::DOUBLE, true));
+        users.add_column(Column::new(
+            "birth_date".to_string(),
+            ColumnType::DATE,
+            true,
+        ));
+        users.add_column(Column::new(
+            "wake_up_at".to_string(),
+            ColumnType::TIME {
+                with_time_zone: None,
+                precision: None,
+            },
+            true,
+        ));
+        users.add_column(Column::new("avatar".to_string(), ColumnType::BLOB, true));
+        users.add_column(Column::new(
+            "external_id".to_string(),
+            ColumnType::UUID,
+            true,
+        ));
+        users.add_column(Column::new("settings".to_string(), ColumnType::JSON, true));
+        users.add_column(with_default(
+            Column::new(
+                "created_at".to_string(),
+                ColumnType::TIMESTAMP {
+                    with_time_zone: None,
+                    precision: None,
+                },
+                false,
+            ),
+            "CURRENT_TIMESTAMP",
+        ));
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        users.add_constraint(Constraint::UNIQUE {
+            columns: vec!["email".to_string()],
+        });
+        users.add_index(Index::with_columns(
+            "idx_users_created_at".to_string(),
+            vec![IndexColumn::new("created_at").with_order(SortOrder::Desc)],
+            false,
+        ));
+        schema.add_table(users);
+
+        // posts テーブル
+        let mut posts = Table::new("posts".to_string());
+        let mut id = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        id.auto_increment = Some(true);
+        posts.add_column(id);
+        posts.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_column(Column::new(
+            "title".to_string(),
+            ColumnType::VARCHAR { length: 200 },
+            false,
+        ));
+        posts.add_column(Column::new("body".to_string(), ColumnType::TEXT, true));
+        let status_type = match dialect {
+            Dialect::PostgreSQL => {
+                schema.add_enum(EnumDefinition {
+                    name: "post_status".to_string(),
+                    values: post_status_values(),
+                    renamed_from: None,
+                });
+                ColumnType::Enum {
+                    name: "post_status".to_string(),
+                }
+            }
+            Dialect::MySQL => ColumnType::DialectSpecific {
+                kind: "ENUM".to_string(),
+                params: serde_json::json!({ "values": post_status_values() }),
+            },
+            Dialect::SQLite => ColumnType::VARCHAR { length: 20 },
+        };
+        posts.add_column(with_default(
+            Column::new("status".to_string(), status_type, false),
+            "'draft'",
+        ));
+        posts.add_column(with_default(
+            Column::new("view_count".to_string(), ColumnType::BIGINT, false),
+            "0",
+        ));
+        posts.add_column(Column::new(
+            "published_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            true,
+        ));
+        posts.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        posts.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: Some(ReferentialAction::Restrict),
+        });
+        posts.add_constraint(Constraint::CHECK {
+            columns: vec!["view_count".to_string()],
+            check_expression: "view_count >= 0".to_string(),
+        });
+        if dialect == Dialect::SQLite {
+            posts.add_constraint(Constraint::CHECK {
+                columns: vec!["status".to_string()],
+                check_expression: format!(
+                    "status IN ({})",
+                    post_status_values()
+                        .iter()
+                        .map(|v| format!("'{}'", v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            });
+        }
+
+        // NULLS FIRST/LAST はPostgreSQLのみがサポート
+        let mut published_at = IndexColumn::new("published_at").with_order(SortOrder::Desc);
+        if dialect == Dialect::PostgreSQL {
+            published_at = published_at.with_nulls(NullsOrder::Last);
+        }
+        posts.add_index(Index::with_columns(
+            "idx_posts_user_published".to_string(),
+            vec![IndexColumn::new("user_id"), published_at],
+            false,
+        ));
+        posts.add_index(Index::new(
+            "idx_posts_user_title".to_string(),
+            vec!["user_id".to_string(), "title".to_string()],
+            true,
+        ));
+        schema.add_table(posts);
+
+        // published_posts ビュー
+        let mut view = View::new(
+            "published_posts".to_string(),
+            "SELECT p.id, p.title, p.published_at, u.email AS author_email FROM posts p JOIN users u ON u.id = p.user_id WHERE p.status = 'published'".to_string(),
+        );
+        view.depends_on = vec!["posts".to_string(), "users".to_string()];
+        schema.add_view(view);
+
+        schema
+    }
+
+    /// サンプルスキーマをコメント付きのYAML文字列として出力
+    pub fn example_schema_yaml(&self, dialect: Dialect) -> Result<String> {
+        let yaml = SchemaSerializerService::new()
+            .serialize_to_string(&self.example_schema(dialect))
+            .context("Failed to serialize example schema")?;
+        Ok(format!("{}{}", EXAMPLE_SCHEMA_HEADER, yaml))
+    }
+
+    /// サンプルスキーマから生成したマイグレーションSQL（up, down）を取得
+    pub fn example_migration_sql(&self, dialect: Dialect) -> Result<(String, String)> {
+        let empty = Schema::new("1.0".to_string());
+        let schema = self.example_schema(dialect);
+        let diff = SchemaDiffDetectorService::new().detect_diff(&empty, &schema);
+
+        let generator = MigrationGeneratorService::new();
+        let (up_sql, _) = generator
+            .generate_up_sql_with_schemas(&diff, &empty, &schema, dialect, false)
+            .map_err(|e| anyhow::anyhow!(e))
+            .context("Failed to generate example UP SQL")?;
+        let (down_sql, _) = generator
+            .generate_down_sql_with_schemas(&diff, &empty, &schema, dialect, true)
+            .map_err(|e| anyhow::anyhow!(e))
+            .context("Failed to generate example DOWN SQL")?;
+
+        let header = "-- Example migration generated by `strata init --with-example`.\n\
+                      -- Reference only: `strata apply` never applies anything under migrations/examples/.\n\n";
+        Ok((
+            format!("{}{}", header, up_sql),
+            format!("{}{}", header, down_sql),
+        ))
+    }
+
+    /// サンプルスキーマ・サンプルマイグレーション・READMEを書き出す
+    ///
+    /// # Returns
+    ///
+    /// 書き出したファイルのパス（プロジェクトルートからの相対パス）
+    pub fn write(
+        &self,
+        project_path: &Path,
+        schema_dir: &Path,
+        migrations_dir: &Path,
+        dialect: Dialect,
+    ) -> Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        let mut write_file = |relative: PathBuf, content: &str| -> Result<()> {
+            let path = project_path.join(&relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+            }
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write example file: {:?}", path))?;
+            written.push(relative);
+            Ok(())
+        };
+
+        write_file(
+            schema_dir.join(EXAMPLE_SCHEMA_FILE),
+            &self.example_schema_yaml(dialect)?,
+        )?;
+        write_file(schema_dir.join(SCHEMA_README_FILE), SCHEMA_README)?;
+
+        let (up_sql, down_sql) = self.example_migration_sql(dialect)?;
+        let migration_dir = migrations_dir
+            .join(EXAMPLES_DIR_NAME)
+            .join(EXAMPLE_MIGRATION_NAME);
+        write_file(migration_dir.join("up.sql"), &up_sql)?;
+        write_file(migration_dir.join("down.sql"), &down_sql)?;
+
+        Ok(written)
+    }
+}
+
+/// デフォルト値を設定したカラムを返す
+fn with_default(mut column: Column, default_value: &str) -> Column {
+    column.default_value = Some(default_value.to_string());
+    column
+}
+
+/// 投稿ステータスの値
+fn post_status_values() -> Vec<String> {
+    vec![
+        "draft".to_string(),
+        "published".to_string(),
+        "archived".to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::schema_io::schema_parser::SchemaParserService;
+    use crate::services::schema_validator::SchemaValidatorService;
+    use tempfile::TempDir;
+
+    const DIALECTS: [Dialect; 3] = [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite];
+
+    #[test]
+    fn test_example_schema_parses_back() {
+        let writer = ExampleProjectWriter::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        for dialect in DIALECTS {
+            let yaml = writer.example_schema_yaml(dialect).unwrap();
+            let path = temp_dir.path().join(format!("{}.yaml", dialect));
+            fs::write(&path, &yaml).unwrap();
+
+            let parsed = SchemaParserService::new().parse_schema_file(&path).unwrap();
+            assert_eq!(parsed, writer.example_schema(dialect), "{}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_example_schema_is_valid() {
+        let writer = ExampleProjectWriter::new();
+
+        for dialect in DIALECTS {
+            let result = SchemaValidatorService::new()
+                .validate_with_dialect(&writer.example_schema(dialect), dialect);
+            assert!(result.is_valid(), "{}: {:?}", dialect, result.errors);
+        }
+    }
+
+    #[test]
+    fn test_example_schema_covers_features() {
+        let yaml = ExampleProjectWriter::new()
+            .example_schema_yaml(Dialect::PostgreSQL)
+            .unwrap();
+
+        for expected in [
+            "enums:",
+            "views:",
+            "on_delete: CASCADE",
+            "check_expression:",
+            "order: DESC",
+            "nulls: LAST",
+            "renamed_from",
+        ] {
+            assert!(yaml.contains(expected), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_example_migration_sql() {
+        let writer = ExampleProjectWriter::new();
+
+        for dialect in DIALECTS {
+            let (up_sql, down_sql) = writer.example_migration_sql(dialect).unwrap();
+            assert!(up_sql.contains("CREATE TABLE"), "{}", dialect);
+            assert!(up_sql.contains("published_posts"), "{}", dialect);
+            assert!(down_sql.contains("DROP TABLE"), "{}", dialect);
+        }
+    }
+}
@@ -0,0 +1,571 @@
+// init対話モード
+//
+// `strata init` を --dialect なしで端末から実行したときに、設定ファイルの内容を対話的に入力します。
+// - Dialect・作成する環境名
+// - 環境ごとの接続先（host/port/database/user）とパスワード（環境変数参照 `${VAR}` を推奨）
+// - スキーマ・マイグレーションディレクトリ
+// - 書き込み前に接続テストを行うか
+//
+// 端末入出力は `Prompter` トレイトで抽象化しており、テストでは `ScriptedPrompter` で差し替えます。
+
+use crate::core::config::{DatabaseConfig, Dialect};
+use anyhow::{anyhow, Context, Result};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+/// 対話モードの端末入出力
+pub trait Prompter {
+    /// 1行の入力を受け取る（空入力の場合は `default` を返す）
+    fn input(&mut self, prompt: &str, default: Option<&str>) -> Result<String>;
+
+    /// パスワードを入力を表示せずに受け取る
+    fn password(&mut self, prompt: &str) -> Result<String>;
+
+    /// yes/no の確認を受け取る（空入力の場合は `default` を返す）
+    fn confirm(&mut self, prompt: &str, default: bool) -> Result<bool>;
+
+    /// メッセージを表示する
+    fn message(&mut self, message: &str);
+}
+
+/// 標準入力から読み込み、プロンプトを標準エラー出力に表示するPrompter
+///
+/// 標準出力はコマンドの結果（`--format json` を含む）のために空けておきます。
+#[derive(Debug, Default)]
+pub struct TerminalPrompter;
+
+impl TerminalPrompter {
+    /// 新しいTerminalPrompterを作成
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn read_line(&self) -> Result<String> {
+        let mut line = String::new();
+        let read = io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .with_context(|| "Failed to read from stdin")?;
+        if read == 0 {
+            return Err(anyhow!(
+                "Input ended before the init wizard finished. No files were written."
+            ));
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    }
+
+    fn show_prompt(&self, prompt: &str) {
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{}", prompt);
+        let _ = stderr.flush();
+    }
+}
+
+impl Prompter for TerminalPrompter {
+    fn input(&mut self, prompt: &str, default: Option<&str>) -> Result<String> {
+        match default {
+            Some(default) => self.show_prompt(&format!("{} [{}]: ", prompt, default)),
+            None => self.show_prompt(&format!("{}: ", prompt)),
+        }
+        let line = self.read_line()?;
+        let answer = line.trim();
+        if answer.is_empty() {
+            Ok(default.unwrap_or_default().to_string())
+        } else {
+            Ok(answer.to_string())
+        }
+    }
+
+    fn password(&mut self, prompt: &str) -> Result<String> {
+        self.show_prompt(&format!("{} (input is hidden): ", prompt));
+        let echo_disabled = set_terminal_echo(false);
+        let line = self.read_line();
+        if echo_disabled {
+            set_terminal_echo(true);
+            eprintln!();
+        }
+        line
+    }
+
+    fn confirm(&mut self, prompt: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        loop {
+            self.show_prompt(&format!("{} [{}]: ", prompt, hint));
+            match parse_yes_no(&self.read_line()?, default) {
+                Some(answer) => return Ok(answer),
+                None => eprintln!("Please answer 'y' or 'n'."),
+            }
+        }
+    }
+
+    fn message(&mut self, message: &str) {
+        eprintln!("{}", message);
+    }
+}
+
+/// 端末のエコーを切り替える（成功した場合はtrue）
+///
+/// 追加の依存を持たないよう `stty` を使います。使えない環境では入力がそのまま表示されます。
+fn set_terminal_echo(enabled: bool) -> bool {
+    if !cfg!(unix) {
+        return false;
+    }
+    std::process::Command::new("stty")
+        .arg(if enabled { "echo" } else { "-echo" })
+        .stdin(std::process::Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// あらかじめ用意した回答を順に返すPrompter
+///
+/// 対話モードをテストやスクリプトから実行するために使います。
+/// 回答が尽きた場合はエラーを返します。
+#[derive(Debug, Default)]
+pub struct ScriptedPrompter {
+    answers: VecDeque<String>,
+    /// 表示されたプロンプトとメッセージ（表示順）
+    pub transcript: Vec<String>,
+}
+
+impl ScriptedPrompter {
+    /// 回答を指定してScriptedPrompterを作成（空文字列はデフォルト値を選ぶ）
+    pub fn new<I, S>(answers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            answers: answers.into_iter().map(Into::into).collect(),
+            transcript: Vec::new(),
+        }
+    }
+
+    /// 使われなかった回答の数
+    pub fn remaining(&self) -> usize {
+        self.answers.len()
+    }
+
+    fn next_answer(&mut self, prompt: &str) -> Result<String> {
+        self.transcript.push(prompt.to_string());
+        self.answers
+            .pop_front()
+            .ok_or_else(|| anyhow!("No scripted answer for prompt: {}", prompt))
+    }
+}
+
+impl Prompter for ScriptedPrompter {
+    fn input(&mut self, prompt: &str, default: Option<&str>) -> Result<String> {
+        let answer = self.next_answer(prompt)?;
+        let answer = answer.trim();
+        if answer.is_empty() {
+            Ok(default.unwrap_or_default().to_string())
+        } else {
+            Ok(answer.to_string())
+        }
+    }
+
+    fn password(&mut self, prompt: &str) -> Result<String> {
+        self.next_answer(prompt)
+    }
+
+    fn confirm(&mut self, prompt: &str, default: bool) -> Result<bool> {
+        let answer = self.next_answer(prompt)?;
+        parse_yes_no(&answer, default)
+            .ok_or_else(|| anyhow!("Invalid scripted answer for '{}': {}", prompt, answer))
+    }
+
+    fn message(&mut self, message: &str) {
+        self.transcript.push(message.to_string());
+    }
+}
+
+/// yes/no の回答を解釈する（解釈できない場合はNone）
+fn parse_yes_no(answer: &str, default: bool) -> Option<bool> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "" => Some(default),
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// 対話モードで入力された設定
+#[derive(Debug, Clone)]
+pub struct InitWizardAnswers {
+    /// データベース方言
+    pub dialect: Dialect,
+    /// 作成する環境（入力順）
+    pub environments: Vec<(String, DatabaseConfig)>,
+    /// スキーマ定義ディレクトリ
+    pub schema_dir: PathBuf,
+    /// マイグレーションディレクトリ
+    pub migrations_dir: PathBuf,
+    /// 設定ファイルを書き込む前に接続テストを行うか
+    pub test_connection: bool,
+}
+
+/// init対話モードの入力フロー
+pub struct InitWizard<'a, P: Prompter> {
+    prompter: &'a mut P,
+}
+
+impl<'a, P: Prompter> InitWizard<'a, P> {
+    /// 新しいInitWizardを作成
+    pub fn new(prompter: &'a mut P) -> Self {
+        Self { prompter }
+    }
+
+    /// 設定を対話的に入力する
+    ///
+    /// 不正な値が入力された場合は理由を表示して同じ項目を再度尋ねます。
+    pub fn run(&mut self) -> Result<InitWizardAnswers> {
+        self.prompter.message(
+            "This wizard writes .strata.yaml. Press Enter to accept the value in brackets.",
+        );
+
+        let dialect = self.ask_dialect()?;
+        let environment_names = self.ask_environment_names()?;
+
+        let mut environments = Vec::new();
+        for name in environment_names {
+            let db_config = self.ask_database_config(dialect, &name)?;
+            environments.push((name, db_config));
+        }
+
+        let schema_dir = self.ask_directory("Schema directory", "schema")?;
+        let migrations_dir = self.ask_directory("Migrations directory", "migrations")?;
+        let test_connection = self.prompter.confirm(
+            "Test the connection to each environment before writing the config?",
+            true,
+        )?;
+
+        Ok(InitWizardAnswers {
+            dialect,
+            environments,
+            schema_dir,
+            migrations_dir,
+            test_connection,
+        })
+    }
+
+    fn ask_dialect(&mut self) -> Result<Dialect> {
+        loop {
+            let answer = self.prompter.input(
+                "Database dialect (postgresql, mysql, sqlite)",
+                Some("postgresql"),
+            )?;
+            match Dialect::from_name(&answer.to_ascii_lowercase()) {
+                Some(dialect) => return Ok(dialect),
+                None => self.prompter.message(&format!(
+                    "Unsupported database dialect: {}. Please enter one of: postgresql, mysql, sqlite.",
+                    answer
+                )),
+            }
+        }
+    }
+
+    fn ask_environment_names(&mut self) -> Result<Vec<String>> {
+        loop {
+            let answer = self.prompter.input(
+                "Environments to create (comma-separated)",
+                Some("development"),
+            )?;
+            let mut names: Vec<String> = Vec::new();
+            for name in answer.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                if !names.iter().any(|existing| existing == name) {
+                    names.push(name.to_string());
+                }
+            }
+
+            match names.iter().find(|name| !is_valid_environment_name(name)) {
+                _ if names.is_empty() => self
+                    .prompter
+                    .message("Please enter at least one environment name."),
+                Some(invalid) => self.prompter.message(&format!(
+                    "Invalid environment name: {}. Use letters, digits, '-' and '_'.",
+                    invalid
+                )),
+                None => return Ok(names),
+            }
+        }
+    }
+
+    fn ask_database_config(
+        &mut self,
+        dialect: Dialect,
+        environment: &str,
+    ) -> Result<DatabaseConfig> {
+        self.prompter
+            .message(&format!("\nConnection for environment '{}':", environment));
+
+        if matches!(dialect, Dialect::SQLite) {
+            let default_file = if environment == "development" {
+                "strata.db".to_string()
+            } else {
+                format!("strata_{}.db", environment)
+            };
+            let database = self.ask_required("  Database file", Some(&default_file))?;
+            return Ok(DatabaseConfig {
+                host: String::new(),
+                database,
+                ..Default::default()
+            });
+        }
+
+        let host = self.ask_required("  Host", Some("localhost"))?;
+        let port = self.ask_port(dialect)?;
+        let default_database = if environment == "development" {
+            format!("{}_db", dialect)
+        } else {
+            format!("{}_{}", dialect, environment)
+        };
+        let database = self.ask_required("  Database", Some(&default_database))?;
+        let user = self.ask_required("  User", None)?;
+        let password = self.ask_password(environment)?;
+
+        Ok(DatabaseConfig {
+            host,
+            port: Some(port),
+            database,
+            user: Some(user),
+            password,
+            timeout: Some(30),
+            ..Default::default()
+        })
+    }
+
+    fn ask_port(&mut self, dialect: Dialect) -> Result<u16> {
+        let default_port = dialect.default_port().unwrap_or(0).to_string();
+        loop {
+            let answer = self.prompter.input("  Port", Some(&default_port))?;
+            match answer.parse::<u16>() {
+                Ok(port) if port > 0 => return Ok(port),
+                _ => self.prompter.message(&format!(
+                    "Invalid port number: {}. Please enter a number between 1 and 65535.",
+                    answer
+                )),
+            }
+        }
+    }
+
+    /// パスワードを尋ねる
+    ///
+    /// 設定ファイルに平文で残さないよう、環境変数参照（`${VAR}`）を既定にしています。
+    fn ask_password(&mut self, environment: &str) -> Result<Option<String>> {
+        let use_env_var = self.prompter.confirm(
+            "  Read the password from an environment variable instead of storing it in the file?",
+            true,
+        )?;
+
+        if use_env_var {
+            let default_name = password_env_var_name(environment);
+            loop {
+                let name = self
+                    .prompter
+                    .input("  Environment variable name", Some(&default_name))?;
+                if is_valid_env_var_name(&name) {
+                    return Ok(Some(format!("${{{}}}", name)));
+                }
+                self.prompter.message(&format!(
+                    "Invalid environment variable name: {}. Use letters, digits and '_', not starting with a digit.",
+                    name
+                ));
+            }
+        }
+
+        let password = self.prompter.password("  Password")?;
+        Ok((!password.is_empty()).then_some(password))
+    }
+
+    fn ask_directory(&mut self, prompt: &str, default: &str) -> Result<PathBuf> {
+        Ok(PathBuf::from(self.ask_required(prompt, Some(default))?))
+    }
+
+    fn ask_required(&mut self, prompt: &str, default: Option<&str>) -> Result<String> {
+        loop {
+            let answer = self.prompter.input(prompt, default)?;
+            if !answer.is_empty() {
+                return Ok(answer);
+            }
+            self.prompter.message("A value is required.");
+        }
+    }
+}
+
+/// 環境ごとのパスワード用環境変数名の候補（例: `STRATA_DEVELOPMENT_PASSWORD`）
+pub(crate) fn password_env_var_name(environment: &str) -> String {
+    let environment: String = environment
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("STRATA_{}_PASSWORD", environment)
+}
+
+fn is_valid_environment_name(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_valid_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 接続テスト用に `${VAR}` 形式の値を環境変数の値で置き換える
+///
+/// 設定ファイルを書き込む前に接続を試すため、未設定の環境変数は空文字列として扱います。
+pub(crate) fn resolve_env_reference(value: &str) -> String {
+    match value
+        .strip_prefix("${")
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        Some(name) => std::env::var(name).unwrap_or_default(),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_postgresql_with_env_var_password() {
+        let mut prompter = ScriptedPrompter::new([
+            "postgresql",
+            "development, staging",
+            // development
+            "",
+            "",
+            "",
+            "app",
+            "",
+            "",
+            // staging
+            "db.staging.internal",
+            "6432",
+            "app_staging",
+            "deploy",
+            "y",
+            "STAGING_DB_PASSWORD",
+            "db",
+            "db/migrations",
+            "n",
+        ]);
+
+        let answers = InitWizard::new(&mut prompter).run().unwrap();
+        assert_eq!(prompter.remaining(), 0);
+
+        assert_eq!(answers.dialect, Dialect::PostgreSQL);
+        assert_eq!(answers.schema_dir, PathBuf::from("db"));
+        assert_eq!(answers.migrations_dir, PathBuf::from("db/migrations"));
+        assert!(!answers.test_connection);
+
+        let names: Vec<&str> = answers
+            .environments
+            .iter()
+            .map(|(n, _)| n.as_str())
+            .collect();
+        assert_eq!(names, vec!["development", "staging"]);
+
+        let development = &answers.environments[0].1;
+        assert_eq!(development.host, "localhost");
+        assert_eq!(development.port, Some(5432));
+        assert_eq!(development.database, "postgresql_db");
+        assert_eq!(development.user.as_deref(), Some("app"));
+        assert_eq!(
+            development.password.as_deref(),
+            Some("${STRATA_DEVELOPMENT_PASSWORD}")
+        );
+
+        let staging = &answers.environments[1].1;
+        assert_eq!(staging.host, "db.staging.internal");
+        assert_eq!(staging.port, Some(6432));
+        assert_eq!(staging.database, "app_staging");
+        assert_eq!(staging.password.as_deref(), Some("${STAGING_DB_PASSWORD}"));
+    }
+
+    #[test]
+    fn test_wizard_literal_password_and_reprompts_invalid_values() {
+        let mut prompter = ScriptedPrompter::new([
+            "oracle", // 未対応のDialect → 再入力
+            "mysql", "", "", "99999", // 不正なポート → 再入力
+            "3307", "", "", // ユーザーは必須 → 再入力
+            "root", "n", "s3cret", "", "", "",
+        ]);
+
+        let answers = InitWizard::new(&mut prompter).run().unwrap();
+        assert_eq!(prompter.remaining(), 0);
+
+        assert_eq!(answers.dialect, Dialect::MySQL);
+        let (name, development) = &answers.environments[0];
+        assert_eq!(name, "development");
+        assert_eq!(development.port, Some(3307));
+        assert_eq!(development.database, "mysql_db");
+        assert_eq!(development.user.as_deref(), Some("root"));
+        assert_eq!(development.password.as_deref(), Some("s3cret"));
+        assert!(answers.test_connection);
+
+        assert!(prompter
+            .transcript
+            .iter()
+            .any(|line| line.starts_with("Unsupported database dialect: oracle")));
+        assert!(prompter
+            .transcript
+            .iter()
+            .any(|line| line.starts_with("Invalid port number: 99999")));
+        assert!(prompter
+            .transcript
+            .iter()
+            .any(|line| line == "A value is required."));
+    }
+
+    #[test]
+    fn test_wizard_sqlite_asks_only_for_the_database_file() {
+        let mut prompter =
+            ScriptedPrompter::new(["sqlite", "development,test", "", "", "", "", ""]);
+
+        let answers = InitWizard::new(&mut prompter).run().unwrap();
+        assert_eq!(prompter.remaining(), 0);
+
+        assert_eq!(answers.environments[0].1.database, "strata.db");
+        assert_eq!(answers.environments[1].1.database, "strata_test.db");
+        assert!(answers.environments[1].1.host.is_empty());
+        assert!(answers.environments[1].1.user.is_none());
+    }
+
+    #[test]
+    fn test_wizard_fails_when_input_runs_out() {
+        let mut prompter = ScriptedPrompter::new(["postgresql"]);
+        let result = InitWizard::new(&mut prompter).run();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_password_env_var_name() {
+        assert_eq!(
+            password_env_var_name("development"),
+            "STRATA_DEVELOPMENT_PASSWORD"
+        );
+        assert_eq!(password_env_var_name("qa-1"), "STRATA_QA_1_PASSWORD");
+        assert!(is_valid_env_var_name("STRATA_QA_1_PASSWORD"));
+        assert!(!is_valid_env_var_name("1PASSWORD"));
+        assert!(!is_valid_env_var_name("DB-PASSWORD"));
+    }
+
+    #[test]
+    fn test_resolve_env_reference_leaves_literals() {
+        assert_eq!(resolve_env_reference("plain"), "plain");
+        assert_eq!(resolve_env_reference("${STRATA_WIZARD_UNSET_TEST_VAR}"), "");
+    }
+}
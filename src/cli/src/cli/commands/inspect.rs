@@ -0,0 +1,513 @@
+// inspectコマンドハンドラー
+//
+// 稼働中のデータベースの実データを調べ、スキーマ定義の見直しに使える情報を表示します。
+// --suggest-sizes: VARCHAR/CHAR/TEXT カラムの実データ長と宣言長を比較し、推奨サイズを提案
+
+use crate::adapters::column_statistics::ColumnStatisticsCollector;
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use crate::core::schema::{Column, Schema};
+use crate::services::column_size_advisor::{ColumnSizeAdvisor, ColumnSizeReport, ColumnSizeStatus};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// --allow-full-scan なしでスキャンするテーブルの行数上限（デフォルト）
+pub const DEFAULT_MAX_SCAN_ROWS: u64 = 1_000_000;
+
+/// inspectコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectOutput {
+    /// データベース方言
+    pub dialect: Dialect,
+    /// カラムごとのサイズ判定
+    pub columns: Vec<ColumnSizeReport>,
+    /// 行数の上限によりスキャンしなかったテーブル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_tables: Vec<SkippedTable>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for InspectOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// スキャンしなかったテーブル
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedTable {
+    /// テーブル名
+    pub table: String,
+    /// 行数の見積もり（取得できなかった場合はNone）
+    pub estimated_rows: Option<i64>,
+    /// スキップした理由
+    pub reason: String,
+}
+
+/// inspectコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct InspectCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// カラムサイズの提案を行う
+    pub suggest_sizes: bool,
+    /// 対象テーブル（空の場合は全テーブル）
+    pub tables: Vec<String>,
+    /// 対象カラム（`column` または `table.column`、空の場合は全カラム）
+    pub columns: Vec<String>,
+    /// 行数の上限を超えるテーブルもスキャンする
+    pub allow_full_scan: bool,
+    /// --allow-full-scan なしでスキャンするテーブルの行数上限
+    pub max_rows: u64,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// サイズ判定の対象となるテーブルとカラム
+#[derive(Debug, Clone)]
+struct SizeTarget {
+    table: String,
+    columns: Vec<Column>,
+}
+
+/// inspectコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct InspectCommandHandler {}
+
+impl InspectCommandHandler {
+    /// 新しいInspectCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// inspectコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - inspectコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は調査結果のレポート、失敗時はエラーメッセージ
+    pub async fn execute(&self, command: &InspectCommand) -> Result<String> {
+        if !command.suggest_sizes {
+            return Err(anyhow!("Specify what to inspect, e.g. --suggest-sizes."));
+        }
+
+        // スキーマ定義を読み込み、対象カラムを決定
+        let (context, schema) = CommandContext::load_with_schema(
+            command.project_path.clone(),
+            command.config_path.clone(),
+            &SchemaLoadOptions::default(),
+        )?;
+        let targets = self.select_targets(&schema, &command.tables, &command.columns)?;
+        debug!(
+            tables = targets.len(),
+            "Selected tables for size inspection"
+        );
+
+        let report = context
+            .run_with_pools(async {
+                let pool = context.connect_pool(&command.env).await?;
+                self.suggest_sizes(&pool, context.dialect(), &targets, command)
+                    .await
+            })
+            .await?;
+        render_output(&report, &command.format)
+    }
+
+    /// フィルタを適用し、サイズ判定の対象となるテーブルとカラムを選ぶ
+    fn select_targets(
+        &self,
+        schema: &Schema,
+        tables: &[String],
+        columns: &[String],
+    ) -> Result<Vec<SizeTarget>> {
+        for name in tables {
+            if !schema.has_table(name) {
+                return Err(anyhow!("Table '{}' not found in schema.", name));
+            }
+        }
+
+        let advisor = ColumnSizeAdvisor::new();
+        let mut table_names: Vec<&String> = schema.tables.keys().collect();
+        table_names.sort();
+
+        let mut matched_filters: HashSet<&str> = HashSet::new();
+        let mut targets = Vec::new();
+        for table_name in table_names {
+            if !tables.is_empty() && !tables.contains(table_name) {
+                continue;
+            }
+            let table = &schema.tables[table_name];
+
+            let selected: Vec<Column> = advisor
+                .candidate_columns(table)
+                .into_iter()
+                .filter(|column| {
+                    if columns.is_empty() {
+                        return true;
+                    }
+                    let qualified = format!("{}.{}", table_name, column.name);
+                    match columns
+                        .iter()
+                        .find(|f| **f == column.name || **f == qualified)
+                    {
+                        Some(filter) => {
+                            matched_filters.insert(filter.as_str());
+                            true
+                        }
+                        None => false,
+                    }
+                })
+                .cloned()
+                .collect();
+
+            if !selected.is_empty() {
+                targets.push(SizeTarget {
+                    table: table_name.clone(),
+                    columns: selected,
+                });
+            }
+        }
+
+        if let Some(unmatched) = columns
+            .iter()
+            .find(|f| !matched_filters.contains(f.as_str()))
+        {
+            return Err(anyhow!(
+                "Column '{}' not found among VARCHAR/CHAR/TEXT columns in the schema.",
+                unmatched
+            ));
+        }
+
+        Ok(targets)
+    }
+
+    /// 対象カラムの実データ長を取得し、サイズを判定
+    async fn suggest_sizes(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        targets: &[SizeTarget],
+        command: &InspectCommand,
+    ) -> Result<InspectOutput> {
+        let collector = ColumnStatisticsCollector::new(dialect);
+        let advisor = ColumnSizeAdvisor::new();
+
+        let mut reports = Vec::new();
+        let mut skipped_tables = Vec::new();
+        for target in targets {
+            // 巨大なテーブルを誤って全件スキャンしないよう、行数の見積もりで保護する
+            if !command.allow_full_scan {
+                let estimated_rows = collector.estimate_row_count(pool, &target.table).await?;
+                if let Some(reason) = row_guard_reason(estimated_rows, command.max_rows) {
+                    debug!(table = %target.table, reason = %reason, "Skipping table");
+                    skipped_tables.push(SkippedTable {
+                        table: target.table.clone(),
+                        estimated_rows,
+                        reason,
+                    });
+                    continue;
+                }
+            }
+
+            let column_names: Vec<String> = target.columns.iter().map(|c| c.name.clone()).collect();
+            let stats = collector
+                .fetch_length_stats(pool, &target.table, &column_names)
+                .await?;
+            for (column, stats) in target.columns.iter().zip(&stats) {
+                reports.push(advisor.evaluate(&target.table, column, stats));
+            }
+        }
+
+        let text_message = format_size_report(dialect, &reports, &skipped_tables);
+
+        Ok(InspectOutput {
+            dialect,
+            columns: reports,
+            skipped_tables,
+            text_message,
+        })
+    }
+}
+
+/// 行数の見積もりがスキャンの上限を超える場合、スキップ理由を返す
+fn row_guard_reason(estimated_rows: Option<i64>, max_rows: u64) -> Option<String> {
+    match estimated_rows {
+        None => Some(
+            "row count estimate is unavailable (run ANALYZE, or use --allow-full-scan)".to_string(),
+        ),
+        Some(rows) if rows as u64 > max_rows => Some(format!(
+            "about {} rows exceeds --max-rows {} (use --allow-full-scan)",
+            rows, max_rows
+        )),
+        Some(_) => None,
+    }
+}
+
+/// サイズ判定結果のテキストを生成
+fn format_size_report(
+    dialect: Dialect,
+    reports: &[ColumnSizeReport],
+    skipped_tables: &[SkippedTable],
+) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "=== Column Size Report ({}) ===", dialect);
+
+    for (status, title) in [
+        (ColumnSizeStatus::Oversized, "Oversized columns"),
+        (ColumnSizeStatus::Tight, "Tight columns"),
+    ] {
+        let matching: Vec<&ColumnSizeReport> =
+            reports.iter().filter(|r| r.status == status).collect();
+        if matching.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(output, "\n{}: {}\n", title, matching.len());
+        for report in matching {
+            let mut observed = format!("max {}", report.max_length.unwrap_or_default());
+            if let Some(p99) = report.p99_length {
+                let _ = write!(observed, ", p99 {}", p99);
+            }
+            let suggestion = report
+                .suggested_type
+                .as_ref()
+                .map(|t| format!(" -> suggest {}", t))
+                .unwrap_or_default();
+            let _ = writeln!(
+                output,
+                "  - {}.{} {}: {}{}",
+                report.table, report.column, report.declared_type, observed, suggestion
+            );
+        }
+    }
+
+    if !skipped_tables.is_empty() {
+        let _ = writeln!(output, "\nSkipped tables: {}\n", skipped_tables.len());
+        for skipped in skipped_tables {
+            let _ = writeln!(output, "  - {}: {}", skipped.table, skipped.reason);
+        }
+    }
+
+    let count = |status: ColumnSizeStatus| reports.iter().filter(|r| r.status == status).count();
+    let table_count = reports
+        .iter()
+        .map(|r| r.table.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+    let _ = writeln!(
+        output,
+        "\nChecked {} columns in {} tables ({} oversized, {} tight, {} ok, {} without data).",
+        reports.len(),
+        table_count,
+        count(ColumnSizeStatus::Oversized),
+        count(ColumnSizeStatus::Tight),
+        count(ColumnSizeStatus::Ok),
+        count(ColumnSizeStatus::NoData)
+    );
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{ColumnType, Table};
+
+    fn schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut users = Table::new("users".to_string());
+        users.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        users.add_column(Column::new(
+            "name".to_string(),
+            ColumnType::VARCHAR { length: 255 },
+            false,
+        ));
+        users.add_column(Column::new("bio".to_string(), ColumnType::TEXT, true));
+        schema.add_table(users);
+
+        let mut posts = Table::new("posts".to_string());
+        posts.add_column(Column::new(
+            "title".to_string(),
+            ColumnType::VARCHAR { length: 100 },
+            false,
+        ));
+        posts.add_column(Column::new(
+            "name".to_string(),
+            ColumnType::VARCHAR { length: 50 },
+            false,
+        ));
+        schema.add_table(posts);
+
+        let mut counters = Table::new("counters".to_string());
+        counters.add_column(Column::new(
+            "value".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(counters);
+        schema
+    }
+
+    fn target_names(targets: &[SizeTarget]) -> Vec<String> {
+        targets
+            .iter()
+            .flat_map(|t| {
+                t.columns
+                    .iter()
+                    .map(move |c| format!("{}.{}", t.table, c.name))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_targets_all_string_columns() {
+        let targets = InspectCommandHandler::new()
+            .select_targets(&schema(), &[], &[])
+            .unwrap();
+
+        // 文字列カラムのないテーブルは対象外、テーブル名順
+        assert_eq!(
+            target_names(&targets),
+            vec!["posts.title", "posts.name", "users.name", "users.bio"]
+        );
+    }
+
+    #[test]
+    fn test_select_targets_with_filters() {
+        let handler = InspectCommandHandler::new();
+
+        let targets = handler
+            .select_targets(&schema(), &["users".to_string()], &[])
+            .unwrap();
+        assert_eq!(target_names(&targets), vec!["users.name", "users.bio"]);
+
+        // カラム名のみの指定は全テーブルに、table.column は特定のテーブルに一致する
+        let targets = handler
+            .select_targets(&schema(), &[], &["name".to_string()])
+            .unwrap();
+        assert_eq!(target_names(&targets), vec!["posts.name", "users.name"]);
+
+        let targets = handler
+            .select_targets(&schema(), &[], &["users.bio".to_string()])
+            .unwrap();
+        assert_eq!(target_names(&targets), vec!["users.bio"]);
+    }
+
+    #[test]
+    fn test_select_targets_rejects_unknown_filters() {
+        let handler = InspectCommandHandler::new();
+
+        let error = handler
+            .select_targets(&schema(), &["missing".to_string()], &[])
+            .unwrap_err();
+        assert!(error.to_string().contains("Table 'missing' not found"));
+
+        // 文字列型でないカラムも対象外
+        let error = handler
+            .select_targets(&schema(), &[], &["users.id".to_string()])
+            .unwrap_err();
+        assert!(error.to_string().contains("Column 'users.id' not found"));
+    }
+
+    #[test]
+    fn test_row_guard_reason() {
+        assert_eq!(row_guard_reason(Some(10), 100), None);
+        assert_eq!(row_guard_reason(Some(100), 100), None);
+        assert!(row_guard_reason(Some(101), 100)
+            .unwrap()
+            .contains("about 101 rows exceeds --max-rows 100"));
+        assert!(row_guard_reason(None, 100)
+            .unwrap()
+            .contains("estimate is unavailable"));
+    }
+
+    #[test]
+    fn test_format_size_report() {
+        let report = |column: &str, declared, max, status, suggested| ColumnSizeReport {
+            table: "users".to_string(),
+            column: column.to_string(),
+            declared_type: declared,
+            max_length: max,
+            p99_length: None,
+            status,
+            suggested_type: suggested,
+        };
+        let reports = vec![
+            report(
+                "nickname",
+                ColumnType::VARCHAR { length: 255 },
+                Some(12),
+                ColumnSizeStatus::Oversized,
+                Some(ColumnType::VARCHAR { length: 32 }),
+            ),
+            report(
+                "code",
+                ColumnType::VARCHAR { length: 8 },
+                Some(8),
+                ColumnSizeStatus::Tight,
+                Some(ColumnType::VARCHAR { length: 16 }),
+            ),
+            report(
+                "memo",
+                ColumnType::TEXT,
+                None,
+                ColumnSizeStatus::NoData,
+                None,
+            ),
+        ];
+        let skipped = vec![SkippedTable {
+            table: "events".to_string(),
+            estimated_rows: Some(500_000_000),
+            reason: "about 500000000 rows exceeds --max-rows 1000000 (use --allow-full-scan)"
+                .to_string(),
+        }];
+
+        let text = format_size_report(Dialect::SQLite, &reports, &skipped);
+
+        assert!(text.contains("Oversized columns: 1"), "{}", text);
+        assert!(
+            text.contains("  - users.nickname VARCHAR(255): max 12 -> suggest VARCHAR(32)"),
+            "{}",
+            text
+        );
+        assert!(text.contains("Tight columns: 1"), "{}", text);
+        assert!(
+            text.contains("  - users.code VARCHAR(8): max 8 -> suggest VARCHAR(16)"),
+            "{}",
+            text
+        );
+        assert!(text.contains("Skipped tables: 1"), "{}", text);
+        assert!(
+            text.contains("  - events: about 500000000 rows"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains(
+                "Checked 3 columns in 1 tables (1 oversized, 1 tight, 0 ok, 1 without data)."
+            ),
+            "{}",
+            text
+        );
+    }
+}
@@ -0,0 +1,334 @@
+// introspectコマンドハンドラー
+//
+// 型マッピングの不具合調査用に、DatabaseIntrospector が返す生の情報と
+// SchemaConversionService による変換結果を並べて表示します。
+
+use crate::adapters::database_introspector::{
+    create_introspector, create_introspector_for_config, DatabaseIntrospector, RawConstraintInfo,
+    RawEnumInfo,
+};
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use crate::core::schema::Table;
+use crate::services::schema_conversion::{RawTableInfo, SchemaConversionService};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// introspectコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct IntrospectOutput {
+    /// データベース方言
+    pub dialect: Dialect,
+    /// ENUM定義（PostgreSQLのみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub enums: Vec<RawEnumInfo>,
+    /// テーブルごとのイントロスペクション結果
+    pub tables: Vec<IntrospectedTable>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+/// 単一テーブルのイントロスペクション結果
+#[derive(Debug, Clone, Serialize)]
+pub struct IntrospectedTable {
+    /// テーブル名
+    pub name: String,
+    /// DatabaseIntrospector が返した生の情報
+    pub raw: RawTableInfo,
+    /// 変換後のテーブル定義（変換に失敗した場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted: Option<Table>,
+    /// 変換エラー
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion_error: Option<String>,
+}
+
+impl CommandOutput for IntrospectOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// introspectコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct IntrospectCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// 対象テーブル
+    pub table: Option<String>,
+    /// 全テーブルを対象にする
+    pub all: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// introspectコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct IntrospectCommandHandler {}
+
+impl IntrospectCommandHandler {
+    /// 新しいIntrospectCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// introspectコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - introspectコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は生の情報と変換結果の一覧、失敗時はエラーメッセージ
+    pub async fn execute(&self, command: &IntrospectCommand) -> Result<String> {
+        let table = match (&command.table, command.all) {
+            (Some(_), true) => {
+                return Err(anyhow!("Cannot use --table and --all together."));
+            }
+            (None, false) => {
+                return Err(anyhow!("Specify a table with --table, or use --all."));
+            }
+            (table, _) => table.as_deref(),
+        };
+
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        let introspector = create_introspector_for_config(&context.config);
+        let output = context
+            .run_with_pools(async {
+                let pool = context.connect_pool(&command.env).await?;
+                self.introspect_with(introspector.as_ref(), &pool, context.dialect(), table)
+                    .await
+            })
+            .await?;
+        render_output(&output, &command.format)
+    }
+
+    /// データベースをイントロスペクトし、生の情報と変換結果を収集
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `table` - 対象テーブル（Noneの場合は全テーブル）
+    pub async fn introspect(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        table: Option<&str>,
+    ) -> Result<IntrospectOutput> {
+        let introspector = create_introspector(dialect);
+        self.introspect_with(introspector.as_ref(), pool, dialect, table)
+            .await
+    }
+
+    /// 指定したイントロスペクターでデータベースをイントロスペクト
+    async fn introspect_with(
+        &self,
+        introspector: &dyn DatabaseIntrospector,
+        pool: &AnyPool,
+        dialect: Dialect,
+        table: Option<&str>,
+    ) -> Result<IntrospectOutput> {
+        let enums = introspector
+            .get_enums(pool)
+            .await
+            .with_context(|| "Failed to get ENUM definitions")?;
+        let enum_names: HashSet<String> = enums.iter().map(|e| e.name.clone()).collect();
+        let conversion_service = SchemaConversionService::new(dialect).with_enum_names(enum_names);
+
+        let all_tables = introspector
+            .get_table_names(pool)
+            .await
+            .with_context(|| "Failed to get table names")?;
+
+        let table_names = match table {
+            Some(name) => {
+                if !all_tables.iter().any(|t| t == name) {
+                    return Err(anyhow!(
+                        "Table '{}' not found in database. Available tables: {}",
+                        name,
+                        if all_tables.is_empty() {
+                            "(none)".to_string()
+                        } else {
+                            all_tables.join(", ")
+                        }
+                    ));
+                }
+                vec![name.to_string()]
+            }
+            None => all_tables,
+        };
+        debug!(tables = table_names.len(), "Introspecting tables");
+
+        let mut tables = Vec::new();
+        for table_name in table_names {
+            let raw = fetch_raw_table_info(introspector, pool, &table_name)
+                .await
+                .with_context(|| format!("Failed to get table info for '{}'", table_name))?;
+
+            let (converted, conversion_error) = match conversion_service.convert_table(&raw) {
+                Ok(table) => (Some(table), None),
+                Err(e) => (None, Some(format!("{:#}", e))),
+            };
+
+            tables.push(IntrospectedTable {
+                name: table_name,
+                raw,
+                converted,
+                conversion_error,
+            });
+        }
+
+        let text_message = format_introspection(dialect, &enums, &tables, &conversion_service);
+
+        Ok(IntrospectOutput {
+            dialect,
+            enums,
+            tables,
+            text_message,
+        })
+    }
+}
+
+/// 単一テーブルの生情報を取得
+pub(crate) async fn fetch_raw_table_info(
+    introspector: &dyn DatabaseIntrospector,
+    pool: &AnyPool,
+    table_name: &str,
+) -> Result<RawTableInfo> {
+    let columns = introspector
+        .get_columns(pool, table_name)
+        .await
+        .with_context(|| format!("Failed to get columns for '{}'", table_name))?;
+
+    let indexes = introspector
+        .get_indexes(pool, table_name)
+        .await
+        .with_context(|| format!("Failed to get indexes for '{}'", table_name))?;
+
+    let constraints = introspector
+        .get_constraints(pool, table_name)
+        .await
+        .with_context(|| format!("Failed to get constraints for '{}'", table_name))?;
+
+    let comment = introspector
+        .get_table_comment(pool, table_name)
+        .await
+        .with_context(|| format!("Failed to get comment for '{}'", table_name))?;
+
+    Ok(RawTableInfo {
+        name: table_name.to_string(),
+        columns,
+        indexes,
+        constraints,
+        comment,
+    })
+}
+
+/// 生の情報と変換結果を並べたテキストを生成
+///
+/// 各要素を1行のJSONで表示し、変換前後の値をそのまま比較できるようにする。
+fn format_introspection(
+    dialect: Dialect,
+    enums: &[RawEnumInfo],
+    tables: &[IntrospectedTable],
+    conversion_service: &SchemaConversionService,
+) -> String {
+    let mut output = String::new();
+    let _ = writeln!(output, "=== Introspection ({}) ===", dialect);
+
+    if !enums.is_empty() {
+        output.push_str("\nEnums:\n");
+        for raw_enum in enums {
+            let _ = writeln!(output, "  raw: {}", to_json(raw_enum));
+        }
+    }
+
+    if tables.is_empty() {
+        output.push_str("\nNo tables found.\n");
+    }
+
+    for table in tables {
+        let _ = writeln!(output, "\n--- Table: {} ---", table.name);
+
+        output.push_str("Columns:\n");
+        for raw in &table.raw.columns {
+            let converted = conversion_service
+                .convert_column(raw)
+                .map(|column| to_json(&column));
+            push_pair(&mut output, &raw.name, to_json(raw), converted);
+        }
+
+        if !table.raw.indexes.is_empty() {
+            output.push_str("Indexes:\n");
+            for raw in &table.raw.indexes {
+                let converted = conversion_service
+                    .convert_index(raw)
+                    .map(|index| to_json(&index));
+                push_pair(&mut output, &raw.name, to_json(raw), converted);
+            }
+        }
+
+        if !table.raw.constraints.is_empty() {
+            output.push_str("Constraints:\n");
+            for raw in &table.raw.constraints {
+                let converted = conversion_service
+                    .convert_constraint(raw)
+                    .map(|constraint| to_json(&constraint));
+                push_pair(&mut output, constraint_label(raw), to_json(raw), converted);
+            }
+        }
+
+        if let Some(error) = &table.conversion_error {
+            let _ = writeln!(output, "Conversion error: {}", error);
+        }
+    }
+
+    output
+}
+
+/// 生の値と変換結果を1組として追記
+fn push_pair(output: &mut String, label: &str, raw: String, converted: Result<String>) {
+    let _ = writeln!(output, "  {}", label);
+    let _ = writeln!(output, "    raw:       {}", raw);
+    match converted {
+        Ok(converted) => {
+            let _ = writeln!(output, "    converted: {}", converted);
+        }
+        Err(e) => {
+            let _ = writeln!(output, "    converted: ERROR: {:#}", e);
+        }
+    }
+}
+
+/// 制約の種類を表すラベル
+fn constraint_label(raw: &RawConstraintInfo) -> &'static str {
+    match raw {
+        RawConstraintInfo::PrimaryKey { .. } => "PRIMARY KEY",
+        RawConstraintInfo::ForeignKey { .. } => "FOREIGN KEY",
+        RawConstraintInfo::Unique { .. } => "UNIQUE",
+        RawConstraintInfo::Check { .. } => "CHECK",
+    }
+}
+
+/// 1行のJSONにシリアライズ
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| format!("<serialization error: {}>", e))
+}
@@ -0,0 +1,143 @@
+// マイグレーションディレクトリの書き込み
+//
+// generate が途中で失敗しても、up.sql だけのような壊れたマイグレーション
+// ディレクトリが残らないように、`.` で始まる一時ディレクトリに全ファイルを
+// 書き込んでから最終的なディレクトリ名へリネームします。
+// `.` で始まるディレクトリはマイグレーションの読み込み対象外です。
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 書き込み途中のマイグレーションを置く一時ディレクトリのパス
+pub fn staging_dir_path(migrations_dir: &Path, migration_name: &str) -> PathBuf {
+    migrations_dir.join(format!(".{}.tmp", migration_name))
+}
+
+/// マイグレーションディレクトリをアトミックに作成する
+///
+/// `write_files` には一時ディレクトリのパスが渡されます。すべての書き込みが
+/// 成功した場合だけ `migrations_dir/{migration_name}` にリネームし、
+/// 失敗した場合は一時ディレクトリを削除してエラーを返します。
+/// 同名のディレクトリが既にある場合は、書き込みが成功した後で置き換えます。
+pub fn write_migration_dir_atomically<F>(
+    migrations_dir: &Path,
+    migration_name: &str,
+    write_files: F,
+) -> Result<PathBuf>
+where
+    F: FnOnce(&Path) -> Result<()>,
+{
+    let migration_dir = migrations_dir.join(migration_name);
+
+    // 前回の中断で残った一時ディレクトリは破棄する
+    let staging_dir = staging_dir_path(migrations_dir, migration_name);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir).with_context(|| {
+            format!(
+                "Failed to remove stale staging directory: {:?}",
+                staging_dir
+            )
+        })?;
+    }
+    fs::create_dir_all(&staging_dir)
+        .with_context(|| format!("Failed to create migration directory: {:?}", staging_dir))?;
+
+    let result = write_files(&staging_dir).and_then(|()| {
+        if migration_dir.exists() {
+            fs::remove_dir_all(&migration_dir).with_context(|| {
+                format!(
+                    "Failed to replace existing migration directory: {:?}",
+                    migration_dir
+                )
+            })?;
+        }
+        fs::rename(&staging_dir, &migration_dir).with_context(|| {
+            format!(
+                "Failed to move migration into place: {:?} -> {:?}",
+                staging_dir, migration_dir
+            )
+        })
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    Ok(migration_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_migration_dir_atomically() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = write_migration_dir_atomically(temp_dir.path(), "20260121120000_init", |dir| {
+            fs::write(dir.join("up.sql"), "CREATE TABLE t (id INTEGER);")?;
+            fs::write(dir.join(".meta.yaml"), "version: \"20260121120000\"")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(dir, temp_dir.path().join("20260121120000_init"));
+        assert!(dir.join("up.sql").is_file());
+        assert!(dir.join(".meta.yaml").is_file());
+        assert!(!staging_dir_path(temp_dir.path(), "20260121120000_init").exists());
+    }
+
+    #[test]
+    fn test_failure_between_writes_leaves_nothing_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let result =
+            write_migration_dir_atomically(temp_dir.path(), "20260121120000_init", |dir| {
+                fs::write(dir.join("up.sql"), "CREATE TABLE t (id INTEGER);")?;
+                Err(anyhow!("simulated failure before .meta.yaml"))
+            });
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("simulated failure"));
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_stale_staging_dir_is_replaced() {
+        let temp_dir = TempDir::new().unwrap();
+        let staging = staging_dir_path(temp_dir.path(), "20260121120000_init");
+        fs::create_dir(&staging).unwrap();
+        fs::write(staging.join("stale.sql"), "").unwrap();
+
+        let dir = write_migration_dir_atomically(temp_dir.path(), "20260121120000_init", |dir| {
+            fs::write(dir.join("up.sql"), "")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(!dir.join("stale.sql").exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn test_existing_migration_dir_is_kept_when_writing_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("20260121120000_init");
+        fs::create_dir(&existing).unwrap();
+        fs::write(existing.join("up.sql"), "old").unwrap();
+
+        let result = write_migration_dir_atomically(temp_dir.path(), "20260121120000_init", |_| {
+            Err(anyhow!("simulated failure"))
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(existing.join("up.sql")).unwrap(), "old");
+
+        write_migration_dir_atomically(temp_dir.path(), "20260121120000_init", |dir| {
+            fs::write(dir.join("up.sql"), "new")?;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(existing.join("up.sql")).unwrap(), "new");
+    }
+}
@@ -2,25 +2,57 @@
 // 各CLIコマンドの実装
 
 pub mod apply;
+pub mod baseline;
 pub mod check;
+pub mod confirm;
+pub mod contract;
 pub mod destructive_change_formatter;
+pub mod dev;
+pub mod diff;
+pub mod docs;
 pub(crate) mod dry_run_formatter;
+pub mod dry_run_sql;
+pub use crate::services::migration_files::execution_manifest;
 pub mod export;
+pub mod export_changes;
+pub(crate) mod export_ddl;
 pub mod generate;
+pub(crate) mod git;
+pub mod history;
 pub mod init;
-pub mod migration_loader;
+pub mod init_example;
+pub mod init_wizard;
+pub mod inspect;
+pub mod introspect;
+pub use crate::services::migration_files::migration_checksum;
+pub use crate::services::migration_files::migration_loader;
+pub mod migration_writer;
+pub mod policy_violation_formatter;
+pub mod rename_migration;
+pub mod repair;
+pub mod reset;
+pub use crate::services::migration_files::retained_tables;
 pub mod rollback;
-pub(crate) mod sql_parser;
+pub mod seed;
+pub(crate) use crate::services::migration_files::sql_output;
+pub(crate) use crate::services::migration_files::sql_parser;
+pub mod sql_editor;
+pub use crate::services::migration_files::stacked_migrations;
 pub mod status;
 pub mod validate;
+pub mod validate_file;
+pub mod validate_fixes;
+pub mod verify_execution;
+pub mod version;
+pub mod workspace;
 
+pub(crate) use crate::services::migration_runner::DESTRUCTIVE_SQL_REGEX;
 pub(crate) use sql_parser::split_sql_statements;
 
+use crate::cli::timings::{Timings, TimingsReport};
 use crate::cli::OutputFormat;
 use anyhow::Result;
-use regex::Regex;
 use serde::Serialize;
-use std::sync::LazyLock;
 
 /// コマンド出力を構造化するためのトレイト
 ///
@@ -43,6 +75,33 @@ pub fn render_output<T: CommandOutput>(output: &T, format: &OutputFormat) -> Res
     }
 }
 
+/// 計測結果を含めた出力（`--timings` 指定時のJSON出力用）
+#[derive(Serialize)]
+struct OutputWithTimings<'a, T: Serialize> {
+    #[serde(flatten)]
+    output: &'a T,
+    timings: TimingsReport,
+}
+
+/// OutputFormat に応じて出力文字列を生成する（`--timings` 指定時はJSONに `timings` を含める）
+pub fn render_output_with_timings<T: CommandOutput>(
+    output: &T,
+    format: &OutputFormat,
+    timings: &Timings,
+) -> Result<String> {
+    match format {
+        OutputFormat::Json if timings.is_reported() => {
+            let with_timings = OutputWithTimings {
+                output,
+                timings: timings.report(),
+            };
+            serde_json::to_string_pretty(&with_timings)
+                .map_err(|e| anyhow::anyhow!("JSON serialization error: {}", e))
+        }
+        _ => render_output(output, format),
+    }
+}
+
 /// エラーレスポンスの構造化出力
 #[derive(Debug, Clone, Serialize)]
 pub struct ErrorOutput {
@@ -65,18 +124,27 @@ impl ErrorOutput {
     }
 }
 
-/// 破壊的 SQL 操作を検出するための共通正規表現
+/// 出力を表示したうえで非ゼロの終了コードで終了させるためのエラー
 ///
-/// 検出対象:
-/// - DROP TABLE/COLUMN/TYPE/INDEX/CONSTRAINT/SCHEMA/DATABASE
-/// - ALTER ... DROP/RENAME
-/// - RENAME TABLE/COLUMN
-/// - TRUNCATE TABLE
-/// - DELETE FROM (WHERE句の有無に関わらず潜在的に破壊的)
-pub(crate) static DESTRUCTIVE_SQL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)\b(DROP\s+(TABLE|COLUMN|TYPE|INDEX|CONSTRAINT|SCHEMA|DATABASE)|ALTER\s+.*\s+(DROP|RENAME)|RENAME\s+(TABLE|COLUMN)|TRUNCATE\s+TABLE|DELETE\s+FROM)\b")
-        .expect("Invalid destructive SQL regex pattern")
-});
+/// コマンドは最後まで実行されたものの、結果を失敗として扱いたい場合に使用する。
+/// `output` は通常の出力と同様に標準出力へ、メッセージはエラーとして標準エラーへ出力される。
+#[derive(Debug, Clone)]
+pub struct CompletedWithFailure {
+    /// 標準出力に表示する出力（render_output 済み）
+    pub output: String,
+    /// エラーメッセージ
+    pub message: String,
+    /// プロセスの終了コード
+    pub exit_code: i32,
+}
+
+impl std::fmt::Display for CompletedWithFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for CompletedWithFailure {}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,94 @@
+use crate::core::policy_report::PolicyReport;
+use colored::Colorize;
+
+pub struct PolicyViolationFormatter;
+
+impl Default for PolicyViolationFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PolicyViolationFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn format_error(&self, report: &PolicyReport, command: &str) -> String {
+        let mut output = String::new();
+
+        output.push_str(format!("{}\n\n", "Migration policy violated".red().bold()).as_str());
+
+        for violation in &report.violations {
+            output.push_str(violation.message.red().to_string().as_str());
+            output.push('\n');
+        }
+
+        output.push('\n');
+        output.push_str("To proceed, choose one of the following:\n");
+        output.push_str(
+            "  1. Split the change: apply part of the schema edits, run generate, then repeat\n",
+        );
+        output.push_str(&format!(
+            "  2. Override the policy: {} --override-policy\n",
+            command
+        ));
+
+        output
+    }
+
+    pub fn format_warning(&self, report: &PolicyReport) -> String {
+        let mut output = String::new();
+
+        output.push_str(
+            format!(
+                "{}\n",
+                "Warning: Migration policy overridden".yellow().bold()
+            )
+            .as_str(),
+        );
+
+        for violation in &report.violations {
+            output.push_str(&format!("  {}\n", violation.message.yellow()));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolicyViolationFormatter;
+    use crate::core::policy_report::{PolicyReport, PolicyRule, PolicyViolation};
+
+    fn sample_report() -> PolicyReport {
+        PolicyReport {
+            statement_count: 120,
+            violations: vec![PolicyViolation {
+                rule: PolicyRule::MaxStatements,
+                message: "Migration contains 120 statements, exceeding policy.max_statements (50)"
+                    .to_string(),
+            }],
+            ..PolicyReport::default()
+        }
+    }
+
+    #[test]
+    fn format_error_suggests_split_and_override() {
+        let output =
+            PolicyViolationFormatter::new().format_error(&sample_report(), "strata generate");
+
+        assert!(output.contains("Migration policy violated"));
+        assert!(output.contains("policy.max_statements (50)"));
+        assert!(output.contains("Split the change"));
+        assert!(output.contains("strata generate --override-policy"));
+    }
+
+    #[test]
+    fn format_warning_lists_violations() {
+        let output = PolicyViolationFormatter::new().format_warning(&sample_report());
+
+        assert!(output.contains("Migration policy overridden"));
+        assert!(output.contains("120 statements"));
+    }
+}
@@ -0,0 +1,271 @@
+// rename-migrationコマンドハンドラー
+//
+// 生成済みマイグレーションの説明（ディレクトリ名の `{version}_` 以降）を変更します。
+// - マイグレーションディレクトリ名と `.meta.yaml` の description を書き換える
+// - チェックサムはスキーマ内容のみから計算されるため変更しない
+// - 適用済みの場合は schema_migrations の description を更新するSQLを環境ごとに出力する
+//   （`--env` 指定時はその環境で適用済みなら更新も行う）
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::migration_loader;
+use crate::cli::commands::repair::EnvironmentUpdateSql;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::migration::MigrationMetadata;
+use crate::services::migration_generator::MigrationGeneratorService;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// rename-migrationコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameMigrationOutput {
+    pub version: String,
+    /// 変更前の説明
+    pub old_description: String,
+    /// 変更後の説明
+    pub new_description: String,
+    /// 変更後のマイグレーションディレクトリ
+    pub path: PathBuf,
+    /// schema_migrations を更新した環境（`--env` 指定時、適用済みだった場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_env: Option<String>,
+    /// 他の環境で schema_migrations を更新するSQL
+    pub update_sql: Vec<EnvironmentUpdateSql>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for RenameMigrationOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// rename-migrationコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct RenameMigrationCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 対象のマイグレーションバージョン
+    pub version: String,
+    /// 変更後の説明
+    pub new_description: String,
+    /// schema_migrations を更新する環境（未指定の場合はSQLを出力するのみ）
+    pub env: Option<String>,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// rename-migrationコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct RenameMigrationCommandHandler {}
+
+impl RenameMigrationCommandHandler {
+    /// 新しいRenameMigrationCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// rename-migrationコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - rename-migrationコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は変更結果のサマリー、失敗時はエラーメッセージ
+    pub async fn execute(&self, command: &RenameMigrationCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        let output = context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await?;
+        render_output(&output, &command.format)
+    }
+
+    /// 読み込み済みのコンテキストでrename-migrationコマンドを実行
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &RenameMigrationCommand,
+    ) -> Result<RenameMigrationOutput> {
+        let migrations_dir = context.require_migrations_dir()?;
+        let migrations = migration_loader::load_available_migrations(&migrations_dir)?;
+        let (version, old_description, old_dir) = migrations
+            .iter()
+            .find(|(v, _, _)| *v == command.version)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Migration version '{}' not found in {:?}",
+                    command.version,
+                    migrations_dir
+                )
+            })?;
+
+        let new_description =
+            MigrationGeneratorService::new().sanitize_description(&command.new_description);
+        if new_description.is_empty() {
+            return Err(anyhow!(
+                "Invalid migration description '{}': use letters, digits and underscores.",
+                command.new_description
+            ));
+        }
+        if new_description == *old_description {
+            return Err(anyhow!(
+                "Migration {} is already named '{}'.",
+                version,
+                old_description
+            ));
+        }
+
+        let new_dir = migrations_dir.join(format!("{}_{}", version, new_description));
+        if new_dir.exists() {
+            return Err(anyhow!(
+                "Cannot rename migration {}: {:?} already exists.",
+                version,
+                new_dir
+            ));
+        }
+
+        // ファイルを変更する前に適用状況を確認する（接続できなければ何も変更しない）
+        let target = match &command.env {
+            Some(env) => {
+                let (pool, applied_migrations) = context.connect_and_load_migrations(env).await?;
+                let applied = applied_migrations.iter().any(|r| &r.version == version);
+                debug!(env = %env, applied, "Checked migration status");
+                applied.then(|| (env.clone(), pool))
+            }
+            None => None,
+        };
+
+        fs::rename(old_dir, &new_dir).with_context(|| {
+            format!(
+                "Failed to rename migration directory {:?} to {:?}",
+                old_dir, new_dir
+            )
+        })?;
+        self.update_metadata_description(&new_dir, &new_description)?;
+
+        let migrator = DatabaseMigratorService::new();
+        let updated_env = match target {
+            Some((env, pool)) => {
+                migrator
+                    .update_migration_description(&pool, version, &new_description, context.dialect())
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Renamed the migration files, but failed to update schema_migrations in the '{}' environment. Run there:\n{}",
+                            env,
+                            migrator.generate_rename_migration_sql(version, &new_description)
+                        )
+                    })?;
+                Some(env)
+            }
+            None => None,
+        };
+
+        let sql = migrator.generate_rename_migration_sql(version, &new_description);
+        let mut envs: Vec<&String> = context
+            .config
+            .environments
+            .keys()
+            .filter(|env| updated_env.as_ref() != Some(*env))
+            .collect();
+        envs.sort();
+        let update_sql = envs
+            .into_iter()
+            .map(|env| EnvironmentUpdateSql {
+                env: env.clone(),
+                sql: sql.clone(),
+            })
+            .collect();
+
+        let mut output = RenameMigrationOutput {
+            version: version.clone(),
+            old_description: old_description.clone(),
+            new_description,
+            path: new_dir,
+            updated_env,
+            update_sql,
+            text_message: String::new(),
+        };
+        output.text_message = self.format_summary(&output, command.env.as_deref());
+        Ok(output)
+    }
+
+    /// `.meta.yaml` の説明を書き換える
+    ///
+    /// メタデータファイルが存在しない場合は何もしない。チェックサムなど他の項目は変更しない。
+    fn update_metadata_description(&self, migration_dir: &Path, description: &str) -> Result<()> {
+        let meta_path = migration_dir.join(".meta.yaml");
+        if !meta_path.exists() {
+            return Ok(());
+        }
+
+        let meta_content = fs::read_to_string(&meta_path)
+            .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
+        let mut metadata: MigrationMetadata = serde_saphyr::from_str(&meta_content)
+            .with_context(|| format!("Failed to parse metadata: {:?}", meta_path))?;
+        metadata.description = description.to_string();
+
+        let yaml = serde_saphyr::to_string(&metadata)
+            .with_context(|| "Failed to serialize migration metadata")?;
+        fs::write(&meta_path, yaml)
+            .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
+        Ok(())
+    }
+
+    /// 変更結果のサマリーを生成
+    fn format_summary(&self, output: &RenameMigrationOutput, env: Option<&str>) -> String {
+        let mut text = String::from("=== Rename Migration ===\n");
+        text.push_str(&format!(
+            "✓ {}_{} -> {}_{}\n",
+            output.version, output.old_description, output.version, output.new_description
+        ));
+
+        match (&output.updated_env, env) {
+            (Some(updated), _) => text.push_str(&format!(
+                "✓ Updated schema_migrations in the '{}' environment\n",
+                updated
+            )),
+            (None, Some(env)) => text.push_str(&format!(
+                "Migration {} is not applied to the '{}' environment.\n",
+                output.version, env
+            )),
+            (None, None) => {}
+        }
+
+        if !output.update_sql.is_empty() {
+            text.push_str(
+                "\nIf the migration was applied to other environments, run the following SQL there:\n",
+            );
+            for update in &output.update_sql {
+                text.push_str(&format!("\n-- {}\n{}\n", update.env, update.sql));
+            }
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_handler() {
+        let handler = RenameMigrationCommandHandler::new();
+        assert!(format!("{:?}", handler).contains("RenameMigrationCommandHandler"));
+    }
+}
@@ -0,0 +1,514 @@
+// repairコマンドハンドラー
+//
+// マイグレーションファイルを失った場合の復旧機能を実装します。
+// - `--export-applied-sql`: `store_applied_sql: true` で schema_migrations に保存された
+//   up.sql / down.sql を、マイグレーションディレクトリと同じ構成で書き出す
+// - `--renumber <VERSION>`: 時刻のずれたマシンで生成されたマイグレーションを
+//   現在のタイムスタンプに付け替える
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::migration_loader;
+use crate::cli::commands::sql_output::calculate_sql_file_content_checksum;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::core::config::{Dialect, SqlOutputConfig};
+use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::migration::{is_truncated_sql, AppliedSql, MigrationMetadata};
+use crate::services::migration_generator::MigrationGeneratorService;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 切り詰めて保存されたSQLを書き出す際のファイル名接尾辞
+///
+/// 不完全なSQLが `up.sql` / `down.sql` として適用・ロールバックされないよう、別名で書き出す。
+pub const TRUNCATED_FILE_SUFFIX: &str = ".truncated";
+
+/// repairコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairOutput {
+    /// 書き出し先ディレクトリ
+    pub output_dir: PathBuf,
+    /// 書き出したマイグレーション
+    pub exported: Vec<ExportedMigration>,
+    /// 書き出さなかったマイグレーション
+    pub skipped: Vec<SkippedMigration>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+/// 書き出したマイグレーション
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedMigration {
+    pub version: String,
+    pub description: String,
+    /// 書き出したマイグレーションディレクトリ
+    pub path: PathBuf,
+    /// 切り詰めて保存されていたため `.truncated` 付きで書き出したか
+    pub truncated: bool,
+}
+
+/// 書き出さなかった理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// SQLが保存されていない（`store_applied_sql` 有効化前に適用された）
+    NotStored,
+    /// 書き出し先にマイグレーションディレクトリが既に存在する
+    AlreadyExists,
+}
+
+/// 書き出さなかったマイグレーション
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedMigration {
+    pub version: String,
+    pub description: String,
+    pub reason: SkipReason,
+}
+
+impl CommandOutput for RepairOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// `--renumber` の出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct RenumberOutput {
+    /// 変更前のバージョン
+    pub old_version: String,
+    /// 変更後のバージョン
+    pub new_version: String,
+    pub description: String,
+    /// 変更後のマイグレーションディレクトリ
+    pub path: PathBuf,
+    /// 対象環境で適用済みだったか
+    pub applied: bool,
+    /// 各環境の schema_migrations を更新するSQL
+    pub update_sql: Vec<EnvironmentUpdateSql>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+/// 環境ごとの schema_migrations 更新SQL
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvironmentUpdateSql {
+    pub env: String,
+    pub sql: String,
+}
+
+impl CommandOutput for RenumberOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// repairコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct RepairCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// 保存された適用SQLの書き出し先ディレクトリ
+    pub export_applied_sql: Option<PathBuf>,
+    /// 現在のタイムスタンプに付け替えるマイグレーションのバージョン
+    pub renumber: Option<String>,
+    /// 適用済みのマイグレーションでも付け替える
+    pub force: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// repairコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct RepairCommandHandler {}
+
+impl RepairCommandHandler {
+    /// 新しいRepairCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// repairコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - repairコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// 成功時は書き出し結果のサマリー、失敗時はエラーメッセージ
+    pub async fn execute(&self, command: &RepairCommand) -> Result<String> {
+        if command.export_applied_sql.is_none() && command.renumber.is_none() {
+            return Err(anyhow!(
+                "Specify a repair action, e.g. --export-applied-sql <DIR> or --renumber <VERSION>."
+            ));
+        }
+
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        context
+            .run_with_pools(async {
+                match (&command.renumber, &command.export_applied_sql) {
+                    (Some(version), _) => self
+                        .renumber(&context, &command.env, version, command.force)
+                        .await
+                        .and_then(|output| render_output(&output, &command.format)),
+                    (None, Some(output_dir)) => self
+                        .execute_with_context(&context, &command.env, output_dir)
+                        .await
+                        .and_then(|output| render_output(&output, &command.format)),
+                    (None, None) => unreachable!("repair action is checked above"),
+                }
+            })
+            .await
+    }
+
+    /// 読み込み済みのコンテキストで保存された適用SQLを書き出す
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        env: &str,
+        output_dir: &Path,
+    ) -> Result<RepairOutput> {
+        let (pool, _) = context.connect_and_load_migrations(env).await?;
+        self.export_applied_sql(
+            &pool,
+            context.dialect(),
+            &context.config.sql_output,
+            output_dir,
+        )
+        .await
+    }
+
+    /// 保存された適用SQLをマイグレーションディレクトリの構成で書き出す
+    ///
+    /// 各マイグレーションを `{version}_{description}/` に up.sql / down.sql / .meta.yaml として書き出す。
+    /// 切り詰めて保存されたSQLは `up.sql.truncated` のように別名で書き出す。
+    /// SQLが保存されていないマイグレーションと、書き出し先に既に存在するマイグレーションはスキップする。
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `sql_output` - up.sql のチェックサムを正規形で計算するための出力設定
+    /// * `output_dir` - 書き出し先ディレクトリ
+    pub async fn export_applied_sql(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        sql_output: &SqlOutputConfig,
+        output_dir: &Path,
+    ) -> Result<RepairOutput> {
+        let applied_sql = DatabaseMigratorService::new()
+            .get_applied_sql(pool, dialect, None)
+            .await
+            .with_context(|| "Failed to get applied migration SQL")?;
+
+        fs::create_dir_all(output_dir)
+            .with_context(|| format!("Failed to create output directory: {:?}", output_dir))?;
+
+        let mut exported = Vec::new();
+        let mut skipped = Vec::new();
+        for migration in applied_sql {
+            let migration_dir =
+                output_dir.join(format!("{}_{}", migration.version, migration.description));
+
+            let reason = if migration.up_sql.is_none() {
+                Some(SkipReason::NotStored)
+            } else if migration_dir.exists() {
+                Some(SkipReason::AlreadyExists)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                skipped.push(SkippedMigration {
+                    version: migration.version,
+                    description: migration.description,
+                    reason,
+                });
+                continue;
+            }
+
+            self.write_migration(&migration_dir, &migration, dialect, sql_output)?;
+            exported.push(ExportedMigration {
+                truncated: migration.is_truncated(),
+                version: migration.version,
+                description: migration.description,
+                path: migration_dir,
+            });
+        }
+
+        let text_message = self.format_summary(output_dir, &exported, &skipped);
+        Ok(RepairOutput {
+            output_dir: output_dir.to_path_buf(),
+            exported,
+            skipped,
+            text_message,
+        })
+    }
+
+    /// マイグレーションを現在のタイムスタンプに付け替える
+    ///
+    /// マイグレーションディレクトリ名と `.meta.yaml` のバージョンを書き換える。
+    /// 対象環境で適用済みの場合は、schema_migrations の更新を各環境で行う必要があるため
+    /// `force` が指定されていなければエラーにする。
+    /// 適用済みの場合、各環境で実行する更新SQLを出力に含める。
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - コマンドコンテキスト
+    /// * `env` - 適用状況を確認する環境名
+    /// * `version` - 付け替えるマイグレーションのバージョン
+    /// * `force` - 適用済みでも付け替えるか
+    pub async fn renumber(
+        &self,
+        context: &CommandContext,
+        env: &str,
+        version: &str,
+        force: bool,
+    ) -> Result<RenumberOutput> {
+        let migrations_dir = context.require_migrations_dir()?;
+        let migrations = migration_loader::load_available_migrations(&migrations_dir)?;
+        let (_, description, old_dir) = migrations
+            .iter()
+            .find(|(v, _, _)| v == version)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Migration version '{}' not found in {:?}",
+                    version,
+                    migrations_dir
+                )
+            })?;
+
+        let (_pool, applied_migrations) = context.connect_and_load_migrations(env).await?;
+        let applied = applied_migrations.iter().any(|r| r.version == version);
+        if applied && !force {
+            return Err(anyhow!(
+                "Migration {} is already applied to the '{}' environment. Renumbering it changes the version recorded in schema_migrations, so every environment where it was applied must be updated by hand. Re-run with --force to renumber anyway and print the SQL to run in each environment.",
+                version,
+                env
+            ));
+        }
+
+        let new_version = MigrationGeneratorService::new().generate_timestamp();
+        if migrations.iter().any(|(v, _, _)| *v == new_version) {
+            return Err(anyhow!(
+                "Migration version '{}' already exists. Wait a second and try again.",
+                new_version
+            ));
+        }
+
+        let new_dir = migrations_dir.join(format!("{}_{}", new_version, description));
+        fs::rename(old_dir, &new_dir).with_context(|| {
+            format!(
+                "Failed to rename migration directory {:?} to {:?}",
+                old_dir, new_dir
+            )
+        })?;
+        self.update_metadata_version(&new_dir, &new_version)?;
+
+        let update_sql = if applied {
+            let sql = DatabaseMigratorService::new()
+                .generate_renumber_migration_sql(version, &new_version);
+            let mut envs: Vec<&String> = context.config.environments.keys().collect();
+            envs.sort();
+            envs.into_iter()
+                .map(|env| EnvironmentUpdateSql {
+                    env: env.clone(),
+                    sql: sql.clone(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut output = RenumberOutput {
+            old_version: version.to_string(),
+            new_version,
+            description: description.clone(),
+            path: new_dir,
+            applied,
+            update_sql,
+            text_message: String::new(),
+        };
+        output.text_message = self.format_renumber_summary(&output, env);
+        Ok(output)
+    }
+
+    /// `.meta.yaml` のバージョンを書き換える
+    ///
+    /// メタデータファイルが存在しない場合は何もしない。
+    fn update_metadata_version(&self, migration_dir: &Path, new_version: &str) -> Result<()> {
+        let meta_path = migration_dir.join(".meta.yaml");
+        if !meta_path.exists() {
+            return Ok(());
+        }
+
+        let meta_content = fs::read_to_string(&meta_path)
+            .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
+        let mut metadata: MigrationMetadata = serde_saphyr::from_str(&meta_content)
+            .with_context(|| format!("Failed to parse metadata: {:?}", meta_path))?;
+        metadata.version = new_version.to_string();
+
+        let yaml = serde_saphyr::to_string(&metadata)
+            .with_context(|| "Failed to serialize migration metadata")?;
+        fs::write(&meta_path, yaml)
+            .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
+        Ok(())
+    }
+
+    /// 付け替え結果のサマリーを生成
+    fn format_renumber_summary(&self, output: &RenumberOutput, env: &str) -> String {
+        let mut text = String::from("=== Renumber Migration ===\n");
+        text.push_str(&format!(
+            "✓ {}_{} -> {}_{}\n",
+            output.old_version, output.description, output.new_version, output.description
+        ));
+
+        if output.applied {
+            text.push_str(&format!(
+                "\n⚠️  WARNING: Migration {} was already applied to the '{}' environment.\n",
+                output.old_version, env
+            ));
+            text.push_str(&format!(
+                "Until schema_migrations is updated, status reports {} as orphaned and apply will run {} again.\n",
+                output.old_version, output.new_version
+            ));
+            text.push_str(
+                "Run the following SQL in every environment where the migration was applied:\n",
+            );
+            for update in &output.update_sql {
+                text.push_str(&format!("\n-- {}\n{}\n", update.env, update.sql));
+            }
+        } else {
+            text.push_str(&format!(
+                "\nThe migration was not applied to the '{}' environment. If it was applied to another environment, run there:\n{}\n",
+                env,
+                DatabaseMigratorService::new()
+                    .generate_renumber_migration_sql(&output.old_version, &output.new_version)
+            ));
+        }
+
+        text
+    }
+
+    /// 1マイグレーション分のファイルを書き出す
+    fn write_migration(
+        &self,
+        migration_dir: &Path,
+        migration: &AppliedSql,
+        dialect: Dialect,
+        sql_output: &SqlOutputConfig,
+    ) -> Result<()> {
+        fs::create_dir_all(migration_dir).with_context(|| {
+            format!("Failed to create migration directory: {:?}", migration_dir)
+        })?;
+
+        for (file, sql) in [
+            ("up.sql", &migration.up_sql),
+            ("down.sql", &migration.down_sql),
+        ] {
+            let Some(sql) = sql else {
+                continue;
+            };
+            let file_name = if is_truncated_sql(sql) {
+                format!("{}{}", file, TRUNCATED_FILE_SUFFIX)
+            } else {
+                file.to_string()
+            };
+            let path = migration_dir.join(file_name);
+            fs::write(&path, sql)
+                .with_context(|| format!("Failed to write SQL file: {:?}", path))?;
+        }
+
+        // 破壊的変更・ポリシーの評価結果は保存されていないため空のまま書き出す。
+        // up.sql は適用時の内容そのものなので、途中で切り詰められていなければチェックサムを記録する
+        let up_sql_checksum = migration
+            .up_sql
+            .as_deref()
+            .filter(|sql| !is_truncated_sql(sql))
+            .map(|sql| calculate_sql_file_content_checksum(sql, sql_output));
+        let metadata = MigrationGeneratorService::new().generate_migration_metadata(
+            &migration.version,
+            &migration.description,
+            dialect,
+            &migration.checksum,
+            up_sql_checksum,
+            Some(sql_output.statement_terminator.clone()),
+            DestructiveChangeReport::new(),
+            None,
+            Vec::new(),
+            None,
+            BTreeMap::new(),
+        )?;
+        let meta_path = migration_dir.join(".meta.yaml");
+        fs::write(&meta_path, metadata)
+            .with_context(|| format!("Failed to write metadata: {:?}", meta_path))?;
+
+        Ok(())
+    }
+
+    /// 書き出し結果のサマリーを生成
+    fn format_summary(
+        &self,
+        output_dir: &Path,
+        exported: &[ExportedMigration],
+        skipped: &[SkippedMigration],
+    ) -> String {
+        let mut output = String::from("=== Export Applied SQL ===\n");
+        output.push_str(&format!(
+            "{} migration(s) exported to {}:\n\n",
+            exported.len(),
+            output_dir.display()
+        ));
+
+        for migration in exported {
+            if migration.truncated {
+                output.push_str(&format!(
+                    "⚠️  {} - {} (truncated, written as *.sql{})\n",
+                    migration.version, migration.description, TRUNCATED_FILE_SUFFIX
+                ));
+            } else {
+                output.push_str(&format!(
+                    "✓ {} - {}\n",
+                    migration.version, migration.description
+                ));
+            }
+        }
+
+        if !skipped.is_empty() {
+            output.push_str(&format!("\n{} migration(s) skipped:\n", skipped.len()));
+            for migration in skipped {
+                let reason = match migration.reason {
+                    SkipReason::NotStored => "SQL not stored",
+                    SkipReason::AlreadyExists => "already exists",
+                };
+                output.push_str(&format!(
+                    "  {} - {} ({})\n",
+                    migration.version, migration.description, reason
+                ));
+            }
+        }
+
+        if exported.iter().any(|m| m.truncated) {
+            output.push_str(
+                "\n⚠️  Warning: Truncated SQL is incomplete. Restore the full SQL before renaming the files to up.sql / down.sql.\n",
+            );
+        }
+
+        output
+    }
+}
@@ -0,0 +1,521 @@
+// resetコマンドハンドラー
+//
+// 開発用データベースを空の状態に戻します。
+// - protected_environments に含まれる環境での実行を拒否
+// - 実行内容を表示し、`--yes` または環境名の入力で確認
+// - 通常は適用済みのマイグレーションをすべて down.sql でロールバックし、履歴テーブルを削除
+// - `--hard` ではスキーマスナップショットにあるオブジェクトと履歴テーブルを外部キーの依存順に削除
+//   （`--delete-file` でファイルベースのSQLiteデータベースはファイルごと削除）
+// - `--reapply` ではリセット後にすべてのマイグレーションを適用し直す
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use crate::cli::commands::confirm::Confirm;
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use crate::services::database_reset::{self, HardResetPlanner};
+use crate::services::identifier_case;
+use crate::services::migration_runner::{MigrationRunner, RollbackPlan, RollbackTarget};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::debug;
+
+/// リセットを確認するプロンプト
+pub const RESET_PROMPT: &str = "Type the environment name to reset it:";
+
+/// ロールバックで戻せない場合の案内
+const USE_HARD_HINT: &str =
+    "Could not reset by rolling back; use --hard to drop the tables in the schema snapshot instead";
+
+/// resetコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct ResetCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 対象環境
+    pub env: String,
+    /// down.sql を使わず、スナップショットのテーブルと履歴テーブルを削除する
+    pub hard: bool,
+    /// `--hard` でファイルベースのSQLiteデータベースをファイルごと削除する
+    pub delete_file: bool,
+    /// リセット後にすべてのマイグレーションを適用し直す
+    pub reapply: bool,
+    /// 確認プロンプトを省略する
+    pub yes: bool,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// リセットの方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetMode {
+    /// down.sql によるロールバック
+    Rollback,
+    /// スナップショットのテーブルと履歴テーブルの削除
+    Hard,
+}
+
+/// ロールバックしたマイグレーション
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetRolledBackMigration {
+    pub version: String,
+    pub description: String,
+}
+
+/// resetコマンドの出力
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetOutput {
+    /// 対象環境
+    pub environment: String,
+    /// リセットの方法
+    pub mode: ResetMode,
+    /// ロールバックしたマイグレーション（ロールバック順）
+    pub rolled_back: Vec<ResetRolledBackMigration>,
+    /// ロールバック保護により削除されずに残ったテーブル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub protected_tables: Vec<String>,
+    /// 実行したSQL（実行順）
+    pub statements: Vec<String>,
+    /// 削除したファイル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deleted_files: Vec<String>,
+    /// apply の出力（JSON出力時、--reapply の場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply: Option<serde_json::Value>,
+    /// apply のエラーメッセージ（失敗時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// apply のテキスト出力（--reapply の場合のみ）
+    #[serde(skip)]
+    pub apply_text: Option<String>,
+}
+
+impl CommandOutput for ResetOutput {
+    fn to_text(&self) -> String {
+        let mut text = format!("=== Reset ({}) ===\n", self.environment);
+        if self.mode == ResetMode::Rollback {
+            if self.rolled_back.is_empty() {
+                text.push_str("No migrations to roll back.\n");
+            } else {
+                text.push_str(&format!(
+                    "Rolled back {} migration(s):\n",
+                    self.rolled_back.len()
+                ));
+                for migration in &self.rolled_back {
+                    text.push_str(&format!(
+                        "  ✓ {} - {}\n",
+                        migration.version, migration.description
+                    ));
+                }
+            }
+        }
+        if !self.statements.is_empty() {
+            text.push_str("Executed:\n");
+            for statement in &self.statements {
+                text.push_str(&format!("  ✓ {}\n", statement));
+            }
+        }
+        if !self.deleted_files.is_empty() {
+            text.push_str("Deleted:\n");
+            for file in &self.deleted_files {
+                text.push_str(&format!("  ✓ {}\n", file));
+            }
+        }
+        if !self.protected_tables.is_empty() {
+            text.push_str("Protected tables (left in place):\n");
+            for table in &self.protected_tables {
+                text.push_str(&format!("  - {}\n", table));
+            }
+        }
+        text.push_str(&format!(
+            "Database '{}' has been reset.\n",
+            self.environment
+        ));
+
+        match &self.apply_text {
+            Some(apply_text) => {
+                text.push_str(&format!("\n=== apply ({}) ===\n", self.environment));
+                if !apply_text.is_empty() {
+                    text.push_str(apply_text.trim_end());
+                    text.push('\n');
+                }
+                if let Some(ref error) = self.error {
+                    text.push_str(&format!("Error: {}\n", error));
+                }
+            }
+            None => text.push_str(&format!(
+                "Run `strata apply --env {}` to apply the migrations again.\n",
+                self.environment
+            )),
+        }
+        text.trim_end().to_string()
+    }
+}
+
+/// resetコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct ResetCommandHandler {
+    confirm: Option<Arc<dyn Confirm>>,
+}
+
+impl ResetCommandHandler {
+    /// 新しいResetCommandHandlerを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// リセットの確認プロンプトを設定
+    ///
+    /// 設定しない場合や対話的でない場合は、`--yes` がなければ実行を拒否します。
+    pub fn with_confirm(mut self, confirm: Arc<dyn Confirm>) -> Self {
+        self.confirm = Some(confirm);
+        self
+    }
+
+    /// resetコマンドを実行
+    pub async fn execute(&self, command: &ResetCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?
+        .with_wait_timeout(command.wait_timeout);
+        check_environment(&context.config.protected_environments, &command.env)?;
+        if command.delete_file && !command.hard {
+            return Err(anyhow!("--delete-file can only be used with --hard."));
+        }
+        if command.delete_file && context.dialect() != Dialect::SQLite {
+            return Err(anyhow!(
+                "--delete-file is only supported for SQLite databases."
+            ));
+        }
+
+        // 再適用する apply は自分で接続し直すため、接続プールはリセットが終わった時点で閉じる
+        let output = context
+            .run_with_pools(async {
+                if command.hard {
+                    self.reset_hard(&context, command).await
+                } else {
+                    self.reset_by_rollback(&context, command).await
+                }
+            })
+            .await?;
+
+        if command.reapply {
+            self.reapply(command, output).await
+        } else {
+            render_output(&output, &command.format)
+        }
+    }
+
+    /// 適用済みのマイグレーションをすべてロールバックし、履歴テーブルを削除する
+    async fn reset_by_rollback(
+        &self,
+        context: &CommandContext,
+        command: &ResetCommand,
+    ) -> Result<ResetOutput> {
+        let migrations_dir = context.require_migrations_dir()?;
+        let runner =
+            MigrationRunner::new(context.config.clone(), command.env.clone(), migrations_dir)
+                .allow_destructive(true);
+        let local = runner.local_migrations(None)?;
+
+        let pool = context.connect_pool(&command.env).await?;
+        let runner = runner.with_pool(pool.clone());
+        let migrator = DatabaseMigratorService::new();
+        let table_exists = migrator
+            .migration_table_exists(&pool, context.dialect())
+            .await
+            .with_context(|| "Failed to check migration table existence")?;
+        let plan = if table_exists {
+            runner
+                .plan_rollback(&local, &RollbackTarget::Steps(u32::MAX))
+                .await
+                .with_context(|| USE_HARD_HINT)?
+        } else {
+            RollbackPlan {
+                applied: Vec::new(),
+                migrations: Vec::new(),
+            }
+        };
+        let drop_table = migrator.generate_drop_migration_table_sql();
+
+        let mut summary = format!("Resetting '{}' will:\n", command.env);
+        if plan.migrations.is_empty() {
+            summary.push_str("  - roll back nothing (no migrations have been applied)\n");
+        } else {
+            summary.push_str(&format!(
+                "  - roll back {} migration(s) with their down scripts:\n",
+                plan.migrations.len()
+            ));
+            for migration in &plan.migrations {
+                summary.push_str(&format!(
+                    "      {} - {}\n",
+                    migration.record.version, migration.record.description
+                ));
+            }
+        }
+        summary.push_str(&format!("  - execute: {}\n", drop_table));
+        self.confirm_reset(command, &summary)?;
+
+        debug!(env = %command.env, migrations = plan.migrations.len(), "Resetting database by rolling back");
+        let report = runner
+            .execute_rollback(&plan)
+            .await
+            .with_context(|| USE_HARD_HINT)?;
+        for warning in &report.warnings {
+            eprintln!("{}", warning);
+        }
+        execute_statements(&pool, std::slice::from_ref(&drop_table)).await?;
+
+        Ok(ResetOutput {
+            environment: command.env.clone(),
+            mode: ResetMode::Rollback,
+            rolled_back: report
+                .migrations
+                .iter()
+                .map(|m| ResetRolledBackMigration {
+                    version: m.migration.version.clone(),
+                    description: m.migration.description.clone(),
+                })
+                .collect(),
+            protected_tables: report
+                .migrations
+                .iter()
+                .flat_map(|m| m.protected_tables.iter().cloned())
+                .collect(),
+            statements: vec![drop_table],
+            deleted_files: Vec::new(),
+            apply: None,
+            error: None,
+            apply_text: None,
+        })
+    }
+
+    /// スナップショットのテーブルと履歴テーブルを削除する（SQLiteはファイルの削除も可能）
+    async fn reset_hard(
+        &self,
+        context: &CommandContext,
+        command: &ResetCommand,
+    ) -> Result<ResetOutput> {
+        let mut output = ResetOutput {
+            environment: command.env.clone(),
+            mode: ResetMode::Hard,
+            rolled_back: Vec::new(),
+            protected_tables: Vec::new(),
+            statements: Vec::new(),
+            deleted_files: Vec::new(),
+            apply: None,
+            error: None,
+            apply_text: None,
+        };
+
+        if command.delete_file {
+            let database = context.database_config(&command.env)?.database;
+            let files = database_reset::sqlite_database_files(&database).ok_or_else(|| {
+                anyhow!(
+                    "Environment '{}' uses an in-memory SQLite database, which has no file to delete.",
+                    command.env
+                )
+            })?;
+            let existing: Vec<PathBuf> = files.into_iter().filter(|f| f.exists()).collect();
+            let mut summary = format!("Resetting '{}' will delete:\n", command.env);
+            if existing.is_empty() {
+                summary.push_str(&format!("  - nothing ({} does not exist)\n", database));
+            }
+            for file in &existing {
+                summary.push_str(&format!("  - {}\n", file.display()));
+            }
+            self.confirm_reset(command, &summary)?;
+
+            for file in existing {
+                debug!(file = %file.display(), "Deleting SQLite database file");
+                fs::remove_file(&file)
+                    .with_context(|| format!("Failed to delete {}", file.display()))?;
+                output.deleted_files.push(file.display().to_string());
+            }
+        } else {
+            let snapshot = identifier_case::fold_schema_identifiers(
+                &context.load_schema_snapshot()?,
+                context.config.identifier_case,
+            );
+            let statements = HardResetPlanner::new(context.dialect())
+                .with_identifier_case(context.config.identifier_case)
+                .plan(&snapshot);
+            let mut summary = format!("Resetting '{}' will execute:\n", command.env);
+            for statement in &statements {
+                summary.push_str(&format!("  - {}\n", statement));
+            }
+            self.confirm_reset(command, &summary)?;
+
+            debug!(env = %command.env, statements = statements.len(), "Resetting database by dropping tables");
+            let pool = context.connect_pool(&command.env).await?;
+            execute_statements(&pool, &statements).await?;
+            output.statements = statements;
+        }
+        Ok(output)
+    }
+
+    /// リセットしてよいか確認する
+    ///
+    /// `--yes` があれば確認しない。JSON出力や対話的でない場合は `--yes` を必須とする。
+    fn confirm_reset(&self, command: &ResetCommand, summary: &str) -> Result<()> {
+        if command.yes {
+            return Ok(());
+        }
+        let confirm = self
+            .confirm
+            .as_ref()
+            .filter(|confirm| {
+                !matches!(command.format, OutputFormat::Json) && confirm.is_interactive()
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "{}\nResetting deletes all data in '{}'. Pass --yes to confirm.",
+                    summary.trim_end(),
+                    command.env
+                )
+            })?;
+
+        let answer = confirm.ask(summary.trim_end(), RESET_PROMPT)?;
+        if answer != command.env {
+            return Err(anyhow!(
+                "Reset cancelled: '{}' does not match the environment name '{}'. Nothing was changed.",
+                answer,
+                command.env
+            ));
+        }
+        Ok(())
+    }
+
+    /// すべてのマイグレーションを適用し直し、その出力をリセットの出力に含める
+    async fn reapply(&self, command: &ResetCommand, mut output: ResetOutput) -> Result<String> {
+        let apply_command = ApplyCommand {
+            project_path: command.project_path.clone(),
+            config_path: command.config_path.clone(),
+            dry_run: false,
+            env: command.env.clone(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            // 空のデータベースへの適用のため、確認はリセットの確認で済んでいる
+            allow_destructive: true,
+            yes: true,
+            break_stale_lock: false,
+            skip_checksum_verification: false,
+            format: command.format.clone(),
+            dry_run_sql: Default::default(),
+            restore_sequence_values: None,
+            target: None,
+            wait_timeout: command.wait_timeout,
+            preflight: None,
+            single_transaction: false,
+            only: None,
+            force_out_of_order: false,
+        };
+        let (applied, failure) = match ApplyCommandHandler::new().execute(&apply_command).await {
+            Ok(rendered) => (rendered, None),
+            Err(e) => match e.downcast_ref::<CompletedWithFailure>() {
+                Some(completed) => (
+                    completed.output.clone(),
+                    Some((completed.message.clone(), completed.exit_code)),
+                ),
+                None => (String::new(), Some((format!("{:#}", e), 1))),
+            },
+        };
+        match command.format {
+            OutputFormat::Json => output.apply = serde_json::from_str(&applied).ok(),
+            OutputFormat::Text => output.apply_text = Some(applied),
+        }
+        output.error = failure.as_ref().map(|(message, _)| message.clone());
+
+        let rendered = render_output(&output, &command.format)?;
+        match failure {
+            None => Ok(rendered),
+            Some((message, exit_code)) => Err(CompletedWithFailure {
+                output: rendered,
+                message: format!(
+                    "Reset '{}' but failed to apply the migrations again: {}",
+                    command.env, message
+                ),
+                exit_code,
+            }
+            .into()),
+        }
+    }
+}
+
+/// protected_environments に含まれる環境なら拒否する
+fn check_environment(protected_environments: &[String], env: &str) -> Result<()> {
+    if protected_environments.iter().any(|e| e == env) {
+        return Err(anyhow!(
+            "Environment '{}' is listed in protected_environments; `strata reset` only runs against development databases.",
+            env
+        ));
+    }
+    Ok(())
+}
+
+/// ステートメントを順に実行する
+async fn execute_statements(pool: &AnyPool, statements: &[String]) -> Result<()> {
+    for statement in statements {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .with_context(|| format!("Failed to execute: {}", statement))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_environment() {
+        let protected = vec!["production".to_string()];
+
+        assert!(check_environment(&protected, "development").is_ok());
+        let err = check_environment(&protected, "production").unwrap_err();
+        assert!(err.to_string().contains("protected_environments"));
+    }
+
+    #[test]
+    fn test_reset_output_text() {
+        let output = ResetOutput {
+            environment: "development".to_string(),
+            mode: ResetMode::Rollback,
+            rolled_back: vec![ResetRolledBackMigration {
+                version: "20260122120000".to_string(),
+                description: "create_users".to_string(),
+            }],
+            protected_tables: Vec::new(),
+            statements: vec!["DROP TABLE IF EXISTS schema_migrations".to_string()],
+            deleted_files: Vec::new(),
+            apply: None,
+            error: None,
+            apply_text: None,
+        };
+
+        assert_eq!(
+            output.to_text(),
+            "=== Reset (development) ===\n\
+             Rolled back 1 migration(s):\n  \
+             ✓ 20260122120000 - create_users\n\
+             Executed:\n  \
+             ✓ DROP TABLE IF EXISTS schema_migrations\n\
+             Database 'development' has been reset.\n\
+             Run `strata apply --env development` to apply the migrations again."
+        );
+    }
+}
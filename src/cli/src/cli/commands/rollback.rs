@@ -3,25 +3,24 @@
 // マイグレーションのロールバック機能を実装します。
 // - 最新の適用済みマイグレーションの特定
 // - down.sqlの実行（トランザクション内）
+// - 実行可能なステートメントがない（コメントのみの）down.sqlの拒否
 // - マイグレーション履歴からの削除
+// - ロールバック保護テーブルの記録（generate が再作成しないように）
 // - ロールバック結果の表示
 
-use crate::adapters::database_migrator::DatabaseMigratorService;
 use crate::cli::command_context::CommandContext;
-use crate::cli::commands::migration_loader;
-use crate::cli::commands::split_sql_statements;
-use crate::cli::commands::DESTRUCTIVE_SQL_REGEX;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::commands::dry_run_sql::strip_leading_comments;
+use crate::cli::commands::{render_output_with_timings, CommandOutput};
+use crate::cli::commands::{split_sql_statements, DESTRUCTIVE_SQL_REGEX};
+use crate::cli::timings::Timings;
 use crate::cli::OutputFormat;
-use crate::core::config::Dialect;
 use crate::core::migration::AppliedMigration;
-use anyhow::{anyhow, Context, Result};
-use chrono::Utc;
+use crate::services::migration_runner::{MigrationRunner, RollbackMigration, RollbackTarget};
+use anyhow::{anyhow, Result};
 use colored::Colorize;
 use serde::Serialize;
-use std::fs;
 use std::path::PathBuf;
-use tracing::{debug, info};
+use tracing::debug;
 
 /// rollbackコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
@@ -47,6 +46,16 @@ pub struct RollbackMigrationResult {
     pub duration_ms: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sql: Option<String>,
+    /// ロールバック保護により削除されずに残ったテーブル
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub protected_tables: Vec<String>,
+    /// down.sqlに実行可能なステートメントがない（コメントのみ）
+    #[serde(skip_serializing_if = "is_false")]
+    pub empty_down: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 impl CommandOutput for RollbackOutput {
@@ -64,24 +73,40 @@ pub struct RollbackCommand {
     pub config_path: Option<PathBuf>,
     /// ロールバックするマイグレーションの数
     pub steps: Option<u32>,
+    /// このバージョンが最新の適用済みマイグレーションになるまでロールバックする
+    pub target: Option<String>,
     /// 対象環境
     pub env: String,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
     /// Dry run - 実行せずにSQLを表示
     pub dry_run: bool,
     /// 破壊的変更を許可
     pub allow_destructive: bool,
+    /// 中断されたマイグレーションが残したロックを解放する
+    pub break_stale_lock: bool,
+    /// 実行可能なステートメントがないdown.sqlのロールバックを許可
+    pub allow_empty_down: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
 }
 
 /// rollbackコマンドハンドラー
 #[derive(Debug, Default)]
-pub struct RollbackCommandHandler {}
+pub struct RollbackCommandHandler {
+    timings: Timings,
+}
 
 impl RollbackCommandHandler {
     /// 新しいRollbackCommandHandlerを作成
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// フェーズ別の実行時間の計測先を設定
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
     }
 
     /// rollbackコマンドを実行
@@ -98,21 +123,40 @@ impl RollbackCommandHandler {
         let context = CommandContext::load_with_config(
             command.project_path.clone(),
             command.config_path.clone(),
-        )?;
+        )?
+        .with_timings(self.timings.clone())
+        .with_wait_timeout(command.wait_timeout);
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    /// 読み込み済みのコンテキストでrollbackコマンドを実行
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &RollbackCommand,
+    ) -> Result<String> {
         let config = &context.config;
+        if command.steps.is_some() && command.target.is_some() {
+            return Err(anyhow!("--steps and --target cannot be used together."));
+        }
 
         // マイグレーションディレクトリのパスを解決
         let migrations_dir = context.require_migrations_dir()?;
         debug!(migrations_dir = %migrations_dir.display(), "Resolved migrations directory");
 
+        let runner = MigrationRunner::new(config.clone(), command.env.clone(), migrations_dir)
+            .allow_destructive(command.allow_destructive)
+            .allow_empty_down(command.allow_empty_down)
+            .break_stale_lock(command.break_stale_lock);
+
         // 利用可能なマイグレーションファイルを読み込む
-        let available_migrations = migration_loader::load_available_migrations(&migrations_dir)?;
-        debug!(
-            count = available_migrations.len(),
-            "Loaded available migrations"
-        );
+        // 存在しないバージョンはデータベースに接続する前に拒否する
+        let local = runner.local_migrations(command.target.as_deref())?;
 
-        if available_migrations.is_empty() {
+        if local.is_empty() {
             let output = RollbackOutput {
                 dry_run: command.dry_run,
                 rolled_back_count: 0,
@@ -120,32 +164,32 @@ impl RollbackCommandHandler {
                 total_duration_ms: 0,
                 message: "No migration files found.".to_string(),
             };
-            return render_output(&output, &command.format);
+            return render_output_with_timings(&output, &command.format, &self.timings);
         }
 
-        // データベース接続を確立
-        let pool = context.connect_pool(&command.env).await?;
-
-        // マイグレーション履歴テーブルが存在するか確認
-        let migrator = DatabaseMigratorService::new();
-        let table_exists = migrator
-            .migration_table_exists(&pool, config.dialect)
-            .await
-            .with_context(|| "Failed to check migration table existence")?;
-
-        if !table_exists {
-            return Err(anyhow!(
-                "Migration history table does not exist. Please apply migrations first with the `apply` command."
-            ));
+        // 前回の実行が中断された痕跡を検出（SQLiteは接続すると復旧が始まるため接続前に確認）
+        let leftover = runner.find_leftover_files();
+        for issue in &leftover {
+            eprintln!("{}", format!("Warning: {}", issue).yellow());
         }
 
-        // 適用済みマイグレーションを取得
-        let applied_migrations = migrator
-            .get_migrations(&pool, config.dialect)
-            .await
-            .with_context(|| "Failed to get applied migration history")?;
+        // データベース接続を確立し、マイグレーション履歴からロールバックするマイグレーションを決める
+        let pool = context.connect_pool(&command.env).await?;
+        let runner = runner.with_pool(pool);
+        for message in runner.check_stale_locks(&leftover).await? {
+            eprintln!("{}", message);
+        }
+        let target = match &command.target {
+            Some(version) => RollbackTarget::Version(version.clone()),
+            // 指定件数（デフォルトは1）
+            None => RollbackTarget::Steps(command.steps.unwrap_or(1)),
+        };
+        let plan = self
+            .timings
+            .measure_async("history", runner.plan_rollback(&local, &target))
+            .await?;
 
-        if applied_migrations.is_empty() {
+        if plan.applied.is_empty() {
             let output = RollbackOutput {
                 dry_run: command.dry_run,
                 rolled_back_count: 0,
@@ -153,57 +197,63 @@ impl RollbackCommandHandler {
                 total_duration_ms: 0,
                 message: "No migrations to rollback. No migrations have been applied.".to_string(),
             };
-            return render_output(&output, &command.format);
+            return render_output_with_timings(&output, &command.format, &self.timings);
         }
 
-        // ロールバックする件数を決定（デフォルトは1）
-        let steps = command.steps.unwrap_or(1) as usize;
-
-        // ロールバックするマイグレーションを選択（最新のものから）
-        let to_rollback_count = steps.min(applied_migrations.len());
-        let to_rollback: Vec<_> = applied_migrations
-            .iter()
-            .rev()
-            .take(to_rollback_count)
-            .collect();
-
-        // ロールバック対象のマイグレーションと down.sql を収集
-        let mut rollback_items: Vec<(&crate::core::migration::MigrationRecord, String, PathBuf)> =
-            Vec::new();
-        let mut has_destructive = false;
-
-        for record in &to_rollback {
-            // マイグレーションディレクトリを検索
-            let migration_info = available_migrations
-                .iter()
-                .find(|(v, _, _)| v == &record.version)
-                .ok_or_else(|| anyhow!("Migration file not found: {}", record.version))?;
-
-            let migration_dir = &migration_info.2;
-
-            // down.sqlを読み込み
-            let down_sql_path = migration_dir.join("down.sql");
-            let down_sql = fs::read_to_string(&down_sql_path)
-                .with_context(|| format!("Failed to read migration file: {:?}", down_sql_path))?;
+        // 既に指定バージョンにある場合は何もしない
+        if let Some(target) = command
+            .target
+            .as_ref()
+            .filter(|_| plan.migrations.is_empty())
+        {
+            let output = RollbackOutput {
+                dry_run: command.dry_run,
+                rolled_back_count: 0,
+                migrations: vec![],
+                total_duration_ms: 0,
+                message: format!(
+                    "Database is already at version {}. No migrations to rollback.",
+                    target
+                ),
+            };
+            return render_output_with_timings(&output, &command.format, &self.timings);
+        }
 
-            // 破壊的変更をチェック
-            if self.contains_destructive_sql(&down_sql) {
-                has_destructive = true;
+        // down.sqlが空のマイグレーションは、変更を戻さずに履歴だけが削除されるため拒否する
+        // （ランナーも拒否するが、dry-run 以外では対象のファイルを示して先に中止する）
+        let empty_downs = plan.empty_downs();
+        if !empty_downs.is_empty() && !command.allow_empty_down && !command.dry_run {
+            let mut msg = String::from(
+                "Rollback refused: down.sql has no executable statements (only comments).\n\n",
+            );
+            msg.push_str("Affected migrations:\n");
+            for migration in &empty_downs {
+                msg.push_str(&format!(
+                    "  - {} - {} ({})\n",
+                    migration.record.version,
+                    migration.record.description,
+                    migration.dir.join("down.sql").display()
+                ));
             }
-
-            rollback_items.push((record, down_sql, migration_dir.clone()));
+            msg.push_str(
+                "\nRolling back would remove these migrations from the history without reverting their changes.\n",
+            );
+            msg.push_str(
+                "Write the down.sql, or run with --allow-empty-down flag to proceed anyway.",
+            );
+            return Err(anyhow!(msg));
         }
 
         // 破壊的変更がある場合の処理
-        if has_destructive && !command.allow_destructive && !command.dry_run {
+        if plan.has_destructive() && !command.allow_destructive && !command.dry_run {
             let mut msg = String::from("Rollback contains destructive changes.\n\n");
             msg.push_str("Migrations to rollback:\n");
-            for (record, down_sql, _) in &rollback_items {
+            for migration in &plan.migrations {
                 msg.push_str(&format!(
                     "  - {} - {}\n",
-                    record.version, record.description
+                    migration.record.version, migration.record.description
                 ));
-                if self.contains_destructive_sql(down_sql) {
+                if migration.destructive {
                     msg.push_str("    Contains: DROP/RENAME statements\n");
                 }
             }
@@ -214,59 +264,35 @@ impl RollbackCommandHandler {
 
         // Dry run モードの場合は SQL を表示して終了
         if command.dry_run {
-            return self.execute_dry_run_with_format(
-                &rollback_items,
-                has_destructive,
-                &command.format,
-            );
+            return self.execute_dry_run_with_format(&plan.migrations, &command.format);
         }
 
-        // マイグレーションを順次ロールバック
-        let mut rolled_back = Vec::new();
-        for (record, down_sql, _) in rollback_items {
-            let start_time = Utc::now();
-            info!(version = %record.version, description = %record.description, "Rolling back migration");
-
-            // トランザクション内でロールバックを実行
-            let result = self
-                .rollback_migration_with_transaction(
-                    &pool,
-                    &migrator,
-                    &record.version,
-                    &down_sql,
-                    config.dialect,
-                )
-                .await;
-
-            if let Err(e) = result {
-                return Err(anyhow!(
-                    "Failed to rollback migration {} ({} rolled back, failed on rollback #{}): {}",
-                    record.version,
-                    rolled_back.len(),
-                    rolled_back.len() + 1,
-                    e
-                ));
-            }
-
-            let end_time = Utc::now();
-            let duration = end_time.signed_duration_since(start_time);
-
-            rolled_back.push(AppliedMigration::new(
-                record.version.clone(),
-                record.description.clone(),
-                end_time,
-                duration,
-            ));
+        // マイグレーションを順次ロールバック（各マイグレーションはトランザクション内で実行）
+        // 保護されたテーブルはデータベースに残るため、generate が再作成しないよう記録される
+        let report = self
+            .timings
+            .measure_async("execute", runner.execute_rollback(&plan))
+            .await?;
+        for warning in &report.warnings {
+            eprintln!("{}", warning.yellow());
         }
 
         // 結果サマリーを生成
-        let migration_results: Vec<RollbackMigrationResult> = rolled_back
+        let rolled_back: Vec<AppliedMigration> = report
+            .migrations
             .iter()
-            .map(|m| RollbackMigrationResult {
-                version: m.version.clone(),
-                description: m.description.clone(),
-                duration_ms: m.duration.num_milliseconds(),
+            .map(|result| result.migration.clone())
+            .collect();
+        let migration_results: Vec<RollbackMigrationResult> = report
+            .migrations
+            .into_iter()
+            .map(|result| RollbackMigrationResult {
+                version: result.migration.version,
+                description: result.migration.description,
+                duration_ms: result.migration.duration.num_milliseconds(),
                 sql: None,
+                protected_tables: result.protected_tables,
+                empty_down: false,
             })
             .collect();
 
@@ -275,64 +301,51 @@ impl RollbackCommandHandler {
             .map(|m| m.duration.num_milliseconds())
             .sum();
 
+        let message = format!(
+            "{}{}",
+            self.generate_summary(&rolled_back),
+            self.format_protected_tables(&migration_results, false)
+        );
         let output = RollbackOutput {
             dry_run: false,
             rolled_back_count: rolled_back.len(),
             migrations: migration_results,
             total_duration_ms: total_duration,
-            message: self.generate_summary(&rolled_back),
+            message,
         };
 
-        render_output(&output, &command.format)
+        render_output_with_timings(&output, &command.format, &self.timings)
     }
 
-    /// マイグレーションをトランザクション内でロールバック
-    async fn rollback_migration_with_transaction(
+    /// ロールバック保護により残ったテーブルの一覧をフォーマット
+    fn format_protected_tables(
         &self,
-        pool: &sqlx::AnyPool,
-        migrator: &DatabaseMigratorService,
-        version: &str,
-        down_sql: &str,
-        dialect: Dialect,
-    ) -> Result<()> {
-        // トランザクションを開始
-        let mut tx = pool
-            .begin()
-            .await
-            .with_context(|| "Failed to start transaction")?;
-
-        // マイグレーションdown SQLを文単位で実行
-        for statement in split_sql_statements(down_sql) {
-            sqlx::query(&statement)
-                .execute(&mut *tx)
-                .await
-                .with_context(|| {
-                    format!(
-                        "Failed to execute migration down SQL: {}\nSQL: {}",
-                        version, statement
-                    )
-                })?;
+        results: &[RollbackMigrationResult],
+        dry_run: bool,
+    ) -> String {
+        if results.iter().all(|r| r.protected_tables.is_empty()) {
+            return String::new();
         }
 
-        // マイグレーション履歴から削除（パラメータバインディング使用）
-        let (remove_sql, params) = migrator.generate_remove_migration_query(version, dialect);
-
-        let mut query = sqlx::query(&remove_sql);
-        for param in &params {
-            query = query.bind(param);
+        let mut output = if dry_run {
+            String::from(
+                "Protected tables (will not be dropped):
+",
+            )
+        } else {
+            String::from("\nProtected tables (left in place):\n")
+        };
+        for result in results {
+            for table in &result.protected_tables {
+                output.push_str(&format!("  - {} (created by {})\n", table, result.version));
+            }
         }
-
-        query
-            .execute(&mut *tx)
-            .await
-            .with_context(|| "Failed to remove migration history")?;
-
-        // トランザクションをコミット
-        tx.commit()
-            .await
-            .with_context(|| "Failed to commit transaction")?;
-
-        Ok(())
+        if !dry_run {
+            output.push_str(
+                "These tables still exist in the database. Delete or regenerate the rolled back migrations before applying them again.\n",
+            );
+        }
+        output
     }
 
     /// ロールバック結果のサマリーを生成
@@ -361,29 +374,26 @@ impl RollbackCommandHandler {
         summary
     }
 
-    /// SQLに破壊的変更が含まれているかチェック
-    fn contains_destructive_sql(&self, sql: &str) -> bool {
-        DESTRUCTIVE_SQL_REGEX.is_match(sql)
-    }
-
     /// Dry run モードの出力を生成（フォーマット対応）
     fn execute_dry_run_with_format(
         &self,
-        rollback_items: &[(&crate::core::migration::MigrationRecord, String, PathBuf)],
-        has_destructive: bool,
+        rollback_migrations: &[RollbackMigration],
         format: &OutputFormat,
     ) -> Result<String> {
-        let text = self.execute_dry_run(rollback_items, has_destructive);
+        let mut text = self.execute_dry_run(rollback_migrations);
 
-        let migration_results: Vec<RollbackMigrationResult> = rollback_items
+        let migration_results: Vec<RollbackMigrationResult> = rollback_migrations
             .iter()
-            .map(|(record, down_sql, _)| RollbackMigrationResult {
-                version: record.version.clone(),
-                description: record.description.clone(),
+            .map(|migration| RollbackMigrationResult {
+                version: migration.record.version.clone(),
+                description: migration.record.description.clone(),
                 duration_ms: 0,
-                sql: Some(down_sql.clone()),
+                sql: Some(migration.down_sql.clone()),
+                protected_tables: migration.protected_tables.clone(),
+                empty_down: migration.has_empty_down(),
             })
             .collect();
+        text.push_str(&self.format_protected_tables(&migration_results, true));
 
         let output = RollbackOutput {
             dry_run: true,
@@ -393,44 +403,63 @@ impl RollbackCommandHandler {
             message: text,
         };
 
-        render_output(&output, format)
+        render_output_with_timings(&output, format, &self.timings)
     }
 
     /// Dry run モードの出力を生成
-    fn execute_dry_run(
-        &self,
-        rollback_items: &[(&crate::core::migration::MigrationRecord, String, PathBuf)],
-        has_destructive: bool,
-    ) -> String {
+    fn execute_dry_run(&self, rollback_migrations: &[RollbackMigration]) -> String {
         let mut output = String::from("=== DRY RUN MODE ===\n");
         output.push_str(&format!(
             "The following {} migration(s) will be rolled back:\n\n",
-            rollback_items.len()
+            rollback_migrations.len()
         ));
 
-        for (record, down_sql, _) in rollback_items {
+        for migration in rollback_migrations {
+            let (record, down_sql) = (&migration.record, &migration.down_sql);
             output.push_str(&format!("▶ {} - {}\n", record.version, record.description));
 
-            if self.contains_destructive_sql(down_sql) {
+            if migration.destructive {
                 output.push_str(&format!(
                     "{}\n",
                     "⚠ Contains Destructive Changes".red().bold()
                 ));
             }
 
+            // 実行されるステートメントを順に列挙する（apply --annotate と同じ番号付け）
+            let statements = split_sql_statements(down_sql);
+            if statements.is_empty() {
+                output.push_str(&format!(
+                    "{}\n\n",
+                    "⚠ down.sql has no executable statements (requires --allow-empty-down)"
+                        .yellow()
+                        .bold()
+                ));
+                continue;
+            }
             output.push_str("SQL:\n");
-            let rendered_sql = if self.contains_destructive_sql(down_sql) {
-                self.highlight_destructive_sql(down_sql)
-            } else {
-                down_sql.clone()
-            };
-            output.push_str(&format!("{}\n\n", rendered_sql));
+            let total = statements.len();
+            for (i, statement) in statements.iter().enumerate() {
+                let body = format!("{};", strip_leading_comments(statement));
+                let rendered = if migration.destructive {
+                    self.highlight_destructive_sql(&body)
+                } else {
+                    body
+                };
+                output.push_str(&format!("-- statement {}/{}\n{}\n", i + 1, total, rendered));
+            }
+            output.push('\n');
         }
 
-        if has_destructive {
+        if rollback_migrations.iter().any(|m| m.destructive) {
             output
                 .push_str("To proceed, run without --dry-run and with --allow-destructive flag.\n");
         }
+        if rollback_migrations
+            .iter()
+            .any(RollbackMigration::has_empty_down)
+        {
+            output.push_str("Migrations with an empty down.sql are refused unless --allow-empty-down is passed.\n");
+        }
 
         output
     }
@@ -454,12 +483,7 @@ impl RollbackCommandHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::config::Dialect;
-    use crate::core::migration::Migration;
-    use sqlx::any::install_default_drivers;
-    use sqlx::any::AnyPoolOptions;
-    use sqlx::Row;
-    use tempfile::TempDir;
+    use chrono::Utc;
 
     #[test]
     fn test_new_handler() {
@@ -495,114 +519,6 @@ mod tests {
         assert!(summary.contains("300ms")); // 100 + 200
     }
 
-    #[tokio::test]
-    async fn test_rollback_failure_keeps_record() {
-        install_default_drivers();
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let connection_string = format!("sqlite://{}?mode=rwc", db_path.to_str().unwrap());
-        let pool = AnyPoolOptions::new()
-            .max_connections(1)
-            .connect(&connection_string)
-            .await
-            .unwrap();
-
-        let migrator = DatabaseMigratorService::new();
-        migrator
-            .create_migration_table(&pool, Dialect::SQLite)
-            .await
-            .unwrap();
-
-        let migration = Migration::new(
-            "20260122120001".to_string(),
-            "create_users".to_string(),
-            "checksum".to_string(),
-        );
-        let (record_sql, params) =
-            migrator.generate_record_migration_query(&migration, Dialect::SQLite);
-        let mut query = sqlx::query(&record_sql);
-        for param in &params {
-            query = query.bind(param);
-        }
-        query.execute(&pool).await.unwrap();
-
-        let handler = RollbackCommandHandler::new();
-        let result = handler
-            .rollback_migration_with_transaction(
-                &pool,
-                &migrator,
-                "20260122120001",
-                "INVALID SQL",
-                Dialect::SQLite,
-            )
-            .await;
-
-        assert!(result.is_err());
-
-        let row = sqlx::query("SELECT COUNT(*) FROM schema_migrations")
-            .fetch_one(&pool)
-            .await
-            .unwrap();
-        let count: i64 = row.get(0);
-        assert_eq!(count, 1);
-    }
-
-    #[tokio::test]
-    async fn test_rollback_success_removes_record() {
-        install_default_drivers();
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let connection_string = format!("sqlite://{}?mode=rwc", db_path.to_str().unwrap());
-        let pool = AnyPoolOptions::new()
-            .max_connections(1)
-            .connect(&connection_string)
-            .await
-            .unwrap();
-
-        let migrator = DatabaseMigratorService::new();
-        migrator
-            .create_migration_table(&pool, Dialect::SQLite)
-            .await
-            .unwrap();
-
-        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY)")
-            .execute(&pool)
-            .await
-            .unwrap();
-
-        let migration = Migration::new(
-            "20260122120002".to_string(),
-            "create_users".to_string(),
-            "checksum".to_string(),
-        );
-        let (record_sql, params) =
-            migrator.generate_record_migration_query(&migration, Dialect::SQLite);
-        let mut query = sqlx::query(&record_sql);
-        for param in &params {
-            query = query.bind(param);
-        }
-        query.execute(&pool).await.unwrap();
-
-        let handler = RollbackCommandHandler::new();
-        handler
-            .rollback_migration_with_transaction(
-                &pool,
-                &migrator,
-                "20260122120002",
-                "DROP TABLE users",
-                Dialect::SQLite,
-            )
-            .await
-            .unwrap();
-
-        let row = sqlx::query("SELECT COUNT(*) FROM schema_migrations")
-            .fetch_one(&pool)
-            .await
-            .unwrap();
-        let count: i64 = row.get(0);
-        assert_eq!(count, 0);
-    }
-
     #[test]
     fn test_rollback_output_json_serialization() {
         let output = RollbackOutput {
@@ -613,6 +529,8 @@ mod tests {
                 description: "create_users".to_string(),
                 duration_ms: 0,
                 sql: Some("DROP TABLE users;".to_string()),
+                protected_tables: Vec::new(),
+                empty_down: false,
             }],
             total_duration_ms: 0,
             message: "should not appear in JSON".to_string(),
@@ -628,35 +546,6 @@ mod tests {
         assert_eq!(parsed["migrations"][0]["sql"], "DROP TABLE users;");
     }
 
-    #[test]
-    fn test_contains_destructive_sql_drop_table() {
-        let handler = RollbackCommandHandler::new();
-        assert!(handler.contains_destructive_sql("DROP TABLE users;"));
-        assert!(handler.contains_destructive_sql("DROP TABLE IF EXISTS users;"));
-    }
-
-    #[test]
-    fn test_contains_destructive_sql_drop_column() {
-        let handler = RollbackCommandHandler::new();
-        assert!(handler.contains_destructive_sql("ALTER TABLE users DROP COLUMN email;"));
-    }
-
-    #[test]
-    fn test_contains_destructive_sql_truncate() {
-        let handler = RollbackCommandHandler::new();
-        assert!(handler.contains_destructive_sql("TRUNCATE TABLE users;"));
-    }
-
-    #[test]
-    fn test_contains_destructive_sql_non_destructive() {
-        let handler = RollbackCommandHandler::new();
-        assert!(!handler.contains_destructive_sql("CREATE TABLE users (id INTEGER);"));
-        assert!(
-            !handler.contains_destructive_sql("ALTER TABLE users ADD COLUMN email VARCHAR(255);")
-        );
-        assert!(!handler.contains_destructive_sql("INSERT INTO users (id) VALUES (1);"));
-    }
-
     #[test]
     fn test_highlight_destructive_sql() {
         let handler = RollbackCommandHandler::new();
@@ -675,16 +564,19 @@ mod tests {
         use std::path::PathBuf;
 
         let handler = RollbackCommandHandler::new();
-        let record = MigrationRecord::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum".to_string(),
-        );
-        let down_sql = "DROP TABLE users;".to_string();
-        let path = PathBuf::from("migrations/20260121120000_create_users");
-        let items = vec![(&record, down_sql, path)];
+        let items = vec![RollbackMigration {
+            record: MigrationRecord::new(
+                "20260121120000".to_string(),
+                "create_users".to_string(),
+                "checksum".to_string(),
+            ),
+            dir: PathBuf::from("migrations/20260121120000_create_users"),
+            down_sql: "DROP TABLE users;".to_string(),
+            destructive: true,
+            protected_tables: vec![],
+        }];
 
-        let output = handler.execute_dry_run(&items, true);
+        let output = handler.execute_dry_run(&items);
         assert!(output.contains("DRY RUN MODE"));
         assert!(output.contains("20260121120000"));
         assert!(output.contains("create_users"));
@@ -698,16 +590,19 @@ mod tests {
         use std::path::PathBuf;
 
         let handler = RollbackCommandHandler::new();
-        let record = MigrationRecord::new(
-            "20260121120000".to_string(),
-            "add_column".to_string(),
-            "checksum".to_string(),
-        );
-        let down_sql = "ALTER TABLE users DROP COLUMN email;".to_string();
-        let path = PathBuf::from("migrations/20260121120000_add_column");
-        let items = vec![(&record, down_sql, path)];
+        let items = vec![RollbackMigration {
+            record: MigrationRecord::new(
+                "20260121120000".to_string(),
+                "add_column".to_string(),
+                "checksum".to_string(),
+            ),
+            dir: PathBuf::from("migrations/20260121120000_add_column"),
+            down_sql: "ALTER TABLE users DROP COLUMN email;".to_string(),
+            destructive: false,
+            protected_tables: vec![],
+        }];
 
-        let output = handler.execute_dry_run(&items, false);
+        let output = handler.execute_dry_run(&items);
         assert!(output.contains("DRY RUN MODE"));
         assert!(!output.contains("--allow-destructive"));
     }
@@ -718,16 +613,19 @@ mod tests {
         use std::path::PathBuf;
 
         let handler = RollbackCommandHandler::new();
-        let record = MigrationRecord::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum".to_string(),
-        );
-        let down_sql = "DROP TABLE users;".to_string();
-        let path = PathBuf::from("migrations/20260121120000_create_users");
-        let items = vec![(&record, down_sql, path)];
+        let items = vec![RollbackMigration {
+            record: MigrationRecord::new(
+                "20260121120000".to_string(),
+                "create_users".to_string(),
+                "checksum".to_string(),
+            ),
+            dir: PathBuf::from("migrations/20260121120000_create_users"),
+            down_sql: "DROP TABLE users;".to_string(),
+            destructive: true,
+            protected_tables: vec![],
+        }];
 
-        let result = handler.execute_dry_run_with_format(&items, true, &OutputFormat::Text);
+        let result = handler.execute_dry_run_with_format(&items, &OutputFormat::Text);
         assert!(result.is_ok());
         let output = result.unwrap();
         assert!(output.contains("DRY RUN MODE"));
@@ -739,16 +637,19 @@ mod tests {
         use std::path::PathBuf;
 
         let handler = RollbackCommandHandler::new();
-        let record = MigrationRecord::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum".to_string(),
-        );
-        let down_sql = "DROP TABLE users;".to_string();
-        let path = PathBuf::from("migrations/20260121120000_create_users");
-        let items = vec![(&record, down_sql, path)];
+        let items = vec![RollbackMigration {
+            record: MigrationRecord::new(
+                "20260121120000".to_string(),
+                "create_users".to_string(),
+                "checksum".to_string(),
+            ),
+            dir: PathBuf::from("migrations/20260121120000_create_users"),
+            down_sql: "DROP TABLE users;".to_string(),
+            destructive: true,
+            protected_tables: vec![],
+        }];
 
-        let result = handler.execute_dry_run_with_format(&items, true, &OutputFormat::Json);
+        let result = handler.execute_dry_run_with_format(&items, &OutputFormat::Json);
         assert!(result.is_ok());
         let output = result.unwrap();
         // JSONとしてパース可能
@@ -756,4 +657,45 @@ mod tests {
         assert_eq!(parsed["dry_run"], true);
         assert_eq!(parsed["rolled_back_count"], 1);
     }
+
+    #[test]
+    fn test_execute_dry_run_lists_statements_and_empty_down() {
+        use crate::core::migration::MigrationRecord;
+        use std::path::PathBuf;
+
+        let migration = |version: &str, down_sql: &str| RollbackMigration {
+            record: MigrationRecord::new(
+                version.to_string(),
+                "change".to_string(),
+                "checksum".to_string(),
+            ),
+            dir: PathBuf::from(format!("migrations/{}_change", version)),
+            down_sql: down_sql.to_string(),
+            destructive: false,
+            protected_tables: vec![],
+        };
+        let handler = RollbackCommandHandler::new();
+        let items = vec![
+            migration(
+                "20260121120001",
+                "-- revert\nALTER TABLE users DROP COLUMN email;\nDROP INDEX idx_users_name;",
+            ),
+            migration("20260121120000", "-- nothing to revert\n"),
+        ];
+
+        let output = handler.execute_dry_run(&items);
+
+        assert!(output.contains(
+            "-- statement 1/2\nALTER TABLE users DROP COLUMN email;\n-- statement 2/2\nDROP INDEX idx_users_name;\n"
+        ), "{}", output);
+        assert!(!output.contains("-- revert"), "{}", output);
+        assert!(
+            output.contains("down.sql has no executable statements"),
+            "{}",
+            output
+        );
+        assert!(output.contains("--allow-empty-down"), "{}", output);
+        assert!(items[1].has_empty_down());
+        assert!(!items[0].has_empty_down());
+    }
 }
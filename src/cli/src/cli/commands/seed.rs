@@ -0,0 +1,189 @@
+// seedコマンドハンドラー
+//
+// seeds/ ディレクトリのシードファイルを、指定した環境のデータベースに投入します。
+// - 読み込み: seed_files サービス（ファイル名の順、SQLファイルとYAMLファイル）
+// - 実行: すべてのファイルを1つのトランザクションで実行し、失敗した場合は何も投入しない
+// - シードは適用履歴を記録しないため、何度でも再実行できる内容にする
+
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::OutputFormat;
+use crate::services::seed_files::{load_seed_files, SeedFileKind, SEEDS_DIR};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::debug;
+
+/// seedコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct SeedCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 環境名
+    pub env: String,
+    /// 実行するシードファイル（ファイル名または拡張子を除いたファイル名。Noneの場合はすべて）
+    pub only: Option<String>,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// シードファイルごとの実行結果
+#[derive(Debug, Clone, Serialize)]
+pub struct SeedFileResult {
+    /// ファイル名
+    pub file: String,
+    /// ファイルの種類
+    pub kind: SeedFileKind,
+    /// 実行したステートメント数
+    pub statements: usize,
+    /// 影響を受けた行数（データベースが返す値の合計）
+    pub rows_affected: u64,
+}
+
+/// seedコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct SeedOutput {
+    /// 環境名
+    pub environment: String,
+    /// 実行したファイル（実行順）
+    pub files: Vec<SeedFileResult>,
+    /// 影響を受けた行数の合計
+    pub rows_affected: u64,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+impl CommandOutput for SeedOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// seedコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct SeedCommandHandler {}
+
+impl SeedCommandHandler {
+    /// 新しいSeedCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// seedコマンドを実行
+    pub async fn execute(&self, command: &SeedCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?
+        .with_wait_timeout(command.wait_timeout);
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &SeedCommand,
+    ) -> Result<String> {
+        let seeds_dir = command.project_path.join(SEEDS_DIR);
+        if !seeds_dir.is_dir() {
+            return Err(anyhow!(
+                "Seeds directory not found: {:?}. Create it and add seed files such as {}/01_roles.yaml or {}/01_roles.sql",
+                seeds_dir,
+                SEEDS_DIR,
+                SEEDS_DIR
+            ));
+        }
+
+        let mut files = load_seed_files(&seeds_dir)?;
+        if let Some(only) = &command.only {
+            files.retain(|file| file.matches(only));
+            if files.is_empty() {
+                return Err(anyhow!("Seed file '{}' not found in {:?}", only, seeds_dir));
+            }
+        }
+
+        if files.is_empty() {
+            let output = SeedOutput {
+                environment: command.env.clone(),
+                files: Vec::new(),
+                rows_affected: 0,
+                text_message: format!("No seed files found in {}/", SEEDS_DIR),
+            };
+            return render_output(&output, &command.format);
+        }
+
+        // 接続前にすべてのファイルを読み込み、YAMLの誤りで途中まで実行されないようにする
+        let dialect = context.dialect();
+        let statements = files
+            .iter()
+            .map(|file| file.statements(dialect, &context.config.sql_output))
+            .collect::<Result<Vec<_>>>()?;
+
+        let pool = context.connect_pool(&command.env).await?;
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        let mut results = Vec::new();
+        for (file, file_statements) in files.iter().zip(&statements) {
+            debug!(file = %file.name, statements = file_statements.len(), "Executing seed file");
+            let mut rows_affected = 0;
+            for statement in file_statements {
+                let result = sqlx::query(statement)
+                    .execute(&mut *tx)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to execute seed file '{}' (no seed data was committed)\nSQL: {}",
+                            file.name, statement
+                        )
+                    })?;
+                rows_affected += result.rows_affected();
+            }
+            results.push(SeedFileResult {
+                file: file.name.clone(),
+                kind: file.kind,
+                statements: file_statements.len(),
+                rows_affected,
+            });
+        }
+
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit seed data")?;
+
+        let rows_affected = results.iter().map(|r| r.rows_affected).sum();
+        let output = SeedOutput {
+            environment: command.env.clone(),
+            text_message: format_text(&command.env, &results, rows_affected),
+            files: results,
+            rows_affected,
+        };
+        render_output(&output, &command.format)
+    }
+}
+
+fn format_text(env: &str, results: &[SeedFileResult], rows_affected: u64) -> String {
+    let mut lines = vec![format!(
+        "Seeded '{}' from {} file(s) ({} row(s) affected)",
+        env,
+        results.len(),
+        rows_affected
+    )];
+    for result in results {
+        lines.push(format!(
+            "  {}: {} statement(s), {} row(s) affected",
+            result.file, result.statements, result.rows_affected
+        ));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,297 @@
+// 生成したSQLの編集（generate --edit）
+//
+// マイグレーションディレクトリを書き出す前に、up.sql / down.sql を一時ディレクトリに書き出して
+// エディタで開きます（git commit と同様）。
+// - エディタは STRATA_EDITOR、VISUAL、EDITOR の順に探し、どれもなければ vi を使う
+// - `-- strata:` で始まる案内行は読み戻す際に取り除く
+// - ファイルを空にする、またはエディタが失敗した場合は編集を取りやめる
+// テストでは STRATA_EDITOR（または `with_command`）にスクリプトを指定してエディタの操作を再現します。
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 編集に使うエディタを指定する環境変数（VISUAL / EDITOR より優先）
+pub const EDITOR_ENV: &str = "STRATA_EDITOR";
+
+/// 読み戻す際に取り除く案内行の接頭辞
+const GUIDE_PREFIX: &str = "-- strata:";
+
+/// エディタが指定されていない場合に使うコマンド
+const DEFAULT_EDITOR: &str = "vi";
+
+/// 編集の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// 編集後のSQL（案内行を取り除いたもの）
+    Edited { up_sql: String, down_sql: String },
+    /// 編集が取りやめられた（理由）
+    Abandoned(String),
+}
+
+/// up.sql / down.sql をエディタで編集する
+#[derive(Debug, Clone, Default)]
+pub struct SqlEditor {
+    /// エディタのコマンド（Noneの場合は環境変数から決める）
+    command: Option<String>,
+    /// 端末かどうかの判定を上書きする（Noneの場合は標準入出力から判定）
+    interactive: Option<bool>,
+}
+
+impl SqlEditor {
+    /// 新しいSqlEditorを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 環境変数の代わりに使うエディタのコマンドを設定
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// 端末から実行されているかの判定を上書きする
+    ///
+    /// テストでは、端末に接続されていなくてもエディタを起動できるようにするために使用します。
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = Some(interactive);
+        self
+    }
+
+    /// エディタを起動できる（標準入力と標準エラー出力が端末に接続されている）か
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+            .unwrap_or_else(|| io::stdin().is_terminal() && io::stderr().is_terminal())
+    }
+
+    /// 使用するエディタのコマンド
+    pub fn editor_command(&self) -> String {
+        self.command
+            .clone()
+            .or_else(|| {
+                [EDITOR_ENV, "VISUAL", "EDITOR"]
+                    .iter()
+                    .filter_map(|name| std::env::var(name).ok())
+                    .find(|value| !value.trim().is_empty())
+            })
+            .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+    }
+
+    /// up.sql / down.sql をエディタで開き、編集後の内容を返す
+    ///
+    /// 一時ディレクトリは編集が終わると削除します。
+    pub fn edit(&self, migration_name: &str, up_sql: &str, down_sql: &str) -> Result<EditOutcome> {
+        let dir = std::env::temp_dir().join(format!(
+            "strata-edit-{}-{}",
+            migration_name,
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory for editing: {:?}", dir))?;
+
+        let result = self.edit_in(&dir, migration_name, up_sql, down_sql);
+        let _ = fs::remove_dir_all(&dir);
+        result
+    }
+
+    fn edit_in(
+        &self,
+        dir: &Path,
+        migration_name: &str,
+        up_sql: &str,
+        down_sql: &str,
+    ) -> Result<EditOutcome> {
+        let up_path = dir.join("up.sql");
+        let down_path = dir.join("down.sql");
+        for (path, sql) in [(&up_path, up_sql), (&down_path, down_sql)] {
+            fs::write(path, with_guide(migration_name, path, sql))
+                .with_context(|| format!("Failed to write {:?}", path))?;
+        }
+
+        let editor = self.editor_command();
+        let status = editor_process(&editor, &[&up_path, &down_path])
+            .status()
+            .with_context(|| format!("Failed to start editor '{}'", editor))?;
+        if !status.success() {
+            return Ok(EditOutcome::Abandoned(format!(
+                "the editor '{}' exited with {}",
+                editor, status
+            )));
+        }
+
+        let up_sql = read_edited(&up_path)?;
+        let down_sql = read_edited(&down_path)?;
+        for (name, sql) in [("up.sql", &up_sql), ("down.sql", &down_sql)] {
+            if sql.trim().is_empty() {
+                return Ok(EditOutcome::Abandoned(format!("{} was left empty", name)));
+            }
+        }
+        Ok(EditOutcome::Edited { up_sql, down_sql })
+    }
+}
+
+/// 案内行を付けた編集用のファイルの内容
+fn with_guide(migration_name: &str, path: &Path, sql: &str) -> String {
+    let file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    format!(
+        "{prefix} Editing {file} of migration {name}. Save and close the editor to continue.\n\
+         {prefix} Lines starting with \"{prefix}\" are removed. Delete everything else to cancel generation.\n\
+         {sql}\n",
+        prefix = GUIDE_PREFIX,
+        file = file,
+        name = migration_name,
+        sql = sql.trim_end()
+    )
+}
+
+/// 編集後のファイルを読み込み、案内行を取り除く
+fn read_edited(path: &Path) -> Result<String> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read edited {:?}", path))?;
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with(GUIDE_PREFIX))
+        .collect();
+    Ok(lines.join("\n").trim().to_string())
+}
+
+/// エディタのプロセス
+///
+/// `EDITOR="code --wait"` のように引数を含む指定も扱えるよう、git と同様にシェル経由で起動します。
+fn editor_process(editor: &str, files: &[&PathBuf]) -> Command {
+    #[cfg(unix)]
+    {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("{} \"$@\"", editor))
+            .arg(editor)
+            .args(files);
+        command
+    }
+    #[cfg(not(unix))]
+    {
+        let mut parts = editor.split_whitespace();
+        let mut command = Command::new(parts.next().unwrap_or(DEFAULT_EDITOR));
+        command.args(parts).args(files);
+        command
+    }
+}
+
+/// `--edit` を指定できる状況か確認する
+///
+/// JSON出力やdry-runと組み合わせた場合、端末から実行されていない場合はエラーにします。
+pub(crate) fn check_edit_allowed(editor: &SqlEditor, json: bool, dry_run: bool) -> Result<()> {
+    if json {
+        return Err(anyhow!(
+            "--edit opens an editor and cannot be combined with --format json"
+        ));
+    }
+    if dry_run {
+        return Err(anyhow!(
+            "--edit edits the files before they are written and cannot be combined with --dry-run"
+        ));
+    }
+    if !editor.is_interactive() {
+        return Err(anyhow!(
+            "--edit requires a terminal. Edit up.sql and down.sql after generating instead."
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_edited_removes_guide_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("up.sql");
+        fs::write(
+            &path,
+            with_guide(
+                "20260101000000_add",
+                &path,
+                "CREATE TABLE a (id INTEGER);\n",
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(read_edited(&path).unwrap(), "CREATE TABLE a (id INTEGER);");
+    }
+
+    #[test]
+    fn test_editor_command_prefers_explicit_command() {
+        let editor = SqlEditor::new().with_command("my-editor --wait");
+        assert_eq!(editor.editor_command(), "my-editor --wait");
+    }
+
+    #[test]
+    fn test_check_edit_allowed() {
+        let terminal = SqlEditor::new().with_interactive(true);
+        assert!(check_edit_allowed(&terminal, false, false).is_ok());
+        assert!(check_edit_allowed(&terminal, true, false)
+            .unwrap_err()
+            .to_string()
+            .contains("--format json"));
+        assert!(check_edit_allowed(&terminal, false, true)
+            .unwrap_err()
+            .to_string()
+            .contains("--dry-run"));
+
+        let piped = SqlEditor::new().with_interactive(false);
+        assert!(check_edit_allowed(&piped, false, false)
+            .unwrap_err()
+            .to_string()
+            .contains("requires a terminal"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_abandoned_when_file_is_emptied() {
+        let editor = SqlEditor::new()
+            .with_interactive(true)
+            .with_command("truncate -s 0");
+
+        let outcome = editor
+            .edit(
+                "20260101000000_add",
+                "CREATE TABLE a (id INTEGER);",
+                "DROP TABLE a;",
+            )
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            EditOutcome::Abandoned("up.sql was left empty".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_returns_unchanged_sql() {
+        let editor = SqlEditor::new().with_interactive(true).with_command("true");
+
+        let outcome = editor
+            .edit(
+                "20260101000000_add",
+                "CREATE TABLE a (id INTEGER);",
+                "DROP TABLE a;",
+            )
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            EditOutcome::Edited {
+                up_sql: "CREATE TABLE a (id INTEGER);".to_string(),
+                down_sql: "DROP TABLE a;".to_string(),
+            }
+        );
+    }
+}
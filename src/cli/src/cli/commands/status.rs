@@ -4,20 +4,85 @@
 // - データベース接続と履歴テーブルの読み込み
 // - ローカルマイグレーションファイルとの照合
 // - 適用済み/未適用の状態表示（テーブル形式）
-// - チェックサム不一致の検出と警告
+// - チェックサム不一致（適用後の up.sql / .meta.yaml の変更）の検出と警告
+// - デプロイ前チェック用のゲート（--gate pending,drift,unmanaged）
+// - --verbose 指定時の適用日時・実行時間・適用ユーザーの表示
 
+use crate::adapters::database_introspector::create_introspector_for_config;
+use crate::adapters::database_migrator::DatabaseMigratorService;
 use crate::cli::command_context::CommandContext;
-use crate::cli::commands::migration_loader;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::commands::migration_checksum::ChecksumStatus;
+use crate::cli::commands::{migration_loader, retained_tables};
+use crate::cli::commands::{render_output_with_timings, CommandOutput, CompletedWithFailure};
+use crate::cli::timings::Timings;
 use crate::cli::OutputFormat;
-use crate::core::migration::{Migration, MigrationMetadata, MigrationRecord};
+use crate::core::migration::{MigrationAudit, MigrationRecord};
+use crate::core::schema::Schema;
+use crate::services::migration_runner::{MigrationRunner, MigrationState, MigrationStatus};
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
-use std::collections::HashMap;
-use std::fs;
+use sqlx::AnyPool;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
+/// pending ゲートが失敗した場合の終了コード（未適用のマイグレーションがある）
+pub const EXIT_CODE_GATE_PENDING: i32 = 4;
+/// drift ゲートが失敗した場合の終了コード（適用済みマイグレーションのチェックサム不一致）
+pub const EXIT_CODE_GATE_DRIFT: i32 = 5;
+/// unmanaged ゲートが失敗した場合の終了コード（スナップショットにないテーブルがDBにある）
+pub const EXIT_CODE_GATE_UNMANAGED: i32 = 6;
+
+/// デプロイ前チェックのゲート
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum StatusGate {
+    /// Fail if there are pending migrations
+    Pending,
+    /// Fail if applied migrations have mismatched checksums
+    Drift,
+    /// Fail if the database has tables not represented in the schema snapshot
+    Unmanaged,
+}
+
+impl StatusGate {
+    /// ゲート名（出力用）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusGate::Pending => "pending",
+            StatusGate::Drift => "drift",
+            StatusGate::Unmanaged => "unmanaged",
+        }
+    }
+
+    /// ゲートが失敗した場合の終了コード
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            StatusGate::Pending => EXIT_CODE_GATE_PENDING,
+            StatusGate::Drift => EXIT_CODE_GATE_DRIFT,
+            StatusGate::Unmanaged => EXIT_CODE_GATE_UNMANAGED,
+        }
+    }
+}
+
+/// ゲートの判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GateVerdict {
+    Pass,
+    Fail,
+}
+
+/// ゲートの判定結果
+#[derive(Debug, Clone)]
+pub struct GateResult {
+    pub gate: StatusGate,
+    pub verdict: GateVerdict,
+    /// 判定の詳細（テキスト出力・エラーメッセージ用）
+    pub detail: String,
+}
+
 /// statusコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
 pub struct StatusOutput {
@@ -27,6 +92,12 @@ pub struct StatusOutput {
     pub summary: StatusSummary,
     /// 警告メッセージ
     pub warnings: Vec<String>,
+    /// ゲートの判定（--gate 指定時のみ）
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub gates: BTreeMap<&'static str, GateVerdict>,
+    /// スナップショットにないテーブル（unmanaged ゲート指定時のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmanaged_tables: Vec<String>,
     /// テキスト出力メッセージ
     #[serde(skip)]
     pub text_message: String,
@@ -50,6 +121,33 @@ pub struct MigrationStatusEntry {
     pub status: MigrationStatusValue,
     /// チェックサム不一致かどうか
     pub checksum_mismatch: bool,
+    /// 適用済みマイグレーションのチェックサムの検証結果（未適用・孤立の場合は null）
+    pub checksum_status: Option<ChecksumStatus>,
+    /// バージョン順より先に適用されたか（`apply --only` で適用した場合など）
+    pub out_of_order: bool,
+    /// 適用記録の詳細（--verbose 指定時のみ）
+    #[serde(flatten)]
+    pub audit: Option<MigrationAuditEntry>,
+}
+
+/// 適用記録の詳細エントリ
+///
+/// 未適用のマイグレーションと、記録用カラムの追加前に適用されたマイグレーションの値は null です。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationAuditEntry {
+    pub applied_at: Option<DateTime<Utc>>,
+    pub execution_time_ms: Option<i64>,
+    pub applied_by: Option<String>,
+}
+
+impl From<&MigrationAudit> for MigrationAuditEntry {
+    fn from(audit: &MigrationAudit) -> Self {
+        Self {
+            applied_at: Some(audit.applied_at),
+            execution_time_ms: audit.execution_time_ms,
+            applied_by: audit.applied_by.clone(),
+        }
+    }
 }
 
 /// ステータスサマリー
@@ -59,6 +157,8 @@ pub struct StatusSummary {
     pub applied: usize,
     pub pending: usize,
     pub orphaned: usize,
+    /// 最新の適用済みマイグレーションのバージョン（`apply`/`rollback --target` の基準）
+    pub current_version: Option<String>,
 }
 
 impl CommandOutput for StatusOutput {
@@ -76,18 +176,32 @@ pub struct StatusCommand {
     pub config_path: Option<PathBuf>,
     /// 環境名
     pub env: String,
+    /// データベースが接続を受け付けるまで待つ最大秒数（Noneの場合は環境設定に従う）
+    pub wait_timeout: Option<u64>,
     /// 出力フォーマット
     pub format: OutputFormat,
+    /// 判定するゲート（指定順に評価し、最初に失敗したゲートの終了コードで終了する）
+    pub gates: Vec<StatusGate>,
+    /// 適用日時・実行時間・適用ユーザーを表示するか
+    pub verbose: bool,
 }
 
 /// statusコマンドハンドラー
 #[derive(Debug, Default)]
-pub struct StatusCommandHandler {}
+pub struct StatusCommandHandler {
+    timings: Timings,
+}
 
 impl StatusCommandHandler {
     /// 新しいStatusCommandHandlerを作成
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// フェーズ別の実行時間の計測先を設定
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
     }
 
     /// statusコマンドを実行
@@ -104,17 +218,43 @@ impl StatusCommandHandler {
         let context = CommandContext::load_with_config(
             command.project_path.clone(),
             command.config_path.clone(),
-        )?;
+        )?
+        .with_timings(self.timings.clone())
+        .with_wait_timeout(command.wait_timeout);
 
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    /// 読み込み済みのコンテキストでstatusコマンドを実行
+    pub async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &StatusCommand,
+    ) -> Result<String> {
         // マイグレーションディレクトリのパスを解決
         let migrations_dir = context.require_migrations_dir()?;
 
+        let runner = MigrationRunner::new(
+            context.config.clone(),
+            command.env.clone(),
+            migrations_dir.clone(),
+        );
+
         // ローカルマイグレーションファイルを読み込む
-        let local_migrations = self.load_local_migrations(&migrations_dir)?;
-        debug!(count = local_migrations.len(), "Loaded local migrations");
+        let local = runner.local_migrations(None)?;
+        // ファイルが欠けたマイグレーションは status では警告に留める（apply ではエラー）
+        let incomplete_warnings: Vec<String> = local
+            .incomplete
+            .iter()
+            .map(|m| m.warning_message())
+            .collect();
+        let future_warnings = local.future_warnings.clone();
+        debug!(count = local.migrations.len(), "Loaded local migrations");
 
-        // マイグレーションが存在しない場合
-        if local_migrations.is_empty() {
+        // マイグレーションが存在しない場合（ゲートの判定にはDB接続が必要なため続行する）
+        if local.is_empty() && command.gates.is_empty() {
             let output = StatusOutput {
                 migrations: vec![],
                 summary: StatusSummary {
@@ -122,68 +262,115 @@ impl StatusCommandHandler {
                     applied: 0,
                     pending: 0,
                     orphaned: 0,
+                    current_version: None,
                 },
                 warnings: vec![],
+                gates: BTreeMap::new(),
+                unmanaged_tables: vec![],
                 text_message: self.format_no_migrations(),
             };
-            return render_output(&output, &command.format);
+            return render_output_with_timings(&output, &command.format, &self.timings);
         }
 
-        // データベースに接続し、マイグレーション履歴を取得
-        let (_pool, applied_migrations) = context.connect_and_load_migrations(&command.env).await?;
-
-        // マイグレーション状態を生成
-        let status_list = self.build_migration_status(&local_migrations, &applied_migrations);
+        // データベースに接続し、マイグレーション履歴と照合
+        // 適用済みマイグレーションは up.sql を含めてチェックサムを検証する
+        let pool = context.connect_pool(&command.env).await?;
+        let runner = runner.with_pool(pool.clone());
+        let report = self
+            .timings
+            .measure_async("history", runner.status(&local))
+            .await?;
+        let applied_migrations = &report.applied;
+
+        // --verbose 指定時は適用記録の詳細を取得
+        let audits: Option<HashMap<String, MigrationAudit>> = if command.verbose {
+            let audits = DatabaseMigratorService::new()
+                .get_migration_audits(&pool, context.config.dialect)
+                .await
+                .with_context(|| "Failed to read the migration history details")?;
+            Some(
+                audits
+                    .into_iter()
+                    .map(|audit| (audit.version.clone(), audit))
+                    .collect(),
+            )
+        } else {
+            None
+        };
 
         // 各ステータスの数を計算
-        let applied_count = status_list
-            .iter()
-            .filter(|(_, _, status)| status.starts_with("Applied"))
-            .count();
-        let pending_count = status_list
-            .iter()
-            .filter(|(_, _, status)| status == "Pending")
-            .count();
-        let orphaned_count = status_list
-            .iter()
-            .filter(|(_, _, status)| status == "Orphaned")
-            .count();
+        let applied_count = report.count(MigrationState::Applied);
+        let pending_count = report.count(MigrationState::Pending);
+        let orphaned_count = report.count(MigrationState::Orphaned);
 
         // 構造化出力データを構築
         let mut warnings = Vec::new();
-        let migration_entries: Vec<MigrationStatusEntry> = status_list
+        let status_list: Vec<(String, String, String)> = report
+            .migrations
+            .iter()
+            .map(|m| {
+                (
+                    m.version.clone(),
+                    m.description.clone(),
+                    self.status_label(m).to_string(),
+                )
+            })
+            .collect();
+        let migration_entries: Vec<MigrationStatusEntry> = report
+            .migrations
             .iter()
-            .map(|(v, d, s)| {
-                let checksum_mismatch = s.contains("checksum mismatch");
-                let status = if s.starts_with("Applied") {
-                    if checksum_mismatch {
+            .map(|m| {
+                let checksum_mismatch = m.checksum_status == Some(ChecksumStatus::Modified);
+                let status = match m.state {
+                    MigrationState::Applied if checksum_mismatch => {
                         MigrationStatusValue::AppliedChecksumMismatch
-                    } else {
-                        MigrationStatusValue::Applied
                     }
-                } else if s == "Orphaned" {
-                    MigrationStatusValue::Orphaned
-                } else {
-                    MigrationStatusValue::Pending
+                    MigrationState::Applied => MigrationStatusValue::Applied,
+                    MigrationState::Pending => MigrationStatusValue::Pending,
+                    MigrationState::Orphaned => MigrationStatusValue::Orphaned,
                 };
                 MigrationStatusEntry {
-                    version: v.clone(),
-                    description: d.clone(),
+                    version: m.version.clone(),
+                    description: m.description.clone(),
                     status,
                     checksum_mismatch,
+                    checksum_status: m.checksum_status,
+                    out_of_order: m.out_of_order,
+                    audit: audits.as_ref().map(|audits| {
+                        audits
+                            .get(&m.version)
+                            .map(MigrationAuditEntry::from)
+                            .unwrap_or_default()
+                    }),
                 }
             })
             .collect();
 
         if migration_entries
             .iter()
-            .any(|e| e.status == MigrationStatusValue::AppliedChecksumMismatch)
+            .any(|e| e.checksum_status == Some(ChecksumStatus::Modified))
         {
             warnings.push("Some migrations have mismatched checksums. Migration files may have been modified after being applied.".to_string());
         }
+        if migration_entries
+            .iter()
+            .any(|e| e.checksum_status == Some(ChecksumStatus::MissingMeta))
+        {
+            warnings.push("Some applied migrations have no readable .meta.yaml, so their checksums cannot be verified.".to_string());
+        }
+        if migration_entries.iter().any(|e| e.out_of_order) {
+            warnings.push("Some migrations were applied out of order. Earlier versions were pending when they were applied.".to_string());
+        }
         if orphaned_count > 0 {
             warnings.push("Orphaned migrations detected. These migrations exist in the database but their local files are missing.".to_string());
         }
+        let warning_text: String = incomplete_warnings
+            .iter()
+            .chain(future_warnings.iter())
+            .map(|warning| format!("\n⚠️  {}\n", warning))
+            .collect();
+        warnings.extend(incomplete_warnings);
+        warnings.extend(future_warnings);
 
         // フォーマット用に参照のベクタを作成
         let status_list_refs: Vec<(&str, &str, &str)> = status_list
@@ -191,12 +378,49 @@ impl StatusCommandHandler {
             .map(|(v, d, s)| (v.as_str(), d.as_str(), s.as_str()))
             .collect();
 
-        let text_message = self.format_migration_status(
-            &status_list_refs,
-            applied_count,
+        let mut text_message = if status_list.is_empty() {
+            self.format_no_migrations()
+        } else {
+            self.format_migration_status(
+                &status_list_refs,
+                applied_count,
+                pending_count,
+                orphaned_count,
+            )
+        };
+        // --target で移動する際に影響を受けるバージョンの境界を示す
+        let current_version = applied_migrations.last().map(|r| r.version.clone());
+        if let Some(version) = &current_version {
+            text_message.push_str(&format!(
+                "Current version: {} (`apply --target` applies pending migrations up to a version; `rollback --target` rolls back the ones after it)\n",
+                version
+            ));
+        }
+        if audits.is_some() {
+            text_message.push_str(&self.format_audit_details(&migration_entries));
+        }
+        text_message.push_str(&warning_text);
+
+        // ゲートの判定
+        let mut unmanaged_tables = Vec::new();
+        if command.gates.contains(&StatusGate::Unmanaged) {
+            unmanaged_tables = self
+                .find_unmanaged_tables(context, &pool, &migrations_dir, applied_migrations)
+                .await?;
+        }
+        let mismatch_count = migration_entries
+            .iter()
+            .filter(|e| e.status == MigrationStatusValue::AppliedChecksumMismatch)
+            .count();
+        let gate_results = self.evaluate_gates(
+            &command.gates,
             pending_count,
-            orphaned_count,
+            mismatch_count,
+            &unmanaged_tables,
         );
+        if !gate_results.is_empty() {
+            text_message.push_str(&self.format_gate_results(&gate_results));
+        }
 
         let output = StatusOutput {
             migrations: migration_entries,
@@ -205,94 +429,242 @@ impl StatusCommandHandler {
                 applied: applied_count,
                 pending: pending_count,
                 orphaned: orphaned_count,
+                current_version: current_version.clone(),
             },
             warnings,
+            gates: gate_results
+                .iter()
+                .map(|result| (result.gate.as_str(), result.verdict))
+                .collect(),
+            unmanaged_tables,
             text_message,
         };
 
-        render_output(&output, &command.format)
+        let rendered = render_output_with_timings(&output, &command.format, &self.timings)?;
+        self.check_gates(rendered, &gate_results)
     }
 
-    /// ローカルマイグレーションファイルを読み込む
-    fn load_local_migrations(&self, migrations_dir: &Path) -> Result<Vec<Migration>> {
-        let available = migration_loader::load_available_migrations(migrations_dir)?;
+    /// ゲートを指定順に判定する（重複指定は1回のみ）
+    fn evaluate_gates(
+        &self,
+        gates: &[StatusGate],
+        pending_count: usize,
+        mismatch_count: usize,
+        unmanaged_tables: &[String],
+    ) -> Vec<GateResult> {
+        let mut seen = HashSet::new();
+        gates
+            .iter()
+            .filter(|gate| seen.insert(**gate))
+            .map(|&gate| {
+                let (failed, detail) = match gate {
+                    StatusGate::Pending => (
+                        pending_count > 0,
+                        format!("{} pending migration(s)", pending_count),
+                    ),
+                    StatusGate::Drift => (
+                        mismatch_count > 0,
+                        format!(
+                            "{} applied migration(s) with mismatched checksums",
+                            mismatch_count
+                        ),
+                    ),
+                    StatusGate::Unmanaged if unmanaged_tables.is_empty() => {
+                        (false, "no tables outside the schema snapshot".to_string())
+                    }
+                    StatusGate::Unmanaged => (
+                        true,
+                        format!(
+                            "{} table(s) not in the schema snapshot: {}",
+                            unmanaged_tables.len(),
+                            unmanaged_tables.join(", ")
+                        ),
+                    ),
+                };
+                GateResult {
+                    gate,
+                    verdict: if failed {
+                        GateVerdict::Fail
+                    } else {
+                        GateVerdict::Pass
+                    },
+                    detail,
+                }
+            })
+            .collect()
+    }
 
-        let mut migrations = Vec::new();
-        for (version, description, path) in available {
-            // メタデータファイルからチェックサムを読み込む
-            let meta_path = path.join(".meta.yaml");
-            let checksum = if meta_path.exists() {
-                let meta_content = fs::read_to_string(&meta_path)?;
-                self.extract_checksum_from_meta(&meta_content)?
-            } else {
-                "unknown".to_string()
-            };
+    /// 失敗したゲートがあれば、最初に失敗したゲートの終了コードで失敗として扱う
+    ///
+    /// 出力はすべてのゲートの判定を含めて表示するため、`CompletedWithFailure` エラーとして返します。
+    fn check_gates(&self, rendered: String, gate_results: &[GateResult]) -> Result<String> {
+        let failed: Vec<&GateResult> = gate_results
+            .iter()
+            .filter(|result| result.verdict == GateVerdict::Fail)
+            .collect();
+        let Some(first) = failed.first() else {
+            return Ok(rendered);
+        };
 
-            migrations.push(Migration::new(version, description, checksum));
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "Status gate(s) failed: {}",
+                failed
+                    .iter()
+                    .map(|result| format!("{} ({})", result.gate.as_str(), result.detail))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            exit_code: first.gate.exit_code(),
         }
-
-        Ok(migrations)
+        .into())
     }
 
-    /// メタデータファイルからチェックサムを抽出
-    fn extract_checksum_from_meta(&self, meta_content: &str) -> Result<String> {
-        let metadata: MigrationMetadata = serde_saphyr::from_str(meta_content)
-            .with_context(|| "Failed to parse metadata YAML")?;
-        Ok(metadata.checksum)
+    /// スナップショットにないテーブルをデータベースから検出する
+    ///
+    /// 最後に適用されたマイグレーションのスナップショットと比較し、
+    /// `external_tables` として宣言されたテーブルとロールバック保護で残されたテーブルは除外する。
+    async fn find_unmanaged_tables(
+        &self,
+        context: &CommandContext,
+        pool: &AnyPool,
+        migrations_dir: &Path,
+        applied_migrations: &[MigrationRecord],
+    ) -> Result<Vec<String>> {
+        let snapshot = self.load_applied_snapshot(migrations_dir, applied_migrations)?;
+        let retained: HashSet<String> = retained_tables::load_retained_tables(migrations_dir)?
+            .into_iter()
+            .map(|table| table.name)
+            .collect();
+
+        let introspector = create_introspector_for_config(&context.config);
+        let table_names = introspector
+            .get_table_names(pool)
+            .await
+            .with_context(|| "Failed to read table names from the database")?;
+
+        Ok(table_names
+            .into_iter()
+            .filter(|name| {
+                !snapshot.has_table(name)
+                    && !snapshot.is_external_table(name)
+                    && !retained.contains(name)
+            })
+            .collect())
     }
 
-    /// マイグレーション状態のリストを構築
+    /// 最後に適用されたマイグレーションのスナップショットを読み込む
     ///
-    /// ローカルマイグレーションと適用済みマイグレーションを照合し、
-    /// ローカルに存在しないがDBに記録されているマイグレーションを "Orphaned" として検出する。
-    fn build_migration_status(
+    /// 未適用のマイグレーションのスナップショットはまだDBに反映されていないため使用しない。
+    /// per-migrationスナップショットがない場合はグローバルスナップショットにフォールバックする。
+    fn load_applied_snapshot(
         &self,
-        local_migrations: &[Migration],
+        migrations_dir: &Path,
         applied_migrations: &[MigrationRecord],
-    ) -> Vec<(String, String, String)> {
-        let applied_map: HashMap<&str, &MigrationRecord> = applied_migrations
-            .iter()
-            .map(|m| (m.version.as_str(), m))
-            .collect();
+    ) -> Result<Schema> {
+        if applied_migrations.is_empty() {
+            return Ok(Schema::new("1.0".to_string()));
+        }
 
-        let local_version_set: std::collections::HashSet<&str> = local_migrations
+        let applied: HashSet<&str> = applied_migrations
             .iter()
             .map(|m| m.version.as_str())
             .collect();
-
-        // ローカルマイグレーションの状態
-        let mut status_list: Vec<(String, String, String)> = local_migrations
+        let available = migration_loader::load_available_migrations(migrations_dir)?;
+        let applied_snapshot = available
             .iter()
-            .map(|local| {
-                let status = if let Some(applied) = applied_map.get(local.version.as_str()) {
-                    if applied.checksum == local.checksum {
-                        "Applied".to_string()
-                    } else {
-                        "Applied (checksum mismatch)".to_string()
-                    }
-                } else {
-                    "Pending".to_string()
-                };
+            .rev()
+            .filter(|(version, _, _)| applied.contains(version.as_str()))
+            .map(|(_, _, path)| path.join(SNAPSHOT_FILE))
+            .find(|path| path.exists());
+        let snapshot_path = applied_snapshot.unwrap_or_else(|| migrations_dir.join(SNAPSHOT_FILE));
+        if !snapshot_path.exists() {
+            return Ok(Schema::new("1.0".to_string()));
+        }
 
-                (local.version.clone(), local.description.clone(), status)
-            })
-            .collect();
+        schema_snapshot::read_snapshot(&snapshot_path)
+    }
 
-        // 孤立マイグレーション（DBに記録があるがローカルに存在しない）を追加
-        for record in applied_migrations {
-            if !local_version_set.contains(record.version.as_str()) {
-                status_list.push((
-                    record.version.clone(),
-                    record.description.clone(),
-                    "Orphaned".to_string(),
-                ));
-            }
+    /// ゲートの判定結果をフォーマット
+    fn format_gate_results(&self, gate_results: &[GateResult]) -> String {
+        let mut output = String::from("\n=== Gates ===\n\n");
+        for result in gate_results {
+            let verdict = match result.verdict {
+                GateVerdict::Pass => "✓ PASS",
+                GateVerdict::Fail => "✗ FAIL",
+            };
+            output.push_str(&format!(
+                "{:<10} {:<8} {}\n",
+                result.gate.as_str(),
+                verdict,
+                result.detail
+            ));
         }
+        output
+    }
 
-        // バージョン順にソート
-        status_list.sort_by(|a, b| a.0.cmp(&b.0));
+    /// 適用済みマイグレーションの適用記録の詳細をフォーマット
+    ///
+    /// 記録用カラムの追加前に適用されたマイグレーションの値は "-" と表示する。
+    fn format_audit_details(&self, entries: &[MigrationStatusEntry]) -> String {
+        let mut output = String::from("\n=== Applied Migration Details ===\n\n");
+        output.push_str(&format!(
+            "{:<20} {:<26} {:>12} {}\n",
+            "Version", "Applied At", "Duration", "Applied By"
+        ));
+        output.push_str(&format!("{}\n", "-".repeat(72)));
+
+        let mut applied = 0;
+        for entry in entries {
+            let Some(audit) = entry
+                .audit
+                .as_ref()
+                .filter(|audit| audit.applied_at.is_some())
+            else {
+                continue;
+            };
+            applied += 1;
+            let applied_at = audit
+                .applied_at
+                .map(|at| at.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_default();
+            let duration = audit
+                .execution_time_ms
+                .map(|ms| format!("{} ms", ms))
+                .unwrap_or_else(|| "-".to_string());
+            output.push_str(&format!(
+                "{:<20} {:<26} {:>12} {}\n",
+                entry.version,
+                applied_at,
+                duration,
+                audit.applied_by.as_deref().unwrap_or("-")
+            ));
+        }
+        if applied == 0 {
+            output.push_str("No applied migrations.\n");
+        }
+        output
+    }
 
-        status_list
+    /// マイグレーションの状態の表示名
+    ///
+    /// チェックサムの検証で変更が見つかった場合は "Applied (checksum mismatch)"、
+    /// `.meta.yaml` がなく検証できない場合は "Applied (missing meta)"、
+    /// バージョン順より先に適用された場合は "Applied (out of order)" とする。
+    fn status_label(&self, status: &MigrationStatus) -> &'static str {
+        match (status.state, status.checksum_status) {
+            (MigrationState::Applied, Some(ChecksumStatus::Modified)) => {
+                "Applied (checksum mismatch)"
+            }
+            (MigrationState::Applied, Some(ChecksumStatus::MissingMeta)) => {
+                "Applied (missing meta)"
+            }
+            (MigrationState::Applied, _) if status.out_of_order => "Applied (out of order)",
+            (MigrationState::Applied, _) => "Applied",
+            (MigrationState::Pending, _) => "Pending",
+            (MigrationState::Orphaned, _) => "Orphaned",
+        }
     }
 
     /// マイグレーションが存在しない場合のメッセージ
@@ -318,15 +690,19 @@ impl StatusCommandHandler {
 
         // テーブルヘッダー
         output.push_str(&format!(
-            "{:<20} {:<40} {:<30}\n",
-            "Version", "Description", "Status"
+            "{:<20} {:<40} {:<32} {}\n",
+            "Version", "Description", "Status", "Checksum"
         ));
-        output.push_str(&format!("{}\n", "-".repeat(90)));
+        output.push_str(&format!("{}\n", "-".repeat(104)));
 
         // 各マイグレーションの状態
         for (version, description, status) in status_list {
             let status_display = if status.contains("checksum mismatch") {
                 "⚠️  Applied (checksum mismatch)"
+            } else if status.contains("missing meta") {
+                "⚠️  Applied (missing meta)"
+            } else if status.contains("out of order") {
+                "✓ Applied (out of order)"
             } else if status.contains("Applied") {
                 "✓ Applied"
             } else if *status == "Orphaned" {
@@ -335,14 +711,24 @@ impl StatusCommandHandler {
                 "  Pending"
             };
 
+            let checksum_display = if status.contains("checksum mismatch") {
+                ChecksumStatus::Modified.as_str()
+            } else if status.contains("missing meta") {
+                ChecksumStatus::MissingMeta.as_str()
+            } else if status.contains("Applied") {
+                ChecksumStatus::Ok.as_str()
+            } else {
+                "-"
+            };
+
             output.push_str(&format!(
-                "{:<20} {:<40} {:<30}\n",
-                version, description, status_display
+                "{:<20} {:<40} {:<32} {}\n",
+                version, description, status_display, checksum_display
             ));
         }
 
         // サマリー
-        output.push_str(&format!("\n{}\n", "-".repeat(90)));
+        output.push_str(&format!("\n{}\n", "-".repeat(104)));
         if orphaned_count > 0 {
             output.push_str(&format!(
                 "Total: {} (Applied: {}, Pending: {}, Orphaned: {})\n",
@@ -368,6 +754,23 @@ impl StatusCommandHandler {
             output.push_str("\n⚠️  Warning: Some migrations have mismatched checksums.\n");
             output.push_str("   Migration files may have been modified after being applied.\n");
         }
+        if status_list
+            .iter()
+            .any(|(_, _, s)| s.contains("missing meta"))
+        {
+            output
+                .push_str("\n⚠️  Warning: Some applied migrations have no readable .meta.yaml.\n");
+            output.push_str("   Their checksums cannot be verified.\n");
+        }
+        if status_list
+            .iter()
+            .any(|(_, _, s)| s.contains("out of order"))
+        {
+            output.push_str(
+                "\nNote: Some migrations were applied out of order (with `apply --only`).\n",
+            );
+            output.push_str("   Earlier versions were still pending when they were applied.\n");
+        }
 
         // 孤立マイグレーションの警告
         if orphaned_count > 0 {
@@ -392,102 +795,79 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_checksum_from_meta() {
-        let handler = StatusCommandHandler::new();
-
-        let meta_content = r#"version: "20260121120000"
-description: "create_users"
-dialect: sqlite
-checksum: "test_checksum_123"
-destructive_changes: {}
-"#;
-
-        let checksum = handler.extract_checksum_from_meta(meta_content).unwrap();
-        assert_eq!(checksum, "test_checksum_123");
-    }
-
-    #[test]
-    fn test_build_migration_status() {
-        let handler = StatusCommandHandler::new();
-
-        let local_migrations = vec![
-            Migration::new(
-                "20260121120000".to_string(),
-                "create_users".to_string(),
-                "checksum1".to_string(),
-            ),
-            Migration::new(
-                "20260121120001".to_string(),
-                "create_posts".to_string(),
-                "checksum2".to_string(),
-            ),
-        ];
-
-        let applied_migrations = vec![MigrationRecord::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum1".to_string(),
-        )];
-
-        let status_list = handler.build_migration_status(&local_migrations, &applied_migrations);
-
-        assert_eq!(status_list.len(), 2);
-        assert_eq!(status_list[0].2, "Applied");
-        assert_eq!(status_list[1].2, "Pending");
-    }
-
-    #[test]
-    fn test_build_migration_status_with_checksum_mismatch() {
+    fn test_status_label() {
         let handler = StatusCommandHandler::new();
+        let status = |state, checksum_status| MigrationStatus {
+            version: "20260121120000".to_string(),
+            description: "create_users".to_string(),
+            state,
+            checksum_status,
+            out_of_order: false,
+        };
 
-        let local_migrations = vec![Migration::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum_new".to_string(),
-        )];
-
-        let applied_migrations = vec![MigrationRecord::new(
-            "20260121120000".to_string(),
-            "create_users".to_string(),
-            "checksum_old".to_string(),
-        )];
-
-        let status_list = handler.build_migration_status(&local_migrations, &applied_migrations);
+        assert_eq!(
+            handler.status_label(&status(MigrationState::Applied, Some(ChecksumStatus::Ok))),
+            "Applied"
+        );
+        assert_eq!(
+            handler.status_label(&status(
+                MigrationState::Applied,
+                Some(ChecksumStatus::Modified)
+            )),
+            "Applied (checksum mismatch)"
+        );
+        assert_eq!(
+            handler.status_label(&status(
+                MigrationState::Applied,
+                Some(ChecksumStatus::MissingMeta)
+            )),
+            "Applied (missing meta)"
+        );
+        assert_eq!(
+            handler.status_label(&status(MigrationState::Pending, None)),
+            "Pending"
+        );
+        assert_eq!(
+            handler.status_label(&status(MigrationState::Orphaned, None)),
+            "Orphaned"
+        );
 
-        assert_eq!(status_list.len(), 1);
-        assert_eq!(status_list[0].2, "Applied (checksum mismatch)");
+        let out_of_order = MigrationStatus {
+            out_of_order: true,
+            ..status(MigrationState::Applied, Some(ChecksumStatus::Ok))
+        };
+        assert_eq!(
+            handler.status_label(&out_of_order),
+            "Applied (out of order)"
+        );
+        let modified_out_of_order = MigrationStatus {
+            out_of_order: true,
+            ..status(MigrationState::Applied, Some(ChecksumStatus::Modified))
+        };
+        assert_eq!(
+            handler.status_label(&modified_out_of_order),
+            "Applied (checksum mismatch)"
+        );
     }
 
     #[test]
-    fn test_build_migration_status_with_orphaned() {
+    fn test_format_migration_status_with_out_of_order() {
         let handler = StatusCommandHandler::new();
 
-        let local_migrations = vec![Migration::new(
-            "20260121120001".to_string(),
-            "create_posts".to_string(),
-            "checksum2".to_string(),
-        )];
-
-        let applied_migrations = vec![
-            MigrationRecord::new(
-                "20260121120000".to_string(),
-                "create_users".to_string(),
-                "checksum1".to_string(),
-            ),
-            MigrationRecord::new(
-                "20260121120001".to_string(),
-                "create_posts".to_string(),
-                "checksum2".to_string(),
+        let status_list = vec![
+            ("20260121120000", "rewrite_orders", "Pending"),
+            (
+                "20260121120001",
+                "add_users_index",
+                "Applied (out of order)",
             ),
         ];
 
-        let status_list = handler.build_migration_status(&local_migrations, &applied_migrations);
+        let summary = handler.format_migration_status(&status_list, 1, 1, 0);
 
-        assert_eq!(status_list.len(), 2);
-        assert_eq!(status_list[0].0, "20260121120000");
-        assert_eq!(status_list[0].2, "Orphaned");
-        assert_eq!(status_list[1].0, "20260121120001");
-        assert_eq!(status_list[1].2, "Applied");
+        assert!(summary.contains("✓ Applied (out of order)"));
+        assert!(summary.contains("applied out of order"));
+        assert!(summary.contains("Applied: 1"));
     }
 
     #[test]
@@ -545,18 +925,27 @@ destructive_changes: {}
                     description: "create_users".to_string(),
                     status: MigrationStatusValue::Applied,
                     checksum_mismatch: false,
+                    checksum_status: Some(ChecksumStatus::Ok),
+                    out_of_order: true,
+                    audit: None,
                 },
                 MigrationStatusEntry {
                     version: "20260121120001".to_string(),
                     description: "create_posts".to_string(),
                     status: MigrationStatusValue::Pending,
                     checksum_mismatch: false,
+                    checksum_status: None,
+                    out_of_order: false,
+                    audit: None,
                 },
                 MigrationStatusEntry {
                     version: "20260121120002".to_string(),
                     description: "add_index".to_string(),
                     status: MigrationStatusValue::AppliedChecksumMismatch,
                     checksum_mismatch: true,
+                    checksum_status: Some(ChecksumStatus::Modified),
+                    out_of_order: false,
+                    audit: None,
                 },
             ],
             summary: StatusSummary {
@@ -564,8 +953,11 @@ destructive_changes: {}
                 applied: 1,
                 pending: 1,
                 orphaned: 0,
+                current_version: Some("20260121120002".to_string()),
             },
             warnings: vec!["Some warning".to_string()],
+            gates: BTreeMap::new(),
+            unmanaged_tables: vec![],
             text_message: "should not appear".to_string(),
         };
 
@@ -581,9 +973,16 @@ destructive_changes: {}
             parsed["migrations"][2]["status"],
             "applied_checksum_mismatch"
         );
+        // チェックサムの検証結果（未適用は null）
+        assert_eq!(parsed["migrations"][0]["checksum_status"], "ok");
+        assert!(parsed["migrations"][1]["checksum_status"].is_null());
+        assert_eq!(parsed["migrations"][2]["checksum_status"], "modified");
+        assert_eq!(parsed["migrations"][0]["out_of_order"], true);
+        assert_eq!(parsed["migrations"][1]["out_of_order"], false);
         // サマリー
         assert_eq!(parsed["summary"]["total"], 3);
         assert_eq!(parsed["summary"]["applied"], 1);
+        assert_eq!(parsed["summary"]["current_version"], "20260121120002");
         // 警告
         assert_eq!(parsed["warnings"][0], "Some warning");
     }
@@ -6,16 +6,30 @@
 // - エラーと警告のフォーマットされた表示
 // - 検証結果のサマリー表示
 
-use crate::cli::command_context::CommandContext;
-use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::command_context::{CommandContext, SchemaLoadOptions};
+use crate::cli::commands::git;
+use crate::cli::commands::validate_file;
+use crate::cli::commands::validate_fixes::{self, AppliedFix, SuggestedFix};
+use crate::cli::commands::{render_output_with_timings, CommandOutput, CompletedWithFailure};
+use crate::cli::timings::Timings;
 use crate::cli::OutputFormat;
-use crate::services::schema_io::schema_parser::SchemaParserService;
+use crate::core::config::Config;
+use crate::core::error::{ErrorLocation, ValidationResult};
+use crate::core::schema::Schema;
+use crate::services::schema_io::schema_parser::DuplicateDefinitionsError;
+use crate::services::schema_io::schema_sources::SchemaSources;
+use crate::services::schema_validator::schema_fixes::SchemaFix;
 use crate::services::schema_validator::SchemaValidatorService;
 use anyhow::{anyhow, Context, Result};
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
+/// --apply-fixes で修正案の適用を繰り返す最大回数
+const MAX_FIX_ROUNDS: usize = 5;
+
 /// validateコマンドの出力構造体
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidateOutput {
@@ -23,12 +37,21 @@ pub struct ValidateOutput {
     pub is_valid: bool,
     /// 読み込んだスキーマファイル
     pub schema_files: Vec<String>,
+    /// 報告対象を絞り込んだ変更ファイル（`--changed-since` / `--files` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_files: Option<Vec<String>>,
     /// エラー一覧
     pub errors: Vec<ValidationIssue>,
     /// 警告一覧
     pub warnings: Vec<ValidationIssue>,
     /// 統計情報
     pub statistics: ValidationStatistics,
+    /// 報告したエラーの修正案（`--suggest-fixes` / `--apply-fixes` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixes: Option<Vec<SuggestedFix>>,
+    /// 適用を試みた修正案（`--apply-fixes` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_fixes: Option<Vec<AppliedFix>>,
     /// テキスト出力メッセージ
     #[serde(skip)]
     pub text_message: String,
@@ -39,6 +62,8 @@ pub struct ValidateOutput {
 pub struct ValidationIssue {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub table: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<String>,
@@ -84,18 +109,38 @@ pub struct ValidateCommand {
     pub config_path: Option<PathBuf>,
     /// スキーマディレクトリのパス（指定されない場合は設定ファイルから取得）
     pub schema_dir: Option<PathBuf>,
+    /// 複数ファイルでの重複定義を許可し、後のファイルの定義を採用する
+    pub allow_duplicate_override: bool,
+    /// このgit参照以降に変更されたスキーマファイルに関係する問題のみ報告する
+    pub changed_since: Option<String>,
+    /// 指定したスキーマファイルに関係する問題のみ報告する
+    pub files: Vec<PathBuf>,
+    /// このスキーマファイルだけを単独で検証する（エディタ連携向け）
+    pub file: Option<PathBuf>,
+    /// 報告したエラーの修正案をunified diffとして表示する
+    pub suggest_fixes: bool,
+    /// 安全な修正案（行の追加のみ）を検証してスキーマファイルに書き込む
+    pub apply_fixes: bool,
     /// 出力フォーマット
     pub format: OutputFormat,
 }
 
 /// validateコマンドハンドラー
 #[derive(Debug, Default)]
-pub struct ValidateCommandHandler {}
+pub struct ValidateCommandHandler {
+    timings: Timings,
+}
 
 impl ValidateCommandHandler {
     /// 新しいValidateCommandHandlerを作成
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// フェーズ別の実行時間の計測先を設定
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
     }
 
     /// validateコマンドを実行
@@ -108,36 +153,120 @@ impl ValidateCommandHandler {
     ///
     /// 成功時は検証結果のサマリー、失敗時はエラーメッセージ
     pub fn execute(&self, command: &ValidateCommand) -> Result<String> {
+        // 単一ファイル検証（--file 指定時）
+        if let Some(file) = &command.file {
+            return validate_file::validate_single_file(command, file, &self.timings);
+        }
+
         // 設定ファイルを読み込む
         let context = CommandContext::load_with_config(
             command.project_path.clone(),
             command.config_path.clone(),
-        )?;
-        let config = &context.config;
-
-        // スキーマディレクトリのパスを解決
-        let schema_dir = context.resolve_schema_dir(command.schema_dir.as_ref())?;
-        debug!(schema_dir = %schema_dir.display(), "Resolved schema directory");
-
-        // スキーマ定義を読み込む
-        let parser = SchemaParserService::new();
-        let (schema, schema_files) = parser
-            .parse_schema_directory_with_files(&schema_dir)
-            .with_context(|| "Failed to parse schema")?;
-        debug!(tables = schema.table_count(), "Schema parsed successfully");
-
-        // スキーマを検証
-        let validator = SchemaValidatorService::new();
-        let validation_result = validator.validate_with_dialect(&schema, config.dialect);
+        )?
+        .with_timings(self.timings.clone());
+
+        let mut validated = self.load_and_validate(command, &context, HashMap::new())?;
+
+        // 変更ファイルの解決（--changed-since / --files 指定時）
+        let changed_files = self
+            .resolve_changed_schema_files(command, &validated.schema_files)?
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|file| relative_path(&command.project_path, file))
+                    .collect::<HashSet<_>>()
+            });
+
+        // 安全な修正案を検証して書き込み、書き込んだ場合はスキーマを読み込み直す
+        // 修正案が他の修正案を前提とする場合（主キーを追加してから外部キーの参照先を補うなど）
+        // のため、書き込める修正案がなくなるまで繰り返す
+        let mut fix_results = None;
+        if command.apply_fixes {
+            let mut results: Vec<(SchemaFix, Option<String>)> = Vec::new();
+            for _ in 0..MAX_FIX_ROUNDS {
+                let contents = validate_fixes::read_schema_files(&validated.schema_files)?;
+                let reported = self.reported_result(command, &validated, changed_files.as_ref());
+                let fixes: Vec<SchemaFix> = validate_fixes::fixes_for_reported_errors(
+                    &validated.schema,
+                    &validated.sources,
+                    &contents,
+                    &reported,
+                )
+                .into_iter()
+                .filter(|fix| !results.iter().any(|(tried, _)| tried.error == fix.error))
+                .collect();
+                let round = validate_fixes::apply_safe_fixes(
+                    &fixes,
+                    &contents,
+                    &validated.result,
+                    |patched| {
+                        self.load_and_validate(command, &context, patched)
+                            .map(|validated| validated.result)
+                    },
+                )?;
+                let applied = round.iter().any(|(_, reason)| reason.is_none());
+                results.extend(round);
+                if !applied {
+                    break;
+                }
+                validated = self.load_and_validate(command, &context, HashMap::new())?;
+            }
+            fix_results = Some(results);
+        }
+
+        let validation_result = self.reported_result(command, &validated, changed_files.as_ref());
+        let ValidatedSchema {
+            schema,
+            schema_files,
+            sources,
+            ..
+        } = validated;
+
+        // 報告したエラーの修正案
+        let suggestions = if command.suggest_fixes || command.apply_fixes {
+            let contents = validate_fixes::read_schema_files(&schema_files)?;
+            let fixes = validate_fixes::fixes_for_reported_errors(
+                &schema,
+                &sources,
+                &contents,
+                &validation_result,
+            );
+            Some((fixes, contents))
+        } else {
+            None
+        };
         debug!(
             errors = validation_result.errors.len(),
             warnings = validation_result.warnings.len(),
             "Validation completed"
         );
 
+        let changed_file_names: Option<Vec<String>> = changed_files.map(|files| {
+            let mut names: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+            names.sort();
+            names
+        });
+
         // 検証結果を表示用にフォーマット
-        let text_message =
-            self.format_validation_result(&validation_result, &schema, &schema_files);
+        let mut text_message = self.format_validation_result(
+            &validation_result,
+            &schema,
+            &schema_files,
+            changed_file_names.as_deref(),
+        );
+        if let Some(results) = &fix_results {
+            text_message.insert_str(
+                0,
+                &validate_fixes::format_applied_fixes(results, &command.project_path),
+            );
+        }
+        if let Some((fixes, contents)) = &suggestions {
+            text_message.push_str(&validate_fixes::format_suggested_fixes(
+                fixes,
+                contents,
+                &command.project_path,
+            ));
+        }
         let stats = self.calculate_statistics(&schema);
 
         // 構造化出力データを構築
@@ -148,6 +277,9 @@ impl ValidateCommandHandler {
                 let location = self.get_error_location(error);
                 ValidationIssue {
                     message: format!("{}", error),
+                    file: location
+                        .and_then(|l| l.file())
+                        .map(|f| f.display().to_string()),
                     table: location.and_then(|l| l.table.clone()),
                     column: location.and_then(|l| l.column.clone()),
                     suggestion: self.get_error_suggestion(error).map(|s| s.to_string()),
@@ -162,6 +294,10 @@ impl ValidateCommandHandler {
                 let loc = &warning.location;
                 ValidationIssue {
                     message: warning.message.clone(),
+                    file: loc
+                        .as_ref()
+                        .and_then(|l| l.file())
+                        .map(|f| f.display().to_string()),
                     table: loc.as_ref().and_then(|l| l.table.clone()),
                     column: loc.as_ref().and_then(|l| l.column.clone()),
                     suggestion: None,
@@ -181,6 +317,7 @@ impl ValidateCommandHandler {
         let output = ValidateOutput {
             is_valid: validation_result.is_valid(),
             schema_files: file_names,
+            changed_files: changed_file_names,
             errors,
             warnings,
             statistics: ValidationStatistics {
@@ -190,22 +327,33 @@ impl ValidateCommandHandler {
                 constraints: stats.3,
                 views: stats.4,
             },
+            fixes: suggestions.as_ref().map(|(fixes, contents)| {
+                validate_fixes::suggested_fixes(fixes, contents, &command.project_path)
+            }),
+            applied_fixes: fix_results
+                .as_ref()
+                .map(|results| validate_fixes::applied_fixes(results, &command.project_path)),
             text_message: text_message.clone(),
         };
 
         if validation_result.is_valid() {
-            render_output(&output, &command.format)
+            render_output_with_timings(&output, &command.format, &self.timings)
         } else {
             match &command.format {
                 OutputFormat::Json => {
                     // JSON モードでは構造化出力を stdout に出力した上で Err を返す
                     // （exit code 1 で CI/CD パイプラインが失敗を検出できるようにする）
-                    let json_output = render_output(&output, &command.format)?;
-                    println!("{}", json_output);
-                    Err(anyhow!(
-                        "Validation failed with {} error(s)",
-                        validation_result.errors.len()
-                    ))
+                    let json_output =
+                        render_output_with_timings(&output, &command.format, &self.timings)?;
+                    Err(CompletedWithFailure {
+                        output: json_output,
+                        message: format!(
+                            "Validation failed with {} error(s)",
+                            validation_result.errors.len()
+                        ),
+                        exit_code: 1,
+                    }
+                    .into())
                 }
                 OutputFormat::Text => {
                     eprintln!("{}", text_message);
@@ -218,12 +366,120 @@ impl ValidateCommandHandler {
         }
     }
 
+    /// スキーマ定義を読み込んで検証する
+    ///
+    /// 複数ファイルでの重複定義は、定義元ファイルを示す検証エラーとして結果に含めます。
+    /// `file_contents` に指定したファイルはディスクの代わりにその内容を読み込みます（修正案の検証用）。
+    fn load_and_validate(
+        &self,
+        command: &ValidateCommand,
+        context: &CommandContext,
+        file_contents: HashMap<PathBuf, String>,
+    ) -> Result<ValidatedSchema> {
+        // スキーマ定義を読み込む（定義元ファイルの情報も必要）
+        let loaded = context.load_schema_with_sources(&SchemaLoadOptions {
+            schema_dir: command.schema_dir.clone(),
+            allow_duplicate_override: command.allow_duplicate_override,
+            file_contents,
+        });
+        let (schema, schema_files, sources, duplicate_errors) = match loaded {
+            Ok((schema, schema_files, sources)) => (schema, schema_files, sources, Vec::new()),
+            Err(e) => match e.downcast_ref::<DuplicateDefinitionsError>() {
+                Some(duplicates) => (
+                    Schema::new("1.0".to_string()),
+                    duplicates.schema_files.clone(),
+                    SchemaSources::new(),
+                    duplicates.errors.clone(),
+                ),
+                None => return Err(e),
+            },
+        };
+
+        let mut result = self.timings.measure("validate", || {
+            validator(&context.config).validate_with_dialect(&schema, context.config.dialect)
+        });
+        for mut error in duplicate_errors {
+            if let Some(location) = error.location_mut() {
+                location.file = location
+                    .file
+                    .as_deref()
+                    .map(|file| relative_path(&command.project_path, file));
+            }
+            result.add_error(error);
+        }
+
+        Ok(ValidatedSchema {
+            schema,
+            schema_files,
+            sources,
+            result,
+        })
+    }
+
+    /// 報告する検証結果
+    ///
+    /// 位置情報に定義元ファイル（プロジェクトからの相対パス）と行を設定し、
+    /// `--changed-since` / `--files` 指定時は変更ファイルに関係する問題に絞り込みます。
+    fn reported_result(
+        &self,
+        command: &ValidateCommand,
+        validated: &ValidatedSchema,
+        changed_files: Option<&HashSet<PathBuf>>,
+    ) -> ValidationResult {
+        let mut result = validated.result.clone();
+        let sources = validated.sources.relative_to(&command.project_path);
+        sources.attach_files(&mut result);
+        if let Some(changed_files) = changed_files {
+            restrict_to_changed_files(&mut result, &validated.schema, &sources, changed_files);
+        }
+        result
+    }
+
+    /// 報告対象とする変更済みスキーマファイルを解決
+    ///
+    /// `--changed-since` ではgitの差分から、`--files` では指定されたパスから
+    /// 変更ファイルを求め、読み込んだスキーマファイルのうち該当するものを返す。
+    /// どちらも指定されていない場合はNone（全件を報告）。
+    fn resolve_changed_schema_files(
+        &self,
+        command: &ValidateCommand,
+        schema_files: &[PathBuf],
+    ) -> Result<Option<Vec<PathBuf>>> {
+        let changed: Vec<PathBuf> = if let Some(git_ref) = &command.changed_since {
+            git::changed_files_since(&command.project_path, git_ref)
+                .with_context(|| format!("Failed to list files changed since '{}'", git_ref))?
+        } else if !command.files.is_empty() {
+            command
+                .files
+                .iter()
+                .map(|file| command.project_path.join(file))
+                .collect()
+        } else {
+            return Ok(None);
+        };
+
+        let changed: HashSet<PathBuf> = changed.iter().map(|f| canonical_path(f)).collect();
+        let matched: Vec<PathBuf> = schema_files
+            .iter()
+            .filter(|file| changed.contains(&canonical_path(file)))
+            .cloned()
+            .collect();
+        debug!(
+            changed = changed.len(),
+            schema_files = matched.len(),
+            "Resolved changed schema files"
+        );
+
+        Ok(Some(matched))
+    }
+
     /// 検証結果をフォーマット
     fn format_validation_result(
         &self,
         result: &crate::core::error::ValidationResult,
         schema: &crate::core::schema::Schema,
         schema_files: &[std::path::PathBuf],
+        changed_files: Option<&[String]>,
     ) -> String {
         let mut output = String::new();
 
@@ -242,6 +498,18 @@ impl ValidateCommandHandler {
             output.push('\n');
         }
 
+        // 報告対象の絞り込み
+        if let Some(changed_files) = changed_files {
+            output.push_str(&format!(
+                "Reporting only issues related to {} changed schema file(s):\n",
+                changed_files.len()
+            ));
+            for file in changed_files {
+                output.push_str(&format!("  - {}\n", file));
+            }
+            output.push('\n');
+        }
+
         // エラーの表示
         if !result.errors.is_empty() {
             output.push_str(&format!("❌ {} error(s) found:\n\n", result.errors.len()));
@@ -326,9 +594,8 @@ impl ValidateCommandHandler {
             | crate::core::error::ValidationError::Reference { location, .. }
             | crate::core::error::ValidationError::Constraint { location, .. }
             | crate::core::error::ValidationError::TypeConversion { location, .. }
-            | crate::core::error::ValidationError::DialectConstraint { location, .. } => {
-                location.as_ref()
-            }
+            | crate::core::error::ValidationError::DialectConstraint { location, .. }
+            | crate::core::error::ValidationError::Duplicate { location, .. } => location.as_ref(),
         }
     }
 
@@ -341,7 +608,8 @@ impl ValidateCommandHandler {
             crate::core::error::ValidationError::Syntax { suggestion, .. }
             | crate::core::error::ValidationError::Reference { suggestion, .. }
             | crate::core::error::ValidationError::Constraint { suggestion, .. }
-            | crate::core::error::ValidationError::TypeConversion { suggestion, .. } => {
+            | crate::core::error::ValidationError::TypeConversion { suggestion, .. }
+            | crate::core::error::ValidationError::Duplicate { suggestion, .. } => {
                 suggestion.as_deref()
             }
             crate::core::error::ValidationError::DialectConstraint { .. } => None,
@@ -411,6 +679,66 @@ impl ValidateCommandHandler {
     }
 }
 
+/// 読み込んで検証したスキーマ
+struct ValidatedSchema {
+    schema: Schema,
+    schema_files: Vec<PathBuf>,
+    /// 各要素の定義元（読み込んだときのパス）
+    sources: SchemaSources,
+    /// 検証結果（定義元ファイルは未設定）
+    result: ValidationResult,
+}
+
+/// 設定に従ったスキーマバリデーター
+fn validator(config: &Config) -> SchemaValidatorService {
+    SchemaValidatorService::new()
+        .with_mysql_minimum_version(config.mysql.parsed_minimum_version())
+        .with_required_metadata(config.required_metadata.clone())
+        .with_naming(config.naming.clone())
+        .with_identifiers(config.identifiers.clone())
+}
+
+/// 変更ファイルに関係する問題のみを残す
+///
+/// 位置情報のファイルが変更ファイルに含まれるもの、または変更ファイルで定義された
+/// 要素を参照する要素（外部キーの参照元など）の問題を残す。
+/// 位置情報を持たない問題は対象を特定できないため常に残す。
+fn restrict_to_changed_files(
+    result: &mut ValidationResult,
+    schema: &Schema,
+    sources: &SchemaSources,
+    changed_files: &HashSet<PathBuf>,
+) {
+    let affected = sources.affected_by(schema, changed_files);
+    let is_relevant = |location: Option<&ErrorLocation>| match location {
+        None => true,
+        Some(location) => {
+            location.file().is_some_and(|f| changed_files.contains(f))
+                || location
+                    .table
+                    .as_ref()
+                    .is_some_and(|t| affected.contains(t))
+        }
+    };
+
+    result.errors.retain(|error| is_relevant(error.location()));
+    result
+        .warnings
+        .retain(|warning| is_relevant(warning.location.as_ref()));
+}
+
+/// 比較用に正規化したパス（存在しない場合はそのまま）
+fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// プロジェクトルートからの相対パス（配下にない場合はそのまま）
+fn relative_path(project_path: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(project_path)
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -496,14 +824,17 @@ mod tests {
         let output = ValidateOutput {
             is_valid: false,
             schema_files: vec!["users.yaml".to_string()],
+            changed_files: None,
             errors: vec![ValidationIssue {
                 message: "No primary key".to_string(),
+                file: None,
                 table: Some("users".to_string()),
                 column: None,
                 suggestion: Some("Add a primary key".to_string()),
             }],
             warnings: vec![ValidationIssue {
                 message: "Wide column".to_string(),
+                file: None,
                 table: Some("users".to_string()),
                 column: Some("bio".to_string()),
                 suggestion: None,
@@ -515,6 +846,8 @@ mod tests {
                 constraints: 0,
                 views: 0,
             },
+            fixes: None,
+            applied_fixes: None,
             text_message: "should not appear in JSON".to_string(),
         };
 
@@ -532,5 +865,8 @@ mod tests {
         assert_eq!(parsed["warnings"][0]["column"], "bio");
         assert!(parsed["warnings"][0].get("suggestion").is_none()); // None はスキップ
         assert_eq!(parsed["statistics"]["tables"], 1);
+        // 修正案は指定時のみ出力
+        assert!(parsed.get("fixes").is_none());
+        assert!(parsed.get("applied_fixes").is_none());
     }
 }
@@ -0,0 +1,437 @@
+// validate --file（単一ファイル検証）
+//
+// エディタ連携向けに1つのスキーマファイルだけを読み込み、ファイル内で完結する検証を実行します。
+// - スナップショット・DBにはアクセスしない
+// - 他ファイルへの参照（外部キーの参照先など）は検証を保留（deferred）として報告
+// - 診断結果に行・桁の位置情報を付与
+
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::validate::ValidateCommand;
+use crate::cli::commands::{render_output_with_timings, CommandOutput};
+use crate::cli::timings::Timings;
+use crate::cli::OutputFormat;
+use crate::core::error::{ErrorLocation, ValidationResult, WarningKind};
+use crate::services::schema_io::schema_parser::SchemaParserService;
+use crate::services::schema_validator::{DeferredCheck, SchemaValidatorService, ValidationScope};
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// 単一ファイル検証の出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct FileValidateOutput {
+    /// 検証したファイル
+    pub file: String,
+    /// 検証が成功したかどうか
+    pub is_valid: bool,
+    /// 診断結果（エラー・警告・情報）
+    pub diagnostics: Vec<FileDiagnostic>,
+    /// 単一ファイルでは判定できず保留した検証
+    pub deferred: Vec<DeferredDiagnostic>,
+    /// テキスト出力メッセージ
+    #[serde(skip)]
+    pub text_message: String,
+}
+
+/// 診断結果
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiagnostic {
+    /// 重要度（error / warning / info）
+    pub severity: &'static str,
+    pub message: String,
+    /// 行番号（1始まり）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 桁位置（1始まり）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// 保留した検証
+#[derive(Debug, Clone, Serialize)]
+pub struct DeferredDiagnostic {
+    /// 保留したルール
+    pub rule: &'static str,
+    /// 参照先の名前
+    pub target: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+}
+
+impl CommandOutput for FileValidateOutput {
+    fn to_text(&self) -> String {
+        self.text_message.clone()
+    }
+}
+
+/// 単一のスキーマファイルを検証
+///
+/// # Arguments
+///
+/// * `command` - validateコマンドのパラメータ
+/// * `file` - 検証するファイル（プロジェクトルートからの相対パスも可）
+/// * `timings` - フェーズ別の実行時間の計測先
+pub fn validate_single_file(
+    command: &ValidateCommand,
+    file: &Path,
+    timings: &Timings,
+) -> Result<String> {
+    let context = CommandContext::load_with_config(
+        command.project_path.clone(),
+        command.config_path.clone(),
+    )?
+    .with_timings(timings.clone());
+    let config = &context.config;
+
+    let path = command.project_path.join(file);
+    let display_name = file.display().to_string();
+    let content = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("Failed to read schema file {:?}: {}", path, e))?;
+    let locator = YamlLocator::new(&content);
+
+    let parsed = timings.measure("parse", || {
+        SchemaParserService::new().parse_schema_file(&path)
+    });
+    let (diagnostics, deferred) = match parsed {
+        Ok(schema) => {
+            let validator = SchemaValidatorService::new()
+                .with_mysql_minimum_version(config.mysql.parsed_minimum_version())
+                .with_required_metadata(config.required_metadata.clone())
+                .with_naming(config.naming.clone())
+                .with_identifiers(config.identifiers.clone());
+            let scoped = timings.measure("validate", || {
+                validator.validate_scoped(
+                    &schema,
+                    Some(config.dialect),
+                    ValidationScope::IsolatedFile,
+                )
+            });
+            (
+                to_diagnostics(&scoped.result, &locator),
+                scoped
+                    .deferred
+                    .iter()
+                    .map(|check| to_deferred_diagnostic(check, &locator))
+                    .collect(),
+            )
+        }
+        Err(e) => (vec![parse_error_diagnostic(&e)], Vec::new()),
+    };
+
+    let is_valid = !diagnostics.iter().any(|d| d.severity == "error");
+    let text_message = format_text(&display_name, &diagnostics, &deferred);
+    let error_count = diagnostics.iter().filter(|d| d.severity == "error").count();
+    let output = FileValidateOutput {
+        file: display_name,
+        is_valid,
+        diagnostics,
+        deferred,
+        text_message,
+    };
+
+    if is_valid {
+        return render_output_with_timings(&output, &command.format, timings);
+    }
+
+    match &command.format {
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                render_output_with_timings(&output, &command.format, timings)?
+            );
+        }
+        OutputFormat::Text => eprintln!("{}", output.text_message),
+    }
+    Err(anyhow!("Validation failed with {} error(s)", error_count))
+}
+
+/// 検証結果を診断結果に変換
+fn to_diagnostics(result: &ValidationResult, locator: &YamlLocator) -> Vec<FileDiagnostic> {
+    let errors = result.errors.iter().map(|error| {
+        let location = error.location();
+        let (line, character) = location.and_then(|l| locator.locate(l)).unzip();
+        FileDiagnostic {
+            severity: "error",
+            message: error.to_string(),
+            line,
+            character,
+            table: location.and_then(|l| l.table.clone()),
+            column: location.and_then(|l| l.column.clone()),
+            suggestion: error.suggestion().map(|s| s.to_string()),
+        }
+    });
+
+    let warnings = result.warnings.iter().map(|warning| {
+        let location = warning.location.as_ref();
+        let (line, character) = location.and_then(|l| locator.locate(l)).unzip();
+        FileDiagnostic {
+            severity: if warning.kind == WarningKind::Info {
+                "info"
+            } else {
+                "warning"
+            },
+            message: warning.message.clone(),
+            line,
+            character,
+            table: location.and_then(|l| l.table.clone()),
+            column: location.and_then(|l| l.column.clone()),
+            suggestion: None,
+        }
+    });
+
+    errors.chain(warnings).collect()
+}
+
+/// 保留した検証を出力用に変換
+fn to_deferred_diagnostic(check: &DeferredCheck, locator: &YamlLocator) -> DeferredDiagnostic {
+    let (line, character) = locator.locate(&check.location).unzip();
+    DeferredDiagnostic {
+        rule: check.rule.as_str(),
+        target: check.target.clone(),
+        message: check.message.clone(),
+        line,
+        character,
+        table: check.location.table.clone(),
+        column: check.location.column.clone(),
+    }
+}
+
+/// YAMLの読み込みエラーを診断結果に変換
+///
+/// エラーメッセージに含まれる `line N column M` から位置を取得します。
+fn parse_error_diagnostic(error: &anyhow::Error) -> FileDiagnostic {
+    let message = format!("{:#}", error);
+    let position = Regex::new(r"line (\d+),? column (\d+)")
+        .ok()
+        .and_then(|re| re.captures(&message))
+        .and_then(|caps| Some((caps[1].parse().ok()?, caps[2].parse().ok()?)));
+    let (line, character) = position.unzip();
+
+    FileDiagnostic {
+        severity: "error",
+        message: message.lines().next().unwrap_or_default().to_string(),
+        line,
+        character,
+        table: None,
+        column: None,
+        suggestion: None,
+    }
+}
+
+/// テキスト出力（`<file>:<line>:<character>: <severity>: <message>` 形式）
+fn format_text(
+    file: &str,
+    diagnostics: &[FileDiagnostic],
+    deferred: &[DeferredDiagnostic],
+) -> String {
+    let position = |line: Option<usize>, character: Option<usize>| match (line, character) {
+        (Some(line), Some(character)) => format!("{}:{}:{}", file, line, character),
+        (Some(line), None) => format!("{}:{}", file, line),
+        _ => file.to_string(),
+    };
+
+    let mut lines: Vec<String> = diagnostics
+        .iter()
+        .map(|d| {
+            format!(
+                "{}: {}: {}",
+                position(d.line, d.character),
+                d.severity,
+                d.message
+            )
+        })
+        .collect();
+    lines.extend(deferred.iter().map(|d| {
+        format!(
+            "{}: deferred: {} ({})",
+            position(d.line, d.character),
+            d.message,
+            d.rule
+        )
+    }));
+
+    if lines.is_empty() {
+        format!("{}: no issues found", file)
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// スキーマYAML内の要素の位置を探す
+///
+/// YAMLパーサーは位置情報を保持しないため、`tables:` / `views:` / `enums:` セクション内の
+/// キー行と `- name: <column>` 行をテキストから探します。
+struct YamlLocator<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl<'a> YamlLocator<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().collect(),
+        }
+    }
+
+    /// 位置情報に対応する (行, 桁)（1始まり）を返す
+    fn locate(&self, location: &ErrorLocation) -> Option<(usize, usize)> {
+        let (section, name) = if let Some(view) = location.view_name() {
+            ("views", view)
+        } else if let Some(enum_name) = location.enum_name() {
+            ("enums", enum_name)
+        } else {
+            ("tables", location.table_name()?)
+        };
+
+        let (key_index, key_indent) = self.find_key(section, name)?;
+        if let Some(column) = &location.column {
+            if let Some(found) = self.find_column(key_index, key_indent, column) {
+                return Some(found);
+            }
+        }
+        Some((key_index + 1, key_indent + 1))
+    }
+
+    /// セクション直下のキー行を探す
+    fn find_key(&self, section: &str, name: &str) -> Option<(usize, usize)> {
+        let section_header = format!("{}:", section);
+        let start = self
+            .lines
+            .iter()
+            .position(|line| line.trim_end() == section_header)?;
+
+        let mut key_indent = None;
+        for (index, line) in self.lines.iter().enumerate().skip(start + 1) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if indent == 0 {
+                break;
+            }
+            // 最初に現れたキーのインデントをセクション直下のキーとみなす
+            let key_indent = *key_indent.get_or_insert(indent);
+            if indent == key_indent && unquote(trimmed.trim_end().strip_suffix(':')?) == name {
+                return Some((index, indent));
+            }
+        }
+        None
+    }
+
+    /// キー配下の `- name: <column>` 行を探す
+    fn find_column(
+        &self,
+        key_index: usize,
+        key_indent: usize,
+        column: &str,
+    ) -> Option<(usize, usize)> {
+        for (index, line) in self.lines.iter().enumerate().skip(key_index + 1) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if indent <= key_indent {
+                break;
+            }
+            let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+            if let Some(value) = item.strip_prefix("name:") {
+                if unquote(value.trim()) == column {
+                    let character = line.len() - item.len() + 1;
+                    return Some((index + 1, character));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// YAMLのクォートを外す
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "\
+version: \"1.0\"
+# comment
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: \"email\"
+        type:
+          kind: TEXT
+  posts:
+    columns:
+      - name: id
+views:
+  active_users:
+    definition: SELECT 1
+";
+
+    #[test]
+    fn test_locate_table_column_and_view() {
+        let locator = YamlLocator::new(YAML);
+
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_table("users".to_string())),
+            Some((4, 3))
+        );
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_table_and_column("users", "email")),
+            Some((9, 9))
+        );
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_table_and_column("posts", "id")),
+            Some((14, 9))
+        );
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_view("active_users")),
+            Some((16, 3))
+        );
+        // 見つからないカラムはテーブルの位置に落とす
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_table_and_column("posts", "missing")),
+            Some((12, 3))
+        );
+        assert_eq!(
+            locator.locate(&ErrorLocation::with_table("missing".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_error_diagnostic_extracts_position() {
+        let error = anyhow!("Failed to parse YAML at a.yaml:6: error: line 6 column 15: unclosed bracket\n --> <input>:6:15");
+        let diagnostic = parse_error_diagnostic(&error);
+
+        assert_eq!(diagnostic.line, Some(6));
+        assert_eq!(diagnostic.character, Some(15));
+        assert!(!diagnostic.message.contains("-->"));
+    }
+}
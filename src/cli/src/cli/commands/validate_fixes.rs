@@ -0,0 +1,414 @@
+// 検証エラーの修正案（validate --suggest-fixes / --apply-fixes）
+//
+// 報告した検証エラーの修正案を、スキーマファイルへのunified diffとして表示します。
+// --apply-fixes では安全な修正案（行の追加・並べ替えのみ）だけをファイルに書き込みます。
+// 書き込む前に修正案を適用した内容でスキーマを読み込み直して検証し、
+// 対象のエラーが解消して新しいエラー・警告が増えないことを確認します。
+
+use crate::core::error::ValidationResult;
+use crate::core::schema::Schema;
+use crate::services::schema_io::schema_sources::SchemaSources;
+use crate::services::schema_validator::schema_fixes::{self, FixContext, SchemaFix};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 修正案（JSON出力用）
+#[derive(Debug, Clone, Serialize)]
+pub struct SuggestedFix {
+    /// 書き換えるスキーマファイル（プロジェクトからの相対パス）
+    pub file: String,
+    /// 修正内容
+    pub description: String,
+    /// 解消する検証エラー
+    pub error: String,
+    /// --apply-fixes で適用される安全な修正案か
+    pub safe: bool,
+    /// この修正案だけのunified diff
+    pub patch: String,
+}
+
+/// --apply-fixes で適用を試みた修正案（JSON出力用）
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedFix {
+    /// 書き換えたスキーマファイル（プロジェクトからの相対パス）
+    pub file: String,
+    /// 修正内容
+    pub description: String,
+    /// ファイルに書き込んだか
+    pub applied: bool,
+    /// 書き込まなかった理由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// スキーマファイルを読み込む
+pub(crate) fn read_schema_files(schema_files: &[PathBuf]) -> Result<HashMap<PathBuf, String>> {
+    schema_files
+        .iter()
+        .map(|file| {
+            fs::read_to_string(file)
+                .map(|content| (file.clone(), content))
+                .with_context(|| format!("Failed to read schema file: {:?}", file))
+        })
+        .collect()
+}
+
+/// 報告した検証エラーの修正案
+///
+/// `reported` に含まれるエラー（`--changed-since` などで絞り込んだ後のもの）の修正案だけを返します。
+pub(crate) fn fixes_for_reported_errors(
+    schema: &Schema,
+    sources: &SchemaSources,
+    contents: &HashMap<PathBuf, String>,
+    reported: &ValidationResult,
+) -> Vec<SchemaFix> {
+    let messages: Vec<String> = reported.errors.iter().map(|e| e.to_string()).collect();
+    schema_fixes::suggest_fixes(&FixContext::new(schema, sources, contents))
+        .into_iter()
+        .filter(|fix| messages.iter().any(|message| message.contains(&fix.error)))
+        .collect()
+}
+
+/// 安全な修正案を検証してファイルに書き込む
+///
+/// 修正案ごとに、適用した内容で `revalidate` を実行して確認します。
+/// - 対象のエラーが報告されなくなること
+/// - 適用前になかったエラー・警告が報告されないこと
+///
+/// 確認できた修正案をまとめて適用した内容でも同じ確認を行い、問題がなければ書き込みます。
+pub(crate) fn apply_safe_fixes(
+    fixes: &[SchemaFix],
+    contents: &HashMap<PathBuf, String>,
+    baseline: &ValidationResult,
+    revalidate: impl Fn(HashMap<PathBuf, String>) -> Result<ValidationResult>,
+) -> Result<Vec<(SchemaFix, Option<String>)>> {
+    let mut results: Vec<(SchemaFix, Option<String>)> = Vec::new();
+    for fix in fixes.iter().filter(|fix| fix.safe) {
+        let patched = patch_contents(contents, &[fix]);
+        let reason = match revalidate(patched) {
+            Ok(after) => verification_failure(baseline, &after, &[fix]),
+            Err(e) => Some(format!("the schema no longer loads: {:#}", e)),
+        };
+        results.push((fix.clone(), reason));
+    }
+
+    let accepted: Vec<&SchemaFix> = results
+        .iter()
+        .filter(|(_, reason)| reason.is_none())
+        .map(|(fix, _)| fix)
+        .collect();
+    if accepted.is_empty() {
+        return Ok(results);
+    }
+
+    let patched = patch_contents(contents, &accepted);
+    let combined = match revalidate(patched.clone()) {
+        Ok(after) => verification_failure(baseline, &after, &accepted),
+        Err(e) => Some(format!("the schema no longer loads: {:#}", e)),
+    };
+    if let Some(reason) = combined {
+        let reason = format!("the fixes do not work together: {}", reason);
+        for (_, result) in results.iter_mut().filter(|(_, r)| r.is_none()) {
+            *result = Some(reason.clone());
+        }
+        return Ok(results);
+    }
+
+    for (file, content) in patched {
+        if contents.get(&file) != Some(&content) {
+            fs::write(&file, content)
+                .with_context(|| format!("Failed to write schema file: {:?}", file))?;
+        }
+    }
+    Ok(results)
+}
+
+/// 修正案を適用したファイルの内容（ファイル -> 内容）
+fn patch_contents(
+    contents: &HashMap<PathBuf, String>,
+    fixes: &[&SchemaFix],
+) -> HashMap<PathBuf, String> {
+    let mut patched = contents.clone();
+    for (file, fixes) in group_by_file(fixes) {
+        if let Some(content) = contents.get(file) {
+            patched.insert(
+                file.to_path_buf(),
+                schema_fixes::apply_fixes(content, &fixes),
+            );
+        }
+    }
+    patched
+}
+
+/// 修正案を適用した検証結果に問題があれば、その理由
+fn verification_failure(
+    baseline: &ValidationResult,
+    after: &ValidationResult,
+    fixes: &[&SchemaFix],
+) -> Option<String> {
+    let errors: Vec<String> = after.errors.iter().map(|e| e.to_string()).collect();
+    if let Some(fix) = fixes
+        .iter()
+        .find(|fix| errors.iter().any(|error| error.contains(&fix.error)))
+    {
+        return Some(format!("'{}' is still reported", fix.error));
+    }
+
+    let before: HashSet<String> = baseline.errors.iter().map(|e| e.to_string()).collect();
+    if let Some(error) = errors.iter().find(|error| !before.contains(*error)) {
+        return Some(format!("it introduces a new error: {}", error));
+    }
+    let before: HashSet<&str> = baseline
+        .warnings
+        .iter()
+        .map(|w| w.message.as_str())
+        .collect();
+    after
+        .warnings
+        .iter()
+        .find(|warning| !before.contains(warning.message.as_str()))
+        .map(|warning| format!("it introduces a new warning: {}", warning.message))
+}
+
+/// 修正案をファイルごとにまとめる
+fn group_by_file<'a>(fixes: &[&'a SchemaFix]) -> BTreeMap<&'a Path, Vec<&'a SchemaFix>> {
+    let mut grouped: BTreeMap<&Path, Vec<&SchemaFix>> = BTreeMap::new();
+    for fix in fixes {
+        grouped.entry(fix.file.as_path()).or_default().push(fix);
+    }
+    grouped
+}
+
+/// JSON出力用の修正案
+pub(crate) fn suggested_fixes(
+    fixes: &[SchemaFix],
+    contents: &HashMap<PathBuf, String>,
+    project_path: &Path,
+) -> Vec<SuggestedFix> {
+    fixes
+        .iter()
+        .map(|fix| {
+            let file = relative_path(project_path, &fix.file);
+            SuggestedFix {
+                file: file.display().to_string(),
+                description: fix.description.clone(),
+                error: fix.error.clone(),
+                safe: fix.safe,
+                patch: contents
+                    .get(&fix.file)
+                    .map(|content| schema_fixes::unified_diff(&file, content, &[fix]))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// JSON出力用の適用結果
+pub(crate) fn applied_fixes(
+    results: &[(SchemaFix, Option<String>)],
+    project_path: &Path,
+) -> Vec<AppliedFix> {
+    results
+        .iter()
+        .map(|(fix, reason)| AppliedFix {
+            file: relative_path(project_path, &fix.file).display().to_string(),
+            description: fix.description.clone(),
+            applied: reason.is_none(),
+            reason: reason.clone(),
+        })
+        .collect()
+}
+
+/// 修正案をテキスト形式でフォーマット（一覧とファイルごとのunified diff）
+pub(crate) fn format_suggested_fixes(
+    fixes: &[SchemaFix],
+    contents: &HashMap<PathBuf, String>,
+    project_path: &Path,
+) -> String {
+    let mut output = String::from("\n=== Suggested Fixes ===\n");
+    if fixes.is_empty() {
+        output.push_str("No automatic fixes are available for the reported errors.\n");
+        return output;
+    }
+
+    output.push('\n');
+    for (i, fix) in fixes.iter().enumerate() {
+        output.push_str(&format!(
+            "{}. {} ({})\n",
+            i + 1,
+            fix.description,
+            relative_path(project_path, &fix.file).display()
+        ));
+        if !fix.safe {
+            output.push_str(
+                "   Removes lines; review it and apply it by hand (not applied by --apply-fixes)\n",
+            );
+        }
+    }
+
+    let fixes: Vec<&SchemaFix> = fixes.iter().collect();
+    for (file, fixes) in group_by_file(&fixes) {
+        if let Some(content) = contents.get(file) {
+            output.push('\n');
+            output.push_str(&schema_fixes::unified_diff(
+                &relative_path(project_path, file),
+                content,
+                &fixes,
+            ));
+        }
+    }
+
+    output
+}
+
+/// 適用結果をテキスト形式でフォーマット
+pub(crate) fn format_applied_fixes(
+    results: &[(SchemaFix, Option<String>)],
+    project_path: &Path,
+) -> String {
+    let mut output = String::from("=== Applied Fixes ===\n");
+    if results.is_empty() {
+        output.push_str("No safe fixes are available for the reported errors.\n");
+    }
+    for (fix, reason) in results {
+        let file = relative_path(project_path, &fix.file);
+        match reason {
+            None => output.push_str(&format!("✓ {} ({})\n", fix.description, file.display())),
+            Some(reason) => output.push_str(&format!(
+                "✗ Skipped: {} ({}): {}\n",
+                fix.description,
+                file.display(),
+                reason
+            )),
+        }
+    }
+    output.push('\n');
+    output
+}
+
+/// プロジェクトルートからの相対パス（配下にない場合はそのまま）
+fn relative_path(project_path: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(project_path)
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::{ErrorLocation, ValidationError};
+    use crate::services::schema_validator::schema_fixes::LineEdit;
+
+    fn error(message: &str) -> ValidationError {
+        ValidationError::Constraint {
+            message: message.to_string(),
+            location: Some(ErrorLocation::with_table("users".to_string())),
+            suggestion: None,
+        }
+    }
+
+    fn primary_key_fix(file: &Path) -> SchemaFix {
+        SchemaFix {
+            file: file.to_path_buf(),
+            description: "Add primary_key: [id] to table 'users'".to_string(),
+            error: "Table 'users' has no primary key defined".to_string(),
+            safe: true,
+            edit: LineEdit {
+                start: 1,
+                remove: 0,
+                insert: vec!["primary_key: [id]".to_string()],
+            },
+        }
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_writes_verified_fixes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("users.yaml");
+        fs::write(&file, "a\nb\n").unwrap();
+        let contents = read_schema_files(std::slice::from_ref(&file)).unwrap();
+        let mut baseline = ValidationResult::new();
+        baseline.add_error(error("Table 'users' has no primary key defined"));
+
+        let results = apply_safe_fixes(&[primary_key_fix(&file)], &contents, &baseline, |_| {
+            Ok(ValidationResult::new())
+        })
+        .unwrap();
+
+        assert_eq!(results[0].1, None);
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "a\nprimary_key: [id]\nb\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_rejects_fix_that_introduces_new_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("users.yaml");
+        fs::write(&file, "a\nb\n").unwrap();
+        let contents = read_schema_files(std::slice::from_ref(&file)).unwrap();
+        let mut baseline = ValidationResult::new();
+        baseline.add_error(error("Table 'users' has no primary key defined"));
+
+        let results = apply_safe_fixes(&[primary_key_fix(&file)], &contents, &baseline, |_| {
+            let mut after = ValidationResult::new();
+            after.add_error(error("Something else broke"));
+            Ok(after)
+        })
+        .unwrap();
+
+        assert!(results[0]
+            .1
+            .as_deref()
+            .unwrap()
+            .contains("introduces a new error"));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_apply_safe_fixes_skips_unsafe_fixes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("users.yaml");
+        fs::write(&file, "a\nb\n").unwrap();
+        let contents = read_schema_files(std::slice::from_ref(&file)).unwrap();
+        let mut fix = primary_key_fix(&file);
+        fix.safe = false;
+
+        let results = apply_safe_fixes(&[fix], &contents, &ValidationResult::new(), |_| {
+            Ok(ValidationResult::new())
+        })
+        .unwrap();
+
+        assert!(results.is_empty());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_format_suggested_fixes_lists_fixes_and_patch() {
+        let project = Path::new("/project");
+        let file = project.join("schema/users.yaml");
+        let mut duplicate = primary_key_fix(&file);
+        duplicate.description = "Remove the repeated definition of column 'id'".to_string();
+        duplicate.safe = false;
+        duplicate.edit = LineEdit {
+            start: 0,
+            remove: 1,
+            insert: Vec::new(),
+        };
+        let contents = HashMap::from([(file.clone(), "a\nb\n".to_string())]);
+
+        let text = format_suggested_fixes(&[duplicate, primary_key_fix(&file)], &contents, project);
+
+        assert!(text.contains(
+            "1. Remove the repeated definition of column 'id' (schema/users.yaml)\n   Removes lines"
+        ));
+        assert!(text.contains("2. Add primary_key: [id] to table 'users' (schema/users.yaml)\n"));
+        assert!(text.contains(
+            "--- a/schema/users.yaml\n+++ b/schema/users.yaml\n@@ -1,2 +1,2 @@\n-a\n+primary_key: [id]\n b\n"
+        ));
+    }
+}
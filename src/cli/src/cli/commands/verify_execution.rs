@@ -0,0 +1,255 @@
+// verify-executionコマンドハンドラー
+//
+// 適用時に記録された実行マニフェスト（実行したステートメントのチェックサムの並び）と、
+// ローカルの up.sql から再計算したマニフェストを比較します。
+// - 記録: schema_migrations の statement_checksums（記録がなければ store_applied_sql の up_sql から再計算）
+// - 相違がある場合は出力とともに EXIT_CODE_EXECUTION_DIVERGENCE で終了する
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::cli::command_context::CommandContext;
+use crate::cli::commands::execution_manifest::{self, compare_manifests, StatementDivergence};
+use crate::cli::commands::migration_loader;
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::core::migration::is_truncated_sql;
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use sqlx::AnyPool;
+use std::path::{Path, PathBuf};
+
+/// 実行マニフェストとローカルの up.sql に相違がある場合の終了コード
+pub const EXIT_CODE_EXECUTION_DIVERGENCE: i32 = 8;
+
+/// 比較に使った記録の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestSource {
+    /// 適用時に記録したステートメントのチェックサム
+    StatementChecksums,
+    /// `store_applied_sql` で保存した up.sql から再計算したチェックサム
+    StoredSql,
+}
+
+impl ManifestSource {
+    fn label(&self) -> &'static str {
+        match self {
+            ManifestSource::StatementChecksums => "recorded statement checksums",
+            ManifestSource::StoredSql => "stored up.sql",
+        }
+    }
+}
+
+/// verify-executionコマンドの出力
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyExecutionOutput {
+    /// 環境名
+    pub environment: String,
+    /// マイグレーションバージョン
+    pub version: String,
+    /// マイグレーションの説明
+    pub description: String,
+    /// 比較に使った記録の種類
+    pub source: ManifestSource,
+    /// 記録されたステートメント数
+    pub recorded_statements: usize,
+    /// ローカルの up.sql のステートメント数
+    pub local_statements: usize,
+    /// 記録とローカルの up.sql が一致するか
+    pub matches: bool,
+    /// 相違のあるステートメント
+    pub divergences: Vec<StatementDivergence>,
+}
+
+impl CommandOutput for VerifyExecutionOutput {
+    fn to_text(&self) -> String {
+        let mut text = format!(
+            "Verifying execution of migration {} ({}) in the '{}' database\n",
+            self.version, self.description, self.environment
+        );
+        text.push_str(&format!(
+            "Compared {} statement(s) from the {} with {} statement(s) in the local up.sql\n",
+            self.recorded_statements,
+            self.source.label(),
+            self.local_statements
+        ));
+
+        if !self.divergences.is_empty() {
+            text.push_str("\nDivergent statements:\n");
+            for divergence in &self.divergences {
+                text.push_str(&format!(
+                    "  #{}: recorded {}, local {}\n",
+                    divergence.position,
+                    divergence.recorded.as_deref().unwrap_or("(none)"),
+                    divergence.local.as_deref().unwrap_or("(none)")
+                ));
+                if let Some(statement) = &divergence.statement {
+                    text.push_str(&format!("      {}\n", statement));
+                }
+            }
+        }
+
+        text.push('\n');
+        if self.matches {
+            text.push_str("The local up.sql matches the statements executed, in the same order");
+        } else {
+            text.push_str(&format!(
+                "{} statement(s) differ from what was executed",
+                self.divergences.len()
+            ));
+        }
+        text
+    }
+}
+
+/// verify-executionコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct VerifyExecutionCommand {
+    /// プロジェクトのルートパス
+    pub project_path: PathBuf,
+    /// カスタム設定ファイルパス
+    pub config_path: Option<PathBuf>,
+    /// 検証するマイグレーションバージョン
+    pub version: String,
+    /// 環境名
+    pub env: String,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// verify-executionコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct VerifyExecutionCommandHandler {}
+
+impl VerifyExecutionCommandHandler {
+    /// 新しいVerifyExecutionCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// verify-executionコマンドを実行
+    ///
+    /// 相違がある場合は、出力とともに `EXIT_CODE_EXECUTION_DIVERGENCE` の `CompletedWithFailure` を返します。
+    pub async fn execute(&self, command: &VerifyExecutionCommand) -> Result<String> {
+        let context = CommandContext::load_with_config(
+            command.project_path.clone(),
+            command.config_path.clone(),
+        )?;
+
+        context
+            .run_with_pools(self.execute_with_context(&context, command))
+            .await
+    }
+
+    async fn execute_with_context(
+        &self,
+        context: &CommandContext,
+        command: &VerifyExecutionCommand,
+    ) -> Result<String> {
+        let migrations_dir = context.require_migrations_dir()?;
+        let available_migrations = migration_loader::load_available_migrations(&migrations_dir)?;
+        let (_, description, migration_dir) = available_migrations
+            .iter()
+            .find(|(version, _, _)| version == &command.version)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Migration {} was not found in the migrations directory.",
+                    command.version
+                )
+            })?;
+
+        // 記録と比較する前に、ローカルの up.sql を読めることを確認する
+        let local = execution_manifest::local_manifest(migration_dir, &context.config)?;
+
+        let (pool, applied_migrations) = context.connect_and_load_migrations(&command.env).await?;
+        if !applied_migrations
+            .iter()
+            .any(|record| record.version == command.version)
+        {
+            return Err(anyhow!(
+                "Migration {} has not been applied to the '{}' database.",
+                command.version,
+                command.env
+            ));
+        }
+        let (source, recorded) = self
+            .load_recorded_manifest(&pool, context, &command.version, migration_dir)
+            .await?;
+
+        let divergences = compare_manifests(&recorded, &local);
+        let output = VerifyExecutionOutput {
+            environment: command.env.clone(),
+            version: command.version.clone(),
+            description: description.clone(),
+            source,
+            recorded_statements: recorded.len(),
+            local_statements: local.len(),
+            matches: divergences.is_empty(),
+            divergences,
+        };
+        let rendered = render_output(&output, &command.format)?;
+
+        if output.matches {
+            return Ok(rendered);
+        }
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "Migration {} in the '{}' database diverges from the local up.sql ({} statement(s) differ)",
+                command.version,
+                command.env,
+                output.divergences.len()
+            ),
+            exit_code: EXIT_CODE_EXECUTION_DIVERGENCE,
+        }
+        .into())
+    }
+
+    /// 適用時に実行されたステートメントのチェックサムを取得
+    ///
+    /// 記録されたチェックサムがなければ、`store_applied_sql` で保存された up.sql から再計算します。
+    async fn load_recorded_manifest(
+        &self,
+        pool: &AnyPool,
+        context: &CommandContext,
+        version: &str,
+        migration_dir: &Path,
+    ) -> Result<(ManifestSource, Vec<String>)> {
+        let migrator = DatabaseMigratorService::new();
+        if let Some(checksums) = migrator
+            .get_statement_checksums(pool, context.dialect(), version)
+            .await
+            .with_context(|| "Failed to get recorded statement checksums")?
+        {
+            return Ok((ManifestSource::StatementChecksums, checksums));
+        }
+
+        let stored_up_sql = migrator
+            .get_applied_sql(pool, context.dialect(), Some(version))
+            .await
+            .with_context(|| "Failed to get applied migration SQL")?
+            .into_iter()
+            .next()
+            .and_then(|applied_sql| applied_sql.up_sql)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Migration {} has no recorded statement checksums (it was applied before they were recorded) and no stored up.sql. Set `store_applied_sql: true` to keep the SQL of migrations applied from now on.",
+                    version
+                )
+            })?;
+        if is_truncated_sql(&stored_up_sql) {
+            return Err(anyhow!(
+                "Migration {} has no recorded statement checksums, and its stored up.sql was truncated, so the executed statements cannot be verified.",
+                version
+            ));
+        }
+
+        Ok((
+            ManifestSource::StoredSql,
+            execution_manifest::stored_sql_checksums(
+                &stored_up_sql,
+                migration_dir,
+                &context.config,
+            )?,
+        ))
+    }
+}
@@ -0,0 +1,154 @@
+// versionコマンドハンドラー
+//
+// 実行中のstrataのバージョンを表示します。
+// `--check-latest` 指定時は最新リリースと比較し、古い場合は1行の通知を表示します。
+// ネットワークに接続できない場合も、エラー内容を出力に含めたうえで成功として終了します。
+// `--features` 指定時はバイナリに含まれているデータベースドライバーを一覧表示します。
+
+use crate::adapters::drivers;
+use crate::cli::commands::{render_output, CommandOutput};
+use crate::cli::update_check::UpdateChecker;
+use crate::cli::OutputFormat;
+use crate::core::config::Dialect;
+use anyhow::Result;
+use serde::Serialize;
+
+/// versionコマンドの出力構造体
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionOutput {
+    /// 実行中のバージョン
+    pub version: String,
+    /// 公開されている最新バージョン（`--check-latest` 指定時、取得できた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    /// 新しいバージョンが公開されているか（`--check-latest` 指定時、取得できた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_available: Option<bool>,
+    /// 古いバージョンを実行している場合の通知
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_notice: Option<String>,
+    /// 最新バージョンを確認できなかった理由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_check_error: Option<String>,
+    /// データベースドライバーの一覧（`--features` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drivers: Option<Vec<DriverFeature>>,
+}
+
+/// データベースドライバーがバイナリに含まれているか
+#[derive(Debug, Clone, Serialize)]
+pub struct DriverFeature {
+    /// 方言
+    pub dialect: String,
+    /// ドライバーを含める cargo フィーチャー
+    pub feature: String,
+    /// バイナリに含まれているか
+    pub compiled: bool,
+}
+
+impl DriverFeature {
+    fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect: dialect.to_string(),
+            feature: drivers::driver_feature(dialect).to_string(),
+            compiled: drivers::is_driver_compiled(dialect),
+        }
+    }
+}
+
+impl CommandOutput for VersionOutput {
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!("strata {}", self.version)];
+        if let Some(notice) = &self.update_notice {
+            lines.push(notice.clone());
+        } else if self.update_available == Some(false) {
+            lines.push("strata is up to date.".to_string());
+        }
+        if let Some(error) = &self.update_check_error {
+            lines.push(format!("Could not check for updates: {}", error));
+        }
+        if let Some(drivers) = &self.drivers {
+            lines.push("Database drivers:".to_string());
+            let width = drivers.iter().map(|d| d.dialect.len()).max().unwrap_or(0);
+            for driver in drivers {
+                let status = if driver.compiled {
+                    "included".to_string()
+                } else {
+                    format!("not included (rebuild with --features {})", driver.feature)
+                };
+                lines.push(format!("  {:<width$}  {}", driver.dialect, status));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// versionコマンドの入力パラメータ
+#[derive(Debug, Clone)]
+pub struct VersionCommand {
+    /// 最新リリースと比較するか
+    pub check_latest: bool,
+    /// バイナリに含まれているデータベースドライバーを表示するか
+    pub features: bool,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+}
+
+/// versionコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct VersionCommandHandler {
+    checker: UpdateChecker,
+}
+
+impl VersionCommandHandler {
+    /// 新しいVersionCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {
+            checker: UpdateChecker::new(),
+        }
+    }
+
+    /// 最新リリースの確認サービスを差し替える
+    pub fn with_checker(mut self, checker: UpdateChecker) -> Self {
+        self.checker = checker;
+        self
+    }
+
+    /// versionコマンドを実行
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - versionコマンドのパラメータ
+    ///
+    /// # Returns
+    ///
+    /// バージョン情報（最新リリースの確認に失敗した場合もエラーにはしない）
+    pub fn execute(&self, command: &VersionCommand) -> Result<String> {
+        let mut output = VersionOutput {
+            version: self.checker.current_version().to_string(),
+            latest_version: None,
+            update_available: None,
+            update_notice: None,
+            update_check_error: None,
+            drivers: command.features.then(|| {
+                [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite]
+                    .into_iter()
+                    .map(DriverFeature::new)
+                    .collect()
+            }),
+        };
+
+        if command.check_latest {
+            match self.checker.check() {
+                Ok(status) => {
+                    output.update_notice = status.notice();
+                    output.latest_version = Some(status.latest_version);
+                    output.update_available = Some(status.update_available);
+                }
+                Err(e) => output.update_check_error = Some(format!("{:#}", e)),
+            }
+        }
+
+        render_output(&output, &command.format)
+    }
+}
@@ -0,0 +1,327 @@
+// workspaceコマンドハンドラー
+//
+// 1つのリポジトリに含まれる複数の strata プロジェクトに対してコマンドを実行します。
+// - strata-workspace.yaml からメンバープロジェクトを読み込み
+// - 各メンバーで順にコマンドを実行（失敗したメンバーがあっても残りを続行）
+// - 結果の集約（テキストはプロジェクトごとのセクション、JSONはメンバーの配列）
+
+use crate::cli::commands::status::{StatusCommand, StatusCommandHandler, StatusGate};
+use crate::cli::commands::{render_output, CommandOutput, CompletedWithFailure};
+use crate::cli::OutputFormat;
+use crate::core::naming::WORKSPACE_FILE;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// ワークスペース設定（strata-workspace.yaml）
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    /// メンバープロジェクトのパス（ワークスペース設定ファイルからの相対パス）
+    pub members: Vec<PathBuf>,
+}
+
+impl WorkspaceConfig {
+    /// ワークスペースのルートから設定ファイルを読み込む
+    pub fn load(workspace_path: &Path) -> Result<Self> {
+        let path = workspace_path.join(WORKSPACE_FILE);
+        if !path.exists() {
+            return Err(anyhow!(
+                "Workspace file not found: {:?}\nCreate {} with a `members:` list of project directories.",
+                path,
+                WORKSPACE_FILE
+            ));
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read workspace file: {:?}", path))?;
+        let config: Self = serde_saphyr::from_str(&content)
+            .with_context(|| format!("Failed to parse workspace file: {:?}", path))?;
+
+        if config.members.is_empty() {
+            return Err(anyhow!("Workspace file {:?} has no members", path));
+        }
+        if let Some(duplicate) = config
+            .members
+            .iter()
+            .enumerate()
+            .find(|(i, member)| config.members[..*i].contains(member))
+            .map(|(_, member)| member)
+        {
+            return Err(anyhow!(
+                "Workspace file {:?} lists member {:?} more than once",
+                path,
+                duplicate
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// メンバープロジェクトのルートパス（宣言順）
+    pub fn member_paths(&self, workspace_path: &Path) -> Vec<PathBuf> {
+        self.members
+            .iter()
+            .map(|member| {
+                if member.is_absolute() {
+                    member.clone()
+                } else {
+                    workspace_path.join(member)
+                }
+            })
+            .collect()
+    }
+}
+
+/// workspace status の入力パラメータ
+#[derive(Debug, Clone)]
+pub struct WorkspaceStatusCommand {
+    /// ワークスペースのルートパス（strata-workspace.yaml のあるディレクトリ）
+    pub workspace_path: PathBuf,
+    /// 環境名
+    pub env: String,
+    /// 出力フォーマット
+    pub format: OutputFormat,
+    /// 各メンバーで判定するゲート
+    pub gates: Vec<StatusGate>,
+}
+
+/// メンバーの実行結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberOutcome {
+    Succeeded,
+    Failed,
+}
+
+/// メンバーごとの実行結果
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceMemberResult {
+    /// メンバープロジェクト（strata-workspace.yaml の記載どおり）
+    pub project: String,
+    /// 実行結果
+    pub outcome: MemberOutcome,
+    /// メンバーでのコマンドの出力（JSON出力時）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+    /// エラーメッセージ（失敗時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// メンバーでのコマンドのテキスト出力
+    #[serde(skip)]
+    pub text_output: String,
+    /// 失敗時の終了コード
+    #[serde(skip)]
+    pub exit_code: Option<i32>,
+}
+
+/// workspace コマンドの出力
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceOutput {
+    /// 実行したコマンド
+    pub command: String,
+    /// メンバーごとの結果（宣言順）
+    pub members: Vec<WorkspaceMemberResult>,
+    /// 失敗したメンバーの数
+    pub failed: usize,
+}
+
+impl WorkspaceOutput {
+    fn failed_members(&self) -> Vec<&str> {
+        self.members
+            .iter()
+            .filter(|m| m.outcome == MemberOutcome::Failed)
+            .map(|m| m.project.as_str())
+            .collect()
+    }
+}
+
+impl CommandOutput for WorkspaceOutput {
+    fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for member in &self.members {
+            text.push_str(&format!("=== {} ===\n", member.project));
+            if !member.text_output.is_empty() {
+                text.push_str(member.text_output.trim_end());
+                text.push('\n');
+            }
+            if let Some(ref error) = member.error {
+                text.push_str(&format!("Error: {}\n", error));
+            }
+            text.push('\n');
+        }
+
+        if self.failed == 0 {
+            text.push_str(&format!(
+                "Workspace {}: {} project(s) succeeded",
+                self.command,
+                self.members.len()
+            ));
+        } else {
+            text.push_str(&format!(
+                "Workspace {}: {} of {} project(s) failed ({})",
+                self.command,
+                self.failed,
+                self.members.len(),
+                self.failed_members().join(", ")
+            ));
+        }
+        text
+    }
+}
+
+/// workspaceコマンドハンドラー
+#[derive(Debug, Default)]
+pub struct WorkspaceCommandHandler {}
+
+impl WorkspaceCommandHandler {
+    /// 新しいWorkspaceCommandHandlerを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// 全メンバーで status を実行
+    ///
+    /// メンバーは宣言順に1つずつ実行します。失敗したメンバーがある場合は、全メンバーの
+    /// 結果を含む出力とともに `CompletedWithFailure` を返します。終了コードは最初に
+    /// 失敗したメンバーのもの（ゲートの失敗ならゲートの終了コード）です。
+    pub async fn execute_status(&self, command: &WorkspaceStatusCommand) -> Result<String> {
+        let config = WorkspaceConfig::load(&command.workspace_path)?;
+
+        let mut members = Vec::with_capacity(config.members.len());
+        for (member, member_path) in config
+            .members
+            .iter()
+            .zip(config.member_paths(&command.workspace_path))
+        {
+            debug!(member = %member.display(), "Running status for workspace member");
+            let status_command = StatusCommand {
+                project_path: member_path,
+                config_path: None,
+                env: command.env.clone(),
+                format: command.format.clone(),
+                gates: command.gates.clone(),
+                verbose: false,
+                wait_timeout: None,
+            };
+            let result = StatusCommandHandler::new().execute(&status_command).await;
+            members.push(member_result(
+                member.display().to_string(),
+                result,
+                &command.format,
+            ));
+        }
+
+        let failed = members
+            .iter()
+            .filter(|m| m.outcome == MemberOutcome::Failed)
+            .count();
+        let output = WorkspaceOutput {
+            command: "status".to_string(),
+            members,
+            failed,
+        };
+        let rendered = render_output(&output, &command.format)?;
+
+        if failed == 0 {
+            return Ok(rendered);
+        }
+
+        let exit_code = output.members.iter().find_map(|m| m.exit_code).unwrap_or(1);
+        Err(CompletedWithFailure {
+            output: rendered,
+            message: format!(
+                "{} of {} workspace project(s) failed: {}",
+                failed,
+                output.members.len(),
+                output.failed_members().join(", ")
+            ),
+            exit_code,
+        }
+        .into())
+    }
+}
+
+/// メンバーでのコマンドの結果を集約用の結果に変換
+fn member_result(
+    project: String,
+    result: Result<String>,
+    format: &OutputFormat,
+) -> WorkspaceMemberResult {
+    let (outcome, rendered, error, exit_code) = match result {
+        Ok(rendered) => (MemberOutcome::Succeeded, rendered, None, None),
+        Err(e) => match e.downcast_ref::<CompletedWithFailure>() {
+            // 最後まで実行されたが失敗扱い（ゲートの失敗など）: 出力も残す
+            Some(completed) => (
+                MemberOutcome::Failed,
+                completed.output.clone(),
+                Some(completed.message.clone()),
+                Some(completed.exit_code),
+            ),
+            None => (
+                MemberOutcome::Failed,
+                String::new(),
+                Some(format!("{:#}", e)),
+                Some(1),
+            ),
+        },
+    };
+
+    let (output, text_output) = match format {
+        OutputFormat::Json => (serde_json::from_str(&rendered).ok(), String::new()),
+        OutputFormat::Text => (None, rendered),
+    };
+
+    WorkspaceMemberResult {
+        project,
+        outcome,
+        output,
+        error,
+        text_output,
+        exit_code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_workspace_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join(WORKSPACE_FILE),
+            "members:\n  - services/auth\n  - services/billing\n",
+        )
+        .unwrap();
+
+        let config = WorkspaceConfig::load(root).unwrap();
+
+        assert_eq!(
+            config.member_paths(root),
+            vec![root.join("services/auth"), root.join("services/billing")]
+        );
+    }
+
+    #[test]
+    fn test_load_workspace_config_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let err = WorkspaceConfig::load(root).unwrap_err();
+        assert!(err.to_string().contains("Workspace file not found"));
+
+        fs::write(root.join(WORKSPACE_FILE), "members: []\n").unwrap();
+        let err = WorkspaceConfig::load(root).unwrap_err();
+        assert!(err.to_string().contains("has no members"));
+
+        fs::write(root.join(WORKSPACE_FILE), "members:\n  - a\n  - b\n  - a\n").unwrap();
+        let err = WorkspaceConfig::load(root).unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+}
@@ -0,0 +1,262 @@
+// フェーズ別の実行時間計測
+//
+// 設定読み込み・スキーマ解析・差分検出・DB処理など、コマンドのどこで時間がかかっているかを
+// 確認するための軽量な計測器。グローバル状態は持たず、ハンドラーからコマンドコンテキストへ
+// 渡して共有する。
+//
+// 各フェーズは `phase` スパン内で実行され、完了時に経過時間をdebugログに出力する。
+// コマンド終了時には `config=3ms parse=120ms diff=45ms total=180ms` 形式の1行サマリーを
+// debugレベル（`--timings` 指定時はinfoレベル）で出力する。
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, debug_span, info, Instrument};
+
+/// フェーズの計測結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTiming {
+    /// フェーズ名（`config`, `parse`, `diff`, `execute` など）
+    pub phase: &'static str,
+    /// 経過時間（同じフェーズを複数回実行した場合は合計）
+    pub elapsed: Duration,
+}
+
+/// フェーズ別の実行時間コレクター
+///
+/// クローンしたコレクターは計測結果を共有する。
+#[derive(Debug, Clone)]
+pub struct Timings {
+    phases: Arc<Mutex<Vec<PhaseTiming>>>,
+    started: Instant,
+    report: bool,
+}
+
+impl Timings {
+    /// 新しいコレクターを作成（計測開始時刻は作成時点）
+    pub fn new() -> Self {
+        Self {
+            phases: Arc::new(Mutex::new(Vec::new())),
+            started: Instant::now(),
+            report: false,
+        }
+    }
+
+    /// 計測結果を利用者に報告するか（`--timings`）を設定
+    pub fn with_report(mut self, report: bool) -> Self {
+        self.report = report;
+        self
+    }
+
+    /// 計測結果を利用者に報告するか
+    pub fn is_reported(&self) -> bool {
+        self.report
+    }
+
+    /// 同期処理をフェーズとして計測する
+    pub fn measure<T>(&self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let span = debug_span!("phase", name = phase);
+        let _entered = span.enter();
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    /// 非同期処理をフェーズとして計測する
+    pub async fn measure_async<T>(
+        &self,
+        phase: &'static str,
+        future: impl Future<Output = T>,
+    ) -> T {
+        let span = debug_span!("phase", name = phase);
+        let start = Instant::now();
+        let result = future.instrument(span.clone()).await;
+        span.in_scope(|| self.record(phase, start.elapsed()));
+        result
+    }
+
+    /// フェーズの経過時間を記録する（同じフェーズは合算）
+    pub fn record(&self, phase: &'static str, elapsed: Duration) {
+        debug!(
+            phase,
+            elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+            "Phase completed"
+        );
+        self.accumulate(phase, elapsed);
+    }
+
+    /// 別のコレクターで記録した計測結果を取り込む
+    pub fn absorb(&self, other: &Timings) {
+        if Arc::ptr_eq(&self.phases, &other.phases) {
+            return;
+        }
+        for timing in other.phases() {
+            self.accumulate(timing.phase, timing.elapsed);
+        }
+    }
+
+    fn accumulate(&self, phase: &'static str, elapsed: Duration) {
+        let mut phases = self.phases.lock().unwrap_or_else(|e| e.into_inner());
+        match phases.iter_mut().find(|p| p.phase == phase) {
+            Some(existing) => existing.elapsed += elapsed,
+            None => phases.push(PhaseTiming { phase, elapsed }),
+        }
+    }
+
+    /// 記録済みのフェーズ（最初に記録された順）
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        self.phases
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 作成時点からの経過時間
+    pub fn total(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// 1行サマリー（例: `config=3ms parse=120ms db=2.3s total=2.5s`）
+    pub fn summary_line(&self) -> String {
+        self.phases()
+            .iter()
+            .map(|p| format!("{}={}", p.phase, format_duration(p.elapsed)))
+            .chain(std::iter::once(format!(
+                "total={}",
+                format_duration(self.total())
+            )))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 1行サマリーをログに出力する
+    ///
+    /// 通常はdebugレベル、`--timings` 指定時はinfoレベルで出力する。
+    pub fn log_summary(&self) {
+        let line = self.summary_line();
+        if self.report {
+            info!("timings: {}", line);
+        } else {
+            debug!("timings: {}", line);
+        }
+    }
+
+    /// JSON出力用の計測結果
+    pub fn report(&self) -> TimingsReport {
+        TimingsReport {
+            phases: self.phases(),
+            total: self.total(),
+        }
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// JSON出力に含める計測結果
+///
+/// `{"config_ms": 1.2, "parse_ms": 120.4, "total_ms": 180.0}` のように、
+/// フェーズごとのミリ秒を記録順に出力する。
+#[derive(Debug, Clone)]
+pub struct TimingsReport {
+    pub phases: Vec<PhaseTiming>,
+    pub total: Duration,
+}
+
+impl Serialize for TimingsReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.phases.len() + 1))?;
+        for timing in &self.phases {
+            map.serialize_entry(&format!("{}_ms", timing.phase), &millis(timing.elapsed))?;
+        }
+        map.serialize_entry("total_ms", &millis(self.total))?;
+        map.end()
+    }
+}
+
+/// ミリ秒（小数第3位まで）
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+/// サマリー用の経過時間表記（1秒未満はミリ秒、それ以上は秒）
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates_same_phase_in_first_seen_order() {
+        let timings = Timings::new();
+        timings.record("parse", Duration::from_millis(10));
+        timings.record("diff", Duration::from_millis(5));
+        timings.record("parse", Duration::from_millis(20));
+
+        let phases = timings.phases();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].phase, "parse");
+        assert_eq!(phases[0].elapsed, Duration::from_millis(30));
+        assert_eq!(phases[1].phase, "diff");
+    }
+
+    #[test]
+    fn test_summary_line_format() {
+        let timings = Timings::new();
+        timings.record("parse", Duration::from_millis(120));
+        timings.record("db", Duration::from_millis(2300));
+
+        let line = timings.summary_line();
+        assert!(line.starts_with("parse=120ms db=2.3s total="), "{}", line);
+    }
+
+    #[test]
+    fn test_absorb_shares_and_merges() {
+        let shared = Timings::new().with_report(true);
+        let clone = shared.clone();
+        clone.record("config", Duration::from_millis(1));
+
+        let other = Timings::new();
+        other.record("parse", Duration::from_millis(2));
+        shared.absorb(&other);
+        // 同じコレクターの取り込みは二重計上しない
+        shared.absorb(&clone);
+
+        let phases: Vec<_> = shared.phases().iter().map(|p| p.phase).collect();
+        assert_eq!(phases, vec!["config", "parse"]);
+        assert!(clone.is_reported());
+    }
+
+    #[test]
+    fn test_measure_records_phase() {
+        let timings = Timings::new();
+        let value = timings.measure("generate", || 42);
+        assert_eq!(value, 42);
+        assert_eq!(timings.phases()[0].phase, "generate");
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = TimingsReport {
+            phases: vec![PhaseTiming {
+                phase: "parse",
+                elapsed: Duration::from_micros(1500),
+            }],
+            total: Duration::from_millis(3),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["parse_ms"], 1.5);
+        assert_eq!(json["total_ms"], 3.0);
+    }
+}
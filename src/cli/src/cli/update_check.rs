@@ -0,0 +1,565 @@
+// 最新リリースの確認
+//
+// GitHub Releases API から最新のリリースバージョンを取得し、実行中のバージョンと比較する。
+// - `strata version --check-latest` では毎回問い合わせる
+// - 設定 `update_check: true` による自動確認は `.strata/update-check.json` に結果を
+//   キャッシュし、問い合わせは1日1回までとする
+// - ネットワークに接続できない・タイムアウトした場合もコマンド自体は失敗させない
+//   （呼び出し側でエラーを無視する）
+//
+// HTTPクライアントは `update-check` フィーチャーが有効な場合のみ含まれる。
+// 無効なビルドでは問い合わせが常にエラーとなり、通知は表示されない。
+
+use crate::core::config::Config;
+use crate::core::naming::STATE_DIR;
+use crate::services::config_loader::ConfigLoader;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+/// 最新リリースを問い合わせるエンドポイント
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/Lazialize/strata/releases/latest";
+
+/// リリース一覧のURL（通知に表示する）
+pub const RELEASES_PAGE_URL: &str = "https://github.com/Lazialize/strata/releases";
+
+/// 状態ディレクトリ内のキャッシュファイル名
+pub const CACHE_FILE_NAME: &str = "update-check.json";
+
+/// 問い合わせのタイムアウト
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 自動確認で問い合わせる間隔
+const CHECK_INTERVAL_HOURS: i64 = 24;
+
+/// リリースバージョン（`MAJOR.MINOR.PATCH[-PRERELEASE]`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// プレリリース識別子（例: `rc.1`）
+    pub pre: Option<String>,
+}
+
+impl ReleaseVersion {
+    /// バージョン文字列を解析（先頭の `v` とビルドメタデータ `+...` は無視する）
+    pub fn parse(value: &str) -> Result<Self> {
+        let trimmed = value.trim();
+        let without_prefix = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let without_build = without_prefix
+            .split_once('+')
+            .map_or(without_prefix, |(version, _)| version);
+        let (core, pre) = match without_build.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre.to_string())),
+            Some(_) => return Err(anyhow!("Invalid version: '{}'", value)),
+            None => (without_build, None),
+        };
+
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| anyhow!("Invalid version: '{}'", value))?;
+        match parts.as_slice() {
+            [major, minor, patch] => Ok(Self {
+                major: *major,
+                minor: *minor,
+                patch: *patch,
+                pre,
+            }),
+            _ => Err(anyhow!("Invalid version: '{}'", value)),
+        }
+    }
+}
+
+impl Ord for ReleaseVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                // プレリリースは同じ番号の正式リリースより前
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for ReleaseVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for ReleaseVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// 確認結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpdateStatus {
+    /// 実行中のバージョン
+    pub current_version: String,
+    /// 公開されている最新バージョン
+    pub latest_version: String,
+    /// 新しいバージョンが公開されているか
+    pub update_available: bool,
+}
+
+impl UpdateStatus {
+    /// 実行中のバージョンと最新バージョンを比較
+    pub fn compare(current_version: &str, latest_version: &str) -> Result<Self> {
+        let current = ReleaseVersion::parse(current_version)?;
+        let latest = ReleaseVersion::parse(latest_version)?;
+        Ok(Self {
+            current_version: current.to_string(),
+            latest_version: latest.to_string(),
+            update_available: latest > current,
+        })
+    }
+
+    /// 古いバージョンを実行している場合の1行の通知
+    pub fn notice(&self) -> Option<String> {
+        self.update_available.then(|| {
+            format!(
+                "A new version of strata is available: {} -> {} ({})",
+                self.current_version, self.latest_version, RELEASES_PAGE_URL
+            )
+        })
+    }
+}
+
+/// 自動確認の結果キャッシュ（`.strata/update-check.json`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateCheckCache {
+    /// 最後に問い合わせた日時
+    pub checked_at: DateTime<Utc>,
+    /// 最後に取得できた最新バージョン（問い合わせに失敗し続けている場合は `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+}
+
+impl UpdateCheckCache {
+    /// キャッシュファイルのパス
+    pub fn path(state_dir: &Path) -> PathBuf {
+        state_dir.join(CACHE_FILE_NAME)
+    }
+
+    /// キャッシュを読み込む（存在しない・壊れている場合は `None`）
+    pub fn load(state_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(Self::path(state_dir)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// キャッシュを書き込む
+    pub fn save(&self, state_dir: &Path) -> Result<()> {
+        fs::create_dir_all(state_dir)
+            .with_context(|| format!("Failed to create directory: {:?}", state_dir))?;
+        let content = serde_json::to_string_pretty(self)?;
+        let path = Self::path(state_dir);
+        fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    /// 問い合わせ間隔内のキャッシュか
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        let elapsed = now.signed_duration_since(self.checked_at);
+        elapsed >= ChronoDuration::zero() && elapsed < ChronoDuration::hours(CHECK_INTERVAL_HOURS)
+    }
+}
+
+/// GitHub Releases API のレスポンス（必要な項目のみ）
+#[cfg(feature = "update-check")]
+#[derive(Debug, Deserialize)]
+struct LatestRelease {
+    tag_name: String,
+}
+
+/// 最新リリースの確認サービス
+#[derive(Debug, Clone)]
+pub struct UpdateChecker {
+    endpoint: String,
+    timeout: Duration,
+    current_version: String,
+}
+
+impl Default for UpdateChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UpdateChecker {
+    /// 実行中のバージョンをGitHub Releasesと比較するサービスを作成
+    pub fn new() -> Self {
+        Self {
+            endpoint: LATEST_RELEASE_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            current_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// 問い合わせ先を差し替える（テストやミラー用）
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    /// 問い合わせのタイムアウトを設定
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 比較対象の現在のバージョンを差し替える
+    pub fn with_current_version(mut self, version: impl Into<String>) -> Self {
+        self.current_version = version.into();
+        self
+    }
+
+    /// 現在のバージョン
+    pub fn current_version(&self) -> &str {
+        &self.current_version
+    }
+
+    /// 最新リリースを問い合わせて比較する
+    pub fn check(&self) -> Result<UpdateStatus> {
+        let latest_version = self.fetch_latest_version()?;
+        UpdateStatus::compare(&self.current_version, &latest_version)
+    }
+
+    /// キャッシュを利用して比較する（問い合わせは1日1回まで）
+    ///
+    /// 問い合わせに失敗した場合も確認日時を記録し、次の問い合わせは翌日以降とする。
+    pub fn check_cached(&self, state_dir: &Path, now: DateTime<Utc>) -> Result<UpdateStatus> {
+        let cached = UpdateCheckCache::load(state_dir);
+        if let Some(cache) = cached.as_ref().filter(|cache| cache.is_fresh(now)) {
+            debug!(checked_at = %cache.checked_at, "Using cached update check result");
+            let latest_version = cache
+                .latest_version
+                .as_deref()
+                .ok_or_else(|| anyhow!("Latest version is unknown (last check failed)"))?;
+            return UpdateStatus::compare(&self.current_version, latest_version);
+        }
+
+        let fetched = self.fetch_latest_version();
+        let cache = UpdateCheckCache {
+            checked_at: now,
+            latest_version: match &fetched {
+                Ok(version) => Some(version.clone()),
+                Err(_) => cached.and_then(|cache| cache.latest_version),
+            },
+        };
+        if let Err(e) = cache.save(state_dir) {
+            debug!(error = %e, "Failed to write update check cache");
+        }
+
+        UpdateStatus::compare(&self.current_version, &fetched?)
+    }
+
+    /// 最新リリースのバージョンを取得
+    #[cfg(feature = "update-check")]
+    pub fn fetch_latest_version(&self) -> Result<String> {
+        debug!(endpoint = %self.endpoint, "Fetching latest release");
+        let config = ureq::Agent::config_builder()
+            .timeout_global(Some(self.timeout))
+            .build();
+        let agent = ureq::Agent::new_with_config(config);
+        let body = agent
+            .get(&self.endpoint)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", concat!("strata/", env!("CARGO_PKG_VERSION")))
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .with_context(|| format!("Failed to fetch latest release from {}", self.endpoint))?;
+        let release: LatestRelease = serde_json::from_str(&body)
+            .with_context(|| "Failed to parse latest release response")?;
+        Ok(ReleaseVersion::parse(&release.tag_name)?.to_string())
+    }
+
+    /// 最新リリースのバージョンを取得（`update-check` フィーチャー無効時は常にエラー）
+    #[cfg(not(feature = "update-check"))]
+    pub fn fetch_latest_version(&self) -> Result<String> {
+        debug!(endpoint = %self.endpoint, timeout = ?self.timeout, "Update check is not available in this build");
+        Err(anyhow!(
+            "This build of strata does not include the update check (built without the `update-check` feature)"
+        ))
+    }
+}
+
+/// `update_check: true` のプロジェクトで、古いバージョンを実行している場合の通知を返す
+///
+/// 設定を読み込めない・問い合わせに失敗した場合は `None` を返し、
+/// コマンドの実行結果には影響させない。
+pub fn notice_for_project(project_path: &Path, config_path: Option<&Path>) -> Option<String> {
+    let config_path = config_path
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| project_path.join(Config::DEFAULT_CONFIG_PATH));
+    let config = ConfigLoader::from_file(&config_path).ok()?;
+    if !config.update_check {
+        return None;
+    }
+
+    UpdateChecker::new()
+        .check_cached(&project_path.join(STATE_DIR), Utc::now())
+        .inspect_err(|e| debug!(error = %e, "Update check failed"))
+        .ok()?
+        .notice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_release_version() {
+        assert_eq!(
+            ReleaseVersion::parse("v1.2.3").unwrap(),
+            ReleaseVersion {
+                major: 1,
+                minor: 2,
+                patch: 3,
+                pre: None,
+            }
+        );
+        assert_eq!(
+            ReleaseVersion::parse("0.3.0-rc.1+build.5")
+                .unwrap()
+                .to_string(),
+            "0.3.0-rc.1"
+        );
+        assert!(ReleaseVersion::parse("1.2").is_err());
+        assert!(ReleaseVersion::parse("1.2.x").is_err());
+        assert!(ReleaseVersion::parse("1.2.3-").is_err());
+    }
+
+    #[test]
+    fn test_release_version_ordering() {
+        let v = |s: &str| ReleaseVersion::parse(s).unwrap();
+
+        assert!(v("0.2.1") > v("0.2.0"));
+        assert!(v("0.10.0") > v("0.9.9"));
+        assert!(v("1.0.0") > v("0.99.99"));
+        assert!(v("1.0.0") > v("1.0.0-rc.1"));
+        assert!(v("1.0.0-rc.2") > v("1.0.0-rc.1"));
+        assert_eq!(v("v1.0.0").cmp(&v("1.0.0")), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_update_status_notice() {
+        let outdated = UpdateStatus::compare("0.2.0", "v0.3.0").unwrap();
+        assert!(outdated.update_available);
+        assert_eq!(
+            outdated.notice().unwrap(),
+            "A new version of strata is available: 0.2.0 -> 0.3.0 (https://github.com/Lazialize/strata/releases)"
+        );
+
+        let current = UpdateStatus::compare("0.3.0", "0.3.0").unwrap();
+        assert!(!current.update_available);
+        assert!(current.notice().is_none());
+
+        // 開発版が公開版より新しい場合も通知しない
+        let newer = UpdateStatus::compare("0.4.0", "0.3.0").unwrap();
+        assert!(newer.notice().is_none());
+    }
+
+    #[test]
+    fn test_cache_freshness() {
+        let now = Utc::now();
+        let cache = |hours: i64| UpdateCheckCache {
+            checked_at: now - ChronoDuration::hours(hours),
+            latest_version: Some("0.3.0".to_string()),
+        };
+
+        assert!(cache(0).is_fresh(now));
+        assert!(cache(23).is_fresh(now));
+        assert!(!cache(24).is_fresh(now));
+        // 時計が巻き戻った場合は問い合わせ直す
+        assert!(!cache(-1).is_fresh(now));
+    }
+
+    #[test]
+    fn test_check_cached_uses_fresh_cache_without_network() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Utc::now();
+        UpdateCheckCache {
+            checked_at: now - ChronoDuration::hours(1),
+            latest_version: Some("0.3.0".to_string()),
+        }
+        .save(temp_dir.path())
+        .unwrap();
+
+        // 到達できないエンドポイントでもキャッシュから判定できる
+        let checker = UpdateChecker::new()
+            .with_endpoint("http://127.0.0.1:1/unreachable")
+            .with_current_version("0.2.0");
+        let status = checker.check_cached(temp_dir.path(), now).unwrap();
+
+        assert!(status.update_available);
+        assert_eq!(status.latest_version, "0.3.0");
+    }
+
+    #[test]
+    fn test_check_cached_ignores_broken_cache_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(UpdateCheckCache::path(temp_dir.path()), "not json").unwrap();
+
+        assert!(UpdateCheckCache::load(temp_dir.path()).is_none());
+    }
+
+    #[cfg(feature = "update-check")]
+    mod http {
+        use super::*;
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+        use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+        use std::sync::Arc;
+        use std::thread;
+
+        /// 固定のレスポンスを返すモックサーバーを起動し、URLとリクエスト数を返す
+        fn mock_server(status: &'static str, body: &'static str) -> (String, Arc<AtomicUsize>) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/releases/latest", listener.local_addr().unwrap());
+            let requests = Arc::new(AtomicUsize::new(0));
+            let counter = Arc::clone(&requests);
+
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    counter.fetch_add(1, AtomicOrdering::SeqCst);
+
+                    // リクエストヘッダーを読み捨てる
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                        line.clear();
+                    }
+
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            });
+
+            (url, requests)
+        }
+
+        #[test]
+        fn test_fetch_latest_version_from_mock_server() {
+            let (url, _) = mock_server("200 OK", r#"{"tag_name": "v0.3.0", "name": "v0.3.0"}"#);
+            let checker = UpdateChecker::new()
+                .with_endpoint(url)
+                .with_current_version("0.2.0");
+
+            let status = checker.check().unwrap();
+
+            assert_eq!(
+                status,
+                UpdateStatus {
+                    current_version: "0.2.0".to_string(),
+                    latest_version: "0.3.0".to_string(),
+                    update_available: true,
+                }
+            );
+        }
+
+        #[test]
+        fn test_fetch_latest_version_http_error() {
+            let (url, _) = mock_server("403 Forbidden", r#"{"message": "rate limited"}"#);
+            let checker = UpdateChecker::new().with_endpoint(url);
+
+            assert!(checker.check().is_err());
+        }
+
+        #[test]
+        fn test_fetch_latest_version_unexpected_body() {
+            let (url, _) = mock_server("200 OK", r#"{"tag_name": "nightly"}"#);
+            let checker = UpdateChecker::new().with_endpoint(url);
+
+            assert!(checker.check().is_err());
+        }
+
+        #[test]
+        fn test_fetch_latest_version_times_out() {
+            // 接続は受け付けるが応答しないサーバー
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/", listener.local_addr().unwrap());
+            thread::spawn(move || {
+                let _connections: Vec<_> = listener.incoming().collect();
+            });
+
+            let checker = UpdateChecker::new()
+                .with_endpoint(url)
+                .with_timeout(Duration::from_millis(200));
+            let started = std::time::Instant::now();
+
+            assert!(checker.check().is_err());
+            assert!(started.elapsed() < Duration::from_secs(5));
+        }
+
+        #[test]
+        fn test_check_cached_fetches_once_per_day() {
+            let (url, requests) = mock_server("200 OK", r#"{"tag_name": "v0.3.0"}"#);
+            let temp_dir = TempDir::new().unwrap();
+            let checker = UpdateChecker::new()
+                .with_endpoint(url)
+                .with_current_version("0.2.0");
+            let now = Utc::now();
+
+            let first = checker.check_cached(temp_dir.path(), now).unwrap();
+            let second = checker
+                .check_cached(temp_dir.path(), now + ChronoDuration::hours(1))
+                .unwrap();
+
+            assert_eq!(first, second);
+            assert_eq!(requests.load(AtomicOrdering::SeqCst), 1);
+            assert_eq!(
+                UpdateCheckCache::load(temp_dir.path()).unwrap(),
+                UpdateCheckCache {
+                    checked_at: now,
+                    latest_version: Some("0.3.0".to_string()),
+                }
+            );
+
+            checker
+                .check_cached(temp_dir.path(), now + ChronoDuration::hours(25))
+                .unwrap();
+            assert_eq!(requests.load(AtomicOrdering::SeqCst), 2);
+        }
+
+        #[test]
+        fn test_check_cached_records_failed_check() {
+            let temp_dir = TempDir::new().unwrap();
+            let checker = UpdateChecker::new()
+                .with_endpoint("http://127.0.0.1:1/unreachable")
+                .with_timeout(Duration::from_millis(200));
+            let now = Utc::now();
+
+            assert!(checker.check_cached(temp_dir.path(), now).is_err());
+
+            // 失敗も記録し、当日中は再度問い合わせない
+            let cache = UpdateCheckCache::load(temp_dir.path()).unwrap();
+            assert_eq!(cache.checked_at, now);
+            assert!(cache.latest_version.is_none());
+        }
+    }
+}
@@ -5,4 +5,4 @@
 pub mod cli;
 
 pub use strata_core::core;
-pub use strata_db::{adapters, services};
+pub use strata_db::{adapters, services, SchemaBuilder, SchemaBuilderExt};
@@ -1,19 +1,46 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 use colored::control as color_control;
+use colored::Colorize;
 use std::env;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::process;
+use std::sync::Arc;
 use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::baseline::{BaselineCommand, BaselineCommandHandler};
 use strata::cli::commands::check::{CheckCommand, CheckCommandHandler};
+use strata::cli::commands::confirm::TerminalConfirm;
+use strata::cli::commands::contract::{ContractCommand, ContractCommandHandler};
+use strata::cli::commands::dev::watch::WatchOptions;
+use strata::cli::commands::dev::{DevCommandHandler, DevMigrateCommand, WatchEvent};
+use strata::cli::commands::diff::{DiffCommand, DiffCommandHandler};
+use strata::cli::commands::docs::{DocsCommand, DocsCommandHandler};
 use strata::cli::commands::export::{ExportCommand, ExportCommandHandler};
 use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
-use strata::cli::commands::init::{InitCommand, InitCommandHandler};
+use strata::cli::commands::history::{HistoryCommand, HistoryCommandHandler};
+use strata::cli::commands::init::{InitCommand, InitCommandHandler, InitInteractiveCommand};
+use strata::cli::commands::init_wizard::TerminalPrompter;
+use strata::cli::commands::inspect::{InspectCommand, InspectCommandHandler};
+use strata::cli::commands::introspect::{IntrospectCommand, IntrospectCommandHandler};
+use strata::cli::commands::rename_migration::{
+    RenameMigrationCommand, RenameMigrationCommandHandler,
+};
+use strata::cli::commands::repair::{RepairCommand, RepairCommandHandler};
+use strata::cli::commands::reset::{ResetCommand, ResetCommandHandler};
 use strata::cli::commands::rollback::{RollbackCommand, RollbackCommandHandler};
+use strata::cli::commands::seed::{SeedCommand, SeedCommandHandler};
 use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
 use strata::cli::commands::validate::{ValidateCommand, ValidateCommandHandler};
-use strata::cli::commands::ErrorOutput;
-use strata::cli::{Cli, Commands, OutputFormat};
+use strata::cli::commands::verify_execution::{
+    VerifyExecutionCommand, VerifyExecutionCommandHandler,
+};
+use strata::cli::commands::version::{VersionCommand, VersionCommandHandler};
+use strata::cli::commands::workspace::{WorkspaceCommandHandler, WorkspaceStatusCommand};
+use strata::cli::commands::{CompletedWithFailure, ErrorOutput};
+use strata::cli::timings::Timings;
+use strata::cli::update_check;
+use strata::cli::{Cli, Commands, DevCommands, OutputFormat, WorkspaceCommands};
 use strata::core::config::Dialect;
 use tracing::debug;
 use tracing_subscriber::EnvFilter;
@@ -42,6 +69,16 @@ fn main() {
             }
         }
         Err(e) => {
+            // 処理は完了したが失敗扱いとする場合は、出力を表示してから専用の終了コードで終了する
+            let exit_code = match e.downcast_ref::<CompletedWithFailure>() {
+                Some(completed) => {
+                    if !completed.output.is_empty() {
+                        println!("{}", completed.output);
+                    }
+                    completed.exit_code
+                }
+                None => 1,
+            };
             if is_json {
                 // JSON モードではエラーも構造化JSON形式で出力
                 let error_output = ErrorOutput::new(format!("{:#}", e));
@@ -49,7 +86,7 @@ fn main() {
             } else {
                 eprintln!("Error: {:#}", e);
             }
-            process::exit(1);
+            process::exit(exit_code);
         }
     }
 }
@@ -64,13 +101,17 @@ async fn run_command(cli: Cli) -> Result<String> {
     // --verbose フラグの処理: tracing subscriber を初期化
     // STRATA_LOG 環境変数が設定されている場合はそちらを優先する
     // 例: STRATA_LOG=info strata status
-    let filter = if let Ok(env_filter) = env::var("STRATA_LOG") {
+    let mut filter = if let Ok(env_filter) = env::var("STRATA_LOG") {
         EnvFilter::new(env_filter)
     } else if cli.verbose {
         EnvFilter::new("debug")
     } else {
         EnvFilter::new("warn")
     };
+    // --timings フラグの処理: 詳細度に関わらずサマリーをinfoレベルで出力する
+    if cli.timings {
+        filter = filter.add_directive("strata::cli::timings=info".parse()?);
+    }
     // try_init() を使用して二重登録時のパニックを防止
     // （テストや他のコンテキストで既にsubscriberが登録されている場合がある）
     let _ = tracing_subscriber::fmt()
@@ -83,35 +124,75 @@ async fn run_command(cli: Cli) -> Result<String> {
         debug!("Verbose mode enabled");
     }
 
-    // プロジェクトのルートパスを取得
-    let project_path = env::current_dir()?;
+    // プロジェクトのルートパスを取得（--project 指定時はそのディレクトリ）
+    let current_dir = env::current_dir()?;
+    let project_path = match cli.project {
+        Some(p) => {
+            let path = if p.is_absolute() {
+                p
+            } else {
+                current_dir.join(p)
+            };
+            if !path.is_dir() {
+                anyhow::bail!("Project directory not found: {:?}", path);
+            }
+            path
+        }
+        None => current_dir.clone(),
+    };
 
-    // --config フラグの処理（絶対パスに変換）
+    // --config フラグの処理（カレントディレクトリ基準で絶対パスに変換）
     let config_path: Option<PathBuf> = cli.config.map(|p| {
         if p.is_absolute() {
             p
         } else {
-            project_path.join(p)
+            current_dir.join(p)
         }
     });
 
     let format = cli.format;
     let verbose = cli.verbose;
+    let timings = Timings::new().with_report(cli.timings);
+
+    // `update_check: true` による自動確認（JSON出力を崩さないようテキスト出力時のみ。
+    // version コマンドは自身で確認するため対象外）
+    let update_check_target = (matches!(format, OutputFormat::Text)
+        && !matches!(cli.command, Commands::Version { .. }))
+    .then(|| (project_path.clone(), config_path.clone()));
 
     debug!(project_path = %project_path.display(), "Resolved project path");
     if let Some(ref cp) = config_path {
         debug!(config_path = %cp.display(), "Using custom config path");
     }
 
-    match cli.command {
+    let result = match cli.command {
         Commands::Init {
             dialect,
             force,
             add_gitignore,
+            with_example,
         } => {
             debug!(dialect = ?dialect, force = force, "Executing init command");
-            let dialect = parse_dialect(&dialect)?;
             let handler = InitCommandHandler::new();
+            let Some(dialect) = dialect else {
+                // --dialect なしの場合は対話モード（スクリプトからの実行では従来どおり必須）
+                if !std::io::stdin().is_terminal() {
+                    return Err(anyhow::anyhow!(
+                        "--dialect is required when stdin is not a terminal. Please specify one of: postgresql, mysql, sqlite."
+                    ));
+                }
+                let command = InitInteractiveCommand {
+                    project_path,
+                    force,
+                    add_gitignore,
+                    with_example,
+                    format,
+                };
+                return handler
+                    .execute_interactive(&command, &mut TerminalPrompter::new())
+                    .await;
+            };
+            let dialect = parse_dialect(&dialect)?;
             let command = InitCommand {
                 project_path,
                 dialect,
@@ -126,6 +207,7 @@ async fn run_command(cli: Cli) -> Result<String> {
                 user: None,
                 password: None,
                 add_gitignore,
+                with_example,
                 format,
             };
             handler.execute(&command)
@@ -134,49 +216,168 @@ async fn run_command(cli: Cli) -> Result<String> {
         Commands::Generate {
             description,
             dry_run,
+            dry_run_sql,
+            down,
             allow_destructive,
+            override_policy,
+            allow_duplicate_override,
+            meta,
+            summary_only,
+            require_clean_pending,
+            allow_stacked,
+            with_preflight_queries,
+            edit,
         } => {
             debug!(
                 description = ?description,
                 dry_run = dry_run.dry_run,
+                dry_run_sql = ?dry_run_sql.mode(),
+                down,
                 allow_destructive = allow_destructive.allow_destructive,
+                override_policy,
+                meta = ?meta,
+                summary_only,
+                require_clean_pending,
+                allow_stacked,
+                with_preflight_queries,
+                edit,
                 "Executing generate command"
             );
-            let handler = GenerateCommandHandler::new();
+            let handler = GenerateCommandHandler::new().with_timings(timings.clone());
             let command = GenerateCommand {
                 project_path,
                 config_path,
                 schema_dir: None,
+                allow_duplicate_override: allow_duplicate_override.allow_duplicate_override,
                 description,
                 dry_run: dry_run.dry_run,
                 allow_destructive: allow_destructive.allow_destructive,
+                override_policy,
                 verbose,
                 format,
+                metadata: meta.into_iter().collect(),
+                summary_only,
+                require_clean_pending,
+                allow_stacked,
+                dry_run_sql: dry_run_sql.mode(),
+                down,
+                with_preflight_queries,
+                edit,
+            };
+            handler.execute(&command)
+        }
+
+        Commands::Contract {
+            older_than,
+            columns,
+            all,
+            description,
+            dry_run,
+        } => {
+            debug!(
+                older_than = ?older_than,
+                columns = ?columns,
+                all,
+                description = ?description,
+                dry_run = dry_run.dry_run,
+                "Executing contract command"
+            );
+            let handler = ContractCommandHandler::new();
+            let command = ContractCommand {
+                project_path,
+                config_path,
+                older_than,
+                columns,
+                all,
+                description,
+                dry_run: dry_run.dry_run,
+                format,
             };
             handler.execute(&command)
         }
 
         Commands::Apply {
             dry_run,
+            dry_run_sql,
             env,
+            wait_timeout,
+            target,
             timeout,
+            lock_timeout,
+            statement_timeout,
             allow_destructive,
+            yes,
+            skip_checksum_verification,
+            break_stale_lock,
+            restore_sequence_values,
+            preflight,
+            preflight_threshold,
+            single_transaction,
+            only,
+            force_out_of_order,
         } => {
             debug!(
                 env = %env.env,
+                target = ?target,
                 dry_run = dry_run.dry_run,
+                dry_run_sql = ?dry_run_sql.mode(),
                 timeout = ?timeout,
+                lock_timeout = ?lock_timeout,
+                statement_timeout = ?statement_timeout,
+                wait_timeout = ?wait_timeout.wait_timeout,
                 allow_destructive = allow_destructive.allow_destructive,
+                yes,
+                skip_checksum_verification,
+                break_stale_lock = break_stale_lock.break_stale_lock,
+                restore_sequence_values = ?restore_sequence_values,
+                preflight,
+                preflight_threshold,
+                single_transaction,
+                only = ?only,
+                force_out_of_order,
                 "Executing apply command"
             );
-            let handler = ApplyCommandHandler::new();
+            let handler = ApplyCommandHandler::new()
+                .with_timings(timings.clone())
+                .with_confirm(Arc::new(TerminalConfirm::new()));
             let command = ApplyCommand {
                 project_path,
                 config_path,
                 dry_run: dry_run.dry_run,
                 env: env.env,
+                target,
                 timeout,
+                lock_timeout,
+                statement_timeout,
                 allow_destructive: allow_destructive.allow_destructive,
+                yes,
+                break_stale_lock: break_stale_lock.break_stale_lock,
+                skip_checksum_verification,
+                format,
+                dry_run_sql: dry_run_sql.mode(),
+                restore_sequence_values,
+                wait_timeout: wait_timeout.wait_timeout,
+                preflight: preflight.then_some(preflight_threshold),
+                single_transaction,
+                only,
+                force_out_of_order,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Seed {
+            env,
+            wait_timeout,
+            only,
+        } => {
+            debug!(env = %env.env, only = ?only, wait_timeout = ?wait_timeout.wait_timeout, "Executing seed command");
+            let handler = SeedCommandHandler::new();
+            let command = SeedCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                only,
+                wait_timeout: wait_timeout.wait_timeout,
                 format,
             };
             handler.execute(&command).await
@@ -184,62 +385,178 @@ async fn run_command(cli: Cli) -> Result<String> {
 
         Commands::Rollback {
             steps,
+            target,
             env,
+            wait_timeout,
             dry_run,
             allow_destructive,
+            break_stale_lock,
+            allow_empty_down,
         } => {
             debug!(
                 env = %env.env,
                 steps = ?steps,
+                target = ?target,
+                wait_timeout = ?wait_timeout.wait_timeout,
                 dry_run = dry_run.dry_run,
                 allow_destructive = allow_destructive.allow_destructive,
+                break_stale_lock = break_stale_lock.break_stale_lock,
+                allow_empty_down,
                 "Executing rollback command"
             );
-            let handler = RollbackCommandHandler::new();
+            let handler = RollbackCommandHandler::new().with_timings(timings.clone());
             let command = RollbackCommand {
                 project_path,
                 config_path,
                 steps,
+                target,
                 env: env.env,
                 dry_run: dry_run.dry_run,
                 allow_destructive: allow_destructive.allow_destructive,
+                break_stale_lock: break_stale_lock.break_stale_lock,
+                allow_empty_down,
                 format,
+                wait_timeout: wait_timeout.wait_timeout,
             };
             handler.execute(&command).await
         }
 
-        Commands::Check { schema_dir } => {
+        Commands::Check {
+            schema_dir,
+            allow_duplicate_override,
+        } => {
             debug!(schema_dir = ?schema_dir, "Executing check command");
             let handler = CheckCommandHandler::new();
             let command = CheckCommand {
                 project_path,
                 config_path,
                 schema_dir,
+                allow_duplicate_override: allow_duplicate_override.allow_duplicate_override,
                 format,
             };
             handler.execute(&command)
         }
 
-        Commands::Validate { schema_dir } => {
+        Commands::Validate {
+            schema_dir,
+            allow_duplicate_override,
+            changed_since,
+            files,
+            file,
+            suggest_fixes,
+            apply_fixes,
+        } => {
             debug!(schema_dir = ?schema_dir, "Executing validate command");
-            let handler = ValidateCommandHandler::new();
+            let handler = ValidateCommandHandler::new().with_timings(timings.clone());
             let command = ValidateCommand {
                 project_path,
                 config_path,
                 schema_dir,
+                allow_duplicate_override: allow_duplicate_override.allow_duplicate_override,
+                changed_since,
+                files,
+                file,
+                suggest_fixes,
+                apply_fixes,
                 format,
             };
             handler.execute(&command)
         }
 
-        Commands::Status { env } => {
-            debug!(env = %env.env, "Executing status command");
-            let handler = StatusCommandHandler::new();
+        Commands::Status {
+            env,
+            wait_timeout,
+            gate,
+        } => {
+            debug!(env = %env.env, wait_timeout = ?wait_timeout.wait_timeout, gates = ?gate, "Executing status command");
+            let handler = StatusCommandHandler::new().with_timings(timings.clone());
             let command = StatusCommand {
                 project_path,
                 config_path,
                 env: env.env,
                 format,
+                gates: gate,
+                verbose,
+                wait_timeout: wait_timeout.wait_timeout,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Diff { env } => {
+            debug!(env = %env.env, "Executing diff command");
+            let handler = DiffCommandHandler::new();
+            let command = DiffCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::History { env, show_sql } => {
+            debug!(env = %env.env, show_sql = ?show_sql, "Executing history command");
+            let handler = HistoryCommandHandler::new();
+            let command = HistoryCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                show_sql,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::VerifyExecution {
+            migration_version,
+            env,
+        } => {
+            debug!(version = %migration_version, env = %env.env, "Executing verify-execution command");
+            let handler = VerifyExecutionCommandHandler::new();
+            let command = VerifyExecutionCommand {
+                project_path,
+                config_path,
+                version: migration_version,
+                env: env.env,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Repair {
+            env,
+            export_applied_sql,
+            renumber,
+            force,
+        } => {
+            debug!(env = %env.env, export_applied_sql = ?export_applied_sql, renumber = ?renumber, force = force, "Executing repair command");
+            let handler = RepairCommandHandler::new();
+            let command = RepairCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                export_applied_sql,
+                renumber,
+                force,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::RenameMigration {
+            migration_version,
+            new_description,
+            env,
+        } => {
+            debug!(version = %migration_version, new_description = %new_description, env = ?env, "Executing rename-migration command");
+            let handler = RenameMigrationCommandHandler::new();
+            let command = RenameMigrationCommand {
+                project_path,
+                config_path,
+                version: migration_version,
+                new_description,
+                env,
+                format,
             };
             handler.execute(&command).await
         }
@@ -247,10 +564,15 @@ async fn run_command(cli: Cli) -> Result<String> {
         Commands::Export {
             output,
             env,
+            wait_timeout,
             force,
             split,
             tables,
             exclude_tables,
+            allow_partial,
+            diff_against,
+            include_sequence_values,
+            from_schema,
         } => {
             debug!(
                 env = %env.env,
@@ -259,6 +581,11 @@ async fn run_command(cli: Cli) -> Result<String> {
                 split = split,
                 tables = ?tables,
                 exclude_tables = ?exclude_tables,
+                allow_partial = allow_partial,
+                diff_against = ?diff_against,
+                include_sequence_values = include_sequence_values,
+                from_schema = from_schema,
+                wait_timeout = ?wait_timeout.wait_timeout,
                 "Executing export command"
             );
             let handler = ExportCommandHandler::new();
@@ -272,21 +599,251 @@ async fn run_command(cli: Cli) -> Result<String> {
                 split,
                 tables,
                 exclude_tables,
+                allow_partial,
+                diff_against,
+                include_sequence_values,
+                from_schema,
+                wait_timeout: wait_timeout.wait_timeout,
             };
             handler.execute(&command).await
         }
+
+        Commands::Docs { out, check } => {
+            debug!(out = ?out, check = check, "Executing docs command");
+            let handler = DocsCommandHandler::new();
+            let command = DocsCommand {
+                project_path,
+                config_path,
+                out_dir: out,
+                check,
+                format,
+            };
+            handler.execute(&command)
+        }
+
+        Commands::Baseline {
+            env,
+            dry_run,
+            force,
+        } => {
+            debug!(
+                env = %env.env,
+                dry_run = dry_run.dry_run,
+                force = force,
+                "Executing baseline command"
+            );
+            let handler = BaselineCommandHandler::new();
+            let command = BaselineCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                dry_run: dry_run.dry_run,
+                force,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Inspect {
+            env,
+            suggest_sizes,
+            tables,
+            columns,
+            allow_full_scan,
+            max_rows,
+        } => {
+            debug!(
+                env = %env.env,
+                suggest_sizes = suggest_sizes,
+                tables = ?tables,
+                columns = ?columns,
+                allow_full_scan = allow_full_scan,
+                max_rows = max_rows,
+                "Executing inspect command"
+            );
+            let handler = InspectCommandHandler::new();
+            let command = InspectCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                suggest_sizes,
+                tables,
+                columns,
+                allow_full_scan,
+                max_rows,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Introspect { env, table, all } => {
+            debug!(env = %env.env, table = ?table, all = all, "Executing introspect command");
+            let handler = IntrospectCommandHandler::new();
+            let command = IntrospectCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                table,
+                all,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Version {
+            check_latest,
+            features,
+        } => {
+            debug!(
+                check_latest = check_latest,
+                features = features,
+                "Executing version command"
+            );
+            let handler = VersionCommandHandler::new();
+            let command = VersionCommand {
+                check_latest,
+                features,
+                format,
+            };
+            handler.execute(&command)
+        }
+
+        Commands::Workspace { .. } if config_path.is_some() => Err(anyhow::anyhow!(
+            "--config cannot be used with workspace commands; each member project uses its own {}",
+            strata::core::config::Config::DEFAULT_CONFIG_PATH
+        )),
+
+        Commands::Workspace { command } => match command {
+            WorkspaceCommands::Status { env, gate } => {
+                debug!(env = %env.env, gates = ?gate, "Executing workspace status command");
+                let handler = WorkspaceCommandHandler::new();
+                let command = WorkspaceStatusCommand {
+                    workspace_path: project_path,
+                    env: env.env,
+                    format,
+                    gates: gate,
+                };
+                handler.execute_status(&command).await
+            }
+        },
+
+        Commands::Reset {
+            env,
+            hard,
+            delete_file,
+            reapply,
+            yes,
+            wait_timeout,
+        } => {
+            debug!(
+                env = %env.env,
+                hard = hard,
+                delete_file = delete_file,
+                reapply = reapply,
+                "Executing reset command"
+            );
+            let handler = ResetCommandHandler::new().with_confirm(Arc::new(TerminalConfirm::new()));
+            let command = ResetCommand {
+                project_path,
+                config_path,
+                env: env.env,
+                hard,
+                delete_file,
+                reapply,
+                yes,
+                wait_timeout: wait_timeout.wait_timeout,
+                format,
+            };
+            handler.execute(&command).await
+        }
+
+        Commands::Dev { command } => match command {
+            DevCommands::Migrate {
+                env,
+                description,
+                watch,
+                poll_interval,
+                debounce,
+            } => {
+                debug!(env = %env.env, description = ?description, watch = watch, "Executing dev migrate command");
+                let is_json = matches!(format, OutputFormat::Json);
+                let handler = DevCommandHandler::new();
+                let command = DevMigrateCommand {
+                    project_path,
+                    config_path,
+                    env: env.env,
+                    description,
+                    format,
+                };
+                if watch {
+                    let options = WatchOptions {
+                        poll_interval: std::time::Duration::from_millis(poll_interval),
+                        debounce: std::time::Duration::from_millis(debounce),
+                    };
+                    handler
+                        .watch_migrate(&command, &options, shutdown_signal(), |event| {
+                            print_watch_event(event, is_json)
+                        })
+                        .await
+                } else {
+                    handler.execute_migrate(&command).await
+                }
+            }
+        },
+    };
+
+    timings.log_summary();
+
+    if let Some((project_path, config_path)) = update_check_target {
+        if let Some(notice) =
+            update_check::notice_for_project(&project_path, config_path.as_deref())
+        {
+            eprintln!("{}", notice.yellow());
+        }
+    }
+
+    result
+}
+
+/// Ctrl+C を受け取ると完了する停止要求
+async fn shutdown_signal() {
+    if tokio::signal::ctrl_c().await.is_err() {
+        // シグナルを受け取れない環境では、プロセスの終了まで監視を続ける
+        std::future::pending::<()>().await;
+    }
+}
+
+/// dev migrate --watch のイベントを表示する
+fn print_watch_event(event: WatchEvent, is_json: bool) {
+    match event {
+        WatchEvent::Started(schema_dir) => {
+            if !is_json {
+                eprintln!(
+                    "Watching {} for changes (press Ctrl+C to stop)",
+                    schema_dir.display()
+                );
+            }
+        }
+        WatchEvent::Completed(output) => println!("{}", output),
+        WatchEvent::Failed { output, error } => {
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+            if is_json {
+                eprintln!("{}", ErrorOutput::new(error).to_json());
+            } else {
+                eprintln!("Error: {}", error);
+            }
+        }
     }
 }
 
 /// Dialect文字列をDialect型に変換する
 fn parse_dialect(dialect: &str) -> Result<Dialect> {
-    match dialect {
-        "postgresql" | "postgres" => Ok(Dialect::PostgreSQL),
-        "mysql" => Ok(Dialect::MySQL),
-        "sqlite" => Ok(Dialect::SQLite),
-        other => Err(anyhow::anyhow!(
+    Dialect::from_name(dialect).ok_or_else(|| {
+        anyhow::anyhow!(
             "Unsupported database dialect: {}. Please specify one of: postgresql, mysql, sqlite.",
-            other
-        )),
-    }
+            dialect
+        )
+    })
 }
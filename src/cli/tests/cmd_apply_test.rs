@@ -9,7 +9,12 @@
 use sqlx::any::install_default_drivers;
 use std::fs;
 use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
-use strata::core::config::{Config, Dialect};
+use strata::cli::commands::dry_run_sql::DryRunSqlMode;
+use strata::cli::commands::stacked_migrations;
+use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::timings::Timings;
+use strata::core::config::{Config, Dialect, LineEnding, SqlOutputConfig};
 use strata::services::config_serializer::ConfigSerializer;
 mod common;
 
@@ -33,8 +38,21 @@ async fn test_apply_command_no_config_file() {
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -69,8 +87,21 @@ async fn test_apply_command_no_pending_migrations() {
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -129,8 +160,21 @@ destructive_changes: {}
         dry_run: true,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     // 2.6: dry-run モードでも DB に接続するようになった
@@ -189,8 +233,21 @@ destructive_changes: {}
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -202,6 +259,72 @@ destructive_changes: {}
     assert!(summary.contains("create_users"));
 }
 
+#[tokio::test]
+#[ignore] // Requires SQLx Any driver linkage - run as integration test
+async fn test_apply_command_reads_crlf_bom_and_custom_terminator() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    // sql_output設定（CRLF・BOM付き・終端文字列 "$$"）
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    let mut config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    config.sql_output = SqlOutputConfig {
+        line_endings: LineEnding::Crlf,
+        bom: true,
+        statement_terminator: "$$".to_string(),
+    };
+
+    let config_path = project_path.join(Config::DEFAULT_CONFIG_PATH);
+    let config_yaml = ConfigSerializer::to_yaml(&config).unwrap();
+    fs::write(&config_path, config_yaml).unwrap();
+
+    let migrations_dir = project_path.join(&config.migrations_dir);
+    let migration_dir = migrations_dir.join("20260121120000_create_users");
+    fs::create_dir_all(&migration_dir).unwrap();
+
+    let up_sql = "\u{feff}CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)$$\r\n\r\nINSERT INTO users (id, name) VALUES (1, 'a;b')$$\r\n";
+    fs::write(migration_dir.join("up.sql"), up_sql).unwrap();
+    fs::write(migration_dir.join("down.sql"), "DROP TABLE users$$\r\n").unwrap();
+
+    let metadata = r#"version: "20260121120000"
+description: "create_users"
+dialect: sqlite
+checksum: "test_checksum"
+destructive_changes: {}
+"#;
+    fs::write(migration_dir.join(".meta.yaml"), metadata).unwrap();
+
+    let handler = ApplyCommandHandler::new();
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+
+    let result = handler.execute(&command).await;
+    assert!(result.is_ok(), "apply failed: {:?}", result.err());
+    assert!(result.unwrap().contains("20260121120000"));
+}
+
 #[tokio::test]
 #[ignore] // Requires SQLx Any driver linkage - run as integration test
 async fn test_apply_command_migration_already_applied() {
@@ -249,8 +372,21 @@ destructive_changes: {}
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     // 1回目の適用
@@ -334,8 +470,21 @@ destructive_changes: {}
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -396,8 +545,21 @@ destructive_changes: {}
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -495,8 +657,21 @@ destructive_changes: {}
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     // Before the fix, this would fail with:
@@ -514,3 +689,1237 @@ destructive_changes: {}
     assert!(summary.contains("20260121120000"));
     assert!(summary.contains("20260121120001"));
 }
+
+#[tokio::test]
+async fn test_apply_command_streams_sql_over_threshold() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    // 閾値を小さくして、全てのup.sqlをストリーミングで実行させる
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    let mut config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    config.stream_sql_threshold_bytes = Some(16);
+    config.store_applied_sql = true;
+    fs::write(
+        project_path.join(Config::DEFAULT_CONFIG_PATH),
+        ConfigSerializer::to_yaml(&config).unwrap(),
+    )
+    .unwrap();
+
+    let mut up_sql = String::from("CREATE TABLE events (id INTEGER PRIMARY KEY, note TEXT);\n");
+    for id in 1..=500 {
+        up_sql.push_str(&format!(
+            "INSERT INTO events (id, note) VALUES ({}, 'row; {}');\n",
+            id, id
+        ));
+    }
+    common::create_test_migration(
+        &project_path,
+        "20260121120000",
+        "load_events",
+        &up_sql,
+        "DROP TABLE events;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let result = ApplyCommandHandler::new().execute(&command).await;
+    assert!(result.is_ok(), "apply failed: {:?}", result.err());
+
+    let pool = sqlx::any::AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 500);
+    let stored: String =
+        sqlx::query_scalar("SELECT up_sql FROM schema_migrations WHERE version = '20260121120000'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(stored, up_sql);
+    pool.close().await;
+}
+
+#[tokio::test]
+async fn test_apply_command_reports_db_timings() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260122120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let timings = Timings::new().with_report(true);
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let output = ApplyCommandHandler::new()
+        .with_timings(timings.clone())
+        .execute(&command)
+        .await
+        .unwrap();
+
+    let phases: Vec<_> = timings.phases().iter().map(|p| p.phase).collect();
+    assert_eq!(phases, vec!["config", "connect", "history", "execute"]);
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["applied_count"], 1);
+    let total = json["timings"]["total_ms"].as_f64().unwrap();
+    let execute = json["timings"]["execute_ms"].as_f64().unwrap();
+    assert!(execute > 0.0 && execute <= total, "{} / {}", execute, total);
+}
+
+#[tokio::test]
+async fn test_apply_command_does_not_write_to_migrations_dir() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260123120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let migrations_dir = project_path.join("migrations");
+    let list_files = || {
+        let mut names: Vec<String> = fs::read_dir(&migrations_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+    let before = list_files();
+
+    let result = ApplyCommandHandler::new().execute(&command).await;
+    assert!(result.is_ok(), "apply failed: {:?}", result.err());
+
+    // 読み取り専用のマイグレーションディレクトリでも適用できるよう、何も書き込まない
+    assert_eq!(list_files(), before);
+}
+
+/// 適用したマイグレーションと適用済みだと確認したマイグレーションを未適用の記録から外す
+#[tokio::test]
+async fn test_apply_command_clears_pending_migrations_record() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260123120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+    let migrations_dir = project_path.join("migrations");
+    stacked_migrations::mark_pending(&migrations_dir, ["20260123120000"]).unwrap();
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let handler = ApplyCommandHandler::new();
+    let result = handler.execute(&command).await;
+    assert!(result.is_ok(), "apply failed: {:?}", result.err());
+    assert!(stacked_migrations::find_pending_migrations(&migrations_dir)
+        .unwrap()
+        .is_empty());
+    assert!(!migrations_dir
+        .join(stacked_migrations::PENDING_MIGRATIONS_FILE)
+        .exists());
+
+    // 未適用のものがなくても、既に適用済みのマイグレーションは記録から外す
+    stacked_migrations::mark_pending(&migrations_dir, ["20260123120000"]).unwrap();
+    let output = handler.execute(&command).await.unwrap();
+    assert!(output.contains("No pending migrations"), "{}", output);
+    assert!(stacked_migrations::find_pending_migrations(&migrations_dir)
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_apply_command_dry_run_sql_only() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260123120000",
+        "create_users",
+        "-- Transaction: strata apply wraps this in a transaction automatically.\n\nCREATE TABLE users (id INTEGER PRIMARY KEY);\n\nCREATE INDEX idx_users_id ON users (id);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let mut command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: true,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: DryRunSqlMode::SqlOnly { explain: false },
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let handler = ApplyCommandHandler::new();
+
+    let output = handler.execute(&command).await.unwrap();
+    assert_eq!(
+        output,
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);\n\nCREATE INDEX idx_users_id ON users (id);"
+    );
+
+    command.dry_run_sql = DryRunSqlMode::Annotated;
+    let output = handler.execute(&command).await.unwrap();
+    assert!(output.starts_with("-- Migration: 20260123120000 - create_users\n"));
+    assert!(output.contains("-- object: index idx_users_id on users\n-- source: 20260123120000_create_users/up.sql\n-- destructive: no\nCREATE INDEX"));
+
+    // SQLとして出力するモードはJSON出力と組み合わせられない
+    command.format = strata::cli::OutputFormat::Json;
+    let err = handler.execute(&command).await.unwrap_err();
+    assert!(err.to_string().contains("--format json"));
+}
+
+/// --override-policy で生成したマイグレーションの警告は適用前に判定し、JSON出力の warnings に含める
+#[tokio::test]
+async fn test_apply_command_json_reports_overridden_policy() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_migration_with_meta(
+        &project_path,
+        "20260123120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);\nCREATE TABLE posts (id INTEGER PRIMARY KEY);",
+        r#"version: "20260123120000"
+description: "create_users"
+dialect: "sqlite"
+checksum: "checksum1"
+destructive_changes: {}
+policy:
+  statement_count: 2
+  tables_touched: [posts, users]
+  violations:
+    - rule: max_statements
+      message: "Migration has 2 statements (max_statements: 1)"
+  overridden: true
+"#,
+    );
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let output = ApplyCommandHandler::new().execute(&command).await.unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["applied_count"], 1);
+    let warnings: Vec<&str> = json["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w.as_str().unwrap())
+        .collect();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert!(warnings[0].contains("Migration 20260123120000 was generated with --override-policy"));
+    assert!(warnings[0].contains("Migration has 2 statements (max_statements: 1)"));
+}
+
+#[tokio::test]
+async fn test_apply_command_omits_timings_by_default() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    fs::create_dir_all(project_path.join("migrations")).unwrap();
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let output = ApplyCommandHandler::new().execute(&command).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(json.get("timings").is_none());
+}
+
+#[tokio::test]
+async fn test_apply_command_restores_sequence_values() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    fs::create_dir_all(project_path.join("migrations")).unwrap();
+
+    // 構造だけを複製した環境（AUTOINCREMENT の users と、AUTOINCREMENT のない tags）
+    let pool = sqlx::AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tags (id INTEGER PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let values_path = project_path.join("sequence_values.yaml");
+    fs::write(
+        &values_path,
+        r#"version: "1.0"
+sequences:
+  - table: legacy_orders
+    value: 10
+  - table: tags
+    value: 5
+  - table: users
+    value: 1042
+"#,
+    )
+    .unwrap();
+
+    let mut command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: true,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: Some(values_path.clone()),
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let handler = ApplyCommandHandler::new();
+
+    // dry-run では実行するSQLのみを表示する
+    let output = handler.execute(&command).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        json["sequence_values"]["statements"],
+        serde_json::json!([
+            "DELETE FROM sqlite_sequence WHERE name = 'users'",
+            "INSERT INTO sqlite_sequence (name, seq) VALUES ('users', 1042)",
+        ])
+    );
+    let skipped: Vec<&str> = json["sequence_values"]["skipped"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["table"].as_str().unwrap())
+        .collect();
+    assert_eq!(skipped, vec!["legacy_orders", "tags"]);
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_sequence")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    command.dry_run = false;
+    command.format = strata::cli::OutputFormat::Text;
+    let output = handler.execute(&command).await.unwrap();
+    assert!(
+        output.contains("Restored sequence values from"),
+        "{}",
+        output
+    );
+    assert!(output.contains("  - users = 1042\n"), "{}", output);
+    assert!(
+        output.contains("legacy_orders: table does not exist"),
+        "{}",
+        output
+    );
+
+    // 復元した値の続きから採番される
+    sqlx::query("INSERT INTO users (name) VALUES ('alice')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (id,): (i64,) = sqlx::query_as("SELECT id FROM users WHERE name = 'alice'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(id, 1043);
+}
+
+#[tokio::test]
+async fn test_apply_command_rejects_invalid_sequence_values_file() {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    let values_path = project_path.join("sequence_values.yaml");
+    fs::write(
+        &values_path,
+        "version: \"1.0\"\nsequences:\n  - table: users\n    value: -1\n",
+    )
+    .unwrap();
+
+    let command = ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: Some(values_path),
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let err = ApplyCommandHandler::new()
+        .execute(&command)
+        .await
+        .unwrap_err();
+
+    // マイグレーションの適用前に失敗する
+    assert!(
+        format!("{:#}", err).contains("positive integer"),
+        "{:#}",
+        err
+    );
+}
+
+/// 3つのマイグレーションを持つ SQLite プロジェクトを作成
+fn setup_target_project() -> (tempfile::TempDir, std::path::PathBuf) {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    for (version, table) in [
+        ("20260121120000", "users"),
+        ("20260121120001", "posts"),
+        ("20260121120002", "tags"),
+    ] {
+        common::create_test_migration(
+            &project_path,
+            version,
+            &format!("create_{}", table),
+            &format!("CREATE TABLE {} (id INTEGER PRIMARY KEY);", table),
+            &format!("DROP TABLE {};", table),
+            &format!("checksum_{}", version),
+        )
+        .unwrap();
+    }
+    (temp_dir, project_path)
+}
+
+fn target_command(project_path: &std::path::Path, target: &str, dry_run: bool) -> ApplyCommand {
+    ApplyCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        dry_run,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: Some(target.to_string()),
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    }
+}
+
+fn applied_versions_in_output(output: &str) -> Vec<String> {
+    let json: serde_json::Value = serde_json::from_str(output).unwrap();
+    json["migrations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["version"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_apply_command_target_stops_after_version() {
+    let (_temp_dir, project_path) = setup_target_project();
+    let handler = ApplyCommandHandler::new();
+
+    // dry-run は適用されるマイグレーションを順に列挙する
+    let output = handler
+        .execute(&target_command(&project_path, "20260121120001", true))
+        .await
+        .unwrap();
+    assert_eq!(
+        applied_versions_in_output(&output),
+        vec!["20260121120000", "20260121120001"]
+    );
+
+    let output = handler
+        .execute(&target_command(&project_path, "20260121120001", false))
+        .await
+        .unwrap();
+    assert_eq!(
+        applied_versions_in_output(&output),
+        vec!["20260121120000", "20260121120001"]
+    );
+
+    // 既に指定バージョンにある場合は何もしない
+    let mut command = target_command(&project_path, "20260121120001", false);
+    command.format = strata::cli::OutputFormat::Text;
+    let output = handler.execute(&command).await.unwrap();
+    assert!(
+        output.contains("Database is already at version 20260121120001. No migrations to apply."),
+        "{}",
+        output
+    );
+
+    // 適用済みの最新より古いバージョンはエラー
+    let err = handler
+        .execute(&target_command(&project_path, "20260121120000", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Target version 20260121120000 is older than the latest applied migration 20260121120001"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("strata rollback --target 20260121120000"),
+        "{}",
+        err
+    );
+
+    // 残りを適用
+    let output = handler
+        .execute(&target_command(&project_path, "20260121120002", false))
+        .await
+        .unwrap();
+    assert_eq!(applied_versions_in_output(&output), vec!["20260121120002"]);
+}
+
+/// SQLite はセッションのタイムアウトを持たないため、--lock-timeout / --statement-timeout を無視して適用する
+#[tokio::test]
+async fn test_apply_command_sqlite_ignores_session_timeouts() {
+    let (_temp_dir, project_path) = setup_target_project();
+    let mut command = target_command(&project_path, "20260121120002", false);
+    command.lock_timeout = Some(5);
+    command.statement_timeout = Some(60);
+
+    let output = ApplyCommandHandler::new().execute(&command).await.unwrap();
+    assert_eq!(
+        applied_versions_in_output(&output),
+        vec!["20260121120000", "20260121120001", "20260121120002"]
+    );
+}
+
+#[tokio::test]
+async fn test_apply_command_unknown_target_fails_before_connecting() {
+    let (_temp_dir, project_path) = setup_target_project();
+    // 接続できないデータベースを指定しても、接続前にバージョンの誤りで失敗する
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&project_path.join("missing/dir/test.db").to_string_lossy()),
+    );
+    let handler = ApplyCommandHandler::new();
+
+    let err = handler
+        .execute(&target_command(&project_path, "20260121129999", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Unknown target version '20260121129999'"),
+        "{}",
+        err
+    );
+
+    let err = handler
+        .execute(&target_command(&project_path, "latest", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Invalid target version 'latest'"), "{}", err);
+}
+
+/// マイグレーションの .meta.yaml に変更するテーブルを記録する（generate が記録する内容）
+fn record_touched_tables(project_path: &std::path::Path, migration: &str, tables: &[&str]) {
+    let meta_path = project_path
+        .join("migrations")
+        .join(migration)
+        .join(".meta.yaml");
+    let meta = fs::read_to_string(&meta_path).unwrap();
+    let list: String = tables.iter().map(|t| format!("- {}\n", t)).collect();
+    fs::write(&meta_path, format!("{}touched_tables:\n{}", meta, list)).unwrap();
+}
+
+/// users を書き換えるマイグレーションの後に、users と posts をそれぞれ変更するマイグレーションがある
+fn setup_out_of_order_project() -> (tempfile::TempDir, std::path::PathBuf) {
+    let (temp_dir, project_path) = setup_target_project();
+    fs::write(
+        project_path.join("migrations/20260121120001_create_posts/up.sql"),
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY);\nCREATE INDEX idx_posts_id ON posts (id);",
+    )
+    .unwrap();
+    record_touched_tables(&project_path, "20260121120000_create_users", &["users"]);
+    record_touched_tables(&project_path, "20260121120001_create_posts", &["posts"]);
+    record_touched_tables(
+        &project_path,
+        "20260121120002_create_tags",
+        &["tags", "users"],
+    );
+    (temp_dir, project_path)
+}
+
+fn only_command(project_path: &std::path::Path, version: &str, force: bool) -> ApplyCommand {
+    ApplyCommand {
+        target: None,
+        only: Some(version.to_string()),
+        force_out_of_order: force,
+        ..target_command(project_path, version, false)
+    }
+}
+
+#[tokio::test]
+async fn test_apply_command_only_applies_single_migration() {
+    let (_temp_dir, project_path) = setup_out_of_order_project();
+    let handler = ApplyCommandHandler::new();
+
+    // posts は先行する users のマイグレーションと重ならないため単独で適用できる
+    let output = handler
+        .execute(&only_command(&project_path, "20260121120001", false))
+        .await
+        .unwrap();
+    assert_eq!(applied_versions_in_output(&output), vec!["20260121120001"]);
+    assert!(!table_exists(&project_path, "users").await);
+    assert!(table_exists(&project_path, "posts").await);
+
+    // 適用済みのものを再度指定するとエラー
+    let err = handler
+        .execute(&only_command(&project_path, "20260121120001", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Migration 20260121120001 is already applied"),
+        "{}",
+        err
+    );
+
+    // status は先に適用したものを out of order と表示する
+    let status_command = StatusCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
+    };
+    let status = StatusCommandHandler::new()
+        .execute(&status_command)
+        .await
+        .unwrap();
+    assert!(status.contains("✓ Applied (out of order)"), "{}", status);
+    assert!(status.contains("applied out of order"), "{}", status);
+
+    // 後の通常の apply は単独で適用したものを飛ばす
+    let mut command = target_command(&project_path, "20260121120002", false);
+    command.target = None;
+    let output = handler.execute(&command).await.unwrap();
+    assert_eq!(
+        applied_versions_in_output(&output),
+        vec!["20260121120000", "20260121120002"]
+    );
+}
+
+#[tokio::test]
+async fn test_apply_command_only_refuses_conflicting_earlier_migrations() {
+    let (_temp_dir, project_path) = setup_out_of_order_project();
+    let handler = ApplyCommandHandler::new();
+
+    // tags のマイグレーションは先行する未適用の users のマイグレーションと users が重なる
+    let err = handler
+        .execute(&only_command(&project_path, "20260121120002", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Migration 20260121120002 cannot be applied out of order"),
+        "{}",
+        err
+    );
+    assert!(err.contains("- 20260121120000 (tables: users)"), "{}", err);
+    assert!(!err.contains("- 20260121120001"), "{}", err);
+    assert!(err.contains("--force-out-of-order"), "{}", err);
+    assert!(!table_exists(&project_path, "tags").await);
+
+    // --force-out-of-order で競合を無視して適用する
+    let mut command = only_command(&project_path, "20260121120002", true);
+    command.format = strata::cli::OutputFormat::Text;
+    let output = handler.execute(&command).await.unwrap();
+    assert!(
+        output.contains("Applying 20260121120002 out of order despite conflicts"),
+        "{}",
+        output
+    );
+    assert!(table_exists(&project_path, "tags").await);
+    assert!(!table_exists(&project_path, "users").await);
+}
+
+#[tokio::test]
+async fn test_apply_command_only_treats_unrecorded_tables_as_conflict() {
+    // 変更するテーブルの記録がない（古い strata で生成された）マイグレーションは判定できない
+    let (_temp_dir, project_path) = setup_target_project();
+    record_touched_tables(&project_path, "20260121120001_create_posts", &["posts"]);
+    let handler = ApplyCommandHandler::new();
+
+    let err = handler
+        .execute(&only_command(&project_path, "20260121120001", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("- 20260121120000 (touched tables are not recorded in .meta.yaml)"),
+        "{}",
+        err
+    );
+}
+
+/// マイグレーションの .meta.yaml に現在の up.sql のチェックサムを記録する
+fn record_up_sql_checksum(project_path: &std::path::Path, migration: &str) {
+    let migration_dir = project_path.join("migrations").join(migration);
+    let up_sql = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+    let up_sql_checksum = strata::services::schema_checksum::SchemaChecksumService::new()
+        .calculate_sql_checksum(&up_sql);
+    let meta_path = migration_dir.join(".meta.yaml");
+    let meta = fs::read_to_string(&meta_path).unwrap();
+    fs::write(
+        &meta_path,
+        format!("{}up_sql_checksum: {}\n", meta, up_sql_checksum),
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_apply_command_refuses_when_applied_up_sql_was_modified() {
+    let (_temp_dir, project_path) = setup_target_project();
+    let up_sql_path = project_path.join("migrations/20260121120000_create_users/up.sql");
+    let up_sql = "-- create users\nCREATE TABLE users (id INTEGER PRIMARY KEY);\n";
+    fs::write(&up_sql_path, up_sql).unwrap();
+    record_up_sql_checksum(&project_path, "20260121120000_create_users");
+    let handler = ApplyCommandHandler::new();
+    handler
+        .execute(&target_command(&project_path, "20260121120000", false))
+        .await
+        .unwrap();
+
+    // Windows でのチェックアウトのような改行コードの違いは許容する
+    fs::write(
+        &up_sql_path,
+        format!("\u{feff}{}", up_sql.replace('\n', "\r\n")),
+    )
+    .unwrap();
+    handler
+        .execute(&target_command(&project_path, "20260121120001", false))
+        .await
+        .unwrap();
+
+    // 適用済みの up.sql を書き換えると適用を中止する
+    fs::write(
+        &up_sql_path,
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+    )
+    .unwrap();
+    let err = handler
+        .execute(&target_command(&project_path, "20260121120002", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("1 applied migration(s) failed checksum verification"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("20260121120000: up.sql was modified after it was applied"),
+        "{}",
+        err
+    );
+    assert!(err.contains("--skip-checksum-verification"), "{}", err);
+
+    // --skip-checksum-verification では警告に留めて適用する
+    let mut command = target_command(&project_path, "20260121120002", false);
+    command.skip_checksum_verification = true;
+    let output = handler.execute(&command).await.unwrap();
+    assert_eq!(applied_versions_in_output(&output), vec!["20260121120002"]);
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(json["warnings"].as_array().unwrap().iter().any(|w| w
+        .as_str()
+        .unwrap()
+        .contains("Checksum verification skipped for modified migration 20260121120000")));
+}
+
+/// 追加する制約の事前確認クエリ（CHECK・外部キー）を用意したプロジェクト
+///
+/// 1件目のマイグレーションが違反する行を含むデータを作成し、
+/// 2件目のマイグレーションの preflight.sql がそれを数える。
+fn setup_preflight_project() -> (tempfile::TempDir, std::path::PathBuf) {
+    use strata::core::schema::Constraint;
+    use strata::core::schema_diff::{SchemaDiff, TableDiff};
+    use strata::services::preflight_queries::{
+        render_preflight_file, PreflightQueryBuilder, PREFLIGHT_FILE,
+    };
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260121120000",
+        "create_posts",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);\n\
+         CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER, score INTEGER);\n\
+         INSERT INTO users (id) VALUES (1);\n\
+         INSERT INTO posts (id, user_id, score) VALUES (1, 1, 10), (2, 2, -1), (3, 3, -5), (4, NULL, NULL);",
+        "DROP TABLE posts;\nDROP TABLE users;",
+        "checksum_posts",
+    )
+    .unwrap();
+    common::create_test_migration(
+        &project_path,
+        "20260121120001",
+        "add_post_constraints",
+        "CREATE INDEX idx_posts_score ON posts (score);",
+        "DROP INDEX idx_posts_score;",
+        "checksum_constraints",
+    )
+    .unwrap();
+
+    let mut table_diff = TableDiff::new("posts".to_string());
+    table_diff.added_constraints.push(Constraint::CHECK {
+        columns: vec!["score".to_string()],
+        check_expression: "score >= 0".to_string(),
+    });
+    table_diff.added_constraints.push(Constraint::FOREIGN_KEY {
+        columns: vec!["user_id".to_string()],
+        referenced_table: "users".to_string(),
+        referenced_columns: vec!["id".to_string()],
+        on_delete: None,
+        on_update: None,
+    });
+    let mut diff = SchemaDiff::new();
+    diff.modified_tables.push(table_diff);
+    let queries = PreflightQueryBuilder::new(Dialect::SQLite).build(&diff);
+    fs::write(
+        project_path
+            .join("migrations/20260121120001_add_post_constraints")
+            .join(PREFLIGHT_FILE),
+        render_preflight_file(&queries),
+    )
+    .unwrap();
+
+    (temp_dir, project_path)
+}
+
+#[tokio::test]
+async fn test_apply_command_preflight_aborts_on_violations() {
+    install_default_drivers();
+    let (_temp_dir, project_path) = setup_preflight_project();
+
+    let handler = ApplyCommandHandler::new();
+    let mut command = target_command(&project_path, "20260121120001", false);
+    command.target = None;
+    command.preflight = Some(0);
+
+    let err = handler.execute(&command).await.unwrap_err().to_string();
+    assert!(err.contains("Migration 20260121120001"), "{}", err);
+    assert!(
+        err.contains("CHECK posts.score (score >= 0): 2 row(s)"),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("FOREIGN KEY posts(user_id) -> users(id): 2 row(s)"),
+        "{}",
+        err
+    );
+    assert!(err.contains("--preflight-threshold"), "{}", err);
+
+    // 違反を含むマイグレーションは適用されていない
+    let pool = sqlx::any::AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&format!(
+            "sqlite://{}",
+            project_path.join("test.db").display()
+        ))
+        .await
+        .unwrap();
+    let applied: Vec<String> =
+        sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(applied, vec!["20260121120000".to_string()]);
+    pool.close().await;
+
+    // 許容数以内であれば適用する
+    command.preflight = Some(2);
+    let output = handler.execute(&command).await.unwrap();
+    assert_eq!(applied_versions_in_output(&output), vec!["20260121120001"]);
+}
+
+#[tokio::test]
+async fn test_apply_command_ignores_preflight_without_flag() {
+    install_default_drivers();
+    let (_temp_dir, project_path) = setup_preflight_project();
+
+    let mut command = target_command(&project_path, "20260121120001", false);
+    command.target = None;
+    let output = ApplyCommandHandler::new().execute(&command).await.unwrap();
+    assert_eq!(
+        applied_versions_in_output(&output),
+        vec!["20260121120000", "20260121120001"]
+    );
+}
+
+/// 2件目のマイグレーションが失敗するプロジェクト
+fn setup_failing_project() -> (tempfile::TempDir, std::path::PathBuf) {
+    install_default_drivers();
+    let temp_dir = tempfile::tempdir().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    for (version, description, up_sql) in [
+        (
+            "20260121120000",
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        ),
+        ("20260121120001", "broken", "INVALID SQL;"),
+        (
+            "20260121120002",
+            "create_tags",
+            "CREATE TABLE tags (id INTEGER PRIMARY KEY);",
+        ),
+    ] {
+        common::create_test_migration(
+            &project_path,
+            version,
+            description,
+            up_sql,
+            "",
+            &format!("checksum_{}", version),
+        )
+        .unwrap();
+    }
+    (temp_dir, project_path)
+}
+
+async fn apply_failure_output(
+    project_path: &std::path::Path,
+    single_transaction: bool,
+) -> serde_json::Value {
+    let mut command = target_command(project_path, "20260121120002", false);
+    command.target = None;
+    command.single_transaction = single_transaction;
+    let error = ApplyCommandHandler::new()
+        .execute(&command)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("INVALID SQL"));
+    let completed = error.downcast_ref::<CompletedWithFailure>().unwrap();
+    serde_json::from_str(&completed.output).unwrap()
+}
+
+async fn table_exists(project_path: &std::path::Path, table: &str) -> bool {
+    let pool = sqlx::AnyPool::connect(&format!(
+        "sqlite://{}",
+        project_path.join("test.db").display()
+    ))
+    .await
+    .unwrap();
+    let count: i64 = sqlx::Row::get(
+        &sqlx::query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_one(&pool)
+            .await
+            .unwrap(),
+        0,
+    );
+    pool.close().await;
+    count > 0
+}
+
+fn statuses(output: &serde_json::Value) -> Vec<&str> {
+    output["migrations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["status"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_apply_command_failure_keeps_earlier_migrations() {
+    let (_temp_dir, project_path) = setup_failing_project();
+
+    let output = apply_failure_output(&project_path, false).await;
+    assert_eq!(statuses(&output), vec!["applied", "failed", "pending"]);
+    assert_eq!(output["applied_count"], 1);
+    assert!(table_exists(&project_path, "users").await);
+    assert!(!table_exists(&project_path, "tags").await);
+}
+
+#[tokio::test]
+async fn test_apply_command_single_transaction_rolls_back_all() {
+    let (_temp_dir, project_path) = setup_failing_project();
+
+    let output = apply_failure_output(&project_path, true).await;
+    assert_eq!(statuses(&output), vec!["rolled_back", "failed", "pending"]);
+    assert_eq!(output["applied_count"], 0);
+    assert!(!table_exists(&project_path, "users").await);
+}
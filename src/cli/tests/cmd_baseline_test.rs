@@ -0,0 +1,191 @@
+// baselineコマンドハンドラーのテスト
+//
+// 既存のデータベースを取り込み、初期マイグレーションを適用済みとして記録する baseline を検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use sqlx::AnyPool;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::baseline::{BaselineCommand, BaselineCommandHandler};
+use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// Strata で管理されていないテーブルを持つ SQLite プロジェクトを作成する
+async fn setup_existing_database() -> (TempDir, PathBuf) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    let pool = AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    for sql in [
+        "CREATE TABLE users (id INTEGER NOT NULL PRIMARY KEY, email TEXT NOT NULL UNIQUE)",
+        "CREATE TABLE posts (id INTEGER NOT NULL PRIMARY KEY, user_id INTEGER NOT NULL, title TEXT, FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE)",
+        "CREATE INDEX idx_posts_user_id ON posts (user_id)",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+    pool.close().await;
+
+    (temp_dir, project_path)
+}
+
+fn baseline_command(project_path: &Path, dry_run: bool, force: bool) -> BaselineCommand {
+    BaselineCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        dry_run,
+        force,
+        format: OutputFormat::Text,
+    }
+}
+
+fn generate_dry_run(project_path: &Path) -> String {
+    GenerateCommandHandler::new()
+        .execute(&GenerateCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: None,
+            dry_run: true,
+            allow_destructive: false,
+            override_policy: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        })
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_baseline_records_initial_migration_as_applied() {
+    let (_temp_dir, project_path) = setup_existing_database().await;
+
+    let output = BaselineCommandHandler::new()
+        .execute(&baseline_command(&project_path, false, false))
+        .await
+        .unwrap();
+    assert!(output.contains("=== Baseline Complete ==="), "{}", output);
+    assert!(output.contains("  - posts\n  - users\n"), "{}", output);
+
+    let schema_dir = project_path.join("schema");
+    assert!(schema_dir.join("users.yaml").exists());
+    assert!(schema_dir.join("posts.yaml").exists());
+    assert!(project_path
+        .join("migrations")
+        .join(".schema_snapshot.yaml")
+        .exists());
+
+    // 初期マイグレーションは適用済み（チェックサムも一致）として扱われる
+    let status = StatusCommandHandler::new()
+        .execute(&StatusCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            env: "development".to_string(),
+            format: OutputFormat::Json,
+            gates: vec![],
+            verbose: false,
+            wait_timeout: None,
+        })
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&status).unwrap();
+    let migrations = json["migrations"].as_array().unwrap();
+    assert_eq!(migrations.len(), 1, "{}", status);
+    assert_eq!(migrations[0]["description"], "baseline");
+    assert_eq!(migrations[0]["status"], "applied");
+
+    // 書き出したスキーマとデータベースの間に差分はない
+    let output = generate_dry_run(&project_path);
+    assert!(output.contains("No schema changes"), "{}", output);
+}
+
+#[tokio::test]
+async fn test_baseline_dry_run_writes_nothing() {
+    let (_temp_dir, project_path) = setup_existing_database().await;
+
+    let output = BaselineCommandHandler::new()
+        .execute(&baseline_command(&project_path, true, false))
+        .await
+        .unwrap();
+
+    assert!(output.contains("=== Dry Run: Baseline ==="), "{}", output);
+    assert!(!project_path.join("schema").exists());
+    assert!(!project_path.join("migrations").exists());
+
+    // 履歴も記録されないため、続けて baseline を実行できる
+    BaselineCommandHandler::new()
+        .execute(&baseline_command(&project_path, false, false))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_baseline_refuses_database_with_applied_migrations() {
+    let (_temp_dir, project_path) = setup_existing_database().await;
+    let handler = BaselineCommandHandler::new();
+    handler
+        .execute(&baseline_command(&project_path, false, false))
+        .await
+        .unwrap();
+
+    // マイグレーションを削除しても、履歴テーブルに記録があれば拒否する
+    fs::remove_dir_all(project_path.join("migrations")).unwrap();
+    let err = handler
+        .execute(&baseline_command(&project_path, false, true))
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("already has 1 applied migration(s)"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_baseline_requires_force_to_overwrite_snapshot() {
+    let (_temp_dir, project_path) = setup_existing_database().await;
+    let migrations_dir = project_path.join("migrations");
+    fs::create_dir_all(&migrations_dir).unwrap();
+    fs::write(
+        migrations_dir.join(".schema_snapshot.yaml"),
+        "version: \"1.0\"\ntables: {}\n",
+    )
+    .unwrap();
+
+    let err = BaselineCommandHandler::new()
+        .execute(&baseline_command(&project_path, false, false))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--force"), "{}", err);
+
+    BaselineCommandHandler::new()
+        .execute(&baseline_command(&project_path, false, true))
+        .await
+        .unwrap();
+    let snapshot = fs::read_to_string(migrations_dir.join(".schema_snapshot.yaml")).unwrap();
+    assert!(snapshot.contains("users:"), "{}", snapshot);
+}
@@ -24,6 +24,7 @@ fn test_check_command_struct() {
         project_path: PathBuf::from("/test/path"),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -41,6 +42,7 @@ fn test_check_no_config_file() {
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -84,6 +86,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -126,6 +129,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -179,6 +183,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -226,6 +231,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Json,
     };
 
@@ -265,6 +271,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Json,
     };
 
@@ -310,6 +317,7 @@ tables:
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: Some(custom_schema_dir),
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -371,6 +379,7 @@ tables:
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: Some(custom_schema_dir),
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -432,6 +441,7 @@ tables:
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -485,6 +495,7 @@ tables:
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -523,6 +534,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -554,6 +566,7 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         format: strata::cli::OutputFormat::Text,
     };
 
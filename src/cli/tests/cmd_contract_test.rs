@@ -0,0 +1,233 @@
+/// contractコマンドハンドラーのテスト
+///
+/// 削除待ち（`lifecycle: contract_pending`）のカラムが generate で削除されずに残り、
+/// contract で明示的に削除されることを確認します。
+mod common;
+
+#[cfg(test)]
+mod contract_command_tests {
+    use super::common;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::path::Path;
+    use strata::cli::commands::contract::{ContractCommand, ContractCommandHandler};
+    use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+    use strata::cli::OutputFormat;
+    use strata::core::config::Dialect;
+
+    fn write_users_schema(project_path: &Path, legacy_email: Option<&str>) {
+        let legacy_email = legacy_email
+            .map(|lifecycle| {
+                format!(
+                    "      - name: legacy_email\n        type:\n          kind: TEXT\n        nullable: true\n{}",
+                    lifecycle
+                )
+            })
+            .unwrap_or_default();
+        let yaml = format!(
+            r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: email
+        type:
+          kind: TEXT
+        nullable: true
+{}    primary_key:
+      - id
+"#,
+            legacy_email
+        );
+        fs::write(project_path.join("schema").join("users.yaml"), yaml).unwrap();
+    }
+
+    fn generate(project_path: &Path) -> String {
+        GenerateCommandHandler::new()
+            .execute(&GenerateCommand {
+                project_path: project_path.to_path_buf(),
+                config_path: None,
+                schema_dir: None,
+                allow_duplicate_override: false,
+                description: None,
+                dry_run: false,
+                allow_destructive: false,
+                override_policy: false,
+                verbose: false,
+                format: OutputFormat::Text,
+                metadata: BTreeMap::new(),
+                summary_only: false,
+                require_clean_pending: false,
+                allow_stacked: false,
+                dry_run_sql: Default::default(),
+                down: false,
+                with_preflight_queries: false,
+                edit: false,
+            })
+            .unwrap()
+    }
+
+    fn contract_command(project_path: &Path) -> ContractCommand {
+        ContractCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            older_than: None,
+            columns: Vec::new(),
+            all: false,
+            description: None,
+            dry_run: false,
+            format: OutputFormat::Text,
+        }
+    }
+
+    fn migration_dirs(project_path: &Path) -> Vec<std::path::PathBuf> {
+        let mut dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    fn latest_snapshot(project_path: &Path) -> String {
+        let latest = migration_dirs(project_path).pop().unwrap();
+        fs::read_to_string(latest.join(".schema_snapshot.yaml")).unwrap()
+    }
+
+    /// users.legacy_email を作成し、contract_pending にしてからスキーマ定義から取り除く
+    fn setup_contract_pending_project() -> (tempfile::TempDir, std::path::PathBuf) {
+        let (temp_dir, project_path) =
+            common::setup_test_project(Dialect::PostgreSQL, None, true).unwrap();
+        write_users_schema(&project_path, Some(""));
+        generate(&project_path);
+
+        // 注釈のみの変更はマイグレーションを作らず、スナップショットに日付を記録する
+        write_users_schema(&project_path, Some("        lifecycle: contract_pending\n"));
+        let output = generate(&project_path);
+        assert!(output.contains("No schema changes found"), "{}", output);
+        assert!(
+            output.contains("users.legacy_email: contract_pending (since "),
+            "{}",
+            output
+        );
+        assert_eq!(migration_dirs(&project_path).len(), 1);
+        assert!(latest_snapshot(&project_path).contains("contract_pending_since:"));
+
+        write_users_schema(&project_path, None);
+        (temp_dir, project_path)
+    }
+
+    /// スキーマ定義から取り除いた削除待ちカラムは generate で削除されない
+    #[test]
+    fn test_generate_keeps_removed_contract_pending_column() {
+        let (_temp_dir, project_path) = setup_contract_pending_project();
+
+        let output = generate(&project_path);
+
+        assert!(output.contains("No schema changes found"), "{}", output);
+        assert_eq!(migration_dirs(&project_path).len(), 1);
+        assert!(latest_snapshot(&project_path).contains("legacy_email"));
+    }
+
+    /// 一覧表示では経過日数が足りないカラムを削除できないことを示す
+    #[test]
+    fn test_contract_lists_pending_columns() {
+        let (_temp_dir, project_path) = setup_contract_pending_project();
+
+        let output = ContractCommandHandler::new()
+            .execute(&contract_command(&project_path))
+            .unwrap();
+
+        assert!(output.contains("minimum age: 14 days"), "{}", output);
+        assert!(output.contains("users.legacy_email"), "{}", output);
+        assert!(output.contains("(0 days)  too recent"), "{}", output);
+
+        let mut command = contract_command(&project_path);
+        command.columns = vec!["users.legacy_email".to_string()];
+        let err = ContractCommandHandler::new()
+            .execute(&command)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("less than 14"), "{}", err);
+        assert_eq!(migration_dirs(&project_path).len(), 1);
+    }
+
+    /// 選択したカラムを削除するマイグレーションを生成し、スナップショットからも取り除く
+    #[test]
+    fn test_contract_generates_drop_migration() {
+        let (_temp_dir, project_path) = setup_contract_pending_project();
+
+        // マイグレーションのバージョン（秒単位のタイムスタンプ）の重複を避ける
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        let mut command = contract_command(&project_path);
+        command.older_than = Some(0);
+        command.columns = vec!["users.legacy_email".to_string()];
+        ContractCommandHandler::new().execute(&command).unwrap();
+
+        let dirs = migration_dirs(&project_path);
+        assert_eq!(dirs.len(), 2);
+        let latest = dirs.last().unwrap();
+        assert!(latest
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .ends_with("contract_users_legacy_email"));
+        let up_sql = fs::read_to_string(latest.join("up.sql")).unwrap();
+        assert!(
+            up_sql.contains("DROP COLUMN \"legacy_email\""),
+            "{}",
+            up_sql
+        );
+        assert!(!latest_snapshot(&project_path).contains("legacy_email"));
+
+        // 削除後は generate で差分が出ない
+        let output = generate(&project_path);
+        assert!(output.contains("Schema is up to date"), "{}", output);
+    }
+
+    /// スキーマ定義に残っているカラムは削除できない
+    #[test]
+    fn test_contract_rejects_column_still_in_schema() {
+        let (_temp_dir, project_path) = setup_contract_pending_project();
+        write_users_schema(&project_path, Some("        lifecycle: contract_pending\n"));
+
+        let mut command = contract_command(&project_path);
+        command.older_than = Some(0);
+        command.columns = vec![
+            "users.legacy_email".to_string(),
+            "users.missing".to_string(),
+        ];
+        let err = ContractCommandHandler::new()
+            .execute(&command)
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err.contains("users.legacy_email is still defined"),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("users.missing is not a contract_pending column"),
+            "{}",
+            err
+        );
+
+        // --all では削除できるカラムがなければ何も生成しない
+        let mut command = contract_command(&project_path);
+        command.older_than = Some(0);
+        command.all = true;
+        let output = ContractCommandHandler::new().execute(&command).unwrap();
+        assert!(
+            output.contains("No contract_pending columns are ready"),
+            "{}",
+            output
+        );
+        assert_eq!(migration_dirs(&project_path).len(), 1);
+    }
+}
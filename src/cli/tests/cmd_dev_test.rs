@@ -0,0 +1,199 @@
+// devコマンドハンドラーのテスト
+//
+// generate と apply をまとめて実行する dev migrate と、その watch モードを検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use sqlx::AnyPool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use strata::cli::commands::dev::watch::WatchOptions;
+use strata::cli::commands::dev::{DevCommandHandler, DevMigrateCommand, WatchEvent};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// users テーブルを定義した SQLite プロジェクトを作成する
+fn setup_project() -> (TempDir, PathBuf) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::write_schema_file(&project_path, "users");
+    (temp_dir, project_path)
+}
+
+fn dev_command(project_path: &Path, format: OutputFormat) -> DevMigrateCommand {
+    DevMigrateCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        description: None,
+        format,
+    }
+}
+
+/// データベースに存在するテーブル名（マイグレーション履歴テーブルを除く）
+async fn database_tables(project_path: &Path) -> Vec<String> {
+    let db_path = project_path.join("test.db");
+    let pool = AnyPool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT IN ('schema_migrations', 'schema_migration_rollbacks') AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    rows.into_iter().map(|(name,)| name).collect()
+}
+
+#[tokio::test]
+async fn test_dev_migrate_generates_and_applies() {
+    let (_temp_dir, project_path) = setup_project();
+
+    let output = DevCommandHandler::new()
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Text))
+        .await
+        .unwrap();
+
+    assert!(output.starts_with("=== generate ===\n"));
+    assert!(output.contains("=== apply (development) ==="));
+    assert_eq!(database_tables(&project_path).await, vec!["users"]);
+}
+
+#[tokio::test]
+async fn test_dev_migrate_without_changes_skips_apply() {
+    let (_temp_dir, project_path) = setup_project();
+    let handler = DevCommandHandler::new();
+    handler
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Text))
+        .await
+        .unwrap();
+
+    let output = handler
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Json))
+        .await
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["environment"], "development");
+    assert_eq!(json["migrations"], serde_json::json!([]));
+    assert!(json["generate"].get("migration_name").is_none());
+    assert!(json.get("apply").is_none());
+}
+
+#[tokio::test]
+async fn test_dev_migrate_refuses_protected_environment() {
+    let (_temp_dir, project_path) = setup_project();
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    let mut config = fs::read_to_string(&config_path).unwrap();
+    config.push_str("protected_environments:\n  - development\n");
+    fs::write(&config_path, config).unwrap();
+
+    let err = DevCommandHandler::new()
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Text))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("protected_environments"));
+    // generate も実行されない
+    assert!(!project_path.join("migrations").exists());
+}
+
+#[tokio::test]
+async fn test_dev_migrate_does_not_allow_destructive_changes() {
+    let (_temp_dir, project_path) = setup_project();
+    let handler = DevCommandHandler::new();
+    handler
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Text))
+        .await
+        .unwrap();
+
+    // テーブルを削除する変更は --allow-destructive なしでは生成されない
+    fs::write(
+        project_path.join("schema").join("users.yaml"),
+        "version: \"1.0\"\ntables: {}\n",
+    )
+    .unwrap();
+    let err = handler
+        .execute_migrate(&dev_command(&project_path, OutputFormat::Text))
+        .await
+        .unwrap_err();
+
+    assert!(format!("{:#}", err).contains("allow-destructive"));
+    assert_eq!(database_tables(&project_path).await, vec!["users"]);
+}
+
+#[tokio::test]
+async fn test_dev_migrate_watch_repeats_until_shutdown() {
+    let (_temp_dir, project_path) = setup_project();
+    let command = dev_command(&project_path, OutputFormat::Text);
+    let options = WatchOptions {
+        poll_interval: Duration::from_millis(20),
+        debounce: Duration::from_millis(200),
+    };
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+
+    let handler = DevCommandHandler::new();
+    let watcher = handler.watch_migrate(
+        &command,
+        &options,
+        async {
+            let _ = stop_rx.await;
+        },
+        move |event| event_tx.send(event).unwrap(),
+    );
+
+    let driver = async {
+        let mut events = Vec::new();
+        // 開始時のサイクルで users を作成
+        while events.len() < 2 {
+            events.push(event_rx.recv().await.unwrap());
+        }
+        // 続けて保存された2つのファイルは1回のサイクルにまとめられる
+        common::write_schema_file(&project_path, "posts");
+        common::write_schema_file(&project_path, "comments");
+        events.push(event_rx.recv().await.unwrap());
+        stop_tx.send(()).unwrap();
+        events
+    };
+
+    let (result, events) = tokio::time::timeout(Duration::from_secs(30), async {
+        tokio::join!(watcher, driver)
+    })
+    .await
+    .expect("watch should stop after shutdown");
+
+    assert_eq!(result.unwrap(), "Stopped watching after 2 cycle(s)");
+    assert!(matches!(events[0], WatchEvent::Started(_)));
+    let outputs: Vec<&String> = events[1..]
+        .iter()
+        .map(|event| match event {
+            WatchEvent::Completed(output) => output,
+            other => panic!("unexpected event: {:?}", other),
+        })
+        .collect();
+    assert!(outputs
+        .iter()
+        .all(|o| o.contains("=== apply (development) ===")));
+    assert_eq!(
+        fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().path().is_dir())
+            .count(),
+        2
+    );
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["comments", "posts", "users"]
+    );
+}
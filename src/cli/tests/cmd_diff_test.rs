@@ -0,0 +1,182 @@
+// diffコマンドハンドラーのテスト
+//
+// SQLiteのデータベースとスキーマディレクトリを比較し、差分の一覧と終了コードを検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use sqlx::AnyPool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::diff::{DiffCommand, DiffCommandHandler, EXIT_CODE_SCHEMA_DIFF};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// users テーブルを定義したプロジェクトと、同じ定義のテーブルを持つSQLiteデータベースを作成する
+async fn setup_project() -> (TempDir, PathBuf, AnyPool) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::write_schema_file(&project_path, "users");
+
+    let pool = AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    for sql in [
+        "CREATE TABLE users (id INTEGER NOT NULL, PRIMARY KEY (id))",
+        "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY)",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+
+    (temp_dir, project_path, pool)
+}
+
+async fn run_diff(project_path: &Path, format: OutputFormat) -> anyhow::Result<String> {
+    let command = DiffCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        format,
+    };
+    DiffCommandHandler::new().execute(&command).await
+}
+
+fn expect_failure(result: anyhow::Result<String>) -> CompletedWithFailure {
+    let err = result.expect_err("diff should report differences");
+    err.downcast_ref::<CompletedWithFailure>()
+        .unwrap_or_else(|| panic!("unexpected error: {:#}", err))
+        .clone()
+}
+
+#[tokio::test]
+async fn test_diff_reports_no_differences() {
+    let (_temp_dir, project_path, _pool) = setup_project().await;
+
+    let output = run_diff(&project_path, OutputFormat::Text).await.unwrap();
+
+    // schema_migrations は比較対象外
+    assert!(!output.contains("schema_migrations"));
+    assert!(output.ends_with("The database matches the schema directory"));
+}
+
+#[tokio::test]
+async fn test_diff_reports_drift_with_exit_code() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("CREATE TABLE legacy (id INTEGER)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE INDEX idx_users_id ON users (id)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    common::write_schema_file(&project_path, "posts");
+
+    let failure = expect_failure(run_diff(&project_path, OutputFormat::Text).await);
+
+    assert_eq!(failure.exit_code, EXIT_CODE_SCHEMA_DIFF);
+    assert_eq!(
+        failure.message,
+        "3 difference(s) between the schema directory and the 'development' database"
+    );
+    assert!(failure
+        .output
+        .contains("missing from the database:\n  + table posts\n"));
+    assert!(failure.output.contains(
+        "not in the schema directory:\n  - table legacy\n  - index idx_users_id on users\n"
+    ));
+}
+
+#[tokio::test]
+async fn test_diff_json_output() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("ALTER TABLE users ADD COLUMN nickname TEXT")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let failure = expect_failure(run_diff(&project_path, OutputFormat::Json).await);
+
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(json["environment"], "development");
+    assert_eq!(json["has_differences"], true);
+    assert_eq!(json["added"], serde_json::json!([]));
+    assert_eq!(
+        json["removed"],
+        serde_json::json!([{"table": "users", "kind": "column", "name": "nickname"}])
+    );
+}
+
+#[tokio::test]
+async fn test_diff_ignores_view_definition_rewrites() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("CREATE VIEW user_ids AS SELECT users.id FROM users")
+        .execute(&pool)
+        .await
+        .unwrap();
+    fs::write(
+        project_path.join("schema").join("user_ids.yaml"),
+        "version: \"1.0\"\ntables: {}\nviews:\n  user_ids:\n    definition: \"SELECT id FROM users\"\n",
+    )
+    .unwrap();
+
+    let output = run_diff(&project_path, OutputFormat::Text).await.unwrap();
+
+    assert!(output.contains("Note: View 'user_ids' is stored with a different definition"));
+    assert!(output.ends_with("The database matches the schema directory"));
+}
+
+#[tokio::test]
+async fn test_diff_ignores_external_tables() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("CREATE TABLE auth_users (id INTEGER)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    fs::write(
+        project_path.join("schema").join("_external.yaml"),
+        "version: \"1.0\"\nexternal_tables: [auth_users]\ntables: {}\n",
+    )
+    .unwrap();
+
+    let output = run_diff(&project_path, OutputFormat::Text).await.unwrap();
+
+    assert!(output.ends_with("The database matches the schema directory"));
+}
+
+#[tokio::test]
+async fn test_diff_compares_partial_index_predicates() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("CREATE UNIQUE INDEX idx_users_id ON users (id) WHERE (id > 0)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let write_schema = |predicate: &str| {
+        fs::write(
+            project_path.join("schema").join("users.yaml"),
+            format!(
+                "version: \"1.0\"\ntables:\n  users:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n    primary_key:\n      - id\n    indexes:\n      - name: idx_users_id\n        columns:\n          - id\n        unique: true\n        where: \"{}\"\n",
+                predicate
+            ),
+        )
+        .unwrap();
+    };
+
+    // sqlite_master から読み戻した条件式と比較し、括弧の有無は差分にしない
+    write_schema("id > 0");
+    let output = run_diff(&project_path, OutputFormat::Text).await.unwrap();
+    assert!(output.ends_with("The database matches the schema directory"));
+
+    write_schema("id > 1");
+    let failure = expect_failure(run_diff(&project_path, OutputFormat::Text).await);
+    assert_eq!(failure.exit_code, EXIT_CODE_SCHEMA_DIFF);
+    assert!(failure.output.contains("idx_users_id"));
+}
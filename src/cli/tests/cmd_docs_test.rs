@@ -0,0 +1,164 @@
+// docsコマンドハンドラーのテスト
+//
+// fixtures/docs/schema のスキーマ定義から生成したドキュメントを fixtures/docs/expected と比較し、
+// --check による古いドキュメントの検出と、不要になったページの削除を検証するテストスイート
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::docs::{DocsCommand, DocsCommandHandler, EXIT_CODE_STALE_DOCS};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/docs")
+}
+
+/// fixtures/docs/schema をスキーマディレクトリとするプロジェクトを作成する
+fn setup_project() -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    common::write_config(&project_path, Dialect::PostgreSQL, None);
+
+    let schema_dir = project_path.join("schema");
+    fs::create_dir_all(&schema_dir).unwrap();
+    for entry in fs::read_dir(fixtures_dir().join("schema")).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, schema_dir.join(path.file_name().unwrap())).unwrap();
+    }
+
+    (temp_dir, project_path)
+}
+
+fn run_docs(project_path: &Path, check: bool, format: OutputFormat) -> anyhow::Result<String> {
+    let command = DocsCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        out_dir: PathBuf::from("docs/schema"),
+        check,
+        format,
+    };
+    DocsCommandHandler::new().execute(&command)
+}
+
+fn expect_failure(result: anyhow::Result<String>) -> CompletedWithFailure {
+    let err = result.expect_err("docs --check should report stale pages");
+    err.downcast_ref::<CompletedWithFailure>()
+        .unwrap_or_else(|| panic!("unexpected error: {:#}", err))
+        .clone()
+}
+
+/// ディレクトリ以下のファイルを相対パスと内容の組で読み込む
+fn read_tree(dir: &Path) -> BTreeMap<String, String> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(root, &path, files);
+            } else {
+                let relative = path.strip_prefix(root).unwrap();
+                let key = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.insert(key, fs::read_to_string(&path).unwrap());
+            }
+        }
+    }
+    let mut files = BTreeMap::new();
+    walk(dir, dir, &mut files);
+    files
+}
+
+#[test]
+fn test_docs_matches_fixture_output() {
+    let (_temp_dir, project_path) = setup_project();
+
+    let output = run_docs(&project_path, false, OutputFormat::Text).unwrap();
+    assert!(output.contains("created: index.md"), "{}", output);
+
+    let actual = read_tree(&project_path.join("docs/schema"));
+    let expected = read_tree(&fixtures_dir().join("expected"));
+    assert_eq!(
+        actual.keys().collect::<Vec<_>>(),
+        expected.keys().collect::<Vec<_>>()
+    );
+    for (path, content) in &expected {
+        assert_eq!(&actual[path], content, "page differs: {}", path);
+    }
+}
+
+#[test]
+fn test_docs_is_deterministic_and_skips_unchanged_pages() {
+    let (_temp_dir, project_path) = setup_project();
+    run_docs(&project_path, false, OutputFormat::Text).unwrap();
+    let first = read_tree(&project_path.join("docs/schema"));
+
+    let output = run_docs(&project_path, false, OutputFormat::Json).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["changes"], serde_json::json!([]));
+    assert_eq!(read_tree(&project_path.join("docs/schema")), first);
+}
+
+#[test]
+fn test_docs_check_passes_when_up_to_date() {
+    let (_temp_dir, project_path) = setup_project();
+    run_docs(&project_path, false, OutputFormat::Text).unwrap();
+
+    let output = run_docs(&project_path, true, OutputFormat::Text).unwrap();
+    assert!(output.contains("is up to date"), "{}", output);
+}
+
+#[test]
+fn test_docs_check_reports_stale_pages_without_writing() {
+    let (_temp_dir, project_path) = setup_project();
+    run_docs(&project_path, false, OutputFormat::Text).unwrap();
+
+    let docs_dir = project_path.join("docs/schema");
+    fs::write(docs_dir.join("tables/users.md"), "edited by hand\n").unwrap();
+    fs::remove_file(docs_dir.join("enums/user_status.md")).unwrap();
+    fs::write(docs_dir.join("tables/dropped.md"), "old table\n").unwrap();
+    // 生成対象外のファイルは比較しない
+    fs::write(docs_dir.join("README.md"), "notes\n").unwrap();
+
+    let failure = expect_failure(run_docs(&project_path, true, OutputFormat::Json));
+    assert_eq!(failure.exit_code, EXIT_CODE_STALE_DOCS);
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(
+        json["changes"],
+        serde_json::json!([
+            {"path": "enums/user_status.md", "status": "missing"},
+            {"path": "tables/dropped.md", "status": "extra"},
+            {"path": "tables/users.md", "status": "outdated"},
+        ])
+    );
+    // --check はファイルを変更しない
+    assert_eq!(
+        fs::read_to_string(docs_dir.join("tables/users.md")).unwrap(),
+        "edited by hand\n"
+    );
+    assert!(docs_dir.join("tables/dropped.md").exists());
+}
+
+#[test]
+fn test_docs_updates_stale_pages_and_removes_dropped_objects() {
+    let (_temp_dir, project_path) = setup_project();
+    run_docs(&project_path, false, OutputFormat::Text).unwrap();
+
+    let docs_dir = project_path.join("docs/schema");
+    fs::write(docs_dir.join("tables/users.md"), "edited by hand\n").unwrap();
+    fs::write(docs_dir.join("tables/dropped.md"), "old table\n").unwrap();
+    fs::write(docs_dir.join("README.md"), "notes\n").unwrap();
+
+    let output = run_docs(&project_path, false, OutputFormat::Text).unwrap();
+    assert!(output.contains("updated: tables/users.md"), "{}", output);
+    assert!(output.contains("removed: tables/dropped.md"), "{}", output);
+
+    assert!(!docs_dir.join("tables/dropped.md").exists());
+    assert!(docs_dir.join("README.md").exists());
+    run_docs(&project_path, true, OutputFormat::Text).unwrap();
+}
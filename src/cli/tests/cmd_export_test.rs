@@ -1,6 +1,7 @@
 // exportコマンドハンドラーのテスト
 
 use sqlx::any::install_default_drivers;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use strata::cli::commands::export::{ExportCommand, ExportCommandHandler};
@@ -28,6 +29,11 @@ fn test_export_command_struct() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     assert_eq!(command.project_path, PathBuf::from("/test/path"));
@@ -51,6 +57,11 @@ async fn test_export_no_config_file() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -77,6 +88,11 @@ async fn test_export_invalid_environment() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -160,6 +176,11 @@ async fn test_export_from_sqlite_database() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -219,6 +240,11 @@ async fn test_export_to_stdout() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -286,6 +312,11 @@ async fn test_export_sqlite_integer_primary_key_auto_increment() {
         split: false,
         tables: vec![],
         exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -299,6 +330,175 @@ async fn test_export_sqlite_integer_primary_key_auto_increment() {
     );
 }
 
+/// SQLiteのDBをエクスポートした後、generateで差分が出ないことを確認する
+async fn assert_sqlite_export_generate_round_trip(create_table_sql: &str) {
+    use strata::adapters::database::DatabaseConnectionService;
+    use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+
+    let config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    fs::write(&config_path, ConfigSerializer::to_yaml(&config).unwrap()).unwrap();
+
+    // auto_increment を指定していないYAMLのスナップショット
+    common::write_schema_snapshot(&project_path, "users");
+
+    let db_service = DatabaseConnectionService::new();
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = db_service
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    sqlx::query(create_table_sql).execute(&pool).await.unwrap();
+
+    // DBの内容でスキーマディレクトリを上書き
+    let schema_dir = project_path.join("schema");
+    let export_command = ExportCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: Some(schema_dir),
+        force: true,
+        format: strata::cli::OutputFormat::Text,
+        split: false,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
+    };
+    let result = ExportCommandHandler::new().execute(&export_command).await;
+    assert!(result.is_ok(), "Export failed: {:?}", result);
+
+    let generate_command = GenerateCommand {
+        project_path,
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        description: None,
+        dry_run: true,
+        allow_destructive: false,
+        override_policy: false,
+        verbose: false,
+        format: strata::cli::OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
+    };
+    let output = GenerateCommandHandler::new()
+        .execute(&generate_command)
+        .unwrap();
+    assert!(
+        output.contains("No schema changes found"),
+        "Expected no diff after export, got:\n{}",
+        output
+    );
+}
+
+#[tokio::test]
+#[ignore] // 統合テスト - 実際のデータベースが必要
+async fn test_export_generate_round_trip_sqlite_without_autoincrement_keyword() {
+    assert_sqlite_export_generate_round_trip(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY NOT NULL)",
+    )
+    .await;
+}
+
+#[tokio::test]
+#[ignore] // 統合テスト - 実際のデータベースが必要
+async fn test_export_generate_round_trip_sqlite_with_autoincrement_keyword() {
+    assert_sqlite_export_generate_round_trip(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL)",
+    )
+    .await;
+}
+
+#[tokio::test]
+#[ignore] // 統合テスト - 実際のデータベースが必要
+async fn test_export_skips_external_tables() {
+    use strata::adapters::database::DatabaseConnectionService;
+    use strata::services::schema_io::schema_parser::SchemaParserService;
+
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+
+    let config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    fs::write(&config_path, ConfigSerializer::to_yaml(&config).unwrap()).unwrap();
+
+    // 外部テーブルの宣言
+    fs::write(
+        project_path.join("schema").join("external.yaml"),
+        "version: \"1.0\"\nexternal_tables: [external_users]\ntables: {}\n",
+    )
+    .unwrap();
+
+    let db_service = DatabaseConnectionService::new();
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = db_service
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE external_users (id INTEGER PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES external_users(id))",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let export_dir = project_path.join("exported_schema");
+    let command = ExportCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: Some(export_dir.clone()),
+        force: true,
+        format: strata::cli::OutputFormat::Text,
+        split: true,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
+    };
+    let result = ExportCommandHandler::new().execute(&command).await;
+    assert!(result.is_ok(), "Export failed: {:?}", result);
+
+    assert!(export_dir.join("posts.yaml").exists());
+    assert!(!export_dir.join("external_users.yaml").exists());
+
+    // 宣言が引き継がれ、エクスポート結果をそのまま読み込める
+    let exported = SchemaParserService::new()
+        .parse_schema_directory(&export_dir)
+        .unwrap();
+    assert!(exported.has_table("posts"));
+    assert!(!exported.has_table("external_users"));
+    assert!(exported.is_external_table("external_users"));
+}
+
 #[test]
 fn test_format_export_summary() {
     let handler = ExportCommandHandler::new();
@@ -327,3 +527,836 @@ fn test_format_export_summary_stdout() {
     assert!(summary.contains("Exported tables: 1"));
     assert!(summary.contains("stdout"));
 }
+
+#[test]
+fn test_format_identifier_warnings() {
+    let handler = ExportCommandHandler::new();
+
+    assert_eq!(handler.format_identifier_warnings(&[]), "");
+
+    let warnings = vec!["Table name 'order-items' contains '-'".to_string()];
+    let output = handler.format_identifier_warnings(&warnings);
+    assert!(output.contains("Identifiers that will be rejected by validate and generate: 1"));
+    assert!(output.contains("  - Table name 'order-items' contains '-'"));
+}
+
+#[tokio::test]
+#[ignore] // 統合テスト - 実際のデータベースが必要
+async fn test_export_warns_about_unsafe_identifiers() {
+    use strata::adapters::database::DatabaseConnectionService;
+
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+
+    let config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    fs::write(&config_path, ConfigSerializer::to_yaml(&config).unwrap()).unwrap();
+
+    let db_service = DatabaseConnectionService::new();
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = db_service
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    sqlx::query(r#"CREATE TABLE "order-items" (id INTEGER PRIMARY KEY, "2fa" TEXT)"#)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let export_dir = project_path.join("exported_schema");
+    let command = ExportCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: Some(export_dir.clone()),
+        force: true,
+        format: strata::cli::OutputFormat::Json,
+        split: false,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
+    };
+
+    // 規則に合わない名前があってもエクスポートは成功し、警告として報告する
+    let output = ExportCommandHandler::new().execute(&command).await.unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let warnings: Vec<&str> = json["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w.as_str().unwrap())
+        .collect();
+    assert_eq!(warnings.len(), 2, "{:?}", warnings);
+    assert!(warnings
+        .iter()
+        .any(|w| w.contains("Table name 'order-items' contains '-'")));
+    assert!(warnings
+        .iter()
+        .any(|w| w.contains("Column name '2fa' starts with a digit")));
+    assert!(export_dir.join("schema.yaml").exists());
+}
+
+/// 前回のエクスポート結果（fixtures/export_diff/previous）と今回の結果（current）の変更サマリー
+#[test]
+fn test_export_changes_between_fixture_exports() {
+    use std::path::Path;
+    use strata::cli::commands::export_changes::ExportChanges;
+    use strata::services::schema_io::schema_parser::SchemaParserService;
+
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/export_diff");
+    let parser = SchemaParserService::new();
+    let previous = parser
+        .parse_schema_directory(&fixtures_dir.join("previous"))
+        .unwrap();
+    let current = parser
+        .parse_schema_directory(&fixtures_dir.join("current"))
+        .unwrap();
+    // 旧バージョンの出力に含まれる未知のフィールドは警告として引き継ぐ
+    let warnings = parser
+        .find_unknown_fields(&fixtures_dir.join("previous"))
+        .unwrap();
+
+    let changes = ExportChanges::compare(Path::new("previous"), &previous, &current, warnings);
+
+    let expected = fs::read_to_string(fixtures_dir.join("CHANGES.md")).unwrap();
+    assert_eq!(changes.to_markdown(), expected);
+
+    let json = serde_json::to_value(&changes).unwrap();
+    assert_eq!(
+        json["summary"],
+        serde_json::json!({"added": 1, "removed": 1, "modified": 2})
+    );
+    assert_eq!(
+        json["added"],
+        serde_json::json!([{"type": "table", "name": "posts"}])
+    );
+    assert_eq!(json["modified"][1]["name"], "users");
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_export_diff_against_requires_output() {
+    let command = ExportCommand {
+        project_path: PathBuf::from("/test/path"),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: None,
+        force: false,
+        format: strata::cli::OutputFormat::Text,
+        split: false,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: Some(PathBuf::from("/test/previous")),
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
+    };
+
+    let result = ExportCommandHandler::new().execute(&command).await;
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("--diff-against requires --output"));
+}
+
+#[tokio::test]
+#[ignore] // 統合テスト - 実際のデータベースが必要
+async fn test_export_diff_against_previous_export() {
+    use strata::adapters::database::DatabaseConnectionService;
+
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+
+    let config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    fs::write(&config_path, ConfigSerializer::to_yaml(&config).unwrap()).unwrap();
+
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = DatabaseConnectionService::new()
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let export = |output_dir: PathBuf, diff_against: Option<PathBuf>| ExportCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: Some(output_dir),
+        force: false,
+        format: strata::cli::OutputFormat::Text,
+        split: true,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against,
+        include_sequence_values: false,
+        from_schema: false,
+        wait_timeout: None,
+    };
+
+    let previous_dir = project_path.join("export_previous");
+    ExportCommandHandler::new()
+        .execute(&export(previous_dir.clone(), None))
+        .await
+        .unwrap();
+
+    sqlx::query("CREATE TABLE posts (id INTEGER PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("ALTER TABLE users ADD COLUMN email TEXT")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let current_dir = project_path.join("export_current");
+    let summary = ExportCommandHandler::new()
+        .execute(&export(current_dir.clone(), Some(previous_dir)))
+        .await
+        .unwrap();
+    assert!(
+        summary.contains("1 added, 0 removed, 1 modified"),
+        "{}",
+        summary
+    );
+
+    let markdown = fs::read_to_string(current_dir.join("CHANGES.md")).unwrap();
+    assert!(
+        markdown.contains("## Added\n\n- table `posts`\n"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("- table `users`\n  - + column email\n"),
+        "{}",
+        markdown
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(current_dir.join("changes.json")).unwrap())
+            .unwrap();
+    assert_eq!(json["summary"]["added"], 1);
+}
+
+/// ON UPDATE / ON DELETE 付きの複合外部キーが export → generate で差分を生まない
+#[tokio::test]
+async fn test_export_round_trips_foreign_key_actions() {
+    use strata::cli::commands::dev::{DevCommandHandler, DevMigrateCommand};
+    use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    let schema_dir = project_path.join("schema");
+    fs::create_dir_all(&schema_dir).unwrap();
+    // 主キーではないユニークキー (tenant_id, code) を参照する
+    fs::write(
+        schema_dir.join("billing.yaml"),
+        r#"version: "1.0"
+tables:
+  accounts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: tenant_id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: code
+        type:
+          kind: TEXT
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: UNIQUE
+        columns:
+          - tenant_id
+          - code
+  invoices:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: tenant_id
+        type:
+          kind: INTEGER
+        nullable: true
+      - name: account_code
+        type:
+          kind: TEXT
+        nullable: true
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - tenant_id
+          - account_code
+        referenced_table: accounts
+        referenced_columns:
+          - tenant_id
+          - code
+        on_delete: SET_NULL
+        on_update: CASCADE
+"#,
+    )
+    .unwrap();
+
+    DevCommandHandler::new()
+        .execute_migrate(&DevMigrateCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            env: "development".to_string(),
+            description: None,
+            format: strata::cli::OutputFormat::Text,
+        })
+        .await
+        .unwrap();
+
+    // データベースから書き出したスキーマで定義を置き換える
+    fs::remove_dir_all(&schema_dir).unwrap();
+    ExportCommandHandler::new()
+        .execute(&ExportCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            env: "development".to_string(),
+            output_dir: Some(schema_dir.clone()),
+            force: true,
+            format: strata::cli::OutputFormat::Text,
+            split: false,
+            tables: vec![],
+            exclude_tables: vec![],
+            allow_partial: false,
+            diff_against: None,
+            include_sequence_values: false,
+            from_schema: false,
+            wait_timeout: None,
+        })
+        .await
+        .unwrap();
+    let exported = fs::read_to_string(schema_dir.join("schema.yaml")).unwrap();
+    assert!(exported.contains("on_update: CASCADE"), "{}", exported);
+    assert!(exported.contains("on_delete: SET_NULL"), "{}", exported);
+    assert!(exported.contains("type: UNIQUE"), "{}", exported);
+
+    let output = GenerateCommandHandler::new()
+        .execute(&GenerateCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: None,
+            dry_run: true,
+            allow_destructive: false,
+            override_policy: false,
+            verbose: false,
+            format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        })
+        .unwrap();
+    assert!(output.contains("No schema changes"), "{}", output);
+}
+
+/// identifier_case の各規則で dev migrate → export → generate が差分を生まない
+#[tokio::test]
+async fn test_export_round_trips_under_identifier_case() {
+    use strata::cli::commands::dev::{DevCommandHandler, DevMigrateCommand};
+    use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+    use strata::core::config::IdentifierCase;
+
+    install_default_drivers();
+    for (case, expected_names) in [
+        (
+            IdentifierCase::Preserve,
+            ["Accounts:", "- name: Email", "Idx_Accounts_Email"],
+        ),
+        (
+            IdentifierCase::Lower,
+            ["accounts:", "- name: email", "idx_accounts_email"],
+        ),
+        (
+            IdentifierCase::Upper,
+            ["ACCOUNTS:", "- name: EMAIL", "IDX_ACCOUNTS_EMAIL"],
+        ),
+    ] {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_path_buf();
+        let db_path = project_path.join("test.db");
+        fs::File::create(&db_path).unwrap();
+
+        let mut config =
+            common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+        config.identifier_case = case;
+        let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+        fs::write(&config_path, ConfigSerializer::to_yaml(&config).unwrap()).unwrap();
+
+        let schema_dir = project_path.join("schema");
+        fs::create_dir_all(&schema_dir).unwrap();
+        fs::write(
+            schema_dir.join("billing.yaml"),
+            r#"version: "1.0"
+tables:
+  Accounts:
+    columns:
+      - name: Id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: Email
+        type:
+          kind: TEXT
+        nullable: false
+    primary_key:
+      - Id
+    indexes:
+      - name: Idx_Accounts_Email
+        columns:
+          - Email
+        unique: true
+  Invoices:
+    columns:
+      - name: Id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: AccountId
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - Id
+    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - AccountId
+        referenced_table: Accounts
+        referenced_columns:
+          - Id
+"#,
+        )
+        .unwrap();
+
+        DevCommandHandler::new()
+            .execute_migrate(&DevMigrateCommand {
+                project_path: project_path.clone(),
+                config_path: None,
+                env: "development".to_string(),
+                description: None,
+                format: strata::cli::OutputFormat::Text,
+            })
+            .await
+            .unwrap();
+
+        // データベースから書き出したスキーマで定義を置き換える
+        fs::remove_dir_all(&schema_dir).unwrap();
+        ExportCommandHandler::new()
+            .execute(&ExportCommand {
+                project_path: project_path.clone(),
+                config_path: None,
+                env: "development".to_string(),
+                output_dir: Some(schema_dir.clone()),
+                force: true,
+                format: strata::cli::OutputFormat::Text,
+                split: false,
+                tables: vec![],
+                exclude_tables: vec![],
+                allow_partial: false,
+                diff_against: None,
+                include_sequence_values: false,
+                from_schema: false,
+                wait_timeout: None,
+            })
+            .await
+            .unwrap();
+        let exported = fs::read_to_string(schema_dir.join("schema.yaml")).unwrap();
+        for name in expected_names {
+            assert!(exported.contains(name), "{}: {}", case, exported);
+        }
+
+        let output = GenerateCommandHandler::new()
+            .execute(&GenerateCommand {
+                project_path: project_path.clone(),
+                config_path: None,
+                schema_dir: None,
+                allow_duplicate_override: false,
+                description: None,
+                dry_run: true,
+                allow_destructive: false,
+                override_policy: false,
+                verbose: false,
+                format: strata::cli::OutputFormat::Text,
+                metadata: BTreeMap::new(),
+                summary_only: false,
+                require_clean_pending: false,
+                allow_stacked: false,
+                dry_run_sql: Default::default(),
+                down: false,
+                with_preflight_queries: false,
+                edit: false,
+            })
+            .unwrap();
+        assert!(output.contains("No schema changes"), "{}: {}", case, output);
+    }
+}
+
+#[tokio::test]
+async fn test_export_include_sequence_values() {
+    use strata::services::schema_io::schema_parser::SchemaParserService;
+
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    let pool = sqlx::AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    for sql in [
+        "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)",
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT)",
+        "INSERT INTO users (id, name) VALUES (1042, 'alice')",
+        "INSERT INTO posts (title) VALUES ('hello')",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+    pool.close().await;
+
+    let output_dir = project_path.join("exported");
+    let command = ExportCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        env: "development".to_string(),
+        output_dir: Some(output_dir.clone()),
+        force: false,
+        format: strata::cli::OutputFormat::Text,
+        split: true,
+        tables: vec![],
+        exclude_tables: vec!["posts".to_string()],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: true,
+        from_schema: false,
+        wait_timeout: None,
+    };
+    let output = ExportCommandHandler::new().execute(&command).await.unwrap();
+    assert!(output.contains("(1 table(s))"), "{}", output);
+
+    // エクスポートしたテーブルの値のみを書き出す
+    let values = fs::read_to_string(output_dir.join("sequence_values.yaml")).unwrap();
+    let file = strata::adapters::sequence_values::SequenceValuesFile::from_yaml(&values).unwrap();
+    assert_eq!(file.sequences.len(), 1, "{}", values);
+    assert_eq!(file.sequences[0].table, "users");
+    assert_eq!(file.sequences[0].value, 1042);
+
+    // 値ファイルはスキーマとして読み込まれない
+    let schema = SchemaParserService::new()
+        .parse_schema_directory(&output_dir)
+        .unwrap();
+    assert_eq!(schema.tables.keys().collect::<Vec<_>>(), vec!["users"]);
+
+    // 既存の値ファイルは --force なしでは上書きしない
+    let err = ExportCommandHandler::new()
+        .execute(&ExportCommand {
+            split: false,
+            ..command.clone()
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("sequence_values.yaml"), "{}", err);
+
+    // --output なしでは使えない
+    let err = ExportCommandHandler::new()
+        .execute(&ExportCommand {
+            output_dir: None,
+            split: false,
+            ..command
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("requires --output"), "{}", err);
+}
+
+/// 循環参照とENUMを含むスキーマ定義
+const FROM_SCHEMA_YAML: &str = r#"version: "1.0"
+enums:
+  user_status:
+    name: user_status
+    values:
+      - active
+      - banned
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: status
+        type:
+          kind: ENUM
+          name: user_status
+        nullable: false
+      - name: pinned_post_id
+        type:
+          kind: INTEGER
+        nullable: true
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [pinned_post_id]
+        referenced_table: posts
+        referenced_columns: [id]
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    indexes:
+      - name: idx_posts_user_id
+        columns:
+          - user_id
+        unique: false
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [user_id]
+        referenced_table: users
+        referenced_columns: [id]
+  comments:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: post_id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [post_id]
+        referenced_table: posts
+        referenced_columns: [id]
+"#;
+
+fn from_schema_command(project_path: PathBuf, output_dir: Option<PathBuf>) -> ExportCommand {
+    ExportCommand {
+        project_path,
+        config_path: None,
+        env: "development".to_string(),
+        output_dir,
+        force: false,
+        format: strata::cli::OutputFormat::Text,
+        split: false,
+        tables: vec![],
+        exclude_tables: vec![],
+        allow_partial: false,
+        diff_against: None,
+        include_sequence_values: false,
+        from_schema: true,
+        wait_timeout: None,
+    }
+}
+
+/// --from-schema はデータベースに接続せず、ENUM → テーブル → 循環参照の外部キーの順にDDLを出力する
+#[tokio::test]
+async fn test_export_from_schema_orders_enums_and_circular_foreign_keys() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::PostgreSQL, None, false).unwrap();
+    fs::write(
+        project_path.join("schema").join("app.yaml"),
+        FROM_SCHEMA_YAML,
+    )
+    .unwrap();
+
+    let handler = ExportCommandHandler::new();
+    let command = from_schema_command(project_path.clone(), None);
+    let sql = handler.execute(&command).await.unwrap();
+
+    let position = |needle: &str| {
+        sql.find(needle)
+            .unwrap_or_else(|| panic!("'{}' not found in:\n{}", needle, sql))
+    };
+    let create_type = position(r#"CREATE TYPE "user_status""#);
+    let create_users = position(r#"CREATE TABLE "users""#);
+    let create_posts = position(r#"CREATE TABLE "posts""#);
+    let create_comments = position(r#"CREATE TABLE "comments""#);
+    let add_constraint = position("ADD CONSTRAINT");
+    assert!(
+        create_type < create_users.min(create_posts).min(create_comments),
+        "{}",
+        sql
+    );
+    assert!(
+        create_users.max(create_posts).max(create_comments) < add_constraint,
+        "circular foreign keys must be added after all tables:\n{}",
+        sql
+    );
+    assert!(!sql.contains("Transaction:"), "{}", sql);
+
+    // 同じスキーマからは常に同じ出力になる
+    assert_eq!(handler.execute(&command).await.unwrap(), sql);
+
+    // --output ではschema.sqlに書き出す
+    let output_dir = project_path.join("ddl");
+    let summary = handler
+        .execute(&from_schema_command(
+            project_path.clone(),
+            Some(output_dir.clone()),
+        ))
+        .await
+        .unwrap();
+    assert!(summary.contains("schema.sql"), "{}", summary);
+    assert_eq!(
+        fs::read_to_string(output_dir.join("schema.sql")).unwrap(),
+        sql
+    );
+}
+
+/// --from-schema --split はテーブルごとのファイルと実行順を出力し、--tables で絞り込める
+#[tokio::test]
+async fn test_export_from_schema_split_and_filter() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::PostgreSQL, None, false).unwrap();
+    fs::write(
+        project_path.join("schema").join("app.yaml"),
+        FROM_SCHEMA_YAML,
+    )
+    .unwrap();
+    let handler = ExportCommandHandler::new();
+
+    let output_dir = project_path.join("ddl");
+    let command = ExportCommand {
+        split: true,
+        ..from_schema_command(project_path.clone(), Some(output_dir.clone()))
+    };
+    let summary = handler.execute(&command).await.unwrap();
+
+    let order: Vec<&str> = summary
+        .lines()
+        .filter_map(|line| line.trim().split_once(". ").map(|(_, file)| file))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            "_enums.sql",
+            "comments.sql",
+            "posts.sql",
+            "users.sql",
+            "_constraints.sql"
+        ],
+        "{}",
+        summary
+    );
+    let posts = fs::read_to_string(output_dir.join("posts.sql")).unwrap();
+    assert!(posts.contains(r#"CREATE TABLE "posts""#), "{}", posts);
+    assert!(
+        posts.contains(r#"CREATE INDEX "idx_posts_user_id""#),
+        "{}",
+        posts
+    );
+    let constraints = fs::read_to_string(output_dir.join("_constraints.sql")).unwrap();
+    assert!(constraints.contains("ALTER TABLE"), "{}", constraints);
+
+    // 既存ファイルは --force なしでは上書きしない
+    let err = handler.execute(&command).await.unwrap_err();
+    assert!(err.to_string().contains("already exist"), "{}", err);
+
+    let sql = handler
+        .execute(&ExportCommand {
+            tables: vec!["users".to_string()],
+            ..from_schema_command(project_path.clone(), None)
+        })
+        .await
+        .unwrap();
+    assert!(sql.contains(r#"CREATE TABLE "users""#), "{}", sql);
+    assert!(!sql.contains(r#"CREATE TABLE "posts""#), "{}", sql);
+
+    // 循環参照がない場合、外部キーは各テーブルのファイルに出力する
+    let output_dir = project_path.join("ddl_comments");
+    handler
+        .execute(&ExportCommand {
+            split: true,
+            exclude_tables: vec!["users".to_string()],
+            ..from_schema_command(project_path.clone(), Some(output_dir.clone()))
+        })
+        .await
+        .unwrap();
+    assert!(!output_dir.join("_constraints.sql").exists());
+    let comments = fs::read_to_string(output_dir.join("comments.sql")).unwrap();
+    assert!(
+        comments.contains(r#"ALTER TABLE "comments" ADD CONSTRAINT"#),
+        "{}",
+        comments
+    );
+
+    let err = handler
+        .execute(&ExportCommand {
+            tables: vec!["missing".to_string()],
+            ..from_schema_command(project_path.clone(), None)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Table 'missing' not found in schema definitions."
+    );
+
+    let err = handler
+        .execute(&ExportCommand {
+            include_sequence_values: true,
+            ..from_schema_command(project_path, Some(output_dir))
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--from-schema"), "{}", err);
+}
@@ -0,0 +1,207 @@
+/// generate --edit のテスト
+///
+/// エディタの代わりにシェルスクリプトを指定し、生成したSQLを編集してから書き出せることを確認します。
+#[cfg(unix)]
+mod common;
+
+#[cfg(unix)]
+mod generate_edit_tests {
+    use crate::common;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+    use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+    use strata::cli::commands::sql_editor::{SqlEditor, EDITOR_ENV};
+    use strata::cli::OutputFormat;
+    use strata::core::config::Dialect;
+    use strata::services::schema_checksum::SchemaChecksumService;
+    use tempfile::TempDir;
+
+    fn setup_project() -> (TempDir, PathBuf) {
+        let (temp_dir, project_path) =
+            common::setup_test_project(Dialect::SQLite, Some("test.db"), true).unwrap();
+        common::write_schema_file(&project_path, "users");
+        (temp_dir, project_path)
+    }
+
+    fn edit_command(project_path: &Path, allow_destructive: bool) -> GenerateCommand {
+        GenerateCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some("create users".to_string()),
+            dry_run: false,
+            allow_destructive,
+            override_policy: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: true,
+        }
+    }
+
+    /// `$1`（up.sql）と `$2`（down.sql）を受け取るエディタ代わりのスクリプトを作成
+    fn editor_script(dir: &Path, body: &str) -> String {
+        let path = dir.join("editor.sh");
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path.display().to_string()
+    }
+
+    fn handler(editor: &str) -> GenerateCommandHandler {
+        GenerateCommandHandler::new()
+            .with_sql_editor(SqlEditor::new().with_interactive(true).with_command(editor))
+    }
+
+    fn migration_dirs(project_path: &Path) -> Vec<PathBuf> {
+        fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_writes_edited_sql_with_recomputed_checksum() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(
+            temp_dir.path(),
+            "printf '\\nCREATE INDEX idx_users_id ON users (id);\\n' >> \"$1\"",
+        );
+
+        handler(&editor)
+            .execute(&edit_command(&project_path, false))
+            .unwrap();
+
+        let dirs = migration_dirs(&project_path);
+        assert_eq!(dirs.len(), 1);
+        let up_sql = fs::read_to_string(dirs[0].join("up.sql")).unwrap();
+        assert!(up_sql.contains("CREATE TABLE"));
+        assert!(up_sql.contains("CREATE INDEX idx_users_id ON users (id);"));
+        assert!(!up_sql.contains("-- strata:"));
+
+        let meta = fs::read_to_string(dirs[0].join(".meta.yaml")).unwrap();
+        let checksum = SchemaChecksumService::new().calculate_sql_checksum(&up_sql);
+        assert!(meta.contains(&format!("up_sql_checksum: {}", checksum)));
+    }
+
+    #[test]
+    fn test_edit_cancels_when_file_is_emptied() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(temp_dir.path(), ": > \"$2\"");
+
+        let output = handler(&editor)
+            .execute(&edit_command(&project_path, false))
+            .unwrap();
+
+        assert!(output.contains("Generation cancelled: down.sql was left empty"));
+        assert!(migration_dirs(&project_path).is_empty());
+        assert!(!project_path
+            .join("migrations/.schema_snapshot.yaml")
+            .exists());
+    }
+
+    #[test]
+    fn test_edit_cancels_when_editor_fails() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(temp_dir.path(), "exit 1");
+
+        let output = handler(&editor)
+            .execute(&edit_command(&project_path, false))
+            .unwrap();
+
+        assert!(output.contains("Generation cancelled"));
+        assert!(migration_dirs(&project_path).is_empty());
+    }
+
+    #[test]
+    fn test_edit_rejects_unterminated_string() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(
+            temp_dir.path(),
+            "printf \"INSERT INTO users (id) VALUES ('1);\\n\" >> \"$1\"",
+        );
+
+        let err = handler(&editor)
+            .execute(&edit_command(&project_path, false))
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("The edited up.sql has an unterminated string literal"));
+        assert!(migration_dirs(&project_path).is_empty());
+    }
+
+    #[test]
+    fn test_edit_added_destructive_statement_requires_allow_destructive() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(
+            temp_dir.path(),
+            "printf '\\nDELETE FROM users;\\n' >> \"$1\"",
+        );
+
+        let err = handler(&editor)
+            .execute(&edit_command(&project_path, false))
+            .unwrap_err();
+        assert!(err.to_string().contains("DELETE FROM users"));
+        assert!(err.to_string().contains("--allow-destructive"));
+        assert!(migration_dirs(&project_path).is_empty());
+
+        handler(&editor)
+            .execute(&edit_command(&project_path, true))
+            .unwrap();
+        let dirs = migration_dirs(&project_path);
+        assert_eq!(dirs.len(), 1);
+        let meta = fs::read_to_string(dirs[0].join(".meta.yaml")).unwrap();
+        assert!(meta.contains("edited_statements"));
+        assert!(meta.contains("DELETE FROM users"));
+    }
+
+    #[test]
+    fn test_edit_rejected_for_json_and_non_interactive() {
+        let (_temp_dir, project_path) = setup_project();
+
+        let mut command = edit_command(&project_path, false);
+        command.format = OutputFormat::Json;
+        let err = handler("true").execute(&command).unwrap_err();
+        assert!(err.to_string().contains("--format json"));
+
+        let err = GenerateCommandHandler::new()
+            .with_sql_editor(
+                SqlEditor::new()
+                    .with_interactive(false)
+                    .with_command("true"),
+            )
+            .execute(&edit_command(&project_path, false))
+            .unwrap_err();
+        assert!(err.to_string().contains("requires a terminal"));
+        assert!(migration_dirs(&project_path).is_empty());
+    }
+
+    /// STRATA_EDITOR で指定したエディタが使われる（このバイナリで環境変数を変更するのはこのテストのみ）
+    #[test]
+    fn test_edit_uses_strata_editor_env() {
+        let (temp_dir, project_path) = setup_project();
+        let editor = editor_script(temp_dir.path(), "printf '\\n-- reviewed\\n' >> \"$1\"");
+        std::env::set_var(EDITOR_ENV, &editor);
+
+        let result = GenerateCommandHandler::new()
+            .with_sql_editor(SqlEditor::new().with_interactive(true))
+            .execute(&edit_command(&project_path, false));
+        std::env::remove_var(EDITOR_ENV);
+        result.unwrap();
+
+        let dirs = migration_dirs(&project_path);
+        let up_sql = fs::read_to_string(dirs[0].join("up.sql")).unwrap();
+        assert!(up_sql.contains("-- reviewed"));
+    }
+}
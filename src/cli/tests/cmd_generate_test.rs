@@ -3,8 +3,12 @@
 /// スキーマ差分検出とマイグレーションファイル生成機能が正しく動作することを確認します。
 #[cfg(test)]
 mod generate_command_tests {
+    use std::collections::BTreeMap;
     use std::fs;
+    use strata::cli::commands::dry_run_sql::DryRunSqlMode;
     use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+    use strata::cli::commands::stacked_migrations;
+    use strata::cli::timings::Timings;
     use strata::core::config::Dialect;
     use tempfile::TempDir;
 
@@ -26,11 +30,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("test migration".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -55,11 +69,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("test migration".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -83,11 +107,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("initial migration".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -113,11 +147,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create users table".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -141,6 +185,114 @@ mod generate_command_tests {
         assert!(!entries.is_empty());
     }
 
+    /// 複数のスキーマファイルで同じテーブルを定義している場合はマイグレーションを生成しない
+    #[test]
+    fn test_execute_rejects_duplicate_table_across_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        fs::copy(
+            project_path.join("schema/users.yaml"),
+            project_path.join("schema/accounts.yaml"),
+        )
+        .unwrap();
+
+        let command = GenerateCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some("create users table".to_string()),
+            dry_run: false,
+            allow_destructive: false,
+            override_policy: false,
+            verbose: false,
+            format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        };
+
+        let error = format!(
+            "{:#}",
+            GenerateCommandHandler::new().execute(&command).unwrap_err()
+        );
+        assert!(
+            error.contains("Table 'users' is defined in both"),
+            "{}",
+            error
+        );
+        assert!(error.contains("accounts.yaml"), "{}", error);
+        assert!(error.contains("users.yaml"), "{}", error);
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    /// --timings 指定時はJSON出力に各フェーズの実行時間が含まれる
+    #[test]
+    fn test_execute_reports_phase_timings() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+
+        let timings = Timings::new().with_report(true);
+        let handler = GenerateCommandHandler::new().with_timings(timings.clone());
+        let command = GenerateCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some("create users table".to_string()),
+            dry_run: false,
+            allow_destructive: false,
+            override_policy: false,
+            verbose: false,
+            format: strata::cli::OutputFormat::Json,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        };
+
+        let output = handler.execute(&command).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let reported = json["timings"].as_object().unwrap();
+
+        let phases: Vec<_> = timings.phases().iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            vec!["config", "parse", "snapshot", "diff", "validate", "generate", "write"]
+        );
+        for phase in &phases {
+            assert!(reported.contains_key(&format!("{}_ms", phase)), "{}", phase);
+        }
+
+        // 各フェーズの合計は全体の経過時間を超えない
+        let phase_sum: f64 = reported
+            .iter()
+            .filter(|(key, _)| key.as_str() != "total_ms")
+            .map(|(_, value)| value.as_f64().unwrap())
+            .sum();
+        let total = reported["total_ms"].as_f64().unwrap();
+        assert!(phase_sum <= total, "{} > {}", phase_sum, total);
+        assert!(timings.summary_line().starts_with("config="));
+    }
+
     /// descriptionが指定されていない場合の自動生成
     #[test]
     fn test_execute_auto_description() {
@@ -155,11 +307,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: None, // descriptionなし
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -183,11 +345,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create orders table".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         handler.execute(&command).unwrap();
@@ -242,11 +414,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("initial schema".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -281,11 +463,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create customers".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         let result = handler.execute(&command);
@@ -318,11 +510,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create users table".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
 
         handler.execute(&command).unwrap();
@@ -371,11 +573,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create users".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
         handler.execute(&command).unwrap();
 
@@ -389,11 +601,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create metadata".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
         handler.execute(&command2).unwrap();
 
@@ -419,11 +641,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("recreate metadata".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
         handler.execute(&command3).unwrap();
 
@@ -470,11 +702,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create users".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
         handler.execute(&command1).unwrap();
 
@@ -487,11 +729,21 @@ mod generate_command_tests {
             project_path: project_path.to_path_buf(),
             config_path: None,
             schema_dir: None,
+            allow_duplicate_override: false,
             description: Some("create posts".to_string()),
             dry_run: false,
             allow_destructive: false,
+            override_policy: false,
             verbose: false,
             format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
         };
         handler.execute(&command2).unwrap();
 
@@ -529,7 +781,1570 @@ mod generate_command_tests {
         );
     }
 
-    // ヘルパー関数
+    /// ポリシー上限を超えるマイグレーションは拒否される
+    #[test]
+    fn test_execute_policy_violation_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        append_config(project_path, "policy:\n  max_tables_touched: 2\n");
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        create_simple_schema_file(project_path, "posts", &["id", "title"]);
+        create_simple_schema_file(project_path, "tags", &["id", "label"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+
+        let result = handler.execute(&command);
+        assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("touches 3 tables"));
+        assert!(err_msg.contains("--override-policy"));
+
+        // マイグレーションは生成されない
+        let migration_dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .collect();
+        assert!(migration_dirs.is_empty());
+    }
+
+    /// --override-policy 指定時は警告付きで生成され、評価結果がメタデータに記録される
+    #[test]
+    fn test_execute_policy_override_records_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        append_config(project_path, "policy:\n  max_statements: 1\n");
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        create_simple_schema_file(project_path, "posts", &["id", "title"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, true, strata::cli::OutputFormat::Json);
+
+        let output = handler.execute(&command).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["policy"]["overridden"], true);
+        assert_eq!(parsed["policy"]["violations"][0]["rule"], "max_statements");
+        assert_eq!(parsed["policy"]["tables_touched"][0], "posts");
+        assert!(!parsed["warnings"].as_array().unwrap().is_empty());
+
+        let migration_dir = std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+        let meta = fs::read_to_string(migration_dir.join(".meta.yaml")).unwrap();
+        let metadata: strata::core::migration::MigrationMetadata =
+            serde_saphyr::from_str(&meta).unwrap();
+        let policy = metadata.policy.expect("policy should be recorded");
+        assert!(policy.overridden);
+        assert_eq!(policy.tables_touched, vec!["posts", "users"]);
+    }
+
+    /// ポリシー未設定の場合は評価結果を出力しない
+    #[test]
+    fn test_execute_without_policy_omits_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+
+        let output = handler.execute(&command).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(parsed.get("policy").is_none());
+
+        let migration_dir = std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+        let meta = fs::read_to_string(migration_dir.join(".meta.yaml")).unwrap();
+        assert!(!meta.contains("policy:"));
+    }
+
+    /// sql_output設定に従ってup.sql/down.sqlを書き出し、チェックサムは変化しない
+    ///
+    /// up.sql のチェックサム（up_sql_checksum）も正規形に戻して計算するため、終端文字列に依存しない。
+    #[test]
+    fn test_execute_sql_output_options() {
+        let generate = |sql_output: Option<&str>| {
+            let temp_dir = TempDir::new().unwrap();
+            let project_path = temp_dir.path();
+            setup_test_project(project_path, Dialect::PostgreSQL);
+            if let Some(section) = sql_output {
+                append_config(project_path, section);
+            }
+            create_simple_schema_file(project_path, "users", &["id", "name"]);
+
+            let handler = GenerateCommandHandler::new();
+            let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+            let output = handler.execute(&command).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+            let migration_dir =
+                std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+
+            let up = fs::read(migration_dir.join("up.sql")).unwrap();
+            let down = fs::read(migration_dir.join("down.sql")).unwrap();
+            let meta = fs::read_to_string(migration_dir.join(".meta.yaml")).unwrap();
+            let metadata: strata::core::migration::MigrationMetadata =
+                serde_saphyr::from_str(&meta).unwrap();
+            (
+                up,
+                down,
+                metadata.checksum,
+                metadata.up_sql_checksum.unwrap(),
+                temp_dir,
+            )
+        };
+
+        let (plain_up, plain_down, plain_checksum, plain_up_sql_checksum, _plain_dir) =
+            generate(None);
+        let (up, down, checksum, up_sql_checksum, _dir) = generate(Some(
+            "sql_output:\n  line_endings: crlf\n  bom: true\n  statement_terminator: \"$$\"\n",
+        ));
+        let (go_up, _, _, go_up_sql_checksum, _go_dir) =
+            generate(Some("sql_output:\n  statement_terminator: \"\\nGO\"\n"));
+
+        // BOM・CRLF・"$$" を元に戻すと既定設定の出力とバイト単位で一致する
+        // （コメント内のセミコロンは置き換えられない）
+        let normalize = |bytes: &[u8]| {
+            let body = bytes.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap();
+            let text = String::from_utf8(body.to_vec()).unwrap();
+            assert!(!text.replace("\r\n", "").contains('\n'));
+            text.replace("\r\n", "\n").replace("$$", ";").into_bytes()
+        };
+        assert!(String::from_utf8_lossy(&up).ends_with(")$$"));
+        assert_eq!(normalize(&up), plain_up);
+        assert_eq!(normalize(&down), plain_down);
+        assert_eq!(checksum, plain_checksum);
+
+        assert!(String::from_utf8_lossy(&go_up).ends_with(")\nGO"));
+        assert_eq!(up_sql_checksum, plain_up_sql_checksum);
+        assert_eq!(go_up_sql_checksum, plain_up_sql_checksum);
+    }
+
+    /// populate_fromはインデックス作成後のINSERT ... SELECTとして出力される
+    #[test]
+    fn test_execute_populate_from_inserts_after_indexes() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::SQLite);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        fs::write(
+            project_path.join("schema/user_names.yaml"),
+            r#"version: "1.0"
+tables:
+  user_names:
+    columns:
+      - name: name
+        type:
+          kind: VARCHAR
+          length: 255
+        nullable: false
+    indexes:
+      - name: idx_user_names_name
+        columns: [name]
+        unique: false
+    populate_from: |
+      SELECT name FROM users
+"#,
+        )
+        .unwrap();
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        let output = handler.execute(&command).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let migration_dir = std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+
+        let up = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+        let last_index = up.rfind("CREATE INDEX").unwrap();
+        let insert = up.find(r#"INSERT INTO "user_names" ("name")"#).unwrap();
+        assert!(last_index < insert, "{}", up);
+        assert!(up.contains("-- Populate 'user_names' from populate_from"));
+
+        let down = fs::read_to_string(migration_dir.join("down.sql")).unwrap();
+        assert!(!down.contains("INSERT"), "{}", down);
+        assert!(down.contains("-- Data populated from populate_from for 'user_names'"));
+    }
+
+    /// populate_fromにDDLが含まれる場合は生成を拒否する
+    #[test]
+    fn test_execute_populate_from_rejects_ddl() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        fs::write(
+            project_path.join("schema/reports.yaml"),
+            r#"version: "1.0"
+tables:
+  reports:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    populate_from: "SELECT 1; DROP TABLE users"
+"#,
+        )
+        .unwrap();
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = handler.execute(&command).unwrap_err();
+
+        assert!(err.to_string().contains("populate_from"), "{}", err);
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    /// メタデータ付きのスキーマ（owner は引数で差し替え）
+    fn write_payments_schema(project_path: &std::path::Path, owner: &str) {
+        fs::write(
+            project_path.join("schema/payments.yaml"),
+            format!(
+                r#"version: "1.0"
+tables:
+  payments:
+    metadata:
+      owner: {owner}
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+        metadata:
+          pii: "false"
+    primary_key:
+      - id
+    indexes:
+      - name: idx_payments_id
+        columns: [id]
+        metadata:
+          ticket: PAY-7
+"#
+            ),
+        )
+        .unwrap();
+    }
+
+    /// --meta の値が .meta.yaml に、スキーマのメタデータがスナップショットに記録される
+    #[test]
+    fn test_execute_records_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_payments_schema(project_path, "payments-team");
+
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command
+            .metadata
+            .insert("ticket".to_string(), "PAY-123".to_string());
+        handler.execute(&command).unwrap();
+
+        let migration_dir = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| p.is_dir())
+            .unwrap();
+        let meta = fs::read_to_string(migration_dir.join(".meta.yaml")).unwrap();
+        assert!(meta.contains("metadata:\n  ticket: PAY-123"), "{}", meta);
+
+        // apply --only の競合確認用に、変更するテーブルを記録する
+        assert!(meta.contains("touched_tables:\n  - payments"), "{}", meta);
+
+        let up = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+        assert!(!up.contains("payments-team"), "{}", up);
+
+        let snapshot = fs::read_to_string(migration_dir.join(".schema_snapshot.yaml")).unwrap();
+        assert!(snapshot.contains("owner: payments-team"), "{}", snapshot);
+        assert!(snapshot.contains("pii: 'false'") || snapshot.contains("pii: \"false\""));
+        assert!(snapshot.contains("ticket: PAY-7"), "{}", snapshot);
+    }
+
+    /// メタデータだけの変更ではマイグレーションを生成しない
+    #[test]
+    fn test_execute_metadata_only_change_generates_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_payments_schema(project_path, "payments-team");
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        write_payments_schema(project_path, "billing-team");
+        let output = handler.execute(&command).unwrap();
+        assert!(output.contains("No schema changes"), "{}", output);
+    }
+
+    /// required_metadata のキーがないテーブルがあれば生成を拒否する
+    #[test]
+    fn test_execute_rejects_missing_required_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        append_config(project_path, "required_metadata:\n  - owner\n  - ticket\n");
+        write_payments_schema(project_path, "payments-team");
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = handler.execute(&command).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("Table 'payments' is missing required metadata: ticket"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    /// naming.enabled の場合、命名規約の違反は警告として表示され生成は続行する
+    #[test]
+    fn test_execute_reports_naming_convention_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        append_config(project_path, "naming:\n  enabled: true\n");
+        fs::write(
+            project_path.join("schema/blog.yaml"),
+            r#"version: "1.0"
+tables:
+  person:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: author
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [author]
+        referenced_table: person
+        referenced_columns: [id]
+"#,
+        )
+        .unwrap();
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        let output = handler.execute(&command).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let warnings = parsed["warnings"].to_string();
+
+        assert!(
+            warnings.contains("Table 'person' does not follow the plural table name convention (expected 'people')"),
+            "{}",
+            warnings
+        );
+        assert!(
+            warnings.contains("Foreign key column 'posts.author' references 'person'")
+                && warnings.contains("(expected 'person_id')"),
+            "{}",
+            warnings
+        );
+        assert_eq!(migration_count(project_path), 1);
+    }
+
+    /// 部分インデックスを持つスキーマ定義ファイルを作成
+    fn write_partial_index_schema(project_path: &std::path::Path, predicate: &str) {
+        fs::write(
+            project_path.join("schema").join("orders.yaml"),
+            format!(
+                r#"version: "1.0"
+tables:
+  orders:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: customer_id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    indexes:
+      - name: idx_orders_customer_id
+        columns:
+          - customer_id
+        unique: true
+        where: "{}"
+"#,
+                predicate
+            ),
+        )
+        .unwrap();
+    }
+
+    /// 部分インデックスの条件式のみの変更は DROP INDEX + CREATE INDEX になる
+    #[test]
+    fn test_execute_partial_index_where_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_partial_index_schema(project_path, "customer_id > 0");
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        write_partial_index_schema(project_path, "customer_id > 100");
+        handler.execute(&command).unwrap();
+
+        let mut migration_dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        migration_dirs.sort();
+        assert_eq!(migration_dirs.len(), 2);
+
+        let up1 = fs::read_to_string(migration_dirs[0].join("up.sql")).unwrap();
+        assert!(up1.contains(
+            r#"CREATE UNIQUE INDEX "idx_orders_customer_id" ON "orders" ("customer_id") WHERE customer_id > 0"#
+        ));
+
+        let up2 = fs::read_to_string(migration_dirs[1].join("up.sql")).unwrap();
+        let drop_pos = up2
+            .find(r#"DROP INDEX "idx_orders_customer_id""#)
+            .unwrap_or_else(|| panic!("{}", up2));
+        let create_pos = up2
+            .find(r#"("customer_id") WHERE customer_id > 100"#)
+            .unwrap_or_else(|| panic!("{}", up2));
+        assert!(drop_pos < create_pos);
+    }
+
+    /// MySQL では部分インデックスを拒否する
+    #[test]
+    fn test_execute_rejects_partial_index_on_mysql() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::MySQL);
+        write_partial_index_schema(project_path, "customer_id > 0");
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = handler.execute(&command).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("MySQL does not support partial indexes"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    /// 日時型以外のカラムへの CURRENT_TIMESTAMP のデフォルト値は拒否する
+    #[test]
+    fn test_execute_rejects_current_timestamp_on_non_temporal_column() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::MySQL);
+        fs::write(
+            project_path.join("schema").join("events.yaml"),
+            r#"version: "1.0"
+tables:
+  events:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: created_at
+        type:
+          kind: VARCHAR
+          length: 32
+        nullable: false
+        default_value: CURRENT_TIMESTAMP
+    primary_key:
+      - id
+"#,
+        )
+        .unwrap();
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = handler.execute(&command).unwrap_err().to_string();
+        assert!(err.contains("Default value validation errors"), "{}", err);
+        assert!(
+            err.contains("MySQL only accepts CURRENT_TIMESTAMP on TIMESTAMP or DATETIME columns"),
+            "{}",
+            err
+        );
+    }
+
+    /// 識別子の規則に合わない名前は拒否し、設定で許可した文字は受け付ける
+    #[test]
+    fn test_execute_rejects_unsafe_identifiers() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        fs::write(
+            project_path.join("schema").join("order_items.yaml"),
+            r#"version: "1.0"
+tables:
+  order-items:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#,
+        )
+        .unwrap();
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = handler.execute(&command).unwrap_err().to_string();
+        assert!(err.contains("Identifier validation errors"), "{}", err);
+        assert!(
+            err.contains("Table name 'order-items' contains '-'"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        append_config(project_path, "identifiers:\n  allowed_characters: \"-\"\n");
+        handler.execute(&command).unwrap();
+    }
+
+    /// ビュー定義ファイルを作成
+    fn write_view_schema(project_path: &std::path::Path, definition: &str) {
+        let indented: Vec<String> = definition
+            .lines()
+            .map(|line| format!("      {}", line))
+            .collect();
+        fs::write(
+            project_path.join("schema").join("views.yaml"),
+            format!(
+                "version: \"1.0\"\ntables: {{}}\nviews:\n  active_users:\n    definition: |\n{}\n    depends_on: [users]\n",
+                indented.join("\n")
+            ),
+        )
+        .unwrap();
+    }
+
+    /// ビュー定義の空白・末尾セミコロンのみの変更ではマイグレーションを生成しない
+    #[test]
+    fn test_execute_whitespace_only_view_change_generates_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        write_view_schema(project_path, "SELECT id, name FROM users WHERE id > 0");
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        write_view_schema(
+            project_path,
+            "SELECT id,  name\nFROM users\n  WHERE id > 0;",
+        );
+        let output = handler.execute(&command).unwrap();
+        assert!(output.contains("No schema changes found"), "{}", output);
+    }
+
+    /// ビュー定義のみの変更は変更一覧に含まれ、テーブルに影響しないことが明記される
+    #[test]
+    fn test_execute_view_only_change_is_summarized() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        write_view_schema(project_path, "SELECT id, name FROM users");
+
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let output = handler.execute(&command).unwrap();
+        assert!(!output.contains("only views changed"), "{}", output);
+
+        // ビュー定義の変更は破壊的変更として扱われる
+        command.allow_destructive = true;
+        write_view_schema(project_path, "SELECT id, name FROM users WHERE id > 0");
+        let output = handler.execute(&command).unwrap();
+        assert!(
+            output.contains("Changes:\n  ~ MODIFY VIEW active_users"),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains("Note: only views changed. No table data is affected"),
+            "{}",
+            output
+        );
+
+        write_view_schema(project_path, "SELECT id FROM users WHERE id > 0");
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        command.dry_run = true;
+        let json: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+        assert_eq!(
+            json["changes"],
+            serde_json::json!(["~ MODIFY VIEW active_users"])
+        );
+        assert_eq!(json["view_only"], true);
+    }
+
+    /// ビュー定義の編集は、ビューだけを作り直すマイグレーションになる
+    #[test]
+    fn test_execute_view_definition_edit_only_touches_view() {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let temp_dir = TempDir::new().unwrap();
+            let project_path = temp_dir.path();
+            setup_test_project(project_path, dialect);
+            create_simple_schema_file(project_path, "users", &["id", "name"]);
+            write_view_schema(project_path, "SELECT id, name FROM users");
+
+            let handler = GenerateCommandHandler::new();
+            let mut command =
+                generate_command(project_path, false, strata::cli::OutputFormat::Text);
+            handler.execute(&command).unwrap();
+
+            write_view_schema(project_path, "SELECT id, name FROM users WHERE id > 0");
+            command.allow_destructive = true;
+            handler.execute(&command).unwrap();
+
+            let (up_sql, down_sql) = read_latest_migration(project_path);
+            let statements = |sql: &str| -> Vec<String> {
+                sql.lines()
+                    .filter(|line| !line.trim_start().starts_with("--"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .split(';')
+                    .map(|statement| statement.trim().to_string())
+                    .filter(|statement| !statement.is_empty())
+                    .collect()
+            };
+            for statement in statements(&up_sql).iter().chain(&statements(&down_sql)) {
+                assert!(
+                    statement.contains("VIEW") && !statement.contains("TABLE"),
+                    "{}: {}",
+                    dialect,
+                    statement
+                );
+            }
+            assert!(up_sql.contains("FROM users WHERE id > 0"), "{}", up_sql);
+            assert!(
+                down_sql.contains("SELECT id, name FROM users"),
+                "{}",
+                down_sql
+            );
+            assert!(!down_sql.contains("WHERE id > 0"), "{}", down_sql);
+            if dialect == Dialect::SQLite {
+                assert!(up_sql.contains("DROP VIEW IF EXISTS"), "{}", up_sql);
+                assert!(!up_sql.contains("CREATE OR REPLACE"), "{}", up_sql);
+            } else {
+                assert!(up_sql.contains("CREATE OR REPLACE VIEW"), "{}", up_sql);
+            }
+        }
+    }
+
+    /// users.team_id を参照するビューを定義し、初回のマイグレーションを生成する
+    fn setup_view_rename_project(project_path: &std::path::Path) -> GenerateCommandHandler {
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name", "team_id"]);
+        create_simple_schema_file(project_path, "teams", &["id", "name"]);
+        write_view_schema(
+            project_path,
+            "SELECT u.id, t.name AS team\nFROM users u\nJOIN teams t ON t.id = u.team_id\nWHERE u.team_id IS NOT NULL AND name <> ''",
+        );
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        // users.team_id を group_id にリネーム（ビュー定義は旧名のまま）
+        let users = fs::read_to_string(project_path.join("schema").join("users.yaml")).unwrap();
+        fs::write(
+            project_path.join("schema").join("users.yaml"),
+            users.replace(
+                "- name: team_id\n",
+                "- name: group_id\n        renamed_from: team_id\n",
+            ),
+        )
+        .unwrap();
+        handler
+    }
+
+    /// 最後に生成されたマイグレーションの up.sql / down.sql を読み込む
+    fn read_latest_migration(project_path: &std::path::Path) -> (String, String) {
+        let mut migration_dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        migration_dirs.sort();
+        let latest = migration_dirs.last().unwrap();
+        (
+            fs::read_to_string(latest.join("up.sql")).unwrap(),
+            fs::read_to_string(latest.join("down.sql")).unwrap(),
+        )
+    }
+
+    /// リネームしたカラムをビューが旧名で参照している場合、デフォルトでは生成を中止してビューを一覧表示する
+    #[test]
+    fn test_execute_column_rename_referenced_by_view_fails_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let handler = setup_view_rename_project(project_path);
+        let migrations_before = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .count();
+
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.allow_destructive = true;
+        let err = handler.execute(&command).unwrap_err().to_string();
+
+        assert!(
+            err.contains("views still reference renamed tables or columns"),
+            "{}",
+            err
+        );
+        assert!(err.contains("  - active_users"), "{}", err);
+        assert!(
+            err.contains("column 'users.team_id' -> 'group_id'"),
+            "{}",
+            err
+        );
+        assert!(err.contains("view_rename_propagation: rewrite"), "{}", err);
+        assert_eq!(
+            fs::read_dir(project_path.join("migrations"))
+                .unwrap()
+                .count(),
+            migrations_before
+        );
+    }
+
+    /// view_rename_propagation: rewrite の場合、JOIN・WHERE句の参照を書き換えてビューを作り直す
+    #[test]
+    fn test_execute_column_rename_rewrites_view_when_configured() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let handler = setup_view_rename_project(project_path);
+        append_config(project_path, "view_rename_propagation: rewrite\n");
+
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.allow_destructive = true;
+        let output = handler.execute(&command).unwrap();
+        assert!(
+            output.contains("View 'active_users' was rewritten automatically"),
+            "{}",
+            output
+        );
+
+        let (up_sql, down_sql) = read_latest_migration(project_path);
+        assert!(
+            up_sql.contains(
+                "-- NOTE: The definition of view 'active_users' was rewritten automatically"
+            ),
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains(
+                "JOIN teams t ON t.id = u.group_id\nWHERE u.group_id IS NOT NULL AND name <> ''"
+            ),
+            "{}",
+            up_sql
+        );
+        let rename = up_sql.find("RENAME COLUMN").unwrap();
+        let drop_view = up_sql.find("DROP VIEW IF EXISTS \"active_users\"").unwrap();
+        let create_view = up_sql
+            .find("CREATE OR REPLACE VIEW \"active_users\"")
+            .unwrap();
+        assert!(rename < drop_view && drop_view < create_view, "{}", up_sql);
+
+        // DOWN はビューを削除してからリネームを戻し、旧定義で再作成する
+        let drop_view = down_sql
+            .find("DROP VIEW IF EXISTS \"active_users\"")
+            .unwrap();
+        let rename = down_sql.find("RENAME COLUMN").unwrap();
+        let create_view = down_sql
+            .find("CREATE OR REPLACE VIEW \"active_users\"")
+            .unwrap();
+        assert!(drop_view < rename && rename < create_view, "{}", down_sql);
+        assert!(down_sql.contains("ON t.id = u.team_id"), "{}", down_sql);
+    }
+
+    /// 外部テーブルを参照するスキーマ定義ファイルを作成
+    fn write_external_reference_schema(project_path: &std::path::Path) {
+        fs::write(
+            project_path.join("schema").join("posts.yaml"),
+            r#"version: "1.0"
+external_tables: [external_users]
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+        references: external_users.id
+    primary_key:
+      - id
+"#,
+        )
+        .unwrap();
+    }
+
+    /// 外部テーブルへの外部キーは生成でき、外部テーブル自体のCREATE/DROPは出力しない
+    #[test]
+    fn test_execute_external_table_reference() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_external_reference_schema(project_path);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        let migration_dir = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .find(|e| e.path().is_dir())
+            .unwrap()
+            .path();
+        let up_sql = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+        let down_sql = fs::read_to_string(migration_dir.join("down.sql")).unwrap();
+        assert!(up_sql.contains("CREATE TABLE \"posts\""), "{}", up_sql);
+        assert!(
+            up_sql.contains("REFERENCES \"external_users\""),
+            "{}",
+            up_sql
+        );
+        assert!(
+            !up_sql.contains("CREATE TABLE \"external_users\""),
+            "{}",
+            up_sql
+        );
+        assert!(
+            !down_sql.contains("\"external_users\" CASCADE"),
+            "{}",
+            down_sql
+        );
+        assert!(
+            !down_sql.contains("DROP TABLE \"external_users\""),
+            "{}",
+            down_sql
+        );
+
+        // 差分がないことを確認（外部テーブルは追加・削除として検出されない）
+        let output = handler.execute(&command).unwrap();
+        assert!(output.contains("No schema changes found"), "{}", output);
+    }
+
+    /// 管理していたテーブルを外部テーブルに切り替えても DROP TABLE を生成しない
+    #[test]
+    fn test_execute_table_moved_to_external_is_not_dropped() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "external_users", &["id", "name"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        fs::remove_file(project_path.join("schema").join("external_users.yaml")).unwrap();
+        write_external_reference_schema(project_path);
+        let output = handler.execute(&command).unwrap();
+        assert!(!output.contains("Destructive"), "{}", output);
+
+        let mut migration_dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        migration_dirs.sort();
+        let up_sql = fs::read_to_string(migration_dirs.last().unwrap().join("up.sql")).unwrap();
+        assert!(up_sql.contains("CREATE TABLE \"posts\""), "{}", up_sql);
+        assert!(!up_sql.contains("DROP TABLE"), "{}", up_sql);
+    }
+
+    /// 外部テーブルと通常のテーブルの両方に定義されている場合はエラー
+    #[test]
+    fn test_execute_external_table_also_defined_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "external_users", &["id", "name"]);
+        write_external_reference_schema(project_path);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        let err = format!("{:#}", handler.execute(&command).unwrap_err());
+        assert!(
+            err.contains("Table 'external_users' is declared in external_tables"),
+            "{}",
+            err
+        );
+    }
+
+    /// --summary-only はサマリ行のみを出力し、JSONでは summary_lines に含める
+    #[test]
+    fn test_execute_summary_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name", "legacy_flag"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        handler.execute(&command).unwrap();
+
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        create_simple_schema_file(project_path, "orders", &["id"]);
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.dry_run = true;
+        command.summary_only = true;
+        let output = handler.execute(&command).unwrap();
+        assert_eq!(
+            output,
+            "+ table orders (schema/orders.yaml:3)\n! destructive: drop column users.legacy_flag (schema/users.yaml:3)"
+        );
+
+        command.format = strata::cli::OutputFormat::Json;
+        let json: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+        assert_eq!(
+            json["summary_lines"],
+            serde_json::json!([
+                "+ table orders (schema/orders.yaml:3)",
+                "! destructive: drop column users.legacy_flag (schema/users.yaml:3)"
+            ])
+        );
+
+        // 指定しない場合は summary_lines を含めない
+        command.summary_only = false;
+        let json: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+        assert!(json.get("summary_lines").is_none());
+    }
+
+    /// 前のマイグレーションが適用済みとして記録から外されていれば未適用のマイグレーションを報告しない
+    #[test]
+    fn test_execute_clean_pending_generates() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        append_config(project_path, "require_clean_pending: true\n");
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        let first: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+        let (first_version, _) = latest_migration(project_path);
+
+        // apply が適用したマイグレーションを記録から外す
+        let migrations_dir = project_path.join("migrations");
+        stacked_migrations::mark_applied(&migrations_dir, [first_version.as_str()]).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        create_simple_schema_file(project_path, "posts", &["id", "title"]);
+        let second: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+
+        assert!(first.get("pending_migrations").is_none());
+        assert!(second.get("pending_migrations").is_none());
+        assert!(second["migration_name"]
+            .as_str()
+            .unwrap()
+            .ends_with("_policy_test"));
+    }
+
+    /// 適用前のマイグレーションの上に重ねて生成すると、その一覧をJSONに含める
+    #[test]
+    fn test_execute_stacked_on_pending_migration() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        let handler = GenerateCommandHandler::new();
+        let command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        handler.execute(&command).unwrap();
+        let (first_version, first_description) = latest_migration(project_path);
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        create_simple_schema_file(project_path, "posts", &["id", "title"]);
+        let second: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+
+        assert_eq!(
+            second["pending_migrations"],
+            serde_json::json!([{
+                "version": first_version,
+                "description": first_description,
+            }])
+        );
+        // 重ねたマイグレーションには適用前のマイグレーションの変更を含めない
+        let up_sql = fs::read_to_string(
+            std::path::PathBuf::from(second["migration_path"].as_str().unwrap()).join("up.sql"),
+        )
+        .unwrap();
+        assert!(up_sql.contains("\"posts\""));
+        assert!(!up_sql.contains("\"users\""));
+
+        // 2つ目も適用されるまでは記録に残る
+        let pending =
+            stacked_migrations::load_pending_versions(&project_path.join("migrations")).unwrap();
+        assert_eq!(pending.len(), 2);
+    }
+
+    /// --require-clean-pending は generate → generate で適用前のマイグレーションがあると拒否し、
+    /// --allow-stacked で許可する
+    #[test]
+    fn test_execute_require_clean_pending_refuses_stacked() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.require_clean_pending = true;
+        handler.execute(&command).unwrap();
+        let (first_version, first_description) = latest_migration(project_path);
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        create_simple_schema_file(project_path, "posts", &["id", "title"]);
+        let err_msg = handler.execute(&command).unwrap_err().to_string();
+        assert!(
+            err_msg.contains("1 migration(s) have been generated but not applied yet"),
+            "{}",
+            err_msg
+        );
+        assert!(err_msg.contains(&format!("{} {}", first_version, first_description)));
+        assert!(err_msg.contains("--allow-stacked"));
+        assert_eq!(migration_count(project_path), 1);
+
+        command.allow_stacked = true;
+        handler.execute(&command).unwrap();
+        assert_eq!(migration_count(project_path), 2);
+    }
+
+    /// --sql-only / --explain / --annotate の出力は書き出されるマイグレーションファイルと一致する
+    #[test]
+    fn test_execute_dry_run_sql_matches_generated_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        create_simple_schema_file(project_path, "users", &["id", "name", "legacy_flag"]);
+
+        let handler = GenerateCommandHandler::new();
+        handler
+            .execute(&generate_command(
+                project_path,
+                false,
+                strata::cli::OutputFormat::Text,
+            ))
+            .unwrap();
+
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        create_simple_schema_file(project_path, "orders", &["id", "status"]);
+
+        let dry_run = |mode: DryRunSqlMode, down: bool| {
+            let mut command =
+                generate_command(project_path, false, strata::cli::OutputFormat::Text);
+            command.dry_run = true;
+            command.dry_run_sql = mode;
+            command.down = down;
+            handler.execute(&command).unwrap()
+        };
+        let sql_only = dry_run(DryRunSqlMode::SqlOnly { explain: false }, false);
+        let explained = dry_run(DryRunSqlMode::SqlOnly { explain: true }, false);
+        let annotated = dry_run(DryRunSqlMode::Annotated, false);
+        let down_sql_only = dry_run(DryRunSqlMode::SqlOnly { explain: false }, true);
+        let down_annotated = dry_run(DryRunSqlMode::Annotated, true);
+
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Json);
+        command.allow_destructive = true;
+        let parsed: serde_json::Value =
+            serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+        let migration_dir = std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+        let up_file = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+        let down_file = fs::read_to_string(migration_dir.join("down.sql")).unwrap();
+
+        // --explain はファイルの内容そのもの、--sql-only はファイルからコメントを除いたもの
+        assert_eq!(explained, up_file);
+        assert!(up_file.contains("-- Transaction:"));
+        assert_eq!(sql_only, without_comments(&up_file));
+        assert_eq!(down_sql_only, without_comments(&down_file));
+
+        // --annotate はコメントを除けば --sql-only と同じ
+        assert_eq!(without_comments(&annotated), sql_only);
+        assert_eq!(without_comments(&down_annotated), down_sql_only);
+        assert!(annotated.contains(
+            "-- object: table orders\n-- source: schema/orders.yaml\n-- destructive: no\n"
+        ));
+        assert!(annotated.contains(
+            "-- object: table users\n-- source: schema/users.yaml\n-- destructive: yes\nALTER TABLE \"users\" DROP COLUMN \"legacy_flag\""
+        ));
+        assert!(down_annotated.contains("-- object: table orders\n-- source: schema/orders.yaml\n-- destructive: yes\nDROP TABLE"));
+    }
+
+    /// --sql-only の出力はそのまま実行でき、変更がなければ何も出力しない
+    #[tokio::test]
+    async fn test_execute_dry_run_sql_only_is_executable() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::SQLite);
+        create_simple_schema_file(project_path, "users", &["id", "name"]);
+        create_simple_schema_file(project_path, "orders", &["id", "status"]);
+
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.dry_run = true;
+        command.dry_run_sql = DryRunSqlMode::SqlOnly { explain: false };
+        let sql = handler.execute(&command).unwrap();
+
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            project_path.join("sandbox.db").display()
+        );
+        let pool = sqlx::AnyPool::connect(&url).await.unwrap();
+        sqlx::raw_sql(&sql).execute(&pool).await.unwrap();
+        let tables: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(tables, vec!["orders", "users"]);
+        pool.close().await;
+
+        command.dry_run = false;
+        command.dry_run_sql = DryRunSqlMode::Report;
+        handler.execute(&command).unwrap();
+        command.dry_run = true;
+        command.dry_run_sql = DryRunSqlMode::SqlOnly { explain: false };
+        assert_eq!(handler.execute(&command).unwrap(), "");
+
+        // SQLとして出力するモードはJSON出力と組み合わせられない
+        command.format = strata::cli::OutputFormat::Json;
+        let err = handler.execute(&command).unwrap_err();
+        assert!(err.to_string().contains("--format json"));
+    }
+
+    // ヘルパー関数
+
+    /// SQLからコメント行を除く（--sql-only の出力との比較用）
+    fn without_comments(sql: &str) -> String {
+        sql.lines()
+            .filter(|line| !line.trim_start().starts_with("--"))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_start()
+            .to_string()
+    }
+
+    /// 最新のマイグレーションのバージョンと説明
+    fn latest_migration(project_path: &std::path::Path) -> (String, String) {
+        let mut names: Vec<String> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        let (version, description) = names.last().unwrap().split_once('_').unwrap();
+        (version.to_string(), description.to_string())
+    }
+
+    /// 生成されたマイグレーションの数
+    fn migration_count(project_path: &std::path::Path) -> usize {
+        fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .count()
+    }
+
+    /// 値が失われる型変更は --allow-destructive が必要で、JSON出力にカラムごとの分類が含まれる
+    #[test]
+    fn test_execute_classifies_type_changes() {
+        let write_schema = |project_path: &std::path::Path, old: bool| {
+            let columns = if old {
+                "name: { kind: VARCHAR, length: 255 }
+      visits: { kind: INTEGER, precision: 8 }
+      seen_at: { kind: TIMESTAMP }
+      code: { kind: TEXT }"
+            } else {
+                "name: { kind: VARCHAR, length: 50 }
+      visits: { kind: INTEGER }
+      seen_at: { kind: DATE }
+      code: { kind: INTEGER }"
+            };
+            let mut column_defs = String::new();
+            for line in columns.lines() {
+                let (name, column_type) = line.trim().split_once(": ").unwrap();
+                column_defs.push_str(&format!(
+                    "      - name: {}\n        type: {}\n        nullable: true\n",
+                    name, column_type
+                ));
+            }
+            fs::write(
+                project_path.join("schema").join("events.yaml"),
+                format!(
+                    "version: \"1.0\"\ntables:\n  events:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n{}    primary_key:\n      - id\n",
+                    column_defs
+                ),
+            )
+            .unwrap();
+        };
+
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let temp_dir = TempDir::new().unwrap();
+            let project_path = temp_dir.path();
+            setup_test_project(project_path, dialect);
+            write_schema(project_path, true);
+
+            let handler = GenerateCommandHandler::new();
+            let mut command =
+                generate_command(project_path, false, strata::cli::OutputFormat::Json);
+            handler.execute(&command).unwrap();
+
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            write_schema(project_path, false);
+            let err_msg = handler.execute(&command).unwrap_err().to_string();
+            assert!(
+                err_msg.contains("Columns with lossy type changes:"),
+                "{}: {}",
+                dialect,
+                err_msg
+            );
+            assert!(err_msg.contains("events.name"), "{}: {}", dialect, err_msg);
+            assert!(
+                err_msg.contains("events.visits"),
+                "{}: {}",
+                dialect,
+                err_msg
+            );
+            assert!(
+                err_msg.contains("events.seen_at"),
+                "{}: {}",
+                dialect,
+                err_msg
+            );
+            assert_eq!(migration_count(project_path), 1);
+
+            command.allow_destructive = true;
+            let parsed: serde_json::Value =
+                serde_json::from_str(&handler.execute(&command).unwrap()).unwrap();
+            let mut classifications: Vec<(String, String)> = parsed["type_changes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|entry| {
+                    assert_eq!(entry["table"], "events");
+                    (
+                        entry["column"].as_str().unwrap().to_string(),
+                        entry["classification"].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect();
+            classifications.sort();
+            assert_eq!(
+                classifications,
+                vec![
+                    ("code".to_string(), "needs_using".to_string()),
+                    ("name".to_string(), "lossy".to_string()),
+                    ("seen_at".to_string(), "lossy".to_string()),
+                    ("visits".to_string(), "lossy".to_string()),
+                ],
+                "{}",
+                dialect
+            );
+
+            if dialect == Dialect::PostgreSQL {
+                let migration_dir =
+                    std::path::PathBuf::from(parsed["migration_path"].as_str().unwrap());
+                let up = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+                assert!(up.contains(
+                    "ALTER TABLE \"events\" ALTER COLUMN \"code\" TYPE INTEGER USING \"code\"::INTEGER"
+                ));
+                assert!(
+                    up.contains("ALTER TABLE \"events\" ALTER COLUMN \"name\" TYPE VARCHAR(50);")
+                );
+            }
+        }
+    }
+
+    /// PostgreSQL の互換性のない型変更は、YAML の type_change_using / down_using で変換する
+    #[test]
+    fn test_execute_uses_type_change_using_on_postgres() {
+        let write_schema = |project_path: &std::path::Path, column: &str| {
+            fs::write(
+                project_path.join("schema").join("documents.yaml"),
+                format!(
+                    "version: \"1.0\"\ntables:\n  documents:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n      - name: data\n{}    primary_key:\n      - id\n",
+                    column
+                ),
+            )
+            .unwrap();
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_schema(
+            project_path,
+            "        type: { kind: JSONB }\n        nullable: true\n",
+        );
+
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.allow_destructive = true;
+        handler.execute(&command).unwrap();
+
+        // USING 式がなければ、追加すべきキーを示して失敗する
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        write_schema(
+            project_path,
+            "        type: { kind: INTEGER }\n        nullable: true\n",
+        );
+        let err_msg = handler.execute(&command).unwrap_err().to_string();
+        assert!(
+            err_msg
+                .contains("add `type_change_using: \"<expression>\"` to column 'documents.data'"),
+            "{}",
+            err_msg
+        );
+        assert_eq!(migration_count(project_path), 1);
+
+        write_schema(
+            project_path,
+            "        type: { kind: INTEGER }\n        nullable: true\n        type_change_using: \"(data->>'count')::integer\"\n        down_using: \"jsonb_build_object('count', data)\"\n",
+        );
+        handler.execute(&command).unwrap();
+        let migration_dir = latest_migration_dir(project_path);
+        let up = fs::read_to_string(migration_dir.join("up.sql")).unwrap();
+        assert!(
+            up.contains("ALTER TABLE \"documents\" ALTER COLUMN \"data\" TYPE INTEGER USING (data->>'count')::integer;"),
+            "{}",
+            up
+        );
+        let down = fs::read_to_string(migration_dir.join("down.sql")).unwrap();
+        assert!(
+            down.contains("ALTER TABLE \"documents\" ALTER COLUMN \"data\" TYPE JSONB USING jsonb_build_object('count', data);"),
+            "{}",
+            down
+        );
+
+        // 使用済みの式は次の型変更に持ち越さない
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        write_schema(
+            project_path,
+            "        type: { kind: BIGINT }\n        nullable: true\n        type_change_using: \"(data->>'count')::integer\"\n        down_using: \"jsonb_build_object('count', data)\"\n",
+        );
+        handler.execute(&command).unwrap();
+        let up = fs::read_to_string(latest_migration_dir(project_path).join("up.sql")).unwrap();
+        assert!(
+            up.contains("ALTER TABLE \"documents\" ALTER COLUMN \"data\" TYPE BIGINT;"),
+            "{}",
+            up
+        );
+    }
+
+    /// --with-preflight-queries 指定時は、既存のテーブルに追加する制約ごとの事前確認クエリを書き出す
+    #[test]
+    fn test_execute_writes_preflight_queries() {
+        let write_schema = |project_path: &std::path::Path, constrained: bool| {
+            let (nullable, constraints) = if constrained {
+                (
+                    "false",
+                    r#"
+    constraints:
+      - type: CHECK
+        columns:
+          - score
+        check_expression: "score >= 0"
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - id"#,
+                )
+            } else {
+                ("true", "")
+            };
+            fs::write(
+                project_path.join("schema").join("posts.yaml"),
+                format!(
+                    r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: true
+      - name: score
+        type:
+          kind: INTEGER
+        nullable: {}
+    primary_key:
+      - id{}
+"#,
+                    nullable, constraints
+                ),
+            )
+            .unwrap();
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        setup_test_project(project_path, Dialect::PostgreSQL);
+        write_schema(project_path, false);
+
+        let handler = GenerateCommandHandler::new();
+        let mut command = generate_command(project_path, false, strata::cli::OutputFormat::Text);
+        command.with_preflight_queries = true;
+
+        // 新しく作成するテーブルにはデータがないため、事前確認クエリは書き出さない
+        let output = handler.execute(&command).unwrap();
+        assert!(
+            output.contains("Preflight queries: 0 (no constraints added to existing tables)"),
+            "{}",
+            output
+        );
+        assert!(!latest_migration_dir(project_path)
+            .join("preflight.sql")
+            .exists());
+
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        write_schema(project_path, true);
+        let output = handler.execute(&command).unwrap();
+        assert!(
+            output.contains("Preflight queries: 3 (written to preflight.sql)"),
+            "{}",
+            output
+        );
+        let preflight =
+            fs::read_to_string(latest_migration_dir(project_path).join("preflight.sql")).unwrap();
+        assert!(preflight.contains(
+            "-- preflight: NOT NULL posts.score\nSELECT COUNT(*) FROM \"posts\" WHERE \"score\" IS NULL;"
+        ), "{}", preflight);
+        assert!(preflight.contains(
+            "-- preflight: CHECK posts.score (score >= 0)\nSELECT COUNT(*) FROM \"posts\" WHERE NOT (score >= 0);"
+        ), "{}", preflight);
+        assert!(preflight.contains(
+            "-- preflight: FOREIGN KEY posts(user_id) -> users(id)\nSELECT COUNT(*) FROM \"posts\" \"c\" WHERE \"c\".\"user_id\" IS NOT NULL AND NOT EXISTS (SELECT 1 FROM \"users\" \"p\" WHERE \"p\".\"id\" = \"c\".\"user_id\");"
+        ), "{}", preflight);
+    }
+
+    /// 最後に生成されたマイグレーションのディレクトリ
+    fn latest_migration_dir(project_path: &std::path::Path) -> std::path::PathBuf {
+        let mut migration_dirs: Vec<_> = fs::read_dir(project_path.join("migrations"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| {
+                path.is_dir() && !path.file_name().unwrap().to_string_lossy().starts_with('.')
+            })
+            .collect();
+        migration_dirs.sort();
+        migration_dirs.pop().unwrap()
+    }
+
+    /// generateコマンドを作成
+    fn generate_command(
+        project_path: &std::path::Path,
+        override_policy: bool,
+        format: strata::cli::OutputFormat,
+    ) -> GenerateCommand {
+        GenerateCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some("policy test".to_string()),
+            dry_run: false,
+            allow_destructive: false,
+            override_policy,
+            verbose: false,
+            format,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        }
+    }
+
+    /// 設定ファイルにセクションを追記
+    fn append_config(project_path: &std::path::Path, section: &str) {
+        let config_path = project_path.join(".strata.yaml");
+        let mut content = fs::read_to_string(&config_path).unwrap();
+        content.push_str(section);
+        fs::write(config_path, content).unwrap();
+    }
 
     /// テストプロジェクトをセットアップ
     fn setup_test_project(project_path: &std::path::Path, dialect: Dialect) {
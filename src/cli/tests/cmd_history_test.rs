@@ -0,0 +1,247 @@
+// historyコマンドハンドラーのテスト
+//
+// `store_applied_sql: true` で適用したマイグレーションのSQLが
+// schema_migrations に保存され、history --show-sql で取得できることを確認します。
+
+use sqlx::any::install_default_drivers;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::command_context::CommandContext;
+use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::history::{HistoryCommand, HistoryCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::{Config, Dialect};
+use strata::core::migration::{MAX_STORED_SQL_BYTES, TRUNCATED_SQL_MARKER};
+use strata::services::config_serializer::ConfigSerializer;
+use tempfile::TempDir;
+
+mod common;
+
+/// SQLiteファイルを使うプロジェクトを作成
+fn setup_project(store_applied_sql: bool) -> (TempDir, PathBuf) {
+    let (temp_dir, project_path) = common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_config(&project_path, store_applied_sql);
+    (temp_dir, project_path)
+}
+
+fn write_config(project_path: &Path, store_applied_sql: bool) {
+    let db_path = project_path.join("history.db");
+    let mut config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    config.store_applied_sql = store_applied_sql;
+    fs::write(
+        project_path.join(Config::DEFAULT_CONFIG_PATH),
+        ConfigSerializer::to_yaml(&config).unwrap(),
+    )
+    .unwrap();
+}
+
+async fn apply(project_path: &Path) -> String {
+    install_default_drivers();
+    let command = ApplyCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    ApplyCommandHandler::new().execute(&command).await.unwrap()
+}
+
+async fn history(
+    project_path: &Path,
+    show_sql: Option<&str>,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    let command = HistoryCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        show_sql: show_sql.map(str::to_string),
+        format,
+    };
+    HistoryCommandHandler::new().execute(&command).await
+}
+
+#[tokio::test]
+async fn test_history_show_sql_returns_stored_up_and_down_sql() {
+    let (_temp_dir, project_path) = setup_project(true);
+    let up_sql = "CREATE TABLE users (id INTEGER PRIMARY KEY);\n";
+    common::create_test_migration(
+        &project_path,
+        "20260101120000",
+        "create_users",
+        up_sql,
+        "DROP TABLE users;\n",
+        "checksum1",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    let text = history(&project_path, Some("20260101120000"), OutputFormat::Text)
+        .await
+        .unwrap();
+    assert!(text.contains("=== Migration 20260101120000 - create_users ==="));
+    assert!(text.contains("--- up.sql ---\nCREATE TABLE users (id INTEGER PRIMARY KEY);"));
+    assert!(text.contains("--- down.sql ---\nDROP TABLE users;"));
+
+    let json = history(&project_path, Some("20260101120000"), OutputFormat::Json)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["up_sql"], up_sql);
+    assert_eq!(value["down_sql"], "DROP TABLE users;\n");
+    assert_eq!(value["truncated"], false);
+}
+
+#[tokio::test]
+async fn test_history_upgrades_existing_migration_table_lazily() {
+    // store_applied_sql 無効のまま適用した記録がある状態から有効化する
+    let (_temp_dir, project_path) = setup_project(false);
+    common::create_test_migration(
+        &project_path,
+        "20260101120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    // 保存用カラムがなくても history は動作する
+    let json = history(&project_path, None, OutputFormat::Json)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["migrations"][0]["sql"], "not_stored");
+
+    write_config(&project_path, true);
+    common::create_test_migration(
+        &project_path,
+        "20260102120000",
+        "create_posts",
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+        "DROP TABLE posts;",
+        "checksum2",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    let json = history(&project_path, None, OutputFormat::Json)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let migrations = value["migrations"].as_array().unwrap();
+    assert_eq!(migrations.len(), 2);
+    assert_eq!(migrations[0]["sql"], "not_stored");
+    assert_eq!(migrations[1]["sql"], "stored");
+    assert_eq!(migrations[1]["checksum"], "checksum2");
+
+    let err = history(&project_path, Some("20260101120000"), OutputFormat::Text)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("SQL of migration 20260101120000 was not stored"),
+        "{}",
+        err
+    );
+
+    let err = history(&project_path, Some("20991231235959"), OutputFormat::Text)
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Migration 20991231235959 has not been applied"));
+}
+
+#[tokio::test]
+async fn test_apply_truncates_sql_over_storage_limit() {
+    let (_temp_dir, project_path) = setup_project(true);
+    let padding = "-- padding\n".repeat(MAX_STORED_SQL_BYTES / 10);
+    let up_sql = format!("{}CREATE TABLE big (id INTEGER PRIMARY KEY);\n", padding);
+    common::create_test_migration(
+        &project_path,
+        "20260101120000",
+        "create_big",
+        &up_sql,
+        "DROP TABLE big;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let summary = apply(&project_path).await;
+    assert!(
+        summary.contains(&format!(
+            "up.sql of migration 20260101120000 is {} bytes; only the first {} bytes are stored",
+            up_sql.len(),
+            MAX_STORED_SQL_BYTES
+        )),
+        "{}",
+        summary
+    );
+
+    let json = history(&project_path, Some("20260101120000"), OutputFormat::Json)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let stored = value["up_sql"].as_str().unwrap();
+    assert_eq!(value["truncated"], true);
+    assert!(stored.len() <= MAX_STORED_SQL_BYTES);
+    assert!(stored.starts_with("-- padding\n"));
+    assert!(stored.trim_end().ends_with(&format!(
+        "{} (original size: {} bytes)",
+        TRUNCATED_SQL_MARKER,
+        up_sql.len()
+    )));
+    // 上限内のdown.sqlはそのまま保存される
+    assert_eq!(value["down_sql"], "DROP TABLE big;");
+
+    let text = history(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap();
+    assert!(text.contains("truncated"), "{}", text);
+}
+
+#[tokio::test]
+async fn test_history_does_not_read_schema_directory() {
+    install_default_drivers();
+    let (_temp_dir, project_path) = setup_project(false);
+    fs::write(
+        project_path.join("schema").join("broken.yaml"),
+        "tables: [unclosed",
+    )
+    .unwrap();
+
+    let context = CommandContext::load(project_path.clone()).unwrap();
+    let command = HistoryCommand {
+        project_path,
+        config_path: None,
+        env: "development".to_string(),
+        show_sql: None,
+        format: OutputFormat::Text,
+    };
+
+    let result = HistoryCommandHandler::new()
+        .execute_with_context(&context, &command)
+        .await;
+    context.close_pools().await;
+
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert_eq!(context.schema_loads(), 0);
+}
@@ -5,9 +5,15 @@
 mod init_command_tests {
     use std::fs;
     use std::path::PathBuf;
-    use strata::cli::commands::init::{ConfigFileParams, InitCommand, InitCommandHandler};
+    use strata::cli::commands::init::{
+        ConfigFileParams, InitCommand, InitCommandHandler, InitInteractiveCommand,
+    };
+    use strata::cli::commands::init_example::EXAMPLE_MIGRATION_NAME;
+    use strata::cli::commands::init_wizard::ScriptedPrompter;
+    use strata::cli::commands::migration_loader::load_available_migrations;
     use strata::core::config::Dialect;
     use strata::services::config_loader::ConfigLoader;
+    use strata::services::schema_io::schema_parser::SchemaParserService;
     use tempfile::TempDir;
 
     /// コマンドハンドラーの作成テスト
@@ -69,6 +75,45 @@ mod init_command_tests {
         assert!(content.contains("database: mydb"));
     }
 
+    /// パスワード未指定時は環境変数の参照を書き、未定義なら読み込み時にエラーにする
+    #[test]
+    fn test_generate_config_file_uses_password_reference() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let config_path = project_path.join(".strata.yaml");
+
+        let handler = InitCommandHandler::new();
+        let params = ConfigFileParams {
+            dialect: Dialect::PostgreSQL,
+            database_name: "mydb".to_string(),
+            host: None,
+            port: None,
+            user: None,
+            password: None,
+        };
+        handler
+            .generate_config_file(project_path, params, None)
+            .expect("Failed to generate config file");
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(
+            content.contains("${STRATA_DEVELOPMENT_PASSWORD}"),
+            "{}",
+            content
+        );
+        assert!(!content.contains("your_password"), "{}", content);
+
+        if std::env::var("STRATA_DEVELOPMENT_PASSWORD").is_err() {
+            let err = ConfigLoader::from_file(&config_path).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("Environment variable 'STRATA_DEVELOPMENT_PASSWORD'"),
+                "{:#}",
+                err
+            );
+        }
+    }
+
     /// 設定ファイル生成テスト - MySQL
     #[test]
     fn test_generate_config_file_mysql() {
@@ -177,6 +222,7 @@ mod init_command_tests {
             user: Some("user".to_string()),
             password: Some("pass".to_string()),
             add_gitignore: false,
+            with_example: false,
             format: strata::cli::OutputFormat::Text,
         };
 
@@ -218,6 +264,7 @@ mod init_command_tests {
             user: Some("root".to_string()),
             password: Some("newpass".to_string()),
             add_gitignore: false,
+            with_example: false,
             format: strata::cli::OutputFormat::Text,
         };
 
@@ -248,6 +295,7 @@ mod init_command_tests {
             user: Some("postgres".to_string()),
             password: Some("secret".to_string()),
             add_gitignore: false,
+            with_example: false,
             format: strata::cli::OutputFormat::Text,
         };
 
@@ -270,6 +318,67 @@ mod init_command_tests {
 
         let dev_config = config.get_database_config("development").unwrap();
         assert_eq!(dev_config.database, "myapp");
+
+        // 未適用マイグレーションの記録は --add-gitignore なしでも .gitignore に追加される
+        let gitignore = fs::read_to_string(project_path.join(".gitignore")).unwrap();
+        assert_eq!(gitignore, "migrations/.pending_migrations.yaml\n");
+
+        // 再実行しても重複して追記しない
+        let command = InitCommand {
+            force: true,
+            add_gitignore: true,
+            ..command
+        };
+        handler.execute(&command).unwrap();
+        let gitignore = fs::read_to_string(project_path.join(".gitignore")).unwrap();
+        assert_eq!(
+            gitignore,
+            "migrations/.pending_migrations.yaml\n.strata.yaml\n"
+        );
+    }
+
+    /// --with-example によるサンプル生成テスト
+    #[test]
+    fn test_execute_with_example() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        let handler = InitCommandHandler::new();
+        let command = InitCommand {
+            project_path: project_path.to_path_buf(),
+            dialect: Dialect::PostgreSQL,
+            force: false,
+            database_name: "myapp".to_string(),
+            host: None,
+            port: None,
+            user: None,
+            password: None,
+            add_gitignore: false,
+            with_example: true,
+            format: strata::cli::OutputFormat::Text,
+        };
+
+        let output = handler.execute(&command).unwrap();
+        assert!(output.contains("schema/example.yaml"));
+
+        // サンプルスキーマはスキーマディレクトリとして読み込める
+        let schema = SchemaParserService::new()
+            .parse_schema_directory(&project_path.join("schema"))
+            .unwrap();
+        assert!(schema.tables.contains_key("users"));
+        assert!(schema.tables.contains_key("posts"));
+        assert!(schema.enums.contains_key("post_status"));
+        assert!(schema.views.contains_key("published_posts"));
+        assert!(project_path.join("schema/README.md").exists());
+
+        // サンプルマイグレーションは examples/ に置かれ、適用対象にならない
+        let example_dir = project_path
+            .join("migrations/examples")
+            .join(EXAMPLE_MIGRATION_NAME);
+        assert!(example_dir.join("up.sql").exists());
+        assert!(example_dir.join("down.sql").exists());
+        let migrations = load_available_migrations(&project_path.join("migrations")).unwrap();
+        assert!(migrations.is_empty());
     }
 
     /// 設定ファイルのバリデーションテスト
@@ -315,6 +424,7 @@ mod init_command_tests {
             user: None,
             password: None,
             add_gitignore: false,
+            with_example: false,
             format: strata::cli::OutputFormat::Text,
         };
 
@@ -327,4 +437,134 @@ mod init_command_tests {
         assert!(config.schema_dir.is_relative());
         assert!(config.migrations_dir.is_relative());
     }
+
+    fn interactive_command(project_path: &std::path::Path) -> InitInteractiveCommand {
+        InitInteractiveCommand {
+            project_path: project_path.to_path_buf(),
+            force: false,
+            add_gitignore: false,
+            with_example: false,
+            format: strata::cli::OutputFormat::Text,
+        }
+    }
+
+    /// 対話モードで入力した設定が、設定ファイルの読み込みと検証を通過することを確認
+    #[tokio::test]
+    async fn test_execute_interactive_writes_valid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        let mut prompter = ScriptedPrompter::new([
+            "postgresql",
+            "development,production",
+            // development: パスワードは直接入力
+            "",
+            "",
+            "",
+            "app",
+            "n",
+            "local-pass",
+            // production: パスワードは環境変数参照
+            "db.example.com",
+            "",
+            "app_production",
+            "deploy",
+            "",
+            "PROD_DB_PASSWORD",
+            "db/schema",
+            "db/migrations",
+            "n",
+        ]);
+
+        let handler = InitCommandHandler::new();
+        let output = handler
+            .execute_interactive(&interactive_command(project_path), &mut prompter)
+            .await
+            .unwrap();
+        assert_eq!(prompter.remaining(), 0);
+        assert!(output.contains("Project initialized."));
+
+        let config_path = project_path.join(".strata.yaml");
+        let yaml = fs::read_to_string(&config_path).unwrap();
+        // 環境変数参照はそのまま書き込まれる
+        assert!(yaml.contains("${PROD_DB_PASSWORD}"), "{}", yaml);
+
+        // 未定義の環境変数は読み込み時にエラーになるため、展開せずに読み込む
+        let config = ConfigLoader::from_file_without_interpolation(&config_path).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.dialect, Dialect::PostgreSQL);
+        assert_eq!(config.schema_dir, PathBuf::from("db/schema"));
+        assert_eq!(config.migrations_dir, PathBuf::from("db/migrations"));
+        assert_eq!(config.environments.len(), 2);
+
+        let development = &config.environments["development"];
+        assert_eq!(development.host, "localhost");
+        assert_eq!(development.database, "postgresql_db");
+        assert_eq!(development.password.as_deref(), Some("local-pass"));
+        let production = &config.environments["production"];
+        assert_eq!(production.host, "db.example.com");
+        assert_eq!(production.port, Some(5432));
+        assert_eq!(production.user.as_deref(), Some("deploy"));
+        assert_eq!(production.password.as_deref(), Some("${PROD_DB_PASSWORD}"));
+
+        assert!(project_path.join("db/schema").is_dir());
+        assert!(project_path.join("db/migrations").is_dir());
+    }
+
+    /// 接続テストに失敗して書き込みを取りやめた場合、ファイルが作成されないことを確認
+    #[tokio::test]
+    async fn test_execute_interactive_connection_failure_writes_nothing() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        let mut prompter = ScriptedPrompter::new([
+            "postgresql",
+            "",
+            "127.0.0.1",
+            "1", // 接続できないポート
+            "",
+            "app",
+            "",
+            "",
+            "",
+            "",
+            "",  // 接続テストを行う
+            "n", // 書き込みを取りやめる
+        ]);
+
+        let handler = InitCommandHandler::new();
+        let result = handler
+            .execute_interactive(&interactive_command(project_path), &mut prompter)
+            .await;
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("No files were written"), "{}", err);
+        assert!(prompter
+            .transcript
+            .iter()
+            .any(|line| line.starts_with("✗ development:")));
+        assert!(!project_path.join(".strata.yaml").exists());
+        assert!(!project_path.join("schema").exists());
+    }
+
+    /// 初期化済みのプロジェクトでは入力を求めずにエラーになることを確認
+    #[tokio::test]
+    async fn test_execute_interactive_already_initialized() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        fs::write(project_path.join(".strata.yaml"), "version: \"1.0\"\n").unwrap();
+
+        let mut prompter = ScriptedPrompter::new(Vec::<String>::new());
+        let handler = InitCommandHandler::new();
+        let result = handler
+            .execute_interactive(&interactive_command(project_path), &mut prompter)
+            .await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("already initialized"));
+        assert!(prompter.transcript.is_empty());
+    }
 }
@@ -0,0 +1,187 @@
+// inspectコマンドハンドラーのテスト
+//
+// SQLiteのファイルデータベースに実データを投入し、
+// --suggest-sizes が宣言長と実データ長を比較して推奨サイズを提案することを確認します。
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use std::fs;
+use std::path::PathBuf;
+use strata::cli::commands::inspect::{InspectCommand, InspectCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+
+mod common;
+
+const USERS_SCHEMA: &str = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: nickname
+        type:
+          kind: VARCHAR
+          length: 255
+        nullable: false
+      - name: code
+        type:
+          kind: VARCHAR
+          length: 8
+        nullable: false
+      - name: memo
+        type:
+          kind: TEXT
+        nullable: true
+    primary_key:
+      - id
+"#;
+
+/// users テーブルを作成してデータを投入したプロジェクトを作成
+async fn setup_project() -> (TempDir, PathBuf) {
+    install_default_drivers();
+    let (temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    fs::write(project_path.join("schema").join("users.yaml"), USERS_SCHEMA).unwrap();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    for sql in [
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, nickname VARCHAR(255) NOT NULL, code VARCHAR(8) NOT NULL, memo TEXT)",
+        "INSERT INTO users (id, nickname, code, memo) VALUES (1, 'alice', 'ABCDEFGH', NULL)",
+        "INSERT INTO users (id, nickname, code, memo) VALUES (2, 'bartholomew', 'XY', NULL)",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+    pool.close().await;
+
+    (temp_dir, project_path)
+}
+
+fn inspect_command(project_path: &std::path::Path) -> InspectCommand {
+    InspectCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        suggest_sizes: true,
+        tables: vec![],
+        columns: vec![],
+        allow_full_scan: false,
+        max_rows: 1_000_000,
+        format: OutputFormat::Json,
+    }
+}
+
+async fn run_json(command: &InspectCommand) -> serde_json::Value {
+    let output = InspectCommandHandler::new().execute(command).await.unwrap();
+    serde_json::from_str(&output).unwrap()
+}
+
+fn column<'a>(json: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    json["columns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["column"] == name)
+        .unwrap_or_else(|| panic!("column {} not in {}", name, json))
+}
+
+#[tokio::test]
+async fn test_suggest_sizes_reports_oversized_and_tight_columns() {
+    let (_temp_dir, project_path) = setup_project().await;
+
+    let json = run_json(&inspect_command(&project_path)).await;
+
+    let nickname = column(&json, "nickname");
+    assert_eq!(nickname["status"], "oversized");
+    assert_eq!(nickname["max_length"], 11);
+    assert_eq!(nickname["suggested_type"]["kind"], "VARCHAR");
+    assert_eq!(nickname["suggested_type"]["length"], 32);
+
+    let code = column(&json, "code");
+    assert_eq!(code["status"], "tight");
+    assert_eq!(code["max_length"], 8);
+    assert_eq!(code["suggested_type"]["length"], 16);
+
+    let memo = column(&json, "memo");
+    assert_eq!(memo["status"], "no_data");
+    assert!(memo["max_length"].is_null());
+
+    assert!(json.get("skipped_tables").is_none());
+}
+
+#[tokio::test]
+async fn test_suggest_sizes_text_output_and_column_filter() {
+    let (_temp_dir, project_path) = setup_project().await;
+    let mut command = inspect_command(&project_path);
+    command.format = OutputFormat::Text;
+    command.columns = vec!["users.nickname".to_string()];
+
+    let output = InspectCommandHandler::new()
+        .execute(&command)
+        .await
+        .unwrap();
+
+    assert!(
+        output.contains("  - users.nickname VARCHAR(255): max 11 -> suggest VARCHAR(32)"),
+        "{}",
+        output
+    );
+    assert!(!output.contains("users.code"), "{}", output);
+    assert!(
+        output.contains("Checked 1 columns in 1 tables"),
+        "{}",
+        output
+    );
+}
+
+#[tokio::test]
+async fn test_suggest_sizes_row_guard() {
+    let (_temp_dir, project_path) = setup_project().await;
+    let mut command = inspect_command(&project_path);
+    command.max_rows = 1;
+
+    // 行数が上限を超えるテーブルはスキャンしない
+    let json = run_json(&command).await;
+    assert!(json["columns"].as_array().unwrap().is_empty());
+    assert_eq!(json["skipped_tables"][0]["table"], "users");
+    assert_eq!(json["skipped_tables"][0]["estimated_rows"], 2);
+
+    // --allow-full-scan で上限を無視する
+    command.allow_full_scan = true;
+    let json = run_json(&command).await;
+    assert_eq!(json["columns"].as_array().unwrap().len(), 3);
+    assert!(json.get("skipped_tables").is_none());
+}
+
+#[tokio::test]
+async fn test_suggest_sizes_rejects_unknown_table_filter() {
+    let (_temp_dir, project_path) = setup_project().await;
+    let mut command = inspect_command(&project_path);
+    command.tables = vec!["missing".to_string()];
+
+    let error = InspectCommandHandler::new()
+        .execute(&command)
+        .await
+        .unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .contains("Table 'missing' not found in schema"),
+        "{}",
+        error
+    );
+}
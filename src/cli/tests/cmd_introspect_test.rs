@@ -0,0 +1,263 @@
+// introspectコマンドハンドラーのテスト
+//
+// SQLiteのインメモリデータベースを使用して、生のイントロスペクション結果と
+// 変換後のテーブル定義が並べて出力されることを確認します。
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::AnyPool;
+use strata::cli::commands::introspect::{IntrospectCommand, IntrospectCommandHandler};
+use strata::cli::commands::render_output;
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use strata::core::schema::{ColumnType, IndexColumn, SortOrder};
+
+mod common;
+
+/// テーブルを作成したインメモリSQLiteの接続プールを作成
+async fn setup_memory_database() -> AnyPool {
+    install_default_drivers();
+    // インメモリDBは接続ごとに独立するため、接続数を1に固定する
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    for sql in [
+        r#"CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name VARCHAR(100) NOT NULL,
+            email TEXT UNIQUE
+        )"#,
+        r#"CREATE TABLE posts (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL,
+            score DECIMAL(10,2),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+        )"#,
+        "CREATE INDEX idx_posts_user_id ON posts(user_id)",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+
+    pool
+}
+
+fn introspect_command(table: Option<&str>, all: bool) -> IntrospectCommand {
+    IntrospectCommand {
+        project_path: std::path::PathBuf::from("/nonexistent"),
+        config_path: None,
+        env: "development".to_string(),
+        table: table.map(str::to_string),
+        all,
+        format: OutputFormat::Text,
+    }
+}
+
+#[tokio::test]
+async fn test_introspect_single_table_raw_and_converted() {
+    let pool = setup_memory_database().await;
+    let handler = IntrospectCommandHandler::new();
+
+    let output = handler
+        .introspect(&pool, Dialect::SQLite, Some("users"))
+        .await
+        .unwrap();
+
+    assert_eq!(output.tables.len(), 1);
+    let table = &output.tables[0];
+    assert_eq!(table.name, "users");
+
+    // 生の情報はDBが返した型文字列をそのまま保持する
+    let raw_name = table.raw.columns.iter().find(|c| c.name == "name").unwrap();
+    assert_eq!(raw_name.data_type, "VARCHAR(100)");
+    assert!(!raw_name.is_nullable);
+
+    // 変換後のテーブル定義
+    let converted = table.converted.as_ref().unwrap();
+    assert_eq!(
+        converted.get_column("name").unwrap().column_type,
+        ColumnType::VARCHAR { length: 100 }
+    );
+    assert_eq!(
+        converted.get_column("id").unwrap().auto_increment,
+        Some(true)
+    );
+    assert!(table.conversion_error.is_none());
+}
+
+#[tokio::test]
+async fn test_introspect_all_tables() {
+    let pool = setup_memory_database().await;
+    let handler = IntrospectCommandHandler::new();
+
+    let output = handler
+        .introspect(&pool, Dialect::SQLite, None)
+        .await
+        .unwrap();
+
+    let names: Vec<&str> = output.tables.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["posts", "users"]);
+
+    let posts = &output.tables[0];
+    assert!(posts
+        .raw
+        .indexes
+        .iter()
+        .any(|i| i.name == "idx_posts_user_id"));
+    assert!(!posts.raw.constraints.is_empty());
+}
+
+#[tokio::test]
+async fn test_introspect_text_output_shows_raw_and_converted_side_by_side() {
+    let pool = setup_memory_database().await;
+    let handler = IntrospectCommandHandler::new();
+
+    let output = handler
+        .introspect(&pool, Dialect::SQLite, Some("posts"))
+        .await
+        .unwrap();
+    let text = render_output(&output, &OutputFormat::Text).unwrap();
+
+    assert!(text.contains("--- Table: posts ---"), "{}", text);
+    assert!(text.contains("Columns:"));
+    assert!(text.contains("Indexes:"));
+    assert!(text.contains("Constraints:"));
+    assert!(
+        text.contains(r#"raw:       {"name":"score","data_type":"DECIMAL(10,2)""#),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(r#"converted: {"name":"user_id","type":{"kind":"INTEGER"}"#),
+        "{}",
+        text
+    );
+    assert!(text.contains("FOREIGN KEY"));
+}
+
+#[tokio::test]
+async fn test_introspect_json_output() {
+    let pool = setup_memory_database().await;
+    let handler = IntrospectCommandHandler::new();
+
+    let output = handler
+        .introspect(&pool, Dialect::SQLite, Some("posts"))
+        .await
+        .unwrap();
+    let json = render_output(&output, &OutputFormat::Json).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["dialect"], "sqlite");
+    let table = &value["tables"][0];
+    assert_eq!(table["name"], "posts");
+    assert!(table["raw"]["columns"].is_array());
+    assert!(table["raw"]["constraints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["type"] == "foreign_key" && c["on_delete"] == "CASCADE"));
+    assert!(table["converted"]["columns"].is_array());
+    assert!(table.get("conversion_error").is_none());
+}
+
+#[tokio::test]
+async fn test_introspect_sqlite_descending_index_column() {
+    let pool = setup_memory_database().await;
+    sqlx::query("CREATE INDEX idx_posts_user_score ON posts(user_id, score DESC)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let handler = IntrospectCommandHandler::new();
+
+    let output = handler
+        .introspect(&pool, Dialect::SQLite, Some("posts"))
+        .await
+        .unwrap();
+
+    let converted = output.tables[0].converted.as_ref().unwrap();
+    let index = converted
+        .indexes
+        .iter()
+        .find(|i| i.name == "idx_posts_user_score")
+        .unwrap();
+    assert_eq!(
+        index.columns,
+        vec![
+            IndexColumn::new("user_id"),
+            IndexColumn::new("score").with_order(SortOrder::Desc),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_introspect_unknown_table() {
+    let pool = setup_memory_database().await;
+    let handler = IntrospectCommandHandler::new();
+
+    let err = handler
+        .introspect(&pool, Dialect::SQLite, Some("missing"))
+        .await
+        .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("Table 'missing' not found"), "{}", message);
+    assert!(message.contains("posts, users"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_introspect_requires_table_or_all() {
+    let handler = IntrospectCommandHandler::new();
+
+    let err = handler
+        .execute(&introspect_command(None, false))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--table"));
+
+    let err = handler
+        .execute(&introspect_command(Some("users"), true))
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot use --table and --all together"));
+}
+
+#[tokio::test]
+async fn test_introspect_execute_with_sqlite_config() {
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, false).unwrap();
+    let db_path = project_path.join("introspect.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tags (id INTEGER PRIMARY KEY, label CHAR(8))")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    let handler = IntrospectCommandHandler::new();
+    let command = IntrospectCommand {
+        project_path,
+        format: OutputFormat::Json,
+        ..introspect_command(Some("tags"), false)
+    };
+    let json = handler.execute(&command).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        value["tables"][0]["raw"]["columns"][1]["data_type"],
+        "CHAR(8)"
+    );
+}
@@ -0,0 +1,314 @@
+// repairコマンドハンドラーのテスト
+//
+// `store_applied_sql: true` で保存されたSQLを `--export-applied-sql` で
+// マイグレーションディレクトリの構成に書き出せることと、
+// 未来の日時のマイグレーションを `--renumber` で付け替えられることを確認します。
+
+use sqlx::any::install_default_drivers;
+use std::fs;
+use std::path::Path;
+use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::migration_loader::load_available_migrations;
+use strata::cli::commands::repair::{RepairCommand, RepairCommandHandler};
+use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::{Config, Dialect};
+use strata::core::migration::{MigrationMetadata, MAX_STORED_SQL_BYTES};
+use strata::services::config_serializer::ConfigSerializer;
+
+mod common;
+
+fn write_config(project_path: &Path, store_applied_sql: bool) {
+    let db_path = project_path.join("repair.db");
+    let mut config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    config.store_applied_sql = store_applied_sql;
+    fs::write(
+        project_path.join(Config::DEFAULT_CONFIG_PATH),
+        ConfigSerializer::to_yaml(&config).unwrap(),
+    )
+    .unwrap();
+}
+
+async fn apply(project_path: &Path) {
+    install_default_drivers();
+    let command = ApplyCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    ApplyCommandHandler::new().execute(&command).await.unwrap();
+}
+
+async fn repair(project_path: &Path, export_dir: Option<&Path>) -> anyhow::Result<String> {
+    let command = RepairCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        export_applied_sql: export_dir.map(Path::to_path_buf),
+        renumber: None,
+        force: false,
+        format: OutputFormat::Json,
+    };
+    RepairCommandHandler::new().execute(&command).await
+}
+
+async fn renumber(project_path: &Path, version: &str, force: bool) -> anyhow::Result<String> {
+    install_default_drivers();
+    let command = RepairCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        export_applied_sql: None,
+        renumber: Some(version.to_string()),
+        force,
+        format: OutputFormat::Json,
+    };
+    RepairCommandHandler::new().execute(&command).await
+}
+
+async fn status(project_path: &Path) -> String {
+    install_default_drivers();
+    let command = StatusCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        format: OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
+    };
+    StatusCommandHandler::new().execute(&command).await.unwrap()
+}
+
+/// 時刻のずれたマシンで生成された想定の未来の日時のバージョン
+const FUTURE_VERSION: &str = "20991231235959";
+
+#[tokio::test]
+async fn test_repair_export_applied_sql_restores_migration_directories() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+
+    // 1つ目は保存無効のまま適用し、2つ目から保存する
+    write_config(&project_path, false);
+    common::create_test_migration(
+        &project_path,
+        "20260101120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    write_config(&project_path, true);
+    let up_sql = "CREATE TABLE posts (\n    id INTEGER PRIMARY KEY\n);\n";
+    common::create_test_migration(
+        &project_path,
+        "20260102120000",
+        "create_posts",
+        up_sql,
+        "DROP TABLE posts;\n",
+        "checksum2",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    let export_dir = project_path.join("restored");
+    let json = repair(&project_path, Some(&export_dir)).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["exported"].as_array().unwrap().len(), 1);
+    assert_eq!(value["exported"][0]["version"], "20260102120000");
+    assert_eq!(value["skipped"][0]["version"], "20260101120000");
+    assert_eq!(value["skipped"][0]["reason"], "not_stored");
+
+    // 書き出したディレクトリはマイグレーションとして読み込める
+    let migration_dir = export_dir.join("20260102120000_create_posts");
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("up.sql")).unwrap(),
+        up_sql
+    );
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("down.sql")).unwrap(),
+        "DROP TABLE posts;\n"
+    );
+    let metadata: MigrationMetadata =
+        serde_saphyr::from_str(&fs::read_to_string(migration_dir.join(".meta.yaml")).unwrap())
+            .unwrap();
+    assert_eq!(metadata.checksum, "checksum2");
+    assert_eq!(metadata.dialect, Dialect::SQLite);
+
+    let loaded = load_available_migrations(&export_dir).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].0, "20260102120000");
+
+    // 既に存在するディレクトリは上書きしない
+    fs::write(migration_dir.join("up.sql"), "-- edited").unwrap();
+    let json = repair(&project_path, Some(&export_dir)).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value["exported"].as_array().unwrap().is_empty());
+    assert_eq!(value["skipped"][1]["reason"], "already_exists");
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("up.sql")).unwrap(),
+        "-- edited"
+    );
+}
+
+#[tokio::test]
+async fn test_repair_export_applied_sql_writes_truncated_sql_under_separate_name() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_config(&project_path, true);
+
+    let padding = "-- padding\n".repeat(MAX_STORED_SQL_BYTES / 10);
+    common::create_test_migration(
+        &project_path,
+        "20260101120000",
+        "create_big",
+        &format!("{}CREATE TABLE big (id INTEGER PRIMARY KEY);\n", padding),
+        "DROP TABLE big;",
+        "checksum1",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    let export_dir = project_path.join("restored");
+    let json = repair(&project_path, Some(&export_dir)).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["exported"][0]["truncated"], true);
+
+    // 不完全なSQLが適用されないよう up.sql としては書き出さない
+    let migration_dir = export_dir.join("20260101120000_create_big");
+    assert!(!migration_dir.join("up.sql").exists());
+    assert!(migration_dir.join("up.sql.truncated").exists());
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("down.sql")).unwrap(),
+        "DROP TABLE big;"
+    );
+}
+
+#[tokio::test]
+async fn test_repair_requires_action() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+
+    let err = repair(&project_path, None).await.unwrap_err();
+    assert!(err.to_string().contains("--export-applied-sql"));
+}
+
+#[tokio::test]
+async fn test_repair_renumber_future_dated_migration() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_config(&project_path, false);
+    common::create_test_migration(
+        &project_path,
+        FUTURE_VERSION,
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let before = status(&project_path).await;
+    assert!(before.contains("is dated"), "{}", before);
+    assert!(
+        before.contains(&format!("strata repair --renumber {}", FUTURE_VERSION)),
+        "{}",
+        before
+    );
+
+    let json = renumber(&project_path, FUTURE_VERSION, false)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["old_version"], FUTURE_VERSION);
+    assert_eq!(value["applied"], false);
+    assert!(value["update_sql"].as_array().unwrap().is_empty());
+
+    // ディレクトリ名と .meta.yaml のバージョンが現在のタイムスタンプになる
+    let new_version = value["new_version"].as_str().unwrap().to_string();
+    assert!(new_version.as_str() < FUTURE_VERSION);
+    let loaded = load_available_migrations(&project_path.join("migrations")).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].0, new_version);
+    assert_eq!(loaded[0].1, "create_users");
+    let metadata: MigrationMetadata =
+        serde_saphyr::from_str(&fs::read_to_string(loaded[0].2.join(".meta.yaml")).unwrap())
+            .unwrap();
+    assert_eq!(metadata.version, new_version);
+    assert_eq!(metadata.checksum, "checksum1");
+
+    let after = status(&project_path).await;
+    assert!(!after.contains("is dated"), "{}", after);
+}
+
+#[tokio::test]
+async fn test_repair_renumber_applied_migration_requires_force() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_config(&project_path, false);
+    common::create_test_migration(
+        &project_path,
+        FUTURE_VERSION,
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum1",
+    )
+    .unwrap();
+    apply(&project_path).await;
+
+    let err = renumber(&project_path, FUTURE_VERSION, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already applied"), "{}", err);
+    assert!(err.to_string().contains("--force"), "{}", err);
+    let loaded = load_available_migrations(&project_path.join("migrations")).unwrap();
+    assert_eq!(loaded[0].0, FUTURE_VERSION);
+
+    // --force では付け替え、各環境で実行する更新SQLを出力する
+    let json = renumber(&project_path, FUTURE_VERSION, true).await.unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let new_version = value["new_version"].as_str().unwrap();
+    assert_eq!(value["applied"], true);
+    assert_eq!(value["update_sql"][0]["env"], "development");
+    assert_eq!(
+        value["update_sql"][0]["sql"],
+        format!(
+            "UPDATE schema_migrations SET version = '{}' WHERE version = '{}';",
+            new_version, FUTURE_VERSION
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_repair_renumber_unknown_version() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_config(&project_path, false);
+
+    let err = renumber(&project_path, FUTURE_VERSION, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+}
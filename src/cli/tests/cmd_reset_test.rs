@@ -0,0 +1,289 @@
+// resetコマンドハンドラーのテスト
+//
+// 開発用データベースをロールバックまたはテーブルの削除で空に戻す reset を
+// SQLite で検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use sqlx::AnyPool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use strata::cli::commands::confirm::ScriptedConfirm;
+use strata::cli::commands::dev::{DevCommandHandler, DevMigrateCommand};
+use strata::cli::commands::reset::{ResetCommand, ResetCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// users と、users を参照する posts を定義した SQLite プロジェクトを作成し、適用する
+async fn setup_applied_project() -> (TempDir, PathBuf) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::write_schema_file(&project_path, "users");
+    fs::write(
+        project_path.join("schema/posts.yaml"),
+        r#"version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - id
+"#,
+    )
+    .unwrap();
+
+    DevCommandHandler::new()
+        .execute_migrate(&DevMigrateCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            env: "development".to_string(),
+            description: None,
+            format: OutputFormat::Text,
+        })
+        .await
+        .unwrap();
+
+    // 外部キーの依存順に削除しないと失敗するよう、参照する行を入れておく
+    let pool = connect(&project_path).await;
+    sqlx::query("INSERT INTO users (id) VALUES (1)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO posts (id, user_id) VALUES (1, 1)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    (temp_dir, project_path)
+}
+
+async fn connect(project_path: &Path) -> AnyPool {
+    let db_path = project_path.join("test.db");
+    AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap()
+}
+
+/// データベースに存在するテーブル名（マイグレーション履歴テーブルを含む）
+///
+/// ロールバックの記録テーブルは reset 後も残すため含めない。
+async fn database_tables(project_path: &Path) -> Vec<String> {
+    let pool = connect(project_path).await;
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name != 'schema_migration_rollbacks' ORDER BY name",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    pool.close().await;
+    rows.into_iter().map(|(name,)| name).collect()
+}
+
+fn reset_command(project_path: &Path) -> ResetCommand {
+    ResetCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        hard: false,
+        delete_file: false,
+        reapply: false,
+        yes: true,
+        wait_timeout: None,
+        format: OutputFormat::Text,
+    }
+}
+
+#[tokio::test]
+async fn test_reset_rolls_back_all_migrations_and_drops_history_table() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["posts", "schema_migrations", "users"]
+    );
+
+    let output = ResetCommandHandler::new()
+        .execute(&reset_command(&project_path))
+        .await
+        .unwrap();
+
+    assert!(output.starts_with("=== Reset (development) ===\n"));
+    assert!(output.contains("Rolled back 1 migration(s):"), "{}", output);
+    assert!(
+        output.contains("✓ DROP TABLE IF EXISTS schema_migrations"),
+        "{}",
+        output
+    );
+    assert!(output.contains("Database 'development' has been reset."));
+    assert!(database_tables(&project_path).await.is_empty());
+
+    // ロールバックの記録は残る
+    let pool = connect(&project_path).await;
+    let (rollbacks,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM schema_migration_rollbacks")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    assert_eq!(rollbacks, 1);
+
+    // もう一度実行しても何もしない
+    let output = ResetCommandHandler::new()
+        .execute(&reset_command(&project_path))
+        .await
+        .unwrap();
+    assert!(output.contains("No migrations to roll back."), "{}", output);
+}
+
+#[tokio::test]
+async fn test_reset_reapply_applies_migrations_again() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+
+    let mut command = reset_command(&project_path);
+    command.reapply = true;
+    command.format = OutputFormat::Json;
+    let output = ResetCommandHandler::new().execute(&command).await.unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["mode"], "rollback");
+    assert_eq!(json["rolled_back"].as_array().unwrap().len(), 1);
+    assert_eq!(json["apply"]["applied_count"], 1);
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["posts", "schema_migrations", "users"]
+    );
+
+    // データは残らない
+    let pool = connect(&project_path).await;
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn test_reset_hard_drops_snapshot_tables_in_dependency_order() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+    // down.sql が使えなくても削除できる
+    for entry in fs::read_dir(project_path.join("migrations")).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            fs::write(path.join("down.sql"), "SELECT broken FROM nowhere;").unwrap();
+        }
+    }
+
+    let mut command = reset_command(&project_path);
+    command.hard = true;
+    let output = ResetCommandHandler::new().execute(&command).await.unwrap();
+
+    let posts = output.find(r#"DROP TABLE IF EXISTS "posts""#).unwrap();
+    let users = output.find(r#"DROP TABLE IF EXISTS "users""#).unwrap();
+    let history = output
+        .find("DROP TABLE IF EXISTS schema_migrations")
+        .unwrap();
+    assert!(posts < users && users < history, "{}", output);
+    assert!(!output.contains("Rolled back"), "{}", output);
+    assert!(database_tables(&project_path).await.is_empty());
+    assert!(project_path.join("test.db").exists());
+}
+
+#[tokio::test]
+async fn test_reset_hard_delete_file_removes_sqlite_database() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+
+    let mut command = reset_command(&project_path);
+    command.hard = true;
+    command.delete_file = true;
+    command.reapply = true;
+    let output = ResetCommandHandler::new().execute(&command).await.unwrap();
+
+    assert!(output.contains("Deleted:"), "{}", output);
+    assert!(output.contains("test.db"), "{}", output);
+    assert!(output.contains("=== apply (development) ==="), "{}", output);
+    // 再適用でファイルが作り直される
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["posts", "schema_migrations", "users"]
+    );
+}
+
+#[tokio::test]
+async fn test_reset_refuses_protected_environment() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+    let config_path = project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH);
+    let mut config = fs::read_to_string(&config_path).unwrap();
+    config.push_str("protected_environments:\n  - development\n");
+    fs::write(&config_path, config).unwrap();
+
+    let err = ResetCommandHandler::new()
+        .execute(&reset_command(&project_path))
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("protected_environments"));
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["posts", "schema_migrations", "users"]
+    );
+}
+
+#[tokio::test]
+async fn test_reset_requires_confirmation() {
+    let (_temp_dir, project_path) = setup_applied_project().await;
+    let mut command = reset_command(&project_path);
+    command.yes = false;
+
+    // 対話的でなければ --yes が必要
+    let err = ResetCommandHandler::new()
+        .execute(&command)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Pass --yes"), "{}", err);
+
+    // 環境名が一致しなければ中止
+    let confirm = Arc::new(ScriptedConfirm::new(["production"]));
+    let err = ResetCommandHandler::new()
+        .with_confirm(confirm.clone())
+        .execute(&command)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Reset cancelled"), "{}", err);
+    assert!(confirm.shown()[0].contains("roll back 1 migration(s)"));
+    assert_eq!(
+        database_tables(&project_path).await,
+        vec!["posts", "schema_migrations", "users"]
+    );
+
+    let confirm = Arc::new(ScriptedConfirm::new(["development"]));
+    ResetCommandHandler::new()
+        .with_confirm(confirm)
+        .execute(&command)
+        .await
+        .unwrap();
+    assert!(database_tables(&project_path).await.is_empty());
+}
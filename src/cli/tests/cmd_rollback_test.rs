@@ -4,6 +4,7 @@ use sqlx::any::install_default_drivers;
 use std::fs;
 use std::path::PathBuf;
 use strata::cli::commands::rollback::{RollbackCommand, RollbackCommandHandler};
+use strata::cli::commands::stacked_migrations;
 use strata::core::config::Dialect;
 use strata::services::config_loader::ConfigLoader;
 use strata::services::config_serializer::ConfigSerializer;
@@ -23,10 +24,14 @@ fn test_rollback_command_struct() {
         project_path: PathBuf::from("/test/path"),
         config_path: None,
         steps: Some(1),
+        target: None,
         env: "development".to_string(),
         dry_run: false,
         allow_destructive: false,
+        break_stale_lock: false,
+        allow_empty_down: false,
         format: strata::cli::OutputFormat::Text,
+        wait_timeout: None,
     };
 
     assert_eq!(command.project_path, PathBuf::from("/test/path"));
@@ -44,10 +49,14 @@ async fn test_rollback_no_config_file() {
         project_path,
         config_path: None,
         steps: None,
+        target: None,
         env: "development".to_string(),
         dry_run: false,
         allow_destructive: false,
+        break_stale_lock: false,
+        allow_empty_down: false,
         format: strata::cli::OutputFormat::Text,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -71,10 +80,14 @@ async fn test_rollback_no_migrations_dir() {
         project_path,
         config_path: None,
         steps: None,
+        target: None,
         env: "development".to_string(),
         dry_run: false,
         allow_destructive: false,
+        break_stale_lock: false,
+        allow_empty_down: false,
         format: strata::cli::OutputFormat::Text,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -197,10 +210,14 @@ async fn test_rollback_single_migration_sqlite() {
         project_path: project_path.clone(),
         config_path: None,
         steps: None, // デフォルトは1件
+        target: None,
         env: "development".to_string(),
         dry_run: false,
         allow_destructive: true, // down.sql may contain DROP TABLE
+        break_stale_lock: false,
+        allow_empty_down: false,
         format: strata::cli::OutputFormat::Text,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -224,6 +241,17 @@ async fn test_rollback_single_migration_sqlite() {
             .await
             .unwrap();
     assert!(table_exists.is_none());
+
+    // generate が未適用のマイグレーションとして報告するよう記録される
+    let pending =
+        stacked_migrations::find_pending_migrations(&project_path.join("migrations")).unwrap();
+    assert_eq!(
+        pending
+            .iter()
+            .map(|m| m.version.as_str())
+            .collect::<Vec<_>>(),
+        vec!["20260121120000"]
+    );
 }
 
 #[tokio::test]
@@ -254,3 +282,278 @@ async fn test_generate_summary() {
     assert!(summary.contains("20260121120000"));
     assert!(summary.contains("80ms")); // 50 + 30
 }
+
+#[tokio::test]
+async fn test_rollback_to_target_version() {
+    use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    for (version, table) in [
+        ("20260121120000", "users"),
+        ("20260121120001", "posts"),
+        ("20260121120002", "tags"),
+    ] {
+        common::create_test_migration(
+            &project_path,
+            version,
+            &format!("create_{}", table),
+            &format!("CREATE TABLE {} (id INTEGER PRIMARY KEY);", table),
+            &format!("DROP TABLE {};", table),
+            &format!("checksum_{}", version),
+        )
+        .unwrap();
+    }
+
+    let rollback = |target: &str, dry_run: bool| RollbackCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        steps: None,
+        target: Some(target.to_string()),
+        env: "development".to_string(),
+        dry_run,
+        allow_destructive: true,
+        break_stale_lock: false,
+        allow_empty_down: false,
+        format: strata::cli::OutputFormat::Json,
+        wait_timeout: None,
+    };
+    let rolled_back_versions = |output: &str| -> Vec<String> {
+        let json: serde_json::Value = serde_json::from_str(output).unwrap();
+        json["migrations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["version"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let handler = RollbackCommandHandler::new();
+
+    let apply = |target: &str| ApplyCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: Some(target.to_string()),
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    ApplyCommandHandler::new()
+        .execute(&apply("20260121120001"))
+        .await
+        .unwrap();
+
+    // 未適用のバージョンには戻せない
+    let err = handler
+        .execute(&rollback("20260121120002", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Target version 20260121120002 has not been applied to 'development'"),
+        "{}",
+        err
+    );
+
+    ApplyCommandHandler::new()
+        .execute(&apply("20260121120002"))
+        .await
+        .unwrap();
+
+    // dry-run はロールバックされるマイグレーションを新しい順に列挙する
+    let output = handler
+        .execute(&rollback("20260121120000", true))
+        .await
+        .unwrap();
+    assert_eq!(
+        rolled_back_versions(&output),
+        vec!["20260121120002", "20260121120001"]
+    );
+
+    let output = handler
+        .execute(&rollback("20260121120000", false))
+        .await
+        .unwrap();
+    assert_eq!(
+        rolled_back_versions(&output),
+        vec!["20260121120002", "20260121120001"]
+    );
+
+    // 既に指定バージョンにある場合は何もしない
+    let mut command = rollback("20260121120000", false);
+    command.format = strata::cli::OutputFormat::Text;
+    let output = handler.execute(&command).await.unwrap();
+    assert!(
+        output
+            .contains("Database is already at version 20260121120000. No migrations to rollback."),
+        "{}",
+        output
+    );
+
+    // 存在しないバージョンはエラー
+    let err = handler
+        .execute(&rollback("20990101000000", false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Unknown target version '20990101000000'"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_rollback_refuses_empty_down_in_chain() {
+    use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    // 中間のマイグレーションの down.sql はコメントのみ
+    for (version, table, down_sql) in [
+        ("20260121120000", "users", "DROP TABLE users;".to_string()),
+        (
+            "20260121120001",
+            "posts",
+            "-- Irreversible: data backfill cannot be undone\n".to_string(),
+        ),
+        (
+            "20260121120002",
+            "tags",
+            "DELETE FROM tags;\nDROP TABLE tags;".to_string(),
+        ),
+    ] {
+        common::create_test_migration(
+            &project_path,
+            version,
+            &format!("create_{}", table),
+            &format!("CREATE TABLE {} (id INTEGER PRIMARY KEY);", table),
+            &down_sql,
+            &format!("checksum_{}", version),
+        )
+        .unwrap();
+    }
+    ApplyCommandHandler::new()
+        .execute(&ApplyCommand {
+            project_path: project_path.clone(),
+            config_path: None,
+            dry_run: false,
+            env: "development".to_string(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            allow_destructive: false,
+            yes: false,
+            break_stale_lock: false,
+            skip_checksum_verification: false,
+            format: strata::cli::OutputFormat::Text,
+            dry_run_sql: Default::default(),
+            restore_sequence_values: None,
+            target: None,
+            wait_timeout: None,
+            preflight: None,
+            single_transaction: false,
+            only: None,
+            force_out_of_order: false,
+        })
+        .await
+        .unwrap();
+
+    let rollback = |dry_run: bool, allow_empty_down: bool| RollbackCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        steps: None,
+        target: Some("20260121120000".to_string()),
+        env: "development".to_string(),
+        dry_run,
+        allow_destructive: true,
+        break_stale_lock: false,
+        allow_empty_down,
+        format: strata::cli::OutputFormat::Text,
+        wait_timeout: None,
+    };
+    let handler = RollbackCommandHandler::new();
+
+    // dry-run は各マイグレーションのステートメントを実行順に列挙し、空の down.sql を示す
+    let output = handler.execute(&rollback(true, false)).await.unwrap();
+    let tags = output
+        .find("▶ 20260121120002 - create_tags\n")
+        .expect(&output);
+    let delete = output
+        .find("-- statement 1/2\nDELETE FROM tags;\n")
+        .expect(&output);
+    let drop = output
+        .find("-- statement 2/2\nDROP TABLE tags;\n")
+        .expect(&output);
+    let posts = output
+        .find("▶ 20260121120001 - create_posts\n")
+        .expect(&output);
+    assert!(tags < delete && delete < drop && drop < posts, "{}", output);
+    assert!(
+        output[posts..].contains("down.sql has no executable statements"),
+        "{}",
+        output
+    );
+
+    // 空の down.sql を含むロールバックは拒否され、何も実行されない
+    let err = handler
+        .execute(&rollback(false, false))
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("  - 20260121120001 - create_posts ("),
+        "{}",
+        err
+    );
+    assert!(!err.contains("20260121120002 - create_tags"), "{}", err);
+    assert!(err.contains("--allow-empty-down"), "{}", err);
+
+    let pool = sqlx::AnyPool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let applied = |pool: sqlx::AnyPool| async move {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT version FROM schema_migrations ORDER BY version")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        rows.into_iter().map(|(v,)| v).collect::<Vec<_>>()
+    };
+    assert_eq!(applied(pool.clone()).await.len(), 3);
+
+    // --allow-empty-down で中間のマイグレーションも履歴から削除される
+    let output = handler.execute(&rollback(false, true)).await.unwrap();
+    assert!(output.contains("2 migration(s) rolled back"), "{}", output);
+    assert_eq!(applied(pool.clone()).await, vec!["20260121120000"]);
+    pool.close().await;
+}
@@ -0,0 +1,246 @@
+// seedコマンドハンドラーのテスト
+//
+// SQLiteのデータベースに seeds/ のSQLファイルとYAMLファイルを投入し、
+// 実行順序、再実行時の冪等性、--only による絞り込み、失敗時のロールバックを検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use sqlx::{AnyPool, Row};
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::seed::{SeedCommand, SeedCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// roles と users テーブルを持つSQLiteデータベースと、seeds/ ディレクトリを持つプロジェクトを作成する
+async fn setup_project() -> (TempDir, PathBuf, AnyPool) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    fs::create_dir_all(project_path.join("seeds")).unwrap();
+
+    let pool = AnyPool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    for sql in [
+        "CREATE TABLE roles (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, role_id INTEGER NOT NULL)",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+
+    (temp_dir, project_path, pool)
+}
+
+fn write_seed(project_path: &Path, name: &str, content: &str) {
+    fs::write(project_path.join("seeds").join(name), content).unwrap();
+}
+
+async fn run_seed(
+    project_path: &Path,
+    only: Option<&str>,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    let command = SeedCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        env: "development".to_string(),
+        only: only.map(str::to_string),
+        wait_timeout: None,
+        format,
+    };
+    SeedCommandHandler::new().execute(&command).await
+}
+
+async fn role_names(pool: &AnyPool) -> Vec<String> {
+    sqlx::query("SELECT name FROM roles ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| row.get::<String, _>("name"))
+        .collect()
+}
+
+async fn user_count(pool: &AnyPool) -> i64 {
+    sqlx::query("SELECT COUNT(*) AS n FROM users")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get::<i64, _>("n")
+}
+
+#[tokio::test]
+async fn test_seed_runs_sql_and_yaml_files_in_order() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    write_seed(
+        &project_path,
+        "01_roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: admin }\n  - { id: 2, name: member }\n",
+    );
+    // users は roles を前提にするため、ファイル名の順で実行される必要がある
+    write_seed(
+        &project_path,
+        "02_users.sql",
+        "INSERT INTO users (id, email, role_id) SELECT 1, 'admin@example.com', id FROM roles WHERE name = 'admin';\n",
+    );
+
+    let output = run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap();
+    assert!(
+        output.contains("Seeded 'development' from 2 file(s) (3 row(s) affected)"),
+        "{}",
+        output
+    );
+    assert!(
+        output.contains("01_roles.yaml: 2 statement(s)"),
+        "{}",
+        output
+    );
+    assert_eq!(role_names(&pool).await, vec!["admin", "member"]);
+    assert_eq!(user_count(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn test_seed_yaml_is_idempotent() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    write_seed(
+        &project_path,
+        "roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: admin }\n",
+    );
+    run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap();
+
+    // 既存の行は上書きせずにそのまま残す
+    write_seed(
+        &project_path,
+        "roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: owner }\n  - { id: 2, name: member }\n",
+    );
+    let output = run_seed(&project_path, None, OutputFormat::Json)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["rows_affected"], 1);
+    assert_eq!(role_names(&pool).await, vec!["admin", "member"]);
+}
+
+#[tokio::test]
+async fn test_seed_yaml_on_conflict_update_overwrites_rows() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    sqlx::query("INSERT INTO roles (id, name) VALUES (1, 'admin')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    write_seed(
+        &project_path,
+        "roles.yaml",
+        "table: roles\non_conflict: update\nkey: [id]\nrows:\n  - { id: 1, name: owner }\n",
+    );
+
+    run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap();
+    assert_eq!(role_names(&pool).await, vec!["owner"]);
+}
+
+#[tokio::test]
+async fn test_seed_only_runs_matching_file() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    write_seed(
+        &project_path,
+        "01_roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: admin }\n",
+    );
+    write_seed(
+        &project_path,
+        "02_users.sql",
+        "INSERT INTO users (id, email, role_id) VALUES (1, 'a@example.com', 1);\n",
+    );
+
+    let output = run_seed(&project_path, Some("02_users"), OutputFormat::Json)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["files"][0]["file"], "02_users.sql");
+    assert_eq!(json["files"][0]["kind"], "sql");
+    assert_eq!(json["files"].as_array().unwrap().len(), 1);
+    assert!(role_names(&pool).await.is_empty());
+    assert_eq!(user_count(&pool).await, 1);
+
+    let err = run_seed(&project_path, Some("03_missing"), OutputFormat::Text)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Seed file '03_missing' not found"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_seed_failure_rolls_back_all_files() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    write_seed(
+        &project_path,
+        "01_roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: admin }\n",
+    );
+    write_seed(
+        &project_path,
+        "02_broken.sql",
+        "INSERT INTO missing_table (id) VALUES (1);\n",
+    );
+
+    let err = run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Failed to execute seed file '02_broken.sql'"),
+        "{:#}",
+        err
+    );
+    assert!(role_names(&pool).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_seed_rejects_invalid_yaml_before_connecting() {
+    let (_temp_dir, project_path, pool) = setup_project().await;
+    write_seed(
+        &project_path,
+        "01_roles.yaml",
+        "table: roles\nrows:\n  - { id: 1, name: admin }\n",
+    );
+    write_seed(
+        &project_path,
+        "02_users.yaml",
+        "table: users\non_conflict: update\nrows:\n  - { id: 1, email: a@example.com, role_id: 1 }\n",
+    );
+
+    let err = run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("02_users.yaml"), "{:#}", err);
+    assert!(role_names(&pool).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_seed_reports_empty_seeds_directory() {
+    let (_temp_dir, project_path, _pool) = setup_project().await;
+
+    let output = run_seed(&project_path, None, OutputFormat::Text)
+        .await
+        .unwrap();
+    assert_eq!(output, "No seed files found in seeds/");
+}
@@ -2,8 +2,18 @@
 
 use sqlx::any::install_default_drivers;
 use std::fs;
-use std::path::PathBuf;
-use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
+use std::path::{Path, PathBuf};
+use strata::adapters::database::DatabaseConnectionService;
+use strata::adapters::database_migrator::{
+    DatabaseMigratorService, CURRENT_MIGRATION_TABLE_LAYOUT,
+};
+use strata::cli::command_context::{CommandContext, SchemaLoadOptions};
+use strata::cli::commands::status::{
+    StatusCommand, StatusCommandHandler, StatusGate, EXIT_CODE_GATE_DRIFT, EXIT_CODE_GATE_PENDING,
+    EXIT_CODE_GATE_UNMANAGED,
+};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::OutputFormat;
 use strata::core::config::Dialect;
 use strata::services::config_loader::ConfigLoader;
 use strata::services::config_serializer::ConfigSerializer;
@@ -24,6 +34,9 @@ fn test_status_command_struct() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     assert_eq!(command.project_path, PathBuf::from("/test/path"));
@@ -41,6 +54,9 @@ async fn test_status_no_config_file() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -65,6 +81,9 @@ async fn test_status_no_migrations_dir() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -86,6 +105,9 @@ async fn test_status_no_migrations() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -140,6 +162,9 @@ async fn test_status_with_pending_migrations() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -225,6 +250,9 @@ async fn test_status_with_applied_migrations() {
         config_path: None,
         env: "development".to_string(),
         format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
     };
 
     let result = handler.execute(&command).await;
@@ -249,3 +277,589 @@ fn test_format_migration_status() {
     assert!(summary.contains("Applied"));
     assert!(summary.contains("Total: 1"));
 }
+
+#[tokio::test]
+async fn test_status_connections_share_single_pool_per_environment() {
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    let context = CommandContext::load(project_path).unwrap();
+
+    // status / apply と同じ経路で複数回接続しても、プールは1つだけ作成される
+    let (pool, _) = context
+        .connect_and_load_migrations("development")
+        .await
+        .unwrap();
+    let (_, _) = context
+        .connect_and_load_migrations_with_timeout("development", Some(5))
+        .await
+        .unwrap();
+    let shared = context.connect_pool("development").await.unwrap();
+    assert_eq!(context.connections().pools_created(), 1);
+
+    context.close_pools().await;
+    assert!(pool.is_closed());
+    assert!(shared.is_closed());
+}
+
+#[tokio::test]
+async fn test_run_with_pools_closes_pools_when_command_fails() {
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    let context = CommandContext::load(project_path).unwrap();
+
+    // コマンド本体がエラーを返しても、作成した接続プールは閉じられる
+    let mut opened = None;
+    let result: anyhow::Result<()> = context
+        .run_with_pools(async {
+            opened = Some(context.connect_pool("development").await?);
+            Err(anyhow::anyhow!("command failed"))
+        })
+        .await;
+
+    assert_eq!(result.unwrap_err().to_string(), "command failed");
+    assert!(opened.unwrap().is_closed());
+}
+
+#[tokio::test]
+async fn test_status_does_not_read_schema_directory() {
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    // 読み込めば必ず失敗するスキーマファイルを置く
+    fs::write(
+        project_path.join("schema").join("broken.yaml"),
+        "tables: [unclosed",
+    )
+    .unwrap();
+
+    let context = CommandContext::load(project_path.clone()).unwrap();
+    let command = StatusCommand {
+        project_path,
+        config_path: None,
+        env: "development".to_string(),
+        format: strata::cli::OutputFormat::Text,
+        gates: vec![],
+        verbose: false,
+        wait_timeout: None,
+    };
+
+    let result = StatusCommandHandler::new()
+        .execute_with_context(&context, &command)
+        .await;
+    context.close_pools().await;
+
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert_eq!(context.schema_loads(), 0);
+
+    // スキーマを要求するコマンドの経路では読み込みが記録される
+    assert!(context.load_schema(&SchemaLoadOptions::default()).is_err());
+    assert_eq!(context.schema_loads(), 1);
+}
+
+/// マイグレーションディレクトリにper-migrationスナップショットを書き込む
+fn write_migration_snapshot(project_path: &Path, migration: &str, tables: &[&str]) {
+    let mut content = String::from("version: \"1.0\"\ntables:\n");
+    for table in tables {
+        content.push_str(&format!(
+            "  {}:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n    primary_key:\n      - id\n",
+            table
+        ));
+    }
+    fs::write(
+        project_path
+            .join("migrations")
+            .join(migration)
+            .join(".schema_snapshot.yaml"),
+        content,
+    )
+    .unwrap();
+}
+
+/// ゲート用のプロジェクト: users テーブルを作成するマイグレーションが適用済みの状態
+async fn setup_gate_project() -> (TempDir, PathBuf, sqlx::AnyPool) {
+    install_default_drivers();
+    let (temp_dir, project_path) = common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+
+    common::create_test_migration(
+        &project_path,
+        "20260121120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum_users",
+    )
+    .unwrap();
+    write_migration_snapshot(&project_path, "20260121120000_create_users", &["users"]);
+
+    let config = ConfigLoader::from_file(
+        &project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH),
+    )
+    .unwrap();
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = DatabaseConnectionService::new()
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    let migrator = DatabaseMigratorService::new();
+    migrator
+        .create_migration_table(&pool, Dialect::SQLite)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY);")
+        .execute(&pool)
+        .await
+        .unwrap();
+    migrator
+        .record_migration_with_dialect(
+            &pool,
+            &strata::core::migration::Migration::new(
+                "20260121120000".to_string(),
+                "create_users".to_string(),
+                "checksum_users".to_string(),
+            ),
+            Dialect::SQLite,
+        )
+        .await
+        .unwrap();
+
+    (temp_dir, project_path, pool)
+}
+
+/// 未適用のマイグレーションを追加する（users を削除し accounts を作成）
+fn add_pending_migration(project_path: &Path) {
+    common::create_test_migration(
+        project_path,
+        "20260121120001",
+        "replace_users",
+        "DROP TABLE users; CREATE TABLE accounts (id INTEGER PRIMARY KEY);",
+        "DROP TABLE accounts;",
+        "checksum_accounts",
+    )
+    .unwrap();
+    write_migration_snapshot(project_path, "20260121120001_replace_users", &["accounts"]);
+}
+
+async fn run_status_with_gates(
+    project_path: PathBuf,
+    gates: Vec<StatusGate>,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    let command = StatusCommand {
+        project_path,
+        config_path: None,
+        env: "development".to_string(),
+        format,
+        gates,
+        verbose: false,
+        wait_timeout: None,
+    };
+    StatusCommandHandler::new().execute(&command).await
+}
+
+fn expect_gate_failure(result: anyhow::Result<String>) -> CompletedWithFailure {
+    let err = result.expect_err("status gate should fail");
+    err.downcast_ref::<CompletedWithFailure>()
+        .unwrap_or_else(|| panic!("unexpected error: {:#}", err))
+        .clone()
+}
+
+const ALL_GATES: [StatusGate; 3] = [
+    StatusGate::Pending,
+    StatusGate::Drift,
+    StatusGate::Unmanaged,
+];
+
+#[tokio::test]
+async fn test_status_gates_pass_for_up_to_date_database() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    pool.close().await;
+
+    let output = run_status_with_gates(project_path, ALL_GATES.to_vec(), OutputFormat::Text)
+        .await
+        .unwrap();
+
+    assert!(output.contains("=== Gates ==="));
+    assert!(output.contains("pending    ✓ PASS"));
+    assert!(output.contains("drift      ✓ PASS"));
+    assert!(output.contains("unmanaged  ✓ PASS"));
+}
+
+#[tokio::test]
+async fn test_status_gate_pending() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    pool.close().await;
+    add_pending_migration(&project_path);
+
+    // ゲートを指定しなければ従来どおり成功する
+    assert!(
+        run_status_with_gates(project_path.clone(), vec![], OutputFormat::Text)
+            .await
+            .is_ok()
+    );
+
+    let failure = expect_gate_failure(
+        run_status_with_gates(project_path, vec![StatusGate::Pending], OutputFormat::Text).await,
+    );
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_PENDING);
+    assert!(failure
+        .output
+        .contains("pending    ✗ FAIL   1 pending migration(s)"));
+    assert!(failure.message.contains("pending (1 pending migration(s))"));
+}
+
+#[tokio::test]
+async fn test_status_gate_drift() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    pool.close().await;
+
+    // 適用後にマイグレーションファイルが変更された
+    common::create_test_migration(
+        &project_path,
+        "20260121120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+        "DROP TABLE users;",
+        "checksum_users_modified",
+    )
+    .unwrap();
+
+    let failure = expect_gate_failure(
+        run_status_with_gates(
+            project_path.clone(),
+            vec![StatusGate::Drift],
+            OutputFormat::Text,
+        )
+        .await,
+    );
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_DRIFT);
+    assert!(failure.output.contains("drift      ✗ FAIL"));
+
+    // drift 以外のゲートは通過する
+    assert!(run_status_with_gates(
+        project_path,
+        vec![StatusGate::Pending, StatusGate::Unmanaged],
+        OutputFormat::Text
+    )
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn test_status_gate_unmanaged() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    // マイグレーションを経由せずに作成されたテーブル
+    sqlx::query("CREATE TABLE legacy_audit (id INTEGER PRIMARY KEY);")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    // 未適用のマイグレーションのスナップショット（users なし）とは比較しない
+    add_pending_migration(&project_path);
+
+    let failure = expect_gate_failure(
+        run_status_with_gates(
+            project_path.clone(),
+            vec![StatusGate::Unmanaged],
+            OutputFormat::Json,
+        )
+        .await,
+    );
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_UNMANAGED);
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(json["gates"]["unmanaged"], "fail");
+    assert_eq!(
+        json["unmanaged_tables"],
+        serde_json::json!(["legacy_audit"])
+    );
+
+    // external_tables として宣言されたテーブルは管理外として扱わない
+    fs::write(
+        project_path
+            .join("migrations")
+            .join("20260121120000_create_users")
+            .join(".schema_snapshot.yaml"),
+        "version: \"1.0\"\nexternal_tables:\n  - legacy_audit\ntables:\n  users:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n",
+    )
+    .unwrap();
+    assert!(run_status_with_gates(
+        project_path,
+        vec![StatusGate::Unmanaged],
+        OutputFormat::Text
+    )
+    .await
+    .is_ok());
+}
+
+#[tokio::test]
+async fn test_status_gates_combined_first_failure_wins() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    sqlx::query("CREATE TABLE legacy_audit (id INTEGER PRIMARY KEY);")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    add_pending_migration(&project_path);
+
+    let failure = expect_gate_failure(
+        run_status_with_gates(
+            project_path.clone(),
+            vec![
+                StatusGate::Unmanaged,
+                StatusGate::Drift,
+                StatusGate::Pending,
+            ],
+            OutputFormat::Json,
+        )
+        .await,
+    );
+    // 指定順で最初に失敗したゲートの終了コード
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_UNMANAGED);
+    // すべてのゲートの判定が出力される
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(
+        json["gates"],
+        serde_json::json!({"pending": "fail", "drift": "pass", "unmanaged": "fail"})
+    );
+    assert!(failure.message.contains("unmanaged"));
+    assert!(failure.message.contains("pending"));
+
+    let failure = expect_gate_failure(
+        run_status_with_gates(project_path, ALL_GATES.to_vec(), OutputFormat::Text).await,
+    );
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_PENDING);
+    assert!(failure.output.contains("pending    ✗ FAIL"));
+    assert!(failure.output.contains("drift      ✓ PASS"));
+    assert!(failure.output.contains("unmanaged  ✗ FAIL"));
+}
+
+#[tokio::test]
+async fn test_status_reports_checksum_status_per_migration() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    pool.close().await;
+    add_pending_migration(&project_path);
+
+    let migration_dir = project_path.join("migrations/20260121120000_create_users");
+    let up_sql = "-- create users\nCREATE TABLE users (id INTEGER PRIMARY KEY);\n";
+    fs::write(migration_dir.join("up.sql"), up_sql).unwrap();
+    let up_sql_checksum = strata::services::schema_checksum::SchemaChecksumService::new()
+        .calculate_sql_checksum(up_sql);
+    let meta_path = migration_dir.join(".meta.yaml");
+    let meta = fs::read_to_string(&meta_path).unwrap();
+    fs::write(
+        &meta_path,
+        format!("{}up_sql_checksum: {}\n", meta, up_sql_checksum),
+    )
+    .unwrap();
+
+    let checksum_statuses = |output: String| -> Vec<serde_json::Value> {
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        json["migrations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["checksum_status"].clone())
+            .collect()
+    };
+
+    // 改行コードのみの違いは変更とみなさない
+    fs::write(migration_dir.join("up.sql"), up_sql.replace('\n', "\r\n")).unwrap();
+    let output = run_status_with_gates(project_path.clone(), vec![], OutputFormat::Json)
+        .await
+        .unwrap();
+    assert_eq!(
+        checksum_statuses(output),
+        vec![serde_json::json!("ok"), serde_json::Value::Null]
+    );
+
+    // 適用後に up.sql を書き換えると modified になり、drift ゲートも失敗する
+    fs::write(
+        migration_dir.join("up.sql"),
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+    )
+    .unwrap();
+    let output = run_status_with_gates(project_path.clone(), vec![], OutputFormat::Json)
+        .await
+        .unwrap();
+    assert_eq!(
+        checksum_statuses(output),
+        vec![serde_json::json!("modified"), serde_json::Value::Null]
+    );
+    let failure = expect_gate_failure(
+        run_status_with_gates(
+            project_path.clone(),
+            vec![StatusGate::Drift],
+            OutputFormat::Text,
+        )
+        .await,
+    );
+    assert_eq!(failure.exit_code, EXIT_CODE_GATE_DRIFT);
+
+    fs::remove_file(&meta_path).unwrap();
+    let output = run_status_with_gates(project_path.clone(), vec![], OutputFormat::Json)
+        .await
+        .unwrap();
+    assert_eq!(
+        checksum_statuses(output),
+        vec![serde_json::json!("missing_meta"), serde_json::Value::Null]
+    );
+    let output = run_status_with_gates(project_path, vec![], OutputFormat::Text)
+        .await
+        .unwrap();
+    assert!(output.contains("Checksum"), "{}", output);
+    assert!(output.contains("missing meta"), "{}", output);
+}
+
+async fn run_status_verbose(project_path: PathBuf, format: OutputFormat) -> String {
+    let command = StatusCommand {
+        project_path,
+        config_path: None,
+        env: "development".to_string(),
+        format,
+        gates: vec![],
+        verbose: true,
+        wait_timeout: None,
+    };
+    StatusCommandHandler::new().execute(&command).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_status_verbose_shows_applied_details() {
+    let (_temp_dir, project_path, pool) = setup_gate_project().await;
+    add_pending_migration(&project_path);
+    sqlx::query(
+        "UPDATE schema_migrations SET applied_at = '2026-01-21T12:00:00+00:00', execution_time_ms = 42, applied_by = 'deployer'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool.close().await;
+
+    let text = run_status_verbose(project_path.clone(), OutputFormat::Text).await;
+    assert!(
+        text.contains("=== Applied Migration Details ==="),
+        "{}",
+        text
+    );
+    assert!(text.contains("2026-01-21 12:00:00 UTC"), "{}", text);
+    assert!(text.contains("42 ms"), "{}", text);
+    assert!(text.contains("deployer"), "{}", text);
+
+    let json = run_status_verbose(project_path.clone(), OutputFormat::Json).await;
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let applied = &parsed["migrations"][0];
+    assert_eq!(applied["applied_at"], "2026-01-21T12:00:00Z");
+    assert_eq!(applied["execution_time_ms"], 42);
+    assert_eq!(applied["applied_by"], "deployer");
+    // 未適用のマイグレーションの値は null
+    let pending = &parsed["migrations"][1];
+    assert!(pending["applied_at"].is_null());
+    assert!(pending["execution_time_ms"].is_null());
+    assert!(pending["applied_by"].is_null());
+
+    // --verbose なしでは詳細を出力しない
+    let json = run_status_with_gates(project_path, vec![], OutputFormat::Json)
+        .await
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(parsed["migrations"][0].get("applied_at").is_none());
+}
+
+#[tokio::test]
+async fn test_status_verbose_with_legacy_migration_table() {
+    install_default_drivers();
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::create_test_migration(
+        &project_path,
+        "20260121120000",
+        "create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+        "checksum_users",
+    )
+    .unwrap();
+
+    // 実行時間・適用ユーザーのカラムがない古い履歴テーブル
+    let config = ConfigLoader::from_file(
+        &project_path.join(strata::core::config::Config::DEFAULT_CONFIG_PATH),
+    )
+    .unwrap();
+    let db_config = config.get_database_config("development").unwrap();
+    let pool = DatabaseConnectionService::new()
+        .create_pool(Dialect::SQLite, &db_config)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, description TEXT NOT NULL, applied_at TEXT NOT NULL, checksum TEXT NOT NULL)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO schema_migrations VALUES ('20260121120000', 'create_users', '2026-01-21T12:00:00+00:00', 'checksum_users')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let json = run_status_verbose(project_path.clone(), OutputFormat::Json).await;
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let applied = &parsed["migrations"][0];
+    assert_eq!(applied["applied_at"], "2026-01-21T12:00:00Z");
+    assert!(applied["execution_time_ms"].is_null());
+    assert!(applied["applied_by"].is_null());
+
+    let text = run_status_verbose(project_path, OutputFormat::Text).await;
+    assert!(text.contains("20260121120000"), "{}", text);
+
+    // 接続時に現在のレイアウトに更新され、既存の記録はそのまま残る
+    let columns: Vec<String> =
+        sqlx::query("SELECT name FROM pragma_table_info('schema_migrations')")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| sqlx::Row::get(row, 0))
+            .collect();
+    for column in CURRENT_MIGRATION_TABLE_LAYOUT.columns {
+        assert!(columns.iter().any(|c| c == column), "{:?}", columns);
+    }
+    pool.close().await;
+}
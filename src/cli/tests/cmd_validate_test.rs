@@ -3,6 +3,7 @@
 use std::fs;
 use std::path::PathBuf;
 use strata::cli::commands::validate::{ValidateCommand, ValidateCommandHandler};
+use strata::cli::commands::CompletedWithFailure;
 use strata::core::config::Dialect;
 use tempfile::TempDir;
 
@@ -20,6 +21,12 @@ fn test_validate_command_struct() {
         project_path: PathBuf::from("/test/path"),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -37,6 +44,12 @@ fn test_validate_no_config_file() {
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -61,6 +74,12 @@ fn test_validate_no_schema_dir() {
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -82,6 +101,12 @@ fn test_validate_empty_schema_dir() {
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -129,6 +154,12 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -141,6 +172,74 @@ tables:
     assert!(summary.contains("No errors found"));
 }
 
+#[test]
+fn test_validate_duplicate_table_across_files() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+
+    let schema_yaml = r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#;
+    fs::write(project_path.join("schema/users.yaml"), schema_yaml).unwrap();
+    fs::write(project_path.join("schema/users_copy.yaml"), schema_yaml).unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let mut command = ValidateCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
+        format: strata::cli::OutputFormat::Text,
+    };
+
+    // デフォルトでは両方のファイルパスを含む検証エラーになる
+    let error = format!("{:#}", handler.execute(&command).unwrap_err());
+    assert!(
+        error.contains("Validation failed with 1 error(s)"),
+        "{}",
+        error
+    );
+
+    // 重複したテーブル名と両方のファイルパスが出力に含まれ、位置は後から読み込んだファイル
+    command.format = strata::cli::OutputFormat::Json;
+    let error = handler.execute(&command).unwrap_err();
+    let completed = error.downcast_ref::<CompletedWithFailure>().unwrap();
+    assert_eq!(completed.exit_code, 1);
+    let value: serde_json::Value = serde_json::from_str(&completed.output).unwrap();
+    assert_eq!(value["is_valid"], false);
+    let errors = value["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    let message = errors[0]["message"].as_str().unwrap();
+    assert!(
+        message.contains("Table 'users' is defined in both"),
+        "{}",
+        message
+    );
+    assert!(message.contains("users.yaml"), "{}", message);
+    assert!(message.contains("users_copy.yaml"), "{}", message);
+    assert_eq!(errors[0]["file"], "schema/users_copy.yaml");
+    command.format = strata::cli::OutputFormat::Text;
+
+    // --allow-duplicate-override 指定時は後のファイルを採用して続行する
+    command.allow_duplicate_override = true;
+    let summary = handler.execute(&command).unwrap();
+    assert!(summary.contains("Tables: 1"), "{}", summary);
+}
+
 #[test]
 fn test_validate_invalid_schema_no_primary_key() {
     let (_temp_dir, project_path) =
@@ -169,6 +268,12 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -215,6 +320,12 @@ tables:
         project_path,
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -225,6 +336,203 @@ tables:
     assert!(err_msg.contains("Validation failed"));
 }
 
+#[test]
+fn test_validate_foreign_key_to_external_table() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+
+    // strata管理外のテーブルを参照する外部キー
+    let schema_yaml = r#"
+version: "1.0"
+external_tables: [external_users]
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+        references: external_users.id
+    primary_key:
+      - id
+"#;
+    fs::write(project_path.join("schema/posts.yaml"), schema_yaml).unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let command = ValidateCommand {
+        project_path: project_path.clone(),
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
+        format: strata::cli::OutputFormat::Text,
+    };
+
+    // カラムを検証できないため警告として報告される
+    let output = handler.execute(&command).unwrap();
+    assert!(
+        output.contains("references external table 'external_users'"),
+        "{}",
+        output
+    );
+
+    // 外部テーブルを通常のテーブルとしても定義するとエラー
+    let users_yaml = r#"
+version: "1.0"
+tables:
+  external_users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#;
+    fs::write(project_path.join("schema/users.yaml"), users_yaml).unwrap();
+    let err_msg = format!("{:#}", handler.execute(&command).unwrap_err());
+    assert!(err_msg.contains("Validation failed"), "{}", err_msg);
+}
+
+/// users.yaml / posts.yaml / tags.yaml / legacy.yaml の4ファイル構成を作成
+///
+/// posts は users に存在しないカラムを参照する外部キーを持ち、
+/// legacy は主キーを持たないため、それぞれ検証エラーになる。
+fn write_multi_file_schema(project_path: &std::path::Path) {
+    let files = [
+        (
+            "users.yaml",
+            r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#,
+        ),
+        (
+            "posts.yaml",
+            r#"
+version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - user_uuid
+"#,
+        ),
+        (
+            "tags.yaml",
+            r#"
+version: "1.0"
+tables:
+  tags:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#,
+        ),
+        (
+            "legacy.yaml",
+            r#"
+version: "1.0"
+tables:
+  legacy:
+    columns:
+      - name: value
+        type:
+          kind: TEXT
+        nullable: true
+"#,
+        ),
+    ];
+    for (name, yaml) in files {
+        fs::write(project_path.join("schema").join(name), yaml).unwrap();
+    }
+}
+
+fn validate_files_command(project_path: PathBuf, files: &[&str]) -> ValidateCommand {
+    ValidateCommand {
+        project_path,
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: files.iter().map(PathBuf::from).collect(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
+        format: strata::cli::OutputFormat::Json,
+    }
+}
+
+#[test]
+fn test_validate_files_reports_only_related_issues() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    write_multi_file_schema(&project_path);
+    let handler = ValidateCommandHandler::new();
+
+    // 無関係なファイルのみ変更した場合、既存のエラーは報告しない
+    let command = validate_files_command(project_path.clone(), &["schema/tags.yaml"]);
+    let output = handler.execute(&command).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(value["is_valid"], true);
+    assert_eq!(value["changed_files"][0], "schema/tags.yaml");
+
+    // users.yaml の変更は、変更されていない posts.yaml の外部キーにも影響する
+    let command = validate_files_command(project_path.clone(), &["schema/users.yaml"]);
+    let err = handler.execute(&command).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Validation failed with 1 error(s)"),
+        "{}",
+        err
+    );
+
+    // 全体を検証した場合は legacy.yaml のエラーも含まれる
+    let command = validate_files_command(project_path, &[]);
+    let err = handler.execute(&command).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Validation failed with 2 error(s)"),
+        "{}",
+        err
+    );
+}
+
 #[test]
 fn test_validate_custom_schema_dir() {
     let (_temp_dir, project_path) =
@@ -254,6 +562,12 @@ tables:
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: Some(custom_schema_dir),
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: false,
         format: strata::cli::OutputFormat::Text,
     };
 
@@ -287,3 +601,259 @@ fn test_format_validation_summary() {
     assert!(summary.contains("Tables: 2"));
     assert!(summary.contains("No errors found"));
 }
+
+fn validate_single_file_command(project_path: PathBuf, file: &str) -> ValidateCommand {
+    ValidateCommand {
+        project_path,
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: Some(PathBuf::from(file)),
+        suggest_fixes: false,
+        apply_fixes: false,
+        format: strata::cli::OutputFormat::Json,
+    }
+}
+
+#[test]
+fn test_validate_single_file_defers_cross_file_references() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::PostgreSQL, None, true).unwrap();
+    // users は別ファイルに定義されている想定
+    fs::write(
+        project_path.join("schema/posts.yaml"),
+        r#"version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: status
+        type:
+          kind: ENUM
+          name: post_status
+        nullable: false
+    primary_key:
+      - id
+    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - id
+"#,
+    )
+    .unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let output = handler
+        .execute(&validate_single_file_command(
+            project_path,
+            "schema/posts.yaml",
+        ))
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["file"], "schema/posts.yaml");
+    assert_eq!(json["is_valid"], true);
+    let deferred = json["deferred"].as_array().unwrap();
+    assert_eq!(deferred.len(), 2);
+    assert_eq!(deferred[0]["rule"], "foreign_key_target");
+    assert_eq!(deferred[0]["target"], "users");
+    assert_eq!(deferred[0]["line"], 3);
+    assert_eq!(deferred[1]["rule"], "enum_reference");
+    assert_eq!(deferred[1]["target"], "post_status");
+    assert_eq!(deferred[1]["line"], 13);
+}
+
+#[test]
+fn test_validate_single_file_fails_on_local_errors() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    fs::write(
+        project_path.join("schema/broken.yaml"),
+        r#"version: "1.0"
+tables:
+  broken:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: price
+        type:
+          kind: DECIMAL
+          precision: 5
+          scale: 10
+        nullable: false
+    indexes:
+      - name: idx_missing
+        columns:
+          - missing
+"#,
+    )
+    .unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let result = handler.execute(&validate_single_file_command(
+        project_path,
+        "schema/broken.yaml",
+    ));
+
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("Validation failed"));
+}
+
+#[test]
+fn test_validate_single_file_reports_parse_error() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    fs::write(
+        project_path.join("schema/bad.yaml"),
+        "version: \"1.0\"\ntables:\n  users:\n    columns:\n      - name: id\n        type: {kind: INTEGER\n",
+    )
+    .unwrap();
+
+    let mut command = validate_single_file_command(project_path, "schema/bad.yaml");
+    command.format = strata::cli::OutputFormat::Text;
+    let result = ValidateCommandHandler::new().execute(&command);
+
+    assert!(result.is_err());
+}
+
+fn validate_fixes_command(
+    project_path: PathBuf,
+    format: strata::cli::OutputFormat,
+) -> ValidateCommand {
+    ValidateCommand {
+        project_path,
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        changed_since: None,
+        files: Vec::new(),
+        file: None,
+        suggest_fixes: false,
+        apply_fixes: true,
+        format,
+    }
+}
+
+const FIXABLE_SCHEMA: &str = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: user_id
+        type:
+          kind: INTEGER
+    primary_key: [id]
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [user_id]
+        referenced_table: users
+        referenced_columns: []
+"#;
+
+#[test]
+fn test_validate_apply_fixes_writes_safe_fixes() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let schema_path = project_path.join("schema/blog.yaml");
+    fs::write(&schema_path, FIXABLE_SCHEMA).unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let command = validate_fixes_command(project_path, strata::cli::OutputFormat::Text);
+    let output = handler.execute(&command).unwrap();
+
+    assert!(output.contains("=== Applied Fixes ==="), "{}", output);
+    assert!(
+        output.contains("✓ Add primary_key: [id] to table 'users' (schema/blog.yaml)"),
+        "{}",
+        output
+    );
+    assert!(output.contains("No errors found"), "{}", output);
+    let fixed = fs::read_to_string(&schema_path).unwrap();
+    assert!(
+        fixed.contains("          kind: INTEGER\n    primary_key: [id]\n  posts:"),
+        "{}",
+        fixed
+    );
+    assert!(fixed.contains("referenced_columns: [id]"), "{}", fixed);
+}
+
+#[test]
+fn test_validate_apply_fixes_json_lists_results() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    fs::write(project_path.join("schema/blog.yaml"), FIXABLE_SCHEMA).unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let command = validate_fixes_command(project_path, strata::cli::OutputFormat::Json);
+    let output = handler.execute(&command).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(value["is_valid"], true);
+    let applied = value["applied_fixes"].as_array().unwrap();
+    assert_eq!(applied.len(), 2);
+    assert!(applied.iter().all(|fix| fix["applied"] == true));
+    assert_eq!(applied[0]["file"], "schema/blog.yaml");
+    // 適用後は修正案が残らない
+    assert_eq!(value["fixes"], serde_json::json!([]));
+}
+
+#[test]
+fn test_validate_apply_fixes_leaves_unsafe_fixes() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let schema_yaml = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: id
+        type:
+          kind: INTEGER
+    primary_key: [id]
+"#;
+    let schema_path = project_path.join("schema/users.yaml");
+    fs::write(&schema_path, schema_yaml).unwrap();
+
+    let handler = ValidateCommandHandler::new();
+    let mut command = validate_fixes_command(project_path, strata::cli::OutputFormat::Text);
+    let error = format!("{:#}", handler.execute(&command).unwrap_err());
+    assert!(
+        error.contains("Validation failed with 1 error(s)"),
+        "{}",
+        error
+    );
+    // 重複カラムの削除は書き込まない
+    assert_eq!(fs::read_to_string(&schema_path).unwrap(), schema_yaml);
+
+    // --suggest-fixes のみではファイルを変更しない
+    command.apply_fixes = false;
+    command.suggest_fixes = true;
+    assert!(handler.execute(&command).is_err());
+    assert_eq!(fs::read_to_string(&schema_path).unwrap(), schema_yaml);
+}
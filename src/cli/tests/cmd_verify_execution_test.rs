@@ -0,0 +1,241 @@
+// verify-executionコマンドハンドラーのテスト
+//
+// apply が記録した実行マニフェストと、ローカルの up.sql から再計算した
+// マニフェストの比較を SQLite で確認します。
+
+use sqlx::any::install_default_drivers;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::verify_execution::{
+    VerifyExecutionCommand, VerifyExecutionCommandHandler, EXIT_CODE_EXECUTION_DIVERGENCE,
+};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::OutputFormat;
+use strata::core::config::{Config, Dialect};
+use strata::services::config_serializer::ConfigSerializer;
+use tempfile::TempDir;
+
+mod common;
+
+const VERSION: &str = "20260101120000";
+const UP_SQL: &str = "CREATE TABLE users (id INTEGER PRIMARY KEY);\nCREATE TABLE posts (id INTEGER PRIMARY KEY);\nCREATE INDEX idx_posts_id ON posts (id);\n";
+
+/// 3ステートメントのマイグレーションを持つ SQLite プロジェクトを作成
+fn setup_project(store_applied_sql: bool) -> (TempDir, PathBuf) {
+    install_default_drivers();
+    let (temp_dir, project_path) = common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+    let db_path = project_path.join("verify.db");
+    let mut config = common::create_test_config(Dialect::SQLite, Some(&db_path.to_string_lossy()));
+    config.store_applied_sql = store_applied_sql;
+    fs::write(
+        project_path.join(Config::DEFAULT_CONFIG_PATH),
+        ConfigSerializer::to_yaml(&config).unwrap(),
+    )
+    .unwrap();
+    common::create_test_migration(
+        &project_path,
+        VERSION,
+        "create_users_and_posts",
+        UP_SQL,
+        "DROP TABLE posts;\nDROP TABLE users;\n",
+        "checksum1",
+    )
+    .unwrap();
+    (temp_dir, project_path)
+}
+
+async fn apply(project_path: &Path) -> serde_json::Value {
+    let command = ApplyCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format: OutputFormat::Json,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    };
+    let output = ApplyCommandHandler::new().execute(&command).await.unwrap();
+    serde_json::from_str(&output).unwrap()
+}
+
+async fn verify(project_path: &Path, format: OutputFormat) -> anyhow::Result<String> {
+    let command = VerifyExecutionCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        version: VERSION.to_string(),
+        env: "development".to_string(),
+        format,
+    };
+    VerifyExecutionCommandHandler::new().execute(&command).await
+}
+
+fn expect_divergence(result: anyhow::Result<String>) -> CompletedWithFailure {
+    let err = result.expect_err("verify-execution should fail");
+    err.downcast_ref::<CompletedWithFailure>()
+        .unwrap_or_else(|| panic!("unexpected error: {:#}", err))
+        .clone()
+}
+
+fn write_up_sql(project_path: &Path, up_sql: &str) {
+    let path = project_path
+        .join("migrations")
+        .join(format!("{}_create_users_and_posts", VERSION))
+        .join("up.sql");
+    fs::write(path, up_sql).unwrap();
+}
+
+#[tokio::test]
+async fn test_apply_records_statement_checksums_in_order() {
+    let (_temp_dir, project_path) = setup_project(false);
+
+    let output = apply(&project_path).await;
+    let checksums = output["migrations"][0]["statement_checksums"]
+        .as_array()
+        .unwrap();
+    assert_eq!(checksums.len(), 3);
+    // 一時的なエラーがなければ再試行しない
+    assert_eq!(output["migrations"][0]["retries"], 0);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&verify(&project_path, OutputFormat::Json).await.unwrap()).unwrap();
+    assert_eq!(json["matches"], true);
+    assert_eq!(json["source"], "statement_checksums");
+    assert_eq!(json["recorded_statements"], 3);
+    assert_eq!(json["divergences"], serde_json::json!([]));
+
+    // 改行コードの違いは相違とみなさない
+    write_up_sql(&project_path, &UP_SQL.replace('\n', "\r\n"));
+    let text = verify(&project_path, OutputFormat::Text).await.unwrap();
+    assert!(
+        text.contains("The local up.sql matches the statements executed, in the same order"),
+        "{}",
+        text
+    );
+}
+
+#[tokio::test]
+async fn test_verify_execution_reports_edited_local_file() {
+    let (_temp_dir, project_path) = setup_project(false);
+    apply(&project_path).await;
+
+    // ステートメントの入れ替えと追加
+    write_up_sql(
+        &project_path,
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY);\nCREATE TABLE users (id INTEGER PRIMARY KEY);\nCREATE INDEX idx_posts_id ON posts (id);\nCREATE INDEX idx_users_id ON users (id);\n",
+    );
+
+    let failure = expect_divergence(verify(&project_path, OutputFormat::Text).await);
+    assert_eq!(failure.exit_code, EXIT_CODE_EXECUTION_DIVERGENCE);
+    assert!(
+        failure.output.contains("#1: recorded "),
+        "{}",
+        failure.output
+    );
+    assert!(
+        failure.output.contains("#2: recorded "),
+        "{}",
+        failure.output
+    );
+    assert!(!failure.output.contains("#3:"), "{}", failure.output);
+    assert!(
+        failure.output.contains("#4: recorded (none), local "),
+        "{}",
+        failure.output
+    );
+    assert!(
+        failure
+            .output
+            .contains("CREATE INDEX idx_users_id ON users (id)"),
+        "{}",
+        failure.output
+    );
+    assert!(
+        failure.message.contains("3 statement(s) differ"),
+        "{}",
+        failure.message
+    );
+
+    let failure = expect_divergence(verify(&project_path, OutputFormat::Json).await);
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(json["matches"], false);
+    assert_eq!(json["local_statements"], 4);
+    assert_eq!(json["divergences"][2]["position"], 4);
+    assert_eq!(json["divergences"][2]["recorded"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_verify_execution_falls_back_to_stored_sql() {
+    let (_temp_dir, project_path) = setup_project(true);
+    apply(&project_path).await;
+
+    // チェックサムの記録前に適用されたマイグレーションを再現する
+    let db_path = project_path.join("verify.db");
+    let pool = sqlx::AnyPool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE schema_migrations SET statement_checksums = NULL")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    let json: serde_json::Value =
+        serde_json::from_str(&verify(&project_path, OutputFormat::Json).await.unwrap()).unwrap();
+    assert_eq!(json["matches"], true);
+    assert_eq!(json["source"], "stored_sql");
+
+    write_up_sql(
+        &project_path,
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);\n",
+    );
+    let failure = expect_divergence(verify(&project_path, OutputFormat::Text).await);
+    assert!(
+        failure.output.contains("from the stored up.sql"),
+        "{}",
+        failure.output
+    );
+}
+
+#[tokio::test]
+async fn test_verify_execution_requires_a_recorded_manifest() {
+    let (_temp_dir, project_path) = setup_project(false);
+
+    // 未適用のマイグレーション
+    let err = verify(&project_path, OutputFormat::Text)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("has not been applied"), "{}", err);
+
+    apply(&project_path).await;
+    let db_path = project_path.join("verify.db");
+    let pool = sqlx::AnyPool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    sqlx::query("UPDATE schema_migrations SET statement_checksums = NULL")
+        .execute(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+
+    let err = verify(&project_path, OutputFormat::Text)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no recorded statement checksums"), "{}", err);
+    assert!(err.contains("store_applied_sql: true"), "{}", err);
+}
@@ -0,0 +1,169 @@
+// versionコマンドハンドラーのテスト
+//
+// 最新リリースの確認はモックサーバーに対して行い、ネットワークに接続できない場合も
+// コマンドが失敗しないことを確認します。
+
+use std::fs;
+use std::time::Duration;
+use strata::cli::commands::version::{VersionCommand, VersionCommandHandler};
+use strata::cli::update_check::{self, UpdateChecker};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+
+mod common;
+
+/// 最新リリースとして `tag_name` を返すモックサーバーを起動する
+#[cfg(feature = "update-check")]
+fn mock_latest_release(tag_name: &str) -> String {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/releases/latest", listener.local_addr().unwrap());
+    let body = format!(r#"{{"tag_name": "{}"}}"#, tag_name);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                line.clear();
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+
+    url
+}
+
+fn run(checker: UpdateChecker, check_latest: bool, format: OutputFormat) -> String {
+    VersionCommandHandler::new()
+        .with_checker(checker)
+        .execute(&VersionCommand {
+            check_latest,
+            features: false,
+            format,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_version_without_check_does_not_use_network() {
+    let checker = UpdateChecker::new().with_endpoint("http://127.0.0.1:1/unreachable");
+
+    let output = run(checker, false, OutputFormat::Text);
+
+    assert_eq!(output, format!("strata {}", env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn test_version_features_lists_compiled_drivers() {
+    let command = |format| VersionCommand {
+        check_latest: false,
+        features: true,
+        format,
+    };
+    let handler = VersionCommandHandler::new();
+
+    let output = handler.execute(&command(OutputFormat::Text)).unwrap();
+    assert!(output.contains("Database drivers:"), "{}", output);
+    #[cfg(feature = "sqlite")]
+    assert!(output.contains("  sqlite      included"), "{}", output);
+    #[cfg(not(feature = "mysql"))]
+    assert!(
+        output.contains("  mysql       not included (rebuild with --features mysql)"),
+        "{}",
+        output
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&handler.execute(&command(OutputFormat::Json)).unwrap()).unwrap();
+    let drivers = json["drivers"].as_array().unwrap();
+    assert_eq!(drivers.len(), 3);
+    assert_eq!(drivers[0]["dialect"], "postgresql");
+    assert_eq!(drivers[0]["feature"], "postgres");
+    assert_eq!(drivers[0]["compiled"], cfg!(feature = "postgres"));
+}
+
+#[cfg(feature = "update-check")]
+#[test]
+fn test_version_check_latest_reports_outdated_version() {
+    let checker = UpdateChecker::new()
+        .with_endpoint(mock_latest_release("v0.3.0"))
+        .with_current_version("0.2.0");
+
+    let output = run(checker, true, OutputFormat::Text);
+
+    assert_eq!(
+        output,
+        "strata 0.2.0\nA new version of strata is available: 0.2.0 -> 0.3.0 (https://github.com/Lazialize/strata/releases)"
+    );
+}
+
+#[cfg(feature = "update-check")]
+#[test]
+fn test_version_check_latest_up_to_date() {
+    let checker = UpdateChecker::new()
+        .with_endpoint(mock_latest_release("v0.2.0"))
+        .with_current_version("0.2.0");
+
+    let output = run(checker, true, OutputFormat::Text);
+
+    assert_eq!(output, "strata 0.2.0\nstrata is up to date.");
+}
+
+#[cfg(feature = "update-check")]
+#[test]
+fn test_version_check_latest_json_embeds_notice() {
+    let checker = UpdateChecker::new()
+        .with_endpoint(mock_latest_release("v0.3.0"))
+        .with_current_version("0.2.0");
+
+    let output = run(checker, true, OutputFormat::Json);
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["version"], "0.2.0");
+    assert_eq!(json["latest_version"], "0.3.0");
+    assert_eq!(json["update_available"], true);
+    assert!(json["update_notice"]
+        .as_str()
+        .unwrap()
+        .contains("0.2.0 -> 0.3.0"));
+    assert!(json.get("update_check_error").is_none());
+}
+
+#[test]
+fn test_version_check_latest_network_unavailable_does_not_fail() {
+    let checker = UpdateChecker::new()
+        .with_endpoint("http://127.0.0.1:1/unreachable")
+        .with_timeout(Duration::from_millis(200))
+        .with_current_version("0.2.0");
+
+    let output = run(checker, true, OutputFormat::Json);
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["version"], "0.2.0");
+    assert!(json.get("latest_version").is_none());
+    assert!(json.get("update_notice").is_none());
+    assert!(json["update_check_error"].is_string());
+}
+
+#[test]
+fn test_notice_for_project_requires_update_check_config() {
+    let (_temp_dir, project_path) =
+        common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+
+    assert!(update_check::notice_for_project(&project_path, None).is_none());
+    assert!(!project_path.join(".strata").exists());
+
+    // 設定ファイルがない場合も通知せずに終了する
+    fs::remove_file(project_path.join(".strata.yaml")).unwrap();
+    assert!(update_check::notice_for_project(&project_path, None).is_none());
+}
@@ -0,0 +1,176 @@
+// workspaceコマンドハンドラーのテスト
+//
+// 複数プロジェクトに対する status の実行と結果の集約を検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use std::fs;
+use std::path::Path;
+use strata::cli::commands::status::StatusGate;
+use strata::cli::commands::workspace::{WorkspaceCommandHandler, WorkspaceStatusCommand};
+use strata::cli::commands::CompletedWithFailure;
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use strata::core::naming::WORKSPACE_FILE;
+use tempfile::TempDir;
+mod common;
+
+/// SQLiteのメンバープロジェクトを作成する
+fn create_member(workspace: &Path, member: &str) {
+    let project_path = workspace.join(member);
+    fs::create_dir_all(project_path.join("migrations")).unwrap();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+}
+
+/// 2つのメンバーを持つワークスペースを作成する
+fn setup_workspace() -> TempDir {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    create_member(temp_dir.path(), "services/auth");
+    create_member(temp_dir.path(), "services/billing");
+    fs::write(
+        temp_dir.path().join(WORKSPACE_FILE),
+        "members:\n  - services/auth\n  - services/billing\n",
+    )
+    .unwrap();
+    temp_dir
+}
+
+async fn run_workspace_status(
+    workspace: &Path,
+    gates: Vec<StatusGate>,
+    format: OutputFormat,
+) -> anyhow::Result<String> {
+    let command = WorkspaceStatusCommand {
+        workspace_path: workspace.to_path_buf(),
+        env: "development".to_string(),
+        format,
+        gates,
+    };
+    WorkspaceCommandHandler::new()
+        .execute_status(&command)
+        .await
+}
+
+fn expect_failure(result: anyhow::Result<String>) -> CompletedWithFailure {
+    let err = result.expect_err("workspace status should fail");
+    err.downcast_ref::<CompletedWithFailure>()
+        .unwrap_or_else(|| panic!("unexpected error: {:#}", err))
+        .clone()
+}
+
+#[tokio::test]
+async fn test_workspace_status_text_sections() {
+    let workspace = setup_workspace();
+
+    let output = run_workspace_status(workspace.path(), vec![], OutputFormat::Text)
+        .await
+        .unwrap();
+
+    let auth = output.find("=== services/auth ===").unwrap();
+    let billing = output.find("=== services/billing ===").unwrap();
+    assert!(auth < billing);
+    assert_eq!(output.matches("No migrations found").count(), 2);
+    assert!(output.ends_with("Workspace status: 2 project(s) succeeded"));
+}
+
+#[tokio::test]
+async fn test_workspace_status_json_array() {
+    let workspace = setup_workspace();
+
+    let output = run_workspace_status(workspace.path(), vec![], OutputFormat::Json)
+        .await
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["command"], "status");
+    assert_eq!(json["failed"], 0);
+    let members = json["members"].as_array().unwrap();
+    let projects: Vec<&str> = members
+        .iter()
+        .map(|m| m["project"].as_str().unwrap())
+        .collect();
+    assert_eq!(projects, vec!["services/auth", "services/billing"]);
+    for member in members {
+        assert_eq!(member["outcome"], "succeeded");
+        assert_eq!(member["output"]["summary"]["total"], 0);
+        assert!(member.get("error").is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_workspace_status_continues_after_failed_member() {
+    let workspace = setup_workspace();
+    // 最初のメンバーの設定ファイルを削除して失敗させる
+    fs::remove_file(
+        workspace
+            .path()
+            .join("services/auth")
+            .join(strata::core::config::Config::DEFAULT_CONFIG_PATH),
+    )
+    .unwrap();
+
+    let failure =
+        expect_failure(run_workspace_status(workspace.path(), vec![], OutputFormat::Text).await);
+
+    assert_eq!(failure.exit_code, 1);
+    assert_eq!(
+        failure.message,
+        "1 of 2 workspace project(s) failed: services/auth"
+    );
+    // 失敗したメンバーの後も残りのメンバーは実行される
+    assert!(failure.output.contains("Config file not found"));
+    assert!(failure.output.contains("=== services/billing ==="));
+    assert!(failure.output.contains("No migrations found"));
+    assert!(failure
+        .output
+        .ends_with("Workspace status: 1 of 2 project(s) failed (services/auth)"));
+}
+
+#[tokio::test]
+async fn test_workspace_status_gate_failure_uses_gate_exit_code() {
+    let workspace = setup_workspace();
+    common::create_test_migration(
+        &workspace.path().join("services/billing"),
+        "20260121120000",
+        "create_invoices",
+        "CREATE TABLE invoices (id INTEGER PRIMARY KEY);",
+        "DROP TABLE invoices;",
+        "checksum1",
+    )
+    .unwrap();
+
+    let failure = expect_failure(
+        run_workspace_status(
+            workspace.path(),
+            vec![StatusGate::Pending],
+            OutputFormat::Json,
+        )
+        .await,
+    );
+
+    assert_eq!(failure.exit_code, StatusGate::Pending.exit_code());
+    let json: serde_json::Value = serde_json::from_str(&failure.output).unwrap();
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["members"][0]["outcome"], "succeeded");
+    let billing = &json["members"][1];
+    assert_eq!(billing["outcome"], "failed");
+    assert_eq!(billing["output"]["gates"]["pending"], "fail");
+    assert!(billing["error"].as_str().unwrap().contains("pending"));
+}
+
+#[tokio::test]
+async fn test_workspace_status_without_workspace_file() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let err = run_workspace_status(temp_dir.path(), vec![], OutputFormat::Text)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("Workspace file not found"));
+}
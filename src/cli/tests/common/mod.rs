@@ -29,6 +29,25 @@ pub fn create_test_config(dialect: Dialect, database_path: Option<&str>) -> Conf
         schema_dir: PathBuf::from("schema"),
         migrations_dir: PathBuf::from("migrations"),
         environments,
+        sqlite: Default::default(),
+        mysql: Default::default(),
+        postgres: Default::default(),
+        policy: Default::default(),
+        sql_output: Default::default(),
+        store_applied_sql: false,
+        future_version_tolerance_minutes: None,
+        required_metadata: Vec::new(),
+        require_clean_pending: false,
+        enum_order_sensitive: true,
+        protected_environments: Vec::new(),
+        stream_sql_threshold_bytes: None,
+        naming: Default::default(),
+        identifiers: Default::default(),
+        identifier_case: Default::default(),
+        update_check: false,
+        view_rename_propagation: Default::default(),
+        contract_min_age_days: None,
+        statement_retry: Default::default(),
     }
 }
 
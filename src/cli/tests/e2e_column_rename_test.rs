@@ -1,3 +1,5 @@
+#![cfg(all(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
 /// カラムリネーム機能のE2Eテスト
 ///
 /// testcontainersを使用して実際のデータベースに対するエンドツーエンドテストを実施します。
@@ -11,6 +13,7 @@
 ///
 /// 注意: Docker必須のテストは #[ignore] アトリビュートでマークされています。
 /// Docker起動時に実行するには: `cargo test -- --ignored`
+/// 複数の方言のドライバーを使うため、`postgres` / `mysql` / `sqlite` フィーチャーがすべて有効なビルドでのみ対象になります。
 #[cfg(test)]
 mod column_rename_e2e_tests {
     use sqlx::postgres::PgPoolOptions;
@@ -494,3 +494,208 @@ tables:
         );
     }
 }
+
+#[cfg(test)]
+mod not_null_column_backfill {
+    use crate::common;
+    use std::fs;
+    use strata::core::config::Dialect;
+    use strata::core::error::ValidationResult;
+    use strata::services::migration_pipeline::MigrationPipeline;
+    use strata::services::schema_diff_detector::SchemaDiffDetectorService;
+    use strata::services::schema_io::schema_parser::SchemaParserService;
+    use tempfile::TempDir;
+
+    const OLD_YAML: &str = r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: name
+        type:
+          kind: VARCHAR
+          length: 50
+        nullable: true
+    primary_key:
+      - id
+"#;
+
+    const NEW_YAML: &str = r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: name
+        type:
+          kind: VARCHAR
+          length: 50
+        nullable: true
+      - name: email
+        type:
+          kind: VARCHAR
+          length: 255
+        nullable: false
+    primary_key:
+      - id
+"#;
+
+    /// UP SQL と検証結果（警告）を生成
+    fn generate_up_with_warnings(dialect: Dialect) -> (String, ValidationResult) {
+        let temp_dir = TempDir::new().unwrap();
+        let old_path = temp_dir.path().join("old.yaml");
+        let new_path = temp_dir.path().join("new.yaml");
+        fs::write(&old_path, OLD_YAML).unwrap();
+        fs::write(&new_path, NEW_YAML).unwrap();
+
+        let parser = SchemaParserService::new();
+        let old_schema = parser.parse_schema_file(&old_path).unwrap();
+        let new_schema = parser.parse_schema_file(&new_path).unwrap();
+        let diff = SchemaDiffDetectorService::new().detect_diff(&old_schema, &new_schema);
+
+        MigrationPipeline::new(&diff, dialect)
+            .with_schemas(&old_schema, &new_schema)
+            .generate_up()
+            .unwrap()
+    }
+
+    fn assert_backfill_warning(validation: &ValidationResult) {
+        assert_eq!(validation.warnings.len(), 1, "{:?}", validation.warnings);
+        let message = &validation.warnings[0].message;
+        assert!(
+            message.contains("Column 'users.email' is added as NOT NULL without a default value"),
+            "{}",
+            message
+        );
+        assert!(message.contains("-- TODO: backfill"), "{}", message);
+    }
+
+    /// 位置 `a` の文字列が位置 `b` の文字列より前に現れることを検証
+    fn assert_order(sql: &str, a: &str, b: &str) {
+        let pa = sql
+            .find(a)
+            .unwrap_or_else(|| panic!("{} not found in: {}", a, sql));
+        let pb = sql
+            .find(b)
+            .unwrap_or_else(|| panic!("{} not found in: {}", b, sql));
+        assert!(pa < pb, "Expected {} before {} in: {}", a, b, sql);
+    }
+
+    #[test]
+    fn test_add_not_null_column_without_default_postgres() {
+        let (up_sql, validation) = generate_up_with_warnings(Dialect::PostgreSQL);
+
+        assert!(
+            up_sql.contains(r#"ALTER TABLE "users" ADD COLUMN "email" VARCHAR(255);"#),
+            "Expected nullable ADD COLUMN: {}",
+            up_sql
+        );
+        assert_order(
+            &up_sql,
+            r#"ADD COLUMN "email""#,
+            r#"-- TODO: backfill "users"."email" before it becomes NOT NULL"#,
+        );
+        assert_order(
+            &up_sql,
+            r#"-- UPDATE "users" SET "email" = <value> WHERE "email" IS NULL"#,
+            r#"ALTER TABLE "users" ALTER COLUMN "email" SET NOT NULL"#,
+        );
+        assert_backfill_warning(&validation);
+
+        let (_, down_sql) = common::generate_migration_sql(OLD_YAML, NEW_YAML, Dialect::PostgreSQL);
+        assert!(
+            down_sql.contains(r#"ALTER TABLE "users" DROP COLUMN "email""#),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_add_not_null_column_without_default_mysql() {
+        let (up_sql, validation) = generate_up_with_warnings(Dialect::MySQL);
+
+        assert!(
+            up_sql.contains("ALTER TABLE `users` ADD COLUMN `email` VARCHAR(255);"),
+            "Expected nullable ADD COLUMN: {}",
+            up_sql
+        );
+        assert_order(
+            &up_sql,
+            "-- UPDATE `users` SET `email` = <value> WHERE `email` IS NULL",
+            "ALTER TABLE `users` MODIFY COLUMN `email` VARCHAR(255) NOT NULL",
+        );
+        assert_backfill_warning(&validation);
+
+        let (_, down_sql) = common::generate_migration_sql(OLD_YAML, NEW_YAML, Dialect::MySQL);
+        assert!(
+            down_sql.contains("ALTER TABLE `users` DROP COLUMN `email`"),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_add_not_null_column_without_default_sqlite() {
+        let (up_sql, validation) = generate_up_with_warnings(Dialect::SQLite);
+
+        // SQLite は NOT NULL カラムを ADD COLUMN できないため、テーブル再作成で追加する
+        assert!(!up_sql.contains("ADD COLUMN"), "{}", up_sql);
+        assert_eq!(
+            up_sql
+                .matches(r#"CREATE TABLE "_stratum_tmp_recreate_users""#)
+                .count(),
+            1,
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains(r#""email" TEXT NOT NULL"#),
+            "Expected NOT NULL column in recreated table: {}",
+            up_sql
+        );
+        assert_order(
+            &up_sql,
+            r#"ALTER TABLE "_stratum_tmp_recreate_users" RENAME TO "users""#,
+            r#"-- TODO: backfill "users"."email" (existing rows were filled with a placeholder value)"#,
+        );
+        assert!(
+            up_sql.contains(r#"-- UPDATE "users" SET "email" = <value>"#),
+            "{}",
+            up_sql
+        );
+        assert_backfill_warning(&validation);
+
+        let (_, down_sql) = common::generate_migration_sql(OLD_YAML, NEW_YAML, Dialect::SQLite);
+        assert!(
+            down_sql.contains(r#"ALTER TABLE "users" DROP COLUMN "email""#),
+            "{}",
+            down_sql
+        );
+    }
+
+    /// デフォルト値のある NOT NULL カラムは従来どおり 1 文で追加する
+    #[test]
+    fn test_add_not_null_column_with_default_is_unchanged() {
+        let new_yaml = NEW_YAML.replace(
+            "        nullable: false\n    primary_key",
+            "        nullable: false\n        default_value: \"''\"\n    primary_key",
+        );
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let (up_sql, _) = common::generate_migration_sql(OLD_YAML, &new_yaml, dialect);
+            assert!(up_sql.contains("ADD COLUMN"), "{:?}: {}", dialect, up_sql);
+            assert!(
+                !up_sql.contains("TODO: backfill"),
+                "{:?}: {}",
+                dialect,
+                up_sql
+            );
+        }
+    }
+}
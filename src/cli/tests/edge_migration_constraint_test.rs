@@ -612,10 +612,9 @@ mod self_referencing_fk {
 
     use crate::common;
 
-    /// 自己参照FK付きテーブルの作成は循環参照エラーになる
-    /// （自己参照FKはトポロジカルソートで循環として検出される）
+    /// 自己参照FK付きテーブルの作成（自己参照は作成順序に影響しないため循環参照にならない）
     #[test]
-    fn test_self_referencing_fk_table_creation_detects_circular_reference() {
+    fn test_self_referencing_fk_table_creation() {
         let temp_dir = TempDir::new().unwrap();
         let old_path = temp_dir.path().join("old.yaml");
         let new_path = temp_dir.path().join("new.yaml");
@@ -666,19 +665,16 @@ tables:
 
         let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
             .with_schemas(&old_schema, &new_schema);
-
-        // 自己参照FKは循環参照としてエラーになる
-        let result = pipeline.generate_up();
-        assert!(
-            result.is_err(),
-            "Self-referencing FK should cause circular reference error"
-        );
-        let err_msg = result.unwrap_err().to_string();
-        assert!(
-            err_msg.contains("Circular reference"),
-            "Error should mention circular reference: {}",
-            err_msg
-        );
+        let (sql, _) = pipeline.generate_up().unwrap();
+        let create = sql.find(r#"CREATE TABLE "categories""#).unwrap();
+        let add_fk = sql.find(r#"REFERENCES "categories""#).unwrap();
+        assert!(create < add_fk, "{}", sql);
+
+        // SQLiteではCREATE TABLE内に外部キーを定義する
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::SQLite).with_schemas(&old_schema, &new_schema);
+        let (sql, _) = pipeline.generate_up().unwrap();
+        assert!(sql.contains(r#"REFERENCES "categories""#), "{}", sql);
     }
 
     /// 自己参照FK付きカラムの型変更（parent_id と id を同時に変更）
@@ -398,6 +398,83 @@ tables:
             );
         }
     }
+
+    /// ENUMのリネーム: カラムの型変更を生成せず、型名だけを変更して戻す
+    #[test]
+    fn test_enum_rename_postgres() {
+        let old_yaml = r#"
+version: "1.0"
+enums:
+  status:
+    name: status
+    values:
+      - active
+      - inactive
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: status
+        type:
+          kind: ENUM
+          name: status
+        nullable: false
+    primary_key:
+      - id
+"#;
+
+        let new_yaml = r#"
+version: "1.0"
+enums:
+  user_status:
+    name: user_status
+    renamed_from: status
+    values:
+      - active
+      - inactive
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: status
+        type:
+          kind: ENUM
+          name: user_status
+        nullable: false
+    primary_key:
+      - id
+"#;
+
+        let (up_sql, down_sql) =
+            common::generate_migration_sql(old_yaml, new_yaml, Dialect::PostgreSQL);
+
+        assert!(
+            up_sql.contains(r#"ALTER TYPE "status" RENAME TO "user_status";"#),
+            "Expected ALTER TYPE RENAME: {}",
+            up_sql
+        );
+        assert!(
+            !up_sql.contains("ALTER COLUMN"),
+            "Unexpected column change: {}",
+            up_sql
+        );
+        assert!(
+            !up_sql.contains("DROP TYPE"),
+            "Unexpected DROP TYPE: {}",
+            up_sql
+        );
+        assert!(
+            down_sql.contains(r#"ALTER TYPE "user_status" RENAME TO "status";"#),
+            "Expected ALTER TYPE RENAME in down SQL: {}",
+            down_sql
+        );
+    }
 }
 
 #[cfg(test)]
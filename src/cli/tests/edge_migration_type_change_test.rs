@@ -109,6 +109,7 @@ mod type_change_migration_tests {
                 "created_at".to_string(),
                 ColumnType::TIMESTAMP {
                     with_time_zone: None,
+                    precision: None,
                 },
                 false,
             );
@@ -959,6 +960,7 @@ mod type_change_migration_tests {
                 "created_at".to_string(),
                 ColumnType::TIMESTAMP {
                     with_time_zone: None,
+                    precision: None,
                 },
                 false,
             );
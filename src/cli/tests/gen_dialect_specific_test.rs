@@ -1,5 +1,6 @@
 // DialectSpecific型のSQL生成テスト
 
+use std::collections::BTreeMap;
 use strata::adapters::sql_generator::mysql::MysqlSqlGenerator;
 use strata::adapters::sql_generator::postgres::PostgresSqlGenerator;
 use strata::adapters::sql_generator::sqlite::SqliteSqlGenerator;
@@ -26,6 +27,14 @@ mod postgres_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -59,6 +68,14 @@ mod postgres_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -87,6 +104,14 @@ mod postgres_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -111,6 +136,14 @@ mod postgres_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -139,6 +172,14 @@ mod mysql_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -178,6 +219,14 @@ mod mysql_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -206,6 +255,14 @@ mod mysql_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -242,6 +299,14 @@ mod sqlite_dialect_specific_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -277,6 +342,14 @@ mod mixed_types_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         // 共通型（VARCHAR）
@@ -287,6 +360,14 @@ mod mixed_types_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         // 共通型（DECIMAL）
@@ -300,6 +381,14 @@ mod mixed_types_tests {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         });
 
         let sql = generator.generate_create_table(&table);
@@ -0,0 +1,291 @@
+// 危険な文字を含む識別子のSQL生成テスト
+//
+// クォート文字・セミコロン・コメント記号などを含む識別子は、デフォルトではスキーマ検証で
+// 拒否されます。`identifiers.allowed_characters` で許可した場合は
+// クォート → CREATE TABLE / CREATE INDEX 生成 → （SQLiteは）インメモリDBで実行
+// の順に通し、識別子が1つの識別子のまま往復することを確認します。
+// 制御文字を含む識別子は設定にかかわらず拒否されることも確認します。
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::AnyPool;
+use strata::core::config::{Dialect, IdentifierConfig};
+use strata::core::schema::{Column, ColumnType, Constraint, Index, Schema, Table};
+use strata::services::migration_pipeline::MigrationPipeline;
+use strata::services::schema_diff_detector::SchemaDiffDetectorService;
+use strata::services::schema_validator::SchemaValidatorService;
+
+/// クォートで安全に扱えるべき識別子
+const HOSTILE_NAMES: &[&str] = &[
+    r#"user"s"#,
+    r#"""#,
+    r#""; DROP TABLE victims; --"#,
+    "a`b",
+    "`; DROP TABLE victims; --",
+    "it's",
+    "x' OR '1'='1",
+    "semi;colon",
+    "-- line comment",
+    "/* block */ comment",
+    "$$dollar$$",
+    "$tag$",
+    r"back\slash",
+    "[brackets]",
+    "spaces and CAPS",
+    "ユーザー",
+];
+
+/// HOSTILE_NAMES を検証で通すために許可する文字
+const HOSTILE_CHARACTERS: &str = " \"`';-/*$\\[]=";
+
+/// 制御文字を含むため拒否されるべき識別子
+const CONTROL_CHARACTER_NAMES: &[&str] = &[
+    "nul\0byte",
+    "new\nline",
+    "users\n); DROP TABLE victims; --",
+    "carriage\rreturn",
+    "tab\tname",
+    "escape\u{1b}[31m",
+    "delete\u{7f}",
+];
+
+/// 識別子をテーブル名・カラム名・インデックス名・外部キー参照に使ったスキーマを作成
+fn schema_with_identifier(name: &str) -> Schema {
+    let mut schema = Schema::new("1.0".to_string());
+
+    let mut parent = Table::new(name.to_string());
+    parent.add_column(Column::new(
+        name.to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    parent.add_constraint(Constraint::PRIMARY_KEY {
+        columns: vec![name.to_string()],
+    });
+    schema.add_table(parent);
+
+    let child_name = format!("{} child", name);
+    let child_column = format!("{} id", name);
+    let mut child = Table::new(child_name.clone());
+    child.add_column(Column::new(
+        "id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    child.add_column(Column::new(
+        child_column.clone(),
+        ColumnType::INTEGER { precision: None },
+        true,
+    ));
+    child.add_constraint(Constraint::PRIMARY_KEY {
+        columns: vec!["id".to_string()],
+    });
+    child.add_constraint(Constraint::FOREIGN_KEY {
+        columns: vec![child_column.clone()],
+        referenced_table: name.to_string(),
+        referenced_columns: vec![name.to_string()],
+        on_delete: None,
+        on_update: None,
+    });
+    child.add_index(Index::new(
+        format!("idx {}", name),
+        vec![child_column],
+        false,
+    ));
+    schema.add_table(child);
+
+    schema
+}
+
+/// 空のスキーマからのup.sqlを生成
+fn generate_up_sql(schema: &Schema, dialect: Dialect) -> String {
+    let empty = Schema::new("1.0".to_string());
+    let (diff, _) = SchemaDiffDetectorService::new().detect_diff_with_warnings(&empty, schema);
+    let (sql, _) = MigrationPipeline::new(&diff, dialect)
+        .with_schemas(&empty, schema)
+        .generate_up()
+        .unwrap();
+    sql
+}
+
+/// クォートされた識別子を取り除いたSQLを返す
+///
+/// クォート文字の二重化を考慮して識別子の終端を判定する。
+fn strip_quoted_identifiers(sql: &str, quote: char) -> String {
+    let mut output = String::new();
+    let mut chars = sql.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        if c == quote {
+            if quoted && chars.peek() == Some(&quote) {
+                chars.next();
+                continue;
+            }
+            quoted = !quoted;
+            output.push_str("<id>");
+            continue;
+        }
+        if !quoted {
+            output.push(c);
+        }
+    }
+    assert!(!quoted, "unterminated identifier in:\n{}", sql);
+    output
+}
+
+async fn memory_database() -> AnyPool {
+    install_default_drivers();
+    // インメモリDBは接続ごとに独立するため、接続数を1に固定する
+    AnyPoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+fn permissive_validator() -> SchemaValidatorService {
+    SchemaValidatorService::new().with_identifiers(IdentifierConfig {
+        allowed_characters: HOSTILE_CHARACTERS.to_string(),
+    })
+}
+
+#[test]
+fn test_hostile_identifiers_are_rejected_by_default() {
+    let validator = SchemaValidatorService::new();
+    // 英数字以外の文字を含む名前をテーブル名にだけ使う（Unicodeの文字は許可される）
+    for name in HOSTILE_NAMES.iter().filter(|name| **name != "ユーザー") {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new(name.to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(table);
+
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let result = validator.validate_with_dialect(&schema, dialect);
+            assert!(!result.is_valid(), "{:?} ({}) was accepted", name, dialect);
+            assert!(
+                result
+                    .errors
+                    .iter()
+                    .any(|e| e.is_syntax() && e.to_string().contains("not allowed in identifiers")),
+                "{:?} ({}): {:?}",
+                name,
+                dialect,
+                result.errors
+            );
+        }
+    }
+}
+
+#[test]
+fn test_hostile_identifiers_pass_validation_when_allowed() {
+    let validator = permissive_validator();
+    for name in HOSTILE_NAMES {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let result = validator.validate_with_dialect(&schema_with_identifier(name), dialect);
+            assert!(result.is_valid(), "{:?} ({}): {:?}", name, dialect, result);
+        }
+    }
+}
+
+#[test]
+fn test_control_character_identifiers_are_rejected() {
+    let validator = permissive_validator();
+    for name in CONTROL_CHARACTER_NAMES {
+        let result = validator.validate(&schema_with_identifier(name));
+        assert!(!result.is_valid(), "{:?} was accepted", name);
+        assert!(
+            result
+                .errors
+                .iter()
+                .all(|e| e.is_syntax() && e.to_string().contains("control character")),
+            "{:?}: {:?}",
+            name,
+            result.errors
+        );
+    }
+}
+
+#[test]
+fn test_hostile_identifiers_do_not_escape_quotes_in_generated_sql() {
+    for (dialect, quote) in [
+        (Dialect::PostgreSQL, '"'),
+        (Dialect::MySQL, '`'),
+        (Dialect::SQLite, '"'),
+    ] {
+        // 識別子を取り除くと、通常の名前で生成したSQLと同じ構造だけが残る
+        let expected = strip_quoted_identifiers(
+            &generate_up_sql(&schema_with_identifier("plain"), dialect),
+            quote,
+        );
+
+        for name in HOSTILE_NAMES {
+            let sql = generate_up_sql(&schema_with_identifier(name), dialect);
+            assert_eq!(
+                strip_quoted_identifiers(&sql, quote),
+                expected,
+                "{:?} ({}):\n{}",
+                name,
+                dialect,
+                sql
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_hostile_identifiers_round_trip_on_sqlite() {
+    let pool = memory_database().await;
+
+    for name in HOSTILE_NAMES {
+        let sql = generate_up_sql(&schema_with_identifier(name), Dialect::SQLite);
+        sqlx::raw_sql(&sql)
+            .execute(&pool)
+            .await
+            .unwrap_or_else(|e| panic!("{:?}: {}\n{}", name, e, sql));
+
+        // テーブル・カラム・インデックスが指定した名前のまま作成されている
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name IN (?, ?)",
+        )
+        .bind(*name)
+        .bind(format!("{} child", name))
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(tables.len(), 2, "{:?}: {:?}", name, tables);
+
+        let columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(format!("{} child", name))
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(columns, vec!["id".to_string(), format!("{} id", name)]);
+
+        let indexes: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'index' AND name = ?")
+                .bind(format!("idx {}", name))
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(indexes.len(), 1, "{:?}", name);
+
+        let foreign_keys: Vec<String> =
+            sqlx::query_scalar("SELECT \"table\" FROM pragma_foreign_key_list(?)")
+                .bind(format!("{} child", name))
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(foreign_keys, vec![name.to_string()]);
+    }
+
+    // 攻撃的な名前に埋め込んだDROP文などは実行されていない
+    let table_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(table_count as usize, HOSTILE_NAMES.len() * 2);
+}
@@ -5,7 +5,9 @@
 mod mysql_sql_generator_tests {
     use strata::adapters::sql_generator::mysql::MysqlSqlGenerator;
     use strata::adapters::sql_generator::SqlGenerator;
-    use strata::core::schema::{Column, ColumnType, Constraint, Index, Table};
+    use strata::core::schema::{
+        Column, ColumnType, Constraint, Index, IndexColumn, NullsOrder, SortOrder, Table,
+    };
 
     /// ジェネレーターの作成テスト
     #[test]
@@ -243,6 +245,29 @@ mod mysql_sql_generator_tests {
         assert!(sql.contains("(`user_id`, `created_at`)"));
     }
 
+    /// ソート順付きインデックスの生成テスト（NULL順序は出力されない）
+    #[test]
+    fn test_generate_create_index_with_sort_order() {
+        let generator = MysqlSqlGenerator::new();
+
+        let table = Table::new("posts".to_string());
+        let index = Index::with_columns(
+            "idx_user_created".to_string(),
+            vec![
+                IndexColumn::new("user_id"),
+                IndexColumn::new("created_at")
+                    .with_order(SortOrder::Desc)
+                    .with_nulls(NullsOrder::Last),
+            ],
+            false,
+        );
+
+        let sql = generator.generate_create_index(&table, &index);
+
+        assert!(sql.contains("(`user_id`, `created_at` DESC)"), "{}", sql);
+        assert!(!sql.contains("NULLS"));
+    }
+
     /// 様々なMySQL型のマッピングテスト
     #[test]
     fn test_column_type_mapping() {
@@ -286,6 +311,7 @@ mod mysql_sql_generator_tests {
             "col_timestamp".to_string(),
             ColumnType::TIMESTAMP {
                 with_time_zone: Some(false),
+                precision: None,
             },
             false,
         ));
@@ -493,6 +519,7 @@ mod mysql_sql_generator_tests {
             "start_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: None,
+                precision: None,
             },
             false,
         ));
@@ -5,7 +5,9 @@
 mod postgres_sql_generator_tests {
     use strata::adapters::sql_generator::postgres::PostgresSqlGenerator;
     use strata::adapters::sql_generator::SqlGenerator;
-    use strata::core::schema::{Column, ColumnType, Constraint, Index, Table};
+    use strata::core::schema::{
+        Column, ColumnType, Constraint, Index, IndexColumn, NullsOrder, SortOrder, Table,
+    };
 
     /// ジェネレーターの作成テスト
     #[test]
@@ -223,6 +225,27 @@ mod postgres_sql_generator_tests {
         assert!(sql.contains(r#"("username")"#));
     }
 
+    /// 部分インデックス（WHERE句付き）の生成テスト
+    #[test]
+    fn test_generate_create_partial_index() {
+        let generator = PostgresSqlGenerator::new();
+
+        let table = Table::new("orders".to_string());
+        let index = Index::new(
+            "idx_orders_customer_id".to_string(),
+            vec!["customer_id".to_string()],
+            true,
+        )
+        .with_where("deleted_at IS NULL");
+
+        let sql = generator.generate_create_index(&table, &index);
+
+        assert_eq!(
+            sql,
+            r#"CREATE UNIQUE INDEX "idx_orders_customer_id" ON "orders" ("customer_id") WHERE deleted_at IS NULL"#
+        );
+    }
+
     /// 複合インデックスの生成テスト
     #[test]
     fn test_generate_create_composite_index() {
@@ -242,6 +265,32 @@ mod postgres_sql_generator_tests {
         assert!(sql.contains(r#"("user_id", "created_at")"#));
     }
 
+    /// ソート順・NULL順序付きインデックスの生成テスト
+    #[test]
+    fn test_generate_create_index_with_sort_order() {
+        let generator = PostgresSqlGenerator::new();
+
+        let table = Table::new("posts".to_string());
+        let index = Index::with_columns(
+            "idx_user_created".to_string(),
+            vec![
+                IndexColumn::new("user_id"),
+                IndexColumn::new("created_at")
+                    .with_order(SortOrder::Desc)
+                    .with_nulls(NullsOrder::Last),
+            ],
+            false,
+        );
+
+        let sql = generator.generate_create_index(&table, &index);
+
+        assert!(
+            sql.contains(r#"("user_id", "created_at" DESC NULLS LAST)"#),
+            "{}",
+            sql
+        );
+    }
+
     /// 様々なPostgreSQL型のマッピングテスト
     #[test]
     fn test_column_type_mapping() {
@@ -285,6 +334,7 @@ mod postgres_sql_generator_tests {
             "col_timestamp".to_string(),
             ColumnType::TIMESTAMP {
                 with_time_zone: Some(true),
+                precision: None,
             },
             false,
         ));
@@ -475,6 +525,7 @@ mod postgres_sql_generator_tests {
             "start_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: None,
+                precision: None,
             },
             false,
         ));
@@ -484,6 +535,7 @@ mod postgres_sql_generator_tests {
             "end_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: Some(true),
+                precision: None,
             },
             false,
         ));
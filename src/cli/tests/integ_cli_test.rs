@@ -15,7 +15,7 @@
 #[allow(dead_code)]
 mod cli_integration_tests {
     use sqlx::any::install_default_drivers;
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -92,6 +92,25 @@ mod cli_integration_tests {
                 schema_dir: PathBuf::from("schema"),
                 migrations_dir: PathBuf::from("migrations"),
                 environments,
+                sqlite: Default::default(),
+                mysql: Default::default(),
+                postgres: Default::default(),
+                policy: Default::default(),
+                sql_output: Default::default(),
+                store_applied_sql: false,
+                future_version_tolerance_minutes: None,
+                required_metadata: Vec::new(),
+                require_clean_pending: false,
+                enum_order_sensitive: true,
+                protected_environments: Vec::new(),
+                stream_sql_threshold_bytes: None,
+                naming: Default::default(),
+                identifiers: Default::default(),
+                identifier_case: Default::default(),
+                update_check: false,
+                view_rename_propagation: Default::default(),
+                contract_min_age_days: None,
+                statement_retry: Default::default(),
             };
 
             let config_yaml = ConfigSerializer::to_yaml(&config).unwrap();
@@ -122,6 +141,25 @@ mod cli_integration_tests {
                 schema_dir: PathBuf::from("schema"),
                 migrations_dir: PathBuf::from("migrations"),
                 environments,
+                sqlite: Default::default(),
+                mysql: Default::default(),
+                postgres: Default::default(),
+                policy: Default::default(),
+                sql_output: Default::default(),
+                store_applied_sql: false,
+                future_version_tolerance_minutes: None,
+                required_metadata: Vec::new(),
+                require_clean_pending: false,
+                enum_order_sensitive: true,
+                protected_environments: Vec::new(),
+                stream_sql_threshold_bytes: None,
+                naming: Default::default(),
+                identifiers: Default::default(),
+                identifier_case: Default::default(),
+                update_check: false,
+                view_rename_propagation: Default::default(),
+                contract_min_age_days: None,
+                statement_retry: Default::default(),
             };
 
             let config_yaml = ConfigSerializer::to_yaml(&config).unwrap();
@@ -151,6 +189,25 @@ mod cli_integration_tests {
                 schema_dir: PathBuf::from("schema"),
                 migrations_dir: PathBuf::from("migrations"),
                 environments,
+                sqlite: Default::default(),
+                mysql: Default::default(),
+                postgres: Default::default(),
+                policy: Default::default(),
+                sql_output: Default::default(),
+                store_applied_sql: false,
+                future_version_tolerance_minutes: None,
+                required_metadata: Vec::new(),
+                require_clean_pending: false,
+                enum_order_sensitive: true,
+                protected_environments: Vec::new(),
+                stream_sql_threshold_bytes: None,
+                naming: Default::default(),
+                identifiers: Default::default(),
+                identifier_case: Default::default(),
+                update_check: false,
+                view_rename_propagation: Default::default(),
+                contract_min_age_days: None,
+                statement_retry: Default::default(),
             };
 
             let config_yaml = ConfigSerializer::to_yaml(&config).unwrap();
@@ -338,11 +395,21 @@ tables:
                 project_path: self.project_path.clone(),
                 config_path: None,
                 schema_dir: None,
+                allow_duplicate_override: false,
                 description: Some(description.to_string()),
                 dry_run: false,
                 allow_destructive,
+                override_policy: false,
                 verbose: false,
                 format: strata::cli::OutputFormat::Text,
+                metadata: BTreeMap::new(),
+                summary_only: false,
+                require_clean_pending: false,
+                allow_stacked: false,
+                dry_run_sql: Default::default(),
+                down: false,
+                with_preflight_queries: false,
+                edit: false,
             };
 
             handler.execute(&command).map_err(|e| e.to_string())
@@ -366,8 +433,21 @@ tables:
                 dry_run: false,
                 env: "development".to_string(),
                 timeout: None,
+                lock_timeout: None,
+                statement_timeout: None,
                 allow_destructive,
+                yes: false,
+                break_stale_lock: false,
+                skip_checksum_verification: false,
                 format: strata::cli::OutputFormat::Text,
+                dry_run_sql: Default::default(),
+                restore_sequence_values: None,
+                target: None,
+                wait_timeout: None,
+                preflight: None,
+                single_transaction: false,
+                only: None,
+                force_out_of_order: false,
             };
 
             handler.execute(&command).await.map_err(|e| e.to_string())
@@ -382,8 +462,21 @@ tables:
                 dry_run: true,
                 env: "development".to_string(),
                 timeout: None,
+                lock_timeout: None,
+                statement_timeout: None,
                 allow_destructive: false,
+                yes: false,
+                break_stale_lock: false,
+                skip_checksum_verification: false,
                 format: strata::cli::OutputFormat::Text,
+                dry_run_sql: Default::default(),
+                restore_sequence_values: None,
+                target: None,
+                wait_timeout: None,
+                preflight: None,
+                single_transaction: false,
+                only: None,
+                force_out_of_order: false,
             };
 
             handler.execute(&command).await.map_err(|e| e.to_string())
@@ -396,10 +489,14 @@ tables:
                 project_path: self.project_path.clone(),
                 config_path: None,
                 steps: Some(steps),
+                target: None,
                 env: "development".to_string(),
                 dry_run: false,
                 allow_destructive: true, // down.sql may contain DROP TABLE
+                break_stale_lock: false,
+                allow_empty_down: false,
                 format: strata::cli::OutputFormat::Text,
+                wait_timeout: None,
             };
 
             handler.execute(&command).await.map_err(|e| e.to_string())
@@ -413,6 +510,9 @@ tables:
                 config_path: None,
                 env: "development".to_string(),
                 format: strata::cli::OutputFormat::Text,
+                gates: vec![],
+                verbose: false,
+                wait_timeout: None,
             };
 
             handler.execute(&command).await.map_err(|e| e.to_string())
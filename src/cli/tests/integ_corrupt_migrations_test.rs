@@ -0,0 +1,265 @@
+// 壊れたマイグレーションディレクトリの統合テスト
+//
+// ファイルが欠けたマイグレーション（incomplete）と命名規則に合わないディレクトリ（foreign）に対する
+// status / apply の挙動と、generate のアトミックな書き込み、壊れたスキーマスナップショットの
+// 読み込みを検証するテストスイート
+
+use sqlx::any::install_default_drivers;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+use strata::cli::commands::status::{StatusCommand, StatusCommandHandler};
+use strata::cli::OutputFormat;
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+mod common;
+
+/// SQLite プロジェクトを作成し、users テーブルのマイグレーションを generate する
+fn setup_project() -> (TempDir, PathBuf, PathBuf) {
+    install_default_drivers();
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(&db_path.to_string_lossy()),
+    );
+    common::write_schema_file(&project_path, "users");
+    generate(&project_path, "create users").unwrap();
+
+    let migrations_dir = project_path.join("migrations");
+    let migration_dir = migration_dirs(&migrations_dir).pop().unwrap();
+    (temp_dir, project_path, migration_dir)
+}
+
+fn generate(project_path: &Path, description: &str) -> anyhow::Result<String> {
+    GenerateCommandHandler::new().execute(&GenerateCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        schema_dir: None,
+        allow_duplicate_override: false,
+        description: Some(description.to_string()),
+        dry_run: false,
+        allow_destructive: false,
+        override_policy: false,
+        verbose: false,
+        format: OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
+    })
+}
+
+/// `.` で始まるものを除いたマイグレーションディレクトリの一覧
+fn migration_dirs(migrations_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(migrations_dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_dir())
+        .filter(|p| !p.file_name().unwrap().to_string_lossy().starts_with('.'))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+async fn status_json(project_path: &Path) -> serde_json::Value {
+    let output = StatusCommandHandler::new()
+        .execute(&StatusCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            env: "development".to_string(),
+            format: OutputFormat::Json,
+            gates: vec![],
+            verbose: false,
+            wait_timeout: None,
+        })
+        .await
+        .unwrap();
+    serde_json::from_str(&output).unwrap()
+}
+
+async fn apply(project_path: &Path) -> anyhow::Result<String> {
+    ApplyCommandHandler::new()
+        .execute(&ApplyCommand {
+            project_path: project_path.to_path_buf(),
+            config_path: None,
+            dry_run: false,
+            env: "development".to_string(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            allow_destructive: false,
+            yes: false,
+            break_stale_lock: false,
+            skip_checksum_verification: false,
+            format: OutputFormat::Text,
+            dry_run_sql: Default::default(),
+            restore_sequence_values: None,
+            target: None,
+            wait_timeout: None,
+            preflight: None,
+            single_transaction: false,
+            only: None,
+            force_out_of_order: false,
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_generate_leaves_only_complete_migration() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+
+    for file in ["up.sql", "down.sql", ".meta.yaml", ".schema_snapshot.yaml"] {
+        assert!(migration_dir.join(file).is_file(), "{} is missing", file);
+    }
+    // 一時ディレクトリは残らない
+    let leftovers: Vec<String> = fs::read_dir(project_path.join("migrations"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".tmp"))
+        .collect();
+    assert!(
+        leftovers.is_empty(),
+        "leftover staging dirs: {:?}",
+        leftovers
+    );
+
+    let json = status_json(&project_path).await;
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_missing_meta_is_status_warning_and_apply_error() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+    fs::remove_file(migration_dir.join(".meta.yaml")).unwrap();
+
+    let json = status_json(&project_path).await;
+    let warnings = json["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    let warning = warnings[0].as_str().unwrap();
+    assert!(
+        warning.contains("_create_users is incomplete"),
+        "{}",
+        warning
+    );
+    assert!(warning.contains("missing .meta.yaml"), "{}", warning);
+
+    let err = apply(&project_path).await.unwrap_err().to_string();
+    assert!(
+        err.contains("1 migration directory is incomplete"),
+        "{}",
+        err
+    );
+    assert!(err.contains("_create_users: missing .meta.yaml"), "{}", err);
+    assert!(err.contains("run `strata generate` again"), "{}", err);
+    // 何も適用されていない
+    assert_eq!(
+        status_json(&project_path).await["summary"]["applied"],
+        serde_json::json!(0)
+    );
+}
+
+#[tokio::test]
+async fn test_missing_up_sql_is_status_warning_and_apply_error() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+    fs::remove_file(migration_dir.join("up.sql")).unwrap();
+
+    let json = status_json(&project_path).await;
+    let warning = json["warnings"][0].as_str().unwrap();
+    assert!(warning.contains("missing up.sql"), "{}", warning);
+
+    let err = apply(&project_path).await.unwrap_err().to_string();
+    assert!(err.contains("_create_users: missing up.sql"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_empty_migration_dir_reports_all_missing_files() {
+    let (_temp_dir, project_path, _) = setup_project();
+    fs::create_dir(project_path.join("migrations/20990101000000_half_written")).unwrap();
+
+    let err = apply(&project_path).await.unwrap_err().to_string();
+    assert!(
+        err.contains("20990101000000_half_written: missing up.sql, .meta.yaml"),
+        "{}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_foreign_and_staging_dirs_are_ignored() {
+    let (_temp_dir, project_path, _) = setup_project();
+    let migrations_dir = project_path.join("migrations");
+    fs::create_dir(migrations_dir.join("backup")).unwrap();
+    fs::create_dir(migrations_dir.join("v2_notes")).unwrap();
+    // 中断された generate の一時ディレクトリ
+    let staging = migrations_dir.join(".20990101000000_interrupted.tmp");
+    fs::create_dir(&staging).unwrap();
+    fs::write(
+        staging.join("up.sql"),
+        "CREATE TABLE never_applied (id INTEGER);",
+    )
+    .unwrap();
+
+    let json = status_json(&project_path).await;
+    assert_eq!(json["warnings"].as_array().unwrap().len(), 0);
+    assert_eq!(json["summary"]["total"], serde_json::json!(1));
+
+    apply(&project_path).await.unwrap();
+    let json = status_json(&project_path).await;
+    assert_eq!(json["summary"]["applied"], serde_json::json!(1));
+}
+
+#[test]
+fn test_generate_writes_versioned_snapshot() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+
+    for path in [
+        migration_dir.join(".schema_snapshot.yaml"),
+        project_path.join("migrations/.schema_snapshot.yaml"),
+    ] {
+        let snapshot = fs::read_to_string(&path).unwrap();
+        assert!(snapshot.starts_with("snapshot_format: 1\n"), "{}", snapshot);
+    }
+    assert!(!project_path
+        .join("migrations/.schema_snapshot.yaml.tmp")
+        .exists());
+}
+
+#[test]
+fn test_generate_with_truncated_snapshot_suggests_recovery() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+    let snapshot_path = migration_dir.join(".schema_snapshot.yaml");
+    let snapshot = fs::read_to_string(&snapshot_path).unwrap();
+    // 書き込みの途中で中断されたスナップショット
+    fs::write(&snapshot_path, &snapshot[..snapshot.len() / 2]).unwrap();
+    common::write_schema_file(&project_path, "posts");
+
+    let err = format!("{:#}", generate(&project_path, "add posts").unwrap_err());
+    assert!(err.contains("Failed to parse schema snapshot"), "{}", err);
+    assert!(err.contains("strata export"), "{}", err);
+    assert!(err.contains("strata baseline --force"), "{}", err);
+}
+
+#[test]
+fn test_generate_with_newer_snapshot_format_asks_to_upgrade() {
+    let (_temp_dir, project_path, migration_dir) = setup_project();
+    let snapshot_path = migration_dir.join(".schema_snapshot.yaml");
+    let snapshot = fs::read_to_string(&snapshot_path).unwrap();
+    fs::write(
+        &snapshot_path,
+        snapshot.replace("snapshot_format: 1", "snapshot_format: 99"),
+    )
+    .unwrap();
+
+    let err = format!("{:#}", generate(&project_path, "again").unwrap_err());
+    assert!(err.contains("written by a newer strata"), "{}", err);
+    assert!(err.contains("Please upgrade strata"), "{}", err);
+}
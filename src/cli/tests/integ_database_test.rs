@@ -1,3 +1,5 @@
+#![cfg(all(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
 /// データベース統合テスト
 ///
 /// testcontainersを使用して実際のデータベースに対するエンドツーエンドテストを実施します。
@@ -8,6 +10,7 @@
 /// - トランザクション制御
 ///
 /// 注意: このテストはDockerが必要です。Docker未起動の場合はスキップされます。
+/// 複数の方言のドライバーを使うため、`postgres` / `mysql` / `sqlite` フィーチャーがすべて有効なビルドでのみ対象になります。
 #[cfg(test)]
 mod database_integration_tests {
     use sqlx::postgres::PgPoolOptions;
@@ -1,9 +1,14 @@
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use sqlx::any::install_default_drivers;
 use sqlx::Executor;
 use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::confirm::ScriptedConfirm;
 use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+use strata::cli::commands::CompletedWithFailure;
 use strata::core::config::Dialect;
 use tempfile::TempDir;
 
@@ -23,11 +28,21 @@ fn test_generate_rejects_destructive_without_allow() {
         project_path: project_path.to_path_buf(),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         description: Some("drop_users".to_string()),
         dry_run: false,
         allow_destructive: false,
+        override_policy: false,
         verbose: false,
         format: strata::cli::OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
     };
 
     let result = handler.execute(&command);
@@ -51,11 +66,21 @@ fn test_generate_allows_destructive_with_flag_and_writes_metadata() {
         project_path: project_path.to_path_buf(),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         description: Some("drop_users".to_string()),
         dry_run: false,
         allow_destructive: true,
+        override_policy: false,
         verbose: false,
         format: strata::cli::OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
     };
 
     let output = handler.execute(&command).expect("generate should succeed");
@@ -112,8 +137,21 @@ destructive_changes:
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -161,8 +199,21 @@ destructive_changes:
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: true,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -206,8 +257,21 @@ checksum: "test_checksum"
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: false,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = handler.execute(&command).await;
@@ -237,11 +301,21 @@ async fn test_e2e_destructive_generate_apply_flow() {
         project_path: project_path.clone(),
         config_path: None,
         schema_dir: None,
+        allow_duplicate_override: false,
         description: Some("drop_users".to_string()),
         dry_run: false,
         allow_destructive: true,
+        override_policy: false,
         verbose: false,
         format: strata::cli::OutputFormat::Text,
+        metadata: BTreeMap::new(),
+        summary_only: false,
+        require_clean_pending: false,
+        allow_stacked: false,
+        dry_run_sql: Default::default(),
+        down: false,
+        with_preflight_queries: false,
+        edit: false,
     };
 
     let output = handler.execute(&command).expect("generate should succeed");
@@ -264,8 +338,21 @@ async fn test_e2e_destructive_generate_apply_flow() {
         dry_run: false,
         env: "development".to_string(),
         timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
         allow_destructive: true,
+        yes: false,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
         format: strata::cli::OutputFormat::Text,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
     };
 
     let result = apply_handler.execute(&apply_command).await;
@@ -277,3 +364,152 @@ async fn test_e2e_destructive_generate_apply_flow() {
         .join(".meta.yaml");
     assert!(meta_path.exists());
 }
+
+/// テーブルを削除する（破壊的変更を含む）マイグレーションを用意
+fn setup_destructive_apply_project(temp_dir: &TempDir) -> PathBuf {
+    install_default_drivers();
+    let project_path = temp_dir.path().to_path_buf();
+    let db_path = project_path.join("test.db");
+    fs::File::create(&db_path).unwrap();
+
+    common::write_config(
+        &project_path,
+        Dialect::SQLite,
+        Some(db_path.to_str().unwrap()),
+    );
+    fs::create_dir_all(project_path.join("migrations")).unwrap();
+
+    let meta = r#"version: "20260121120000"
+description: "drop_legacy"
+dialect: sqlite
+checksum: "test_checksum"
+destructive_changes:
+  tables_dropped:
+    - "legacy"
+"#;
+    common::create_migration_with_meta(
+        &project_path,
+        "20260121120000",
+        "drop_legacy",
+        "CREATE TABLE legacy (id INTEGER);\nDROP TABLE legacy;",
+        meta,
+    );
+    project_path
+}
+
+fn destructive_apply_command(
+    project_path: &Path,
+    allow_destructive: bool,
+    yes: bool,
+    format: strata::cli::OutputFormat,
+) -> ApplyCommand {
+    ApplyCommand {
+        project_path: project_path.to_path_buf(),
+        config_path: None,
+        dry_run: false,
+        env: "development".to_string(),
+        timeout: None,
+        lock_timeout: None,
+        statement_timeout: None,
+        allow_destructive,
+        yes,
+        break_stale_lock: false,
+        skip_checksum_verification: false,
+        format,
+        dry_run_sql: Default::default(),
+        restore_sequence_values: None,
+        target: None,
+        wait_timeout: None,
+        preflight: None,
+        single_transaction: false,
+        only: None,
+        force_out_of_order: false,
+    }
+}
+
+#[tokio::test]
+async fn test_apply_destructive_confirmed_by_environment_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = setup_destructive_apply_project(&temp_dir);
+
+    let confirm = Arc::new(ScriptedConfirm::new(["development"]));
+    let handler = ApplyCommandHandler::new().with_confirm(confirm.clone());
+    let command =
+        destructive_apply_command(&project_path, false, false, strata::cli::OutputFormat::Text);
+
+    let output = handler.execute(&command).await.unwrap();
+    assert!(output.contains("Migration Apply Complete"));
+
+    // 対象がマイグレーションごとに表示される
+    let shown = confirm.shown();
+    assert_eq!(shown.len(), 1);
+    assert!(shown[0].contains("environment 'development'"));
+    assert!(shown[0].contains("20260121120000 - drop_legacy"));
+    assert!(shown[0].contains("Tables to be dropped: legacy"));
+    assert!(shown[0].contains("DROP TABLE legacy"));
+    assert!(!shown[0].contains("CREATE TABLE legacy"));
+}
+
+#[tokio::test]
+async fn test_apply_destructive_cancelled_by_wrong_environment_name() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = setup_destructive_apply_project(&temp_dir);
+
+    let confirm = Arc::new(ScriptedConfirm::new(["production"]));
+    let handler = ApplyCommandHandler::new().with_confirm(confirm.clone());
+    // --allow-destructive を指定していても、端末では確認する
+    let command =
+        destructive_apply_command(&project_path, true, false, strata::cli::OutputFormat::Text);
+
+    let err = handler.execute(&command).await.unwrap_err().to_string();
+    assert!(err.contains("Apply cancelled"), "{}", err);
+    assert!(err.contains("No migrations were applied"), "{}", err);
+    assert_eq!(confirm.shown().len(), 1);
+}
+
+#[tokio::test]
+async fn test_apply_destructive_with_yes_skips_prompt() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = setup_destructive_apply_project(&temp_dir);
+
+    let confirm = Arc::new(ScriptedConfirm::new(Vec::<String>::new()));
+    let handler = ApplyCommandHandler::new().with_confirm(confirm.clone());
+
+    // --yes のみでは従来どおり --allow-destructive が必要
+    let command =
+        destructive_apply_command(&project_path, false, true, strata::cli::OutputFormat::Text);
+    let err = handler.execute(&command).await.unwrap_err().to_string();
+    assert!(err.contains("Destructive changes detected"), "{}", err);
+
+    let command =
+        destructive_apply_command(&project_path, true, true, strata::cli::OutputFormat::Text);
+    let output = handler.execute(&command).await.unwrap();
+    assert!(output.contains("Migration Apply Complete"));
+    assert!(confirm.shown().is_empty());
+}
+
+#[tokio::test]
+async fn test_apply_destructive_json_fails_with_structured_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = setup_destructive_apply_project(&temp_dir);
+
+    let confirm = Arc::new(ScriptedConfirm::new(["development"]));
+    let handler = ApplyCommandHandler::new().with_confirm(confirm.clone());
+    let command =
+        destructive_apply_command(&project_path, false, false, strata::cli::OutputFormat::Json);
+
+    let err = handler.execute(&command).await.unwrap_err();
+    let completed = err.downcast_ref::<CompletedWithFailure>().unwrap();
+    assert!(completed.message.contains("--allow-destructive"));
+
+    let json: serde_json::Value = serde_json::from_str(&completed.output).unwrap();
+    assert_eq!(json["error"], "destructive_changes_not_allowed");
+    assert_eq!(json["environment"], "development");
+    assert_eq!(json["migrations"][0]["version"], "20260121120000");
+    assert_eq!(
+        json["migrations"][0]["changes"][0],
+        "Tables to be dropped: legacy"
+    );
+    // JSON出力では確認しない
+    assert!(confirm.shown().is_empty());
+}
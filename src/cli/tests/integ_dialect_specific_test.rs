@@ -1,3 +1,5 @@
+#![cfg(all(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+
 /// 方言固有カラム型の統合テスト
 ///
 /// このテストは実際のデータベースに対して方言固有型を使用したテーブル作成を実行し、
@@ -7,6 +9,8 @@
 /// - Docker環境が必要（testcontainersを使用）
 /// - PostgreSQL, MySQLコンテナを起動して実行
 /// - 実行時は `cargo test -- --ignored` を使用
+///
+/// 複数の方言のドライバーを使うため、`postgres` / `mysql` / `sqlite` フィーチャーがすべて有効なビルドでのみ対象になります。
 use sqlx::{Connection, MySqlConnection, PgConnection, Row};
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::mysql::Mysql;
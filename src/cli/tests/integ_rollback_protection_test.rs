@@ -0,0 +1,314 @@
+// ロールバック保護の統合テスト
+//
+// `rollback_protection: true` のテーブルについて、
+// generate → apply → rollback → generate → apply の一連の流れを SQLite で検証します。
+// ロールバック後もテーブルはデータベースに残るため、
+// 次の generate で再作成されないこと・削除すれば DROP TABLE が生成されることを確認します。
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::AnyPool;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::cli::commands::apply::{ApplyCommand, ApplyCommandHandler};
+use strata::cli::commands::generate::{GenerateCommand, GenerateCommandHandler};
+use strata::cli::commands::retained_tables::RETAINED_TABLES_FILE;
+use strata::cli::commands::rollback::{RollbackCommand, RollbackCommandHandler};
+use strata::core::config::Dialect;
+use tempfile::TempDir;
+
+mod common;
+
+const AUDIT_LOG_SCHEMA: &str = r#"version: "1.0"
+tables:
+  audit_log:
+    rollback_protection: true
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: message
+        type:
+          kind: TEXT
+        nullable: false
+    primary_key:
+      - id
+"#;
+
+struct ProtectedProject {
+    _temp_dir: TempDir,
+    project_path: PathBuf,
+    db_path: PathBuf,
+}
+
+impl ProtectedProject {
+    fn new() -> Self {
+        install_default_drivers();
+        let (temp_dir, project_path) =
+            common::setup_test_project(Dialect::SQLite, None, true).unwrap();
+        let db_path = project_path.join("test.db");
+        fs::File::create(&db_path).unwrap();
+        common::write_config(
+            &project_path,
+            Dialect::SQLite,
+            Some(&db_path.to_string_lossy()),
+        );
+
+        Self {
+            _temp_dir: temp_dir,
+            project_path,
+            db_path,
+        }
+    }
+
+    fn migrations_dir(&self) -> PathBuf {
+        self.project_path.join("migrations")
+    }
+
+    fn write_audit_log_schema(&self) {
+        fs::write(
+            self.project_path.join("schema").join("audit_log.yaml"),
+            AUDIT_LOG_SCHEMA,
+        )
+        .unwrap();
+    }
+
+    fn generate(&self, description: &str, allow_destructive: bool) -> Result<String, String> {
+        // 直前のマイグレーションとバージョン（秒単位のタイムスタンプ）が重ならないようにする
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        let command = GenerateCommand {
+            project_path: self.project_path.clone(),
+            config_path: None,
+            schema_dir: None,
+            allow_duplicate_override: false,
+            description: Some(description.to_string()),
+            dry_run: false,
+            allow_destructive,
+            override_policy: false,
+            verbose: false,
+            format: strata::cli::OutputFormat::Text,
+            metadata: BTreeMap::new(),
+            summary_only: false,
+            require_clean_pending: false,
+            allow_stacked: false,
+            dry_run_sql: Default::default(),
+            down: false,
+            with_preflight_queries: false,
+            edit: false,
+        };
+        GenerateCommandHandler::new()
+            .execute(&command)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn apply(&self, allow_destructive: bool) -> Result<String, String> {
+        let command = ApplyCommand {
+            project_path: self.project_path.clone(),
+            config_path: None,
+            dry_run: false,
+            env: "development".to_string(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            allow_destructive,
+            yes: false,
+            break_stale_lock: false,
+            skip_checksum_verification: false,
+            format: strata::cli::OutputFormat::Text,
+            dry_run_sql: Default::default(),
+            restore_sequence_values: None,
+            target: None,
+            wait_timeout: None,
+            preflight: None,
+            single_transaction: false,
+            only: None,
+            force_out_of_order: false,
+        };
+        ApplyCommandHandler::new()
+            .execute(&command)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn rollback(&self, dry_run: bool) -> Result<String, String> {
+        let command = RollbackCommand {
+            project_path: self.project_path.clone(),
+            config_path: None,
+            steps: Some(1),
+            target: None,
+            env: "development".to_string(),
+            dry_run,
+            allow_destructive: true, // down.sql may contain DROP TABLE
+            break_stale_lock: false,
+            allow_empty_down: false,
+            format: strata::cli::OutputFormat::Text,
+            wait_timeout: None,
+        };
+        RollbackCommandHandler::new()
+            .execute(&command)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// 最新のマイグレーションディレクトリ
+    fn latest_migration_dir(&self) -> PathBuf {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(self.migrations_dir())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        dirs.sort();
+        dirs.pop().expect("no migration directory")
+    }
+
+    async fn pool(&self) -> AnyPool {
+        AnyPoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite://{}", self.db_path.display()))
+            .await
+            .unwrap()
+    }
+
+    async fn table_exists(&self, table_name: &str) -> bool {
+        let pool = self.pool().await;
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+        )
+        .bind(table_name)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+        count == 1
+    }
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("{:?}: {}", path, e))
+}
+
+/// users を作成した後、保護付きの audit_log と posts を追加して適用した状態を作る
+async fn setup_applied_project() -> (ProtectedProject, PathBuf) {
+    let project = ProtectedProject::new();
+
+    common::write_schema_file(&project.project_path, "users");
+    project.generate("create_users", false).unwrap();
+
+    project.write_audit_log_schema();
+    common::write_schema_file(&project.project_path, "posts");
+    project.generate("create_audit_log", false).unwrap();
+    let protected_dir = project.latest_migration_dir();
+
+    project.apply(false).await.unwrap();
+    assert!(project.table_exists("audit_log").await);
+    assert!(project.table_exists("posts").await);
+
+    (project, protected_dir)
+}
+
+#[tokio::test]
+async fn test_protected_table_is_recorded_in_generated_migration() {
+    let (_project, protected_dir) = setup_applied_project().await;
+
+    let down_sql = read(&protected_dir.join("down.sql"));
+    assert!(
+        down_sql.contains("-- PROTECTED: table audit_log will not be dropped by rollback"),
+        "{}",
+        down_sql
+    );
+    assert!(
+        !down_sql.contains(r#"DROP TABLE "audit_log""#),
+        "{}",
+        down_sql
+    );
+    assert!(down_sql.contains(r#"DROP TABLE "posts""#), "{}", down_sql);
+
+    let meta = read(&protected_dir.join(".meta.yaml"));
+    assert!(meta.contains("rollback_protected_tables"), "{}", meta);
+    assert!(meta.contains("audit_log"), "{}", meta);
+}
+
+#[tokio::test]
+async fn test_rollback_leaves_protected_table_in_place() {
+    let (project, _protected_dir) = setup_applied_project().await;
+
+    // dry-run では保護対象を表示するだけで何も記録しない
+    let preview = project.rollback(true).await.unwrap();
+    assert!(
+        preview.contains("Protected tables (will not be dropped):"),
+        "{}",
+        preview
+    );
+    assert!(preview.contains("audit_log"), "{}", preview);
+    assert!(!project.migrations_dir().join(RETAINED_TABLES_FILE).exists());
+
+    let output = project.rollback(false).await.unwrap();
+    assert!(
+        output.contains("Protected tables (left in place):"),
+        "{}",
+        output
+    );
+    assert!(output.contains("  - audit_log (created by "), "{}", output);
+
+    assert!(project.table_exists("audit_log").await);
+    assert!(!project.table_exists("posts").await);
+    assert!(project.table_exists("users").await);
+
+    let retained = read(&project.migrations_dir().join(RETAINED_TABLES_FILE));
+    assert!(retained.contains("audit_log"), "{}", retained);
+    assert!(
+        retained.contains("rollback_protection: true"),
+        "{}",
+        retained
+    );
+    assert!(!retained.contains("posts"), "{}", retained);
+}
+
+#[tokio::test]
+async fn test_generate_after_rollback_does_not_recreate_protected_table() {
+    let (project, protected_dir) = setup_applied_project().await;
+    project.rollback(false).await.unwrap();
+
+    // ロールバックしたマイグレーションを作り直す
+    fs::remove_dir_all(&protected_dir).unwrap();
+    project.generate("recreate_posts", false).unwrap();
+
+    let regenerated_dir = project.latest_migration_dir();
+    let up_sql = read(&regenerated_dir.join("up.sql"));
+    assert!(up_sql.contains(r#"CREATE TABLE "posts""#), "{}", up_sql);
+    assert!(!up_sql.contains("audit_log"), "{}", up_sql);
+
+    // スナップショットに反映されたため記録は削除され、保護フラグはスナップショットに残る
+    assert!(!project.migrations_dir().join(RETAINED_TABLES_FILE).exists());
+    let snapshot = read(&regenerated_dir.join(".schema_snapshot.yaml"));
+    assert!(snapshot.contains("audit_log"), "{}", snapshot);
+
+    project.apply(false).await.unwrap();
+    assert!(project.table_exists("posts").await);
+    assert!(project.table_exists("audit_log").await);
+
+    // 以降は変更がなければ何も生成されない
+    let output = project.generate("no_changes", false).unwrap();
+    assert!(output.contains("No schema changes found"), "{}", output);
+}
+
+#[tokio::test]
+async fn test_removing_retained_table_after_rollback_generates_drop() {
+    let (project, protected_dir) = setup_applied_project().await;
+    project.rollback(false).await.unwrap();
+    fs::remove_dir_all(&protected_dir).unwrap();
+
+    // テーブルはデータベースに残っているため、スキーマから外すと DROP TABLE になる
+    fs::remove_file(project.project_path.join("schema").join("audit_log.yaml")).unwrap();
+    project.generate("drop_audit_log", true).unwrap();
+
+    let up_sql = read(&project.latest_migration_dir().join("up.sql"));
+    assert!(up_sql.contains(r#"DROP TABLE "audit_log""#), "{}", up_sql);
+    assert!(up_sql.contains(r#"CREATE TABLE "posts""#), "{}", up_sql);
+
+    project.apply(true).await.unwrap();
+    assert!(!project.table_exists("audit_log").await);
+    assert!(project.table_exists("posts").await);
+}
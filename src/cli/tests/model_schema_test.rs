@@ -4,7 +4,7 @@
 /// 正しく動作し、YAML形式とのシリアライズ/デシリアライズが可能であることを確認します。
 #[cfg(test)]
 mod schema_model_tests {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
     use strata::core::schema::{Column, ColumnType, Constraint, Index, Schema, Table};
 
     /// Schema構造体が正しくデシリアライズできることを確認
@@ -57,6 +57,7 @@ tables:
     fn test_table_structure() {
         let table = Table {
             name: "products".to_string(),
+            schema: None,
             columns: vec![
                 Column {
                     name: "id".to_string(),
@@ -65,6 +66,14 @@ tables:
                     default_value: None,
                     auto_increment: Some(true),
                     renamed_from: None,
+                    generated: None,
+                    metadata: BTreeMap::new(),
+                    lifecycle: None,
+                    contract_pending_since: None,
+                    on_update_current_timestamp: false,
+                    type_change_using: None,
+                    down_using: None,
+                    comment: None,
                 },
                 Column {
                     name: "name".to_string(),
@@ -73,6 +82,14 @@ tables:
                     default_value: None,
                     auto_increment: None,
                     renamed_from: None,
+                    generated: None,
+                    metadata: BTreeMap::new(),
+                    lifecycle: None,
+                    contract_pending_since: None,
+                    on_update_current_timestamp: false,
+                    type_change_using: None,
+                    down_using: None,
+                    comment: None,
                 },
             ],
             indexes: vec![],
@@ -80,6 +97,10 @@ tables:
                 columns: vec!["id".to_string()],
             }],
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            comment: None,
+            metadata: BTreeMap::new(),
         };
 
         assert_eq!(table.name, "products");
@@ -128,7 +149,7 @@ with_time_zone: true
 "#;
         let col_type: ColumnType = serde_saphyr::from_str(timestamp_yaml).unwrap();
         match col_type {
-            ColumnType::TIMESTAMP { with_time_zone } => assert_eq!(with_time_zone, Some(true)),
+            ColumnType::TIMESTAMP { with_time_zone, .. } => assert_eq!(with_time_zone, Some(true)),
             _ => panic!("Expected TIMESTAMP type"),
         }
 
@@ -206,12 +227,16 @@ check_expression: "age >= 0"
     fn test_index_structure() {
         let index = Index {
             name: "idx_user_email".to_string(),
-            columns: vec!["email".to_string()],
+            columns: vec!["email".into()],
             unique: true,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         };
 
         assert_eq!(index.name, "idx_user_email");
-        assert_eq!(index.columns, vec!["email"]);
+        assert_eq!(index.column_names(), vec!["email"]);
         assert!(index.unique);
     }
 
@@ -224,6 +249,7 @@ check_expression: "age >= 0"
             "users".to_string(),
             Table {
                 name: "users".to_string(),
+                schema: None,
                 columns: vec![Column {
                     name: "id".to_string(),
                     column_type: ColumnType::INTEGER { precision: None },
@@ -231,10 +257,22 @@ check_expression: "age >= 0"
                     default_value: None,
                     auto_increment: Some(true),
                     renamed_from: None,
+                    generated: None,
+                    metadata: BTreeMap::new(),
+                    lifecycle: None,
+                    contract_pending_since: None,
+                    on_update_current_timestamp: false,
+                    type_change_using: None,
+                    down_using: None,
+                    comment: None,
                 }],
                 indexes: vec![],
                 constraints: vec![],
                 renamed_from: None,
+                populate_from: None,
+                rollback_protection: false,
+                comment: None,
+                metadata: BTreeMap::new(),
             },
         );
 
@@ -242,6 +280,7 @@ check_expression: "age >= 0"
             "posts".to_string(),
             Table {
                 name: "posts".to_string(),
+                schema: None,
                 columns: vec![
                     Column {
                         name: "id".to_string(),
@@ -250,6 +289,14 @@ check_expression: "age >= 0"
                         default_value: None,
                         auto_increment: Some(true),
                         renamed_from: None,
+                        generated: None,
+                        metadata: BTreeMap::new(),
+                        lifecycle: None,
+                        contract_pending_since: None,
+                        on_update_current_timestamp: false,
+                        type_change_using: None,
+                        down_using: None,
+                        comment: None,
                     },
                     Column {
                         name: "user_id".to_string(),
@@ -258,6 +305,14 @@ check_expression: "age >= 0"
                         default_value: None,
                         auto_increment: None,
                         renamed_from: None,
+                        generated: None,
+                        metadata: BTreeMap::new(),
+                        lifecycle: None,
+                        contract_pending_since: None,
+                        on_update_current_timestamp: false,
+                        type_change_using: None,
+                        down_using: None,
+                        comment: None,
                     },
                 ],
                 indexes: vec![],
@@ -269,6 +324,10 @@ check_expression: "age >= 0"
                     on_update: None,
                 }],
                 renamed_from: None,
+                populate_from: None,
+                rollback_protection: false,
+                comment: None,
+                metadata: BTreeMap::new(),
             },
         );
 
@@ -278,6 +337,7 @@ check_expression: "age >= 0"
             enums: BTreeMap::new(),
             tables,
             views: BTreeMap::new(),
+            external_tables: BTreeSet::new(),
         };
 
         assert_eq!(schema.tables.len(), 2);
@@ -293,10 +353,15 @@ check_expression: "age >= 0"
             "users".to_string(),
             Table {
                 name: "users".to_string(),
+                schema: None,
                 columns: vec![],
                 indexes: vec![],
                 constraints: vec![],
                 renamed_from: None,
+                populate_from: None,
+                rollback_protection: false,
+                comment: None,
+                metadata: BTreeMap::new(),
             },
         );
 
@@ -306,6 +371,7 @@ check_expression: "age >= 0"
             enums: BTreeMap::new(),
             tables,
             views: BTreeMap::new(),
+            external_tables: BTreeSet::new(),
         };
 
         assert!(schema.has_table("users"));
@@ -408,7 +474,7 @@ with_time_zone: null
 "#;
         let col_type: ColumnType = serde_saphyr::from_str(yaml_without_tz).unwrap();
         match col_type {
-            ColumnType::TIME { with_time_zone } => assert_eq!(with_time_zone, None),
+            ColumnType::TIME { with_time_zone, .. } => assert_eq!(with_time_zone, None),
             _ => panic!("Expected TIME type"),
         }
 
@@ -418,7 +484,7 @@ with_time_zone: true
 "#;
         let col_type: ColumnType = serde_saphyr::from_str(yaml_with_tz).unwrap();
         match col_type {
-            ColumnType::TIME { with_time_zone } => assert_eq!(with_time_zone, Some(true)),
+            ColumnType::TIME { with_time_zone, .. } => assert_eq!(with_time_zone, Some(true)),
             _ => panic!("Expected TIME type"),
         }
     }
@@ -187,3 +187,357 @@ mod database_migrator_tests {
         assert!(sql.contains("schema_migrations"));
     }
 }
+
+/// 古いバージョンのstrataが作成した履歴テーブルの更新テスト（SQLite）
+mod migration_table_layout_tests {
+    use sqlx::any::{install_default_drivers, AnyPoolOptions};
+    use sqlx::{AnyPool, Row};
+    use strata::adapters::database_migrator::{
+        detect_migration_table_layout, DatabaseMigratorService, CURRENT_MIGRATION_TABLE_LAYOUT,
+    };
+    use strata::core::config::Dialect;
+    use strata::core::migration::Migration;
+    use tempfile::TempDir;
+
+    async fn sqlite_pool(temp_dir: &TempDir) -> AnyPool {
+        install_default_drivers();
+        let db_path = temp_dir.path().join("test.db");
+        AnyPoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+            .await
+            .unwrap()
+    }
+
+    async fn table_columns(pool: &AnyPool) -> Vec<String> {
+        sqlx::query("SELECT name FROM pragma_table_info('schema_migrations') ORDER BY cid")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get::<String, _>(0))
+            .collect()
+    }
+
+    /// description カラムがない旧レイアウトは、記録を残したまま現在のレイアウトに更新される
+    #[tokio::test]
+    async fn test_upgrade_layout_without_description() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, applied_at TEXT NOT NULL DEFAULT (datetime('now')), checksum TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, checksum) VALUES ('20240101120000', 'abc')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let migrator = DatabaseMigratorService::new();
+        let upgraded = migrator
+            .upgrade_migration_table(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+        assert_eq!(upgraded, Some(1));
+        assert_eq!(
+            table_columns(&pool).await,
+            vec![
+                "version",
+                "applied_at",
+                "checksum",
+                "description",
+                "up_sql",
+                "down_sql",
+                "statement_checksums",
+                "execution_time_ms",
+                "applied_by"
+            ]
+        );
+
+        // 既存の記録を読み込め、新しい記録も書き込める
+        let records = migrator
+            .get_migrations(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].checksum, "abc");
+        assert_eq!(records[0].description, "");
+        migrator
+            .record_migration_with_dialect(
+                &pool,
+                &Migration::new(
+                    "20240102120000".to_string(),
+                    "create_posts".to_string(),
+                    "def".to_string(),
+                ),
+                Dialect::SQLite,
+            )
+            .await
+            .unwrap();
+
+        // 更新後は何もしない
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            None
+        );
+        pool.close().await;
+    }
+
+    /// 適用SQLの保存用カラムがないレイアウトは、store_applied_sql の設定に関わらずカラムが追加される
+    #[tokio::test]
+    async fn test_upgrade_adds_applied_sql_columns() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, description TEXT NOT NULL, applied_at TEXT NOT NULL, checksum TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let migrator = DatabaseMigratorService::new();
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        let columns = table_columns(&pool).await;
+        assert!(columns.iter().any(|c| c == "up_sql"), "{:?}", columns);
+        assert!(columns.iter().any(|c| c == "down_sql"), "{:?}", columns);
+        assert!(migrator
+            .applied_sql_columns_exist(&pool, Dialect::SQLite)
+            .await
+            .unwrap());
+        pool.close().await;
+    }
+
+    /// カラム名が大文字の旧レイアウトも判定でき、既存のカラムは追加しない
+    #[tokio::test]
+    async fn test_upgrade_layout_with_uppercase_columns() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        sqlx::query(
+            "CREATE TABLE schema_migrations (VERSION TEXT PRIMARY KEY, DESCRIPTION TEXT NOT NULL, APPLIED_AT TEXT NOT NULL, CHECKSUM TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (VERSION, DESCRIPTION, APPLIED_AT, CHECKSUM) VALUES ('20240101120000', 'create_users', '2024-01-01 12:00:00', 'abc')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let migrator = DatabaseMigratorService::new();
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        let columns: Vec<String> = table_columns(&pool)
+            .await
+            .iter()
+            .map(|column| column.to_lowercase())
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                "version",
+                "description",
+                "applied_at",
+                "checksum",
+                "up_sql",
+                "down_sql",
+                "statement_checksums",
+                "execution_time_ms",
+                "applied_by"
+            ]
+        );
+
+        let records = migrator
+            .get_migrations(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].checksum, "abc");
+
+        // 更新後は何もしない
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            None
+        );
+        pool.close().await;
+    }
+
+    /// 更新の途中で失敗した（MySQLでDDLが一部だけコミットされた）テーブルは、残りのカラムを追加して再開する
+    #[tokio::test]
+    async fn test_upgrade_resumes_partially_upgraded_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        // レイアウト1から description と up_sql を追加した後、down_sql の追加で失敗した状態
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, applied_at TEXT NOT NULL, checksum TEXT NOT NULL, description TEXT NOT NULL DEFAULT '', up_sql TEXT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, applied_at, checksum) VALUES ('20240101120000', '2024-01-01 12:00:00', 'abc')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let columns = table_columns(&pool).await;
+        assert_eq!(
+            detect_migration_table_layout(&columns).map(|layout| layout.version),
+            Some(2)
+        );
+
+        let migrator = DatabaseMigratorService::new();
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            table_columns(&pool).await,
+            vec![
+                "version",
+                "applied_at",
+                "checksum",
+                "description",
+                "up_sql",
+                "down_sql",
+                "statement_checksums",
+                "execution_time_ms",
+                "applied_by"
+            ]
+        );
+        let records = migrator
+            .get_migrations(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].checksum, "abc");
+
+        assert_eq!(
+            migrator
+                .upgrade_migration_table(&pool, Dialect::SQLite)
+                .await
+                .unwrap(),
+            None
+        );
+        pool.close().await;
+    }
+
+    /// 接続時のテーブル作成処理で旧レイアウトが更新される
+    #[tokio::test]
+    async fn test_create_migration_table_upgrades_existing_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, applied_at TEXT NOT NULL, checksum TEXT NOT NULL, up_sql TEXT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        DatabaseMigratorService::new()
+            .create_migration_table(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+
+        let columns = table_columns(&pool).await;
+        for column in CURRENT_MIGRATION_TABLE_LAYOUT.columns {
+            assert!(columns.iter().any(|c| c == column), "{:?}", columns);
+        }
+        // 任意カラムは残る
+        assert!(columns.iter().any(|c| c == "up_sql"));
+        pool.close().await;
+    }
+
+    /// 新規作成したテーブルは現在のレイアウトで、ロールバックの記録テーブルも作成される
+    #[tokio::test]
+    async fn test_new_table_has_current_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        let migrator = DatabaseMigratorService::new();
+
+        migrator
+            .create_migration_table(&pool, Dialect::SQLite)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            table_columns(&pool).await,
+            CURRENT_MIGRATION_TABLE_LAYOUT.columns.to_vec()
+        );
+        assert!(migrator
+            .get_rollback_audits(&pool, Dialect::SQLite)
+            .await
+            .unwrap()
+            .is_empty());
+        let columns = table_columns(&pool).await;
+        assert_eq!(
+            detect_migration_table_layout(&columns),
+            Some(CURRENT_MIGRATION_TABLE_LAYOUT)
+        );
+        pool.close().await;
+    }
+
+    /// 認識できないレイアウトはカラムを変更せずに、見つかったカラムと期待するカラムを示して拒否する
+    #[tokio::test]
+    async fn test_unrecognized_layout_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&temp_dir).await;
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY, description TEXT NOT NULL, applied_at TEXT NOT NULL, checksum TEXT NOT NULL, installed_by TEXT NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let err = DatabaseMigratorService::new()
+            .create_migration_table(&pool, Dialect::SQLite)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_unrecognized_migration_table(), "{:?}", err);
+        let message = err.to_string();
+        assert!(
+            message.contains(
+                "found columns [applied_at, checksum, description, installed_by, version]"
+            ),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains(&format!(
+                "expected [{}]",
+                CURRENT_MIGRATION_TABLE_LAYOUT.columns.join(", ")
+            )),
+            "{}",
+            message
+        );
+        assert!(message.contains("unexpected: installed_by"), "{}", message);
+        assert_eq!(table_columns(&pool).await.len(), 5);
+        pool.close().await;
+    }
+}
@@ -0,0 +1,232 @@
+/// 生成結果の決定性テスト
+///
+/// 同じ入力から差分検出・SQL生成・YAMLシリアライズを繰り返し実行し、
+/// 出力がバイト単位で一致することを確認します。
+use strata::cli::commands::init_example::ExampleProjectWriter;
+use strata::core::config::Dialect;
+use strata::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Index, Schema, Table};
+use strata::services::migration_generator::MigrationGeneratorService;
+use strata::services::schema_diff_detector::SchemaDiffDetectorService;
+use strata::services::schema_io::schema_serializer::SchemaSerializerService;
+
+const RUNS: usize = 5;
+
+/// 変更前スキーマ: サンプルスキーマに削除対象のテーブル・カラムを加えたもの
+fn old_schema(dialect: Dialect) -> Schema {
+    let mut schema = ExampleProjectWriter::new().example_schema(dialect);
+
+    let posts = schema.tables.get_mut("posts").unwrap();
+    for name in ["legacy_a", "legacy_b", "legacy_c", "legacy_d"] {
+        posts.add_column(Column::new(name.to_string(), ColumnType::TEXT, true));
+    }
+    posts.add_index(Index::new(
+        "idx_posts_legacy_a".to_string(),
+        vec!["legacy_a".to_string()],
+        false,
+    ));
+    posts.add_index(Index::new(
+        "idx_posts_legacy_b".to_string(),
+        vec!["legacy_b".to_string()],
+        false,
+    ));
+
+    for name in ["archive_logs", "archive_events", "archive_jobs"] {
+        schema.add_table(simple_table(name, None));
+    }
+    schema
+}
+
+/// 変更後スキーマ: テーブル・カラム・インデックス・制約・ENUMを多数追加したもの
+fn new_schema(dialect: Dialect) -> Schema {
+    let mut schema = ExampleProjectWriter::new().example_schema(dialect);
+
+    let posts = schema.tables.get_mut("posts").unwrap();
+    for name in ["summary", "slug", "locale", "word_count", "reading_time"] {
+        posts.add_column(Column::new(name.to_string(), ColumnType::TEXT, true));
+    }
+    for name in ["summary", "slug", "locale"] {
+        posts.add_index(Index::new(
+            format!("idx_posts_{}", name),
+            vec![name.to_string()],
+            false,
+        ));
+    }
+    posts.add_constraint(Constraint::UNIQUE {
+        columns: vec!["slug".to_string()],
+    });
+    posts.add_constraint(Constraint::UNIQUE {
+        columns: vec!["locale".to_string(), "slug".to_string()],
+    });
+    posts.add_constraint(Constraint::CHECK {
+        columns: vec!["word_count".to_string()],
+        check_expression: "word_count IS NOT NULL".to_string(),
+    });
+
+    for name in [
+        "tags", "comments", "likes", "follows", "sessions", "audits", "settings", "tokens",
+    ] {
+        schema.add_table(simple_table(name, Some("users")));
+    }
+
+    if dialect == Dialect::PostgreSQL {
+        for name in ["priority", "visibility", "mood"] {
+            schema.add_enum(EnumDefinition {
+                name: name.to_string(),
+                values: vec!["c".to_string(), "a".to_string(), "b".to_string()],
+                renamed_from: None,
+            });
+        }
+    }
+    schema
+}
+
+fn simple_table(name: &str, references: Option<&str>) -> Table {
+    let mut table = Table::new(name.to_string());
+    table.add_column(Column::new(
+        "id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    for column in ["owner_id", "label", "note", "payload"] {
+        table.add_column(Column::new(
+            column.to_string(),
+            ColumnType::VARCHAR { length: 100 },
+            true,
+        ));
+    }
+    table.add_constraint(Constraint::PRIMARY_KEY {
+        columns: vec!["id".to_string()],
+    });
+    if let Some(referenced_table) = references {
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["owner_id".to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+    }
+    table.add_constraint(Constraint::UNIQUE {
+        columns: vec!["label".to_string()],
+    });
+    table.add_index(Index::new(
+        format!("idx_{}_note", name),
+        vec!["note".to_string()],
+        false,
+    ));
+    table
+}
+
+/// 差分検出からUP/DOWN SQLまでを1回実行した出力
+fn generate_once(dialect: Dialect) -> String {
+    let old_schema = old_schema(dialect);
+    let new_schema = new_schema(dialect);
+    let diff = SchemaDiffDetectorService::new().detect_diff(&old_schema, &new_schema);
+    let generator = MigrationGeneratorService::new();
+    let (up, _) = generator
+        .generate_up_sql_with_schemas(&diff, &old_schema, &new_schema, dialect, true)
+        .unwrap();
+    let (down, _) = generator
+        .generate_down_sql_with_schemas(&diff, &old_schema, &new_schema, dialect, true)
+        .unwrap();
+    format!("{}\n-- down --\n{}", up, down)
+}
+
+#[test]
+fn test_generated_sql_is_deterministic() {
+    for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+        let first = generate_once(dialect);
+        assert!(first.contains("tags"), "{:?}: unexpected output", dialect);
+        for _ in 1..RUNS {
+            assert_eq!(
+                generate_once(dialect),
+                first,
+                "{:?}: generated SQL differs between runs",
+                dialect
+            );
+        }
+    }
+}
+
+#[test]
+fn test_diff_follows_declared_and_alphabetical_order() {
+    let dialect = Dialect::PostgreSQL;
+    let diff =
+        SchemaDiffDetectorService::new().detect_diff(&old_schema(dialect), &new_schema(dialect));
+
+    // テーブル・ENUMは名前順
+    let added_tables: Vec<&str> = diff.added_tables.iter().map(|t| t.name.as_str()).collect();
+    let mut sorted = added_tables.clone();
+    sorted.sort();
+    assert_eq!(added_tables, sorted);
+    assert_eq!(
+        diff.removed_tables,
+        vec!["archive_events", "archive_jobs", "archive_logs"]
+    );
+    let added_enums: Vec<&str> = diff.added_enums.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(added_enums, vec!["mood", "priority", "visibility"]);
+    // ENUM値は宣言順のまま
+    assert_eq!(diff.added_enums[0].values, vec!["c", "a", "b"]);
+
+    // カラム・インデックスは宣言順、制約は種類 → 対象カラム順
+    let posts = diff
+        .modified_tables
+        .iter()
+        .find(|t| t.table_name == "posts")
+        .unwrap();
+    let added_columns: Vec<&str> = posts
+        .added_columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(
+        added_columns,
+        vec!["summary", "slug", "locale", "word_count", "reading_time"]
+    );
+    assert_eq!(
+        posts.removed_columns,
+        vec!["legacy_a", "legacy_b", "legacy_c", "legacy_d"]
+    );
+    let added_indexes: Vec<&str> = posts
+        .added_indexes
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect();
+    assert_eq!(
+        added_indexes,
+        vec!["idx_posts_summary", "idx_posts_slug", "idx_posts_locale"]
+    );
+    let added_constraints: Vec<(&str, Vec<&str>)> = posts
+        .added_constraints
+        .iter()
+        .map(|c| (c.kind(), c.columns().iter().map(|s| s.as_str()).collect()))
+        .collect();
+    assert_eq!(
+        added_constraints,
+        vec![
+            ("UNIQUE", vec!["locale", "slug"]),
+            ("UNIQUE", vec!["slug"]),
+            ("CHECK", vec!["word_count"]),
+        ]
+    );
+}
+
+#[test]
+fn test_serialized_yaml_is_deterministic() {
+    let serializer = SchemaSerializerService::new();
+    for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+        let first = serializer
+            .serialize_to_string(&new_schema(dialect))
+            .unwrap();
+        for _ in 1..RUNS {
+            assert_eq!(
+                serializer
+                    .serialize_to_string(&new_schema(dialect))
+                    .unwrap(),
+                first,
+                "{:?}: serialized YAML differs between runs",
+                dialect
+            );
+        }
+    }
+}
@@ -148,7 +148,13 @@ mod migration_generator_tests {
                 description,
                 Dialect::PostgreSQL,
                 checksum,
+                Some("up_sql_checksum_123".to_string()),
+                Some("\nGO".to_string()),
                 strata::core::destructive_change_report::DestructiveChangeReport::new(),
+                None,
+                Vec::new(),
+                None,
+                std::collections::BTreeMap::new(),
             )
             .expect("Failed to generate metadata");
 
@@ -159,6 +165,10 @@ mod migration_generator_tests {
         assert!(metadata.contains("dialect:"));
         assert!(metadata.contains("checksum:"));
         assert!(metadata.contains("abc123def456"));
+        assert!(metadata.contains("up_sql_checksum: up_sql_checksum_123"));
+        let parsed: strata::core::migration::MigrationMetadata =
+            serde_saphyr::from_str(&metadata).unwrap();
+        assert_eq!(parsed.statement_terminator.as_deref(), Some("\nGO"));
         assert!(metadata.contains("destructive_changes"));
     }
 
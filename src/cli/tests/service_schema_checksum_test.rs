@@ -188,6 +188,7 @@ mod schema_checksum_tests {
             "created_at".to_string(),
             ColumnType::TIMESTAMP {
                 with_time_zone: Some(true),
+                precision: None,
             },
             false,
         ));
@@ -3,7 +3,9 @@
 /// スキーマ間の差分を正しく検出することを確認します。
 #[cfg(test)]
 mod schema_diff_detector_tests {
-    use strata::core::schema::{Column, ColumnType, Constraint, Index, Schema, Table};
+    use strata::core::schema::{
+        Column, ColumnType, Constraint, Index, IndexColumn, NullsOrder, Schema, SortOrder, Table,
+    };
     use strata::services::schema_diff_detector::SchemaDiffDetectorService;
 
     /// サービスの作成テスト
@@ -290,6 +292,100 @@ mod schema_diff_detector_tests {
         assert_eq!(table_diff.removed_indexes[0], "idx_email");
     }
 
+    /// インデックスカラムのソート順・NULL順序の変更検出
+    #[test]
+    fn test_detect_index_sort_order_modified() {
+        let service = SchemaDiffDetectorService::new();
+
+        let schema_with_index = |columns: Vec<IndexColumn>| {
+            let mut schema = Schema::new("1.0".to_string());
+            let mut table = Table::new("events".to_string());
+            table.add_column(Column::new(
+                "created_at".to_string(),
+                ColumnType::TIMESTAMP {
+                    with_time_zone: None,
+                    precision: None,
+                },
+                true,
+            ));
+            table.add_index(Index::with_columns(
+                "idx_events_created_at".to_string(),
+                columns,
+                false,
+            ));
+            schema.add_table(table);
+            schema
+        };
+
+        let plain = schema_with_index(vec![IndexColumn::new("created_at")]);
+        let explicit_asc = schema_with_index(vec![IndexColumn::new("created_at")
+            .with_order(SortOrder::Asc)
+            .with_nulls(NullsOrder::Last)]);
+        let desc_nulls_last = schema_with_index(vec![IndexColumn::new("created_at")
+            .with_order(SortOrder::Desc)
+            .with_nulls(NullsOrder::Last)]);
+
+        // デフォルトと同じ指定は変更とみなさない
+        assert!(service.detect_diff(&plain, &explicit_asc).is_empty());
+
+        let diff = service.detect_diff(&plain, &desc_nulls_last);
+        assert_eq!(diff.modified_tables.len(), 1);
+        let table_diff = &diff.modified_tables[0];
+        assert_eq!(table_diff.modified_indexes.len(), 1);
+        assert_eq!(
+            table_diff.modified_indexes[0].index_name,
+            "idx_events_created_at"
+        );
+    }
+
+    /// 部分インデックスの条件式の変更検出
+    #[test]
+    fn test_detect_index_where_clause_modified() {
+        let service = SchemaDiffDetectorService::new();
+
+        let schema_with_index = |predicate: Option<&str>| {
+            let mut schema = Schema::new("1.0".to_string());
+            let mut table = Table::new("orders".to_string());
+            table.add_column(Column::new(
+                "customer_id".to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+            let index = Index::new(
+                "idx_orders_customer_id".to_string(),
+                vec!["customer_id".to_string()],
+                true,
+            );
+            table.add_index(match predicate {
+                Some(predicate) => index.with_where(predicate),
+                None => index,
+            });
+            schema.add_table(table);
+            schema
+        };
+
+        let active = schema_with_index(Some("deleted_at IS NULL"));
+
+        // データベースが返す括弧付き・空白違いの式は同じ条件とみなす
+        assert!(service
+            .detect_diff(&active, &schema_with_index(Some("(deleted_at  IS NULL)")))
+            .is_empty());
+
+        for changed in [
+            schema_with_index(Some("archived_at IS NULL")),
+            schema_with_index(None),
+        ] {
+            let diff = service.detect_diff(&active, &changed);
+            assert_eq!(diff.modified_tables.len(), 1);
+            let table_diff = &diff.modified_tables[0];
+            assert_eq!(table_diff.modified_indexes.len(), 1);
+            assert_eq!(
+                table_diff.modified_indexes[0].index_name,
+                "idx_orders_customer_id"
+            );
+        }
+    }
+
     /// 制約追加の検出
     #[test]
     fn test_detect_constraint_added() {
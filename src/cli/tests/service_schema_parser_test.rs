@@ -230,8 +230,12 @@ tables:
 "#;
         fs::write(schema_dir.join("users.yaml"), schema_yaml).unwrap();
 
-        // .yml ファイル
-        fs::write(schema_dir.join("posts.yml"), schema_yaml).unwrap();
+        // .yml ファイル（重複定義はエラーになるため別テーブルにする）
+        fs::write(
+            schema_dir.join("posts.yml"),
+            schema_yaml.replace("users:", "posts:"),
+        )
+        .unwrap();
 
         // .txt ファイル（無視されるべき）
         fs::write(schema_dir.join("readme.txt"), "This is not YAML").unwrap();
@@ -242,7 +246,9 @@ tables:
             .expect("Failed to parse directory");
 
         // .yaml と .yml のみが解析されるはず
-        assert_eq!(schema.tables.len(), 1); // users テーブルのみ（postsも同じusersなので上書き）
+        assert_eq!(schema.tables.len(), 2);
+        assert!(schema.tables.contains_key("users"));
+        assert!(schema.tables.contains_key("posts"));
     }
 
     /// スキーマバージョンの保持テスト
@@ -558,4 +564,93 @@ tables:
             reparsed_posts.constraints.len()
         );
     }
+
+    /// カラムの references 省略記法と明示的な FOREIGN_KEY 制約が同じスキーマになること
+    /// （参照先テーブルが renamed_from でリネームされる場合を含む）
+    #[test]
+    fn test_column_references_shorthand_matches_explicit_foreign_key() {
+        use strata::services::schema_io::schema_serializer::SchemaSerializerService;
+        use strata::services::schema_validator::SchemaValidatorService;
+
+        let users_yaml = r#"
+version: "1.0"
+tables:
+  users:
+    renamed_from: accounts
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#;
+        let posts_yaml = |user_id_extra: &str, constraints: &str| {
+            format!(
+                r#"
+version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+{}    primary_key:
+      - id
+{}"#,
+                user_id_extra, constraints
+            )
+        };
+
+        let parse = |posts: String| {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(temp_dir.path().join("users.yaml"), users_yaml).unwrap();
+            fs::write(temp_dir.path().join("posts.yaml"), posts).unwrap();
+            SchemaParserService::new()
+                .parse_schema_directory(temp_dir.path())
+                .unwrap()
+        };
+
+        let shorthand = parse(posts_yaml(
+            "        references: users.id\n        on_delete: CASCADE\n",
+            "",
+        ));
+        let explicit = parse(posts_yaml(
+            "",
+            r#"    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - id
+        on_delete: CASCADE
+"#,
+        ));
+
+        assert_eq!(shorthand, explicit);
+        let result = SchemaValidatorService::new().validate(&shorthand);
+        assert!(result.is_valid(), "{:?}", result.errors);
+
+        // エクスポートでは省略記法が使われ、再パースしても同じスキーマになる
+        let yaml = SchemaSerializerService::new()
+            .serialize_to_string(&explicit)
+            .unwrap();
+        assert!(yaml.contains("references: users.id"), "{}", yaml);
+        assert!(yaml.contains("renamed_from: accounts"), "{}", yaml);
+        assert!(!yaml.contains("type: FOREIGN_KEY"), "{}", yaml);
+
+        let temp_dir = TempDir::new().unwrap();
+        let serialized_file = temp_dir.path().join("serialized.yaml");
+        fs::write(&serialized_file, yaml).unwrap();
+        let reparsed = SchemaParserService::new()
+            .parse_schema_file(&serialized_file)
+            .unwrap();
+        assert_eq!(reparsed, explicit);
+    }
 }
@@ -0,0 +1,866 @@
+use chrono::NaiveDate;
+/// YAML DTO層のラウンドトリップ整合性テスト
+///
+/// リポジトリ内のサンプルスキーマと合成スキーマに対して
+/// parse → serialize → parse を実行し、`Schema` が完全に一致することを確認します。
+/// 新しいフィールドを追加した場合は `FieldCoverage` の分解束縛がコンパイルエラーになるため、
+/// 合成スキーマと `EXPECTED_FIELDS` を合わせて更新してください。
+/// `#[non_exhaustive]` の `ColumnType` と `Constraint` はここでは網羅的に分解できないため、
+/// 未対応のバリアントは記録時にパニックします（網羅は strata-core のテストで確認しています）。
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use strata::core::config::Dialect;
+use strata::core::schema::{
+    Column, ColumnLifecycle, ColumnType, Constraint, EnumDefinition, GeneratedColumn, Index,
+    IndexColumn, IndexType, NullsOrder, ReferentialAction, Schema, SortOrder, Table, View,
+};
+use strata::services::migration_generator::MigrationGeneratorService;
+use strata::services::schema_diff_detector::SchemaDiffDetectorService;
+use strata::services::schema_io::schema_parser::SchemaParserService;
+use strata::services::schema_io::schema_serializer::SchemaSerializerService;
+use tempfile::TempDir;
+
+/// 合成スキーマで網羅すべきフィールド・バリアントの一覧
+const EXPECTED_FIELDS: &[&str] = &[
+    "schema.version",
+    "schema.enum_recreate_allowed",
+    "schema.enums",
+    "schema.tables",
+    "schema.views",
+    "schema.external_tables",
+    "enum.values",
+    "enum.renamed_from",
+    "table.schema",
+    "table.columns",
+    "table.indexes",
+    "table.constraints",
+    "table.renamed_from",
+    "table.populate_from",
+    "table.rollback_protection",
+    "table.comment",
+    "table.metadata",
+    "column.nullable",
+    "column.default_value",
+    "column.auto_increment",
+    "column.renamed_from",
+    "column.generated",
+    "column.generated.stored",
+    "column.comment",
+    "column.on_update_current_timestamp",
+    "column.type_change_using",
+    "column.down_using",
+    "column.metadata",
+    "column.lifecycle",
+    "column.contract_pending_since",
+    "type.INTEGER",
+    "type.SMALLINT",
+    "type.BIGINT",
+    "type.VARCHAR",
+    "type.TEXT",
+    "type.BOOLEAN",
+    "type.TIMESTAMP",
+    "type.TIMESTAMP.with_time_zone",
+    "type.TIMESTAMP.precision",
+    "type.JSON",
+    "type.DECIMAL",
+    "type.FLOAT",
+    "type.DOUBLE",
+    "type.CHAR",
+    "type.DATE",
+    "type.TIME",
+    "type.TIME.with_time_zone",
+    "type.TIME.precision",
+    "type.BLOB",
+    "type.UUID",
+    "type.JSONB",
+    "type.ENUM",
+    "type.DialectSpecific",
+    "type.DialectSpecific.params",
+    "index.unique",
+    "index.type",
+    "index.where",
+    "index.renamed_from",
+    "index.metadata",
+    "index.column.order",
+    "index.column.nulls",
+    "index.column.length",
+    "constraint.PRIMARY_KEY",
+    "constraint.FOREIGN_KEY",
+    "constraint.FOREIGN_KEY.on_delete",
+    "constraint.FOREIGN_KEY.on_update",
+    "constraint.UNIQUE",
+    "constraint.CHECK",
+    "view.definition",
+    "view.depends_on",
+    "view.renamed_from",
+];
+
+/// スキーマ内で値が設定されているフィールドを記録する
+///
+/// 構造体とenumを網羅的に分解しているため、ドメインモデルにフィールドや
+/// バリアントが追加されるとコンパイルエラーとなり、ハーネスの更新漏れを防ぎます。
+#[derive(Debug, Default)]
+struct FieldCoverage {
+    seen: BTreeSet<&'static str>,
+}
+
+impl FieldCoverage {
+    fn record_schema(&mut self, schema: &Schema) {
+        let Schema {
+            version,
+            enum_recreate_allowed,
+            enums,
+            tables,
+            views,
+            external_tables,
+        } = schema;
+
+        self.mark("schema.version", !version.is_empty());
+        self.mark("schema.enum_recreate_allowed", *enum_recreate_allowed);
+        self.mark("schema.enums", !enums.is_empty());
+        self.mark("schema.tables", !tables.is_empty());
+        self.mark("schema.views", !views.is_empty());
+        self.mark("schema.external_tables", !external_tables.is_empty());
+
+        for enum_def in enums.values() {
+            let EnumDefinition {
+                name: _,
+                values,
+                renamed_from,
+            } = enum_def;
+            self.mark("enum.values", !values.is_empty());
+            self.mark("enum.renamed_from", renamed_from.is_some());
+        }
+        for table in tables.values() {
+            self.record_table(table);
+        }
+        for view in views.values() {
+            let View {
+                name: _,
+                definition,
+                depends_on,
+                renamed_from,
+            } = view;
+            self.mark("view.definition", !definition.is_empty());
+            self.mark("view.depends_on", !depends_on.is_empty());
+            self.mark("view.renamed_from", renamed_from.is_some());
+        }
+    }
+
+    fn record_table(&mut self, table: &Table) {
+        let Table {
+            name: _,
+            schema,
+            columns,
+            indexes,
+            constraints,
+            renamed_from,
+            populate_from,
+            rollback_protection,
+            comment,
+            metadata,
+        } = table;
+
+        self.mark("table.schema", schema.is_some());
+        self.mark("table.columns", !columns.is_empty());
+        self.mark("table.indexes", !indexes.is_empty());
+        self.mark("table.constraints", !constraints.is_empty());
+        self.mark("table.renamed_from", renamed_from.is_some());
+        self.mark("table.populate_from", populate_from.is_some());
+        self.mark("table.rollback_protection", *rollback_protection);
+        self.mark("table.comment", comment.is_some());
+        self.mark("table.metadata", !metadata.is_empty());
+
+        for column in columns {
+            self.record_column(column);
+        }
+        for index in indexes {
+            let Index {
+                name: _,
+                columns,
+                unique,
+                index_type,
+                where_clause,
+                renamed_from,
+                metadata,
+            } = index;
+            self.mark("index.unique", *unique);
+            self.mark("index.type", index_type.is_some());
+            self.mark("index.where", where_clause.is_some());
+            self.mark("index.renamed_from", renamed_from.is_some());
+            self.mark("index.metadata", !metadata.is_empty());
+            for column in columns {
+                let IndexColumn {
+                    name: _,
+                    order,
+                    nulls,
+                    length,
+                } = column;
+                self.mark("index.column.order", order.is_some());
+                self.mark("index.column.nulls", nulls.is_some());
+                self.mark("index.column.length", length.is_some());
+            }
+        }
+        for constraint in constraints {
+            self.record_constraint(constraint);
+        }
+    }
+
+    fn record_column(&mut self, column: &Column) {
+        let Column {
+            name: _,
+            column_type,
+            nullable,
+            default_value,
+            auto_increment,
+            renamed_from,
+            generated,
+            comment,
+            on_update_current_timestamp,
+            type_change_using,
+            down_using,
+            metadata,
+            lifecycle,
+            contract_pending_since,
+        } = column;
+
+        self.mark("column.nullable", *nullable);
+        self.mark("column.default_value", default_value.is_some());
+        self.mark("column.auto_increment", auto_increment.is_some());
+        self.mark("column.renamed_from", renamed_from.is_some());
+        self.mark("column.comment", comment.is_some());
+        self.mark(
+            "column.on_update_current_timestamp",
+            *on_update_current_timestamp,
+        );
+        self.mark("column.type_change_using", type_change_using.is_some());
+        self.mark("column.down_using", down_using.is_some());
+        if let Some(GeneratedColumn {
+            expression: _,
+            stored,
+        }) = generated
+        {
+            self.mark("column.generated", true);
+            self.mark("column.generated.stored", *stored);
+        }
+        self.mark("column.metadata", !metadata.is_empty());
+        self.mark("column.lifecycle", lifecycle.is_some());
+        self.mark(
+            "column.contract_pending_since",
+            contract_pending_since.is_some(),
+        );
+
+        match column_type {
+            ColumnType::INTEGER { .. } => self.mark("type.INTEGER", true),
+            ColumnType::SMALLINT => self.mark("type.SMALLINT", true),
+            ColumnType::BIGINT => self.mark("type.BIGINT", true),
+            ColumnType::VARCHAR { length: _ } => self.mark("type.VARCHAR", true),
+            ColumnType::TEXT => self.mark("type.TEXT", true),
+            ColumnType::BOOLEAN => self.mark("type.BOOLEAN", true),
+            ColumnType::TIMESTAMP {
+                with_time_zone,
+                precision,
+            } => {
+                self.mark("type.TIMESTAMP", true);
+                self.mark("type.TIMESTAMP.with_time_zone", with_time_zone.is_some());
+                self.mark("type.TIMESTAMP.precision", precision.is_some());
+            }
+            ColumnType::JSON => self.mark("type.JSON", true),
+            ColumnType::DECIMAL {
+                precision: _,
+                scale: _,
+            } => self.mark("type.DECIMAL", true),
+            ColumnType::FLOAT => self.mark("type.FLOAT", true),
+            ColumnType::DOUBLE => self.mark("type.DOUBLE", true),
+            ColumnType::CHAR { length: _ } => self.mark("type.CHAR", true),
+            ColumnType::DATE => self.mark("type.DATE", true),
+            ColumnType::TIME {
+                with_time_zone,
+                precision,
+            } => {
+                self.mark("type.TIME", true);
+                self.mark("type.TIME.with_time_zone", with_time_zone.is_some());
+                self.mark("type.TIME.precision", precision.is_some());
+            }
+            ColumnType::BLOB => self.mark("type.BLOB", true),
+            ColumnType::UUID => self.mark("type.UUID", true),
+            ColumnType::JSONB => self.mark("type.JSONB", true),
+            ColumnType::Enum { name: _ } => self.mark("type.ENUM", true),
+            ColumnType::DialectSpecific { kind: _, params } => {
+                self.mark("type.DialectSpecific", true);
+                self.mark(
+                    "type.DialectSpecific.params",
+                    params.as_object().is_some_and(|m| !m.is_empty()),
+                );
+            }
+            // ColumnType は #[non_exhaustive] のため、新しい型はここで記録を追加する
+            other => panic!(
+                "column type not covered by the round-trip test: {:?}",
+                other
+            ),
+        }
+    }
+
+    fn record_constraint(&mut self, constraint: &Constraint) {
+        match constraint {
+            Constraint::PRIMARY_KEY { columns: _ } => self.mark("constraint.PRIMARY_KEY", true),
+            Constraint::FOREIGN_KEY {
+                columns: _,
+                referenced_table: _,
+                referenced_columns: _,
+                on_delete,
+                on_update,
+            } => {
+                self.mark("constraint.FOREIGN_KEY", true);
+                self.mark("constraint.FOREIGN_KEY.on_delete", on_delete.is_some());
+                self.mark("constraint.FOREIGN_KEY.on_update", on_update.is_some());
+            }
+            Constraint::UNIQUE { columns: _ } => self.mark("constraint.UNIQUE", true),
+            Constraint::CHECK {
+                columns: _,
+                check_expression: _,
+            } => self.mark("constraint.CHECK", true),
+            // Constraint も同様に、新しい種類はここで記録を追加する
+            other => panic!("constraint not covered by the round-trip test: {:?}", other),
+        }
+    }
+
+    fn mark(&mut self, field: &'static str, present: bool) {
+        if present {
+            self.seen.insert(field);
+        }
+    }
+
+    /// 未網羅のフィールド一覧
+    fn missing(&self) -> Vec<&'static str> {
+        EXPECTED_FIELDS
+            .iter()
+            .copied()
+            .filter(|f| !self.seen.contains(f))
+            .collect()
+    }
+}
+
+/// 決定的な疑似乱数生成器（線形合同法）
+///
+/// 外部クレートに依存せず、再現可能な合成スキーマを生成するために使用します。
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.0 >> 33
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self) -> bool {
+        self.below(2) == 0
+    }
+}
+
+/// 全ColumnTypeバリアントの代表値
+fn all_column_types() -> Vec<ColumnType> {
+    vec![
+        ColumnType::INTEGER { precision: None },
+        ColumnType::SMALLINT,
+        ColumnType::BIGINT,
+        ColumnType::VARCHAR { length: 255 },
+        ColumnType::TEXT,
+        ColumnType::BOOLEAN,
+        ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: None,
+        },
+        ColumnType::TIMESTAMP {
+            with_time_zone: Some(true),
+            precision: Some(3),
+        },
+        ColumnType::JSON,
+        ColumnType::DECIMAL {
+            precision: 10,
+            scale: 2,
+        },
+        ColumnType::FLOAT,
+        ColumnType::DOUBLE,
+        ColumnType::CHAR { length: 3 },
+        ColumnType::DATE,
+        ColumnType::TIME {
+            with_time_zone: None,
+            precision: None,
+        },
+        ColumnType::TIME {
+            with_time_zone: Some(false),
+            precision: Some(6),
+        },
+        ColumnType::BLOB,
+        ColumnType::UUID,
+        ColumnType::JSONB,
+        ColumnType::Enum {
+            name: "status".to_string(),
+        },
+        ColumnType::DialectSpecific {
+            kind: "SERIAL".to_string(),
+            params: serde_json::json!({}),
+        },
+        ColumnType::DialectSpecific {
+            kind: "VARBIT".to_string(),
+            params: serde_json::json!({"length": 16}),
+        },
+        ColumnType::DialectSpecific {
+            kind: "ENUM".to_string(),
+            params: serde_json::json!({"values": ["a", "b"]}),
+        },
+    ]
+}
+
+/// 型に応じたデフォルト値の候補
+fn default_value_for(column_type: &ColumnType) -> Option<String> {
+    match column_type {
+        ColumnType::INTEGER { .. } | ColumnType::SMALLINT | ColumnType::BIGINT => {
+            Some("0".to_string())
+        }
+        ColumnType::VARCHAR { .. } | ColumnType::TEXT | ColumnType::CHAR { .. } => {
+            Some("'n/a'".to_string())
+        }
+        ColumnType::BOOLEAN => Some("false".to_string()),
+        ColumnType::TIMESTAMP { .. } => Some("CURRENT_TIMESTAMP".to_string()),
+        ColumnType::DECIMAL { .. } => Some("1.5".to_string()),
+        _ => None,
+    }
+}
+
+/// 参照アクションの候補
+fn referential_action(rng: &mut Lcg) -> Option<ReferentialAction> {
+    match rng.below(6) {
+        0 => None,
+        1 => Some(ReferentialAction::NoAction),
+        2 => Some(ReferentialAction::Cascade),
+        3 => Some(ReferentialAction::SetNull),
+        4 => Some(ReferentialAction::SetDefault),
+        _ => Some(ReferentialAction::Restrict),
+    }
+}
+
+/// 合成スキーマを生成
+///
+/// 固定の「全部入り」テーブルと、シードに応じてランダムに構成したテーブルを含みます。
+fn synthetic_schema(seed: u64) -> Schema {
+    let mut rng = Lcg(seed);
+    let mut schema = Schema::new("1.0".to_string());
+    schema.enum_recreate_allowed = rng.chance();
+    schema.add_enum(EnumDefinition {
+        name: "status".to_string(),
+        values: vec!["active".to_string(), "inactive".to_string()],
+        renamed_from: Some("state".to_string()),
+    });
+    schema
+        .external_tables
+        .insert("external_accounts".to_string());
+
+    // 全ColumnTypeバリアントを含むテーブル
+    let mut kitchen_sink = Table::new("kitchen_sink".to_string());
+    kitchen_sink.add_column(Column::new(
+        "id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    kitchen_sink.columns[0].auto_increment = Some(true);
+    kitchen_sink.columns[0]
+        .metadata
+        .insert("pii".to_string(), "false".to_string());
+    for (i, column_type) in all_column_types().into_iter().enumerate() {
+        let mut column = Column::new(format!("col_{}", i), column_type, i % 2 == 0);
+        column.default_value = default_value_for(&column.column_type);
+        kitchen_sink.add_column(column);
+    }
+    let mut total = Column::new(
+        "total".to_string(),
+        ColumnType::INTEGER { precision: None },
+        true,
+    );
+    total.generated = Some(GeneratedColumn::new("id * 2", true));
+    kitchen_sink.add_column(total);
+    let mut label = Column::new("label".to_string(), ColumnType::TEXT, true);
+    label.generated = Some(GeneratedColumn::new("'row ' || id", false));
+    kitchen_sink.add_column(label);
+    let mut updated_at = Column::new(
+        "updated_at".to_string(),
+        ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: None,
+        },
+        false,
+    );
+    updated_at.default_value = Some("CURRENT_TIMESTAMP".to_string());
+    updated_at.on_update_current_timestamp = true;
+    kitchen_sink.add_column(updated_at);
+    let mut legacy_code = Column::new("legacy_code".to_string(), ColumnType::TEXT, true);
+    legacy_code.lifecycle = Some(ColumnLifecycle::ContractPending);
+    legacy_code.contract_pending_since = NaiveDate::from_ymd_opt(2026, 1, 15);
+    legacy_code.comment = Some("Replaced by code_v2; it's kept until the contract".to_string());
+    legacy_code.type_change_using = Some("NULLIF(legacy_code, '')".to_string());
+    legacy_code.down_using = Some("legacy_code::text".to_string());
+    kitchen_sink.add_column(legacy_code);
+    kitchen_sink.add_constraint(Constraint::PRIMARY_KEY {
+        columns: vec!["id".to_string()],
+    });
+    let mut lookup_index = Index::new(
+        "idx_kitchen_sink_col_0".to_string(),
+        vec!["col_0".to_string()],
+        false,
+    );
+    lookup_index
+        .metadata
+        .insert("ticket".to_string(), "DATA-42".to_string());
+    lookup_index.renamed_from = Some("idx_kitchen_sink_lookup".to_string());
+    kitchen_sink.add_index(lookup_index);
+    kitchen_sink.comment = Some("Covers every schema field".to_string());
+    kitchen_sink
+        .metadata
+        .insert("owner".to_string(), "platform".to_string());
+    kitchen_sink
+        .metadata
+        .insert("ticket".to_string(), "DATA-1".to_string());
+    schema.add_table(kitchen_sink);
+
+    // スキーマ修飾されたテーブル（PostgreSQL）
+    let mut audit_events = Table::new_in_schema("audit".to_string(), "events");
+    audit_events.add_column(Column::new(
+        "id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    audit_events.add_column(Column::new(
+        "kitchen_sink_id".to_string(),
+        ColumnType::INTEGER { precision: None },
+        false,
+    ));
+    audit_events.add_constraint(Constraint::PRIMARY_KEY {
+        columns: vec!["id".to_string()],
+    });
+    audit_events.add_constraint(Constraint::FOREIGN_KEY {
+        columns: vec!["kitchen_sink_id".to_string()],
+        referenced_table: "kitchen_sink".to_string(),
+        referenced_columns: vec!["id".to_string()],
+        on_delete: None,
+        on_update: None,
+    });
+    schema.add_table(audit_events);
+
+    // ランダム構成のテーブル
+    let column_types = all_column_types();
+    let table_count = 2 + rng.below(4) as usize;
+    for t in 0..table_count {
+        let mut table = Table::new(format!("table_{}", t));
+        if rng.chance() {
+            table.renamed_from = Some(format!("old_table_{}", t));
+        }
+        if rng.chance() {
+            table.populate_from = Some("SELECT id FROM kitchen_sink".to_string());
+        }
+        if rng.chance() {
+            table.rollback_protection = true;
+        }
+
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        let column_count = 1 + rng.below(6) as usize;
+        for c in 0..column_count {
+            let column_type = column_types[rng.below(column_types.len() as u64) as usize].clone();
+            let mut column = Column::new(format!("c{}", c), column_type, rng.chance());
+            if rng.chance() {
+                column.default_value = default_value_for(&column.column_type);
+            }
+            if rng.below(4) == 0 {
+                column.auto_increment = Some(rng.chance());
+            }
+            if rng.below(4) == 0 {
+                column.renamed_from = Some(format!("old_c{}", c));
+            }
+            table.add_column(column);
+        }
+
+        table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        // 他の制約より前の単一カラム外部キーは references 省略記法にならず constraints に残る
+        if t > 1 && rng.chance() {
+            table.add_constraint(Constraint::FOREIGN_KEY {
+                columns: vec!["c0".to_string()],
+                referenced_table: "table_0".to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: None,
+                on_update: None,
+            });
+        }
+        table.add_constraint(Constraint::UNIQUE {
+            columns: vec!["c0".to_string()],
+        });
+        table.add_constraint(Constraint::CHECK {
+            columns: vec!["id".to_string()],
+            check_expression: "id > 0".to_string(),
+        });
+        if t > 0 {
+            table.add_constraint(Constraint::FOREIGN_KEY {
+                columns: vec!["id".to_string()],
+                referenced_table: format!("table_{}", t - 1),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: referential_action(&mut rng),
+                on_update: referential_action(&mut rng),
+            });
+        }
+
+        let mut c0_index = Index::new(
+            format!("idx_table_{}_c0", t),
+            vec!["c0".to_string()],
+            rng.chance(),
+        );
+        if rng.chance() {
+            c0_index = c0_index.with_where("c0 IS NOT NULL");
+        }
+        if rng.chance() {
+            c0_index = c0_index.with_index_type(IndexType::Hash);
+        }
+        table.add_index(c0_index);
+        let mut sorted_c0 = IndexColumn::new("c0").with_order(SortOrder::Desc);
+        if rng.chance() {
+            sorted_c0 = sorted_c0.with_nulls(NullsOrder::Last);
+        }
+        table.add_index(Index::with_columns(
+            format!("idx_table_{}_id_c0", t),
+            vec![IndexColumn::new("id"), sorted_c0],
+            true,
+        ));
+        table.add_index(Index::with_columns(
+            format!("idx_table_{}_c0_prefix", t),
+            vec![IndexColumn::new("c0").with_length(16)],
+            false,
+        ));
+
+        schema.add_table(table);
+    }
+
+    let mut view = View::new(
+        "active_rows".to_string(),
+        "SELECT id\nFROM table_0\nWHERE id > 0".to_string(),
+    );
+    view.depends_on = vec!["table_0".to_string()];
+    if rng.chance() {
+        view.renamed_from = Some("old_active_rows".to_string());
+    }
+    schema.add_view(view);
+
+    schema
+}
+
+/// YAML文字列をファイル経由でパース
+fn parse_yaml(dir: &Path, file_name: &str, yaml: &str) -> Schema {
+    let path = dir.join(file_name);
+    fs::write(&path, yaml).unwrap();
+    SchemaParserService::new()
+        .parse_schema_file(&path)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {:#}\n{}", file_name, e, yaml))
+}
+
+/// parse済みスキーマを serialize → parse し、元のスキーマと一致することを確認
+fn assert_round_trip(label: &str, original: &Schema) {
+    let temp_dir = TempDir::new().unwrap();
+    let serializer = SchemaSerializerService::new();
+
+    let yaml = serializer.serialize_to_string(original).unwrap();
+    let restored = parse_yaml(temp_dir.path(), "round_trip.yaml", &yaml);
+
+    assert_eq!(
+        &restored, original,
+        "round trip mismatch for {}\n--- serialized YAML ---\n{}",
+        label, yaml
+    );
+}
+
+/// リポジトリ内のサンプルスキーマファイル一覧
+fn example_schema_files() -> Vec<PathBuf> {
+    let example_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../example");
+    let mut files = Vec::new();
+
+    for dir in [example_dir.clone(), example_dir.join("schema")] {
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let is_yaml = path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml");
+            // .stratum.yaml などの設定ファイルは対象外
+            let is_hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if path.is_file() && is_yaml && !is_hidden {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+#[test]
+fn test_example_schemas_round_trip() {
+    let files = example_schema_files();
+    assert!(!files.is_empty(), "no example schemas found");
+
+    let parser = SchemaParserService::new();
+    for file in files {
+        let parsed = parser.parse_schema_file(&file).unwrap();
+        assert_round_trip(&file.display().to_string(), &parsed);
+    }
+}
+
+#[test]
+fn test_synthetic_schemas_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let serializer = SchemaSerializerService::new();
+
+    for seed in 0..32 {
+        // 生成したスキーマを一度パースし、パーサーが正規化した形を基準にする
+        let generated = synthetic_schema(seed);
+        let yaml = serializer.serialize_to_string(&generated).unwrap();
+        let parsed = parse_yaml(temp_dir.path(), "generated.yaml", &yaml);
+        assert_eq!(
+            parsed, generated,
+            "generated schema (seed {}) lost information on first parse\n{}",
+            seed, yaml
+        );
+
+        assert_round_trip(&format!("synthetic seed {}", seed), &parsed);
+    }
+}
+
+#[test]
+fn test_synthetic_schemas_cover_all_fields() {
+    let mut coverage = FieldCoverage::default();
+    for seed in 0..32 {
+        coverage.record_schema(&synthetic_schema(seed));
+    }
+
+    let missing = coverage.missing();
+    assert!(
+        missing.is_empty(),
+        "synthetic schemas do not exercise: {:?}",
+        missing
+    );
+}
+
+#[test]
+fn test_directory_round_trip_preserves_all_schema_files() {
+    // 複数ファイルに分割されたスキーマをディレクトリ単位で読み込んでも同一になること
+    let temp_dir = TempDir::new().unwrap();
+    let serializer = SchemaSerializerService::new();
+    let mut schema = synthetic_schema(7);
+    schema.enum_recreate_allowed = true;
+
+    for (name, table) in &schema.tables {
+        let mut single = Schema::new(schema.version.clone());
+        single.enum_recreate_allowed = schema.enum_recreate_allowed;
+        single.add_table(table.clone());
+        serializer
+            .serialize_to_file(&single, &temp_dir.path().join(format!("{}.yaml", name)))
+            .unwrap();
+    }
+    let mut enums = Schema::new(schema.version.clone());
+    enums.enums = schema.enums.clone();
+    serializer
+        .serialize_to_file(&enums, &temp_dir.path().join("enums.yaml"))
+        .unwrap();
+    let mut views = Schema::new(schema.version.clone());
+    views.views = schema.views.clone();
+    views.external_tables = schema.external_tables.clone();
+    serializer
+        .serialize_to_file(&views, &temp_dir.path().join("views.yaml"))
+        .unwrap();
+
+    let restored = SchemaParserService::new()
+        .parse_schema_directory(temp_dir.path())
+        .unwrap();
+
+    assert_eq!(restored, schema);
+}
+
+/// 非推奨の `precision` 指定と明示的な型が同じスキーマ・同じSQLになることを確認
+#[test]
+fn test_integer_precision_alias_matches_explicit_kind() {
+    let temp_dir = TempDir::new().unwrap();
+    let alias = parse_yaml(
+        temp_dir.path(),
+        "alias.yaml",
+        r#"version: "1.0"
+tables:
+  counters:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+          precision: 8
+        auto_increment: true
+      - name: small
+        type:
+          kind: INTEGER
+          precision: 2
+    primary_key: [id]
+"#,
+    );
+    let explicit = parse_yaml(
+        temp_dir.path(),
+        "explicit.yaml",
+        r#"version: "1.0"
+tables:
+  counters:
+    columns:
+      - name: id
+        type:
+          kind: BIGINT
+        auto_increment: true
+      - name: small
+        type:
+          kind: SMALLINT
+    primary_key: [id]
+"#,
+    );
+
+    assert_eq!(alias, explicit);
+    assert_round_trip("integer precision alias", &alias);
+
+    // 書き出しは明示的な型になる
+    let yaml = SchemaSerializerService::new()
+        .serialize_to_string(&alias)
+        .unwrap();
+    assert!(yaml.contains("kind: BIGINT"), "{}", yaml);
+    assert!(yaml.contains("kind: SMALLINT"), "{}", yaml);
+    assert!(!yaml.contains("precision"), "{}", yaml);
+
+    // 書き換えてもマイグレーションは発生しない
+    let diff = SchemaDiffDetectorService::new().detect_diff(&alias, &explicit);
+    assert!(diff.is_empty());
+
+    // 空のスキーマから生成したSQLは方言ごとに同じ
+    let empty = Schema::new(alias.version.clone());
+    let generator = MigrationGeneratorService::new();
+    for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+        let up_sql = |target: &Schema| {
+            let diff = SchemaDiffDetectorService::new().detect_diff(&empty, target);
+            generator
+                .generate_up_sql_with_schemas(&diff, &empty, target, dialect, false)
+                .unwrap()
+                .0
+        };
+        assert_eq!(up_sql(&alias), up_sql(&explicit), "{}", dialect);
+    }
+}
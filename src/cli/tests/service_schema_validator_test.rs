@@ -612,4 +612,55 @@ mod schema_validator_tests {
         assert!(!sqlite_warnings.is_empty());
         assert!(sqlite_warnings[0].message.contains("TEXT"));
     }
+
+    /// 他のカラムを参照するデフォルト値は方言ごとにエラー・警告になる
+    #[test]
+    fn test_default_value_referencing_column() {
+        use strata::core::config::Dialect;
+
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("products".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_column(Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            false,
+        ));
+        let mut tax = Column::new(
+            "tax".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            false,
+        );
+        tax.default_value = Some("(price * 0.2)".to_string());
+        table.add_column(tax);
+        table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        schema.add_table(table);
+
+        let validator = SchemaValidatorService::new();
+
+        let result = validator.validate_with_dialect(&schema, Dialect::PostgreSQL);
+        assert!(!result.is_valid());
+        assert!(result.errors_to_string().contains(
+            "Default value of column 'tax' in table 'products' references column(s) price"
+        ));
+
+        let result = validator.validate_with_dialect(&schema, Dialect::MySQL);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("references column(s) price")));
+    }
 }
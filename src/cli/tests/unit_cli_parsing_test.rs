@@ -87,6 +87,158 @@ mod cli_tests {
         assert!(matches!(cli.command, strata::cli::Commands::Status { .. }));
     }
 
+    /// status --gate がカンマ区切りでパース可能であることを確認
+    #[test]
+    fn test_status_gate_parses() {
+        use strata::cli::commands::status::StatusGate;
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "status", "--gate", "unmanaged,pending"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Status { gate, .. } => {
+                assert_eq!(gate, vec![StatusGate::Unmanaged, StatusGate::Pending]);
+            }
+            _ => panic!("expected status command"),
+        }
+        assert!(Cli::try_parse_from(["strata", "status", "--gate", "unknown"]).is_err());
+    }
+
+    /// diffサブコマンドがパース可能であることを確認
+    #[test]
+    fn test_diff_command_parses() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "diff", "--env", "production"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Diff { env } => assert_eq!(env.env, "production"),
+            _ => panic!("expected diff command"),
+        }
+    }
+
+    /// docsサブコマンドの --out のデフォルトと --check がパース可能であることを確認
+    #[test]
+    fn test_docs_command_parses() {
+        use std::path::PathBuf;
+        use strata::cli::{Cli, Commands};
+
+        let cli = Cli::try_parse_from(["strata", "docs"]).unwrap();
+        match cli.command {
+            Commands::Docs { out, check } => {
+                assert_eq!(out, PathBuf::from("docs/schema"));
+                assert!(!check);
+            }
+            _ => panic!("expected docs command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["strata", "docs", "--out", "wiki/schema", "--check"]).unwrap();
+        match cli.command {
+            Commands::Docs { out, check } => {
+                assert_eq!(out, PathBuf::from("wiki/schema"));
+                assert!(check);
+            }
+            _ => panic!("expected docs command"),
+        }
+    }
+
+    /// seedサブコマンドの --env と --only がパース可能であることを確認
+    #[test]
+    fn test_seed_command_parses() {
+        use strata::cli::{Cli, Commands};
+
+        let cli = Cli::try_parse_from(["strata", "seed"]).unwrap();
+        match cli.command {
+            Commands::Seed { env, only, .. } => {
+                assert_eq!(env.env, "development");
+                assert!(only.is_none());
+            }
+            _ => panic!("expected seed command"),
+        }
+
+        let cli = Cli::try_parse_from(["strata", "seed", "--env", "staging", "--only", "01_roles"])
+            .unwrap();
+        match cli.command {
+            Commands::Seed { env, only, .. } => {
+                assert_eq!(env.env, "staging");
+                assert_eq!(only.as_deref(), Some("01_roles"));
+            }
+            _ => panic!("expected seed command"),
+        }
+    }
+
+    /// dev migrate サブコマンドと --watch のオプションがパース可能であることを確認
+    #[test]
+    fn test_dev_migrate_command_parses() {
+        use strata::cli::{Cli, Commands, DevCommands};
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "dev",
+            "migrate",
+            "-d",
+            "add_email",
+            "--watch",
+            "--debounce",
+            "1000",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Dev {
+                command:
+                    DevCommands::Migrate {
+                        env,
+                        description,
+                        watch,
+                        poll_interval,
+                        debounce,
+                    },
+            } => {
+                assert_eq!(env.env, "development");
+                assert_eq!(description.as_deref(), Some("add_email"));
+                assert!(watch);
+                assert_eq!(poll_interval, 500);
+                assert_eq!(debounce, 1000);
+            }
+            _ => panic!("expected dev migrate command"),
+        }
+
+        // ポーリングの設定は --watch と併用する
+        assert!(Cli::try_parse_from(["strata", "dev", "migrate", "--debounce", "10"]).is_err());
+    }
+
+    /// resetサブコマンドがパース可能であることを確認
+    #[test]
+    fn test_reset_command_parses() {
+        use strata::cli::{Cli, Commands};
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "reset",
+            "--hard",
+            "--delete-file",
+            "--reapply",
+            "-y",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Reset {
+                env,
+                hard,
+                delete_file,
+                reapply,
+                yes,
+                ..
+            } => {
+                assert_eq!(env.env, "development");
+                assert!(hard && delete_file && reapply && yes);
+            }
+            _ => panic!("expected reset command"),
+        }
+
+        // --delete-file は --hard と併用する
+        assert!(Cli::try_parse_from(["strata", "reset", "--delete-file"]).is_err());
+    }
+
     /// exportサブコマンドがパース可能であることを確認
     #[test]
     fn test_export_command_parses() {
@@ -96,6 +248,170 @@ mod cli_tests {
         assert!(matches!(cli.command, strata::cli::Commands::Export { .. }));
     }
 
+    /// baselineサブコマンドのオプションがパース可能であることを確認
+    #[test]
+    fn test_baseline_command_parses() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "baseline"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Baseline { force: false, .. }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "baseline",
+            "--env",
+            "production",
+            "--dry-run",
+            "--force",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Baseline {
+                env,
+                dry_run,
+                force,
+            } => {
+                assert_eq!(env.env, "production");
+                assert!(dry_run.dry_run);
+                assert!(force);
+            }
+            _ => panic!("Expected Baseline command"),
+        }
+    }
+
+    /// export --allow-partial がパース可能であることを確認
+    #[test]
+    fn test_export_allow_partial_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "export"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Export {
+                allow_partial: false,
+                ..
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["strata", "export", "--allow-partial"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Export {
+                allow_partial: true,
+                ..
+            }
+        ));
+    }
+
+    /// export --diff-against がパース可能であることを確認
+    #[test]
+    fn test_export_diff_against_option() {
+        use std::path::PathBuf;
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "export",
+            "--output",
+            "./current",
+            "--diff-against",
+            "./previous",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Export { diff_against, .. } => {
+                assert_eq!(diff_against, Some(PathBuf::from("./previous")));
+            }
+            _ => panic!("Expected Export command"),
+        }
+    }
+
+    /// inspectサブコマンドは --suggest-sizes を要求し、フィルタと行数上限を受け付ける
+    #[test]
+    fn test_inspect_command_parses() {
+        use strata::cli::Cli;
+
+        assert!(Cli::try_parse_from(["strata", "inspect"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "inspect",
+            "--suggest-sizes",
+            "--env",
+            "prod",
+            "--tables",
+            "users,posts",
+            "--columns",
+            "email,users.name",
+            "--max-rows",
+            "5000",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Inspect {
+                env,
+                suggest_sizes,
+                tables,
+                columns,
+                allow_full_scan,
+                max_rows,
+            } => {
+                assert_eq!(env.env, "prod");
+                assert!(suggest_sizes);
+                assert_eq!(tables, vec!["users", "posts"]);
+                assert_eq!(columns, vec!["email", "users.name"]);
+                assert!(!allow_full_scan);
+                assert_eq!(max_rows, 5000);
+            }
+            _ => panic!("Expected Inspect command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["strata", "inspect", "--suggest-sizes", "--allow-full-scan"])
+                .unwrap();
+        match cli.command {
+            strata::cli::Commands::Inspect {
+                allow_full_scan,
+                max_rows,
+                ..
+            } => {
+                assert!(allow_full_scan);
+                assert_eq!(
+                    max_rows,
+                    strata::cli::commands::inspect::DEFAULT_MAX_SCAN_ROWS
+                );
+            }
+            _ => panic!("Expected Inspect command"),
+        }
+    }
+
+    /// introspectサブコマンドは --table か --all のどちらか一方を要求する
+    #[test]
+    fn test_introspect_command_parses() {
+        use strata::cli::Cli;
+
+        let cli =
+            Cli::try_parse_from(["strata", "introspect", "--env", "prod", "--table", "users"])
+                .unwrap();
+        match cli.command {
+            strata::cli::Commands::Introspect { env, table, all } => {
+                assert_eq!(env.env, "prod");
+                assert_eq!(table.as_deref(), Some("users"));
+                assert!(!all);
+            }
+            _ => panic!("Expected Introspect command"),
+        }
+
+        assert!(Cli::try_parse_from(["strata", "introspect", "--all"]).is_ok());
+        assert!(Cli::try_parse_from(["strata", "introspect"]).is_err());
+        assert!(
+            Cli::try_parse_from(["strata", "introspect", "--table", "users", "--all"]).is_err()
+        );
+    }
+
     /// グローバルオプション --config がパース可能であることを確認
     #[test]
     fn test_global_config_option() {
@@ -111,6 +427,59 @@ mod cli_tests {
         );
     }
 
+    /// グローバルオプション --project がパース可能であることを確認
+    #[test]
+    fn test_global_project_option() {
+        use std::path::Path;
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "--project",
+            "services/auth",
+            "apply",
+            "--env",
+            "production",
+        ])
+        .unwrap();
+        assert_eq!(cli.project.as_deref(), Some(Path::new("services/auth")));
+
+        // サブコマンドの後にも指定できる
+        let cli =
+            Cli::try_parse_from(["strata", "status", "--project", "services/billing"]).unwrap();
+        assert_eq!(cli.project.as_deref(), Some(Path::new("services/billing")));
+    }
+
+    /// workspace status がパース可能であることを確認
+    #[test]
+    fn test_workspace_status_command_parses() {
+        use strata::cli::commands::status::StatusGate;
+        use strata::cli::{Cli, Commands, WorkspaceCommands};
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "workspace",
+            "status",
+            "--env",
+            "production",
+            "--gate",
+            "pending,drift",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Workspace {
+                command: WorkspaceCommands::Status { env, gate },
+            } => {
+                assert_eq!(env.env, "production");
+                assert_eq!(gate, vec![StatusGate::Pending, StatusGate::Drift]);
+            }
+            _ => panic!("Expected Workspace Status command"),
+        }
+
+        assert!(Cli::try_parse_from(["strata", "workspace"]).is_err());
+    }
+
     /// グローバルオプション --verbose がパース可能であることを確認
     #[test]
     fn test_global_verbose_option() {
@@ -131,6 +500,18 @@ mod cli_tests {
         assert!(cli.no_color);
     }
 
+    /// グローバルオプション --timings がパース可能であることを確認
+    #[test]
+    fn test_global_timings_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "generate", "--timings"]).unwrap();
+        assert!(cli.timings);
+
+        let cli = Cli::try_parse_from(["strata", "status"]).unwrap();
+        assert!(!cli.timings);
+    }
+
     /// apply コマンドの --dry-run オプションがパース可能であることを確認
     #[test]
     fn test_apply_dry_run_option() {
@@ -146,6 +527,90 @@ mod cli_tests {
         }
     }
 
+    /// apply コマンドの --single-transaction オプションがパース可能であることを確認
+    #[test]
+    fn test_apply_single_transaction_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "apply", "--single-transaction"]).unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Apply {
+                single_transaction, ..
+            } => {
+                assert!(single_transaction);
+            }
+            _ => panic!("Expected Apply command"),
+        }
+    }
+
+    /// apply コマンドの --lock-timeout / --statement-timeout オプションがパース可能であることを確認
+    #[test]
+    fn test_apply_session_timeout_options() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "apply",
+            "--lock-timeout",
+            "5",
+            "--statement-timeout",
+            "600",
+        ])
+        .unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Apply {
+                lock_timeout,
+                statement_timeout,
+                ..
+            } => {
+                assert_eq!(lock_timeout, Some(5));
+                assert_eq!(statement_timeout, Some(600));
+            }
+            _ => panic!("Expected Apply command"),
+        }
+    }
+
+    /// apply コマンドの --only / --force-out-of-order オプションがパース可能であることを確認
+    #[test]
+    fn test_apply_only_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "apply",
+            "--only",
+            "20260121120001",
+            "--force-out-of-order",
+        ])
+        .unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Apply {
+                only,
+                force_out_of_order,
+                ..
+            } => {
+                assert_eq!(only.as_deref(), Some("20260121120001"));
+                assert!(force_out_of_order);
+            }
+            _ => panic!("Expected Apply command"),
+        }
+
+        // --force-out-of-order は --only と併用する。--target とは併用できない
+        assert!(Cli::try_parse_from(["strata", "apply", "--force-out-of-order"]).is_err());
+        assert!(Cli::try_parse_from([
+            "strata",
+            "apply",
+            "--only",
+            "20260121120001",
+            "--target",
+            "20260121120002",
+        ])
+        .is_err());
+    }
+
     /// generate コマンドの --allow-destructive オプションがパース可能であることを確認
     #[test]
     fn test_generate_allow_destructive_option() {
@@ -163,6 +628,219 @@ mod cli_tests {
         }
     }
 
+    /// generate コマンドの --summary-only オプションをパースできることを確認
+    #[test]
+    fn test_generate_summary_only_option() {
+        use strata::cli::Cli;
+
+        let cli =
+            Cli::try_parse_from(["strata", "generate", "--dry-run", "--summary-only"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Generate {
+                dry_run,
+                summary_only,
+                ..
+            } => {
+                assert!(dry_run.dry_run);
+                assert!(summary_only);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+    }
+
+    /// generate コマンドの --sql-only / --annotate / --down をパースできることを確認
+    #[test]
+    fn test_generate_dry_run_sql_options() {
+        use strata::cli::commands::dry_run_sql::DryRunSqlMode;
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "generate",
+            "--dry-run",
+            "--sql-only",
+            "--explain",
+            "--down",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Generate {
+                dry_run_sql, down, ..
+            } => {
+                assert_eq!(dry_run_sql.mode(), DryRunSqlMode::SqlOnly { explain: true });
+                assert!(down);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        let cli = Cli::try_parse_from(["strata", "generate", "--dry-run", "--annotate"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Generate { dry_run_sql, .. } => {
+                assert_eq!(dry_run_sql.mode(), DryRunSqlMode::Annotated);
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        // --dry-run なし、組み合わせられないオプション、--down 単独はエラー
+        for args in [
+            vec!["strata", "generate", "--sql-only"],
+            vec!["strata", "generate", "--dry-run", "--explain"],
+            vec!["strata", "generate", "--dry-run", "--down"],
+            vec![
+                "strata",
+                "generate",
+                "--dry-run",
+                "--sql-only",
+                "--annotate",
+            ],
+            vec![
+                "strata",
+                "generate",
+                "--dry-run",
+                "--sql-only",
+                "--summary-only",
+            ],
+            vec!["strata", "apply", "--sql-only"],
+        ] {
+            assert!(Cli::try_parse_from(&args).is_err(), "{:?}", args);
+        }
+
+        let cli = Cli::try_parse_from(["strata", "apply", "--dry-run", "--sql-only"]).unwrap();
+        assert!(matches!(cli.command, strata::cli::Commands::Apply { .. }));
+    }
+
+    /// generate コマンドの --meta オプションが繰り返し指定できることを確認
+    #[test]
+    fn test_generate_meta_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "generate",
+            "--meta",
+            "team=payments",
+            "--meta",
+            "ticket=PAY-123",
+        ])
+        .unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Generate { meta, .. } => {
+                assert_eq!(
+                    meta,
+                    vec![
+                        ("team".to_string(), "payments".to_string()),
+                        ("ticket".to_string(), "PAY-123".to_string()),
+                    ]
+                );
+            }
+            _ => panic!("Expected Generate command"),
+        }
+
+        assert!(Cli::try_parse_from(["strata", "generate", "--meta", "ticket"]).is_err());
+    }
+
+    /// validate コマンドの --allow-duplicate-override オプションがパース可能であることを確認
+    #[test]
+    fn test_validate_allow_duplicate_override_option() {
+        use strata::cli::Cli;
+
+        let cli =
+            Cli::try_parse_from(["strata", "validate", "--allow-duplicate-override"]).unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Validate {
+                allow_duplicate_override,
+                ..
+            } => {
+                assert!(allow_duplicate_override.allow_duplicate_override);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+    }
+
+    /// validate コマンドの --suggest-fixes / --apply-fixes オプションがパース可能であることを確認
+    #[test]
+    fn test_validate_fix_options() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "validate", "--suggest-fixes", "--apply-fixes"])
+            .unwrap();
+        match cli.command {
+            strata::cli::Commands::Validate {
+                suggest_fixes,
+                apply_fixes,
+                ..
+            } => {
+                assert!(suggest_fixes);
+                assert!(apply_fixes);
+            }
+            _ => panic!("Expected Validate command"),
+        }
+
+        // 単一ファイル検証とは組み合わせられない
+        assert!(Cli::try_parse_from([
+            "strata",
+            "validate",
+            "--file",
+            "schema/users.yaml",
+            "--apply-fixes"
+        ])
+        .is_err());
+    }
+
+    /// validate コマンドの --changed-since / --files オプションがパース可能であることを確認
+    #[test]
+    fn test_validate_changed_since_and_files_options() {
+        use strata::cli::Cli;
+
+        let cli =
+            Cli::try_parse_from(["strata", "validate", "--changed-since", "origin/main"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Validate {
+                changed_since,
+                files,
+                ..
+            } => {
+                assert_eq!(changed_since.as_deref(), Some("origin/main"));
+                assert!(files.is_empty());
+            }
+            _ => panic!("Expected Validate command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "validate",
+            "--files",
+            "schema/users.yaml",
+            "schema/posts.yaml",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Validate { files, .. } => {
+                assert_eq!(
+                    files,
+                    vec![
+                        std::path::PathBuf::from("schema/users.yaml"),
+                        std::path::PathBuf::from("schema/posts.yaml"),
+                    ]
+                );
+            }
+            _ => panic!("Expected Validate command"),
+        }
+
+        // 両方を同時に指定することはできない
+        assert!(Cli::try_parse_from([
+            "strata",
+            "validate",
+            "--changed-since",
+            "HEAD",
+            "--files",
+            "schema/users.yaml",
+        ])
+        .is_err());
+    }
+
     /// apply コマンドの --allow-destructive オプションがパース可能であることを確認
     #[test]
     fn test_apply_allow_destructive_option() {
@@ -180,6 +858,44 @@ mod cli_tests {
         }
     }
 
+    /// apply コマンドの --restore-sequence-values オプションがパース可能であることを確認
+    #[test]
+    fn test_apply_restore_sequence_values_option() {
+        use std::path::PathBuf;
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "apply",
+            "--restore-sequence-values",
+            "prod/sequence_values.yaml",
+        ])
+        .unwrap();
+        match cli.command {
+            strata::cli::Commands::Apply {
+                restore_sequence_values,
+                ..
+            } => {
+                assert_eq!(
+                    restore_sequence_values,
+                    Some(PathBuf::from("prod/sequence_values.yaml"))
+                );
+            }
+            _ => panic!("Expected Apply command"),
+        }
+
+        // SQLのみを出力するモードとは組み合わせられない
+        assert!(Cli::try_parse_from([
+            "strata",
+            "apply",
+            "--dry-run",
+            "--sql-only",
+            "--restore-sequence-values",
+            "sequence_values.yaml",
+        ])
+        .is_err());
+    }
+
     /// rollback コマンドの --steps オプションがパース可能であることを確認
     #[test]
     fn test_rollback_steps_option() {
@@ -194,4 +910,143 @@ mod cli_tests {
             _ => panic!("Expected Rollback command"),
         }
     }
+
+    /// apply / rollback コマンドの --target オプションがパース可能であることを確認
+    #[test]
+    fn test_target_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "apply", "--target", "20240301120000"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Apply { target, .. } => {
+                assert_eq!(target.as_deref(), Some("20240301120000"));
+            }
+            _ => panic!("Expected Apply command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["strata", "rollback", "--target", "20240301120000"]).unwrap();
+        match cli.command {
+            strata::cli::Commands::Rollback { target, steps, .. } => {
+                assert_eq!(target.as_deref(), Some("20240301120000"));
+                assert_eq!(steps, None);
+            }
+            _ => panic!("Expected Rollback command"),
+        }
+
+        // --steps と --target は同時に指定できない
+        assert!(Cli::try_parse_from([
+            "strata",
+            "rollback",
+            "--steps",
+            "2",
+            "--target",
+            "20240301120000"
+        ])
+        .is_err());
+    }
+
+    /// repair コマンドの --renumber / --force オプションがパース可能であることを確認
+    #[test]
+    fn test_repair_renumber_option() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "repair",
+            "--renumber",
+            "20991231235959",
+            "--force",
+        ])
+        .unwrap();
+
+        match cli.command {
+            strata::cli::Commands::Repair {
+                renumber, force, ..
+            } => {
+                assert_eq!(renumber.as_deref(), Some("20991231235959"));
+                assert!(force);
+            }
+            _ => panic!("Expected Repair command"),
+        }
+
+        // --force は --renumber と組み合わせる必要がある
+        assert!(Cli::try_parse_from(["strata", "repair", "--force"]).is_err());
+        // --export-applied-sql とは同時に指定できない
+        assert!(Cli::try_parse_from([
+            "strata",
+            "repair",
+            "--renumber",
+            "20991231235959",
+            "--export-applied-sql",
+            "out"
+        ])
+        .is_err());
+    }
+
+    /// rename-migration コマンドの引数がパース可能であることを確認
+    #[test]
+    fn test_rename_migration_command() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from([
+            "strata",
+            "rename-migration",
+            "20240101120000",
+            "add_users_table",
+            "--env",
+            "staging",
+        ])
+        .unwrap();
+
+        match cli.command {
+            strata::cli::Commands::RenameMigration {
+                migration_version,
+                new_description,
+                env,
+            } => {
+                assert_eq!(migration_version, "20240101120000");
+                assert_eq!(new_description, "add_users_table");
+                assert_eq!(env.as_deref(), Some("staging"));
+            }
+            _ => panic!("Expected RenameMigration command"),
+        }
+
+        // 新しい説明は必須
+        assert!(Cli::try_parse_from(["strata", "rename-migration", "20240101120000"]).is_err());
+    }
+
+    /// version コマンドの引数がパース可能であることを確認
+    #[test]
+    fn test_version_command() {
+        use strata::cli::Cli;
+
+        let cli = Cli::try_parse_from(["strata", "version", "--check-latest"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Version {
+                check_latest: true,
+                features: false
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["strata", "version"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Version {
+                check_latest: false,
+                features: false
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["strata", "version", "--features"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            strata::cli::Commands::Version { features: true, .. }
+        ));
+
+        // --version フラグは従来どおり使える
+        let err = Cli::try_parse_from(["strata", "--version"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::DisplayVersion);
+    }
 }
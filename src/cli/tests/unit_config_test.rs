@@ -6,9 +6,12 @@
 mod config_tests {
     use std::fs;
     use std::path::Path;
-    use strata::core::config::{Config, DatabaseConfig, Dialect};
+    use strata::core::config::{
+        Config, DatabaseConfig, Dialect, IdentifierCase, ViewRenamePropagation,
+    };
     use strata::services::config_loader::ConfigLoader;
     use strata::services::database_config_resolver::DatabaseConfigResolver;
+    use strata::services::schema_diff_detector::SchemaDiffOptions;
     use tempfile::TempDir;
 
     fn load_config_from_yaml(yaml: &str) -> Config {
@@ -154,6 +157,144 @@ environments:
         // デフォルト値の確認
         assert_eq!(config.schema_dir, Path::new("schema"));
         assert_eq!(config.migrations_dir, Path::new("migrations"));
+        assert!(!config.sqlite.strict_autoincrement);
+        assert!(config.enum_order_sensitive);
+        assert_eq!(config.identifier_case, IdentifierCase::Preserve);
+    }
+
+    /// ENUM値の並び順を無視する設定を読み込めることを確認
+    #[test]
+    fn test_config_enum_order_sensitive_opt_out() {
+        let yaml = r#"
+version: "1.0"
+dialect: postgresql
+
+environments:
+  development:
+    database: app
+
+enum_order_sensitive: false
+"#;
+
+        let config = load_config_from_yaml(yaml);
+
+        assert!(!config.enum_order_sensitive);
+        assert!(SchemaDiffOptions::from_config(&config).ignore_enum_order);
+    }
+
+    /// 識別子の大文字・小文字の規則を読み込めることを確認
+    #[test]
+    fn test_config_identifier_case() {
+        let yaml = r#"
+version: "1.0"
+dialect: postgresql
+
+environments:
+  development:
+    database: app
+
+identifier_case: upper
+"#;
+
+        let config = load_config_from_yaml(yaml);
+
+        assert_eq!(config.identifier_case, IdentifierCase::Upper);
+        assert_eq!(
+            SchemaDiffOptions::from_config(&config).identifier_case,
+            IdentifierCase::Upper
+        );
+
+        let invalid: Result<IdentifierCase, _> = serde_saphyr::from_str("camel");
+        assert!(invalid.is_err());
+    }
+
+    /// リネームのビュー定義への反映方法を読み込めることを確認
+    #[test]
+    fn test_config_view_rename_propagation() {
+        let yaml = r#"
+version: "1.0"
+dialect: postgresql
+
+environments:
+  development:
+    database: app
+"#;
+        assert_eq!(
+            load_config_from_yaml(yaml).view_rename_propagation,
+            ViewRenamePropagation::Fail
+        );
+
+        let config =
+            load_config_from_yaml(&format!("{}\nview_rename_propagation: rewrite\n", yaml));
+        assert_eq!(
+            config.view_rename_propagation,
+            ViewRenamePropagation::Rewrite
+        );
+    }
+
+    /// 削除待ちのカラムを削除できるようになるまでの日数を読み込めることを確認
+    #[test]
+    fn test_config_contract_min_age_days() {
+        let yaml = r#"
+version: "1.0"
+dialect: postgresql
+
+environments:
+  development:
+    database: app
+"#;
+        assert_eq!(
+            load_config_from_yaml(yaml).contract_min_age_days(),
+            Config::DEFAULT_CONTRACT_MIN_AGE_DAYS
+        );
+
+        let config = load_config_from_yaml(&format!("{}\ncontract_min_age_days: 3\n", yaml));
+        assert_eq!(config.contract_min_age_days(), 3);
+    }
+
+    /// 識別子に使用できる文字の設定を読み込めることを確認
+    #[test]
+    fn test_config_identifiers_allowed_characters() {
+        let yaml = r#"
+version: "1.0"
+dialect: postgresql
+
+environments:
+  development:
+    database: app
+"#;
+        let config = load_config_from_yaml(yaml);
+        assert!(config.identifiers.is_default());
+        assert!(!config.identifiers.allows('-'));
+
+        let config = load_config_from_yaml(&format!(
+            "{}\nidentifiers:\n  allowed_characters: \"$-\"\n",
+            yaml
+        ));
+        assert_eq!(config.identifiers.allowed_characters, "$-");
+        assert!(config.identifiers.allows('-'));
+        assert!(config.identifiers.allows('_'));
+        assert!(!config.identifiers.allows(';'));
+    }
+
+    /// SQLite固有の設定を読み込めることを確認
+    #[test]
+    fn test_config_sqlite_strict_autoincrement() {
+        let yaml = r#"
+version: "1.0"
+dialect: sqlite
+
+environments:
+  development:
+    database: strata.db
+
+sqlite:
+  strict_autoincrement: true
+"#;
+
+        let config = load_config_from_yaml(yaml);
+
+        assert!(config.sqlite.strict_autoincrement);
     }
 
     /// 環境変数からデータベース設定を上書きできることを確認
@@ -1,5 +1,6 @@
 // DialectSpecific カラム型のシリアライゼーション・デシリアライゼーションテスト
 
+use std::collections::BTreeMap;
 use strata::core::schema::{Column, ColumnType};
 
 #[cfg(test)]
@@ -164,6 +165,14 @@ nullable: false
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            comment: None,
         };
 
         let yaml = serde_saphyr::to_string(&column).expect("Failed to serialize");
@@ -1,9 +1,12 @@
+#![cfg(feature = "postgres")]
+
 /// DialectSpecific型のデータベース実行時エラーメッセージ伝達テスト
 ///
 /// testcontainersを使用して実際のデータベースに対して無効な型を使用し、
 /// データベースからのエラーメッセージが透過的に伝達されることを確認します。
 ///
 /// 注意: このテストはDockerが必要です。Docker未起動の場合はスキップされます。
+/// PostgreSQLドライバーを使うため、`postgres` フィーチャーが無効なビルドでは対象外です。
 #[cfg(test)]
 mod dialect_specific_database_error_tests {
     use sqlx::{Connection, PgConnection, Row};
@@ -17,6 +17,7 @@ mod error_tests {
                 table: Some("users".to_string()),
                 column: None,
                 line: Some(42),
+                file: None,
             }),
             suggestion: Some("Please write in correct YAML format".to_string()),
         };
@@ -36,6 +37,7 @@ mod error_tests {
                 table: Some("posts".to_string()),
                 column: Some("user_id".to_string()),
                 line: None,
+                file: None,
             }),
             suggestion: Some("Define table 'users'".to_string()),
         };
@@ -55,6 +57,7 @@ mod error_tests {
                 table: Some("users".to_string()),
                 column: None,
                 line: Some(10),
+                file: None,
             }),
             suggestion: None,
         };
@@ -71,6 +74,7 @@ mod error_tests {
             table: Some("users".to_string()),
             column: Some("email".to_string()),
             line: Some(25),
+            file: None,
         };
 
         assert_eq!(location.table.as_deref(), Some("users"));
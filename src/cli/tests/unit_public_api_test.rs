@@ -0,0 +1,164 @@
+/// ライブラリとして利用される公開APIのテスト
+///
+/// strata_db / strata_core を直接組み込む利用者向けに安定させている関数の
+/// パスとシグネチャを固定します。ここが壊れる変更は利用者側のビルドも壊すため、
+/// 意図した変更であればリリースノートで告知してからテストを更新してください。
+use strata_core::core::config::Dialect;
+use strata_core::core::error::ValidationResult;
+use strata_core::core::schema::{ColumnType, Constraint, Schema};
+use strata_core::core::schema_diff::ColumnChange;
+use strata_db::adapters::constraint_naming;
+use strata_db::adapters::sql_quote;
+use strata_db::services::migration_files::sql_parser;
+use strata_db::services::schema_checksum::SchemaChecksumService;
+use strata_db::{SchemaBuilder, SchemaBuilderExt};
+
+#[cfg(test)]
+mod public_api_tests {
+    use super::*;
+
+    /// ステートメント分割関数のシグネチャ
+    #[test]
+    fn test_statement_splitting_signatures() {
+        let split: fn(&str) -> Vec<String> = sql_parser::split_sql_statements;
+        let split_with_terminator: fn(&str, &str) -> Vec<String> =
+            sql_parser::split_sql_statements_with_terminator;
+
+        assert_eq!(
+            split("SELECT ';'; SELECT 2;"),
+            vec!["SELECT ';'", "SELECT 2"]
+        );
+        assert_eq!(
+            split_with_terminator("SELECT 1\nGO\nSELECT 2", "\nGO\n"),
+            vec!["SELECT 1", "SELECT 2"]
+        );
+    }
+
+    /// 識別子クォート関数のシグネチャ
+    #[test]
+    fn test_identifier_quoting_signatures() {
+        let quote: fn(Dialect, &str) -> String = sql_quote::quote_identifier;
+        let per_dialect: [fn(&str) -> String; 5] = [
+            sql_quote::quote_identifier_postgres,
+            sql_quote::quote_identifier_mysql,
+            sql_quote::quote_identifier_sqlite,
+            sql_quote::quote_table_postgres,
+            sql_quote::quote_regclass_postgres,
+        ];
+        let columns: [fn(&[String]) -> String; 3] = [
+            sql_quote::quote_columns_postgres,
+            sql_quote::quote_columns_mysql,
+            sql_quote::quote_columns_sqlite,
+        ];
+        let _: fn(&str) -> Option<char> = sql_quote::find_control_character;
+
+        assert_eq!(quote(Dialect::SQLite, "users"), per_dialect[2]("users"));
+        assert_eq!(columns[1](&["a".to_string(), "b".to_string()]), "`a`, `b`");
+    }
+
+    /// チェックサム計算のシグネチャ
+    #[test]
+    fn test_checksum_signatures() {
+        let schema_checksum: fn(&SchemaChecksumService, &Schema) -> String =
+            SchemaChecksumService::calculate_checksum;
+        let sql_checksum: fn(&SchemaChecksumService, &str) -> String =
+            SchemaChecksumService::calculate_sql_checksum;
+
+        let service = SchemaChecksumService::new();
+        assert_eq!(
+            schema_checksum(&service, &Schema::new("1.0".to_string())).len(),
+            64
+        );
+        assert_eq!(
+            sql_checksum(&service, "SELECT 1;\r\n"),
+            sql_checksum(&service, "SELECT 1;\n")
+        );
+    }
+
+    /// 制約名生成関数のシグネチャと生成される名前
+    ///
+    /// 生成される名前は既存のマイグレーションから参照されるため、値も固定します。
+    #[test]
+    fn test_constraint_naming_signatures() {
+        let generic: fn(&str, &str) -> String = constraint_naming::generate_constraint_name;
+        let fk: fn(&str, &[String], &str) -> String =
+            constraint_naming::generate_fk_constraint_name;
+        let uq: fn(&str, &[String]) -> String = constraint_naming::generate_uq_constraint_name;
+        let ck: fn(&str, &[String]) -> String = constraint_naming::generate_ck_constraint_name;
+
+        let columns = ["user_id".to_string()];
+        assert_eq!(generic("ix", "posts_user_id"), "ix_posts_user_id");
+        assert_eq!(fk("posts", &columns, "users"), "fk_posts_user_id_users");
+        assert_eq!(uq("posts", &columns), "uq_posts_user_id");
+        assert_eq!(ck("posts", &columns), "ck_posts_user_id");
+
+        let long_name = fk("a".repeat(40).as_str(), &columns, &"b".repeat(40));
+        assert!(long_name.len() <= constraint_naming::MAX_IDENTIFIER_LENGTH);
+        assert_eq!(
+            long_name,
+            fk("a".repeat(40).as_str(), &columns, &"b".repeat(40))
+        );
+    }
+
+    /// 移動前のパス（sql_generator）の制約名生成関数は非推奨として残す
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_constraint_naming_paths() {
+        use strata_db::adapters::sql_generator;
+
+        let columns = ["user_id".to_string()];
+        assert_eq!(
+            sql_generator::generate_fk_constraint_name("posts", &columns, "users"),
+            constraint_naming::generate_fk_constraint_name("posts", &columns, "users")
+        );
+        assert_eq!(
+            sql_generator::generate_uq_constraint_name("posts", &columns),
+            constraint_naming::generate_uq_constraint_name("posts", &columns)
+        );
+        assert_eq!(
+            sql_generator::generate_ck_constraint_name("posts", &columns),
+            constraint_naming::generate_ck_constraint_name("posts", &columns)
+        );
+    }
+
+    /// スキーマビルダーのパスと検証付きビルドのシグネチャ
+    #[test]
+    fn test_schema_builder_signatures() {
+        let build: fn(SchemaBuilder) -> Result<Schema, ValidationResult> =
+            <SchemaBuilder as SchemaBuilderExt>::build;
+        let build_for_dialect: fn(SchemaBuilder, Dialect) -> Result<Schema, ValidationResult> =
+            <SchemaBuilder as SchemaBuilderExt>::build_for_dialect;
+        let _: fn(SchemaBuilder) -> Schema = strata_core::SchemaBuilder::build_unvalidated;
+
+        let users = || {
+            SchemaBuilder::new("1.0").table("users", |t| {
+                t.integer("id").primary_key();
+            })
+        };
+        assert!(build(users()).unwrap().has_table("users"));
+        assert!(build_for_dialect(users(), Dialect::SQLite).is_ok());
+        assert!(build(SchemaBuilder::new("1.0").table("empty", |_| {})).is_err());
+    }
+
+    /// 拡張予定の列挙型は、クレート外ではワイルドカード付きで照合する
+    #[test]
+    fn test_non_exhaustive_enums_match_with_wildcard() {
+        let column_type = ColumnType::TEXT;
+        let sql_type = match column_type {
+            ColumnType::TEXT => "TEXT",
+            _ => "other",
+        };
+        assert_eq!(sql_type, "TEXT");
+
+        let constraint = Constraint::UNIQUE {
+            columns: vec!["email".to_string()],
+        };
+        assert_eq!(constraint.kind(), "UNIQUE");
+
+        let change = ColumnChange::TypeChanged {
+            old_type: "TEXT".to_string(),
+            new_type: "VARCHAR(255)".to_string(),
+        };
+        assert!(!matches!(change, ColumnChange::NullableChanged { .. }));
+    }
+}
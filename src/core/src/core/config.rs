@@ -2,10 +2,12 @@
 //
 // プロジェクトの設定ファイル（YAML形式）の読み込み、検証、
 // 環境別のデータベース接続設定の管理を行います。
+// 環境は `extends` で別の環境の設定を引き継げます（読み込み時に解決）。
 
 use crate::core::error::ConfigError;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::core::server_version::ServerVersion;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// SSL接続モード
@@ -57,6 +59,18 @@ impl std::fmt::Display for Dialect {
 }
 
 impl Dialect {
+    /// CLI引数などで指定された名前からDialectを取得
+    ///
+    /// `postgres` は `postgresql` の別名として扱います。
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "postgresql" | "postgres" => Some(Dialect::PostgreSQL),
+            "mysql" => Some(Dialect::MySQL),
+            "sqlite" => Some(Dialect::SQLite),
+            _ => None,
+        }
+    }
+
     /// Dialectに応じたデフォルトポートを返す
     ///
     /// - PostgreSQL: 5432
@@ -69,6 +83,19 @@ impl Dialect {
             Dialect::SQLite => None,
         }
     }
+
+    /// TIMESTAMP / TIME の秒の小数部の桁数を省略したときのデフォルト
+    ///
+    /// - PostgreSQL: 6
+    /// - MySQL: 0
+    /// - SQLite: None（TEXT として保存するため精度を持たない）
+    pub fn default_datetime_precision(&self) -> Option<u8> {
+        match self {
+            Dialect::PostgreSQL => Some(6),
+            Dialect::MySQL => Some(0),
+            Dialect::SQLite => None,
+        }
+    }
 }
 
 /// プロジェクト設定
@@ -89,7 +116,572 @@ pub struct Config {
     pub migrations_dir: PathBuf,
 
     /// 環境別のデータベース設定
+    ///
+    /// 各環境は `extends: <環境名>` で別の環境の設定を引き継ぎ、一部の項目だけを上書きできます。
+    /// 継承は読み込み時に解決され、ここには解決済みの設定が入ります。
+    #[serde(deserialize_with = "deserialize_environments")]
     pub environments: HashMap<String, DatabaseConfig>,
+
+    /// SQLite固有の設定
+    #[serde(default, skip_serializing_if = "SqliteConfig::is_default")]
+    pub sqlite: SqliteConfig,
+
+    /// MySQL固有の設定
+    #[serde(default, skip_serializing_if = "MysqlConfig::is_default")]
+    pub mysql: MysqlConfig,
+
+    /// PostgreSQL固有の設定
+    #[serde(default, skip_serializing_if = "PostgresConfig::is_default")]
+    pub postgres: PostgresConfig,
+
+    /// マイグレーションの規模に関するポリシー
+    #[serde(default, skip_serializing_if = "PolicyConfig::is_default")]
+    pub policy: PolicyConfig,
+
+    /// 生成SQLファイル（up.sql / down.sql）の出力形式
+    #[serde(default, skip_serializing_if = "SqlOutputConfig::is_default")]
+    pub sql_output: SqlOutputConfig,
+
+    /// 適用したup.sql / down.sqlをschema_migrationsに保存するか（デフォルト: false）
+    ///
+    /// 有効にすると、apply時に履歴テーブルの `up_sql` / `down_sql` カラムへ
+    /// SQLを保存し、`strata repair --export-applied-sql` で復元できるようにします。
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub store_applied_sql: bool,
+
+    /// 未来の日時のバージョンを許容する幅（分）（デフォルト: 10分）
+    ///
+    /// 現在時刻よりこの幅以上先のタイムスタンプを持つマイグレーションは、
+    /// 時刻のずれたマシンで生成されたものとして status / generate / apply で警告します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub future_version_tolerance_minutes: Option<u64>,
+
+    /// テーブルに必須のメタデータキー（例: `[owner]`）
+    ///
+    /// 指定したキーが `metadata` にないテーブルを validate / generate でエラーにします。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_metadata: Vec<String>,
+
+    /// 生成後にまだ適用していないマイグレーションがある間は generate を拒否するか（デフォルト: false）
+    ///
+    /// generate が記録し apply / rollback が更新する `.pending_migrations.yaml` で判定します。
+    /// `strata generate --allow-stacked` で1回だけ許可できます。
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub require_clean_pending: bool,
+
+    /// PostgreSQLのENUM値の並び順を差分として扱うか（デフォルト: true）
+    ///
+    /// 値の並び順は ORDER BY や大小比較の結果に影響するため、並び替えのみの変更も
+    /// ENUMの再作成として検出します。`false` にすると並び順の違いを無視します。
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enum_order_sensitive: bool,
+
+    /// `strata dev migrate` と `strata reset` の実行を拒否する環境（例: `[staging, production]`）
+    ///
+    /// 開発ループ用のコマンドが共有環境のデータベースを誤って変更しないようにします。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_environments: Vec<String>,
+
+    /// apply時にSQLファイルをストリーミングで実行するサイズの閾値（バイト）（デフォルト: 16MiB）
+    ///
+    /// これより大きい up.sql はファイル全体を読み込まず、少しずつ読みながら
+    /// ステートメントを分割・実行します。小さいファイルは従来どおりメモリ上で処理します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_sql_threshold_bytes: Option<u64>,
+
+    /// テーブル名・外部キーカラム名の命名規約チェック（デフォルト: 無効）
+    #[serde(default, skip_serializing_if = "NamingConfig::is_default")]
+    pub naming: NamingConfig,
+
+    /// テーブル名・カラム名などの識別子の検証設定
+    #[serde(default, skip_serializing_if = "IdentifierConfig::is_default")]
+    pub identifiers: IdentifierConfig,
+
+    /// 生成SQLの識別子の大文字・小文字（デフォルト: preserve）
+    ///
+    /// テーブル・カラム・インデックス・制約名を、クォートする前にこの規則で変換します。
+    /// スキーマ定義ファイルの解析時には変換せず、差分検出とSQL生成の時点で適用します。
+    #[serde(default, skip_serializing_if = "IdentifierCase::is_preserve")]
+    pub identifier_case: IdentifierCase,
+
+    /// コマンド実行時に新しいバージョンのstrataが公開されていないか確認するか（デフォルト: false）
+    ///
+    /// 確認は1日1回までで、結果は `.strata/update-check.json` にキャッシュします。
+    /// ネットワークに接続できない場合も、コマンドの実行結果には影響しません。
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub update_check: bool,
+
+    /// テーブル・カラムのリネームを参照元のビュー定義へ反映する方法（デフォルト: fail）
+    ///
+    /// ビュー定義が旧名を参照したままの場合、`fail` は generate をエラーにして対象のビューを一覧表示し、
+    /// `rewrite` はビュー定義中の識別子を新しい名前に書き換えて再作成するSQLを生成します。
+    #[serde(default, skip_serializing_if = "ViewRenamePropagation::is_fail")]
+    pub view_rename_propagation: ViewRenamePropagation,
+
+    /// `strata contract` で削除できるようになるまでの、削除待ちになってからの日数（デフォルト: 14日）
+    ///
+    /// `lifecycle: contract_pending` のカラムは、この日数が経過するまで削除対象になりません。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_min_age_days: Option<u64>,
+
+    /// apply 時に一時的なエラー（デッドロック・シリアライズ失敗・ロック待ち・SQLITE_BUSY）で
+    /// 失敗したステートメントを再試行する設定
+    #[serde(default, skip_serializing_if = "StatementRetryConfig::is_default")]
+    pub statement_retry: StatementRetryConfig,
+}
+
+/// SQLite固有の設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqliteConfig {
+    /// AUTOINCREMENTキーワードの有無を厳密に比較するか（デフォルト: false）
+    ///
+    /// SQLiteの INTEGER PRIMARY KEY は ROWID のエイリアスとなるため、
+    /// デフォルトではキーワードの有無による auto_increment の差分を無視します。
+    #[serde(default)]
+    pub strict_autoincrement: bool,
+}
+
+impl SqliteConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 強制されないCHECK制約を検出した場合の動作
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnenforcedCheckAction {
+    /// 警告を表示して続行（デフォルト）
+    #[default]
+    Warn,
+    /// エラーとして中断
+    Error,
+}
+
+/// MySQL固有の設定
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MysqlConfig {
+    /// サポート対象とする最も古いサーバーバージョン（例: "5.7"）
+    ///
+    /// 8.0.16 未満の場合、validate時にCHECK制約が強制されない旨を警告します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_version: Option<String>,
+
+    /// apply時に接続先サーバーがCHECK制約を強制しない場合の動作（デフォルト: warn）
+    #[serde(default)]
+    pub unenforced_check: UnenforcedCheckAction,
+}
+
+impl MysqlConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 設定の妥当性を検証
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(version) = &self.minimum_version {
+            if ServerVersion::parse(version).is_none() {
+                return Err(ConfigError::InvalidMysqlMinimumVersion {
+                    version: version.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// `minimum_version` を解析したバージョン
+    pub fn parsed_minimum_version(&self) -> Option<ServerVersion> {
+        self.minimum_version
+            .as_deref()
+            .and_then(ServerVersion::parse)
+    }
+}
+
+/// PostgreSQL固有の設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostgresConfig {
+    /// イントロスペクション（export / diff / status など）の対象とするスキーマ（デフォルト: `[public]`）
+    ///
+    /// `public` 以外のスキーマのテーブルは `schema.table` 形式の名前で扱います。
+    #[serde(default = "default_postgres_schemas")]
+    pub schemas: Vec<String>,
+}
+
+impl Default for PostgresConfig {
+    fn default() -> Self {
+        Self {
+            schemas: default_postgres_schemas(),
+        }
+    }
+}
+
+impl PostgresConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 設定の妥当性を検証
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.schemas.is_empty() {
+            return Err(ConfigError::EmptyPostgresSchemas);
+        }
+        if let Some(schema) = self
+            .schemas
+            .iter()
+            .find(|schema| schema.is_empty() || schema.contains('.'))
+        {
+            return Err(ConfigError::InvalidPostgresSchema {
+                schema: schema.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn default_postgres_schemas() -> Vec<String> {
+    vec![DEFAULT_POSTGRES_SCHEMA.to_string()]
+}
+
+/// PostgreSQLのデフォルトスキーマ名
+pub const DEFAULT_POSTGRES_SCHEMA: &str = "public";
+
+/// ステートメント単位の再試行設定
+///
+/// 再試行の待ち時間は `backoff_ms` から始めて、再試行のたびに2倍（`max_backoff_ms` まで）にします。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementRetryConfig {
+    /// 1つのステートメントを実行する最大回数（デフォルト: 3、1 の場合は再試行しない）
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+
+    /// 最初の再試行までの待ち時間（ミリ秒、デフォルト: 200）
+    #[serde(default = "default_retry_backoff_ms")]
+    pub backoff_ms: u64,
+
+    /// 再試行までの待ち時間の上限（ミリ秒、デフォルト: 5000）
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for StatementRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            backoff_ms: default_retry_backoff_ms(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+        }
+    }
+}
+
+impl StatementRetryConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 設定値を検証
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.max_attempts == 0 {
+            return Err(ConfigError::InvalidStatementRetryAttempts);
+        }
+        Ok(())
+    }
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    200
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    5000
+}
+
+/// マイグレーションポリシー設定
+///
+/// generate時に生成されるマイグレーションの規模を制限します。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    /// 1マイグレーションあたりの最大ステートメント数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_statements: Option<usize>,
+
+    /// 1マイグレーションで変更できる最大テーブル数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tables_touched: Option<usize>,
+
+    /// 破壊的変更と追加系の変更の混在を禁止するか
+    #[serde(default)]
+    pub forbid_mixed_destructive_and_additive: bool,
+}
+
+impl PolicyConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// テーブル名の単数形・複数形の規約
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableNameStyle {
+    /// 複数形（例: `users`）（デフォルト）
+    #[default]
+    Plural,
+    /// 単数形（例: `user`）
+    Singular,
+    /// チェックしない
+    Any,
+}
+
+/// 識別子の検証設定
+///
+/// テーブル名・カラム名・インデックス名などは、英数字とアンダースコアのみを使用でき、
+/// 数字で始めることはできません。それ以外の文字が必要な場合は `allowed_characters` に追加します。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentifierConfig {
+    /// 英数字とアンダースコアに加えて識別子に使用できる文字（例: `"$-"`）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub allowed_characters: String,
+}
+
+impl IdentifierConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 識別子に使用できる文字かどうか
+    pub fn allows(&self, c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || self.allowed_characters.contains(c)
+    }
+}
+
+/// 命名規約チェックの設定
+///
+/// 有効にすると、validate / generate 時にテーブル名と外部キーカラム名の規約違反を警告します。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamingConfig {
+    /// 命名規約チェックを有効にするか（デフォルト: false）
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 外部キーカラム名のテンプレート（デフォルト: `{singular_table}_id`）
+    ///
+    /// `{table}`（参照先テーブル名）、`{singular_table}`（その単数形）、
+    /// `{column}`（参照先カラム名）を使用できます。
+    #[serde(default = "default_foreign_key_column_template")]
+    pub foreign_key_column: String,
+
+    /// テーブル名の単数形・複数形の規約（デフォルト: plural）
+    #[serde(default)]
+    pub table_names: TableNameStyle,
+
+    /// 中間テーブル名が参照先テーブル名をアルファベット順に連結した形か確認するか（デフォルト: true）
+    #[serde(default = "default_true")]
+    pub junction_tables: bool,
+
+    /// 単数形から複数形への変換の例外（例: `{ person: people }`）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inflections: BTreeMap<String, String>,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            foreign_key_column: default_foreign_key_column_template(),
+            table_names: TableNameStyle::default(),
+            junction_tables: true,
+            inflections: BTreeMap::new(),
+        }
+    }
+}
+
+impl NamingConfig {
+    /// テンプレートで使用できるプレースホルダー
+    pub const PLACEHOLDERS: [&'static str; 3] = ["{table}", "{singular_table}", "{column}"];
+
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 設定の妥当性を検証
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        // 未知のプレースホルダーや、参照先を含まない（全カラムが同名になる）テンプレートを拒否する
+        let template = &self.foreign_key_column;
+        let stripped = Self::PLACEHOLDERS
+            .iter()
+            .fold(template.clone(), |acc, placeholder| {
+                acc.replace(placeholder, "")
+            });
+        if stripped.contains('{') || stripped.contains('}') || stripped == *template {
+            return Err(ConfigError::InvalidForeignKeyColumnTemplate {
+                template: template.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 生成SQLの識別子の大文字・小文字の規則
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierCase {
+    /// スキーマ定義のまま（デフォルト）
+    #[default]
+    Preserve,
+    /// すべて小文字
+    Lower,
+    /// すべて大文字
+    Upper,
+}
+
+impl IdentifierCase {
+    /// デフォルト（preserve）かどうか（シリアライズ省略判定用）
+    pub fn is_preserve(&self) -> bool {
+        *self == IdentifierCase::Preserve
+    }
+
+    /// 識別子をこの規則で変換
+    pub fn apply(&self, identifier: &str) -> String {
+        match self {
+            IdentifierCase::Preserve => identifier.to_string(),
+            IdentifierCase::Lower => identifier.to_lowercase(),
+            IdentifierCase::Upper => identifier.to_uppercase(),
+        }
+    }
+
+    /// 識別子がこの規則に従っているか
+    pub fn matches(&self, identifier: &str) -> bool {
+        self.apply(identifier) == identifier
+    }
+
+    /// 設定ファイルでの表記
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierCase::Preserve => "preserve",
+            IdentifierCase::Lower => "lower",
+            IdentifierCase::Upper => "upper",
+        }
+    }
+}
+
+impl std::fmt::Display for IdentifierCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// リネームされたテーブル・カラムを参照するビューの扱い
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewRenamePropagation {
+    /// 旧名を参照するビューを一覧表示してエラーにする（デフォルト）
+    #[default]
+    Fail,
+    /// ビュー定義の識別子を書き換えて再作成する
+    Rewrite,
+}
+
+impl ViewRenamePropagation {
+    /// デフォルト（fail）かどうか（シリアライズ省略判定用）
+    pub fn is_fail(&self) -> bool {
+        *self == ViewRenamePropagation::Fail
+    }
+}
+
+/// 改行コード
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// LF（デフォルト）
+    #[default]
+    Lf,
+    /// CRLF
+    Crlf,
+}
+
+impl LineEnding {
+    /// 改行コードの文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+/// 生成SQLファイルの出力設定
+///
+/// up.sql / down.sql を書き出す際の改行コード、BOM、ステートメント終端文字列を指定します。
+/// チェックサムはスキーマから計算されるため、出力設定によって変化しません。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlOutputConfig {
+    /// 改行コード（デフォルト: lf）
+    #[serde(default)]
+    pub line_endings: LineEnding,
+
+    /// UTF-8 BOMを付与するか（デフォルト: false）
+    #[serde(default)]
+    pub bom: bool,
+
+    /// ステートメント終端文字列（デフォルト: ";"）
+    #[serde(default = "default_statement_terminator")]
+    pub statement_terminator: String,
+}
+
+impl Default for SqlOutputConfig {
+    fn default() -> Self {
+        Self {
+            line_endings: LineEnding::default(),
+            bom: false,
+            statement_terminator: default_statement_terminator(),
+        }
+    }
+}
+
+impl SqlOutputConfig {
+    /// デフォルト設定かどうか（シリアライズ省略判定用）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 設定の妥当性を検証
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.statement_terminator.trim().is_empty() {
+            return Err(ConfigError::EmptyStatementTerminator);
+        }
+        Ok(())
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_foreign_key_column_template() -> String {
+    "{singular_table}_id".to_string()
+}
+
+fn default_statement_terminator() -> String {
+    ";".to_string()
 }
 
 fn default_schema_dir() -> PathBuf {
@@ -104,6 +696,35 @@ impl Config {
     /// デフォルトの設定ファイルパス
     pub const DEFAULT_CONFIG_PATH: &'static str = crate::core::naming::CONFIG_FILE;
 
+    /// 未来の日時のバージョンを許容する幅のデフォルト（分）
+    pub const DEFAULT_FUTURE_VERSION_TOLERANCE_MINUTES: u64 = 10;
+
+    /// 未来の日時のバージョンを許容する幅
+    pub fn future_version_tolerance(&self) -> chrono::Duration {
+        let minutes = self
+            .future_version_tolerance_minutes
+            .unwrap_or(Self::DEFAULT_FUTURE_VERSION_TOLERANCE_MINUTES);
+        chrono::Duration::minutes(i64::try_from(minutes).unwrap_or(i64::MAX / 60_000))
+    }
+
+    /// 削除待ちのカラムを削除できるようになるまでの日数のデフォルト
+    pub const DEFAULT_CONTRACT_MIN_AGE_DAYS: u64 = 14;
+
+    /// 削除待ちのカラムを削除できるようになるまでの日数
+    pub fn contract_min_age_days(&self) -> u64 {
+        self.contract_min_age_days
+            .unwrap_or(Self::DEFAULT_CONTRACT_MIN_AGE_DAYS)
+    }
+
+    /// SQLファイルをストリーミングで実行するサイズの閾値のデフォルト（バイト）
+    pub const DEFAULT_STREAM_SQL_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+
+    /// SQLファイルをストリーミングで実行するサイズの閾値（バイト）
+    pub fn stream_sql_threshold(&self) -> u64 {
+        self.stream_sql_threshold_bytes
+            .unwrap_or(Self::DEFAULT_STREAM_SQL_THRESHOLD_BYTES)
+    }
+
     /// 指定された環境のデータベース設定を取得
     pub fn get_database_config(&self, environment: &str) -> Result<DatabaseConfig, ConfigError> {
         self.environments.get(environment).cloned().ok_or_else(|| {
@@ -121,6 +742,12 @@ impl Config {
             return Err(ConfigError::MissingVersion);
         }
 
+        self.sql_output.validate()?;
+        self.mysql.validate()?;
+        self.postgres.validate()?;
+        self.naming.validate()?;
+        self.statement_retry.validate()?;
+
         // 環境設定チェック
         if self.environments.is_empty() {
             return Err(ConfigError::NoEnvironments);
@@ -178,10 +805,33 @@ pub struct DatabaseConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_connections: Option<u32>,
 
+    /// コネクション取得タイムアウト（秒、未設定の場合は timeout を使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acquire_timeout: Option<u64>,
+
     /// アイドルタイムアウト（秒）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub idle_timeout: Option<u64>,
 
+    /// 最初の接続が確立するまで待つ時間（秒、未設定の場合は再試行しない）
+    ///
+    /// 接続拒否などの一時的なネットワークエラーの間、指数バックオフで接続を再試行します。
+    /// 認証エラーや存在しないデータベースへの接続は再試行しません。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_timeout: Option<u64>,
+
+    /// apply でロックの取得を待つ時間の上限（秒、未設定の場合はデータベースの設定に従う）
+    ///
+    /// PostgreSQL は `lock_timeout`、MySQL は `lock_wait_timeout` として設定します。SQLite では無視します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_timeout: Option<u64>,
+
+    /// apply で1つのステートメントを実行する時間の上限（秒、未設定の場合はデータベースの設定に従う）
+    ///
+    /// PostgreSQL は `statement_timeout`、MySQL は `max_execution_time` として設定します。SQLite では無視します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_timeout: Option<u64>,
+
     /// 追加接続オプション（クエリパラメータとして付与）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub options: Option<HashMap<String, String>>,
@@ -191,6 +841,130 @@ fn default_host() -> String {
     "localhost".to_string()
 }
 
+/// 継承を解決する前の環境定義
+///
+/// 明示的に指定された項目だけを持ち、指定されなかった項目は継承元の値、
+/// 継承元にもなければデフォルト値になります。
+#[derive(Debug, Clone, Default, Deserialize)]
+struct EnvironmentDefinition {
+    extends: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    database: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    timeout: Option<u64>,
+    ssl_mode: Option<SslMode>,
+    max_connections: Option<u32>,
+    min_connections: Option<u32>,
+    acquire_timeout: Option<u64>,
+    idle_timeout: Option<u64>,
+    wait_timeout: Option<u64>,
+    lock_timeout: Option<u64>,
+    statement_timeout: Option<u64>,
+    options: Option<HashMap<String, String>>,
+}
+
+impl EnvironmentDefinition {
+    /// `child` で明示的に指定された項目で上書きする
+    fn overlay(self, child: &EnvironmentDefinition) -> Self {
+        let child = child.clone();
+        Self {
+            extends: child.extends,
+            host: child.host.or(self.host),
+            port: child.port.or(self.port),
+            database: child.database.or(self.database),
+            user: child.user.or(self.user),
+            password: child.password.or(self.password),
+            timeout: child.timeout.or(self.timeout),
+            ssl_mode: child.ssl_mode.or(self.ssl_mode),
+            max_connections: child.max_connections.or(self.max_connections),
+            min_connections: child.min_connections.or(self.min_connections),
+            acquire_timeout: child.acquire_timeout.or(self.acquire_timeout),
+            idle_timeout: child.idle_timeout.or(self.idle_timeout),
+            wait_timeout: child.wait_timeout.or(self.wait_timeout),
+            lock_timeout: child.lock_timeout.or(self.lock_timeout),
+            statement_timeout: child.statement_timeout.or(self.statement_timeout),
+            options: child.options.or(self.options),
+        }
+    }
+}
+
+/// 環境定義の `extends` を解決する
+///
+/// 継承元から順に上書きするため、優先順位は「明示的な指定 > 継承した値 > デフォルト値」になります。
+/// 存在しない環境の継承と循環した `extends` はエラーにします。
+fn resolve_environments(
+    definitions: &HashMap<String, EnvironmentDefinition>,
+) -> Result<HashMap<String, DatabaseConfig>, ConfigError> {
+    let mut resolved = HashMap::new();
+    for name in definitions.keys() {
+        // 自身から継承元へ辿る
+        let mut chain = vec![name.as_str()];
+        let mut current = name.as_str();
+        while let Some(parent) = definitions[current].extends.as_deref() {
+            if !definitions.contains_key(parent) {
+                return Err(ConfigError::UnknownExtendsEnvironment {
+                    environment: current.to_string(),
+                    extends: parent.to_string(),
+                });
+            }
+            if chain.contains(&parent) {
+                chain.push(parent);
+                return Err(ConfigError::CircularExtends {
+                    chain: chain.join(" -> "),
+                });
+            }
+            chain.push(parent);
+            current = parent;
+        }
+
+        let definition = chain
+            .iter()
+            .rev()
+            .fold(EnvironmentDefinition::default(), |acc, env| {
+                acc.overlay(&definitions[*env])
+            });
+        let database = definition
+            .database
+            .ok_or_else(|| ConfigError::InvalidEnvironment {
+                environment: name.clone(),
+                source: Box::new(ConfigError::MissingDatabaseName),
+            })?;
+        resolved.insert(
+            name.clone(),
+            DatabaseConfig {
+                host: definition.host.unwrap_or_else(default_host),
+                port: definition.port,
+                database,
+                user: definition.user,
+                password: definition.password,
+                timeout: definition.timeout,
+                ssl_mode: definition.ssl_mode,
+                max_connections: definition.max_connections,
+                min_connections: definition.min_connections,
+                acquire_timeout: definition.acquire_timeout,
+                idle_timeout: definition.idle_timeout,
+                wait_timeout: definition.wait_timeout,
+                lock_timeout: definition.lock_timeout,
+                statement_timeout: definition.statement_timeout,
+                options: definition.options,
+            },
+        );
+    }
+    Ok(resolved)
+}
+
+fn deserialize_environments<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, DatabaseConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let definitions = HashMap::<String, EnvironmentDefinition>::deserialize(deserializer)?;
+    resolve_environments(&definitions).map_err(serde::de::Error::custom)
+}
+
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
@@ -203,7 +977,11 @@ impl Default for DatabaseConfig {
             ssl_mode: None,
             max_connections: None,
             min_connections: None,
+            acquire_timeout: None,
             idle_timeout: None,
+            wait_timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
             options: None,
         }
     }
@@ -224,6 +1002,16 @@ impl DatabaseConfig {
         if self.database.is_empty() {
             return Err(ConfigError::MissingDatabaseName);
         }
+        for (name, value) in [
+            ("lock_timeout", self.lock_timeout),
+            ("statement_timeout", self.statement_timeout),
+        ] {
+            if value == Some(0) {
+                return Err(ConfigError::ZeroSessionTimeout {
+                    name: name.to_string(),
+                });
+            }
+        }
 
         Ok(())
     }
@@ -240,6 +1028,15 @@ mod tests {
         assert_eq!(Dialect::SQLite.to_string(), "sqlite");
     }
 
+    #[test]
+    fn test_dialect_from_name() {
+        assert_eq!(Dialect::from_name("postgresql"), Some(Dialect::PostgreSQL));
+        assert_eq!(Dialect::from_name("postgres"), Some(Dialect::PostgreSQL));
+        assert_eq!(Dialect::from_name("mysql"), Some(Dialect::MySQL));
+        assert_eq!(Dialect::from_name("sqlite"), Some(Dialect::SQLite));
+        assert_eq!(Dialect::from_name("oracle"), None);
+    }
+
     #[test]
     fn test_dialect_default_port() {
         assert_eq!(Dialect::PostgreSQL.default_port(), Some(5432));
@@ -273,6 +1070,158 @@ mod tests {
         assert_eq!(config.resolved_port(Dialect::SQLite), 0);
     }
 
+    #[test]
+    fn test_sql_output_config_defaults() {
+        let config = SqlOutputConfig::default();
+        assert_eq!(config.line_endings, LineEnding::Lf);
+        assert!(!config.bom);
+        assert_eq!(config.statement_terminator, ";");
+        assert!(config.is_default());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_sql_output_config_rejects_empty_terminator() {
+        let config = SqlOutputConfig {
+            statement_terminator: "  ".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::EmptyStatementTerminator)
+        ));
+    }
+
+    #[test]
+    fn test_mysql_config_minimum_version() {
+        let config = MysqlConfig {
+            minimum_version: Some("5.7".to_string()),
+            unenforced_check: UnenforcedCheckAction::Error,
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.parsed_minimum_version(),
+            Some(ServerVersion::mysql(5, 7, 0))
+        );
+
+        let invalid = MysqlConfig {
+            minimum_version: Some("latest".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(ConfigError::InvalidMysqlMinimumVersion { .. })
+        ));
+        assert!(MysqlConfig::default().is_default());
+    }
+
+    #[test]
+    fn test_postgres_config_schemas() {
+        let config = PostgresConfig::default();
+        assert_eq!(config.schemas, vec!["public".to_string()]);
+        assert!(config.is_default());
+        assert!(config.validate().is_ok());
+
+        let config: PostgresConfig = serde_saphyr::from_str("schemas: [public, app]").unwrap();
+        assert_eq!(
+            config.schemas,
+            vec!["public".to_string(), "app".to_string()]
+        );
+        assert!(!config.is_default());
+
+        let empty = PostgresConfig { schemas: vec![] };
+        assert!(matches!(
+            empty.validate(),
+            Err(ConfigError::EmptyPostgresSchemas)
+        ));
+        let dotted = PostgresConfig {
+            schemas: vec!["a.b".to_string()],
+        };
+        assert!(matches!(
+            dotted.validate(),
+            Err(ConfigError::InvalidPostgresSchema { .. })
+        ));
+    }
+
+    #[test]
+    fn test_naming_config_foreign_key_column_template() {
+        let config = NamingConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.foreign_key_column, "{singular_table}_id");
+        assert!(config.is_default());
+        assert!(config.validate().is_ok());
+
+        let custom = NamingConfig {
+            foreign_key_column: "fk_{table}_{column}".to_string(),
+            ..Default::default()
+        };
+        assert!(custom.validate().is_ok());
+
+        for template in ["{tbl}_id", "owner_id", "{table}_{id}"] {
+            let invalid = NamingConfig {
+                foreign_key_column: template.to_string(),
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    invalid.validate(),
+                    Err(ConfigError::InvalidForeignKeyColumnTemplate { .. })
+                ),
+                "{}",
+                template
+            );
+        }
+    }
+
+    #[test]
+    fn test_statement_retry_config() {
+        let config = StatementRetryConfig::default();
+        assert_eq!(config.max_attempts, 3);
+        assert_eq!(config.backoff_ms, 200);
+        assert_eq!(config.max_backoff_ms, 5000);
+        assert!(config.is_default());
+        assert!(config.validate().is_ok());
+
+        let config: StatementRetryConfig = serde_saphyr::from_str("max_attempts: 5").unwrap();
+        assert_eq!(config.max_attempts, 5);
+        assert_eq!(config.backoff_ms, 200);
+        assert!(!config.is_default());
+
+        let disabled = StatementRetryConfig {
+            max_attempts: 1,
+            ..Default::default()
+        };
+        assert!(disabled.validate().is_ok());
+        let invalid = StatementRetryConfig {
+            max_attempts: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            invalid.validate(),
+            Err(ConfigError::InvalidStatementRetryAttempts)
+        ));
+    }
+
+    #[test]
+    fn test_database_config_rejects_zero_session_timeouts() {
+        let config = DatabaseConfig {
+            database: "app".to_string(),
+            lock_timeout: Some(5),
+            statement_timeout: Some(600),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let zero = DatabaseConfig {
+            lock_timeout: Some(0),
+            ..config
+        };
+        assert!(matches!(
+            zero.validate(),
+            Err(ConfigError::ZeroSessionTimeout { name }) if name == "lock_timeout"
+        ));
+    }
+
     #[test]
     fn test_explicit_port_5432_for_mysql_not_overwritten() {
         // ユーザーが意図的にMySQLにポート5432を設定した場合、上書きされない
@@ -284,4 +1233,107 @@ mod tests {
 
         assert_eq!(config.resolved_port(Dialect::MySQL), 5432);
     }
+
+    fn load_environments(yaml: &str) -> Result<HashMap<String, DatabaseConfig>, ConfigError> {
+        let definitions: HashMap<String, EnvironmentDefinition> =
+            serde_saphyr::from_str(yaml).unwrap();
+        resolve_environments(&definitions)
+    }
+
+    #[test]
+    fn test_environment_extends_overrides_individual_fields() {
+        let environments = load_environments(
+            r#"
+base:
+  host: db.internal
+  port: 5433
+  user: app
+  database: app_dev
+  timeout: 30
+staging:
+  extends: base
+  database: app_staging
+  password: staging_pass
+production:
+  extends: staging
+  database: app_production
+  timeout: 60
+"#,
+        )
+        .unwrap();
+
+        let production = &environments["production"];
+        // 明示的な指定 > 継承した値
+        assert_eq!(production.database, "app_production");
+        assert_eq!(production.timeout, Some(60));
+        // 継承元の継承元から引き継ぐ
+        assert_eq!(production.host, "db.internal");
+        assert_eq!(production.port, Some(5433));
+        assert_eq!(production.user.as_deref(), Some("app"));
+        assert_eq!(production.password.as_deref(), Some("staging_pass"));
+        // 継承元は変わらない
+        assert_eq!(environments["base"].password, None);
+    }
+
+    #[test]
+    fn test_environment_extends_falls_back_to_defaults() {
+        let environments = load_environments(
+            r#"
+base:
+  user: app
+  database: app_dev
+test:
+  extends: base
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(environments["test"].host, "localhost");
+        assert_eq!(environments["test"].database, "app_dev");
+        assert_eq!(environments["test"].port, None);
+    }
+
+    #[test]
+    fn test_environment_extends_rejects_unknown_and_circular() {
+        let err = load_environments("staging:\n  extends: base\n  database: db\n").unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::UnknownExtendsEnvironment { ref environment, ref extends }
+                if environment == "staging" && extends == "base"
+        ));
+
+        let err = load_environments(
+            "a:\n  extends: b\n  database: a\nb:\n  extends: c\n  database: b\nc:\n  extends: a\n  database: c\n",
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.starts_with("Circular `extends` between environments: "),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("a -> b -> c -> a")
+                || message.contains("b -> c -> a -> b")
+                || message.contains("c -> a -> b -> c"),
+            "{}",
+            message
+        );
+
+        let err = load_environments("a:\n  extends: a\n  database: a\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Circular `extends` between environments: a -> a"
+        );
+    }
+
+    #[test]
+    fn test_environment_without_database_is_rejected() {
+        let err = load_environments("base:\n  host: db\nstaging:\n  extends: base\n").unwrap_err();
+        assert!(
+            err.to_string().contains("Database name is not specified"),
+            "{}",
+            err
+        );
+    }
 }
@@ -23,6 +23,10 @@ pub struct DestructiveChangeReport {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub enums_recreated: Vec<String>,
 
+    /// 値の並び替えのために再作成されるENUM
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enums_reordered: Vec<ReorderedEnum>,
+
     /// 削除されるView名のリスト
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub views_dropped: Vec<String>,
@@ -30,6 +34,14 @@ pub struct DestructiveChangeReport {
     /// 定義が変更されるView名のリスト
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub views_modified: Vec<String>,
+
+    /// 値の丸め・桁あふれが起こりうる型に変更されるカラム
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns_narrowed: Vec<NarrowedColumn>,
+
+    /// `generate --edit` で追加された破壊的なステートメント
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edited_statements: Vec<String>,
 }
 
 /// 削除されるカラム情報
@@ -47,6 +59,29 @@ pub struct RenamedColumnInfo {
     pub new_name: String,
 }
 
+/// 値の並び順が変更されるENUM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReorderedEnum {
+    pub name: String,
+    /// 変更前の並び順
+    pub old_values: Vec<String>,
+    /// 変更後の並び順
+    pub new_values: Vec<String>,
+}
+
+/// 値が失われる可能性のある型変更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NarrowedColumn {
+    pub table: String,
+    pub column: String,
+    /// 変更前の型（例: `DECIMAL(10, 2)`）
+    pub old_type: String,
+    /// 変更後の型（例: `DECIMAL(8, 2)`）
+    pub new_type: String,
+    /// 値が失われる理由
+    pub reason: String,
+}
+
 impl Default for DestructiveChangeReport {
     fn default() -> Self {
         Self::new()
@@ -62,8 +97,11 @@ impl DestructiveChangeReport {
             columns_renamed: Vec::new(),
             enums_dropped: Vec::new(),
             enums_recreated: Vec::new(),
+            enums_reordered: Vec::new(),
             views_dropped: Vec::new(),
             views_modified: Vec::new(),
+            columns_narrowed: Vec::new(),
+            edited_statements: Vec::new(),
         }
     }
 
@@ -74,8 +112,11 @@ impl DestructiveChangeReport {
             || !self.columns_renamed.is_empty()
             || !self.enums_dropped.is_empty()
             || !self.enums_recreated.is_empty()
+            || !self.enums_reordered.is_empty()
             || !self.views_dropped.is_empty()
             || !self.views_modified.is_empty()
+            || !self.columns_narrowed.is_empty()
+            || !self.edited_statements.is_empty()
     }
 
     /// 破壊的変更の総数をカウント
@@ -91,14 +132,19 @@ impl DestructiveChangeReport {
             + self.columns_renamed.len()
             + self.enums_dropped.len()
             + self.enums_recreated.len()
+            + self.enums_reordered.len()
             + self.views_dropped.len()
             + self.views_modified.len()
+            + self.columns_narrowed.len()
+            + self.edited_statements.len()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{DestructiveChangeReport, DroppedColumn, RenamedColumnInfo};
+    use super::{
+        DestructiveChangeReport, DroppedColumn, NarrowedColumn, RenamedColumnInfo, ReorderedEnum,
+    };
 
     #[test]
     fn new_report_is_empty() {
@@ -115,11 +161,20 @@ mod tests {
             columns_renamed: Vec::new(),
             enums_dropped: Vec::new(),
             enums_recreated: Vec::new(),
+            enums_reordered: Vec::new(),
             views_dropped: Vec::new(),
             views_modified: Vec::new(),
+            columns_narrowed: Vec::new(),
+            edited_statements: Vec::new(),
         };
 
         assert!(report.has_destructive_changes());
+
+        let report = DestructiveChangeReport {
+            columns_narrowed: vec![narrowed_price()],
+            ..DestructiveChangeReport::new()
+        };
+        assert!(report.has_destructive_changes());
     }
 
     #[test]
@@ -150,11 +205,17 @@ mod tests {
             ],
             enums_dropped: vec!["old_status".to_string()],
             enums_recreated: vec!["priority".to_string()],
+            enums_reordered: vec![reordered_level()],
             views_dropped: vec!["old_view".to_string()],
             views_modified: vec!["changed_view".to_string()],
+            columns_narrowed: vec![narrowed_price()],
+            edited_statements: vec!["DELETE FROM sessions".to_string()],
         };
 
-        assert_eq!(report.total_change_count(), 2 + 3 + 2 + 1 + 1 + 1 + 1);
+        assert_eq!(
+            report.total_change_count(),
+            2 + 3 + 2 + 1 + 1 + 1 + 1 + 1 + 1 + 1
+        );
     }
 
     #[test]
@@ -172,8 +233,11 @@ mod tests {
             }],
             enums_dropped: vec!["old_status".to_string()],
             enums_recreated: vec!["priority".to_string()],
+            enums_reordered: vec![reordered_level()],
             views_dropped: vec!["old_view".to_string()],
             views_modified: vec!["changed_view".to_string()],
+            columns_narrowed: vec![narrowed_price()],
+            edited_statements: vec!["DELETE FROM sessions".to_string()],
         };
 
         let yaml = serde_saphyr::to_string(&report).expect("serialize report");
@@ -195,5 +259,24 @@ mod tests {
         assert!(!yaml.contains("enums_recreated"));
         assert!(!yaml.contains("views_dropped"));
         assert!(!yaml.contains("views_modified"));
+        assert!(!yaml.contains("columns_narrowed"));
+    }
+
+    fn narrowed_price() -> NarrowedColumn {
+        NarrowedColumn {
+            table: "products".to_string(),
+            column: "price".to_string(),
+            old_type: "DECIMAL(10, 2)".to_string(),
+            new_type: "DECIMAL(8, 2)".to_string(),
+            reason: "existing values may overflow".to_string(),
+        }
+    }
+
+    fn reordered_level() -> ReorderedEnum {
+        ReorderedEnum {
+            name: "level".to_string(),
+            old_values: vec!["high".to_string(), "low".to_string()],
+            new_values: vec!["low".to_string(), "high".to_string()],
+        }
     }
 }
@@ -3,6 +3,7 @@
 // アプリケーション全体で使用されるカスタムエラー型を提供します。
 // thiserrorを使用して、ValidationError, DatabaseError, IoError, MigrationError を定義します。
 
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// バリデーションエラー
@@ -64,6 +65,17 @@ pub enum ValidationError {
         /// 対象のデータベース方言
         dialect: String,
     },
+
+    /// Duplicate definition error (same table, enum or view defined more than once)
+    #[error("Duplicate definition: {message}{}", format_location_opt(.location))]
+    Duplicate {
+        /// エラーメッセージ
+        message: String,
+        /// エラー発生位置（後から読み込んだ定義元ファイル）
+        location: Option<ErrorLocation>,
+        /// 修正提案
+        suggestion: Option<String>,
+    },
 }
 
 impl ValidationError {
@@ -92,6 +104,11 @@ impl ValidationError {
         matches!(self, ValidationError::DialectConstraint { .. })
     }
 
+    /// 重複定義エラーかどうか
+    pub fn is_duplicate(&self) -> bool {
+        matches!(self, ValidationError::Duplicate { .. })
+    }
+
     /// エラー発生位置を取得
     pub fn location(&self) -> Option<&ErrorLocation> {
         match self {
@@ -99,7 +116,20 @@ impl ValidationError {
             | ValidationError::Reference { location, .. }
             | ValidationError::Constraint { location, .. }
             | ValidationError::TypeConversion { location, .. }
-            | ValidationError::DialectConstraint { location, .. } => location.as_ref(),
+            | ValidationError::DialectConstraint { location, .. }
+            | ValidationError::Duplicate { location, .. } => location.as_ref(),
+        }
+    }
+
+    /// エラー発生位置を可変参照で取得
+    pub fn location_mut(&mut self) -> Option<&mut ErrorLocation> {
+        match self {
+            ValidationError::Syntax { location, .. }
+            | ValidationError::Reference { location, .. }
+            | ValidationError::Constraint { location, .. }
+            | ValidationError::TypeConversion { location, .. }
+            | ValidationError::DialectConstraint { location, .. }
+            | ValidationError::Duplicate { location, .. } => location.as_mut(),
         }
     }
 
@@ -109,7 +139,8 @@ impl ValidationError {
             ValidationError::Syntax { suggestion, .. }
             | ValidationError::Reference { suggestion, .. }
             | ValidationError::Constraint { suggestion, .. }
-            | ValidationError::TypeConversion { suggestion, .. } => suggestion.as_deref(),
+            | ValidationError::TypeConversion { suggestion, .. }
+            | ValidationError::Duplicate { suggestion, .. } => suggestion.as_deref(),
             ValidationError::DialectConstraint { .. } => None,
         }
     }
@@ -148,6 +179,10 @@ pub enum WarningKind {
     RenamedFromRemoveRecommendation,
     /// タイプミスの可能性に関する警告
     PossibleTypo,
+    /// 命名規約に関する警告
+    NamingConvention,
+    /// 情報提供のみを目的とした通知
+    Info,
 }
 
 impl ValidationWarning {
@@ -207,13 +242,28 @@ impl ValidationWarning {
         Self::new(message, location, WarningKind::PossibleTypo)
     }
 
+    /// 命名規約の警告を作成
+    pub fn naming_convention(message: String, location: Option<ErrorLocation>) -> Self {
+        Self::new(message, location, WarningKind::NamingConvention)
+    }
+
+    /// 情報レベルの通知を作成
+    pub fn info(message: String, location: Option<ErrorLocation>) -> Self {
+        Self::new(message, location, WarningKind::Info)
+    }
+
     /// 位置情報をフォーマット
     pub fn format(&self) -> String {
         let location_str = self
             .location
             .as_ref()
             .map_or(String::new(), |loc| loc.format());
-        format!("Warning: {}{}", self.message, location_str)
+        let label = if self.kind == WarningKind::Info {
+            "Info"
+        } else {
+            "Warning"
+        };
+        format!("{}: {}{}", label, self.message, location_str)
     }
 }
 
@@ -228,15 +278,24 @@ pub struct ErrorLocation {
     pub column: Option<String>,
     /// 行番号
     pub line: Option<usize>,
+    /// 定義元のスキーマファイル
+    pub file: Option<PathBuf>,
 }
 
 impl ErrorLocation {
+    /// ビューを表す `table` の接頭辞
+    pub const VIEW_PREFIX: &'static str = "view:";
+
+    /// ENUMを表す `table` の接頭辞
+    pub const ENUM_PREFIX: &'static str = "enum:";
+
     /// 新しいエラー位置を作成
     pub fn new() -> Self {
         Self {
             table: None,
             column: None,
             line: None,
+            file: None,
         }
     }
 
@@ -244,8 +303,7 @@ impl ErrorLocation {
     pub fn with_table(table: String) -> Self {
         Self {
             table: Some(table),
-            column: None,
-            line: None,
+            ..Self::new()
         }
     }
 
@@ -254,23 +312,64 @@ impl ErrorLocation {
         Self {
             table: Some(table.to_string()),
             column: Some(column.to_string()),
-            line: None,
+            ..Self::new()
         }
     }
 
     /// ビュー名を指定してエラー位置を作成
     pub fn with_view(view: &str) -> Self {
         Self {
-            table: Some(format!("view:{}", view)),
-            column: None,
-            line: None,
+            table: Some(format!("{}{}", Self::VIEW_PREFIX, view)),
+            ..Self::new()
+        }
+    }
+
+    /// ENUM名を指定してエラー位置を作成
+    pub fn with_enum(enum_name: &str) -> Self {
+        Self {
+            table: Some(format!("{}{}", Self::ENUM_PREFIX, enum_name)),
+            ..Self::new()
         }
     }
 
+    /// 定義元のスキーマファイルを設定
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// 対象のビュー名（ビューの位置でない場合はNone）
+    pub fn view_name(&self) -> Option<&str> {
+        self.table.as_deref()?.strip_prefix(Self::VIEW_PREFIX)
+    }
+
+    /// 対象のENUM名（ENUMの位置でない場合はNone）
+    pub fn enum_name(&self) -> Option<&str> {
+        self.table.as_deref()?.strip_prefix(Self::ENUM_PREFIX)
+    }
+
+    /// 対象のテーブル名（ビュー・ENUMの位置の場合はNone）
+    pub fn table_name(&self) -> Option<&str> {
+        let table = self.table.as_deref()?;
+        if table.starts_with(Self::VIEW_PREFIX) || table.starts_with(Self::ENUM_PREFIX) {
+            None
+        } else {
+            Some(table)
+        }
+    }
+
+    /// 定義元のスキーマファイル
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
     /// 位置情報をフォーマット
     pub fn format(&self) -> String {
         let mut parts = Vec::new();
 
+        if let Some(file) = &self.file {
+            parts.push(format!("file: {}", file.display()));
+        }
         if let Some(table) = &self.table {
             parts.push(format!("table: {}", table));
         }
@@ -476,6 +575,22 @@ pub enum DatabaseError {
         reason: String,
     },
 
+    /// Migration history table has an unrecognized layout
+    #[error(
+        "The migration history table '{table_name}' has an unrecognized layout: found columns [{}], expected [{}]{}",
+        .found.join(", "),
+        .expected.join(", "),
+        unrecognized_layout_hint(found, expected)
+    )]
+    UnrecognizedMigrationTable {
+        /// テーブル名
+        table_name: String,
+        /// 既存テーブルのカラム
+        found: Vec<String>,
+        /// 現在のレイアウトのカラム
+        expected: Vec<String>,
+    },
+
     /// Column rename operation failed
     #[error(
         "Failed to rename column '{old_name}' to '{new_name}' in table '{table_name}': {reason}"
@@ -492,6 +607,60 @@ pub enum DatabaseError {
         /// 提案
         suggestion: Option<String>,
     },
+
+    /// Database driver is not available
+    #[error("{}", driver_unavailable_message(dialect, feature, *compiled))]
+    DriverUnavailable {
+        /// 方言名
+        dialect: String,
+        /// ドライバーを含める cargo フィーチャー
+        feature: String,
+        /// ドライバーがバイナリに含まれているか（含まれていれば sqlx::any への登録漏れ）
+        compiled: bool,
+    },
+}
+
+/// ドライバーを使用できない場合のメッセージ（原因と対処を示す）
+fn driver_unavailable_message(dialect: &str, feature: &str, compiled: bool) -> String {
+    if compiled {
+        format!(
+            "The {} driver is not registered with sqlx::any. Call `sqlx::any::install_default_drivers()` before connecting",
+            dialect
+        )
+    } else {
+        format!(
+            "This build of strata does not include the {} driver (built without the `{}` cargo feature). \
+             Rebuild with `cargo install --path src/cli --features {}`, or use a release binary, which includes every driver. \
+             Run `strata version --features` to list the compiled-in drivers",
+            dialect, feature, feature
+        )
+    }
+}
+
+/// 認識できない履歴テーブルのレイアウトについて、不足・余分なカラムと対処を示す
+fn unrecognized_layout_hint(found: &[String], expected: &[String]) -> String {
+    let missing: Vec<&str> = expected
+        .iter()
+        .filter(|column| !found.contains(column))
+        .map(String::as_str)
+        .collect();
+    let unexpected: Vec<&str> = found
+        .iter()
+        .filter(|column| !expected.contains(column))
+        .map(String::as_str)
+        .collect();
+
+    let mut hint = String::new();
+    if !missing.is_empty() {
+        hint.push_str(&format!("; missing: {}", missing.join(", ")));
+    }
+    if !unexpected.is_empty() {
+        hint.push_str(&format!("; unexpected: {}", unexpected.join(", ")));
+    }
+    hint.push_str(
+        ". Strata does not drop columns from this table automatically; adjust it manually before running this command again",
+    );
+    hint
 }
 
 impl DatabaseError {
@@ -520,11 +689,21 @@ impl DatabaseError {
         matches!(self, DatabaseError::InvalidTableName { .. })
     }
 
+    /// 履歴テーブルのレイアウト不明エラーかどうか
+    pub fn is_unrecognized_migration_table(&self) -> bool {
+        matches!(self, DatabaseError::UnrecognizedMigrationTable { .. })
+    }
+
     /// リネームカラム失敗エラーかどうか
     pub fn is_rename_column_failed(&self) -> bool {
         matches!(self, DatabaseError::RenameColumnFailed { .. })
     }
 
+    /// ドライバー未対応エラーかどうか
+    pub fn is_driver_unavailable(&self) -> bool {
+        matches!(self, DatabaseError::DriverUnavailable { .. })
+    }
+
     /// データベースエラーメッセージからリネーム失敗の原因を解析
     ///
     /// # Arguments
@@ -618,6 +797,73 @@ pub enum ConfigError {
     #[error("Database name is not specified")]
     MissingDatabaseName,
 
+    /// ステートメント終端文字列が空
+    #[error("sql_output.statement_terminator must not be empty")]
+    EmptyStatementTerminator,
+
+    /// mysql.minimum_version を解析できない
+    #[error("mysql.minimum_version '{version}' is not a valid version (expected e.g. \"5.7\" or \"8.0.16\")")]
+    InvalidMysqlMinimumVersion {
+        /// 指定された値
+        version: String,
+    },
+
+    /// postgres.schemas が空
+    #[error("postgres.schemas must list at least one schema")]
+    EmptyPostgresSchemas,
+
+    /// postgres.schemas に不正なスキーマ名が含まれる
+    #[error("postgres.schemas contains an invalid schema name '{schema}' (must be non-empty and must not contain '.')")]
+    InvalidPostgresSchema {
+        /// 指定された値
+        schema: String,
+    },
+
+    /// naming.foreign_key_column のテンプレートが不正
+    #[error("naming.foreign_key_column '{template}' must contain at least one of {{table}}, {{singular_table}} or {{column}} and no other placeholders")]
+    InvalidForeignKeyColumnTemplate {
+        /// 指定された値
+        template: String,
+    },
+
+    /// lock_timeout / statement_timeout が 0
+    #[error("{name} must be at least 1 second (remove it to use the database default)")]
+    ZeroSessionTimeout {
+        /// 設定項目名
+        name: String,
+    },
+
+    /// statement_retry.max_attempts が 0
+    #[error("statement_retry.max_attempts must be at least 1 (use 1 to disable retries)")]
+    InvalidStatementRetryAttempts,
+
+    /// `extends` で指定された環境が存在しない
+    #[error(
+        "Environment '{environment}' extends '{extends}', which is not defined in environments"
+    )]
+    UnknownExtendsEnvironment {
+        /// 環境名
+        environment: String,
+        /// 継承元として指定された環境名
+        extends: String,
+    },
+
+    /// `extends` が循環している
+    #[error("Circular `extends` between environments: {chain}")]
+    CircularExtends {
+        /// 循環している環境（`a -> b -> a` の形式）
+        chain: String,
+    },
+
+    /// 設定ファイルで参照している環境変数が未定義
+    #[error("Environment variable '{name}' used on line {line} of the config file is not defined")]
+    UndefinedEnvironmentVariable {
+        /// 環境変数名
+        name: String,
+        /// 参照している行（1始まり）
+        line: usize,
+    },
+
     /// 環境別設定の検証エラー
     #[error("Invalid config for environment '{environment}': {source}")]
     InvalidEnvironment {
@@ -714,6 +960,7 @@ mod tests {
             table: Some("users".to_string()),
             column: Some("email".to_string()),
             line: Some(42),
+            file: None,
         };
 
         let formatted = location.format();
@@ -887,6 +1134,7 @@ mod tests {
             table: Some("orders".to_string()),
             column: None,
             line: None,
+            file: None,
         };
         let formatted = loc.format();
         assert_eq!(formatted, " (table: orders)");
@@ -898,6 +1146,7 @@ mod tests {
             table: None,
             column: Some("email".to_string()),
             line: None,
+            file: None,
         };
         let formatted = loc.format();
         assert_eq!(formatted, " (column: email)");
@@ -909,6 +1158,7 @@ mod tests {
             table: None,
             column: None,
             line: Some(10),
+            file: None,
         };
         let formatted = loc.format();
         assert_eq!(formatted, " (line: 10)");
@@ -5,10 +5,13 @@
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::core::config::Dialect;
+use crate::core::config::{Dialect, SqlOutputConfig};
 use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::policy_report::PolicyReport;
 
 /// マイグレーションファイル
 ///
@@ -55,8 +58,41 @@ pub struct MigrationMetadata {
     /// マイグレーションファイルのチェックサム
     pub checksum: String,
 
+    /// up.sql の内容のチェックサム（BOM・改行コードの違いは無視）
+    ///
+    /// apply / status で適用済みマイグレーションの up.sql が書き換えられていないか確認します。
+    /// この項目がない古いマイグレーションでは up.sql の確認を省略します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub up_sql_checksum: Option<String>,
+
+    /// up.sql / down.sql を書き出したときのステートメント終端文字列（`sql_output.statement_terminator`）
+    ///
+    /// 後から `sql_output` を変更しても、SQLファイルの読み込みと up.sql のチェックサム検証には
+    /// この値を使います。この項目がない古いマイグレーションは現在の設定で読み込みます。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_terminator: Option<String>,
+
     /// 破壊的変更の検出結果
     pub destructive_changes: DestructiveChangeReport,
+
+    /// マイグレーションポリシーの評価結果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyReport>,
+
+    /// ロールバックしても削除されないテーブル（`rollback_protection: true` で追加されたテーブル）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollback_protected_tables: Vec<String>,
+
+    /// マイグレーションが変更するテーブル・ビュー（generate 時に差分から記録）
+    ///
+    /// `apply --only` で先行する未適用マイグレーションとの競合確認に使います。
+    /// この項目がない古いマイグレーションは、変更するテーブルが不明なものとして扱います。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub touched_tables: Option<Vec<String>>,
+
+    /// 外部ツール向けの任意のメタデータ（`generate --meta key=value` で指定）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
 }
 
 /// 破壊的変更の判定結果
@@ -69,6 +105,20 @@ pub enum DestructiveChangeStatus {
 }
 
 impl MigrationMetadata {
+    /// SQLファイルを読み込むときの出力設定
+    ///
+    /// 書き出し時の終端文字列が記録されていればそれを使います。
+    /// BOMと改行コードは読み込み時に設定に関わらず正規化されるため、記録しません。
+    pub fn sql_output(&self, current: &SqlOutputConfig) -> SqlOutputConfig {
+        match &self.statement_terminator {
+            Some(terminator) => SqlOutputConfig {
+                statement_terminator: terminator.clone(),
+                ..current.clone()
+            },
+            None => current.clone(),
+        }
+    }
+
     /// 破壊的変更の有無を判定
     pub fn destructive_change_status(&self) -> DestructiveChangeStatus {
         if self.destructive_changes.has_destructive_changes() {
@@ -192,6 +242,124 @@ impl MigrationRecord {
     }
 }
 
+/// schema_migrations に保存するSQLの最大バイト数（up.sql / down.sql それぞれ）
+///
+/// これを超えるSQLは切り詰め、末尾に [`TRUNCATED_SQL_MARKER`] で始まる行を付けて保存します。
+/// マーカー行を含めてこのバイト数を超えることはありません。
+pub const MAX_STORED_SQL_BYTES: usize = 1024 * 1024;
+
+/// 切り詰めて保存したSQLの末尾に付けるコメント行の接頭辞
+pub const TRUNCATED_SQL_MARKER: &str = "-- strata: truncated";
+
+/// マイグレーションの適用記録の詳細
+///
+/// `status --verbose` で表示する、適用日時・実行時間・適用したデータベースユーザーです。
+/// 記録用カラムの追加前に適用された場合、実行時間と適用ユーザーはNoneです。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationAudit {
+    /// マイグレーションバージョン
+    pub version: String,
+
+    /// マイグレーションが適用された日時
+    pub applied_at: DateTime<Utc>,
+
+    /// 適用にかかった時間（ミリ秒）
+    pub execution_time_ms: Option<i64>,
+
+    /// 適用したデータベースユーザー
+    pub applied_by: Option<String>,
+}
+
+/// ロールバックの記録
+///
+/// ロールバック時に `schema_migration_rollbacks` へ残す、元の適用日時・ロールバック日時・
+/// ロールバックしたデータベースユーザーです。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationRollbackAudit {
+    /// マイグレーションバージョン
+    pub version: String,
+
+    /// マイグレーションの説明
+    pub description: String,
+
+    /// ロールバックしたマイグレーションが適用された日時
+    pub applied_at: DateTime<Utc>,
+
+    /// ロールバックした日時
+    pub rolled_back_at: DateTime<Utc>,
+
+    /// ロールバックしたデータベースユーザー（SQLiteではNone）
+    pub rolled_back_by: Option<String>,
+}
+
+/// 適用時に保存されたSQL
+///
+/// `store_applied_sql: true` の場合に schema_migrations に保存される
+/// up.sql / down.sql の内容を表現します。保存されていない場合はNoneです。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedSql {
+    /// マイグレーションバージョン
+    pub version: String,
+
+    /// マイグレーションの説明
+    pub description: String,
+
+    /// マイグレーションファイルのチェックサム
+    pub checksum: String,
+
+    /// 適用時のup.sql
+    pub up_sql: Option<String>,
+
+    /// 適用時のdown.sql
+    pub down_sql: Option<String>,
+}
+
+impl AppliedSql {
+    /// up.sql / down.sql のいずれかが切り詰めて保存されているか
+    pub fn is_truncated(&self) -> bool {
+        [&self.up_sql, &self.down_sql]
+            .into_iter()
+            .flatten()
+            .any(|sql| is_truncated_sql(sql))
+    }
+}
+
+/// 保存用にSQLを切り詰める
+///
+/// [`MAX_STORED_SQL_BYTES`] 以下のSQLはそのまま返します。
+/// 超える場合は文字境界で切り詰め、元のサイズを記したマーカー行を末尾に付けます。
+pub fn truncate_sql_for_storage(sql: &str) -> Cow<'_, str> {
+    if sql.len() <= MAX_STORED_SQL_BYTES {
+        return Cow::Borrowed(sql);
+    }
+
+    Cow::Owned(truncate_sql_prefix_for_storage(sql, sql.len()))
+}
+
+/// 先頭部分だけ読み込んだSQLから保存用の切り詰め済みSQLを作る
+///
+/// 大きなSQLファイルを全て読み込まずに済むよう、先頭 [`MAX_STORED_SQL_BYTES`] バイト程度と
+/// 元のサイズ（`original_size`）から [`truncate_sql_for_storage`] と同じ結果を生成します。
+pub fn truncate_sql_prefix_for_storage(prefix: &str, original_size: usize) -> String {
+    let marker = format!(
+        "\n{} (original size: {} bytes)\n",
+        TRUNCATED_SQL_MARKER, original_size
+    );
+    let mut end = (MAX_STORED_SQL_BYTES - marker.len()).min(prefix.len());
+    while !prefix.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &prefix[..end], marker)
+}
+
+/// 保存されたSQLが切り詰められているか
+pub fn is_truncated_sql(sql: &str) -> bool {
+    sql.trim_end()
+        .lines()
+        .last()
+        .is_some_and(|line| line.starts_with(TRUNCATED_SQL_MARKER))
+}
+
 /// 適用済みマイグレーション
 ///
 /// マイグレーション適用時の実行情報を表現します。
@@ -454,6 +622,47 @@ destructive_changes: {}
         );
     }
 
+    #[test]
+    fn test_metadata_without_policy_is_none() {
+        let yaml = r#"version: "20260125120000"
+description: "safe"
+dialect: postgresql
+checksum: "abc123"
+destructive_changes: {}
+"#;
+
+        let metadata: MigrationMetadata =
+            serde_saphyr::from_str(yaml).expect("Failed to deserialize metadata");
+
+        assert!(metadata.policy.is_none());
+    }
+
+    #[test]
+    fn test_metadata_with_policy_report() {
+        let yaml = r#"version: "20260125120000"
+description: "large"
+dialect: postgresql
+checksum: "abc123"
+destructive_changes: {}
+policy:
+  statement_count: 60
+  tables_touched:
+    - "users"
+  violations:
+    - rule: max_statements
+      message: "too many statements"
+  overridden: true
+"#;
+
+        let metadata: MigrationMetadata =
+            serde_saphyr::from_str(yaml).expect("Failed to deserialize metadata");
+
+        let policy = metadata.policy.expect("policy should be present");
+        assert_eq!(policy.statement_count, 60);
+        assert!(policy.overridden);
+        assert!(policy.has_violations());
+    }
+
     #[test]
     fn test_metadata_destructive_changes_present() {
         let yaml = r#"version: "20260125120000"
@@ -588,4 +797,76 @@ destructive_changes:
         assert_eq!(applied.version, "20260121120000");
         assert_eq!(applied.duration, Duration::seconds(5));
     }
+
+    #[test]
+    fn test_truncate_sql_for_storage_keeps_small_sql() {
+        let sql = "CREATE TABLE users (id INTEGER PRIMARY KEY);\n";
+        let stored = truncate_sql_for_storage(sql);
+
+        assert!(matches!(stored, Cow::Borrowed(_)));
+        assert_eq!(stored, sql);
+        assert!(!is_truncated_sql(&stored));
+
+        // ちょうど上限のSQLは切り詰めない
+        let sql = "x".repeat(MAX_STORED_SQL_BYTES);
+        assert_eq!(truncate_sql_for_storage(&sql).len(), MAX_STORED_SQL_BYTES);
+    }
+
+    #[test]
+    fn test_truncate_sql_for_storage_truncates_large_sql() {
+        let sql = "x".repeat(MAX_STORED_SQL_BYTES + 1);
+        let stored = truncate_sql_for_storage(&sql);
+
+        assert!(stored.len() <= MAX_STORED_SQL_BYTES);
+        assert!(stored.starts_with("xxxx"));
+        assert!(is_truncated_sql(&stored));
+        assert!(stored.ends_with(&format!(
+            "{} (original size: {} bytes)\n",
+            TRUNCATED_SQL_MARKER,
+            MAX_STORED_SQL_BYTES + 1
+        )));
+    }
+
+    #[test]
+    fn test_truncate_sql_for_storage_respects_char_boundary() {
+        // 3バイト文字のみで構成されたSQLでも文字の途中で切らない
+        let sql = "あ".repeat(MAX_STORED_SQL_BYTES / 3 + 1);
+        let stored = truncate_sql_for_storage(&sql);
+
+        assert!(stored.len() <= MAX_STORED_SQL_BYTES);
+        let body = stored.split('\n').next().unwrap();
+        assert!(body.chars().all(|c| c == 'あ'));
+    }
+
+    #[test]
+    fn test_truncate_sql_prefix_matches_full_truncation() {
+        let sql = "あいう;".repeat(MAX_STORED_SQL_BYTES / 5);
+        let mut prefix_end = MAX_STORED_SQL_BYTES;
+        while !sql.is_char_boundary(prefix_end) {
+            prefix_end -= 1;
+        }
+
+        assert_eq!(
+            truncate_sql_prefix_for_storage(&sql[..prefix_end], sql.len()),
+            truncate_sql_for_storage(&sql)
+        );
+    }
+
+    #[test]
+    fn test_applied_sql_is_truncated() {
+        let mut applied = AppliedSql {
+            version: "20260121120000".to_string(),
+            description: "test".to_string(),
+            checksum: "abc".to_string(),
+            up_sql: Some("SELECT 1;".to_string()),
+            down_sql: None,
+        };
+        assert!(!applied.is_truncated());
+
+        applied.down_sql = Some(format!(
+            "SELECT 1;\n{} (original size: 2 bytes)\n",
+            TRUNCATED_SQL_MARKER
+        ));
+        assert!(applied.is_truncated());
+    }
 }
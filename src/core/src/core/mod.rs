@@ -6,6 +6,8 @@ pub mod destructive_change_report;
 pub mod error;
 pub mod migration;
 pub mod naming;
+pub mod policy_report;
 pub mod schema;
 pub mod schema_diff;
+pub mod server_version;
 pub mod type_category;
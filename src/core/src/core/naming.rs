@@ -8,6 +8,9 @@ pub const APP_NAME: &str = "strata";
 /// 既定の設定ファイル名
 pub const CONFIG_FILE: &str = ".strata.yaml";
 
+/// ワークスペース設定ファイル名（複数プロジェクトをまとめて扱う）
+pub const WORKSPACE_FILE: &str = "strata-workspace.yaml";
+
 /// 既定の状態ディレクトリ
 pub const STATE_DIR: &str = ".strata";
 
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+/// マイグレーションポリシーの評価レポート
+///
+/// generate時に計測したマイグレーションの規模と、ポリシー違反の一覧を保持します。
+/// `.meta.yaml` に記録され、apply時の再評価にも使用されます。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    /// UP SQLのステートメント数
+    pub statement_count: usize,
+
+    /// 変更対象となるテーブル名のリスト（ソート済み）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables_touched: Vec<String>,
+
+    /// 破壊的変更と追加系の変更が混在しているか
+    #[serde(default)]
+    pub mixed_destructive_and_additive: bool,
+
+    /// ポリシー違反のリスト
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<PolicyViolation>,
+
+    /// `--override-policy` により違反を許可して生成したか
+    #[serde(default)]
+    pub overridden: bool,
+}
+
+/// ポリシー違反
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    /// 違反したルール
+    pub rule: PolicyRule,
+
+    /// 違反内容の説明
+    pub message: String,
+}
+
+/// ポリシールールの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// ステートメント数の上限
+    MaxStatements,
+    /// 変更対象テーブル数の上限
+    MaxTablesTouched,
+    /// 破壊的変更と追加系の変更の混在禁止
+    ForbidMixedDestructiveAndAdditive,
+}
+
+impl PolicyReport {
+    /// ポリシー違反が含まれているかを判定
+    pub fn has_violations(&self) -> bool {
+        !self.violations.is_empty()
+    }
+
+    /// 変更対象のテーブル数
+    pub fn tables_touched_count(&self) -> usize {
+        self.tables_touched.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PolicyReport, PolicyRule, PolicyViolation};
+
+    #[test]
+    fn default_report_has_no_violations() {
+        let report = PolicyReport::default();
+        assert!(!report.has_violations());
+        assert_eq!(report.tables_touched_count(), 0);
+    }
+
+    #[test]
+    fn serializes_rule_in_snake_case() {
+        let report = PolicyReport {
+            statement_count: 60,
+            tables_touched: vec!["users".to_string()],
+            mixed_destructive_and_additive: false,
+            violations: vec![PolicyViolation {
+                rule: PolicyRule::MaxStatements,
+                message: "too many statements".to_string(),
+            }],
+            overridden: true,
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"rule\":\"max_statements\""));
+
+        let restored: PolicyReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, report);
+    }
+}
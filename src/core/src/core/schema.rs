@@ -3,12 +3,17 @@
 // データベーススキーマの定義を表現する型システム。
 // Schema, Table, Column, Index, Constraint などの構造体を提供します。
 
+pub mod builder;
+
+use chrono::NaiveDate;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use builder::SchemaBuilder;
 
 /// YAMLの default_value フィールドを柔軟にデシリアライズする。
 /// 文字列だけでなく、boolean（false/true）や数値も文字列として受け付ける。
-fn deserialize_default_value<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+pub fn deserialize_default_value<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -180,6 +185,10 @@ pub struct Schema {
     /// ビュー定義のマップ（ビュー名 -> View）
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub views: BTreeMap<String, View>,
+
+    /// strata管理外のテーブル名（存在するがカラム構成は不明として扱う）
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub external_tables: BTreeSet<String>,
 }
 
 impl Schema {
@@ -191,6 +200,7 @@ impl Schema {
             enums: BTreeMap::new(),
             tables: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: BTreeSet::new(),
         }
     }
 
@@ -231,6 +241,11 @@ impl Schema {
         self.tables.get(table_name)
     }
 
+    /// 指定されたテーブルが外部テーブルとして宣言されているか確認
+    pub fn is_external_table(&self, table_name: &str) -> bool {
+        self.external_tables.contains(table_name)
+    }
+
     /// テーブル数を取得
     pub fn table_count(&self) -> usize {
         self.tables.len()
@@ -264,9 +279,13 @@ impl Schema {
 /// カラム、インデックス、制約の定義を保持します。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Table {
-    /// テーブル名
+    /// テーブル名（`schema` 指定時は `schema.table` 形式の修飾名）
     pub name: String,
 
+    /// PostgreSQLのスキーマ名（オプショナル、未指定時は search_path 上のデフォルトスキーマ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
     /// カラム定義のリスト
     pub columns: Vec<Column>,
 
@@ -279,6 +298,39 @@ pub struct Table {
     /// リネーム元のテーブル名（オプショナル）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub renamed_from: Option<String>,
+
+    /// テーブル作成直後にデータを投入するSELECT文（オプショナル）
+    ///
+    /// テーブル追加時に `INSERT INTO ... SELECT ...` として up.sql に出力されます。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub populate_from: Option<String>,
+
+    /// ロールバック保護フラグ（デフォルト: false）
+    ///
+    /// true の場合、このテーブルを作成したマイグレーションをロールバックしても
+    /// DROP TABLE を実行せず、テーブルをデータベースに残します（追記専用の監査テーブル等）。
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub rollback_protection: bool,
+
+    /// テーブルのコメント（オプショナル、空文字列はコメントなしとして扱う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// 外部ツール向けの任意のメタデータ（所有チーム、チケット等）
+    ///
+    /// スナップショットとエクスポートには保持されますが、SQL生成と差分検出では無視されます。
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// スキーマ名とテーブル名から `schema.table` 形式の修飾名を組み立てる
+///
+/// テーブル名がすでに同じスキーマで修飾されている場合はそのまま返します。
+pub fn qualify_table_name(schema: &str, name: &str) -> String {
+    match name.strip_prefix(schema) {
+        Some(rest) if rest.starts_with('.') => name.to_string(),
+        _ => format!("{}.{}", schema, name),
+    }
 }
 
 impl Table {
@@ -286,13 +338,39 @@ impl Table {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            schema: None,
             columns: Vec::new(),
             indexes: Vec::new(),
             constraints: Vec::new(),
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            comment: None,
+            metadata: BTreeMap::new(),
         }
     }
 
+    /// スキーマを指定して新しいテーブルを作成
+    ///
+    /// テーブル名は `schema.table` 形式の修飾名になります。
+    pub fn new_in_schema(schema: String, name: &str) -> Self {
+        let mut table = Self::new(qualify_table_name(&schema, name));
+        table.schema = Some(schema);
+        table
+    }
+
+    /// スキーマ名を除いたテーブル名
+    pub fn unqualified_name(&self) -> &str {
+        self.schema
+            .as_deref()
+            .and_then(|schema| {
+                self.name
+                    .strip_prefix(schema)
+                    .and_then(|rest| rest.strip_prefix('.'))
+            })
+            .unwrap_or(&self.name)
+    }
+
     /// カラムを追加
     pub fn add_column(&mut self, column: Column) {
         self.columns.push(column);
@@ -322,6 +400,11 @@ impl Table {
     pub fn get_column(&self, column_name: &str) -> Option<&Column> {
         self.columns.iter().find(|c| c.name == column_name)
     }
+
+    /// テーブルのコメント（空文字列の場合は None）
+    pub fn comment_text(&self) -> Option<&str> {
+        non_empty_comment(&self.comment)
+    }
 }
 
 /// カラム定義
@@ -355,6 +438,111 @@ pub struct Column {
     /// リネーム元のカラム名（オプショナル）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub renamed_from: Option<String>,
+
+    /// 生成カラム定義（`GENERATED ALWAYS AS (...)`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<GeneratedColumn>,
+
+    /// カラムのコメント（オプショナル、空文字列はコメントなしとして扱う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// 行の更新時に現在時刻を設定するか（MySQL専用、`ON UPDATE CURRENT_TIMESTAMP`）
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub on_update_current_timestamp: bool,
+
+    /// 型変更で使う `USING` 式（PostgreSQL専用、差分検出では無視）
+    ///
+    /// up.sql の `ALTER COLUMN ... TYPE ... USING <式>` に使用します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_change_using: Option<String>,
+
+    /// 型変更を戻す down.sql で使う `USING` 式（PostgreSQL専用、差分検出では無視）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub down_using: Option<String>,
+
+    /// 外部ツール向けの任意のメタデータ（SQL生成と差分検出では無視）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+
+    /// expand/contract による移行でのカラムの段階（オプショナル、SQL生成では無視）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<ColumnLifecycle>,
+
+    /// `contract_pending` になったことを generate が最初に記録した日付
+    ///
+    /// スナップショットにのみ記録され、`strata contract` の経過日数の判定に使用します。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_pending_since: Option<NaiveDate>,
+}
+
+/// カラムのライフサイクル
+///
+/// expand/contract による段階的な移行（カラムを追加し、二重書き込み・移行を経て旧カラムを削除する）で、
+/// カラムがどの段階にあるかを表現します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnLifecycle {
+    /// 追加したばかりで、アプリケーションがまだ値を書き込んでいない
+    Expand,
+    /// アプリケーションから使われなくなり、削除を待っている
+    ///
+    /// スキーマ定義から取り除いても削除されず、`strata contract` で明示的に削除します。
+    ContractPending,
+    /// 通常のカラム
+    Active,
+}
+
+impl std::fmt::Display for ColumnLifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ColumnLifecycle::Expand => "expand",
+            ColumnLifecycle::ContractPending => "contract_pending",
+            ColumnLifecycle::Active => "active",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 生成カラム定義
+///
+/// 式から値が算出されるカラムを表現します。`stored` が false の場合は VIRTUAL として扱います。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratedColumn {
+    /// 生成式
+    pub expression: String,
+
+    /// STORED フラグ（デフォルト: false = VIRTUAL）
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stored: bool,
+}
+
+impl GeneratedColumn {
+    /// 新しい生成カラム定義を作成
+    pub fn new(expression: impl Into<String>, stored: bool) -> Self {
+        Self {
+            expression: expression.into(),
+            stored,
+        }
+    }
+
+    /// 格納方式のキーワード（`STORED` / `VIRTUAL`）
+    pub fn storage_keyword(&self) -> &'static str {
+        if self.stored {
+            "STORED"
+        } else {
+            "VIRTUAL"
+        }
+    }
+
+    /// カラム定義に埋め込む `GENERATED ALWAYS AS (...) STORED|VIRTUAL` 句
+    pub fn to_sql_clause(&self) -> String {
+        format!(
+            "GENERATED ALWAYS AS ({}) {}",
+            self.expression.trim(),
+            self.storage_keyword()
+        )
+    }
 }
 
 /// ENUM定義
@@ -367,6 +555,10 @@ pub struct EnumDefinition {
 
     /// ENUM値（順序を保持）
     pub values: Vec<String>,
+
+    /// リネーム元のENUM型名（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
 }
 
 /// ビュー定義
@@ -411,6 +603,14 @@ impl Column {
             default_value: None,
             auto_increment: None,
             renamed_from: None,
+            generated: None,
+            comment: None,
+            on_update_current_timestamp: false,
+            type_change_using: None,
+            down_using: None,
+            metadata: BTreeMap::new(),
+            lifecycle: None,
+            contract_pending_since: None,
         }
     }
 
@@ -418,6 +618,33 @@ impl Column {
     pub fn is_auto_increment(&self) -> bool {
         self.auto_increment.unwrap_or(false)
     }
+
+    /// 生成カラムかどうか
+    pub fn is_generated(&self) -> bool {
+        self.generated.is_some()
+    }
+
+    /// 削除待ち（`lifecycle: contract_pending`）のカラムかどうか
+    pub fn is_contract_pending(&self) -> bool {
+        self.lifecycle == Some(ColumnLifecycle::ContractPending)
+    }
+
+    /// カラムのコメント（空文字列の場合は None）
+    pub fn comment_text(&self) -> Option<&str> {
+        non_empty_comment(&self.comment)
+    }
+
+    /// デフォルト値が現在日時（`CURRENT_TIMESTAMP` とその同等の表記）かどうか
+    pub fn has_current_timestamp_default(&self) -> bool {
+        self.default_value
+            .as_deref()
+            .is_some_and(is_current_timestamp_default)
+    }
+}
+
+/// 空文字列のコメントをコメントなしとして扱う
+fn non_empty_comment(comment: &Option<String>) -> Option<&str> {
+    comment.as_deref().filter(|comment| !comment.is_empty())
 }
 
 fn is_false(value: &bool) -> bool {
@@ -427,16 +654,27 @@ fn is_false(value: &bool) -> bool {
 /// カラム型
 ///
 /// サポートされるデータ型を表現します。
+/// 今後も型が追加されるため、クレート外からの `match` にはワイルドカードが必要です。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind")]
+#[non_exhaustive]
 pub enum ColumnType {
-    /// 整数型
+    /// 整数型（4バイト）
     INTEGER {
-        /// 精度（ビット数）
+        /// 精度（バイト数）
+        ///
+        /// 非推奨: `2` / `8` は SMALLINT / BIGINT の別名として読み込み時に置き換えられます
+        /// （[`ColumnType::normalize_integer_precision`]）。
         #[serde(default, skip_serializing_if = "Option::is_none")]
         precision: Option<u32>,
     },
 
+    /// 2バイト整数型
+    SMALLINT,
+
+    /// 8バイト整数型
+    BIGINT,
+
     /// 可変長文字列型
     VARCHAR {
         /// 最大長
@@ -454,6 +692,9 @@ pub enum ColumnType {
         /// タイムゾーン付きかどうか
         #[serde(default, skip_serializing_if = "Option::is_none")]
         with_time_zone: Option<bool>,
+        /// 秒の小数部の桁数 (0-6、未指定時は方言のデフォルト)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        precision: Option<u8>,
     },
 
     /// JSON型
@@ -487,6 +728,9 @@ pub enum ColumnType {
         /// タイムゾーン付きかどうか (PostgreSQL only)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         with_time_zone: Option<bool>,
+        /// 秒の小数部の桁数 (0-6、未指定時は方言のデフォルト)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        precision: Option<u8>,
     },
 
     /// バイナリラージオブジェクト型
@@ -520,18 +764,50 @@ pub enum ColumnType {
     },
 }
 
+impl ColumnType {
+    /// 非推奨の `INTEGER` の `precision` 指定を明示的な整数型に置き換える
+    ///
+    /// `precision: 2` は SMALLINT、`precision: 8` は BIGINT になります。
+    /// それ以外の値はSQL生成で無視されてきたため、精度なしの INTEGER になります。
+    /// 置き換える必要がない場合は `None` を返します。
+    pub fn normalize_integer_precision(&self) -> Option<ColumnType> {
+        match self {
+            ColumnType::INTEGER {
+                precision: Some(precision),
+            } => Some(match precision {
+                2 => ColumnType::SMALLINT,
+                8 => ColumnType::BIGINT,
+                _ => ColumnType::INTEGER { precision: None },
+            }),
+            _ => None,
+        }
+    }
+
+    /// 整数型のバイト数（SMALLINT: 2, INTEGER: 4, BIGINT: 8、整数型以外は `None`）
+    pub fn integer_width(&self) -> Option<u32> {
+        match self {
+            ColumnType::SMALLINT | ColumnType::INTEGER { precision: Some(2) } => Some(2),
+            ColumnType::BIGINT | ColumnType::INTEGER { precision: Some(8) } => Some(8),
+            ColumnType::INTEGER { .. } => Some(4),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for ColumnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ColumnType::INTEGER { precision: None } => write!(f, "INTEGER"),
             ColumnType::INTEGER { precision: Some(p) } => write!(f, "INTEGER({})", p),
+            ColumnType::SMALLINT => write!(f, "SMALLINT"),
+            ColumnType::BIGINT => write!(f, "BIGINT"),
             ColumnType::VARCHAR { length } => write!(f, "VARCHAR({})", length),
             ColumnType::TEXT => write!(f, "TEXT"),
             ColumnType::BOOLEAN => write!(f, "BOOLEAN"),
             ColumnType::TIMESTAMP {
-                with_time_zone: Some(true),
-            } => write!(f, "TIMESTAMP WITH TIME ZONE"),
-            ColumnType::TIMESTAMP { .. } => write!(f, "TIMESTAMP"),
+                with_time_zone,
+                precision,
+            } => format_datetime(f, "TIMESTAMP", *with_time_zone, *precision),
             ColumnType::JSON => write!(f, "JSON"),
             ColumnType::DECIMAL { precision, scale } => {
                 write!(f, "DECIMAL({}, {})", precision, scale)
@@ -541,9 +817,9 @@ impl std::fmt::Display for ColumnType {
             ColumnType::CHAR { length } => write!(f, "CHAR({})", length),
             ColumnType::DATE => write!(f, "DATE"),
             ColumnType::TIME {
-                with_time_zone: Some(true),
-            } => write!(f, "TIME WITH TIME ZONE"),
-            ColumnType::TIME { .. } => write!(f, "TIME"),
+                with_time_zone,
+                precision,
+            } => format_datetime(f, "TIME", *with_time_zone, *precision),
             ColumnType::BLOB => write!(f, "BLOB"),
             ColumnType::UUID => write!(f, "UUID"),
             ColumnType::JSONB => write!(f, "JSONB"),
@@ -555,6 +831,23 @@ impl std::fmt::Display for ColumnType {
     }
 }
 
+/// TIMESTAMP / TIME 型を `TIMESTAMP(3) WITH TIME ZONE` の形式で出力
+fn format_datetime(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    with_time_zone: Option<bool>,
+    precision: Option<u8>,
+) -> std::fmt::Result {
+    write!(f, "{}", name)?;
+    if let Some(precision) = precision {
+        write!(f, "({})", precision)?;
+    }
+    if with_time_zone == Some(true) {
+        write!(f, " WITH TIME ZONE")?;
+    }
+    Ok(())
+}
+
 /// DialectSpecific 型を SQL 風のフォーマットで出力
 fn format_dialect_specific(
     f: &mut std::fmt::Formatter<'_>,
@@ -627,22 +920,347 @@ pub struct Index {
     /// インデックス名
     pub name: String,
 
-    /// インデックス対象のカラム名リスト
-    pub columns: Vec<String>,
+    /// インデックス対象のカラムリスト
+    pub columns: Vec<IndexColumn>,
 
     /// ユニークインデックスかどうか
     #[serde(default, skip_serializing_if = "is_false")]
     pub unique: bool,
+
+    /// インデックスの種類（YAMLでは `type`、省略時はデータベースのデフォルト）
+    ///
+    /// FULLTEXTはMySQL、GIN / GISTはPostgreSQLのみ対応します。
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<IndexType>,
+
+    /// 部分インデックスの条件式（YAMLでは `where`、例: `deleted_at IS NULL`）
+    ///
+    /// PostgreSQLとSQLiteのみ対応します。MySQLではバリデーションエラーになります。
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub where_clause: Option<String>,
+
+    /// リネーム元のインデックス名（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+
+    /// 外部ツール向けの任意のメタデータ（SQL生成と差分検出では無視）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl Index {
     /// 新しいインデックスを作成
+    ///
+    /// 各カラムはソート順・NULL順序の指定なし（ASC）として扱われます。
     pub fn new(name: String, columns: Vec<String>, unique: bool) -> Self {
+        Self::with_columns(
+            name,
+            columns.into_iter().map(IndexColumn::new).collect(),
+            unique,
+        )
+    }
+
+    /// ソート順・NULL順序付きのカラムリストからインデックスを作成
+    pub fn with_columns(name: String, columns: Vec<IndexColumn>, unique: bool) -> Self {
         Self {
             name,
             columns,
             unique,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// 部分インデックスの条件式を設定
+    pub fn with_where(mut self, predicate: impl Into<String>) -> Self {
+        self.where_clause = Some(predicate.into());
+        self
+    }
+
+    /// インデックスの種類を設定
+    pub fn with_index_type(mut self, index_type: IndexType) -> Self {
+        self.index_type = Some(index_type);
+        self
+    }
+
+    /// 実効的なインデックスの種類
+    ///
+    /// 省略時はB-treeとして扱います（各データベースのデフォルト）。
+    pub fn effective_index_type(&self) -> IndexType {
+        self.index_type.unwrap_or(IndexType::BTree)
+    }
+
+    /// インデックス対象のカラム名リストを取得
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.name.clone()).collect()
+    }
+
+    /// カラム構成が同等かどうか
+    ///
+    /// 省略されたソート順・NULL順序はデフォルト値として比較します。
+    pub fn columns_equivalent(&self, other: &Index) -> bool {
+        self.columns.len() == other.columns.len()
+            && self
+                .columns
+                .iter()
+                .zip(&other.columns)
+                .all(|(a, b)| a.is_equivalent(b))
+    }
+}
+
+/// インデックスの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum IndexType {
+    /// B-tree（デフォルト）
+    #[serde(rename = "BTREE")]
+    BTree,
+    /// ハッシュ（PostgreSQL・MySQL）
+    Hash,
+    /// 全文検索（MySQLのみ）
+    Fulltext,
+    /// GIN（PostgreSQLのみ）
+    Gin,
+    /// GiST（PostgreSQLのみ）
+    Gist,
+}
+
+impl IndexType {
+    /// YAMLおよびSQLでの名前を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexType::BTree => "BTREE",
+            IndexType::Hash => "HASH",
+            IndexType::Fulltext => "FULLTEXT",
+            IndexType::Gin => "GIN",
+            IndexType::Gist => "GIST",
+        }
+    }
+
+    /// データベースが返すアクセスメソッド名から取得（大文字小文字は区別しない）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "BTREE" => Some(IndexType::BTree),
+            "HASH" => Some(IndexType::Hash),
+            "FULLTEXT" => Some(IndexType::Fulltext),
+            "GIN" => Some(IndexType::Gin),
+            "GIST" => Some(IndexType::Gist),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for IndexType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// インデックスカラムのソート順
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SortOrder {
+    /// 昇順（デフォルト）
+    Asc,
+    /// 降順
+    Desc,
+}
+
+impl SortOrder {
+    /// SQLキーワードを取得
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+/// インデックスカラムのNULL順序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum NullsOrder {
+    /// NULLを先頭に配置
+    First,
+    /// NULLを末尾に配置
+    Last,
+}
+
+impl NullsOrder {
+    /// SQLキーワードを取得
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            NullsOrder::First => "NULLS FIRST",
+            NullsOrder::Last => "NULLS LAST",
+        }
+    }
+}
+
+/// インデックスカラム
+///
+/// YAMLではカラム名のみの文字列（ASC・NULL順序指定なし）と、
+/// `name` / `order` / `nulls` / `length` を持つマップの両方を受け付けます。
+/// 修飾子がない場合は文字列としてシリアライズされます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexColumn {
+    /// カラム名
+    pub name: String,
+
+    /// ソート順（省略時はASC）
+    pub order: Option<SortOrder>,
+
+    /// NULL順序（省略時はソート順に応じたデータベースのデフォルト）
+    pub nulls: Option<NullsOrder>,
+
+    /// 先頭から何文字をインデックス対象にするか（MySQLのプレフィックスインデックス）
+    pub length: Option<u32>,
+}
+
+impl IndexColumn {
+    /// 修飾子なしのインデックスカラムを作成
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            order: None,
+            nulls: None,
+            length: None,
+        }
+    }
+
+    /// ソート順を設定
+    pub fn with_order(mut self, order: SortOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// NULL順序を設定
+    pub fn with_nulls(mut self, nulls: NullsOrder) -> Self {
+        self.nulls = Some(nulls);
+        self
+    }
+
+    /// プレフィックス長を設定
+    pub fn with_length(mut self, length: u32) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// 降順かどうか
+    pub fn is_descending(&self) -> bool {
+        self.order == Some(SortOrder::Desc)
+    }
+
+    /// 実効的なNULL順序
+    ///
+    /// 省略時は昇順ならNULLS LAST、降順ならNULLS FIRST（PostgreSQLのデフォルト）。
+    pub fn effective_nulls(&self) -> NullsOrder {
+        self.nulls.unwrap_or(if self.is_descending() {
+            NullsOrder::First
+        } else {
+            NullsOrder::Last
+        })
+    }
+
+    /// ソート順・NULL順序・プレフィックス長の修飾子を持つかどうか
+    pub fn has_modifiers(&self) -> bool {
+        self.order.is_some() || self.nulls.is_some() || self.length.is_some()
+    }
+
+    /// 省略値をデフォルトとして解釈した上で同等かどうか
+    pub fn is_equivalent(&self, other: &IndexColumn) -> bool {
+        self.name == other.name
+            && self.is_descending() == other.is_descending()
+            && self.effective_nulls() == other.effective_nulls()
+            && self.length == other.length
+    }
+}
+
+impl From<&str> for IndexColumn {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for IndexColumn {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl std::fmt::Display for IndexColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(length) = self.length {
+            write!(f, "({})", length)?;
+        }
+        if let Some(order) = self.order {
+            write!(f, " {}", order.as_sql())?;
         }
+        if let Some(nulls) = self.nulls {
+            write!(f, " {}", nulls.as_sql())?;
+        }
+        Ok(())
+    }
+}
+
+/// IndexColumnのYAML表現
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum IndexColumnRepr {
+    /// カラム名のみ
+    Name(String),
+    /// 修飾子付き
+    Detailed {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        order: Option<SortOrder>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        nulls: Option<NullsOrder>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        length: Option<u32>,
+    },
+}
+
+impl Serialize for IndexColumn {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let repr = if self.has_modifiers() {
+            IndexColumnRepr::Detailed {
+                name: self.name.clone(),
+                order: self.order,
+                nulls: self.nulls,
+                length: self.length,
+            }
+        } else {
+            IndexColumnRepr::Name(self.name.clone())
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IndexColumn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match IndexColumnRepr::deserialize(deserializer)? {
+            IndexColumnRepr::Name(name) => IndexColumn::new(name),
+            IndexColumnRepr::Detailed {
+                name,
+                order,
+                nulls,
+                length,
+            } => IndexColumn {
+                name,
+                order,
+                nulls,
+                length,
+            },
+        })
     }
 }
 
@@ -681,9 +1299,11 @@ impl ReferentialAction {
 /// 制約定義
 ///
 /// テーブルの制約（PRIMARY KEY, FOREIGN KEY, UNIQUE, CHECK）を表現します。
+/// 制約の種類は今後も追加されるため、クレート外からの `match` にはワイルドカードが必要です。
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[allow(non_camel_case_types)]
+#[non_exhaustive]
 pub enum Constraint {
     /// プライマリキー制約
     PRIMARY_KEY {
@@ -719,7 +1339,8 @@ pub enum Constraint {
 
     /// チェック制約
     CHECK {
-        /// 対象カラム
+        /// 対象カラム（参考情報。差分検出では式のみを比較する）
+        #[serde(default)]
         columns: Vec<String>,
 
         /// チェック式
@@ -737,12 +1358,481 @@ impl Constraint {
             Constraint::CHECK { .. } => "CHECK",
         }
     }
+
+    /// 対象カラムを取得
+    pub fn columns(&self) -> &[String] {
+        match self {
+            Constraint::PRIMARY_KEY { columns }
+            | Constraint::FOREIGN_KEY { columns, .. }
+            | Constraint::UNIQUE { columns }
+            | Constraint::CHECK { columns, .. } => columns,
+        }
+    }
+
+    /// 決定的な出力順のためのソートキー（種類 → 対象カラム）
+    ///
+    /// 種類の順序は宣言順（PRIMARY KEY, FOREIGN KEY, UNIQUE, CHECK）です。
+    pub fn sort_key(&self) -> (u8, &[String]) {
+        let rank = match self {
+            Constraint::PRIMARY_KEY { .. } => 0,
+            Constraint::FOREIGN_KEY { .. } => 1,
+            Constraint::UNIQUE { .. } => 2,
+            Constraint::CHECK { .. } => 3,
+        };
+        (rank, self.columns())
+    }
+}
+
+/// 式全体が一対の括弧で囲まれている場合のみ外側の括弧を除去する
+///
+/// `(balance >= 0)` → `balance >= 0` (除去)
+/// `(val >= 0) AND (val <= 100)` → そのまま (除去しない: 先頭の `(` と末尾の `)` が対応していない)
+pub fn strip_outer_parens(expr: &str) -> String {
+    let trimmed = expr.trim();
+    if !trimmed.starts_with('(') || !trimmed.ends_with(')') {
+        return trimmed.to_string();
+    }
+
+    // 先頭の '(' に対応する ')' が末尾であることを確認
+    let mut depth = 0;
+    for (i, ch) in trimmed.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    // 先頭の '(' の対応が末尾の ')' と一致するか
+                    return if i == trimmed.len() - 1 {
+                        trimmed[1..i].trim().to_string()
+                    } else {
+                        trimmed.to_string()
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    trimmed.to_string()
+}
+
+/// 現在日時を表すデフォルト値の正規の表記
+///
+/// スキーマでは `default_value: CURRENT_TIMESTAMP` と書き、SQL生成時に方言ごとの表記に置き換えます。
+pub const CURRENT_TIMESTAMP_DEFAULT: &str = "CURRENT_TIMESTAMP";
+
+/// デフォルト値が現在日時を表す式かどうか
+///
+/// 正規の表記 `CURRENT_TIMESTAMP`（大文字・小文字は問わない）に加えて、データベースから
+/// 読み込まれる同等の表記を認識します。
+/// - `CURRENT_TIMESTAMP(6)`、`current_timestamp()`（MySQL・MariaDB）
+/// - `now()`（PostgreSQL・MySQL）
+/// - `datetime('now')`（SQLite）
+/// - 式全体を括弧で囲んだもの（`(datetime('now'))` など）
+pub fn is_current_timestamp_default(value: &str) -> bool {
+    let mut expr = value.trim().to_string();
+    loop {
+        let stripped = strip_outer_parens(&expr);
+        if stripped == expr {
+            break;
+        }
+        expr = stripped;
+    }
+    let expr: String = expr
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    // 関数呼び出しの引数（秒の小数部の桁数）は省略可能
+    let is_call_with_precision = |name: &str| {
+        expr.strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('('))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .is_some_and(|digits| digits.chars().all(|c| c.is_ascii_digit()))
+    };
+
+    expr == "current_timestamp"
+        || expr == "datetime('now')"
+        || is_call_with_precision("current_timestamp")
+        || is_call_with_precision("now")
+}
+
+/// デフォルト値を正規化する
+///
+/// 現在日時を表す式は正規の表記 `CURRENT_TIMESTAMP` に置き換え、それ以外はそのまま返します。
+/// データベースから読み込んだスキーマとスキーマ定義の表記の違いで差分が出ないようにします。
+pub fn normalize_default_value(value: &str) -> String {
+    if is_current_timestamp_default(value) {
+        CURRENT_TIMESTAMP_DEFAULT.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// 型キャストの型名の続きとして読み飛ばす単語（`character varying`、`double precision` など）
+const CAST_TYPE_CONTINUATIONS: &[&str] =
+    &["varying", "precision", "with", "without", "time", "zone"];
+
+/// 比較用にCHECK制約の式を正規化する
+///
+/// データベースから読み込んだ式とスキーマ定義に記述した式の表記の違いを無視します。
+/// - キーワード・識別子の大文字と小文字、空白の違い（文字列リテラル内は保持）
+/// - MySQLが付けるバッククォート（`` `balance` `` → `balance`）
+/// - PostgreSQLが付ける型キャスト（`(status)::text` → `status`）
+/// - 単一の識別子・リテラルを囲む括弧と、式全体を囲む括弧
+pub fn normalize_check_expression(expr: &str) -> String {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut result = String::with_capacity(expr.len());
+    let mut pending_space = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '\'' || ch == '"' {
+            // 文字列リテラル・クォート識別子はそのまま保持（'' はエスケープ）
+            push_token_char(&mut result, ch, &mut pending_space);
+            i += 1;
+            while i < chars.len() {
+                result.push(chars[i]);
+                if chars[i] == ch {
+                    if chars.get(i + 1) == Some(&ch) {
+                        result.push(ch);
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            i += 1;
+        } else if ch == '`' {
+            i += 1;
+        } else if ch == ':' && chars.get(i + 1) == Some(&':') {
+            i = skip_cast_type(&chars, i + 2);
+        } else if ch.is_whitespace() {
+            pending_space = true;
+            i += 1;
+        } else {
+            push_token_char(&mut result, ch.to_ascii_lowercase(), &mut pending_space);
+            i += 1;
+        }
+    }
+
+    let mut normalized = unwrap_single_token_parens(&result);
+    loop {
+        let stripped = strip_outer_parens(&normalized);
+        if stripped == normalized {
+            return normalized;
+        }
+        normalized = stripped;
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// 正規化後の文字を追加する（空白は単語と単語の間にのみ1つ残す）
+fn push_token_char(result: &mut String, ch: char, pending_space: &mut bool) {
+    if *pending_space
+        && result
+            .chars()
+            .last()
+            .is_some_and(|last| is_word_char(last) || last == '\'' || last == '"')
+        && (is_word_char(ch) || ch == '\'' || ch == '"')
+    {
+        result.push(' ');
+    }
+    *pending_space = false;
+    result.push(ch);
+}
+
+/// `::` に続く型名（`text`、`character varying(20)`、`text[]` など）を読み飛ばし、次の位置を返す
+fn skip_cast_type(chars: &[char], start: usize) -> usize {
+    let read_word = |from: usize| {
+        let mut end = from;
+        while end < chars.len() && is_word_char(chars[end]) {
+            end += 1;
+        }
+        end
+    };
+
+    let mut i = read_word(start);
+    loop {
+        let mut next = i;
+        while next < chars.len() && chars[next] == ' ' {
+            next += 1;
+        }
+        let end = read_word(next);
+        let word: String = chars[next..end].iter().collect::<String>().to_lowercase();
+        if next == i || !CAST_TYPE_CONTINUATIONS.contains(&word.as_str()) {
+            break;
+        }
+        i = end;
+    }
+    if chars.get(i) == Some(&'(') {
+        if let Some(close) = chars[i..].iter().position(|&ch| ch == ')') {
+            i += close + 1;
+        }
+    }
+    while chars.get(i) == Some(&'[') && chars.get(i + 1) == Some(&']') {
+        i += 2;
+    }
+    i
+}
+
+/// 単一の識別子・数値を囲む括弧を除去する（関数呼び出しの括弧は残す）
+///
+/// `(status) = 'a'` → `status = 'a'`、`length(name) > 0` → そのまま
+fn unwrap_single_token_parens(expr: &str) -> String {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut result = String::with_capacity(expr.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '(' && !result.chars().last().is_some_and(is_word_char) {
+            let mut end = i + 1;
+            while end < chars.len() && (is_word_char(chars[end]) || chars[end] == '.') {
+                end += 1;
+            }
+            if end > i + 1 && chars.get(end) == Some(&')') {
+                result.extend(&chars[i + 1..end]);
+                // `(tags)is` のように括弧で区切られていた単語の間には空白を残す
+                if chars.get(end + 1).is_some_and(|&next| is_word_char(next)) {
+                    result.push(' ');
+                }
+                i = end + 1;
+                continue;
+            }
+        }
+        result.push(chars[i]);
+        if chars[i] == '\'' || chars[i] == '"' {
+            // クォート内の括弧は対象外
+            let quote = chars[i];
+            i += 1;
+            while i < chars.len() {
+                result.push(chars[i]);
+                if chars[i] == quote {
+                    break;
+                }
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// `column_type_coverage_keys` が返すキーの一覧
+    const COLUMN_TYPE_COVERAGE_KEYS: &[&str] = &[
+        "type.INTEGER",
+        "type.SMALLINT",
+        "type.BIGINT",
+        "type.VARCHAR",
+        "type.TEXT",
+        "type.BOOLEAN",
+        "type.TIMESTAMP",
+        "type.TIMESTAMP.with_time_zone",
+        "type.TIMESTAMP.precision",
+        "type.JSON",
+        "type.DECIMAL",
+        "type.FLOAT",
+        "type.DOUBLE",
+        "type.CHAR",
+        "type.DATE",
+        "type.TIME",
+        "type.TIME.with_time_zone",
+        "type.TIME.precision",
+        "type.BLOB",
+        "type.UUID",
+        "type.JSONB",
+        "type.ENUM",
+        "type.DialectSpecific",
+        "type.DialectSpecific.params",
+    ];
+
+    /// `constraint_coverage_keys` が返すキーの一覧
+    const CONSTRAINT_COVERAGE_KEYS: &[&str] = &[
+        "constraint.PRIMARY_KEY",
+        "constraint.FOREIGN_KEY",
+        "constraint.FOREIGN_KEY.on_delete",
+        "constraint.FOREIGN_KEY.on_update",
+        "constraint.UNIQUE",
+        "constraint.CHECK",
+    ];
+
+    /// カラム型ごとの網羅確認キー
+    ///
+    /// `ColumnType` はクレート外では `#[non_exhaustive]` のため、他クレートのテストでは網羅的に分解できない。
+    /// ここで全バリアントとフィールドを分解し、追加した時点でコンパイルエラーになるようにする。
+    /// キーを追加したら `COLUMN_TYPE_COVERAGE_KEYS` と `round_trip_column_types` を更新し、
+    /// 各方言のSQLジェネレーターとYAMLのラウンドトリップテスト（cli/tests）も対応させること。
+    fn column_type_coverage_keys(column_type: &ColumnType) -> &'static [&'static str] {
+        match column_type {
+            ColumnType::INTEGER { precision: _ } => &["type.INTEGER"],
+            ColumnType::SMALLINT => &["type.SMALLINT"],
+            ColumnType::BIGINT => &["type.BIGINT"],
+            ColumnType::VARCHAR { length: _ } => &["type.VARCHAR"],
+            ColumnType::TEXT => &["type.TEXT"],
+            ColumnType::BOOLEAN => &["type.BOOLEAN"],
+            ColumnType::TIMESTAMP {
+                with_time_zone: _,
+                precision: _,
+            } => &[
+                "type.TIMESTAMP",
+                "type.TIMESTAMP.with_time_zone",
+                "type.TIMESTAMP.precision",
+            ],
+            ColumnType::JSON => &["type.JSON"],
+            ColumnType::DECIMAL {
+                precision: _,
+                scale: _,
+            } => &["type.DECIMAL"],
+            ColumnType::FLOAT => &["type.FLOAT"],
+            ColumnType::DOUBLE => &["type.DOUBLE"],
+            ColumnType::CHAR { length: _ } => &["type.CHAR"],
+            ColumnType::DATE => &["type.DATE"],
+            ColumnType::TIME {
+                with_time_zone: _,
+                precision: _,
+            } => &[
+                "type.TIME",
+                "type.TIME.with_time_zone",
+                "type.TIME.precision",
+            ],
+            ColumnType::BLOB => &["type.BLOB"],
+            ColumnType::UUID => &["type.UUID"],
+            ColumnType::JSONB => &["type.JSONB"],
+            ColumnType::Enum { name: _ } => &["type.ENUM"],
+            ColumnType::DialectSpecific { kind: _, params: _ } => {
+                &["type.DialectSpecific", "type.DialectSpecific.params"]
+            }
+        }
+    }
+
+    /// 制約ごとの網羅確認キー
+    ///
+    /// `column_type_coverage_keys` と同様に、制約の種類やフィールドの追加をコンパイルエラーで検出する。
+    fn constraint_coverage_keys(constraint: &Constraint) -> &'static [&'static str] {
+        match constraint {
+            Constraint::PRIMARY_KEY { columns: _ } => &["constraint.PRIMARY_KEY"],
+            Constraint::FOREIGN_KEY {
+                columns: _,
+                referenced_table: _,
+                referenced_columns: _,
+                on_delete: _,
+                on_update: _,
+            } => &[
+                "constraint.FOREIGN_KEY",
+                "constraint.FOREIGN_KEY.on_delete",
+                "constraint.FOREIGN_KEY.on_update",
+            ],
+            Constraint::UNIQUE { columns: _ } => &["constraint.UNIQUE"],
+            Constraint::CHECK {
+                columns: _,
+                check_expression: _,
+            } => &["constraint.CHECK"],
+        }
+    }
+
+    /// 全バリアントのカラム型
+    fn round_trip_column_types() -> Vec<ColumnType> {
+        vec![
+            ColumnType::INTEGER { precision: None },
+            ColumnType::SMALLINT,
+            ColumnType::BIGINT,
+            ColumnType::VARCHAR { length: 255 },
+            ColumnType::TEXT,
+            ColumnType::BOOLEAN,
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            ColumnType::JSON,
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            ColumnType::FLOAT,
+            ColumnType::DOUBLE,
+            ColumnType::CHAR { length: 2 },
+            ColumnType::DATE,
+            ColumnType::TIME {
+                with_time_zone: None,
+                precision: None,
+            },
+            ColumnType::BLOB,
+            ColumnType::UUID,
+            ColumnType::JSONB,
+            ColumnType::Enum {
+                name: "status".to_string(),
+            },
+            ColumnType::DialectSpecific {
+                kind: "VARBIT".to_string(),
+                params: serde_json::json!({"length": 64}),
+            },
+        ]
+    }
+
+    /// 全種類の制約
+    fn round_trip_constraints() -> Vec<Constraint> {
+        let columns = vec!["id".to_string()];
+        vec![
+            Constraint::PRIMARY_KEY {
+                columns: columns.clone(),
+            },
+            Constraint::FOREIGN_KEY {
+                columns: columns.clone(),
+                referenced_table: "users".to_string(),
+                referenced_columns: columns.clone(),
+                on_delete: None,
+                on_update: None,
+            },
+            Constraint::UNIQUE {
+                columns: columns.clone(),
+            },
+            Constraint::CHECK {
+                columns,
+                check_expression: "id > 0".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_samples_cover_all_column_types_and_constraints() {
+        let column_type_keys = round_trip_column_types()
+            .iter()
+            .flat_map(column_type_coverage_keys)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(column_type_keys, COLUMN_TYPE_COVERAGE_KEYS);
+
+        let constraint_keys = round_trip_constraints()
+            .iter()
+            .flat_map(constraint_coverage_keys)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(constraint_keys, CONSTRAINT_COVERAGE_KEYS);
+    }
+
+    #[test]
+    fn test_column_types_and_constraints_serde_round_trip() {
+        for column_type in round_trip_column_types() {
+            let json = serde_json::to_string(&column_type).unwrap();
+            let restored: ColumnType = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, column_type, "round trip changed {}", json);
+        }
+        for constraint in round_trip_constraints() {
+            let json = serde_json::to_string(&constraint).unwrap();
+            let restored: Constraint = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, constraint, "round trip changed {}", json);
+        }
+    }
+
     #[test]
     fn test_schema_new() {
         let schema = Schema::new("1.0".to_string());
@@ -758,6 +1848,7 @@ mod tests {
         let enum_def = EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         };
 
         schema.add_enum(enum_def);
@@ -767,6 +1858,16 @@ mod tests {
         assert_eq!(stored.values.len(), 2);
     }
 
+    #[test]
+    fn test_schema_external_tables() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.external_tables.insert("external_users".to_string());
+
+        assert!(schema.is_external_table("external_users"));
+        assert!(!schema.has_table("external_users"));
+        assert!(!schema.is_external_table("users"));
+    }
+
     #[test]
     fn test_table_new() {
         let table = Table::new("users".to_string());
@@ -793,6 +1894,196 @@ mod tests {
         assert!(index.unique);
     }
 
+    #[test]
+    fn test_index_column_deserialize_string_and_map() {
+        let yaml = r#"
+name: idx_events_created_at
+columns:
+  - user_id
+  - name: created_at
+    order: DESC
+    nulls: LAST
+"#;
+        let index: Index = serde_saphyr::from_str(yaml).unwrap();
+
+        assert_eq!(index.columns[0], IndexColumn::new("user_id"));
+        assert_eq!(
+            index.columns[1],
+            IndexColumn::new("created_at")
+                .with_order(SortOrder::Desc)
+                .with_nulls(NullsOrder::Last)
+        );
+        assert_eq!(index.column_names(), vec!["user_id", "created_at"]);
+    }
+
+    #[test]
+    fn test_index_column_serialize_plain_column_as_string() {
+        let index = Index::with_columns(
+            "idx_events_created_at".to_string(),
+            vec![
+                IndexColumn::new("user_id"),
+                IndexColumn::new("created_at").with_order(SortOrder::Desc),
+            ],
+            false,
+        );
+
+        let json = serde_json::to_value(&index).unwrap();
+        assert_eq!(
+            json["columns"],
+            serde_json::json!(["user_id", {"name": "created_at", "order": "DESC"}])
+        );
+    }
+
+    #[test]
+    fn test_index_column_equivalence_uses_defaults() {
+        let plain = IndexColumn::new("created_at");
+        let asc = IndexColumn::new("created_at").with_order(SortOrder::Asc);
+        let asc_nulls_last = asc.clone().with_nulls(NullsOrder::Last);
+        let desc = IndexColumn::new("created_at").with_order(SortOrder::Desc);
+        let desc_nulls_first = desc.clone().with_nulls(NullsOrder::First);
+        let desc_nulls_last = desc.clone().with_nulls(NullsOrder::Last);
+
+        // 省略値はデフォルトとして扱われる
+        assert!(plain.is_equivalent(&asc));
+        assert!(plain.is_equivalent(&asc_nulls_last));
+        assert!(desc.is_equivalent(&desc_nulls_first));
+
+        // ソート順・NULL順序の違いは検出される
+        assert!(!plain.is_equivalent(&desc));
+        assert!(!desc.is_equivalent(&desc_nulls_last));
+        assert_eq!(desc_nulls_last.to_string(), "created_at DESC NULLS LAST");
+    }
+
+    #[test]
+    fn test_normalize_integer_precision() {
+        assert_eq!(
+            ColumnType::INTEGER { precision: Some(2) }.normalize_integer_precision(),
+            Some(ColumnType::SMALLINT)
+        );
+        assert_eq!(
+            ColumnType::INTEGER { precision: Some(8) }.normalize_integer_precision(),
+            Some(ColumnType::BIGINT)
+        );
+        assert_eq!(
+            ColumnType::INTEGER { precision: Some(4) }.normalize_integer_precision(),
+            Some(ColumnType::INTEGER { precision: None })
+        );
+        assert_eq!(
+            ColumnType::INTEGER { precision: None }.normalize_integer_precision(),
+            None
+        );
+        assert_eq!(ColumnType::BIGINT.normalize_integer_precision(), None);
+    }
+
+    #[test]
+    fn test_integer_width() {
+        assert_eq!(ColumnType::SMALLINT.integer_width(), Some(2));
+        assert_eq!(
+            ColumnType::INTEGER { precision: None }.integer_width(),
+            Some(4)
+        );
+        assert_eq!(ColumnType::BIGINT.integer_width(), Some(8));
+        assert_eq!(
+            ColumnType::INTEGER { precision: Some(8) }.integer_width(),
+            ColumnType::BIGINT.integer_width()
+        );
+        assert_eq!(ColumnType::TEXT.integer_width(), None);
+    }
+
+    #[test]
+    fn test_normalize_default_value_current_timestamp_table() {
+        for value in [
+            "CURRENT_TIMESTAMP",
+            "current_timestamp",
+            "CURRENT_TIMESTAMP()",
+            "CURRENT_TIMESTAMP(6)",
+            "current_timestamp(3)",
+            "now()",
+            "NOW(6)",
+            "datetime('now')",
+            "(datetime('now'))",
+            "DATETIME( 'now' )",
+        ] {
+            assert!(is_current_timestamp_default(value), "{}", value);
+            assert_eq!(normalize_default_value(value), CURRENT_TIMESTAMP_DEFAULT);
+        }
+
+        for value in [
+            "'CURRENT_TIMESTAMP'",
+            "CURRENT_DATE",
+            "now() + interval '1 day'",
+            "datetime('now', 'localtime')",
+            "current_timestamp(x)",
+            "0",
+        ] {
+            assert!(!is_current_timestamp_default(value), "{}", value);
+            assert_eq!(normalize_default_value(value), value);
+        }
+    }
+
+    #[test]
+    fn test_normalize_check_expression_ignores_parens_case_and_whitespace() {
+        let expected = normalize_check_expression("start_date < end_date");
+        assert_eq!(expected, "start_date<end_date");
+        assert_eq!(
+            normalize_check_expression("((start_date < end_date))"),
+            expected
+        );
+        assert_eq!(
+            normalize_check_expression("(START_DATE <   end_date)"),
+            expected
+        );
+        assert_eq!(
+            normalize_check_expression("(price >= 0) AND (price <= 100)"),
+            normalize_check_expression("(price>=0) and (price<=100)")
+        );
+        // 式全体を囲んでいない括弧は残す
+        assert_ne!(
+            normalize_check_expression("(a > 0) OR (b > 0)"),
+            normalize_check_expression("a > 0 OR b > 0")
+        );
+    }
+
+    #[test]
+    fn test_normalize_check_expression_removes_postgres_casts() {
+        assert_eq!(
+            normalize_check_expression("((status)::text <> 'archived'::text)"),
+            normalize_check_expression("status <> 'archived'")
+        );
+        assert_eq!(
+            normalize_check_expression("(length((code)::character varying(10)) > 0)"),
+            normalize_check_expression("length(code) > 0")
+        );
+        assert_eq!(
+            normalize_check_expression("(tags)::text[] IS NOT NULL"),
+            normalize_check_expression("tags IS NOT NULL")
+        );
+    }
+
+    #[test]
+    fn test_normalize_check_expression_removes_mysql_backticks() {
+        assert_eq!(
+            normalize_check_expression("(`balance` >= 0)"),
+            normalize_check_expression("balance >= 0")
+        );
+        assert_eq!(
+            normalize_check_expression("(`start_date` < `end_date`)"),
+            normalize_check_expression("start_date < end_date")
+        );
+    }
+
+    #[test]
+    fn test_normalize_check_expression_keeps_string_literals() {
+        assert_eq!(
+            normalize_check_expression("status IN ('Active',  'A (b)')"),
+            "status in('Active','A (b)')"
+        );
+        assert_ne!(
+            normalize_check_expression("status = 'Active'"),
+            normalize_check_expression("status = 'active'")
+        );
+    }
+
     #[test]
     fn test_constraint_kind() {
         let pk = Constraint::PRIMARY_KEY {
@@ -1155,7 +2446,8 @@ mod tests {
             format!(
                 "{}",
                 ColumnType::TIMESTAMP {
-                    with_time_zone: None
+                    with_time_zone: None,
+                    precision: None
                 }
             ),
             "TIMESTAMP"
@@ -1164,7 +2456,8 @@ mod tests {
             format!(
                 "{}",
                 ColumnType::TIMESTAMP {
-                    with_time_zone: Some(false)
+                    with_time_zone: Some(false),
+                    precision: None
                 }
             ),
             "TIMESTAMP"
@@ -1173,7 +2466,8 @@ mod tests {
             format!(
                 "{}",
                 ColumnType::TIMESTAMP {
-                    with_time_zone: Some(true)
+                    with_time_zone: Some(true),
+                    precision: None
                 }
             ),
             "TIMESTAMP WITH TIME ZONE"
@@ -1186,7 +2480,8 @@ mod tests {
             format!(
                 "{}",
                 ColumnType::TIME {
-                    with_time_zone: None
+                    with_time_zone: None,
+                    precision: None
                 }
             ),
             "TIME"
@@ -1195,7 +2490,8 @@ mod tests {
             format!(
                 "{}",
                 ColumnType::TIME {
-                    with_time_zone: Some(true)
+                    with_time_zone: Some(true),
+                    precision: None
                 }
             ),
             "TIME WITH TIME ZONE"
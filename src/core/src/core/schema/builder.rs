@@ -0,0 +1,678 @@
+// スキーマビルダー
+//
+// テストやコード生成で Schema / Table / Column を組み立てるための流れるようなAPI。
+// `Table::new` + `add_column` + `add_constraint` の呼び出しを1テーブル1クロージャにまとめます。
+//
+// 検証は strata-db の SchemaValidatorService が担うため、このモジュールは構造の組み立てだけを行い、
+// 検証関数を受け取る `build_with` を提供します（`strata_db::SchemaBuilderExt::build` が利用）。
+
+use crate::core::error::ValidationResult;
+use crate::core::schema::{
+    Column, ColumnType, Constraint, EnumDefinition, Index, IndexType, ReferentialAction, Schema,
+    Table, View,
+};
+
+/// スキーマビルダー
+///
+/// # Examples
+///
+/// ```
+/// use strata_core::core::schema::SchemaBuilder;
+///
+/// let schema = SchemaBuilder::new("1.0")
+///     .table("orgs", |t| {
+///         t.integer("id").primary_key();
+///     })
+///     .table("users", |t| {
+///         t.integer("id").primary_key();
+///         t.integer("org_id");
+///         t.varchar("email", 255).nullable().unique();
+///         t.foreign_key(&["org_id"], "orgs", &["id"]).on_delete_cascade();
+///     })
+///     .build_unvalidated();
+///
+/// assert_eq!(schema.table_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl SchemaBuilder {
+    /// 指定したバージョンの空のスキーマから組み立てを始める
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            schema: Schema::new(version.into()),
+        }
+    }
+
+    /// ENUM型を追加（PostgreSQL専用）
+    pub fn enum_type(mut self, name: &str, values: &[&str]) -> Self {
+        self.schema.add_enum(EnumDefinition {
+            name: name.to_string(),
+            values: to_strings(values),
+            renamed_from: None,
+        });
+        self
+    }
+
+    /// テーブルを追加
+    ///
+    /// 同じ名前のテーブルを再度追加した場合は後の定義で置き換えます。
+    pub fn table(mut self, name: &str, define: impl FnOnce(&mut TableBuilder)) -> Self {
+        let mut builder = TableBuilder::new(Table::new(name.to_string()));
+        define(&mut builder);
+        self.schema.add_table(builder.table);
+        self
+    }
+
+    /// PostgreSQLのスキーマを指定してテーブルを追加
+    pub fn table_in_schema(
+        mut self,
+        schema: &str,
+        name: &str,
+        define: impl FnOnce(&mut TableBuilder),
+    ) -> Self {
+        let mut builder = TableBuilder::new(Table::new_in_schema(schema.to_string(), name));
+        define(&mut builder);
+        self.schema.add_table(builder.table);
+        self
+    }
+
+    /// ビューを追加
+    pub fn view(mut self, name: &str, definition: &str, depends_on: &[&str]) -> Self {
+        let mut view = View::new(name.to_string(), definition.to_string());
+        view.depends_on = to_strings(depends_on);
+        self.schema.add_view(view);
+        self
+    }
+
+    /// strata管理外のテーブルを宣言
+    pub fn external_table(mut self, name: &str) -> Self {
+        self.schema.external_tables.insert(name.to_string());
+        self
+    }
+
+    /// ENUMの再作成を許可
+    pub fn enum_recreate_allowed(mut self) -> Self {
+        self.schema.enum_recreate_allowed = true;
+        self
+    }
+
+    /// 検証関数でスキーマを検証して返す
+    ///
+    /// 検証結果にエラーがある場合は、警告を含む検証結果全体を `Err` で返します。
+    pub fn build_with(
+        self,
+        validate: impl FnOnce(&Schema) -> ValidationResult,
+    ) -> Result<Schema, ValidationResult> {
+        let result = validate(&self.schema);
+        if result.is_valid() {
+            Ok(self.schema)
+        } else {
+            Err(result)
+        }
+    }
+
+    /// 検証せずにスキーマを返す
+    ///
+    /// 検証エラーになるスキーマを意図的に組み立てるテスト向けです。
+    pub fn build_unvalidated(self) -> Schema {
+        self.schema
+    }
+}
+
+/// テーブルビルダー
+///
+/// [`SchemaBuilder::table`] のクロージャに渡されます。
+/// カラムは追加した順に並び、NULL許可の指定がない限り NOT NULL になります（YAMLと同じ）。
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+    table: Table,
+}
+
+impl TableBuilder {
+    fn new(table: Table) -> Self {
+        Self { table }
+    }
+
+    /// 任意の型のカラムを追加
+    pub fn column(&mut self, name: &str, column_type: ColumnType) -> ColumnBuilder<'_> {
+        self.table
+            .add_column(Column::new(name.to_string(), column_type, false));
+        let index = self.table.columns.len() - 1;
+        ColumnBuilder {
+            table: &mut self.table,
+            index,
+        }
+    }
+
+    /// INTEGER カラムを追加
+    pub fn integer(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::INTEGER { precision: None })
+    }
+
+    /// SMALLINT カラムを追加
+    pub fn smallint(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::SMALLINT)
+    }
+
+    /// BIGINT カラムを追加
+    pub fn bigint(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::BIGINT)
+    }
+
+    /// VARCHAR カラムを追加
+    pub fn varchar(&mut self, name: &str, length: u32) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::VARCHAR { length })
+    }
+
+    /// CHAR カラムを追加
+    pub fn char(&mut self, name: &str, length: u32) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::CHAR { length })
+    }
+
+    /// TEXT カラムを追加
+    pub fn text(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::TEXT)
+    }
+
+    /// BOOLEAN カラムを追加
+    pub fn boolean(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::BOOLEAN)
+    }
+
+    /// TIMESTAMP カラムを追加
+    pub fn timestamp(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(
+            name,
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+        )
+    }
+
+    /// タイムゾーン付きの TIMESTAMP カラムを追加
+    pub fn timestamp_tz(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(
+            name,
+            ColumnType::TIMESTAMP {
+                with_time_zone: Some(true),
+                precision: None,
+            },
+        )
+    }
+
+    /// DATE カラムを追加
+    pub fn date(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::DATE)
+    }
+
+    /// TIME カラムを追加
+    pub fn time(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(
+            name,
+            ColumnType::TIME {
+                with_time_zone: None,
+                precision: None,
+            },
+        )
+    }
+
+    /// DECIMAL カラムを追加
+    pub fn decimal(&mut self, name: &str, precision: u32, scale: u32) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::DECIMAL { precision, scale })
+    }
+
+    /// FLOAT カラムを追加
+    pub fn float(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::FLOAT)
+    }
+
+    /// DOUBLE カラムを追加
+    pub fn double(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::DOUBLE)
+    }
+
+    /// JSON カラムを追加
+    pub fn json(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::JSON)
+    }
+
+    /// JSONB カラムを追加（PostgreSQL専用）
+    pub fn jsonb(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::JSONB)
+    }
+
+    /// BLOB カラムを追加
+    pub fn blob(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::BLOB)
+    }
+
+    /// UUID カラムを追加
+    pub fn uuid(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::UUID)
+    }
+
+    /// ENUM型を参照するカラムを追加（PostgreSQL専用）
+    pub fn enum_column(&mut self, name: &str, enum_name: &str) -> ColumnBuilder<'_> {
+        self.column(
+            name,
+            ColumnType::Enum {
+                name: enum_name.to_string(),
+            },
+        )
+    }
+
+    /// パラメータなしの方言固有型のカラムを追加（例: `SERIAL`, `INET`）
+    ///
+    /// パラメータ付きの型は [`TableBuilder::column`] に `ColumnType::DialectSpecific` を渡します。
+    pub fn dialect_specific(&mut self, name: &str, kind: &str) -> ColumnBuilder<'_> {
+        self.column(
+            name,
+            ColumnType::DialectSpecific {
+                kind: kind.to_string(),
+                params: serde_json::Value::Null,
+            },
+        )
+    }
+
+    /// 複合プライマリキーを設定
+    pub fn primary_key(&mut self, columns: &[&str]) -> &mut Self {
+        self.table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: to_strings(columns),
+        });
+        self
+    }
+
+    /// UNIQUE制約を追加
+    pub fn unique(&mut self, columns: &[&str]) -> &mut Self {
+        self.table.add_constraint(Constraint::UNIQUE {
+            columns: to_strings(columns),
+        });
+        self
+    }
+
+    /// CHECK制約を追加
+    pub fn check(&mut self, expression: &str, columns: &[&str]) -> &mut Self {
+        self.table.add_constraint(Constraint::CHECK {
+            columns: to_strings(columns),
+            check_expression: expression.to_string(),
+        });
+        self
+    }
+
+    /// 外部キー制約を追加
+    pub fn foreign_key(
+        &mut self,
+        columns: &[&str],
+        referenced_table: &str,
+        referenced_columns: &[&str],
+    ) -> ForeignKeyBuilder<'_> {
+        self.table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: to_strings(columns),
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: to_strings(referenced_columns),
+            on_delete: None,
+            on_update: None,
+        });
+        let index = self.table.constraints.len() - 1;
+        ForeignKeyBuilder {
+            table: &mut self.table,
+            index,
+        }
+    }
+
+    /// インデックスを追加
+    pub fn index(&mut self, name: &str, columns: &[&str]) -> IndexBuilder<'_> {
+        self.table
+            .add_index(Index::new(name.to_string(), to_strings(columns), false));
+        let index = self.table.indexes.len() - 1;
+        IndexBuilder {
+            table: &mut self.table,
+            index,
+        }
+    }
+
+    /// テーブルのコメントを設定
+    pub fn comment(&mut self, comment: &str) -> &mut Self {
+        self.table.comment = Some(comment.to_string());
+        self
+    }
+
+    /// リネーム元のテーブル名を設定
+    pub fn renamed_from(&mut self, old_name: &str) -> &mut Self {
+        self.table.renamed_from = Some(old_name.to_string());
+        self
+    }
+
+    /// メタデータを追加
+    pub fn metadata(&mut self, key: &str, value: &str) -> &mut Self {
+        self.table
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// カラムビルダー
+///
+/// [`TableBuilder`] のカラム追加メソッドが返し、追加したカラムの属性を設定します。
+#[derive(Debug)]
+pub struct ColumnBuilder<'a> {
+    table: &'a mut Table,
+    index: usize,
+}
+
+impl ColumnBuilder<'_> {
+    fn column(&mut self) -> &mut Column {
+        &mut self.table.columns[self.index]
+    }
+
+    /// NULLを許可
+    pub fn nullable(mut self) -> Self {
+        self.column().nullable = true;
+        self
+    }
+
+    /// デフォルト値を設定（SQL式として出力される値）
+    pub fn default_value(mut self, value: &str) -> Self {
+        self.column().default_value = Some(value.to_string());
+        self
+    }
+
+    /// 自動増分にする
+    pub fn auto_increment(mut self) -> Self {
+        self.column().auto_increment = Some(true);
+        self
+    }
+
+    /// プライマリキーに追加
+    ///
+    /// 複数のカラムで呼び出すと、呼び出した順の複合プライマリキーになります。
+    pub fn primary_key(mut self) -> Self {
+        let name = self.column().name.clone();
+        let existing = self
+            .table
+            .constraints
+            .iter_mut()
+            .find_map(|constraint| match constraint {
+                Constraint::PRIMARY_KEY { columns } => Some(columns),
+                _ => None,
+            });
+        match existing {
+            Some(columns) => columns.push(name),
+            None => self.table.add_constraint(Constraint::PRIMARY_KEY {
+                columns: vec![name],
+            }),
+        }
+        self
+    }
+
+    /// 単一カラムのUNIQUE制約を追加
+    pub fn unique(mut self) -> Self {
+        let name = self.column().name.clone();
+        self.table.add_constraint(Constraint::UNIQUE {
+            columns: vec![name],
+        });
+        self
+    }
+
+    /// カラムのコメントを設定
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.column().comment = Some(comment.to_string());
+        self
+    }
+
+    /// リネーム元のカラム名を設定
+    pub fn renamed_from(mut self, old_name: &str) -> Self {
+        self.column().renamed_from = Some(old_name.to_string());
+        self
+    }
+
+    /// メタデータを追加
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.column()
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// 外部キービルダー
+///
+/// [`TableBuilder::foreign_key`] が返し、参照アクションを設定します。
+#[derive(Debug)]
+pub struct ForeignKeyBuilder<'a> {
+    table: &'a mut Table,
+    index: usize,
+}
+
+impl ForeignKeyBuilder<'_> {
+    /// 参照先レコード削除時のアクションを設定
+    pub fn on_delete(self, action: ReferentialAction) -> Self {
+        if let Constraint::FOREIGN_KEY { on_delete, .. } = &mut self.table.constraints[self.index] {
+            *on_delete = Some(action);
+        }
+        self
+    }
+
+    /// 参照先レコード更新時のアクションを設定
+    pub fn on_update(self, action: ReferentialAction) -> Self {
+        if let Constraint::FOREIGN_KEY { on_update, .. } = &mut self.table.constraints[self.index] {
+            *on_update = Some(action);
+        }
+        self
+    }
+
+    /// `ON DELETE CASCADE` を設定
+    pub fn on_delete_cascade(self) -> Self {
+        self.on_delete(ReferentialAction::Cascade)
+    }
+
+    /// `ON DELETE SET NULL` を設定
+    pub fn on_delete_set_null(self) -> Self {
+        self.on_delete(ReferentialAction::SetNull)
+    }
+}
+
+/// インデックスビルダー
+///
+/// [`TableBuilder::index`] が返し、インデックスの属性を設定します。
+#[derive(Debug)]
+pub struct IndexBuilder<'a> {
+    table: &'a mut Table,
+    index: usize,
+}
+
+impl IndexBuilder<'_> {
+    fn index(&mut self) -> &mut Index {
+        &mut self.table.indexes[self.index]
+    }
+
+    /// ユニークインデックスにする
+    pub fn unique(mut self) -> Self {
+        self.index().unique = true;
+        self
+    }
+
+    /// 部分インデックスの条件式を設定
+    pub fn where_clause(mut self, predicate: &str) -> Self {
+        self.index().where_clause = Some(predicate.to_string());
+        self
+    }
+
+    /// インデックスの種類を設定
+    pub fn index_type(mut self, index_type: IndexType) -> Self {
+        self.index().index_type = Some(index_type);
+        self
+    }
+
+    /// リネーム元のインデックス名を設定
+    pub fn renamed_from(mut self, old_name: &str) -> Self {
+        self.index().renamed_from = Some(old_name.to_string());
+        self
+    }
+
+    /// メタデータを追加
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.index()
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::ValidationError;
+
+    #[test]
+    fn test_builds_tables_columns_and_constraints() {
+        let schema = SchemaBuilder::new("1.0")
+            .enum_type("user_status", &["active", "banned"])
+            .table("users", |t| {
+                t.integer("id").primary_key().auto_increment();
+                t.integer("org_id");
+                t.varchar("email", 255).nullable().unique();
+                t.enum_column("status", "user_status")
+                    .default_value("'active'");
+                t.foreign_key(&["org_id"], "orgs", &["id"])
+                    .on_delete_cascade();
+                t.index("idx_users_status", &["status"])
+                    .where_clause("status = 'active'");
+                t.comment("Application users");
+            })
+            .build_unvalidated();
+
+        let users = schema.get_table("users").unwrap();
+        assert_eq!(
+            users
+                .columns
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["id", "org_id", "email", "status"]
+        );
+        assert!(!users.get_column("id").unwrap().nullable);
+        assert!(users.get_column("id").unwrap().is_auto_increment());
+        assert!(users.get_column("email").unwrap().nullable);
+        assert_eq!(
+            users.get_column("status").unwrap().column_type,
+            ColumnType::Enum {
+                name: "user_status".to_string()
+            }
+        );
+        assert_eq!(
+            users.get_primary_key_columns(),
+            Some(vec!["id".to_string()])
+        );
+        assert!(users.constraints.contains(&Constraint::UNIQUE {
+            columns: vec!["email".to_string()]
+        }));
+        assert!(users.constraints.contains(&Constraint::FOREIGN_KEY {
+            columns: vec!["org_id".to_string()],
+            referenced_table: "orgs".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: None,
+        }));
+        assert_eq!(
+            users.indexes[0].where_clause.as_deref(),
+            Some("status = 'active'")
+        );
+        assert_eq!(users.comment_text(), Some("Application users"));
+        assert_eq!(
+            schema.get_enum("user_status").unwrap().values,
+            vec!["active", "banned"]
+        );
+    }
+
+    #[test]
+    fn test_column_primary_keys_form_composite_key_in_call_order() {
+        let schema = SchemaBuilder::new("1.0")
+            .table("memberships", |t| {
+                t.integer("user_id").primary_key();
+                t.integer("org_id").primary_key();
+            })
+            .build_unvalidated();
+
+        let table = schema.get_table("memberships").unwrap();
+        assert_eq!(
+            table.constraints,
+            vec![Constraint::PRIMARY_KEY {
+                columns: vec!["user_id".to_string(), "org_id".to_string()]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_matches_hand_built_schema() {
+        let built = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.bigint("id").primary_key();
+                t.timestamp_tz("created_at")
+                    .default_value("CURRENT_TIMESTAMP");
+                t.index("idx_users_created_at", &["created_at"]).unique();
+            })
+            .build_unvalidated();
+
+        let mut expected = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new("id".to_string(), ColumnType::BIGINT, false));
+        let mut created_at = Column::new(
+            "created_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: Some(true),
+                precision: None,
+            },
+            false,
+        );
+        created_at.default_value = Some("CURRENT_TIMESTAMP".to_string());
+        table.add_column(created_at);
+        table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        table.add_index(Index::new(
+            "idx_users_created_at".to_string(),
+            vec!["created_at".to_string()],
+            true,
+        ));
+        expected.add_table(table);
+
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn test_build_with_returns_validation_errors() {
+        let builder = SchemaBuilder::new("1.0").table("users", |t| {
+            t.integer("id");
+        });
+
+        let result = builder.clone().build_with(|_| ValidationResult::new());
+        assert!(result.is_ok());
+
+        let errors = builder
+            .build_with(|schema| {
+                let mut result = ValidationResult::new();
+                for table in schema.tables.values() {
+                    if table.get_primary_key_columns().is_none() {
+                        result.add_error(ValidationError::Constraint {
+                            message: format!("table '{}' has no primary key", table.name),
+                            location: None,
+                            suggestion: None,
+                        });
+                    }
+                }
+                result
+            })
+            .unwrap_err();
+        assert_eq!(errors.error_count(), 1);
+    }
+}
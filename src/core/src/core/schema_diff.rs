@@ -4,15 +4,20 @@
 // テーブル、カラム、インデックス、制約の追加、削除、変更を表現します。
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use crate::core::config::IdentifierCase;
 use crate::core::error::ValidationError;
-use crate::core::schema::{Column, Constraint, EnumDefinition, Index, Table, View};
+use crate::core::schema::{
+    normalize_default_value, Column, Constraint, EnumDefinition, GeneratedColumn, Index, Table,
+    View,
+};
 
 /// FK制約から依存関係グラフを構築
 ///
 /// 各ノード（テーブル名）について、そのテーブルが参照しているテーブル名のリストを返します。
 /// `target_names` に含まれるテーブル間の依存関係のみを抽出します。
+/// 自己参照は作成順序に影響しないため除外し、同じテーブルへの複数の参照は1つにまとめます。
 fn build_dependency_graph<'a, F>(
     target_names: &HashSet<&'a str>,
     get_constraints: F,
@@ -30,8 +35,12 @@ where
                     referenced_table, ..
                 } = constraint
                 {
-                    if target_names.contains(referenced_table.as_str()) {
-                        deps.push(referenced_table.as_str());
+                    let referenced = referenced_table.as_str();
+                    if referenced != table_name
+                        && target_names.contains(referenced)
+                        && !deps.contains(&referenced)
+                    {
+                        deps.push(referenced);
                     }
                 }
             }
@@ -98,6 +107,70 @@ fn topological_sort_kahn<'a>(
     (sorted, remaining)
 }
 
+/// 循環参照に含まれるノードを抽出
+///
+/// トポロジカルソートの残余ノードには、循環参照に含まれるノードに加えて
+/// それらに依存するだけのノードも含まれるため、残余ノードの中で
+/// 他のどのノードからも依存されていないノードを繰り返し取り除きます。
+///
+/// # Returns
+///
+/// 循環参照に含まれるノード名（名前順）
+fn cycle_members<'a>(
+    remaining: &[&'a str],
+    dependencies: &HashMap<&'a str, Vec<&'a str>>,
+) -> Vec<&'a str> {
+    let mut members: HashSet<&str> = remaining.iter().copied().collect();
+    loop {
+        let leaves: Vec<&str> = members
+            .iter()
+            .copied()
+            .filter(|&node| {
+                !members.iter().any(|&other| {
+                    dependencies
+                        .get(other)
+                        .is_some_and(|deps| deps.contains(&node))
+                })
+            })
+            .collect();
+        if leaves.is_empty() {
+            break;
+        }
+        for leaf in leaves {
+            members.remove(leaf);
+        }
+    }
+
+    let mut members: Vec<&str> = members.into_iter().collect();
+    members.sort();
+    members
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// 追加テーブルの作成順序
+///
+/// `SchemaDiff::order_added_tables` の結果です。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddedTableOrder {
+    /// 作成順に並べたテーブル（循環参照に関わるテーブルは名前順で末尾に配置）
+    pub tables: Vec<Table>,
+    /// 外部キーの循環参照に含まれるテーブル名（名前順）
+    pub cyclic_tables: Vec<String>,
+}
+
+impl AddedTableOrder {
+    /// 外部キーの循環参照があるかどうか
+    ///
+    /// 循環参照がある場合、テーブルを外部キーなしで作成した後に
+    /// 外部キーを追加する必要があります。
+    pub fn has_cycle(&self) -> bool {
+        !self.cyclic_tables.is_empty()
+    }
+}
+
 /// スキーマ差分
 ///
 /// 2つのスキーマ間の差分を表現します。
@@ -106,6 +179,14 @@ pub struct SchemaDiff {
     /// ENUM再作成の許可フラグ
     pub enum_recreate_allowed: bool,
 
+    /// SQLiteのAUTOINCREMENTキーワードを厳密に扱うか
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub sqlite_strict_autoincrement: bool,
+
+    /// 生成SQLの識別子の大文字・小文字の規則（自動生成する制約名に適用）
+    #[serde(default, skip_serializing_if = "IdentifierCase::is_preserve")]
+    pub identifier_case: IdentifierCase,
+
     /// 追加されたENUM定義
     pub added_enums: Vec<EnumDefinition>,
 
@@ -115,6 +196,10 @@ pub struct SchemaDiff {
     /// 変更されたENUM定義
     pub modified_enums: Vec<EnumDiff>,
 
+    /// リネームされたENUM定義
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub renamed_enums: Vec<RenamedEnum>,
+
     /// 追加されたテーブル
     pub added_tables: Vec<Table>,
 
@@ -164,6 +249,10 @@ pub struct ViewDiff {
 
     /// 変更後のビュー
     pub new_view: View,
+
+    /// リネームの反映のため、ビュー定義を自動で書き換えたか（設定 `view_rename_propagation: rewrite`）
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub auto_rewritten: bool,
 }
 
 /// リネームされたビュー
@@ -178,6 +267,18 @@ pub struct RenamedView {
     pub new_view: View,
 }
 
+/// リネームされたENUM
+///
+/// ENUM型名の変更を表現します。値の変更は、新しい型名の `EnumDiff` として別に記録されます。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenamedEnum {
+    /// 旧ENUM型名
+    pub old_name: String,
+
+    /// 新ENUM定義
+    pub new_enum: EnumDefinition,
+}
+
 /// リネームされたテーブル
 ///
 /// テーブル名の変更を表現します。
@@ -195,9 +296,12 @@ impl SchemaDiff {
     pub fn new() -> Self {
         Self {
             enum_recreate_allowed: false,
+            sqlite_strict_autoincrement: false,
+            identifier_case: IdentifierCase::Preserve,
             added_enums: Vec::new(),
             removed_enums: Vec::new(),
             modified_enums: Vec::new(),
+            renamed_enums: Vec::new(),
             added_tables: Vec::new(),
             removed_tables: Vec::new(),
             modified_tables: Vec::new(),
@@ -214,6 +318,7 @@ impl SchemaDiff {
         self.added_enums.is_empty()
             && self.removed_enums.is_empty()
             && self.modified_enums.is_empty()
+            && self.renamed_enums.is_empty()
             && self.added_tables.is_empty()
             && self.removed_tables.is_empty()
             && self.modified_tables.is_empty()
@@ -224,11 +329,30 @@ impl SchemaDiff {
             && self.renamed_views.is_empty()
     }
 
+    /// ビューの変更のみを含む差分かどうか（テーブルとENUMに変更がない）
+    pub fn is_view_only(&self) -> bool {
+        let has_view_changes = !self.added_views.is_empty()
+            || !self.removed_views.is_empty()
+            || !self.modified_views.is_empty()
+            || !self.renamed_views.is_empty();
+
+        has_view_changes
+            && self.added_enums.is_empty()
+            && self.removed_enums.is_empty()
+            && self.modified_enums.is_empty()
+            && self.renamed_enums.is_empty()
+            && self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.modified_tables.is_empty()
+            && self.renamed_tables.is_empty()
+    }
+
     /// 差分の項目数を取得
     pub fn count(&self) -> usize {
         self.added_enums.len()
             + self.removed_enums.len()
             + self.modified_enums.len()
+            + self.renamed_enums.len()
             + self.added_tables.len()
             + self.removed_tables.len()
             + self.renamed_tables.len()
@@ -247,9 +371,80 @@ impl SchemaDiff {
     /// # Returns
     ///
     /// ソートされたテーブルのリスト、または循環参照エラー
+    #[allow(clippy::result_large_err)]
     pub fn sort_added_tables_by_dependency(&self) -> Result<Vec<Table>, ValidationError> {
+        let order = self.order_added_tables();
+
+        if order.has_cycle() {
+            return Err(ValidationError::Reference {
+                message: format!(
+                    "Circular reference detected. The following tables have circular references: [{}]",
+                    order.cyclic_tables.join(", ")
+                ),
+                location: None,
+                suggestion: Some("Remove or refactor circular foreign key dependencies".to_string()),
+            });
+        }
+
+        Ok(order.tables)
+    }
+
+    /// ロールバック保護（`rollback_protection: true`）付きで追加されたテーブル名を取得
+    ///
+    /// これらのテーブルはDOWNで削除されず、ロールバック後もデータベースに残ります。
+    pub fn rollback_protected_tables(&self) -> Vec<String> {
+        self.added_tables
+            .iter()
+            .filter(|table| table.rollback_protection)
+            .map(|table| table.name.clone())
+            .collect()
+    }
+
+    /// 差分が変更するテーブル・ビューの名前を取得（名前順、重複なし）
+    ///
+    /// リネームは旧名・新名の両方を含みます。ENUMの変更は、そのENUMを使うカラムのテーブルを含みます。
+    /// `apply --only` で、先行する未適用マイグレーションと同じテーブルを変更しないかの確認に使います。
+    pub fn touched_tables(&self) -> Vec<String> {
+        let mut tables = BTreeSet::new();
+        tables.extend(self.added_tables.iter().map(|table| table.name.clone()));
+        tables.extend(self.removed_tables.iter().cloned());
+        tables.extend(
+            self.modified_tables
+                .iter()
+                .map(|table_diff| table_diff.table_name.clone()),
+        );
+        for renamed in &self.renamed_tables {
+            tables.insert(renamed.old_name.clone());
+            tables.insert(renamed.new_table.name.clone());
+        }
+        tables.extend(
+            self.modified_enums
+                .iter()
+                .flat_map(|enum_diff| &enum_diff.columns)
+                .map(|column| column.table_name.clone()),
+        );
+        tables.extend(self.added_views.iter().map(|view| view.name.clone()));
+        tables.extend(self.removed_views.iter().cloned());
+        tables.extend(
+            self.modified_views
+                .iter()
+                .map(|view_diff| view_diff.view_name.clone()),
+        );
+        for renamed in &self.renamed_views {
+            tables.insert(renamed.old_name.clone());
+            tables.insert(renamed.new_view.name.clone());
+        }
+        tables.into_iter().collect()
+    }
+
+    /// 外部キー制約による依存関係を考慮して、追加テーブルの作成順序を決定
+    ///
+    /// 被参照テーブルが先に作成されるように並び替えます。
+    /// 循環参照がある場合もエラーにはせず、循環参照に関わるテーブルを名前順で末尾に配置し、
+    /// 循環参照に含まれるテーブル名を `cyclic_tables` に設定します。
+    pub fn order_added_tables(&self) -> AddedTableOrder {
         if self.added_tables.is_empty() {
-            return Ok(Vec::new());
+            return AddedTableOrder::default();
         }
 
         // テーブル名 -> テーブルのマッピング
@@ -265,25 +460,23 @@ impl SchemaDiff {
             table_map.get(name).map(|t| &t.constraints)
         });
 
-        let (sorted_names, remaining) = topological_sort_kahn(&table_names, &dependencies);
-
-        if !remaining.is_empty() {
-            return Err(ValidationError::Reference {
-                message: format!(
-                    "Circular reference detected. The following tables have circular references: {:?}",
-                    remaining
-                ),
-                location: None,
-                suggestion: Some("Remove or refactor circular foreign key dependencies".to_string()),
-            });
-        }
+        let (mut sorted_names, mut remaining) = topological_sort_kahn(&table_names, &dependencies);
+        remaining.sort();
+        let cyclic_tables = cycle_members(&remaining, &dependencies)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        sorted_names.extend(remaining);
 
-        let sorted = sorted_names
+        let tables = sorted_names
             .into_iter()
             .filter_map(|name| table_map.get(name).map(|t| (*t).clone()))
             .collect();
 
-        Ok(sorted)
+        AddedTableOrder {
+            tables,
+            cyclic_tables,
+        }
     }
 
     /// 外部キー制約による依存関係を考慮して、削除テーブルを逆順にソート
@@ -402,11 +595,19 @@ pub struct TableDiff {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub modified_indexes: Vec<IndexDiff>,
 
+    /// リネームされたインデックス（定義は同じで名前のみ変更）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub renamed_indexes: Vec<RenamedIndex>,
+
     /// 追加された制約
     pub added_constraints: Vec<Constraint>,
 
     /// 削除された制約
     pub removed_constraints: Vec<Constraint>,
+
+    /// テーブルのコメントの変更
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_change: Option<CommentChange>,
 }
 
 impl TableDiff {
@@ -421,8 +622,10 @@ impl TableDiff {
             added_indexes: Vec::new(),
             removed_indexes: Vec::new(),
             modified_indexes: Vec::new(),
+            renamed_indexes: Vec::new(),
             added_constraints: Vec::new(),
             removed_constraints: Vec::new(),
+            comment_change: None,
         }
     }
 
@@ -435,8 +638,32 @@ impl TableDiff {
             && self.added_indexes.is_empty()
             && self.removed_indexes.is_empty()
             && self.modified_indexes.is_empty()
+            && self.renamed_indexes.is_empty()
             && self.added_constraints.is_empty()
             && self.removed_constraints.is_empty()
+            && self.comment_change.is_none()
+    }
+}
+
+/// コメントの変更
+///
+/// 空文字列のコメントはコメントなし（None）として扱います。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentChange {
+    /// 変更前のコメント
+    pub old_comment: Option<String>,
+
+    /// 変更後のコメント
+    pub new_comment: Option<String>,
+}
+
+impl CommentChange {
+    /// コメントが異なる場合に変更を作成
+    pub fn detect(old_comment: Option<&str>, new_comment: Option<&str>) -> Option<Self> {
+        (old_comment != new_comment).then(|| Self {
+            old_comment: old_comment.map(str::to_string),
+            new_comment: new_comment.map(str::to_string),
+        })
     }
 }
 
@@ -506,10 +733,19 @@ pub struct EnumDiff {
 pub enum EnumChangeKind {
     /// 追加のみ
     AddOnly,
+    /// 値の追加・削除はなく並び順のみ変更（再作成が必要）
+    ReorderOnly,
     /// 再作成が必要
     Recreate,
 }
 
+impl EnumChangeKind {
+    /// 型の再作成が必要な変更か
+    pub fn requires_recreate(&self) -> bool {
+        matches!(self, EnumChangeKind::ReorderOnly | EnumChangeKind::Recreate)
+    }
+}
+
 /// ENUM参照カラム
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumColumnRef {
@@ -532,6 +768,19 @@ pub struct IndexDiff {
     pub new_index: Index,
 }
 
+/// インデックスリネーム情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenamedIndex {
+    /// 旧インデックス名
+    pub old_name: String,
+
+    /// 旧インデックス定義（SQLiteでの再作成とDown方向で使用）
+    pub old_index: Index,
+
+    /// 新インデックス定義
+    pub new_index: Index,
+}
+
 impl ColumnDiff {
     /// 新しいカラム差分を作成
     pub fn new(column_name: String, old_column: Column, new_column: Column) -> Self {
@@ -553,8 +802,16 @@ impl ColumnDiff {
             });
         }
 
-        // デフォルト値の変更を検出
-        if old_column.default_value != new_column.default_value {
+        // デフォルト値の変更を検出（now() と CURRENT_TIMESTAMP のような同等の表記は同じとみなす）
+        if old_column
+            .default_value
+            .as_deref()
+            .map(normalize_default_value)
+            != new_column
+                .default_value
+                .as_deref()
+                .map(normalize_default_value)
+        {
             changes.push(ColumnChange::DefaultValueChanged {
                 old_default: old_column.default_value.clone(),
                 new_default: new_column.default_value.clone(),
@@ -569,6 +826,32 @@ impl ColumnDiff {
             });
         }
 
+        // ON UPDATE CURRENT_TIMESTAMP の追加・削除を検出
+        if old_column.on_update_current_timestamp != new_column.on_update_current_timestamp {
+            changes.push(ColumnChange::OnUpdateCurrentTimestampChanged {
+                old_on_update: old_column.on_update_current_timestamp,
+                new_on_update: new_column.on_update_current_timestamp,
+            });
+        }
+
+        // 生成カラム定義の変更を検出
+        if old_column.generated != new_column.generated {
+            changes.push(ColumnChange::GeneratedChanged {
+                old_generated: old_column.generated.clone(),
+                new_generated: new_column.generated.clone(),
+            });
+        }
+
+        // コメントの変更を検出
+        if let Some(change) =
+            CommentChange::detect(old_column.comment_text(), new_column.comment_text())
+        {
+            changes.push(ColumnChange::CommentChanged {
+                old_comment: change.old_comment,
+                new_comment: change.new_comment,
+            });
+        }
+
         Self {
             column_name,
             old_column,
@@ -576,12 +859,41 @@ impl ColumnDiff {
             changes,
         }
     }
+
+    /// up.sql の型変更に使う `USING` 式（新しいカラムの `type_change_using`）
+    ///
+    /// 変更前のカラム（スナップショット）と同じ式は以前のマイグレーションで使用済みとみなし、
+    /// 次の型変更に持ち越さないよう None を返します。
+    pub fn up_using(&self) -> Option<&str> {
+        fresh_using(
+            &self.old_column.type_change_using,
+            &self.new_column.type_change_using,
+        )
+    }
+
+    /// down.sql の型変更に使う `USING` 式（新しいカラムの `down_using`）
+    ///
+    /// `up_using` と同様に、変更前のカラムと同じ式は None を返します。
+    pub fn down_using(&self) -> Option<&str> {
+        fresh_using(&self.old_column.down_using, &self.new_column.down_using)
+    }
+}
+
+/// 変更前と異なる、空でない `USING` 式
+fn fresh_using<'a>(old: &Option<String>, new: &'a Option<String>) -> Option<&'a str> {
+    let new = new.as_deref().map(str::trim).filter(|s| !s.is_empty())?;
+    if old.as_deref().map(str::trim) == Some(new) {
+        return None;
+    }
+    Some(new)
 }
 
 /// カラム変更
 ///
 /// カラムの変更内容の種類を表現します。
+/// 検出する変更は今後も追加されるため、クレート外からの `match` にはワイルドカードが必要です。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ColumnChange {
     /// 型の変更
     TypeChanged { old_type: String, new_type: String },
@@ -604,8 +916,26 @@ pub enum ColumnChange {
         new_auto_increment: Option<bool>,
     },
 
+    /// ON UPDATE CURRENT_TIMESTAMP の追加・削除（MySQL専用）
+    OnUpdateCurrentTimestampChanged {
+        old_on_update: bool,
+        new_on_update: bool,
+    },
+
+    /// 生成カラム定義の変更
+    GeneratedChanged {
+        old_generated: Option<GeneratedColumn>,
+        new_generated: Option<GeneratedColumn>,
+    },
+
     /// カラム名の変更
     Renamed { old_name: String, new_name: String },
+
+    /// コメントの変更
+    CommentChanged {
+        old_comment: Option<String>,
+        new_comment: Option<String>,
+    },
 }
 
 #[cfg(test)]
@@ -629,6 +959,55 @@ mod tests {
         assert_eq!(diff.count(), 1);
     }
 
+    #[test]
+    fn test_schema_diff_is_view_only() {
+        let mut diff = SchemaDiff::new();
+        assert!(!diff.is_view_only());
+
+        diff.removed_views.push("old_summary".to_string());
+        assert!(diff.is_view_only());
+
+        diff.added_tables.push(Table::new("users".to_string()));
+        assert!(!diff.is_view_only());
+    }
+
+    #[test]
+    fn test_schema_diff_touched_tables() {
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(Table::new("users".to_string()));
+        diff.modified_tables
+            .push(TableDiff::new("posts".to_string()));
+        diff.renamed_tables.push(RenamedTable {
+            old_name: "comments".to_string(),
+            new_table: Table::new("post_comments".to_string()),
+        });
+        diff.modified_enums.push(EnumDiff {
+            enum_name: "status".to_string(),
+            old_values: vec!["active".to_string()],
+            new_values: vec!["active".to_string(), "archived".to_string()],
+            added_values: vec!["archived".to_string()],
+            removed_values: Vec::new(),
+            change_kind: EnumChangeKind::AddOnly,
+            columns: vec![EnumColumnRef {
+                table_name: "users".to_string(),
+                column_name: "status".to_string(),
+            }],
+        });
+        diff.removed_views.push("active_users".to_string());
+
+        assert_eq!(
+            diff.touched_tables(),
+            vec![
+                "active_users".to_string(),
+                "comments".to_string(),
+                "post_comments".to_string(),
+                "posts".to_string(),
+                "users".to_string(),
+            ]
+        );
+        assert!(SchemaDiff::new().touched_tables().is_empty());
+    }
+
     #[test]
     fn test_table_diff_new() {
         let diff = TableDiff::new("users".to_string());
@@ -813,6 +1192,68 @@ mod tests {
             .contains("Circular reference"));
     }
 
+    fn fk_to(column: &str, referenced_table: &str) -> Constraint {
+        Constraint::FOREIGN_KEY {
+            columns: vec![column.to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    #[test]
+    fn test_order_added_tables_with_cycle() {
+        let mut diff = SchemaDiff::new();
+
+        // users <-> posts の循環参照と、posts に依存するだけの comments、独立した tags
+        let mut users = Table::new("users".to_string());
+        users.constraints.push(fk_to("best_post_id", "posts"));
+        let mut posts = Table::new("posts".to_string());
+        posts.constraints.push(fk_to("user_id", "users"));
+        let mut comments = Table::new("comments".to_string());
+        comments.constraints.push(fk_to("post_id", "posts"));
+        diff.added_tables.push(comments);
+        diff.added_tables.push(users);
+        diff.added_tables.push(posts);
+        diff.added_tables.push(Table::new("tags".to_string()));
+
+        let order = diff.order_added_tables();
+
+        assert!(order.has_cycle());
+        assert_eq!(order.cyclic_tables, vec!["posts", "users"]);
+        let names: Vec<&str> = order.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["tags", "comments", "posts", "users"]);
+
+        let err = diff.sort_added_tables_by_dependency().unwrap_err();
+        assert!(err.to_string().contains("[posts, users]"), "{}", err);
+    }
+
+    #[test]
+    fn test_order_added_tables_self_and_duplicate_references_are_not_cycles() {
+        let mut diff = SchemaDiff::new();
+
+        // 自己参照（categories.parent_id）と、同じテーブルへの複数の外部キー
+        let mut categories = Table::new("categories".to_string());
+        categories
+            .constraints
+            .push(fk_to("parent_id", "categories"));
+        let mut posts = Table::new("posts".to_string());
+        posts.constraints.push(fk_to("author_id", "users"));
+        posts.constraints.push(fk_to("editor_id", "users"));
+        posts.constraints.push(fk_to("category_id", "categories"));
+        diff.added_tables.push(posts);
+        diff.added_tables.push(categories);
+        diff.added_tables.push(Table::new("users".to_string()));
+
+        let order = diff.order_added_tables();
+
+        assert!(!order.has_cycle());
+        let names: Vec<&str> = order.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names.last(), Some(&"posts"));
+        assert!(diff.sort_added_tables_by_dependency().is_ok());
+    }
+
     #[test]
     fn test_sort_added_tables_external_reference() {
         let mut diff = SchemaDiff::new();
@@ -0,0 +1,188 @@
+// データベースサーバーのバージョン
+//
+// `SELECT VERSION()` が返す文字列や設定ファイルの `mysql.minimum_version` を解析し、
+// バージョンに依存する機能（MySQLのCHECK制約の強制など）の判定に使用します。
+
+use serde::Serialize;
+use std::fmt;
+
+/// CHECK制約が強制されるようになったMySQLのバージョン
+pub const MYSQL_CHECK_ENFORCEMENT_VERSION: (u32, u32, u32) = (8, 0, 16);
+
+/// MySQL互換サーバーの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerFlavor {
+    /// MySQL
+    MySql,
+    /// MariaDB
+    MariaDb,
+}
+
+/// サーバーバージョン
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ServerVersion {
+    /// メジャーバージョン
+    pub major: u32,
+    /// マイナーバージョン
+    pub minor: u32,
+    /// パッチバージョン
+    pub patch: u32,
+    /// サーバー種別
+    pub flavor: ServerFlavor,
+}
+
+impl ServerVersion {
+    /// MySQLのバージョンを作成
+    pub fn mysql(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            flavor: ServerFlavor::MySql,
+        }
+    }
+
+    /// バージョン文字列を解析
+    ///
+    /// `8.0.36-0ubuntu0.22.04.1` のようなディストリビューション固有の接尾辞は無視します。
+    /// `MariaDB` を含む文字列はMariaDBとして扱い、レプリケーション互換のための
+    /// `5.5.5-` 接頭辞（例: `5.5.5-10.6.12-MariaDB`）は取り除きます。
+    /// マイナー・パッチバージョンが省略された場合は0とみなします。
+    ///
+    /// # Returns
+    ///
+    /// 解析できた場合はバージョン、先頭が数値でない場合はNone
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let flavor = if version.to_ascii_lowercase().contains("mariadb") {
+            ServerFlavor::MariaDb
+        } else {
+            ServerFlavor::MySql
+        };
+
+        let numeric = match flavor {
+            ServerFlavor::MariaDb => version.strip_prefix("5.5.5-").unwrap_or(version),
+            ServerFlavor::MySql => version,
+        };
+        let end = numeric
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(numeric.len());
+
+        let mut parts = numeric[..end].split('.');
+        let major = parts.next()?.parse().ok()?;
+        let mut next_part = || -> Option<u32> {
+            match parts.next() {
+                Some(part) => part.parse().ok(),
+                None => Some(0),
+            }
+        };
+        let minor = next_part()?;
+        let patch = next_part()?;
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            flavor,
+        })
+    }
+
+    /// CHECK制約がサーバーで強制されるかどうか
+    ///
+    /// MySQL 8.0.16 未満はCHECK制約を解析するだけで無視します。
+    /// MariaDBはMySQLと同じ方法では強制しないため、常にfalseを返します。
+    pub fn enforces_check_constraints(&self) -> bool {
+        match self.flavor {
+            ServerFlavor::MySql => {
+                (self.major, self.minor, self.patch) >= MYSQL_CHECK_ENFORCEMENT_VERSION
+            }
+            ServerFlavor::MariaDb => false,
+        }
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.flavor == ServerFlavor::MariaDb {
+            write!(f, " (MariaDB)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_mysql_version() {
+        assert_eq!(
+            ServerVersion::parse("8.0.16"),
+            Some(ServerVersion::mysql(8, 0, 16))
+        );
+        assert_eq!(
+            ServerVersion::parse("5.7"),
+            Some(ServerVersion::mysql(5, 7, 0))
+        );
+        assert_eq!(
+            ServerVersion::parse("8"),
+            Some(ServerVersion::mysql(8, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_distribution_suffix() {
+        assert_eq!(
+            ServerVersion::parse("8.0.36-0ubuntu0.22.04.1"),
+            Some(ServerVersion::mysql(8, 0, 36))
+        );
+        assert_eq!(
+            ServerVersion::parse("5.7.44-log"),
+            Some(ServerVersion::mysql(5, 7, 44))
+        );
+    }
+
+    #[test]
+    fn test_parse_mariadb_version() {
+        let version = ServerVersion::parse("10.6.12-MariaDB-0ubuntu0.22.04.1").unwrap();
+        assert_eq!(version.flavor, ServerFlavor::MariaDb);
+        assert_eq!((version.major, version.minor, version.patch), (10, 6, 12));
+
+        // レプリケーション互換の接頭辞付き
+        let version = ServerVersion::parse("5.5.5-10.11.6-MariaDB").unwrap();
+        assert_eq!(version.flavor, ServerFlavor::MariaDb);
+        assert_eq!((version.major, version.minor, version.patch), (10, 11, 6));
+        assert_eq!(version.to_string(), "10.11.6 (MariaDB)");
+    }
+
+    #[test]
+    fn test_parse_invalid_version() {
+        assert_eq!(ServerVersion::parse(""), None);
+        assert_eq!(ServerVersion::parse("latest"), None);
+        assert_eq!(ServerVersion::parse("8..1"), None);
+    }
+
+    #[test]
+    fn test_enforces_check_constraints() {
+        assert!(!ServerVersion::parse("5.7.44")
+            .unwrap()
+            .enforces_check_constraints());
+        assert!(!ServerVersion::parse("8.0.15")
+            .unwrap()
+            .enforces_check_constraints());
+        assert!(ServerVersion::parse("8.0.16")
+            .unwrap()
+            .enforces_check_constraints());
+        assert!(ServerVersion::parse("8.0.36-0ubuntu0.22.04.1")
+            .unwrap()
+            .enforces_check_constraints());
+        assert!(ServerVersion::parse("9.1.0")
+            .unwrap()
+            .enforces_check_constraints());
+        assert!(!ServerVersion::parse("11.4.2-MariaDB")
+            .unwrap()
+            .enforces_check_constraints());
+    }
+}
@@ -2,6 +2,8 @@
 //
 // カラム型を型カテゴリに分類し、型変更の互換性を検証するための機能を提供します。
 
+use serde::Serialize;
+
 use super::schema::ColumnType;
 
 /// 型カテゴリ
@@ -10,7 +12,7 @@ use super::schema::ColumnType;
 /// 型変更の互換性検証に使用されます。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TypeCategory {
-    /// 数値型 (INTEGER, DECIMAL, FLOAT, DOUBLE)
+    /// 数値型 (SMALLINT, INTEGER, BIGINT, DECIMAL, FLOAT, DOUBLE)
     Numeric,
     /// 文字列型 (VARCHAR, TEXT, CHAR)
     String,
@@ -47,6 +49,8 @@ impl TypeCategory {
         match column_type {
             // 数値型
             ColumnType::INTEGER { .. }
+            | ColumnType::SMALLINT
+            | ColumnType::BIGINT
             | ColumnType::DECIMAL { .. }
             | ColumnType::FLOAT
             | ColumnType::DOUBLE => TypeCategory::Numeric,
@@ -154,6 +158,238 @@ impl TypeCategory {
     pub fn is_error_conversion(&self, other: &Self) -> bool {
         matches!(self.conversion_result(other), TypeConversionResult::Error)
     }
+
+    /// 他のカテゴリへの変換に既存値の明示的なキャストが必要かどうか
+    ///
+    /// PostgreSQLでは `ALTER COLUMN ... TYPE ... USING` 句が必要になる変換です。
+    pub fn requires_explicit_cast(&self, to: &Self) -> bool {
+        use TypeCategory::*;
+
+        match (self, to) {
+            // 同一カテゴリ内: 不要
+            (Numeric, Numeric)
+            | (String, String)
+            | (DateTime, DateTime)
+            | (Binary, Binary)
+            | (Json, Json)
+            | (Boolean, Boolean)
+            | (Uuid, Uuid) => false,
+
+            // String → Numeric/Boolean/DateTime/Json: 必要
+            (String, Numeric) | (String, Boolean) | (String, DateTime) | (String, Json) => true,
+
+            // 文字列への変換・Boolean → Numeric: 不要（暗黙変換）
+            (Numeric, String)
+            | (DateTime, String)
+            | (Boolean, Numeric)
+            | (Boolean, String)
+            | (Uuid, String)
+            | (Json, String)
+            | (Binary, String) => false,
+
+            // Otherカテゴリ・その他の変換: 安全のため必要とみなす
+            _ => true,
+        }
+    }
+}
+
+/// 型変更の分類
+///
+/// `generate` のJSON出力にカラムごとに含まれます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeChangeClass {
+    /// 既存の値をそのまま変換できる
+    Safe,
+    /// 既存の値の明示的なキャストが必要（PostgreSQLでは USING 句を生成）
+    NeedsUsing,
+    /// 値の切り捨て・桁あふれなど、既存の値が失われる可能性がある
+    Lossy,
+}
+
+/// 型変更の分類結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeChangeClassification {
+    pub class: TypeChangeClass,
+    /// 値が失われる理由（`Lossy` の場合のみ）
+    pub reason: Option<String>,
+}
+
+impl TypeChangeClassification {
+    /// 型変更を分類
+    ///
+    /// 値が失われるかどうかは定義上の型で判定します。明示的なキャストの要否は
+    /// 型カテゴリ間の変換規則（[`TypeCategory::requires_explicit_cast`]）に従います。
+    pub fn classify(old_type: &ColumnType, new_type: &ColumnType) -> Self {
+        if let Some(reason) = lossy_reason(old_type, new_type) {
+            return Self {
+                class: TypeChangeClass::Lossy,
+                reason: Some(reason),
+            };
+        }
+
+        let source = TypeCategory::from_column_type(old_type);
+        let target = TypeCategory::from_column_type(new_type);
+        let class = if old_type != new_type && source.requires_explicit_cast(&target) {
+            TypeChangeClass::NeedsUsing
+        } else {
+            TypeChangeClass::Safe
+        };
+        Self {
+            class,
+            reason: None,
+        }
+    }
+
+    /// 値が失われる可能性がある変更かどうか
+    pub fn is_lossy(&self) -> bool {
+        self.class == TypeChangeClass::Lossy
+    }
+}
+
+/// 値が失われる理由を判定（失われない場合は `None`）
+fn lossy_reason(old_type: &ColumnType, new_type: &ColumnType) -> Option<String> {
+    use ColumnType::*;
+
+    if let Some(change) = DecimalChange::between(old_type, new_type) {
+        return change.reason().map(str::to_string);
+    }
+
+    let truncated = |length: u32| {
+        Some(format!(
+            "existing values longer than {} characters do not fit",
+            length
+        ))
+    };
+
+    match (old_type, new_type) {
+        (
+            VARCHAR { length: old } | CHAR { length: old },
+            VARCHAR { length: new } | CHAR { length: new },
+        ) if new < old => truncated(*new),
+        (TEXT, VARCHAR { length } | CHAR { length }) => truncated(*length),
+        _ if matches!(
+            (old_type.integer_width(), new_type.integer_width()),
+            (Some(old), Some(new)) if new < old
+        ) =>
+        {
+            Some("existing values may overflow".to_string())
+        }
+        (DOUBLE, FLOAT) => Some("floating-point precision is reduced".to_string()),
+        (FLOAT | DOUBLE, _) if new_type.integer_width().is_some() => {
+            Some("fractional parts are discarded".to_string())
+        }
+        (DECIMAL { scale, .. }, _) if *scale > 0 && new_type.integer_width().is_some() => {
+            Some("fractional parts are discarded".to_string())
+        }
+        (TIMESTAMP { .. }, DATE) => Some("the time of day is discarded".to_string()),
+        (TIMESTAMP { .. }, TIME { .. }) => Some("the date is discarded".to_string()),
+        (
+            TIMESTAMP {
+                precision: Some(old),
+                ..
+            }
+            | TIME {
+                precision: Some(old),
+                ..
+            },
+            TIMESTAMP {
+                precision: Some(new),
+                ..
+            }
+            | TIME {
+                precision: Some(new),
+                ..
+            },
+        ) if new < old => Some("fractional seconds are truncated".to_string()),
+        _ => None,
+    }
+}
+
+/// DECIMAL の精度・スケール変更の分類
+///
+/// 整数部の桁数（precision - scale）と小数部の桁数（scale）がともに減らない変更は、
+/// 既存の値をすべて表現できるため安全とみなします。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalChange {
+    /// 既存の値をすべて表現できる変更（例: DECIMAL(10, 2) → DECIMAL(12, 2)）
+    Safe,
+    /// 値の丸め・桁あふれが起こりうる変更
+    Lossy {
+        /// スケール縮小により小数部が丸められる（例: DECIMAL(10, 4) → DECIMAL(10, 2)）
+        scale_truncation: bool,
+        /// 整数部の桁数が減り、既存の値が桁あふれする可能性がある
+        /// （例: DECIMAL(10, 2) → DECIMAL(8, 2)、DECIMAL(10, 2) → DECIMAL(10, 4)）
+        overflow_risk: bool,
+    },
+}
+
+impl DecimalChange {
+    /// 精度・スケールの変更を分類
+    pub fn classify(
+        old_precision: u32,
+        old_scale: u32,
+        new_precision: u32,
+        new_scale: u32,
+    ) -> Self {
+        let old_integer_digits = old_precision.saturating_sub(old_scale);
+        let new_integer_digits = new_precision.saturating_sub(new_scale);
+
+        let scale_truncation = new_scale < old_scale;
+        let overflow_risk = new_integer_digits < old_integer_digits;
+
+        if scale_truncation || overflow_risk {
+            DecimalChange::Lossy {
+                scale_truncation,
+                overflow_risk,
+            }
+        } else {
+            DecimalChange::Safe
+        }
+    }
+
+    /// DECIMAL同士の型変更であれば分類を返す
+    pub fn between(old_type: &ColumnType, new_type: &ColumnType) -> Option<Self> {
+        match (old_type, new_type) {
+            (
+                ColumnType::DECIMAL {
+                    precision: old_precision,
+                    scale: old_scale,
+                },
+                ColumnType::DECIMAL {
+                    precision: new_precision,
+                    scale: new_scale,
+                },
+            ) => Some(Self::classify(
+                *old_precision,
+                *old_scale,
+                *new_precision,
+                *new_scale,
+            )),
+            _ => None,
+        }
+    }
+
+    /// 値が失われる可能性がある変更かどうか
+    pub fn is_lossy(&self) -> bool {
+        matches!(self, DecimalChange::Lossy { .. })
+    }
+
+    /// 値が失われる理由（安全な変更の場合は `None`）
+    pub fn reason(&self) -> Option<&'static str> {
+        match self {
+            DecimalChange::Safe => None,
+            DecimalChange::Lossy {
+                scale_truncation: true,
+                overflow_risk: true,
+            } => Some("fractional digits are rounded and existing values may overflow"),
+            DecimalChange::Lossy {
+                scale_truncation: true,
+                ..
+            } => Some("fractional digits are rounded"),
+            DecimalChange::Lossy { .. } => Some("existing values may overflow"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -211,13 +447,15 @@ mod tests {
         );
         assert_eq!(
             TypeCategory::from_column_type(&ColumnType::TIME {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }),
             TypeCategory::DateTime
         );
         assert_eq!(
             TypeCategory::from_column_type(&ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }),
             TypeCategory::DateTime
         );
@@ -442,4 +680,181 @@ mod tests {
         assert!(!Numeric.is_error_conversion(&String));
         assert!(!String.is_error_conversion(&Json));
     }
+
+    // ==========================================
+    // DecimalChange のテスト
+    // ==========================================
+
+    #[test]
+    fn test_decimal_change_matrix() {
+        let lossy = |scale_truncation, overflow_risk| DecimalChange::Lossy {
+            scale_truncation,
+            overflow_risk,
+        };
+
+        // (旧精度, 旧スケール, 新精度, 新スケール, 期待値)
+        let cases = [
+            // 変更なし・精度のみ拡大・整数部と小数部をともに拡大
+            (10, 2, 10, 2, DecimalChange::Safe),
+            (10, 2, 12, 2, DecimalChange::Safe),
+            (10, 2, 12, 4, DecimalChange::Safe),
+            (10, 0, 20, 10, DecimalChange::Safe),
+            // 精度縮小（整数部の桁あふれ）
+            (10, 2, 8, 2, lossy(false, true)),
+            (10, 0, 5, 0, lossy(false, true)),
+            // スケール縮小（小数部の丸め）
+            (10, 4, 10, 2, lossy(true, false)),
+            (10, 4, 8, 2, lossy(true, false)),
+            // スケール拡大に対して精度の拡大が不足（整数部の桁あふれ）
+            (10, 2, 10, 4, lossy(false, true)),
+            (10, 2, 11, 4, lossy(false, true)),
+            // スケール縮小と整数部の縮小が同時に起こる
+            (10, 4, 5, 2, lossy(true, true)),
+        ];
+
+        for (old_p, old_s, new_p, new_s, expected) in cases {
+            assert_eq!(
+                DecimalChange::classify(old_p, old_s, new_p, new_s),
+                expected,
+                "DECIMAL({}, {}) -> DECIMAL({}, {})",
+                old_p,
+                old_s,
+                new_p,
+                new_s
+            );
+        }
+    }
+
+    #[test]
+    fn test_decimal_change_between_column_types() {
+        let old = ColumnType::DECIMAL {
+            precision: 10,
+            scale: 2,
+        };
+        let new = ColumnType::DECIMAL {
+            precision: 8,
+            scale: 2,
+        };
+
+        let change = DecimalChange::between(&old, &new).unwrap();
+        assert!(change.is_lossy());
+        assert_eq!(change.reason(), Some("existing values may overflow"));
+        assert!(DecimalChange::between(&old, &ColumnType::TEXT).is_none());
+        assert_eq!(
+            DecimalChange::between(&old, &old),
+            Some(DecimalChange::Safe)
+        );
+    }
+
+    #[test]
+    fn test_requires_explicit_cast() {
+        use TypeCategory::*;
+        assert!(String.requires_explicit_cast(&Numeric));
+        assert!(String.requires_explicit_cast(&Json));
+        assert!(Numeric.requires_explicit_cast(&Boolean));
+        assert!(Other.requires_explicit_cast(&String));
+        assert!(!Numeric.requires_explicit_cast(&String));
+        assert!(!String.requires_explicit_cast(&String));
+    }
+
+    #[test]
+    fn test_classify_lossy_type_changes() {
+        let cases = [
+            (
+                ColumnType::VARCHAR { length: 255 },
+                ColumnType::VARCHAR { length: 50 },
+                "existing values longer than 50 characters do not fit",
+            ),
+            (
+                ColumnType::TEXT,
+                ColumnType::CHAR { length: 10 },
+                "existing values longer than 10 characters do not fit",
+            ),
+            (
+                ColumnType::BIGINT,
+                ColumnType::INTEGER { precision: None },
+                "existing values may overflow",
+            ),
+            (
+                ColumnType::INTEGER { precision: None },
+                ColumnType::SMALLINT,
+                "existing values may overflow",
+            ),
+            (
+                ColumnType::DOUBLE,
+                ColumnType::INTEGER { precision: None },
+                "fractional parts are discarded",
+            ),
+            (
+                ColumnType::TIMESTAMP {
+                    with_time_zone: Some(true),
+                    precision: None,
+                },
+                ColumnType::DATE,
+                "the time of day is discarded",
+            ),
+            (
+                ColumnType::TIMESTAMP {
+                    with_time_zone: None,
+                    precision: Some(6),
+                },
+                ColumnType::TIMESTAMP {
+                    with_time_zone: None,
+                    precision: Some(3),
+                },
+                "fractional seconds are truncated",
+            ),
+            (
+                ColumnType::DECIMAL {
+                    precision: 10,
+                    scale: 2,
+                },
+                ColumnType::DECIMAL {
+                    precision: 8,
+                    scale: 2,
+                },
+                "existing values may overflow",
+            ),
+        ];
+
+        for (old, new, reason) in cases {
+            let classification = TypeChangeClassification::classify(&old, &new);
+            assert!(classification.is_lossy(), "{} -> {}", old, new);
+            assert_eq!(classification.reason.as_deref(), Some(reason));
+        }
+    }
+
+    #[test]
+    fn test_classify_safe_and_needs_using_type_changes() {
+        let classify =
+            |old: ColumnType, new: ColumnType| TypeChangeClassification::classify(&old, &new).class;
+
+        assert_eq!(
+            classify(
+                ColumnType::VARCHAR { length: 50 },
+                ColumnType::VARCHAR { length: 255 }
+            ),
+            TypeChangeClass::Safe
+        );
+        assert_eq!(
+            classify(ColumnType::INTEGER { precision: None }, ColumnType::BIGINT),
+            TypeChangeClass::Safe
+        );
+        assert_eq!(
+            classify(ColumnType::SMALLINT, ColumnType::BIGINT),
+            TypeChangeClass::Safe
+        );
+        assert_eq!(
+            classify(ColumnType::DATE, ColumnType::TEXT),
+            TypeChangeClass::Safe
+        );
+        assert_eq!(
+            classify(ColumnType::TEXT, ColumnType::INTEGER { precision: None }),
+            TypeChangeClass::NeedsUsing
+        );
+        assert_eq!(
+            serde_json::to_string(&TypeChangeClass::NeedsUsing).unwrap(),
+            "\"needs_using\""
+        );
+    }
 }
@@ -1,3 +1,5 @@
 // Stratum core crate
 
 pub mod core;
+
+pub use core::schema::SchemaBuilder;
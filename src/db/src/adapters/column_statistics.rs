@@ -0,0 +1,257 @@
+// カラム統計アダプター
+//
+// 稼働中のデータベースから文字列カラムの実データ長と行数の見積もりを取得します。
+// 識別子はすべて sql_quote のクォート関数を通して埋め込みます。
+
+use crate::adapters::sql_quote::{quote_identifier, quote_identifier_sqlite, quote_table_postgres};
+use crate::core::config::Dialect;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::{AnyPool, Row};
+
+/// カラムの実データ長の統計
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnLengthStats {
+    /// カラム名
+    pub column: String,
+    /// 観測された最大長（文字数）。行がない、またはすべてNULLの場合はNone
+    pub max_length: Option<i64>,
+    /// 99パーセンタイルの長さ（PostgreSQLのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p99_length: Option<i64>,
+}
+
+/// カラム統計の取得
+///
+/// 行数は方言ごとに安価な方法で見積もります。
+/// - PostgreSQL: `pg_class.reltuples`（未ANALYZEのテーブルはNone）
+/// - MySQL: `information_schema.TABLES.TABLE_ROWS`
+/// - SQLite: `COUNT(*)`（ローカルファイルのため正確な件数を使用）
+#[derive(Debug, Clone)]
+pub struct ColumnStatisticsCollector {
+    dialect: Dialect,
+}
+
+impl ColumnStatisticsCollector {
+    /// 新しいColumnStatisticsCollectorを作成
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect }
+    }
+
+    /// 99パーセンタイルを安価に取得できるか（集約関数がある方言のみ）
+    pub fn supports_percentile(&self) -> bool {
+        matches!(self.dialect, Dialect::PostgreSQL)
+    }
+
+    /// テーブルの行数の見積もりを取得
+    ///
+    /// 見積もりが得られない場合はNoneを返します。
+    pub async fn estimate_row_count(
+        &self,
+        pool: &AnyPool,
+        table_name: &str,
+    ) -> Result<Option<i64>> {
+        let sql = self.build_row_count_query(table_name);
+        let query = sqlx::query(&sql);
+        let query = match self.dialect {
+            Dialect::PostgreSQL => query.bind(quote_table_postgres(table_name)),
+            Dialect::MySQL => query.bind(table_name.to_string()),
+            Dialect::SQLite => query,
+        };
+
+        let row = query
+            .fetch_optional(pool)
+            .await
+            .with_context(|| format!("Failed to estimate row count for '{}'", table_name))?;
+        let estimate = match row {
+            Some(row) => row.try_get::<Option<i64>, _>(0)?,
+            None => None,
+        };
+
+        // PostgreSQL は一度も ANALYZE されていないテーブルで -1 を返す
+        Ok(estimate.filter(|count| *count >= 0))
+    }
+
+    /// 指定カラムの最大長（と99パーセンタイル）を取得
+    pub async fn fetch_length_stats(
+        &self,
+        pool: &AnyPool,
+        table_name: &str,
+        columns: &[String],
+    ) -> Result<Vec<ColumnLengthStats>> {
+        if columns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = self.build_length_stats_query(table_name, columns);
+        let row = sqlx::query(&sql)
+            .fetch_one(pool)
+            .await
+            .with_context(|| format!("Failed to read column lengths for '{}'", table_name))?;
+
+        let values_per_column = if self.supports_percentile() { 2 } else { 1 };
+        columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let index = i * values_per_column;
+                let max_length = row.try_get::<Option<i64>, _>(index)?;
+                let p99_length = if self.supports_percentile() {
+                    row.try_get::<Option<i64>, _>(index + 1)?
+                } else {
+                    None
+                };
+                Ok(ColumnLengthStats {
+                    column: column.clone(),
+                    max_length,
+                    p99_length,
+                })
+            })
+            .collect()
+    }
+
+    /// 行数見積もりクエリを生成
+    ///
+    /// PostgreSQL と MySQL ではテーブル名をバインドパラメータとして渡します。
+    pub fn build_row_count_query(&self, table_name: &str) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => {
+                "SELECT CAST(reltuples AS BIGINT) FROM pg_class WHERE oid = to_regclass($1)"
+                    .to_string()
+            }
+            Dialect::MySQL => "SELECT CAST(TABLE_ROWS AS SIGNED) FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?".to_string(),
+            Dialect::SQLite => format!(
+                "SELECT COUNT(*) FROM {}",
+                quote_identifier_sqlite(table_name)
+            ),
+        }
+    }
+
+    /// 最大長（と99パーセンタイル）を1回のスキャンで取得するクエリを生成
+    ///
+    /// 結果はカラムごとに `max`（PostgreSQLでは `max, p99`）の順で並びます。
+    pub fn build_length_stats_query(&self, table_name: &str, columns: &[String]) -> String {
+        let select_list: Vec<String> = columns
+            .iter()
+            .flat_map(|column| {
+                let length = self.length_expression(column);
+                match self.dialect {
+                    Dialect::PostgreSQL => vec![
+                        format!("CAST(MAX({}) AS BIGINT)", length),
+                        format!(
+                            "CAST(CEIL(PERCENTILE_CONT(0.99) WITHIN GROUP (ORDER BY {})) AS BIGINT)",
+                            length
+                        ),
+                    ],
+                    Dialect::MySQL => vec![format!("CAST(MAX({}) AS SIGNED)", length)],
+                    Dialect::SQLite => vec![format!("MAX({})", length)],
+                }
+            })
+            .collect();
+
+        format!(
+            "SELECT {} FROM {}",
+            select_list.join(", "),
+            self.quote_table(table_name)
+        )
+    }
+
+    /// 文字数を求める式
+    fn length_expression(&self, column: &str) -> String {
+        let quoted = self.quote_identifier(column);
+        match self.dialect {
+            Dialect::PostgreSQL | Dialect::MySQL => format!("CHAR_LENGTH({})", quoted),
+            Dialect::SQLite => format!("LENGTH({})", quoted),
+        }
+    }
+
+    fn quote_table(&self, name: &str) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => quote_table_postgres(name),
+            _ => self.quote_identifier(name),
+        }
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        quote_identifier(self.dialect, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_build_length_stats_query_postgres() {
+        let collector = ColumnStatisticsCollector::new(Dialect::PostgreSQL);
+        let sql = collector.build_length_stats_query("users", &columns(&["email", "name"]));
+
+        assert_eq!(
+            sql,
+            r#"SELECT CAST(MAX(CHAR_LENGTH("email")) AS BIGINT), CAST(CEIL(PERCENTILE_CONT(0.99) WITHIN GROUP (ORDER BY CHAR_LENGTH("email"))) AS BIGINT), CAST(MAX(CHAR_LENGTH("name")) AS BIGINT), CAST(CEIL(PERCENTILE_CONT(0.99) WITHIN GROUP (ORDER BY CHAR_LENGTH("name"))) AS BIGINT) FROM "users""#
+        );
+    }
+
+    #[test]
+    fn test_build_length_stats_query_mysql() {
+        let collector = ColumnStatisticsCollector::new(Dialect::MySQL);
+        let sql = collector.build_length_stats_query("users", &columns(&["email"]));
+
+        assert_eq!(
+            sql,
+            "SELECT CAST(MAX(CHAR_LENGTH(`email`)) AS SIGNED) FROM `users`"
+        );
+    }
+
+    #[test]
+    fn test_build_length_stats_query_sqlite() {
+        let collector = ColumnStatisticsCollector::new(Dialect::SQLite);
+        let sql = collector.build_length_stats_query("users", &columns(&["email", "name"]));
+
+        assert_eq!(
+            sql,
+            r#"SELECT MAX(LENGTH("email")), MAX(LENGTH("name")) FROM "users""#
+        );
+    }
+
+    #[test]
+    fn test_build_length_stats_query_quotes_identifiers() {
+        let collector = ColumnStatisticsCollector::new(Dialect::MySQL);
+        let sql = collector.build_length_stats_query("odd`table", &columns(&["a`b"]));
+
+        assert_eq!(
+            sql,
+            "SELECT CAST(MAX(CHAR_LENGTH(`a``b`)) AS SIGNED) FROM `odd``table`"
+        );
+
+        let collector = ColumnStatisticsCollector::new(Dialect::PostgreSQL);
+        let sql = collector.build_row_count_query(r#"odd"table"#);
+        assert!(sql.contains("to_regclass($1)"), "{}", sql);
+        assert!(!sql.contains("odd"), "{}", sql);
+    }
+
+    #[test]
+    fn test_build_row_count_query() {
+        assert!(ColumnStatisticsCollector::new(Dialect::PostgreSQL)
+            .build_row_count_query("users")
+            .contains("pg_class"));
+        assert!(ColumnStatisticsCollector::new(Dialect::MySQL)
+            .build_row_count_query("users")
+            .contains("information_schema.TABLES"));
+        assert_eq!(
+            ColumnStatisticsCollector::new(Dialect::SQLite).build_row_count_query("users"),
+            r#"SELECT COUNT(*) FROM "users""#
+        );
+    }
+
+    #[test]
+    fn test_supports_percentile() {
+        assert!(ColumnStatisticsCollector::new(Dialect::PostgreSQL).supports_percentile());
+        assert!(!ColumnStatisticsCollector::new(Dialect::MySQL).supports_percentile());
+        assert!(!ColumnStatisticsCollector::new(Dialect::SQLite).supports_percentile());
+    }
+}
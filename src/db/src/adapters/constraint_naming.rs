@@ -0,0 +1,334 @@
+// 制約名生成ユーティリティ
+//
+// 名前が省略された FOREIGN KEY / UNIQUE / CHECK 制約に付ける名前を生成します。
+// 生成される名前はマイグレーションの DROP CONSTRAINT などで参照されるため、
+// 同じ入力からは常に同じ名前を返すことをマイナーバージョン間でも保証します。
+
+use sha2::{Digest, Sha256};
+
+/// PostgreSQL/MySQLの識別子最大長
+pub const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+/// 制約名を生成する共通ヘルパー
+///
+/// `{prefix}_{body}`形式で名前を組み立て、63文字（`MAX_IDENTIFIER_LENGTH`）を超える場合は
+/// SHA-256ハッシュ付きで切り詰めます。
+///
+/// # Arguments
+///
+/// * `prefix` - 制約名のプレフィックス（例: "fk", "uq", "ck"）
+/// * `body` - プレフィックス以降の本体部分（テーブル名、カラム名等を結合済み）
+///
+/// # Returns
+///
+/// 63文字以内の制約名
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::constraint_naming::{generate_constraint_name, MAX_IDENTIFIER_LENGTH};
+/// assert_eq!(generate_constraint_name("ix", "users_email"), "ix_users_email");
+/// assert!(generate_constraint_name("ix", &"a".repeat(100)).len() <= MAX_IDENTIFIER_LENGTH);
+/// ```
+pub fn generate_constraint_name(prefix: &str, body: &str) -> String {
+    let base_name = format!("{}_{}", prefix, body);
+
+    if base_name.len() <= MAX_IDENTIFIER_LENGTH {
+        return base_name;
+    }
+
+    // 長すぎる場合はハッシュを付けて切り詰める
+    // ハッシュは元の完全な名前から生成するため、同じ入力には同じ出力を保証
+    let mut hasher = Sha256::new();
+    hasher.update(base_name.as_bytes());
+    let hash = hasher.finalize();
+    let hash_suffix = format!(
+        "_{:x}",
+        &hash[..4].iter().fold(0u32, |acc, &b| acc << 8 | b as u32)
+    );
+
+    let prefix_with_sep = format!("{}_", prefix);
+    let available_length = MAX_IDENTIFIER_LENGTH - prefix_with_sep.len() - hash_suffix.len();
+
+    if body.len() <= available_length {
+        format!("{}{}{}", prefix_with_sep, body, hash_suffix)
+    } else {
+        format!(
+            "{}{}{}",
+            prefix_with_sep,
+            &body[..available_length],
+            hash_suffix
+        )
+    }
+}
+
+/// 外部キー制約名を生成
+///
+/// `fk_{table_name}_{columns}_{referenced_table}`形式で名前を生成します。
+/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::constraint_naming::generate_fk_constraint_name;
+/// let name = generate_fk_constraint_name("posts", &["user_id".to_string()], "users");
+/// assert_eq!(name, "fk_posts_user_id_users");
+/// ```
+pub fn generate_fk_constraint_name(
+    table_name: &str,
+    columns: &[String],
+    referenced_table: &str,
+) -> String {
+    let body = format!("{}_{}", table_name, columns.join("_"));
+    let body_with_ref = format!("{}_{}", body, referenced_table);
+    generate_constraint_name("fk", &body_with_ref)
+}
+
+/// UNIQUE制約名を生成
+///
+/// `uq_{table_name}_{columns}`形式で名前を生成します。
+/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::constraint_naming::generate_uq_constraint_name;
+/// let name = generate_uq_constraint_name("users", &["email".to_string()]);
+/// assert_eq!(name, "uq_users_email");
+/// ```
+pub fn generate_uq_constraint_name(table_name: &str, columns: &[String]) -> String {
+    let body = format!("{}_{}", table_name, columns.join("_"));
+    generate_constraint_name("uq", &body)
+}
+
+/// CHECK制約名を生成
+///
+/// `ck_{table_name}_{columns}`形式で名前を生成します。
+/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::constraint_naming::generate_ck_constraint_name;
+/// let name = generate_ck_constraint_name("users", &["age".to_string()]);
+/// assert_eq!(name, "ck_users_age");
+/// ```
+pub fn generate_ck_constraint_name(table_name: &str, columns: &[String]) -> String {
+    let body = format!("{}_{}", table_name, columns.join("_"));
+    generate_constraint_name("ck", &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ==========================================
+    // generate_fk_constraint_name のテスト
+    // ==========================================
+
+    #[test]
+    fn test_generate_fk_constraint_name_short() {
+        // 63文字以下の場合はそのまま返す
+        let name = generate_fk_constraint_name("posts", &["user_id".to_string()], "users");
+        assert_eq!(name, "fk_posts_user_id_users");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_fk_constraint_name_composite() {
+        // 複合キーの場合
+        let name = generate_fk_constraint_name(
+            "order_items",
+            &["order_id".to_string(), "product_id".to_string()],
+            "orders",
+        );
+        assert_eq!(name, "fk_order_items_order_id_product_id_orders");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_fk_constraint_name_truncated() {
+        // 63文字を超える場合はハッシュ付きで切り詰め
+        let name = generate_fk_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string(), "department_id".to_string()],
+            "another_very_long_table_name_here",
+        );
+
+        // 63文字以下であることを確認
+        assert!(
+            name.len() <= 63,
+            "Constraint name '{}' exceeds 63 characters (len={})",
+            name,
+            name.len()
+        );
+
+        // fk_プレフィックスで始まることを確認
+        assert!(name.starts_with("fk_"));
+
+        // ハッシュサフィックスが付いていることを確認（_xxxxxxxx形式）
+        assert!(name.contains("_"), "Expected hash suffix in '{}'", name);
+    }
+
+    #[test]
+    fn test_generate_fk_constraint_name_deterministic() {
+        // 同じ入力には同じ出力を保証
+        let name1 = generate_fk_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+            "another_very_long_table_name_here",
+        );
+        let name2 = generate_fk_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+            "another_very_long_table_name_here",
+        );
+        assert_eq!(name1, name2);
+    }
+
+    // ==========================================
+    // generate_uq_constraint_name のテスト
+    // ==========================================
+
+    #[test]
+    fn test_generate_uq_constraint_name_short() {
+        // 63文字以下の場合はそのまま返す
+        let name = generate_uq_constraint_name("users", &["email".to_string()]);
+        assert_eq!(name, "uq_users_email");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_uq_constraint_name_composite() {
+        // 複合カラムの場合
+        let name = generate_uq_constraint_name(
+            "order_items",
+            &["order_id".to_string(), "product_id".to_string()],
+        );
+        assert_eq!(name, "uq_order_items_order_id_product_id");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_uq_constraint_name_truncated() {
+        // 63文字を超える場合はハッシュ付きで切り詰め
+        let name = generate_uq_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &[
+                "organization_id".to_string(),
+                "department_id".to_string(),
+                "another_long_column".to_string(),
+            ],
+        );
+
+        assert!(
+            name.len() <= 63,
+            "Constraint name '{}' exceeds 63 characters (len={})",
+            name,
+            name.len()
+        );
+        assert!(name.starts_with("uq_"));
+    }
+
+    #[test]
+    fn test_generate_uq_constraint_name_deterministic() {
+        // 同じ入力には同じ出力を保証
+        let name1 = generate_uq_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+        );
+        let name2 = generate_uq_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+        );
+        assert_eq!(name1, name2);
+    }
+
+    // ==========================================
+    // generate_ck_constraint_name のテスト
+    // ==========================================
+
+    #[test]
+    fn test_generate_ck_constraint_name_short() {
+        // 63文字以下の場合はそのまま返す
+        let name = generate_ck_constraint_name("users", &["age".to_string()]);
+        assert_eq!(name, "ck_users_age");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_ck_constraint_name_composite() {
+        // 複合カラムの場合
+        let name =
+            generate_ck_constraint_name("products", &["price".to_string(), "discount".to_string()]);
+        assert_eq!(name, "ck_products_price_discount");
+        assert!(name.len() <= 63);
+    }
+
+    #[test]
+    fn test_generate_ck_constraint_name_truncated() {
+        // 63文字を超える場合はハッシュ付きで切り詰め
+        let name = generate_ck_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &[
+                "organization_id".to_string(),
+                "department_id".to_string(),
+                "another_long_column".to_string(),
+            ],
+        );
+
+        assert!(
+            name.len() <= 63,
+            "Constraint name '{}' exceeds 63 characters (len={})",
+            name,
+            name.len()
+        );
+        assert!(name.starts_with("ck_"));
+    }
+
+    #[test]
+    fn test_generate_ck_constraint_name_deterministic() {
+        // 同じ入力には同じ出力を保証
+        let name1 = generate_ck_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+        );
+        let name2 = generate_ck_constraint_name(
+            "very_long_table_name_with_many_characters",
+            &["organization_id".to_string()],
+        );
+        assert_eq!(name1, name2);
+    }
+
+    // ==========================================
+    // generate_constraint_name 共通ヘルパーのテスト
+    // ==========================================
+
+    #[test]
+    fn test_different_prefixes_produce_different_names() {
+        // uq_ と ck_ は同じ入力でも異なる名前を生成する
+        let uq_name = generate_uq_constraint_name("users", &["email".to_string()]);
+        let ck_name = generate_ck_constraint_name("users", &["email".to_string()]);
+        assert_ne!(uq_name, ck_name);
+        assert!(uq_name.starts_with("uq_"));
+        assert!(ck_name.starts_with("ck_"));
+    }
+
+    #[test]
+    fn test_generate_fk_constraint_name_different_inputs_different_outputs() {
+        // 異なる入力には異なる出力（ハッシュが異なる）
+        let name1 = generate_fk_constraint_name(
+            "very_long_table_name_with_many_characters_a",
+            &["column_id".to_string()],
+            "referenced_table",
+        );
+        let name2 = generate_fk_constraint_name(
+            "very_long_table_name_with_many_characters_b",
+            &["column_id".to_string()],
+            "referenced_table",
+        );
+
+        // 両方63文字以下
+        assert!(name1.len() <= 63);
+        assert!(name2.len() <= 63);
+
+        // 異なる名前が生成される
+        assert_ne!(name1, name2);
+    }
+}
@@ -4,11 +4,16 @@
 // PostgreSQL、MySQL、SQLiteに対応した統一されたインターフェースを提供します。
 
 use crate::adapters::connection_string;
+use crate::adapters::drivers;
 use crate::core::config::{DatabaseConfig, Dialect};
 use crate::core::error::DatabaseError;
 use sqlx::pool::PoolOptions;
 use sqlx::{Any, AnyPool};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use tracing::debug;
 
 /// データベース接続サービス
@@ -54,22 +59,61 @@ impl DatabaseConnectionService {
         dialect: Dialect,
         config: &DatabaseConfig,
     ) -> Result<AnyPool, DatabaseError> {
+        // ドライバーを含めずにビルドした方言には接続しない
+        drivers::ensure_driver_compiled(dialect)?;
+
         let connection_string = self.build_connection_string(dialect, config);
         debug!(dialect = ?dialect, host = %config.host, database = %config.database, "Creating database connection pool");
 
-        // プールオプションを作成
-        let pool_options = self.create_pool_options_from_config(config);
+        // wait_timeout が設定されている場合は、一時的なエラーの間だけ接続を再試行する
+        let wait_timeout = config.wait_timeout.map(Duration::from_secs);
+        let started = Instant::now();
+        let mut delay = RETRY_INITIAL_DELAY;
+        let mut attempt = 1u32;
 
-        // 接続プールを作成
-        let pool = pool_options
-            .connect(&connection_string)
-            .await
-            .map_err(|e| DatabaseError::Connection {
-                message: format!("Failed to create database connection pool: {}", dialect),
-                cause: e.to_string(),
-            })?;
-        debug!("Database connection pool created successfully");
-        Ok(pool)
+        loop {
+            // 接続プールを作成
+            let result = self
+                .create_pool_options_from_config(config)
+                .connect(&connection_string)
+                .await;
+            let error = match result {
+                Ok(pool) => {
+                    debug!(attempt, "Database connection pool created successfully");
+                    return Ok(pool);
+                }
+                Err(e) => e,
+            };
+
+            let remaining = wait_timeout
+                .and_then(|timeout| timeout.checked_sub(started.elapsed()))
+                .filter(|remaining| !remaining.is_zero());
+            let class = classify_connection_error(&error);
+            match remaining {
+                Some(remaining) if class == ConnectionErrorClass::Transient => {
+                    let sleep = delay.min(remaining);
+                    debug!(
+                        attempt,
+                        error = %error,
+                        retry_in_ms = sleep.as_millis() as u64,
+                        "Database is not reachable yet, retrying"
+                    );
+                    tokio::time::sleep(sleep).await;
+                    delay = (delay * 2).min(RETRY_MAX_DELAY);
+                    attempt += 1;
+                }
+                _ if drivers::is_missing_driver_error(&error) => {
+                    return Err(drivers::driver_unavailable(dialect));
+                }
+                _ => {
+                    debug!(attempt, error = %error, class = ?class, "Giving up connecting to database");
+                    return Err(DatabaseError::Connection {
+                        message: format!("Failed to create database connection pool: {}", dialect),
+                        cause: error.to_string(),
+                    });
+                }
+            }
+        }
     }
 
     /// 接続テストを実行
@@ -93,17 +137,48 @@ impl DatabaseConnectionService {
             })
     }
 
+    /// サーバーのバージョン文字列を取得
+    ///
+    /// PostgreSQL / MySQL は `VERSION()`、SQLite は `sqlite_version()` の結果をそのまま返します。
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    pub async fn fetch_server_version(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<String, DatabaseError> {
+        let sql = match dialect {
+            Dialect::PostgreSQL => "SELECT version()",
+            Dialect::MySQL => "SELECT CAST(VERSION() AS CHAR)",
+            Dialect::SQLite => "SELECT sqlite_version()",
+        };
+        let (version,): (String,) =
+            sqlx::query_as(sql)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| DatabaseError::Query {
+                    message: format!("Failed to get server version: {}", e),
+                    sql: Some(sql.to_string()),
+                })?;
+        debug!(version = %version, "Detected server version");
+        Ok(version)
+    }
+
     /// DatabaseConfigからプールオプションを作成
     ///
-    /// max_connections, min_connections, idle_timeout, timeout の設定を反映します。
-    /// 未設定の場合はデフォルト値（max_connections=5, timeout=30秒）を使用します。
+    /// max_connections, min_connections, acquire_timeout, idle_timeout の設定を反映します。
+    /// acquire_timeout が未設定の場合は timeout を使用し、どちらも未設定の場合は
+    /// デフォルト値（max_connections=5, acquire_timeout=30秒）を使用します。
     pub fn create_pool_options_from_config(&self, config: &DatabaseConfig) -> PoolOptions<Any> {
         let max_conn = config.max_connections.unwrap_or(5);
-        let timeout = config.timeout.unwrap_or(30);
+        let acquire_timeout = config.acquire_timeout.or(config.timeout).unwrap_or(30);
 
         let mut opts = PoolOptions::new()
             .max_connections(max_conn)
-            .acquire_timeout(Duration::from_secs(timeout));
+            .acquire_timeout(Duration::from_secs(acquire_timeout));
 
         if let Some(min_conn) = config.min_connections {
             opts = opts.min_connections(min_conn);
@@ -126,12 +201,149 @@ impl DatabaseConnectionService {
     }
 }
 
+/// 接続を再試行するまでの最初の待ち時間
+const RETRY_INITIAL_DELAY: Duration = Duration::from_millis(200);
+
+/// 接続を再試行するまでの待ち時間の上限
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// 接続エラーの分類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionErrorClass {
+    /// データベースの起動待ちなど、時間をおけば解消する可能性があるエラー
+    Transient,
+    /// 認証エラー・存在しないデータベースなど、再試行しても解消しないエラー
+    Permanent,
+}
+
+/// 接続時のエラーを再試行の対象かどうかで分類する
+pub fn classify_connection_error(error: &sqlx::Error) -> ConnectionErrorClass {
+    match error {
+        sqlx::Error::Io(e) => classify_io_error_kind(e.kind()),
+        sqlx::Error::PoolTimedOut => ConnectionErrorClass::Transient,
+        sqlx::Error::Database(e) => classify_database_error_code(e.code().as_deref()),
+        _ => ConnectionErrorClass::Permanent,
+    }
+}
+
+/// 接続時のIOエラーの種類を分類する
+///
+/// 接続の拒否・切断・タイムアウトは、コンテナ内のデータベースがまだ起動していない場合に起こる。
+pub fn classify_io_error_kind(kind: std::io::ErrorKind) -> ConnectionErrorClass {
+    use std::io::ErrorKind;
+
+    match kind {
+        ErrorKind::ConnectionRefused
+        | ErrorKind::ConnectionReset
+        | ErrorKind::ConnectionAborted
+        | ErrorKind::NotConnected
+        | ErrorKind::AddrNotAvailable
+        | ErrorKind::BrokenPipe
+        | ErrorKind::TimedOut
+        | ErrorKind::UnexpectedEof
+        | ErrorKind::Interrupted => ConnectionErrorClass::Transient,
+        _ => ConnectionErrorClass::Permanent,
+    }
+}
+
+/// 接続時にデータベースが返したエラーコード（SQLSTATE）を分類する
+///
+/// 起動中・接続数超過・通信エラーのみを一時的なエラーとし、
+/// 認証エラー（28000 / 28P01）や存在しないデータベース（3D000 / MySQLの42000）は再試行しない。
+pub fn classify_database_error_code(code: Option<&str>) -> ConnectionErrorClass {
+    match code {
+        // PostgreSQL: cannot_connect_now（起動中）・too_many_connections
+        Some("57P03") | Some("53300")
+        // 接続例外（08000 / 08001 / 08006）・MySQLの通信エラー（08S01）
+        | Some("08000") | Some("08001") | Some("08006") | Some("08S01") => {
+            ConnectionErrorClass::Transient
+        }
+        _ => ConnectionErrorClass::Permanent,
+    }
+}
+
 impl Default for DatabaseConnectionService {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// 接続プールマネージャー
+///
+/// 解決済みの接続文字列ごとに `AnyPool` を1つだけ作成し、コマンド実行中は
+/// 同じ環境への接続要求に対して共有のプールを返します。
+/// クローンしたマネージャーはキャッシュを共有します。
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionManager {
+    service: DatabaseConnectionService,
+    pools: Arc<Mutex<HashMap<String, AnyPool>>>,
+    pools_created: Arc<AtomicUsize>,
+}
+
+impl ConnectionManager {
+    /// 新しいConnectionManagerを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 接続文字列に対応する共有プールを取得
+    ///
+    /// 同じ接続文字列のプールが既に存在する場合はそれを返し、
+    /// 存在しない場合は新しく作成してキャッシュします。
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - データベース方言
+    /// * `config` - データベース設定
+    ///
+    /// # Returns
+    ///
+    /// 共有の接続プールまたはエラー
+    pub async fn get_pool(
+        &self,
+        dialect: Dialect,
+        config: &DatabaseConfig,
+    ) -> Result<AnyPool, DatabaseError> {
+        let key = self.service.build_connection_string(dialect, config);
+
+        // 作成中に同じ環境への要求が来ても二重に作成しないよう、作成完了までロックを保持する
+        let mut pools = self.pools.lock().await;
+        if let Some(pool) = pools.get(&key) {
+            if !pool.is_closed() {
+                debug!("Reusing cached database connection pool");
+                return Ok(pool.clone());
+            }
+        }
+
+        let pool = self.service.create_pool(dialect, config).await?;
+        self.pools_created.fetch_add(1, Ordering::SeqCst);
+        pools.insert(key, pool.clone());
+        Ok(pool)
+    }
+
+    /// これまでに作成したプールの数
+    pub fn pools_created(&self) -> usize {
+        self.pools_created.load(Ordering::SeqCst)
+    }
+
+    /// キャッシュしている全てのプールを閉じる
+    pub async fn close_all(&self) {
+        let pools: Vec<AnyPool> = self
+            .pools
+            .lock()
+            .await
+            .drain()
+            .map(|(_, pool)| pool)
+            .collect();
+        if !pools.is_empty() {
+            debug!(count = pools.len(), "Closing database connection pools");
+        }
+        for pool in pools {
+            self.service.close_pool(pool).await;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +372,7 @@ mod tests {
         let config = DatabaseConfig {
             database: "test".to_string(),
             timeout: Some(60),
+            acquire_timeout: Some(10),
             max_connections: Some(20),
             min_connections: Some(2),
             idle_timeout: Some(300),
@@ -169,4 +382,148 @@ mod tests {
 
         assert!(format!("{:?}", pool_options).contains("PoolOptions"));
     }
+
+    #[test]
+    fn test_classify_io_errors() {
+        use std::io::{Error, ErrorKind};
+
+        for kind in [
+            ErrorKind::ConnectionRefused,
+            ErrorKind::ConnectionReset,
+            ErrorKind::ConnectionAborted,
+            ErrorKind::TimedOut,
+            ErrorKind::UnexpectedEof,
+        ] {
+            let error = sqlx::Error::Io(Error::from(kind));
+            assert_eq!(
+                classify_connection_error(&error),
+                ConnectionErrorClass::Transient,
+                "{:?}",
+                kind
+            );
+        }
+        for kind in [
+            ErrorKind::PermissionDenied,
+            ErrorKind::NotFound,
+            ErrorKind::InvalidData,
+        ] {
+            assert_eq!(
+                classify_io_error_kind(kind),
+                ConnectionErrorClass::Permanent,
+                "{:?}",
+                kind
+            );
+        }
+        assert_eq!(
+            classify_connection_error(&sqlx::Error::PoolTimedOut),
+            ConnectionErrorClass::Transient
+        );
+        assert_eq!(
+            classify_connection_error(&sqlx::Error::Configuration("bad url".into())),
+            ConnectionErrorClass::Permanent
+        );
+    }
+
+    #[test]
+    fn test_classify_database_error_codes() {
+        // 起動中・接続数超過・通信エラーは再試行する
+        for code in ["57P03", "53300", "08001", "08006", "08S01"] {
+            assert_eq!(
+                classify_database_error_code(Some(code)),
+                ConnectionErrorClass::Transient,
+                "{}",
+                code
+            );
+        }
+        // 認証エラー・存在しないデータベースは再試行しない
+        for code in ["28P01", "28000", "3D000", "42000"] {
+            assert_eq!(
+                classify_database_error_code(Some(code)),
+                ConnectionErrorClass::Permanent,
+                "{}",
+                code
+            );
+        }
+        assert_eq!(
+            classify_database_error_code(None),
+            ConnectionErrorClass::Permanent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_pool_gives_up_after_wait_timeout() {
+        sqlx::any::install_default_drivers();
+        // 接続を受け付けないポートに接続し、wait_timeout の経過後にエラーとなることを確認する
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let config = DatabaseConfig {
+            host: "127.0.0.1".to_string(),
+            port: Some(port),
+            database: "app".to_string(),
+            user: Some("app".to_string()),
+            acquire_timeout: Some(1),
+            wait_timeout: Some(2),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let result = DatabaseConnectionService::new()
+            .create_pool(Dialect::PostgreSQL, &config)
+            .await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() >= Duration::from_secs(2));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    fn sqlite_config(path: &std::path::Path) -> DatabaseConfig {
+        DatabaseConfig {
+            database: path.to_string_lossy().to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connection_manager_reuses_pool_per_environment() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = sqlite_config(&temp_dir.path().join("app.db"));
+        let manager = ConnectionManager::new();
+
+        let first = manager.get_pool(Dialect::SQLite, &config).await.unwrap();
+        let second = manager
+            .clone()
+            .get_pool(Dialect::SQLite, &config)
+            .await
+            .unwrap();
+        sqlx::query("SELECT 1").execute(&second).await.unwrap();
+
+        assert_eq!(manager.pools_created(), 1);
+
+        // 別の環境（接続文字列）には別のプールを作成する
+        let other = sqlite_config(&temp_dir.path().join("other.db"));
+        manager.get_pool(Dialect::SQLite, &other).await.unwrap();
+        assert_eq!(manager.pools_created(), 2);
+
+        manager.close_all().await;
+        assert!(first.is_closed());
+        assert!(second.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_connection_manager_recreates_closed_pool() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = sqlite_config(&temp_dir.path().join("app.db"));
+        let manager = ConnectionManager::new();
+
+        manager.get_pool(Dialect::SQLite, &config).await.unwrap();
+        manager.close_all().await;
+
+        let pool = manager.get_pool(Dialect::SQLite, &config).await.unwrap();
+        assert!(!pool.is_closed());
+        assert_eq!(manager.pools_created(), 2);
+        manager.close_all().await;
+    }
 }
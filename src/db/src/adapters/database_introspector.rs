@@ -8,9 +8,13 @@ use std::sync::LazyLock;
 use anyhow::Result;
 use async_trait::async_trait;
 use regex::Regex;
+use serde::Serialize;
 use sqlx::AnyPool;
 use sqlx::Row;
 
+use crate::core::config::DEFAULT_POSTGRES_SCHEMA;
+use crate::core::schema::{qualify_table_name, strip_outer_parens};
+
 /// 識別子検出用の正規表現（コンパイル済みキャッシュ）
 /// Unicode 識別子 (XID_Start/XID_Continue) とアンダースコアを許可する。
 static IDENTIFIER_REGEX: LazyLock<Regex> =
@@ -19,7 +23,7 @@ static IDENTIFIER_REGEX: LazyLock<Regex> =
 /// MySQL の information_schema は多くのカラムを BLOB/VARBINARY 型で返す。
 /// sqlx の Any ドライバは String として直接デコードできないため、
 /// まず String を試し、失敗したら Vec<u8> → String 変換にフォールバックする。
-fn mysql_get_string(row: &sqlx::any::AnyRow, index: usize) -> String {
+pub(crate) fn mysql_get_string(row: &sqlx::any::AnyRow, index: usize) -> String {
     row.try_get::<String, _>(index).unwrap_or_else(|_| {
         let bytes: Vec<u8> = row.get(index);
         String::from_utf8_lossy(&bytes).to_string()
@@ -79,6 +83,7 @@ fn parse_mysql_enum_values(column_type: &str) -> Option<Vec<String>> {
                     value_closed = true;
                 }
             }
+            #[allow(clippy::collapsible_match)]
             ',' if !in_quote => {
                 // クォートが閉じられた値のみ追加（空文字列も含む）
                 if value_closed {
@@ -178,6 +183,7 @@ fn parse_mysql_set_values(column_type: &str) -> Option<Vec<String>> {
                     value_closed = true;
                 }
             }
+            #[allow(clippy::collapsible_match)]
             ',' if !in_quote => {
                 if value_closed {
                     values.push(current.clone());
@@ -213,7 +219,7 @@ fn is_mysql_unsigned(column_type: &str) -> bool {
 ///
 /// データベースから取得したカラム情報を保持する構造体。
 /// TypeMappingService で ColumnType に変換されます。
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawColumnInfo {
     /// カラム名
     pub name: String,
@@ -229,6 +235,8 @@ pub struct RawColumnInfo {
     pub numeric_precision: Option<i32>,
     /// 数値型のスケール
     pub numeric_scale: Option<i32>,
+    /// 日時型の秒の小数部の桁数
+    pub datetime_precision: Option<i32>,
     /// ユーザー定義型名（PostgreSQLのENUM等）
     pub udt_name: Option<String>,
     /// 自動増分フラグ（SQLite AUTOINCREMENT検出用）
@@ -239,10 +247,18 @@ pub struct RawColumnInfo {
     pub set_values: Option<Vec<String>>,
     /// UNSIGNED修飾子（MySQL用）
     pub is_unsigned: bool,
+    /// 生成カラムの式（生成カラムでない場合はNone）
+    pub generation_expression: Option<String>,
+    /// 生成カラムがSTOREDかどうか（falseの場合はVIRTUAL）
+    pub generated_stored: bool,
+    /// カラムコメント（SQLiteは常にNone）
+    pub comment: Option<String>,
+    /// 行の更新時に現在時刻を設定するか（MySQLの EXTRA が `on update CURRENT_TIMESTAMP`）
+    pub on_update_current_timestamp: bool,
 }
 
 /// 生のインデックス情報（DB固有フォーマット）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawIndexInfo {
     /// インデックス名
     pub name: String,
@@ -250,10 +266,26 @@ pub struct RawIndexInfo {
     pub columns: Vec<String>,
     /// ユニーク制約フラグ
     pub unique: bool,
+    /// カラムごとの降順フラグ（columnsと同じ順序。空の場合はすべて昇順）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub descending: Vec<bool>,
+    /// カラムごとのNULLS FIRSTフラグ（PostgreSQLのみ。空の場合はソート順のデフォルト）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nulls_first: Vec<bool>,
+    /// 部分インデックスの条件式（PostgreSQL・SQLiteのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predicate: Option<String>,
+    /// カラムごとのプレフィックス長（MySQLのみ。columnsと同じ順序。空の場合はすべてカラム全体）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefix_lengths: Vec<Option<u32>>,
+    /// インデックスの種類（MySQLの INDEX_TYPE、PostgreSQLのアクセスメソッド名）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<String>,
 }
 
 /// 生の制約情報（DB固有フォーマット）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum RawConstraintInfo {
     /// プライマリキー制約
     PrimaryKey { columns: Vec<String> },
@@ -264,6 +296,8 @@ pub enum RawConstraintInfo {
         referenced_columns: Vec<String>,
         /// ON DELETE アクション（例: "CASCADE", "SET NULL", "RESTRICT", "NO ACTION"）
         on_delete: Option<String>,
+        /// ON UPDATE アクション（ON DELETE と同じ表記）
+        on_update: Option<String>,
     },
     /// ユニーク制約
     Unique { columns: Vec<String> },
@@ -275,7 +309,7 @@ pub enum RawConstraintInfo {
 }
 
 /// 生のENUM情報（PostgreSQL専用）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawEnumInfo {
     /// ENUM型名
     pub name: String,
@@ -284,7 +318,7 @@ pub struct RawEnumInfo {
 }
 
 /// 生のView情報（DB固有フォーマット）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawViewInfo {
     /// ビュー名
     pub name: String,
@@ -320,10 +354,131 @@ pub trait DatabaseIntrospector: Send + Sync {
 
     /// View定義を取得
     async fn get_views(&self, pool: &AnyPool) -> Result<Vec<RawViewInfo>>;
+
+    /// テーブルコメントを取得（コメントを保存できないSQLiteではNoneを返す）
+    async fn get_table_comment(
+        &self,
+        _pool: &AnyPool,
+        _table_name: &str,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// キー内の位置順にカラム名を並べる
+///
+/// `rows` は (キー内の位置, カラム名)。主キー・ユニーク制約のカラム順は意味を持つため、
+/// テーブル上のカラム順（SQLite の PRAGMA table_info など）ではなくキー内の位置で並べる。
+fn columns_in_key_order(mut rows: Vec<(i64, String)>) -> Vec<String> {
+    rows.sort_by_key(|(position, _)| *position);
+    rows.into_iter().map(|(_, column)| column).collect()
+}
+
+/// ユニーク制約の行 (制約名, 制約内の位置, カラム名) を制約ごとにまとめる
+///
+/// 制約は制約名順、各制約のカラムは制約内の位置順に並べる。
+fn group_unique_rows(rows: Vec<(String, i64, String)>) -> Vec<RawConstraintInfo> {
+    let mut groups: std::collections::BTreeMap<String, Vec<(i64, String)>> =
+        std::collections::BTreeMap::new();
+    for (name, position, column) in rows {
+        groups.entry(name).or_default().push((position, column));
+    }
+
+    groups
+        .into_values()
+        .map(|columns| RawConstraintInfo::Unique {
+            columns: columns_in_key_order(columns),
+        })
+        .collect()
+}
+
+/// 外部キーを構成する1カラム分の行
+#[derive(Debug, Clone)]
+struct ForeignKeyColumnRow {
+    /// 外部キー内の位置
+    position: i64,
+    column: String,
+    referenced_table: String,
+    referenced_column: String,
+    on_delete: Option<String>,
+    on_update: Option<String>,
+}
+
+/// 外部キーの行を制約ごとにまとめる（複合外部キー対応）
+///
+/// `rows` の1要素目は制約の識別子（制約名、SQLite では id）。制約は識別子順、
+/// 各制約のカラムと参照先カラムは外部キー内の位置順に並べ、対応関係を保つ。
+fn group_foreign_key_rows<K: Ord>(rows: Vec<(K, ForeignKeyColumnRow)>) -> Vec<RawConstraintInfo> {
+    let mut groups: std::collections::BTreeMap<K, Vec<ForeignKeyColumnRow>> =
+        std::collections::BTreeMap::new();
+    for (key, row) in rows {
+        groups.entry(key).or_default().push(row);
+    }
+
+    groups
+        .into_values()
+        .map(|mut rows| {
+            rows.sort_by_key(|row| row.position);
+            let referenced_table = rows[0].referenced_table.clone();
+            let on_delete = rows[0].on_delete.clone();
+            let on_update = rows[0].on_update.clone();
+            let (columns, referenced_columns) = rows
+                .into_iter()
+                .map(|row| (row.column, row.referenced_column))
+                .unzip();
+            RawConstraintInfo::ForeignKey {
+                columns,
+                referenced_table,
+                referenced_columns,
+                on_delete,
+                on_update,
+            }
+        })
+        .collect()
 }
 
 /// PostgreSQL用イントロスペクター
-pub struct PostgresIntrospector;
+///
+/// `schemas` に含まれるスキーマのテーブルを取得します。
+/// `public` 以外のスキーマのテーブルは `schema.table` 形式の名前で返します。
+/// ENUMとビューは `public` スキーマのみが対象です。
+pub struct PostgresIntrospector {
+    schemas: Vec<String>,
+}
+
+impl PostgresIntrospector {
+    /// 対象スキーマを指定してイントロスペクターを作成
+    pub fn new(schemas: Vec<String>) -> Self {
+        Self { schemas }
+    }
+
+    /// テーブル名をスキーマ名とスキーマ修飾なしのテーブル名に分割
+    ///
+    /// 対象スキーマで修飾されていない名前は `public` のテーブルとして扱います。
+    fn split_table_name<'a>(&'a self, table_name: &'a str) -> (&'a str, &'a str) {
+        if let Some((schema, table)) = table_name.split_once('.') {
+            if self.schemas.iter().any(|s| s == schema) {
+                return (schema, table);
+            }
+        }
+        (DEFAULT_POSTGRES_SCHEMA, table_name)
+    }
+}
+
+impl Default for PostgresIntrospector {
+    fn default() -> Self {
+        Self::new(vec![DEFAULT_POSTGRES_SCHEMA.to_string()])
+    }
+}
+
+/// `public` 以外のスキーマのテーブル名を `schema.table` 形式に修飾する
+fn qualify_pg_table_name(schema: &str, table_name: String) -> String {
+    if schema == DEFAULT_POSTGRES_SCHEMA {
+        table_name
+    } else {
+        qualify_table_name(schema, &table_name)
+    }
+}
 
 /// MySQL用イントロスペクター
 pub struct MySqlIntrospector;
@@ -332,14 +487,128 @@ pub struct MySqlIntrospector;
 pub struct SqliteIntrospector;
 
 /// 方言に応じたイントロスペクターを作成
+///
+/// PostgreSQLは `public` スキーマのみが対象です。
+/// 設定の `postgres.schemas` を反映するには [`create_introspector_for_config`] を使用してください。
 pub fn create_introspector(dialect: crate::core::config::Dialect) -> Box<dyn DatabaseIntrospector> {
     match dialect {
-        crate::core::config::Dialect::PostgreSQL => Box::new(PostgresIntrospector),
+        crate::core::config::Dialect::PostgreSQL => Box::new(PostgresIntrospector::default()),
         crate::core::config::Dialect::MySQL => Box::new(MySqlIntrospector),
         crate::core::config::Dialect::SQLite => Box::new(SqliteIntrospector),
     }
 }
 
+/// 設定に応じたイントロスペクターを作成
+///
+/// PostgreSQLでは `postgres.schemas` のスキーマを対象にします。
+pub fn create_introspector_for_config(
+    config: &crate::core::config::Config,
+) -> Box<dyn DatabaseIntrospector> {
+    match config.dialect {
+        crate::core::config::Dialect::PostgreSQL => {
+            Box::new(PostgresIntrospector::new(config.postgres.schemas.clone()))
+        }
+        dialect => create_introspector(dialect),
+    }
+}
+
+/// MySQL の権限不足エラー番号
+///
+/// - 1044: ER_DBACCESS_DENIED_ERROR
+/// - 1142: ER_TABLEACCESS_DENIED_ERROR
+/// - 1143: ER_COLUMNACCESS_DENIED_ERROR
+/// - 1227: ER_SPECIFIC_ACCESS_DENIED_ERROR
+const MYSQL_PERMISSION_DENIED_ERRORS: &[u16] = &[1044, 1142, 1143, 1227];
+
+/// イントロスペクション中のエラーが権限不足によるものかを判定
+///
+/// エラーチェーン内の sqlx データベースエラーを方言ごとのエラーコードで分類します。
+/// 権限不足以外のエラー（接続断や構文エラーなど）は false を返します。
+pub fn is_permission_denied_error(
+    dialect: crate::core::config::Dialect,
+    error: &anyhow::Error,
+) -> bool {
+    error
+        .chain()
+        .any(|cause| match cause.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::Database(db_error)) => {
+                is_permission_denied_database_error(dialect, db_error.as_ref())
+            }
+            _ => false,
+        })
+}
+
+/// sqlx のデータベースエラーが権限不足によるものかを判定
+pub fn is_permission_denied_database_error(
+    dialect: crate::core::config::Dialect,
+    db_error: &dyn sqlx::error::DatabaseError,
+) -> bool {
+    #[cfg(feature = "mysql")]
+    let mysql_error_number = db_error
+        .try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>()
+        .map(|e| e.number());
+    #[cfg(not(feature = "mysql"))]
+    let mysql_error_number = None;
+    is_permission_denied_code(dialect, db_error.code().as_deref(), mysql_error_number)
+}
+
+/// 方言ごとのエラーコードが権限不足を表すかを判定
+///
+/// - PostgreSQL: SQLSTATE 42501 (insufficient_privilege)
+/// - MySQL: エラー番号で判定（SQLSTATE 42000 は構文エラーと共通のため使用しない）
+/// - SQLite: SQLITE_AUTH (23)。拡張エラーコードは下位8ビットで判定する
+pub fn is_permission_denied_code(
+    dialect: crate::core::config::Dialect,
+    code: Option<&str>,
+    mysql_error_number: Option<u16>,
+) -> bool {
+    match dialect {
+        crate::core::config::Dialect::PostgreSQL => code == Some("42501"),
+        crate::core::config::Dialect::MySQL => {
+            mysql_error_number.is_some_and(|n| MYSQL_PERMISSION_DENIED_ERRORS.contains(&n))
+        }
+        crate::core::config::Dialect::SQLite => code
+            .and_then(|c| c.parse::<i32>().ok())
+            .is_some_and(|c| c & 0xff == 23),
+    }
+}
+
+/// PostgreSQLのユーザー定義型（DOMAIN・拡張型・複合型）の型名を解決する
+///
+/// DOMAIN型のカラムは information_schema 上では基底型として報告されるため、
+/// DOMAIN名を `USER-DEFINED` 型として扱い、基底型への置き換えを防ぎます。
+/// `public` / `pg_catalog` 以外のスキーマに属する型はスキーマ名で修飾します。
+///
+/// # Returns
+///
+/// (data_type, udt_name) のタプル
+fn resolve_pg_user_defined_type(
+    data_type: String,
+    udt_name: Option<String>,
+    udt_schema: Option<String>,
+    domain_name: Option<String>,
+    domain_schema: Option<String>,
+) -> (String, Option<String>) {
+    let qualify = |schema: Option<String>, name: String| match schema.as_deref() {
+        None | Some("public") | Some("pg_catalog") => name,
+        Some(schema) => format!("{}.{}", schema, name),
+    };
+
+    if let Some(domain_name) = domain_name {
+        return (
+            "USER-DEFINED".to_string(),
+            Some(qualify(domain_schema, domain_name)),
+        );
+    }
+
+    if data_type == "USER-DEFINED" {
+        let udt_name = udt_name.map(|name| qualify(udt_schema, name));
+        return (data_type, udt_name);
+    }
+
+    (data_type, udt_name)
+}
+
 // =============================================================================
 // PostgreSQL イントロスペクター実装
 // =============================================================================
@@ -349,16 +618,24 @@ impl DatabaseIntrospector for PostgresIntrospector {
     async fn get_table_names(&self, pool: &AnyPool) -> Result<Vec<String>> {
         use sqlx::Row;
 
+        // 対象スキーマは設定値のため、バインドせずに取得後に絞り込む
+        // （Anyドライバーは配列のバインドに対応していない）
         let sql = r#"
-            SELECT table_name::text
+            SELECT table_schema::text, table_name::text
             FROM information_schema.tables
-            WHERE table_schema = 'public'
-                AND table_name != 'schema_migrations'
-            ORDER BY table_name
+            WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+                AND table_name NOT IN ('schema_migrations', 'schema_migration_rollbacks')
+            ORDER BY table_schema, table_name
         "#;
 
         let rows = sqlx::query(sql).fetch_all(pool).await?;
-        let table_names = rows.iter().map(|row| row.get::<String, _>(0)).collect();
+        let mut table_names: Vec<String> = rows
+            .iter()
+            .map(|row| (row.get::<String, _>(0), row.get::<String, _>(1)))
+            .filter(|(schema, _)| self.schemas.contains(schema))
+            .map(|(schema, table_name)| qualify_pg_table_name(&schema, table_name))
+            .collect();
+        table_names.sort();
 
         Ok(table_names)
     }
@@ -366,38 +643,84 @@ impl DatabaseIntrospector for PostgresIntrospector {
     async fn get_columns(&self, pool: &AnyPool, table_name: &str) -> Result<Vec<RawColumnInfo>> {
         use sqlx::Row;
 
+        // 生成カラムは generation_expression に式が入り、pg_attribute.attgenerated が
+        // 's'（STORED）または 'v'（VIRTUAL、PostgreSQL 18以降）となる
         let sql = r#"
             SELECT
-                column_name::text,
-                data_type::text,
-                is_nullable::text,
-                column_default::text,
-                character_maximum_length::integer,
-                numeric_precision::integer,
-                numeric_scale::integer,
-                udt_name::text
-            FROM information_schema.columns
-            WHERE table_name = $1 AND table_schema = 'public'
-            ORDER BY ordinal_position
+                c.column_name::text,
+                c.data_type::text,
+                c.is_nullable::text,
+                c.column_default::text,
+                c.character_maximum_length::integer,
+                c.numeric_precision::integer,
+                c.numeric_scale::integer,
+                c.udt_name::text,
+                c.udt_schema::text,
+                c.domain_name::text,
+                c.domain_schema::text,
+                c.datetime_precision::integer,
+                c.generation_expression::text,
+                (
+                    SELECT a.attgenerated::text
+                    FROM pg_attribute a
+                    JOIN pg_class t ON t.oid = a.attrelid
+                    JOIN pg_namespace n ON n.oid = t.relnamespace
+                    WHERE n.nspname = c.table_schema
+                        AND t.relname = c.table_name
+                        AND a.attname = c.column_name
+                ) as attgenerated,
+                (
+                    SELECT pg_catalog.col_description(t.oid, a.attnum)
+                    FROM pg_attribute a
+                    JOIN pg_class t ON t.oid = a.attrelid
+                    JOIN pg_namespace n ON n.oid = t.relnamespace
+                    WHERE n.nspname = c.table_schema
+                        AND t.relname = c.table_name
+                        AND a.attname = c.column_name
+                ) as column_comment
+            FROM information_schema.columns c
+            WHERE c.table_name = $1 AND c.table_schema = $2
+            ORDER BY c.ordinal_position
         "#;
 
-        let rows = sqlx::query(sql).bind(table_name).fetch_all(pool).await?;
+        let (schema, table_name) = self.split_table_name(table_name);
+        let rows = sqlx::query(sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
 
         let columns = rows
             .iter()
-            .map(|row| RawColumnInfo {
-                name: row.get(0),
-                data_type: row.get(1),
-                is_nullable: row.get::<String, _>(2) == "YES",
-                default_value: row.get(3),
-                char_max_length: row.get(4),
-                numeric_precision: row.get(5),
-                numeric_scale: row.get(6),
-                udt_name: row.get(7),
-                auto_increment: None,
-                enum_values: None, // PostgreSQLはget_enums()で別途取得
-                set_values: None,
-                is_unsigned: false,
+            .map(|row| {
+                let (data_type, udt_name) = resolve_pg_user_defined_type(
+                    row.get(1),
+                    row.get(7),
+                    row.get(8),
+                    row.get(9),
+                    row.get(10),
+                );
+                RawColumnInfo {
+                    name: row.get(0),
+                    data_type,
+                    is_nullable: row.get::<String, _>(2) == "YES",
+                    default_value: row.get(3),
+                    char_max_length: row.get(4),
+                    numeric_precision: row.get(5),
+                    numeric_scale: row.get(6),
+                    datetime_precision: row.get(11),
+                    udt_name,
+                    auto_increment: None,
+                    enum_values: None, // PostgreSQLはget_enums()で別途取得
+                    set_values: None,
+                    is_unsigned: false,
+                    generation_expression: row
+                        .get::<Option<String>, _>(12)
+                        .filter(|e| !e.is_empty()),
+                    generated_stored: row.get::<Option<String>, _>(13).as_deref() == Some("s"),
+                    on_update_current_timestamp: false,
+                    comment: row.get::<Option<String>, _>(14).filter(|c| !c.is_empty()),
+                }
             })
             .collect();
 
@@ -407,50 +730,72 @@ impl DatabaseIntrospector for PostgresIntrospector {
     async fn get_indexes(&self, pool: &AnyPool, table_name: &str) -> Result<Vec<RawIndexInfo>> {
         use sqlx::Row;
 
+        // indkey の並び順でカラムを取得し、indoption からソート順・NULL順序を読み取る
+        // （indoption のビット0: DESC、ビット1: NULLS FIRST）
+        // 部分インデックスの条件式は indpred を pg_get_expr で復元する
+        // インデックスの種類は pg_am のアクセスメソッド名（btree / hash / gin / gist など）
         let sql = r#"
             SELECT
                 i.relname::text as index_name,
                 a.attname::text as column_name,
-                ix.indisunique as is_unique
+                ix.indisunique as is_unique,
+                (ix.indoption[(k.ord - 1)::int]::int & 1) = 1 as is_desc,
+                (ix.indoption[(k.ord - 1)::int]::int & 2) = 2 as is_nulls_first,
+                pg_get_expr(ix.indpred, ix.indrelid) as predicate,
+                am.amname::text as index_method
             FROM pg_class t
             JOIN pg_index ix ON t.oid = ix.indrelid
             JOIN pg_class i ON i.oid = ix.indexrelid
-            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey)
+            JOIN pg_am am ON am.oid = i.relam
+            JOIN LATERAL unnest(ix.indkey) WITH ORDINALITY AS k(attnum, ord) ON true
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
             JOIN pg_namespace n ON n.oid = t.relnamespace
             WHERE t.relkind = 'r'
                 AND t.relname = $1
-                AND n.nspname = 'public'
+                AND n.nspname = $2
                 AND NOT ix.indisprimary
-            ORDER BY i.relname, a.attnum
+            ORDER BY i.relname, k.ord
         "#;
 
-        let rows = sqlx::query(sql).bind(table_name).fetch_all(pool).await?;
+        let (schema, table_name) = self.split_table_name(table_name);
+        let rows = sqlx::query(sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
 
         // グループ化してインデックスごとにまとめる
-        let mut index_map: std::collections::HashMap<String, (Vec<String>, bool)> =
-            std::collections::HashMap::new();
+        let mut index_map: std::collections::BTreeMap<String, RawIndexInfo> =
+            std::collections::BTreeMap::new();
 
         for row in rows {
             let index_name: String = row.get(0);
             let column_name: String = row.get(1);
             let is_unique: bool = row.get(2);
+            let is_desc: bool = row.get(3);
+            let is_nulls_first: bool = row.get(4);
+            let predicate: Option<String> = row.get(5);
+            let index_method: String = row.get(6);
 
             let entry = index_map
-                .entry(index_name)
-                .or_insert_with(|| (Vec::new(), is_unique));
-            entry.0.push(column_name);
+                .entry(index_name.clone())
+                .or_insert_with(|| RawIndexInfo {
+                    name: index_name,
+                    columns: Vec::new(),
+                    unique: is_unique,
+                    descending: Vec::new(),
+                    nulls_first: Vec::new(),
+                    // pg_get_expr は式全体を括弧で囲んで返す
+                    predicate: predicate.as_deref().map(strip_outer_parens),
+                    prefix_lengths: Vec::new(),
+                    index_type: Some(index_method),
+                });
+            entry.columns.push(column_name);
+            entry.descending.push(is_desc);
+            entry.nulls_first.push(is_nulls_first);
         }
 
-        let indexes = index_map
-            .into_iter()
-            .map(|(name, (columns, unique))| RawIndexInfo {
-                name,
-                columns,
-                unique,
-            })
-            .collect();
-
-        Ok(indexes)
+        Ok(index_map.into_values().collect())
     }
 
     async fn get_constraints(
@@ -461,22 +806,32 @@ impl DatabaseIntrospector for PostgresIntrospector {
         use sqlx::Row;
 
         let mut constraints = Vec::new();
+        let (schema, table_name) = self.split_table_name(table_name);
 
         // PRIMARY KEY
         let pk_sql = r#"
-            SELECT a.attname::text
+            SELECT a.attname::text, array_position(i.indkey, a.attnum)::bigint AS position
             FROM pg_index i
             JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
             JOIN pg_class c ON c.oid = i.indrelid
             JOIN pg_namespace n ON n.oid = c.relnamespace
             WHERE i.indisprimary
                 AND c.relname = $1
-                AND n.nspname = 'public'
-            ORDER BY array_position(i.indkey, a.attnum)
+                AND n.nspname = $2
+            ORDER BY position
         "#;
 
-        let pk_rows = sqlx::query(pk_sql).bind(table_name).fetch_all(pool).await?;
-        let pk_columns: Vec<String> = pk_rows.iter().map(|row| row.get(0)).collect();
+        let pk_rows = sqlx::query(pk_sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+        let pk_columns = columns_in_key_order(
+            pk_rows
+                .iter()
+                .map(|row| (row.get::<i64, _>(1), row.get::<String, _>(0)))
+                .collect(),
+        );
 
         if !pk_columns.is_empty() {
             constraints.push(RawConstraintInfo::PrimaryKey {
@@ -486,102 +841,105 @@ impl DatabaseIntrospector for PostgresIntrospector {
 
         // FOREIGN KEY
         // 制約名でグループ化して、同一テーブルへの複数FKを正しく区別する
-        // pg_constraint から on_delete アクションも取得
+        // conkey / confkey を同じ位置で展開し、複合外部キーのカラムと参照先カラムの対応を保つ
+        // （information_schema.constraint_column_usage は参照先カラムの順序を持たない）
         let fk_sql = r#"
             SELECT
-                tc.constraint_name::text,
-                kcu.column_name::text,
-                ccu.table_name::text AS referenced_table,
-                ccu.column_name::text AS referenced_column,
-                CASE pgc.confdeltype
+                con.conname::text,
+                a.attname::text,
+                rc.relname::text AS referenced_table,
+                ra.attname::text AS referenced_column,
+                CASE con.confdeltype
                     WHEN 'a' THEN 'NO ACTION'
                     WHEN 'r' THEN 'RESTRICT'
                     WHEN 'c' THEN 'CASCADE'
                     WHEN 'n' THEN 'SET NULL'
                     WHEN 'd' THEN 'SET DEFAULT'
                     ELSE 'NO ACTION'
-                END::text AS on_delete
-            FROM information_schema.table_constraints tc
-            JOIN information_schema.key_column_usage kcu
-                ON tc.constraint_name = kcu.constraint_name
-                AND tc.table_schema = kcu.table_schema
-            JOIN information_schema.constraint_column_usage ccu
-                ON ccu.constraint_name = tc.constraint_name
-                AND ccu.table_schema = tc.table_schema
-            LEFT JOIN pg_constraint pgc
-                ON pgc.conname = tc.constraint_name::name
-                AND pgc.connamespace = (SELECT oid FROM pg_namespace WHERE nspname = 'public')
-            WHERE tc.constraint_type = 'FOREIGN KEY'
-                AND tc.table_name = $1
-                AND tc.table_schema = 'public'
-            ORDER BY tc.constraint_name, kcu.ordinal_position
+                END::text AS on_delete,
+                k.ord::bigint AS position,
+                CASE con.confupdtype
+                    WHEN 'a' THEN 'NO ACTION'
+                    WHEN 'r' THEN 'RESTRICT'
+                    WHEN 'c' THEN 'CASCADE'
+                    WHEN 'n' THEN 'SET NULL'
+                    WHEN 'd' THEN 'SET DEFAULT'
+                    ELSE 'NO ACTION'
+                END::text AS on_update,
+                rn.nspname::text AS referenced_schema
+            FROM pg_constraint con
+            JOIN pg_class c ON c.oid = con.conrelid
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_class rc ON rc.oid = con.confrelid
+            JOIN pg_namespace rn ON rn.oid = rc.relnamespace
+            CROSS JOIN LATERAL unnest(con.conkey, con.confkey)
+                WITH ORDINALITY AS k(attnum, refattnum, ord)
+            JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+            JOIN pg_attribute ra ON ra.attrelid = con.confrelid AND ra.attnum = k.refattnum
+            WHERE con.contype = 'f'
+                AND c.relname = $1
+                AND n.nspname = $2
+            ORDER BY con.conname, k.ord
         "#;
 
-        let fk_rows = sqlx::query(fk_sql).bind(table_name).fetch_all(pool).await?;
-
-        // 制約名でグループ化（複合外部キー対応）
-        // (referenced_table, columns, referenced_columns, on_delete)
-        let mut fk_map: std::collections::HashMap<
-            String,
-            (String, Vec<String>, Vec<String>, Option<String>),
-        > = std::collections::HashMap::new();
-
-        for row in &fk_rows {
-            let constraint_name: String = row.get(0);
-            let column: String = row.get(1);
-            let ref_table: String = row.get(2);
-            let ref_column: String = row.get(3);
-            let on_delete: Option<String> = row.get(4);
-
-            let entry = fk_map
-                .entry(constraint_name)
-                .or_insert_with(|| (ref_table.clone(), Vec::new(), Vec::new(), on_delete));
-            entry.1.push(column);
-            entry.2.push(ref_column);
-        }
+        let fk_rows = sqlx::query(fk_sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
 
-        for (_constraint_name, (ref_table, columns, ref_columns, on_delete)) in fk_map {
-            constraints.push(RawConstraintInfo::ForeignKey {
-                columns,
-                referenced_table: ref_table,
-                referenced_columns: ref_columns,
-                on_delete,
-            });
-        }
+        constraints.extend(group_foreign_key_rows(
+            fk_rows
+                .iter()
+                .map(|row| {
+                    (
+                        row.get::<String, _>(0),
+                        ForeignKeyColumnRow {
+                            position: row.get(5),
+                            column: row.get(1),
+                            referenced_table: qualify_pg_table_name(
+                                &row.get::<String, _>(7),
+                                row.get(2),
+                            ),
+                            referenced_column: row.get(3),
+                            on_delete: row.get(4),
+                            on_update: row.get(6),
+                        },
+                    )
+                })
+                .collect(),
+        ));
 
         // UNIQUE (インデックスとは別の制約として取得)
         // 制約名でグループ化して、複数のUNIQUE制約を正しく区別する
         let unique_sql = r#"
-            SELECT tc.constraint_name::text, kcu.column_name::text
+            SELECT
+                tc.constraint_name::text,
+                kcu.ordinal_position::bigint,
+                kcu.column_name::text
             FROM information_schema.table_constraints tc
             JOIN information_schema.key_column_usage kcu
                 ON tc.constraint_name = kcu.constraint_name
                 AND tc.table_schema = kcu.table_schema
             WHERE tc.constraint_type = 'UNIQUE'
                 AND tc.table_name = $1
-                AND tc.table_schema = 'public'
+                AND tc.table_schema = $2
             ORDER BY tc.constraint_name, kcu.ordinal_position
         "#;
 
         let unique_rows = sqlx::query(unique_sql)
             .bind(table_name)
+            .bind(schema)
             .fetch_all(pool)
             .await?;
 
         // 制約名でグループ化
-        let mut unique_map: std::collections::HashMap<String, Vec<String>> =
-            std::collections::HashMap::new();
-
-        for row in unique_rows {
-            let constraint_name: String = row.get(0);
-            let column: String = row.get(1);
-
-            unique_map.entry(constraint_name).or_default().push(column);
-        }
-
-        for (_constraint_name, columns) in unique_map {
-            constraints.push(RawConstraintInfo::Unique { columns });
-        }
+        constraints.extend(group_unique_rows(
+            unique_rows
+                .iter()
+                .map(|row| (row.get(0), row.get(1), row.get(2)))
+                .collect(),
+        ));
 
         // CHECK制約
         // pg_constraintからCHECK制約を取得（contype = 'c'）
@@ -599,13 +957,14 @@ impl DatabaseIntrospector for PostgresIntrospector {
             LEFT JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = u.attnum
             WHERE con.contype = 'c'
                 AND c.relname = $1
-                AND n.nspname = 'public'
+                AND n.nspname = $2
             GROUP BY con.conname, con.oid
             ORDER BY con.conname
         "#;
 
         let check_rows = sqlx::query(check_sql)
             .bind(table_name)
+            .bind(schema)
             .fetch_all(pool)
             .await?;
 
@@ -655,8 +1014,8 @@ impl DatabaseIntrospector for PostgresIntrospector {
         let rows = sqlx::query(sql).fetch_all(pool).await?;
 
         // ENUM名ごとにグループ化
-        let mut enum_map: std::collections::HashMap<String, Vec<(String, f64)>> =
-            std::collections::HashMap::new();
+        let mut enum_map: std::collections::BTreeMap<String, Vec<(String, f64)>> =
+            std::collections::BTreeMap::new();
 
         for row in rows {
             let name: String = row.get(0);
@@ -729,6 +1088,28 @@ impl DatabaseIntrospector for PostgresIntrospector {
 
         Ok(views)
     }
+
+    async fn get_table_comment(&self, pool: &AnyPool, table_name: &str) -> Result<Option<String>> {
+        use sqlx::Row;
+
+        let sql = r#"
+            SELECT pg_catalog.obj_description(t.oid, 'pg_class')
+            FROM pg_class t
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            WHERE t.relname = $1 AND n.nspname = $2
+        "#;
+
+        let (schema, table_name) = self.split_table_name(table_name);
+        let row = sqlx::query(sql)
+            .bind(table_name)
+            .bind(schema)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(row
+            .and_then(|row| row.get::<Option<String>, _>(0))
+            .filter(|c| !c.is_empty()))
+    }
 }
 
 // =============================================================================
@@ -742,7 +1123,7 @@ impl DatabaseIntrospector for MySqlIntrospector {
             SELECT table_name
             FROM information_schema.tables
             WHERE table_schema = DATABASE()
-                AND table_name != 'schema_migrations'
+                AND table_name NOT IN ('schema_migrations', 'schema_migration_rollbacks')
             ORDER BY table_name
         "#;
 
@@ -765,7 +1146,10 @@ impl DatabaseIntrospector for MySqlIntrospector {
                 numeric_precision,
                 numeric_scale,
                 extra,
-                column_type
+                column_type,
+                datetime_precision,
+                generation_expression,
+                column_comment
             FROM information_schema.columns
             WHERE table_name = ? AND table_schema = DATABASE()
             ORDER BY ordinal_position
@@ -806,6 +1190,22 @@ impl DatabaseIntrospector for MySqlIntrospector {
                 // column_type から UNSIGNED 修飾子を検出
                 let is_unsigned = is_mysql_unsigned(&column_type);
 
+                // 生成カラムは EXTRA が 'STORED GENERATED' / 'VIRTUAL GENERATED' となる
+                let generated_extra = extra
+                    .as_ref()
+                    .map(|e| e.to_uppercase())
+                    .filter(|e| e.contains("GENERATED") && !e.contains("DEFAULT_GENERATED"));
+                let generation_expression = generated_extra
+                    .as_ref()
+                    .and_then(|_| mysql_get_optional_string(row, 10))
+                    .filter(|e| !e.is_empty());
+                let generated_stored = generated_extra.is_some_and(|e| e.contains("STORED"));
+
+                // EXTRA は 'on update CURRENT_TIMESTAMP'（精度付きの場合は 'CURRENT_TIMESTAMP(3)'）となる
+                let on_update_current_timestamp = extra
+                    .as_ref()
+                    .is_some_and(|e| e.to_lowercase().contains("on update current_timestamp"));
+
                 RawColumnInfo {
                     name: mysql_get_string(row, 0),
                     data_type,
@@ -814,11 +1214,17 @@ impl DatabaseIntrospector for MySqlIntrospector {
                     char_max_length: row.get(4),
                     numeric_precision: row.get(5),
                     numeric_scale: row.get(6),
+                    datetime_precision: row.get(9),
                     udt_name: None,
                     auto_increment,
                     enum_values,
                     set_values,
                     is_unsigned,
+                    generation_expression,
+                    generated_stored,
+                    on_update_current_timestamp,
+                    // コメントがないカラムは空文字列になる
+                    comment: mysql_get_optional_string(row, 11).filter(|c| !c.is_empty()),
                 }
             })
             .collect();
@@ -829,11 +1235,16 @@ impl DatabaseIntrospector for MySqlIntrospector {
     async fn get_indexes(&self, pool: &AnyPool, table_name: &str) -> Result<Vec<RawIndexInfo>> {
         use sqlx::Row;
 
+        // collation は 'A'（昇順）/ 'D'（降順）/ NULL（ソートなし）
+        // sub_part はプレフィックスインデックスの文字数（カラム全体の場合は NULL）
         let sql = r#"
             SELECT
                 index_name,
                 column_name,
-                non_unique
+                non_unique,
+                collation,
+                CAST(sub_part AS SIGNED) AS sub_part,
+                index_type
             FROM information_schema.statistics
             WHERE table_name = ? AND table_schema = DATABASE()
                 AND index_name != 'PRIMARY'
@@ -842,30 +1253,37 @@ impl DatabaseIntrospector for MySqlIntrospector {
 
         let rows = sqlx::query(sql).bind(table_name).fetch_all(pool).await?;
 
-        let mut index_map: std::collections::HashMap<String, (Vec<String>, bool)> =
-            std::collections::HashMap::new();
+        let mut index_map: std::collections::BTreeMap<String, RawIndexInfo> =
+            std::collections::BTreeMap::new();
 
         for row in rows {
             let index_name = mysql_get_string(&row, 0);
             let column_name = mysql_get_string(&row, 1);
             let non_unique: i32 = row.get(2);
+            let collation = mysql_get_optional_string(&row, 3);
+            let sub_part: Option<i64> = row.get(4);
+            let index_type = mysql_get_string(&row, 5);
 
             let entry = index_map
-                .entry(index_name)
-                .or_insert_with(|| (Vec::new(), non_unique == 0));
-            entry.0.push(column_name);
+                .entry(index_name.clone())
+                .or_insert_with(|| RawIndexInfo {
+                    name: index_name,
+                    columns: Vec::new(),
+                    unique: non_unique == 0,
+                    descending: Vec::new(),
+                    nulls_first: Vec::new(),
+                    predicate: None,
+                    prefix_lengths: Vec::new(),
+                    index_type: Some(index_type),
+                });
+            entry.columns.push(column_name);
+            entry.descending.push(collation.as_deref() == Some("D"));
+            entry
+                .prefix_lengths
+                .push(sub_part.and_then(|length| u32::try_from(length).ok()));
         }
 
-        let indexes = index_map
-            .into_iter()
-            .map(|(name, (columns, unique))| RawIndexInfo {
-                name,
-                columns,
-                unique,
-            })
-            .collect();
-
-        Ok(indexes)
+        Ok(index_map.into_values().collect())
     }
 
     async fn get_constraints(
@@ -877,7 +1295,7 @@ impl DatabaseIntrospector for MySqlIntrospector {
 
         // PRIMARY KEY
         let pk_sql = r#"
-            SELECT column_name
+            SELECT column_name, CAST(seq_in_index AS SIGNED)
             FROM information_schema.statistics
             WHERE table_name = ? AND table_schema = DATABASE()
                 AND index_name = 'PRIMARY'
@@ -885,7 +1303,12 @@ impl DatabaseIntrospector for MySqlIntrospector {
         "#;
 
         let pk_rows = sqlx::query(pk_sql).bind(table_name).fetch_all(pool).await?;
-        let pk_columns: Vec<String> = pk_rows.iter().map(|row| mysql_get_string(row, 0)).collect();
+        let pk_columns = columns_in_key_order(
+            pk_rows
+                .iter()
+                .map(|row| (row.get::<i64, _>(1), mysql_get_string(row, 0)))
+                .collect(),
+        );
 
         if !pk_columns.is_empty() {
             constraints.push(RawConstraintInfo::PrimaryKey {
@@ -895,14 +1318,16 @@ impl DatabaseIntrospector for MySqlIntrospector {
 
         // FOREIGN KEY
         // 制約名でグループ化して、同一テーブルへの複数FKを正しく区別する
-        // REFERENTIAL_CONSTRAINTS テーブルから ON DELETE アクションも取得
+        // REFERENTIAL_CONSTRAINTS テーブルから ON DELETE / ON UPDATE アクションも取得
         let fk_sql = r#"
             SELECT
                 kcu.constraint_name,
                 kcu.column_name,
                 kcu.referenced_table_name,
                 kcu.referenced_column_name,
-                rc.delete_rule
+                rc.delete_rule,
+                CAST(kcu.ordinal_position AS SIGNED),
+                rc.update_rule
             FROM information_schema.key_column_usage kcu
             JOIN information_schema.referential_constraints rc
                 ON kcu.constraint_name = rc.constraint_name
@@ -915,46 +1340,33 @@ impl DatabaseIntrospector for MySqlIntrospector {
         let fk_rows = sqlx::query(fk_sql).bind(table_name).fetch_all(pool).await?;
 
         // 制約名でグループ化（複合外部キー対応）
-        // (referenced_table, columns, referenced_columns, on_delete)
-        let mut fk_map: std::collections::HashMap<
-            String,
-            (String, Vec<String>, Vec<String>, Option<String>),
-        > = std::collections::HashMap::new();
-
-        for row in &fk_rows {
-            let constraint_name = mysql_get_string(row, 0);
-            let column = mysql_get_string(row, 1);
-            let ref_table = mysql_get_string(row, 2);
-            let ref_column = mysql_get_string(row, 3);
-            let delete_rule = mysql_get_optional_string(row, 4);
-
-            let entry = fk_map.entry(constraint_name).or_insert_with(|| {
-                let on_delete = delete_rule.and_then(|rule| {
-                    if rule == "NO ACTION" || rule == "RESTRICT" {
-                        None
-                    } else {
-                        Some(rule)
-                    }
-                });
-                (ref_table.clone(), Vec::new(), Vec::new(), on_delete)
-            });
-            entry.1.push(column);
-            entry.2.push(ref_column);
-        }
-
-        for (_constraint_name, (ref_table, columns, ref_columns, on_delete)) in fk_map {
-            constraints.push(RawConstraintInfo::ForeignKey {
-                columns,
-                referenced_table: ref_table,
-                referenced_columns: ref_columns,
-                on_delete,
-            });
-        }
+        constraints.extend(group_foreign_key_rows(
+            fk_rows
+                .iter()
+                .map(|row| {
+                    let on_delete = mysql_get_optional_string(row, 4)
+                        .filter(|rule| rule != "NO ACTION" && rule != "RESTRICT");
+                    let on_update = mysql_get_optional_string(row, 6)
+                        .filter(|rule| rule != "NO ACTION" && rule != "RESTRICT");
+                    (
+                        mysql_get_string(row, 0),
+                        ForeignKeyColumnRow {
+                            position: row.get(5),
+                            column: mysql_get_string(row, 1),
+                            referenced_table: mysql_get_string(row, 2),
+                            referenced_column: mysql_get_string(row, 3),
+                            on_delete,
+                            on_update,
+                        },
+                    )
+                })
+                .collect(),
+        ));
 
         // UNIQUE
         // インデックス名でグループ化して、複数のUNIQUE制約を正しく区別する
         let unique_sql = r#"
-            SELECT index_name, column_name
+            SELECT index_name, column_name, CAST(seq_in_index AS SIGNED)
             FROM information_schema.statistics
             WHERE table_name = ? AND table_schema = DATABASE()
                 AND non_unique = 0
@@ -968,19 +1380,18 @@ impl DatabaseIntrospector for MySqlIntrospector {
             .await?;
 
         // インデックス名でグループ化
-        let mut unique_map: std::collections::HashMap<String, Vec<String>> =
-            std::collections::HashMap::new();
-
-        for row in unique_rows {
-            let index_name = mysql_get_string(&row, 0);
-            let column = mysql_get_string(&row, 1);
-
-            unique_map.entry(index_name).or_default().push(column);
-        }
-
-        for (_index_name, columns) in unique_map {
-            constraints.push(RawConstraintInfo::Unique { columns });
-        }
+        constraints.extend(group_unique_rows(
+            unique_rows
+                .iter()
+                .map(|row| {
+                    (
+                        mysql_get_string(row, 0),
+                        row.get(2),
+                        mysql_get_string(row, 1),
+                    )
+                })
+                .collect(),
+        ));
 
         // CHECK制約 (MySQL 8.0.16+)
         // information_schema.check_constraints と table_constraints を結合して取得
@@ -1113,6 +1524,24 @@ impl DatabaseIntrospector for MySqlIntrospector {
 
         Ok(views)
     }
+
+    async fn get_table_comment(&self, pool: &AnyPool, table_name: &str) -> Result<Option<String>> {
+        let sql = r#"
+            SELECT table_comment
+            FROM information_schema.tables
+            WHERE table_name = ? AND table_schema = DATABASE()
+        "#;
+
+        let row = sqlx::query(sql)
+            .bind(table_name)
+            .fetch_optional(pool)
+            .await?;
+
+        // コメントがないテーブルは空文字列になる
+        Ok(row
+            .and_then(|row| mysql_get_optional_string(&row, 0))
+            .filter(|c| !c.is_empty()))
+    }
 }
 
 // =============================================================================
@@ -1129,7 +1558,7 @@ impl DatabaseIntrospector for SqliteIntrospector {
             FROM sqlite_master
             WHERE type = 'table'
                 AND name NOT LIKE 'sqlite_%'
-                AND name != 'schema_migrations'
+                AND name NOT IN ('schema_migrations', 'schema_migration_rollbacks')
             ORDER BY name
         "#;
 
@@ -1143,13 +1572,36 @@ impl DatabaseIntrospector for SqliteIntrospector {
         use crate::adapters::sql_quote::quote_identifier_sqlite;
         use sqlx::Row;
 
+        // table_xinfo は table_info に hidden 列を加えたもの
+        // （hidden: 2 = VIRTUAL 生成カラム、3 = STORED 生成カラム）
         let quoted_name = quote_identifier_sqlite(table_name);
-        let sql = format!("PRAGMA table_info({})", quoted_name);
+        let sql = format!("PRAGMA table_xinfo({})", quoted_name);
         let rows = sqlx::query(&sql).fetch_all(pool).await?;
 
+        let has_generated = rows.iter().any(|row| row.get::<i32, _>(6) >= 2);
+        let create_sql: Option<String> = if has_generated {
+            sqlx::query("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(table_name)
+                .fetch_optional(pool)
+                .await?
+                .and_then(|row| row.get(0))
+        } else {
+            None
+        };
+
         let columns = rows
             .iter()
+            .filter(|row| row.get::<i32, _>(6) != 1)
             .map(|row| {
+                let hidden: i32 = row.get(6);
+                let name: String = row.get(1);
+                let generation_expression = if hidden >= 2 {
+                    create_sql
+                        .as_deref()
+                        .and_then(|sql| extract_sqlite_generated_expression(sql, &name))
+                } else {
+                    None
+                };
                 let not_null: i32 = row.get(3);
                 let is_pk: i32 = row.get(5);
                 let data_type: String = row.get(2);
@@ -1163,18 +1615,23 @@ impl DatabaseIntrospector for SqliteIntrospector {
                     None
                 };
                 RawColumnInfo {
-                    name: row.get(1),
+                    name,
                     data_type,
                     is_nullable: not_null == 0,
                     default_value: row.get(4),
                     char_max_length: None,
                     numeric_precision: None,
                     numeric_scale: None,
+                    datetime_precision: None,
                     udt_name: None,
                     auto_increment,
                     enum_values: None, // SQLiteはENUM型をサポートしない
                     set_values: None,
                     is_unsigned: false,
+                    generation_expression,
+                    generated_stored: hidden == 3,
+                    on_update_current_timestamp: false,
+                    comment: None,
                 }
             })
             .collect();
@@ -1195,23 +1652,51 @@ impl DatabaseIntrospector for SqliteIntrospector {
         for row in rows {
             let index_name: String = row.get(1);
             let is_unique: i32 = row.get(2);
+            let is_partial: i32 = row.get(4);
 
             // システムインデックスをスキップ
             if index_name.starts_with("sqlite_") {
                 continue;
             }
 
-            // インデックスのカラムを取得
+            // インデックスのカラムとソート順を取得
+            // index_xinfo は (seqno, cid, name, desc, coll, key) を返し、key=0 は補助カラム
             let quoted_index = quote_identifier_sqlite(&index_name);
-            let info_sql = format!("PRAGMA index_info({})", quoted_index);
+            let info_sql = format!("PRAGMA index_xinfo({})", quoted_index);
             let info_rows = sqlx::query(&info_sql).fetch_all(pool).await?;
 
-            let columns: Vec<String> = info_rows.iter().map(|r| r.get::<String, _>(2)).collect();
+            let key_rows: Vec<_> = info_rows
+                .iter()
+                .filter(|r| r.get::<i32, _>(5) == 1)
+                .collect();
+            let columns: Vec<String> = key_rows.iter().map(|r| r.get::<String, _>(2)).collect();
+            let descending: Vec<bool> = key_rows.iter().map(|r| r.get::<i32, _>(3) == 1).collect();
+
+            // 部分インデックスの条件式は CREATE INDEX 文の WHERE 以降から取得する
+            let predicate = if is_partial == 1 {
+                let create_sql: Option<String> = sqlx::query_scalar(
+                    "SELECT sql FROM sqlite_master WHERE type = 'index' AND name = ?",
+                )
+                .bind(&index_name)
+                .fetch_optional(pool)
+                .await?
+                .flatten();
+                create_sql
+                    .as_deref()
+                    .and_then(extract_sqlite_index_predicate)
+            } else {
+                None
+            };
 
             indexes.push(RawIndexInfo {
                 name: index_name,
                 columns,
                 unique: is_unique == 1,
+                descending,
+                nulls_first: Vec::new(),
+                predicate,
+                prefix_lengths: Vec::new(),
+                index_type: None,
             });
         }
 
@@ -1233,11 +1718,14 @@ impl DatabaseIntrospector for SqliteIntrospector {
         let table_info_sql = format!("PRAGMA table_info({})", quoted_table);
         let rows = sqlx::query(&table_info_sql).fetch_all(pool).await?;
 
-        let pk_columns: Vec<String> = rows
-            .iter()
-            .filter(|row| row.get::<i32, _>(5) > 0) // pk列が0より大きい
-            .map(|row| row.get::<String, _>(1))
-            .collect();
+        // pk列は主キー内の位置（1始まり、主キーでなければ0）。PRAGMA table_info は
+        // テーブル上のカラム順で返すため、主キー内の位置で並べ替える
+        let pk_columns = columns_in_key_order(
+            rows.iter()
+                .filter(|row| row.get::<i64, _>(5) > 0)
+                .map(|row| (row.get::<i64, _>(5), row.get::<String, _>(1)))
+                .collect(),
+        );
 
         if !pk_columns.is_empty() {
             constraints.push(RawConstraintInfo::PrimaryKey {
@@ -1250,39 +1738,54 @@ impl DatabaseIntrospector for SqliteIntrospector {
         let fk_rows = sqlx::query(&fk_sql).fetch_all(pool).await?;
 
         // PRAGMA foreign_key_list columns: id, seq, table, from, to, on_update, on_delete, match
-        let mut fk_map: std::collections::HashMap<
-            i32,
-            (String, Vec<String>, Vec<String>, Option<String>),
-        > = std::collections::HashMap::new();
-
-        for row in fk_rows {
-            let id: i32 = row.get(0);
-            let ref_table: String = row.get(2);
-            let from_col: String = row.get(3);
-            let to_col: String = row.get(4);
-            let on_delete: String = row.get(6);
-
-            let entry = fk_map.entry(id).or_insert_with(|| {
-                let od = if on_delete == "NO ACTION" {
-                    None
-                } else {
-                    Some(on_delete.clone())
-                };
-                (ref_table.clone(), Vec::new(), Vec::new(), od)
-            });
-
-            entry.1.push(from_col);
-            entry.2.push(to_col);
-        }
+        constraints.extend(group_foreign_key_rows(
+            fk_rows
+                .iter()
+                .map(|row| {
+                    let on_update: String = row.get(5);
+                    let on_delete: String = row.get(6);
+                    (
+                        row.get::<i64, _>(0),
+                        ForeignKeyColumnRow {
+                            position: row.get(1),
+                            column: row.get(3),
+                            referenced_table: row.get(2),
+                            referenced_column: row.get(4),
+                            on_delete: (on_delete != "NO ACTION").then_some(on_delete),
+                            on_update: (on_update != "NO ACTION").then_some(on_update),
+                        },
+                    )
+                })
+                .collect(),
+        ));
 
-        for (_id, (ref_table, from_cols, to_cols, on_delete)) in fk_map {
-            constraints.push(RawConstraintInfo::ForeignKey {
-                columns: from_cols,
-                referenced_table: ref_table,
-                referenced_columns: to_cols,
-                on_delete,
-            });
+        // UNIQUE
+        // テーブル定義の UNIQUE は自動生成インデックス（origin = 'u'）として現れるため、
+        // PRAGMA index_list から取り出す（CREATE INDEX のインデックスは origin = 'c'）
+        let index_list_sql = format!("PRAGMA index_list({})", quoted_table);
+        let index_rows = sqlx::query(&index_list_sql).fetch_all(pool).await?;
+
+        let mut unique_rows = Vec::new();
+        for index_row in &index_rows {
+            let origin: String = index_row.get(3);
+            if origin != "u" {
+                continue;
+            }
+            let index_name: String = index_row.get(1);
+            let info_sql = format!(
+                "PRAGMA index_info({})",
+                quote_identifier_sqlite(&index_name)
+            );
+            // index_info は (seqno, cid, name) を返す
+            for info_row in sqlx::query(&info_sql).fetch_all(pool).await? {
+                unique_rows.push((
+                    index_name.clone(),
+                    info_row.get::<i64, _>(0),
+                    info_row.get::<String, _>(2),
+                ));
+            }
         }
+        constraints.extend(group_unique_rows(unique_rows));
 
         // CHECK制約
         // sqlite_masterからCREATE TABLE文を取得してCHECK制約をパースする
@@ -1382,37 +1885,164 @@ fn extract_pg_check_expression(raw: &str) -> String {
     }
 }
 
-/// 式全体が一対の括弧で囲まれている場合のみ外側の括弧を除去する
+/// SQLite の CREATE INDEX 文から部分インデックスの条件式（WHERE 以降）を抽出する
 ///
-/// `(balance >= 0)` → `balance >= 0` (除去)
-/// `(val >= 0) AND (val <= 100)` → そのまま (除去しない: 先頭の `(` と末尾の `)` が対応していない)
-fn strip_outer_parens(expr: &str) -> String {
-    let trimmed = expr.trim();
-    if !trimmed.starts_with('(') || !trimmed.ends_with(')') {
-        return trimmed.to_string();
+/// 括弧・文字列リテラル・クォート識別子の外側にある最初の WHERE を条件式の開始とみなす。
+/// 例: `CREATE UNIQUE INDEX idx ON orders (customer_id) WHERE deleted_at IS NULL`
+fn extract_sqlite_index_predicate(create_sql: &str) -> Option<String> {
+    let bytes = create_sql.as_bytes();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(close) => {
+                if b == close {
+                    quote = None;
+                }
+            }
+            None => match b {
+                b'\'' | b'"' | b'`' => quote = Some(b),
+                b'[' => quote = Some(b']'),
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ if depth == 0
+                    && bytes[i..]
+                        .get(..5)
+                        .is_some_and(|word| word.eq_ignore_ascii_case(b"WHERE")) =>
+                {
+                    let is_ident =
+                        |c: Option<&u8>| c.is_some_and(|c| *c == b'_' || c.is_ascii_alphanumeric());
+                    let prev = i.checked_sub(1).and_then(|p| bytes.get(p));
+                    if !is_ident(prev) && !is_ident(bytes.get(i + 5)) {
+                        let predicate = create_sql[i + 5..].trim().trim_end_matches(';').trim();
+                        return (!predicate.is_empty()).then(|| predicate.to_string());
+                    }
+                }
+                _ => {}
+            },
+        }
+        i += 1;
     }
 
-    // 先頭の '(' に対応する ')' が末尾であることを確認
-    let mut depth = 0;
-    for (i, ch) in trimmed.char_indices() {
-        match ch {
-            '(' => depth += 1,
-            ')' => {
+    None
+}
+
+/// SQLite の CREATE TABLE 文から生成カラムの式を抽出する
+///
+/// 対象カラムの定義内で、括弧・文字列リテラル・クォート識別子の外側にある
+/// `AS (...)` の括弧内を式とみなす（`GENERATED ALWAYS` は省略可能）。
+/// 例: `CREATE TABLE t (price INTEGER, total INTEGER GENERATED ALWAYS AS (price * 2) STORED)`
+fn extract_sqlite_generated_expression(create_sql: &str, column_name: &str) -> Option<String> {
+    split_sqlite_table_elements(create_sql)
+        .into_iter()
+        .find_map(|definition| {
+            let (name, rest) = split_sqlite_column_name(definition)?;
+            if !name.eq_ignore_ascii_case(column_name) {
+                return None;
+            }
+            let bytes = rest.as_bytes();
+            let mut depth = 0i32;
+            let mut quote: Option<u8> = None;
+            let is_ident =
+                |c: Option<&u8>| c.is_some_and(|c| *c == b'_' || c.is_ascii_alphanumeric());
+            for i in 0..bytes.len() {
+                let b = bytes[i];
+                if let Some(close) = quote {
+                    if b == close {
+                        quote = None;
+                    }
+                    continue;
+                }
+                match b {
+                    b'\'' | b'"' | b'`' => quote = Some(b),
+                    b'[' => quote = Some(b']'),
+                    b'(' => depth += 1,
+                    b')' => depth -= 1,
+                    _ if depth == 0
+                        && bytes[i..]
+                            .get(..2)
+                            .is_some_and(|word| word.eq_ignore_ascii_case(b"AS"))
+                        && !is_ident(i.checked_sub(1).and_then(|p| bytes.get(p)))
+                        && !is_ident(bytes.get(i + 2)) =>
+                    {
+                        let after = rest[i + 2..].trim_start();
+                        let inner = after.strip_prefix('(')?;
+                        return split_sqlite_table_elements(&format!("({})", inner))
+                            .first()
+                            .map(|expr| expr.trim().to_string());
+                    }
+                    _ => {}
+                }
+            }
+            None
+        })
+}
+
+/// CREATE TABLE 文の最初の括弧内を、トップレベルのカンマで要素に分割する
+fn split_sqlite_table_elements(create_sql: &str) -> Vec<&str> {
+    let bytes = create_sql.as_bytes();
+    let mut elements = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<u8> = None;
+    let mut start = None;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if let Some(close) = quote {
+            if b == close {
+                quote = None;
+            }
+            continue;
+        }
+        match b {
+            b'\'' | b'"' | b'`' => quote = Some(b),
+            b'[' => quote = Some(b']'),
+            b'(' => {
+                depth += 1;
+                if depth == 1 && start.is_none() {
+                    start = Some(i + 1);
+                }
+            }
+            b')' => {
                 depth -= 1;
                 if depth == 0 {
-                    // 先頭の '(' の対応が末尾の ')' と一致するか
-                    return if i == trimmed.len() - 1 {
-                        trimmed[1..i].trim().to_string()
-                    } else {
-                        trimmed.to_string()
-                    };
+                    if let Some(s) = start {
+                        elements.push(&create_sql[s..i]);
+                    }
+                    break;
+                }
+            }
+            b',' if depth == 1 => {
+                if let Some(s) = start {
+                    elements.push(&create_sql[s..i]);
                 }
+                start = Some(i + 1);
             }
             _ => {}
         }
     }
 
-    trimmed.to_string()
+    elements
+}
+
+/// カラム定義を、クォートを外したカラム名と残りの定義に分割する
+fn split_sqlite_column_name(definition: &str) -> Option<(&str, &str)> {
+    let definition = definition.trim_start();
+    let close = match definition.as_bytes().first()? {
+        b'"' => '"',
+        b'`' => '`',
+        b'[' => ']',
+        _ => {
+            let end = definition
+                .find(char::is_whitespace)
+                .unwrap_or(definition.len());
+            return Some((&definition[..end], &definition[end..]));
+        }
+    };
+    let end = definition[1..].find(close)? + 1;
+    Some((&definition[1..end], &definition[end + 1..]))
 }
 
 /// CREATE VIEW 文からビュー定義（AS以降）を抽出する
@@ -1596,7 +2226,11 @@ fn parse_sqlite_check_constraints(
 /// 文字列リテラル（'...'）内の単語は無視し、
 /// PRAGMA table_info から取得した実カラム名一覧と照合して
 /// 式中に出現するカラム名のみを返す。
-fn extract_columns_from_sqlite_check(expression: &str, table_columns: &[String]) -> Vec<String> {
+/// デフォルト値の式に含まれるカラム参照の検証（schema_validator）でも使用する。
+pub(crate) fn extract_columns_from_sqlite_check(
+    expression: &str,
+    table_columns: &[String],
+) -> Vec<String> {
     // 文字列リテラルを除去してからパース
     let stripped = strip_string_literals(expression);
 
@@ -1673,6 +2307,186 @@ mod tests {
         let _introspector = create_introspector(Dialect::SQLite);
     }
 
+    // =========================================================================
+    // 権限不足エラー判定テスト
+    // =========================================================================
+
+    /// テスト用のデータベースエラー
+    #[derive(Debug)]
+    struct MockDatabaseError {
+        code: &'static str,
+    }
+
+    impl std::fmt::Display for MockDatabaseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "mock database error {}", self.code)
+        }
+    }
+
+    impl std::error::Error for MockDatabaseError {}
+
+    impl sqlx::error::DatabaseError for MockDatabaseError {
+        fn message(&self) -> &str {
+            "mock database error"
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(std::borrow::Cow::Borrowed(self.code))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn mock_database_error(code: &'static str) -> anyhow::Error {
+        anyhow::Error::from(sqlx::Error::Database(Box::new(MockDatabaseError { code })))
+    }
+
+    #[test]
+    fn test_is_permission_denied_error_postgres() {
+        let error = mock_database_error("42501").context("Failed to get columns for 'secret'");
+        assert!(is_permission_denied_error(Dialect::PostgreSQL, &error));
+
+        let error = mock_database_error("42P01");
+        assert!(!is_permission_denied_error(Dialect::PostgreSQL, &error));
+    }
+
+    #[test]
+    fn test_is_permission_denied_error_ignores_non_database_errors() {
+        let error = anyhow::Error::from(sqlx::Error::PoolTimedOut);
+        assert!(!is_permission_denied_error(Dialect::PostgreSQL, &error));
+
+        let error = anyhow::anyhow!("permission denied for table secret");
+        assert!(!is_permission_denied_error(Dialect::PostgreSQL, &error));
+    }
+
+    #[test]
+    fn test_is_permission_denied_error_sqlite_auth() {
+        assert!(is_permission_denied_error(
+            Dialect::SQLite,
+            &mock_database_error("23")
+        ));
+        // SQLITE_AUTH_USER (279) は拡張エラーコード
+        assert!(is_permission_denied_error(
+            Dialect::SQLite,
+            &mock_database_error("279")
+        ));
+        assert!(!is_permission_denied_error(
+            Dialect::SQLite,
+            &mock_database_error("1")
+        ));
+    }
+
+    #[test]
+    fn test_is_permission_denied_code_mysql_uses_error_number() {
+        assert!(is_permission_denied_code(
+            Dialect::MySQL,
+            Some("42000"),
+            Some(1142)
+        ));
+        assert!(is_permission_denied_code(
+            Dialect::MySQL,
+            Some("42000"),
+            Some(1044)
+        ));
+        // 構文エラー (1064) も SQLSTATE 42000 だが権限不足ではない
+        assert!(!is_permission_denied_code(
+            Dialect::MySQL,
+            Some("42000"),
+            Some(1064)
+        ));
+        assert!(!is_permission_denied_code(
+            Dialect::MySQL,
+            Some("42000"),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_is_permission_denied_code_is_dialect_specific() {
+        assert!(!is_permission_denied_code(
+            Dialect::MySQL,
+            Some("42501"),
+            None
+        ));
+        assert!(!is_permission_denied_code(
+            Dialect::SQLite,
+            Some("42501"),
+            None
+        ));
+        assert!(!is_permission_denied_code(
+            Dialect::PostgreSQL,
+            Some("23"),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_resolve_pg_user_defined_type() {
+        let some = |s: &str| Some(s.to_string());
+
+        // 拡張型（citext）は public スキーマなら修飾しない
+        assert_eq!(
+            resolve_pg_user_defined_type(
+                "USER-DEFINED".to_string(),
+                some("citext"),
+                some("public"),
+                None,
+                None
+            ),
+            ("USER-DEFINED".to_string(), some("citext"))
+        );
+
+        // DOMAIN は基底型ではなくDOMAIN名として扱う
+        assert_eq!(
+            resolve_pg_user_defined_type(
+                "text".to_string(),
+                some("text"),
+                some("pg_catalog"),
+                some("email_address"),
+                some("public")
+            ),
+            ("USER-DEFINED".to_string(), some("email_address"))
+        );
+
+        // public 以外のスキーマの複合型はスキーマ名で修飾する
+        assert_eq!(
+            resolve_pg_user_defined_type(
+                "USER-DEFINED".to_string(),
+                some("address"),
+                some("billing"),
+                None,
+                None
+            ),
+            ("USER-DEFINED".to_string(), some("billing.address"))
+        );
+
+        // 組み込み型はそのまま
+        assert_eq!(
+            resolve_pg_user_defined_type(
+                "integer".to_string(),
+                some("int4"),
+                some("pg_catalog"),
+                None,
+                None
+            ),
+            ("integer".to_string(), some("int4"))
+        );
+    }
+
     // =========================================================================
     // RawColumnInfo 構造体テスト
     // =========================================================================
@@ -1687,11 +2501,16 @@ mod tests {
             char_max_length: None,
             numeric_precision: None,
             numeric_scale: None,
+            datetime_precision: None,
             udt_name: None,
             auto_increment: None,
             enum_values: None,
             set_values: None,
             is_unsigned: false,
+            generation_expression: None,
+            generated_stored: false,
+            on_update_current_timestamp: false,
+            comment: None,
         };
         assert!(format!("{:?}", column).contains("id"));
     }
@@ -1706,11 +2525,16 @@ mod tests {
             char_max_length: Some(255),
             numeric_precision: None,
             numeric_scale: None,
+            datetime_precision: None,
             udt_name: None,
             auto_increment: None,
             enum_values: None,
             set_values: None,
             is_unsigned: false,
+            generation_expression: None,
+            generated_stored: false,
+            on_update_current_timestamp: false,
+            comment: None,
         };
         let cloned = column.clone();
         assert_eq!(cloned.name, "email");
@@ -1727,6 +2551,11 @@ mod tests {
             name: "idx_email".to_string(),
             columns: vec!["email".to_string()],
             unique: true,
+            descending: vec![],
+            nulls_first: vec![],
+            predicate: None,
+            prefix_lengths: vec![],
+            index_type: None,
         };
         assert!(format!("{:?}", index).contains("idx_email"));
     }
@@ -1737,6 +2566,11 @@ mod tests {
             name: "idx_composite".to_string(),
             columns: vec!["col1".to_string(), "col2".to_string()],
             unique: false,
+            descending: vec![],
+            nulls_first: vec![],
+            predicate: None,
+            prefix_lengths: vec![],
+            index_type: None,
         };
         let cloned = index.clone();
         assert_eq!(cloned.columns.len(), 2);
@@ -1761,6 +2595,7 @@ mod tests {
             referenced_table: "users".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete: None,
+            on_update: None,
         };
         assert!(format!("{:?}", fk).contains("ForeignKey"));
     }
@@ -1835,6 +2670,43 @@ mod tests {
     // extract_view_definition_from_create_sql テスト
     // =========================================================================
 
+    #[test]
+    fn test_extract_sqlite_index_predicate() {
+        assert_eq!(
+            extract_sqlite_index_predicate(
+                "CREATE UNIQUE INDEX idx_orders ON orders (customer_id) WHERE deleted_at IS NULL"
+            ),
+            Some("deleted_at IS NULL".to_string())
+        );
+        // 括弧・文字列リテラル・クォート識別子内の WHERE は無視する
+        assert_eq!(
+            extract_sqlite_index_predicate(
+                "CREATE INDEX \"where idx\" ON \"t\" (\"where\")\nwhere status = 'where'"
+            ),
+            Some("status = 'where'".to_string())
+        );
+        assert_eq!(
+            extract_sqlite_index_predicate("CREATE INDEX idx_nowhere ON t (nowhere)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_sqlite_generated_expression() {
+        let sql = "CREATE TABLE \"orders\" (\"price\" INTEGER NOT NULL, \"total\" INTEGER GENERATED ALWAYS AS (price * (1 + tax)) STORED, label TEXT AS ('a, b' || price) VIRTUAL, CHECK (price > 0))";
+        assert_eq!(
+            extract_sqlite_generated_expression(sql, "total"),
+            Some("price * (1 + tax)".to_string())
+        );
+        // GENERATED ALWAYS の省略形と、式内のカンマ
+        assert_eq!(
+            extract_sqlite_generated_expression(sql, "label"),
+            Some("'a, b' || price".to_string())
+        );
+        assert_eq!(extract_sqlite_generated_expression(sql, "price"), None);
+        assert_eq!(extract_sqlite_generated_expression(sql, "missing"), None);
+    }
+
     #[test]
     fn test_extract_view_definition_simple() {
         let sql = "CREATE VIEW active_users AS SELECT * FROM users WHERE active = 1";
@@ -2457,4 +3329,170 @@ mod tests {
         assert!(!super::is_mysql_unsigned("int(11)"));
         assert!(!super::is_mysql_unsigned("varchar(255)"));
     }
+
+    // =========================================================================
+    // キーのカラム順テスト
+    // =========================================================================
+
+    fn fk_row(position: i64, column: &str, referenced_column: &str) -> ForeignKeyColumnRow {
+        ForeignKeyColumnRow {
+            position,
+            column: column.to_string(),
+            referenced_table: "parents".to_string(),
+            referenced_column: referenced_column.to_string(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    #[test]
+    fn test_columns_in_key_order_uses_key_position() {
+        // SQLite の PRAGMA table_info はテーブル上のカラム順（a_col, z_col）で返す
+        let rows = vec![(2, "a_col".to_string()), (1, "z_col".to_string())];
+
+        assert_eq!(columns_in_key_order(rows), vec!["z_col", "a_col"]);
+    }
+
+    #[test]
+    fn test_group_unique_rows_keeps_position_order() {
+        let rows = vec![
+            ("uq_b".to_string(), 1, "x".to_string()),
+            ("uq_a".to_string(), 2, "a_col".to_string()),
+            ("uq_a".to_string(), 1, "z_col".to_string()),
+        ];
+
+        let constraints = group_unique_rows(rows);
+
+        assert_eq!(
+            constraints,
+            vec![
+                RawConstraintInfo::Unique {
+                    columns: vec!["z_col".to_string(), "a_col".to_string()],
+                },
+                RawConstraintInfo::Unique {
+                    columns: vec!["x".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_foreign_key_rows_keeps_column_pairs() {
+        // 制約ごと・位置ごとにばらばらの順で届いても、カラムと参照先カラムの対応を保つ
+        let rows = vec![
+            (1, fk_row(1, "other_id", "id")),
+            (0, fk_row(2, "parent_a", "a_col")),
+            (0, fk_row(1, "parent_z", "z_col")),
+        ];
+
+        let constraints = group_foreign_key_rows(rows);
+
+        assert_eq!(
+            constraints,
+            vec![
+                RawConstraintInfo::ForeignKey {
+                    columns: vec!["parent_z".to_string(), "parent_a".to_string()],
+                    referenced_table: "parents".to_string(),
+                    referenced_columns: vec!["z_col".to_string(), "a_col".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                },
+                RawConstraintInfo::ForeignKey {
+                    columns: vec!["other_id".to_string()],
+                    referenced_table: "parents".to_string(),
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: None,
+                    on_update: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_introspection_preserves_column_and_key_order() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            temp_dir.path().join("order.db").display()
+        );
+        let pool = AnyPool::connect(&url).await.unwrap();
+        for sql in [
+            "CREATE TABLE parents (a_col INTEGER NOT NULL, z_col INTEGER NOT NULL, PRIMARY KEY (z_col, a_col))",
+            "CREATE TABLE children (id INTEGER PRIMARY KEY, parent_a INTEGER NOT NULL, parent_z INTEGER NOT NULL, \
+             FOREIGN KEY (parent_z, parent_a) REFERENCES parents (z_col, a_col))",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        let introspector = SqliteIntrospector;
+
+        let columns = introspector.get_columns(&pool, "parents").await.unwrap();
+        let column_names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(column_names, vec!["a_col", "z_col"]);
+
+        let constraints = introspector
+            .get_constraints(&pool, "parents")
+            .await
+            .unwrap();
+        assert!(constraints.contains(&RawConstraintInfo::PrimaryKey {
+            columns: vec!["z_col".to_string(), "a_col".to_string()],
+        }));
+
+        let constraints = introspector
+            .get_constraints(&pool, "children")
+            .await
+            .unwrap();
+        assert!(constraints.contains(&RawConstraintInfo::ForeignKey {
+            columns: vec!["parent_z".to_string(), "parent_a".to_string()],
+            referenced_table: "parents".to_string(),
+            referenced_columns: vec!["z_col".to_string(), "a_col".to_string()],
+            on_delete: None,
+            on_update: None,
+        }));
+
+        pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_introspection_reads_foreign_key_actions() {
+        sqlx::any::install_default_drivers();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            temp_dir.path().join("fk_actions.db").display()
+        );
+        let pool = AnyPool::connect(&url).await.unwrap();
+        // 主キーではないユニークキーを参照する複合外部キー
+        for sql in [
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY, tenant_id INTEGER NOT NULL, code TEXT NOT NULL, \
+             UNIQUE (tenant_id, code))",
+            "CREATE TABLE invoices (id INTEGER PRIMARY KEY, tenant_id INTEGER, account_code TEXT, \
+             FOREIGN KEY (tenant_id, account_code) REFERENCES accounts (tenant_id, code) \
+             ON UPDATE CASCADE ON DELETE SET NULL)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let constraints = SqliteIntrospector
+            .get_constraints(&pool, "accounts")
+            .await
+            .unwrap();
+        assert!(constraints.contains(&RawConstraintInfo::Unique {
+            columns: vec!["tenant_id".to_string(), "code".to_string()],
+        }));
+
+        let constraints = SqliteIntrospector
+            .get_constraints(&pool, "invoices")
+            .await
+            .unwrap();
+        assert!(constraints.contains(&RawConstraintInfo::ForeignKey {
+            columns: vec!["tenant_id".to_string(), "account_code".to_string()],
+            referenced_table: "accounts".to_string(),
+            referenced_columns: vec!["tenant_id".to_string(), "code".to_string()],
+            on_delete: Some("SET NULL".to_string()),
+            on_update: Some("CASCADE".to_string()),
+        }));
+
+        pool.close().await;
+    }
 }
@@ -3,9 +3,17 @@
 // マイグレーション履歴テーブルの管理とトランザクション制御を担当するサービス。
 // データベース固有のSQL構文を抽象化し、マイグレーション適用とロールバックをサポートします。
 
+pub mod session_timeouts;
+pub mod statement_retry;
+
+use self::session_timeouts::SessionTimeouts;
+use self::statement_retry::{RetryPolicy, StatementExecutor, StatementFailure};
 use crate::core::config::Dialect;
 use crate::core::error::DatabaseError;
-use crate::core::migration::{Migration, MigrationRecord};
+use crate::core::migration::{
+    truncate_sql_for_storage, AppliedSql, Migration, MigrationAudit, MigrationRecord,
+    MigrationRollbackAudit,
+};
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use sqlx::{any::AnyQueryResult, AnyPool, Row};
@@ -25,6 +33,115 @@ static ALLOWED_TABLE_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
 /// デフォルトのマイグレーションテーブル名
 pub const DEFAULT_MIGRATION_TABLE: &str = "schema_migrations";
 
+/// 適用SQLを保存するカラム（`store_applied_sql: true` の場合のみ値を保存する）
+pub const APPLIED_SQL_COLUMNS: [&str; 2] = ["up_sql", "down_sql"];
+
+/// 実行したステートメントのチェックサム一覧を保存するカラム
+///
+/// 実行順のチェックサムを改行区切りで保存します。
+pub const STATEMENT_CHECKSUMS_COLUMN: &str = "statement_checksums";
+
+/// 適用時の実行時間と適用したユーザーを保存するカラム
+pub const AUDIT_COLUMNS: [&str; 2] = ["execution_time_ms", "applied_by"];
+
+/// ロールバックの記録を残すテーブル
+///
+/// ロールバックで `schema_migrations` から削除した記録を、ロールバックした日時とユーザーとともに残します。
+pub const ROLLBACK_AUDIT_TABLE: &str = "schema_migration_rollbacks";
+
+/// マイグレーション履歴テーブルのレイアウト
+///
+/// 各バージョンのstrataが作成する `schema_migrations` のカラム構成です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationTableLayout {
+    /// レイアウトのバージョン
+    pub version: u32,
+    /// カラム（作成順）
+    pub columns: &'static [&'static str],
+}
+
+/// 既知の履歴テーブルレイアウト（古い順、最後が現在のレイアウト）
+pub const MIGRATION_TABLE_LAYOUTS: [MigrationTableLayout; 5] = [
+    // description カラム追加前
+    MigrationTableLayout {
+        version: 1,
+        columns: &["version", "applied_at", "checksum"],
+    },
+    // 適用SQLの保存用カラム追加前
+    MigrationTableLayout {
+        version: 2,
+        columns: &["version", "description", "applied_at", "checksum"],
+    },
+    // ステートメントのチェックサム保存用カラム追加前
+    MigrationTableLayout {
+        version: 3,
+        columns: &[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            APPLIED_SQL_COLUMNS[0],
+            APPLIED_SQL_COLUMNS[1],
+        ],
+    },
+    // 実行時間・適用ユーザーの保存用カラム追加前
+    MigrationTableLayout {
+        version: 4,
+        columns: &[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            APPLIED_SQL_COLUMNS[0],
+            APPLIED_SQL_COLUMNS[1],
+            STATEMENT_CHECKSUMS_COLUMN,
+        ],
+    },
+    MigrationTableLayout {
+        version: 5,
+        columns: &[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            APPLIED_SQL_COLUMNS[0],
+            APPLIED_SQL_COLUMNS[1],
+            STATEMENT_CHECKSUMS_COLUMN,
+            AUDIT_COLUMNS[0],
+            AUDIT_COLUMNS[1],
+        ],
+    },
+];
+
+/// 現在の履歴テーブルレイアウト
+pub const CURRENT_MIGRATION_TABLE_LAYOUT: MigrationTableLayout =
+    MIGRATION_TABLE_LAYOUTS[MIGRATION_TABLE_LAYOUTS.len() - 1];
+
+/// 既存テーブルのカラムから履歴テーブルのレイアウトを判定
+///
+/// カラム名は大文字小文字を区別せず、順序も問いません。
+/// 各レイアウトのカラムを全て含む最新のレイアウトを返します。更新の途中で中断したテーブルや、
+/// 以前のバージョンのstrataが必要に応じて追加したカラムを持つテーブルは、
+/// 新しいレイアウトのカラムを一部だけ含むことがあります。
+/// 現在のレイアウトにないカラムがある場合や、どのレイアウトのカラムも揃っていない場合はNoneを返します。
+pub fn detect_migration_table_layout(columns: &[String]) -> Option<MigrationTableLayout> {
+    let found: Vec<String> = columns.iter().map(|column| column.to_lowercase()).collect();
+    if !found.iter().all(|column| {
+        CURRENT_MIGRATION_TABLE_LAYOUT
+            .columns
+            .contains(&column.as_str())
+    }) {
+        return None;
+    }
+
+    MIGRATION_TABLE_LAYOUTS.into_iter().rev().find(|layout| {
+        layout
+            .columns
+            .iter()
+            .all(|column| found.iter().any(|c| c == column))
+    })
+}
+
 /// データベース方言に応じたプレースホルダ文字列を返す
 ///
 /// PostgreSQLは `$1`, `$2`, ... 形式、MySQL/SQLiteは `?` 形式を使用する。
@@ -39,6 +156,17 @@ fn placeholder(dialect: Dialect, n: usize) -> String {
     }
 }
 
+/// 接続中のデータベースユーザーを返すSQL式
+///
+/// SQLiteにはユーザーの概念がないためNULLを返す。
+fn current_user_sql(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::PostgreSQL => "current_user",
+        Dialect::MySQL => "CURRENT_USER()",
+        Dialect::SQLite => "NULL",
+    }
+}
+
 /// データベースマイグレーターサービス
 ///
 /// マイグレーション履歴の管理とトランザクション制御を提供します。
@@ -96,21 +224,36 @@ impl DatabaseMigratorService {
     version VARCHAR(255) PRIMARY KEY,
     description TEXT NOT NULL,
     applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
-    checksum VARCHAR(64) NOT NULL
+    checksum VARCHAR(64) NOT NULL,
+    up_sql TEXT NULL,
+    down_sql TEXT NULL,
+    statement_checksums TEXT NULL,
+    execution_time_ms BIGINT NULL,
+    applied_by TEXT NULL
 )"#
             .to_string(),
             Dialect::MySQL => r#"CREATE TABLE IF NOT EXISTS schema_migrations (
     version VARCHAR(255) PRIMARY KEY,
     description VARCHAR(1024) NOT NULL,
     applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    checksum VARCHAR(64) NOT NULL
+    checksum VARCHAR(64) NOT NULL,
+    up_sql MEDIUMTEXT NULL,
+    down_sql MEDIUMTEXT NULL,
+    statement_checksums MEDIUMTEXT NULL,
+    execution_time_ms BIGINT NULL,
+    applied_by VARCHAR(255) NULL
 )"#
             .to_string(),
             Dialect::SQLite => r#"CREATE TABLE IF NOT EXISTS schema_migrations (
     version TEXT PRIMARY KEY,
     description TEXT NOT NULL,
     applied_at TEXT NOT NULL DEFAULT (datetime('now')),
-    checksum TEXT NOT NULL
+    checksum TEXT NOT NULL,
+    up_sql TEXT NULL,
+    down_sql TEXT NULL,
+    statement_checksums TEXT NULL,
+    execution_time_ms INTEGER NULL,
+    applied_by TEXT NULL
 )"#
             .to_string(),
         }
@@ -142,9 +285,157 @@ impl DatabaseMigratorService {
                 sql: Some(sql),
             })?;
 
+        // 古いバージョンのstrataが作成したテーブルは現在のレイアウトに更新する
+        self.upgrade_migration_table(pool, dialect).await?;
+
+        let sql = self.generate_create_rollback_audit_table_sql(dialect);
+        sqlx::query(&sql)
+            .execute(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to create rollback audit table: {}", e),
+                sql: Some(sql),
+            })?;
+
         Ok(())
     }
 
+    /// ロールバックの記録テーブル作成SQLを生成
+    ///
+    /// 同じバージョンを何度もロールバックできるため、連番を主キーにする。
+    pub fn generate_create_rollback_audit_table_sql(&self, dialect: Dialect) -> String {
+        let columns = match dialect {
+            Dialect::PostgreSQL => {
+                r#"    id BIGSERIAL PRIMARY KEY,
+    version VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    checksum VARCHAR(64) NOT NULL,
+    applied_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    rolled_back_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    rolled_back_by TEXT NULL"#
+            }
+            Dialect::MySQL => {
+                r#"    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    version VARCHAR(255) NOT NULL,
+    description VARCHAR(1024) NOT NULL,
+    checksum VARCHAR(64) NOT NULL,
+    applied_at TIMESTAMP NOT NULL,
+    rolled_back_at TIMESTAMP NOT NULL,
+    rolled_back_by VARCHAR(255) NULL"#
+            }
+            Dialect::SQLite => {
+                r#"    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    version TEXT NOT NULL,
+    description TEXT NOT NULL,
+    checksum TEXT NOT NULL,
+    applied_at TEXT NOT NULL,
+    rolled_back_at TEXT NOT NULL,
+    rolled_back_by TEXT NULL"#
+            }
+        };
+        format!("CREATE TABLE IF NOT EXISTS {ROLLBACK_AUDIT_TABLE} (\n{columns}\n)")
+    }
+
+    /// マイグレーション履歴テーブルを削除するSQLを生成
+    ///
+    /// `strata reset` で使用します。テーブルは次の apply で現在のレイアウトで再作成されます。
+    pub fn generate_drop_migration_table_sql(&self) -> String {
+        format!("DROP TABLE IF EXISTS {}", DEFAULT_MIGRATION_TABLE)
+    }
+
+    /// 履歴テーブルのレイアウト更新で追加するカラムの定義を生成
+    ///
+    /// 既存の記録があるため、NOT NULLカラムにはDEFAULTを付けます。
+    /// 適用SQL・ステートメントのチェックサムの保存用カラムは
+    /// [`Self::generate_add_applied_sql_column_sql`] と同じ型です。
+    pub fn generate_add_layout_column_sql(&self, dialect: Dialect, column: &str) -> String {
+        if APPLIED_SQL_COLUMNS.contains(&column) || column == STATEMENT_CHECKSUMS_COLUMN {
+            return self.generate_add_applied_sql_column_sql(dialect, column);
+        }
+        let definition = match (column, dialect) {
+            ("description", Dialect::MySQL) => "VARCHAR(1024) NOT NULL DEFAULT ''",
+            ("description", _) => "TEXT NOT NULL DEFAULT ''",
+            ("execution_time_ms", Dialect::SQLite) => "INTEGER NULL",
+            ("execution_time_ms", _) => "BIGINT NULL",
+            (_, Dialect::MySQL) => "VARCHAR(255) NULL",
+            (_, _) => "TEXT NULL",
+        };
+        format!("ALTER TABLE schema_migrations ADD COLUMN {column} {definition}")
+    }
+
+    /// 既存の履歴テーブルを現在のレイアウトに更新
+    ///
+    /// テーブルのカラムから既知のレイアウトを判定し、現在のレイアウトに不足しているカラムを
+    /// 1つのトランザクション内で追加します。カラムの削除は行いません。
+    /// MySQLではDDLが暗黙にコミットされるため、途中で失敗すると一部のカラムだけが追加された
+    /// テーブルが残ります。追加するカラムは既存のカラムから毎回求めるため、次回の実行では
+    /// 残りのカラムだけを追加して更新を再開します。
+    ///
+    /// # Returns
+    ///
+    /// 更新した場合は更新前のレイアウトバージョン、テーブルがない・既に最新の場合はNone。
+    /// どのレイアウトにも一致しない場合は `DatabaseError::UnrecognizedMigrationTable`
+    pub async fn upgrade_migration_table(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<Option<u32>, DatabaseError> {
+        let columns = self.get_migration_table_columns(pool, dialect).await?;
+        if columns.is_empty() {
+            return Ok(None);
+        }
+
+        let missing: Vec<&str> = CURRENT_MIGRATION_TABLE_LAYOUT
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| !columns.iter().any(|c| c.eq_ignore_ascii_case(column)))
+            .collect();
+        let Some(layout) = detect_migration_table_layout(&columns) else {
+            let mut found = columns;
+            found.sort();
+            return Err(DatabaseError::UnrecognizedMigrationTable {
+                table_name: DEFAULT_MIGRATION_TABLE.to_string(),
+                found,
+                expected: CURRENT_MIGRATION_TABLE_LAYOUT
+                    .columns
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect(),
+            });
+        };
+        if missing.is_empty() {
+            return Ok(None);
+        }
+
+        debug!(
+            from = layout.version,
+            to = CURRENT_MIGRATION_TABLE_LAYOUT.version,
+            "Upgrading migration history table layout"
+        );
+        let mut tx = pool.begin().await.map_err(|e| DatabaseError::Transaction {
+            message: format!("Failed to start migration table upgrade: {}", e),
+        })?;
+        for column in missing {
+            let sql = self.generate_add_layout_column_sql(dialect, column);
+            sqlx::query(&sql)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DatabaseError::Query {
+                    message: format!(
+                        "Failed to upgrade migration table: {} (rerun to add the remaining columns; columns already added are kept)",
+                        e
+                    ),
+                    sql: Some(sql.clone()),
+                })?;
+        }
+        tx.commit().await.map_err(|e| DatabaseError::Transaction {
+            message: format!("Failed to commit migration table upgrade: {}", e),
+        })?;
+
+        Ok(Some(layout.version))
+    }
+
     /// マイグレーション記録クエリを生成（パラメータバインド対応）
     ///
     /// # Security
@@ -252,6 +543,178 @@ impl DatabaseMigratorService {
         (sql, params)
     }
 
+    /// ロールバックの記録クエリを生成（パラメータバインド対応）
+    ///
+    /// 削除する前の `schema_migrations` の記録を、ロールバックした日時とユーザーとともに
+    /// `schema_migration_rollbacks` にコピーする。履歴からの削除と同じトランザクションで実行する。
+    ///
+    /// # Returns
+    ///
+    /// (SQL文字列, バインドパラメータのベクタ)
+    pub fn generate_record_rollback_query(
+        &self,
+        version: &str,
+        rolled_back_at: DateTime<Utc>,
+        dialect: Dialect,
+    ) -> (String, Vec<String>) {
+        let p1 = placeholder(dialect, 1);
+        let p2 = placeholder(dialect, 2);
+        let cast = if dialect == Dialect::PostgreSQL {
+            "::timestamptz"
+        } else {
+            ""
+        };
+        let current_user = current_user_sql(dialect);
+        let sql = format!(
+            "INSERT INTO {ROLLBACK_AUDIT_TABLE} (version, description, checksum, applied_at, rolled_back_at, rolled_back_by) SELECT version, description, checksum, applied_at, {p1}{cast}, {current_user} FROM schema_migrations WHERE version = {p2}"
+        );
+
+        let params = vec![rolled_back_at.to_rfc3339(), version.to_string()];
+
+        (sql, params)
+    }
+
+    /// ロールバックの記録の取得SQLを生成
+    pub fn generate_get_rollback_audits_sql(&self, dialect: Dialect) -> String {
+        match dialect {
+            Dialect::PostgreSQL => format!(
+                "SELECT version, description, applied_at::text AS applied_at, rolled_back_at::text AS rolled_back_at, rolled_back_by::text AS rolled_back_by FROM {ROLLBACK_AUDIT_TABLE} ORDER BY id"
+            ),
+            Dialect::MySQL => format!(
+                "SELECT version, CAST(description AS CHAR) AS description, CAST(applied_at AS CHAR) AS applied_at, CAST(rolled_back_at AS CHAR) AS rolled_back_at, CAST(rolled_back_by AS CHAR) AS rolled_back_by FROM {ROLLBACK_AUDIT_TABLE} ORDER BY id"
+            ),
+            Dialect::SQLite => format!(
+                "SELECT version, description, applied_at, rolled_back_at, rolled_back_by FROM {ROLLBACK_AUDIT_TABLE} ORDER BY id"
+            ),
+        }
+    }
+
+    /// ロールバックの記録を古い順に取得
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    pub async fn get_rollback_audits(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<Vec<MigrationRollbackAudit>, DatabaseError> {
+        let sql = self.generate_get_rollback_audits_sql(dialect);
+        let rows = sqlx::query(&sql)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to get rollback history: {}", e),
+                sql: Some(sql),
+            })?;
+
+        let parse = |value: String| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now())
+        };
+        Ok(rows
+            .iter()
+            .map(|row| MigrationRollbackAudit {
+                version: row.get(0),
+                description: row.get(1),
+                applied_at: parse(row.get(2)),
+                rolled_back_at: parse(row.get(3)),
+                rolled_back_by: row.get(4),
+            })
+            .collect())
+    }
+
+    /// マイグレーション記録のバージョンを書き換えるSQLを生成
+    ///
+    /// `strata repair --renumber` で各環境に手動で実行してもらうためのSQLで、
+    /// バインドパラメータではなくリテラルを埋め込む。
+    /// バージョンは数字のみのタイムスタンプのため、単一引用符は二重化するだけで十分。
+    ///
+    /// # Arguments
+    ///
+    /// * `old_version` - 変更前のバージョン
+    /// * `new_version` - 変更後のバージョン
+    pub fn generate_renumber_migration_sql(&self, old_version: &str, new_version: &str) -> String {
+        format!(
+            "UPDATE schema_migrations SET version = '{}' WHERE version = '{}';",
+            new_version.replace('\'', "''"),
+            old_version.replace('\'', "''")
+        )
+    }
+
+    /// マイグレーション記録の説明を書き換えるSQLを生成
+    ///
+    /// `strata rename-migration` で各環境に手動で実行してもらうためのSQLで、
+    /// バインドパラメータではなくリテラルを埋め込む（単一引用符は二重化する）。
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - 対象のバージョン
+    /// * `new_description` - 変更後の説明
+    pub fn generate_rename_migration_sql(&self, version: &str, new_description: &str) -> String {
+        format!(
+            "UPDATE schema_migrations SET description = '{}' WHERE version = '{}';",
+            new_description.replace('\'', "''"),
+            version.replace('\'', "''")
+        )
+    }
+
+    /// マイグレーション記録の説明を更新するクエリを生成（パラメータバインド対応）
+    ///
+    /// # Returns
+    ///
+    /// (SQL文字列, バインドパラメータのベクタ)
+    pub fn generate_update_description_query(
+        &self,
+        version: &str,
+        new_description: &str,
+        dialect: Dialect,
+    ) -> (String, Vec<String>) {
+        let p1 = placeholder(dialect, 1);
+        let p2 = placeholder(dialect, 2);
+        let sql = format!("UPDATE schema_migrations SET description = {p1} WHERE version = {p2}");
+
+        let params = vec![new_description.to_string(), version.to_string()];
+
+        (sql, params)
+    }
+
+    /// マイグレーション記録の説明を更新（パラメータバインド対応）
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `version` - 対象のバージョン
+    /// * `new_description` - 変更後の説明
+    /// * `dialect` - データベース方言
+    pub async fn update_migration_description(
+        &self,
+        pool: &AnyPool,
+        version: &str,
+        new_description: &str,
+        dialect: Dialect,
+    ) -> Result<(), DatabaseError> {
+        let (sql, params) =
+            self.generate_update_description_query(version, new_description, dialect);
+
+        let mut query = sqlx::query(&sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+
+        query
+            .execute(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to update migration description: {}", e),
+                sql: Some(sql),
+            })?;
+
+        Ok(())
+    }
+
     /// マイグレーション記録をデータベースから削除（パラメータバインド対応）
     ///
     /// # Security
@@ -482,6 +945,62 @@ impl DatabaseMigratorService {
         "ROLLBACK".to_string()
     }
 
+    /// apply 中のセッションにタイムアウトを設定するSQLを生成
+    ///
+    /// PostgreSQLは `SET LOCAL` のため、トランザクション内で実行する必要があります。
+    /// SQLiteには対応する設定がないため、空のリストを返します。
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - データベース方言
+    /// * `timeouts` - 設定するタイムアウト（秒）
+    pub fn generate_session_timeout_sql(
+        &self,
+        dialect: Dialect,
+        timeouts: &SessionTimeouts,
+    ) -> Vec<String> {
+        let mut statements = Vec::new();
+        match dialect {
+            Dialect::PostgreSQL => {
+                if let Some(seconds) = timeouts.lock_timeout {
+                    statements.push(format!("SET LOCAL lock_timeout = '{}s'", seconds));
+                }
+                if let Some(seconds) = timeouts.statement_timeout {
+                    statements.push(format!("SET LOCAL statement_timeout = '{}s'", seconds));
+                }
+            }
+            Dialect::MySQL => {
+                if let Some(seconds) = timeouts.lock_timeout {
+                    statements.push(format!("SET SESSION lock_wait_timeout = {}", seconds));
+                }
+                // max_execution_time はミリ秒で指定する
+                if let Some(seconds) = timeouts.statement_timeout {
+                    statements.push(format!(
+                        "SET SESSION max_execution_time = {}",
+                        seconds.saturating_mul(1000)
+                    ));
+                }
+            }
+            Dialect::SQLite => {}
+        }
+        statements
+    }
+
+    /// マイグレーションのステートメントを実行し、一時的なエラーの場合は再試行
+    ///
+    /// # Returns
+    ///
+    /// 成功した場合は再試行した回数。失敗した場合は最後のエラーと実行した回数
+    pub async fn execute_statement_with_retry<E: StatementExecutor + ?Sized>(
+        &self,
+        executor: &mut E,
+        dialect: Dialect,
+        statement: &str,
+        policy: &RetryPolicy,
+    ) -> Result<u32, StatementFailure> {
+        statement_retry::execute_with_retry(executor, dialect, statement, policy).await
+    }
+
     /// マイグレーションテーブル存在確認SQLを生成
     ///
     /// # Arguments
@@ -537,6 +1056,366 @@ impl DatabaseMigratorService {
         Ok(row_result.is_some())
     }
 
+    /// マイグレーションテーブルのカラム一覧取得SQLを生成
+    pub fn generate_get_migration_table_columns_sql(&self, dialect: Dialect) -> String {
+        match dialect {
+            Dialect::PostgreSQL => {
+                "SELECT column_name::text FROM information_schema.columns WHERE table_schema = ANY(current_schemas(false)) AND table_name = 'schema_migrations'"
+                    .to_string()
+            }
+            Dialect::MySQL => {
+                "SELECT CAST(column_name AS CHAR) FROM information_schema.columns WHERE table_schema = DATABASE() AND table_name = 'schema_migrations'"
+                    .to_string()
+            }
+            Dialect::SQLite => {
+                "SELECT name FROM pragma_table_info('schema_migrations')".to_string()
+            }
+        }
+    }
+
+    /// 適用SQL保存用カラムの追加SQLを生成
+    ///
+    /// MySQLの TEXT は64KBまでのため、[`MAX_STORED_SQL_BYTES`](crate::core::migration::MAX_STORED_SQL_BYTES) を格納できる MEDIUMTEXT を使用する。
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - データベース方言
+    /// * `column` - 追加するカラム名（`up_sql`・`down_sql`・`statement_checksums`）
+    pub fn generate_add_applied_sql_column_sql(&self, dialect: Dialect, column: &str) -> String {
+        let column_type = match dialect {
+            Dialect::MySQL => "MEDIUMTEXT",
+            Dialect::PostgreSQL | Dialect::SQLite => "TEXT",
+        };
+        format!("ALTER TABLE schema_migrations ADD COLUMN {column} {column_type} NULL")
+    }
+
+    /// 適用SQL保存用カラムが存在するか確認
+    ///
+    /// # Returns
+    ///
+    /// `up_sql` / `down_sql` の両方が存在する場合はtrue
+    pub async fn applied_sql_columns_exist(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<bool, DatabaseError> {
+        let columns = self.get_migration_table_columns(pool, dialect).await?;
+        Ok(APPLIED_SQL_COLUMNS
+            .iter()
+            .all(|column| columns.iter().any(|c| c == column)))
+    }
+
+    /// 実行時間・適用ユーザーの保存クエリを生成（パラメータバインド対応）
+    ///
+    /// 適用ユーザーは接続中のデータベースユーザーをSQL側で取得する。
+    /// SQLiteにはユーザーの概念がないためNULLを保存する。
+    /// 実行時間は整数のため、型の異なるバインドを避けてリテラルとして埋め込む。
+    ///
+    /// # Returns
+    ///
+    /// (SQL文字列, バインドパラメータのベクタ)
+    pub fn generate_store_audit_query(
+        &self,
+        dialect: Dialect,
+        version: &str,
+        execution_time_ms: u64,
+    ) -> (String, Vec<String>) {
+        let current_user = current_user_sql(dialect);
+        let p1 = placeholder(dialect, 1);
+        let sql = format!(
+            "UPDATE schema_migrations SET execution_time_ms = {execution_time_ms}, applied_by = {current_user} WHERE version = {p1}"
+        );
+
+        (sql, vec![version.to_string()])
+    }
+
+    /// 適用記録の詳細の取得SQLを生成
+    pub fn generate_get_migration_audits_sql(&self, dialect: Dialect) -> String {
+        match dialect {
+            Dialect::PostgreSQL => {
+                "SELECT version, applied_at::text AS applied_at, execution_time_ms, applied_by::text AS applied_by FROM schema_migrations ORDER BY version"
+                    .to_string()
+            }
+            Dialect::MySQL => {
+                "SELECT version, CAST(applied_at AS CHAR) AS applied_at, execution_time_ms, CAST(applied_by AS CHAR) AS applied_by FROM schema_migrations ORDER BY version"
+                    .to_string()
+            }
+            Dialect::SQLite => {
+                "SELECT version, applied_at, execution_time_ms, applied_by FROM schema_migrations ORDER BY version"
+                    .to_string()
+            }
+        }
+    }
+
+    /// 適用日時・実行時間・適用ユーザーを取得
+    ///
+    /// 保存用カラムがまだない場合は、実行時間と適用ユーザーをNoneとして返す（テーブルは変更しない）。
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    pub async fn get_migration_audits(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<Vec<MigrationAudit>, DatabaseError> {
+        let columns = self.get_migration_table_columns(pool, dialect).await?;
+        if !AUDIT_COLUMNS
+            .iter()
+            .all(|column| columns.iter().any(|c| c == column))
+        {
+            return Ok(self
+                .get_migrations(pool, dialect)
+                .await?
+                .into_iter()
+                .map(|record| MigrationAudit {
+                    version: record.version,
+                    applied_at: record.applied_at,
+                    execution_time_ms: None,
+                    applied_by: None,
+                })
+                .collect());
+        }
+
+        let sql = self.generate_get_migration_audits_sql(dialect);
+        let rows = sqlx::query(&sql)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to get migration history: {}", e),
+                sql: Some(sql),
+            })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let applied_at_str: String = row.get(1);
+                MigrationAudit {
+                    version: row.get(0),
+                    applied_at: DateTime::parse_from_rfc3339(&applied_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    execution_time_ms: row.get(2),
+                    applied_by: row.get(3),
+                }
+            })
+            .collect())
+    }
+
+    /// 実行したステートメントのチェックサム一覧の保存クエリを生成（パラメータバインド対応）
+    ///
+    /// チェックサムは実行順に改行区切りで保存する。
+    ///
+    /// # Returns
+    ///
+    /// (SQL文字列, バインドパラメータのベクタ)
+    pub fn generate_store_statement_checksums_query(
+        &self,
+        dialect: Dialect,
+        version: &str,
+        checksums: &[String],
+    ) -> (String, Vec<String>) {
+        let p1 = placeholder(dialect, 1);
+        let p2 = placeholder(dialect, 2);
+        let sql = format!(
+            "UPDATE schema_migrations SET {STATEMENT_CHECKSUMS_COLUMN} = {p1} WHERE version = {p2}"
+        );
+
+        let params = vec![checksums.join("\n"), version.to_string()];
+
+        (sql, params)
+    }
+
+    /// 保存されたステートメントのチェックサム一覧の取得SQLを生成
+    pub fn generate_get_statement_checksums_sql(&self, dialect: Dialect) -> String {
+        let column = match dialect {
+            Dialect::PostgreSQL | Dialect::SQLite => STATEMENT_CHECKSUMS_COLUMN.to_string(),
+            Dialect::MySQL => format!("CAST({0} AS CHAR) AS {0}", STATEMENT_CHECKSUMS_COLUMN),
+        };
+        format!(
+            "SELECT {} FROM schema_migrations WHERE version = {}",
+            column,
+            placeholder(dialect, 1)
+        )
+    }
+
+    /// 適用時に記録された、実行したステートメントのチェックサム一覧を取得
+    ///
+    /// 保存用カラムがまだない場合、記録がない場合、カラムの追加前に適用された場合はNoneを返す
+    /// （テーブルは変更しない）。
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `version` - 取得するバージョン
+    pub async fn get_statement_checksums(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        version: &str,
+    ) -> Result<Option<Vec<String>>, DatabaseError> {
+        let columns = self.get_migration_table_columns(pool, dialect).await?;
+        if !columns.iter().any(|c| c == STATEMENT_CHECKSUMS_COLUMN) {
+            return Ok(None);
+        }
+
+        let sql = self.generate_get_statement_checksums_sql(dialect);
+        let row = sqlx::query(&sql)
+            .bind(version)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to get statement checksums: {}", e),
+                sql: Some(sql),
+            })?;
+
+        let stored: Option<String> = row.and_then(|row| row.get(0));
+        Ok(stored.map(|stored| {
+            stored
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        }))
+    }
+
+    /// マイグレーションテーブルのカラム名を取得
+    async fn get_migration_table_columns(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+    ) -> Result<Vec<String>, DatabaseError> {
+        let sql = self.generate_get_migration_table_columns_sql(dialect);
+
+        let rows = sqlx::query(&sql)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to get migration table columns: {}", e),
+                sql: Some(sql),
+            })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| row.get::<String, _>(0).to_lowercase())
+            .collect())
+    }
+
+    /// 適用SQLの保存クエリを生成（パラメータバインド対応）
+    ///
+    /// [`MAX_STORED_SQL_BYTES`](crate::core::migration::MAX_STORED_SQL_BYTES) を超えるSQLは [`truncate_sql_for_storage`] で切り詰めて保存する。
+    /// down.sql が存在しない場合はNULLを保存する。
+    ///
+    /// # Returns
+    ///
+    /// (SQL文字列, バインドパラメータのベクタ)
+    pub fn generate_store_applied_sql_query(
+        &self,
+        dialect: Dialect,
+        version: &str,
+        up_sql: &str,
+        down_sql: Option<&str>,
+    ) -> (String, Vec<Option<String>>) {
+        let p1 = placeholder(dialect, 1);
+        let p2 = placeholder(dialect, 2);
+        let p3 = placeholder(dialect, 3);
+        let sql = format!(
+            "UPDATE schema_migrations SET up_sql = {p1}, down_sql = {p2} WHERE version = {p3}"
+        );
+
+        let params = vec![
+            Some(truncate_sql_for_storage(up_sql).into_owned()),
+            down_sql.map(|sql| truncate_sql_for_storage(sql).into_owned()),
+            Some(version.to_string()),
+        ];
+
+        (sql, params)
+    }
+
+    /// 保存された適用SQLの取得SQLを生成
+    ///
+    /// `with_version` がtrueの場合はバージョンで絞り込む（パラメータ1つ）。
+    pub fn generate_get_applied_sql_sql(&self, dialect: Dialect, with_version: bool) -> String {
+        let select = match dialect {
+            Dialect::PostgreSQL | Dialect::SQLite => {
+                "SELECT version, description, checksum, up_sql, down_sql FROM schema_migrations"
+            }
+            Dialect::MySQL => {
+                "SELECT version, CAST(description AS CHAR) AS description, CAST(checksum AS CHAR) AS checksum, CAST(up_sql AS CHAR) AS up_sql, CAST(down_sql AS CHAR) AS down_sql FROM schema_migrations"
+            }
+        };
+        if with_version {
+            format!("{} WHERE version = {}", select, placeholder(dialect, 1))
+        } else {
+            format!("{} ORDER BY version", select)
+        }
+    }
+
+    /// 保存された適用SQLを取得
+    ///
+    /// 適用SQL保存用カラムがまだ追加されていない場合は、SQLをNoneとして
+    /// マイグレーション記録のみを返す（テーブルは変更しない）。
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - データベース接続プール
+    /// * `dialect` - データベース方言
+    /// * `version` - 取得するバージョン（Noneの場合は全件）
+    pub async fn get_applied_sql(
+        &self,
+        pool: &AnyPool,
+        dialect: Dialect,
+        version: Option<&str>,
+    ) -> Result<Vec<AppliedSql>, DatabaseError> {
+        if !self.applied_sql_columns_exist(pool, dialect).await? {
+            let records = match version {
+                Some(version) => self
+                    .get_migration_by_version_safe(pool, dialect, version)
+                    .await?
+                    .into_iter()
+                    .collect(),
+                None => self.get_migrations(pool, dialect).await?,
+            };
+            return Ok(records
+                .into_iter()
+                .map(|record| AppliedSql {
+                    version: record.version,
+                    description: record.description,
+                    checksum: record.checksum,
+                    up_sql: None,
+                    down_sql: None,
+                })
+                .collect());
+        }
+
+        let sql = self.generate_get_applied_sql_sql(dialect, version.is_some());
+        let mut query = sqlx::query(&sql);
+        if let Some(version) = version {
+            query = query.bind(version);
+        }
+
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| DatabaseError::Query {
+                message: format!("Failed to get applied migration SQL: {}", e),
+                sql: Some(sql),
+            })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| AppliedSql {
+                version: row.get(0),
+                description: row.get(1),
+                checksum: row.get(2),
+                up_sql: row.get(3),
+                down_sql: row.get(4),
+            })
+            .collect())
+    }
+
     /// マイグレーションSQLを実行
     ///
     /// # Arguments
@@ -699,6 +1578,36 @@ mod tests {
         assert_eq!(sql, "ROLLBACK");
     }
 
+    #[test]
+    fn test_generate_session_timeout_sql() {
+        let service = DatabaseMigratorService::new();
+        let timeouts = SessionTimeouts {
+            lock_timeout: Some(5),
+            statement_timeout: Some(60),
+        };
+
+        assert_eq!(
+            service.generate_session_timeout_sql(Dialect::PostgreSQL, &timeouts),
+            vec![
+                "SET LOCAL lock_timeout = '5s'",
+                "SET LOCAL statement_timeout = '60s'",
+            ]
+        );
+        assert_eq!(
+            service.generate_session_timeout_sql(Dialect::MySQL, &timeouts),
+            vec![
+                "SET SESSION lock_wait_timeout = 5",
+                "SET SESSION max_execution_time = 60000",
+            ]
+        );
+        assert!(service
+            .generate_session_timeout_sql(Dialect::SQLite, &timeouts)
+            .is_empty());
+        assert!(service
+            .generate_session_timeout_sql(Dialect::PostgreSQL, &SessionTimeouts::default())
+            .is_empty());
+    }
+
     #[test]
     fn test_generate_check_migration_table_exists_sql_postgres() {
         let service = DatabaseMigratorService::new();
@@ -877,6 +1786,58 @@ mod tests {
         assert_eq!(params.len(), 1);
     }
 
+    #[test]
+    fn test_generate_renumber_migration_sql() {
+        let service = DatabaseMigratorService::new();
+
+        let sql = service.generate_renumber_migration_sql("20990101120000", "20260121120000");
+
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET version = '20260121120000' WHERE version = '20990101120000';"
+        );
+    }
+
+    #[test]
+    fn test_generate_rename_migration_sql() {
+        let service = DatabaseMigratorService::new();
+
+        let sql = service.generate_rename_migration_sql("20260121120000", "add_users_table");
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET description = 'add_users_table' WHERE version = '20260121120000';"
+        );
+
+        let sql = service.generate_rename_migration_sql("20260121120000", "it's");
+        assert!(sql.contains("'it''s'"), "{}", sql);
+    }
+
+    #[test]
+    fn test_generate_update_description_query() {
+        let service = DatabaseMigratorService::new();
+
+        let (sql, params) = service.generate_update_description_query(
+            "20260121120000",
+            "add_users_table",
+            Dialect::PostgreSQL,
+        );
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET description = $1 WHERE version = $2"
+        );
+        assert_eq!(params, vec!["add_users_table", "20260121120000"]);
+
+        let (sql, _) = service.generate_update_description_query(
+            "20260121120000",
+            "add_users_table",
+            Dialect::MySQL,
+        );
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET description = ? WHERE version = ?"
+        );
+    }
+
     #[test]
     fn test_generate_get_migration_by_version_query_postgres() {
         let service = DatabaseMigratorService::new();
@@ -895,4 +1856,256 @@ mod tests {
     fn test_default_migration_table_name() {
         assert_eq!(DEFAULT_MIGRATION_TABLE, "schema_migrations");
     }
+
+    #[test]
+    fn test_generate_add_applied_sql_column_sql() {
+        let service = DatabaseMigratorService::new();
+
+        assert_eq!(
+            service.generate_add_applied_sql_column_sql(Dialect::PostgreSQL, "up_sql"),
+            "ALTER TABLE schema_migrations ADD COLUMN up_sql TEXT NULL"
+        );
+        // MySQLのTEXTは64KBまでのためMEDIUMTEXTを使用
+        assert_eq!(
+            service.generate_add_applied_sql_column_sql(Dialect::MySQL, "down_sql"),
+            "ALTER TABLE schema_migrations ADD COLUMN down_sql MEDIUMTEXT NULL"
+        );
+        assert_eq!(
+            service.generate_add_applied_sql_column_sql(Dialect::SQLite, "up_sql"),
+            "ALTER TABLE schema_migrations ADD COLUMN up_sql TEXT NULL"
+        );
+    }
+
+    #[test]
+    fn test_generate_store_applied_sql_query() {
+        let service = DatabaseMigratorService::new();
+
+        let (sql, params) = service.generate_store_applied_sql_query(
+            Dialect::PostgreSQL,
+            "20240101120000",
+            "CREATE TABLE users (id INTEGER);",
+            None,
+        );
+
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET up_sql = $1, down_sql = $2 WHERE version = $3"
+        );
+        assert_eq!(
+            params,
+            vec![
+                Some("CREATE TABLE users (id INTEGER);".to_string()),
+                None,
+                Some("20240101120000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_store_applied_sql_query_truncates_large_sql() {
+        use crate::core::migration::{is_truncated_sql, MAX_STORED_SQL_BYTES};
+
+        let service = DatabaseMigratorService::new();
+        let large = "-- padding\n".repeat(MAX_STORED_SQL_BYTES / 10);
+
+        let (_, params) = service.generate_store_applied_sql_query(
+            Dialect::SQLite,
+            "20240101120000",
+            &large,
+            Some("DROP TABLE users;"),
+        );
+
+        let up_sql = params[0].as_deref().unwrap();
+        assert!(up_sql.len() <= MAX_STORED_SQL_BYTES);
+        assert!(is_truncated_sql(up_sql));
+        assert_eq!(params[1].as_deref(), Some("DROP TABLE users;"));
+    }
+
+    #[test]
+    fn test_generate_statement_checksums_queries() {
+        let service = DatabaseMigratorService::new();
+
+        let (sql, params) = service.generate_store_statement_checksums_query(
+            Dialect::PostgreSQL,
+            "20240101120000",
+            &["aaa".to_string(), "bbb".to_string()],
+        );
+        assert_eq!(
+            sql,
+            "UPDATE schema_migrations SET statement_checksums = $1 WHERE version = $2"
+        );
+        assert_eq!(
+            params,
+            vec!["aaa\nbbb".to_string(), "20240101120000".to_string()]
+        );
+
+        assert_eq!(
+            service.generate_get_statement_checksums_sql(Dialect::MySQL),
+            "SELECT CAST(statement_checksums AS CHAR) AS statement_checksums FROM schema_migrations WHERE version = ?"
+        );
+    }
+
+    #[test]
+    fn test_generate_get_applied_sql_sql() {
+        let service = DatabaseMigratorService::new();
+
+        let sql = service.generate_get_applied_sql_sql(Dialect::PostgreSQL, true);
+        assert!(sql.ends_with("FROM schema_migrations WHERE version = $1"));
+
+        let sql = service.generate_get_applied_sql_sql(Dialect::MySQL, false);
+        assert!(sql.contains("CAST(up_sql AS CHAR) AS up_sql"));
+        assert!(sql.ends_with("ORDER BY version"));
+    }
+
+    #[test]
+    fn test_migration_table_layout_registry() {
+        // 現在のレイアウトは作成SQLのカラム構成と一致する
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let sql = DatabaseMigratorService::new().generate_create_migration_table_sql(dialect);
+            for column in CURRENT_MIGRATION_TABLE_LAYOUT.columns {
+                assert!(sql.contains(&format!("    {} ", column)), "{}", sql);
+            }
+        }
+
+        // レイアウトは古い順で、新しいレイアウトは古いレイアウトのカラムを削除しない
+        for pair in MIGRATION_TABLE_LAYOUTS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+            assert!(pair[0]
+                .columns
+                .iter()
+                .all(|column| pair[1].columns.contains(column)));
+        }
+    }
+
+    #[test]
+    fn test_detect_migration_table_layout() {
+        let columns = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let layout =
+            detect_migration_table_layout(&columns(&["checksum", "VERSION", "applied_at"]));
+        assert_eq!(layout.map(|l| l.version), Some(1));
+
+        let layout = detect_migration_table_layout(&columns(&[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            "up_sql",
+            "down_sql",
+            "statement_checksums",
+            "execution_time_ms",
+            "applied_by",
+        ]));
+        assert_eq!(layout, Some(CURRENT_MIGRATION_TABLE_LAYOUT));
+
+        let layout = detect_migration_table_layout(&columns(&[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            "up_sql",
+            "down_sql",
+            "statement_checksums",
+        ]));
+        assert_eq!(layout.map(|l| l.version), Some(4));
+
+        let layout = detect_migration_table_layout(&columns(&[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            "up_sql",
+            "down_sql",
+        ]));
+        assert_eq!(layout.map(|l| l.version), Some(3));
+
+        // 新しいレイアウトのカラムが一部だけある場合は、カラムが揃っている最新のレイアウト
+        let layout = detect_migration_table_layout(&columns(&[
+            "version",
+            "description",
+            "applied_at",
+            "checksum",
+            "up_sql",
+        ]));
+        assert_eq!(layout.map(|l| l.version), Some(2));
+        let layout = detect_migration_table_layout(&columns(&[
+            "version",
+            "applied_at",
+            "checksum",
+            "up_sql",
+            "down_sql",
+        ]));
+        assert_eq!(layout.map(|l| l.version), Some(1));
+
+        assert_eq!(
+            detect_migration_table_layout(&columns(&[
+                "version",
+                "description",
+                "applied_at",
+                "checksum",
+                "installed_by",
+            ])),
+            None
+        );
+        assert_eq!(
+            detect_migration_table_layout(&columns(&["version", "checksum"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate_add_layout_column_sql() {
+        let service = DatabaseMigratorService::new();
+
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::SQLite, "description"),
+            "ALTER TABLE schema_migrations ADD COLUMN description TEXT NOT NULL DEFAULT ''"
+        );
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::MySQL, "description"),
+            "ALTER TABLE schema_migrations ADD COLUMN description VARCHAR(1024) NOT NULL DEFAULT ''"
+        );
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::MySQL, "up_sql"),
+            "ALTER TABLE schema_migrations ADD COLUMN up_sql MEDIUMTEXT NULL"
+        );
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::SQLite, "statement_checksums"),
+            "ALTER TABLE schema_migrations ADD COLUMN statement_checksums TEXT NULL"
+        );
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::PostgreSQL, "execution_time_ms"),
+            "ALTER TABLE schema_migrations ADD COLUMN execution_time_ms BIGINT NULL"
+        );
+        assert_eq!(
+            service.generate_add_layout_column_sql(Dialect::MySQL, "applied_by"),
+            "ALTER TABLE schema_migrations ADD COLUMN applied_by VARCHAR(255) NULL"
+        );
+    }
+
+    #[test]
+    fn test_generate_record_rollback_query() {
+        let service = DatabaseMigratorService::new();
+        let rolled_back_at = DateTime::parse_from_rfc3339("2026-01-22T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (sql, params) = service.generate_record_rollback_query(
+            "20260122120000",
+            rolled_back_at,
+            Dialect::PostgreSQL,
+        );
+        assert_eq!(
+            sql,
+            "INSERT INTO schema_migration_rollbacks (version, description, checksum, applied_at, rolled_back_at, rolled_back_by) SELECT version, description, checksum, applied_at, $1::timestamptz, current_user FROM schema_migrations WHERE version = $2"
+        );
+        assert_eq!(params, vec!["2026-01-22T12:00:00+00:00", "20260122120000"]);
+
+        let (sql, _) = service.generate_record_rollback_query(
+            "20260122120000",
+            rolled_back_at,
+            Dialect::MySQL,
+        );
+        assert!(sql.contains("?, CURRENT_USER() FROM schema_migrations WHERE version = ?"));
+    }
 }
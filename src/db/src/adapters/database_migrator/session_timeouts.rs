@@ -0,0 +1,238 @@
+// apply 中のセッションのタイムアウト
+//
+// マイグレーションのステートメントが他のセッションのロックの後ろで待ち続け、
+// さらに後続のクエリを待たせてしまうことを防ぐため、apply のセッションにロック待ちと
+// ステートメントの実行時間の上限を設定します。
+// - PostgreSQL: SET LOCAL lock_timeout / statement_timeout（トランザクションの終了で元に戻る）
+// - MySQL: SET SESSION lock_wait_timeout / max_execution_time
+// - SQLite: 対応する設定がないため何もしない
+
+use super::statement_retry::database_error_codes;
+use crate::core::config::{DatabaseConfig, Dialect};
+use std::fmt;
+
+/// MySQLのロック待ちタイムアウト（ER_LOCK_WAIT_TIMEOUT）
+const MYSQL_LOCK_WAIT_TIMEOUT: u16 = 1205;
+
+/// MySQLの max_execution_time 超過（ER_QUERY_TIMEOUT）
+const MYSQL_QUERY_TIMEOUT: u16 = 3024;
+
+/// apply 中のセッションに設定するタイムアウト（秒）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionTimeouts {
+    /// ロックの取得を待つ時間の上限
+    pub lock_timeout: Option<u64>,
+    /// 1つのステートメントを実行する時間の上限
+    pub statement_timeout: Option<u64>,
+}
+
+impl SessionTimeouts {
+    /// 環境のデータベース設定から作成
+    pub fn from_config(config: &DatabaseConfig) -> Self {
+        Self {
+            lock_timeout: config.lock_timeout,
+            statement_timeout: config.statement_timeout,
+        }
+    }
+
+    /// 指定された値で上書きする（Noneの項目は元の値のまま）
+    pub fn overridden_by(self, lock_timeout: Option<u64>, statement_timeout: Option<u64>) -> Self {
+        Self {
+            lock_timeout: lock_timeout.or(self.lock_timeout),
+            statement_timeout: statement_timeout.or(self.statement_timeout),
+        }
+    }
+
+    /// タイムアウトが1つも設定されていないか
+    pub fn is_empty(&self) -> bool {
+        self.lock_timeout.is_none() && self.statement_timeout.is_none()
+    }
+
+    /// ステートメントのエラーが、設定したタイムアウトによるものかを判定
+    pub fn exceeded(&self, dialect: Dialect, error: &sqlx::Error) -> Option<TimeoutExceeded> {
+        let (code, mysql_error_number) = database_error_codes(error)?;
+        self.exceeded_by_code(dialect, code.as_deref(), mysql_error_number)
+    }
+
+    /// 方言ごとのエラーコードが、設定したタイムアウトの超過を表すかを判定
+    ///
+    /// - PostgreSQL: SQLSTATE 55P03 (lock_not_available) / 57014 (query_canceled)
+    /// - MySQL: エラー番号 1205 (ロック待ちのタイムアウト) / 3024 (max_execution_time の超過)
+    ///
+    /// 対応するタイムアウトを設定していない場合は None を返す。
+    pub fn exceeded_by_code(
+        &self,
+        dialect: Dialect,
+        code: Option<&str>,
+        mysql_error_number: Option<u16>,
+    ) -> Option<TimeoutExceeded> {
+        let (setting, seconds) = match dialect {
+            Dialect::PostgreSQL => match code {
+                Some("55P03") => (TimeoutSetting::LockTimeout, self.lock_timeout?),
+                Some("57014") => (TimeoutSetting::StatementTimeout, self.statement_timeout?),
+                _ => return None,
+            },
+            Dialect::MySQL => match mysql_error_number {
+                Some(MYSQL_LOCK_WAIT_TIMEOUT) => (TimeoutSetting::LockTimeout, self.lock_timeout?),
+                Some(MYSQL_QUERY_TIMEOUT) => {
+                    (TimeoutSetting::StatementTimeout, self.statement_timeout?)
+                }
+                _ => return None,
+            },
+            Dialect::SQLite => return None,
+        };
+        Some(TimeoutExceeded {
+            dialect,
+            setting,
+            seconds,
+        })
+    }
+}
+
+/// タイムアウトの設定項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutSetting {
+    /// ロックの取得を待つ時間の上限（`--lock-timeout`）
+    LockTimeout,
+    /// 1つのステートメントを実行する時間の上限（`--statement-timeout`）
+    StatementTimeout,
+}
+
+impl TimeoutSetting {
+    /// データベース側の設定名
+    pub fn variable_name(&self, dialect: Dialect) -> &'static str {
+        match (self, dialect) {
+            (TimeoutSetting::LockTimeout, Dialect::MySQL) => "lock_wait_timeout",
+            (TimeoutSetting::StatementTimeout, Dialect::MySQL) => "max_execution_time",
+            (TimeoutSetting::LockTimeout, _) => "lock_timeout",
+            (TimeoutSetting::StatementTimeout, _) => "statement_timeout",
+        }
+    }
+
+    /// 上書きに使うCLIのオプション
+    pub fn flag(&self) -> &'static str {
+        match self {
+            TimeoutSetting::LockTimeout => "--lock-timeout",
+            TimeoutSetting::StatementTimeout => "--statement-timeout",
+        }
+    }
+}
+
+/// ステートメントが超過したタイムアウト
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutExceeded {
+    /// データベース方言
+    pub dialect: Dialect,
+    /// 超過した設定
+    pub setting: TimeoutSetting,
+    /// 設定していた値（秒）
+    pub seconds: u64,
+}
+
+impl fmt::Display for TimeoutExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The statement was stopped by {} ({}s). Retry the apply off-peak, when fewer sessions hold locks on the tables, or raise {}.",
+            self.setting.variable_name(self.dialect),
+            self.seconds,
+            self.setting.flag()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeouts() -> SessionTimeouts {
+        SessionTimeouts {
+            lock_timeout: Some(5),
+            statement_timeout: Some(60),
+        }
+    }
+
+    #[test]
+    fn test_overridden_by_keeps_unspecified_values() {
+        let config = DatabaseConfig {
+            lock_timeout: Some(5),
+            statement_timeout: Some(60),
+            ..Default::default()
+        };
+        let timeouts = SessionTimeouts::from_config(&config).overridden_by(Some(2), None);
+        assert_eq!(timeouts.lock_timeout, Some(2));
+        assert_eq!(timeouts.statement_timeout, Some(60));
+        assert!(!timeouts.is_empty());
+        assert!(SessionTimeouts::default().is_empty());
+    }
+
+    #[test]
+    fn test_exceeded_by_code() {
+        let exceeded = timeouts()
+            .exceeded_by_code(Dialect::PostgreSQL, Some("55P03"), None)
+            .unwrap();
+        assert_eq!(exceeded.setting, TimeoutSetting::LockTimeout);
+        assert_eq!(exceeded.seconds, 5);
+        let exceeded = timeouts()
+            .exceeded_by_code(Dialect::PostgreSQL, Some("57014"), None)
+            .unwrap();
+        assert_eq!(exceeded.setting, TimeoutSetting::StatementTimeout);
+        assert_eq!(exceeded.seconds, 60);
+        let exceeded = timeouts()
+            .exceeded_by_code(Dialect::MySQL, Some("HY000"), Some(3024))
+            .unwrap();
+        assert_eq!(exceeded.setting, TimeoutSetting::StatementTimeout);
+        assert_eq!(
+            timeouts()
+                .exceeded_by_code(Dialect::MySQL, Some("HY000"), Some(1205))
+                .map(|e| e.setting),
+            Some(TimeoutSetting::LockTimeout)
+        );
+
+        // 設定していないタイムアウトや、タイムアウト以外のエラーは対象外
+        let lock_only = SessionTimeouts {
+            lock_timeout: Some(5),
+            statement_timeout: None,
+        };
+        assert_eq!(
+            lock_only.exceeded_by_code(Dialect::PostgreSQL, Some("57014"), None),
+            None
+        );
+        assert_eq!(
+            timeouts().exceeded_by_code(Dialect::PostgreSQL, Some("40P01"), None),
+            None
+        );
+        assert_eq!(
+            timeouts().exceeded_by_code(Dialect::SQLite, Some("5"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_timeout_exceeded_message_names_setting() {
+        let exceeded = TimeoutExceeded {
+            dialect: Dialect::MySQL,
+            setting: TimeoutSetting::LockTimeout,
+            seconds: 5,
+        };
+        let message = exceeded.to_string();
+        assert!(message.contains("lock_wait_timeout (5s)"), "{}", message);
+        assert!(message.contains("off-peak"), "{}", message);
+        assert!(message.contains("--lock-timeout"), "{}", message);
+
+        let exceeded = TimeoutExceeded {
+            dialect: Dialect::PostgreSQL,
+            setting: TimeoutSetting::StatementTimeout,
+            seconds: 60,
+        };
+        assert!(exceeded.to_string().contains("statement_timeout (60s)"));
+    }
+
+    #[test]
+    fn test_non_database_errors_are_not_timeouts() {
+        assert_eq!(
+            timeouts().exceeded(Dialect::PostgreSQL, &sqlx::Error::PoolTimedOut),
+            None
+        );
+    }
+}
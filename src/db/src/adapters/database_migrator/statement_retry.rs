@@ -0,0 +1,616 @@
+// ステートメント単位の再試行
+//
+// apply 中のステートメントが一時的なエラー（デッドロック・シリアライズ失敗・ロック待ちのタイムアウト・
+// SQLITE_BUSY）で失敗した場合に、待ち時間を空けて同じステートメントを再実行します。
+// - 判定: sqlx のデータベースエラーから方言ごとのエラーコードを取り出して分類する
+// - PostgreSQL: エラーでトランザクション全体が中断されるため、ステートメントごとにSAVEPOINTを置き、
+//   失敗時はSAVEPOINTまで戻してから再実行する
+// - MySQL: デッドロックはトランザクション全体をロールバックするため、
+//   暗黙的にコミットされるDDLの場合だけ再試行する
+// - MySQL: --lock-timeout を設定した場合、ロック待ちのタイムアウトは上限の超過として扱い再試行しない
+// - 一時的でないエラーは再試行せず、そのまま返す
+
+use crate::core::config::{Dialect, StatementRetryConfig};
+use async_trait::async_trait;
+use sqlx::AnyConnection;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// 再試行の前後に置くSAVEPOINT名（PostgreSQL）
+const RETRY_SAVEPOINT: &str = "strata_statement_retry";
+
+/// MySQLのデッドロック（ER_LOCK_DEADLOCK）
+const MYSQL_LOCK_DEADLOCK: u16 = 1213;
+
+/// MySQLのロック待ちタイムアウト（ER_LOCK_WAIT_TIMEOUT）
+const MYSQL_LOCK_WAIT_TIMEOUT: u16 = 1205;
+
+/// SQLITE_BUSY（拡張エラーコードは下位8ビットで判定する）
+const SQLITE_BUSY: i32 = 5;
+
+/// 暗黙的にコミットされる（MySQL）ステートメントの先頭キーワード
+const IMPLICIT_COMMIT_KEYWORDS: [&str; 5] = ["CREATE", "ALTER", "DROP", "RENAME", "TRUNCATE"];
+
+/// トランザクション制御のステートメントの先頭キーワード（SAVEPOINTで囲まない）
+const TRANSACTION_CONTROL_KEYWORDS: [&str; 7] = [
+    "BEGIN",
+    "START",
+    "COMMIT",
+    "END",
+    "ROLLBACK",
+    "SAVEPOINT",
+    "RELEASE",
+];
+
+/// 再試行の対象になる一時的なエラーの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientError {
+    /// シリアライズ失敗（PostgreSQL 40001）
+    SerializationFailure,
+    /// デッドロック（PostgreSQL 40P01 / MySQL 1213）
+    Deadlock,
+    /// ロック待ちのタイムアウト（MySQL 1205）
+    LockWaitTimeout,
+    /// データベースがロックされている（SQLite SQLITE_BUSY）
+    Busy,
+}
+
+impl TransientError {
+    /// ログ・出力用の名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransientError::SerializationFailure => "serialization_failure",
+            TransientError::Deadlock => "deadlock",
+            TransientError::LockWaitTimeout => "lock_wait_timeout",
+            TransientError::Busy => "busy",
+        }
+    }
+}
+
+/// sqlx のエラーが一時的なエラーかを判定
+pub fn classify_transient_error(dialect: Dialect, error: &sqlx::Error) -> Option<TransientError> {
+    let (code, mysql_error_number) = database_error_codes(error)?;
+    classify_transient_code(dialect, code.as_deref(), mysql_error_number)
+}
+
+/// データベースのエラーからエラーコード（SQLSTATE など）とMySQLのエラー番号を取り出す
+///
+/// データベースが返したエラーでない場合は None を返す。
+pub(crate) fn database_error_codes(error: &sqlx::Error) -> Option<(Option<String>, Option<u16>)> {
+    let sqlx::Error::Database(db_error) = error else {
+        return None;
+    };
+    #[cfg(feature = "mysql")]
+    let mysql_error_number = db_error
+        .try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>()
+        .map(|e| e.number());
+    #[cfg(not(feature = "mysql"))]
+    let mysql_error_number = None;
+    Some((db_error.code().map(|c| c.into_owned()), mysql_error_number))
+}
+
+/// 方言ごとのエラーコードが一時的なエラーを表すかを判定
+///
+/// - PostgreSQL: SQLSTATE 40001 (serialization_failure) / 40P01 (deadlock_detected)
+/// - MySQL: エラー番号 1213 (デッドロック) / 1205 (ロック待ちのタイムアウト)
+/// - SQLite: SQLITE_BUSY (5)
+///
+/// ステートメントやロック取得のタイムアウト（PostgreSQL 57014 / 55P03）は、
+/// 設定された上限を超えたことを表すため再試行しない。
+pub fn classify_transient_code(
+    dialect: Dialect,
+    code: Option<&str>,
+    mysql_error_number: Option<u16>,
+) -> Option<TransientError> {
+    match dialect {
+        Dialect::PostgreSQL => match code {
+            Some("40001") => Some(TransientError::SerializationFailure),
+            Some("40P01") => Some(TransientError::Deadlock),
+            _ => None,
+        },
+        Dialect::MySQL => match mysql_error_number {
+            Some(MYSQL_LOCK_DEADLOCK) => Some(TransientError::Deadlock),
+            Some(MYSQL_LOCK_WAIT_TIMEOUT) => Some(TransientError::LockWaitTimeout),
+            _ => None,
+        },
+        Dialect::SQLite => code
+            .and_then(|c| c.parse::<i32>().ok())
+            .filter(|c| c & 0xff == SQLITE_BUSY)
+            .map(|_| TransientError::Busy),
+    }
+}
+
+/// 失敗したステートメントだけを再実行して安全かを判定
+///
+/// MySQLのデッドロックはトランザクション全体をロールバックするため、それまでに実行した
+/// ステートメントの結果が失われる。暗黙的にコミットされるDDLの場合だけ再試行する。
+fn is_retry_safe(dialect: Dialect, error: TransientError, statement: &str) -> bool {
+    match (dialect, error) {
+        (Dialect::MySQL, TransientError::Deadlock) => {
+            IMPLICIT_COMMIT_KEYWORDS.contains(&leading_keyword(statement).as_str())
+        }
+        _ => true,
+    }
+}
+
+/// コメントと空白を除いた先頭のキーワード（大文字）
+fn leading_keyword(statement: &str) -> String {
+    let mut rest = statement.trim_start();
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, r)| r).trim_start();
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, r)| r).trim_start();
+        } else {
+            break;
+        }
+    }
+    rest.split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase()
+}
+
+/// ステートメントの再試行方針
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 1つのステートメントを実行する最大回数（1 の場合は再試行しない）
+    pub max_attempts: u32,
+    /// 最初の再試行までの待ち時間
+    pub backoff: Duration,
+    /// 再試行までの待ち時間の上限
+    pub max_backoff: Duration,
+    /// この時刻を過ぎる再試行は行わない（apply 全体のタイムアウト）
+    pub deadline: Option<Instant>,
+    /// ロック待ちのタイムアウト（MySQL 1205）を再試行するか
+    pub retry_lock_wait_timeout: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&StatementRetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    /// 設定から作成
+    pub fn from_config(config: &StatementRetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts.max(1),
+            backoff: Duration::from_millis(config.backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            deadline: None,
+            retry_lock_wait_timeout: true,
+        }
+    }
+
+    /// 再試行しない方針
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// 再試行を打ち切る時刻を設定
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// ロック待ちの上限（`--lock-timeout`）を設定
+    ///
+    /// 上限を設定した場合、ロック待ちのタイムアウトは設定した上限の超過として扱い再試行しない。
+    /// 再試行すると1つのステートメントが上限の数倍の時間ロックを待つことになるため。
+    pub fn with_lock_timeout(mut self, lock_timeout: Option<u64>) -> Self {
+        self.retry_lock_wait_timeout = lock_timeout.is_none();
+        self
+    }
+
+    /// 一時的なエラーの種類が再試行の対象か
+    fn retries(&self, error: TransientError) -> bool {
+        error != TransientError::LockWaitTimeout || self.retry_lock_wait_timeout
+    }
+
+    /// 再試行が有効か
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    /// `attempt` 回目の実行が失敗した後の待ち時間
+    fn backoff_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// ステートメントを実行する接続
+///
+/// 再試行の動作を、実際のデータベースに接続せずにテストできるようにするための抽象化です。
+#[async_trait]
+pub trait StatementExecutor: Send {
+    /// ステートメントを実行
+    async fn execute_statement(&mut self, sql: &str) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+impl StatementExecutor for AnyConnection {
+    async fn execute_statement(&mut self, sql: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(sql).execute(&mut *self).await.map(|_| ())
+    }
+}
+
+/// 再試行しても成功しなかったステートメントのエラー
+#[derive(Debug)]
+pub struct StatementFailure {
+    /// 最後に発生したデータベースのエラー
+    pub source: sqlx::Error,
+    /// 実行した回数
+    pub attempts: u32,
+}
+
+/// ステートメントを実行し、一時的なエラーの場合は `policy` に従って再試行する
+///
+/// 成功した場合は再試行した回数を返す。
+pub async fn execute_with_retry<E: StatementExecutor + ?Sized>(
+    executor: &mut E,
+    dialect: Dialect,
+    statement: &str,
+    policy: &RetryPolicy,
+) -> Result<u32, StatementFailure> {
+    // PostgreSQLはエラーでトランザクションが中断されるため、SAVEPOINTまで戻してから再実行する
+    let use_savepoint = policy.is_enabled()
+        && dialect == Dialect::PostgreSQL
+        && !TRANSACTION_CONTROL_KEYWORDS.contains(&leading_keyword(statement).as_str());
+
+    let mut attempt = 1;
+    loop {
+        let fail = |source| StatementFailure {
+            source,
+            attempts: attempt,
+        };
+        if use_savepoint {
+            executor
+                .execute_statement(&format!("SAVEPOINT {}", RETRY_SAVEPOINT))
+                .await
+                .map_err(fail)?;
+        }
+
+        let source = match executor.execute_statement(statement).await {
+            Ok(()) => {
+                if use_savepoint {
+                    executor
+                        .execute_statement(&format!("RELEASE SAVEPOINT {}", RETRY_SAVEPOINT))
+                        .await
+                        .map_err(fail)?;
+                }
+                return Ok(attempt - 1);
+            }
+            Err(source) => source,
+        };
+
+        let transient = classify_transient_error(dialect, &source)
+            .filter(|error| policy.retries(*error) && is_retry_safe(dialect, *error, statement));
+        let backoff = policy.backoff_after(attempt);
+        let within_deadline = policy
+            .deadline
+            .is_none_or(|deadline| Instant::now() + backoff < deadline);
+        let error = match transient {
+            Some(error) if attempt < policy.max_attempts && within_deadline => error,
+            _ => return Err(fail(source)),
+        };
+
+        if use_savepoint {
+            // SAVEPOINTまで戻せない場合は、元のエラーを返す
+            if executor
+                .execute_statement(&format!("ROLLBACK TO SAVEPOINT {}", RETRY_SAVEPOINT))
+                .await
+                .is_err()
+            {
+                return Err(fail(source));
+            }
+        }
+        info!(
+            attempt,
+            max_attempts = policy.max_attempts,
+            error = error.as_str(),
+            retry_in_ms = backoff.as_millis() as u64,
+            "Statement failed with a transient error, retrying"
+        );
+        tokio::time::sleep(backoff).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use std::collections::VecDeque;
+    use std::fmt;
+
+    /// エラーコードだけを持つテスト用のデータベースエラー
+    #[derive(Debug)]
+    struct FakeDatabaseError {
+        code: &'static str,
+    }
+
+    impl fmt::Display for FakeDatabaseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "fake database error {}", self.code)
+        }
+    }
+
+    impl std::error::Error for FakeDatabaseError {}
+
+    impl sqlx::error::DatabaseError for FakeDatabaseError {
+        fn message(&self) -> &str {
+            "fake database error"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.code))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn db_error(code: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(FakeDatabaseError { code }))
+    }
+
+    /// マイグレーションのステートメントが失敗する順番を指定できる接続
+    #[derive(Default)]
+    struct FakeExecutor {
+        failures: VecDeque<&'static str>,
+        executed: Vec<String>,
+    }
+
+    impl FakeExecutor {
+        fn failing_with(codes: &[&'static str]) -> Self {
+            Self {
+                failures: codes.iter().copied().collect(),
+                executed: Vec::new(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StatementExecutor for FakeExecutor {
+        async fn execute_statement(&mut self, sql: &str) -> Result<(), sqlx::Error> {
+            self.executed.push(sql.to_string());
+            if sql.contains("SAVEPOINT") {
+                return Ok(());
+            }
+            match self.failures.pop_front() {
+                Some(code) => Err(db_error(code)),
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            deadline: None,
+            retry_lock_wait_timeout: true,
+        }
+    }
+
+    #[test]
+    fn test_classify_transient_code() {
+        assert_eq!(
+            classify_transient_code(Dialect::PostgreSQL, Some("40001"), None),
+            Some(TransientError::SerializationFailure)
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::PostgreSQL, Some("40P01"), None),
+            Some(TransientError::Deadlock)
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::PostgreSQL, Some("57014"), None),
+            None
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::MySQL, Some("40001"), Some(1213)),
+            Some(TransientError::Deadlock)
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::MySQL, Some("HY000"), Some(1205)),
+            Some(TransientError::LockWaitTimeout)
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::MySQL, Some("42000"), Some(1064)),
+            None
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::SQLite, Some("5"), None),
+            Some(TransientError::Busy)
+        );
+        // SQLITE_BUSY_SNAPSHOT (517) は拡張エラーコード
+        assert_eq!(
+            classify_transient_code(Dialect::SQLite, Some("517"), None),
+            Some(TransientError::Busy)
+        );
+        assert_eq!(
+            classify_transient_code(Dialect::SQLite, Some("19"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_mysql_deadlock_is_retried_only_for_implicit_commit_statements() {
+        let deadlock = TransientError::Deadlock;
+        assert!(is_retry_safe(
+            Dialect::MySQL,
+            deadlock,
+            "-- add column\nALTER TABLE users ADD COLUMN age INT"
+        ));
+        assert!(!is_retry_safe(
+            Dialect::MySQL,
+            deadlock,
+            "UPDATE users SET age = 0"
+        ));
+        assert!(is_retry_safe(
+            Dialect::MySQL,
+            TransientError::LockWaitTimeout,
+            "UPDATE users SET age = 0"
+        ));
+    }
+
+    #[test]
+    fn test_lock_wait_timeout_is_not_retried_when_lock_timeout_is_set() {
+        let policy = fast_policy(3);
+        assert!(policy.retries(TransientError::LockWaitTimeout));
+
+        let policy = fast_policy(3).with_lock_timeout(Some(5));
+        assert!(!policy.retries(TransientError::LockWaitTimeout));
+        assert!(policy.retries(TransientError::Deadlock));
+        assert!(policy.retries(TransientError::SerializationFailure));
+
+        assert!(policy
+            .with_lock_timeout(None)
+            .retries(TransientError::LockWaitTimeout));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            deadline: None,
+            retry_lock_wait_timeout: true,
+        };
+        assert_eq!(policy.backoff_after(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_after(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_after(3), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_transient_errors_are_retried_until_success() {
+        let mut executor = FakeExecutor::failing_with(&["5", "5"]);
+        let retries = execute_with_retry(
+            &mut executor,
+            Dialect::SQLite,
+            "CREATE TABLE t (id INTEGER)",
+            &fast_policy(3),
+        )
+        .await
+        .unwrap();
+        assert_eq!(retries, 2);
+        assert_eq!(executor.executed.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_at_max_attempts() {
+        let mut executor = FakeExecutor::failing_with(&["5", "5", "5"]);
+        let failure = execute_with_retry(
+            &mut executor,
+            Dialect::SQLite,
+            "CREATE TABLE t (id INTEGER)",
+            &fast_policy(2),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(failure.attempts, 2);
+        assert_eq!(executor.executed.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_non_transient_errors_fail_immediately() {
+        let mut executor = FakeExecutor::failing_with(&["42P01"]);
+        let failure = execute_with_retry(
+            &mut executor,
+            Dialect::PostgreSQL,
+            "ALTER TABLE missing ADD COLUMN a INT",
+            &fast_policy(3),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(failure.attempts, 1);
+        assert_eq!(
+            executor.executed,
+            vec![
+                "SAVEPOINT strata_statement_retry",
+                "ALTER TABLE missing ADD COLUMN a INT",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_postgres_rolls_back_to_savepoint_before_retrying() {
+        let mut executor = FakeExecutor::failing_with(&["40P01"]);
+        let retries = execute_with_retry(
+            &mut executor,
+            Dialect::PostgreSQL,
+            "UPDATE users SET age = 0",
+            &fast_policy(3),
+        )
+        .await
+        .unwrap();
+        assert_eq!(retries, 1);
+        assert_eq!(
+            executor.executed,
+            vec![
+                "SAVEPOINT strata_statement_retry",
+                "UPDATE users SET age = 0",
+                "ROLLBACK TO SAVEPOINT strata_statement_retry",
+                "SAVEPOINT strata_statement_retry",
+                "UPDATE users SET age = 0",
+                "RELEASE SAVEPOINT strata_statement_retry",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_policy_executes_once_without_savepoint() {
+        let mut executor = FakeExecutor::failing_with(&["40001"]);
+        let failure = execute_with_retry(
+            &mut executor,
+            Dialect::PostgreSQL,
+            "UPDATE users SET age = 0",
+            &RetryPolicy::disabled(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(failure.attempts, 1);
+        assert_eq!(executor.executed, vec!["UPDATE users SET age = 0"]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_is_not_scheduled_past_deadline() {
+        let mut executor = FakeExecutor::failing_with(&["5"]);
+        let policy = RetryPolicy {
+            backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(60),
+            ..fast_policy(3)
+        }
+        .with_deadline(Some(Instant::now() + Duration::from_secs(1)));
+        let failure = execute_with_retry(
+            &mut executor,
+            Dialect::SQLite,
+            "CREATE TABLE t (id INTEGER)",
+            &policy,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(failure.attempts, 1);
+    }
+}
@@ -0,0 +1,126 @@
+// データベースドライバーの可用性
+//
+// 各方言のドライバーは cargo フィーチャー（postgres / mysql / sqlite）で有効になり、
+// `sqlx::any::install_default_drivers()` で sqlx::any に登録される。
+// ドライバーを含めずにビルドしたバイナリで接続すると sqlx は
+// "no driver found for URL scheme" という原因のわかりにくいエラーを返すため、
+// 接続前に確認し、方言とフィーチャー名を示すエラーに置き換える。
+
+use crate::core::config::Dialect;
+use crate::core::error::DatabaseError;
+
+/// ドライバーを含める cargo フィーチャー名
+pub fn driver_feature(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::PostgreSQL => "postgres",
+        Dialect::MySQL => "mysql",
+        Dialect::SQLite => "sqlite",
+    }
+}
+
+/// 方言のドライバーがバイナリに含まれているか
+pub fn is_driver_compiled(dialect: Dialect) -> bool {
+    match dialect {
+        Dialect::PostgreSQL => cfg!(feature = "postgres"),
+        Dialect::MySQL => cfg!(feature = "mysql"),
+        Dialect::SQLite => cfg!(feature = "sqlite"),
+    }
+}
+
+/// バイナリに含まれているドライバーの方言一覧
+pub fn compiled_drivers() -> Vec<Dialect> {
+    [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite]
+        .into_iter()
+        .filter(|dialect| is_driver_compiled(*dialect))
+        .collect()
+}
+
+/// 方言のドライバーを使用できることを確認する
+///
+/// ドライバーがバイナリに含まれていない場合は `DatabaseError::DriverUnavailable` を返す。
+pub fn ensure_driver_compiled(dialect: Dialect) -> Result<(), DatabaseError> {
+    if is_driver_compiled(dialect) {
+        Ok(())
+    } else {
+        Err(driver_unavailable(dialect))
+    }
+}
+
+/// 接続時のエラーがドライバー未登録によるものかを判定
+pub fn is_missing_driver_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Configuration(e) => e.to_string().starts_with("no driver found for URL"),
+        _ => false,
+    }
+}
+
+/// ドライバーを使用できない場合のエラー
+///
+/// ドライバーがバイナリに含まれている場合は、sqlx::any への登録漏れとして扱う。
+pub fn driver_unavailable(dialect: Dialect) -> DatabaseError {
+    DatabaseError::DriverUnavailable {
+        dialect: dialect.to_string(),
+        feature: driver_feature(dialect).to_string(),
+        compiled: is_driver_compiled(dialect),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "postgres", feature = "mysql", feature = "sqlite"))]
+    fn test_default_build_includes_every_driver() {
+        assert_eq!(
+            compiled_drivers(),
+            vec![Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite]
+        );
+        assert!(ensure_driver_compiled(Dialect::MySQL).is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature = "mysql"))]
+    fn test_driver_not_compiled_is_rejected_before_connecting() {
+        assert!(!compiled_drivers().contains(&Dialect::MySQL));
+        let err = ensure_driver_compiled(Dialect::MySQL).unwrap_err();
+        assert!(err.to_string().contains("`mysql` cargo feature"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_driver_error_is_detected() {
+        let error = sqlx::Error::Configuration(
+            "no driver found for URL scheme \"mysql\""
+                .to_string()
+                .into(),
+        );
+        assert!(is_missing_driver_error(&error));
+
+        let other = sqlx::Error::Configuration("invalid port number".to_string().into());
+        assert!(!is_missing_driver_error(&other));
+        assert!(!is_missing_driver_error(&sqlx::Error::PoolTimedOut));
+    }
+
+    #[test]
+    fn test_driver_unavailable_message() {
+        let missing = DatabaseError::DriverUnavailable {
+            dialect: "mysql".to_string(),
+            feature: "mysql".to_string(),
+            compiled: false,
+        };
+        let message = missing.to_string();
+        assert!(message.contains("does not include the mysql driver"));
+        assert!(message.contains("--features mysql"));
+        assert!(message.contains("strata version --features"));
+        assert!(missing.is_driver_unavailable());
+
+        let not_registered = DatabaseError::DriverUnavailable {
+            dialect: "mysql".to_string(),
+            feature: "mysql".to_string(),
+            compiled: true,
+        };
+        assert!(not_registered
+            .to_string()
+            .contains("install_default_drivers()"));
+    }
+}
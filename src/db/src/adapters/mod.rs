@@ -1,10 +1,14 @@
 // Adapters
 // データベースとファイルシステムへのアクセスを抽象化
 
+pub mod column_statistics;
 pub mod connection_string;
+pub mod constraint_naming;
 pub mod database;
 pub mod database_introspector;
 pub mod database_migrator;
+pub mod drivers;
+pub mod sequence_values;
 pub mod sql_generator;
 pub mod sql_quote;
 pub mod type_mapping;
@@ -0,0 +1,402 @@
+// シーケンス値アダプター
+//
+// 環境の複製（export → generate → apply）のために、自動採番の現在値を読み取り、
+// 別のデータベースで復元するSQLを生成します。
+// - PostgreSQL: カラムが所有するシーケンスの `pg_sequences.last_value`
+// - MySQL: `information_schema.TABLES.AUTO_INCREMENT`
+// - SQLite: AUTOINCREMENT テーブルの `sqlite_sequence.seq`
+//
+// 値はすべて「最後に採番された値」として扱い、MySQL の AUTO_INCREMENT（次に採番する値）とは
+// 読み取り・復元時に変換します。
+
+use crate::adapters::database_introspector::mysql_get_string;
+use crate::adapters::sql_quote::{quote_identifier_mysql, quote_regclass_table_postgres};
+use crate::core::config::Dialect;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{AnyPool, Row};
+use std::collections::BTreeSet;
+
+/// シーケンス値ファイルのファイル名
+///
+/// export の出力先（スキーマディレクトリ）に書き出されるため、スキーマの読み込みでは無視されます。
+pub const SEQUENCE_VALUES_FILE: &str = "sequence_values.yaml";
+
+/// シーケンス値ファイルのフォーマットバージョン
+pub const SEQUENCE_VALUES_FORMAT_VERSION: &str = "1.0";
+
+/// テーブルの自動採番の現在値
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceValue {
+    /// テーブル名
+    pub table: String,
+    /// 自動採番のカラム名（PostgreSQL では復元に必須）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// 最後に採番された値
+    pub value: i64,
+}
+
+/// シーケンス値ファイル（sequence_values.yaml）
+///
+/// スキーマ定義とは別のファイルのため、通常の差分検出の対象にはなりません。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceValuesFile {
+    /// フォーマットバージョン
+    pub version: String,
+    /// テーブルごとの値（テーブル名順）
+    #[serde(default)]
+    pub sequences: Vec<SequenceValue>,
+}
+
+impl SequenceValuesFile {
+    /// 値の一覧からファイルを作成
+    pub fn new(sequences: Vec<SequenceValue>) -> Self {
+        Self {
+            version: SEQUENCE_VALUES_FORMAT_VERSION.to_string(),
+            sequences,
+        }
+    }
+
+    /// YAMLから読み込み、内容を検証
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let file: Self =
+            serde_saphyr::from_str(content).with_context(|| "Failed to parse sequence values")?;
+
+        if file.version != SEQUENCE_VALUES_FORMAT_VERSION {
+            return Err(anyhow!(
+                "Unsupported sequence values version '{}' (expected '{}')",
+                file.version,
+                SEQUENCE_VALUES_FORMAT_VERSION
+            ));
+        }
+
+        let mut seen = BTreeSet::new();
+        for sequence in &file.sequences {
+            if sequence.table.is_empty() {
+                return Err(anyhow!("Sequence value has an empty table name"));
+            }
+            if sequence.value < 1 {
+                return Err(anyhow!(
+                    "Sequence value for '{}' must be a positive integer, got {}",
+                    sequence.table,
+                    sequence.value
+                ));
+            }
+            let key = (sequence.table.as_str(), sequence.column.as_deref());
+            if !seen.insert(key) {
+                return Err(anyhow!(
+                    "Duplicate sequence value for '{}'",
+                    sequence.display_name()
+                ));
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// YAMLに変換
+    pub fn to_yaml(&self) -> Result<String> {
+        serde_saphyr::to_string(self).with_context(|| "Failed to serialize sequence values")
+    }
+}
+
+impl SequenceValue {
+    /// 表示用の名前（`table.column` または `table`）
+    pub fn display_name(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}.{}", self.table, column),
+            None => self.table.clone(),
+        }
+    }
+}
+
+/// 自動採番の現在値の読み取りと復元SQLの生成
+#[derive(Debug, Clone)]
+pub struct SequenceValueCollector {
+    dialect: Dialect,
+}
+
+impl SequenceValueCollector {
+    /// 新しいSequenceValueCollectorを作成
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect }
+    }
+
+    /// 自動採番の現在値を取得
+    ///
+    /// 一度も採番されていないシーケンスは含めません。
+    pub async fn fetch_sequence_values(&self, pool: &AnyPool) -> Result<Vec<SequenceValue>> {
+        // sqlite_sequence は AUTOINCREMENT テーブルが作成されるまで存在しない
+        if self.dialect == Dialect::SQLite && !self.has_sqlite_sequence(pool).await? {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(&self.build_fetch_query())
+            .fetch_all(pool)
+            .await
+            .with_context(|| "Failed to read sequence values")?;
+
+        rows.iter()
+            .map(|row| {
+                let sequence = match self.dialect {
+                    Dialect::PostgreSQL => SequenceValue {
+                        table: row.try_get(0)?,
+                        column: Some(row.try_get(1)?),
+                        value: row.try_get(2)?,
+                    },
+                    Dialect::MySQL => SequenceValue {
+                        table: mysql_get_string(row, 0),
+                        column: Some(mysql_get_string(row, 1)),
+                        value: row.try_get(2)?,
+                    },
+                    Dialect::SQLite => SequenceValue {
+                        table: row.try_get(0)?,
+                        column: None,
+                        value: row.try_get(1)?,
+                    },
+                };
+                Ok(sequence)
+            })
+            .collect()
+    }
+
+    /// 値を復元できるテーブルの一覧を取得
+    ///
+    /// SQLite では AUTOINCREMENT を宣言したテーブルのみが対象です。
+    /// それ以外のテーブルは最大の rowid から採番されるため、復元する値がありません。
+    pub async fn fetch_restorable_tables(&self, pool: &AnyPool) -> Result<BTreeSet<String>> {
+        let rows = sqlx::query(&self.build_restorable_tables_query())
+            .fetch_all(pool)
+            .await
+            .with_context(|| "Failed to read table names")?;
+
+        rows.iter()
+            .map(|row| match self.dialect {
+                Dialect::MySQL => Ok(mysql_get_string(row, 0)),
+                Dialect::PostgreSQL | Dialect::SQLite => Ok(row.try_get(0)?),
+            })
+            .collect()
+    }
+
+    /// 復元の対象外となったテーブルのスキップ理由
+    pub fn unrestorable_reason(&self) -> &'static str {
+        match self.dialect {
+            Dialect::PostgreSQL | Dialect::MySQL => "table does not exist",
+            Dialect::SQLite => "table does not exist or has no AUTOINCREMENT column",
+        }
+    }
+
+    /// 現在値を取得するクエリを生成
+    ///
+    /// 結果は PostgreSQL・MySQL では (テーブル名, カラム名, 値)、SQLite では (テーブル名, 値) です。
+    pub fn build_fetch_query(&self) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => r#"
+                SELECT c.relname::text, a.attname::text, s.last_value
+                FROM pg_sequences s
+                JOIN pg_namespace n ON n.nspname = s.schemaname
+                JOIN pg_class seq ON seq.relnamespace = n.oid AND seq.relname = s.sequencename
+                JOIN pg_depend d ON d.objid = seq.oid
+                    AND d.classid = 'pg_class'::regclass
+                    AND d.refclassid = 'pg_class'::regclass
+                    AND d.deptype IN ('a', 'i')
+                JOIN pg_class c ON c.oid = d.refobjid
+                JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = d.refobjsubid
+                WHERE s.schemaname = 'public'
+                    AND s.last_value IS NOT NULL
+                ORDER BY c.relname, a.attname
+            "#
+            .to_string(),
+            Dialect::MySQL => r#"
+                SELECT t.TABLE_NAME, c.COLUMN_NAME, CAST(t.AUTO_INCREMENT AS SIGNED) - 1
+                FROM information_schema.TABLES t
+                JOIN information_schema.COLUMNS c ON c.TABLE_SCHEMA = t.TABLE_SCHEMA
+                    AND c.TABLE_NAME = t.TABLE_NAME
+                    AND c.EXTRA LIKE '%auto_increment%'
+                WHERE t.TABLE_SCHEMA = DATABASE()
+                    AND t.AUTO_INCREMENT > 1
+                ORDER BY t.TABLE_NAME
+            "#
+            .to_string(),
+            Dialect::SQLite => {
+                "SELECT name, seq FROM sqlite_sequence WHERE seq > 0 ORDER BY name".to_string()
+            }
+        }
+    }
+
+    /// 値を復元できるテーブルの一覧を取得するクエリを生成
+    pub fn build_restorable_tables_query(&self) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => r#"
+                SELECT table_name::text
+                FROM information_schema.tables
+                WHERE table_schema = 'public' AND table_type = 'BASE TABLE'
+            "#
+            .to_string(),
+            Dialect::MySQL => r#"
+                SELECT TABLE_NAME
+                FROM information_schema.TABLES
+                WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'
+            "#
+            .to_string(),
+            Dialect::SQLite => r#"
+                SELECT name
+                FROM sqlite_master
+                WHERE type = 'table' AND UPPER(sql) LIKE '%AUTOINCREMENT%'
+            "#
+            .to_string(),
+        }
+    }
+
+    /// 値を復元するSQLを生成
+    ///
+    /// - PostgreSQL: `SELECT setval(pg_get_serial_sequence('"table"', 'column'), n)`
+    /// - MySQL: `ALTER TABLE ... AUTO_INCREMENT = n + 1`
+    /// - SQLite: `sqlite_sequence` の行を置き換え
+    pub fn build_restore_statements(&self, sequence: &SequenceValue) -> Result<Vec<String>> {
+        let statements = match self.dialect {
+            Dialect::PostgreSQL => {
+                let column = sequence.column.as_deref().ok_or_else(|| {
+                    anyhow!(
+                        "Sequence value for '{}' has no column; PostgreSQL needs the column that owns the sequence",
+                        sequence.table
+                    )
+                })?;
+                vec![format!(
+                    "SELECT setval(pg_get_serial_sequence({}, {}), {})",
+                    quote_regclass_table_postgres(&sequence.table),
+                    quote_string_literal(column),
+                    sequence.value
+                )]
+            }
+            Dialect::MySQL => vec![format!(
+                "ALTER TABLE {} AUTO_INCREMENT = {}",
+                quote_identifier_mysql(&sequence.table),
+                sequence.value + 1
+            )],
+            Dialect::SQLite => {
+                let table = quote_string_literal(&sequence.table);
+                vec![
+                    format!("DELETE FROM sqlite_sequence WHERE name = {}", table),
+                    format!(
+                        "INSERT INTO sqlite_sequence (name, seq) VALUES ({}, {})",
+                        table, sequence.value
+                    ),
+                ]
+            }
+        };
+        Ok(statements)
+    }
+
+    async fn has_sqlite_sequence(&self, pool: &AnyPool) -> Result<bool> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_sequence'",
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(row.try_get::<i64, _>(0)? > 0)
+    }
+}
+
+/// シングルクォートの文字列リテラル
+fn quote_string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(table: &str, column: Option<&str>, value: i64) -> SequenceValue {
+        SequenceValue {
+            table: table.to_string(),
+            column: column.map(|c| c.to_string()),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_build_restore_statements_postgres() {
+        let collector = SequenceValueCollector::new(Dialect::PostgreSQL);
+        let statements = collector
+            .build_restore_statements(&sequence("users", Some("id"), 1042))
+            .unwrap();
+
+        assert_eq!(
+            statements,
+            vec![r#"SELECT setval(pg_get_serial_sequence('"users"', 'id'), 1042)"#]
+        );
+
+        let err = collector
+            .build_restore_statements(&sequence("users", None, 1042))
+            .unwrap_err();
+        assert!(err.to_string().contains("no column"), "{}", err);
+    }
+
+    #[test]
+    fn test_build_restore_statements_mysql() {
+        let collector = SequenceValueCollector::new(Dialect::MySQL);
+        let statements = collector
+            .build_restore_statements(&sequence("odd`table", Some("id"), 1042))
+            .unwrap();
+
+        // AUTO_INCREMENT は次に採番する値
+        assert_eq!(
+            statements,
+            vec!["ALTER TABLE `odd``table` AUTO_INCREMENT = 1043"]
+        );
+    }
+
+    #[test]
+    fn test_build_restore_statements_sqlite() {
+        let collector = SequenceValueCollector::new(Dialect::SQLite);
+        let statements = collector
+            .build_restore_statements(&sequence("it's", None, 7))
+            .unwrap();
+
+        assert_eq!(
+            statements,
+            vec![
+                "DELETE FROM sqlite_sequence WHERE name = 'it''s'",
+                "INSERT INTO sqlite_sequence (name, seq) VALUES ('it''s', 7)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequence_values_file_round_trip() {
+        let file = SequenceValuesFile::new(vec![
+            sequence("posts", Some("id"), 12),
+            sequence("users", None, 1042),
+        ]);
+
+        let yaml = file.to_yaml().unwrap();
+        assert!(
+            yaml.contains("version: '1.0'") || yaml.contains("version: \"1.0\""),
+            "{}",
+            yaml
+        );
+        assert_eq!(SequenceValuesFile::from_yaml(&yaml).unwrap(), file);
+    }
+
+    #[test]
+    fn test_sequence_values_file_validation() {
+        let err = SequenceValuesFile::from_yaml("version: \"2.0\"\nsequences: []\n").unwrap_err();
+        assert!(err.to_string().contains("Unsupported"), "{}", err);
+
+        let err = SequenceValuesFile::from_yaml(
+            "version: \"1.0\"\nsequences:\n  - table: users\n    value: 0\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("positive integer"), "{}", err);
+
+        let err = SequenceValuesFile::from_yaml(
+            "version: \"1.0\"\nsequences:\n  - table: users\n    value: 1\n  - table: users\n    value: 2\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Duplicate"), "{}", err);
+
+        assert!(SequenceValuesFile::from_yaml("sequences: []\n").is_err());
+    }
+}
@@ -7,95 +7,92 @@ pub mod postgres;
 pub mod sqlite;
 pub mod sqlite_table_recreator;
 
-use crate::core::error::ValidationError;
-use crate::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Index, Table};
-use crate::core::schema_diff::{ColumnDiff, EnumDiff, RenamedColumn};
-use sha2::{Digest, Sha256};
+use crate::core::config::{Dialect, IdentifierCase};
+use crate::core::error::{ErrorLocation, ValidationError};
+use crate::core::schema::{
+    is_current_timestamp_default, Column, ColumnType, Constraint, EnumDefinition, Index,
+    IndexColumn, Table, CURRENT_TIMESTAMP_DEFAULT,
+};
+use crate::core::schema_diff::{ColumnDiff, EnumDiff, RenamedColumn, RenamedIndex};
+
+use crate::adapters::constraint_naming;
 
 // sql_quoteモジュールから識別子クォート関数を再エクスポート
 pub(crate) use crate::adapters::sql_quote::{
     quote_columns_mysql, quote_columns_postgres, quote_columns_sqlite, quote_identifier_mysql,
-    quote_identifier_postgres, quote_identifier_sqlite, quote_regclass_postgres,
+    quote_identifier_postgres, quote_identifier_sqlite, quote_regclass_table_postgres,
+    quote_table_postgres,
 };
 
-/// PostgreSQL/MySQLの識別子最大長
-const MAX_IDENTIFIER_LENGTH: usize = 63;
-
-/// 制約名を生成する共通ヘルパー
-///
-/// `{prefix}_{body}`形式で名前を組み立て、63文字（`MAX_IDENTIFIER_LENGTH`）を超える場合は
-/// SHA-256ハッシュ付きで切り詰めます。
-///
-/// # Arguments
-///
-/// * `prefix` - 制約名のプレフィックス（例: "fk", "uq", "ck"）
-/// * `body` - プレフィックス以降の本体部分（テーブル名、カラム名等を結合済み）
-///
-/// # Returns
+/// インデックスカラムリストを生成する共通ヘルパー
 ///
-/// 63文字以内の制約名
-fn generate_constraint_name(prefix: &str, body: &str) -> String {
-    let base_name = format!("{}_{}", prefix, body);
-
-    if base_name.len() <= MAX_IDENTIFIER_LENGTH {
-        return base_name;
-    }
-
-    // 長すぎる場合はハッシュを付けて切り詰める
-    // ハッシュは元の完全な名前から生成するため、同じ入力には同じ出力を保証
-    let mut hasher = Sha256::new();
-    hasher.update(base_name.as_bytes());
-    let hash = hasher.finalize();
-    let hash_suffix = format!(
-        "_{:x}",
-        &hash[..4].iter().fold(0u32, |acc, &b| acc << 8 | b as u32)
-    );
-
-    let prefix_with_sep = format!("{}_", prefix);
-    let available_length = MAX_IDENTIFIER_LENGTH - prefix_with_sep.len() - hash_suffix.len();
+/// 各カラムをクォートし、プレフィックス長（`col(191)`）とソート順（ASC/DESC）を付与します。
+/// `include_nulls` がfalseの場合、NULL順序（NULLS FIRST/LAST）は出力しません。
+/// プレフィックス長はMySQL以外ではバリデーションで拒否されるため、ここでは区別しません。
+pub(crate) fn format_index_columns(
+    columns: &[IndexColumn],
+    quote: impl Fn(&str) -> String,
+    include_nulls: bool,
+) -> String {
+    columns
+        .iter()
+        .map(|column| {
+            let mut name = quote(&column.name);
+            if let Some(length) = column.length {
+                name.push_str(&format!("({})", length));
+            }
+            let mut parts = vec![name];
+            if let Some(order) = column.order {
+                parts.push(order.as_sql().to_string());
+            }
+            if let (true, Some(nulls)) = (include_nulls, column.nulls) {
+                parts.push(nulls.as_sql().to_string());
+            }
+            parts.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
-    if body.len() <= available_length {
-        format!("{}{}{}", prefix_with_sep, body, hash_suffix)
-    } else {
-        format!(
-            "{}{}{}",
-            prefix_with_sep,
-            &body[..available_length],
-            hash_suffix
-        )
+/// 部分インデックスの WHERE 句を生成する共通ヘルパー
+///
+/// 条件式がない場合は空文字列を返します。
+pub(crate) fn index_where_clause(index: &Index) -> String {
+    match index.where_clause.as_deref().map(str::trim) {
+        Some(predicate) if !predicate.is_empty() => format!(" WHERE {}", predicate),
+        Some(_) | None => String::new(),
     }
 }
 
-/// 外部キー制約名を生成
-///
-/// `fk_{table_name}_{columns}_{referenced_table}`形式で名前を生成します。
-/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
-pub(crate) fn generate_fk_constraint_name(
+/// 外部キー制約名を生成（旧パス）
+#[deprecated(
+    since = "0.2.0",
+    note = "use strata_db::adapters::constraint_naming::generate_fk_constraint_name"
+)]
+pub fn generate_fk_constraint_name(
     table_name: &str,
     columns: &[String],
     referenced_table: &str,
 ) -> String {
-    let body = format!("{}_{}", table_name, columns.join("_"));
-    let body_with_ref = format!("{}_{}", body, referenced_table);
-    generate_constraint_name("fk", &body_with_ref)
+    constraint_naming::generate_fk_constraint_name(table_name, columns, referenced_table)
 }
 
-/// UNIQUE制約名を生成
-///
-/// `uq_{table_name}_{columns}`形式で名前を生成します。
-/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
-pub(crate) fn generate_uq_constraint_name(table_name: &str, columns: &[String]) -> String {
-    let body = format!("{}_{}", table_name, columns.join("_"));
-    generate_constraint_name("uq", &body)
+/// UNIQUE制約名を生成（旧パス）
+#[deprecated(
+    since = "0.2.0",
+    note = "use strata_db::adapters::constraint_naming::generate_uq_constraint_name"
+)]
+pub fn generate_uq_constraint_name(table_name: &str, columns: &[String]) -> String {
+    constraint_naming::generate_uq_constraint_name(table_name, columns)
 }
 
-/// CHECK制約名を生成
-///
-/// `ck_{table_name}_{columns}`形式で名前を生成します。
-/// 63文字を超える場合は、末尾にハッシュを付けて切り詰めます。
-pub(crate) fn generate_ck_constraint_name(table_name: &str, columns: &[String]) -> String {
-    let body = format!("{}_{}", table_name, columns.join("_"));
-    generate_constraint_name("ck", &body)
+/// CHECK制約名を生成（旧パス）
+#[deprecated(
+    since = "0.2.0",
+    note = "use strata_db::adapters::constraint_naming::generate_ck_constraint_name"
+)]
+pub fn generate_ck_constraint_name(table_name: &str, columns: &[String]) -> String {
+    constraint_naming::generate_ck_constraint_name(table_name, columns)
 }
 
 /// CHECK式のバリデーション
@@ -106,6 +103,7 @@ pub(crate) fn generate_ck_constraint_name(table_name: &str, columns: &[String])
 /// # Returns
 ///
 /// バリデーションに失敗した場合はエラーメッセージを返します。
+#[allow(clippy::result_large_err)]
 pub(crate) fn validate_check_expression(expr: &str) -> Result<(), ValidationError> {
     // 大文字に正規化してキーワードを検査
     let upper = expr.to_uppercase();
@@ -159,6 +157,106 @@ pub(crate) fn format_check_constraint(expr: &str) -> String {
     }
 }
 
+/// populate_from で許可しないDML/DDLキーワード
+const POPULATE_FORBIDDEN_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "MERGE", "DROP", "ALTER", "CREATE", "TRUNCATE", "RENAME",
+    "GRANT", "REVOKE", "EXEC", "EXECUTE", "CALL",
+];
+
+/// テーブルの populate_from（データ投入用SELECT文）のバリデーション
+///
+/// `INSERT INTO ... SELECT ...` として up.sql に埋め込まれるため、
+/// SELECT / WITH で始まる単一の問い合わせのみを許可します。
+/// 破壊的操作やステートメント区切りを含む式は拒否します。
+#[allow(clippy::result_large_err)]
+pub(crate) fn validate_populate_expression(
+    table_name: &str,
+    expr: &str,
+) -> Result<(), ValidationError> {
+    let error = |message: String, suggestion: &str| ValidationError::Constraint {
+        message,
+        location: Some(ErrorLocation::with_table(table_name.to_string())),
+        suggestion: Some(suggestion.to_string()),
+    };
+
+    // 文字列リテラルやクォートされた識別子の中身は判定対象から除外する
+    let upper = mask_quoted_spans(expr.trim()).to_uppercase();
+    // 識別子の一部（updated_at 等）を誤検出しないよう、単語単位で分割
+    let mut tokens = upper
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|token| !token.is_empty());
+
+    match tokens.next() {
+        None => {
+            return Err(error(
+                format!("Table '{}' has an empty populate_from", table_name),
+                "Provide a SELECT statement or remove populate_from",
+            ));
+        }
+        Some("SELECT") | Some("WITH") => {}
+        Some(_) => {
+            return Err(error(
+                format!(
+                    "Table '{}' populate_from must start with SELECT or WITH",
+                    table_name
+                ),
+                "Write populate_from as a single SELECT statement",
+            ));
+        }
+    }
+
+    if upper.contains(';') {
+        return Err(error(
+            format!("Table '{}' populate_from contains a semicolon", table_name),
+            "Remove the semicolon; populate_from must be a single SELECT statement",
+        ));
+    }
+
+    if let Some(keyword) = tokens.find(|token| POPULATE_FORBIDDEN_KEYWORDS.contains(token)) {
+        return Err(error(
+            format!(
+                "Table '{}' populate_from contains forbidden keyword '{}'",
+                table_name, keyword
+            ),
+            "populate_from may only read data; move other statements to a separate migration",
+        ));
+    }
+
+    Ok(())
+}
+
+/// 文字列リテラル（`'...'`）とクォートされた識別子（`"..."` / `` `...` ``）の中身を空白に置き換える
+///
+/// 連続したクォート（`''` など）によるエスケープに対応します。閉じられていないクォートは末尾まで置き換えます。
+fn mask_quoted_spans(sql: &str) -> String {
+    let mut masked = String::with_capacity(sql.len());
+    let mut quote: Option<char> = None;
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            None => {
+                if matches!(c, '\'' | '"' | '`') {
+                    quote = Some(c);
+                }
+                masked.push(c);
+            }
+            Some(q) if c == q => {
+                if chars.peek() == Some(&q) {
+                    chars.next();
+                    masked.push_str("  ");
+                } else {
+                    quote = None;
+                    masked.push(c);
+                }
+            }
+            Some(_) => masked.push(' '),
+        }
+    }
+
+    masked
+}
+
 /// SQLコメント内に埋め込む文字列をサニタイズ
 ///
 /// `*/` を `* /` に置換して、SQLコメント `/* ... */` が壊れるのを防ぎます。
@@ -166,6 +264,53 @@ pub(crate) fn sanitize_sql_comment(s: &str) -> String {
     s.replace("*/", "* /")
 }
 
+/// コメントを `'...'` の文字列リテラルにする
+///
+/// シングルクォートは `''` にエスケープします。
+/// MySQLはバックスラッシュもエスケープ文字として扱うため、`escape_backslash` で `\\` にします。
+pub(crate) fn quote_comment_literal(comment: &str, escape_backslash: bool) -> String {
+    let escaped = if escape_backslash {
+        comment.replace('\\', "\\\\")
+    } else {
+        comment.to_string()
+    };
+    format!("'{}'", escaped.replace('\'', "''"))
+}
+
+/// 秒の小数部の桁数（TIMESTAMP型またはDATETIMEなどの方言固有型で1以上の場合のみ）
+pub(crate) fn fractional_seconds_precision(column_type: &ColumnType) -> Option<u64> {
+    let precision = match column_type {
+        ColumnType::TIMESTAMP { precision, .. } => precision.map(u64::from),
+        ColumnType::DialectSpecific { params, .. } => {
+            params.get("precision").and_then(|p| p.as_u64())
+        }
+        _ => None,
+    };
+    precision.filter(|&precision| precision > 0)
+}
+
+/// デフォルト値を方言ごとの表記で出力
+///
+/// 現在日時を表す式（`CURRENT_TIMESTAMP`・`now()`・`datetime('now')` など）は
+/// どの方言でも受け付けられる `CURRENT_TIMESTAMP` に揃えます。MySQLは秒の小数部の桁数が
+/// カラムと一致することを要求するため、桁数を指定したカラムでは `CURRENT_TIMESTAMP(6)` とします。
+/// それ以外のデフォルト値はそのまま出力します。
+pub(crate) fn render_default_value(
+    value: &str,
+    column_type: &ColumnType,
+    dialect: Dialect,
+) -> String {
+    if !is_current_timestamp_default(value) {
+        return value.to_string();
+    }
+    match (dialect, fractional_seconds_precision(column_type)) {
+        (Dialect::MySQL, Some(precision)) => {
+            format!("{}({})", CURRENT_TIMESTAMP_DEFAULT, precision)
+        }
+        _ => CURRENT_TIMESTAMP_DEFAULT.to_string(),
+    }
+}
+
 /// カラム定義の共通組み立てヘルパー
 ///
 /// # Arguments
@@ -174,17 +319,24 @@ pub(crate) fn sanitize_sql_comment(s: &str) -> String {
 /// * `column` - カラム定義（nullable, default_valueなどを参照）
 /// * `type_str` - SQL型文字列
 /// * `extra_parts` - 追加の修飾子（AUTO_INCREMENTなど）
+/// * `dialect` - デフォルト値の表記を決める方言
 pub(crate) fn build_column_definition(
     quoted_name: &str,
     column: &Column,
     type_str: String,
     extra_parts: &[&str],
+    dialect: Dialect,
 ) -> String {
     let mut parts = Vec::new();
 
     parts.push(quoted_name.to_string());
     parts.push(type_str);
 
+    // MySQLは生成句を型の直後に要求するため、NOT NULLより前に置く
+    if let Some(ref generated) = column.generated {
+        parts.push(generated.to_sql_clause());
+    }
+
     if !column.nullable {
         parts.push("NOT NULL".to_string());
     }
@@ -196,7 +348,10 @@ pub(crate) fn build_column_definition(
     }
 
     if let Some(ref default_value) = column.default_value {
-        parts.push(format!("DEFAULT {}", default_value));
+        parts.push(format!(
+            "DEFAULT {}",
+            render_default_value(default_value, &column.column_type, dialect)
+        ));
     }
 
     parts.join(" ")
@@ -270,6 +425,22 @@ pub trait SqlGenerator {
     // デフォルト実装付きメソッド
     // ===========================================
 
+    /// テーブル名をクォート
+    ///
+    /// デフォルト: 識別子としてクォート。
+    /// PostgreSQLはオーバーライドして `schema.table` 形式の修飾名に対応。
+    fn quote_table_name(&self, name: &str) -> String {
+        self.quote_identifier(name)
+    }
+
+    /// CREATE INDEX用のカラムリストを生成
+    ///
+    /// デフォルト: ソート順のみ出力（MySQL 8+ / SQLiteはNULL順序に非対応）。
+    /// PostgreSQLはオーバーライドしてNULL順序も出力。
+    fn quote_index_columns(&self, columns: &[IndexColumn]) -> String {
+        format_index_columns(columns, |name| self.quote_identifier(name), false)
+    }
+
     /// テーブル制約としてCREATE TABLE内に含めるかの判定
     ///
     /// デフォルト: FOREIGN_KEY以外はtrue。
@@ -284,7 +455,7 @@ pub trait SqlGenerator {
 
         parts.push(format!(
             "CREATE TABLE {}",
-            self.quote_identifier(&table.name)
+            self.quote_table_name(&table.name)
         ));
         parts.push("(".to_string());
 
@@ -320,11 +491,12 @@ pub trait SqlGenerator {
         };
 
         format!(
-            "CREATE {} {} ON {} ({})",
+            "CREATE {} {} ON {} ({}){}",
             index_type,
             self.quote_identifier(&index.name),
-            self.quote_identifier(&table.name),
-            self.quote_columns(&index.columns)
+            self.quote_table_name(&table.name),
+            self.quote_index_columns(&index.columns),
+            index_where_clause(index)
         )
     }
 
@@ -332,7 +504,7 @@ pub trait SqlGenerator {
     fn generate_add_column(&self, table_name: &str, column: &Column) -> String {
         format!(
             "ALTER TABLE {} ADD COLUMN {}",
-            self.quote_identifier(table_name),
+            self.quote_table_name(table_name),
             self.generate_column_definition(column)
         )
     }
@@ -341,14 +513,14 @@ pub trait SqlGenerator {
     fn generate_drop_column(&self, table_name: &str, column_name: &str) -> String {
         format!(
             "ALTER TABLE {} DROP COLUMN {}",
-            self.quote_identifier(table_name),
+            self.quote_table_name(table_name),
             self.quote_identifier(column_name)
         )
     }
 
     /// DROP TABLE文を生成
     fn generate_drop_table(&self, table_name: &str) -> String {
-        format!("DROP TABLE {}", self.quote_identifier(table_name))
+        format!("DROP TABLE {}", self.quote_table_name(table_name))
     }
 
     /// DROP INDEX文を生成
@@ -371,11 +543,32 @@ pub trait SqlGenerator {
     fn generate_rename_table(&self, old_name: &str, new_name: &str) -> String {
         format!(
             "ALTER TABLE {} RENAME TO {}",
-            self.quote_identifier(old_name),
+            self.quote_table_name(old_name),
             self.quote_identifier(new_name)
         )
     }
 
+    /// populate_from によるデータ投入SQL文を生成
+    ///
+    /// 出所を示すコメントを付けた `INSERT INTO ... (列, ...) SELECT ...` を返します。
+    /// 列の一覧はテーブル定義のカラム順で、値を投入できない生成カラムは含めません。
+    /// SELECTの列はこの順序で対応付けられます。
+    fn generate_populate_table(&self, table: &Table, select: &str) -> String {
+        let columns: Vec<String> = table
+            .columns
+            .iter()
+            .filter(|column| column.generated.is_none())
+            .map(|column| self.quote_identifier(&column.name))
+            .collect();
+        format!(
+            "-- Populate '{}' from populate_from in schema definition\nINSERT INTO {} ({})\n{}",
+            table.name,
+            self.quote_table_name(&table.name),
+            columns.join(", "),
+            select.trim()
+        )
+    }
+
     /// DOWN時に復元が必要なテーブルの注意コメントを生成
     fn generate_missing_table_notice(&self, table_name: &str) -> String {
         format!(
@@ -401,15 +594,20 @@ pub trait SqlGenerator {
                     on_delete,
                     on_update,
                 } => {
-                    let constraint_name =
-                        generate_fk_constraint_name(&table.name, columns, referenced_table);
+                    let constraint_name = self.identifier_case().apply(
+                        &constraint_naming::generate_fk_constraint_name(
+                            &table.name,
+                            columns,
+                            referenced_table,
+                        ),
+                    );
 
                     let mut sql = format!(
                         "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
-                        self.quote_identifier(&table.name),
+                        self.quote_table_name(&table.name),
                         self.quote_identifier(&constraint_name),
                         self.quote_columns(columns),
-                        self.quote_identifier(referenced_table),
+                        self.quote_table_name(referenced_table),
                         self.quote_columns(referenced_columns)
                     );
 
@@ -542,6 +740,46 @@ pub trait SqlGenerator {
         Vec::new()
     }
 
+    /// 生成SQLの識別子の大文字・小文字の規則
+    ///
+    /// 自動生成する制約名（`fk_` / `uq_` / `ck_`）に適用します。
+    fn identifier_case(&self) -> IdentifierCase {
+        IdentifierCase::Preserve
+    }
+
+    /// インデックスリネームSQL生成
+    ///
+    /// デフォルト実装はインデックスのリネーム構文を持たない方言（SQLite）向けで、
+    /// 旧インデックスを削除して新しい名前で作成し直します。
+    /// リネーム構文を持つ方言はオーバーライドしてください。
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - インデックスが属するテーブル
+    /// * `renamed_index` - リネームされたインデックス情報
+    /// * `direction` - マイグレーション方向（Up: old→new, Down: new→old）
+    fn generate_rename_index(
+        &self,
+        table: &Table,
+        renamed_index: &RenamedIndex,
+        direction: MigrationDirection,
+    ) -> Vec<String> {
+        let (from_name, to_index) = match direction {
+            MigrationDirection::Up => (&renamed_index.old_name, &renamed_index.new_index),
+            MigrationDirection::Down => (&renamed_index.new_index.name, &renamed_index.old_index),
+        };
+
+        vec![
+            format!(
+                "-- NOTE: Index rename is not supported; recreating '{}' as '{}'\n{}",
+                from_name,
+                to_index.name,
+                self.generate_drop_index(&table.name, from_name)
+            ),
+            self.generate_create_index(table, to_index),
+        ]
+    }
+
     /// カラムのNULL制約変更SQL生成
     ///
     /// # Arguments
@@ -574,6 +812,54 @@ pub trait SqlGenerator {
         Vec::new()
     }
 
+    // ===========================================
+    // コメント関連メソッド
+    // ===========================================
+
+    /// カラムコメントをカラム定義に含めるか
+    ///
+    /// MySQLはカラム定義の `COMMENT '...'` で指定するため、
+    /// カラムの追加・変更時に別途コメントを設定する必要がない。
+    fn inline_column_comments(&self) -> bool {
+        false
+    }
+
+    /// テーブルコメントの設定SQL生成
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - テーブル名
+    /// * `comment` - 新しいコメント（Noneの場合はコメントを削除）
+    fn generate_table_comment(&self, _table_name: &str, _comment: Option<&str>) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// カラムコメントの設定SQL生成
+    ///
+    /// `column.comment` がNoneの場合はコメントを削除する。
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - テーブル名
+    /// * `column` - 対象のカラム（MySQL用の完全な定義を含む）
+    fn generate_column_comment(&self, _table_name: &str, _column: &Column) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// CREATE TABLE後に設定するテーブル・カラムコメントのSQL生成
+    fn generate_create_table_comments(&self, table: &Table) -> Vec<String> {
+        let mut statements = Vec::new();
+        if let Some(comment) = table.comment_text() {
+            statements.extend(self.generate_table_comment(&table.name, Some(comment)));
+        }
+        if !self.inline_column_comments() {
+            for column in table.columns.iter().filter(|c| c.comment_text().is_some()) {
+                statements.extend(self.generate_column_comment(&table.name, column));
+            }
+        }
+        statements
+    }
+
     /// ENUM型の作成（PostgreSQL専用）
     fn generate_create_enum_type(&self, _enum_def: &EnumDefinition) -> Vec<String> {
         Vec::new()
@@ -594,6 +880,11 @@ pub trait SqlGenerator {
         Vec::new()
     }
 
+    /// ENUMリネーム（PostgreSQL専用）
+    fn generate_rename_enum_type(&self, _old_name: &str, _new_name: &str) -> Vec<String> {
+        Vec::new()
+    }
+
     // ===========================================
     // ビュー関連メソッド
     // ===========================================
@@ -628,8 +919,9 @@ pub trait SqlGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::schema::{Column, Constraint};
+    use crate::core::schema::{Column, Constraint, GeneratedColumn};
     use crate::core::schema_diff::RenamedColumn;
+    use std::collections::BTreeMap;
 
     // ダミー実装（デフォルト実装のテスト用）
     struct DummySqlGenerator;
@@ -723,220 +1015,6 @@ mod tests {
         );
     }
 
-    // ==========================================
-    // generate_fk_constraint_name のテスト
-    // ==========================================
-
-    #[test]
-    fn test_generate_fk_constraint_name_short() {
-        // 63文字以下の場合はそのまま返す
-        let name = generate_fk_constraint_name("posts", &["user_id".to_string()], "users");
-        assert_eq!(name, "fk_posts_user_id_users");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_fk_constraint_name_composite() {
-        // 複合キーの場合
-        let name = generate_fk_constraint_name(
-            "order_items",
-            &["order_id".to_string(), "product_id".to_string()],
-            "orders",
-        );
-        assert_eq!(name, "fk_order_items_order_id_product_id_orders");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_fk_constraint_name_truncated() {
-        // 63文字を超える場合はハッシュ付きで切り詰め
-        let name = generate_fk_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string(), "department_id".to_string()],
-            "another_very_long_table_name_here",
-        );
-
-        // 63文字以下であることを確認
-        assert!(
-            name.len() <= 63,
-            "Constraint name '{}' exceeds 63 characters (len={})",
-            name,
-            name.len()
-        );
-
-        // fk_プレフィックスで始まることを確認
-        assert!(name.starts_with("fk_"));
-
-        // ハッシュサフィックスが付いていることを確認（_xxxxxxxx形式）
-        assert!(name.contains("_"), "Expected hash suffix in '{}'", name);
-    }
-
-    #[test]
-    fn test_generate_fk_constraint_name_deterministic() {
-        // 同じ入力には同じ出力を保証
-        let name1 = generate_fk_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-            "another_very_long_table_name_here",
-        );
-        let name2 = generate_fk_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-            "another_very_long_table_name_here",
-        );
-        assert_eq!(name1, name2);
-    }
-
-    // ==========================================
-    // generate_uq_constraint_name のテスト
-    // ==========================================
-
-    #[test]
-    fn test_generate_uq_constraint_name_short() {
-        // 63文字以下の場合はそのまま返す
-        let name = generate_uq_constraint_name("users", &["email".to_string()]);
-        assert_eq!(name, "uq_users_email");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_uq_constraint_name_composite() {
-        // 複合カラムの場合
-        let name = generate_uq_constraint_name(
-            "order_items",
-            &["order_id".to_string(), "product_id".to_string()],
-        );
-        assert_eq!(name, "uq_order_items_order_id_product_id");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_uq_constraint_name_truncated() {
-        // 63文字を超える場合はハッシュ付きで切り詰め
-        let name = generate_uq_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &[
-                "organization_id".to_string(),
-                "department_id".to_string(),
-                "another_long_column".to_string(),
-            ],
-        );
-
-        assert!(
-            name.len() <= 63,
-            "Constraint name '{}' exceeds 63 characters (len={})",
-            name,
-            name.len()
-        );
-        assert!(name.starts_with("uq_"));
-    }
-
-    #[test]
-    fn test_generate_uq_constraint_name_deterministic() {
-        // 同じ入力には同じ出力を保証
-        let name1 = generate_uq_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-        );
-        let name2 = generate_uq_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-        );
-        assert_eq!(name1, name2);
-    }
-
-    // ==========================================
-    // generate_ck_constraint_name のテスト
-    // ==========================================
-
-    #[test]
-    fn test_generate_ck_constraint_name_short() {
-        // 63文字以下の場合はそのまま返す
-        let name = generate_ck_constraint_name("users", &["age".to_string()]);
-        assert_eq!(name, "ck_users_age");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_ck_constraint_name_composite() {
-        // 複合カラムの場合
-        let name =
-            generate_ck_constraint_name("products", &["price".to_string(), "discount".to_string()]);
-        assert_eq!(name, "ck_products_price_discount");
-        assert!(name.len() <= 63);
-    }
-
-    #[test]
-    fn test_generate_ck_constraint_name_truncated() {
-        // 63文字を超える場合はハッシュ付きで切り詰め
-        let name = generate_ck_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &[
-                "organization_id".to_string(),
-                "department_id".to_string(),
-                "another_long_column".to_string(),
-            ],
-        );
-
-        assert!(
-            name.len() <= 63,
-            "Constraint name '{}' exceeds 63 characters (len={})",
-            name,
-            name.len()
-        );
-        assert!(name.starts_with("ck_"));
-    }
-
-    #[test]
-    fn test_generate_ck_constraint_name_deterministic() {
-        // 同じ入力には同じ出力を保証
-        let name1 = generate_ck_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-        );
-        let name2 = generate_ck_constraint_name(
-            "very_long_table_name_with_many_characters",
-            &["organization_id".to_string()],
-        );
-        assert_eq!(name1, name2);
-    }
-
-    // ==========================================
-    // generate_constraint_name 共通ヘルパーのテスト
-    // ==========================================
-
-    #[test]
-    fn test_different_prefixes_produce_different_names() {
-        // uq_ と ck_ は同じ入力でも異なる名前を生成する
-        let uq_name = generate_uq_constraint_name("users", &["email".to_string()]);
-        let ck_name = generate_ck_constraint_name("users", &["email".to_string()]);
-        assert_ne!(uq_name, ck_name);
-        assert!(uq_name.starts_with("uq_"));
-        assert!(ck_name.starts_with("ck_"));
-    }
-
-    #[test]
-    fn test_generate_fk_constraint_name_different_inputs_different_outputs() {
-        // 異なる入力には異なる出力（ハッシュが異なる）
-        let name1 = generate_fk_constraint_name(
-            "very_long_table_name_with_many_characters_a",
-            &["column_id".to_string()],
-            "referenced_table",
-        );
-        let name2 = generate_fk_constraint_name(
-            "very_long_table_name_with_many_characters_b",
-            &["column_id".to_string()],
-            "referenced_table",
-        );
-
-        // 両方63文字以下
-        assert!(name1.len() <= 63);
-        assert!(name2.len() <= 63);
-
-        // 異なる名前が生成される
-        assert_ne!(name1, name2);
-    }
-
     // ==========================================
     // validate_check_expression のテスト
     // ==========================================
@@ -1027,6 +1105,73 @@ mod tests {
         assert!(validate_check_expression("created_by IS NOT NULL").is_ok());
     }
 
+    // ==========================================
+    // validate_populate_expression のテスト
+    // ==========================================
+
+    #[test]
+    fn test_validate_populate_expression_accepts_select_and_with() {
+        assert!(validate_populate_expression("t", "SELECT id, updated_at FROM users").is_ok());
+        assert!(validate_populate_expression(
+            "t",
+            "WITH recent AS (SELECT id FROM users) SELECT id FROM recent"
+        )
+        .is_ok());
+        assert!(validate_populate_expression("t", "\n  select created_by from users\n").is_ok());
+    }
+
+    #[test]
+    fn test_validate_populate_expression_rejects_non_select() {
+        assert!(validate_populate_expression("t", "").is_err());
+        assert!(validate_populate_expression("t", "VALUES (1)").is_err());
+        assert!(validate_populate_expression("t", "DELETE FROM users").is_err());
+    }
+
+    #[test]
+    fn test_validate_populate_expression_rejects_semicolon() {
+        let result = validate_populate_expression("t", "SELECT 1; SELECT 2");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_populate_expression_rejects_ddl_and_dml_keywords() {
+        for expr in [
+            "SELECT * FROM users WHERE id IN (DELETE FROM t RETURNING id)",
+            "WITH x AS (UPDATE users SET a = 1 RETURNING *) SELECT * FROM x",
+            "SELECT 1 FROM users,drop",
+            "select * from t where exists (truncate t)",
+        ] {
+            let err = validate_populate_expression("reports", expr).unwrap_err();
+            assert!(
+                err.to_string().contains("forbidden keyword"),
+                "unexpected error for {}: {}",
+                expr,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_populate_expression_ignores_quoted_spans() {
+        assert!(validate_populate_expression(
+            "t",
+            "SELECT id FROM notes WHERE note = 'drop; later'"
+        )
+        .is_ok());
+        assert!(validate_populate_expression("t", "SELECT id, 'it''s; fine' FROM notes").is_ok());
+        assert!(
+            validate_populate_expression("t", r#"SELECT "delete", `update` FROM audit"#).is_ok()
+        );
+
+        // クォートの外にあるものは引き続き拒否する
+        assert!(validate_populate_expression("t", "SELECT 'a'; DROP TABLE users").is_err());
+        assert!(validate_populate_expression(
+            "t",
+            r#"SELECT "delete" FROM t WHERE x IN (DELETE FROM t)"#
+        )
+        .is_err());
+    }
+
     // ==========================================
     // format_check_constraint のテスト
     // ==========================================
@@ -1090,7 +1235,13 @@ mod tests {
             ColumnType::VARCHAR { length: 100 },
             false,
         );
-        let result = build_column_definition("\"name\"", &column, "VARCHAR(100)".to_string(), &[]);
+        let result = build_column_definition(
+            "\"name\"",
+            &column,
+            "VARCHAR(100)".to_string(),
+            &[],
+            Dialect::PostgreSQL,
+        );
         assert_eq!(result, "\"name\" VARCHAR(100) NOT NULL");
     }
 
@@ -1101,7 +1252,13 @@ mod tests {
             ColumnType::VARCHAR { length: 255 },
             true,
         );
-        let result = build_column_definition("\"email\"", &column, "VARCHAR(255)".to_string(), &[]);
+        let result = build_column_definition(
+            "\"email\"",
+            &column,
+            "VARCHAR(255)".to_string(),
+            &[],
+            Dialect::PostgreSQL,
+        );
         assert_eq!(result, "\"email\" VARCHAR(255)");
     }
 
@@ -1113,7 +1270,13 @@ mod tests {
             false,
         );
         column.default_value = Some("'active'".to_string());
-        let result = build_column_definition("\"status\"", &column, "VARCHAR(20)".to_string(), &[]);
+        let result = build_column_definition(
+            "\"status\"",
+            &column,
+            "VARCHAR(20)".to_string(),
+            &[],
+            Dialect::PostgreSQL,
+        );
         assert_eq!(result, "\"status\" VARCHAR(20) NOT NULL DEFAULT 'active'");
     }
 
@@ -1129,6 +1292,7 @@ mod tests {
             &column,
             "INTEGER".to_string(),
             &["AUTO_INCREMENT"],
+            Dialect::MySQL,
         );
         assert_eq!(result, "\"id\" INTEGER NOT NULL AUTO_INCREMENT");
     }
@@ -1145,10 +1309,93 @@ mod tests {
             &column,
             "INTEGER".to_string(),
             &["", "PRIMARY KEY", ""],
+            Dialect::PostgreSQL,
         );
         assert_eq!(result, "\"id\" INTEGER NOT NULL PRIMARY KEY");
     }
 
+    #[test]
+    fn test_build_column_definition_generated_before_not_null() {
+        let mut column = Column::new(
+            "total".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        column.generated = Some(GeneratedColumn::new("price * quantity", true));
+        let result = build_column_definition(
+            "\"total\"",
+            &column,
+            "INTEGER".to_string(),
+            &[],
+            Dialect::PostgreSQL,
+        );
+        assert_eq!(
+            result,
+            "\"total\" INTEGER GENERATED ALWAYS AS (price * quantity) STORED NOT NULL"
+        );
+
+        column.generated = Some(GeneratedColumn::new("price * quantity", false));
+        let result = build_column_definition(
+            "\"total\"",
+            &column,
+            "INTEGER".to_string(),
+            &[],
+            Dialect::PostgreSQL,
+        );
+        assert!(result.contains("GENERATED ALWAYS AS (price * quantity) VIRTUAL"));
+    }
+
+    #[test]
+    fn test_render_default_value_current_timestamp_per_dialect() {
+        let timestamp = ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: None,
+        };
+        let timestamp6 = ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: Some(6),
+        };
+        let datetime3 = ColumnType::DialectSpecific {
+            kind: "DATETIME".to_string(),
+            params: serde_json::json!({ "precision": 3 }),
+        };
+
+        for value in [
+            "CURRENT_TIMESTAMP",
+            "now()",
+            "CURRENT_TIMESTAMP(6)",
+            "datetime('now')",
+        ] {
+            assert_eq!(
+                render_default_value(value, &timestamp, Dialect::MySQL),
+                "CURRENT_TIMESTAMP"
+            );
+            // MySQLは秒の小数部の桁数をカラムに合わせる
+            assert_eq!(
+                render_default_value(value, &timestamp6, Dialect::MySQL),
+                "CURRENT_TIMESTAMP(6)"
+            );
+            assert_eq!(
+                render_default_value(value, &datetime3, Dialect::MySQL),
+                "CURRENT_TIMESTAMP(3)"
+            );
+            assert_eq!(
+                render_default_value(value, &timestamp6, Dialect::PostgreSQL),
+                "CURRENT_TIMESTAMP"
+            );
+            assert_eq!(
+                render_default_value(value, &timestamp6, Dialect::SQLite),
+                "CURRENT_TIMESTAMP"
+            );
+        }
+
+        // 現在日時以外のデフォルト値はそのまま出力する
+        assert_eq!(
+            render_default_value("'2024-01-01'", &timestamp, Dialect::SQLite),
+            "'2024-01-01'"
+        );
+    }
+
     // ==========================================
     // SqlGenerator trait デフォルト実装のテスト
     // ==========================================
@@ -1216,8 +1463,12 @@ mod tests {
         let table = Table::new("users".to_string());
         let index = Index {
             name: "idx_users_email".to_string(),
-            columns: vec!["email".to_string()],
+            columns: vec!["email".into()],
             unique: false,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         };
         let result = gen.generate_create_index(&table, &index);
         assert!(result.contains("CREATE INDEX"));
@@ -1230,8 +1481,12 @@ mod tests {
         let table = Table::new("users".to_string());
         let index = Index {
             name: "idx_users_email".to_string(),
-            columns: vec!["email".to_string()],
+            columns: vec!["email".into()],
             unique: true,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         };
         let result = gen.generate_create_index(&table, &index);
         assert!(result.contains("CREATE UNIQUE INDEX"));
@@ -2,21 +2,24 @@
 //
 // スキーマ定義からMySQL用のDDL文を生成します。
 
+use crate::adapters::constraint_naming::{
+    generate_ck_constraint_name, generate_fk_constraint_name, generate_uq_constraint_name,
+};
 use crate::adapters::sql_generator::{
-    build_column_definition, format_check_constraint, generate_ck_constraint_name,
-    generate_fk_constraint_name, generate_uq_constraint_name, quote_columns_mysql,
-    quote_identifier_mysql, sanitize_sql_comment, validate_check_expression, MigrationDirection,
-    SqlGenerator,
+    build_column_definition, format_check_constraint, fractional_seconds_precision,
+    quote_columns_mysql, quote_comment_literal, quote_identifier_mysql, sanitize_sql_comment,
+    validate_check_expression, MigrationDirection, SqlGenerator,
 };
 use crate::adapters::type_mapping::TypeMappingService;
-use crate::core::config::Dialect;
-use crate::core::schema::{Column, ColumnType, Constraint, Table};
-use crate::core::schema_diff::{ColumnDiff, RenamedColumn};
+use crate::core::config::{Dialect, IdentifierCase};
+use crate::core::schema::{Column, ColumnType, Constraint, Index, IndexType, Table};
+use crate::core::schema_diff::{ColumnDiff, RenamedColumn, RenamedIndex};
 
 /// MySQL用SQLジェネレーター
 #[derive(Debug, Clone)]
 pub struct MysqlSqlGenerator {
     type_mapping: TypeMappingService,
+    identifier_case: IdentifierCase,
 }
 
 impl MysqlSqlGenerator {
@@ -24,9 +27,18 @@ impl MysqlSqlGenerator {
     pub fn new() -> Self {
         Self {
             type_mapping: TypeMappingService::new(Dialect::MySQL),
+            identifier_case: IdentifierCase::Preserve,
         }
     }
 
+    /// 生成SQLの識別子の大文字・小文字の規則を設定
+    ///
+    /// 自動生成する制約名に適用します（テーブル・カラム名は変換済みのスキーマを渡す）。
+    pub fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
     /// ColumnTypeをMySQLの型文字列にマッピング
     ///
     /// TypeMappingServiceに委譲して型変換を行います。
@@ -53,11 +65,97 @@ impl MysqlSqlGenerator {
             ""
         };
         let quoted_name = quote_identifier_mysql(column_name);
-        build_column_definition(&quoted_name, target_column, type_str, &[auto_increment])
+        let on_update = on_update_clause(target_column);
+        with_column_comment(
+            build_column_definition(
+                &quoted_name,
+                target_column,
+                type_str,
+                &[auto_increment],
+                Dialect::MySQL,
+            ) + &on_update,
+            target_column,
+        )
+    }
+}
+
+/// カラム定義にコメント句を付加
+///
+/// MODIFY COLUMN / CHANGE COLUMN はコメントも置き換えるため、
+/// カラム定義を生成するすべての箇所でコメントを含める。
+fn with_column_comment(definition: String, column: &Column) -> String {
+    match column.comment_text() {
+        Some(comment) => format!(
+            "{} COMMENT {}",
+            definition,
+            quote_comment_literal(comment, true)
+        ),
+        None => definition,
+    }
+}
+
+/// `ON UPDATE CURRENT_TIMESTAMP` 句（先頭に空白を含む、指定がない場合は空文字列）
+///
+/// MODIFY COLUMN / CHANGE COLUMN は ON UPDATE も置き換えるため、コメントと同様に
+/// カラム定義を生成するすべての箇所で含める。MySQLは秒の小数部の桁数が
+/// カラムと一致することを要求するため、桁数を指定したカラムでは同じ桁数を付ける。
+fn on_update_clause(column: &Column) -> String {
+    if !column.on_update_current_timestamp {
+        return String::new();
+    }
+    match fractional_seconds_precision(&column.column_type) {
+        Some(precision) => format!(" ON UPDATE CURRENT_TIMESTAMP({})", precision),
+        None => " ON UPDATE CURRENT_TIMESTAMP".to_string(),
+    }
+}
+
+/// `ALTER COLUMN ... SET DEFAULT / DROP DEFAULT` で変更できるかどうか
+///
+/// MySQLの `SET DEFAULT` はリテラルのみを受け付けるため、`CURRENT_TIMESTAMP` などの
+/// 式はMODIFY COLUMNで指定する必要があります。また、TEXT/BLOB/JSON型のカラムは
+/// リテラルのデフォルト値を持てないため、DROP DEFAULT以外は対象外とします。
+fn supports_alter_column_default(column_type: &ColumnType, new_default: Option<&str>) -> bool {
+    let Some(default_value) = new_default else {
+        return true;
+    };
+    if matches!(
+        column_type,
+        ColumnType::TEXT | ColumnType::BLOB | ColumnType::JSON | ColumnType::JSONB
+    ) {
+        return false;
     }
+    is_literal_default(default_value)
+}
+
+/// デフォルト値がリテラル（文字列・数値・NULL・真偽値）かどうか
+fn is_literal_default(value: &str) -> bool {
+    let value = value.trim();
+    if ["NULL", "TRUE", "FALSE"]
+        .iter()
+        .any(|keyword| value.eq_ignore_ascii_case(keyword))
+    {
+        return true;
+    }
+    if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        // 'a' || 'b' のような式を除外するため、内部の引用符はエスケープ済みのみ許可
+        return !inner.replace("''", "").contains('\'');
+    }
+
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+    !digits.is_empty()
+        && digits.chars().any(|c| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.matches('.').count() <= 1
 }
 
 impl SqlGenerator for MysqlSqlGenerator {
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
     fn quote_identifier(&self, name: &str) -> String {
         quote_identifier_mysql(name)
     }
@@ -66,6 +164,33 @@ impl SqlGenerator for MysqlSqlGenerator {
         quote_columns_mysql(columns)
     }
 
+    /// CREATE INDEX文を生成
+    ///
+    /// FULLTEXTは `CREATE FULLTEXT INDEX`、BTREE / HASHは `USING` 句として出力します。
+    fn generate_create_index(&self, table: &Table, index: &Index) -> String {
+        let index_kind = match index.index_type {
+            Some(IndexType::Fulltext) => "FULLTEXT INDEX",
+            _ if index.unique => "UNIQUE INDEX",
+            _ => "INDEX",
+        };
+        let using = match index.index_type {
+            Some(index_type @ (IndexType::BTree | IndexType::Hash)) => {
+                format!(" USING {}", index_type.as_str())
+            }
+            // FULLTEXTは CREATE FULLTEXT INDEX で表現し、GIN / GiSTはバリデーションで拒否される
+            Some(IndexType::Fulltext | IndexType::Gin | IndexType::Gist) | None => String::new(),
+        };
+
+        format!(
+            "CREATE {} {} ON {} ({}){}",
+            index_kind,
+            quote_identifier_mysql(&index.name),
+            self.quote_table_name(&table.name),
+            self.quote_index_columns(&index.columns),
+            using
+        )
+    }
+
     fn generate_column_definition(&self, column: &Column) -> String {
         let type_str = self.map_column_type(&column.column_type, column.auto_increment);
         let auto_increment = if column.auto_increment.unwrap_or(false) {
@@ -74,7 +199,17 @@ impl SqlGenerator for MysqlSqlGenerator {
             ""
         };
         let quoted_name = quote_identifier_mysql(&column.name);
-        build_column_definition(&quoted_name, column, type_str, &[auto_increment])
+        let on_update = on_update_clause(column);
+        with_column_comment(
+            build_column_definition(
+                &quoted_name,
+                column,
+                type_str,
+                &[auto_increment],
+                Dialect::MySQL,
+            ) + &on_update,
+            column,
+        )
     }
 
     fn generate_constraint_definition(&self, constraint: &Constraint) -> String {
@@ -95,9 +230,33 @@ impl SqlGenerator for MysqlSqlGenerator {
                 // FOREIGN KEY制約はALTER TABLEで追加するため、ここでは空文字列を返す
                 String::new()
             }
+            // Constraint は #[non_exhaustive] のため、新しい種類はここで対応を追加する
+            other => unreachable!("MySQL generator does not support constraint {:?}", other),
         }
     }
 
+    fn inline_column_comments(&self) -> bool {
+        true
+    }
+
+    fn generate_table_comment(&self, table_name: &str, comment: Option<&str>) -> Vec<String> {
+        // MySQLは空文字列でコメントを削除する
+        vec![format!(
+            "ALTER TABLE {} COMMENT = {}",
+            quote_identifier_mysql(table_name),
+            quote_comment_literal(comment.unwrap_or(""), true)
+        )]
+    }
+
+    fn generate_column_comment(&self, table_name: &str, column: &Column) -> Vec<String> {
+        // コメントだけを変更する構文はないため、MODIFY COLUMNで完全なカラム定義を再指定する
+        vec![format!(
+            "ALTER TABLE {} MODIFY COLUMN {}",
+            quote_identifier_mysql(table_name),
+            self.generate_column_definition(column)
+        )]
+    }
+
     fn generate_drop_index(&self, table_name: &str, index_name: &str) -> String {
         format!(
             "DROP INDEX {} ON {}",
@@ -106,6 +265,26 @@ impl SqlGenerator for MysqlSqlGenerator {
         )
     }
 
+    fn generate_rename_index(
+        &self,
+        table: &Table,
+        renamed_index: &RenamedIndex,
+        direction: MigrationDirection,
+    ) -> Vec<String> {
+        let (from_name, to_name) = match direction {
+            MigrationDirection::Up => (&renamed_index.old_name, &renamed_index.new_index.name),
+            MigrationDirection::Down => (&renamed_index.new_index.name, &renamed_index.old_name),
+        };
+
+        // RENAME INDEX は MySQL 5.7 以降で利用可能（インデックスの再構築は行われない）
+        vec![format!(
+            "ALTER TABLE {} RENAME INDEX {} TO {}",
+            quote_identifier_mysql(&table.name),
+            quote_identifier_mysql(from_name),
+            quote_identifier_mysql(to_name)
+        )]
+    }
+
     fn generate_rename_table(&self, old_name: &str, new_name: &str) -> String {
         format!(
             "RENAME TABLE {} TO {}",
@@ -167,7 +346,22 @@ impl SqlGenerator for MysqlSqlGenerator {
         column: &Column,
         new_default: Option<&str>,
     ) -> Vec<String> {
-        // MySQLではMODIFY COLUMNで完全なカラム定義を再指定する必要がある
+        // デフォルト値のみの変更はメタデータのみの ALTER COLUMN で行う
+        // （MODIFY COLUMNはバージョンやアルゴリズムによってテーブルを再構築する）
+        if supports_alter_column_default(&column.column_type, new_default) {
+            let action = match new_default {
+                Some(val) => format!("SET DEFAULT {}", val),
+                None => "DROP DEFAULT".to_string(),
+            };
+            return vec![format!(
+                "ALTER TABLE {} ALTER COLUMN {} {}",
+                quote_identifier_mysql(table_name),
+                quote_identifier_mysql(&column.name),
+                action
+            )];
+        }
+
+        // リテラル以外のデフォルト値はMODIFY COLUMNで完全なカラム定義を再指定する
         let mut target_column = column.clone();
         target_column.default_value = new_default.map(|s| s.to_string());
         let table = Table::new(table_name.to_string());
@@ -220,8 +414,11 @@ impl SqlGenerator for MysqlSqlGenerator {
                 on_delete,
                 on_update,
             } => {
-                let constraint_name =
-                    generate_fk_constraint_name(table_name, columns, referenced_table);
+                let constraint_name = self.identifier_case().apply(&generate_fk_constraint_name(
+                    table_name,
+                    columns,
+                    referenced_table,
+                ));
 
                 let mut sql = format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
@@ -242,7 +439,9 @@ impl SqlGenerator for MysqlSqlGenerator {
                 sql
             }
             Constraint::UNIQUE { columns } => {
-                let constraint_name = generate_uq_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_uq_constraint_name(table_name, columns));
 
                 format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
@@ -255,7 +454,9 @@ impl SqlGenerator for MysqlSqlGenerator {
                 columns,
                 check_expression,
             } => {
-                let constraint_name = generate_ck_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_ck_constraint_name(table_name, columns));
 
                 if let Err(err) = validate_check_expression(check_expression) {
                     let sanitized_msg = sanitize_sql_comment(&err.to_string());
@@ -291,8 +492,11 @@ impl SqlGenerator for MysqlSqlGenerator {
                 referenced_table,
                 ..
             } => {
-                let constraint_name =
-                    generate_fk_constraint_name(table_name, columns, referenced_table);
+                let constraint_name = self.identifier_case().apply(&generate_fk_constraint_name(
+                    table_name,
+                    columns,
+                    referenced_table,
+                ));
 
                 // MySQLではDROP FOREIGN KEYを使用
                 format!(
@@ -302,7 +506,9 @@ impl SqlGenerator for MysqlSqlGenerator {
                 )
             }
             Constraint::UNIQUE { columns } => {
-                let constraint_name = generate_uq_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_uq_constraint_name(table_name, columns));
 
                 // MySQLではUNIQUE制約はDROP INDEXで削除
                 format!(
@@ -312,7 +518,9 @@ impl SqlGenerator for MysqlSqlGenerator {
                 )
             }
             Constraint::CHECK { columns, .. } => {
-                let constraint_name = generate_ck_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_ck_constraint_name(table_name, columns));
 
                 // MySQL 8.0.16+: DROP CHECKで削除
                 format!(
@@ -346,6 +554,7 @@ impl Default for MysqlSqlGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::schema::IndexColumn;
 
     #[test]
     fn test_new_generator() {
@@ -674,6 +883,75 @@ mod tests {
         assert_eq!(sql[0], "ALTER TABLE `posts` MODIFY COLUMN `content` TEXT");
     }
 
+    #[test]
+    fn test_alter_column_type_char_to_varchar_same_length() {
+        let generator = MysqlSqlGenerator::new();
+        let mut table = Table::new("addresses".to_string());
+        table.columns.push(Column::new(
+            "country".to_string(),
+            ColumnType::VARCHAR { length: 2 },
+            false,
+        ));
+
+        // CHAR(2) → VARCHAR(2)
+        let old_column = Column::new("country".to_string(), ColumnType::CHAR { length: 2 }, false);
+        let new_column = Column::new(
+            "country".to_string(),
+            ColumnType::VARCHAR { length: 2 },
+            false,
+        );
+        let diff = ColumnDiff::new("country".to_string(), old_column, new_column);
+
+        let up = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+        let down = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Down);
+
+        assert_eq!(
+            up,
+            vec!["ALTER TABLE `addresses` MODIFY COLUMN `country` VARCHAR(2) NOT NULL"]
+        );
+        assert_eq!(
+            down,
+            vec!["ALTER TABLE `addresses` MODIFY COLUMN `country` CHAR(2) NOT NULL"]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_type_decimal_scale_change() {
+        let generator = MysqlSqlGenerator::new();
+        let table = Table::new("products".to_string());
+
+        // DECIMAL(10, 2) → DECIMAL(12, 4)
+        let old_column = Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            false,
+        );
+        let new_column = Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 12,
+                scale: 4,
+            },
+            false,
+        );
+        let diff = ColumnDiff::new("price".to_string(), old_column, new_column);
+
+        let up = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec!["ALTER TABLE `products` MODIFY COLUMN `price` DECIMAL(12, 4) NOT NULL"]
+        );
+
+        let down = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec!["ALTER TABLE `products` MODIFY COLUMN `price` DECIMAL(10, 2) NOT NULL"]
+        );
+    }
+
     // ==========================================
     // generate_rename_column のテスト
     // ==========================================
@@ -761,6 +1039,67 @@ mod tests {
         );
     }
 
+    fn create_renamed_index() -> RenamedIndex {
+        let old_index = Index::new("idx_email".to_string(), vec!["email".to_string()], true);
+        let mut new_index = old_index.clone();
+        new_index.name = "users_email_key".to_string();
+        new_index.renamed_from = Some("idx_email".to_string());
+        RenamedIndex {
+            old_name: "idx_email".to_string(),
+            old_index,
+            new_index,
+        }
+    }
+
+    #[test]
+    fn test_generate_rename_index() {
+        let generator = MysqlSqlGenerator::new();
+        let table = create_test_table();
+        let renamed = create_renamed_index();
+
+        let up = generator.generate_rename_index(&table, &renamed, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec!["ALTER TABLE `users` RENAME INDEX `idx_email` TO `users_email_key`".to_string()]
+        );
+
+        let down = generator.generate_rename_index(&table, &renamed, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec!["ALTER TABLE `users` RENAME INDEX `users_email_key` TO `idx_email`".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_generate_create_index_with_prefix_length_and_type() {
+        let generator = MysqlSqlGenerator::new();
+        let table = create_test_table();
+
+        let prefixed = Index::with_columns(
+            "idx_body".to_string(),
+            vec![IndexColumn::new("body").with_length(191)],
+            false,
+        );
+        assert_eq!(
+            generator.generate_create_index(&table, &prefixed),
+            "CREATE INDEX `idx_body` ON `users` (`body`(191))"
+        );
+
+        let fulltext = Index::new("idx_body_ft".to_string(), vec!["body".to_string()], false)
+            .with_index_type(IndexType::Fulltext);
+        assert_eq!(
+            generator.generate_create_index(&table, &fulltext),
+            "CREATE FULLTEXT INDEX `idx_body_ft` ON `users` (`body`)"
+        );
+
+        let hash = Index::new("idx_email".to_string(), vec!["email".to_string()], true)
+            .with_index_type(IndexType::Hash);
+        assert_eq!(
+            generator.generate_create_index(&table, &hash),
+            "CREATE UNIQUE INDEX `idx_email` ON `users` (`email`) USING HASH"
+        );
+    }
+
     #[test]
     fn test_generate_rename_column_with_type_change() {
         // リネームと同時に型変更がある場合
@@ -798,6 +1137,35 @@ mod tests {
     // 制約メソッドのテスト
     // ==========================================
 
+    #[test]
+    fn test_constraint_names_follow_identifier_case() {
+        let generator = MysqlSqlGenerator::new().with_identifier_case(IdentifierCase::Upper);
+        let constraint = Constraint::FOREIGN_KEY {
+            columns: vec!["USER_ID".to_string()],
+            referenced_table: "USERS".to_string(),
+            referenced_columns: vec!["ID".to_string()],
+            on_delete: None,
+            on_update: None,
+        };
+
+        assert_eq!(
+            generator.generate_add_constraint_for_existing_table("POSTS", &constraint),
+            "ALTER TABLE `POSTS` ADD CONSTRAINT `FK_POSTS_USER_ID_USERS` FOREIGN KEY (`USER_ID`) REFERENCES `USERS` (`ID`)"
+        );
+        assert_eq!(
+            generator.generate_drop_constraint_for_existing_table("POSTS", &constraint),
+            "ALTER TABLE `POSTS` DROP FOREIGN KEY `FK_POSTS_USER_ID_USERS`"
+        );
+
+        let check = Constraint::CHECK {
+            columns: vec!["PRICE".to_string()],
+            check_expression: "PRICE >= 0".to_string(),
+        };
+        assert!(generator
+            .generate_add_constraint_for_existing_table("PRODUCTS", &check)
+            .contains("`CK_PRODUCTS_PRICE`"));
+    }
+
     #[test]
     fn test_generate_add_constraint_for_existing_table_foreign_key() {
         let generator = MysqlSqlGenerator::new();
@@ -959,4 +1327,194 @@ mod tests {
         let sql = generator.generate_rename_view("old_view", "new_view");
         assert_eq!(sql, "RENAME TABLE `old_view` TO `new_view`");
     }
+
+    fn column_with_default(column_type: ColumnType, default_value: Option<&str>) -> Column {
+        let mut column = Column::new("status".to_string(), column_type, false);
+        column.default_value = default_value.map(str::to_string);
+        column
+    }
+
+    #[test]
+    fn test_alter_column_default_string_literal_uses_set_default() {
+        let generator = MysqlSqlGenerator::new();
+        let column = column_with_default(ColumnType::VARCHAR { length: 50 }, Some("'active'"));
+
+        let sql = generator.generate_alter_column_default("users", &column, Some("'active'"));
+        assert_eq!(
+            sql,
+            vec!["ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT 'active'"]
+        );
+
+        // エスケープされた引用符を含む文字列もリテラルとして扱う
+        let sql = generator.generate_alter_column_default("users", &column, Some("'it''s'"));
+        assert_eq!(
+            sql,
+            vec!["ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT 'it''s'"]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_default_numeric_and_keyword_literals_use_set_default() {
+        let generator = MysqlSqlGenerator::new();
+        let column = column_with_default(ColumnType::INTEGER { precision: None }, None);
+
+        for value in ["0", "-1", "3.14", "NULL", "TRUE", "false"] {
+            let sql = generator.generate_alter_column_default("users", &column, Some(value));
+            assert_eq!(
+                sql,
+                vec![format!(
+                    "ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT {}",
+                    value
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn test_alter_column_default_drop_uses_drop_default() {
+        let generator = MysqlSqlGenerator::new();
+        let column = column_with_default(ColumnType::TEXT, None);
+
+        let sql = generator.generate_alter_column_default("users", &column, None);
+        assert_eq!(
+            sql,
+            vec!["ALTER TABLE `users` ALTER COLUMN `status` DROP DEFAULT"]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_default_expression_falls_back_to_modify() {
+        let generator = MysqlSqlGenerator::new();
+        let column = column_with_default(
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            None,
+        );
+
+        for value in ["CURRENT_TIMESTAMP", "(UUID())", "'a' || 'b'", "1 + 1"] {
+            let sql = generator.generate_alter_column_default("users", &column, Some(value));
+            assert_eq!(sql.len(), 1);
+            assert!(
+                sql[0].starts_with("ALTER TABLE `users` MODIFY COLUMN `status`"),
+                "{}",
+                sql[0]
+            );
+            assert!(
+                sql[0].ends_with(&format!("DEFAULT {}", value)),
+                "{}",
+                sql[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_alter_column_default_on_text_column_falls_back_to_modify() {
+        let generator = MysqlSqlGenerator::new();
+        let column = column_with_default(ColumnType::JSON, None);
+
+        let sql = generator.generate_alter_column_default("users", &column, Some("'{}'"));
+        assert_eq!(
+            sql,
+            vec!["ALTER TABLE `users` MODIFY COLUMN `status` JSON NOT NULL DEFAULT '{}'"]
+        );
+    }
+
+    #[test]
+    fn test_column_comment_is_inline() {
+        let generator = MysqlSqlGenerator::new();
+        let mut table = Table::new("users".to_string());
+        table.comment = Some("Registered users".to_string());
+        let mut column = Column::new("email".to_string(), ColumnType::TEXT, false);
+        column.comment = Some(r"User's login \ address".to_string());
+        table.add_column(column.clone());
+
+        assert_eq!(
+            generator.generate_column_definition(&column),
+            r"`email` TEXT NOT NULL COMMENT 'User''s login \\ address'"
+        );
+        assert!(generator.inline_column_comments());
+        // カラムコメントはCREATE TABLEに含まれるため、テーブルコメントのみ設定する
+        assert_eq!(
+            generator.generate_create_table_comments(&table),
+            vec!["ALTER TABLE `users` COMMENT = 'Registered users'"]
+        );
+
+        column.comment = None;
+        assert_eq!(
+            generator.generate_column_comment("users", &column),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `email` TEXT NOT NULL"]
+        );
+        assert_eq!(
+            generator.generate_table_comment("users", None),
+            vec!["ALTER TABLE `users` COMMENT = ''"]
+        );
+    }
+
+    fn updated_at_column(precision: Option<u8>) -> Column {
+        let mut column = Column::new(
+            "updated_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision,
+            },
+            false,
+        );
+        column.default_value = Some(match precision {
+            Some(p) => format!("CURRENT_TIMESTAMP({})", p),
+            None => "CURRENT_TIMESTAMP".to_string(),
+        });
+        column.on_update_current_timestamp = true;
+        column
+    }
+
+    #[test]
+    fn test_column_definition_with_on_update_current_timestamp() {
+        let generator = MysqlSqlGenerator::new();
+
+        assert_eq!(
+            generator.generate_column_definition(&updated_at_column(None)),
+            "`updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP"
+        );
+        // 秒の小数部の桁数はカラムと一致させる
+        assert_eq!(
+            generator.generate_column_definition(&updated_at_column(Some(3))),
+            "`updated_at` TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)"
+        );
+    }
+
+    #[test]
+    fn test_modify_column_preserves_on_update_current_timestamp() {
+        let generator = MysqlSqlGenerator::new();
+        let mut table = Table::new("users".to_string());
+        let column = updated_at_column(None);
+        table.add_column(column.clone());
+
+        // NULL制約の変更
+        assert_eq!(
+            generator.generate_alter_column_nullable("users", &column, true),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `updated_at` TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP"]
+        );
+
+        // 型の変更（TIMESTAMP → DATETIME）
+        let mut new_column = column.clone();
+        new_column.column_type = ColumnType::DialectSpecific {
+            kind: "DATETIME".to_string(),
+            params: serde_json::Value::Null,
+        };
+        let diff = ColumnDiff::new("updated_at".to_string(), column.clone(), new_column);
+        assert_eq!(
+            generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `updated_at` DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP"]
+        );
+
+        // コメントの変更
+        let mut commented = column.clone();
+        commented.comment = Some("last update".to_string());
+        assert_eq!(
+            generator.generate_column_comment("users", &commented),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT 'last update'"]
+        );
+    }
 }
@@ -2,22 +2,28 @@
 //
 // スキーマ定義からPostgreSQL用のDDL文を生成します。
 
+use crate::adapters::constraint_naming::{
+    generate_ck_constraint_name, generate_fk_constraint_name, generate_uq_constraint_name,
+};
 use crate::adapters::sql_generator::{
-    build_column_definition, format_check_constraint, generate_ck_constraint_name,
-    generate_fk_constraint_name, generate_uq_constraint_name, quote_columns_postgres,
-    quote_identifier_postgres, quote_regclass_postgres, sanitize_sql_comment,
-    validate_check_expression, MigrationDirection, SqlGenerator,
+    build_column_definition, format_check_constraint, format_index_columns, index_where_clause,
+    quote_columns_postgres, quote_comment_literal, quote_identifier_postgres,
+    quote_regclass_table_postgres, quote_table_postgres, render_default_value,
+    sanitize_sql_comment, validate_check_expression, MigrationDirection, SqlGenerator,
 };
 use crate::adapters::type_mapping::TypeMappingService;
-use crate::core::config::Dialect;
-use crate::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Table};
-use crate::core::schema_diff::{ColumnDiff, EnumDiff, RenamedColumn};
+use crate::core::config::{Dialect, IdentifierCase};
+use crate::core::schema::{
+    Column, ColumnType, Constraint, EnumDefinition, Index, IndexColumn, Table,
+};
+use crate::core::schema_diff::{ColumnDiff, EnumDiff, RenamedColumn, RenamedIndex};
 use crate::core::type_category::TypeCategory;
 
 /// PostgreSQL用SQLジェネレーター
 #[derive(Debug, Clone)]
 pub struct PostgresSqlGenerator {
     type_mapping: TypeMappingService,
+    identifier_case: IdentifierCase,
 }
 
 impl PostgresSqlGenerator {
@@ -25,9 +31,18 @@ impl PostgresSqlGenerator {
     pub fn new() -> Self {
         Self {
             type_mapping: TypeMappingService::new(Dialect::PostgreSQL),
+            identifier_case: IdentifierCase::Preserve,
         }
     }
 
+    /// 生成SQLの識別子の大文字・小文字の規則を設定
+    ///
+    /// 自動生成する制約名に適用します（テーブル・カラム名は変換済みのスキーマを渡す）。
+    pub fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
     /// ColumnTypeをPostgreSQLの型文字列にマッピング
     ///
     /// TypeMappingServiceに委譲して型変換を行います。
@@ -68,8 +83,9 @@ impl PostgresSqlGenerator {
     /// - ENUM型への変換で中間キャストが必要な場合
     ///   例: `USING "col"::text::new_enum_type`
     ///
-    /// 自動生成されるマイグレーションファイルを確認し、
-    /// 必要に応じてUSING句を適切な変換ロジックに修正してください。
+    /// これらの変換式は、スキーマYAMLのカラムに `type_change_using`（up.sql）と
+    /// `down_using`（down.sql）で指定できます。指定された式 `using` は単純キャストより優先され、
+    /// 暗黙のキャストで足りる型変換でも `USING <式>` として出力します。
     #[allow(clippy::too_many_arguments)]
     fn generate_type_change_sql(
         &self,
@@ -77,6 +93,7 @@ impl PostgresSqlGenerator {
         target_type: &ColumnType,
         target_is_auto: bool,
         target_auto_increment: Option<bool>,
+        using: Option<&str>,
         quoted_table: &str,
         quoted_column: &str,
         statements: &mut Vec<String>,
@@ -95,7 +112,12 @@ impl PostgresSqlGenerator {
 
         let needs_using = self.needs_using_clause(source_type, target_type);
 
-        let sql = if needs_using {
+        let sql = if let Some(expression) = using {
+            format!(
+                "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}",
+                quoted_table, quoted_column, target_type_str, expression
+            )
+        } else if needs_using {
             format!(
                 "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{}",
                 quoted_table, quoted_column, target_type_str, quoted_column, target_type_str
@@ -106,7 +128,39 @@ impl PostgresSqlGenerator {
                 quoted_table, quoted_column, target_type_str
             )
         };
-        statements.push(sql);
+        match self.char_padding_note(source_type, target_type, quoted_column) {
+            Some(note) => statements.push(format!("{}\n{}", note, sql)),
+            None => statements.push(sql),
+        }
+    }
+
+    /// CHAR と可変長文字列型の間の型変更に付けるコメントを生成
+    ///
+    /// CHAR(n) の値は末尾が空白で n 文字に埋められます。PostgreSQLは CHAR から
+    /// VARCHAR / TEXT へのキャストで末尾の空白を取り除くため、空白を値の一部として
+    /// 残す必要がある場合は USING 句で明示するよう促します。
+    /// 逆方向の変更では、短い値が空白で埋められることを示します。
+    fn char_padding_note(
+        &self,
+        source_type: &ColumnType,
+        target_type: &ColumnType,
+        quoted_column: &str,
+    ) -> Option<String> {
+        match (source_type, target_type) {
+            (ColumnType::CHAR { length }, ColumnType::VARCHAR { .. } | ColumnType::TEXT) => {
+                Some(format!(
+                    "-- NOTE: {} → {} removes the trailing spaces of existing values; use USING rpad({}, {}) to keep them",
+                    source_type, target_type, quoted_column, length
+                ))
+            }
+            (ColumnType::VARCHAR { .. } | ColumnType::TEXT, ColumnType::CHAR { length }) => {
+                Some(format!(
+                    "-- NOTE: {} → {} pads values shorter than {} characters with trailing spaces",
+                    source_type, target_type, length
+                ))
+            }
+            _ => None,
+        }
     }
 
     /// INTEGER → SERIAL (auto_increment: false → true) のSQL生成
@@ -129,8 +183,8 @@ impl PostgresSqlGenerator {
         }
 
         let sequence_name = format!("{}_{}_seq", table_name, column_name);
-        let quoted_sequence = quote_identifier_postgres(&sequence_name);
-        let regclass_literal = quote_regclass_postgres(&sequence_name);
+        let quoted_sequence = quote_table_postgres(&sequence_name);
+        let regclass_literal = quote_regclass_table_postgres(&sequence_name);
         statements.push(format!("CREATE SEQUENCE IF NOT EXISTS {}", quoted_sequence));
         // 既存データがある場合に備えてシーケンスを最大値に初期化
         // COALESCE(..., 0) により空テーブルでは nextval() が 1 を返す
@@ -173,7 +227,7 @@ impl PostgresSqlGenerator {
             quoted_table, quoted_column
         ));
         let sequence_name = format!("{}_{}_seq", table_name, column_name);
-        let quoted_sequence = quote_identifier_postgres(&sequence_name);
+        let quoted_sequence = quote_table_postgres(&sequence_name);
         statements.push(format!(
             "DROP SEQUENCE IF EXISTS {} CASCADE",
             quoted_sequence
@@ -191,52 +245,23 @@ impl PostgresSqlGenerator {
     /// 複雑な型変換（例: 文字列→タイムスタンプのフォーマット指定、条件付きキャスト）には対応していません。
     /// そのようなケースでは、生成されたマイグレーションSQLを手動で修正してください。
     fn needs_using_clause(&self, source_type: &ColumnType, target_type: &ColumnType) -> bool {
-        let source_category = TypeCategory::from_column_type(source_type);
-        let target_category = TypeCategory::from_column_type(target_type);
-
-        use TypeCategory::*;
-
-        match (source_category, target_category) {
-            // 同一カテゴリ内: 不要
-            (Numeric, Numeric)
-            | (String, String)
-            | (DateTime, DateTime)
-            | (Binary, Binary)
-            | (Json, Json)
-            | (Boolean, Boolean)
-            | (Uuid, Uuid) => false,
-
-            // String → Numeric/Boolean/DateTime/Json: 必要
-            (String, Numeric) | (String, Boolean) | (String, DateTime) | (String, Json) => true,
-
-            // Numeric → String: 不要（暗黙変換）
-            (Numeric, String) => false,
-
-            // DateTime → String: 不要（暗黙変換）
-            (DateTime, String) => false,
-
-            // Boolean → Numeric/String: 不要（暗黙変換）
-            (Boolean, Numeric) | (Boolean, String) => false,
-
-            // Uuid → String: 不要（暗黙変換）
-            (Uuid, String) => false,
-
-            // Json → String: 不要（暗黙変換）
-            (Json, String) => false,
-
-            // Binary → String: 不要（暗黙変換）
-            (Binary, String) => false,
-
-            // Otherカテゴリ: 安全のためUSING句を付与
-            (Other, _) | (_, Other) => true,
-
-            // その他の変換: 安全のためUSING句を付与
-            _ => true,
-        }
+        TypeCategory::from_column_type(source_type)
+            .requires_explicit_cast(&TypeCategory::from_column_type(target_type))
     }
 }
 
+/// COMMENT ON の値（Noneの場合はNULLでコメントを削除）
+fn comment_value(comment: Option<&str>) -> String {
+    comment
+        .map(|comment| quote_comment_literal(comment, false))
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
 impl SqlGenerator for PostgresSqlGenerator {
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
     fn quote_identifier(&self, name: &str) -> String {
         quote_identifier_postgres(name)
     }
@@ -245,10 +270,43 @@ impl SqlGenerator for PostgresSqlGenerator {
         quote_columns_postgres(columns)
     }
 
+    fn quote_table_name(&self, name: &str) -> String {
+        quote_table_postgres(name)
+    }
+
+    fn quote_index_columns(&self, columns: &[IndexColumn]) -> String {
+        format_index_columns(columns, quote_identifier_postgres, true)
+    }
+
+    /// CREATE INDEX文を生成
+    ///
+    /// インデックスの種類は `USING gin` のようにアクセスメソッドとして出力します。
+    fn generate_create_index(&self, table: &Table, index: &Index) -> String {
+        let index_kind = if index.unique {
+            "UNIQUE INDEX"
+        } else {
+            "INDEX"
+        };
+        let using = index
+            .index_type
+            .map(|index_type| format!(" USING {}", index_type.as_str().to_lowercase()))
+            .unwrap_or_default();
+
+        format!(
+            "CREATE {} {} ON {}{} ({}){}",
+            index_kind,
+            quote_identifier_postgres(&index.name),
+            quote_table_postgres(&table.name),
+            using,
+            self.quote_index_columns(&index.columns),
+            index_where_clause(index)
+        )
+    }
+
     fn generate_column_definition(&self, column: &Column) -> String {
         let type_str = self.map_column_type(&column.column_type, column.auto_increment);
         let quoted_name = quote_identifier_postgres(&column.name);
-        build_column_definition(&quoted_name, column, type_str, &[])
+        build_column_definition(&quoted_name, column, type_str, &[], Dialect::PostgreSQL)
     }
 
     fn generate_constraint_definition(&self, constraint: &Constraint) -> String {
@@ -266,9 +324,31 @@ impl SqlGenerator for PostgresSqlGenerator {
                 // FOREIGN KEY制約はALTER TABLEで追加するため、ここでは空文字列を返す
                 String::new()
             }
+            // Constraint は #[non_exhaustive] のため、新しい種類はここで対応を追加する
+            other => unreachable!(
+                "PostgreSQL generator does not support constraint {:?}",
+                other
+            ),
         }
     }
 
+    fn generate_table_comment(&self, table_name: &str, comment: Option<&str>) -> Vec<String> {
+        vec![format!(
+            "COMMENT ON TABLE {} IS {}",
+            quote_table_postgres(table_name),
+            comment_value(comment)
+        )]
+    }
+
+    fn generate_column_comment(&self, table_name: &str, column: &Column) -> Vec<String> {
+        vec![format!(
+            "COMMENT ON COLUMN {}.{} IS {}",
+            quote_table_postgres(table_name),
+            quote_identifier_postgres(&column.name),
+            comment_value(column.comment_text())
+        )]
+    }
+
     fn generate_create_enum_type(&self, enum_def: &EnumDefinition) -> Vec<String> {
         let values = self.format_enum_values(&enum_def.values);
         vec![format!(
@@ -305,7 +385,7 @@ impl SqlGenerator for PostgresSqlGenerator {
         for column in &enum_diff.columns {
             statements.push(format!(
                 "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::text::{}",
-                quote_identifier_postgres(&column.table_name),
+                quote_table_postgres(&column.table_name),
                 quote_identifier_postgres(&column.column_name),
                 quote_identifier_postgres(&enum_diff.enum_name),
                 quote_identifier_postgres(&column.column_name),
@@ -327,6 +407,14 @@ impl SqlGenerator for PostgresSqlGenerator {
         )]
     }
 
+    fn generate_rename_enum_type(&self, old_name: &str, new_name: &str) -> Vec<String> {
+        vec![format!(
+            "ALTER TYPE {} RENAME TO {}",
+            quote_identifier_postgres(old_name),
+            quote_identifier_postgres(new_name)
+        )]
+    }
+
     fn generate_alter_column_nullable(
         &self,
         table_name: &str,
@@ -340,7 +428,7 @@ impl SqlGenerator for PostgresSqlGenerator {
         };
         vec![format!(
             "ALTER TABLE {} ALTER COLUMN {} {}",
-            quote_identifier_postgres(table_name),
+            quote_table_postgres(table_name),
             quote_identifier_postgres(&column.name),
             action
         )]
@@ -353,12 +441,15 @@ impl SqlGenerator for PostgresSqlGenerator {
         new_default: Option<&str>,
     ) -> Vec<String> {
         let action = match new_default {
-            Some(val) => format!("SET DEFAULT {}", val),
+            Some(val) => format!(
+                "SET DEFAULT {}",
+                render_default_value(val, &column.column_type, Dialect::PostgreSQL)
+            ),
             None => "DROP DEFAULT".to_string(),
         };
         vec![format!(
             "ALTER TABLE {} ALTER COLUMN {} {}",
-            quote_identifier_postgres(table_name),
+            quote_table_postgres(table_name),
             quote_identifier_postgres(&column.name),
             action
         )]
@@ -371,23 +462,25 @@ impl SqlGenerator for PostgresSqlGenerator {
         direction: MigrationDirection,
     ) -> Vec<String> {
         let column_name = &column_diff.column_name;
-        let quoted_table = quote_identifier_postgres(&table.name);
+        let quoted_table = quote_table_postgres(&table.name);
         let quoted_column = quote_identifier_postgres(column_name);
 
-        // 方向に応じて対象の型とauto_incrementフラグを決定
-        let (source_type, target_type, source_auto_increment, target_auto_increment) =
+        // 方向に応じて対象の型とauto_incrementフラグ、USING式を決定
+        let (source_type, target_type, source_auto_increment, target_auto_increment, using) =
             match direction {
                 MigrationDirection::Up => (
                     &column_diff.old_column.column_type,
                     &column_diff.new_column.column_type,
                     column_diff.old_column.auto_increment,
                     column_diff.new_column.auto_increment,
+                    column_diff.up_using(),
                 ),
                 MigrationDirection::Down => (
                     &column_diff.new_column.column_type,
                     &column_diff.old_column.column_type,
                     column_diff.new_column.auto_increment,
                     column_diff.old_column.auto_increment,
+                    column_diff.down_using(),
                 ),
             };
 
@@ -402,6 +495,7 @@ impl SqlGenerator for PostgresSqlGenerator {
             target_type,
             target_is_auto,
             target_auto_increment,
+            using,
             &quoted_table,
             &quoted_column,
             &mut statements,
@@ -443,12 +537,50 @@ impl SqlGenerator for PostgresSqlGenerator {
 
         vec![format!(
             "ALTER TABLE {} RENAME COLUMN {} TO {}",
-            quote_identifier_postgres(&table.name),
+            quote_table_postgres(&table.name),
             quote_identifier_postgres(from_name),
             quote_identifier_postgres(to_name)
         )]
     }
 
+    fn generate_rename_index(
+        &self,
+        table: &Table,
+        renamed_index: &RenamedIndex,
+        direction: MigrationDirection,
+    ) -> Vec<String> {
+        let (from_name, to_name) = match direction {
+            MigrationDirection::Up => (&renamed_index.old_name, &renamed_index.new_index.name),
+            MigrationDirection::Down => (&renamed_index.new_index.name, &renamed_index.old_name),
+        };
+
+        // RENAME TO にはスキーマ修飾なしの名前を指定する（スキーマは変わらない）
+        vec![format!(
+            "ALTER INDEX {} RENAME TO {}",
+            qualified_index_name(&table.name, from_name),
+            quote_identifier_postgres(to_name)
+        )]
+    }
+
+    fn generate_drop_index(&self, table_name: &str, index_name: &str) -> String {
+        format!(
+            "DROP INDEX {}",
+            qualified_index_name(table_name, index_name)
+        )
+    }
+
+    fn generate_rename_table(&self, old_name: &str, new_name: &str) -> String {
+        // RENAME TO にはスキーマ修飾なしの名前を指定する（スキーマは変わらない）
+        let unqualified_new = new_name
+            .split_once('.')
+            .map_or(new_name, |(_, table)| table);
+        format!(
+            "ALTER TABLE {} RENAME TO {}",
+            quote_table_postgres(old_name),
+            quote_identifier_postgres(unqualified_new)
+        )
+    }
+
     fn generate_add_constraint_for_existing_table(
         &self,
         table_name: &str,
@@ -462,15 +594,18 @@ impl SqlGenerator for PostgresSqlGenerator {
                 on_delete,
                 on_update,
             } => {
-                let constraint_name =
-                    generate_fk_constraint_name(table_name, columns, referenced_table);
+                let constraint_name = self.identifier_case().apply(&generate_fk_constraint_name(
+                    table_name,
+                    columns,
+                    referenced_table,
+                ));
 
                 let mut sql = format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name),
                     quote_columns_postgres(columns),
-                    quote_identifier_postgres(referenced_table),
+                    quote_table_postgres(referenced_table),
                     quote_columns_postgres(referenced_columns)
                 );
 
@@ -484,11 +619,13 @@ impl SqlGenerator for PostgresSqlGenerator {
                 sql
             }
             Constraint::UNIQUE { columns } => {
-                let constraint_name = generate_uq_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_uq_constraint_name(table_name, columns));
 
                 format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name),
                     quote_columns_postgres(columns)
                 )
@@ -497,20 +634,22 @@ impl SqlGenerator for PostgresSqlGenerator {
                 columns,
                 check_expression,
             } => {
-                let constraint_name = generate_ck_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_ck_constraint_name(table_name, columns));
 
                 if let Err(err) = validate_check_expression(check_expression) {
                     let sanitized_msg = sanitize_sql_comment(&err.to_string());
                     return format!(
                         "/* ERROR: {} */ ALTER TABLE {} ADD CONSTRAINT {} CHECK (FALSE)",
                         sanitized_msg,
-                        quote_identifier_postgres(table_name),
+                        quote_table_postgres(table_name),
                         quote_identifier_postgres(&constraint_name),
                     );
                 }
                 format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({})",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name),
                     check_expression
                 )
@@ -533,30 +672,37 @@ impl SqlGenerator for PostgresSqlGenerator {
                 referenced_table,
                 ..
             } => {
-                let constraint_name =
-                    generate_fk_constraint_name(table_name, columns, referenced_table);
+                let constraint_name = self.identifier_case().apply(&generate_fk_constraint_name(
+                    table_name,
+                    columns,
+                    referenced_table,
+                ));
 
                 format!(
                     "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name)
                 )
             }
             Constraint::UNIQUE { columns } => {
-                let constraint_name = generate_uq_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_uq_constraint_name(table_name, columns));
 
                 format!(
                     "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name)
                 )
             }
             Constraint::CHECK { columns, .. } => {
-                let constraint_name = generate_ck_constraint_name(table_name, columns);
+                let constraint_name = self
+                    .identifier_case()
+                    .apply(&generate_ck_constraint_name(table_name, columns));
 
                 format!(
                     "ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}",
-                    quote_identifier_postgres(table_name),
+                    quote_table_postgres(table_name),
                     quote_identifier_postgres(&constraint_name)
                 )
             }
@@ -568,6 +714,21 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 }
 
+/// テーブルと同じスキーマで修飾したインデックス名
+///
+/// インデックスはテーブルと同じスキーマに作成されるため、
+/// スキーマ修飾されたテーブルのインデックスはスキーマで修飾して参照する。
+fn qualified_index_name(table_name: &str, index_name: &str) -> String {
+    match table_name.split_once('.') {
+        Some((schema, _)) => format!(
+            "{}.{}",
+            quote_identifier_postgres(schema),
+            quote_identifier_postgres(index_name)
+        ),
+        None => quote_identifier_postgres(index_name),
+    }
+}
+
 impl Default for PostgresSqlGenerator {
     fn default() -> Self {
         Self::new()
@@ -577,6 +738,7 @@ impl Default for PostgresSqlGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::schema::IndexType;
 
     #[test]
     fn test_new_generator() {
@@ -610,6 +772,7 @@ mod tests {
         let generator = PostgresSqlGenerator::new();
         let col_type = ColumnType::TIMESTAMP {
             with_time_zone: Some(true),
+            precision: None,
         };
         assert_eq!(
             generator.map_column_type(&col_type, None),
@@ -825,6 +988,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_alter_column_type_char_varchar_notes_padding() {
+        let generator = PostgresSqlGenerator::new();
+        let table = create_test_table();
+
+        // CHAR(2) → VARCHAR(2)（同じ長さでも末尾の空白の扱いが変わる）
+        let old_column = Column::new("name".to_string(), ColumnType::CHAR { length: 2 }, false);
+        let new_column = Column::new("name".to_string(), ColumnType::VARCHAR { length: 2 }, false);
+        let diff = ColumnDiff::new("name".to_string(), old_column, new_column);
+
+        let up = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec![concat!(
+                "-- NOTE: CHAR(2) → VARCHAR(2) removes the trailing spaces of existing values; ",
+                "use USING rpad(\"name\", 2) to keep them\n",
+                r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE VARCHAR(2)"#
+            )]
+        );
+
+        let down = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec![concat!(
+                "-- NOTE: VARCHAR(2) → CHAR(2) pads values shorter than 2 characters with trailing spaces\n",
+                r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE CHAR(2)"#
+            )]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_type_uses_type_change_using() {
+        let generator = PostgresSqlGenerator::new();
+        let table = create_test_table();
+
+        // VARCHAR → INTEGER を YAML の USING 式で変換し、down_using で戻す
+        let old_column = Column::new(
+            "name".to_string(),
+            ColumnType::VARCHAR { length: 255 },
+            false,
+        );
+        let mut new_column = Column::new(
+            "name".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        );
+        new_column.type_change_using = Some(r#"NULLIF("name", '')::integer"#.to_string());
+        new_column.down_using = Some(r#""name"::text"#.to_string());
+        let diff = ColumnDiff::new("name".to_string(), old_column, new_column);
+
+        let up = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec![
+                r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE INTEGER USING NULLIF("name", '')::integer"#
+            ]
+        );
+
+        let down = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec![r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE VARCHAR(255) USING "name"::text"#]
+        );
+    }
+
+    #[test]
+    fn test_alter_column_type_ignores_using_from_previous_migration() {
+        let generator = PostgresSqlGenerator::new();
+        let table = create_test_table();
+
+        // スナップショットに残っている式は前回の型変更のもの
+        let mut old_column = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        old_column.type_change_using = Some(r#"NULLIF("id", '')::integer"#.to_string());
+        let mut new_column = old_column.clone();
+        new_column.column_type = ColumnType::INTEGER { precision: Some(8) };
+        let diff = ColumnDiff::new("id".to_string(), old_column, new_column);
+
+        let sql = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+
+        assert_eq!(
+            sql,
+            vec![r#"ALTER TABLE "users" ALTER COLUMN "id" TYPE BIGINT"#]
+        );
+    }
+
     #[test]
     fn test_alter_column_type_down_direction() {
         let generator = PostgresSqlGenerator::new();
@@ -853,6 +1105,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_alter_column_type_decimal_precision_change_no_using() {
+        let generator = PostgresSqlGenerator::new();
+        let table = create_test_table();
+
+        // DECIMAL(10, 2) → DECIMAL(8, 2)（NUMERIC同士は暗黙変換）
+        let old_column = Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            false,
+        );
+        let new_column = Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 8,
+                scale: 2,
+            },
+            false,
+        );
+        let diff = ColumnDiff::new("price".to_string(), old_column, new_column);
+
+        let up = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec![r#"ALTER TABLE "users" ALTER COLUMN "price" TYPE NUMERIC(8, 2)"#]
+        );
+
+        let down = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec![r#"ALTER TABLE "users" ALTER COLUMN "price" TYPE NUMERIC(10, 2)"#]
+        );
+    }
+
     #[test]
     fn test_alter_column_type_datetime_to_string_no_using() {
         let generator = PostgresSqlGenerator::new();
@@ -863,6 +1152,7 @@ mod tests {
             "name".to_string(),
             ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: None,
             },
             false,
         );
@@ -889,6 +1179,7 @@ mod tests {
             "name".to_string(),
             ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: None,
             },
             false,
         );
@@ -973,6 +1264,53 @@ mod tests {
         );
     }
 
+    fn create_renamed_index() -> RenamedIndex {
+        let old_index = Index::new("idx_email".to_string(), vec!["email".to_string()], true);
+        let mut new_index = old_index.clone();
+        new_index.name = "users_email_key".to_string();
+        new_index.renamed_from = Some("idx_email".to_string());
+        RenamedIndex {
+            old_name: "idx_email".to_string(),
+            old_index,
+            new_index,
+        }
+    }
+
+    #[test]
+    fn test_generate_rename_index() {
+        let generator = PostgresSqlGenerator::new();
+        let table = create_test_table();
+        let renamed = create_renamed_index();
+
+        let up = generator.generate_rename_index(&table, &renamed, MigrationDirection::Up);
+        assert_eq!(
+            up,
+            vec![r#"ALTER INDEX "idx_email" RENAME TO "users_email_key""#.to_string()]
+        );
+
+        let down = generator.generate_rename_index(&table, &renamed, MigrationDirection::Down);
+        assert_eq!(
+            down,
+            vec![r#"ALTER INDEX "users_email_key" RENAME TO "idx_email""#.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_generate_rename_index_schema_qualified() {
+        let generator = PostgresSqlGenerator::new();
+        let table = Table::new("app.users".to_string());
+
+        let sql = generator.generate_rename_index(
+            &table,
+            &create_renamed_index(),
+            MigrationDirection::Up,
+        );
+        assert_eq!(
+            sql,
+            vec![r#"ALTER INDEX "app"."idx_email" RENAME TO "users_email_key""#.to_string()]
+        );
+    }
+
     // ==========================================
     // SERIAL変換のテスト
     // ==========================================
@@ -1133,6 +1471,36 @@ mod tests {
     // 制約メソッドのテスト
     // ==========================================
 
+    #[test]
+    fn test_constraint_names_follow_identifier_case() {
+        let constraint = Constraint::FOREIGN_KEY {
+            columns: vec!["USER_ID".to_string()],
+            referenced_table: "USERS".to_string(),
+            referenced_columns: vec!["ID".to_string()],
+            on_delete: None,
+            on_update: None,
+        };
+
+        let upper = PostgresSqlGenerator::new().with_identifier_case(IdentifierCase::Upper);
+        assert_eq!(
+            upper.generate_add_constraint_for_existing_table("POSTS", &constraint),
+            r#"ALTER TABLE "POSTS" ADD CONSTRAINT "FK_POSTS_USER_ID_USERS" FOREIGN KEY ("USER_ID") REFERENCES "USERS" ("ID")"#
+        );
+        assert_eq!(
+            upper.generate_drop_constraint_for_existing_table("POSTS", &constraint),
+            r#"ALTER TABLE "POSTS" DROP CONSTRAINT IF EXISTS "FK_POSTS_USER_ID_USERS""#
+        );
+
+        let unique = Constraint::UNIQUE {
+            columns: vec!["email".to_string()],
+        };
+        let lower = PostgresSqlGenerator::new().with_identifier_case(IdentifierCase::Lower);
+        assert_eq!(
+            lower.generate_add_constraint_for_existing_table("users", &unique),
+            r#"ALTER TABLE "users" ADD CONSTRAINT "uq_users_email" UNIQUE ("email")"#
+        );
+    }
+
     #[test]
     fn test_generate_add_constraint_for_existing_table_foreign_key() {
         let generator = PostgresSqlGenerator::new();
@@ -1290,4 +1658,130 @@ mod tests {
 
         assert!(sql.is_empty());
     }
+
+    // ==========================================
+    // スキーマ修飾テーブルのテスト
+    // ==========================================
+
+    #[test]
+    fn test_generate_create_index_with_access_method() {
+        let generator = PostgresSqlGenerator::new();
+        let table = Table::new("documents".to_string());
+
+        let gin = Index::new("idx_tags".to_string(), vec!["tags".to_string()], false)
+            .with_index_type(IndexType::Gin)
+            .with_where("archived = false");
+        assert_eq!(
+            generator.generate_create_index(&table, &gin),
+            r#"CREATE INDEX "idx_tags" ON "documents" USING gin ("tags") WHERE archived = false"#
+        );
+
+        let default = Index::new("idx_title".to_string(), vec!["title".to_string()], true);
+        assert_eq!(
+            generator.generate_create_index(&table, &default),
+            r#"CREATE UNIQUE INDEX "idx_title" ON "documents" ("title")"#
+        );
+    }
+
+    #[test]
+    fn test_schema_qualified_table_statements() {
+        let generator = PostgresSqlGenerator::new();
+        let mut table = Table::new_in_schema("app".to_string(), "users");
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["id".to_string()],
+            referenced_table: "audit.accounts".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        let index = crate::core::schema::Index::new(
+            "idx_users_id".to_string(),
+            vec!["id".to_string()],
+            false,
+        );
+
+        assert!(generator
+            .generate_create_table(&table)
+            .starts_with(r#"CREATE TABLE "app"."users""#));
+        assert_eq!(
+            generator.generate_create_index(&table, &index),
+            r#"CREATE INDEX "idx_users_id" ON "app"."users" ("id")"#
+        );
+        assert_eq!(
+            generator.generate_drop_index("app.users", "idx_users_id"),
+            r#"DROP INDEX "app"."idx_users_id""#
+        );
+        assert_eq!(
+            generator.generate_drop_table("app.users"),
+            r#"DROP TABLE "app"."users""#
+        );
+        assert_eq!(
+            generator.generate_rename_table("app.members", "app.users"),
+            r#"ALTER TABLE "app"."members" RENAME TO "users""#
+        );
+        assert_eq!(
+            generator.generate_alter_table_add_constraint(&table, 0),
+            r#"ALTER TABLE "app"."users" ADD CONSTRAINT "fk_app.users_id_audit.accounts" FOREIGN KEY ("id") REFERENCES "audit"."accounts" ("id")"#
+        );
+    }
+
+    #[test]
+    fn test_alter_column_integer_to_serial_in_schema() {
+        let generator = PostgresSqlGenerator::new();
+        let table = Table::new_in_schema("app".to_string(), "users");
+
+        let mut old_column = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        old_column.auto_increment = Some(false);
+        let mut new_column = old_column.clone();
+        new_column.auto_increment = Some(true);
+        let diff = ColumnDiff::new("id".to_string(), old_column, new_column);
+
+        let sql = generator.generate_alter_column_type(&table, &diff, MigrationDirection::Up);
+
+        // シーケンスはテーブルと同じスキーマに作成する
+        assert_eq!(
+            sql[0],
+            r#"CREATE SEQUENCE IF NOT EXISTS "app"."users_id_seq""#
+        );
+        assert_eq!(
+            sql[2],
+            r#"ALTER TABLE "app"."users" ALTER COLUMN "id" SET DEFAULT nextval('"app"."users_id_seq"')"#
+        );
+    }
+
+    #[test]
+    fn test_comment_statements_escape_single_quotes() {
+        let generator = PostgresSqlGenerator::new();
+        let mut column = Column::new("email".to_string(), ColumnType::TEXT, false);
+        column.comment = Some("User's login".to_string());
+
+        assert_eq!(
+            generator.generate_column_comment("app.users", &column),
+            vec![r#"COMMENT ON COLUMN "app"."users"."email" IS 'User''s login'"#]
+        );
+        assert_eq!(
+            generator.generate_table_comment("users", Some("Registered users")),
+            vec![r#"COMMENT ON TABLE "users" IS 'Registered users'"#]
+        );
+
+        // コメントの削除は NULL を設定する
+        column.comment = Some(String::new());
+        assert_eq!(
+            generator.generate_column_comment("users", &column),
+            vec![r#"COMMENT ON COLUMN "users"."email" IS NULL"#]
+        );
+        assert_eq!(
+            generator.generate_table_comment("users", None),
+            vec![r#"COMMENT ON TABLE "users" IS NULL"#]
+        );
+    }
 }
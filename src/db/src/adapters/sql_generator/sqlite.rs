@@ -13,10 +13,25 @@ use crate::core::config::Dialect;
 use crate::core::schema::{Column, ColumnType, Constraint, Table};
 use crate::core::schema_diff::{ColumnDiff, RenamedColumn};
 
+/// ROWIDのエイリアスとなるカラムを取得
+///
+/// SQLiteでは単一カラムの INTEGER PRIMARY KEY が ROWID のエイリアスとなり、
+/// AUTOINCREMENT キーワードの有無に関わらず自動採番されます。
+pub(crate) fn sqlite_rowid_alias_column(table: &Table) -> Option<&Column> {
+    let pk_columns = table.get_primary_key_columns()?;
+    let [pk_column] = pk_columns.as_slice() else {
+        return None;
+    };
+    table
+        .get_column(pk_column)
+        .filter(|column| column.column_type.integer_width().is_some())
+}
+
 /// SQLite用SQLジェネレーター
 #[derive(Debug, Clone)]
 pub struct SqliteSqlGenerator {
     type_mapping: TypeMappingService,
+    strict_autoincrement: bool,
 }
 
 impl SqliteSqlGenerator {
@@ -24,9 +39,24 @@ impl SqliteSqlGenerator {
     pub fn new() -> Self {
         Self {
             type_mapping: TypeMappingService::new(Dialect::SQLite),
+            strict_autoincrement: false,
         }
     }
 
+    /// AUTOINCREMENTキーワードを明示的に出力するかを設定
+    ///
+    /// 有効な場合、auto_increment: true の INTEGER PRIMARY KEY カラムを
+    /// `PRIMARY KEY AUTOINCREMENT` としてカラム定義内に出力します。
+    pub fn with_strict_autoincrement(mut self, strict_autoincrement: bool) -> Self {
+        self.strict_autoincrement = strict_autoincrement;
+        self
+    }
+
+    /// テーブル再作成サービスを作成
+    fn table_recreator(&self) -> SqliteTableRecreator {
+        SqliteTableRecreator::new().with_strict_autoincrement(self.strict_autoincrement)
+    }
+
     /// ColumnTypeをSQLiteの型文字列にマッピング
     ///
     /// TypeMappingServiceに委譲して型変換を行います。
@@ -35,6 +65,14 @@ impl SqliteSqlGenerator {
     }
 }
 
+/// SQLコメント行に埋め込むコメント（改行は空白に置換）
+fn comment_line_text(comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => comment.replace(['\r', '\n'], " "),
+        None => "comment removed".to_string(),
+    }
+}
+
 impl SqlGenerator for SqliteSqlGenerator {
     fn quote_identifier(&self, name: &str) -> String {
         quote_identifier_sqlite(name)
@@ -47,7 +85,7 @@ impl SqlGenerator for SqliteSqlGenerator {
     fn generate_column_definition(&self, column: &Column) -> String {
         let type_str = self.map_column_type(&column.column_type);
         let quoted_name = quote_identifier_sqlite(&column.name);
-        build_column_definition(&quoted_name, column, type_str, &[])
+        build_column_definition(&quoted_name, column, type_str, &[], Dialect::SQLite)
     }
 
     fn generate_constraint_definition(&self, constraint: &Constraint) -> String {
@@ -85,14 +123,44 @@ impl SqlGenerator for SqliteSqlGenerator {
 
                 sql
             }
+            // Constraint は #[non_exhaustive] のため、新しい種類はここで対応を追加する
+            other => unreachable!("SQLite generator does not support constraint {:?}", other),
         }
     }
 
+    /// SQLiteはコメントを保存できないため、SQLコメント行として残す
+    fn generate_table_comment(&self, table_name: &str, comment: Option<&str>) -> Vec<String> {
+        vec![format!(
+            "-- Table {}: {}",
+            quote_identifier_sqlite(table_name),
+            comment_line_text(comment)
+        )]
+    }
+
+    /// SQLiteはコメントを保存できないため、SQLコメント行として残す
+    fn generate_column_comment(&self, table_name: &str, column: &Column) -> Vec<String> {
+        vec![format!(
+            "-- Column {}.{}: {}",
+            quote_identifier_sqlite(table_name),
+            quote_identifier_sqlite(&column.name),
+            comment_line_text(column.comment_text())
+        )]
+    }
+
     /// SQLiteは全制約をCREATE TABLE内で定義
     fn should_add_as_table_constraint(&self, _constraint: &Constraint) -> bool {
         true
     }
 
+    /// CREATE TABLE文を生成
+    ///
+    /// strict_autoincrement が有効な場合は PRIMARY KEY をカラム定義内に出力するため、
+    /// テーブル再作成と同じ生成ロジックを使用します。
+    fn generate_create_table(&self, table: &Table) -> String {
+        self.table_recreator()
+            .generate_create_table_with_name(table, &table.name)
+    }
+
     fn generate_alter_table_add_constraint(
         &self,
         _table: &Table,
@@ -123,7 +191,7 @@ impl SqlGenerator for SqliteSqlGenerator {
         column_diff: &ColumnDiff,
         direction: MigrationDirection,
     ) -> Vec<String> {
-        let recreator = SqliteTableRecreator::new();
+        let recreator = self.table_recreator();
         recreator.generate_table_recreation(table, column_diff, direction)
     }
 
@@ -149,7 +217,7 @@ impl SqlGenerator for SqliteSqlGenerator {
         _column_diff: &ColumnDiff,
         _direction: MigrationDirection,
     ) -> Vec<String> {
-        let recreator = SqliteTableRecreator::new();
+        let recreator = self.table_recreator();
         recreator.generate_table_recreation_with_old_table(table, old_table)
     }
 
@@ -206,6 +274,8 @@ mod tests {
     use super::*;
     use crate::core::schema::Index;
     use crate::core::schema_diff::ColumnChange;
+    use crate::core::schema_diff::RenamedIndex;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_new_generator() {
@@ -239,6 +309,7 @@ mod tests {
         let generator = SqliteSqlGenerator::new();
         let col_type = ColumnType::TIMESTAMP {
             with_time_zone: Some(false),
+            precision: None,
         };
         assert_eq!(generator.map_column_type(&col_type), "TEXT");
     }
@@ -468,8 +539,12 @@ mod tests {
         // インデックスを追加
         table.indexes.push(Index {
             name: "idx_users_name".to_string(),
-            columns: vec!["name".to_string()],
+            columns: vec!["name".into()],
             unique: false,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         });
 
         let old_column = Column::new(
@@ -618,4 +693,136 @@ mod tests {
             r#"ALTER TABLE "users" RENAME COLUMN "user_name" TO "name""#
         );
     }
+
+    fn create_renamed_index() -> RenamedIndex {
+        let old_index = Index::new("idx_email".to_string(), vec!["email".to_string()], true);
+        let mut new_index = old_index.clone();
+        new_index.name = "users_email_key".to_string();
+        new_index.renamed_from = Some("idx_email".to_string());
+        RenamedIndex {
+            old_name: "idx_email".to_string(),
+            old_index,
+            new_index,
+        }
+    }
+
+    #[test]
+    fn test_generate_rename_index_recreates() {
+        // SQLiteにはインデックスのリネーム構文がないため削除+作成になる
+        let generator = SqliteSqlGenerator::new();
+        let table = create_test_table();
+        let renamed = create_renamed_index();
+
+        let up = generator.generate_rename_index(&table, &renamed, MigrationDirection::Up);
+        assert_eq!(up.len(), 2);
+        assert!(up[0].starts_with("-- NOTE: Index rename is not supported"));
+        assert!(up[0].ends_with(r#"DROP INDEX "idx_email""#));
+        assert_eq!(
+            up[1],
+            r#"CREATE UNIQUE INDEX "users_email_key" ON "users" ("email")"#
+        );
+
+        let down = generator.generate_rename_index(&table, &renamed, MigrationDirection::Down);
+        assert!(down[0].ends_with(r#"DROP INDEX "users_email_key""#));
+        assert_eq!(
+            down[1],
+            r#"CREATE UNIQUE INDEX "idx_email" ON "users" ("email")"#
+        );
+    }
+
+    #[test]
+    fn test_generate_create_table_with_folded_identifiers() {
+        // SQLiteは制約名を生成しないため、identifier_case は変換済みのスキーマで反映される
+        use crate::core::config::IdentifierCase;
+        use crate::services::identifier_case::fold_schema_identifiers;
+
+        let mut schema = crate::core::schema::Schema::new("1.0".to_string());
+        let mut table = create_test_table();
+        table.add_index(Index::new(
+            "idx_users_name".to_string(),
+            vec!["name".to_string()],
+            false,
+        ));
+        schema.add_table(table);
+        let generator = SqliteSqlGenerator::new();
+
+        let upper = fold_schema_identifiers(&schema, IdentifierCase::Upper);
+        let table = upper.tables.get("USERS").unwrap();
+        let sql = generator.generate_create_table(table);
+        assert!(sql.starts_with(r#"CREATE TABLE "USERS""#), "{}", sql);
+        assert!(sql.contains(r#""NAME""#), "{}", sql);
+        assert_eq!(
+            generator.generate_create_index(table, &table.indexes[0]),
+            r#"CREATE INDEX "IDX_USERS_NAME" ON "USERS" ("NAME")"#
+        );
+    }
+
+    // ==========================================
+    // strict_autoincrement のテスト
+    // ==========================================
+
+    fn create_autoincrement_table() -> Table {
+        let mut table = create_test_table();
+        table.columns[0].auto_increment = Some(true);
+        table.constraints.push(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        table
+    }
+
+    #[test]
+    fn test_generate_create_table_without_strict_autoincrement() {
+        let generator = SqliteSqlGenerator::new();
+        let sql = generator.generate_create_table(&create_autoincrement_table());
+
+        assert!(!sql.contains("AUTOINCREMENT"));
+        assert!(sql.contains(r#"PRIMARY KEY ("id")"#));
+    }
+
+    #[test]
+    fn test_generate_create_table_with_strict_autoincrement() {
+        let generator = SqliteSqlGenerator::new().with_strict_autoincrement(true);
+        let sql = generator.generate_create_table(&create_autoincrement_table());
+
+        assert!(sql.contains(r#""id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT"#));
+        assert!(!sql.contains(r#"PRIMARY KEY ("id")"#));
+    }
+
+    #[test]
+    fn test_sqlite_rowid_alias_column_requires_single_integer_primary_key() {
+        let table = create_autoincrement_table();
+        assert_eq!(sqlite_rowid_alias_column(&table).unwrap().name, "id");
+
+        let mut composite = create_test_table();
+        composite.constraints.push(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string(), "name".to_string()],
+        });
+        assert!(sqlite_rowid_alias_column(&composite).is_none());
+
+        let mut text_pk = create_test_table();
+        text_pk.constraints.push(Constraint::PRIMARY_KEY {
+            columns: vec!["name".to_string()],
+        });
+        assert!(sqlite_rowid_alias_column(&text_pk).is_none());
+    }
+
+    #[test]
+    fn test_comments_are_sql_comment_lines() {
+        let generator = SqliteSqlGenerator::new();
+        let mut table = create_test_table();
+        table.comment = Some("Registered\nusers".to_string());
+        table.columns[1].comment = Some("Display name".to_string());
+
+        assert_eq!(
+            generator.generate_create_table_comments(&table),
+            vec![
+                r#"-- Table "users": Registered users"#,
+                r#"-- Column "users"."name": Display name"#,
+            ]
+        );
+        assert_eq!(
+            generator.generate_table_comment("users", None),
+            vec![r#"-- Table "users": comment removed"#]
+        );
+    }
 }
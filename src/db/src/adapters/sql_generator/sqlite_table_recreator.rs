@@ -3,8 +3,10 @@
 // SQLiteはALTER COLUMN TYPEをサポートしていないため、
 // テーブル再作成パターンで型変更を実現します。
 
+use crate::adapters::sql_generator::sqlite::sqlite_rowid_alias_column;
 use crate::adapters::sql_generator::{
-    format_check_constraint, quote_columns_sqlite, quote_identifier_sqlite, MigrationDirection,
+    format_check_constraint, format_index_columns, index_where_clause, quote_columns_sqlite,
+    quote_identifier_sqlite, render_default_value, MigrationDirection,
 };
 use crate::adapters::type_mapping::TypeMappingService;
 use crate::core::config::Dialect;
@@ -17,6 +19,7 @@ use crate::core::schema_diff::ColumnDiff;
 /// 12ステップのテーブル再作成手順を生成します。
 pub struct SqliteTableRecreator {
     type_mapping: TypeMappingService,
+    strict_autoincrement: bool,
 }
 
 impl SqliteTableRecreator {
@@ -24,9 +27,16 @@ impl SqliteTableRecreator {
     pub fn new() -> Self {
         Self {
             type_mapping: TypeMappingService::new(Dialect::SQLite),
+            strict_autoincrement: false,
         }
     }
 
+    /// AUTOINCREMENTキーワードを明示的に出力するかを設定
+    pub fn with_strict_autoincrement(mut self, strict_autoincrement: bool) -> Self {
+        self.strict_autoincrement = strict_autoincrement;
+        self
+    }
+
     /// テーブル再作成SQLを生成
     ///
     /// # Arguments
@@ -118,7 +128,11 @@ impl SqliteTableRecreator {
     }
 
     /// 指定した名前でCREATE TABLE文を生成
-    fn generate_create_table_with_name(&self, table: &Table, table_name: &str) -> String {
+    pub(crate) fn generate_create_table_with_name(
+        &self,
+        table: &Table,
+        table_name: &str,
+    ) -> String {
         let mut parts = Vec::new();
 
         parts.push(format!(
@@ -129,13 +143,29 @@ impl SqliteTableRecreator {
 
         let mut elements = Vec::new();
 
+        // AUTOINCREMENTを明示する場合、PRIMARY KEYはカラム定義内に出力する
+        let autoincrement_column = self
+            .strict_autoincrement
+            .then(|| sqlite_rowid_alias_column(table))
+            .flatten()
+            .filter(|column| column.is_auto_increment());
+
         // カラム定義
         for column in &table.columns {
-            elements.push(format!("    {}", self.generate_column_definition(column)));
+            let mut column_def = self.generate_column_definition(column);
+            if autoincrement_column.is_some_and(|c| c.name == column.name) {
+                column_def.push_str(" PRIMARY KEY AUTOINCREMENT");
+            }
+            elements.push(format!("    {}", column_def));
         }
 
         // テーブル制約
         for constraint in &table.constraints {
+            if autoincrement_column.is_some()
+                && matches!(constraint, Constraint::PRIMARY_KEY { .. })
+            {
+                continue;
+            }
             let constraint_def = self.generate_constraint_definition(constraint);
             if !constraint_def.is_empty() {
                 elements.push(format!("    {}", constraint_def));
@@ -155,12 +185,19 @@ impl SqliteTableRecreator {
         parts.push(quote_identifier_sqlite(&column.name));
         parts.push(self.map_column_type(&column.column_type));
 
+        if let Some(ref generated) = column.generated {
+            parts.push(generated.to_sql_clause());
+        }
+
         if !column.nullable {
             parts.push("NOT NULL".to_string());
         }
 
         if let Some(ref default_value) = column.default_value {
-            parts.push(format!("DEFAULT {}", default_value));
+            parts.push(format!(
+                "DEFAULT {}",
+                render_default_value(default_value, &column.column_type, Dialect::SQLite)
+            ));
         }
 
         parts.join(" ")
@@ -208,6 +245,11 @@ impl SqliteTableRecreator {
 
                 sql
             }
+            // Constraint は #[non_exhaustive] のため、新しい種類はここで対応を追加する
+            other => unreachable!(
+                "SQLite table recreator does not support constraint {:?}",
+                other
+            ),
         }
     }
 
@@ -239,6 +281,11 @@ impl SqliteTableRecreator {
         let mut select_expressions = Vec::new();
 
         for column in &new_table.columns {
+            // 生成カラムには値を挿入できないため、コピー対象から除外
+            if column.is_generated() {
+                continue;
+            }
+
             insert_columns.push(quote_identifier_sqlite(&column.name));
 
             if old_columns.contains(column.name.as_str()) {
@@ -247,7 +294,11 @@ impl SqliteTableRecreator {
             } else {
                 // 追加されたカラム: DEFAULT値またはNULLを使用
                 if let Some(ref default_value) = column.default_value {
-                    select_expressions.push(default_value.clone());
+                    select_expressions.push(render_default_value(
+                        default_value,
+                        &column.column_type,
+                        Dialect::SQLite,
+                    ));
                 } else if column.nullable {
                     select_expressions.push("NULL".to_string());
                 } else {
@@ -272,7 +323,9 @@ impl SqliteTableRecreator {
     /// NOT NULLカラムのフォールバック値を取得
     fn get_fallback_value(&self, column_type: &ColumnType) -> String {
         match column_type {
-            ColumnType::INTEGER { .. } => "0".to_string(),
+            ColumnType::INTEGER { .. } | ColumnType::SMALLINT | ColumnType::BIGINT => {
+                "0".to_string()
+            }
             ColumnType::FLOAT | ColumnType::DOUBLE => "0.0".to_string(),
             ColumnType::BOOLEAN => "0".to_string(),
             ColumnType::VARCHAR { .. }
@@ -286,6 +339,8 @@ impl SqliteTableRecreator {
             ColumnType::JSON | ColumnType::JSONB => "'{}'".to_string(),
             ColumnType::BLOB => "X''".to_string(),
             ColumnType::Enum { .. } | ColumnType::DialectSpecific { .. } => "''".to_string(),
+            // ColumnType は #[non_exhaustive] のため、新しい型はここでフォールバック値を追加する
+            other => unreachable!("no NOT NULL fallback value for column type {:?}", other),
         }
     }
 
@@ -298,11 +353,12 @@ impl SqliteTableRecreator {
         };
 
         format!(
-            "CREATE {} {} ON {} ({})",
+            "CREATE {} {} ON {} ({}){}",
             index_type,
             quote_identifier_sqlite(&index.name),
             quote_identifier_sqlite(&table.name),
-            quote_columns_sqlite(&index.columns)
+            format_index_columns(&index.columns, quote_identifier_sqlite, false),
+            index_where_clause(index)
         )
     }
 }
@@ -316,7 +372,7 @@ impl Default for SqliteTableRecreator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::schema::Column;
+    use crate::core::schema::{Column, GeneratedColumn};
 
     fn create_test_table() -> Table {
         let mut table = Table::new("users".to_string());
@@ -465,7 +521,8 @@ mod tests {
         assert_eq!(recreator.map_column_type(&ColumnType::BOOLEAN), "INTEGER");
         assert_eq!(
             recreator.map_column_type(&ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }),
             "TEXT"
         );
@@ -670,6 +727,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_data_copy_skips_generated_columns() {
+        let recreator = SqliteTableRecreator::new();
+
+        // 旧テーブル: id, total（通常カラム）
+        let mut old_table = Table::new("orders".to_string());
+        old_table.columns.push(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        old_table.columns.push(Column::new(
+            "total".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        ));
+
+        // 新テーブル: id, total（生成カラム）
+        let mut new_table = old_table.clone();
+        new_table.columns[1].generated = Some(GeneratedColumn::new("id * 2", true));
+
+        let statements =
+            recreator.generate_table_recreation_with_old_table(&new_table, Some(&old_table));
+
+        assert!(statements[2].contains(r#""total" INTEGER GENERATED ALWAYS AS (id * 2) STORED"#));
+        assert_eq!(
+            statements[3],
+            r#"INSERT INTO "_stratum_tmp_recreate_orders" ("id") SELECT "id" FROM "orders""#
+        );
+    }
+
     #[test]
     fn test_data_copy_without_old_table_info() {
         let recreator = SqliteTableRecreator::new();
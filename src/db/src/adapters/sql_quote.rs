@@ -1,8 +1,30 @@
 // SQL識別子クォートユーティリティ
 //
-// 各データベース方言用の識別子クォート関数を提供します。
+// 各データベース方言用の識別子クォート関数と、クォートでは安全に扱えない
+// 制御文字の検出関数を提供します。
 // type_mappingとsql_generatorの両方から使用される共有モジュールです。
 
+use crate::core::config::Dialect;
+
+/// 方言に応じた識別子クォート
+///
+/// 方言ごとの関数（[`quote_identifier_postgres`] など）に振り分けます。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::sql_quote::quote_identifier;
+/// use strata_db::core::config::Dialect;
+/// assert_eq!(quote_identifier(Dialect::PostgreSQL, "users"), r#""users""#);
+/// assert_eq!(quote_identifier(Dialect::MySQL, "users"), "`users`");
+/// ```
+pub fn quote_identifier(dialect: Dialect, name: &str) -> String {
+    match dialect {
+        Dialect::PostgreSQL => quote_identifier_postgres(name),
+        Dialect::MySQL => quote_identifier_mysql(name),
+        Dialect::SQLite => quote_identifier_sqlite(name),
+    }
+}
+
 /// PostgreSQL用識別子クォート（ダブルクォート）
 ///
 /// 識別子内のダブルクォートは二重にエスケープします。
@@ -45,6 +67,24 @@ pub fn quote_identifier_sqlite(name: &str) -> String {
     format!("\"{}\"", name.replace('"', "\"\""))
 }
 
+/// 識別子に含まれる制御文字を検出
+///
+/// NUL・改行・タブなどの制御文字はクォートしても安全に扱えない
+/// （NULで文字列が切れる、生成SQLのコメント行から抜け出す等）ため、
+/// スキーマ検証で識別子として拒否するために使用します。
+/// 最初に見つかった制御文字を返します。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::sql_quote::find_control_character;
+/// assert_eq!(find_control_character("users"), None);
+/// assert_eq!(find_control_character("user\nname"), Some('\n'));
+/// assert_eq!(find_control_character("nul\0"), Some('\0'));
+/// ```
+pub fn find_control_character(name: &str) -> Option<char> {
+    name.chars().find(|c| c.is_control())
+}
+
 /// カラム名リストをクォートしてカンマ区切りで結合（PostgreSQL用）
 pub fn quote_columns_postgres(columns: &[String]) -> String {
     columns
@@ -93,6 +133,62 @@ pub fn quote_regclass_postgres(name: &str) -> String {
     format!("'\"{}\"'", escaped_sq)
 }
 
+/// PostgreSQL用テーブル名クォート
+///
+/// `schema.table` 形式の修飾名は、最初の `.` で分割してスキーマ名とテーブル名を個別にクォートします。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::sql_quote::quote_table_postgres;
+/// assert_eq!(quote_table_postgres("users"), r#""users""#);
+/// assert_eq!(quote_table_postgres("app.users"), r#""app"."users""#);
+/// ```
+pub fn quote_table_postgres(name: &str) -> String {
+    match name.split_once('.') {
+        Some((schema, table)) => format!(
+            "{}.{}",
+            quote_identifier_postgres(schema),
+            quote_identifier_postgres(table)
+        ),
+        None => quote_identifier_postgres(name),
+    }
+}
+
+/// PostgreSQL用regclass文字列リテラル（修飾名対応）
+///
+/// [`quote_table_postgres`] でクォートした名前を文字列リテラルとして返します。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::sql_quote::quote_regclass_table_postgres;
+/// assert_eq!(quote_regclass_table_postgres("users_id_seq"), r#"'"users_id_seq"'"#);
+/// assert_eq!(quote_regclass_table_postgres("app.users_id_seq"), r#"'"app"."users_id_seq"'"#);
+/// ```
+pub fn quote_regclass_table_postgres(name: &str) -> String {
+    format!("'{}'", quote_table_postgres(name).replace('\'', "''"))
+}
+
+/// 方言に応じた文字列リテラル
+///
+/// シングルクォートは `''` にエスケープします。
+/// MySQLはバックスラッシュもエスケープ文字として扱うため、`\\` にします。
+///
+/// # Examples
+/// ```
+/// use strata_db::adapters::sql_quote::quote_string_literal;
+/// use strata_db::core::config::Dialect;
+/// assert_eq!(quote_string_literal(Dialect::PostgreSQL, "O'Brien"), "'O''Brien'");
+/// assert_eq!(quote_string_literal(Dialect::PostgreSQL, r"C:\temp"), r"'C:\temp'");
+/// assert_eq!(quote_string_literal(Dialect::MySQL, r"C:\temp"), r"'C:\\temp'");
+/// ```
+pub fn quote_string_literal(dialect: Dialect, value: &str) -> String {
+    let escaped = match dialect {
+        Dialect::MySQL => value.replace('\\', "\\\\"),
+        Dialect::PostgreSQL | Dialect::SQLite => value.to_string(),
+    };
+    format!("'{}'", escaped.replace('\'', "''"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +197,20 @@ mod tests {
     // PostgreSQL quote_identifier tests
     // =========================================================================
 
+    #[test]
+    fn test_quote_table_postgres_qualified() {
+        assert_eq!(quote_table_postgres("users"), r#""users""#);
+        assert_eq!(quote_table_postgres("app.users"), r#""app"."users""#);
+        assert_eq!(
+            quote_table_postgres(r#"a"pp.us"ers"#),
+            r#""a""pp"."us""ers""#
+        );
+        assert_eq!(
+            quote_regclass_table_postgres("app.o'brien_id_seq"),
+            r#"'"app"."o''brien_id_seq"'"#
+        );
+    }
+
     #[test]
     fn test_quote_identifier_postgres_simple() {
         assert_eq!(quote_identifier_postgres("users"), r#""users""#);
@@ -144,6 +254,50 @@ mod tests {
         assert_eq!(quote_identifier_postgres(""), r#""""#);
     }
 
+    #[test]
+    fn test_quote_identifier_hostile_names_round_trip() {
+        // クォート文字を二重化した結果、内側に単独のクォートが残らず元の名前に戻せる
+        for name in [
+            r#"a"; DROP TABLE users; --"#,
+            "\"",
+            "\"\"",
+            "a`b",
+            "x' OR '1'='1",
+            "/* c */",
+            "$$",
+        ] {
+            for (quoted, quote) in [
+                (quote_identifier_postgres(name), "\""),
+                (quote_identifier_sqlite(name), "\""),
+                (quote_identifier_mysql(name), "`"),
+            ] {
+                let doubled = quote.repeat(2);
+                let inner = &quoted[1..quoted.len() - 1];
+                assert!(quoted.starts_with(quote) && quoted.ends_with(quote));
+                assert!(
+                    !inner.replace(&doubled, "").contains(quote),
+                    "{} -> {}",
+                    name,
+                    quoted
+                );
+                assert_eq!(inner.replace(&doubled, quote), name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_control_character() {
+        assert_eq!(find_control_character("users"), None);
+        assert_eq!(find_control_character("ユーザー"), None);
+        assert_eq!(find_control_character(r#"a"b`c;d"#), None);
+        assert_eq!(find_control_character("a\0b"), Some('\0'));
+        assert_eq!(find_control_character("a\nb"), Some('\n'));
+        assert_eq!(find_control_character("a\r\nb"), Some('\r'));
+        assert_eq!(find_control_character("a\tb"), Some('\t'));
+        assert_eq!(find_control_character("a\u{1b}b"), Some('\u{1b}'));
+        assert_eq!(find_control_character("a\u{7f}b"), Some('\u{7f}'));
+    }
+
     // =========================================================================
     // MySQL quote_identifier tests
     // =========================================================================
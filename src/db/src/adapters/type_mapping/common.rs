@@ -2,6 +2,8 @@
 //
 // 複数の方言で共通するColumnType → SQL型文字列の変換を提供します。
 
+use super::TypeMetadata;
+use crate::core::config::Dialect;
 use crate::core::schema::ColumnType;
 
 /// 共通SQL型のフォーマット
@@ -25,3 +27,21 @@ pub fn format_common_sql_type(column_type: &ColumnType) -> Option<String> {
         _ => None,
     }
 }
+
+/// TIMESTAMP / TIME の秒の小数部の桁数を `(p)` の形式で出力
+///
+/// 精度が未指定の場合は空文字列を返し、方言のデフォルト精度に任せます。
+pub fn format_fractional_seconds(precision: Option<u8>) -> String {
+    precision.map(|p| format!("({})", p)).unwrap_or_default()
+}
+
+/// イントロスペクションで取得した秒の小数部の桁数を ColumnType の精度に変換
+///
+/// 方言のデフォルト精度と同じ場合は未指定（None）として扱い、
+/// 精度を書かないスキーマ定義と差分が出ないようにします。
+pub fn parse_datetime_precision(metadata: &TypeMetadata, dialect: Dialect) -> Option<u8> {
+    metadata
+        .datetime_precision
+        .and_then(|p| u8::try_from(p).ok())
+        .filter(|p| Some(*p) != dialect.default_datetime_precision())
+}
@@ -28,6 +28,8 @@ pub struct TypeMetadata {
     pub numeric_precision: Option<u32>,
     /// 数値型の小数点以下桁数
     pub numeric_scale: Option<u32>,
+    /// 日時型の秒の小数部の桁数
+    pub datetime_precision: Option<u32>,
     /// ユーザー定義型名（PostgreSQLのENUM等）
     pub udt_name: Option<String>,
     /// 既知のENUM型名のセット（PostgreSQL用）
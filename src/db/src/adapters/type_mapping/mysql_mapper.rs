@@ -1,8 +1,9 @@
 // MySQL用型マッパー
 
-use super::common::format_common_sql_type;
+use super::common::{format_common_sql_type, format_fractional_seconds, parse_datetime_precision};
 use super::TypeMapper;
 use super::TypeMetadata;
+use crate::core::config::Dialect;
 use crate::core::schema::ColumnType;
 
 /// MySQL用型マッパー
@@ -16,8 +17,8 @@ impl TypeMapper for MySqlTypeMapper {
                 // Strata の precision は意味的な精度であり、表示幅ではない。
                 precision: None,
             }),
-            "smallint" => Some(ColumnType::INTEGER { precision: Some(2) }),
-            "bigint" => Some(ColumnType::INTEGER { precision: Some(8) }),
+            "smallint" => Some(ColumnType::SMALLINT),
+            "bigint" => Some(ColumnType::BIGINT),
             "tinyint" => {
                 // UNSIGNED 修飾子がある場合は DialectSpecific として返す
                 if metadata.is_unsigned {
@@ -56,6 +57,7 @@ impl TypeMapper for MySqlTypeMapper {
             "tinyint(1)" => Some(ColumnType::BOOLEAN),
             "datetime" | "timestamp" => Some(ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: parse_datetime_precision(metadata, Dialect::MySQL),
             }),
             "json" => Some(ColumnType::JSON),
             "decimal" | "numeric" => Some(ColumnType::DECIMAL {
@@ -70,6 +72,7 @@ impl TypeMapper for MySqlTypeMapper {
             "date" => Some(ColumnType::DATE),
             "time" => Some(ColumnType::TIME {
                 with_time_zone: None,
+                precision: parse_datetime_precision(metadata, Dialect::MySQL),
             }),
             "year" => {
                 // YEAR は MySQL 固有の型
@@ -114,19 +117,25 @@ impl TypeMapper for MySqlTypeMapper {
         }
 
         match column_type {
-            ColumnType::INTEGER { precision } => match precision {
-                Some(2) => "SMALLINT".to_string(),
-                Some(8) => "BIGINT".to_string(),
-                _ => "INT".to_string(),
-            },
-            ColumnType::TIMESTAMP { .. } => "TIMESTAMP".to_string(),
+            ColumnType::INTEGER { .. } | ColumnType::SMALLINT | ColumnType::BIGINT => {
+                match column_type.integer_width() {
+                    Some(2) => "SMALLINT".to_string(),
+                    Some(8) => "BIGINT".to_string(),
+                    _ => "INT".to_string(),
+                }
+            }
+            ColumnType::TIMESTAMP { precision, .. } => {
+                format!("TIMESTAMP{}", format_fractional_seconds(*precision))
+            }
             ColumnType::JSONB => "JSON".to_string(),
             ColumnType::DECIMAL { precision, scale } => {
                 format!("DECIMAL({}, {})", precision, scale)
             }
             ColumnType::FLOAT => "FLOAT".to_string(),
             ColumnType::DOUBLE => "DOUBLE".to_string(),
-            ColumnType::TIME { .. } => "TIME".to_string(),
+            ColumnType::TIME { precision, .. } => {
+                format!("TIME{}", format_fractional_seconds(*precision))
+            }
             ColumnType::BLOB => "BLOB".to_string(),
             ColumnType::UUID => "CHAR(36)".to_string(),
             // MySQLは名前付きENUM型をサポートしないため、TEXTにフォールバック
@@ -185,14 +194,8 @@ mod tests {
             service.to_sql_type(&ColumnType::INTEGER { precision: None }),
             "INT"
         );
-        assert_eq!(
-            service.to_sql_type(&ColumnType::INTEGER { precision: Some(2) }),
-            "SMALLINT"
-        );
-        assert_eq!(
-            service.to_sql_type(&ColumnType::INTEGER { precision: Some(8) }),
-            "BIGINT"
-        );
+        assert_eq!(service.to_sql_type(&ColumnType::SMALLINT), "SMALLINT");
+        assert_eq!(service.to_sql_type(&ColumnType::BIGINT), "BIGINT");
     }
 
     #[test]
@@ -272,7 +275,7 @@ mod tests {
         let mapper = MySqlTypeMapper;
         let metadata = TypeMetadata::default();
         let result = mapper.parse_sql_type("smallint", &metadata).unwrap();
-        assert!(matches!(result, ColumnType::INTEGER { precision: Some(2) }));
+        assert!(matches!(result, ColumnType::SMALLINT));
     }
 
     #[test]
@@ -280,7 +283,7 @@ mod tests {
         let mapper = MySqlTypeMapper;
         let metadata = TypeMetadata::default();
         let result = mapper.parse_sql_type("bigint", &metadata).unwrap();
-        assert!(matches!(result, ColumnType::INTEGER { precision: Some(8) }));
+        assert!(matches!(result, ColumnType::BIGINT));
     }
 
     #[test]
@@ -349,7 +352,8 @@ mod tests {
         assert!(matches!(
             result,
             ColumnType::TIME {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }
         ));
     }
@@ -363,7 +367,8 @@ mod tests {
         assert!(matches!(
             result,
             ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }
         ));
 
@@ -371,7 +376,8 @@ mod tests {
         assert!(matches!(
             result,
             ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }
         ));
     }
@@ -439,10 +445,20 @@ mod tests {
         let result = mapper.format_sql_type(
             &ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: None,
             },
             None,
         );
         assert_eq!(result, "TIMESTAMP");
+
+        let result = mapper.format_sql_type(
+            &ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: Some(6),
+            },
+            None,
+        );
+        assert_eq!(result, "TIMESTAMP(6)");
     }
 
     #[test]
@@ -451,6 +467,7 @@ mod tests {
         let result = mapper.format_sql_type(
             &ColumnType::TIME {
                 with_time_zone: None,
+                precision: None,
             },
             None,
         );
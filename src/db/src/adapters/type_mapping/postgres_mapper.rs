@@ -1,9 +1,10 @@
 // PostgreSQL用型マッパー
 
-use super::common::format_common_sql_type;
+use super::common::{format_common_sql_type, format_fractional_seconds, parse_datetime_precision};
 use super::TypeMapper;
 use super::TypeMetadata;
 use crate::adapters::sql_quote::quote_identifier_postgres;
+use crate::core::config::Dialect;
 use crate::core::schema::ColumnType;
 
 /// PostgreSQL内部型名（udt_name）から標準SQL型名への正規化マッピング
@@ -79,8 +80,8 @@ impl TypeMapper for PostgresTypeMapper {
     fn parse_sql_type(&self, sql_type: &str, metadata: &TypeMetadata) -> Option<ColumnType> {
         match sql_type {
             "integer" | "int4" => Some(ColumnType::INTEGER { precision: None }),
-            "smallint" | "int2" => Some(ColumnType::INTEGER { precision: Some(2) }),
-            "bigint" | "int8" => Some(ColumnType::INTEGER { precision: Some(8) }),
+            "smallint" | "int2" => Some(ColumnType::SMALLINT),
+            "bigint" | "int8" => Some(ColumnType::BIGINT),
             "character varying" | "varchar" => Some(ColumnType::VARCHAR {
                 length: metadata.char_max_length.unwrap_or(255),
             }),
@@ -88,9 +89,11 @@ impl TypeMapper for PostgresTypeMapper {
             "boolean" | "bool" => Some(ColumnType::BOOLEAN),
             "timestamp with time zone" | "timestamptz" => Some(ColumnType::TIMESTAMP {
                 with_time_zone: Some(true),
+                precision: parse_datetime_precision(metadata, Dialect::PostgreSQL),
             }),
             "timestamp without time zone" | "timestamp" => Some(ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: parse_datetime_precision(metadata, Dialect::PostgreSQL),
             }),
             "json" => Some(ColumnType::JSON),
             "jsonb" => Some(ColumnType::JSONB),
@@ -106,9 +109,11 @@ impl TypeMapper for PostgresTypeMapper {
             "date" => Some(ColumnType::DATE),
             "time with time zone" | "timetz" => Some(ColumnType::TIME {
                 with_time_zone: Some(true),
+                precision: parse_datetime_precision(metadata, Dialect::PostgreSQL),
             }),
             "time without time zone" | "time" => Some(ColumnType::TIME {
                 with_time_zone: None,
+                precision: parse_datetime_precision(metadata, Dialect::PostgreSQL),
             }),
             "bytea" => Some(ColumnType::BLOB),
             "uuid" => Some(ColumnType::UUID),
@@ -160,6 +165,13 @@ impl TypeMapper for PostgresTypeMapper {
                             params: serde_json::json!({}),
                         });
                     }
+
+                    // citext・DOMAIN・複合型などはTEXTに退化させず、
+                    // 型名をそのまま保持するパススルー型として扱う
+                    return Some(ColumnType::DialectSpecific {
+                        kind: udt_name.clone(),
+                        params: serde_json::json!({}),
+                    });
                 }
 
                 None
@@ -177,26 +189,26 @@ impl TypeMapper for PostgresTypeMapper {
         }
 
         match column_type {
-            ColumnType::INTEGER { precision } => {
-                if auto_increment.unwrap_or(false) {
-                    match precision {
-                        Some(8) => "BIGSERIAL".to_string(),
-                        Some(2) => "SMALLSERIAL".to_string(),
-                        _ => "SERIAL".to_string(),
-                    }
-                } else {
-                    match precision {
-                        Some(2) => "SMALLINT".to_string(),
-                        Some(8) => "BIGINT".to_string(),
-                        _ => "INTEGER".to_string(),
-                    }
+            ColumnType::INTEGER { .. } | ColumnType::SMALLINT | ColumnType::BIGINT => {
+                let serial = auto_increment.unwrap_or(false);
+                match (column_type.integer_width(), serial) {
+                    (Some(2), true) => "SMALLSERIAL".to_string(),
+                    (Some(8), true) => "BIGSERIAL".to_string(),
+                    (_, true) => "SERIAL".to_string(),
+                    (Some(2), false) => "SMALLINT".to_string(),
+                    (Some(8), false) => "BIGINT".to_string(),
+                    (_, false) => "INTEGER".to_string(),
                 }
             }
-            ColumnType::TIMESTAMP { with_time_zone } => {
+            ColumnType::TIMESTAMP {
+                with_time_zone,
+                precision,
+            } => {
+                let precision = format_fractional_seconds(*precision);
                 if with_time_zone.unwrap_or(false) {
-                    "TIMESTAMP WITH TIME ZONE".to_string()
+                    format!("TIMESTAMP{} WITH TIME ZONE", precision)
                 } else {
-                    "TIMESTAMP".to_string()
+                    format!("TIMESTAMP{}", precision)
                 }
             }
             ColumnType::JSONB => "JSONB".to_string(),
@@ -205,11 +217,15 @@ impl TypeMapper for PostgresTypeMapper {
             }
             ColumnType::FLOAT => "REAL".to_string(),
             ColumnType::DOUBLE => "DOUBLE PRECISION".to_string(),
-            ColumnType::TIME { with_time_zone } => {
+            ColumnType::TIME {
+                with_time_zone,
+                precision,
+            } => {
+                let precision = format_fractional_seconds(*precision);
                 if with_time_zone.unwrap_or(false) {
-                    "TIME WITH TIME ZONE".to_string()
+                    format!("TIME{} WITH TIME ZONE", precision)
                 } else {
-                    "TIME".to_string()
+                    format!("TIME{}", precision)
                 }
             }
             ColumnType::BLOB => "BYTEA".to_string(),
@@ -284,14 +300,8 @@ mod tests {
             service.to_sql_type(&ColumnType::INTEGER { precision: None }),
             "INTEGER"
         );
-        assert_eq!(
-            service.to_sql_type(&ColumnType::INTEGER { precision: Some(2) }),
-            "SMALLINT"
-        );
-        assert_eq!(
-            service.to_sql_type(&ColumnType::INTEGER { precision: Some(8) }),
-            "BIGINT"
-        );
+        assert_eq!(service.to_sql_type(&ColumnType::SMALLINT), "SMALLINT");
+        assert_eq!(service.to_sql_type(&ColumnType::BIGINT), "BIGINT");
     }
 
     #[test]
@@ -305,17 +315,11 @@ mod tests {
             "SERIAL"
         );
         assert_eq!(
-            service.to_sql_type_with_auto_increment(
-                &ColumnType::INTEGER { precision: Some(8) },
-                Some(true)
-            ),
+            service.to_sql_type_with_auto_increment(&ColumnType::BIGINT, Some(true)),
             "BIGSERIAL"
         );
         assert_eq!(
-            service.to_sql_type_with_auto_increment(
-                &ColumnType::INTEGER { precision: Some(2) },
-                Some(true)
-            ),
+            service.to_sql_type_with_auto_increment(&ColumnType::SMALLINT, Some(true)),
             "SMALLSERIAL"
         );
     }
@@ -334,16 +338,25 @@ mod tests {
         let service = TypeMappingService::new(Dialect::PostgreSQL);
         assert_eq!(
             service.to_sql_type(&ColumnType::TIMESTAMP {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             }),
             "TIMESTAMP WITH TIME ZONE"
         );
         assert_eq!(
             service.to_sql_type(&ColumnType::TIMESTAMP {
-                with_time_zone: Some(false)
+                with_time_zone: Some(false),
+                precision: None
             }),
             "TIMESTAMP"
         );
+        assert_eq!(
+            service.to_sql_type(&ColumnType::TIMESTAMP {
+                with_time_zone: Some(true),
+                precision: Some(3)
+            }),
+            "TIMESTAMP(3) WITH TIME ZONE"
+        );
     }
 
     #[test]
@@ -393,7 +406,7 @@ mod tests {
         assert!(matches!(result, ColumnType::INTEGER { precision: None }));
 
         let result = service.from_sql_type("bigint", &metadata).unwrap();
-        assert!(matches!(result, ColumnType::INTEGER { precision: Some(8) }));
+        assert!(matches!(result, ColumnType::BIGINT));
     }
 
     #[test]
@@ -421,7 +434,8 @@ mod tests {
         assert!(matches!(
             result,
             ColumnType::TIMESTAMP {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             }
         ));
 
@@ -431,7 +445,8 @@ mod tests {
         assert!(matches!(
             result,
             ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             }
         ));
     }
@@ -678,15 +693,15 @@ mod tests {
         ));
         assert!(matches!(
             mapper.parse_sql_type("int2", &meta),
-            Some(ColumnType::INTEGER { precision: Some(2) })
+            Some(ColumnType::SMALLINT)
         ));
         assert!(matches!(
             mapper.parse_sql_type("smallint", &meta),
-            Some(ColumnType::INTEGER { precision: Some(2) })
+            Some(ColumnType::SMALLINT)
         ));
         assert!(matches!(
             mapper.parse_sql_type("int8", &meta),
-            Some(ColumnType::INTEGER { precision: Some(8) })
+            Some(ColumnType::BIGINT)
         ));
 
         // varchar alias
@@ -719,13 +734,15 @@ mod tests {
         assert!(matches!(
             mapper.parse_sql_type("timestamptz", &meta),
             Some(ColumnType::TIMESTAMP {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             })
         ));
         assert!(matches!(
             mapper.parse_sql_type("timestamp", &meta),
             Some(ColumnType::TIMESTAMP {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             })
         ));
 
@@ -802,25 +819,29 @@ mod tests {
         assert!(matches!(
             mapper.parse_sql_type("time with time zone", &meta),
             Some(ColumnType::TIME {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             })
         ));
         assert!(matches!(
             mapper.parse_sql_type("timetz", &meta),
             Some(ColumnType::TIME {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             })
         ));
         assert!(matches!(
             mapper.parse_sql_type("time without time zone", &meta),
             Some(ColumnType::TIME {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             })
         ));
         assert!(matches!(
             mapper.parse_sql_type("time", &meta),
             Some(ColumnType::TIME {
-                with_time_zone: None
+                with_time_zone: None,
+                precision: None
             })
         ));
 
@@ -845,16 +866,25 @@ mod tests {
         let service = TypeMappingService::new(Dialect::PostgreSQL);
         assert_eq!(
             service.to_sql_type(&ColumnType::TIME {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             }),
             "TIME WITH TIME ZONE"
         );
         assert_eq!(
             service.to_sql_type(&ColumnType::TIME {
-                with_time_zone: Some(false)
+                with_time_zone: Some(false),
+                precision: None
             }),
             "TIME"
         );
+        assert_eq!(
+            service.to_sql_type(&ColumnType::TIME {
+                with_time_zone: None,
+                precision: Some(0)
+            }),
+            "TIME(0)"
+        );
     }
 
     #[test]
@@ -872,18 +902,71 @@ mod tests {
     fn test_postgres_user_defined_not_enum() {
         let mapper = PostgresTypeMapper;
         // USER-DEFINED but unknown type (not ENUM, not known dialect-specific)
+        // はパススルー型として型名を保持する
         let meta = TypeMetadata {
             udt_name: Some("geometry".to_string()),
             enum_names: Some(HashSet::new()),
             ..Default::default()
         };
-        assert!(mapper.parse_sql_type("USER-DEFINED", &meta).is_none());
+        assert_eq!(
+            mapper.parse_sql_type("USER-DEFINED", &meta),
+            Some(ColumnType::DialectSpecific {
+                kind: "geometry".to_string(),
+                params: serde_json::json!({}),
+            })
+        );
 
         // USER-DEFINED with no metadata
         let meta_empty = TypeMetadata::default();
         assert!(mapper.parse_sql_type("USER-DEFINED", &meta_empty).is_none());
     }
 
+    // =========================================================================
+    // citext・DOMAIN・複合型のパススルーテスト
+    // =========================================================================
+
+    #[test]
+    fn test_postgres_passthrough_types_round_trip() {
+        let mapper = PostgresTypeMapper;
+
+        for udt_name in ["citext", "email_address", "billing.address"] {
+            let meta = TypeMetadata {
+                udt_name: Some(udt_name.to_string()),
+                enum_names: Some(HashSet::new()),
+                ..Default::default()
+            };
+            let parsed = mapper
+                .parse_sql_type("USER-DEFINED", &meta)
+                .expect("passthrough type should be preserved");
+            assert_eq!(
+                parsed,
+                ColumnType::DialectSpecific {
+                    kind: udt_name.to_string(),
+                    params: serde_json::json!({}),
+                }
+            );
+            assert_eq!(mapper.format_sql_type(&parsed, None), udt_name);
+        }
+    }
+
+    #[test]
+    fn test_postgres_passthrough_enum_takes_precedence() {
+        let mapper = PostgresTypeMapper;
+        let mut enum_names = HashSet::new();
+        enum_names.insert("status".to_string());
+        let meta = TypeMetadata {
+            udt_name: Some("status".to_string()),
+            enum_names: Some(enum_names),
+            ..Default::default()
+        };
+        assert_eq!(
+            mapper.parse_sql_type("USER-DEFINED", &meta),
+            Some(ColumnType::Enum {
+                name: "status".to_string()
+            })
+        );
+    }
+
     // =========================================================================
     // PostgreSQL固有型 (INET, CIDR, VARBIT) のパース・ラウンドトリップテスト
     // Fixes #23: export時にINET/CIDR/VARBITがTEXTに変換される問題
@@ -11,7 +11,11 @@ impl TypeMapper for SqliteTypeMapper {
     fn parse_sql_type(&self, sql_type: &str, _metadata: &TypeMetadata) -> Option<ColumnType> {
         let upper = sql_type.to_uppercase();
 
-        if upper.contains("INT") {
+        if upper == "BIGINT" || upper == "INT8" {
+            Some(ColumnType::BIGINT)
+        } else if upper == "SMALLINT" || upper == "INT2" {
+            Some(ColumnType::SMALLINT)
+        } else if upper.contains("INT") {
             Some(ColumnType::INTEGER { precision: None })
         } else if upper.contains("CHAR") || upper.contains("VARCHAR") {
             // VARCHAR(255) のような形式から長さを抽出
@@ -40,7 +44,9 @@ impl TypeMapper for SqliteTypeMapper {
         // SQLiteは型アフィニティによる簡略化された型システムを持つため、
         // 共通型ヘルパーは使用せず、すべてSQLite固有のマッピングを行う
         match column_type {
-            ColumnType::INTEGER { .. } => "INTEGER".to_string(),
+            ColumnType::INTEGER { .. } | ColumnType::SMALLINT | ColumnType::BIGINT => {
+                "INTEGER".to_string()
+            }
             ColumnType::VARCHAR { .. } => "TEXT".to_string(),
             ColumnType::TEXT => "TEXT".to_string(),
             ColumnType::BOOLEAN => "INTEGER".to_string(),
@@ -60,6 +66,8 @@ impl TypeMapper for SqliteTypeMapper {
             ColumnType::DialectSpecific { kind, params } => {
                 self.format_dialect_specific(kind, params)
             }
+            // 型アフィニティで受け付けられるTEXTにフォールバック
+            _ => "TEXT".to_string(),
         }
     }
 
@@ -107,7 +115,8 @@ mod tests {
         let service = TypeMappingService::new(Dialect::SQLite);
         assert_eq!(
             service.to_sql_type(&ColumnType::TIMESTAMP {
-                with_time_zone: Some(true)
+                with_time_zone: Some(true),
+                precision: None
             }),
             "TEXT"
         );
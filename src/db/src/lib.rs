@@ -1,6 +1,17 @@
 // Stratum DB crate
+//
+// ライブラリとして組み込む利用者向けに、次の関数はマイナーバージョン間で互換性を保つ
+// （パスとシグネチャは cli/tests/unit_public_api_test.rs で固定している）。
+// - ステートメント分割: services::migration_files::sql_parser
+// - 識別子クォート: adapters::sql_quote
+// - チェックサム計算: services::schema_checksum::SchemaChecksumService
+// - 制約名生成: adapters::constraint_naming
+// - スキーマの組み立て: SchemaBuilder / SchemaBuilderExt
+// CLIの出力形式（OutputFormat など）は strata crate にのみ置く。
 
 pub mod adapters;
 pub mod services;
 
+pub use services::schema_validator::SchemaBuilderExt;
 pub use strata_core::core;
+pub use strata_core::SchemaBuilder;
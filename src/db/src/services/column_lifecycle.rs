@@ -0,0 +1,353 @@
+// カラムのライフサイクル（expand/contract）の管理
+//
+// `lifecycle: contract_pending` のカラムはスキーマ定義から取り除いても削除せず、
+// スナップショットに残して `strata contract` で明示的に削除する。
+// - スキーマ定義から取り除かれた削除待ちカラムをスナップショットから引き継ぐ
+// - 削除待ちになった日付（`contract_pending_since`）を記録・引き継ぐ
+// - 削除待ちカラムの一覧と経過日数を求める
+
+use crate::core::schema::{Column, Schema, Table};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+/// ライフサイクルの反映結果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LifecycleReconciliation {
+    /// スキーマ定義から取り除かれたため、前回のスキーマから引き継いだ削除待ちカラム（`table.column`）
+    pub retained: Vec<String>,
+    /// 今回新たに削除待ちの日付を記録したカラム（`table.column`）
+    pub newly_pending: Vec<String>,
+}
+
+impl LifecycleReconciliation {
+    /// 反映した内容がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.retained.is_empty() && self.newly_pending.is_empty()
+    }
+}
+
+/// 削除待ちのカラム
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractPendingColumn {
+    /// テーブル名
+    pub table: String,
+    /// カラム名
+    pub column: String,
+    /// 削除待ちになった日付（記録されていない場合は None）
+    pub since: Option<NaiveDate>,
+}
+
+impl ContractPendingColumn {
+    /// `table.column` 形式の名前
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.table, self.column)
+    }
+
+    /// `today` 時点で削除待ちになってからの経過日数
+    pub fn age_days(&self, today: NaiveDate) -> Option<i64> {
+        self.since.map(|since| (today - since).num_days())
+    }
+
+    /// `min_age_days` 日以上経過しているか（日付が記録されていない場合は経過していないとみなす）
+    pub fn is_older_than(&self, min_age_days: u64, today: NaiveDate) -> bool {
+        self.age_days(today)
+            .is_some_and(|age| age >= 0 && age as u64 >= min_age_days)
+    }
+}
+
+/// 前回のスキーマの削除待ちカラムの状態を現在のスキーマに反映する
+///
+/// - 前回のスキーマで削除待ちだったカラムがスキーマ定義から取り除かれている場合は、
+///   削除せずに現在のスキーマへ引き継ぐ（削除は `strata contract` で行う）
+/// - 削除待ちのカラムには、前回記録した日付を引き継ぐ。記録がなければ `today` を記録する
+/// - 削除待ちでなくなったカラムの日付は消去する
+///
+/// テーブル自体が削除された場合は、削除待ちカラムも一緒に削除される。
+pub fn reconcile(
+    previous: &Schema,
+    current: &mut Schema,
+    today: NaiveDate,
+) -> LifecycleReconciliation {
+    let mut reconciliation = LifecycleReconciliation::default();
+
+    for (table_name, table) in current.tables.iter_mut() {
+        let previous_table = previous.tables.get(table_name).or_else(|| {
+            table
+                .renamed_from
+                .as_ref()
+                .and_then(|old_name| previous.tables.get(old_name))
+        });
+
+        if let Some(previous_table) = previous_table {
+            for column in retained_columns(previous_table, table) {
+                reconciliation
+                    .retained
+                    .push(format!("{}.{}", table_name, column.name));
+                table.columns.push(column);
+            }
+        }
+
+        for column in table.columns.iter_mut() {
+            if !column.is_contract_pending() {
+                column.contract_pending_since = None;
+                continue;
+            }
+            if column.contract_pending_since.is_some() {
+                continue;
+            }
+            let previous_since = previous_table
+                .and_then(|t| previous_column(t, column))
+                .filter(|c| c.is_contract_pending())
+                .and_then(|c| c.contract_pending_since);
+            match previous_since {
+                Some(since) => column.contract_pending_since = Some(since),
+                None => {
+                    column.contract_pending_since = Some(today);
+                    reconciliation
+                        .newly_pending
+                        .push(format!("{}.{}", table_name, column.name));
+                }
+            }
+        }
+    }
+
+    reconciliation
+}
+
+/// スキーマ内の削除待ちカラムの一覧（テーブル名・宣言順）
+pub fn contract_pending_columns(schema: &Schema) -> Vec<ContractPendingColumn> {
+    schema
+        .tables
+        .iter()
+        .flat_map(|(table_name, table)| {
+            table
+                .columns
+                .iter()
+                .filter(|column| column.is_contract_pending())
+                .map(move |column| ContractPendingColumn {
+                    table: table_name.clone(),
+                    column: column.name.clone(),
+                    since: column.contract_pending_since,
+                })
+        })
+        .collect()
+}
+
+/// 前回のスキーマからライフサイクル（`lifecycle` / `contract_pending_since`）が変わったカラム
+///
+/// 両方のスキーマに存在するカラムについて、`table.column: <lifecycle>` 形式で返す。
+/// ライフサイクルはSQLに影響しないため差分検出の対象外で、スナップショットの更新要否の判定に使用する。
+pub fn lifecycle_changes(previous: &Schema, current: &Schema) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (table_name, table) in &current.tables {
+        let Some(previous_table) = previous.tables.get(table_name) else {
+            continue;
+        };
+        for column in &table.columns {
+            let Some(previous_column) = previous_table.get_column(&column.name) else {
+                continue;
+            };
+            if previous_column.lifecycle == column.lifecycle
+                && previous_column.contract_pending_since == column.contract_pending_since
+            {
+                continue;
+            }
+            let lifecycle = column
+                .lifecycle
+                .map(|lifecycle| lifecycle.to_string())
+                .unwrap_or_else(|| "(none)".to_string());
+            let since = column
+                .contract_pending_since
+                .map(|since| format!(" (since {})", since))
+                .unwrap_or_default();
+            changes.push(format!(
+                "{}.{}: {}{}",
+                table_name, column.name, lifecycle, since
+            ));
+        }
+    }
+    changes
+}
+
+/// スキーマ定義から取り除かれた削除待ちカラム（前回のスキーマの宣言順）
+fn retained_columns(previous_table: &Table, table: &Table) -> Vec<Column> {
+    let names: HashSet<&str> = table
+        .columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .chain(
+            table
+                .columns
+                .iter()
+                .filter_map(|c| c.renamed_from.as_deref()),
+        )
+        .collect();
+
+    previous_table
+        .columns
+        .iter()
+        .filter(|column| column.is_contract_pending() && !names.contains(column.name.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// 現在のカラムに対応する前回のカラム（リネームを考慮）
+fn previous_column<'a>(previous_table: &'a Table, column: &Column) -> Option<&'a Column> {
+    previous_table.get_column(&column.name).or_else(|| {
+        column
+            .renamed_from
+            .as_deref()
+            .and_then(|old_name| previous_table.get_column(old_name))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{ColumnLifecycle, ColumnType};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn users(columns: Vec<Column>) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        for column in columns {
+            table.add_column(column);
+        }
+        schema.add_table(table);
+        schema
+    }
+
+    fn pending(name: &str, since: Option<NaiveDate>) -> Column {
+        let mut column = Column::new(name.to_string(), ColumnType::TEXT, true);
+        column.lifecycle = Some(ColumnLifecycle::ContractPending);
+        column.contract_pending_since = since;
+        column
+    }
+
+    #[test]
+    fn test_newly_pending_column_records_today() {
+        let previous = users(vec![Column::new(
+            "email".to_string(),
+            ColumnType::TEXT,
+            true,
+        )]);
+        let mut current = users(vec![pending("email", None)]);
+
+        let result = reconcile(&previous, &mut current, date(10));
+
+        assert_eq!(result.newly_pending, vec!["users.email".to_string()]);
+        assert!(result.retained.is_empty());
+        let column = current.tables["users"].get_column("email").unwrap();
+        assert_eq!(column.contract_pending_since, Some(date(10)));
+    }
+
+    #[test]
+    fn test_pending_since_is_carried_over() {
+        let previous = users(vec![pending("email", Some(date(1)))]);
+        let mut current = users(vec![pending("email", None)]);
+
+        let result = reconcile(&previous, &mut current, date(10));
+
+        assert!(result.is_empty());
+        let column = current.tables["users"].get_column("email").unwrap();
+        assert_eq!(column.contract_pending_since, Some(date(1)));
+    }
+
+    #[test]
+    fn test_removed_pending_column_is_retained() {
+        let previous = users(vec![
+            pending("email", Some(date(1))),
+            Column::new("nickname".to_string(), ColumnType::TEXT, true),
+        ]);
+        let mut current = users(Vec::new());
+
+        let result = reconcile(&previous, &mut current, date(10));
+
+        assert_eq!(result.retained, vec!["users.email".to_string()]);
+        let table = &current.tables["users"];
+        assert_eq!(
+            table.get_column("email").unwrap().contract_pending_since,
+            Some(date(1))
+        );
+        // 削除待ちでないカラムは通常どおり削除される
+        assert!(table.get_column("nickname").is_none());
+    }
+
+    #[test]
+    fn test_renamed_pending_column_is_not_retained() {
+        let previous = users(vec![pending("email", Some(date(1)))]);
+        let mut renamed = pending("legacy_email", None);
+        renamed.renamed_from = Some("email".to_string());
+        let mut current = users(vec![renamed]);
+
+        let result = reconcile(&previous, &mut current, date(10));
+
+        assert!(result.is_empty());
+        let table = &current.tables["users"];
+        assert!(table.get_column("email").is_none());
+        assert_eq!(
+            table
+                .get_column("legacy_email")
+                .unwrap()
+                .contract_pending_since,
+            Some(date(1))
+        );
+    }
+
+    #[test]
+    fn test_active_column_clears_pending_since() {
+        let previous = users(vec![pending("email", Some(date(1)))]);
+        let mut active = Column::new("email".to_string(), ColumnType::TEXT, true);
+        active.lifecycle = Some(ColumnLifecycle::Active);
+        active.contract_pending_since = Some(date(1));
+        let mut current = users(vec![active]);
+
+        reconcile(&previous, &mut current, date(10));
+
+        let column = current.tables["users"].get_column("email").unwrap();
+        assert_eq!(column.contract_pending_since, None);
+    }
+
+    #[test]
+    fn test_lifecycle_changes_lists_annotation_only_changes() {
+        let previous = users(vec![Column::new(
+            "email".to_string(),
+            ColumnType::TEXT,
+            true,
+        )]);
+        let mut current = users(vec![pending("email", None)]);
+        reconcile(&previous, &mut current, date(10));
+
+        assert_eq!(
+            lifecycle_changes(&previous, &current),
+            vec!["users.email: contract_pending (since 2026-03-10)".to_string()]
+        );
+        assert!(lifecycle_changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_contract_pending_columns_age() {
+        let schema = users(vec![
+            pending("email", Some(date(1))),
+            pending("phone", None),
+        ]);
+
+        let columns = contract_pending_columns(&schema);
+
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].qualified_name(), "users.email");
+        assert_eq!(columns[0].age_days(date(8)), Some(7));
+        assert!(columns[0].is_older_than(7, date(8)));
+        assert!(!columns[0].is_older_than(8, date(8)));
+        // 日付のないカラムは対象にしない
+        assert!(!columns[1].is_older_than(0, date(8)));
+    }
+}
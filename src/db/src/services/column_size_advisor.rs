@@ -0,0 +1,289 @@
+// カラムサイズ提案サービス
+//
+// スキーマで宣言された文字列カラムの長さと、稼働中のデータベースで観測された
+// 実データ長を比較し、過大なカラム・余裕のないカラムと推奨サイズを判定します。
+
+use crate::adapters::column_statistics::ColumnLengthStats;
+use crate::core::schema::{Column, ColumnType, Table};
+use serde::Serialize;
+
+/// 推奨サイズの候補（実データ長の2倍以上で最小のものを選ぶ）
+const SIZE_STEPS: &[u32] = &[
+    16, 32, 64, 128, 255, 512, 1024, 2048, 4096, 8192, 16383, 65535,
+];
+
+/// 宣言長が最大長のこの倍数以上であれば過大とみなす
+const OVERSIZE_RATIO: i64 = 4;
+
+/// TEXTカラムをVARCHARに置き換える提案を行う上限
+const TEXT_TO_VARCHAR_LIMIT: u32 = 255;
+
+/// カラムサイズの判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnSizeStatus {
+    /// 宣言長に対して実データが大幅に短い
+    Oversized,
+    /// 実データが宣言長に達している
+    Tight,
+    /// 問題なし
+    Ok,
+    /// 値がなく判定できない
+    NoData,
+}
+
+/// 単一カラムのサイズ判定
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnSizeReport {
+    /// テーブル名
+    pub table: String,
+    /// カラム名
+    pub column: String,
+    /// スキーマで宣言された型
+    pub declared_type: ColumnType,
+    /// 観測された最大長
+    pub max_length: Option<i64>,
+    /// 99パーセンタイルの長さ（取得できた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p99_length: Option<i64>,
+    /// 判定結果
+    pub status: ColumnSizeStatus,
+    /// 推奨する型（Oversized / Tight の場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_type: Option<ColumnType>,
+}
+
+/// カラムサイズ提案サービス
+///
+/// 判定ルール:
+/// - 推奨長は最大長の2倍以上で最小の区切りの良いサイズ（16, 32, 64, 128, 255, ...）
+/// - VARCHAR/CHAR: 宣言長が最大長の4倍以上で、推奨長が宣言長より小さければ Oversized
+/// - VARCHAR: 最大長が宣言長以上であれば Tight（CHARは固定長のため対象外）
+/// - TEXT: 推奨長が255以下に収まれば VARCHAR への置き換えを Oversized として提案
+#[derive(Debug, Clone, Default)]
+pub struct ColumnSizeAdvisor {}
+
+impl ColumnSizeAdvisor {
+    /// 新しいColumnSizeAdvisorを作成
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// サイズ判定の対象となるカラム（VARCHAR/CHAR/TEXT）
+    pub fn candidate_columns<'a>(&self, table: &'a Table) -> Vec<&'a Column> {
+        table
+            .columns
+            .iter()
+            .filter(|c| {
+                matches!(
+                    c.column_type,
+                    ColumnType::VARCHAR { .. } | ColumnType::CHAR { .. } | ColumnType::TEXT
+                )
+            })
+            .collect()
+    }
+
+    /// 観測値からカラムのサイズを判定
+    pub fn evaluate(
+        &self,
+        table_name: &str,
+        column: &Column,
+        stats: &ColumnLengthStats,
+    ) -> ColumnSizeReport {
+        let (status, suggested_type) = match stats.max_length {
+            None => (ColumnSizeStatus::NoData, None),
+            Some(max_length) => self.judge(&column.column_type, max_length),
+        };
+
+        ColumnSizeReport {
+            table: table_name.to_string(),
+            column: column.name.clone(),
+            declared_type: column.column_type.clone(),
+            max_length: stats.max_length,
+            p99_length: stats.p99_length,
+            status,
+            suggested_type,
+        }
+    }
+
+    fn judge(
+        &self,
+        column_type: &ColumnType,
+        max_length: i64,
+    ) -> (ColumnSizeStatus, Option<ColumnType>) {
+        let suggested = suggested_length(max_length);
+
+        match column_type {
+            ColumnType::VARCHAR { length } => {
+                let declared = i64::from(*length);
+                if max_length >= declared {
+                    let suggested_type = match suggested {
+                        Some(length) => ColumnType::VARCHAR { length },
+                        None => ColumnType::TEXT,
+                    };
+                    (ColumnSizeStatus::Tight, Some(suggested_type))
+                } else if is_oversized(declared, max_length, suggested) {
+                    (
+                        ColumnSizeStatus::Oversized,
+                        suggested.map(|length| ColumnType::VARCHAR { length }),
+                    )
+                } else {
+                    (ColumnSizeStatus::Ok, None)
+                }
+            }
+            ColumnType::CHAR { length } => {
+                if is_oversized(i64::from(*length), max_length, suggested) {
+                    (
+                        ColumnSizeStatus::Oversized,
+                        suggested.map(|length| ColumnType::CHAR { length }),
+                    )
+                } else {
+                    (ColumnSizeStatus::Ok, None)
+                }
+            }
+            ColumnType::TEXT => match suggested {
+                Some(length) if length <= TEXT_TO_VARCHAR_LIMIT => (
+                    ColumnSizeStatus::Oversized,
+                    Some(ColumnType::VARCHAR { length }),
+                ),
+                _ => (ColumnSizeStatus::Ok, None),
+            },
+            _ => (ColumnSizeStatus::Ok, None),
+        }
+    }
+}
+
+/// 最大長の2倍以上で最小の区切りの良いサイズ（どれにも収まらない場合はNone）
+fn suggested_length(max_length: i64) -> Option<u32> {
+    let target = max_length.saturating_mul(2);
+    SIZE_STEPS
+        .iter()
+        .copied()
+        .find(|step| i64::from(*step) >= target)
+}
+
+fn is_oversized(declared: i64, max_length: i64, suggested: Option<u32>) -> bool {
+    declared >= max_length.saturating_mul(OVERSIZE_RATIO)
+        && suggested.is_some_and(|length| i64::from(length) < declared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(column_type: ColumnType) -> Column {
+        Column::new("value".to_string(), column_type, true)
+    }
+
+    fn stats(max_length: Option<i64>) -> ColumnLengthStats {
+        ColumnLengthStats {
+            column: "value".to_string(),
+            max_length,
+            p99_length: None,
+        }
+    }
+
+    fn evaluate(column_type: ColumnType, max_length: Option<i64>) -> ColumnSizeReport {
+        ColumnSizeAdvisor::new().evaluate("t", &column(column_type), &stats(max_length))
+    }
+
+    #[test]
+    fn test_oversized_varchar() {
+        let report = evaluate(ColumnType::VARCHAR { length: 255 }, Some(12));
+
+        assert_eq!(report.status, ColumnSizeStatus::Oversized);
+        assert_eq!(
+            report.suggested_type,
+            Some(ColumnType::VARCHAR { length: 32 })
+        );
+    }
+
+    #[test]
+    fn test_tight_varchar() {
+        let report = evaluate(ColumnType::VARCHAR { length: 100 }, Some(100));
+
+        assert_eq!(report.status, ColumnSizeStatus::Tight);
+        assert_eq!(
+            report.suggested_type,
+            Some(ColumnType::VARCHAR { length: 255 })
+        );
+
+        // 推奨サイズの上限を超える場合は TEXT を提案
+        let report = evaluate(ColumnType::VARCHAR { length: 40000 }, Some(40000));
+        assert_eq!(report.suggested_type, Some(ColumnType::TEXT));
+    }
+
+    #[test]
+    fn test_varchar_within_range_is_ok() {
+        assert_eq!(
+            evaluate(ColumnType::VARCHAR { length: 255 }, Some(120)).status,
+            ColumnSizeStatus::Ok
+        );
+        // 4倍以上でも推奨サイズが縮まらなければ指摘しない
+        let report = evaluate(ColumnType::VARCHAR { length: 16 }, Some(4));
+        assert_eq!(report.status, ColumnSizeStatus::Ok);
+        assert_eq!(report.suggested_type, None);
+    }
+
+    #[test]
+    fn test_char_is_never_tight() {
+        assert_eq!(
+            evaluate(ColumnType::CHAR { length: 2 }, Some(2)).status,
+            ColumnSizeStatus::Ok
+        );
+
+        let report = evaluate(ColumnType::CHAR { length: 200 }, Some(3));
+        assert_eq!(report.status, ColumnSizeStatus::Oversized);
+        assert_eq!(report.suggested_type, Some(ColumnType::CHAR { length: 16 }));
+    }
+
+    #[test]
+    fn test_short_text_suggests_varchar() {
+        let report = evaluate(ColumnType::TEXT, Some(40));
+        assert_eq!(report.status, ColumnSizeStatus::Oversized);
+        assert_eq!(
+            report.suggested_type,
+            Some(ColumnType::VARCHAR { length: 128 })
+        );
+
+        assert_eq!(
+            evaluate(ColumnType::TEXT, Some(2000)).status,
+            ColumnSizeStatus::Ok
+        );
+    }
+
+    #[test]
+    fn test_no_data() {
+        let report = evaluate(ColumnType::VARCHAR { length: 255 }, None);
+        assert_eq!(report.status, ColumnSizeStatus::NoData);
+        assert_eq!(report.suggested_type, None);
+    }
+
+    #[test]
+    fn test_candidate_columns() {
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_column(Column::new(
+            "name".to_string(),
+            ColumnType::VARCHAR { length: 255 },
+            false,
+        ));
+        table.add_column(Column::new("bio".to_string(), ColumnType::TEXT, true));
+        table.add_column(Column::new(
+            "country".to_string(),
+            ColumnType::CHAR { length: 2 },
+            true,
+        ));
+
+        let names: Vec<&str> = ColumnSizeAdvisor::new()
+            .candidate_columns(&table)
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["name", "bio", "country"]);
+    }
+}
@@ -3,6 +3,7 @@
 // core::config の純粋性を保つため、ファイルI/Oはこのサービスに集約する。
 
 use crate::core::config::Config;
+use crate::core::error::ConfigError;
 use anyhow::{Context, Result};
 use regex::Regex;
 use serde_saphyr;
@@ -16,14 +17,24 @@ impl ConfigLoader {
     /// YAMLファイルから設定を読み込む
     ///
     /// 設定値内の `${ENV_VAR}` パターンを環境変数の値で展開します。
-    /// 環境変数が未定義の場合は空文字列に置換されます。
+    /// 環境変数が未定義の場合は、その変数名を示すエラーになります。
+    /// 環境の `extends` は展開後の値で解決されます。
     pub fn from_file(path: &Path) -> Result<Config> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {:?}", path))?;
-        let expanded = Self::expand_env_vars(&content);
+        let expanded = Self::expand_env_vars(&content)?;
         serde_saphyr::from_str(&expanded).with_context(|| "Failed to parse config file")
     }
 
+    /// YAMLファイルから `${ENV_VAR}` を展開せずに設定を読み込む
+    ///
+    /// 読み込んだ設定をファイルに書き戻す場合に使用し、環境変数の値が書き込まれないようにします。
+    pub fn from_file_without_interpolation(path: &Path) -> Result<Config> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+        serde_saphyr::from_str(&content).with_context(|| "Failed to parse config file")
+    }
+
     /// デフォルトパスから設定を読み込む
     pub fn load_default() -> Result<Config> {
         let path = Path::new(Config::DEFAULT_CONFIG_PATH);
@@ -32,23 +43,33 @@ impl ConfigLoader {
 
     /// 文字列内の `${ENV_VAR}` パターンを環境変数の値で展開
     ///
-    /// 環境変数が未定義の場合は空文字列に置換し、警告を出力します。
-    fn expand_env_vars(content: &str) -> String {
+    /// コメント行（`#` で始まる行）は展開しません。
+    /// 環境変数が未定義の場合は、変数名と行番号を示すエラーを返します。
+    fn expand_env_vars(content: &str) -> Result<String, ConfigError> {
         let re = Regex::new(r"\$\{([^}]+)\}").expect("Invalid regex");
-        re.replace_all(content, |caps: &regex::Captures| {
-            let var_name = &caps[1];
-            match std::env::var(var_name) {
-                Ok(value) => value,
-                Err(_) => {
-                    eprintln!(
-                        "Warning: Environment variable '{}' is not defined, using empty string",
-                        var_name
-                    );
-                    String::new()
-                }
+        let mut expanded = String::with_capacity(content.len());
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            if line.trim_start().starts_with('#') {
+                expanded.push_str(line);
+                continue;
             }
-        })
-        .to_string()
+            let mut last = 0;
+            for caps in re.captures_iter(line) {
+                let whole = caps.get(0).expect("capture 0 always exists");
+                let var_name = &caps[1];
+                let value = std::env::var(var_name).map_err(|_| {
+                    ConfigError::UndefinedEnvironmentVariable {
+                        name: var_name.to_string(),
+                        line: index + 1,
+                    }
+                })?;
+                expanded.push_str(&line[last..whole.start()]);
+                expanded.push_str(&value);
+                last = whole.end();
+            }
+            expanded.push_str(&line[last..]);
+        }
+        Ok(expanded)
     }
 }
 
@@ -61,23 +82,36 @@ mod tests {
     #[serial]
     fn test_expand_env_vars_replaces_known_var() {
         std::env::set_var("TEST_STRATUM_VAR", "hello");
-        let result = ConfigLoader::expand_env_vars("password: ${TEST_STRATUM_VAR}");
+        let result = ConfigLoader::expand_env_vars("password: ${TEST_STRATUM_VAR}").unwrap();
         assert_eq!(result, "password: hello");
         std::env::remove_var("TEST_STRATUM_VAR");
     }
 
     #[test]
     #[serial]
-    fn test_expand_env_vars_unknown_var_becomes_empty() {
+    fn test_expand_env_vars_unknown_var_is_error() {
+        std::env::remove_var("NONEXISTENT_STRATUM_VAR");
+        let err = ConfigLoader::expand_env_vars("host: db\npassword: ${NONEXISTENT_STRATUM_VAR}")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Environment variable 'NONEXISTENT_STRATUM_VAR' used on line 2 of the config file is not defined"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_expand_env_vars_skips_comment_lines() {
         std::env::remove_var("NONEXISTENT_STRATUM_VAR");
-        let result = ConfigLoader::expand_env_vars("password: ${NONEXISTENT_STRATUM_VAR}");
-        assert_eq!(result, "password: ");
+        let input = "# password: ${NONEXISTENT_STRATUM_VAR}\nhost: db\n";
+        let result = ConfigLoader::expand_env_vars(input).unwrap();
+        assert_eq!(result, input);
     }
 
     #[test]
     fn test_expand_env_vars_no_vars() {
         let input = "password: plain_text";
-        let result = ConfigLoader::expand_env_vars(input);
+        let result = ConfigLoader::expand_env_vars(input).unwrap();
         assert_eq!(result, input);
     }
 
@@ -86,7 +120,8 @@ mod tests {
     fn test_expand_env_vars_multiple() {
         std::env::set_var("TEST_HOST", "myhost");
         std::env::set_var("TEST_PORT", "5432");
-        let result = ConfigLoader::expand_env_vars("host: ${TEST_HOST}\nport: ${TEST_PORT}");
+        let result =
+            ConfigLoader::expand_env_vars("host: ${TEST_HOST}\nport: ${TEST_PORT}").unwrap();
         assert_eq!(result, "host: myhost\nport: 5432");
         std::env::remove_var("TEST_HOST");
         std::env::remove_var("TEST_PORT");
@@ -129,4 +164,57 @@ environments:
         let result = ConfigLoader::from_file(&config_path);
         assert!(result.is_err());
     }
+
+    #[test]
+    #[serial]
+    fn test_from_file_resolves_extends_with_interpolation() {
+        std::env::set_var("TEST_STRATUM_STAGING_PASSWORD", "s3cret");
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("config.yaml");
+        let config_content = r#"version: "1.0"
+dialect: postgresql
+environments:
+  development:
+    host: db.internal
+    user: app
+    database: app_dev
+  staging:
+    extends: development
+    database: app_staging
+    password: ${TEST_STRATUM_STAGING_PASSWORD}
+"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let config = ConfigLoader::from_file(&config_path).unwrap();
+        let staging = config.get_database_config("staging").unwrap();
+        assert_eq!(staging.host, "db.internal");
+        assert_eq!(staging.user.as_deref(), Some("app"));
+        assert_eq!(staging.database, "app_staging");
+        assert_eq!(staging.password.as_deref(), Some("s3cret"));
+        std::env::remove_var("TEST_STRATUM_STAGING_PASSWORD");
+    }
+
+    #[test]
+    fn test_from_file_rejects_circular_extends() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("config.yaml");
+        let config_content = r#"version: "1.0"
+dialect: postgresql
+environments:
+  staging:
+    extends: production
+    database: app_staging
+  production:
+    extends: staging
+    database: app_production
+"#;
+        std::fs::write(&config_path, config_content).unwrap();
+
+        let err = ConfigLoader::from_file(&config_path).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("Circular `extends` between environments"),
+            "{:#}",
+            err
+        );
+    }
 }
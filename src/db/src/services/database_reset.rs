@@ -0,0 +1,249 @@
+// 開発用データベースのリセット（`strata reset --hard`）
+//
+// スキーマスナップショットにあるオブジェクトとマイグレーション履歴テーブルを削除するSQLを生成します。
+// - ビュー: 依存するビューを先に削除
+// - テーブル: 外部キーの参照元を先に削除（作成順の逆、SchemaDiff の依存関係ソートを利用）
+//   循環参照のテーブルは、PostgreSQL では CASCADE、MySQL では外部キーを先に削除して対応
+// - ENUM型（PostgreSQL）
+// - マイグレーション履歴テーブル
+// 途中までしか適用されていないデータベースでも実行できるよう、削除はすべて `IF EXISTS` 付きです。
+// ファイルベースのSQLiteデータベースは、SQLを実行する代わりにファイルごと削除できます。
+
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::adapters::sql_generator::mysql::MysqlSqlGenerator;
+use crate::adapters::sql_generator::postgres::PostgresSqlGenerator;
+use crate::adapters::sql_generator::sqlite::SqliteSqlGenerator;
+use crate::adapters::sql_generator::SqlGenerator;
+use crate::core::config::{Dialect, IdentifierCase};
+use crate::core::schema::{Constraint, Schema};
+use crate::core::schema_diff::SchemaDiff;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// `--hard` でのリセットに使うSQLの生成
+#[derive(Debug, Clone)]
+pub struct HardResetPlanner {
+    dialect: Dialect,
+    identifier_case: IdentifierCase,
+}
+
+impl HardResetPlanner {
+    /// 新しいHardResetPlannerを作成
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            identifier_case: IdentifierCase::default(),
+        }
+    }
+
+    /// 制約名の生成に使う識別子の大文字・小文字の規則を設定
+    pub fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
+    /// スナップショットのオブジェクトとマイグレーション履歴テーブルを削除するステートメント（実行順）
+    ///
+    /// `snapshot` の識別子は identifier_case 適用済みである必要があります。
+    pub fn plan(&self, snapshot: &Schema) -> Vec<String> {
+        let generator = self.sql_generator();
+        let mut statements = Vec::new();
+
+        // 依存するビューを先に削除（作成順の逆）
+        let mut views_diff = SchemaDiff::new();
+        views_diff.added_views = snapshot.views.values().cloned().collect();
+        for view in views_diff.sort_added_views_by_dependency().iter().rev() {
+            statements.push(generator.generate_drop_view(&view.name));
+        }
+
+        // 参照元テーブルを先に削除（作成順の逆）
+        let mut tables_diff = SchemaDiff::new();
+        tables_diff.added_tables = snapshot.tables.values().cloned().collect();
+        let order = tables_diff.order_added_tables();
+        let cyclic: HashSet<&str> = order.cyclic_tables.iter().map(String::as_str).collect();
+        if matches!(self.dialect, Dialect::MySQL) {
+            // 循環参照のテーブルは、互いを参照する外部キーを先に削除しないと削除できない
+            for table in order
+                .tables
+                .iter()
+                .filter(|t| cyclic.contains(t.name.as_str()))
+            {
+                for constraint in &table.constraints {
+                    if let Constraint::FOREIGN_KEY {
+                        referenced_table, ..
+                    } = constraint
+                    {
+                        if cyclic.contains(referenced_table.as_str()) {
+                            statements.push(generator.generate_drop_constraint_for_existing_table(
+                                &table.name,
+                                constraint,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        for table in order.tables.iter().rev() {
+            let cascade = if matches!(self.dialect, Dialect::PostgreSQL)
+                && cyclic.contains(table.name.as_str())
+            {
+                " CASCADE"
+            } else {
+                ""
+            };
+            statements.push(format!(
+                "DROP TABLE IF EXISTS {}{}",
+                generator.quote_table_name(&table.name),
+                cascade
+            ));
+        }
+
+        if matches!(self.dialect, Dialect::PostgreSQL) {
+            for enum_name in snapshot.enums.keys() {
+                statements.push(format!(
+                    "DROP TYPE IF EXISTS {}",
+                    generator.quote_identifier(enum_name)
+                ));
+            }
+        }
+
+        statements.push(DatabaseMigratorService::new().generate_drop_migration_table_sql());
+        statements
+    }
+
+    fn sql_generator(&self) -> Box<dyn SqlGenerator> {
+        match self.dialect {
+            Dialect::PostgreSQL => {
+                Box::new(PostgresSqlGenerator::new().with_identifier_case(self.identifier_case))
+            }
+            Dialect::MySQL => {
+                Box::new(MysqlSqlGenerator::new().with_identifier_case(self.identifier_case))
+            }
+            Dialect::SQLite => Box::new(SqliteSqlGenerator::new()),
+        }
+    }
+}
+
+/// ファイルベースのSQLiteデータベースのファイル（データベース本体とジャーナル・WALファイル）
+///
+/// インメモリデータベースの場合は None を返します。
+pub fn sqlite_database_files(database: &str) -> Option<Vec<PathBuf>> {
+    if database.is_empty() || database == ":memory:" || database.starts_with("file::memory:") {
+        return None;
+    }
+    Some(
+        ["", "-journal", "-wal", "-shm"]
+            .iter()
+            .map(|suffix| PathBuf::from(format!("{}{}", database, suffix)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, EnumDefinition, Table, View};
+
+    fn table_with_fk(name: &str, referenced: Option<&str>) -> Table {
+        let mut table = Table::new(name.to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        if let Some(referenced) = referenced {
+            table.add_column(Column::new(
+                format!("{}_id", referenced),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+            table.add_constraint(Constraint::FOREIGN_KEY {
+                columns: vec![format!("{}_id", referenced)],
+                referenced_table: referenced.to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: None,
+                on_update: None,
+            });
+        }
+        table
+    }
+
+    #[test]
+    fn test_plan_drops_referencing_tables_first() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(table_with_fk("comments", Some("posts")));
+        schema.add_table(table_with_fk("posts", Some("users")));
+        schema.add_table(table_with_fk("users", None));
+        schema.add_view(View::new(
+            "active_users".to_string(),
+            "SELECT * FROM users".to_string(),
+        ));
+
+        let statements = HardResetPlanner::new(Dialect::SQLite).plan(&schema);
+
+        assert_eq!(
+            statements,
+            vec![
+                r#"DROP VIEW IF EXISTS "active_users""#,
+                r#"DROP TABLE IF EXISTS "comments""#,
+                r#"DROP TABLE IF EXISTS "posts""#,
+                r#"DROP TABLE IF EXISTS "users""#,
+                "DROP TABLE IF EXISTS schema_migrations",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_postgres_drops_enums_and_cascades_cycles() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(table_with_fk("a", Some("b")));
+        schema.add_table(table_with_fk("b", Some("a")));
+        schema.add_enum(EnumDefinition {
+            name: "status".to_string(),
+            values: vec!["active".to_string()],
+            renamed_from: None,
+        });
+
+        let statements = HardResetPlanner::new(Dialect::PostgreSQL).plan(&schema);
+
+        assert!(statements.contains(&r#"DROP TABLE IF EXISTS "a" CASCADE"#.to_string()));
+        assert!(statements.contains(&r#"DROP TABLE IF EXISTS "b" CASCADE"#.to_string()));
+        assert_eq!(
+            &statements[statements.len() - 2..],
+            [
+                r#"DROP TYPE IF EXISTS "status""#,
+                "DROP TABLE IF EXISTS schema_migrations",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_mysql_drops_cyclic_foreign_keys_first() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(table_with_fk("a", Some("b")));
+        schema.add_table(table_with_fk("b", Some("a")));
+
+        let statements = HardResetPlanner::new(Dialect::MySQL).plan(&schema);
+
+        assert_eq!(statements.len(), 5, "{:?}", statements);
+        assert!(statements[0].starts_with("ALTER TABLE `a` DROP FOREIGN KEY"));
+        assert!(statements[1].starts_with("ALTER TABLE `b` DROP FOREIGN KEY"));
+        assert!(statements[2].starts_with("DROP TABLE IF EXISTS"));
+        assert_eq!(statements[4], "DROP TABLE IF EXISTS schema_migrations");
+    }
+
+    #[test]
+    fn test_sqlite_database_files() {
+        assert_eq!(
+            sqlite_database_files("dev.db").unwrap(),
+            vec![
+                PathBuf::from("dev.db"),
+                PathBuf::from("dev.db-journal"),
+                PathBuf::from("dev.db-wal"),
+                PathBuf::from("dev.db-shm"),
+            ]
+        );
+        assert!(sqlite_database_files(":memory:").is_none());
+        assert!(sqlite_database_files("").is_none());
+    }
+}
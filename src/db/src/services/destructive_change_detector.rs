@@ -1,7 +1,9 @@
 use crate::core::destructive_change_report::{
-    DestructiveChangeReport, DroppedColumn, RenamedColumnInfo,
+    DestructiveChangeReport, DroppedColumn, NarrowedColumn, RenamedColumnInfo, ReorderedEnum,
 };
-use crate::core::schema_diff::{EnumChangeKind, SchemaDiff};
+use crate::core::schema::Column;
+use crate::core::schema_diff::{ColumnChange, EnumChangeKind, SchemaDiff};
+use crate::core::type_category::TypeChangeClassification;
 
 /// 破壊的変更の検出サービス
 #[derive(Debug, Default)]
@@ -27,10 +29,23 @@ impl DestructiveChangeDetector {
             .collect();
 
         for table_diff in &schema_diff.modified_tables {
-            if !table_diff.removed_columns.is_empty() {
+            // 通常カラムから生成カラムへの変更は、カラムの再作成で既存の値が失われる
+            let dropped_columns: Vec<String> = table_diff
+                .removed_columns
+                .iter()
+                .cloned()
+                .chain(
+                    table_diff
+                        .modified_columns
+                        .iter()
+                        .filter(|c| !c.old_column.is_generated() && c.new_column.is_generated())
+                        .map(|c| c.column_name.clone()),
+                )
+                .collect();
+            if !dropped_columns.is_empty() {
                 report.columns_dropped.push(DroppedColumn {
                     table: table_diff.table_name.clone(),
-                    columns: table_diff.removed_columns.clone(),
+                    columns: dropped_columns,
                 });
             }
 
@@ -41,27 +56,78 @@ impl DestructiveChangeDetector {
                     new_name: renamed.new_column.name.clone(),
                 });
             }
+
+            // 方言上等価として除外された型変更は対象外（changes に TypeChanged が残るもののみ）
+            let changed_columns = table_diff
+                .modified_columns
+                .iter()
+                .map(|c| (&c.old_column, &c.new_column, &c.changes))
+                .chain(
+                    table_diff
+                        .renamed_columns
+                        .iter()
+                        .map(|r| (&r.old_column, &r.new_column, &r.changes)),
+                )
+                .filter(|(_, _, changes)| {
+                    changes
+                        .iter()
+                        .any(|change| matches!(change, ColumnChange::TypeChanged { .. }))
+                });
+            for (old_column, new_column, _) in changed_columns {
+                if let Some(narrowed) =
+                    self.detect_narrowing(&table_diff.table_name, old_column, new_column)
+                {
+                    report.columns_narrowed.push(narrowed);
+                }
+            }
         }
 
         for enum_diff in &schema_diff.modified_enums {
-            if matches!(enum_diff.change_kind, EnumChangeKind::Recreate) {
-                report.enums_recreated.push(enum_diff.enum_name.clone());
+            match enum_diff.change_kind {
+                EnumChangeKind::Recreate => {
+                    report.enums_recreated.push(enum_diff.enum_name.clone());
+                }
+                EnumChangeKind::ReorderOnly => report.enums_reordered.push(ReorderedEnum {
+                    name: enum_diff.enum_name.clone(),
+                    old_values: enum_diff.old_values.clone(),
+                    new_values: enum_diff.new_values.clone(),
+                }),
+                EnumChangeKind::AddOnly => {}
             }
         }
 
         report
     }
+
+    /// 値が失われる可能性のある型変更を検出
+    fn detect_narrowing(
+        &self,
+        table_name: &str,
+        old_column: &Column,
+        new_column: &Column,
+    ) -> Option<NarrowedColumn> {
+        let reason =
+            TypeChangeClassification::classify(&old_column.column_type, &new_column.column_type)
+                .reason?;
+        Some(NarrowedColumn {
+            table: table_name.to_string(),
+            column: new_column.name.clone(),
+            old_type: old_column.column_type.to_string(),
+            new_type: new_column.column_type.to_string(),
+            reason,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::DestructiveChangeDetector;
     use crate::core::destructive_change_report::{
-        DestructiveChangeReport, DroppedColumn, RenamedColumnInfo,
+        DestructiveChangeReport, DroppedColumn, NarrowedColumn, RenamedColumnInfo, ReorderedEnum,
     };
-    use crate::core::schema::{Column, ColumnType};
+    use crate::core::schema::{Column, ColumnType, GeneratedColumn};
     use crate::core::schema_diff::{
-        EnumChangeKind, EnumColumnRef, EnumDiff, RenamedColumn, SchemaDiff, TableDiff,
+        ColumnDiff, EnumChangeKind, EnumColumnRef, EnumDiff, RenamedColumn, SchemaDiff, TableDiff,
     };
 
     fn integer_column(name: &str) -> Column {
@@ -135,6 +201,34 @@ mod tests {
         assert_eq!(report.enums_recreated, vec!["priority".to_string()]);
     }
 
+    #[test]
+    fn detect_enum_reorder_with_old_and_new_order() {
+        let detector = DestructiveChangeDetector::new();
+        let mut diff = SchemaDiff::new();
+        diff.modified_enums.push(EnumDiff {
+            enum_name: "priority".to_string(),
+            old_values: vec!["low".to_string(), "high".to_string()],
+            new_values: vec!["high".to_string(), "low".to_string()],
+            added_values: Vec::new(),
+            removed_values: Vec::new(),
+            change_kind: EnumChangeKind::ReorderOnly,
+            columns: Vec::new(),
+        });
+
+        let report = detector.detect(&diff);
+
+        assert!(report.enums_recreated.is_empty());
+        assert_eq!(
+            report.enums_reordered,
+            vec![ReorderedEnum {
+                name: "priority".to_string(),
+                old_values: vec!["low".to_string(), "high".to_string()],
+                new_values: vec!["high".to_string(), "low".to_string()],
+            }]
+        );
+        assert!(report.has_destructive_changes());
+    }
+
     #[test]
     fn detect_multiple_tables_and_columns() {
         let detector = DestructiveChangeDetector::new();
@@ -177,4 +271,88 @@ mod tests {
 
         assert_eq!(first, second);
     }
+
+    fn decimal_column(name: &str, precision: u32, scale: u32) -> Column {
+        Column::new(
+            name.to_string(),
+            ColumnType::DECIMAL { precision, scale },
+            false,
+        )
+    }
+
+    #[test]
+    fn detect_lossy_decimal_changes_only() {
+        let detector = DestructiveChangeDetector::new();
+        let mut diff = SchemaDiff::new();
+
+        let mut table_diff = TableDiff::new("products".to_string());
+        table_diff.modified_columns = vec![
+            ColumnDiff::new(
+                "price".to_string(),
+                decimal_column("price", 10, 2),
+                decimal_column("price", 8, 2),
+            ),
+            ColumnDiff::new(
+                "cost".to_string(),
+                decimal_column("cost", 10, 2),
+                decimal_column("cost", 12, 2),
+            ),
+        ];
+        // 型変更が差分から除外されている場合は対象外
+        let mut filtered = ColumnDiff::new(
+            "tax".to_string(),
+            decimal_column("tax", 10, 4),
+            decimal_column("tax", 10, 2),
+        );
+        filtered.changes.clear();
+        table_diff.modified_columns.push(filtered);
+        diff.modified_tables.push(table_diff);
+
+        let report = detector.detect(&diff);
+
+        assert_eq!(
+            report.columns_narrowed,
+            vec![NarrowedColumn {
+                table: "products".to_string(),
+                column: "price".to_string(),
+                old_type: "DECIMAL(10, 2)".to_string(),
+                new_type: "DECIMAL(8, 2)".to_string(),
+                reason: "existing values may overflow".to_string(),
+            }]
+        );
+        assert!(report.has_destructive_changes());
+    }
+
+    #[test]
+    fn detect_plain_column_becoming_generated_as_dropped() {
+        let detector = DestructiveChangeDetector::new();
+        let mut diff = SchemaDiff::new();
+
+        let generated = |expression: &str| {
+            let mut column = integer_column("total");
+            column.generated = Some(GeneratedColumn::new(expression, true));
+            column
+        };
+        let mut table_diff = TableDiff::new("orders".to_string());
+        table_diff.modified_columns = vec![
+            ColumnDiff::new(
+                "total".to_string(),
+                integer_column("total"),
+                generated("a + b"),
+            ),
+            // 生成カラム同士の式変更は値が再計算されるだけなので対象外
+            ColumnDiff::new("total".to_string(), generated("a + b"), generated("a * b")),
+        ];
+        diff.modified_tables.push(table_diff);
+
+        let report = detector.detect(&diff);
+
+        assert_eq!(
+            report.columns_dropped,
+            vec![DroppedColumn {
+                table: "orders".to_string(),
+                columns: vec!["total".to_string()],
+            }]
+        );
+    }
 }
@@ -0,0 +1,203 @@
+// 識別子の大文字・小文字の変換
+//
+// 設定 `identifier_case` に従い、スキーマ定義の識別子（テーブル・カラム・インデックス名と
+// 制約が参照するカラム・テーブル名）を変換します。
+// スキーマ定義ファイルの解析結果はそのまま保持し、差分検出とSQL生成の直前に適用します。
+// ビュー定義・CHECK式・デフォルト値などのSQL式は書き換えません。
+
+use crate::core::config::IdentifierCase;
+use crate::core::schema::{Constraint, Schema, Table};
+
+/// スキーマの識別子を規則に従って変換したコピーを返す
+pub fn fold_schema_identifiers(schema: &Schema, case: IdentifierCase) -> Schema {
+    if case.is_preserve() {
+        return schema.clone();
+    }
+
+    let mut folded = schema.clone();
+    folded.tables = schema
+        .tables
+        .values()
+        .map(|table| {
+            let table = fold_table_identifiers(table, case);
+            (table.name.clone(), table)
+        })
+        .collect();
+    folded.external_tables = schema
+        .external_tables
+        .iter()
+        .map(|name| case.apply(name))
+        .collect();
+    folded
+}
+
+fn fold_table_identifiers(table: &Table, case: IdentifierCase) -> Table {
+    let fold = |name: &String| case.apply(name);
+    let fold_all = |names: &[String]| names.iter().map(fold).collect::<Vec<_>>();
+
+    let mut folded = table.clone();
+    folded.name = fold(&table.name);
+    folded.schema = table.schema.as_ref().map(fold);
+    folded.renamed_from = table.renamed_from.as_ref().map(fold);
+
+    for column in &mut folded.columns {
+        column.name = fold(&column.name);
+        column.renamed_from = column.renamed_from.as_ref().map(fold);
+    }
+
+    for index in &mut folded.indexes {
+        index.name = fold(&index.name);
+        index.renamed_from = index.renamed_from.as_ref().map(fold);
+        for column in &mut index.columns {
+            column.name = fold(&column.name);
+        }
+    }
+
+    for constraint in &mut folded.constraints {
+        match constraint {
+            Constraint::PRIMARY_KEY { columns }
+            | Constraint::UNIQUE { columns }
+            | Constraint::CHECK { columns, .. } => *columns = fold_all(columns),
+            Constraint::FOREIGN_KEY {
+                columns,
+                referenced_table,
+                referenced_columns,
+                ..
+            } => {
+                *columns = fold_all(columns);
+                *referenced_table = fold(referenced_table);
+                *referenced_columns = fold_all(referenced_columns);
+            }
+            _ => {}
+        }
+    }
+
+    folded
+}
+
+/// 規則に従っていない識別子の一覧（`table` / `table.column` / `table.index` 形式）
+///
+/// スナップショットに対して使用し、規則を途中で変更したプロジェクト
+/// （既存のオブジェクトが以前の規則で作成されている）を検出します。
+pub fn find_mismatched_identifiers(schema: &Schema, case: IdentifierCase) -> Vec<String> {
+    let mut mismatched = Vec::new();
+    if case.is_preserve() {
+        return mismatched;
+    }
+
+    for table in schema.tables.values() {
+        if !case.matches(&table.name) {
+            mismatched.push(table.name.clone());
+        }
+        for column in &table.columns {
+            if !case.matches(&column.name) {
+                mismatched.push(format!("{}.{}", table.name, column.name));
+            }
+        }
+        for index in &table.indexes {
+            if !case.matches(&index.name) {
+                mismatched.push(format!("{}.{}", table.name, index.name));
+            }
+        }
+    }
+
+    mismatched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Index};
+
+    fn sample_schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut users = Table::new("Users".to_string());
+        users.add_column(Column::new(
+            "Id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["Id".to_string()],
+        });
+        schema.add_table(users);
+
+        let mut posts = Table::new("Posts".to_string());
+        let mut author = Column::new(
+            "AuthorId".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        author.renamed_from = Some("UserId".to_string());
+        posts.add_column(author);
+        posts.add_index(Index::new(
+            "Idx_Posts_Author".to_string(),
+            vec!["AuthorId".to_string()],
+            false,
+        ));
+        posts.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["AuthorId".to_string()],
+            referenced_table: "Users".to_string(),
+            referenced_columns: vec!["Id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        schema.add_table(posts);
+        schema.external_tables.insert("Legacy".to_string());
+        schema
+    }
+
+    #[test]
+    fn test_preserve_keeps_schema() {
+        let schema = sample_schema();
+        assert_eq!(
+            fold_schema_identifiers(&schema, IdentifierCase::Preserve),
+            schema
+        );
+        assert!(find_mismatched_identifiers(&schema, IdentifierCase::Preserve).is_empty());
+    }
+
+    #[test]
+    fn test_fold_upper() {
+        let folded = fold_schema_identifiers(&sample_schema(), IdentifierCase::Upper);
+
+        assert!(folded.tables.contains_key("USERS"));
+        let posts = folded.tables.get("POSTS").unwrap();
+        assert_eq!(posts.name, "POSTS");
+        assert_eq!(posts.columns[0].name, "AUTHORID");
+        assert_eq!(posts.columns[0].renamed_from.as_deref(), Some("USERID"));
+        assert_eq!(posts.indexes[0].name, "IDX_POSTS_AUTHOR");
+        assert_eq!(posts.indexes[0].columns[0].name, "AUTHORID");
+        assert_eq!(
+            posts.constraints[0],
+            Constraint::FOREIGN_KEY {
+                columns: vec!["AUTHORID".to_string()],
+                referenced_table: "USERS".to_string(),
+                referenced_columns: vec!["ID".to_string()],
+                on_delete: None,
+                on_update: None,
+            }
+        );
+        assert!(folded.external_tables.contains("LEGACY"));
+        assert!(find_mismatched_identifiers(&folded, IdentifierCase::Upper).is_empty());
+    }
+
+    #[test]
+    fn test_find_mismatched_identifiers() {
+        let lower = fold_schema_identifiers(&sample_schema(), IdentifierCase::Lower);
+
+        let mismatched = find_mismatched_identifiers(&lower, IdentifierCase::Upper);
+        assert_eq!(
+            mismatched,
+            vec![
+                "posts",
+                "posts.authorid",
+                "posts.idx_posts_author",
+                "users",
+                "users.id"
+            ]
+        );
+        assert!(find_mismatched_identifiers(&lower, IdentifierCase::Lower).is_empty());
+    }
+}
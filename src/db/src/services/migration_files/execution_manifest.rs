@@ -0,0 +1,193 @@
+// 実行マニフェスト
+//
+// apply が実際に実行したステートメントのチェックサムを実行順に記録し、
+// 後から up.sql と突き合わせられるようにします。
+// - apply: 実行したステートメントごとにチェックサムを計算して schema_migrations に保存
+// - verify-execution: ローカルの up.sql から同じ手順でチェックサムを再計算して比較
+// ステートメントの分割・正規化は apply の読み込み処理と同じものを使うため、
+// 同じ up.sql からは常に同じマニフェストが得られます。
+
+use super::sql_output::{migration_sql_output, normalize_sql_file};
+use super::sql_parser::split_sql_statements;
+use super::sql_stream::read_sql_statements;
+use crate::core::config::{Config, Dialect};
+use crate::services::schema_checksum::SchemaChecksumService;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+/// 相違の表示で使うステートメントの最大文字数
+const STATEMENT_PREVIEW_CHARS: usize = 80;
+
+/// ステートメントのチェックサムを計算
+pub fn statement_checksum(statement: &str) -> String {
+    SchemaChecksumService::new().calculate_sql_checksum(statement)
+}
+
+/// apply が実行しないステートメントか判定
+///
+/// SQLite ではマイグレーション全体を1つのトランザクションで実行するため、
+/// テーブル再作成で生成される BEGIN TRANSACTION / COMMIT はネストを防ぐために実行しない。
+pub fn is_skipped_statement(statement: &str, dialect: Dialect) -> bool {
+    if dialect != Dialect::SQLite {
+        return false;
+    }
+    let statement = statement.trim().to_uppercase();
+    statement == "BEGIN TRANSACTION" || statement == "COMMIT"
+}
+
+/// マニフェストの1ステートメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestStatement {
+    /// ステートメントのチェックサム
+    pub checksum: String,
+    /// 表示用のステートメントの先頭部分
+    pub preview: String,
+}
+
+impl ManifestStatement {
+    fn new(statement: &str) -> Self {
+        let first_line = statement.trim().lines().next().unwrap_or_default();
+        let mut preview: String = first_line.chars().take(STATEMENT_PREVIEW_CHARS).collect();
+        if preview.len() < statement.trim().len() {
+            preview.push_str(" ...");
+        }
+        Self {
+            checksum: statement_checksum(statement),
+            preview,
+        }
+    }
+}
+
+/// ローカルの up.sql から、apply が実行するステートメントのマニフェストを作成
+pub fn local_manifest(migration_dir: &Path, config: &Config) -> Result<Vec<ManifestStatement>> {
+    let mut manifest = Vec::new();
+    for statement in read_sql_statements(
+        &migration_dir.join("up.sql"),
+        &migration_sql_output(migration_dir, &config.sql_output)?,
+        config.stream_sql_threshold(),
+    )? {
+        let statement = statement?;
+        if !is_skipped_statement(&statement, config.dialect) {
+            manifest.push(ManifestStatement::new(&statement));
+        }
+    }
+    Ok(manifest)
+}
+
+/// `store_applied_sql` で保存された up.sql から、適用時に実行されたステートメントのチェックサムを再計算
+///
+/// 保存されたSQLはファイルの内容そのものなので、ローカルのマイグレーションに記録された終端文字列で正規化する。
+pub fn stored_sql_checksums(
+    stored_up_sql: &str,
+    migration_dir: &Path,
+    config: &Config,
+) -> Result<Vec<String>> {
+    let sql_output = migration_sql_output(migration_dir, &config.sql_output)?;
+    Ok(
+        split_sql_statements(&normalize_sql_file(stored_up_sql, &sql_output))
+            .iter()
+            .filter(|statement| !is_skipped_statement(statement, config.dialect))
+            .map(|statement| statement_checksum(statement))
+            .collect(),
+    )
+}
+
+/// 記録とローカルで異なるステートメント
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatementDivergence {
+    /// ステートメントの位置（1始まり）
+    pub position: usize,
+    /// 記録されたチェックサム（記録の方が短い場合はなし）
+    pub recorded: Option<String>,
+    /// ローカルの up.sql から計算したチェックサム（ローカルの方が短い場合はなし）
+    pub local: Option<String>,
+    /// ローカルのステートメントの先頭部分
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement: Option<String>,
+}
+
+/// 記録されたチェックサムとローカルのマニフェストを位置ごとに比較
+pub fn compare_manifests(
+    recorded: &[String],
+    local: &[ManifestStatement],
+) -> Vec<StatementDivergence> {
+    (0..recorded.len().max(local.len()))
+        .filter_map(|index| {
+            let recorded = recorded.get(index);
+            let local = local.get(index);
+            if recorded.is_some() && recorded == local.map(|statement| &statement.checksum) {
+                return None;
+            }
+            Some(StatementDivergence {
+                position: index + 1,
+                recorded: recorded.cloned(),
+                local: local.map(|statement| statement.checksum.clone()),
+                statement: local.map(|statement| statement.preview.clone()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(statements: &[&str]) -> Vec<ManifestStatement> {
+        statements
+            .iter()
+            .map(|s| ManifestStatement::new(s))
+            .collect()
+    }
+
+    #[test]
+    fn test_is_skipped_statement() {
+        assert!(is_skipped_statement(" begin transaction ", Dialect::SQLite));
+        assert!(is_skipped_statement("COMMIT", Dialect::SQLite));
+        assert!(!is_skipped_statement("COMMIT", Dialect::PostgreSQL));
+        assert!(!is_skipped_statement(
+            "CREATE TABLE t (id INTEGER)",
+            Dialect::SQLite
+        ));
+    }
+
+    #[test]
+    fn test_compare_manifests_reports_reordered_and_extra_statements() {
+        let recorded: Vec<String> =
+            manifest(&["CREATE TABLE a (id INTEGER)", "CREATE TABLE b (id INTEGER)"])
+                .into_iter()
+                .map(|statement| statement.checksum)
+                .collect();
+
+        assert!(compare_manifests(
+            &recorded,
+            &manifest(&["CREATE TABLE a (id INTEGER)", "CREATE TABLE b (id INTEGER)"])
+        )
+        .is_empty());
+
+        let local = manifest(&[
+            "CREATE TABLE b (id INTEGER)",
+            "CREATE TABLE a (id INTEGER)",
+            "CREATE INDEX idx_a ON a (id)",
+        ]);
+        let divergences = compare_manifests(&recorded, &local);
+        assert_eq!(
+            divergences
+                .iter()
+                .map(|divergence| divergence.position)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(divergences[2].recorded, None);
+        assert_eq!(
+            divergences[2].statement.as_deref(),
+            Some("CREATE INDEX idx_a ON a (id)")
+        );
+    }
+
+    #[test]
+    fn test_statement_preview_is_truncated() {
+        let statement = ManifestStatement::new("INSERT INTO t VALUES\n(1),\n(2)");
+        assert_eq!(statement.preview, "INSERT INTO t VALUES ...");
+    }
+}
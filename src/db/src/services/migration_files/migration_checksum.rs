@@ -0,0 +1,302 @@
+// 適用済みマイグレーションのチェックサム検証
+//
+// 適用後にマイグレーションファイルが書き換えられ、環境ごとにスキーマが
+// 食い違っていくのを防ぐため、apply / status で次の2点を確認します。
+// - `.meta.yaml` のチェックサムと schema_migrations に記録されたチェックサムの一致
+// - `.meta.yaml` の `up_sql_checksum` と現在の up.sql の内容の一致
+//   （BOM・改行コードの違いは無視し、終端文字列は `.meta.yaml` に記録された書き出し時の値で
+//   正規形に戻すため、Windowsでのチェックアウトや後からの出力設定の変更では不一致になりません）
+
+use super::sql_stream::calculate_sql_file_checksum;
+use crate::core::config::SqlOutputConfig;
+use crate::core::migration::{MigrationMetadata, MigrationRecord};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 適用済みマイグレーションのチェックサムの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksumStatus {
+    /// 適用時から変更なし
+    Ok,
+    /// 適用後に up.sql または .meta.yaml が変更された
+    Modified,
+    /// .meta.yaml がない（または読み込めない）ため検証できない
+    MissingMeta,
+}
+
+impl ChecksumStatus {
+    /// 表示用の文字列
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumStatus::Ok => "ok",
+            ChecksumStatus::Modified => "modified",
+            ChecksumStatus::MissingMeta => "missing meta",
+        }
+    }
+}
+
+/// 1マイグレーション分の検証結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumVerification {
+    /// マイグレーションバージョン
+    pub version: String,
+    /// 検証結果
+    pub status: ChecksumStatus,
+    /// 不一致の内容（`Ok` 以外）
+    pub detail: Option<String>,
+}
+
+impl ChecksumVerification {
+    fn new(version: &str, status: ChecksumStatus, detail: Option<String>) -> Self {
+        Self {
+            version: version.to_string(),
+            status,
+            detail,
+        }
+    }
+}
+
+/// 適用済みマイグレーションのチェックサムを検証
+///
+/// ローカルにディレクトリがある適用済みマイグレーションのみを対象とし、適用順に返します。
+/// up.sql は書き出し時の終端文字列（記録がなければ `sql_output` の設定）で正規形に戻してから
+/// チェックサムを計算します。
+pub fn verify_applied_migrations(
+    available_migrations: &[(String, String, PathBuf)],
+    applied_migrations: &[MigrationRecord],
+    sql_output: &SqlOutputConfig,
+) -> Vec<ChecksumVerification> {
+    applied_migrations
+        .iter()
+        .filter_map(|record| {
+            available_migrations
+                .iter()
+                .find(|(version, _, _)| version == &record.version)
+                .map(|(_, _, migration_dir)| {
+                    verify_applied_migration(migration_dir, record, sql_output)
+                })
+        })
+        .collect()
+}
+
+/// 1つの適用済みマイグレーションのチェックサムを検証
+pub fn verify_applied_migration(
+    migration_dir: &Path,
+    record: &MigrationRecord,
+    sql_output: &SqlOutputConfig,
+) -> ChecksumVerification {
+    let version = record.version.as_str();
+    let meta_path = migration_dir.join(".meta.yaml");
+    let Ok(meta_content) = fs::read_to_string(&meta_path) else {
+        return ChecksumVerification::new(
+            version,
+            ChecksumStatus::MissingMeta,
+            Some(".meta.yaml is missing".to_string()),
+        );
+    };
+    let Ok(metadata) = serde_saphyr::from_str::<MigrationMetadata>(&meta_content) else {
+        return ChecksumVerification::new(
+            version,
+            ChecksumStatus::MissingMeta,
+            Some(".meta.yaml could not be parsed".to_string()),
+        );
+    };
+
+    if metadata.checksum != record.checksum {
+        return ChecksumVerification::new(
+            version,
+            ChecksumStatus::Modified,
+            Some(format!(
+                "checksum in .meta.yaml ({}) differs from the applied checksum ({})",
+                metadata.checksum, record.checksum
+            )),
+        );
+    }
+
+    // up_sql_checksum がない古いマイグレーションは up.sql の検証を省略する
+    if let Some(expected) = &metadata.up_sql_checksum {
+        // 巨大な up.sql も全体を読み込まずにチェックサムを計算する
+        let up_sql_path = migration_dir.join("up.sql");
+        let sql_output = metadata.sql_output(sql_output);
+        let detail = match calculate_sql_file_checksum(&up_sql_path, &sql_output) {
+            Err(_) if !up_sql_path.exists() => Some("up.sql is missing".to_string()),
+            Err(e) => Some(format!("up.sql could not be read: {:#}", e)),
+            Ok(checksum) => (checksum != *expected)
+                .then(|| "up.sql was modified after it was applied".to_string()),
+        };
+        if detail.is_some() {
+            return ChecksumVerification::new(version, ChecksumStatus::Modified, detail);
+        }
+    }
+
+    ChecksumVerification::new(version, ChecksumStatus::Ok, None)
+}
+
+/// 検証に失敗したマイグレーションの一覧をエラーメッセージにする
+pub fn format_verification_error(failures: &[&ChecksumVerification]) -> String {
+    let mut message = format!(
+        "{} applied migration(s) failed checksum verification:\n",
+        failures.len()
+    );
+    for failure in failures {
+        message.push_str(&format!(
+            "  - {}: {}\n",
+            failure.version,
+            failure.detail.as_deref().unwrap_or(failure.status.as_str())
+        ));
+    }
+    message.push_str(
+        "\nApplied migrations must not be edited. Restore the original files and put the change in a new migration, or rerun with --skip-checksum-verification to apply anyway.",
+    );
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::migration_files::sql_output::render_sql_file;
+    use crate::services::schema_checksum::SchemaChecksumService;
+    use tempfile::TempDir;
+
+    const UP_SQL: &str = "CREATE TABLE users (id INTEGER);\n";
+
+    fn write_migration(dir: &Path, checksum: &str, up_sql_checksum: Option<&str>) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("up.sql"), UP_SQL).unwrap();
+        let mut meta = format!(
+            "version: \"20260121120000\"\ndescription: create_users\ndialect: sqlite\nchecksum: {}\ndestructive_changes: {{}}\n",
+            checksum
+        );
+        if let Some(up_sql_checksum) = up_sql_checksum {
+            meta.push_str(&format!("up_sql_checksum: {}\n", up_sql_checksum));
+        }
+        fs::write(dir.join(".meta.yaml"), meta).unwrap();
+    }
+
+    fn record(checksum: &str) -> MigrationRecord {
+        MigrationRecord::new(
+            "20260121120000".to_string(),
+            "create_users".to_string(),
+            checksum.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_unchanged_migration_is_ok_even_with_crlf() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("20260121120000_create_users");
+        let sql_checksum = SchemaChecksumService::new().calculate_sql_checksum(UP_SQL);
+        write_migration(&dir, "abc", Some(&sql_checksum));
+
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::Ok
+        );
+
+        // Windowsでのチェックアウトなど、改行コードのみの違いは変更とみなさない
+        fs::write(dir.join("up.sql"), UP_SQL.replace('\n', "\r\n")).unwrap();
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::Ok
+        );
+    }
+
+    #[test]
+    fn test_up_sql_written_with_custom_terminator_is_ok() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("20260121120000_create_users");
+        let sql_checksum = SchemaChecksumService::new().calculate_sql_checksum(UP_SQL);
+        write_migration(&dir, "abc", Some(&sql_checksum));
+
+        // 終端文字列を変えて書き出しても、正規形のチェックサムは変わらない
+        let sql_output = SqlOutputConfig {
+            statement_terminator: "\nGO".to_string(),
+            ..SqlOutputConfig::default()
+        };
+        fs::write(dir.join("up.sql"), render_sql_file(UP_SQL, &sql_output)).unwrap();
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &sql_output).status,
+            ChecksumStatus::Ok
+        );
+    }
+
+    #[test]
+    fn test_terminator_changed_after_generate_is_ok() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("20260121120000_create_users");
+        let sql_checksum = SchemaChecksumService::new().calculate_sql_checksum(UP_SQL);
+        write_migration(&dir, "abc", Some(&sql_checksum));
+        let generated_with = SqlOutputConfig {
+            statement_terminator: "\nGO".to_string(),
+            ..SqlOutputConfig::default()
+        };
+        fs::write(dir.join("up.sql"), render_sql_file(UP_SQL, &generated_with)).unwrap();
+        let meta_path = dir.join(".meta.yaml");
+        let mut meta = fs::read_to_string(&meta_path).unwrap();
+        meta.push_str("statement_terminator: \"\\nGO\"\n");
+        fs::write(&meta_path, meta).unwrap();
+
+        // 生成後に設定の終端文字列を既定に戻しても、生成時の終端文字列で検証する
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::Ok
+        );
+
+        // 記録がなければ現在の設定で検証するため、変更とみなされる
+        let meta = fs::read_to_string(&meta_path).unwrap();
+        fs::write(
+            &meta_path,
+            meta.replace("statement_terminator: \"\\nGO\"\n", ""),
+        )
+        .unwrap();
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::Modified
+        );
+    }
+
+    #[test]
+    fn test_modified_up_sql_and_meta_are_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("20260121120000_create_users");
+        let sql_checksum = SchemaChecksumService::new().calculate_sql_checksum(UP_SQL);
+        write_migration(&dir, "abc", Some(&sql_checksum));
+
+        fs::write(dir.join("up.sql"), "CREATE TABLE users (id BIGINT);\n").unwrap();
+        let result = verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default());
+        assert_eq!(result.status, ChecksumStatus::Modified);
+        assert_eq!(
+            result.detail.as_deref(),
+            Some("up.sql was modified after it was applied")
+        );
+
+        let result = verify_applied_migration(&dir, &record("other"), &SqlOutputConfig::default());
+        assert_eq!(result.status, ChecksumStatus::Modified);
+        assert!(result
+            .detail
+            .unwrap()
+            .contains("differs from the applied checksum"));
+    }
+
+    #[test]
+    fn test_missing_meta_and_legacy_meta() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("20260121120000_create_users");
+
+        // up_sql_checksum のない古いメタデータでは up.sql の検証を省略する
+        write_migration(&dir, "abc", None);
+        fs::write(dir.join("up.sql"), "-- edited\n").unwrap();
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::Ok
+        );
+
+        fs::remove_file(dir.join(".meta.yaml")).unwrap();
+        assert_eq!(
+            verify_applied_migration(&dir, &record("abc"), &SqlOutputConfig::default()).status,
+            ChecksumStatus::MissingMeta
+        );
+    }
+}
@@ -0,0 +1,628 @@
+// マイグレーションディレクトリ読み込みの共通ユーティリティ
+//
+// apply, rollback, status コマンドで共通して使用する
+// マイグレーションディレクトリのスキャン・パースロジックを提供します。
+// 時刻のずれたマシンで生成された未来の日時のバージョンの検出もここで行います。
+//
+// 各ディレクトリは次のいずれかに分類されます。
+// - complete: 命名規則に合い、必要なファイルがすべて揃っている
+// - incomplete: 命名規則に合うが、必要なファイルが欠けている（generate の中断など）
+// - foreign: 命名規則に合わない（マイグレーションとして扱わない）
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// 参照用のサンプルマイグレーションを置くディレクトリ名
+///
+/// `strata init --with-example` が生成します。apply等の対象にはなりません。
+pub const EXAMPLES_DIR_NAME: &str = "examples";
+
+/// マイグレーションの適用に必要なファイル
+pub const REQUIRED_MIGRATION_FILES: [&str; 2] = ["up.sql", ".meta.yaml"];
+
+/// マイグレーションディレクトリの分類
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationDirKind {
+    /// 必要なファイルがすべて揃っている
+    Complete,
+    /// 必要なファイルが欠けている
+    Incomplete {
+        /// 欠けているファイル名
+        missing: Vec<&'static str>,
+    },
+    /// 命名規則 `{timestamp}_{description}` に合わない
+    Foreign {
+        /// 合わない理由
+        reason: String,
+    },
+}
+
+/// スキャンしたマイグレーションディレクトリ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedMigrationDir {
+    /// ディレクトリ名
+    pub name: String,
+    /// ディレクトリのパス
+    pub path: PathBuf,
+    /// 分類
+    pub kind: MigrationDirKind,
+}
+
+/// 必要なファイルが欠けているマイグレーション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompleteMigration {
+    pub version: String,
+    pub description: String,
+    pub path: PathBuf,
+    /// 欠けているファイル名
+    pub missing: Vec<&'static str>,
+}
+
+impl IncompleteMigration {
+    /// status で表示する警告メッセージを生成
+    pub fn warning_message(&self) -> String {
+        format!(
+            "Migration {}_{} is incomplete: missing {}. It was probably left behind by an interrupted `strata generate`.",
+            self.version,
+            self.description,
+            self.missing.join(", ")
+        )
+    }
+}
+
+/// 欠けているファイルのあるマイグレーションを apply のエラーメッセージにする
+pub fn format_incomplete_error(incomplete: &[IncompleteMigration]) -> String {
+    let list: String = incomplete
+        .iter()
+        .map(|m| {
+            format!(
+                "  - {}_{}: missing {}\n",
+                m.version,
+                m.description,
+                m.missing.join(", ")
+            )
+        })
+        .collect();
+    format!(
+        "{} migration director{} incomplete:\n{}Delete the director{} and run `strata generate` again, or restore the missing files.",
+        incomplete.len(),
+        if incomplete.len() == 1 { "y is" } else { "ies are" },
+        list,
+        if incomplete.len() == 1 { "y" } else { "ies" }
+    )
+}
+
+/// タイムスタンプ形式が有効かどうかを検証する
+///
+/// 有効な形式: 数字のみで構成された14桁の文字列 (YYYYMMDDHHmmss)
+fn is_valid_timestamp(s: &str) -> bool {
+    s.len() == 14 && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// `--target` に指定されたバージョンを検証する
+///
+/// データベースに接続する前に、形式の誤りやローカルに存在しないバージョンを検出します。
+///
+/// # Arguments
+///
+/// * `migrations` - `load_available_migrations` の戻り値
+/// * `target` - 指定されたバージョン
+pub fn check_target_version(migrations: &[(String, String, PathBuf)], target: &str) -> Result<()> {
+    if !is_valid_timestamp(target) {
+        return Err(anyhow!(
+            "Invalid target version '{}': expected a 14-digit timestamp (YYYYMMDDHHmmss)",
+            target
+        ));
+    }
+    if !migrations.iter().any(|(version, _, _)| version == target) {
+        return Err(anyhow!(
+            "Unknown target version '{}': no migration with this version exists. Run `strata status` to list the available versions.",
+            target
+        ));
+    }
+    Ok(())
+}
+
+/// マイグレーションディレクトリ直下のディレクトリをスキャンして分類する
+///
+/// `.` で始まるディレクトリ（generate の書き込み途中のディレクトリを含む）と
+/// `examples/` ディレクトリは対象外です。ディレクトリ名順にソートして返します。
+pub fn scan_migration_dirs(migrations_dir: &Path) -> Result<Vec<ScannedMigrationDir>> {
+    let mut dirs = Vec::new();
+
+    let entries = fs::read_dir(migrations_dir)
+        .with_context(|| format!("Failed to read migrations directory: {:?}", migrations_dir))?;
+
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let dir_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow!("Invalid directory name"))?
+            .to_string();
+
+        // .で始まるディレクトリとサンプルマイグレーションはスキップ
+        if dir_name.starts_with('.') || dir_name == EXAMPLES_DIR_NAME {
+            continue;
+        }
+
+        let kind = classify_migration_dir(&dir_name, &path);
+        dirs.push(ScannedMigrationDir {
+            name: dir_name,
+            path,
+            kind,
+        });
+    }
+
+    dirs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dirs)
+}
+
+/// ディレクトリ名と必要なファイルの有無からディレクトリを分類する
+pub fn classify_migration_dir(dir_name: &str, path: &Path) -> MigrationDirKind {
+    // 形式: {timestamp}_{description}
+    let Some((version, _)) = dir_name.split_once('_') else {
+        return MigrationDirKind::Foreign {
+            reason: "does not match expected format '{timestamp}_{description}'".to_string(),
+        };
+    };
+    if !is_valid_timestamp(version) {
+        return MigrationDirKind::Foreign {
+            reason: format!(
+                "version '{}' is not a valid 14-digit timestamp (YYYYMMDDHHmmss)",
+                version
+            ),
+        };
+    }
+
+    let missing: Vec<&'static str> = REQUIRED_MIGRATION_FILES
+        .into_iter()
+        .filter(|file| !path.join(file).is_file())
+        .collect();
+    if missing.is_empty() {
+        MigrationDirKind::Complete
+    } else {
+        MigrationDirKind::Incomplete { missing }
+    }
+}
+
+/// 必要なファイルが欠けているマイグレーションを検出する
+///
+/// # Arguments
+///
+/// * `migrations` - `load_available_migrations` の戻り値
+pub fn find_incomplete_migrations(
+    migrations: &[(String, String, PathBuf)],
+) -> Vec<IncompleteMigration> {
+    migrations
+        .iter()
+        .filter_map(|(version, description, path)| {
+            let dir_name = format!("{}_{}", version, description);
+            match classify_migration_dir(&dir_name, path) {
+                MigrationDirKind::Incomplete { missing } => Some(IncompleteMigration {
+                    version: version.clone(),
+                    description: description.clone(),
+                    path: path.clone(),
+                    missing,
+                }),
+                MigrationDirKind::Complete | MigrationDirKind::Foreign { .. } => None,
+            }
+        })
+        .collect()
+}
+
+/// マイグレーションディレクトリをスキャンし、(version, description, path) のタプルを返す
+///
+/// ディレクトリ名の形式: `{timestamp}_{description}`
+/// - `.` で始まるディレクトリと `examples/` ディレクトリはスキップ
+/// - 命名規則に合わないディレクトリ（foreign）は警告を出力してスキップ
+/// - 必要なファイルが欠けたディレクトリ（incomplete）も含めて返す。
+///   扱いは呼び出し側で `find_incomplete_migrations` を使って決める
+/// - 重複バージョンが検出された場合はエラーを返す
+/// - バージョン順（昇順）にソートして返す
+pub fn load_available_migrations(migrations_dir: &Path) -> Result<Vec<(String, String, PathBuf)>> {
+    let mut migrations = Vec::new();
+
+    for dir in scan_migration_dirs(migrations_dir)? {
+        if let MigrationDirKind::Foreign { reason } = &dir.kind {
+            eprintln!("Warning: Skipping directory '{}': {}", dir.name, reason);
+            continue;
+        }
+
+        let (version, description) = dir
+            .name
+            .split_once('_')
+            .map(|(v, d)| (v.to_string(), d.to_string()))
+            .expect("non-foreign directories contain '_'");
+        migrations.push((version, description, dir.path));
+    }
+
+    // バージョン順にソート
+    migrations.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // 重複バージョンの検出
+    for window in migrations.windows(2) {
+        if window[0].0 == window[1].0 {
+            return Err(anyhow!(
+                "Duplicate migration version detected: '{}' (directories: '{}' and '{}')",
+                window[0].0,
+                window[0].1,
+                window[1].1,
+            ));
+        }
+    }
+
+    Ok(migrations)
+}
+
+/// 現在時刻より先のタイムスタンプを持つマイグレーション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FutureMigration {
+    pub version: String,
+    pub description: String,
+    /// 現在時刻からどれだけ先か
+    pub ahead: Duration,
+}
+
+impl FutureMigration {
+    /// 警告メッセージを生成
+    pub fn warning_message(&self) -> String {
+        format!(
+            "Migration {}_{} is dated {} in the future. It was probably generated on a machine with a wrong clock and will sort after newer migrations. Run `strata repair --renumber {}` to give it a current timestamp.",
+            self.version,
+            self.description,
+            format_duration(self.ahead),
+            self.version
+        )
+    }
+}
+
+/// バージョン（YYYYMMDDHHmmss、UTC）を日時に変換する
+///
+/// 14桁の数字でも日時として不正な場合は None を返す。
+pub fn parse_version_timestamp(version: &str) -> Option<DateTime<Utc>> {
+    if !is_valid_timestamp(version) {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(version, "%Y%m%d%H%M%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// 現在時刻より許容幅を超えて先のバージョンを持つマイグレーションを検出する
+///
+/// # Arguments
+///
+/// * `migrations` - `load_available_migrations` の戻り値
+/// * `now` - 現在時刻
+/// * `tolerance` - 許容する幅
+pub fn find_future_migrations(
+    migrations: &[(String, String, PathBuf)],
+    now: DateTime<Utc>,
+    tolerance: Duration,
+) -> Vec<FutureMigration> {
+    migrations
+        .iter()
+        .filter_map(|(version, description, _)| {
+            let ahead = parse_version_timestamp(version)? - now;
+            (ahead > tolerance).then(|| FutureMigration {
+                version: version.clone(),
+                description: description.clone(),
+                ahead,
+            })
+        })
+        .collect()
+}
+
+/// 未来の日時のマイグレーションを検出して警告メッセージを返す
+///
+/// 検出結果はログにも出力する。
+pub fn check_future_migrations(
+    migrations: &[(String, String, PathBuf)],
+    tolerance: Duration,
+) -> Vec<String> {
+    find_future_migrations(migrations, Utc::now(), tolerance)
+        .iter()
+        .map(|migration| {
+            warn!(
+                version = %migration.version,
+                ahead_minutes = migration.ahead.num_minutes(),
+                "Future-dated migration detected"
+            );
+            migration.warning_message()
+        })
+        .collect()
+}
+
+/// 時間幅を「2d 3h」のような概算表記にする
+fn format_duration(duration: Duration) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_valid_timestamp() {
+        assert!(is_valid_timestamp("20260121120000"));
+        assert!(!is_valid_timestamp("not_a_timestamp"));
+        assert!(!is_valid_timestamp("2026012112")); // 10桁
+        assert!(!is_valid_timestamp("202601211200001")); // 15桁
+        assert!(!is_valid_timestamp("2026012112000a")); // 非数字を含む
+    }
+
+    #[test]
+    fn test_load_valid_migrations() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_create_users")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120001_create_posts")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].0, "20260121120000");
+        assert_eq!(migrations[1].0, "20260121120001");
+    }
+
+    #[test]
+    fn test_skip_invalid_directory_name() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_create_users")).unwrap();
+        fs::create_dir(temp_dir.path().join("nodescription")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 1);
+    }
+
+    #[test]
+    fn test_skip_invalid_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_valid")).unwrap();
+        fs::create_dir(temp_dir.path().join("not_a_migration")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].0, "20260121120000");
+    }
+
+    #[test]
+    fn test_duplicate_version_error() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_create_users")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_create_posts")).unwrap();
+
+        let result = load_available_migrations(temp_dir.path());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate migration version"));
+    }
+
+    #[test]
+    fn test_skip_hidden_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join(".hidden")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_valid")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 1);
+    }
+
+    #[test]
+    fn test_skip_examples_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let examples_dir = temp_dir.path().join(EXAMPLES_DIR_NAME);
+        fs::create_dir(&examples_dir).unwrap();
+        fs::create_dir(examples_dir.join("20260121120000_example")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120001_valid")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].0, "20260121120001");
+    }
+
+    #[test]
+    fn test_empty_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert!(migrations.is_empty());
+    }
+
+    #[test]
+    fn test_nonexistent_directory() {
+        let result = load_available_migrations(Path::new("/nonexistent/path"));
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to read migrations directory"));
+    }
+
+    #[test]
+    fn test_files_are_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_valid")).unwrap();
+        fs::write(temp_dir.path().join("20260121120001_a_file.txt"), "content").unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].0, "20260121120000");
+    }
+
+    #[test]
+    fn test_sorted_by_version_ascending() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121130000_third")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121110000_first")).unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_second")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 3);
+        assert_eq!(migrations[0].1, "first");
+        assert_eq!(migrations[1].1, "second");
+        assert_eq!(migrations[2].1, "third");
+    }
+
+    #[test]
+    fn test_description_extraction() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120000_create_users_table")).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations[0].1, "create_users_table");
+    }
+
+    #[test]
+    fn test_path_is_preserved() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir_path = temp_dir.path().join("20260121120000_migration");
+        fs::create_dir(&dir_path).unwrap();
+
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations[0].2, dir_path);
+    }
+
+    #[test]
+    fn test_classify_migration_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let complete = temp_dir.path().join("20260121120000_complete");
+        fs::create_dir(&complete).unwrap();
+        fs::write(complete.join("up.sql"), "").unwrap();
+        fs::write(complete.join(".meta.yaml"), "").unwrap();
+        // generate が .meta.yaml を書く前に中断された状態
+        let interrupted = temp_dir.path().join("20260121120001_interrupted");
+        fs::create_dir(&interrupted).unwrap();
+        fs::write(interrupted.join("up.sql"), "").unwrap();
+        fs::write(interrupted.join("down.sql"), "").unwrap();
+        fs::create_dir(temp_dir.path().join("20260121120002_empty")).unwrap();
+        fs::create_dir(temp_dir.path().join("backup")).unwrap();
+        fs::create_dir(temp_dir.path().join("v1_legacy")).unwrap();
+        fs::create_dir(temp_dir.path().join(".20260121120003_writing")).unwrap();
+
+        let dirs = scan_migration_dirs(temp_dir.path()).unwrap();
+        let kinds: Vec<(&str, &MigrationDirKind)> =
+            dirs.iter().map(|d| (d.name.as_str(), &d.kind)).collect();
+        assert_eq!(kinds.len(), 5);
+        assert_eq!(
+            kinds[0],
+            ("20260121120000_complete", &MigrationDirKind::Complete)
+        );
+        assert_eq!(
+            kinds[1],
+            (
+                "20260121120001_interrupted",
+                &MigrationDirKind::Incomplete {
+                    missing: vec![".meta.yaml"]
+                }
+            )
+        );
+        assert_eq!(
+            kinds[2],
+            (
+                "20260121120002_empty",
+                &MigrationDirKind::Incomplete {
+                    missing: vec!["up.sql", ".meta.yaml"]
+                }
+            )
+        );
+        assert!(matches!(
+            kinds[3],
+            ("backup", MigrationDirKind::Foreign { .. })
+        ));
+        assert!(
+            matches!(kinds[4], ("v1_legacy", MigrationDirKind::Foreign { reason }) if reason.contains("14-digit"))
+        );
+
+        // incomplete は読み込み結果に含まれ、呼び出し側で検出できる
+        let migrations = load_available_migrations(temp_dir.path()).unwrap();
+        assert_eq!(migrations.len(), 3);
+        let incomplete = find_incomplete_migrations(&migrations);
+        assert_eq!(incomplete.len(), 2);
+        assert_eq!(incomplete[0].version, "20260121120001");
+        assert!(incomplete[0]
+            .warning_message()
+            .contains("20260121120001_interrupted is incomplete: missing .meta.yaml"));
+
+        let error = format_incomplete_error(&incomplete);
+        assert!(error.starts_with("2 migration directories are incomplete:\n"));
+        assert!(error.contains("  - 20260121120002_empty: missing up.sql, .meta.yaml\n"));
+        assert!(error.contains("run `strata generate` again"));
+    }
+
+    #[test]
+    fn test_check_target_version() {
+        let migrations = vec![(
+            "20260121120000".to_string(),
+            "init".to_string(),
+            PathBuf::from("migrations/20260121120000_init"),
+        )];
+
+        assert!(check_target_version(&migrations, "20260121120000").is_ok());
+        let err = check_target_version(&migrations, "2026-01-21").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid target version '2026-01-21'"));
+        let err = check_target_version(&migrations, "20260121120001").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown target version '20260121120001'"));
+    }
+
+    #[test]
+    fn test_parse_version_timestamp() {
+        let parsed = parse_version_timestamp("20260121120000").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2026-01-21T12:00:00+00:00");
+        assert!(parse_version_timestamp("20261399120000").is_none());
+        assert!(parse_version_timestamp("not_a_version").is_none());
+    }
+
+    #[test]
+    fn test_find_future_migrations() {
+        let migrations: Vec<(String, String, PathBuf)> = [
+            ("20260121110000", "past"),
+            ("20260121120500", "within_tolerance"),
+            ("20260123140000", "future"),
+        ]
+        .iter()
+        .map(|(v, d)| (v.to_string(), d.to_string(), PathBuf::from(v)))
+        .collect();
+        let now = parse_version_timestamp("20260121120000").unwrap();
+
+        let future = find_future_migrations(&migrations, now, Duration::minutes(10));
+        assert_eq!(future.len(), 1);
+        assert_eq!(future[0].version, "20260123140000");
+        assert_eq!(future[0].ahead, Duration::hours(50));
+
+        let message = future[0].warning_message();
+        assert!(message.contains("20260123140000_future is dated 2d 2h in the future"));
+        assert!(message.contains("strata repair --renumber 20260123140000"));
+
+        // 許容幅を0にすると少しでも先のものは検出される
+        let future = find_future_migrations(&migrations, now, Duration::zero());
+        assert_eq!(future.len(), 2);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::minutes(42)), "42m");
+        assert_eq!(format_duration(Duration::minutes(125)), "2h 5m");
+        assert_eq!(format_duration(Duration::hours(49)), "2d 1h");
+    }
+}
@@ -0,0 +1,14 @@
+// マイグレーションファイルモジュール
+//
+// マイグレーションディレクトリの読み込みとSQLファイルの入出力、
+// 生成後にまだ適用していないマイグレーションの記録を集約します。
+// CLIコマンドとライブラリAPI（`migration_runner`）で共有します。
+
+pub mod execution_manifest;
+pub mod migration_checksum;
+pub mod migration_loader;
+pub mod retained_tables;
+pub mod sql_output;
+pub mod sql_parser;
+pub mod sql_stream;
+pub mod stacked_migrations;
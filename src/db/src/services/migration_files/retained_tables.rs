@@ -0,0 +1,140 @@
+// ロールバック保護で残されたテーブルの記録
+//
+// `rollback_protection: true` のテーブルはロールバックしてもデータベースに残るため、
+// rollback コマンドはその定義をマイグレーションディレクトリ直下の
+// `.rollback_retained.yaml` に記録します。
+// generate コマンドは前回のスキーマにこれらのテーブルを加えて差分を取り、
+// 既に存在するテーブルを再作成しないようにします。
+// 次のマイグレーションを生成した時点でスナップショットに反映されるため、記録は削除されます。
+
+use crate::core::schema::{Schema, Table};
+use crate::services::schema_io::schema_parser::SchemaParserService;
+use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// ロールバック後も残っているテーブルの記録ファイル名
+pub const RETAINED_TABLES_FILE: &str = ".rollback_retained.yaml";
+
+/// 記録されたテーブルを読み込む
+///
+/// 記録ファイルが存在しない場合は空のリストを返す。
+pub fn load_retained_tables(migrations_dir: &Path) -> Result<Vec<Table>> {
+    let path = migrations_dir.join(RETAINED_TABLES_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let schema = SchemaParserService::new()
+        .parse_schema_file(&path)
+        .with_context(|| format!("Failed to parse retained tables: {:?}", path))?;
+    Ok(schema.tables.into_values().collect())
+}
+
+/// ロールバックで残したテーブルを記録する
+///
+/// 既存の記録に追加し、同名のテーブルは新しい定義で上書きする。
+pub fn record_retained_tables(migrations_dir: &Path, tables: Vec<Table>) -> Result<()> {
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let mut schema = Schema::new("1.0".to_string());
+    for table in load_retained_tables(migrations_dir)?
+        .into_iter()
+        .chain(tables)
+    {
+        schema.add_table(table);
+    }
+
+    let yaml = SchemaSerializerService::new()
+        .serialize_to_string(&schema)
+        .with_context(|| "Failed to serialize retained tables")?;
+    let path = migrations_dir.join(RETAINED_TABLES_FILE);
+    fs::write(&path, yaml)
+        .with_context(|| format!("Failed to write retained tables: {:?}", path))?;
+
+    Ok(())
+}
+
+/// 記録を削除する
+///
+/// 新しいマイグレーションのスナップショットに状態が反映された後に呼び出す。
+pub fn clear_retained_tables(migrations_dir: &Path) -> Result<()> {
+    let path = migrations_dir.join(RETAINED_TABLES_FILE);
+    if path.exists() {
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove retained tables: {:?}", path))?;
+    }
+    Ok(())
+}
+
+/// 前回のスキーマに記録されたテーブルを加える
+///
+/// スナップショットに既に存在するテーブルはそのまま維持する。
+/// 戻り値は追加したテーブル名。
+pub fn merge_retained_tables(schema: &mut Schema, retained: Vec<Table>) -> Vec<String> {
+    let mut merged = Vec::new();
+    for table in retained {
+        if !schema.tables.contains_key(&table.name) {
+            merged.push(table.name.clone());
+            schema.add_table(table);
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType};
+    use tempfile::TempDir;
+
+    fn protected_table(name: &str) -> Table {
+        let mut table = Table::new(name.to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.rollback_protection = true;
+        table
+    }
+
+    #[test]
+    fn test_record_load_and_clear_retained_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert!(load_retained_tables(dir).unwrap().is_empty());
+
+        record_retained_tables(dir, vec![protected_table("audit_log")]).unwrap();
+        record_retained_tables(dir, vec![protected_table("access_log")]).unwrap();
+
+        let tables = load_retained_tables(dir).unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["access_log", "audit_log"]);
+        assert!(tables.iter().all(|t| t.rollback_protection));
+
+        clear_retained_tables(dir).unwrap();
+        assert!(!dir.join(RETAINED_TABLES_FILE).exists());
+        assert!(load_retained_tables(dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_merge_retained_tables_keeps_snapshot_definition() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut snapshot_table = protected_table("audit_log");
+        snapshot_table.add_column(Column::new("message".to_string(), ColumnType::TEXT, false));
+        schema.add_table(snapshot_table);
+
+        let merged = merge_retained_tables(
+            &mut schema,
+            vec![protected_table("audit_log"), protected_table("access_log")],
+        );
+
+        assert_eq!(merged, vec!["access_log".to_string()]);
+        assert_eq!(schema.tables["audit_log"].columns.len(), 2);
+        assert!(schema.tables.contains_key("access_log"));
+    }
+}
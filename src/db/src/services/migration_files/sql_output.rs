@@ -0,0 +1,210 @@
+// SQLファイル出力形式
+//
+// `sql_output` 設定に従って up.sql / down.sql を書き出します。
+// 読み込み時は LF・BOMなし・セミコロン終端の正規形に戻すため、
+// どの出力設定で書かれたファイルでも同じように適用できます。
+// up.sql のチェックサムも正規形に対して計算するため、出力設定を変えても値は変わりません。
+// 終端文字列は `.meta.yaml` に記録し、後から設定を変更しても書き出し時の値で読み込みます。
+
+use super::sql_parser::replace_statement_terminators;
+use crate::core::config::{LineEnding, SqlOutputConfig};
+use crate::core::migration::MigrationMetadata;
+use crate::services::schema_checksum::SchemaChecksumService;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// UTF-8 BOM
+pub const UTF8_BOM: char = '\u{feff}';
+
+/// 正規形のステートメント終端文字列
+pub const CANONICAL_TERMINATOR: &str = ";";
+
+/// 正規形のSQLを出力設定に従って変換
+pub fn render_sql_file(sql: &str, config: &SqlOutputConfig) -> String {
+    let mut rendered = normalize_line_endings(sql);
+
+    if config.statement_terminator != CANONICAL_TERMINATOR {
+        rendered = replace_statement_terminators(
+            &rendered,
+            CANONICAL_TERMINATOR,
+            &config.statement_terminator,
+        );
+    }
+
+    if config.line_endings == LineEnding::Crlf {
+        rendered = rendered.replace('\n', LineEnding::Crlf.as_str());
+    }
+
+    if config.bom {
+        rendered.insert(0, UTF8_BOM);
+    }
+
+    rendered
+}
+
+/// 読み込んだSQLファイルを正規形（LF・BOMなし・セミコロン終端）に戻す
+///
+/// BOMと改行コードは設定に関わらず正規化するため、設定変更前に書かれたファイルも読み込めます。
+pub fn normalize_sql_file(content: &str, config: &SqlOutputConfig) -> String {
+    let content = content.strip_prefix(UTF8_BOM).unwrap_or(content);
+    let normalized = normalize_line_endings(content);
+
+    if config.statement_terminator != CANONICAL_TERMINATOR {
+        replace_statement_terminators(
+            &normalized,
+            &config.statement_terminator,
+            CANONICAL_TERMINATOR,
+        )
+    } else {
+        normalized
+    }
+}
+
+/// SQLファイルの内容のチェックサムを正規形に戻してから計算
+///
+/// 書き出し時（`.meta.yaml` の `up_sql_checksum`）と検証時の両方で使用します。
+pub fn calculate_sql_file_content_checksum(content: &str, config: &SqlOutputConfig) -> String {
+    SchemaChecksumService::new().calculate_sql_checksum(&normalize_sql_file(content, config))
+}
+
+/// マイグレーションのSQLファイルを読み込むときの出力設定
+///
+/// `.meta.yaml` に書き出し時の終端文字列が記録されていればそれを使い、
+/// `.meta.yaml` や記録がない場合は現在の設定を使います。
+pub fn migration_sql_output(
+    migration_dir: &Path,
+    config: &SqlOutputConfig,
+) -> Result<SqlOutputConfig> {
+    let meta_path = migration_dir.join(".meta.yaml");
+    if !meta_path.exists() {
+        return Ok(config.clone());
+    }
+    let content = fs::read_to_string(&meta_path)
+        .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
+    let metadata: MigrationMetadata = serde_saphyr::from_str(&content)
+        .with_context(|| format!("Failed to parse metadata: {:?}", meta_path))?;
+    Ok(metadata.sql_output(config))
+}
+
+/// SQLファイルを出力設定に従って書き出す
+pub fn write_sql_file(path: &Path, sql: &str, config: &SqlOutputConfig) -> Result<()> {
+    fs::write(path, render_sql_file(sql, config))
+        .with_context(|| format!("Failed to write SQL file: {:?}", path))
+}
+
+/// SQLファイルを読み込み、正規形に変換して返す
+pub fn read_sql_file(path: &Path, config: &SqlOutputConfig) -> Result<String> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read migration file: {:?}", path))?;
+    Ok(normalize_sql_file(&content, config))
+}
+
+/// CRLF / CR を LF に統一
+fn normalize_line_endings(sql: &str) -> String {
+    sql.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQL: &str =
+        "-- up\nCREATE TABLE users (id INTEGER);\n\nINSERT INTO users VALUES (';');\n";
+
+    fn config(line_endings: LineEnding, bom: bool, terminator: &str) -> SqlOutputConfig {
+        SqlOutputConfig {
+            line_endings,
+            bom,
+            statement_terminator: terminator.to_string(),
+        }
+    }
+
+    #[test]
+    fn default_config_keeps_sql_unchanged() {
+        let rendered = render_sql_file(SQL, &SqlOutputConfig::default());
+        assert_eq!(rendered.as_bytes(), SQL.as_bytes());
+    }
+
+    #[test]
+    fn renders_byte_exact_output_for_each_combination() {
+        let cases: Vec<(SqlOutputConfig, &[u8])> = vec![
+            (
+                config(LineEnding::Crlf, false, ";"),
+                b"-- up\r\nCREATE TABLE users (id INTEGER);\r\n\r\nINSERT INTO users VALUES (';');\r\n",
+            ),
+            (
+                config(LineEnding::Lf, true, ";"),
+                b"\xef\xbb\xbf-- up\nCREATE TABLE users (id INTEGER);\n\nINSERT INTO users VALUES (';');\n",
+            ),
+            (
+                config(LineEnding::Crlf, true, ";"),
+                b"\xef\xbb\xbf-- up\r\nCREATE TABLE users (id INTEGER);\r\n\r\nINSERT INTO users VALUES (';');\r\n",
+            ),
+            (
+                config(LineEnding::Lf, false, "$$"),
+                b"-- up\nCREATE TABLE users (id INTEGER)$$\n\nINSERT INTO users VALUES (';')$$\n",
+            ),
+            (
+                config(LineEnding::Crlf, true, "\nGO"),
+                b"\xef\xbb\xbf-- up\r\nCREATE TABLE users (id INTEGER)\r\nGO\r\n\r\nINSERT INTO users VALUES (';')\r\nGO\r\n",
+            ),
+        ];
+
+        for (config, expected) in cases {
+            let rendered = render_sql_file(SQL, &config);
+            assert_eq!(
+                rendered.as_bytes(),
+                expected,
+                "unexpected output for {:?}",
+                config
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_restores_canonical_form() {
+        for config in [
+            config(LineEnding::Crlf, true, ";"),
+            config(LineEnding::Lf, true, "$$"),
+            config(LineEnding::Crlf, false, "\nGO"),
+        ] {
+            let rendered = render_sql_file(SQL, &config);
+            assert_eq!(normalize_sql_file(&rendered, &config), SQL);
+        }
+    }
+
+    #[test]
+    fn checksum_is_independent_of_output_settings() {
+        let expected = calculate_sql_file_content_checksum(SQL, &SqlOutputConfig::default());
+        for config in [
+            config(LineEnding::Crlf, true, ";"),
+            config(LineEnding::Lf, false, "$$"),
+            config(LineEnding::Crlf, true, "\nGO"),
+        ] {
+            let rendered = render_sql_file(SQL, &config);
+            assert_eq!(
+                calculate_sql_file_content_checksum(&rendered, &config),
+                expected,
+                "checksum changed for {:?}",
+                config
+            );
+        }
+    }
+
+    #[test]
+    fn normalize_accepts_files_written_with_other_settings() {
+        // 設定がLFでもCRLF・BOM付きファイルを読み込める
+        let crlf_with_bom = render_sql_file(SQL, &config(LineEnding::Crlf, true, ";"));
+        assert_eq!(
+            normalize_sql_file(&crlf_with_bom, &SqlOutputConfig::default()),
+            SQL
+        );
+
+        // 設定がCRLFでもLFのファイルを読み込める
+        assert_eq!(
+            normalize_sql_file(SQL, &config(LineEnding::Crlf, true, ";")),
+            SQL
+        );
+    }
+}
@@ -0,0 +1,724 @@
+// SQLステートメント分割パーサー
+//
+// SQL文字列をセミコロン区切りで個別のステートメントに分割します。
+// シングルクォート、ダブルクォート、バッククォート（MySQL識別子）、
+// PostgreSQLドル引用符内のセミコロンはステートメント区切りとして扱いません。
+// SQLコメント（行コメント `--` / ブロックコメント `/* */`）内の
+// セミコロンも同様にスキップします。
+// 巨大なファイル向けに、チャンク単位で入力を受け取る `StatementSplitter` も提供します。
+
+/// SQL文字列を個別のステートメントに分割
+///
+/// クォート内やコメント内のセミコロンを正しくスキップしながら、
+/// SQL文を分割します。
+///
+/// # Arguments
+///
+/// * `sql` - 分割するSQL文字列
+///
+/// # Returns
+///
+/// 個別のSQL文のベクター（前後の空白はトリム済み）
+pub fn split_sql_statements(sql: &str) -> Vec<String> {
+    split_sql_statements_with_terminator(sql, ";")
+}
+
+/// 指定した終端文字列でSQL文字列を個別のステートメントに分割
+///
+/// `sql_output.statement_terminator` でセミコロン以外の終端文字列を
+/// 使用しているファイルの分割に使用します。
+pub fn split_sql_statements_with_terminator(sql: &str, terminator: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+
+    for offset in find_statement_terminators(sql, terminator) {
+        push_statement(&mut statements, &sql[start..offset]);
+        start = offset + terminator.len();
+    }
+    push_statement(&mut statements, &sql[start..]);
+
+    statements
+}
+
+/// SQLの末尾で閉じられていないクォート・ブロックコメントを検出
+///
+/// 閉じられていない場合は以降のステートメントが分割されないため、
+/// 手で編集したSQLの検証に使用します。閉じられていないものの種類を返します。
+pub fn find_unterminated_sql(sql: &str) -> Option<&'static str> {
+    let mut scanner = TerminatorScanner::new(";");
+    let mut pos = 0;
+    while let ScanStep::Terminator(offset) = scanner.scan(sql, pos, true) {
+        pos = offset + 1;
+    }
+
+    match scanner.state {
+        ParseState::Normal | ParseState::LineComment => None,
+        ParseState::SingleQuoted => Some("string literal"),
+        ParseState::QuotedIdentifier(_) => Some("quoted identifier"),
+        ParseState::DollarQuoted(_) => Some("dollar-quoted string"),
+        ParseState::BlockComment(_) => Some("block comment"),
+    }
+}
+
+/// ステートメント終端文字列を置き換える
+///
+/// クォート内やコメント内の `from` は置き換えません。
+pub fn replace_statement_terminators(sql: &str, from: &str, to: &str) -> String {
+    let mut output = String::with_capacity(sql.len());
+    let mut start = 0;
+
+    for offset in find_statement_terminators(sql, from) {
+        output.push_str(&sql[start..offset]);
+        output.push_str(to);
+        start = offset + from.len();
+    }
+    output.push_str(&sql[start..]);
+
+    output
+}
+
+/// ステートメント終端文字列のバイト位置を列挙
+///
+/// クォート（シングル・ダブル・バッククォート・PostgreSQLドル引用符）内と
+/// コメント（行コメント `--` / ブロックコメント `/* */`）内の終端文字列はスキップします。
+fn find_statement_terminators(sql: &str, terminator: &str) -> Vec<usize> {
+    let mut scanner = TerminatorScanner::new(terminator);
+    let mut offsets = Vec::new();
+    let mut pos = 0;
+
+    while let ScanStep::Terminator(offset) = scanner.scan(sql, pos, true) {
+        offsets.push(offset);
+        pos = offset + terminator.len();
+    }
+
+    offsets
+}
+
+/// SQLを少しずつ受け取りながらステートメントに分割する
+///
+/// 大きなSQLファイルを全て読み込まずに実行するために使用します。
+/// 受け取ったチャンクを連結した文字列を [`split_sql_statements_with_terminator`] で
+/// 分割した場合と同じステートメント列を返します。
+/// 保持するのは未完了のステートメント1つ分のみです。
+#[derive(Debug)]
+pub struct StatementSplitter {
+    scanner: TerminatorScanner,
+    /// 未完了のステートメント
+    buffer: String,
+    /// `buffer` 内の走査済みバイト位置
+    scanned: usize,
+    /// これまでに保持した `buffer` の最大バイト数
+    peak_buffered: usize,
+}
+
+impl StatementSplitter {
+    /// 指定した終端文字列で分割するStatementSplitterを作成
+    pub(crate) fn new(terminator: &str) -> Self {
+        Self {
+            scanner: TerminatorScanner::new(terminator),
+            buffer: String::new(),
+            scanned: 0,
+            peak_buffered: 0,
+        }
+    }
+
+    /// チャンクを追加し、完成したステートメントを返す
+    pub(crate) fn push(&mut self, chunk: &str) -> Vec<String> {
+        self.buffer.push_str(chunk);
+        self.peak_buffered = self.peak_buffered.max(self.buffer.len());
+        self.drain(false)
+    }
+
+    /// 入力の終わりを通知し、残りのステートメントを返す
+    pub(crate) fn finish(&mut self) -> Vec<String> {
+        let mut statements = self.drain(true);
+        push_statement(&mut statements, &self.buffer);
+        self.buffer.clear();
+        self.scanned = 0;
+        statements
+    }
+
+    /// これまでに保持した未完了ステートメントの最大バイト数
+    #[cfg(test)]
+    pub(crate) fn peak_buffered(&self) -> usize {
+        self.peak_buffered
+    }
+
+    fn drain(&mut self, eof: bool) -> Vec<String> {
+        let mut statements = Vec::new();
+        let mut start = 0;
+
+        loop {
+            match self.scanner.scan(&self.buffer, self.scanned, eof) {
+                ScanStep::Terminator(offset) => {
+                    push_statement(&mut statements, &self.buffer[start..offset]);
+                    start = offset + self.scanner.terminator.len();
+                    self.scanned = start;
+                }
+                ScanStep::NeedMore(pos) => {
+                    self.scanned = pos;
+                    break;
+                }
+                ScanStep::End => {
+                    self.scanned = self.buffer.len();
+                    break;
+                }
+            }
+        }
+
+        // 完成したステートメントは手放す
+        self.buffer.drain(..start);
+        self.scanned -= start;
+        statements
+    }
+}
+
+/// SQLを少しずつ受け取りながらステートメント終端文字列を置き換える
+///
+/// 受け取ったチャンクを連結した文字列を [`replace_statement_terminators`] で
+/// 置き換えた場合と同じ文字列を、確定した部分から順に返します。
+#[derive(Debug)]
+pub(crate) struct TerminatorReplacer {
+    scanner: TerminatorScanner,
+    /// 置き換え後の終端文字列
+    to: String,
+    /// 判定に続きの入力が必要な未出力部分
+    buffer: String,
+}
+
+impl TerminatorReplacer {
+    /// `from` を `to` に置き換えるTerminatorReplacerを作成
+    pub(crate) fn new(from: &str, to: &str) -> Self {
+        Self {
+            scanner: TerminatorScanner::new(from),
+            to: to.to_string(),
+            buffer: String::new(),
+        }
+    }
+
+    /// チャンクを追加し、置き換えが確定した部分を返す
+    pub(crate) fn push(&mut self, chunk: &str) -> String {
+        self.buffer.push_str(chunk);
+        self.drain(false)
+    }
+
+    /// 入力の終わりを通知し、残りを返す
+    pub(crate) fn finish(&mut self) -> String {
+        self.drain(true)
+    }
+
+    fn drain(&mut self, eof: bool) -> String {
+        let mut output = String::with_capacity(self.buffer.len());
+        let mut start = 0;
+
+        let scanned = loop {
+            match self.scanner.scan(&self.buffer, start, eof) {
+                ScanStep::Terminator(offset) => {
+                    output.push_str(&self.buffer[start..offset]);
+                    output.push_str(&self.to);
+                    start = offset + self.scanner.terminator.len();
+                }
+                ScanStep::NeedMore(pos) => break pos,
+                ScanStep::End => break self.buffer.len(),
+            }
+        };
+
+        // 走査済みの部分は状態が確定しているため出力する
+        output.push_str(&self.buffer[start..scanned]);
+        self.buffer.drain(..scanned);
+        output
+    }
+}
+
+/// 空のステートメントとコメントのみのステートメントを除外して追加
+fn push_statement(statements: &mut Vec<String>, raw: &str) {
+    let statement = raw.trim();
+    if !statement.is_empty() && !is_comment_only(statement) {
+        statements.push(statement.to_string());
+    }
+}
+
+/// 走査の結果
+enum ScanStep {
+    /// 終端文字列を検出した（バイト位置）
+    Terminator(usize),
+    /// 判定に続きの入力が必要（このバイト位置から走査を再開する）
+    NeedMore(usize),
+    /// 入力の末尾まで走査した
+    End,
+}
+
+/// ステートメント終端文字列を探す走査器
+///
+/// クォートやコメントの状態を保持するため、入力を分割して渡しても走査を続けられます。
+#[derive(Debug)]
+struct TerminatorScanner {
+    terminator: String,
+    state: ParseState,
+}
+
+impl TerminatorScanner {
+    fn new(terminator: &str) -> Self {
+        Self {
+            terminator: terminator.to_string(),
+            state: ParseState::Normal,
+        }
+    }
+
+    /// `sql` の `from` バイト目から次の終端文字列まで走査
+    ///
+    /// `eof` がfalseの場合、判定に後続の文字が必要な位置で [`ScanStep::NeedMore`] を返します。
+    fn scan(&mut self, sql: &str, from: usize, eof: bool) -> ScanStep {
+        let mut pos = from;
+
+        while pos < sql.len() {
+            let rest = &sql[pos..];
+            let mut chars = rest.chars();
+            let c = chars.next().unwrap_or_default();
+            let next = chars.next();
+            let needs_more = next.is_none() && !eof;
+
+            match &self.state {
+                ParseState::DollarQuoted(tag) => {
+                    if c == '$' && rest.starts_with(tag.as_str()) {
+                        pos += tag.len();
+                        self.state = ParseState::Normal;
+                        continue;
+                    }
+                    if c == '$' && !eof && tag.starts_with(rest) {
+                        return ScanStep::NeedMore(pos);
+                    }
+                    pos += c.len_utf8();
+                }
+                ParseState::SingleQuoted => {
+                    if c == '\'' {
+                        if needs_more {
+                            return ScanStep::NeedMore(pos);
+                        }
+                        if next == Some('\'') {
+                            // エスケープされたシングルクォート('')
+                            pos += 2;
+                            continue;
+                        }
+                        self.state = ParseState::Normal;
+                    }
+                    pos += c.len_utf8();
+                }
+                ParseState::QuotedIdentifier(quote) => {
+                    if c == *quote {
+                        if needs_more {
+                            return ScanStep::NeedMore(pos);
+                        }
+                        if next == Some(*quote) {
+                            // エスケープされたクォート("" / ``)
+                            pos += 2;
+                            continue;
+                        }
+                        self.state = ParseState::Normal;
+                    }
+                    pos += c.len_utf8();
+                }
+                ParseState::LineComment => {
+                    if c == '\n' {
+                        self.state = ParseState::Normal;
+                    }
+                    pos += c.len_utf8();
+                }
+                ParseState::BlockComment(depth) => {
+                    let depth = *depth;
+                    match (c, next) {
+                        ('/', Some('*')) => {
+                            // ネストされたブロックコメント開始
+                            pos += 2;
+                            self.state = ParseState::BlockComment(depth + 1);
+                        }
+                        ('*', Some('/')) => {
+                            pos += 2;
+                            self.state = if depth == 1 {
+                                ParseState::Normal
+                            } else {
+                                ParseState::BlockComment(depth - 1)
+                            };
+                        }
+                        ('/' | '*', None) if needs_more => return ScanStep::NeedMore(pos),
+                        _ => pos += c.len_utf8(),
+                    }
+                }
+                ParseState::Normal => {
+                    if rest.starts_with(self.terminator.as_str()) {
+                        return ScanStep::Terminator(pos);
+                    }
+                    if !eof && self.terminator.starts_with(rest) {
+                        return ScanStep::NeedMore(pos);
+                    }
+
+                    match c {
+                        '\'' => {
+                            self.state = ParseState::SingleQuoted;
+                            pos += 1;
+                        }
+                        '"' | '`' => {
+                            self.state = ParseState::QuotedIdentifier(c);
+                            pos += 1;
+                        }
+                        '-' | '/' if needs_more => return ScanStep::NeedMore(pos),
+                        '-' if next == Some('-') => {
+                            // 行コメント開始
+                            self.state = ParseState::LineComment;
+                            pos += 2;
+                        }
+                        '/' if next == Some('*') => {
+                            // ブロックコメント開始（深さ1）
+                            self.state = ParseState::BlockComment(1);
+                            pos += 2;
+                        }
+                        '$' => match dollar_quote_tag(rest, eof) {
+                            DollarTag::Tag(tag) => {
+                                pos += tag.len();
+                                self.state = ParseState::DollarQuoted(tag);
+                            }
+                            DollarTag::Literal => pos += 1,
+                            DollarTag::NeedMore => return ScanStep::NeedMore(pos),
+                        },
+                        _ => pos += c.len_utf8(),
+                    }
+                }
+            }
+        }
+
+        ScanStep::End
+    }
+}
+
+/// ステートメントがコメントのみで構成されているかを判定
+///
+/// 行コメント (`--`) とブロックコメント (`/* */`) を除去した後に
+/// 有効なSQL文が残らない場合は true を返します。
+fn is_comment_only(s: &str) -> bool {
+    let mut remaining = s.trim();
+
+    loop {
+        if remaining.is_empty() {
+            return true;
+        }
+
+        if remaining.starts_with("--") {
+            // 行コメント: 改行まで、または末尾までスキップ
+            match remaining.find('\n') {
+                Some(pos) => remaining = remaining[pos + 1..].trim(),
+                None => return true,
+            }
+        } else if remaining.starts_with("/*") {
+            // ブロックコメント: ネスト対応でスキップ
+            let mut depth: u32 = 1;
+            let chars: Vec<char> = remaining.chars().collect();
+            let mut i = 2; // "/*" の次から
+            while i < chars.len() && depth > 0 {
+                if chars[i] == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+                    depth -= 1;
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+            if depth > 0 {
+                // 閉じられていないコメント: 全体がコメント扱い
+                return true;
+            }
+            // コメント後の残りをチェック
+            let byte_offset: usize = chars[..i].iter().map(|c| c.len_utf8()).sum();
+            remaining = remaining[byte_offset..].trim();
+        } else {
+            // コメントでない文字が見つかった
+            return false;
+        }
+    }
+}
+
+/// パーサーの状態
+#[derive(Debug)]
+enum ParseState {
+    Normal,
+    SingleQuoted,
+    /// クォートされた識別子（PostgreSQL/SQLiteのダブルクォート、MySQLのバッククォート）
+    QuotedIdentifier(char),
+    DollarQuoted(String),
+    LineComment,
+    /// ブロックコメント（ネスト深さを保持。PostgreSQLのネストされたコメントに対応）
+    BlockComment(u32),
+}
+
+/// `$` から始まる文字列の判定結果
+enum DollarTag {
+    /// ドル引用符の開始タグ（`$tag$`）
+    Tag(String),
+    /// リテラルの `$`
+    Literal,
+    /// 判定に続きの入力が必要
+    NeedMore,
+}
+
+/// ドル引用符の開始を判定
+///
+/// `$tag$` パターン（タグは英数字とアンダースコアのみ）に一致する場合は開始タグを返し、
+/// 一致しない場合はリテラル `$` として扱います。
+fn dollar_quote_tag(rest: &str, eof: bool) -> DollarTag {
+    for (offset, ch) in rest.char_indices().skip(1) {
+        if ch == '$' {
+            return DollarTag::Tag(rest[..=offset].to_string());
+        }
+        if !(ch.is_ascii_alphanumeric() || ch == '_') {
+            return DollarTag::Literal;
+        }
+    }
+
+    if eof {
+        DollarTag::Literal
+    } else {
+        DollarTag::NeedMore
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_statements() {
+        let sql = "CREATE TABLE users (id INT); INSERT INTO users VALUES (1);";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "CREATE TABLE users (id INT)");
+        assert_eq!(stmts[1], "INSERT INTO users VALUES (1)");
+    }
+
+    #[test]
+    fn test_single_quoted_semicolon() {
+        let sql = "INSERT INTO t VALUES ('a;b'); SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "INSERT INTO t VALUES ('a;b')");
+    }
+
+    #[test]
+    fn test_double_quoted_semicolon() {
+        let sql = r#"SELECT "col;name" FROM t; SELECT 1;"#;
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+    }
+
+    #[test]
+    fn test_backtick_quoted_semicolon() {
+        // MySQLのバッククォート識別子内のセミコロン・クォート・ドル記号は区切りとして扱わない
+        let sql = "CREATE TABLE `a``;b` (`it's` INT, `$$` INT); SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "CREATE TABLE `a``;b` (`it's` INT, `$$` INT)");
+    }
+
+    #[test]
+    fn test_dollar_quoted_semicolon() {
+        let sql = "CREATE FUNCTION f() RETURNS void AS $$ BEGIN NULL; END; $$ LANGUAGE plpgsql; SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+    }
+
+    #[test]
+    fn test_escaped_single_quote() {
+        let sql = "INSERT INTO t VALUES ('it''s'); SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "INSERT INTO t VALUES ('it''s')");
+    }
+
+    #[test]
+    fn test_trailing_statement_without_semicolon() {
+        let sql = "SELECT 1";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0], "SELECT 1");
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let stmts = split_sql_statements("");
+        assert!(stmts.is_empty());
+    }
+
+    #[test]
+    fn test_whitespace_only() {
+        let stmts = split_sql_statements("  \n  ");
+        assert!(stmts.is_empty());
+    }
+
+    // ==========================================
+    // UTF-8対応テスト
+    // ==========================================
+
+    #[test]
+    fn test_utf8_in_string_literal() {
+        // 日本語文字列リテラル内のセミコロンは区切りとして扱わない
+        let sql = "INSERT INTO t VALUES ('日本語;テスト'); SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "INSERT INTO t VALUES ('日本語;テスト')");
+        assert_eq!(stmts[1], "SELECT 1");
+    }
+
+    #[test]
+    fn test_utf8_in_identifier() {
+        // マルチバイト文字を含む識別子
+        let sql = r#"SELECT "名前" FROM t; SELECT 1;"#;
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+    }
+
+    // ==========================================
+    // コメント処理テスト
+    // ==========================================
+
+    #[test]
+    fn test_line_comment_with_semicolon() {
+        // 行コメント内のセミコロンは区切りとして扱わない
+        let sql = "SELECT 1 -- comment; not a separator\nFROM t;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0], "SELECT 1 -- comment; not a separator\nFROM t");
+    }
+
+    #[test]
+    fn test_block_comment_with_semicolon() {
+        // ブロックコメント内のセミコロンは区切りとして扱わない
+        let sql = "SELECT 1 /* comment; with semicolon */ FROM t;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0], "SELECT 1 /* comment; with semicolon */ FROM t");
+    }
+
+    #[test]
+    fn test_block_comment_multiline() {
+        let sql = "SELECT 1\n/* multi\nline; comment\n*/\nFROM t; SELECT 2;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+    }
+
+    #[test]
+    fn test_line_comment_at_end() {
+        // コメントのみのステートメントは除外される
+        let sql = "SELECT 1; -- trailing comment";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0], "SELECT 1");
+    }
+
+    #[test]
+    fn test_double_dash_in_string_not_comment() {
+        // 文字列リテラル内の -- はコメントとして扱わない
+        let sql = "INSERT INTO t VALUES ('a--b;c'); SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "INSERT INTO t VALUES ('a--b;c')");
+    }
+
+    #[test]
+    fn test_comment_only_statement_filtered() {
+        // コメントのみのステートメントが除外されること
+        let sql = "/* just a comment */; SELECT 1;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0], "SELECT 1");
+    }
+
+    #[test]
+    fn test_comment_with_sql_preserved() {
+        // コメント + SQL のステートメントは保持される
+        let sql = "/* comment */ SELECT 1; SELECT 2;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "/* comment */ SELECT 1");
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        // PostgreSQLスタイルのネストされたブロックコメント
+        let sql = "SELECT 1 /* outer /* inner; */ still comment; */ FROM t; SELECT 2;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(
+            stmts[0],
+            "SELECT 1 /* outer /* inner; */ still comment; */ FROM t"
+        );
+        assert_eq!(stmts[1], "SELECT 2");
+    }
+
+    #[test]
+    fn test_nested_block_comment_deep() {
+        // 3段ネスト
+        let sql = "SELECT /* a /* b /* c; */ d */ e */ 1; SELECT 2;";
+        let stmts = split_sql_statements(sql);
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "SELECT /* a /* b /* c; */ d */ e */ 1");
+        assert_eq!(stmts[1], "SELECT 2");
+    }
+
+    #[test]
+    fn test_custom_terminator() {
+        let sql = "CREATE TABLE t (id INT)$$\nINSERT INTO t VALUES ('$$')$$\n-- done$$\n";
+        let stmts = split_sql_statements_with_terminator(sql, "$$");
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0], "CREATE TABLE t (id INT)");
+        assert_eq!(stmts[1], "INSERT INTO t VALUES ('$$')");
+    }
+
+    #[test]
+    fn test_replace_statement_terminators_skips_quotes_and_comments() {
+        let sql = "-- BEGIN; COMMIT;\nINSERT INTO t VALUES (';');\nSELECT 1;";
+        assert_eq!(
+            replace_statement_terminators(sql, ";", "\nGO"),
+            "-- BEGIN; COMMIT;\nINSERT INTO t VALUES (';')\nGO\nSELECT 1\nGO"
+        );
+    }
+
+    #[test]
+    fn test_terminator_replacer_matches_whole_input_at_any_chunk_size() {
+        let sql = "-- a\nGO\nINSERT INTO t VALUES ('x\nGO')\nGO\n$$\nGO$$\nGO\nSELECT 1\nGO";
+        let expected = replace_statement_terminators(sql, "\nGO", ";");
+
+        for chunk_size in 1..=sql.len() {
+            let mut replacer = TerminatorReplacer::new("\nGO", ";");
+            let mut output = String::new();
+            for chunk in sql.as_bytes().chunks(chunk_size) {
+                output.push_str(&replacer.push(std::str::from_utf8(chunk).unwrap()));
+            }
+            output.push_str(&replacer.finish());
+            assert_eq!(output, expected, "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_find_unterminated_sql() {
+        assert_eq!(
+            find_unterminated_sql("UPDATE users SET name = 'a;b';\n-- it's fine"),
+            None
+        );
+        assert_eq!(
+            find_unterminated_sql("UPDATE users SET name = 'oops;\nDROP TABLE x;"),
+            Some("string literal")
+        );
+        assert_eq!(
+            find_unterminated_sql("SELECT 1; /* not closed"),
+            Some("block comment")
+        );
+        assert_eq!(
+            find_unterminated_sql("DO $body$ BEGIN NULL; END;"),
+            Some("dollar-quoted string")
+        );
+        assert_eq!(
+            find_unterminated_sql(r#"SELECT "name FROM users;"#),
+            Some("quoted identifier")
+        );
+    }
+}
@@ -0,0 +1,592 @@
+// SQLファイルのストリーミング読み込み
+//
+// 大量のINSERTを含むような巨大なup.sqlを、ファイル全体をメモリに載せずに
+// 少しずつ読みながらステートメント単位で取り出します。
+// 読み込み時の正規化（BOM除去・改行コード統一・終端文字列の置き換え）は
+// `sql_output::read_sql_file` と同じ結果になるように逐次適用します。
+// up.sql のチェックサムも同じ正規化を適用しながら、正規形（セミコロン終端）に対して逐次計算します。
+
+use super::sql_output::{normalize_sql_file, CANONICAL_TERMINATOR, UTF8_BOM};
+use super::sql_parser::{split_sql_statements, StatementSplitter, TerminatorReplacer};
+use crate::core::config::SqlOutputConfig;
+use crate::services::schema_checksum::SchemaChecksumService;
+use anyhow::{anyhow, Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// 1回の読み込みで取得するバイト数
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// SQLファイルのステートメントを順に取り出す
+///
+/// `stream_threshold` バイト以下のファイルは全体を読み込んで分割し、
+/// それより大きいファイルはストリーミングで読み込みます。
+pub fn read_sql_statements(
+    path: &Path,
+    config: &SqlOutputConfig,
+    stream_threshold: u64,
+) -> Result<SqlStatements> {
+    let size = fs::metadata(path)
+        .with_context(|| format!("Failed to read migration file: {:?}", path))?
+        .len();
+
+    if size <= stream_threshold {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read migration file: {:?}", path))?;
+        let sql = normalize_sql_file(&content, config);
+        let checksum = SchemaChecksumService::new().calculate_sql_checksum(&sql);
+        return Ok(SqlStatements::InMemory {
+            statements: split_sql_statements(&sql).into_iter(),
+            checksum,
+        });
+    }
+
+    debug!(path = %path.display(), size, "Streaming large SQL file");
+    let file =
+        File::open(path).with_context(|| format!("Failed to read migration file: {:?}", path))?;
+    Ok(SqlStatements::Streaming(Box::new(SqlStatementReader::new(
+        BufReader::new(file),
+        path,
+        config,
+    ))))
+}
+
+/// SQLファイルのステートメント列
+pub enum SqlStatements {
+    /// 全体を読み込んで分割済み
+    InMemory {
+        statements: std::vec::IntoIter<String>,
+        /// ファイル全体のチェックサム（正規形に対して計算）
+        checksum: String,
+    },
+    /// ファイルから逐次読み込み
+    Streaming(Box<SqlStatementReader<BufReader<File>>>),
+}
+
+impl SqlStatements {
+    /// 読み込んだファイルのチェックサム
+    ///
+    /// ストリーミングで読み込む場合は、全てのステートメントを取り出し終えるまで `None` を返します。
+    pub fn checksum(&self) -> Option<&str> {
+        match self {
+            SqlStatements::InMemory { checksum, .. } => Some(checksum),
+            SqlStatements::Streaming(reader) => reader.checksum(),
+        }
+    }
+}
+
+impl Iterator for SqlStatements {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SqlStatements::InMemory { statements, .. } => statements.next().map(Ok),
+            SqlStatements::Streaming(reader) => reader.next(),
+        }
+    }
+}
+
+/// SQLファイルを少しずつ読み込み、正規形に戻したチェックサムを計算する
+///
+/// ファイル全体を読み込んで [`calculate_sql_file_content_checksum`] を
+/// 呼び出した場合と同じ値（BOM・改行コード・終端文字列の違いを無視したもの）を返します。
+///
+/// [`calculate_sql_file_content_checksum`]: super::sql_output::calculate_sql_file_content_checksum
+pub fn calculate_sql_file_checksum(path: &Path, config: &SqlOutputConfig) -> Result<String> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to read migration file: {:?}", path))?;
+    let mut reader = BufReader::new(file);
+    let mut decoder = SqlTextDecoder::new(path);
+    let mut hasher = CanonicalSqlHasher::new(config);
+    let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+
+    loop {
+        let read = reader
+            .read(&mut chunk)
+            .with_context(|| format!("Failed to read migration file: {:?}", path))?;
+        if read == 0 {
+            hasher.update(&decoder.finish()?);
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&decoder.decode(&chunk[..read])?);
+    }
+}
+
+/// 正規化済みのテキストを受け取り、終端文字列を正規形に戻しながらハッシュを計算する
+struct CanonicalSqlHasher {
+    hasher: Sha256,
+    /// 終端文字列がセミコロン以外の場合の置き換え
+    replacer: Option<TerminatorReplacer>,
+}
+
+impl CanonicalSqlHasher {
+    fn new(config: &SqlOutputConfig) -> Self {
+        let replacer = (config.statement_terminator != CANONICAL_TERMINATOR)
+            .then(|| TerminatorReplacer::new(&config.statement_terminator, CANONICAL_TERMINATOR));
+        Self {
+            hasher: Sha256::new(),
+            replacer,
+        }
+    }
+
+    fn update(&mut self, text: &str) {
+        match self.replacer.as_mut() {
+            Some(replacer) => self.hasher.update(replacer.push(text).as_bytes()),
+            None => self.hasher.update(text.as_bytes()),
+        }
+    }
+
+    fn finalize(mut self) -> String {
+        if let Some(replacer) = self.replacer.as_mut() {
+            self.hasher.update(replacer.finish().as_bytes());
+        }
+        format!("{:x}", self.hasher.finalize())
+    }
+}
+
+/// SQLを少しずつ読み込みながらステートメントを取り出すリーダー
+///
+/// 保持するのは読み込み中のチャンクと未完了のステートメント1つ分のみです。
+/// 読み込んだ内容のチェックサムも逐次計算し、読み終えた後に [`Self::checksum`] で取得できます。
+pub struct SqlStatementReader<R: Read> {
+    reader: R,
+    decoder: SqlTextDecoder,
+    splitter: StatementSplitter,
+    /// 終端文字列がセミコロン以外の場合、分割後の各ステートメントをさらにセミコロンで分割する
+    resplit_canonical: bool,
+    /// 取り出し待ちのステートメント
+    pending: VecDeque<String>,
+    /// 正規形に戻した内容のハッシュ（読み終えるまで）
+    hasher: Option<CanonicalSqlHasher>,
+    /// 読み終えた内容のチェックサム
+    checksum: Option<String>,
+    finished: bool,
+}
+
+impl<R: Read> SqlStatementReader<R> {
+    /// 新しいSqlStatementReaderを作成
+    pub(crate) fn new(reader: R, path: &Path, config: &SqlOutputConfig) -> Self {
+        Self {
+            reader,
+            decoder: SqlTextDecoder::new(path),
+            splitter: StatementSplitter::new(&config.statement_terminator),
+            resplit_canonical: config.statement_terminator != CANONICAL_TERMINATOR,
+            pending: VecDeque::new(),
+            hasher: Some(CanonicalSqlHasher::new(config)),
+            checksum: None,
+            finished: false,
+        }
+    }
+
+    /// 読み込んだ内容のチェックサム
+    ///
+    /// 全てのステートメントを取り出し終えるまでは `None` を返します。
+    /// 値は [`calculate_sql_file_checksum`] と同じです。
+    pub fn checksum(&self) -> Option<&str> {
+        self.checksum.as_deref()
+    }
+
+    /// これまでに保持した未完了ステートメントの最大バイト数
+    #[cfg(test)]
+    pub(crate) fn peak_buffered(&self) -> usize {
+        self.splitter.peak_buffered()
+    }
+
+    /// 次のチャンクを読み込み、完成したステートメントを取り出し待ちに加える
+    fn fill(&mut self) -> Result<()> {
+        let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+        let read = self
+            .reader
+            .read(&mut chunk)
+            .with_context(|| format!("Failed to read migration file: {:?}", self.decoder.path))?;
+
+        if read == 0 {
+            let text = self.decoder.finish()?;
+            if let Some(mut hasher) = self.hasher.take() {
+                hasher.update(&text);
+                self.checksum = Some(hasher.finalize());
+            }
+            let mut statements = self.splitter.push(&text);
+            statements.extend(self.splitter.finish());
+            self.enqueue(statements);
+            self.finished = true;
+            return Ok(());
+        }
+
+        let text = self.decoder.decode(&chunk[..read])?;
+        if let Some(hasher) = self.hasher.as_mut() {
+            hasher.update(&text);
+        }
+        let statements = self.splitter.push(&text);
+        self.enqueue(statements);
+        Ok(())
+    }
+
+    fn enqueue(&mut self, statements: Vec<String>) {
+        if self.resplit_canonical {
+            for statement in statements {
+                self.pending.extend(split_sql_statements(&statement));
+            }
+        } else {
+            self.pending.extend(statements);
+        }
+    }
+}
+
+impl<R: Read> Iterator for SqlStatementReader<R> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(statement) = self.pending.pop_front() {
+                return Some(Ok(statement));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// 読み込んだバイト列をUTF-8として復号し、BOM除去・改行コード統一を逐次適用する
+struct SqlTextDecoder {
+    path: PathBuf,
+    /// UTF-8として未確定の末尾バイト（文字の途中でチャンクが切れた場合）
+    undecoded: Vec<u8>,
+    /// 直前のチャンクが `\r` で終わっていたか（`\r\n` の判定を次のチャンクまで保留する）
+    pending_cr: bool,
+    /// ファイル先頭（BOM判定前）か
+    at_start: bool,
+}
+
+impl SqlTextDecoder {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            undecoded: Vec::new(),
+            pending_cr: false,
+            at_start: true,
+        }
+    }
+
+    /// チャンクを追加し、確定した部分を正規化した文字列を返す
+    fn decode(&mut self, chunk: &[u8]) -> Result<String> {
+        self.undecoded.extend_from_slice(chunk);
+        let valid_up_to = match std::str::from_utf8(&self.undecoded) {
+            Ok(_) => self.undecoded.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return Err(self.invalid_utf8()),
+        };
+        let rest = self.undecoded.split_off(valid_up_to);
+        let decoded = String::from_utf8(std::mem::replace(&mut self.undecoded, rest))
+            .map_err(|_| self.invalid_utf8())?;
+
+        let mut text = decoded.as_str();
+        if self.at_start && !text.is_empty() {
+            text = text.strip_prefix(UTF8_BOM).unwrap_or(text);
+            self.at_start = false;
+        }
+
+        Ok(self.normalize_line_endings(text))
+    }
+
+    /// 入力の終わりを通知し、保留していた改行を返す
+    fn finish(&mut self) -> Result<String> {
+        if !self.undecoded.is_empty() {
+            return Err(self.invalid_utf8());
+        }
+        let mut text = String::new();
+        if self.pending_cr {
+            text.push('\n');
+            self.pending_cr = false;
+        }
+        Ok(text)
+    }
+
+    /// CRLF / CR を LF に統一（チャンク境界をまたぐ `\r\n` にも対応）
+    fn normalize_line_endings(&mut self, text: &str) -> String {
+        let mut normalized = String::with_capacity(text.len() + 1);
+        let mut chars = text.chars().peekable();
+
+        if self.pending_cr {
+            normalized.push('\n');
+            if chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            self.pending_cr = false;
+        }
+
+        while let Some(c) = chars.next() {
+            if c != '\r' {
+                normalized.push(c);
+                continue;
+            }
+            match chars.peek() {
+                Some('\n') => {
+                    chars.next();
+                    normalized.push('\n');
+                }
+                Some(_) => normalized.push('\n'),
+                None => self.pending_cr = true,
+            }
+        }
+
+        normalized
+    }
+
+    fn invalid_utf8(&self) -> anyhow::Error {
+        anyhow!(
+            "Failed to read migration file: {:?}: stream did not contain valid UTF-8",
+            self.path
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::migration_files::sql_output::{read_sql_file, render_sql_file};
+    use crate::services::migration_files::sql_parser::split_sql_statements_with_terminator;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// 指定したバイト数ずつしか返さないリーダー（チャンク境界の検証用）
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn config(terminator: &str) -> SqlOutputConfig {
+        SqlOutputConfig {
+            statement_terminator: terminator.to_string(),
+            ..SqlOutputConfig::default()
+        }
+    }
+
+    fn stream(content: &str, chunk: usize, config: &SqlOutputConfig) -> Vec<String> {
+        let reader = ChunkedReader {
+            data: content.as_bytes(),
+            chunk,
+        };
+        SqlStatementReader::new(reader, Path::new("up.sql"), config)
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    fn in_memory(content: &str, config: &SqlOutputConfig) -> Vec<String> {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("up.sql");
+        fs::write(&path, content).unwrap();
+        split_sql_statements(&read_sql_file(&path, config).unwrap())
+    }
+
+    const TRICKY_SQL: &str = "\u{feff}-- header; comment\r\n\
+        CREATE TABLE \"we;ird\" (id INTEGER, note TEXT DEFAULT 'it''s; fine');\r\n\
+        /* block; /* nested; */ still comment; */\r\n\
+        INSERT INTO `t` VALUES ('a;b', \"c\"\"d;\", 'ü;é');\r\
+        CREATE FUNCTION f() RETURNS trigger AS $body$ BEGIN x := 1; RETURN NEW; END; $body$ LANGUAGE plpgsql;\n\
+        SELECT $1, 'price: $5;';\n\
+        SELECT 1 -- trailing; comment\n\
+        ;\n\
+        -- only a comment;\n\
+        SELECT 2";
+
+    #[test]
+    fn test_streaming_matches_in_memory_for_every_chunk_size() {
+        let config = config(";");
+        let expected = in_memory(TRICKY_SQL, &config);
+        assert_eq!(expected.len(), 6, "{:?}", expected);
+
+        for chunk in 1..=TRICKY_SQL.len() {
+            assert_eq!(
+                stream(TRICKY_SQL, chunk, &config),
+                expected,
+                "chunk size {}",
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming_matches_in_memory_with_custom_terminator() {
+        let config = config("\nGO\n");
+        let content = "CREATE TABLE a (id INTEGER)\nGO\nINSERT INTO a VALUES (1); INSERT INTO a VALUES (2)\nGO\nSELECT 'GO'\nGO\n";
+        let expected = in_memory(content, &config);
+        assert_eq!(expected.len(), 4, "{:?}", expected);
+
+        for chunk in 1..=content.len() {
+            assert_eq!(stream(content, chunk, &config), expected, "chunk {}", chunk);
+        }
+    }
+
+    #[test]
+    fn test_streamed_checksum_matches_whole_file_checksum() {
+        let expected = SchemaChecksumService::new().calculate_sql_checksum(TRICKY_SQL);
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("up.sql");
+        fs::write(&path, TRICKY_SQL).unwrap();
+        assert_eq!(
+            calculate_sql_file_checksum(&path, &config(";")).unwrap(),
+            expected
+        );
+
+        for chunk in 1..=TRICKY_SQL.len() {
+            let mut reader = SqlStatementReader::new(
+                ChunkedReader {
+                    data: TRICKY_SQL.as_bytes(),
+                    chunk,
+                },
+                Path::new("up.sql"),
+                &config(";"),
+            );
+            assert_eq!(reader.checksum(), None);
+            for statement in reader.by_ref() {
+                statement.unwrap();
+            }
+            assert_eq!(
+                reader.checksum(),
+                Some(expected.as_str()),
+                "chunk {}",
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn test_streamed_checksum_ignores_statement_terminator() {
+        let canonical = "CREATE TABLE a (id INTEGER);\nINSERT INTO a VALUES ('x\nGO');\n";
+        let expected = SchemaChecksumService::new().calculate_sql_checksum(canonical);
+        let config = config("\nGO");
+        let rendered = render_sql_file(canonical, &config);
+        assert_ne!(rendered, canonical);
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("up.sql");
+        fs::write(&path, &rendered).unwrap();
+        assert_eq!(
+            calculate_sql_file_checksum(&path, &config).unwrap(),
+            expected
+        );
+        assert_eq!(
+            read_sql_statements(&path, &config, 1024)
+                .unwrap()
+                .checksum(),
+            Some(expected.as_str())
+        );
+
+        for chunk in 1..=rendered.len() {
+            let mut reader = SqlStatementReader::new(
+                ChunkedReader {
+                    data: rendered.as_bytes(),
+                    chunk,
+                },
+                Path::new("up.sql"),
+                &config,
+            );
+            for statement in reader.by_ref() {
+                statement.unwrap();
+            }
+            assert_eq!(
+                reader.checksum(),
+                Some(expected.as_str()),
+                "chunk {}",
+                chunk
+            );
+        }
+    }
+
+    #[test]
+    fn test_splitter_matches_split_for_every_split_point() {
+        let sql = "SELECT 'a''b'; SELECT \"x\"\"y\"; /* c */ SELECT $$ ; $$; SELECT 1 --;\n";
+        let expected = split_sql_statements_with_terminator(sql, ";");
+
+        for split_at in (0..=sql.len()).filter(|i| sql.is_char_boundary(*i)) {
+            let mut splitter = StatementSplitter::new(";");
+            let mut statements = splitter.push(&sql[..split_at]);
+            statements.extend(splitter.push(&sql[split_at..]));
+            statements.extend(splitter.finish());
+            assert_eq!(statements, expected, "split at {}", split_at);
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_reported() {
+        let data = b"SELECT 1;\nSELECT '\xff';\n";
+        let reader = ChunkedReader { data, chunk: 4 };
+        let results: Vec<Result<String>> =
+            SqlStatementReader::new(reader, Path::new("up.sql"), &config(";")).collect();
+
+        assert_eq!(results[0].as_ref().unwrap(), "SELECT 1");
+        let error = results.last().unwrap().as_ref().unwrap_err();
+        assert!(error.to_string().contains("valid UTF-8"), "{}", error);
+    }
+
+    #[test]
+    fn test_read_sql_statements_switches_on_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("up.sql");
+        fs::write(&path, "SELECT 1;\nSELECT 2;\n").unwrap();
+        let config = SqlOutputConfig::default();
+
+        let expected_checksum = calculate_sql_file_checksum(&path, &config).unwrap();
+
+        let statements = read_sql_statements(&path, &config, 1024).unwrap();
+        assert!(matches!(statements, SqlStatements::InMemory { .. }));
+        assert_eq!(statements.checksum(), Some(expected_checksum.as_str()));
+
+        let mut statements = read_sql_statements(&path, &config, 4).unwrap();
+        assert!(matches!(statements, SqlStatements::Streaming(_)));
+        let collected: Vec<String> = statements.by_ref().collect::<Result<_>>().unwrap();
+        assert_eq!(collected, vec!["SELECT 1", "SELECT 2"]);
+        assert_eq!(statements.checksum(), Some(expected_checksum.as_str()));
+    }
+
+    #[test]
+    fn test_streaming_large_file_keeps_memory_bounded() {
+        // 数十MBのINSERTを並べたファイル
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("up.sql");
+        let row = "INSERT INTO events (id, payload) VALUES (1, 'lorem ipsum; dolor sit amet, consectetur adipiscing elit');\n";
+        let rows = 24 * 1024 * 1024 / row.len();
+        {
+            let mut file = std::io::BufWriter::new(File::create(&path).unwrap());
+            for _ in 0..rows {
+                file.write_all(row.as_bytes()).unwrap();
+            }
+        }
+
+        let file = BufReader::new(File::open(&path).unwrap());
+        let mut reader = SqlStatementReader::new(file, &path, &SqlOutputConfig::default());
+        let mut count = 0;
+        for statement in reader.by_ref() {
+            assert!(statement.unwrap().starts_with("INSERT INTO events"));
+            count += 1;
+        }
+
+        assert_eq!(count, rows);
+        // 保持するのは1チャンクと未完了のステートメント程度
+        assert!(
+            reader.peak_buffered() <= READ_CHUNK_BYTES + row.len(),
+            "{}",
+            reader.peak_buffered()
+        );
+    }
+}
@@ -0,0 +1,266 @@
+// 生成後にまだ適用していないマイグレーションの記録
+//
+// generate は新しいマイグレーションのバージョンを、マイグレーションディレクトリ直下の
+// `.pending_migrations.yaml` に未適用として記録します。
+// apply はマイグレーションを適用した（または既に適用済みだと確認した）時点で記録から外し、
+// rollback はロールバックしたマイグレーションを記録に戻します。
+// generate は新しいマイグレーションを重ねる前にこの記録を確認し、未適用のものを一覧表示します。
+// - 判定はファイルだけで行い、データベースに接続しない
+// - 記録ファイルがない場合（このプロジェクトでまだ generate していない場合など）は何も報告しない
+// - 記録にあってもディレクトリが削除されたマイグレーションは報告しない
+// - 記録ファイルは作業中のマシンごとの状態なので、init が `.gitignore` に追加する。
+//   読み込めない・壊れた記録ファイル（マージの衝突など）は警告を出して空として扱い、
+//   次に記録を書き込むときに置き換える
+
+use super::migration_loader;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+/// 未適用のマイグレーションの記録ファイル名
+pub const PENDING_MIGRATIONS_FILE: &str = ".pending_migrations.yaml";
+
+/// 記録ファイルの内容
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PendingMigrationsRecord {
+    /// 生成後にまだ適用していないバージョン
+    #[serde(default)]
+    pending: BTreeSet<String>,
+}
+
+/// 未適用のマイグレーション
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PendingMigration {
+    /// バージョン
+    pub version: String,
+    /// 説明
+    pub description: String,
+}
+
+/// 記録を読み込む
+///
+/// 記録ファイルがない、または読み込めない場合は空の記録を返す。
+fn load_record(migrations_dir: &Path) -> PendingMigrationsRecord {
+    let path = migrations_dir.join(PENDING_MIGRATIONS_FILE);
+    if !path.exists() {
+        return PendingMigrationsRecord::default();
+    }
+
+    let record = fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| serde_saphyr::from_str(&content).map_err(anyhow::Error::from));
+    match record {
+        Ok(record) => record,
+        Err(e) => {
+            warn!(
+                "Ignoring unreadable pending migrations file {:?}: {}",
+                path, e
+            );
+            PendingMigrationsRecord::default()
+        }
+    }
+}
+
+/// 記録を保存する（未適用のものがなくなった場合はファイルを削除する）
+fn save_record(migrations_dir: &Path, record: &PendingMigrationsRecord) -> Result<()> {
+    let path = migrations_dir.join(PENDING_MIGRATIONS_FILE);
+    if record.pending.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove pending migrations: {:?}", path))?;
+        }
+        return Ok(());
+    }
+
+    let yaml = serde_saphyr::to_string(record)
+        .with_context(|| "Failed to serialize pending migrations")?;
+    fs::write(&path, yaml)
+        .with_context(|| format!("Failed to write pending migrations: {:?}", path))?;
+
+    Ok(())
+}
+
+/// 記録されている未適用のバージョンを読み込む
+///
+/// 記録ファイルが存在しない、または読み込めない場合は空のセットを返す。
+pub fn load_pending_versions(migrations_dir: &Path) -> Result<BTreeSet<String>> {
+    Ok(load_record(migrations_dir).pending)
+}
+
+/// マイグレーションを未適用として記録する
+///
+/// generate が新しいマイグレーションを書き出した後と、rollback がロールバックした後に呼び出す。
+pub fn mark_pending<'a>(
+    migrations_dir: &Path,
+    versions: impl IntoIterator<Item = &'a str>,
+) -> Result<()> {
+    let mut record = load_record(migrations_dir);
+    let before = record.pending.len();
+    record
+        .pending
+        .extend(versions.into_iter().map(str::to_string));
+    if record.pending.len() == before {
+        return Ok(());
+    }
+    save_record(migrations_dir, &record)
+}
+
+/// マイグレーションを適用済みとして記録から外す
+///
+/// 記録にないバージョンだけの場合はファイルに書き込まない。
+pub fn mark_applied<'a>(
+    migrations_dir: &Path,
+    versions: impl IntoIterator<Item = &'a str>,
+) -> Result<()> {
+    let mut record = load_record(migrations_dir);
+    let before = record.pending.len();
+    for version in versions {
+        record.pending.remove(version);
+    }
+    if record.pending.len() == before {
+        return Ok(());
+    }
+    save_record(migrations_dir, &record)
+}
+
+/// 記録に照らして未適用のマイグレーションを取得する
+///
+/// マイグレーションディレクトリや記録ファイルがない場合は空のリストを返す。
+pub fn find_pending_migrations(migrations_dir: &Path) -> Result<Vec<PendingMigration>> {
+    if !migrations_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let pending = load_pending_versions(migrations_dir)?;
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let migrations = migration_loader::load_available_migrations(migrations_dir)?;
+    Ok(migrations
+        .into_iter()
+        .filter(|(version, _, _)| pending.contains(version))
+        .map(|(version, description, _)| PendingMigration {
+            version,
+            description,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn add_migration(dir: &Path, name: &str) {
+        let migration_dir = dir.join(name);
+        fs::create_dir(&migration_dir).unwrap();
+        fs::write(migration_dir.join("up.sql"), "").unwrap();
+    }
+
+    #[test]
+    fn test_nothing_pending_without_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        add_migration(dir, "20260101000000_create_users");
+
+        assert!(find_pending_migrations(dir).unwrap().is_empty());
+        assert!(find_pending_migrations(&dir.join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_generated_migrations_are_pending_until_applied() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        add_migration(dir, "20260101000000_create_users");
+        add_migration(dir, "20260102000000_create_posts");
+        mark_pending(dir, ["20260101000000"]).unwrap();
+        mark_pending(dir, ["20260102000000"]).unwrap();
+
+        assert_eq!(
+            find_pending_migrations(dir).unwrap(),
+            vec![
+                PendingMigration {
+                    version: "20260101000000".to_string(),
+                    description: "create_users".to_string(),
+                },
+                PendingMigration {
+                    version: "20260102000000".to_string(),
+                    description: "create_posts".to_string(),
+                },
+            ]
+        );
+
+        mark_applied(dir, ["20260101000000"]).unwrap();
+        assert_eq!(
+            find_pending_migrations(dir).unwrap(),
+            vec![PendingMigration {
+                version: "20260102000000".to_string(),
+                description: "create_posts".to_string(),
+            }]
+        );
+
+        // すべて適用されると記録ファイルは削除される
+        mark_applied(dir, ["20260102000000"]).unwrap();
+        assert!(find_pending_migrations(dir).unwrap().is_empty());
+        assert!(!dir.join(PENDING_MIGRATIONS_FILE).exists());
+    }
+
+    #[test]
+    fn test_rolled_back_migration_is_pending_again() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        add_migration(dir, "20260101000000_create_users");
+
+        // 記録にないバージョンを適用しても記録ファイルは作らない
+        mark_applied(dir, ["20260101000000"]).unwrap();
+        assert!(!dir.join(PENDING_MIGRATIONS_FILE).exists());
+
+        mark_pending(dir, ["20260101000000"]).unwrap();
+        assert_eq!(
+            load_pending_versions(dir).unwrap(),
+            BTreeSet::from(["20260101000000".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_removed_migration_directories_are_not_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        add_migration(dir, "20260101000000_create_users");
+        mark_pending(dir, ["20260101000000", "20260102000000"]).unwrap();
+
+        assert_eq!(
+            find_pending_migrations(dir).unwrap(),
+            vec![PendingMigration {
+                version: "20260101000000".to_string(),
+                description: "create_users".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unreadable_record_is_treated_as_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        add_migration(dir, "20260101000000_create_users");
+        fs::write(
+            dir.join(PENDING_MIGRATIONS_FILE),
+            "<<<<<<< HEAD\npending:\n- '20260101000000'\n=======\n",
+        )
+        .unwrap();
+
+        assert!(find_pending_migrations(dir).unwrap().is_empty());
+
+        // 壊れた記録ファイルは次の書き込みで置き換えられる
+        mark_pending(dir, ["20260101000000"]).unwrap();
+        assert_eq!(
+            load_pending_versions(dir).unwrap(),
+            BTreeSet::from(["20260101000000".to_string()])
+        );
+    }
+}
@@ -9,10 +9,12 @@ use crate::core::config::Dialect;
 use crate::core::destructive_change_report::DestructiveChangeReport;
 use crate::core::error::ValidationResult;
 use crate::core::migration::MigrationMetadata;
+use crate::core::policy_report::PolicyReport;
 use crate::core::schema::Schema;
 use crate::core::schema_diff::SchemaDiff;
 use crate::services::migration_pipeline::MigrationPipeline;
 use chrono::Utc;
+use std::collections::BTreeMap;
 
 /// マイグレーションファイル生成サービス
 ///
@@ -142,24 +144,44 @@ impl MigrationGeneratorService {
     /// * `description` - マイグレーションの説明
     /// * `dialect` - データベース方言
     /// * `checksum` - チェックサム
+    /// * `up_sql_checksum` - up.sql の内容のチェックサム
+    /// * `statement_terminator` - up.sql / down.sql を書き出したときのステートメント終端文字列
+    /// * `destructive_changes` - 破壊的変更の検出結果
+    /// * `policy` - マイグレーションポリシーの評価結果
+    /// * `rollback_protected_tables` - ロールバックで削除されないテーブル
+    /// * `touched_tables` - マイグレーションが変更するテーブル（不明な場合は None）
+    /// * `metadata` - 外部ツール向けの任意のメタデータ（`--meta key=value`）
     ///
     /// # Returns
     ///
     /// YAML形式のメタデータ文字列
+    #[allow(clippy::too_many_arguments)]
     pub fn generate_migration_metadata(
         &self,
         version: &str,
         description: &str,
         dialect: Dialect,
         checksum: &str,
+        up_sql_checksum: Option<String>,
+        statement_terminator: Option<String>,
         destructive_changes: DestructiveChangeReport,
+        policy: Option<PolicyReport>,
+        rollback_protected_tables: Vec<String>,
+        touched_tables: Option<Vec<String>>,
+        metadata: BTreeMap<String, String>,
     ) -> Result<String> {
         let metadata = MigrationMetadata {
             version: version.to_string(),
             description: description.to_string(),
             dialect,
             checksum: checksum.to_string(),
+            up_sql_checksum,
+            statement_terminator,
             destructive_changes,
+            policy,
+            rollback_protected_tables,
+            touched_tables,
+            metadata,
         };
 
         serde_saphyr::to_string(&metadata)
@@ -274,20 +296,33 @@ impl crate::services::traits::MigrationGenerator for MigrationGeneratorService {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn generate_migration_metadata(
         &self,
         version: &str,
         description: &str,
         dialect: Dialect,
         checksum: &str,
+        up_sql_checksum: Option<String>,
+        statement_terminator: Option<String>,
         destructive_changes: DestructiveChangeReport,
+        policy: Option<PolicyReport>,
+        rollback_protected_tables: Vec<String>,
+        touched_tables: Option<Vec<String>>,
+        metadata: BTreeMap<String, String>,
     ) -> Result<String> {
         self.generate_migration_metadata(
             version,
             description,
             dialect,
             checksum,
+            up_sql_checksum,
+            statement_terminator,
             destructive_changes,
+            policy,
+            rollback_protected_tables,
+            touched_tables,
+            metadata,
         )
     }
 }
@@ -340,7 +375,13 @@ mod tests {
                 "create_users",
                 Dialect::PostgreSQL,
                 "abc123",
+                None,
+                None,
                 DestructiveChangeReport::new(),
+                None,
+                Vec::new(),
+                None,
+                BTreeMap::new(),
             )
             .expect("Failed to generate metadata");
 
@@ -350,6 +391,34 @@ mod tests {
         );
         assert!(metadata.contains("description: create_users"));
         assert!(metadata.contains("destructive_changes"));
+        assert!(!metadata.contains("metadata:"));
+    }
+
+    #[test]
+    fn test_generate_migration_metadata_with_metadata() {
+        let generator = MigrationGeneratorService::new();
+        let mut extra = BTreeMap::new();
+        extra.insert("team".to_string(), "payments".to_string());
+        extra.insert("ticket".to_string(), "PAY-123".to_string());
+
+        let yaml = generator
+            .generate_migration_metadata(
+                "20260122120000",
+                "create_users",
+                Dialect::PostgreSQL,
+                "abc123",
+                None,
+                None,
+                DestructiveChangeReport::new(),
+                None,
+                Vec::new(),
+                None,
+                extra.clone(),
+            )
+            .expect("Failed to generate metadata");
+
+        let parsed: MigrationMetadata = serde_saphyr::from_str(&yaml).unwrap();
+        assert_eq!(parsed.metadata, extra);
     }
 
     #[test]
@@ -359,6 +428,7 @@ mod tests {
         diff.added_enums.push(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let sql = generator
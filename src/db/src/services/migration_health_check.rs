@@ -0,0 +1,439 @@
+// 中断されたマイグレーションの検出と復旧
+//
+// apply / rollback の開始時に、前回の実行が中断された痕跡を検出します。
+// - PostgreSQL: トランザクションを開いたまま放置されたセッションが保持するロック
+//   （履歴テーブル・ACCESS EXCLUSIVE ロック・トランザクションレベルのアドバイザリロック）
+// - MySQL: 開いたままのトランザクションが保持する履歴テーブルのメタデータロック
+// - SQLite: 異常終了で残ったロールバックジャーナル（`-journal`）・WALファイル（`-wal`）
+//
+// `--break-stale-lock` 指定時は、安全に解放できるものに限り解放します。
+// セッションは解放直前にも放置状態であることを確認してから終了し、SQLiteは他のプロセスが
+// データベースを使用していないことを確認してから SQLite 自身の復旧処理を実行します
+// （ジャーナル・WALファイルを削除することはありません）。
+
+use crate::adapters::database_migrator::DEFAULT_MIGRATION_TABLE;
+use crate::core::config::Dialect;
+use anyhow::{anyhow, Context, Result};
+use sqlx::{AnyPool, Row};
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// 放置されたセッションとみなすまでの待機時間（秒）
+///
+/// 実行中のマイグレーションのセッションを誤って終了しないよう、この時間以上
+/// 何も実行していないセッションのみを対象とします。
+pub const STALE_SESSION_IDLE_SECONDS: i64 = 60;
+
+/// 中断されたマイグレーションの痕跡
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthIssue {
+    /// ロックを保持したまま放置されたセッション（PostgreSQL / MySQL）
+    StaleSession {
+        dialect: Dialect,
+        session: StaleSession,
+    },
+    /// 書き込みトランザクションの途中で残ったロールバックジャーナル（SQLite）
+    SqliteJournal { path: PathBuf },
+    /// チェックポイントされずに残ったWALファイル（SQLite）
+    SqliteWal { path: PathBuf },
+}
+
+/// ロックを保持したまま放置されたセッション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleSession {
+    /// PostgreSQL のバックエンドPID / MySQL の接続ID
+    pub id: i64,
+    /// 接続ユーザー
+    pub user: Option<String>,
+    /// 最後に実行を終えてからの経過秒数
+    pub idle_seconds: i64,
+    /// 保持しているロック（例: `AccessExclusiveLock on users`）
+    pub locks: String,
+}
+
+impl HealthIssue {
+    /// 検出内容の説明
+    pub fn description(&self) -> String {
+        match self {
+            HealthIssue::StaleSession { dialect, session } => {
+                let kind = match dialect {
+                    Dialect::MySQL => "Connection",
+                    _ => "Session",
+                };
+                format!(
+                    "{} {}{} has been idle in an open transaction for {}s, holding {}",
+                    kind,
+                    session.id,
+                    session
+                        .user
+                        .as_ref()
+                        .map(|user| format!(" ({})", user))
+                        .unwrap_or_default(),
+                    session.idle_seconds,
+                    session.locks
+                )
+            }
+            HealthIssue::SqliteJournal { path } => format!(
+                "Rollback journal {} was left by an interrupted write transaction",
+                path.display()
+            ),
+            HealthIssue::SqliteWal { path } => format!(
+                "Write-ahead log {} was not checkpointed; the database was not closed cleanly or is open in another process",
+                path.display()
+            ),
+        }
+    }
+
+    /// 対処方法
+    pub fn remediation(&self) -> String {
+        match self {
+            HealthIssue::StaleSession {
+                dialect: Dialect::MySQL,
+                session,
+            } => format!(
+                "Run `KILL {}` to roll back its transaction, or rerun with --break-stale-lock.",
+                session.id
+            ),
+            HealthIssue::StaleSession { session, .. } => format!(
+                "Run `SELECT pg_terminate_backend({})` to roll back its transaction, or rerun with --break-stale-lock.",
+                session.id
+            ),
+            HealthIssue::SqliteJournal { .. } => "Do not delete the journal: SQLite uses it to roll back the interrupted transaction when the database is next opened. Make sure no other process is using the database, then rerun with --break-stale-lock to roll it back now.".to_string(),
+            HealthIssue::SqliteWal { .. } => "Do not delete the WAL file: it may contain committed changes. Make sure no other process is using the database, then rerun with --break-stale-lock to checkpoint it into the database.".to_string(),
+        }
+    }
+
+    /// マイグレーションの実行を妨げるか
+    ///
+    /// 放置されたセッションのロックは apply / rollback を待たせ続けるため中断します。
+    /// SQLite のジャーナル・WALファイルは次に開いたときに SQLite が復旧するため警告に留めます。
+    pub fn is_blocking(&self) -> bool {
+        matches!(self, HealthIssue::StaleSession { .. })
+    }
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n  {}", self.description(), self.remediation())
+    }
+}
+
+/// 検出内容の一覧を整形（1件ごとに説明と対処方法）
+pub fn format_issues(issues: &[HealthIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("  - {}", issue.to_string().replace('\n', "\n  ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// SQLiteのデータベースファイルに残ったジャーナル・WALファイルを検出
+///
+/// データベースに接続すると SQLite が復旧を始めるため、接続前に呼び出します。
+/// 空のファイルは正常終了後にも残りうるため対象外とします。ジャーナルはヘッダーが
+/// 書き込まれる前（先頭が 0）であれば SQLite も無視するため対象外とします。
+pub fn find_sqlite_leftover_files(database: &Path) -> Vec<HealthIssue> {
+    let sibling = |suffix: &str| {
+        let mut path = database.as_os_str().to_os_string();
+        path.push(suffix);
+        PathBuf::from(path)
+    };
+    let non_empty = |path: &Path| fs::metadata(path).is_ok_and(|m| m.len() > 0);
+    let hot = |path: &Path| {
+        let mut first = [0u8; 1];
+        fs::File::open(path)
+            .and_then(|mut file| file.read_exact(&mut first))
+            .is_ok_and(|_| first[0] != 0)
+    };
+
+    let mut issues = Vec::new();
+    let journal = sibling("-journal");
+    if hot(&journal) {
+        issues.push(HealthIssue::SqliteJournal { path: journal });
+    }
+    let wal = sibling("-wal");
+    if non_empty(&wal) {
+        issues.push(HealthIssue::SqliteWal { path: wal });
+    }
+    issues
+}
+
+/// ロックを保持したまま放置されたセッションを検出（PostgreSQL / MySQL）
+///
+/// 権限不足などでロックの情報を参照できない場合は検出を省略します。
+pub async fn find_stale_sessions(pool: &AnyPool, dialect: Dialect) -> Result<Vec<HealthIssue>> {
+    let sql = match dialect {
+        Dialect::PostgreSQL => postgres_stale_sessions_sql(),
+        Dialect::MySQL => mysql_stale_sessions_sql(),
+        Dialect::SQLite => return Ok(Vec::new()),
+    };
+
+    let rows = match sqlx::query(&sql).fetch_all(pool).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            debug!(error = %e, "Skipping stale session check: lock information is not available");
+            return Ok(Vec::new());
+        }
+    };
+
+    rows.iter()
+        .map(|row| {
+            let id = match dialect {
+                Dialect::PostgreSQL => i64::from(row.try_get::<i32, _>("id")?),
+                _ => row.try_get::<i64, _>("id")?,
+            };
+            Ok(HealthIssue::StaleSession {
+                dialect,
+                session: StaleSession {
+                    id,
+                    user: row.try_get::<Option<String>, _>("user_name")?,
+                    idle_seconds: row.try_get::<i64, _>("idle_seconds")?,
+                    locks: row.try_get::<String, _>("locks")?,
+                },
+            })
+        })
+        .collect::<std::result::Result<Vec<_>, sqlx::Error>>()
+        .with_context(|| "Failed to read stale session information")
+}
+
+fn postgres_stale_sessions_sql() -> String {
+    format!(
+        r#"SELECT a.pid AS id,
+       a.usename::text AS user_name,
+       EXTRACT(EPOCH FROM (now() - a.state_change))::bigint AS idle_seconds,
+       string_agg(DISTINCT l.mode || ' on ' || COALESCE(l.relation::regclass::text, l.locktype), ', ') AS locks
+FROM pg_locks l
+JOIN pg_stat_activity a ON a.pid = l.pid
+WHERE l.granted
+  AND a.pid <> pg_backend_pid()
+  AND a.datname = current_database()
+  AND {}
+  AND (l.locktype = 'advisory' OR l.mode = 'AccessExclusiveLock' OR l.relation = to_regclass('{}'))
+GROUP BY a.pid, a.usename, a.state_change
+ORDER BY a.pid"#,
+        postgres_idle_condition(),
+        DEFAULT_MIGRATION_TABLE
+    )
+}
+
+/// トランザクションを開いたまま一定時間以上放置されているセッションの条件
+fn postgres_idle_condition() -> String {
+    format!(
+        "a.state IN ('idle in transaction', 'idle in transaction (aborted)') AND a.state_change < now() - interval '{} seconds'",
+        STALE_SESSION_IDLE_SECONDS
+    )
+}
+
+fn mysql_stale_sessions_sql() -> String {
+    format!(
+        r#"SELECT CAST(t.PROCESSLIST_ID AS SIGNED) AS id,
+       CAST(t.PROCESSLIST_USER AS CHAR) AS user_name,
+       CAST(t.PROCESSLIST_TIME AS SIGNED) AS idle_seconds,
+       CAST(GROUP_CONCAT(DISTINCT CONCAT(ml.LOCK_TYPE, ' on ', ml.OBJECT_NAME) SEPARATOR ', ') AS CHAR) AS locks
+FROM performance_schema.metadata_locks ml
+JOIN performance_schema.threads t ON t.THREAD_ID = ml.OWNER_THREAD_ID
+WHERE ml.OBJECT_TYPE = 'TABLE'
+  AND ml.OBJECT_SCHEMA = DATABASE()
+  AND ml.OBJECT_NAME = '{}'
+  AND ml.LOCK_STATUS = 'GRANTED'
+  AND ml.LOCK_DURATION = 'TRANSACTION'
+  AND {}
+GROUP BY t.PROCESSLIST_ID, t.PROCESSLIST_USER, t.PROCESSLIST_TIME
+ORDER BY t.PROCESSLIST_ID"#,
+        DEFAULT_MIGRATION_TABLE,
+        mysql_idle_condition()
+    )
+}
+
+/// 何も実行せずに一定時間以上経過している接続の条件
+fn mysql_idle_condition() -> String {
+    format!(
+        "t.PROCESSLIST_ID <> CONNECTION_ID() AND t.PROCESSLIST_COMMAND = 'Sleep' AND t.PROCESSLIST_TIME >= {}",
+        STALE_SESSION_IDLE_SECONDS
+    )
+}
+
+/// 検出した痕跡を解放し、行った処理を返す（`--break-stale-lock`）
+///
+/// セッションは終了する直前にも放置状態であることを確認し、その間に実行を再開した
+/// セッションは終了しません。SQLite は [`recover_sqlite`] で復旧します。
+pub async fn release(pool: &AnyPool, issues: &[HealthIssue]) -> Result<Vec<String>> {
+    let mut released = Vec::new();
+    for issue in issues {
+        if let HealthIssue::StaleSession { dialect, session } = issue {
+            if terminate_session(pool, *dialect, session.id).await? {
+                released.push(format!(
+                    "Terminated idle session {}; its open transaction was rolled back.",
+                    session.id
+                ));
+            } else {
+                released.push(format!(
+                    "Session {} is no longer idle and was left running.",
+                    session.id
+                ));
+            }
+        }
+    }
+
+    if issues.iter().any(|issue| {
+        matches!(
+            issue,
+            HealthIssue::SqliteJournal { .. } | HealthIssue::SqliteWal { .. }
+        )
+    }) {
+        recover_sqlite(pool).await?;
+        released.push(
+            "Recovered the SQLite database: rolled back the interrupted transaction and checkpointed the write-ahead log."
+                .to_string(),
+        );
+    }
+
+    Ok(released)
+}
+
+/// 放置状態のままであればセッションを終了（終了した場合は true）
+async fn terminate_session(pool: &AnyPool, dialect: Dialect, id: i64) -> Result<bool> {
+    match dialect {
+        Dialect::PostgreSQL => {
+            let sql = format!(
+                "SELECT pg_terminate_backend(a.pid) FROM pg_stat_activity a WHERE a.pid = {} AND {}",
+                id,
+                postgres_idle_condition()
+            );
+            let terminated: Option<bool> = sqlx::query_scalar(&sql)
+                .fetch_optional(pool)
+                .await
+                .with_context(|| format!("Failed to terminate session {}", id))?;
+            Ok(terminated.unwrap_or(false))
+        }
+        Dialect::MySQL => {
+            let sql = format!(
+                "SELECT COUNT(*) FROM performance_schema.threads t WHERE t.PROCESSLIST_ID = {} AND {}",
+                id,
+                mysql_idle_condition()
+            );
+            let idle: i64 = sqlx::query_scalar(&sql)
+                .fetch_one(pool)
+                .await
+                .with_context(|| format!("Failed to check connection {}", id))?;
+            if idle == 0 {
+                return Ok(false);
+            }
+            sqlx::query(&format!("KILL {}", id))
+                .execute(pool)
+                .await
+                .with_context(|| format!("Failed to terminate connection {}", id))?;
+            Ok(true)
+        }
+        Dialect::SQLite => Ok(false),
+    }
+}
+
+/// SQLiteのデータベースを復旧
+///
+/// 書き込みロックを取得して中断されたトランザクションを SQLite にロールバックさせ、
+/// WALファイルの内容をデータベースに書き戻します。他のプロセスが書き込み中・読み込み中の
+/// 場合は何も変更せずにエラーを返します。
+pub async fn recover_sqlite(pool: &AnyPool) -> Result<()> {
+    let in_use = || {
+        anyhow!(
+            "The SQLite database is in use by another process. Stop that process before using --break-stale-lock."
+        )
+    };
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .with_context(|| "Failed to connect to the SQLite database")?;
+    let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+        .fetch_one(&mut *conn)
+        .await?;
+    sqlx::raw_sql("PRAGMA busy_timeout = 0")
+        .execute(&mut *conn)
+        .await?;
+
+    // 書き込みロックを取得できれば、ホットジャーナルは SQLite によりロールバックされている
+    let locked = sqlx::raw_sql("BEGIN IMMEDIATE").execute(&mut *conn).await;
+    if locked.is_ok() {
+        sqlx::raw_sql("ROLLBACK").execute(&mut *conn).await?;
+    }
+    let checkpoint = match locked {
+        Ok(_) => sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .fetch_one(&mut *conn)
+            .await
+            .map(|row| row.try_get::<i64, _>(0).unwrap_or(1)),
+        Err(e) => Err(e),
+    };
+    sqlx::raw_sql(&format!("PRAGMA busy_timeout = {}", busy_timeout))
+        .execute(&mut *conn)
+        .await?;
+
+    match checkpoint {
+        // 1列目は他の接続が読み込み中でチェックポイントを完了できなかった場合に 1
+        Ok(0) => Ok(()),
+        Ok(_) => Err(in_use()),
+        Err(e) => Err(anyhow::Error::new(e).context(in_use())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_sqlite_leftover_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let database = temp_dir.path().join("app.db");
+        fs::write(&database, b"").unwrap();
+        assert!(find_sqlite_leftover_files(&database).is_empty());
+
+        // 正常終了後に残る空のファイル・ヘッダーが書き込まれる前のジャーナルは対象外
+        fs::write(temp_dir.path().join("app.db-journal"), [0u8; 8]).unwrap();
+        fs::write(temp_dir.path().join("app.db-wal"), b"").unwrap();
+        assert!(find_sqlite_leftover_files(&database).is_empty());
+
+        fs::write(temp_dir.path().join("app.db-journal"), b"journal").unwrap();
+        fs::write(temp_dir.path().join("app.db-wal"), b"wal").unwrap();
+        let issues = find_sqlite_leftover_files(&database);
+        assert_eq!(
+            issues,
+            vec![
+                HealthIssue::SqliteJournal {
+                    path: temp_dir.path().join("app.db-journal")
+                },
+                HealthIssue::SqliteWal {
+                    path: temp_dir.path().join("app.db-wal")
+                },
+            ]
+        );
+        assert!(!issues.iter().any(HealthIssue::is_blocking));
+        assert!(issues[0].remediation().contains("--break-stale-lock"));
+    }
+
+    #[test]
+    fn test_format_stale_session() {
+        let issue = |dialect| HealthIssue::StaleSession {
+            dialect,
+            session: StaleSession {
+                id: 4242,
+                user: Some("deploy".to_string()),
+                idle_seconds: 900,
+                locks: "AccessExclusiveLock on users".to_string(),
+            },
+        };
+
+        let postgres = issue(Dialect::PostgreSQL);
+        assert!(postgres.is_blocking());
+        assert_eq!(
+            format_issues(&[postgres]),
+            "  - Session 4242 (deploy) has been idle in an open transaction for 900s, holding AccessExclusiveLock on users\n    Run `SELECT pg_terminate_backend(4242)` to roll back its transaction, or rerun with --break-stale-lock."
+        );
+        assert!(issue(Dialect::MySQL)
+            .remediation()
+            .starts_with("Run `KILL 4242`"));
+    }
+}
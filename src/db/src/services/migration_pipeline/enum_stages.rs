@@ -21,12 +21,20 @@ impl<'a> MigrationPipeline<'a> {
                 .diff
                 .modified_enums
                 .iter()
-                .any(|e| matches!(e.change_kind, EnumChangeKind::Recreate)))
+                .any(|e| e.change_kind.requires_recreate()))
             && !self.allow_destructive
         {
             return Err(PipelineStageError::EnumRecreationNotAllowed);
         }
 
+        // ENUMリネーム（以降の文はすべて新しい型名を参照するため最初に実行）
+        for renamed_enum in &self.diff.renamed_enums {
+            statements.extend(
+                generator
+                    .generate_rename_enum_type(&renamed_enum.old_name, &renamed_enum.new_enum.name),
+            );
+        }
+
         // 新規ENUM作成
         for enum_def in &self.diff.added_enums {
             statements.extend(generator.generate_create_enum_type(enum_def));
@@ -45,7 +53,7 @@ impl<'a> MigrationPipeline<'a> {
         Ok(statements)
     }
 
-    /// ステージ: enum_statements (post-table) - ENUM再作成
+    /// ステージ: enum_statements (post-table) - ENUM再作成（並び替えを含む）
     pub(super) fn stage_enum_post_table(
         &self,
         generator: &dyn SqlGenerator,
@@ -53,7 +61,7 @@ impl<'a> MigrationPipeline<'a> {
         let mut statements = Vec::new();
 
         for enum_diff in &self.diff.modified_enums {
-            if matches!(enum_diff.change_kind, EnumChangeKind::Recreate) {
+            if enum_diff.change_kind.requires_recreate() {
                 statements.extend(generator.generate_recreate_enum_type(enum_diff));
             }
         }
@@ -66,8 +74,9 @@ impl<'a> MigrationPipeline<'a> {
 mod tests {
     use super::*;
     use crate::core::config::Dialect;
-    use crate::core::schema::EnumDefinition;
+    use crate::core::schema::{Column, ColumnType, EnumDefinition, Schema, Table};
     use crate::core::schema_diff::{EnumColumnRef, EnumDiff, SchemaDiff};
+    use crate::services::schema_diff_detector::SchemaDiffDetectorService;
 
     #[test]
     fn test_pipeline_enum_create() {
@@ -75,6 +84,7 @@ mod tests {
         diff.added_enums.push(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
@@ -153,4 +163,157 @@ mod tests {
         assert!(sql.contains(r#"CREATE TYPE "status" AS ENUM ('inactive', 'active')"#));
         assert!(sql.contains(r#"DROP TYPE "status_old""#));
     }
+
+    #[test]
+    fn test_pipeline_enum_reorder_requires_opt_in() {
+        let mut diff = SchemaDiff::new();
+        diff.modified_enums.push(EnumDiff {
+            enum_name: "status".to_string(),
+            old_values: vec!["active".to_string(), "inactive".to_string()],
+            new_values: vec!["inactive".to_string(), "active".to_string()],
+            added_values: Vec::new(),
+            removed_values: Vec::new(),
+            change_kind: EnumChangeKind::ReorderOnly,
+            columns: Vec::new(),
+        });
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let result = pipeline.generate_up();
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert_eq!(err.stage(), "enum_statements");
+    }
+
+    #[test]
+    fn test_pipeline_enum_reorder_recreates_type_with_opt_in() {
+        let mut diff = SchemaDiff::new();
+        diff.modified_enums.push(EnumDiff {
+            enum_name: "status".to_string(),
+            old_values: vec!["active".to_string(), "inactive".to_string()],
+            new_values: vec!["inactive".to_string(), "active".to_string()],
+            added_values: Vec::new(),
+            removed_values: Vec::new(),
+            change_kind: EnumChangeKind::ReorderOnly,
+            columns: vec![EnumColumnRef {
+                table_name: "users".to_string(),
+                column_name: "status".to_string(),
+            }],
+        });
+
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::PostgreSQL).with_allow_destructive(true);
+        let result = pipeline.generate_up();
+
+        assert!(result.is_ok());
+        let (sql, _) = result.unwrap();
+        assert!(sql.contains(r#"ALTER TYPE "status" RENAME TO "status_old""#));
+        assert!(sql.contains(r#"CREATE TYPE "status" AS ENUM ('inactive', 'active')"#));
+        assert!(sql.contains(r#"DROP TYPE "status_old""#));
+    }
+
+    /// state → user_status のENUMリネームと、それを参照するテーブルの変更を含むスキーマの組
+    fn enum_rename_schemas() -> (Schema, Schema) {
+        let enum_column = |name: &str, enum_name: &str| {
+            Column::new(
+                name.to_string(),
+                ColumnType::Enum {
+                    name: enum_name.to_string(),
+                },
+                true,
+            )
+        };
+        let table = |name: &str, columns: Vec<Column>| {
+            let mut table = Table::new(name.to_string());
+            table.add_column(Column::new(
+                "id".to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+            for column in columns {
+                table.add_column(column);
+            }
+            table
+        };
+
+        let mut old_schema = Schema::new("1.0".to_string());
+        old_schema.add_enum(EnumDefinition {
+            name: "state".to_string(),
+            values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
+        });
+        old_schema.add_table(table("users", vec![enum_column("status", "state")]));
+        old_schema.add_table(table("legacy", vec![enum_column("state", "state")]));
+
+        let mut new_schema = Schema::new("1.0".to_string());
+        new_schema.add_enum(EnumDefinition {
+            name: "user_status".to_string(),
+            values: vec![
+                "active".to_string(),
+                "inactive".to_string(),
+                "banned".to_string(),
+            ],
+            renamed_from: Some("state".to_string()),
+        });
+        new_schema.add_table(table(
+            "users",
+            vec![
+                enum_column("status", "user_status"),
+                enum_column("previous_status", "user_status"),
+            ],
+        ));
+
+        (old_schema, new_schema)
+    }
+
+    /// 文の位置（見つからない場合はパニック）
+    fn position(sql: &str, statement: &str) -> usize {
+        sql.find(statement)
+            .unwrap_or_else(|| panic!("{} not found in:\n{}", statement, sql))
+    }
+
+    #[test]
+    fn test_pipeline_enum_rename_runs_before_dependent_statements() {
+        let (old_schema, new_schema) = enum_rename_schemas();
+        let diff = SchemaDiffDetectorService::new().detect_diff(&old_schema, &new_schema);
+        assert_eq!(diff.renamed_enums.len(), 1);
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
+            .with_schemas(&old_schema, &new_schema)
+            .with_allow_destructive(true);
+        let (up, _) = pipeline.generate_up().unwrap();
+
+        let rename = position(&up, r#"ALTER TYPE "state" RENAME TO "user_status""#);
+        let add_value = position(&up, r#"ALTER TYPE "user_status" ADD VALUE 'banned'"#);
+        let add_column = position(
+            &up,
+            r#"ALTER TABLE "users" ADD COLUMN "previous_status" "user_status""#,
+        );
+        let drop_table = position(&up, r#"DROP TABLE "legacy""#);
+        assert!(rename < add_value && add_value < add_column && add_column < drop_table);
+        // リネームだけで型名が変わったカラムは変更しない
+        assert!(!up.contains(r#"ALTER COLUMN "status" TYPE"#), "{}", up);
+    }
+
+    #[test]
+    fn test_pipeline_enum_rename_down_restores_name_between_table_reversals() {
+        let (old_schema, new_schema) = enum_rename_schemas();
+        let diff = SchemaDiffDetectorService::new().detect_diff(&old_schema, &new_schema);
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
+            .with_schemas(&old_schema, &new_schema)
+            .with_allow_destructive(true);
+        let (down, _) = pipeline.generate_down().unwrap();
+
+        // 追加カラムの削除 → 型名を戻す → 変更前のスキーマから旧型名でテーブルを再作成
+        let drop_column = position(
+            &down,
+            r#"ALTER TABLE "users" DROP COLUMN "previous_status""#,
+        );
+        let rename = position(&down, r#"ALTER TYPE "user_status" RENAME TO "state""#);
+        let create_table = position(&down, r#"CREATE TABLE "legacy""#);
+        assert!(drop_column < rename && rename < create_table, "{}", down);
+        assert!(down.contains(r#""state" "state""#), "{}", down);
+        assert!(!down.contains(r#"ALTER COLUMN "status" TYPE"#), "{}", down);
+    }
 }
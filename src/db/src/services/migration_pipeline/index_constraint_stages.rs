@@ -2,7 +2,7 @@
 //
 // インデックス作成、制約追加、テーブル/型の削除を処理するステージ。
 
-use crate::adapters::sql_generator::SqlGenerator;
+use crate::adapters::sql_generator::{MigrationDirection, SqlGenerator};
 use crate::core::config::Dialect;
 
 use super::{MigrationPipeline, PipelineStageError};
@@ -12,11 +12,22 @@ impl<'a> MigrationPipeline<'a> {
     ///
     /// 追加されたインデックスのCREATE INDEX文と、
     /// 削除されたインデックスのDROP INDEX文を生成します。
-    /// 変更されたインデックスはDROP後にCREATEします。
+    /// 変更されたインデックスはDROP後にCREATEし、
+    /// リネームされたインデックスは方言のリネーム構文で名前のみ変更します。
     pub(super) fn stage_index_statements(&self, generator: &dyn SqlGenerator) -> Vec<String> {
         let mut statements = Vec::new();
 
         for table_diff in &self.diff.modified_tables {
+            // リネームされたインデックス（再構築を伴わない）
+            for renamed_index in &table_diff.renamed_indexes {
+                let table = crate::core::schema::Table::new(table_diff.table_name.clone());
+                statements.extend(generator.generate_rename_index(
+                    &table,
+                    renamed_index,
+                    MigrationDirection::Up,
+                ));
+            }
+
             // 削除されたインデックスのDROP INDEX
             for index in &table_diff.removed_indexes {
                 statements.push(generator.generate_drop_index(&table_diff.table_name, index));
@@ -57,7 +68,7 @@ impl<'a> MigrationPipeline<'a> {
                     .any(|cd| self.has_nullable_or_default_change(cd));
 
                 if has_constraint_changes || has_nullable_or_default_changes {
-                    // カラム型変更がある場合はステージ3で再作成済み → スキップ
+                    // カラム型変更・NOT NULL カラム追加がある場合はステージ3で再作成済み → スキップ
                     let has_type_change = table_diff
                         .modified_columns
                         .iter()
@@ -67,14 +78,17 @@ impl<'a> MigrationPipeline<'a> {
                         .iter()
                         .any(|rc| self.has_type_change_in_renamed(rc));
 
-                    if !has_type_change && !has_renamed_type_change {
+                    if !has_type_change
+                        && !has_renamed_type_change
+                        && !self.sqlite_recreates_for_backfill(table_diff)
+                    {
                         // テーブル再作成で制約変更を適用
                         if let Some(new_schema) = self.new_schema {
                             if let Some(new_table) = new_schema.tables.get(&table_diff.table_name) {
                                 let old_table = self
                                     .old_schema
                                     .and_then(|s| s.tables.get(&table_diff.table_name));
-                                let recreator = crate::adapters::sql_generator::sqlite_table_recreator::SqliteTableRecreator::new();
+                                let recreator = self.get_sqlite_table_recreator();
                                 let recreation_stmts = recreator
                                     .generate_table_recreation_with_old_table(new_table, old_table);
                                 statements.extend(recreation_stmts);
@@ -139,6 +153,7 @@ mod tests {
     use super::*;
     use crate::core::schema::Constraint;
     use crate::core::schema_diff::{SchemaDiff, TableDiff};
+    use std::collections::BTreeMap;
 
     // ==========================================
     // 外部キー制約追加のテスト
@@ -1101,8 +1116,12 @@ mod tests {
         // 追加（完全なIndex構造体）
         table_diff.added_indexes.push(Index {
             name: "idx_users_new".to_string(),
-            columns: vec!["new_column".to_string()],
+            columns: vec!["new_column".into()],
             unique: true,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         });
         diff.modified_tables.push(table_diff);
 
@@ -1139,13 +1158,21 @@ mod tests {
             index_name: "idx_users_email".to_string(),
             old_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string()],
+                columns: vec!["email".into()],
                 unique: false,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
             new_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string(), "name".to_string()],
+                columns: vec!["email".into(), "name".into()],
                 unique: true,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
         });
         diff.modified_tables.push(table_diff);
@@ -1187,13 +1214,21 @@ mod tests {
             index_name: "idx_users_email".to_string(),
             old_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string()],
+                columns: vec!["email".into()],
                 unique: false,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
             new_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string()],
-                unique: true, // unique に変更
+                columns: vec!["email".into()],
+                unique: true, // unique に変更,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
         });
         diff.modified_tables.push(table_diff);
@@ -1226,13 +1261,21 @@ mod tests {
             index_name: "idx_users_email".to_string(),
             old_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string()],
+                columns: vec!["email".into()],
                 unique: false,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
             new_index: Index {
                 name: "idx_users_email".to_string(),
-                columns: vec!["email".to_string(), "name".to_string()],
+                columns: vec!["email".into(), "name".into()],
                 unique: false,
+                index_type: None,
+                where_clause: None,
+                renamed_from: None,
+                metadata: BTreeMap::new(),
             },
         });
         diff.modified_tables.push(table_diff);
@@ -1253,4 +1296,87 @@ mod tests {
             sql
         );
     }
+
+    // ==========================================
+    // インデックスリネームのテスト
+    // ==========================================
+
+    fn renamed_index_diff() -> SchemaDiff {
+        use crate::core::schema::Index;
+        use crate::core::schema_diff::RenamedIndex;
+
+        let old_index = Index::new("idx_email".to_string(), vec!["email".to_string()], true);
+        let mut new_index = old_index.clone();
+        new_index.name = "users_email_key".to_string();
+
+        let mut diff = SchemaDiff::new();
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.renamed_indexes.push(RenamedIndex {
+            old_name: "idx_email".to_string(),
+            old_index,
+            new_index,
+        });
+        diff.modified_tables.push(table_diff);
+        diff
+    }
+
+    #[test]
+    fn test_pipeline_rename_index_postgres() {
+        let diff = renamed_index_diff();
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql.contains(r#"ALTER INDEX "idx_email" RENAME TO "users_email_key""#),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("DROP INDEX"));
+        assert!(!up_sql.contains("CREATE"));
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains(r#"ALTER INDEX "users_email_key" RENAME TO "idx_email""#),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_rename_index_mysql() {
+        let diff = renamed_index_diff();
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql.contains("ALTER TABLE `users` RENAME INDEX `idx_email` TO `users_email_key`"),
+            "{}",
+            up_sql
+        );
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains("ALTER TABLE `users` RENAME INDEX `users_email_key` TO `idx_email`"),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_rename_index_sqlite_recreates() {
+        let diff = renamed_index_diff();
+        let pipeline = MigrationPipeline::new(&diff, Dialect::SQLite);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(up_sql.contains("-- NOTE: Index rename is not supported"));
+        let drop_pos = up_sql.find(r#"DROP INDEX "idx_email""#).unwrap();
+        let create_pos = up_sql
+            .find(r#"CREATE UNIQUE INDEX "users_email_key" ON "users" ("email")"#)
+            .unwrap();
+        assert!(drop_pos < create_pos, "{}", up_sql);
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(down_sql.contains(r#"DROP INDEX "users_email_key""#));
+        assert!(down_sql.contains(r#"CREATE UNIQUE INDEX "idx_email" ON "users" ("email")"#));
+    }
 }
@@ -10,11 +10,13 @@ mod table_stages;
 use crate::adapters::sql_generator::mysql::MysqlSqlGenerator;
 use crate::adapters::sql_generator::postgres::PostgresSqlGenerator;
 use crate::adapters::sql_generator::sqlite::SqliteSqlGenerator;
+use crate::adapters::sql_generator::sqlite_table_recreator::SqliteTableRecreator;
 use crate::adapters::sql_generator::{MigrationDirection, SqlGenerator};
 use crate::core::config::Dialect;
 use crate::core::error::ValidationResult;
-use crate::core::schema::Schema;
-use crate::core::schema_diff::{ColumnChange, SchemaDiff};
+use crate::core::schema::{Column, Schema};
+use crate::core::schema_diff::{ColumnChange, SchemaDiff, TableDiff};
+use table_stages::protected_table_notice;
 use thiserror::Error;
 
 /// パイプラインステージでのエラー
@@ -59,8 +61,9 @@ impl PipelineStageError {
 /// 3. table_statements - CREATE/ALTER TABLE
 /// 4. index_statements - CREATE INDEX
 /// 5. constraint_statements - 制約追加
-/// 6. cleanup_statements - DROP TABLE/TYPE
-/// 7. finalize - SQL結合
+/// 6. populate_statements - populate_from によるデータ投入
+/// 7. cleanup_statements - DROP TABLE/TYPE
+/// 8. finalize - SQL結合
 pub struct MigrationPipeline<'a> {
     diff: &'a SchemaDiff,
     old_schema: Option<&'a Schema>,
@@ -147,6 +150,10 @@ impl<'a> MigrationPipeline<'a> {
             statements.extend(enum_post_stmts);
         }
 
+        // populate_statements - INSERT INTO ... SELECT（インデックス作成後）
+        let populate_stmts = self.stage_populate_statements(&*generator)?;
+        statements.extend(populate_stmts);
+
         // ステージ6: cleanup_statements - DROP TABLE/TYPE
         let cleanup_stmts = self.stage_cleanup_statements(&*generator)?;
         statements.extend(cleanup_stmts);
@@ -174,12 +181,8 @@ impl<'a> MigrationPipeline<'a> {
         let mut statements = Vec::new();
 
         // ENUM操作の逆処理（PostgreSQL）
+        // 追加されたENUMの削除は、それを使うカラム・テーブルを削除した後に行う
         if matches!(self.dialect, Dialect::PostgreSQL) {
-            // 追加されたENUMを削除
-            for enum_def in &self.diff.added_enums {
-                statements.extend(generator.generate_drop_enum_type(&enum_def.name));
-            }
-
             // 変更されたENUMの逆処理（手動対応が必要）
             for enum_diff in &self.diff.modified_enums {
                 statements.push(format!(
@@ -213,14 +216,48 @@ impl<'a> MigrationPipeline<'a> {
         statements.extend(view_drop_stmts);
 
         // 追加されたテーブルを削除（依存関係の逆順）
-        let sorted_tables = self.diff.sort_added_tables_by_dependency().map_err(|e| {
-            PipelineStageError::CircularDependency {
-                message: e.to_string(),
+        let order = self.added_table_order()?;
+
+        // 循環参照がある場合は、テーブル間の外部キーを先に削除する
+        if order.has_cycle() {
+            for table in &order.tables {
+                for constraint in &table.constraints {
+                    if matches!(
+                        constraint,
+                        crate::core::schema::Constraint::FOREIGN_KEY { .. }
+                    ) {
+                        let sql = generator
+                            .generate_drop_constraint_for_existing_table(&table.name, constraint);
+                        if !sql.is_empty() {
+                            statements.push(sql);
+                        }
+                    }
+                }
             }
-        })?;
+        }
 
-        for table in sorted_tables.iter().rev() {
-            statements.push(generator.generate_drop_table(&table.name));
+        // ロールバック保護テーブルが削除対象のテーブルを参照している場合、外部キーを先に外す
+        if !order.has_cycle() {
+            statements
+                .extend(self.protected_table_foreign_key_statements(&*generator, &order.tables));
+        }
+
+        for table in order.tables.iter().rev() {
+            if table.rollback_protection {
+                statements.push(protected_table_notice(&table.name));
+                continue;
+            }
+
+            let drop_table = generator.generate_drop_table(&table.name);
+            if table.populate_from.is_some() {
+                // 投入データはテーブル削除で消えるため、逆操作は不要
+                statements.push(format!(
+                    "-- Data populated from populate_from for '{}' is removed with the table\n{}",
+                    table.name, drop_table
+                ));
+            } else {
+                statements.push(drop_table);
+            }
         }
 
         // 変更されたテーブルの処理（逆操作）
@@ -265,6 +302,18 @@ impl<'a> MigrationPipeline<'a> {
                 }
             }
 
+            // 生成カラム定義変更の逆処理（削除して旧定義で再追加）
+            for column_diff in &table_diff.modified_columns {
+                if self.regenerates_column(&column_diff.changes) {
+                    statements.extend(Self::regenerate_column_statements(
+                        &*generator,
+                        &table_diff.table_name,
+                        &column_diff.new_column.name,
+                        &column_diff.old_column,
+                    ));
+                }
+            }
+
             // nullable/default変更の逆処理（型変更がないカラム、SQLite以外）
             if !matches!(self.dialect, Dialect::SQLite) {
                 for column_diff in &table_diff.modified_columns {
@@ -282,7 +331,9 @@ impl<'a> MigrationPipeline<'a> {
                                         *old_nullable,
                                     ));
                                 }
-                                ColumnChange::DefaultValueChanged { old_default, .. } => {
+                                ColumnChange::DefaultValueChanged { old_default, .. }
+                                    if !self.default_change_covered_by_modify(column_diff) =>
+                                {
                                     statements.extend(generator.generate_alter_column_default(
                                         &table_diff.table_name,
                                         target_column,
@@ -322,6 +373,18 @@ impl<'a> MigrationPipeline<'a> {
                             statements.extend(alter_statements);
                         }
 
+                        // 生成カラム定義の変更も、リネームの逆より先に旧定義へ戻す
+                        if self.regenerates_column(&renamed_column.changes) {
+                            let mut restored = renamed_column.old_column.clone();
+                            restored.name = renamed_column.new_column.name.clone();
+                            statements.extend(Self::regenerate_column_statements(
+                                &*generator,
+                                &table_diff.table_name,
+                                &renamed_column.new_column.name,
+                                &restored,
+                            ));
+                        }
+
                         // リネームの逆（new_name → old_name）
                         let rename_statements = generator.generate_rename_column(
                             table,
@@ -338,6 +401,16 @@ impl<'a> MigrationPipeline<'a> {
                 statements.push(generator.generate_drop_index(&table_diff.table_name, &index.name));
             }
 
+            // リネームされたインデックスを元の名前に戻す
+            for renamed_index in &table_diff.renamed_indexes {
+                let table = crate::core::schema::Table::new(table_diff.table_name.clone());
+                statements.extend(generator.generate_rename_index(
+                    &table,
+                    renamed_index,
+                    MigrationDirection::Down,
+                ));
+            }
+
             // 制約の逆操作（Down方向）
             if matches!(self.dialect, Dialect::SQLite) {
                 // SQLite: 制約変更またはnullable/default変更がある場合はテーブル再作成
@@ -365,7 +438,7 @@ impl<'a> MigrationPipeline<'a> {
                                 let new_table_as_old = self
                                     .new_schema
                                     .and_then(|s| s.tables.get(&table_diff.table_name));
-                                let recreator = crate::adapters::sql_generator::sqlite_table_recreator::SqliteTableRecreator::new();
+                                let recreator = self.get_sqlite_table_recreator();
                                 let recreation_stmts = recreator
                                     .generate_table_recreation_with_old_table(
                                         old_table,
@@ -400,6 +473,13 @@ impl<'a> MigrationPipeline<'a> {
                     }
                 }
             }
+
+            // コメントを元に戻す（カラム名はリネームの逆を適用した後の名前）
+            statements.extend(self.comment_statements(
+                &*generator,
+                table_diff,
+                MigrationDirection::Down,
+            ));
         }
 
         // リネームされたテーブルの逆処理（new_name → old_name）
@@ -410,12 +490,25 @@ impl<'a> MigrationPipeline<'a> {
             );
         }
 
+        // リネームされたENUMの逆処理（new_name → old_name）
+        // 変更されたテーブルの逆処理は新しい型名を参照し、削除されたテーブルの再作成は
+        // 変更前のスキーマの旧型名を参照するため、その間で実行する
+        if matches!(self.dialect, Dialect::PostgreSQL) {
+            for renamed_enum in &self.diff.renamed_enums {
+                statements.extend(generator.generate_rename_enum_type(
+                    &renamed_enum.new_enum.name,
+                    &renamed_enum.old_name,
+                ));
+            }
+        }
+
         // 削除されたテーブルを再作成
         for table_name in &self.diff.removed_tables {
             if let Some(old_schema) = self.old_schema {
                 if let Some(old_table) = old_schema.tables.get(table_name) {
                     // old_schemaからCREATE TABLE文を生成
                     statements.push(generator.generate_create_table(old_table));
+                    statements.extend(generator.generate_create_table_comments(old_table));
 
                     // インデックスも再作成
                     for index in &old_table.indexes {
@@ -449,6 +542,13 @@ impl<'a> MigrationPipeline<'a> {
         let view_create_stmts = self.stage_view_down_create_statements(&*generator);
         statements.extend(view_create_stmts);
 
+        // 追加されたENUMを削除（PostgreSQL）
+        if matches!(self.dialect, Dialect::PostgreSQL) {
+            for enum_def in &self.diff.added_enums {
+                statements.extend(generator.generate_drop_enum_type(&enum_def.name));
+            }
+        }
+
         let sql = self.stage_finalize(statements);
         let sql = self.add_transaction_header(sql);
 
@@ -502,6 +602,16 @@ impl<'a> MigrationPipeline<'a> {
 
         // 変更されたビュー（CREATE OR REPLACE / DROP+CREATE）
         for view_diff in &self.diff.modified_views {
+            if view_diff.auto_rewritten {
+                statements.push(format!(
+                    "-- NOTE: The definition of view '{}' was rewritten automatically to follow renamed tables/columns. Review it before applying, and update the schema file to match",
+                    view_diff.view_name
+                ));
+                // PostgreSQLの CREATE OR REPLACE VIEW は出力カラム名を変更できないため作り直す
+                if matches!(self.dialect, Dialect::PostgreSQL) {
+                    statements.push(generator.generate_drop_view(&view_diff.view_name));
+                }
+            }
             statements.push(
                 generator.generate_create_view(&view_diff.view_name, &view_diff.new_definition),
             );
@@ -524,10 +634,15 @@ impl<'a> MigrationPipeline<'a> {
         }
 
         // 変更されたビューを旧定義に戻す
+        // （リネームに合わせて書き換えたビューは、リネームを戻した後のCREATEフェーズで再作成する）
         for view_diff in &self.diff.modified_views {
-            statements.push(
-                generator.generate_create_view(&view_diff.view_name, &view_diff.old_definition),
-            );
+            if view_diff.auto_rewritten {
+                statements.push(generator.generate_drop_view(&view_diff.view_name));
+            } else {
+                statements.push(
+                    generator.generate_create_view(&view_diff.view_name, &view_diff.old_definition),
+                );
+            }
         }
 
         // リネームされたビューの逆処理
@@ -556,11 +671,18 @@ impl<'a> MigrationPipeline<'a> {
 
     /// ビューステージ（DOWN）CREATEフェーズ: テーブル再作成の後に実行
     ///
-    /// 削除されたビューの再作成を行う。
+    /// 削除されたビューと、リネームに合わせて書き換えたビューの再作成を行う。
     /// ビューが削除テーブルを参照している場合、テーブル再作成後にビューを作成する必要がある。
     fn stage_view_down_create_statements(&self, generator: &dyn SqlGenerator) -> Vec<String> {
         let mut statements = Vec::new();
 
+        // リネームに合わせて書き換えたビューを旧定義で再作成
+        for view_diff in self.diff.modified_views.iter().filter(|v| v.auto_rewritten) {
+            statements.push(
+                generator.generate_create_view(&view_diff.view_name, &view_diff.old_definition),
+            );
+        }
+
         // 削除されたビューを再作成
         for view_name in &self.diff.removed_views {
             if let Some(old_schema) = self.old_schema {
@@ -587,12 +709,24 @@ impl<'a> MigrationPipeline<'a> {
     /// SqlGenerator を取得
     fn get_sql_generator(&self) -> Box<dyn SqlGenerator> {
         match self.dialect {
-            Dialect::PostgreSQL => Box::new(PostgresSqlGenerator::new()),
-            Dialect::MySQL => Box::new(MysqlSqlGenerator::new()),
-            Dialect::SQLite => Box::new(SqliteSqlGenerator::new()),
+            Dialect::PostgreSQL => Box::new(
+                PostgresSqlGenerator::new().with_identifier_case(self.diff.identifier_case),
+            ),
+            Dialect::MySQL => {
+                Box::new(MysqlSqlGenerator::new().with_identifier_case(self.diff.identifier_case))
+            }
+            Dialect::SQLite => Box::new(
+                SqliteSqlGenerator::new()
+                    .with_strict_autoincrement(self.diff.sqlite_strict_autoincrement),
+            ),
         }
     }
 
+    /// SQLiteテーブル再作成サービスを取得
+    fn get_sqlite_table_recreator(&self) -> SqliteTableRecreator {
+        SqliteTableRecreator::new().with_strict_autoincrement(self.diff.sqlite_strict_autoincrement)
+    }
+
     /// ステージ7: finalize - SQL結合
     fn stage_finalize(&self, statements: Vec<String>) -> String {
         statements.join(";\n\n") + if statements.is_empty() { "" } else { ";" }
@@ -634,20 +768,152 @@ impl<'a> MigrationPipeline<'a> {
     ///
     /// PostgreSQLでは auto_increment の変更はSERIAL型への変換を伴うため、
     /// 型変更として扱う必要があります。
+    ///
+    /// SQLiteでは生成カラム定義の変更もテーブル再作成で行うため、型変更として扱います。
+    /// MySQLの ON UPDATE CURRENT_TIMESTAMP の変更もMODIFY COLUMNで行うため同様です。
     fn has_type_change(&self, column_diff: &crate::core::schema_diff::ColumnDiff) -> bool {
-        column_diff.changes.iter().any(|change| {
-            matches!(
-                change,
-                ColumnChange::TypeChanged { .. } | ColumnChange::AutoIncrementChanged { .. }
-            )
+        self.changes_require_type_change(&column_diff.changes)
+    }
+
+    /// 変更一覧が型変更（テーブル再作成を含む）で処理されるかどうか
+    fn changes_require_type_change(&self, changes: &[ColumnChange]) -> bool {
+        if self.regenerates_column(changes) {
+            return false;
+        }
+        changes.iter().any(|change| match change {
+            ColumnChange::TypeChanged { .. } | ColumnChange::AutoIncrementChanged { .. } => true,
+            ColumnChange::GeneratedChanged { .. } => matches!(self.dialect, Dialect::SQLite),
+            // MySQLはMODIFY COLUMNで完全なカラム定義を再指定する（他の方言では無視）
+            ColumnChange::OnUpdateCurrentTimestampChanged { .. } => {
+                matches!(self.dialect, Dialect::MySQL)
+            }
+            _ => false,
         })
     }
 
+    /// 生成カラム定義の変更をカラムの削除・再追加で行うかどうか（PostgreSQL・MySQL）
+    ///
+    /// 再追加するカラム定義に型・NULL制約・デフォルト値の変更も含まれるため、
+    /// 該当カラムの他の変更は個別に処理しません。
+    fn regenerates_column(&self, changes: &[ColumnChange]) -> bool {
+        !matches!(self.dialect, Dialect::SQLite)
+            && changes
+                .iter()
+                .any(|change| matches!(change, ColumnChange::GeneratedChanged { .. }))
+    }
+
+    /// 生成カラムを削除して指定の定義で再追加するSQLを生成
+    fn regenerate_column_statements(
+        generator: &dyn SqlGenerator,
+        table_name: &str,
+        drop_column_name: &str,
+        target_column: &Column,
+    ) -> Vec<String> {
+        vec![
+            generator.generate_drop_column(table_name, drop_column_name),
+            generator.generate_add_column(table_name, target_column),
+        ]
+    }
+
+    /// テーブル・カラムのコメント変更SQLを生成
+    ///
+    /// 追加したカラム（Downでは削除から復元したカラム）と、削除・再追加した生成カラムの
+    /// コメントもここで設定する。MySQLはカラム定義にコメントを含めるため、
+    /// MODIFY COLUMN / CHANGE COLUMN で変更されるカラムには別途設定しない。
+    fn comment_statements(
+        &self,
+        generator: &dyn SqlGenerator,
+        table_diff: &TableDiff,
+        direction: MigrationDirection,
+    ) -> Vec<String> {
+        let mut statements = Vec::new();
+        let table_name = &table_diff.table_name;
+        let inline = generator.inline_column_comments();
+
+        if let Some(change) = &table_diff.comment_change {
+            let comment = match direction {
+                MigrationDirection::Up => change.new_comment.as_deref(),
+                MigrationDirection::Down => change.old_comment.as_deref(),
+            };
+            statements.extend(generator.generate_table_comment(table_name, comment));
+        }
+
+        if !inline {
+            let added_columns: Vec<&Column> = match direction {
+                MigrationDirection::Up => table_diff.added_columns.iter().collect(),
+                MigrationDirection::Down => self
+                    .old_schema
+                    .and_then(|s| s.tables.get(table_name))
+                    .map(|old_table| {
+                        table_diff
+                            .removed_columns
+                            .iter()
+                            .filter_map(|name| old_table.get_column(name))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            };
+            for column in added_columns {
+                if column.comment_text().is_some() {
+                    statements.extend(generator.generate_column_comment(table_name, column));
+                }
+            }
+        }
+
+        let modified = table_diff
+            .modified_columns
+            .iter()
+            .map(|cd| (&cd.changes, &cd.old_column, &cd.new_column, false));
+        let renamed = table_diff
+            .renamed_columns
+            .iter()
+            .map(|rc| (&rc.changes, &rc.old_column, &rc.new_column, true));
+        for (changes, old_column, new_column, is_rename) in modified.chain(renamed) {
+            let column = match direction {
+                MigrationDirection::Up => new_column,
+                MigrationDirection::Down => old_column,
+            };
+            if self.needs_column_comment(changes, column, inline, is_rename) {
+                statements.extend(generator.generate_column_comment(table_name, column));
+            }
+        }
+
+        statements
+    }
+
+    /// カラムのコメントを別の文で設定する必要があるかどうか
+    fn needs_column_comment(
+        &self,
+        changes: &[ColumnChange],
+        column: &Column,
+        inline: bool,
+        is_rename: bool,
+    ) -> bool {
+        let comment_changed = changes
+            .iter()
+            .any(|change| matches!(change, ColumnChange::CommentChanged { .. }));
+        if inline {
+            // リネーム・型変更・生成カラムの再追加・NULL許可の変更はカラム定義ごと指定する
+            let redefined = is_rename
+                || self.regenerates_column(changes)
+                || self.changes_require_type_change(changes)
+                || changes
+                    .iter()
+                    .any(|change| matches!(change, ColumnChange::NullableChanged { .. }));
+            return comment_changed && !redefined;
+        }
+        // 削除・再追加した生成カラムはコメントが失われる
+        comment_changed || (self.regenerates_column(changes) && column.comment_text().is_some())
+    }
+
     /// カラム差分がNullableChangedまたはDefaultValueChangedを含むかどうか
     fn has_nullable_or_default_change(
         &self,
         column_diff: &crate::core::schema_diff::ColumnDiff,
     ) -> bool {
+        if self.regenerates_column(&column_diff.changes) {
+            return false;
+        }
         column_diff.changes.iter().any(|change| {
             matches!(
                 change,
@@ -656,17 +922,27 @@ impl<'a> MigrationPipeline<'a> {
         })
     }
 
+    /// デフォルト値の変更がNULL許可の変更と同じMODIFY COLUMNに含まれるかどうか
+    ///
+    /// MySQLのNULL許可変更は完全なカラム定義（デフォルト値を含む）を再指定するため、
+    /// 同時に変更されたデフォルト値を別の文で変更する必要はない。
+    fn default_change_covered_by_modify(
+        &self,
+        column_diff: &crate::core::schema_diff::ColumnDiff,
+    ) -> bool {
+        matches!(self.dialect, Dialect::MySQL)
+            && column_diff
+                .changes
+                .iter()
+                .any(|change| matches!(change, ColumnChange::NullableChanged { .. }))
+    }
+
     /// リネームカラムがTypeChangedまたはAutoIncrementChangedを含むかどうか
     fn has_type_change_in_renamed(
         &self,
         renamed_column: &crate::core::schema_diff::RenamedColumn,
     ) -> bool {
-        renamed_column.changes.iter().any(|change| {
-            matches!(
-                change,
-                ColumnChange::TypeChanged { .. } | ColumnChange::AutoIncrementChanged { .. }
-            )
-        })
+        self.changes_require_type_change(&renamed_column.changes)
     }
 }
 
@@ -725,41 +1001,115 @@ mod tests {
         assert!(error.to_string().contains("enum_statements"));
     }
 
-    #[test]
-    fn test_pipeline_circular_dependency_error() {
-        use crate::core::schema::Constraint;
-
-        let mut diff = SchemaDiff::new();
+    /// users.best_post_id（NULL許可）と posts.user_id が相互に参照する差分
+    fn create_mutual_reference_diff() -> SchemaDiff {
+        use crate::core::schema::{Column, ColumnType, Constraint};
 
-        // 循環参照: A -> B -> A
-        let mut table_a = Table::new("a".to_string());
-        table_a.constraints.push(Constraint::FOREIGN_KEY {
-            columns: vec!["b_id".to_string()],
-            referenced_table: "b".to_string(),
+        let mut users = Table::new("users".to_string());
+        users.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        users.add_column(Column::new(
+            "best_post_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        ));
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        users.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["best_post_id".to_string()],
+            referenced_table: "posts".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete: None,
             on_update: None,
         });
 
-        let mut table_b = Table::new("b".to_string());
-        table_b.constraints.push(Constraint::FOREIGN_KEY {
-            columns: vec!["a_id".to_string()],
-            referenced_table: "a".to_string(),
+        let mut posts = Table::new("posts".to_string());
+        posts.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        posts.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
             referenced_columns: vec!["id".to_string()],
             on_delete: None,
             on_update: None,
         });
 
-        diff.added_tables.push(table_a);
-        diff.added_tables.push(table_b);
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(users);
+        diff.added_tables.push(posts);
+        diff
+    }
 
-        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
-        let result = pipeline.generate_up();
+    #[test]
+    fn test_pipeline_mutual_reference_sqlite_error_names_tables() {
+        let diff = create_mutual_reference_diff();
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::SQLite);
+        let err = pipeline.generate_up().unwrap_err();
 
-        assert!(result.is_err());
-        let err = result.unwrap_err();
         assert_eq!(err.stage(), "table_statements");
         assert!(err.to_string().contains("Circular reference"));
+        assert!(err.to_string().contains("[posts, users]"), "{}", err);
+    }
+
+    #[test]
+    fn test_pipeline_mutual_reference_adds_foreign_keys_after_all_tables() {
+        for (dialect, create_users, create_posts) in [
+            (
+                Dialect::PostgreSQL,
+                r#"CREATE TABLE "users""#,
+                r#"CREATE TABLE "posts""#,
+            ),
+            (
+                Dialect::MySQL,
+                "CREATE TABLE `users`",
+                "CREATE TABLE `posts`",
+            ),
+        ] {
+            let diff = create_mutual_reference_diff();
+            let pipeline = MigrationPipeline::new(&diff, dialect);
+            let (sql, _) = pipeline.generate_up().unwrap();
+
+            let last_create = sql
+                .find(create_users)
+                .unwrap()
+                .max(sql.find(create_posts).unwrap());
+            let first_fk = sql.find("ADD CONSTRAINT").unwrap();
+            assert!(last_create < first_fk, "{:?}: {}", dialect, sql);
+            assert_eq!(sql.matches("FOREIGN KEY").count(), 2, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_pipeline_mutual_reference_down_drops_foreign_keys_before_tables() {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL] {
+            let diff = create_mutual_reference_diff();
+            let pipeline = MigrationPipeline::new(&diff, dialect);
+            let (sql, _) = pipeline.generate_down().unwrap();
+
+            let first_drop_table = sql.find("DROP TABLE").unwrap();
+            let drop_fks: Vec<usize> = sql
+                .match_indices("fk_")
+                .map(|(i, _)| i)
+                .filter(|&i| i < first_drop_table)
+                .collect();
+            assert_eq!(drop_fks.len(), 2, "{:?}: {}", dialect, sql);
+        }
     }
 
     // ==========================================
@@ -781,6 +1131,93 @@ mod tests {
         assert!(sql.contains(r#"DROP TABLE "posts""#));
     }
 
+    /// users と、users を参照するロールバック保護付きの audit_log を追加する差分
+    fn create_protected_table_diff() -> SchemaDiff {
+        use crate::core::schema::{Column, ColumnType, Constraint};
+
+        let mut users = Table::new("users".to_string());
+        users.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+
+        let mut audit_log = Table::new("audit_log".to_string());
+        audit_log.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        ));
+        audit_log.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        audit_log.rollback_protection = true;
+
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(users);
+        diff.added_tables.push(audit_log);
+        diff
+    }
+
+    #[test]
+    fn test_pipeline_generate_down_keeps_protected_table() {
+        let diff = create_protected_table_diff();
+        assert_eq!(diff.rollback_protected_tables(), vec!["audit_log"]);
+
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let (sql, _) = MigrationPipeline::new(&diff, dialect)
+                .generate_down()
+                .unwrap();
+
+            assert!(
+                sql.contains("-- PROTECTED: table audit_log will not be dropped by rollback"),
+                "{:?}: {}",
+                dialect,
+                sql
+            );
+            assert!(!sql.contains("DROP TABLE \"audit_log\""), "{}", sql);
+            assert!(!sql.contains("DROP TABLE `audit_log`"), "{}", sql);
+            assert_eq!(sql.matches("DROP TABLE").count(), 1, "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_pipeline_generate_down_drops_foreign_key_from_protected_table() {
+        let diff = create_protected_table_diff();
+
+        // 保護テーブルから削除されるテーブルへの外部キーは DROP TABLE の前に外す
+        let (sql, _) = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
+            .generate_down()
+            .unwrap();
+        let drop_fk = sql
+            .find(r#"ALTER TABLE "audit_log" DROP CONSTRAINT"#)
+            .unwrap_or_else(|| panic!("{}", sql));
+        assert!(
+            drop_fk < sql.find(r#"DROP TABLE "users""#).unwrap(),
+            "{}",
+            sql
+        );
+
+        // SQLiteは制約を個別に削除できないため注記のみ
+        let (sql, _) = MigrationPipeline::new(&diff, Dialect::SQLite)
+            .generate_down()
+            .unwrap();
+        assert!(
+            sql.contains(
+                "-- NOTE: Foreign key from protected table 'audit_log' to 'users' remains"
+            ),
+            "{}",
+            sql
+        );
+    }
+
     #[test]
     fn test_pipeline_with_allow_destructive() {
         let diff = SchemaDiff::new();
@@ -931,6 +1368,7 @@ mod tests {
         diff.added_enums.push(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
@@ -940,6 +1378,35 @@ mod tests {
         assert!(sql.contains("DROP TYPE") || sql.contains("status"));
     }
 
+    #[test]
+    fn test_pipeline_generate_down_drops_added_enum_after_columns_using_it() {
+        use crate::core::schema::{Column, ColumnType, EnumDefinition};
+        use crate::core::schema_diff::TableDiff;
+
+        let mut diff = SchemaDiff::new();
+        diff.added_enums.push(EnumDefinition {
+            name: "priority".to_string(),
+            values: vec!["low".to_string(), "high".to_string()],
+            renamed_from: None,
+        });
+        let mut table_diff = TableDiff::new("tickets".to_string());
+        table_diff.added_columns.push(Column::new(
+            "priority".to_string(),
+            ColumnType::Enum {
+                name: "priority".to_string(),
+            },
+            true,
+        ));
+        diff.modified_tables.push(table_diff);
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let (sql, _) = pipeline.generate_down().unwrap();
+
+        let drop_column = sql.find("DROP COLUMN").unwrap();
+        let drop_type = sql.find(r#"DROP TYPE "priority""#).unwrap();
+        assert!(drop_column < drop_type, "{}", sql);
+    }
+
     #[test]
     fn test_pipeline_generate_down_enum_removed_with_old_schema() {
         use crate::core::schema::EnumDefinition;
@@ -953,6 +1420,7 @@ mod tests {
             EnumDefinition {
                 name: "status".to_string(),
                 values: vec!["active".to_string(), "inactive".to_string()],
+                renamed_from: None,
             },
         );
         let new_schema = Schema::new("1.0".to_string());
@@ -1054,6 +1522,7 @@ mod tests {
                 "active_users".to_string(),
                 "SELECT id, email FROM users WHERE active = true".to_string(),
             ),
+            auto_rewritten: false,
         });
 
         let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
@@ -1098,6 +1567,7 @@ mod tests {
                 "active_users".to_string(),
                 "SELECT id FROM users WHERE active = true".to_string(),
             ),
+            auto_rewritten: false,
         });
 
         let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
@@ -2,11 +2,15 @@
 //
 // テーブルの作成・変更・型変更検証を処理するステージ。
 
-use crate::adapters::sql_generator::{MigrationDirection, SqlGenerator};
+use crate::adapters::sql_generator::{
+    validate_populate_expression, MigrationDirection, SqlGenerator,
+};
 use crate::core::config::Dialect;
-use crate::core::error::ValidationResult;
-use crate::core::schema_diff::ColumnChange;
+use crate::core::error::{ErrorLocation, ValidationResult, ValidationWarning};
+use crate::core::schema::{Column, Constraint, Table};
+use crate::core::schema_diff::{AddedTableOrder, ColumnChange};
 use crate::services::type_change_validator::TypeChangeValidator;
+use std::collections::HashSet;
 
 use super::{MigrationPipeline, PipelineStageError};
 
@@ -29,6 +33,40 @@ impl<'a> MigrationPipeline<'a> {
             }
         }
 
+        // 既存テーブルへの NOT NULL カラム追加は、行があると値を埋める手段がない
+        for table_diff in &self.diff.modified_tables {
+            for column in table_diff
+                .added_columns
+                .iter()
+                .filter(|c| requires_backfill(c))
+            {
+                let how = if matches!(self.dialect, Dialect::SQLite) {
+                    "The table is recreated with placeholder values for existing rows"
+                } else {
+                    "The column is added as nullable and set to NOT NULL afterwards"
+                };
+                total_validation_result.add_warning(ValidationWarning::compatibility(
+                    format!(
+                        "Column '{}.{}' is added as NOT NULL without a default value. {}; fill in the `-- TODO: backfill` UPDATE in up.sql before applying to a table that has rows.",
+                        table_diff.table_name, column.name, how
+                    ),
+                    Some(ErrorLocation::with_table_and_column(
+                        &table_diff.table_name,
+                        &column.name,
+                    )),
+                ));
+            }
+        }
+
+        // 追加テーブルの populate_from を検証
+        for table in &self.diff.added_tables {
+            if let Some(select) = &table.populate_from {
+                if let Err(error) = validate_populate_expression(&table.name, select) {
+                    total_validation_result.add_error(error);
+                }
+            }
+        }
+
         Ok(total_validation_result)
     }
 
@@ -48,15 +86,15 @@ impl<'a> MigrationPipeline<'a> {
         }
 
         // 外部キー依存関係を考慮してテーブルをソート
-        let sorted_tables = self.diff.sort_added_tables_by_dependency().map_err(|e| {
-            PipelineStageError::CircularDependency {
-                message: e.to_string(),
-            }
-        })?;
+        let order = self.added_table_order()?;
+
+        // 循環参照がある場合は、全テーブルを作成してから外部キーを追加する
+        let defer_foreign_keys = order.has_cycle();
 
         // 追加されたテーブルのCREATE TABLE文を生成
-        for table in &sorted_tables {
+        for table in &order.tables {
             statements.push(generator.generate_create_table(table));
+            statements.extend(generator.generate_create_table_comments(table));
 
             // インデックスの作成
             for index in &table.indexes {
@@ -64,26 +102,53 @@ impl<'a> MigrationPipeline<'a> {
             }
 
             // FOREIGN KEY制約の追加（SQLite以外）
-            if !matches!(self.dialect, Dialect::SQLite) {
-                for (i, constraint) in table.constraints.iter().enumerate() {
-                    if matches!(
-                        constraint,
-                        crate::core::schema::Constraint::FOREIGN_KEY { .. }
-                    ) {
-                        let alter_sql = generator.generate_alter_table_add_constraint(table, i);
-                        if !alter_sql.is_empty() {
-                            statements.push(alter_sql);
-                        }
-                    }
-                }
+            if !defer_foreign_keys && !matches!(self.dialect, Dialect::SQLite) {
+                statements.extend(Self::add_foreign_key_statements(generator, table));
+            }
+        }
+
+        if defer_foreign_keys {
+            for table in &order.tables {
+                statements.extend(Self::add_foreign_key_statements(generator, table));
             }
         }
 
         // 変更されたテーブルの処理
         for table_diff in &self.diff.modified_tables {
+            let backfill_columns: Vec<&Column> = table_diff
+                .added_columns
+                .iter()
+                .filter(|c| requires_backfill(c))
+                .collect();
+            // SQLite は NOT NULL カラムを ADD COLUMN できないため、テーブル再作成で追加する
+            let sqlite_recreate = self.sqlite_recreates_for_backfill(table_diff);
+
             // カラムの追加
             for column in &table_diff.added_columns {
-                statements.push(generator.generate_add_column(&table_diff.table_name, column));
+                if sqlite_recreate {
+                    continue;
+                }
+                if requires_backfill(column) && !matches!(self.dialect, Dialect::SQLite) {
+                    // NULL 許可で追加 → 既存行を埋める → NOT NULL にする
+                    let mut nullable_column = column.clone();
+                    nullable_column.nullable = true;
+                    statements.push(
+                        generator.generate_add_column(&table_diff.table_name, &nullable_column),
+                    );
+                    statements.push(backfill_todo(
+                        generator,
+                        &table_diff.table_name,
+                        column,
+                        false,
+                    ));
+                    statements.extend(generator.generate_alter_column_nullable(
+                        &table_diff.table_name,
+                        column,
+                        false,
+                    ));
+                } else {
+                    statements.push(generator.generate_add_column(&table_diff.table_name, column));
+                }
             }
 
             // カラムの削除
@@ -125,6 +190,16 @@ impl<'a> MigrationPipeline<'a> {
                                 );
                             statements.extend(alter_statements);
                         }
+
+                        // 生成カラム定義の変更は、リネーム後のカラムを削除して再追加する
+                        if self.regenerates_column(&renamed_column.changes) {
+                            statements.extend(Self::regenerate_column_statements(
+                                generator,
+                                &table_diff.table_name,
+                                &renamed_column.new_column.name,
+                                &renamed_column.new_column,
+                            ));
+                        }
                     }
                 }
             }
@@ -150,6 +225,18 @@ impl<'a> MigrationPipeline<'a> {
                 }
             }
 
+            // 生成カラム定義の変更（PostgreSQL・MySQL）: 削除して新しい定義で再追加
+            for column_diff in &table_diff.modified_columns {
+                if self.regenerates_column(&column_diff.changes) {
+                    statements.extend(Self::regenerate_column_statements(
+                        generator,
+                        &table_diff.table_name,
+                        &column_diff.old_column.name,
+                        &column_diff.new_column,
+                    ));
+                }
+            }
+
             // nullable/default変更の処理（型変更がないカラム、SQLite以外）
             if !matches!(self.dialect, Dialect::SQLite) {
                 for column_diff in &table_diff.modified_columns {
@@ -167,7 +254,9 @@ impl<'a> MigrationPipeline<'a> {
                                         *new_nullable,
                                     ));
                                 }
-                                ColumnChange::DefaultValueChanged { new_default, .. } => {
+                                ColumnChange::DefaultValueChanged { new_default, .. }
+                                    if !self.default_change_covered_by_modify(column_diff) =>
+                                {
                                     statements.extend(generator.generate_alter_column_default(
                                         &table_diff.table_name,
                                         target_column,
@@ -180,18 +269,234 @@ impl<'a> MigrationPipeline<'a> {
                     }
                 }
             }
+
+            // テーブル・カラムのコメント
+            statements.extend(self.comment_statements(
+                generator,
+                table_diff,
+                MigrationDirection::Up,
+            ));
+
+            // SQLite: 型変更による再作成がなければ、ここでテーブルを再作成してカラムを追加する
+            if sqlite_recreate {
+                let has_type_change = table_diff
+                    .modified_columns
+                    .iter()
+                    .any(|cd| self.has_type_change(cd))
+                    || table_diff
+                        .renamed_columns
+                        .iter()
+                        .any(|rc| self.has_type_change_in_renamed(rc));
+                if !has_type_change {
+                    if let Some(new_table) = self
+                        .new_schema
+                        .and_then(|s| s.tables.get(&table_diff.table_name))
+                    {
+                        // リネーム済みのカラムは新しい名前でコピーする
+                        let old_table = self
+                            .old_schema
+                            .and_then(|s| s.tables.get(&table_diff.table_name))
+                            .map(|old| {
+                                let mut current = old.clone();
+                                for renamed in &table_diff.renamed_columns {
+                                    if let Some(column) = current
+                                        .columns
+                                        .iter_mut()
+                                        .find(|c| c.name == renamed.old_name)
+                                    {
+                                        column.name = renamed.new_column.name.clone();
+                                    }
+                                }
+                                current
+                            });
+                        statements.extend(
+                            self.get_sqlite_table_recreator()
+                                .generate_table_recreation_with_old_table(
+                                    new_table,
+                                    old_table.as_ref(),
+                                ),
+                        );
+                    }
+                }
+                for column in &backfill_columns {
+                    statements.push(backfill_todo(
+                        generator,
+                        &table_diff.table_name,
+                        column,
+                        true,
+                    ));
+                }
+            }
         }
 
         Ok(statements)
     }
+
+    /// SQLite で NOT NULL カラムの追加をテーブル再作成で行うかどうか
+    ///
+    /// SQLite は STORED の生成カラムや、`CURRENT_TIMESTAMP` のように定数でないデフォルト値を
+    /// 持つカラムも ADD COLUMN できないため、同様に再作成で追加する。
+    /// 再作成には変更後のテーブル定義が必要なため、スキーマ情報がない場合は行わない。
+    pub(super) fn sqlite_recreates_for_backfill(
+        &self,
+        table_diff: &crate::core::schema_diff::TableDiff,
+    ) -> bool {
+        matches!(self.dialect, Dialect::SQLite)
+            && table_diff.added_columns.iter().any(|column| {
+                requires_backfill(column)
+                    || column.generated.as_ref().is_some_and(|g| g.stored)
+                    || column.has_current_timestamp_default()
+            })
+            && self
+                .new_schema
+                .is_some_and(|s| s.tables.contains_key(&table_diff.table_name))
+    }
+
+    /// 外部キー依存関係を考慮した追加テーブルの作成順序
+    ///
+    /// PostgreSQL・MySQLでは循環参照があっても外部キーを後から追加できるため許可する。
+    /// SQLiteは外部キーをCREATE TABLE内で定義する必要があるため、循環参照はエラーとする。
+    pub(super) fn added_table_order(&self) -> Result<AddedTableOrder, PipelineStageError> {
+        let order = self.diff.order_added_tables();
+        if order.has_cycle() && matches!(self.dialect, Dialect::SQLite) {
+            return Err(PipelineStageError::CircularDependency {
+                message: format!(
+                    "Circular reference detected between tables [{}]. SQLite requires foreign keys to be defined inline in CREATE TABLE, so a foreign key cycle cannot be created. Remove one of the foreign keys or create the tables in separate migrations.",
+                    order.cyclic_tables.join(", ")
+                ),
+            });
+        }
+        Ok(order)
+    }
+
+    /// テーブルの外部キー制約を ALTER TABLE ADD CONSTRAINT で追加するSQLを生成
+    fn add_foreign_key_statements(generator: &dyn SqlGenerator, table: &Table) -> Vec<String> {
+        table
+            .constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, constraint)| matches!(constraint, Constraint::FOREIGN_KEY { .. }))
+            .map(|(i, _)| generator.generate_alter_table_add_constraint(table, i))
+            .filter(|sql| !sql.is_empty())
+            .collect()
+    }
+
+    /// populate_statements - 追加テーブルへのデータ投入（INSERT INTO ... SELECT）
+    ///
+    /// インデックス・制約の作成後に実行し、投入データのインデックス構築を一括で済ませる。
+    pub(super) fn stage_populate_statements(
+        &self,
+        generator: &dyn SqlGenerator,
+    ) -> Result<Vec<String>, PipelineStageError> {
+        let order = self.added_table_order()?;
+
+        Ok(order
+            .tables
+            .iter()
+            .filter_map(|table| {
+                table
+                    .populate_from
+                    .as_ref()
+                    .map(|select| generator.generate_populate_table(table, select))
+            })
+            .collect())
+    }
+
+    /// ロールバック保護テーブルから削除対象テーブルへの外部キーを外すSQLを生成（DOWN用）
+    ///
+    /// 保護テーブルはロールバック後も残るため、同じマイグレーションで追加された
+    /// 保護されていないテーブルへの外部キーが残ると DROP TABLE が失敗する。
+    /// SQLiteは制約を個別に削除できないため、手動対応を促すコメントを出力する。
+    pub(super) fn protected_table_foreign_key_statements(
+        &self,
+        generator: &dyn SqlGenerator,
+        added_tables: &[Table],
+    ) -> Vec<String> {
+        let dropped: HashSet<&str> = added_tables
+            .iter()
+            .filter(|table| !table.rollback_protection)
+            .map(|table| table.name.as_str())
+            .collect();
+
+        let mut statements = Vec::new();
+        for table in added_tables.iter().filter(|t| t.rollback_protection) {
+            for constraint in &table.constraints {
+                let Constraint::FOREIGN_KEY {
+                    referenced_table, ..
+                } = constraint
+                else {
+                    continue;
+                };
+                if !dropped.contains(referenced_table.as_str()) {
+                    continue;
+                }
+
+                if matches!(self.dialect, Dialect::SQLite) {
+                    statements.push(format!(
+                        "-- NOTE: Foreign key from protected table '{}' to '{}' remains; recreate '{}' without it if '{}' must be dropped",
+                        table.name, referenced_table, table.name, referenced_table
+                    ));
+                } else {
+                    let sql = generator
+                        .generate_drop_constraint_for_existing_table(&table.name, constraint);
+                    if !sql.is_empty() {
+                        statements.push(sql);
+                    }
+                }
+            }
+        }
+        statements
+    }
+}
+
+/// 既存テーブルへの追加時に既存行の値を埋める必要があるカラムか
+///
+/// NOT NULL でデフォルト値がなく、自動採番でも生成カラムでもないカラムが対象です。
+fn requires_backfill(column: &Column) -> bool {
+    !column.nullable
+        && column.default_value.is_none()
+        && column.auto_increment != Some(true)
+        && !column.is_generated()
+}
+
+/// 既存行を埋める UPDATE のひな形（コメント）を生成
+///
+/// `placeholder_filled` は SQLite のテーブル再作成で仮の値が入っている場合に true を指定します。
+fn backfill_todo(
+    generator: &dyn SqlGenerator,
+    table_name: &str,
+    column: &Column,
+    placeholder_filled: bool,
+) -> String {
+    let table = generator.quote_table_name(table_name);
+    let column_name = generator.quote_identifier(&column.name);
+    if placeholder_filled {
+        format!(
+            "-- TODO: backfill {}.{} (existing rows were filled with a placeholder value)\n-- UPDATE {} SET {} = <value>",
+            table, column_name, table, column_name
+        )
+    } else {
+        format!(
+            "-- TODO: backfill {}.{} before it becomes NOT NULL\n-- UPDATE {} SET {} = <value> WHERE {} IS NULL",
+            table, column_name, table, column_name, column_name
+        )
+    }
+}
+
+/// ロールバック保護テーブルの DROP TABLE の代わりに出力するコメント
+pub(super) fn protected_table_notice(table_name: &str) -> String {
+    format!(
+        "-- PROTECTED: table {} will not be dropped by rollback",
+        table_name
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::schema::{Column, ColumnType, Constraint, Schema, Table};
+    use crate::core::schema::{Column, ColumnType, Constraint, GeneratedColumn, Schema, Table};
     use crate::core::schema_diff::{
-        ColumnChange, ColumnDiff, RenamedColumn, SchemaDiff, TableDiff,
+        ColumnChange, ColumnDiff, CommentChange, RenamedColumn, SchemaDiff, TableDiff,
     };
 
     // ==========================================
@@ -235,6 +540,127 @@ mod tests {
         assert!(sql.contains(r#"DROP TABLE "users""#));
     }
 
+    // ==========================================
+    // populate_from 関連テスト
+    // ==========================================
+
+    fn create_populated_table() -> Table {
+        let mut table = Table::new("user_reports".to_string());
+        table.columns.push(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.columns.push(Column::new(
+            "post_count".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.indexes.push(crate::core::schema::Index::new(
+            "idx_user_reports_post_count".to_string(),
+            vec!["post_count".to_string()],
+            false,
+        ));
+        table.populate_from =
+            Some("SELECT user_id, COUNT(*) FROM posts GROUP BY user_id\n".to_string());
+        table
+    }
+
+    #[test]
+    fn test_pipeline_populate_from_inserts_after_indexes() {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let mut diff = SchemaDiff::new();
+            diff.added_tables.push(create_populated_table());
+
+            let pipeline = MigrationPipeline::new(&diff, dialect);
+            let (sql, _) = pipeline.generate_up().unwrap();
+
+            let create_table = sql.find("CREATE TABLE").unwrap();
+            let create_index = sql.find("CREATE INDEX").unwrap();
+            let insert = sql
+                .find("INSERT INTO")
+                .unwrap_or_else(|| panic!("Expected INSERT in: {}", sql));
+            assert!(
+                create_table < create_index && create_index < insert,
+                "{}",
+                sql
+            );
+            assert!(
+                sql.contains("-- Populate 'user_reports' from populate_from"),
+                "Expected provenance comment in: {}",
+                sql
+            );
+            assert!(
+                sql.ends_with("SELECT user_id, COUNT(*) FROM posts GROUP BY user_id;"),
+                "{}",
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn test_pipeline_populate_from_lists_columns_in_definition_order() {
+        let mut table = create_populated_table();
+        let mut doubled = Column::new(
+            "doubled_count".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        );
+        doubled.generated = Some(crate::core::schema::GeneratedColumn {
+            expression: "post_count * 2".to_string(),
+            stored: true,
+        });
+        table.columns.push(doubled);
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(table);
+
+        // SELECTの列はテーブル定義の順で対応付け、生成カラムには投入しない
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let (sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            sql.contains(
+                "INSERT INTO \"user_reports\" (\"user_id\", \"post_count\")\nSELECT user_id, COUNT(*)"
+            ),
+            "{}",
+            sql
+        );
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+        let (sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            sql.contains("INSERT INTO `user_reports` (`user_id`, `post_count`)"),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_populate_from_down_only_comments() {
+        let mut diff = SchemaDiff::new();
+        diff.added_tables.push(create_populated_table());
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let (sql, _) = pipeline.generate_down().unwrap();
+
+        assert!(!sql.contains("INSERT"), "{}", sql);
+        assert!(sql.contains("-- Data populated from populate_from for 'user_reports'"));
+        assert!(sql.contains(r#"DROP TABLE "user_reports""#));
+    }
+
+    #[test]
+    fn test_pipeline_populate_from_rejects_ddl() {
+        let mut diff = SchemaDiff::new();
+        let mut table = create_populated_table();
+        table.populate_from = Some("SELECT 1; DROP TABLE users".to_string());
+        diff.added_tables.push(table);
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let err = pipeline.generate_up().unwrap_err();
+
+        assert_eq!(err.stage(), "prepare");
+        assert!(err.to_string().contains("populate_from"), "{}", err);
+    }
+
     // ==========================================
     // 型変更関連テスト
     // ==========================================
@@ -324,6 +750,209 @@ mod tests {
         assert!(sql.contains("ALTER TABLE `users` MODIFY COLUMN `age`"));
     }
 
+    fn create_diff_with_default_change(old_nullable: bool, new_nullable: bool) -> SchemaDiff {
+        let mut old_column = Column::new(
+            "status".to_string(),
+            ColumnType::VARCHAR { length: 20 },
+            old_nullable,
+        );
+        old_column.default_value = Some("'draft'".to_string());
+        let mut new_column = old_column.clone();
+        new_column.nullable = new_nullable;
+        new_column.default_value = Some("'active'".to_string());
+
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.modified_columns.push(ColumnDiff::new(
+            "status".to_string(),
+            old_column,
+            new_column,
+        ));
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+        diff
+    }
+
+    #[test]
+    fn test_pipeline_default_only_change_mysql_uses_set_default() {
+        let diff = create_diff_with_default_change(false, false);
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql.contains("ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT 'active'"),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("MODIFY COLUMN"), "{}", up_sql);
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains("ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT 'draft'"),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_default_and_nullable_change_mysql_uses_single_modify() {
+        let diff = create_diff_with_default_change(true, false);
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert_eq!(up_sql.matches("MODIFY COLUMN").count(), 1, "{}", up_sql);
+        assert!(
+            up_sql.contains(
+                "ALTER TABLE `users` MODIFY COLUMN `status` VARCHAR(20) NOT NULL DEFAULT 'active'"
+            ),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("SET DEFAULT"), "{}", up_sql);
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert_eq!(down_sql.matches("MODIFY COLUMN").count(), 1, "{}", down_sql);
+        assert!(
+            down_sql
+                .contains("ALTER TABLE `users` MODIFY COLUMN `status` VARCHAR(20) DEFAULT 'draft'"),
+            "{}",
+            down_sql
+        );
+    }
+
+    fn create_diff_with_comment_change(nullable_change: bool) -> SchemaDiff {
+        let old_column = Column::new("email".to_string(), ColumnType::TEXT, true);
+        let mut new_column = old_column.clone();
+        new_column.nullable = !nullable_change;
+        new_column.comment = Some("User's login".to_string());
+
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.modified_columns.push(ColumnDiff::new(
+            "email".to_string(),
+            old_column,
+            new_column,
+        ));
+        table_diff.comment_change = CommentChange::detect(None, Some("Registered users"));
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+        diff
+    }
+
+    #[test]
+    fn test_pipeline_comment_only_change() {
+        let diff = create_diff_with_comment_change(false);
+
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL);
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        // カラムの型は変更しない
+        assert!(
+            up_sql.ends_with(
+                "COMMENT ON TABLE \"users\" IS 'Registered users';\n\n\
+                 COMMENT ON COLUMN \"users\".\"email\" IS 'User''s login';"
+            ),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("ALTER TABLE"), "{}", up_sql);
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains("COMMENT ON COLUMN \"users\".\"email\" IS NULL"),
+            "{}",
+            down_sql
+        );
+        assert!(
+            down_sql.contains("COMMENT ON TABLE \"users\" IS NULL"),
+            "{}",
+            down_sql
+        );
+
+        // MySQLはカラム定義を再指定してコメントを変更する
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql
+                .contains("ALTER TABLE `users` MODIFY COLUMN `email` TEXT COMMENT 'User''s login'"),
+            "{}",
+            up_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_comment_with_nullable_change_mysql_uses_single_modify() {
+        let diff = create_diff_with_comment_change(true);
+        let pipeline = MigrationPipeline::new(&diff, Dialect::MySQL);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert_eq!(up_sql.matches("MODIFY COLUMN").count(), 1, "{}", up_sql);
+        assert!(
+            up_sql.contains("MODIFY COLUMN `email` TEXT NOT NULL COMMENT 'User''s login'"),
+            "{}",
+            up_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_on_update_current_timestamp_change() {
+        let mut old_column = Column::new(
+            "updated_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            false,
+        );
+        old_column.default_value = Some("CURRENT_TIMESTAMP".to_string());
+        let mut new_column = old_column.clone();
+        new_column.on_update_current_timestamp = true;
+
+        let mut old_schema = Schema::new("1.0".to_string());
+        let mut old_table = Table::new("users".to_string());
+        old_table.add_column(old_column.clone());
+        old_schema.add_table(old_table);
+        let mut new_schema = Schema::new("1.0".to_string());
+        let mut new_table = Table::new("users".to_string());
+        new_table.add_column(new_column.clone());
+        new_schema.add_table(new_table);
+
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.modified_columns.push(ColumnDiff::new(
+            "updated_at".to_string(),
+            old_column,
+            new_column,
+        ));
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+
+        // MySQLはカラム定義を再指定して ON UPDATE を付け外しする
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::MySQL).with_schemas(&old_schema, &new_schema);
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert_eq!(up_sql.matches("MODIFY COLUMN").count(), 1, "{}", up_sql);
+        assert!(
+            up_sql.contains(
+                "ALTER TABLE `users` MODIFY COLUMN `updated_at` TIMESTAMP NOT NULL \
+                 DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP"
+            ),
+            "{}",
+            up_sql
+        );
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains(
+                "ALTER TABLE `users` MODIFY COLUMN `updated_at` TIMESTAMP NOT NULL \
+                 DEFAULT CURRENT_TIMESTAMP"
+            ),
+            "{}",
+            down_sql
+        );
+        assert!(!down_sql.contains("ON UPDATE"), "{}", down_sql);
+
+        // MySQL以外では無視されるため、SQLは生成しない
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
+            .with_schemas(&old_schema, &new_schema);
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(!up_sql.contains("ALTER TABLE"), "{}", up_sql);
+    }
+
     #[test]
     fn test_pipeline_type_change_sqlite() {
         let (old_schema, new_schema) = create_test_schemas_for_type_change();
@@ -382,6 +1011,216 @@ mod tests {
         assert_eq!(err.stage(), "prepare");
     }
 
+    // ==========================================
+    // 生成カラム関連テスト
+    // ==========================================
+
+    fn create_generated_schemas(
+        old_generated: Option<GeneratedColumn>,
+        new_generated: Option<GeneratedColumn>,
+    ) -> (Schema, Schema, SchemaDiff) {
+        let create_schema = |generated: Option<GeneratedColumn>| {
+            let mut schema = Schema::new("1.0".to_string());
+            let mut table = Table::new("orders".to_string());
+            table.columns.push(Column::new(
+                "price".to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+            let mut total = Column::new(
+                "total".to_string(),
+                ColumnType::INTEGER { precision: None },
+                true,
+            );
+            total.generated = generated;
+            table.columns.push(total);
+            schema.tables.insert("orders".to_string(), table);
+            schema
+        };
+        let old_schema = create_schema(old_generated);
+        let new_schema = create_schema(new_generated);
+
+        let mut table_diff = TableDiff::new("orders".to_string());
+        table_diff.modified_columns.push(ColumnDiff::new(
+            "total".to_string(),
+            old_schema.tables["orders"].columns[1].clone(),
+            new_schema.tables["orders"].columns[1].clone(),
+        ));
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+
+        (old_schema, new_schema, diff)
+    }
+
+    #[test]
+    fn test_pipeline_generated_change_regenerates_column_postgresql() {
+        let (old_schema, new_schema, diff) = create_generated_schemas(
+            Some(GeneratedColumn::new("price * 2", true)),
+            Some(GeneratedColumn::new("price * 3", true)),
+        );
+        let pipeline = MigrationPipeline::new(&diff, Dialect::PostgreSQL)
+            .with_schemas(&old_schema, &new_schema);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        let drop = up_sql
+            .find(r#"ALTER TABLE "orders" DROP COLUMN "total""#)
+            .expect(&up_sql);
+        let add = up_sql
+            .find(r#"ALTER TABLE "orders" ADD COLUMN "total" INTEGER GENERATED ALWAYS AS (price * 3) STORED"#)
+            .expect(&up_sql);
+        assert!(drop < add, "{}", up_sql);
+
+        let (down_sql, _) = pipeline.generate_down().unwrap();
+        assert!(
+            down_sql.contains("GENERATED ALWAYS AS (price * 2) STORED"),
+            "{}",
+            down_sql
+        );
+    }
+
+    #[test]
+    fn test_pipeline_generated_change_regenerates_column_mysql() {
+        let (old_schema, new_schema, diff) =
+            create_generated_schemas(None, Some(GeneratedColumn::new("price * 2", false)));
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::MySQL).with_schemas(&old_schema, &new_schema);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql.contains("ALTER TABLE `orders` DROP COLUMN `total`"),
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains("ADD COLUMN `total` INT GENERATED ALWAYS AS (price * 2) VIRTUAL"),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("MODIFY COLUMN"), "{}", up_sql);
+    }
+
+    #[test]
+    fn test_pipeline_generated_change_recreates_table_sqlite() {
+        let (old_schema, new_schema, diff) = create_generated_schemas(
+            Some(GeneratedColumn::new("price * 2", false)),
+            Some(GeneratedColumn::new("price * 3", false)),
+        );
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::SQLite).with_schemas(&old_schema, &new_schema);
+
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+        assert!(
+            up_sql.contains(r#"CREATE TABLE "_stratum_tmp_recreate_orders""#),
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains("GENERATED ALWAYS AS (price * 3) VIRTUAL"),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("DROP COLUMN"), "{}", up_sql);
+    }
+
+    #[test]
+    fn test_pipeline_add_stored_generated_column_sqlite_recreates_table() {
+        let mut old_schema = Schema::new("1.0".to_string());
+        let mut old_table = Table::new("orders".to_string());
+        old_table.columns.push(Column::new(
+            "price".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        old_schema
+            .tables
+            .insert("orders".to_string(), old_table.clone());
+
+        // NOT NULL でも生成カラムはバックフィル不要
+        let mut total = Column::new(
+            "total".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        total.generated = Some(GeneratedColumn::new("price * 2", true));
+        let mut new_schema = Schema::new("1.0".to_string());
+        let mut new_table = old_table;
+        new_table.columns.push(total.clone());
+        new_schema.tables.insert("orders".to_string(), new_table);
+
+        let mut table_diff = TableDiff::new("orders".to_string());
+        table_diff.added_columns.push(total);
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::SQLite).with_schemas(&old_schema, &new_schema);
+        let (up_sql, validation) = pipeline.generate_up().unwrap();
+
+        assert!(!up_sql.contains("ADD COLUMN"), "{}", up_sql);
+        assert!(
+            up_sql.contains(r#"CREATE TABLE "_stratum_tmp_recreate_orders""#),
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains(r#"INSERT INTO "_stratum_tmp_recreate_orders" ("price") SELECT "price" FROM "orders""#),
+            "{}",
+            up_sql
+        );
+        assert!(!up_sql.contains("TODO: backfill"), "{}", up_sql);
+        assert!(validation.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_add_current_timestamp_column_sqlite_recreates_table() {
+        let mut old_schema = Schema::new("1.0".to_string());
+        let mut old_table = Table::new("events".to_string());
+        old_table.columns.push(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        old_schema
+            .tables
+            .insert("events".to_string(), old_table.clone());
+
+        // SQLite は定数でないデフォルト値のカラムを ADD COLUMN できない
+        let mut created_at = Column::new(
+            "created_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            false,
+        );
+        created_at.default_value = Some("now()".to_string());
+        let mut new_schema = Schema::new("1.0".to_string());
+        let mut new_table = old_table;
+        new_table.columns.push(created_at.clone());
+        new_schema.tables.insert("events".to_string(), new_table);
+
+        let mut table_diff = TableDiff::new("events".to_string());
+        table_diff.added_columns.push(created_at);
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+
+        let pipeline =
+            MigrationPipeline::new(&diff, Dialect::SQLite).with_schemas(&old_schema, &new_schema);
+        let (up_sql, _) = pipeline.generate_up().unwrap();
+
+        assert!(!up_sql.contains("ADD COLUMN"), "{}", up_sql);
+        assert!(
+            up_sql.contains(r#""created_at" TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP"#),
+            "{}",
+            up_sql
+        );
+        assert!(
+            up_sql.contains(r#"INSERT INTO "_stratum_tmp_recreate_events" ("id", "created_at") SELECT "id", CURRENT_TIMESTAMP FROM "events""#),
+            "{}",
+            up_sql
+        );
+    }
+
     // ==========================================
     // DOWN SQL 型変更テスト
     // ==========================================
@@ -0,0 +1,259 @@
+// マイグレーションポリシー検査サービス
+//
+// スキーマ差分と生成されたSQLの規模を計測し、設定されたポリシーに照らして評価する。
+
+use std::collections::BTreeSet;
+
+use crate::core::config::PolicyConfig;
+use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::policy_report::{PolicyReport, PolicyRule, PolicyViolation};
+use crate::core::schema_diff::SchemaDiff;
+
+/// マイグレーションポリシー検査サービス
+#[derive(Debug, Default)]
+pub struct MigrationPolicyChecker;
+
+impl MigrationPolicyChecker {
+    /// 新しいCheckerを作成
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// スキーマ差分とステートメント数からポリシーを評価
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - 適用するポリシー設定
+    /// * `schema_diff` - スキーマ差分
+    /// * `destructive_report` - 破壊的変更の検出結果
+    /// * `statement_count` - UP SQLのステートメント数
+    pub fn check(
+        &self,
+        policy: &PolicyConfig,
+        schema_diff: &SchemaDiff,
+        destructive_report: &DestructiveChangeReport,
+        statement_count: usize,
+    ) -> PolicyReport {
+        let mut report = PolicyReport {
+            statement_count,
+            tables_touched: self.collect_tables_touched(schema_diff),
+            mixed_destructive_and_additive: destructive_report.has_destructive_changes()
+                && self.has_additive_changes(schema_diff),
+            ..PolicyReport::default()
+        };
+        report.violations = self.evaluate(policy, &report);
+        report
+    }
+
+    /// 計測済みのレポートをポリシーに照らして評価
+    ///
+    /// apply時に `.meta.yaml` に記録された計測値を現在のポリシーで再評価する際にも使用します。
+    pub fn evaluate(&self, policy: &PolicyConfig, report: &PolicyReport) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(max_statements) = policy.max_statements {
+            if report.statement_count > max_statements {
+                violations.push(PolicyViolation {
+                    rule: PolicyRule::MaxStatements,
+                    message: format!(
+                        "Migration contains {} statements, exceeding policy.max_statements ({})",
+                        report.statement_count, max_statements
+                    ),
+                });
+            }
+        }
+
+        if let Some(max_tables_touched) = policy.max_tables_touched {
+            if report.tables_touched_count() > max_tables_touched {
+                violations.push(PolicyViolation {
+                    rule: PolicyRule::MaxTablesTouched,
+                    message: format!(
+                        "Migration touches {} tables, exceeding policy.max_tables_touched ({})",
+                        report.tables_touched_count(),
+                        max_tables_touched
+                    ),
+                });
+            }
+        }
+
+        if policy.forbid_mixed_destructive_and_additive && report.mixed_destructive_and_additive {
+            violations.push(PolicyViolation {
+                rule: PolicyRule::ForbidMixedDestructiveAndAdditive,
+                message: "Migration mixes destructive and additive changes, which is forbidden by policy.forbid_mixed_destructive_and_additive".to_string(),
+            });
+        }
+
+        violations
+    }
+
+    /// 差分から変更対象のテーブル名を収集
+    fn collect_tables_touched(&self, schema_diff: &SchemaDiff) -> Vec<String> {
+        let mut tables: BTreeSet<String> = BTreeSet::new();
+
+        tables.extend(schema_diff.added_tables.iter().map(|t| t.name.clone()));
+        tables.extend(schema_diff.removed_tables.iter().cloned());
+        tables.extend(
+            schema_diff
+                .modified_tables
+                .iter()
+                .map(|t| t.table_name.clone()),
+        );
+        tables.extend(
+            schema_diff
+                .renamed_tables
+                .iter()
+                .map(|t| t.new_table.name.clone()),
+        );
+
+        tables.into_iter().collect()
+    }
+
+    /// 追加系の変更が含まれているかを判定
+    fn has_additive_changes(&self, schema_diff: &SchemaDiff) -> bool {
+        !schema_diff.added_tables.is_empty()
+            || !schema_diff.added_enums.is_empty()
+            || !schema_diff.added_views.is_empty()
+            || schema_diff.modified_tables.iter().any(|t| {
+                !t.added_columns.is_empty()
+                    || !t.added_indexes.is_empty()
+                    || !t.added_constraints.is_empty()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MigrationPolicyChecker;
+    use crate::core::config::PolicyConfig;
+    use crate::core::destructive_change_report::DestructiveChangeReport;
+    use crate::core::policy_report::PolicyRule;
+    use crate::core::schema::{Column, ColumnType, Table};
+    use crate::core::schema_diff::{SchemaDiff, TableDiff};
+
+    fn diff_with_tables(count: usize) -> SchemaDiff {
+        let mut diff = SchemaDiff::new();
+        for i in 0..count {
+            diff.added_tables.push(Table::new(format!("table_{}", i)));
+        }
+        diff
+    }
+
+    #[test]
+    fn no_violations_without_policy() {
+        let checker = MigrationPolicyChecker::new();
+        let report = checker.check(
+            &PolicyConfig::default(),
+            &diff_with_tables(100),
+            &DestructiveChangeReport::new(),
+            1000,
+        );
+
+        assert!(!report.has_violations());
+        assert_eq!(report.tables_touched_count(), 100);
+        assert_eq!(report.statement_count, 1000);
+    }
+
+    #[test]
+    fn detects_max_statements_and_max_tables_touched() {
+        let checker = MigrationPolicyChecker::new();
+        let policy = PolicyConfig {
+            max_statements: Some(50),
+            max_tables_touched: Some(10),
+            forbid_mixed_destructive_and_additive: false,
+        };
+
+        let report = checker.check(
+            &policy,
+            &diff_with_tables(11),
+            &DestructiveChangeReport::new(),
+            51,
+        );
+
+        let rules: Vec<PolicyRule> = report.violations.iter().map(|v| v.rule).collect();
+        assert_eq!(
+            rules,
+            vec![PolicyRule::MaxStatements, PolicyRule::MaxTablesTouched]
+        );
+    }
+
+    #[test]
+    fn limits_are_inclusive() {
+        let checker = MigrationPolicyChecker::new();
+        let policy = PolicyConfig {
+            max_statements: Some(10),
+            max_tables_touched: Some(10),
+            forbid_mixed_destructive_and_additive: false,
+        };
+
+        let report = checker.check(
+            &policy,
+            &diff_with_tables(10),
+            &DestructiveChangeReport::new(),
+            10,
+        );
+
+        assert!(!report.has_violations());
+    }
+
+    #[test]
+    fn tables_touched_are_deduplicated_from_diff() {
+        let checker = MigrationPolicyChecker::new();
+        let mut diff = SchemaDiff::new();
+        diff.removed_tables.push("legacy".to_string());
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.added_columns.push(Column::new(
+            "email".to_string(),
+            ColumnType::VARCHAR { length: 255 },
+            true,
+        ));
+        diff.modified_tables.push(table_diff);
+        diff.added_tables.push(Table::new("users".to_string()));
+
+        let report = checker.check(
+            &PolicyConfig::default(),
+            &diff,
+            &DestructiveChangeReport::new(),
+            3,
+        );
+
+        assert_eq!(report.tables_touched, vec!["legacy", "users"]);
+    }
+
+    #[test]
+    fn detects_mixed_destructive_and_additive_changes() {
+        let checker = MigrationPolicyChecker::new();
+        let policy = PolicyConfig {
+            forbid_mixed_destructive_and_additive: true,
+            ..PolicyConfig::default()
+        };
+        let mut diff = diff_with_tables(1);
+        diff.removed_tables.push("legacy".to_string());
+        let mut destructive_report = DestructiveChangeReport::new();
+        destructive_report.tables_dropped.push("legacy".to_string());
+
+        let report = checker.check(&policy, &diff, &destructive_report, 2);
+
+        assert!(report.mixed_destructive_and_additive);
+        assert_eq!(
+            report.violations[0].rule,
+            PolicyRule::ForbidMixedDestructiveAndAdditive
+        );
+    }
+
+    #[test]
+    fn destructive_only_changes_are_not_mixed() {
+        let checker = MigrationPolicyChecker::new();
+        let policy = PolicyConfig {
+            forbid_mixed_destructive_and_additive: true,
+            ..PolicyConfig::default()
+        };
+        let mut diff = SchemaDiff::new();
+        diff.removed_tables.push("legacy".to_string());
+        let mut destructive_report = DestructiveChangeReport::new();
+        destructive_report.tables_dropped.push("legacy".to_string());
+
+        let report = checker.check(&policy, &diff, &destructive_report, 1);
+
+        assert!(!report.has_violations());
+    }
+}
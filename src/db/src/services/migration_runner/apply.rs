@@ -0,0 +1,616 @@
+// 計画したマイグレーションの適用
+
+use super::{
+    read_metadata, AppliedMigrationResult, ApplyPlan, ApplyReport, ExecutedStatements,
+    ExecutionError, ExecutionSettings, LocalMigration, MigrationRunner, MigrationRunnerError,
+    RunnerResult, SqlError,
+};
+use crate::adapters::database_migrator::statement_retry::{RetryPolicy, StatementExecutor};
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::adapters::sql_generator::MigrationDirection;
+use crate::core::config::Dialect;
+use crate::core::migration::{
+    truncate_sql_prefix_for_storage, AppliedMigration, DestructiveChangeStatus, Migration,
+    MAX_STORED_SQL_BYTES,
+};
+use crate::services::migration_files::execution_manifest;
+use crate::services::migration_files::sql_output::{migration_sql_output, read_sql_file};
+use crate::services::migration_files::sql_stream::read_sql_statements;
+use crate::services::migration_files::stacked_migrations;
+use crate::services::preflight_queries::{self, PreflightViolation, PREFLIGHT_FILE};
+use anyhow::{anyhow, Context};
+use chrono::Utc;
+use sqlx::{AnyConnection, AnyPool, Row};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+impl MigrationRunner {
+    /// 計画したマイグレーションを順に適用
+    ///
+    /// マイグレーションごとにトランザクション内で実行し、履歴を記録します。
+    /// 失敗した場合は、それまでに適用したマイグレーションをコミットしたまま中断します
+    /// （`single_transaction` が有効な場合はすべてロールバックします）。
+    /// 適用済みのマイグレーションは `.pending_migrations.yaml` の未適用の記録から外します。
+    pub async fn apply(&self, plan: &ApplyPlan) -> RunnerResult<ApplyReport> {
+        self.record_applied(plan)?;
+        if plan.pending.is_empty() {
+            return Ok(ApplyReport::default());
+        }
+
+        let timeouts = self.session_timeouts();
+        let settings = ExecutionSettings {
+            retry: RetryPolicy::from_config(&self.config.statement_retry)
+                .with_deadline(
+                    self.timeout
+                        .map(|timeout| Instant::now() + std::time::Duration::from_secs(timeout)),
+                )
+                .with_lock_timeout(timeouts.lock_timeout),
+            timeouts,
+        };
+        let pool = self.pool().await?;
+        let dialect = self.config.dialect;
+        if dialect == Dialect::SQLite && !settings.timeouts.is_empty() {
+            debug!(
+                lock_timeout = ?settings.timeouts.lock_timeout,
+                statement_timeout = ?settings.timeouts.statement_timeout,
+                "SQLite has no session lock or statement timeout, ignoring"
+            );
+        }
+        let migrator = DatabaseMigratorService::new();
+
+        // single_transaction では全体を1つのトランザクションで実行し、失敗すればすべて取り消す
+        let single_transaction = self.uses_single_transaction();
+        if self.single_transaction && !single_transaction {
+            warn!("single_transaction is not supported on MySQL; applying each migration in its own transaction");
+        }
+        let mut batch_tx = if single_transaction {
+            Some(
+                pool.begin()
+                    .await
+                    .with_context(|| "Failed to start transaction")?,
+            )
+        } else {
+            None
+        };
+        // 中断時に適用済みとして報告するマイグレーション（single_transaction ではロールバックされる）
+        let completed = |report: ApplyReport| -> Vec<AppliedMigration> {
+            if single_transaction {
+                Vec::new()
+            } else {
+                report.migrations.into_iter().map(|m| m.migration).collect()
+            }
+        };
+
+        let mut report = ApplyReport::default();
+        for migration in &plan.pending {
+            let start_time = Utc::now();
+            let version = &migration.version;
+            info!(version = %version, description = %migration.description, "Applying migration");
+
+            let metadata = read_metadata(&migration.dir)?;
+
+            // up.sqlを読み込み（BOM・改行コード・書き出し時の終端文字列を正規化）
+            // 閾値を超える大きなファイルは全体を読み込まず、ステートメント単位で逐次実行する
+            let mut up_statements = read_sql_statements(
+                &migration.dir.join("up.sql"),
+                &metadata.sql_output(&self.config.sql_output),
+                self.config.stream_sql_threshold(),
+            )?;
+
+            // 破壊的変更の判定
+            if metadata.destructive_change_status() == DestructiveChangeStatus::Present
+                && !self.allow_destructive
+            {
+                return Err(MigrationRunnerError::DestructiveChanges {
+                    version: version.clone(),
+                    report: Box::new(metadata.destructive_changes),
+                    completed: completed(report),
+                });
+            }
+
+            // 既存のデータが追加する制約に違反しないかを確認
+            if let Some(threshold) = self.preflight_threshold {
+                let violations = match batch_tx.as_mut() {
+                    Some(tx) => self.run_preflight_queries(tx, migration).await?,
+                    None => {
+                        let mut conn = pool
+                            .acquire()
+                            .await
+                            .with_context(|| "Failed to acquire a database connection")?;
+                        self.run_preflight_queries(&mut conn, migration).await?
+                    }
+                };
+                let violations: Vec<PreflightViolation> = violations
+                    .into_iter()
+                    .filter(|violation| violation.count as u64 > threshold)
+                    .collect();
+                if !violations.is_empty() {
+                    return Err(MigrationRunnerError::PreflightViolations {
+                        version: version.clone(),
+                        violations,
+                        threshold,
+                        completed: completed(report),
+                    });
+                }
+            }
+
+            // 保存用のSQLはファイルの内容をそのまま使う
+            let mut warnings = Vec::new();
+            let stored_sql = if self.config.store_applied_sql {
+                let stored_up = read_sql_for_storage(&migration.dir.join("up.sql"))?;
+                let down_path = migration.dir.join("down.sql");
+                let stored_down = if down_path.exists() {
+                    Some(read_sql_for_storage(&down_path)?)
+                } else {
+                    None
+                };
+                for warning in
+                    storage_truncation_warnings(version, &stored_up, stored_down.as_ref())
+                {
+                    warn!("{}", warning);
+                    warnings.push(warning);
+                }
+                Some((stored_up.sql, stored_down.map(|down| down.sql)))
+            } else {
+                None
+            };
+
+            // トランザクション内でマイグレーションを実行
+            let stored_sql = stored_sql
+                .as_ref()
+                .map(|(up, down)| (up.as_str(), down.as_deref()));
+            let result = match batch_tx.as_mut() {
+                Some(tx) => {
+                    self.execute_migration(
+                        tx,
+                        &migrator,
+                        migration,
+                        &mut up_statements,
+                        &metadata.checksum,
+                        stored_sql,
+                        &settings,
+                    )
+                    .await
+                }
+                None => {
+                    self.apply_migration_with_transaction(
+                        &pool,
+                        &migrator,
+                        migration,
+                        &mut up_statements,
+                        &metadata.checksum,
+                        stored_sql,
+                        &settings,
+                    )
+                    .await
+                }
+            };
+            let executed = match result {
+                Ok(executed) => executed,
+                Err(source) => {
+                    if let Some(progress) = &self.progress {
+                        progress.failed(migration);
+                    }
+                    let executed: Vec<AppliedMigration> =
+                        report.migrations.into_iter().map(|m| m.migration).collect();
+                    let (completed, rolled_back) = if single_transaction {
+                        (Vec::new(), executed)
+                    } else {
+                        (executed, Vec::new())
+                    };
+                    return Err(MigrationRunnerError::Apply {
+                        version: version.clone(),
+                        completed,
+                        rolled_back,
+                        source: Box::new(source),
+                    });
+                }
+            };
+
+            // 生成時から up.sql が編集されていると、適用後のチェックサム検証で変更ありと判定される
+            if let (Some(expected), Some(actual)) =
+                (&metadata.up_sql_checksum, up_statements.checksum())
+            {
+                if expected != actual {
+                    let warning = format!(
+                        "up.sql of migration {} does not match up_sql_checksum in .meta.yaml; checksum verification will report it as modified. Update up_sql_checksum if the edit was intentional.",
+                        version
+                    );
+                    warn!("{}", warning);
+                    warnings.push(warning);
+                }
+            }
+
+            // single_transaction ではコミット後にまとめて記録から外す
+            if !single_transaction {
+                warnings.extend(self.mark_applied_versions([version.as_str()]));
+            }
+
+            let end_time = Utc::now();
+            let applied = AppliedMigration::new(
+                version.clone(),
+                migration.description.clone(),
+                end_time,
+                end_time.signed_duration_since(start_time),
+            );
+            if let Some(progress) = &self.progress {
+                progress.applied(&applied);
+            }
+            report.migrations.push(AppliedMigrationResult {
+                migration: applied,
+                statement_checksums: executed.checksums,
+                retries: executed.retries,
+                metadata,
+                warnings,
+            });
+        }
+
+        if let Some(tx) = batch_tx {
+            tx.commit()
+                .await
+                .with_context(|| "Failed to commit transaction")?;
+            let warning = self.mark_applied_versions(
+                report
+                    .migrations
+                    .iter()
+                    .map(|result| result.migration.version.as_str()),
+            );
+            if let Some(result) = report.migrations.last_mut() {
+                result.warnings.extend(warning);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 計画時点で適用済みのマイグレーションを `.pending_migrations.yaml` の記録から外す
+    ///
+    /// 他の方法で適用されたマイグレーションも generate が未適用と報告しないよう、
+    /// 未適用のマイグレーションがない場合も呼び出します。
+    pub fn record_applied(&self, plan: &ApplyPlan) -> RunnerResult<()> {
+        stacked_migrations::mark_applied(
+            &self.migrations_dir,
+            plan.applied.iter().map(|record| record.version.as_str()),
+        )?;
+        Ok(())
+    }
+
+    /// 適用したマイグレーションを `.pending_migrations.yaml` の記録から外す
+    ///
+    /// データベースへの適用はコミット済みのため、記録に失敗しても警告を返すだけにする。
+    fn mark_applied_versions<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a str>,
+    ) -> Option<String> {
+        stacked_migrations::mark_applied(&self.migrations_dir, versions)
+            .err()
+            .map(|e| {
+                let warning = format!(
+                    "Warning: Failed to update {}: {:#}",
+                    stacked_migrations::PENDING_MIGRATIONS_FILE,
+                    e
+                );
+                warn!("{}", warning);
+                warning
+            })
+    }
+
+    /// マイグレーションの事前確認クエリを実行し、制約ごとの違反件数を返す
+    ///
+    /// preflight.sql がないマイグレーションは空のリストを返します。
+    async fn run_preflight_queries(
+        &self,
+        conn: &mut AnyConnection,
+        migration: &LocalMigration,
+    ) -> RunnerResult<Vec<PreflightViolation>> {
+        let path = migration.dir.join(PREFLIGHT_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = read_sql_file(
+            &path,
+            &migration_sql_output(&migration.dir, &self.config.sql_output)?,
+        )?;
+
+        let mut violations = Vec::new();
+        for query in preflight_queries::parse_preflight_file(&content) {
+            debug!(version = %migration.version, label = %query.label, "Running preflight query");
+            let row = sqlx::query(&query.sql)
+                .fetch_one(&mut *conn)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to run preflight query of migration {} ({})\nSQL: {}",
+                        migration.version, query.label, query.sql
+                    )
+                })?;
+            let count = row.try_get::<i64, _>(0).with_context(|| {
+                format!(
+                    "Preflight query of migration {} ({}) did not return a row count",
+                    migration.version, query.label
+                )
+            })?;
+            violations.push(PreflightViolation {
+                label: query.label,
+                count,
+            });
+        }
+        Ok(violations)
+    }
+
+    /// マイグレーションをトランザクション内で適用
+    ///
+    /// 実行したステートメントのチェックサムを実行順に返し、履歴とともに保存する。
+    #[allow(clippy::too_many_arguments)]
+    async fn apply_migration_with_transaction(
+        &self,
+        pool: &AnyPool,
+        migrator: &DatabaseMigratorService,
+        migration: &LocalMigration,
+        up_statements: impl Iterator<Item = anyhow::Result<String>>,
+        checksum: &str,
+        stored_sql: Option<(&str, Option<&str>)>,
+        settings: &ExecutionSettings,
+    ) -> Result<ExecutedStatements, ExecutionError> {
+        // トランザクションを開始
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        let executed = self
+            .execute_migration(
+                &mut tx,
+                migrator,
+                migration,
+                up_statements,
+                checksum,
+                stored_sql,
+                settings,
+            )
+            .await?;
+
+        // トランザクションをコミット
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+
+        Ok(executed)
+    }
+
+    /// 開始済みのトランザクション内でマイグレーションSQLを実行し、履歴を記録
+    ///
+    /// 一時的なエラーで失敗したステートメントは `settings` の方針に従って再試行する。
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_migration(
+        &self,
+        tx: &mut AnyConnection,
+        migrator: &DatabaseMigratorService,
+        migration: &LocalMigration,
+        up_statements: impl Iterator<Item = anyhow::Result<String>>,
+        checksum: &str,
+        stored_sql: Option<(&str, Option<&str>)>,
+        settings: &ExecutionSettings,
+    ) -> Result<ExecutedStatements, ExecutionError> {
+        let dialect = self.config.dialect;
+        let version = migration.version.as_str();
+
+        let started = Instant::now();
+
+        // マイグレーションSQLを文単位で実行
+        let executed = execute_statements(
+            &mut *tx,
+            migrator,
+            dialect,
+            version,
+            up_statements,
+            settings,
+        )
+        .await?;
+        // 実行時間には履歴の記録にかかった時間を含めない
+        let execution_time_ms = started.elapsed().as_millis() as u64;
+
+        // マイグレーション履歴を記録（パラメータバインディング使用）
+        let record = Migration::new(
+            version.to_string(),
+            migration.description.clone(),
+            checksum.to_string(),
+        );
+        let (record_sql, params) = migrator.generate_record_migration_query(&record, dialect);
+
+        let mut query = sqlx::query(&record_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+
+        query.execute(&mut *tx).await.map_err(|e| {
+            anyhow!(
+                "Failed to record migration history: SQL={}, Error={}",
+                record_sql,
+                e
+            )
+        })?;
+
+        // 実行したステートメントのチェックサムを実行順に保存
+        let (manifest_sql, params) = migrator.generate_store_statement_checksums_query(
+            dialect,
+            version,
+            &executed.checksums,
+        );
+        let mut query = sqlx::query(&manifest_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        query.execute(&mut *tx).await.map_err(|e| {
+            anyhow!(
+                "Failed to store statement checksums: SQL={}, Error={}",
+                manifest_sql,
+                e
+            )
+        })?;
+
+        // 実行時間と適用ユーザーを記録
+        let (audit_sql, params) =
+            migrator.generate_store_audit_query(dialect, version, execution_time_ms);
+        let mut query = sqlx::query(&audit_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        query.execute(&mut *tx).await.map_err(|e| {
+            anyhow!(
+                "Failed to store migration audit: SQL={}, Error={}",
+                audit_sql,
+                e
+            )
+        })?;
+
+        // 適用SQLを保存（store_applied_sql: true の場合）
+        if let Some((stored_up_sql, stored_down_sql)) = stored_sql {
+            let (store_sql, params) = migrator.generate_store_applied_sql_query(
+                dialect,
+                version,
+                stored_up_sql,
+                stored_down_sql,
+            );
+
+            let mut query = sqlx::query(&store_sql);
+            for param in params {
+                query = query.bind(param);
+            }
+
+            query.execute(&mut *tx).await.map_err(|e| {
+                anyhow!(
+                    "Failed to store applied migration SQL: SQL={}, Error={}",
+                    store_sql,
+                    e
+                )
+            })?;
+        }
+
+        Ok(executed)
+    }
+}
+
+/// マイグレーションSQLを文単位で実行
+///
+/// 最初のステートメントの前にセッションのタイムアウトを設定し、一時的なエラーで失敗した
+/// ステートメントは再試行する。実行したステートメントのチェックサムを実行順に返す。
+pub(super) async fn execute_statements<E: StatementExecutor + ?Sized>(
+    executor: &mut E,
+    migrator: &DatabaseMigratorService,
+    dialect: Dialect,
+    version: &str,
+    statements: impl Iterator<Item = anyhow::Result<String>>,
+    settings: &ExecutionSettings,
+) -> Result<ExecutedStatements, ExecutionError> {
+    for sql in migrator.generate_session_timeout_sql(dialect, &settings.timeouts) {
+        executor
+            .execute_statement(&sql)
+            .await
+            .with_context(|| format!("Failed to set session timeout: {}", sql))?;
+    }
+
+    let mut checksums = Vec::new();
+    let mut retries = 0;
+    for statement in statements {
+        let statement = statement?;
+        // SQLite: 既にトランザクション内なので、ネストを防ぐために
+        // BEGIN TRANSACTION/COMMIT をスキップする
+        // (sqlite_table_recreator がこれらを生成するが、apply は既にトランザクションを開始している)
+        if execution_manifest::is_skipped_statement(&statement, dialect) {
+            debug!(
+                statement = %statement,
+                "Skipping transaction control statement (already in transaction)"
+            );
+            continue;
+        }
+
+        match migrator
+            .execute_statement_with_retry(executor, dialect, &statement, &settings.retry)
+            .await
+        {
+            Ok(statement_retries) => retries += statement_retries,
+            Err(failure) => {
+                return Err(SqlError {
+                    version: version.to_string(),
+                    direction: MigrationDirection::Up,
+                    statement,
+                    attempts: failure.attempts,
+                    timeout: settings.timeouts.exceeded(dialect, &failure.source),
+                    source: failure.source,
+                }
+                .into());
+            }
+        }
+        checksums.push(execution_manifest::statement_checksum(&statement));
+    }
+    Ok(ExecutedStatements { checksums, retries })
+}
+
+/// 保存用に読み込んだSQLファイル
+struct StoredSqlFile {
+    /// 保存するSQL（上限を超える場合は切り詰め済み）
+    sql: String,
+    /// ファイルのバイト数
+    size: usize,
+}
+
+/// 保存用にSQLファイルをファイルの内容のまま読み込む
+///
+/// 保存上限を超えるファイルは先頭部分だけを読み込み、切り詰めた形で返す。
+fn read_sql_for_storage(path: &Path) -> anyhow::Result<StoredSqlFile> {
+    let file =
+        fs::File::open(path).with_context(|| format!("Failed to read SQL file: {:?}", path))?;
+    let size = file
+        .metadata()
+        .with_context(|| format!("Failed to read SQL file: {:?}", path))?
+        .len() as usize;
+
+    let mut prefix = Vec::new();
+    file.take(MAX_STORED_SQL_BYTES as u64)
+        .read_to_end(&mut prefix)
+        .with_context(|| format!("Failed to read SQL file: {:?}", path))?;
+
+    let sql = if size <= MAX_STORED_SQL_BYTES {
+        String::from_utf8(prefix).with_context(|| format!("Failed to read SQL file: {:?}", path))?
+    } else {
+        // 文字の途中で切れた末尾は捨てる
+        let valid_up_to = match std::str::from_utf8(&prefix) {
+            Ok(_) => prefix.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read SQL file: {:?}", path))
+            }
+        };
+        let prefix = std::str::from_utf8(&prefix[..valid_up_to])
+            .with_context(|| format!("Failed to read SQL file: {:?}", path))?;
+        truncate_sql_prefix_for_storage(prefix, size)
+    };
+
+    Ok(StoredSqlFile { sql, size })
+}
+
+/// 保存上限を超えて切り詰められるSQLの警告を生成
+fn storage_truncation_warnings(
+    version: &str,
+    up: &StoredSqlFile,
+    down: Option<&StoredSqlFile>,
+) -> Vec<String> {
+    [("up.sql", Some(up)), ("down.sql", down)]
+        .into_iter()
+        .filter_map(|(file, stored)| Some((file, stored?)))
+        .filter(|(_, stored)| stored.size > MAX_STORED_SQL_BYTES)
+        .map(|(file, stored)| {
+            format!(
+                "Warning: {} of migration {} is {} bytes; only the first {} bytes are stored in schema_migrations (marked as truncated).",
+                file,
+                version,
+                stored.size,
+                MAX_STORED_SQL_BYTES
+            )
+        })
+        .collect()
+}
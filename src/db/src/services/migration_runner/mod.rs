@@ -0,0 +1,889 @@
+// マイグレーション実行API
+//
+// apply / rollback / status コマンドの実行手順をライブラリとして提供します。
+// アプリケーションの起動時に未適用のマイグレーションを適用する場合など、strata を組み込んで
+// 使う場合も CLI と同じ手順（チェックサム検証・トランザクション内での実行・履歴の記録）で実行されます。
+// 結果は整形済みの文字列ではなく型付きの値で返し、表示は呼び出し側に任せます。
+//
+// 各操作は次の2段階に分かれています。CLIは計画を表示（dry-run）してから実行します。
+// - 計画（`plan_apply` / `plan_rollback`）: ファイルと履歴を照合し、実行するマイグレーションを決める
+// - 実行（`apply` / `execute_rollback`）: 計画したマイグレーションを1件ずつトランザクション内で実行する
+//   （`single_transaction` を有効にすると、PostgreSQL/SQLite ではすべてを1つのトランザクションで実行する）
+// `apply_all` / `apply_to` / `rollback` / `rollback_to` は両方をまとめて行います。
+
+mod apply;
+mod plan;
+mod rollback;
+mod status;
+
+#[cfg(test)]
+mod tests;
+
+use crate::adapters::database::ConnectionManager;
+use crate::adapters::database_migrator::session_timeouts::{SessionTimeouts, TimeoutExceeded};
+use crate::adapters::database_migrator::statement_retry::RetryPolicy;
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::adapters::sql_generator::MigrationDirection;
+use crate::core::config::Config;
+use crate::core::destructive_change_report::DestructiveChangeReport;
+use crate::core::migration::{AppliedMigration, MigrationMetadata, MigrationRecord};
+use crate::services::database_config_resolver::DatabaseConfigResolver;
+use crate::services::migration_files::migration_checksum::ChecksumStatus;
+use crate::services::migration_files::migration_loader::{self, IncompleteMigration};
+use crate::services::migration_files::sql_parser::split_sql_statements;
+use crate::services::migration_files::stacked_migrations::PendingMigration;
+use crate::services::migration_health_check::{self, HealthIssue};
+use crate::services::preflight_queries::{self, PreflightViolation};
+use anyhow::Context;
+use chrono::Duration;
+use regex::Regex;
+use sqlx::AnyPool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+pub use plan::format_out_of_order_conflicts;
+use plan::pending_migrations;
+
+/// 破壊的 SQL 操作を検出するための共通正規表現
+///
+/// 検出対象:
+/// - DROP TABLE/COLUMN/TYPE/INDEX/CONSTRAINT/SCHEMA/DATABASE
+/// - ALTER ... DROP/RENAME
+/// - RENAME TABLE/COLUMN
+/// - TRUNCATE TABLE
+/// - DELETE FROM (WHERE句の有無に関わらず潜在的に破壊的)
+pub static DESTRUCTIVE_SQL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(DROP\s+(TABLE|COLUMN|TYPE|INDEX|CONSTRAINT|SCHEMA|DATABASE)|ALTER\s+.*\s+(DROP|RENAME)|RENAME\s+(TABLE|COLUMN)|TRUNCATE\s+TABLE|DELETE\s+FROM)\b")
+        .expect("Invalid destructive SQL regex pattern")
+});
+
+/// CHECK制約を含むSQLを検出するための正規表現
+pub static CHECK_CONSTRAINT_SQL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bCHECK\s*\(").expect("Invalid CHECK constraint regex pattern")
+});
+
+/// マイグレーションSQLの実行エラー
+#[derive(Debug, Error)]
+#[error("Failed to execute migration {}SQL: {version}{}\nSQL: {statement}{}", direction_label(.direction), attempts_label(*.attempts), timeout_label(.timeout))]
+pub struct SqlError {
+    /// マイグレーションバージョン
+    pub version: String,
+    /// 実行していたSQLの方向（up.sql / down.sql）
+    pub direction: MigrationDirection,
+    /// 失敗したステートメント
+    pub statement: String,
+    /// ステートメントを実行した回数（一時的なエラーで再試行した場合は2以上）
+    pub attempts: u32,
+    /// 失敗の原因になったセッションのタイムアウト（`--lock-timeout` / `--statement-timeout`）
+    pub timeout: Option<TimeoutExceeded>,
+    /// データベースのエラー
+    #[source]
+    pub source: sqlx::Error,
+}
+
+fn direction_label(direction: &MigrationDirection) -> &'static str {
+    match direction {
+        MigrationDirection::Up => "",
+        MigrationDirection::Down => "down ",
+    }
+}
+
+fn timeout_label(timeout: &Option<TimeoutExceeded>) -> String {
+    timeout
+        .map(|timeout| format!("\n{}", timeout))
+        .unwrap_or_default()
+}
+
+fn attempts_label(attempts: u32) -> String {
+    if attempts > 1 {
+        format!(" (after {} attempts)", attempts)
+    } else {
+        String::new()
+    }
+}
+
+/// マイグレーション1件の実行エラー
+#[derive(Debug, Error)]
+pub enum ExecutionError {
+    /// SQLの実行に失敗した
+    #[error(transparent)]
+    Sql(#[from] SqlError),
+
+    /// 履歴の記録やトランザクションの操作に失敗した
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// マイグレーション実行APIのエラー
+#[derive(Debug, Error)]
+pub enum MigrationRunnerError {
+    /// マイグレーションの適用に失敗した（このマイグレーションの変更はロールバック済み）
+    #[error("Failed to apply migration {version} ({} applied, failed on migration #{})", .completed.len(), .completed.len() + .rolled_back.len() + 1)]
+    Apply {
+        /// 失敗したマイグレーションのバージョン
+        version: String,
+        /// 失敗するまでに適用したマイグレーション
+        completed: Vec<AppliedMigration>,
+        /// 失敗したマイグレーションとともにロールバックしたマイグレーション（`single_transaction` のみ）
+        rolled_back: Vec<AppliedMigration>,
+        /// 失敗の原因
+        #[source]
+        source: Box<ExecutionError>,
+    },
+
+    /// マイグレーションのロールバックに失敗した（このマイグレーションの変更はロールバック済み）
+    #[error("Failed to rollback migration {version} ({} rolled back, failed on rollback #{})", .completed.len(), .completed.len() + 1)]
+    Rollback {
+        /// 失敗したマイグレーションのバージョン
+        version: String,
+        /// 失敗するまでにロールバックしたマイグレーション
+        completed: Vec<AppliedMigration>,
+        /// 失敗の原因
+        #[source]
+        source: Box<ExecutionError>,
+    },
+
+    /// 破壊的変更を含むマイグレーションを適用しようとした（`allow_destructive` が無効）
+    #[error(
+        "Migration {version} contains destructive changes. Enable allow_destructive to apply it."
+    )]
+    DestructiveChanges {
+        /// マイグレーションのバージョン
+        version: String,
+        /// `.meta.yaml` に記録された破壊的変更
+        report: Box<DestructiveChangeReport>,
+        /// 中断するまでに適用したマイグレーション
+        completed: Vec<AppliedMigration>,
+    },
+
+    /// 事前確認クエリ（preflight.sql）で制約に違反する行が許容数を超えた
+    #[error(
+        "Migration {version} would add constraints that existing rows violate (more than {threshold} row(s) allowed):\n{}",
+        preflight_queries::format_violations(.violations)
+    )]
+    PreflightViolations {
+        /// マイグレーションのバージョン
+        version: String,
+        /// 許容数を超えた制約ごとの違反件数
+        violations: Vec<PreflightViolation>,
+        /// 許容する違反件数
+        threshold: u64,
+        /// 中断するまでに適用したマイグレーション
+        completed: Vec<AppliedMigration>,
+    },
+
+    /// 破壊的なSQLを含むロールバックを実行しようとした（`allow_destructive` が無効）
+    #[error("Rollback of migration(s) {} contains destructive changes. Enable allow_destructive to roll back.", .versions.join(", "))]
+    DestructiveRollback {
+        /// 破壊的なSQLを含むマイグレーションのバージョン
+        versions: Vec<String>,
+    },
+
+    /// down.sql に実行可能なステートメントがないマイグレーションをロールバックしようとした
+    /// （`allow_empty_down` が無効）
+    #[error("Rollback of migration(s) {} refused: down.sql has no executable statements (only comments). Rolling back would remove them from the history without reverting their changes. Write the down.sql, or enable allow_empty_down to roll back anyway.", .versions.join(", "))]
+    EmptyDownRollback {
+        /// down.sql が空のマイグレーションのバージョン
+        versions: Vec<String>,
+    },
+
+    /// 指定したマイグレーションより前の未適用マイグレーションが同じテーブルを変更する
+    /// （`force_out_of_order` が無効）
+    #[error(
+        "Migration {version} cannot be applied out of order; earlier pending migrations touch the same tables:\n{}\nApply them first, or use --force-out-of-order to apply {version} anyway.",
+        format_out_of_order_conflicts(.conflicts)
+    )]
+    OutOfOrderConflicts {
+        /// 単独で適用しようとしたマイグレーションのバージョン
+        version: String,
+        /// 競合する未適用マイグレーション（バージョン順）
+        conflicts: Vec<OutOfOrderConflict>,
+    },
+
+    /// 中断されたマイグレーションのセッションがロックを保持している（`break_stale_lock` が無効）
+    #[error("Stale locks from an interrupted migration were found:\n{}", migration_health_check::format_issues(.issues))]
+    StaleLocks {
+        /// 検出したセッション
+        issues: Vec<HealthIssue>,
+    },
+
+    /// ファイルの読み込み・接続・検証などのエラー
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl MigrationRunnerError {
+    /// SQLの実行に失敗した場合はそのエラーを返す
+    pub fn sql_error(&self) -> Option<&SqlError> {
+        match self {
+            Self::Apply { source, .. } | Self::Rollback { source, .. } => match source.as_ref() {
+                ExecutionError::Sql(error) => Some(error),
+                ExecutionError::Other(_) => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+type RunnerResult<T> = Result<T, MigrationRunnerError>;
+
+/// マイグレーションディレクトリ内のマイグレーション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalMigration {
+    /// バージョン
+    pub version: String,
+    /// 説明
+    pub description: String,
+    /// マイグレーションのディレクトリ
+    pub dir: PathBuf,
+}
+
+/// マイグレーションディレクトリの読み込み結果
+#[derive(Debug, Clone)]
+pub struct LocalMigrations {
+    /// マイグレーション（バージョン順、ファイルが欠けたものを含む）
+    pub migrations: Vec<LocalMigration>,
+    /// 必要なファイルが欠けたマイグレーション
+    pub incomplete: Vec<IncompleteMigration>,
+    /// 未来の日時のバージョンの警告
+    pub future_warnings: Vec<String>,
+}
+
+impl LocalMigrations {
+    /// マイグレーションが1件もないか
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    /// `migration_loader` の関数に渡す形式
+    fn entries(&self) -> Vec<(String, String, PathBuf)> {
+        self.migrations
+            .iter()
+            .map(|m| (m.version.clone(), m.description.clone(), m.dir.clone()))
+            .collect()
+    }
+
+    fn find(&self, version: &str) -> Option<&LocalMigration> {
+        self.migrations.iter().find(|m| m.version == version)
+    }
+}
+
+/// 適用計画
+#[derive(Debug, Clone)]
+pub struct ApplyPlan {
+    /// 適用済みマイグレーションの履歴
+    pub applied: Vec<MigrationRecord>,
+    /// 適用するマイグレーション（バージョン順）
+    pub pending: Vec<LocalMigration>,
+    /// 警告（CHECK制約を強制しないMySQLサーバー）
+    pub warnings: Vec<String>,
+    /// 適用済みマイグレーションのチェックサム検証の警告
+    pub checksum_warnings: Vec<String>,
+}
+
+/// 単独で適用するマイグレーションと競合する、先行する未適用マイグレーション
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfOrderConflict {
+    /// 先行する未適用マイグレーションのバージョン
+    pub version: String,
+    /// 両方が変更するテーブル（どちらかの `.meta.yaml` に記録がなく判定できない場合は None）
+    pub tables: Option<Vec<String>>,
+}
+
+/// 適用したマイグレーション
+#[derive(Debug, Clone)]
+pub struct AppliedMigrationResult {
+    /// バージョン・説明・適用日時・実行時間
+    pub migration: AppliedMigration,
+    /// 実行したステートメントのチェックサム（実行順）
+    pub statement_checksums: Vec<String>,
+    /// 一時的なエラーでステートメントを再試行した回数の合計
+    pub retries: u32,
+    /// `.meta.yaml` の内容
+    pub metadata: MigrationMetadata,
+    /// 警告（履歴に保存するSQLの切り詰め）
+    pub warnings: Vec<String>,
+}
+
+/// マイグレーションSQLの実行設定
+struct ExecutionSettings {
+    /// 一時的なエラーの再試行方針
+    retry: RetryPolicy,
+    /// apply 中のセッションに設定するタイムアウト
+    timeouts: SessionTimeouts,
+}
+
+/// マイグレーションSQLの実行結果
+struct ExecutedStatements {
+    /// 実行したステートメントのチェックサム（実行順）
+    checksums: Vec<String>,
+    /// 一時的なエラーでステートメントを再試行した回数の合計
+    retries: u32,
+}
+
+/// 適用結果
+#[derive(Debug, Clone, Default)]
+pub struct ApplyReport {
+    /// 適用したマイグレーション（適用順）
+    pub migrations: Vec<AppliedMigrationResult>,
+}
+
+impl ApplyReport {
+    /// 適用したバージョン
+    pub fn applied_versions(&self) -> Vec<&str> {
+        self.migrations
+            .iter()
+            .map(|m| m.migration.version.as_str())
+            .collect()
+    }
+
+    /// 合計実行時間
+    pub fn total_duration(&self) -> Duration {
+        self.migrations
+            .iter()
+            .fold(Duration::zero(), |total, m| total + m.migration.duration)
+    }
+}
+
+/// 適用の進捗の通知先
+///
+/// CLI はマイグレーションごとの進捗行の表示に使います。
+pub trait ApplyProgress: std::fmt::Debug + Send + Sync {
+    /// マイグレーションを1件適用した（`single_transaction` ではコミット前に通知します）
+    fn applied(&self, migration: &AppliedMigration);
+
+    /// マイグレーションの実行に失敗した
+    fn failed(&self, migration: &LocalMigration);
+}
+
+/// ロールバックの対象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollbackTarget {
+    /// 最新のものから指定件数
+    Steps(u32),
+    /// 指定バージョンより新しいものすべて（指定バージョンが最新の適用済みマイグレーションになる）
+    Version(String),
+}
+
+/// ロールバックするマイグレーション
+#[derive(Debug, Clone)]
+pub struct RollbackMigration {
+    /// 履歴の記録
+    pub record: MigrationRecord,
+    /// マイグレーションのディレクトリ
+    pub dir: PathBuf,
+    /// 実行する down.sql（正規化済み）
+    pub down_sql: String,
+    /// 破壊的なSQLを含むか
+    pub destructive: bool,
+    /// ロールバック保護により削除されずに残るテーブル
+    pub protected_tables: Vec<String>,
+}
+
+impl RollbackMigration {
+    /// down.sql に実行可能なステートメントがない（空、またはコメントのみ）か
+    pub fn has_empty_down(&self) -> bool {
+        split_sql_statements(&self.down_sql).is_empty()
+    }
+}
+
+/// ロールバック計画
+#[derive(Debug, Clone)]
+pub struct RollbackPlan {
+    /// 適用済みマイグレーションの履歴
+    pub applied: Vec<MigrationRecord>,
+    /// ロールバックするマイグレーション（ロールバック順 = 新しいものから）
+    pub migrations: Vec<RollbackMigration>,
+}
+
+impl RollbackPlan {
+    /// 破壊的なSQLを含むか
+    pub fn has_destructive(&self) -> bool {
+        self.migrations.iter().any(|m| m.destructive)
+    }
+
+    /// down.sql が空のマイグレーション
+    pub fn empty_downs(&self) -> Vec<&RollbackMigration> {
+        self.migrations
+            .iter()
+            .filter(|m| m.has_empty_down())
+            .collect()
+    }
+}
+
+/// ロールバックしたマイグレーション
+#[derive(Debug, Clone)]
+pub struct RolledBackMigrationResult {
+    /// バージョン・説明・ロールバック日時・実行時間
+    pub migration: AppliedMigration,
+    /// ロールバック保護により削除されずに残ったテーブル
+    pub protected_tables: Vec<String>,
+}
+
+/// ロールバック結果
+#[derive(Debug, Clone, Default)]
+pub struct RollbackReport {
+    /// ロールバックしたマイグレーション（ロールバック順）
+    pub migrations: Vec<RolledBackMigrationResult>,
+    /// 警告（残ったテーブルの定義を記録できなかった場合）
+    pub warnings: Vec<String>,
+}
+
+impl RollbackReport {
+    /// ロールバックしたバージョン
+    pub fn rolled_back_versions(&self) -> Vec<&str> {
+        self.migrations
+            .iter()
+            .map(|m| m.migration.version.as_str())
+            .collect()
+    }
+}
+
+/// マイグレーションの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// 適用済み
+    Applied,
+    /// 未適用
+    Pending,
+    /// 適用済みだがローカルにファイルがない
+    Orphaned,
+}
+
+/// マイグレーション1件の状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// バージョン
+    pub version: String,
+    /// 説明
+    pub description: String,
+    /// 状態
+    pub state: MigrationState,
+    /// 適用済みマイグレーションのチェックサムの検証結果（未適用・孤立の場合は None）
+    pub checksum_status: Option<ChecksumStatus>,
+    /// バージョン順より先に適用されたか（より前のバージョンが未適用、または後から適用された）
+    pub out_of_order: bool,
+}
+
+/// マイグレーションの状態の一覧
+#[derive(Debug, Clone)]
+pub struct StatusReport {
+    /// マイグレーション（バージョン順、孤立したものを含む）
+    pub migrations: Vec<MigrationStatus>,
+    /// 適用済みマイグレーションの履歴
+    pub applied: Vec<MigrationRecord>,
+}
+
+impl StatusReport {
+    /// 指定した状態のマイグレーションの数
+    pub fn count(&self, state: MigrationState) -> usize {
+        self.migrations.iter().filter(|m| m.state == state).count()
+    }
+
+    /// 最新の適用済みマイグレーションのバージョン
+    pub fn current_version(&self) -> Option<&str> {
+        self.applied.last().map(|record| record.version.as_str())
+    }
+}
+
+/// マイグレーション実行API
+///
+/// 設定・環境名・マイグレーションディレクトリを指定して作成します。
+/// `with_pool` で既存の接続プールを渡さない場合は、設定の環境に接続します
+/// （環境変数による上書きを含む）。作成した接続は `close` で閉じます。
+///
+/// # Examples
+///
+/// 既存の接続プールを使って、起動時に未適用のマイグレーションを適用する:
+///
+/// ```no_run
+/// use sqlx::any::{install_default_drivers, AnyPoolOptions};
+/// use std::path::Path;
+/// use strata_db::services::config_loader::ConfigLoader;
+/// use strata_db::services::migration_runner::MigrationRunner;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// install_default_drivers();
+/// let pool = AnyPoolOptions::new()
+///     .connect("postgres://app@localhost:5432/app")
+///     .await?;
+///
+/// let config = ConfigLoader::from_file(Path::new(".strata.yaml"))?;
+/// let runner = MigrationRunner::new(config, "production", "migrations").with_pool(pool.clone());
+///
+/// let report = runner.apply_all().await?;
+/// for applied in &report.migrations {
+///     println!(
+///         "applied {} ({}ms)",
+///         applied.migration.version,
+///         applied.migration.duration.num_milliseconds()
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigrationRunner {
+    config: Config,
+    env: String,
+    migrations_dir: PathBuf,
+    pool: Option<AnyPool>,
+    connections: ConnectionManager,
+    timeout: Option<u64>,
+    lock_timeout: Option<u64>,
+    statement_timeout: Option<u64>,
+    allow_destructive: bool,
+    allow_empty_down: bool,
+    skip_checksum_verification: bool,
+    break_stale_lock: bool,
+    preflight_threshold: Option<u64>,
+    single_transaction: bool,
+    progress: Option<Arc<dyn ApplyProgress>>,
+}
+
+impl MigrationRunner {
+    /// 新しいMigrationRunnerを作成
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - 設定
+    /// * `env` - 対象環境
+    /// * `migrations_dir` - マイグレーションディレクトリ
+    pub fn new(config: Config, env: impl Into<String>, migrations_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config,
+            env: env.into(),
+            migrations_dir: migrations_dir.into(),
+            pool: None,
+            connections: ConnectionManager::new(),
+            timeout: None,
+            lock_timeout: None,
+            statement_timeout: None,
+            allow_destructive: false,
+            allow_empty_down: false,
+            skip_checksum_verification: false,
+            break_stale_lock: false,
+            preflight_threshold: None,
+            single_transaction: false,
+            progress: None,
+        }
+    }
+
+    /// 既存の接続プールを使用する
+    pub fn with_pool(mut self, pool: AnyPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// タイムアウト（秒）を設定
+    ///
+    /// `with_pool` を使用しない場合は接続時のタイムアウトになります。
+    /// また、apply の開始からこの時間を過ぎる場合は、一時的なエラーになったステートメントを再試行しません。
+    pub fn with_timeout(mut self, timeout: Option<u64>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// apply 中のロック待ちの上限（秒）を設定（Noneの場合は環境設定の `lock_timeout` に従う）
+    pub fn with_lock_timeout(mut self, lock_timeout: Option<u64>) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// apply 中の1ステートメントの実行時間の上限（秒）を設定
+    /// （Noneの場合は環境設定の `statement_timeout` に従う）
+    pub fn with_statement_timeout(mut self, statement_timeout: Option<u64>) -> Self {
+        self.statement_timeout = statement_timeout;
+        self
+    }
+
+    /// apply 中のセッションに設定するタイムアウト
+    fn session_timeouts(&self) -> SessionTimeouts {
+        self.config
+            .environments
+            .get(&self.env)
+            .map(SessionTimeouts::from_config)
+            .unwrap_or_default()
+            .overridden_by(self.lock_timeout, self.statement_timeout)
+    }
+
+    /// 破壊的変更を含むマイグレーションの適用・ロールバックを許可する
+    pub fn allow_destructive(mut self, allow: bool) -> Self {
+        self.allow_destructive = allow;
+        self
+    }
+
+    /// down.sql に実行可能なステートメントがないマイグレーションのロールバックを許可する
+    ///
+    /// 許可した場合、そのマイグレーションは変更を戻さずに履歴からのみ削除されます。
+    pub fn allow_empty_down(mut self, allow: bool) -> Self {
+        self.allow_empty_down = allow;
+        self
+    }
+
+    /// 適用済みマイグレーションのチェックサム不一致を警告に留めて適用する
+    pub fn skip_checksum_verification(mut self, skip: bool) -> Self {
+        self.skip_checksum_verification = skip;
+        self
+    }
+
+    /// 中断されたマイグレーションが残したロックを解放する
+    ///
+    /// 放置されたセッションを終了し、SQLiteのデータベースを復旧します
+    /// （[`MigrationRunner::check_stale_locks`]）。
+    pub fn break_stale_lock(mut self, allow: bool) -> Self {
+        self.break_stale_lock = allow;
+        self
+    }
+
+    /// 適用前に各マイグレーションの事前確認クエリ（preflight.sql）を実行する
+    ///
+    /// いずれかの制約の違反件数が `threshold` を超えた場合は、そのマイグレーションを適用せずに
+    /// [`MigrationRunnerError::PreflightViolations`] を返します。`None` の場合は実行しません。
+    pub fn preflight(mut self, threshold: Option<u64>) -> Self {
+        self.preflight_threshold = threshold;
+        self
+    }
+
+    /// 計画したマイグレーションをすべて1つのトランザクションで適用する
+    ///
+    /// いずれかが失敗すると、それまでに適用したものも含めてロールバックします。
+    /// MySQL では DDL が暗黙的にコミットされるため無視し、マイグレーションごとに適用します
+    /// （[`MigrationRunner::uses_single_transaction`]）。
+    pub fn single_transaction(mut self, enabled: bool) -> Self {
+        self.single_transaction = enabled;
+        self
+    }
+
+    /// 適用の進捗の通知先を設定
+    pub fn with_progress(mut self, progress: Arc<dyn ApplyProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// すべてのマイグレーションを1つのトランザクションで適用するか
+    pub fn uses_single_transaction(&self) -> bool {
+        self.single_transaction && self.config.dialect != crate::core::config::Dialect::MySQL
+    }
+
+    /// 設定
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// マイグレーションディレクトリ
+    pub fn migrations_dir(&self) -> &Path {
+        &self.migrations_dir
+    }
+
+    /// `with_pool` を使用せずに作成した接続を閉じる
+    pub async fn close(&self) {
+        self.connections.close_all().await;
+    }
+
+    /// 未適用のマイグレーションを取得
+    pub async fn pending(&self) -> RunnerResult<Vec<PendingMigration>> {
+        let local = self.local_migrations(None)?;
+        if local.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.pool().await?;
+        let applied = self.load_history(&pool).await?;
+        Ok(pending_migrations(&local, &applied, None)
+            .into_iter()
+            .map(|m| PendingMigration {
+                version: m.version,
+                description: m.description,
+            })
+            .collect())
+    }
+
+    /// 未適用のマイグレーションをすべて適用
+    pub async fn apply_all(&self) -> RunnerResult<ApplyReport> {
+        self.apply_up_to(None).await
+    }
+
+    /// 指定バージョンまでの未適用のマイグレーションを適用
+    pub async fn apply_to(&self, version: &str) -> RunnerResult<ApplyReport> {
+        self.apply_up_to(Some(version)).await
+    }
+
+    async fn apply_up_to(&self, target: Option<&str>) -> RunnerResult<ApplyReport> {
+        let local = self.local_migrations(target)?;
+        if local.is_empty() {
+            return Ok(ApplyReport::default());
+        }
+        self.startup_check().await?;
+        let plan = self.plan_apply(&local, target).await?;
+        self.apply(&plan).await
+    }
+
+    /// 最新のものから指定件数のマイグレーションをロールバック
+    pub async fn rollback(&self, steps: u32) -> RunnerResult<RollbackReport> {
+        self.rollback_by(RollbackTarget::Steps(steps)).await
+    }
+
+    /// 指定バージョンが最新の適用済みマイグレーションになるまでロールバック
+    pub async fn rollback_to(&self, version: &str) -> RunnerResult<RollbackReport> {
+        self.rollback_by(RollbackTarget::Version(version.to_string()))
+            .await
+    }
+
+    async fn rollback_by(&self, target: RollbackTarget) -> RunnerResult<RollbackReport> {
+        let target_version = match &target {
+            RollbackTarget::Version(version) => Some(version.as_str()),
+            RollbackTarget::Steps(_) => None,
+        };
+        let local = self.local_migrations(target_version)?;
+        if local.is_empty() {
+            return Ok(RollbackReport::default());
+        }
+        self.startup_check().await?;
+        let plan = self.plan_rollback(&local, &target).await?;
+        self.execute_rollback(&plan).await
+    }
+
+    /// 接続前に前回の実行が中断された痕跡を検出し、接続後にロックを確認する
+    async fn startup_check(&self) -> RunnerResult<()> {
+        let leftover = self.find_leftover_files();
+        for issue in &leftover {
+            warn!("{}", issue);
+        }
+        for message in self.check_stale_locks(&leftover).await? {
+            info!("{}", message);
+        }
+        Ok(())
+    }
+
+    /// 異常終了で残ったSQLiteのジャーナル・WALファイルを検出
+    ///
+    /// SQLiteはデータベースを開くと復旧を始めるため、接続前に呼び出します。
+    /// SQLite以外の方言では常に空です。
+    pub fn find_leftover_files(&self) -> Vec<HealthIssue> {
+        if self.config.dialect != crate::core::config::Dialect::SQLite {
+            return Vec::new();
+        }
+        match self.config.get_database_config(&self.env) {
+            Ok(config) => migration_health_check::find_sqlite_leftover_files(Path::new(
+                &DatabaseConfigResolver::apply_env_overrides(&config).database,
+            )),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// 中断されたマイグレーションのセッションが保持するロックを確認
+    ///
+    /// `leftover` には接続前に [`MigrationRunner::find_leftover_files`] で検出したものを渡します。
+    /// `break_stale_lock` が有効な場合は検出したものを解放し、行った処理を返します。
+    /// 無効な場合、ロックを保持したセッションがあれば `StaleLocks` エラーを返します
+    /// （SQLiteのファイルは次に開いたときに SQLite が復旧するため、エラーにしません）。
+    pub async fn check_stale_locks(&self, leftover: &[HealthIssue]) -> RunnerResult<Vec<String>> {
+        let pool = self.pool().await?;
+        let sessions =
+            migration_health_check::find_stale_sessions(&pool, self.config.dialect).await?;
+
+        if self.break_stale_lock {
+            let issues: Vec<HealthIssue> = leftover.iter().cloned().chain(sessions).collect();
+            return Ok(migration_health_check::release(&pool, &issues).await?);
+        }
+        if !sessions.is_empty() {
+            return Err(MigrationRunnerError::StaleLocks { issues: sessions });
+        }
+        Ok(Vec::new())
+    }
+
+    /// マイグレーションディレクトリを読み込む
+    ///
+    /// `target` を指定した場合は、データベースに接続する前に
+    /// 形式の誤りやローカルに存在しないバージョンを拒否します。
+    pub fn local_migrations(&self, target: Option<&str>) -> RunnerResult<LocalMigrations> {
+        let entries = migration_loader::load_available_migrations(&self.migrations_dir)?;
+        debug!(count = entries.len(), "Loaded available migrations");
+
+        let incomplete = migration_loader::find_incomplete_migrations(&entries);
+        let future_warnings = migration_loader::check_future_migrations(
+            &entries,
+            self.config.future_version_tolerance(),
+        );
+        if let Some(target) = target {
+            migration_loader::check_target_version(&entries, target)?;
+        }
+
+        Ok(LocalMigrations {
+            migrations: entries
+                .into_iter()
+                .map(|(version, description, dir)| LocalMigration {
+                    version,
+                    description,
+                    dir,
+                })
+                .collect(),
+            incomplete,
+            future_warnings,
+        })
+    }
+
+    /// 接続プールを取得（`with_pool` で渡されていない場合は環境の設定で接続）
+    async fn pool(&self) -> RunnerResult<AnyPool> {
+        if let Some(pool) = &self.pool {
+            return Ok(pool.clone());
+        }
+
+        let config = self
+            .config
+            .get_database_config(&self.env)
+            .with_context(|| format!("Config for environment '{}' not found", self.env))?;
+        let mut db_config = DatabaseConfigResolver::apply_env_overrides(&config);
+        if let Some(timeout) = self.timeout {
+            db_config.timeout = Some(timeout);
+            db_config.acquire_timeout = Some(timeout);
+        }
+        let pool = self
+            .connections
+            .get_pool(self.config.dialect, &db_config)
+            .await
+            .with_context(|| "Failed to connect to database")?;
+        Ok(pool)
+    }
+
+    /// マイグレーション履歴テーブルを作成（未作成の場合）し、適用済みマイグレーションを取得
+    async fn load_history(&self, pool: &AnyPool) -> RunnerResult<Vec<MigrationRecord>> {
+        let migrator = DatabaseMigratorService::new();
+        migrator
+            .create_migration_table(pool, self.config.dialect)
+            .await
+            .with_context(|| "Failed to create migration history table")?;
+        Ok(migrator
+            .get_migrations(pool, self.config.dialect)
+            .await
+            .with_context(|| "Failed to get applied migration history")?)
+    }
+}
+
+/// `.meta.yaml` を読み込む
+pub fn read_metadata(migration_dir: &Path) -> anyhow::Result<MigrationMetadata> {
+    let meta_path = migration_dir.join(".meta.yaml");
+    let meta_content = fs::read_to_string(&meta_path)
+        .with_context(|| format!("Failed to read metadata file: {:?}", meta_path))?;
+    serde_saphyr::from_str(&meta_content)
+        .with_context(|| format!("Failed to parse metadata: {:?}", meta_path))
+}
+
+/// SQLに破壊的な操作が含まれるか判定
+pub fn contains_destructive_sql(sql: &str) -> bool {
+    DESTRUCTIVE_SQL_REGEX.is_match(sql)
+}
+
+/// SQLにCHECK制約が含まれるか判定
+///
+/// 行コメント（`--`）内の記述は判定対象から除外する。
+pub fn contains_check_constraint(sql: &str) -> bool {
+    sql.lines().any(|line| {
+        let code = line.split("--").next().unwrap_or_default();
+        CHECK_CONSTRAINT_SQL_REGEX.is_match(code)
+    })
+}
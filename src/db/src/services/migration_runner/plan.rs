@@ -0,0 +1,414 @@
+// 適用・ロールバックの計画
+
+use super::{
+    contains_check_constraint, contains_destructive_sql, read_metadata, ApplyPlan, LocalMigration,
+    LocalMigrations, MigrationRunner, MigrationRunnerError, OutOfOrderConflict, RollbackMigration,
+    RollbackPlan, RollbackTarget, RunnerResult,
+};
+use crate::adapters::database::DatabaseConnectionService;
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::core::config::UnenforcedCheckAction;
+use crate::core::migration::MigrationRecord;
+use crate::core::server_version::{ServerVersion, MYSQL_CHECK_ENFORCEMENT_VERSION};
+use crate::services::migration_files::migration_checksum::{self, ChecksumStatus};
+use crate::services::migration_files::migration_loader;
+use crate::services::migration_files::sql_output::{migration_sql_output, read_sql_file};
+use crate::services::migration_files::sql_stream::read_sql_statements;
+use anyhow::{anyhow, Context};
+use sqlx::AnyPool;
+use std::path::Path;
+use tracing::{debug, warn};
+
+impl MigrationRunner {
+    /// 適用するマイグレーションを決める
+    ///
+    /// ファイルが欠けたマイグレーションがある場合と、適用済みマイグレーションが
+    /// 適用後に書き換えられている場合（`skip_checksum_verification` 無効時）はエラーを返します。
+    pub async fn plan_apply(
+        &self,
+        local: &LocalMigrations,
+        target: Option<&str>,
+    ) -> RunnerResult<ApplyPlan> {
+        // generate の中断などでファイルが欠けたマイグレーションは適用しない
+        if !local.incomplete.is_empty() {
+            return Err(
+                anyhow!(migration_loader::format_incomplete_error(&local.incomplete)).into(),
+            );
+        }
+
+        // dry-run でも DB に接続して適用済みマイグレーションを確認する
+        let pool = self.pool().await?;
+        let applied = self.load_history(&pool).await?;
+
+        // 適用済みの最新より古いバージョンへは rollback で戻す
+        if let (Some(target), Some(latest)) = (target, applied.last()) {
+            if target < latest.version.as_str() {
+                return Err(anyhow!(
+                    "Target version {} is older than the latest applied migration {}. Use `strata rollback --target {}` to migrate down.",
+                    target,
+                    latest.version,
+                    target
+                )
+                .into());
+            }
+        }
+
+        let pending = pending_migrations(local, &applied, target);
+        debug!(
+            pending = pending.len(),
+            applied = applied.len(),
+            "Migration status"
+        );
+
+        self.complete_apply_plan(&pool, local, applied, pending, Vec::new())
+            .await
+    }
+
+    /// 指定した1件のマイグレーションだけを適用する計画を立てる（`apply --only`）
+    ///
+    /// より前の未適用マイグレーションは適用しません。それらが同じテーブルを変更する場合
+    /// （`.meta.yaml` に変更するテーブルの記録がなく判定できない場合を含む）は、
+    /// `force_out_of_order` が有効でなければ競合するバージョンの一覧をエラーとして返します。
+    /// 履歴は通常どおり記録するため、後の apply ではこのマイグレーションを飛ばします。
+    pub async fn plan_apply_only(
+        &self,
+        local: &LocalMigrations,
+        version: &str,
+        force_out_of_order: bool,
+    ) -> RunnerResult<ApplyPlan> {
+        if !local.incomplete.is_empty() {
+            return Err(
+                anyhow!(migration_loader::format_incomplete_error(&local.incomplete)).into(),
+            );
+        }
+        let Some(migration) = local.find(version) else {
+            return Err(anyhow!(
+                "Unknown version '{}': no migration with this version exists. Run `strata status` to list the available versions.",
+                version
+            )
+            .into());
+        };
+
+        let pool = self.pool().await?;
+        let applied = self.load_history(&pool).await?;
+        if applied.iter().any(|record| record.version == version) {
+            return Err(anyhow!("Migration {} is already applied.", version).into());
+        }
+
+        let earlier: Vec<LocalMigration> = pending_migrations(local, &applied, None)
+            .into_iter()
+            .filter(|m| m.version.as_str() < version)
+            .collect();
+        let conflicts = find_out_of_order_conflicts(migration, &earlier)?;
+        let mut warnings = Vec::new();
+        if !conflicts.is_empty() {
+            if !force_out_of_order {
+                return Err(MigrationRunnerError::OutOfOrderConflicts {
+                    version: version.to_string(),
+                    conflicts,
+                });
+            }
+            let warning = format!(
+                "Applying {} out of order despite conflicts with earlier pending migrations:\n{}",
+                version,
+                format_out_of_order_conflicts(&conflicts)
+            );
+            warn!("{}", warning);
+            warnings.push(warning);
+        }
+
+        self.complete_apply_plan(&pool, local, applied, vec![migration.clone()], warnings)
+            .await
+    }
+
+    /// 適用計画の共通の確認（チェックサム検証・CHECK制約の強制の確認）を行い、計画を組み立てる
+    async fn complete_apply_plan(
+        &self,
+        pool: &AnyPool,
+        local: &LocalMigrations,
+        applied: Vec<MigrationRecord>,
+        pending: Vec<LocalMigration>,
+        mut warnings: Vec<String>,
+    ) -> RunnerResult<ApplyPlan> {
+        let mut checksum_warnings = Vec::new();
+        if !pending.is_empty() {
+            // 適用済みマイグレーションのチェックサム検証（適用後に書き換えられていれば中止）
+            checksum_warnings = self.verify_applied_checksums(local, &applied)?;
+
+            // MySQLの場合、CHECK制約がサーバーで強制されるかを確認
+            if let Some(warning) = self
+                .verify_check_constraint_enforcement(pool, &pending)
+                .await?
+            {
+                warn!("{}", warning);
+                warnings.push(warning);
+            }
+        }
+
+        Ok(ApplyPlan {
+            applied,
+            pending,
+            warnings,
+            checksum_warnings,
+        })
+    }
+
+    /// ロールバックするマイグレーションを決める
+    pub async fn plan_rollback(
+        &self,
+        local: &LocalMigrations,
+        target: &RollbackTarget,
+    ) -> RunnerResult<RollbackPlan> {
+        let pool = self.pool().await?;
+        let dialect = self.config.dialect;
+
+        // マイグレーション履歴テーブルが存在するか確認
+        let migrator = DatabaseMigratorService::new();
+        let table_exists = migrator
+            .migration_table_exists(&pool, dialect)
+            .await
+            .with_context(|| "Failed to check migration table existence")?;
+        if !table_exists {
+            return Err(anyhow!(
+                "Migration history table does not exist. Please apply migrations first with the `apply` command."
+            )
+            .into());
+        }
+
+        // 古いレイアウトの履歴テーブルを更新し、ロールバックの記録テーブルを作成する
+        let applied = self.load_history(&pool).await?;
+
+        // ロールバックするマイグレーションを選択（最新のものから）
+        let to_rollback: Vec<&MigrationRecord> = match target {
+            // 指定バージョンより新しいものをすべて（一度も適用されていないバージョンには戻せない）
+            RollbackTarget::Version(version) => {
+                if !applied.iter().any(|r| &r.version == version) {
+                    return Err(anyhow!(
+                        "Target version {} has not been applied to '{}', so the database cannot be rolled back to it.",
+                        version,
+                        self.env
+                    )
+                    .into());
+                }
+                applied
+                    .iter()
+                    .rev()
+                    .take_while(|record| &record.version > version)
+                    .collect()
+            }
+            RollbackTarget::Steps(steps) => applied.iter().rev().take(*steps as usize).collect(),
+        };
+
+        // ロールバック対象のマイグレーションと down.sql を収集
+        let mut migrations = Vec::new();
+        for record in to_rollback {
+            let migration = local
+                .find(&record.version)
+                .ok_or_else(|| anyhow!("Migration file not found: {}", record.version))?;
+
+            // down.sqlを読み込み（BOM・改行コード・書き出し時の終端文字列を正規化）
+            let down_sql = read_sql_file(
+                &migration.dir.join("down.sql"),
+                &migration_sql_output(&migration.dir, &self.config.sql_output)?,
+            )?;
+            migrations.push(RollbackMigration {
+                record: record.clone(),
+                dir: migration.dir.clone(),
+                destructive: contains_destructive_sql(&down_sql),
+                down_sql,
+                protected_tables: load_protected_tables(&migration.dir)?,
+            });
+        }
+
+        Ok(RollbackPlan {
+            applied,
+            migrations,
+        })
+    }
+
+    /// 適用済みマイグレーションのチェックサム検証
+    ///
+    /// `.meta.yaml` と up.sql が適用時から変更されていないか確認し、変更があれば
+    /// 該当バージョンの一覧をエラーとして返す。`skip_checksum_verification` の場合は警告として返す。
+    /// `.meta.yaml` がなく検証できないマイグレーションは常に警告に留める。
+    fn verify_applied_checksums(
+        &self,
+        local: &LocalMigrations,
+        applied: &[MigrationRecord],
+    ) -> RunnerResult<Vec<String>> {
+        let verifications = migration_checksum::verify_applied_migrations(
+            &local.entries(),
+            applied,
+            &self.config.sql_output,
+        );
+        let modified: Vec<_> = verifications
+            .iter()
+            .filter(|verification| verification.status == ChecksumStatus::Modified)
+            .collect();
+
+        if !modified.is_empty() && !self.skip_checksum_verification {
+            return Err(anyhow!(migration_checksum::format_verification_error(&modified)).into());
+        }
+
+        Ok(verifications
+            .iter()
+            .filter_map(|verification| {
+                let detail = verification
+                    .detail
+                    .as_deref()
+                    .unwrap_or(verification.status.as_str());
+                match verification.status {
+                    ChecksumStatus::Ok => None,
+                    ChecksumStatus::Modified => Some(format!(
+                        "Warning: Checksum verification skipped for modified migration {}: {}",
+                        verification.version, detail
+                    )),
+                    ChecksumStatus::MissingMeta => Some(format!(
+                        "Warning: Cannot verify checksum of applied migration {}: {}",
+                        verification.version, detail
+                    )),
+                }
+            })
+            .collect())
+    }
+
+    /// 未適用マイグレーションのCHECK制約がサーバーで強制されるかを確認
+    ///
+    /// MySQL 8.0.16 未満（およびMariaDB）はCHECK制約を解析するだけで強制しないため、
+    /// CHECK制約を含むマイグレーションがある場合は警告を返す。
+    /// `mysql.unenforced_check: error` の場合はエラーとして中断する。
+    async fn verify_check_constraint_enforcement(
+        &self,
+        pool: &AnyPool,
+        pending: &[LocalMigration],
+    ) -> RunnerResult<Option<String>> {
+        let config = &self.config;
+        if config.dialect != crate::core::config::Dialect::MySQL {
+            return Ok(None);
+        }
+
+        let mut versions_with_check = Vec::new();
+        for migration in pending {
+            let statements = read_sql_statements(
+                &migration.dir.join("up.sql"),
+                &migration_sql_output(&migration.dir, &config.sql_output)?,
+                config.stream_sql_threshold(),
+            )?;
+            for statement in statements {
+                if contains_check_constraint(&statement?) {
+                    versions_with_check.push(migration.version.as_str());
+                    break;
+                }
+            }
+        }
+        if versions_with_check.is_empty() {
+            return Ok(None);
+        }
+
+        let raw_version = DatabaseConnectionService::new()
+            .fetch_server_version(pool, config.dialect)
+            .await
+            .with_context(|| "Failed to detect MySQL server version")?;
+        let (major, minor, patch) = MYSQL_CHECK_ENFORCEMENT_VERSION;
+        let reason = match ServerVersion::parse(&raw_version) {
+            Some(version) if version.enforces_check_constraints() => return Ok(None),
+            Some(version) => format!(
+                "MySQL server {} does not enforce CHECK constraints (requires MySQL {}.{}.{} or later)",
+                version, major, minor, patch
+            ),
+            None => format!(
+                "Could not parse MySQL server version '{}' to verify CHECK constraint enforcement",
+                raw_version
+            ),
+        };
+        let message = format!(
+            "{}: CHECK constraints in migration(s) {} will be parsed but silently ignored.",
+            reason,
+            versions_with_check.join(", ")
+        );
+
+        match config.mysql.unenforced_check {
+            UnenforcedCheckAction::Error => Err(anyhow!(
+                "{}\nSet `mysql.unenforced_check: warn` in the config to apply anyway.",
+                message
+            )
+            .into()),
+            UnenforcedCheckAction::Warn => Ok(Some(format!("Warning: {}", message))),
+        }
+    }
+}
+
+/// 単独で適用するマイグレーションと、先行する未適用マイグレーションの競合を検出
+///
+/// `.meta.yaml` に記録された変更するテーブルが重なるものを競合とします。
+/// どちらかに記録がない（古いバージョンの strata で生成された）場合は判定できないため競合とします。
+pub(super) fn find_out_of_order_conflicts(
+    migration: &LocalMigration,
+    earlier: &[LocalMigration],
+) -> anyhow::Result<Vec<OutOfOrderConflict>> {
+    let touched_tables = read_metadata(&migration.dir)?.touched_tables;
+
+    let mut conflicts = Vec::new();
+    for other in earlier {
+        let other_tables = read_metadata(&other.dir)?.touched_tables;
+        let tables = match (&touched_tables, other_tables) {
+            (Some(tables), Some(other_tables)) => {
+                let shared: Vec<String> = tables
+                    .iter()
+                    .filter(|table| other_tables.contains(table))
+                    .cloned()
+                    .collect();
+                if shared.is_empty() {
+                    continue;
+                }
+                Some(shared)
+            }
+            _ => None,
+        };
+        conflicts.push(OutOfOrderConflict {
+            version: other.version.clone(),
+            tables,
+        });
+    }
+    Ok(conflicts)
+}
+
+/// 競合の一覧を1件1行でフォーマット
+pub fn format_out_of_order_conflicts(conflicts: &[OutOfOrderConflict]) -> String {
+    conflicts
+        .iter()
+        .map(|conflict| match &conflict.tables {
+            Some(tables) => format!("  - {} (tables: {})", conflict.version, tables.join(", ")),
+            None => format!(
+                "  - {} (touched tables are not recorded in .meta.yaml)",
+                conflict.version
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 未適用のマイグレーションを特定（`target` 指定時はそのバージョンまで）
+pub(super) fn pending_migrations(
+    local: &LocalMigrations,
+    applied: &[MigrationRecord],
+    target: Option<&str>,
+) -> Vec<LocalMigration> {
+    local
+        .migrations
+        .iter()
+        .filter(|m| !applied.iter().any(|record| record.version == m.version))
+        .filter(|m| target.is_none_or(|target| m.version.as_str() <= target))
+        .cloned()
+        .collect()
+}
+
+/// `.meta.yaml` からロールバック保護されたテーブルを読み込む
+///
+/// メタデータがないマイグレーション（古いバージョンで生成等）は保護対象なしとして扱う。
+fn load_protected_tables(migration_dir: &Path) -> anyhow::Result<Vec<String>> {
+    if !migration_dir.join(".meta.yaml").exists() {
+        return Ok(Vec::new());
+    }
+    Ok(read_metadata(migration_dir)?.rollback_protected_tables)
+}
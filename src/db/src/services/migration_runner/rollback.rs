@@ -0,0 +1,203 @@
+// 計画したマイグレーションのロールバック
+
+use super::{
+    ExecutionError, MigrationRunner, MigrationRunnerError, RollbackPlan, RollbackReport,
+    RolledBackMigrationResult, RunnerResult, SqlError,
+};
+use crate::adapters::database_migrator::DatabaseMigratorService;
+use crate::adapters::sql_generator::MigrationDirection;
+use crate::core::migration::AppliedMigration;
+use crate::services::migration_files::retained_tables;
+use crate::services::migration_files::sql_parser::split_sql_statements;
+use crate::services::migration_files::stacked_migrations;
+use crate::services::schema_io::schema_snapshot::{self, SNAPSHOT_FILE};
+use anyhow::Context;
+use chrono::Utc;
+use sqlx::AnyPool;
+use std::path::Path;
+use tracing::{info, warn};
+
+impl MigrationRunner {
+    /// 計画したマイグレーションを順にロールバック
+    ///
+    /// ロールバック保護により残るテーブルの定義は `.rollback_retained.yaml` に記録し、
+    /// generate が再作成しないようにします。
+    /// down.sql が空のマイグレーションを含む場合は、`allow_empty_down` が無効なら何も実行せずにエラーを返します。
+    /// ロールバックしたマイグレーションは `.pending_migrations.yaml` に未適用として記録します。
+    pub async fn execute_rollback(&self, plan: &RollbackPlan) -> RunnerResult<RollbackReport> {
+        // down.sql が空のマイグレーションは、変更を戻さずに履歴だけが削除されるため拒否する
+        let empty_downs = plan.empty_downs();
+        if !empty_downs.is_empty() && !self.allow_empty_down {
+            return Err(MigrationRunnerError::EmptyDownRollback {
+                versions: empty_downs
+                    .iter()
+                    .map(|m| m.record.version.clone())
+                    .collect(),
+            });
+        }
+
+        if plan.has_destructive() && !self.allow_destructive {
+            return Err(MigrationRunnerError::DestructiveRollback {
+                versions: plan
+                    .migrations
+                    .iter()
+                    .filter(|m| m.destructive)
+                    .map(|m| m.record.version.clone())
+                    .collect(),
+            });
+        }
+
+        let pool = self.pool().await?;
+        let migrator = DatabaseMigratorService::new();
+        let mut report = RollbackReport::default();
+        for migration in &plan.migrations {
+            let record = &migration.record;
+            let start_time = Utc::now();
+            info!(version = %record.version, description = %record.description, "Rolling back migration");
+
+            // トランザクション内でロールバックを実行
+            if let Err(source) = self
+                .rollback_migration_with_transaction(
+                    &pool,
+                    &migrator,
+                    &record.version,
+                    &migration.down_sql,
+                )
+                .await
+            {
+                return Err(MigrationRunnerError::Rollback {
+                    version: record.version.clone(),
+                    completed: report.migrations.into_iter().map(|m| m.migration).collect(),
+                    source: Box::new(source),
+                });
+            }
+
+            // 保護されたテーブルはデータベースに残るため、generate が再作成しないよう記録する
+            let protected_tables = migration.protected_tables.clone();
+            if let Some(warning) = self.record_retained_tables(&migration.dir, &protected_tables)? {
+                warn!("{}", warning);
+                report.warnings.push(warning);
+            }
+
+            // generate が未適用のマイグレーションとして報告するよう記録に戻す
+            if let Err(e) =
+                stacked_migrations::mark_pending(&self.migrations_dir, [record.version.as_str()])
+            {
+                let warning = format!(
+                    "Warning: Failed to update {}: {:#}",
+                    stacked_migrations::PENDING_MIGRATIONS_FILE,
+                    e
+                );
+                warn!("{}", warning);
+                report.warnings.push(warning);
+            }
+
+            let end_time = Utc::now();
+            report.migrations.push(RolledBackMigrationResult {
+                migration: AppliedMigration::new(
+                    record.version.clone(),
+                    record.description.clone(),
+                    end_time,
+                    end_time.signed_duration_since(start_time),
+                ),
+                protected_tables,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// マイグレーションをトランザクション内でロールバック
+    pub(super) async fn rollback_migration_with_transaction(
+        &self,
+        pool: &AnyPool,
+        migrator: &DatabaseMigratorService,
+        version: &str,
+        down_sql: &str,
+    ) -> Result<(), ExecutionError> {
+        // トランザクションを開始
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        // マイグレーションdown SQLを文単位で実行
+        for statement in split_sql_statements(down_sql) {
+            if let Err(source) = sqlx::query(&statement).execute(&mut *tx).await {
+                return Err(SqlError {
+                    version: version.to_string(),
+                    direction: MigrationDirection::Down,
+                    statement,
+                    attempts: 1,
+                    timeout: None,
+                    source,
+                }
+                .into());
+            }
+        }
+
+        // 削除する記録を、ロールバックした日時とユーザーとともに残す
+        let (record_sql, params) =
+            migrator.generate_record_rollback_query(version, Utc::now(), self.config.dialect);
+        let mut query = sqlx::query(&record_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+        query
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "Failed to record the rollback")?;
+
+        // マイグレーション履歴から削除（パラメータバインディング使用）
+        let (remove_sql, params) =
+            migrator.generate_remove_migration_query(version, self.config.dialect);
+
+        let mut query = sqlx::query(&remove_sql);
+        for param in &params {
+            query = query.bind(param);
+        }
+
+        query
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "Failed to remove migration history")?;
+
+        // トランザクションをコミット
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+
+        Ok(())
+    }
+
+    /// ロールバック後も残ったテーブルの定義を記録
+    ///
+    /// テーブル定義はロールバックしたマイグレーションのスナップショットから取得する。
+    /// スナップショットがなく記録できない場合は警告を返す。
+    fn record_retained_tables(
+        &self,
+        migration_dir: &Path,
+        protected_tables: &[String],
+    ) -> RunnerResult<Option<String>> {
+        if protected_tables.is_empty() {
+            return Ok(None);
+        }
+
+        let snapshot_path = migration_dir.join(SNAPSHOT_FILE);
+        if !snapshot_path.exists() {
+            return Ok(Some(format!(
+                "Warning: Schema snapshot not found in {:?}; protected tables [{}] are not recorded and `generate` may try to recreate them.",
+                migration_dir,
+                protected_tables.join(", ")
+            )));
+        }
+
+        let mut snapshot = schema_snapshot::read_snapshot(&snapshot_path)?;
+        let tables = protected_tables
+            .iter()
+            .filter_map(|name| snapshot.tables.remove(name))
+            .collect();
+        retained_tables::record_retained_tables(&self.migrations_dir, tables)?;
+        Ok(None)
+    }
+}
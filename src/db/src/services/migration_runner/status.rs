@@ -0,0 +1,89 @@
+// マイグレーションの状態の一覧
+
+use super::{
+    LocalMigrations, MigrationRunner, MigrationState, MigrationStatus, RunnerResult, StatusReport,
+};
+use crate::core::config::SqlOutputConfig;
+use crate::core::migration::MigrationRecord;
+use crate::services::migration_files::migration_checksum::{self, ChecksumStatus};
+use std::collections::HashMap;
+
+impl MigrationRunner {
+    /// マイグレーションの状態の一覧を取得
+    pub async fn status(&self, local: &LocalMigrations) -> RunnerResult<StatusReport> {
+        let pool = self.pool().await?;
+        let applied = self.load_history(&pool).await?;
+
+        let migrations = build_migration_statuses(local, &applied, &self.config.sql_output);
+        Ok(StatusReport {
+            migrations,
+            applied,
+        })
+    }
+}
+
+/// ローカルのマイグレーションと適用済みマイグレーションを照合し、状態の一覧を生成
+///
+/// 適用済みマイグレーションは up.sql を含めてチェックサムを検証する。
+pub(super) fn build_migration_statuses(
+    local: &LocalMigrations,
+    applied: &[MigrationRecord],
+    sql_output: &SqlOutputConfig,
+) -> Vec<MigrationStatus> {
+    let checksum_statuses: HashMap<String, ChecksumStatus> =
+        migration_checksum::verify_applied_migrations(&local.entries(), applied, sql_output)
+            .into_iter()
+            .map(|verification| (verification.version, verification.status))
+            .collect();
+
+    let mut migrations: Vec<MigrationStatus> = local
+        .migrations
+        .iter()
+        .map(|m| {
+            let record = applied.iter().find(|record| record.version == m.version);
+            MigrationStatus {
+                version: m.version.clone(),
+                description: m.description.clone(),
+                state: if record.is_some() {
+                    MigrationState::Applied
+                } else {
+                    MigrationState::Pending
+                },
+                checksum_status: checksum_statuses.get(&m.version).copied(),
+                out_of_order: record.is_some_and(|record| is_out_of_order(record, local, applied)),
+            }
+        })
+        .collect();
+
+    // 孤立マイグレーション（DBに記録があるがローカルに存在しない）を追加
+    for record in applied {
+        if local.find(&record.version).is_none() {
+            migrations.push(MigrationStatus {
+                version: record.version.clone(),
+                description: record.description.clone(),
+                state: MigrationState::Orphaned,
+                checksum_status: None,
+                out_of_order: false,
+            });
+        }
+    }
+    migrations.sort_by(|a, b| a.version.cmp(&b.version));
+    migrations
+}
+
+/// 適用済みマイグレーションがバージョン順より先に適用されたか
+///
+/// より前のバージョンがまだ未適用の場合と、より前のバージョンが後から適用された場合に該当します。
+fn is_out_of_order(
+    record: &MigrationRecord,
+    local: &LocalMigrations,
+    applied: &[MigrationRecord],
+) -> bool {
+    let earlier_pending = local.migrations.iter().any(|m| {
+        m.version < record.version && !applied.iter().any(|other| other.version == m.version)
+    });
+    let earlier_applied_later = applied
+        .iter()
+        .any(|other| other.version < record.version && other.applied_at > record.applied_at);
+    earlier_pending || earlier_applied_later
+}
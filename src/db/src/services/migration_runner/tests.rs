@@ -0,0 +1,999 @@
+use super::apply::execute_statements;
+use super::plan::find_out_of_order_conflicts;
+use super::status::build_migration_statuses;
+use super::*;
+use crate::adapters::database_migrator::statement_retry::StatementExecutor;
+use crate::core::config::{Dialect, SqlOutputConfig};
+use crate::services::preflight_queries::PREFLIGHT_FILE;
+use crate::services::schema_checksum::SchemaChecksumService;
+use chrono::{Datelike, Utc};
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::Row;
+use tempfile::TempDir;
+
+fn sqlite_config() -> Config {
+    serde_saphyr::from_str(
+        "version: \"1.0\"\ndialect: sqlite\nenvironments:\n  development:\n    database: app.db\n",
+    )
+    .unwrap()
+}
+
+async fn sqlite_pool(temp_dir: &TempDir) -> AnyPool {
+    install_default_drivers();
+    let db_path = temp_dir.path().join("test.db");
+    AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap()
+}
+
+fn write_migration(migrations_dir: &Path, version: &str, up_sql: &str, down_sql: &str) {
+    let dir = migrations_dir.join(format!("{}_create_users", version));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("up.sql"), up_sql).unwrap();
+    fs::write(dir.join("down.sql"), down_sql).unwrap();
+    fs::write(
+        dir.join(".meta.yaml"),
+        format!(
+            "version: \"{}\"\ndescription: \"create_users\"\ndialect: sqlite\nchecksum: \"checksum\"\ndestructive_changes: {{}}\n",
+            version
+        ),
+    )
+    .unwrap();
+}
+
+async fn history_count(pool: &AnyPool) -> i64 {
+    sqlx::query("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get(0)
+}
+
+fn record(version: &str, description: &str) -> MigrationRecord {
+    MigrationRecord::new(
+        version.to_string(),
+        description.to_string(),
+        "checksum".to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_apply_all_and_rollback() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .allow_destructive(true);
+
+    assert_eq!(runner.pending().await.unwrap().len(), 1);
+
+    let report = runner.apply_all().await.unwrap();
+    assert_eq!(report.applied_versions(), vec!["20260122120000"]);
+    assert_eq!(report.migrations[0].statement_checksums.len(), 1);
+    assert!(runner.pending().await.unwrap().is_empty());
+    assert_eq!(history_count(&pool).await, 1);
+
+    let report = runner.rollback(1).await.unwrap();
+    assert_eq!(report.rolled_back_versions(), vec!["20260122120000"]);
+    assert_eq!(history_count(&pool).await, 0);
+}
+
+#[tokio::test]
+async fn test_apply_warns_when_up_sql_differs_from_meta_checksum() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    let up_sql = "CREATE TABLE users (id INTEGER PRIMARY KEY);";
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        up_sql,
+        "DROP TABLE users;",
+    );
+    let dir = migrations_dir.join("20260122120000_create_users");
+    let meta_path = dir.join(".meta.yaml");
+    let mut meta = fs::read_to_string(&meta_path).unwrap();
+    meta.push_str(&format!(
+        "up_sql_checksum: {}\n",
+        SchemaChecksumService::new().calculate_sql_checksum(up_sql)
+    ));
+    fs::write(&meta_path, meta).unwrap();
+    fs::write(
+        dir.join("up.sql"),
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+    )
+    .unwrap();
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone());
+
+    let report = runner.apply_all().await.unwrap();
+    let warnings = &report.migrations[0].warnings;
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("does not match up_sql_checksum"));
+}
+
+#[tokio::test]
+async fn test_apply_records_audit_of_latest_apply() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .allow_destructive(true);
+    let migrator = DatabaseMigratorService::new();
+
+    runner.apply_all().await.unwrap();
+    let audits = migrator
+        .get_migration_audits(&pool, Dialect::SQLite)
+        .await
+        .unwrap();
+    assert_eq!(audits.len(), 1);
+    assert!(audits[0].execution_time_ms.is_some());
+    // SQLiteにはデータベースユーザーがない
+    assert_eq!(audits[0].applied_by, None);
+
+    // ロールバックで記録を削除し、再適用時に新しい値を記録する
+    sqlx::query("UPDATE schema_migrations SET applied_at = '2000-01-01T00:00:00+00:00', execution_time_ms = 99999")
+        .execute(&pool)
+        .await
+        .unwrap();
+    runner.rollback(1).await.unwrap();
+    assert!(migrator
+        .get_migration_audits(&pool, Dialect::SQLite)
+        .await
+        .unwrap()
+        .is_empty());
+    runner.apply_all().await.unwrap();
+    let audits = migrator
+        .get_migration_audits(&pool, Dialect::SQLite)
+        .await
+        .unwrap();
+    assert!(audits[0].applied_at.year() > 2000);
+    assert_ne!(audits[0].execution_time_ms, Some(99999));
+}
+
+#[tokio::test]
+async fn test_rollback_records_audit() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .allow_destructive(true);
+    let migrator = DatabaseMigratorService::new();
+
+    runner.apply_all().await.unwrap();
+    let applied_at = migrator
+        .get_migrations(&pool, Dialect::SQLite)
+        .await
+        .unwrap()[0]
+        .applied_at;
+    let before_rollback = Utc::now();
+    runner.rollback(1).await.unwrap();
+
+    // 履歴からは削除され、ロールバックの記録が残る
+    assert_eq!(history_count(&pool).await, 0);
+    let audits = migrator
+        .get_rollback_audits(&pool, Dialect::SQLite)
+        .await
+        .unwrap();
+    assert_eq!(audits.len(), 1);
+    assert_eq!(audits[0].version, "20260122120000");
+    assert_eq!(audits[0].description, "create_users");
+    assert_eq!(audits[0].applied_at, applied_at);
+    assert!(audits[0].rolled_back_at >= before_rollback - Duration::seconds(1));
+    // SQLiteにはデータベースユーザーがない
+    assert_eq!(audits[0].rolled_back_by, None);
+
+    // 同じバージョンを再度ロールバックすると記録が追加される
+    runner.apply_all().await.unwrap();
+    runner.rollback(1).await.unwrap();
+    assert_eq!(
+        migrator
+            .get_rollback_audits(&pool, Dialect::SQLite)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_apply_failure_does_not_record() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    write_migration(&migrations_dir, "20260122120001", "INVALID SQL;", "");
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone());
+
+    let error = runner.apply_all().await.unwrap_err();
+    match &error {
+        MigrationRunnerError::Apply {
+            version, completed, ..
+        } => {
+            assert_eq!(version, "20260122120001");
+            assert_eq!(completed.len(), 1);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(error.sql_error().unwrap().statement, "INVALID SQL");
+    assert_eq!(history_count(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn test_apply_single_transaction_rolls_back_all_on_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    write_migration(&migrations_dir, "20260122120001", "INVALID SQL;", "");
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .single_transaction(true);
+
+    let error = runner.apply_all().await.unwrap_err();
+    match &error {
+        MigrationRunnerError::Apply {
+            version,
+            completed,
+            rolled_back,
+            ..
+        } => {
+            assert_eq!(version, "20260122120001");
+            assert!(completed.is_empty());
+            assert_eq!(rolled_back.len(), 1);
+            assert_eq!(rolled_back[0].version, "20260122120000");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(history_count(&pool).await, 0);
+    let users: i64 =
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'users'")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+    assert_eq!(users, 0);
+}
+
+#[test]
+fn test_single_transaction_is_ignored_on_mysql() {
+    let mut config = sqlite_config();
+    let runner =
+        MigrationRunner::new(config.clone(), "development", "migrations").single_transaction(true);
+    assert!(runner.uses_single_transaction());
+
+    config.dialect = Dialect::MySQL;
+    let runner = MigrationRunner::new(config, "development", "migrations").single_transaction(true);
+    assert!(!runner.uses_single_transaction());
+}
+
+#[derive(Debug, Default)]
+struct RecordingProgress {
+    events: std::sync::Mutex<Vec<String>>,
+}
+
+impl ApplyProgress for RecordingProgress {
+    fn applied(&self, migration: &AppliedMigration) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("applied {}", migration.version));
+    }
+
+    fn failed(&self, migration: &LocalMigration) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("failed {}", migration.version));
+    }
+}
+
+#[tokio::test]
+async fn test_apply_reports_progress_per_migration() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    write_migration(&migrations_dir, "20260122120001", "INVALID SQL;", "");
+    let pool = sqlite_pool(&temp_dir).await;
+    let progress = Arc::new(RecordingProgress::default());
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool)
+        .with_progress(progress.clone());
+
+    runner.apply_all().await.unwrap_err();
+    assert_eq!(
+        *progress.events.lock().unwrap(),
+        vec!["applied 20260122120000", "failed 20260122120001"]
+    );
+}
+
+#[tokio::test]
+async fn test_preflight_violations_abort_before_applying() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, age INTEGER);\nINSERT INTO users (id, age) VALUES (1, 20), (2, -1), (3, NULL);",
+        "DROP TABLE users;",
+    );
+    write_migration(
+        &migrations_dir,
+        "20260122120001",
+        "CREATE INDEX idx_users_age ON users (age);",
+        "DROP INDEX idx_users_age;",
+    );
+    fs::write(
+        migrations_dir
+            .join("20260122120001_create_users")
+            .join(PREFLIGHT_FILE),
+        "-- preflight: CHECK users.age (age >= 0)\nSELECT COUNT(*) FROM \"users\" WHERE NOT (age >= 0);\n\n-- preflight: NOT NULL users.age\nSELECT COUNT(*) FROM \"users\" WHERE \"age\" IS NULL;\n",
+    )
+    .unwrap();
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .preflight(Some(0));
+
+    // 先のマイグレーションで作成したテーブルに対して、適用直前に実行する
+    let error = runner.apply_all().await.unwrap_err();
+    match &error {
+        MigrationRunnerError::PreflightViolations {
+            version,
+            violations,
+            completed,
+            ..
+        } => {
+            assert_eq!(version, "20260122120001");
+            assert_eq!(completed.len(), 1);
+            assert_eq!(
+                violations,
+                &vec![
+                    PreflightViolation {
+                        label: "CHECK users.age (age >= 0)".to_string(),
+                        count: 1,
+                    },
+                    PreflightViolation {
+                        label: "NOT NULL users.age".to_string(),
+                        count: 1,
+                    },
+                ]
+            );
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(error
+        .to_string()
+        .contains("CHECK users.age (age >= 0): 1 row(s)"));
+    assert_eq!(history_count(&pool).await, 1);
+
+    // 許容数以内であれば適用する
+    let report = runner.preflight(Some(1)).apply_all().await.unwrap();
+    assert_eq!(report.applied_versions(), vec!["20260122120001"]);
+}
+
+#[tokio::test]
+async fn test_rollback_failure_keeps_record() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "INVALID SQL;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone());
+    runner.apply_all().await.unwrap();
+
+    let error = runner.rollback(1).await.unwrap_err();
+    assert!(matches!(error, MigrationRunnerError::Rollback { .. }));
+    assert_eq!(
+        error.sql_error().unwrap().direction,
+        MigrationDirection::Down
+    );
+    assert_eq!(history_count(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn test_rollback_success_removes_record() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone());
+    runner.apply_all().await.unwrap();
+    assert_eq!(history_count(&pool).await, 1);
+
+    runner
+        .rollback_migration_with_transaction(
+            &pool,
+            &DatabaseMigratorService::new(),
+            "20260122120000",
+            "DROP TABLE users;",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(history_count(&pool).await, 0);
+    let tables: i64 =
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'users'")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+    assert_eq!(tables, 0);
+}
+
+#[tokio::test]
+async fn test_rollback_rejects_destructive_sql() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone());
+    runner.apply_all().await.unwrap();
+
+    let error = runner.rollback(1).await.unwrap_err();
+    assert!(matches!(
+        error,
+        MigrationRunnerError::DestructiveRollback { ref versions } if versions == &["20260122120000"]
+    ));
+    assert_eq!(history_count(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn test_rollback_refuses_empty_down_in_chain() {
+    let temp_dir = TempDir::new().unwrap();
+    let migrations_dir = temp_dir.path().join("migrations");
+    write_migration(
+        &migrations_dir,
+        "20260122120000",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    );
+    write_migration(
+        &migrations_dir,
+        "20260122120001",
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+        "-- TODO: write the rollback\n",
+    );
+    write_migration(
+        &migrations_dir,
+        "20260122120002",
+        "CREATE TABLE tags (id INTEGER PRIMARY KEY);",
+        "DROP TABLE tags;",
+    );
+    let pool = sqlite_pool(&temp_dir).await;
+    let runner = MigrationRunner::new(sqlite_config(), "development", &migrations_dir)
+        .with_pool(pool.clone())
+        .allow_destructive(true);
+    runner.apply_all().await.unwrap();
+
+    // 途中のマイグレーションの down.sql が空の場合は、何もロールバックせずに拒否する
+    let error = runner.rollback(3).await.unwrap_err();
+    assert!(matches!(
+        error,
+        MigrationRunnerError::EmptyDownRollback { ref versions } if versions == &["20260122120001"]
+    ));
+    assert_eq!(history_count(&pool).await, 3);
+
+    let report = runner.allow_empty_down(true).rollback(3).await.unwrap();
+    assert_eq!(
+        report.rolled_back_versions(),
+        vec!["20260122120002", "20260122120001", "20260122120000"]
+    );
+    assert_eq!(history_count(&pool).await, 0);
+}
+
+/// 書き込み中に異常終了したデータベース（ホットジャーナル付き）を作成
+async fn write_interrupted_database(temp_dir: &TempDir) -> PathBuf {
+    let pool = sqlite_pool(temp_dir).await;
+    sqlx::raw_sql(
+        "PRAGMA journal_mode = DELETE; CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT); INSERT INTO users VALUES (1, 'alice');",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // トランザクションの途中のファイルを複製し、プロセスが強制終了された状態を再現
+    // （キャッシュを溢れさせ、ジャーナルの同期後にデータベースファイルへ書き込ませる）
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::raw_sql(
+        "PRAGMA cache_size = 1; UPDATE users SET name = 'bob'; WITH RECURSIVE n(i) AS (SELECT 2 UNION ALL SELECT i + 1 FROM n WHERE i < 500) INSERT INTO users SELECT i, printf('%.500c', 'x') FROM n;",
+    )
+    .execute(&mut *tx)
+    .await
+    .unwrap();
+    let crashed = temp_dir.path().join("crashed.db");
+    fs::copy(temp_dir.path().join("test.db"), &crashed).unwrap();
+    fs::copy(
+        temp_dir.path().join("test.db-journal"),
+        temp_dir.path().join("crashed.db-journal"),
+    )
+    .unwrap();
+    tx.rollback().await.unwrap();
+    pool.close().await;
+    crashed
+}
+
+#[tokio::test]
+async fn test_break_stale_lock_recovers_sqlite_hot_journal() {
+    let temp_dir = TempDir::new().unwrap();
+    let crashed = write_interrupted_database(&temp_dir).await;
+    let config: Config = serde_saphyr::from_str(&format!(
+        "version: \"1.0\"\ndialect: sqlite\nenvironments:\n  development:\n    database: {}\n",
+        crashed.display()
+    ))
+    .unwrap();
+    let runner = MigrationRunner::new(config, "development", temp_dir.path().join("migrations"))
+        .break_stale_lock(true);
+
+    let leftover = runner.find_leftover_files();
+    assert_eq!(
+        leftover,
+        vec![HealthIssue::SqliteJournal {
+            path: temp_dir.path().join("crashed.db-journal")
+        }]
+    );
+
+    let released = runner.check_stale_locks(&leftover).await.unwrap();
+    assert_eq!(released.len(), 1);
+    assert!(released[0].starts_with("Recovered the SQLite database"));
+    runner.close().await;
+
+    // 中断されたトランザクションはロールバックされ、ジャーナルは残らない
+    assert!(runner.find_leftover_files().is_empty());
+    install_default_drivers();
+    let pool = AnyPoolOptions::new()
+        .connect(&format!("sqlite://{}", crashed.display()))
+        .await
+        .unwrap();
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM users")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(names, vec!["alice"]);
+    pool.close().await;
+}
+
+#[tokio::test]
+async fn test_break_stale_lock_refuses_database_in_use() {
+    let temp_dir = TempDir::new().unwrap();
+    let crashed = write_interrupted_database(&temp_dir).await;
+    let url = format!("sqlite://{}", crashed.display());
+    install_default_drivers();
+
+    // 他のプロセスが書き込み中
+    let other = AnyPoolOptions::new().connect(&url).await.unwrap();
+    let mut tx = other.begin().await.unwrap();
+    sqlx::query("UPDATE users SET name = 'carol'")
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await
+        .unwrap();
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path())
+        .with_pool(pool.clone())
+        .break_stale_lock(true);
+    let leftover = vec![HealthIssue::SqliteJournal {
+        path: temp_dir.path().join("crashed.db-journal"),
+    }];
+    let error = runner.check_stale_locks(&leftover).await.unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("The SQLite database is in use by another process"));
+
+    tx.rollback().await.unwrap();
+    other.close().await;
+    pool.close().await;
+}
+
+#[test]
+fn test_build_migration_statuses() {
+    let temp_dir = TempDir::new().unwrap();
+    write_migration(temp_dir.path(), "20260121120000", "SELECT 1;", "");
+    write_migration(temp_dir.path(), "20260121120001", "SELECT 1;", "");
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path());
+    let local = runner.local_migrations(None).unwrap();
+
+    let mut modified = record("20260121120001", "create_users");
+    modified.checksum = "other".to_string();
+    let statuses = build_migration_statuses(
+        &local,
+        &[record("20260121110000", "legacy"), modified],
+        &SqlOutputConfig::default(),
+    );
+
+    let summary: Vec<_> = statuses
+        .iter()
+        .map(|s| (s.version.as_str(), s.state, s.checksum_status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("20260121110000", MigrationState::Orphaned, None),
+            ("20260121120000", MigrationState::Pending, None),
+            (
+                "20260121120001",
+                MigrationState::Applied,
+                Some(ChecksumStatus::Modified)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_build_migration_statuses_marks_out_of_order() {
+    let temp_dir = TempDir::new().unwrap();
+    for version in ["20260121120000", "20260121120001", "20260121120002"] {
+        write_migration(temp_dir.path(), version, "SELECT 1;", "");
+    }
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path());
+    let local = runner.local_migrations(None).unwrap();
+    let applied_at = |version: &str, minutes: i64| MigrationRecord {
+        applied_at: Utc::now() - Duration::minutes(minutes),
+        ..record(version, "create_users")
+    };
+    let out_of_order = |applied: &[MigrationRecord]| -> Vec<bool> {
+        build_migration_statuses(&local, applied, &SqlOutputConfig::default())
+            .iter()
+            .map(|s| s.out_of_order)
+            .collect()
+    };
+
+    // より前のバージョンが未適用
+    assert_eq!(
+        out_of_order(&[applied_at("20260121120001", 0)]),
+        vec![false, true, false]
+    );
+    // より前のバージョンが後から適用された
+    assert_eq!(
+        out_of_order(&[
+            applied_at("20260121120000", 5),
+            applied_at("20260121120001", 10),
+            applied_at("20260121120002", 1),
+        ]),
+        vec![false, true, false]
+    );
+    // バージョン順に適用された
+    assert_eq!(
+        out_of_order(&[
+            applied_at("20260121120000", 3),
+            applied_at("20260121120001", 2),
+            applied_at("20260121120002", 1),
+        ]),
+        vec![false, false, false]
+    );
+}
+
+#[test]
+fn test_build_migration_status_with_checksum_mismatch() {
+    let temp_dir = TempDir::new().unwrap();
+    write_migration(temp_dir.path(), "20260121120000", "SELECT 1;", "");
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path());
+    let local = runner.local_migrations(None).unwrap();
+
+    // 適用後に .meta.yaml のチェックサムが書き換えられた
+    let mut applied = record("20260121120000", "create_users");
+    applied.checksum = "checksum_old".to_string();
+    let statuses = build_migration_statuses(&local, &[applied], &SqlOutputConfig::default());
+
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].state, MigrationState::Applied);
+    assert_eq!(statuses[0].checksum_status, Some(ChecksumStatus::Modified));
+}
+
+#[test]
+fn test_build_migration_status_with_orphaned() {
+    let temp_dir = TempDir::new().unwrap();
+    write_migration(temp_dir.path(), "20260121120001", "SELECT 1;", "");
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path());
+    let local = runner.local_migrations(None).unwrap();
+
+    let statuses = build_migration_statuses(
+        &local,
+        &[
+            record("20260121120000", "create_users"),
+            record("20260121120001", "create_users"),
+        ],
+        &SqlOutputConfig::default(),
+    );
+
+    // ローカルにディレクトリがない適用済みマイグレーションも、バージョン順に並ぶ
+    let summary: Vec<_> = statuses
+        .iter()
+        .map(|s| (s.version.as_str(), s.state, s.checksum_status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("20260121120000", MigrationState::Orphaned, None),
+            (
+                "20260121120001",
+                MigrationState::Applied,
+                Some(ChecksumStatus::Ok)
+            ),
+        ]
+    );
+}
+
+fn write_touched_tables(migrations_dir: &Path, version: &str, tables: &[&str]) {
+    let meta_path = migrations_dir
+        .join(format!("{}_create_users", version))
+        .join(".meta.yaml");
+    let mut metadata = read_metadata(meta_path.parent().unwrap()).unwrap();
+    metadata.touched_tables = Some(tables.iter().map(|t| t.to_string()).collect());
+    fs::write(&meta_path, serde_saphyr::to_string(&metadata).unwrap()).unwrap();
+}
+
+#[test]
+fn test_find_out_of_order_conflicts() {
+    let temp_dir = TempDir::new().unwrap();
+    for version in [
+        "20260121120000",
+        "20260121120001",
+        "20260121120002",
+        "20260121120003",
+    ] {
+        write_migration(temp_dir.path(), version, "SELECT 1;", "");
+    }
+    write_touched_tables(temp_dir.path(), "20260121120000", &["orders", "users"]);
+    write_touched_tables(temp_dir.path(), "20260121120001", &["posts"]);
+    write_touched_tables(temp_dir.path(), "20260121120003", &["users"]);
+    let runner = MigrationRunner::new(sqlite_config(), "development", temp_dir.path());
+    let local = runner.local_migrations(None).unwrap();
+    let (earlier, target) = local.migrations.split_at(3);
+
+    let conflicts = find_out_of_order_conflicts(&target[0], earlier).unwrap();
+    assert_eq!(
+        conflicts,
+        vec![
+            OutOfOrderConflict {
+                version: "20260121120000".to_string(),
+                tables: Some(vec!["users".to_string()]),
+            },
+            // 20260121120002 は変更するテーブルの記録がない
+            OutOfOrderConflict {
+                version: "20260121120002".to_string(),
+                tables: None,
+            },
+        ]
+    );
+    assert_eq!(
+        format_out_of_order_conflicts(&conflicts),
+        "  - 20260121120000 (tables: users)\n  - 20260121120002 (touched tables are not recorded in .meta.yaml)"
+    );
+
+    let (earlier, target) = local.migrations.split_at(1);
+    assert!(find_out_of_order_conflicts(&target[0], earlier)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_extract_checksum_from_meta() {
+    let temp_dir = TempDir::new().unwrap();
+    write_migration(temp_dir.path(), "20260121120000", "SELECT 1;", "");
+
+    let metadata = read_metadata(&temp_dir.path().join("20260121120000_create_users")).unwrap();
+    assert_eq!(metadata.version, "20260121120000");
+    assert_eq!(metadata.checksum, "checksum");
+
+    assert!(read_metadata(&temp_dir.path().join("20260121120001_missing")).is_err());
+}
+
+#[test]
+fn test_contains_destructive_sql() {
+    assert!(contains_destructive_sql("DROP TABLE IF EXISTS users;"));
+    assert!(contains_destructive_sql(
+        "ALTER TABLE users DROP COLUMN email;"
+    ));
+    assert!(contains_destructive_sql("TRUNCATE TABLE users;"));
+    assert!(!contains_destructive_sql(
+        "ALTER TABLE users ADD COLUMN email VARCHAR(255);"
+    ));
+    assert!(!contains_destructive_sql(
+        "INSERT INTO users (id) VALUES (1);"
+    ));
+}
+
+#[test]
+fn test_contains_check_constraint() {
+    assert!(contains_check_constraint(
+        "CREATE TABLE users (\n  age INT,\n  CONSTRAINT ck_age CHECK (age >= 0)\n);"
+    ));
+    assert!(contains_check_constraint(
+        "ALTER TABLE users ADD CONSTRAINT ck_age check(age >= 0);"
+    ));
+    assert!(!contains_check_constraint(
+        "-- CHECK (age >= 0)\nCREATE TABLE checklists (id INT);"
+    ));
+}
+
+/// 実行したSQLを記録する接続
+#[derive(Default)]
+struct RecordingExecutor {
+    executed: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl StatementExecutor for RecordingExecutor {
+    async fn execute_statement(&mut self, sql: &str) -> Result<(), sqlx::Error> {
+        self.executed.push(sql.to_string());
+        Ok(())
+    }
+}
+
+fn timeout_settings(
+    lock_timeout: Option<u64>,
+    statement_timeout: Option<u64>,
+) -> ExecutionSettings {
+    ExecutionSettings {
+        retry: RetryPolicy::disabled(),
+        timeouts: SessionTimeouts {
+            lock_timeout,
+            statement_timeout,
+        },
+    }
+}
+
+async fn executed_sql(dialect: Dialect, settings: &ExecutionSettings) -> Vec<String> {
+    let mut executor = RecordingExecutor::default();
+    let statements = [
+        "ALTER TABLE users ADD COLUMN age INT",
+        "CREATE INDEX idx_age ON users (age)",
+    ]
+    .into_iter()
+    .map(|sql| Ok(sql.to_string()));
+    let executed = execute_statements(
+        &mut executor,
+        &DatabaseMigratorService::new(),
+        dialect,
+        "20260121120000",
+        statements,
+        settings,
+    )
+    .await
+    .unwrap();
+    assert_eq!(executed.checksums.len(), 2);
+    executor.executed
+}
+
+#[tokio::test]
+async fn test_session_timeouts_are_set_before_first_statement_postgres() {
+    let executed = executed_sql(Dialect::PostgreSQL, &timeout_settings(Some(5), Some(60))).await;
+    assert_eq!(
+        executed,
+        vec![
+            "SET LOCAL lock_timeout = '5s'",
+            "SET LOCAL statement_timeout = '60s'",
+            "ALTER TABLE users ADD COLUMN age INT",
+            "CREATE INDEX idx_age ON users (age)",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_session_timeouts_are_set_before_first_statement_mysql() {
+    let executed = executed_sql(Dialect::MySQL, &timeout_settings(Some(5), None)).await;
+    assert_eq!(
+        executed,
+        vec![
+            "SET SESSION lock_wait_timeout = 5",
+            "ALTER TABLE users ADD COLUMN age INT",
+            "CREATE INDEX idx_age ON users (age)",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_session_timeouts_are_ignored_on_sqlite() {
+    let executed = executed_sql(Dialect::SQLite, &timeout_settings(Some(5), Some(60))).await;
+    assert_eq!(
+        executed,
+        vec![
+            "ALTER TABLE users ADD COLUMN age INT",
+            "CREATE INDEX idx_age ON users (age)",
+        ]
+    );
+}
+
+#[test]
+fn test_session_timeouts_cli_values_override_environment_config() {
+    let config: Config = serde_saphyr::from_str(
+        "version: \"1.0\"\ndialect: postgresql\nenvironments:\n  production:\n    database: app\n    lock_timeout: 5\n    statement_timeout: 600\n",
+    )
+    .unwrap();
+    let runner = MigrationRunner::new(config.clone(), "production", "migrations");
+    assert_eq!(
+        runner.session_timeouts(),
+        SessionTimeouts {
+            lock_timeout: Some(5),
+            statement_timeout: Some(600),
+        }
+    );
+    let runner =
+        MigrationRunner::new(config, "production", "migrations").with_lock_timeout(Some(2));
+    assert_eq!(runner.session_timeouts().lock_timeout, Some(2));
+    assert_eq!(runner.session_timeouts().statement_timeout, Some(600));
+}
+
+#[test]
+fn test_sql_error_names_exceeded_timeout() {
+    let error = SqlError {
+        version: "20260121120000".to_string(),
+        direction: MigrationDirection::Up,
+        statement: "ALTER TABLE users ADD COLUMN age INT".to_string(),
+        attempts: 1,
+        timeout: SessionTimeouts {
+            lock_timeout: Some(5),
+            statement_timeout: None,
+        }
+        .exceeded_by_code(Dialect::PostgreSQL, Some("55P03"), None),
+        source: sqlx::Error::PoolTimedOut,
+    };
+    let message = error.to_string();
+    assert!(
+        message.contains("The statement was stopped by lock_timeout (5s)"),
+        "{}",
+        message
+    );
+    assert!(message.contains("off-peak"), "{}", message);
+}
@@ -1,16 +1,29 @@
 // Services Layer
 // ドメインロジックを実行するサービス層
 
+pub mod column_lifecycle;
+pub mod column_size_advisor;
 pub mod config_loader;
 pub mod config_serializer;
 pub mod database_config_resolver;
+pub mod database_reset;
 pub mod destructive_change_detector;
+pub mod identifier_case;
+pub mod migration_files;
 pub mod migration_generator;
+pub mod migration_health_check;
 pub mod migration_pipeline;
+pub mod migration_policy_checker;
+pub mod migration_runner;
+pub mod preflight_queries;
 pub mod schema_checksum;
 pub mod schema_conversion;
 pub mod schema_diff_detector;
+pub mod schema_docs;
 pub mod schema_io;
 pub mod schema_validator;
+pub mod seed_files;
 pub mod traits;
 pub mod type_change_validator;
+pub mod view_definition;
+pub mod view_rename_propagation;
@@ -0,0 +1,558 @@
+// 事前確認クエリ（preflight.sql）
+//
+// 既存のテーブルに制約を追加するマイグレーションについて、現在のデータが
+// 制約に違反する行数を数える読み取り専用のクエリを生成します。
+// - CHECK制約: チェック式を満たさない行
+// - NOT NULL: NULLの行（既存カラム）、行の数（デフォルト値のない新しいカラム）
+// - 外部キー: 参照先に対応する行がない行
+//
+// generate は `--with-preflight-queries` 指定時に up.sql と並べて preflight.sql を書き出し、
+// apply は `--preflight` 指定時にマイグレーションごとの適用前にクエリを実行します。
+// クエリはマイグレーション適用前のスキーマに対して実行するため、
+// 同じマイグレーションで追加・リネームされるカラムを参照する制約は対象外です。
+// 識別子はすべて sql_quote のクォート関数を通して埋め込みます。
+
+use crate::adapters::sql_quote::{quote_identifier, quote_table_postgres};
+use crate::core::config::Dialect;
+use crate::core::schema::{Column, Constraint};
+use crate::core::schema_diff::{ColumnChange, SchemaDiff, TableDiff};
+use crate::services::migration_files::sql_parser::split_sql_statements;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// 事前確認クエリのファイル名（マイグレーションディレクトリ内）
+pub const PREFLIGHT_FILE: &str = "preflight.sql";
+
+/// クエリの対象の制約を示すコメントの接頭辞
+const LABEL_PREFIX: &str = "-- preflight:";
+
+/// 事前確認クエリ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreflightQuery {
+    /// 対象の制約（例: `NOT NULL users.email`）
+    pub label: String,
+    /// 違反する行数を返す `SELECT COUNT(*)` クエリ（終端のセミコロンなし）
+    pub sql: String,
+}
+
+/// 事前確認クエリの実行結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreflightViolation {
+    /// 対象の制約
+    pub label: String,
+    /// 制約に違反する行数
+    pub count: i64,
+}
+
+/// 事前確認クエリの生成
+#[derive(Debug, Clone)]
+pub struct PreflightQueryBuilder {
+    dialect: Dialect,
+}
+
+impl PreflightQueryBuilder {
+    /// 新しいPreflightQueryBuilderを作成
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect }
+    }
+
+    /// スキーマ差分で既存のテーブルに追加される制約ごとのクエリを生成
+    ///
+    /// 新しく作成するテーブルにはデータがないため対象外です。
+    pub fn build(&self, diff: &SchemaDiff) -> Vec<PreflightQuery> {
+        // 外部キーの参照先が同じマイグレーションで作成・リネームされる場合に使用
+        let added_tables: HashSet<&str> =
+            diff.added_tables.iter().map(|t| t.name.as_str()).collect();
+        let renamed_tables: BTreeMap<&str, &str> = diff
+            .renamed_tables
+            .iter()
+            .map(|r| (r.new_table.name.as_str(), r.old_name.as_str()))
+            .collect();
+
+        let mut queries = Vec::new();
+        for table_diff in &diff.modified_tables {
+            queries.extend(self.not_null_queries(table_diff));
+            for constraint in &table_diff.added_constraints {
+                match constraint {
+                    Constraint::CHECK {
+                        columns,
+                        check_expression,
+                    } if !references_new_columns(table_diff, columns) => {
+                        queries.push(self.check_query(
+                            &table_diff.table_name,
+                            columns,
+                            check_expression,
+                        ));
+                    }
+                    Constraint::FOREIGN_KEY {
+                        columns,
+                        referenced_table,
+                        referenced_columns,
+                        ..
+                    } => {
+                        if references_new_columns(table_diff, columns) {
+                            continue;
+                        }
+                        let referenced = if added_tables.contains(referenced_table.as_str()) {
+                            None
+                        } else {
+                            Some(
+                                renamed_tables
+                                    .get(referenced_table.as_str())
+                                    .copied()
+                                    .unwrap_or(referenced_table),
+                            )
+                        };
+                        queries.push(self.foreign_key_query(
+                            &table_diff.table_name,
+                            columns,
+                            referenced_table,
+                            referenced,
+                            referenced_columns,
+                        ));
+                    }
+                    // 追加カラムを参照するCHECK、PRIMARY KEY / UNIQUEは事前確認しない
+                    Constraint::CHECK { .. }
+                    | Constraint::PRIMARY_KEY { .. }
+                    | Constraint::UNIQUE { .. } => {}
+                    // 事前確認クエリのない種類の制約は適用時のエラーに任せる
+                    _ => {}
+                }
+            }
+        }
+        queries
+    }
+
+    /// NOT NULLになるカラムのクエリ
+    ///
+    /// 既存のカラムはNULLの行を数えます。デフォルト値のない新しいNOT NULLカラムは
+    /// すべての行がNULLになるため、テーブルの行数を数えます。
+    fn not_null_queries(&self, table_diff: &TableDiff) -> Vec<PreflightQuery> {
+        let table = &table_diff.table_name;
+        let mut queries = Vec::new();
+
+        for column in &table_diff.added_columns {
+            if !column.nullable && !fills_new_rows(column) {
+                queries.push(PreflightQuery {
+                    label: format!(
+                        "NOT NULL {}.{} (new column without default)",
+                        table, column.name
+                    ),
+                    sql: format!("SELECT COUNT(*) FROM {}", self.quote_table(table)),
+                });
+            }
+        }
+
+        let modified = table_diff
+            .modified_columns
+            .iter()
+            .map(|c| (c.column_name.as_str(), c.column_name.as_str(), &c.changes));
+        let renamed = table_diff
+            .renamed_columns
+            .iter()
+            .map(|c| (c.old_name.as_str(), c.new_column.name.as_str(), &c.changes));
+        for (old_name, new_name, changes) in modified.chain(renamed) {
+            let becomes_not_null = changes.iter().any(|change| {
+                matches!(
+                    change,
+                    ColumnChange::NullableChanged {
+                        old_nullable: true,
+                        new_nullable: false,
+                    }
+                )
+            });
+            if becomes_not_null {
+                queries.push(PreflightQuery {
+                    label: format!("NOT NULL {}.{}", table, new_name),
+                    sql: format!(
+                        "SELECT COUNT(*) FROM {} WHERE {} IS NULL",
+                        self.quote_table(table),
+                        self.quote_identifier(old_name)
+                    ),
+                });
+            }
+        }
+        queries
+    }
+
+    /// CHECK制約のクエリ
+    ///
+    /// チェック式がNULLになる行は制約を満たすため、`NOT (式)` が真の行だけを数えます。
+    fn check_query(&self, table: &str, columns: &[String], expression: &str) -> PreflightQuery {
+        let label = if columns.is_empty() {
+            format!("CHECK {} ({})", table, expression)
+        } else {
+            format!("CHECK {}.{} ({})", table, columns.join(", "), expression)
+        };
+        PreflightQuery {
+            label,
+            sql: format!(
+                "SELECT COUNT(*) FROM {} WHERE NOT ({})",
+                self.quote_table(table),
+                expression
+            ),
+        }
+    }
+
+    /// 外部キー制約のクエリ
+    ///
+    /// 参照元のカラムがすべて非NULLで、参照先に対応する行がない行を数えます。
+    /// 参照先のテーブルが同じマイグレーションで作成される場合（`referenced` がNone）は、
+    /// 参照元のカラムが非NULLの行がすべて違反になります。
+    fn foreign_key_query(
+        &self,
+        table: &str,
+        columns: &[String],
+        referenced_table: &str,
+        referenced: Option<&str>,
+        referenced_columns: &[String],
+    ) -> PreflightQuery {
+        let child = self.quote_identifier("c");
+        let parent = self.quote_identifier("p");
+        let not_null = columns
+            .iter()
+            .map(|column| format!("{}.{} IS NOT NULL", child, self.quote_identifier(column)))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let sql = match referenced {
+            Some(referenced) => {
+                let join = columns
+                    .iter()
+                    .zip(referenced_columns)
+                    .map(|(column, referenced_column)| {
+                        format!(
+                            "{}.{} = {}.{}",
+                            parent,
+                            self.quote_identifier(referenced_column),
+                            child,
+                            self.quote_identifier(column)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" AND ");
+                format!(
+                    "SELECT COUNT(*) FROM {} {} WHERE {} AND NOT EXISTS (SELECT 1 FROM {} {} WHERE {})",
+                    self.quote_table(table),
+                    child,
+                    not_null,
+                    self.quote_table(referenced),
+                    parent,
+                    join
+                )
+            }
+            None => format!(
+                "SELECT COUNT(*) FROM {} {} WHERE {}",
+                self.quote_table(table),
+                child,
+                not_null
+            ),
+        };
+
+        PreflightQuery {
+            label: format!(
+                "FOREIGN KEY {}({}) -> {}({})",
+                table,
+                columns.join(", "),
+                referenced_table,
+                referenced_columns.join(", ")
+            ),
+            sql,
+        }
+    }
+
+    fn quote_table(&self, name: &str) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => quote_table_postgres(name),
+            _ => self.quote_identifier(name),
+        }
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        quote_identifier(self.dialect, name)
+    }
+}
+
+/// 制約のカラムに、同じマイグレーションで追加・リネームされるカラムが含まれるか
+///
+/// 適用前のテーブルにはそのカラムがないため、クエリを実行できません。
+fn references_new_columns(table_diff: &TableDiff, columns: &[String]) -> bool {
+    columns.iter().any(|column| {
+        table_diff.added_columns.iter().any(|c| &c.name == column)
+            || table_diff
+                .renamed_columns
+                .iter()
+                .any(|c| &c.new_column.name == column)
+    })
+}
+
+/// 追加したカラムに既存の行の値が入るか（デフォルト値・自動採番・生成カラム）
+fn fills_new_rows(column: &Column) -> bool {
+    column.default_value.is_some()
+        || column.auto_increment == Some(true)
+        || column.generated.is_some()
+}
+
+/// preflight.sql の内容を生成
+pub fn render_preflight_file(queries: &[PreflightQuery]) -> String {
+    let mut content = String::from(
+        "-- Preflight queries generated by strata.\n\
+         -- Each query counts the existing rows that would violate a constraint added by up.sql.\n\
+         -- `strata apply --preflight` runs them before applying this migration.\n",
+    );
+    for query in queries {
+        content.push_str(&format!(
+            "\n{} {}\n{};\n",
+            LABEL_PREFIX, query.label, query.sql
+        ));
+    }
+    content
+}
+
+/// preflight.sql の内容を読み取る
+///
+/// 各ステートメントの直前の `-- preflight:` コメントを対象の制約として扱います。
+/// コメントがないステートメントは先頭行を対象の制約とします（手で追加したクエリ）。
+pub fn parse_preflight_file(content: &str) -> Vec<PreflightQuery> {
+    split_sql_statements(content)
+        .into_iter()
+        .filter_map(|statement| {
+            let mut label = None;
+            let mut sql_lines = Vec::new();
+            for line in statement.lines() {
+                let trimmed = line.trim();
+                if let Some(rest) = trimmed.strip_prefix(LABEL_PREFIX) {
+                    label = Some(rest.trim().to_string());
+                } else if !trimmed.is_empty() && !trimmed.starts_with("--") {
+                    sql_lines.push(line);
+                }
+            }
+            if sql_lines.is_empty() {
+                return None;
+            }
+            let sql = sql_lines.join("\n");
+            Some(PreflightQuery {
+                label: label.unwrap_or_else(|| sql_lines[0].trim().to_string()),
+                sql,
+            })
+        })
+        .collect()
+}
+
+/// 違反件数を表示用に整形
+pub fn format_violations(violations: &[PreflightViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("  - {}: {} row(s)", violation.label, violation.count))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{ColumnType, Table};
+    use crate::core::schema_diff::{ColumnDiff, RenamedTable};
+
+    fn integer_column(name: &str, nullable: bool) -> Column {
+        Column::new(
+            name.to_string(),
+            ColumnType::INTEGER { precision: None },
+            nullable,
+        )
+    }
+
+    fn diff_with(table_diff: TableDiff) -> SchemaDiff {
+        let mut diff = SchemaDiff::new();
+        diff.modified_tables.push(table_diff);
+        diff
+    }
+
+    fn check_diff() -> SchemaDiff {
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.added_constraints.push(Constraint::CHECK {
+            columns: vec!["age".to_string()],
+            check_expression: "age >= 0".to_string(),
+        });
+        diff_with(table_diff)
+    }
+
+    fn foreign_key_diff() -> SchemaDiff {
+        let mut table_diff = TableDiff::new("posts".to_string());
+        table_diff.added_constraints.push(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        diff_with(table_diff)
+    }
+
+    #[test]
+    fn test_check_query_per_dialect() {
+        let diff = check_diff();
+        let expected = [
+            (
+                Dialect::PostgreSQL,
+                r#"SELECT COUNT(*) FROM "users" WHERE NOT (age >= 0)"#,
+            ),
+            (
+                Dialect::MySQL,
+                "SELECT COUNT(*) FROM `users` WHERE NOT (age >= 0)",
+            ),
+            (
+                Dialect::SQLite,
+                r#"SELECT COUNT(*) FROM "users" WHERE NOT (age >= 0)"#,
+            ),
+        ];
+        for (dialect, sql) in expected {
+            let queries = PreflightQueryBuilder::new(dialect).build(&diff);
+            assert_eq!(queries.len(), 1);
+            assert_eq!(queries[0].label, "CHECK users.age (age >= 0)");
+            assert_eq!(queries[0].sql, sql);
+        }
+    }
+
+    #[test]
+    fn test_not_null_query_for_existing_column() {
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.modified_columns.push(ColumnDiff {
+            column_name: "email".to_string(),
+            old_column: integer_column("email", true),
+            new_column: integer_column("email", false),
+            changes: vec![ColumnChange::NullableChanged {
+                old_nullable: true,
+                new_nullable: false,
+            }],
+        });
+        let diff = diff_with(table_diff);
+
+        let queries = PreflightQueryBuilder::new(Dialect::MySQL).build(&diff);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].label, "NOT NULL users.email");
+        assert_eq!(
+            queries[0].sql,
+            "SELECT COUNT(*) FROM `users` WHERE `email` IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_not_null_query_for_new_column_without_default() {
+        let mut table_diff = TableDiff::new("app.users".to_string());
+        table_diff
+            .added_columns
+            .push(integer_column("score", false));
+        let mut with_default = integer_column("level", false);
+        with_default.default_value = Some("1".to_string());
+        table_diff.added_columns.push(with_default);
+        table_diff
+            .added_columns
+            .push(integer_column("nickname", true));
+        let diff = diff_with(table_diff);
+
+        let queries = PreflightQueryBuilder::new(Dialect::PostgreSQL).build(&diff);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(
+            queries[0].label,
+            "NOT NULL app.users.score (new column without default)"
+        );
+        assert_eq!(queries[0].sql, r#"SELECT COUNT(*) FROM "app"."users""#);
+    }
+
+    #[test]
+    fn test_foreign_key_query_per_dialect() {
+        let diff = foreign_key_diff();
+
+        let queries = PreflightQueryBuilder::new(Dialect::PostgreSQL).build(&diff);
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].label, "FOREIGN KEY posts(user_id) -> users(id)");
+        assert_eq!(
+            queries[0].sql,
+            r#"SELECT COUNT(*) FROM "posts" "c" WHERE "c"."user_id" IS NOT NULL AND NOT EXISTS (SELECT 1 FROM "users" "p" WHERE "p"."id" = "c"."user_id")"#
+        );
+
+        let queries = PreflightQueryBuilder::new(Dialect::MySQL).build(&diff);
+        assert_eq!(
+            queries[0].sql,
+            "SELECT COUNT(*) FROM `posts` `c` WHERE `c`.`user_id` IS NOT NULL AND NOT EXISTS (SELECT 1 FROM `users` `p` WHERE `p`.`id` = `c`.`user_id`)"
+        );
+    }
+
+    #[test]
+    fn test_foreign_key_query_to_new_or_renamed_table() {
+        // 同じマイグレーションで作成するテーブルは適用前に存在しない
+        let mut diff = foreign_key_diff();
+        diff.added_tables.push(Table::new("users".to_string()));
+        let queries = PreflightQueryBuilder::new(Dialect::SQLite).build(&diff);
+        assert_eq!(
+            queries[0].sql,
+            r#"SELECT COUNT(*) FROM "posts" "c" WHERE "c"."user_id" IS NOT NULL"#
+        );
+
+        // リネームされるテーブルは旧名で参照する
+        let mut diff = foreign_key_diff();
+        diff.renamed_tables.push(RenamedTable {
+            old_name: "accounts".to_string(),
+            new_table: Table::new("users".to_string()),
+        });
+        let queries = PreflightQueryBuilder::new(Dialect::SQLite).build(&diff);
+        assert!(
+            queries[0].sql.contains(r#"FROM "accounts" "p""#),
+            "{}",
+            queries[0].sql
+        );
+    }
+
+    #[test]
+    fn test_constraints_on_new_columns_and_tables_are_skipped() {
+        let mut table_diff = TableDiff::new("users".to_string());
+        table_diff.added_columns.push(integer_column("age", true));
+        table_diff.added_constraints.push(Constraint::CHECK {
+            columns: vec!["age".to_string()],
+            check_expression: "age >= 0".to_string(),
+        });
+        table_diff.added_constraints.push(Constraint::UNIQUE {
+            columns: vec!["email".to_string()],
+        });
+        let mut diff = diff_with(table_diff);
+        diff.added_tables.push(Table::new("orders".to_string()));
+
+        assert!(PreflightQueryBuilder::new(Dialect::SQLite)
+            .build(&diff)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_quotes_identifiers() {
+        let mut table_diff = TableDiff::new(r#"odd"table"#.to_string());
+        table_diff.added_constraints.push(Constraint::FOREIGN_KEY {
+            columns: vec![r#"a"b"#.to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        let queries = PreflightQueryBuilder::new(Dialect::SQLite).build(&diff_with(table_diff));
+        assert!(
+            queries[0].sql.starts_with(
+                r#"SELECT COUNT(*) FROM "odd""table" "c" WHERE "c"."a""b" IS NOT NULL"#
+            ),
+            "{}",
+            queries[0].sql
+        );
+    }
+
+    #[test]
+    fn test_render_and_parse_round_trip() {
+        let queries = PreflightQueryBuilder::new(Dialect::PostgreSQL).build(&check_diff());
+        let content = render_preflight_file(&queries);
+        assert!(content.contains("-- preflight: CHECK users.age (age >= 0)"));
+        assert_eq!(parse_preflight_file(&content), queries);
+
+        // ラベルのないクエリは先頭行を対象とする
+        let parsed = parse_preflight_file("SELECT COUNT(*) FROM t WHERE x < 0;\n");
+        assert_eq!(parsed[0].label, "SELECT COUNT(*) FROM t WHERE x < 0");
+        assert!(parse_preflight_file(&render_preflight_file(&[])).is_empty());
+    }
+}
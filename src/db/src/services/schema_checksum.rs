@@ -10,6 +10,8 @@ use std::collections::BTreeMap;
 /// スキーマチェックサムサービス
 ///
 /// スキーマのSHA-256ハッシュ計算を行います。
+/// マイグレーションのバージョンや説明は計算に含まれないため、
+/// `strata rename-migration` で説明を変更しても `.meta.yaml` のチェックサムは変わりません。
 #[derive(Debug, Clone)]
 pub struct SchemaChecksumService {
     // 将来的な拡張のためのフィールドを予約
@@ -43,6 +45,27 @@ impl SchemaChecksumService {
         format!("{:x}", result)
     }
 
+    /// SQLファイルの内容のチェックサムを計算
+    ///
+    /// Windows環境でのチェックアウトで誤検出しないよう、BOMを除き改行コードを
+    /// LFに統一してから計算します。
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - SQLファイルの内容
+    ///
+    /// # Returns
+    ///
+    /// SHA-256ハッシュ（64文字の16進数文字列）
+    pub fn calculate_sql_checksum(&self, sql: &str) -> String {
+        let sql = sql.strip_prefix('\u{feff}').unwrap_or(sql);
+        let normalized = sql.replace("\r\n", "\n").replace('\r', "\n");
+
+        let mut hasher = Sha256::new();
+        hasher.update(normalized.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
     /// スキーマを正規化された文字列表現に変換
     ///
     /// テーブルやカラムの順序に依存しない一貫した表現を生成します。
@@ -82,6 +105,9 @@ impl SchemaChecksumService {
                     if let Some(auto_increment) = col.auto_increment {
                         col_data.insert("auto_increment".to_string(), auto_increment.to_string());
                     }
+                    if let Some(ref generated) = col.generated {
+                        col_data.insert("generated".to_string(), generated.to_sql_clause());
+                    }
                     col_data
                 })
                 .collect::<Vec<_>>();
@@ -94,8 +120,22 @@ impl SchemaChecksumService {
                 .map(|idx| {
                     let mut idx_data = BTreeMap::new();
                     idx_data.insert("name".to_string(), idx.name.clone());
-                    idx_data.insert("columns".to_string(), idx.columns.join(","));
+                    idx_data.insert(
+                        "columns".to_string(),
+                        idx.columns
+                            .iter()
+                            .map(|c| c.to_string())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    );
                     idx_data.insert("unique".to_string(), idx.unique.to_string());
+                    if let Some(index_type) = idx.index_type {
+                        idx_data.insert("type".to_string(), index_type.to_string());
+                    }
+                    // 部分インデックスでない場合は含めない（既存のチェックサムを変えないため）
+                    if let Some(ref predicate) = idx.where_clause {
+                        idx_data.insert("where".to_string(), predicate.clone());
+                    }
                     idx_data
                 })
                 .collect::<Vec<_>>();
@@ -147,6 +187,10 @@ impl SchemaChecksumService {
                             constraint_data
                                 .insert("check_expression".to_string(), check_expression.clone());
                         }
+                        other => {
+                            constraint_data
+                                .insert("columns".to_string(), other.columns().join(","));
+                        }
                     }
 
                     constraint_data
@@ -212,18 +256,16 @@ impl SchemaChecksumService {
                 Some(p) => format!("INTEGER({})", p),
                 None => "INTEGER".to_string(),
             },
+            // SMALLINT / BIGINT は非推奨の別名（INTEGER の precision 指定）と同じ文字列にし、
+            // 書き方を変えても既存のチェックサムが変わらないようにする
+            ColumnType::SMALLINT => "INTEGER(2)".to_string(),
+            ColumnType::BIGINT => "INTEGER(8)".to_string(),
             ColumnType::VARCHAR { length } => format!("VARCHAR({})", length),
             ColumnType::TEXT => "TEXT".to_string(),
             ColumnType::BOOLEAN => "BOOLEAN".to_string(),
             ColumnType::DATE => "DATE".to_string(),
-            ColumnType::TIMESTAMP { with_time_zone } => match with_time_zone {
-                Some(true) => "TIMESTAMP WITH TIME ZONE".to_string(),
-                _ => "TIMESTAMP".to_string(),
-            },
-            ColumnType::TIME { with_time_zone } => match with_time_zone {
-                Some(true) => "TIME WITH TIME ZONE".to_string(),
-                _ => "TIME".to_string(),
-            },
+            // 精度未指定時は精度導入前と同じ文字列になり、既存のチェックサムが変わらない
+            ColumnType::TIMESTAMP { .. } | ColumnType::TIME { .. } => column_type.to_string(),
             ColumnType::DECIMAL { precision, scale } => {
                 format!("DECIMAL({},{})", precision, scale)
             }
@@ -238,6 +280,7 @@ impl SchemaChecksumService {
             ColumnType::DialectSpecific { kind, params } => {
                 format!("DIALECT_SPECIFIC({},{})", kind, params)
             }
+            other => other.to_string(),
         }
     }
 
@@ -318,6 +361,22 @@ mod tests {
         assert!(!service.compare_checksums(checksum1, checksum3));
     }
 
+    #[test]
+    fn test_calculate_sql_checksum_ignores_line_endings_and_bom() {
+        let service = SchemaChecksumService::new();
+        let lf = service.calculate_sql_checksum("CREATE TABLE t (id INTEGER);\n");
+
+        assert_eq!(lf.len(), 64);
+        assert_eq!(
+            lf,
+            service.calculate_sql_checksum("\u{feff}CREATE TABLE t (id INTEGER);\r\n")
+        );
+        assert_ne!(
+            lf,
+            service.calculate_sql_checksum("CREATE TABLE t (id BIGINT);\n")
+        );
+    }
+
     #[test]
     fn test_normalize_schema() {
         let mut schema = Schema::new("1.0".to_string());
@@ -353,6 +412,7 @@ mod tests {
             EnumDefinition {
                 name: "status".to_string(),
                 values: vec!["active".to_string(), "inactive".to_string()],
+                renamed_from: None,
             },
         );
 
@@ -374,6 +434,7 @@ mod tests {
             EnumDefinition {
                 name: "status".to_string(),
                 values: vec!["active".to_string(), "inactive".to_string()],
+                renamed_from: None,
             },
         );
         schema1.enums.insert(
@@ -381,6 +442,7 @@ mod tests {
             EnumDefinition {
                 name: "role".to_string(),
                 values: vec!["admin".to_string(), "user".to_string()],
+                renamed_from: None,
             },
         );
 
@@ -390,6 +452,7 @@ mod tests {
             EnumDefinition {
                 name: "role".to_string(),
                 values: vec!["admin".to_string(), "user".to_string()],
+                renamed_from: None,
             },
         );
         schema2.enums.insert(
@@ -397,6 +460,7 @@ mod tests {
             EnumDefinition {
                 name: "status".to_string(),
                 values: vec!["active".to_string(), "inactive".to_string()],
+                renamed_from: None,
             },
         );
 
@@ -424,8 +488,12 @@ mod tests {
         ));
         table.indexes.push(crate::core::schema::Index {
             name: "idx_email".to_string(),
-            columns: vec!["email".to_string()],
+            columns: vec!["email".into()],
             unique: true,
+            index_type: None,
+            where_clause: None,
+            renamed_from: None,
+            metadata: BTreeMap::new(),
         });
         schema.add_table(table);
 
@@ -547,9 +615,11 @@ mod tests {
             ColumnType::DATE,
             ColumnType::TIMESTAMP {
                 with_time_zone: Some(true),
+                precision: None,
             },
             ColumnType::TIME {
                 with_time_zone: Some(true),
+                precision: None,
             },
             ColumnType::DECIMAL {
                 precision: 10,
@@ -1,5 +1,6 @@
 use super::{RawTableInfo, SchemaConversionService};
 use crate::adapters::database_introspector::{RawEnumInfo, RawViewInfo};
+use crate::core::config::Dialect;
 use crate::core::schema::{Schema, Table, View};
 use anyhow::{Context, Result};
 use std::collections::HashSet;
@@ -9,6 +10,15 @@ impl SchemaConversionService {
     pub fn convert_table(&self, raw: &RawTableInfo) -> Result<Table> {
         let mut table = Table::new(raw.name.clone());
 
+        // PostgreSQLのイントロスペクターは public 以外のスキーマのテーブルを `schema.table` 形式で返す
+        if matches!(self.type_mapping.dialect(), Dialect::PostgreSQL) {
+            if let Some((schema, _)) = raw.name.split_once('.') {
+                table.schema = Some(schema.to_string());
+            }
+        }
+
+        table.comment = raw.comment.clone().filter(|c| !c.is_empty());
+
         // カラムを変換
         for raw_column in &raw.columns {
             let column = self
@@ -4,7 +4,8 @@ use crate::adapters::database_introspector::{
 };
 use crate::adapters::type_mapping::TypeMetadata;
 use crate::core::schema::{
-    Column, ColumnType, Constraint, EnumDefinition, Index, ReferentialAction,
+    normalize_default_value, Column, ColumnType, Constraint, EnumDefinition, GeneratedColumn,
+    Index, IndexColumn, IndexType, NullsOrder, ReferentialAction, SortOrder,
 };
 use anyhow::{Context, Result};
 
@@ -17,6 +18,7 @@ impl SchemaConversionService {
             char_max_length: raw.char_max_length.map(|l| l as u32),
             numeric_precision: raw.numeric_precision.map(|p| p as u32),
             numeric_scale: raw.numeric_scale.map(|s| s as u32),
+            datetime_precision: raw.datetime_precision.map(|p| p as u32),
             udt_name: raw.udt_name.clone(),
             enum_names: if self.enum_names.is_empty() {
                 None
@@ -51,7 +53,9 @@ impl SchemaConversionService {
                         _ => default.clone(),
                     }
                 } else {
-                    default.clone()
+                    // 現在日時を表す now() / CURRENT_TIMESTAMP(6) / datetime('now') などは
+                    // スキーマ定義の正規の表記 CURRENT_TIMESTAMP に揃え、差分が出ないようにする
+                    normalize_default_value(default)
                 };
                 column.default_value = Some(normalized);
             }
@@ -62,16 +66,56 @@ impl SchemaConversionService {
             column.auto_increment = Some(true);
         }
 
+        // 生成カラムは式から値を算出するため、デフォルト値を持たない
+        if let Some(ref expression) = raw.generation_expression {
+            column.generated = Some(GeneratedColumn::new(
+                expression.trim(),
+                raw.generated_stored,
+            ));
+            column.default_value = None;
+        }
+
+        column.comment = raw.comment.clone().filter(|c| !c.is_empty());
+        column.on_update_current_timestamp = raw.on_update_current_timestamp;
+
         Ok(column)
     }
 
     /// 生のインデックス情報を内部モデルに変換
+    ///
+    /// 降順のカラムには `order: DESC` を設定し、NULL順序はソート順のデフォルト
+    /// （昇順はNULLS LAST、降順はNULLS FIRST）と異なる場合のみ設定します。
+    /// インデックスの種類はデフォルトのB-tree以外の場合のみ設定します。
     pub fn convert_index(&self, raw: &RawIndexInfo) -> Result<Index> {
-        Ok(Index {
-            name: raw.name.clone(),
-            columns: raw.columns.clone(),
-            unique: raw.unique,
-        })
+        let columns = raw
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let descending = raw.descending.get(i).copied().unwrap_or(false);
+                let mut column = IndexColumn::new(name.clone());
+                if let Some(Some(length)) = raw.prefix_lengths.get(i) {
+                    column = column.with_length(*length);
+                }
+                if descending {
+                    column = column.with_order(SortOrder::Desc);
+                }
+                match raw.nulls_first.get(i) {
+                    Some(true) if !descending => column.with_nulls(NullsOrder::First),
+                    Some(false) if descending => column.with_nulls(NullsOrder::Last),
+                    _ => column,
+                }
+            })
+            .collect();
+
+        let mut index = Index::with_columns(raw.name.clone(), columns, raw.unique);
+        index.where_clause = raw.predicate.clone();
+        index.index_type = raw
+            .index_type
+            .as_deref()
+            .and_then(IndexType::from_name)
+            .filter(|index_type| *index_type != IndexType::BTree);
+        Ok(index)
     }
 
     /// 生の制約情報を内部モデルに変換
@@ -85,16 +129,14 @@ impl SchemaConversionService {
                 referenced_table,
                 referenced_columns,
                 on_delete,
-            } => {
-                let on_delete_action = on_delete.as_deref().and_then(parse_on_delete_action);
-                Constraint::FOREIGN_KEY {
-                    columns: columns.clone(),
-                    referenced_table: referenced_table.clone(),
-                    referenced_columns: referenced_columns.clone(),
-                    on_delete: on_delete_action,
-                    on_update: None,
-                }
-            }
+                on_update,
+            } => Constraint::FOREIGN_KEY {
+                columns: columns.clone(),
+                referenced_table: referenced_table.clone(),
+                referenced_columns: referenced_columns.clone(),
+                on_delete: on_delete.as_deref().and_then(parse_referential_action),
+                on_update: on_update.as_deref().and_then(parse_referential_action),
+            },
             RawConstraintInfo::Unique { columns } => Constraint::UNIQUE {
                 columns: columns.clone(),
             },
@@ -115,11 +157,12 @@ impl SchemaConversionService {
         Ok(EnumDefinition {
             name: raw.name.clone(),
             values: raw.values.clone(),
+            renamed_from: None,
         })
     }
 }
 
-fn parse_on_delete_action(action: &str) -> Option<ReferentialAction> {
+fn parse_referential_action(action: &str) -> Option<ReferentialAction> {
     match action {
         "CASCADE" => Some(ReferentialAction::Cascade),
         "SET NULL" => Some(ReferentialAction::SetNull),
@@ -12,12 +12,13 @@ mod tests;
 use crate::adapters::database_introspector::{RawColumnInfo, RawConstraintInfo, RawIndexInfo};
 use crate::adapters::type_mapping::TypeMappingService;
 use crate::core::config::Dialect;
+use serde::Serialize;
 use std::collections::HashSet;
 
 /// 生のテーブル情報
 ///
 /// DatabaseIntrospector から取得した全テーブル情報を保持します。
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RawTableInfo {
     /// テーブル名
     pub name: String,
@@ -27,6 +28,8 @@ pub struct RawTableInfo {
     pub indexes: Vec<RawIndexInfo>,
     /// 制約情報
     pub constraints: Vec<RawConstraintInfo>,
+    /// テーブルコメント
+    pub comment: Option<String>,
 }
 
 /// スキーマ変換サービス
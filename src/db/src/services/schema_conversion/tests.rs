@@ -1,7 +1,10 @@
 use super::*;
 use crate::adapters::database_introspector::{RawEnumInfo, RawViewInfo};
 use crate::core::config::Dialect;
-use crate::core::schema::{ColumnType, Constraint};
+use crate::core::schema::{
+    ColumnType, Constraint, GeneratedColumn, IndexColumn, IndexType, NullsOrder, ReferentialAction,
+    SortOrder,
+};
 use std::collections::HashSet;
 
 // =========================================================================
@@ -39,11 +42,16 @@ fn test_convert_column_integer() {
         char_max_length: None,
         numeric_precision: Some(32),
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -53,6 +61,38 @@ fn test_convert_column_integer() {
     assert!(matches!(column.column_type, ColumnType::INTEGER { .. }));
 }
 
+#[test]
+fn test_convert_column_generated() {
+    let service = SchemaConversionService::new(Dialect::PostgreSQL);
+    let raw = RawColumnInfo {
+        name: "total".to_string(),
+        data_type: "integer".to_string(),
+        is_nullable: true,
+        default_value: None,
+        char_max_length: None,
+        numeric_precision: Some(32),
+        numeric_scale: None,
+        datetime_precision: None,
+        udt_name: None,
+        auto_increment: None,
+        enum_values: None,
+        set_values: None,
+        is_unsigned: false,
+        generation_expression: Some("(price * 2)".to_string()),
+        generated_stored: true,
+        on_update_current_timestamp: false,
+        comment: None,
+    };
+
+    let column = service.convert_column(&raw).unwrap();
+
+    assert_eq!(
+        column.generated,
+        Some(GeneratedColumn::new("(price * 2)", true))
+    );
+    assert_eq!(column.default_value, None);
+}
+
 #[test]
 fn test_convert_column_varchar() {
     let service = SchemaConversionService::new(Dialect::PostgreSQL);
@@ -64,11 +104,16 @@ fn test_convert_column_varchar() {
         char_max_length: Some(255),
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -97,11 +142,16 @@ fn test_convert_column_enum() {
         char_max_length: None,
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: Some("status".to_string()),
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -123,11 +173,16 @@ fn test_convert_column_sqlite_integer() {
         char_max_length: None,
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -147,11 +202,16 @@ fn test_convert_column_mysql_varchar() {
         char_max_length: Some(100),
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -163,6 +223,67 @@ fn test_convert_column_mysql_varchar() {
     ));
 }
 
+#[test]
+fn test_convert_column_datetime_precision() {
+    let convert = |dialect, data_type: &str, datetime_precision| {
+        let raw = RawColumnInfo {
+            name: "created_at".to_string(),
+            data_type: data_type.to_string(),
+            is_nullable: true,
+            default_value: None,
+            char_max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            datetime_precision,
+            udt_name: None,
+            auto_increment: None,
+            enum_values: None,
+            set_values: None,
+            is_unsigned: false,
+            generation_expression: None,
+            generated_stored: false,
+            on_update_current_timestamp: false,
+            comment: None,
+        };
+        SchemaConversionService::new(dialect)
+            .convert_column(&raw)
+            .unwrap()
+            .column_type
+    };
+
+    // MySQL の DATETIME(6) は精度を保持し、DATETIME（fsp 0）は未指定になる
+    assert_eq!(
+        convert(Dialect::MySQL, "datetime", Some(6)),
+        ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: Some(6),
+        }
+    );
+    assert_eq!(
+        convert(Dialect::MySQL, "time", Some(0)),
+        ColumnType::TIME {
+            with_time_zone: None,
+            precision: None,
+        }
+    );
+
+    // PostgreSQL の timestamptz(3) は精度を保持し、デフォルトの 6 桁は未指定になる
+    assert_eq!(
+        convert(Dialect::PostgreSQL, "timestamp with time zone", Some(3)),
+        ColumnType::TIMESTAMP {
+            with_time_zone: Some(true),
+            precision: Some(3),
+        }
+    );
+    assert_eq!(
+        convert(Dialect::PostgreSQL, "timestamp without time zone", Some(6)),
+        ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: None,
+        }
+    );
+}
+
 // =========================================================================
 // Issue #25: MySQL dialect-specific type conversion tests
 // =========================================================================
@@ -178,11 +299,16 @@ fn test_convert_column_mysql_boolean_default_true_normalized() {
         char_max_length: None,
         numeric_precision: Some(3),
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -202,11 +328,16 @@ fn test_convert_column_mysql_boolean_default_false_normalized() {
         char_max_length: None,
         numeric_precision: Some(3),
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -226,11 +357,16 @@ fn test_convert_column_mysql_tinyint_unsigned() {
         char_max_length: None,
         numeric_precision: Some(3),
         numeric_scale: Some(0),
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: true,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -258,6 +394,7 @@ fn test_convert_column_mysql_set() {
         char_max_length: None,
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
@@ -268,6 +405,10 @@ fn test_convert_column_mysql_set() {
             "admin".to_string(),
         ]),
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -293,11 +434,16 @@ fn test_convert_column_mysql_mediumint_unsigned() {
         char_max_length: None,
         numeric_precision: Some(7),
         numeric_scale: Some(0),
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: true,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -325,11 +471,16 @@ fn test_convert_column_mysql_year() {
         char_max_length: None,
         numeric_precision: None,
         numeric_scale: None,
+        datetime_precision: None,
         udt_name: None,
         auto_increment: None,
         enum_values: None,
         set_values: None,
         is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
     };
 
     let column = service.convert_column(&raw).unwrap();
@@ -345,6 +496,80 @@ fn test_convert_column_mysql_year() {
     }
 }
 
+#[test]
+fn test_convert_column_mysql_on_update_current_timestamp() {
+    let service = SchemaConversionService::new(Dialect::MySQL);
+    let raw = RawColumnInfo {
+        name: "updated_at".to_string(),
+        data_type: "timestamp".to_string(),
+        is_nullable: false,
+        default_value: Some("CURRENT_TIMESTAMP".to_string()),
+        char_max_length: None,
+        numeric_precision: None,
+        numeric_scale: None,
+        datetime_precision: Some(0),
+        udt_name: None,
+        auto_increment: None,
+        enum_values: None,
+        set_values: None,
+        is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: true,
+        comment: None,
+    };
+
+    let column = service.convert_column(&raw).unwrap();
+
+    assert!(column.on_update_current_timestamp);
+    assert_eq!(column.default_value.as_deref(), Some("CURRENT_TIMESTAMP"));
+    assert!(column.generated.is_none());
+}
+
+#[test]
+fn test_convert_column_normalizes_current_timestamp_defaults() {
+    let cases = [
+        (Dialect::PostgreSQL, "now()"),
+        (Dialect::PostgreSQL, "CURRENT_TIMESTAMP"),
+        (Dialect::MySQL, "CURRENT_TIMESTAMP(6)"),
+        (Dialect::MySQL, "current_timestamp()"),
+        (Dialect::SQLite, "datetime('now')"),
+        (Dialect::SQLite, "(datetime('now'))"),
+    ];
+    for (dialect, default_value) in cases {
+        let service = SchemaConversionService::new(dialect);
+        let raw = RawColumnInfo {
+            name: "created_at".to_string(),
+            data_type: "timestamp".to_string(),
+            is_nullable: false,
+            default_value: Some(default_value.to_string()),
+            char_max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            datetime_precision: None,
+            udt_name: None,
+            auto_increment: None,
+            enum_values: None,
+            set_values: None,
+            is_unsigned: false,
+            generation_expression: None,
+            generated_stored: false,
+            on_update_current_timestamp: false,
+            comment: None,
+        };
+
+        let column = service.convert_column(&raw).unwrap();
+
+        assert_eq!(
+            column.default_value.as_deref(),
+            Some("CURRENT_TIMESTAMP"),
+            "{} ({})",
+            default_value,
+            dialect
+        );
+    }
+}
+
 // =========================================================================
 // convert_index テスト
 // =========================================================================
@@ -356,15 +581,39 @@ fn test_convert_index_simple() {
         name: "idx_email".to_string(),
         columns: vec!["email".to_string()],
         unique: true,
+        descending: vec![],
+        nulls_first: vec![],
+        predicate: None,
+        prefix_lengths: vec![],
+        index_type: None,
     };
 
     let index = service.convert_index(&raw).unwrap();
 
     assert_eq!(index.name, "idx_email");
-    assert_eq!(index.columns, vec!["email"]);
+    assert_eq!(index.column_names(), vec!["email"]);
     assert!(index.unique);
 }
 
+#[test]
+fn test_convert_index_partial() {
+    let service = SchemaConversionService::new(Dialect::PostgreSQL);
+    let raw = RawIndexInfo {
+        name: "idx_orders_customer_id".to_string(),
+        columns: vec!["customer_id".to_string()],
+        unique: true,
+        descending: vec![],
+        nulls_first: vec![],
+        predicate: Some("deleted_at IS NULL".to_string()),
+        prefix_lengths: vec![],
+        index_type: None,
+    };
+
+    let index = service.convert_index(&raw).unwrap();
+
+    assert_eq!(index.where_clause.as_deref(), Some("deleted_at IS NULL"));
+}
+
 #[test]
 fn test_convert_index_composite() {
     let service = SchemaConversionService::new(Dialect::MySQL);
@@ -372,6 +621,11 @@ fn test_convert_index_composite() {
         name: "idx_user_role".to_string(),
         columns: vec!["user_id".to_string(), "role_id".to_string()],
         unique: false,
+        descending: vec![],
+        nulls_first: vec![],
+        predicate: None,
+        prefix_lengths: vec![],
+        index_type: None,
     };
 
     let index = service.convert_index(&raw).unwrap();
@@ -380,6 +634,86 @@ fn test_convert_index_composite() {
     assert!(!index.unique);
 }
 
+#[test]
+fn test_convert_index_sort_order_and_nulls() {
+    let service = SchemaConversionService::new(Dialect::PostgreSQL);
+    let raw = RawIndexInfo {
+        name: "idx_events".to_string(),
+        columns: vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+        ],
+        unique: false,
+        descending: vec![false, true, true, false],
+        nulls_first: vec![false, true, false, true],
+        predicate: None,
+        prefix_lengths: vec![],
+        index_type: None,
+    };
+
+    let index = service.convert_index(&raw).unwrap();
+
+    // デフォルトのNULL順序（ASC: LAST / DESC: FIRST）は省略される
+    assert_eq!(index.columns[0], IndexColumn::new("a"));
+    assert_eq!(
+        index.columns[1],
+        IndexColumn::new("b").with_order(SortOrder::Desc)
+    );
+    assert_eq!(
+        index.columns[2],
+        IndexColumn::new("c")
+            .with_order(SortOrder::Desc)
+            .with_nulls(NullsOrder::Last)
+    );
+    assert_eq!(
+        index.columns[3],
+        IndexColumn::new("d").with_nulls(NullsOrder::First)
+    );
+}
+
+#[test]
+fn test_convert_index_prefix_lengths_and_type() {
+    let service = SchemaConversionService::new(Dialect::MySQL);
+    let raw = RawIndexInfo {
+        name: "idx_body".to_string(),
+        columns: vec!["body".to_string(), "id".to_string()],
+        unique: false,
+        descending: vec![false, false],
+        nulls_first: vec![],
+        predicate: None,
+        prefix_lengths: vec![Some(191), None],
+        index_type: Some("FULLTEXT".to_string()),
+    };
+
+    let index = service.convert_index(&raw).unwrap();
+
+    assert_eq!(
+        index.columns,
+        vec![
+            IndexColumn::new("body").with_length(191),
+            IndexColumn::new("id")
+        ]
+    );
+    assert_eq!(index.index_type, Some(IndexType::Fulltext));
+
+    // デフォルトのB-treeは省略される（PostgreSQLのアクセスメソッド名は小文字）
+    let btree = RawIndexInfo {
+        index_type: Some("btree".to_string()),
+        ..raw.clone()
+    };
+    assert_eq!(service.convert_index(&btree).unwrap().index_type, None);
+    let gin = RawIndexInfo {
+        index_type: Some("gin".to_string()),
+        ..raw
+    };
+    assert_eq!(
+        service.convert_index(&gin).unwrap().index_type,
+        Some(IndexType::Gin)
+    );
+}
+
 // =========================================================================
 // convert_constraint テスト
 // =========================================================================
@@ -423,6 +757,7 @@ fn test_convert_constraint_foreign_key() {
         referenced_table: "users".to_string(),
         referenced_columns: vec!["id".to_string()],
         on_delete: None,
+        on_update: None,
     };
 
     let constraint = service.convert_constraint(&raw).unwrap();
@@ -442,6 +777,52 @@ fn test_convert_constraint_foreign_key() {
     }
 }
 
+#[test]
+fn test_convert_constraint_foreign_key_actions() {
+    let service = SchemaConversionService::new(Dialect::PostgreSQL);
+    let raw = RawConstraintInfo::ForeignKey {
+        columns: vec!["user_id".to_string()],
+        referenced_table: "users".to_string(),
+        referenced_columns: vec!["id".to_string()],
+        on_delete: Some("SET NULL".to_string()),
+        on_update: Some("CASCADE".to_string()),
+    };
+
+    let constraint = service.convert_constraint(&raw).unwrap();
+
+    if let Constraint::FOREIGN_KEY {
+        on_delete,
+        on_update,
+        ..
+    } = constraint
+    {
+        assert_eq!(on_delete, Some(ReferentialAction::SetNull));
+        assert_eq!(on_update, Some(ReferentialAction::Cascade));
+    } else {
+        panic!("Expected FOREIGN_KEY");
+    }
+
+    // NO ACTION はデフォルトなので省略される
+    let raw = RawConstraintInfo::ForeignKey {
+        columns: vec!["user_id".to_string()],
+        referenced_table: "users".to_string(),
+        referenced_columns: vec!["id".to_string()],
+        on_delete: Some("NO ACTION".to_string()),
+        on_update: Some("NO ACTION".to_string()),
+    };
+    if let Constraint::FOREIGN_KEY {
+        on_delete,
+        on_update,
+        ..
+    } = service.convert_constraint(&raw).unwrap()
+    {
+        assert!(on_delete.is_none());
+        assert!(on_update.is_none());
+    } else {
+        panic!("Expected FOREIGN_KEY");
+    }
+}
+
 #[test]
 fn test_convert_constraint_unique() {
     let service = SchemaConversionService::new(Dialect::MySQL);
@@ -514,14 +895,20 @@ fn test_convert_table_minimal() {
             char_max_length: None,
             numeric_precision: None,
             numeric_scale: None,
+            datetime_precision: None,
             udt_name: None,
             auto_increment: None,
             enum_values: None,
             set_values: None,
             is_unsigned: false,
+            generation_expression: None,
+            generated_stored: false,
+            on_update_current_timestamp: false,
+            comment: None,
         }],
         indexes: vec![],
         constraints: vec![],
+        comment: None,
     };
 
     let table = service.convert_table(&raw).unwrap();
@@ -532,6 +919,30 @@ fn test_convert_table_minimal() {
     assert!(table.constraints.is_empty());
 }
 
+#[test]
+fn test_convert_table_sets_schema_for_qualified_postgres_name() {
+    let raw = RawTableInfo {
+        name: "app.users".to_string(),
+        columns: vec![],
+        indexes: vec![],
+        constraints: vec![],
+        comment: None,
+    };
+
+    let table = SchemaConversionService::new(Dialect::PostgreSQL)
+        .convert_table(&raw)
+        .unwrap();
+    assert_eq!(table.name, "app.users");
+    assert_eq!(table.schema.as_deref(), Some("app"));
+    assert_eq!(table.unqualified_name(), "users");
+
+    // PostgreSQL以外ではスキーマとして扱わない
+    let table = SchemaConversionService::new(Dialect::SQLite)
+        .convert_table(&raw)
+        .unwrap();
+    assert_eq!(table.schema, None);
+}
+
 #[test]
 fn test_convert_table_with_all_elements() {
     let service = SchemaConversionService::new(Dialect::PostgreSQL);
@@ -546,11 +957,16 @@ fn test_convert_table_with_all_elements() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             },
             RawColumnInfo {
                 name: "title".to_string(),
@@ -560,11 +976,16 @@ fn test_convert_table_with_all_elements() {
                 char_max_length: Some(200),
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             },
             RawColumnInfo {
                 name: "user_id".to_string(),
@@ -574,17 +995,27 @@ fn test_convert_table_with_all_elements() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             },
         ],
         indexes: vec![RawIndexInfo {
             name: "idx_title".to_string(),
             columns: vec!["title".to_string()],
             unique: false,
+            descending: vec![],
+            nulls_first: vec![],
+            predicate: None,
+            prefix_lengths: vec![],
+            index_type: None,
         }],
         constraints: vec![
             RawConstraintInfo::PrimaryKey {
@@ -595,8 +1026,10 @@ fn test_convert_table_with_all_elements() {
                 referenced_table: "users".to_string(),
                 referenced_columns: vec!["id".to_string()],
                 on_delete: None,
+                on_update: None,
             },
         ],
+        comment: None,
     };
 
     let table = service.convert_table(&raw).unwrap();
@@ -607,6 +1040,65 @@ fn test_convert_table_with_all_elements() {
     assert_eq!(table.constraints.len(), 2);
 }
 
+#[test]
+fn test_convert_table_preserves_database_order_in_yaml() {
+    use crate::core::schema::Schema;
+    use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+
+    let service = SchemaConversionService::new(Dialect::PostgreSQL);
+    let column = |name: &str| RawColumnInfo {
+        name: name.to_string(),
+        data_type: "integer".to_string(),
+        is_nullable: false,
+        default_value: None,
+        char_max_length: None,
+        numeric_precision: None,
+        numeric_scale: None,
+        datetime_precision: None,
+        udt_name: None,
+        auto_increment: None,
+        enum_values: None,
+        set_values: None,
+        is_unsigned: false,
+        generation_expression: None,
+        generated_stored: false,
+        on_update_current_timestamp: false,
+        comment: None,
+    };
+    // データベース上の順序（ordinal_position 順）はアルファベット順ではない
+    let raw = RawTableInfo {
+        name: "entries".to_string(),
+        columns: vec![column("z_col"), column("m_col"), column("a_col")],
+        indexes: vec![],
+        constraints: vec![RawConstraintInfo::PrimaryKey {
+            columns: vec!["z_col".to_string(), "a_col".to_string()],
+        }],
+        comment: None,
+    };
+
+    let table = service.convert_table(&raw).unwrap();
+    let mut schema = Schema::new("1.0".to_string());
+    schema.add_table(table);
+    let yaml = SchemaSerializerService::new()
+        .serialize_to_string(&schema)
+        .unwrap();
+
+    let z = yaml.find("name: z_col").unwrap();
+    let m = yaml.find("name: m_col").unwrap();
+    let a = yaml.find("name: a_col").unwrap();
+    assert!(
+        z < m && m < a,
+        "columns must keep database order:\n{}",
+        yaml
+    );
+    let primary_key = &yaml[yaml.find("primary_key:").unwrap()..];
+    assert!(
+        primary_key.find("z_col").unwrap() < primary_key.find("a_col").unwrap(),
+        "primary key must keep key order:\n{}",
+        yaml
+    );
+}
+
 // =========================================================================
 // build_schema テスト
 // =========================================================================
@@ -636,14 +1128,20 @@ fn test_build_schema_with_tables() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             }],
             indexes: vec![],
             constraints: vec![],
+            comment: None,
         },
         RawTableInfo {
             name: "posts".to_string(),
@@ -655,14 +1153,20 @@ fn test_build_schema_with_tables() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             }],
             indexes: vec![],
             constraints: vec![],
+            comment: None,
         },
     ];
 
@@ -712,11 +1216,16 @@ fn test_build_schema_complex() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: None,
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             },
             RawColumnInfo {
                 name: "status".to_string(),
@@ -726,21 +1235,32 @@ fn test_build_schema_complex() {
                 char_max_length: None,
                 numeric_precision: None,
                 numeric_scale: None,
+                datetime_precision: None,
                 udt_name: Some("status".to_string()),
                 auto_increment: None,
                 enum_values: None,
                 set_values: None,
                 is_unsigned: false,
+                generation_expression: None,
+                generated_stored: false,
+                on_update_current_timestamp: false,
+                comment: None,
             },
         ],
         indexes: vec![RawIndexInfo {
             name: "idx_status".to_string(),
             columns: vec!["status".to_string()],
             unique: false,
+            descending: vec![],
+            nulls_first: vec![],
+            predicate: None,
+            prefix_lengths: vec![],
+            index_type: None,
         }],
         constraints: vec![RawConstraintInfo::PrimaryKey {
             columns: vec!["id".to_string()],
         }],
+        comment: None,
     }];
 
     let raw_enums = vec![RawEnumInfo {
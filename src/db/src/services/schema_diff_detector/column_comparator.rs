@@ -1,18 +1,21 @@
 // カラムレベルの差分検出
 
+use crate::adapters::sql_generator::sqlite::sqlite_rowid_alias_column;
+use crate::core::config::Dialect;
 use crate::core::error::{ErrorLocation, ValidationWarning, WarningKind};
-use crate::core::schema::Column;
-use crate::core::schema_diff::{ColumnChange, ColumnDiff, RenamedColumn, TableDiff};
+use crate::core::schema::{normalize_default_value, Column, ColumnType, GeneratedColumn, Table};
+use crate::core::schema_diff::{ColumnChange, ColumnDiff, CommentChange, RenamedColumn, TableDiff};
 use std::collections::{HashMap, HashSet};
 
+use super::index_comparator::normalize_expression;
 use super::SchemaDiffDetectorService;
 
 impl SchemaDiffDetectorService {
     /// カラム差分を検出
     pub(crate) fn detect_column_diff(
         &self,
-        old_table: &crate::core::schema::Table,
-        new_table: &crate::core::schema::Table,
+        old_table: &Table,
+        new_table: &Table,
         table_diff: &mut TableDiff,
     ) {
         let old_col_map: HashMap<&str, &Column> = old_table
@@ -20,11 +23,6 @@ impl SchemaDiffDetectorService {
             .iter()
             .map(|c| (c.name.as_str(), c))
             .collect();
-        let new_col_map: HashMap<&str, &Column> = new_table
-            .columns
-            .iter()
-            .map(|c| (c.name.as_str(), c))
-            .collect();
         let old_column_names: HashSet<&String> =
             old_table.columns.iter().map(|c| &c.name).collect();
         let new_column_names: HashSet<&String> =
@@ -39,7 +37,14 @@ impl SchemaDiffDetectorService {
                 // 旧テーブルに該当カラムが存在するか確認 (O(1) lookup)
                 if let Some(old_column) = old_col_map.get(old_name.as_str()) {
                     // リネームとして検出
-                    let changes = self.detect_column_changes(old_column, new_column);
+                    let mut changes = self.detect_column_changes(old_column, new_column);
+                    self.retain_significant_changes(
+                        old_table,
+                        new_table,
+                        old_column,
+                        new_column,
+                        &mut changes,
+                    );
                     table_diff.renamed_columns.push(RenamedColumn {
                         old_name: old_name.clone(),
                         old_column: (*old_column).clone(),
@@ -56,37 +61,40 @@ impl SchemaDiffDetectorService {
         }
 
         // 追加されたカラム（リネームを除く）
-        for column_name in new_column_names.difference(&old_column_names) {
+        // 出力を実行ごとに安定させるため、集合ではなく宣言順に走査する
+        for column in &new_table.columns {
             // リネーム済みは除外
-            if renamed_new_names.contains(*column_name) {
+            if old_column_names.contains(&column.name) || renamed_new_names.contains(&column.name) {
                 continue;
             }
-            if let Some(column) = new_col_map.get(column_name.as_str()) {
-                table_diff.added_columns.push((*column).clone());
-            }
+            table_diff.added_columns.push(column.clone());
         }
 
-        // 削除されたカラム（リネームを除く）
-        for column_name in old_column_names.difference(&new_column_names) {
+        // 削除されたカラム（リネームを除く、旧スキーマの宣言順）
+        for column in &old_table.columns {
             // リネーム済みは除外
-            if renamed_old_names.contains(*column_name) {
+            if new_column_names.contains(&column.name) || renamed_old_names.contains(&column.name) {
                 continue;
             }
-            table_diff.removed_columns.push((*column_name).clone());
+            table_diff.removed_columns.push(column.name.clone());
         }
 
-        // 変更されたカラム (O(1) lookups via HashMap)
-        for column_name in old_column_names.intersection(&new_column_names) {
-            if let (Some(old_column), Some(new_column)) = (
-                old_col_map.get(column_name.as_str()),
-                new_col_map.get(column_name.as_str()),
-            ) {
+        // 変更されたカラム（新スキーマの宣言順、O(1) lookups via HashMap）
+        for new_column in &new_table.columns {
+            if let Some(old_column) = old_col_map.get(new_column.name.as_str()) {
                 // カラムの定義が変更されているか確認
-                if old_column != new_column {
-                    let column_diff = ColumnDiff::new(
-                        (*column_name).clone(),
+                if *old_column != new_column {
+                    let mut column_diff = ColumnDiff::new(
+                        new_column.name.clone(),
                         (*old_column).clone(),
-                        (*new_column).clone(),
+                        new_column.clone(),
+                    );
+                    self.retain_significant_changes(
+                        old_table,
+                        new_table,
+                        &column_diff.old_column,
+                        &column_diff.new_column,
+                        &mut column_diff.changes,
                     );
                     if !column_diff.changes.is_empty() {
                         table_diff.modified_columns.push(column_diff);
@@ -99,8 +107,8 @@ impl SchemaDiffDetectorService {
     /// カラム差分を検出（警告付き）
     pub(crate) fn detect_column_diff_with_warnings(
         &self,
-        old_table: &crate::core::schema::Table,
-        new_table: &crate::core::schema::Table,
+        old_table: &Table,
+        new_table: &Table,
         table_diff: &mut TableDiff,
         warnings: &mut Vec<ValidationWarning>,
     ) {
@@ -109,11 +117,6 @@ impl SchemaDiffDetectorService {
             .iter()
             .map(|c| (c.name.as_str(), c))
             .collect();
-        let new_col_map: HashMap<&str, &Column> = new_table
-            .columns
-            .iter()
-            .map(|c| (c.name.as_str(), c))
-            .collect();
         let old_column_names: HashSet<&String> =
             old_table.columns.iter().map(|c| &c.name).collect();
         let new_column_names: HashSet<&String> =
@@ -128,7 +131,14 @@ impl SchemaDiffDetectorService {
                 // 旧テーブルに該当カラムが存在するか確認 (O(1) lookup)
                 if let Some(old_column) = old_col_map.get(old_name.as_str()) {
                     // リネームとして検出
-                    let changes = self.detect_column_changes(old_column, new_column);
+                    let mut changes = self.detect_column_changes(old_column, new_column);
+                    self.retain_significant_changes(
+                        old_table,
+                        new_table,
+                        old_column,
+                        new_column,
+                        &mut changes,
+                    );
                     table_diff.renamed_columns.push(RenamedColumn {
                         old_name: old_name.clone(),
                         old_column: (*old_column).clone(),
@@ -155,37 +165,40 @@ impl SchemaDiffDetectorService {
         }
 
         // 追加されたカラム（リネームを除く）
-        for column_name in new_column_names.difference(&old_column_names) {
+        // 出力を実行ごとに安定させるため、集合ではなく宣言順に走査する
+        for column in &new_table.columns {
             // リネーム済みは除外
-            if renamed_new_names.contains(*column_name) {
+            if old_column_names.contains(&column.name) || renamed_new_names.contains(&column.name) {
                 continue;
             }
-            if let Some(column) = new_col_map.get(column_name.as_str()) {
-                table_diff.added_columns.push((*column).clone());
-            }
+            table_diff.added_columns.push(column.clone());
         }
 
-        // 削除されたカラム（リネームを除く）
-        for column_name in old_column_names.difference(&new_column_names) {
+        // 削除されたカラム（リネームを除く、旧スキーマの宣言順）
+        for column in &old_table.columns {
             // リネーム済みは除外
-            if renamed_old_names.contains(*column_name) {
+            if new_column_names.contains(&column.name) || renamed_old_names.contains(&column.name) {
                 continue;
             }
-            table_diff.removed_columns.push((*column_name).clone());
+            table_diff.removed_columns.push(column.name.clone());
         }
 
-        // 変更されたカラム (O(1) lookups via HashMap)
-        for column_name in old_column_names.intersection(&new_column_names) {
-            if let (Some(old_column), Some(new_column)) = (
-                old_col_map.get(column_name.as_str()),
-                new_col_map.get(column_name.as_str()),
-            ) {
+        // 変更されたカラム（新スキーマの宣言順、O(1) lookups via HashMap）
+        for new_column in &new_table.columns {
+            if let Some(old_column) = old_col_map.get(new_column.name.as_str()) {
                 // カラムの定義が変更されているか確認
-                if old_column != new_column {
-                    let column_diff = ColumnDiff::new(
-                        (*column_name).clone(),
+                if *old_column != new_column {
+                    let mut column_diff = ColumnDiff::new(
+                        new_column.name.clone(),
                         (*old_column).clone(),
-                        (*new_column).clone(),
+                        new_column.clone(),
+                    );
+                    self.retain_significant_changes(
+                        old_table,
+                        new_table,
+                        &column_diff.old_column,
+                        &column_diff.new_column,
+                        &mut column_diff.changes,
                     );
                     if !column_diff.changes.is_empty() {
                         table_diff.modified_columns.push(column_diff);
@@ -219,8 +232,16 @@ impl SchemaDiffDetectorService {
             });
         }
 
-        // デフォルト値の変更を検出
-        if old_column.default_value != new_column.default_value {
+        // デフォルト値の変更を検出（now() と CURRENT_TIMESTAMP のような同等の表記は同じとみなす）
+        if old_column
+            .default_value
+            .as_deref()
+            .map(normalize_default_value)
+            != new_column
+                .default_value
+                .as_deref()
+                .map(normalize_default_value)
+        {
             changes.push(ColumnChange::DefaultValueChanged {
                 old_default: old_column.default_value.clone(),
                 new_default: new_column.default_value.clone(),
@@ -235,14 +256,151 @@ impl SchemaDiffDetectorService {
             });
         }
 
+        // ON UPDATE CURRENT_TIMESTAMP の追加・削除を検出
+        if old_column.on_update_current_timestamp != new_column.on_update_current_timestamp {
+            changes.push(ColumnChange::OnUpdateCurrentTimestampChanged {
+                old_on_update: old_column.on_update_current_timestamp,
+                new_on_update: new_column.on_update_current_timestamp,
+            });
+        }
+
+        // 生成カラム定義の変更を検出
+        if old_column.generated != new_column.generated {
+            changes.push(ColumnChange::GeneratedChanged {
+                old_generated: old_column.generated.clone(),
+                new_generated: new_column.generated.clone(),
+            });
+        }
+
+        // コメントの変更を検出（空文字列とコメントなしは同じとみなす）
+        if let Some(change) =
+            CommentChange::detect(old_column.comment_text(), new_column.comment_text())
+        {
+            changes.push(ColumnChange::CommentChanged {
+                old_comment: change.old_comment,
+                new_comment: change.new_comment,
+            });
+        }
+
         changes
     }
+
+    /// 方言上は等価とみなせる変更を除外
+    ///
+    /// SQLiteの INTEGER PRIMARY KEY は AUTOINCREMENT キーワードの有無に関わらず
+    /// ROWID のエイリアスとして自動採番されるため、strict_autoincrement が
+    /// 有効でない限り auto_increment の差異を無視します。
+    ///
+    /// また、SQLiteは DECIMAL の精度・スケールを保持しないため、
+    /// DECIMAL同士の精度・スケールのみの変更も無視します。
+    ///
+    /// TIMESTAMP / TIME の秒の小数部の桁数は、方言のデフォルト精度を補って比較し、
+    /// 未指定と明示的なデフォルト精度（PostgreSQL の 6 など）の違いは無視します。
+    ///
+    /// 生成カラムの式は、空白と式全体を囲む括弧の違いを無視して比較します
+    /// （データベースはイントロスペクション時に式を整形して返すため）。
+    fn retain_significant_changes(
+        &self,
+        old_table: &Table,
+        new_table: &Table,
+        old_column: &Column,
+        new_column: &Column,
+        changes: &mut Vec<ColumnChange>,
+    ) {
+        if generated_equivalent(&old_column.generated, &new_column.generated) {
+            changes.retain(|change| !matches!(change, ColumnChange::GeneratedChanged { .. }));
+        }
+
+        if let Some(dialect) = self.options.dialect {
+            if datetime_precision_equivalent(
+                &old_column.column_type,
+                &new_column.column_type,
+                dialect,
+            ) {
+                changes.retain(|change| !matches!(change, ColumnChange::TypeChanged { .. }));
+            }
+        }
+
+        if self.options.dialect != Some(Dialect::SQLite) {
+            return;
+        }
+
+        if matches!(
+            (&old_column.column_type, &new_column.column_type),
+            (ColumnType::DECIMAL { .. }, ColumnType::DECIMAL { .. })
+        ) {
+            changes.retain(|change| !matches!(change, ColumnChange::TypeChanged { .. }));
+        }
+
+        if self.options.sqlite_strict_autoincrement {
+            return;
+        }
+
+        let is_rowid_alias = |table: &Table, column: &Column| {
+            sqlite_rowid_alias_column(table).is_some_and(|c| c.name == column.name)
+        };
+
+        if is_rowid_alias(old_table, old_column) && is_rowid_alias(new_table, new_column) {
+            changes.retain(|change| !matches!(change, ColumnChange::AutoIncrementChanged { .. }));
+        }
+    }
+}
+
+/// 生成カラム定義が、式の表記揺れを除いて同じか判定
+fn generated_equivalent(old: &Option<GeneratedColumn>, new: &Option<GeneratedColumn>) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            old.stored == new.stored
+                && normalize_expression(&old.expression) == normalize_expression(&new.expression)
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// TIMESTAMP / TIME 同士で、精度の違いが方言上は同じ型を指すか判定
+///
+/// SQLite は日時を TEXT として保存し精度を持たないため、精度のみの違いは常に同じ型とみなす。
+fn datetime_precision_equivalent(
+    old_type: &ColumnType,
+    new_type: &ColumnType,
+    dialect: Dialect,
+) -> bool {
+    let effective = |precision: &Option<u8>| match dialect {
+        Dialect::SQLite => None,
+        _ => precision.or(dialect.default_datetime_precision()),
+    };
+    match (old_type, new_type) {
+        (
+            ColumnType::TIMESTAMP {
+                with_time_zone: old_tz,
+                precision: old_precision,
+            },
+            ColumnType::TIMESTAMP {
+                with_time_zone: new_tz,
+                precision: new_precision,
+            },
+        )
+        | (
+            ColumnType::TIME {
+                with_time_zone: old_tz,
+                precision: old_precision,
+            },
+            ColumnType::TIME {
+                with_time_zone: new_tz,
+                precision: new_precision,
+            },
+        ) => old_tz == new_tz && effective(old_precision) == effective(new_precision),
+        _ => false,
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::core::schema::{Column, ColumnType, Schema, Table};
-    use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+    use crate::core::config::Dialect;
+    use crate::core::schema::{Column, ColumnType, GeneratedColumn, Schema, Table};
+    use crate::core::schema_diff::ColumnChange;
+    use crate::services::schema_diff_detector::{SchemaDiffDetectorService, SchemaDiffOptions};
 
     #[test]
     fn test_detect_column_added() {
@@ -536,6 +694,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_current_timestamp_equivalent_defaults_are_not_a_change() {
+        let service = SchemaDiffDetectorService::new();
+
+        let schema_with_default = |default_value: &str| {
+            let mut schema = Schema::new("1.0".to_string());
+            let mut table = Table::new("events".to_string());
+            let mut column = Column::new(
+                "created_at".to_string(),
+                ColumnType::TIMESTAMP {
+                    with_time_zone: None,
+                    precision: None,
+                },
+                false,
+            );
+            column.default_value = Some(default_value.to_string());
+            table.add_column(column);
+            schema.add_table(table);
+            schema
+        };
+
+        let diff = service.detect_diff(
+            &schema_with_default("now()"),
+            &schema_with_default("CURRENT_TIMESTAMP"),
+        );
+        assert!(diff.is_empty());
+
+        let diff = service.detect_diff(
+            &schema_with_default("CURRENT_TIMESTAMP"),
+            &schema_with_default("'2024-01-01 00:00:00'"),
+        );
+        assert_eq!(diff.modified_tables.len(), 1);
+    }
+
     // detect_diff_with_warningsのテスト
 
     #[test]
@@ -664,4 +856,382 @@ mod tests {
             .iter()
             .all(|w| w.kind == WarningKind::OldColumnNotFound));
     }
+
+    // SQLite AUTOINCREMENT 比較のテスト
+
+    fn create_pk_schema(auto_increment: Option<bool>) -> Schema {
+        use crate::core::schema::Constraint;
+
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        let mut id = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        id.auto_increment = auto_increment;
+        table.add_column(id);
+        table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        schema.add_table(table);
+        schema
+    }
+
+    fn sqlite_options(strict: bool) -> SchemaDiffOptions {
+        SchemaDiffOptions {
+            dialect: Some(Dialect::SQLite),
+            sqlite_strict_autoincrement: strict,
+            ignore_enum_order: false,
+            identifier_case: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_sqlite_ignores_auto_increment_on_integer_primary_key() {
+        let service = SchemaDiffDetectorService::new().with_options(sqlite_options(false));
+
+        let diff = service.detect_diff(&create_pk_schema(None), &create_pk_schema(Some(true)));
+        assert!(diff.is_empty());
+
+        let (diff, _) = service.detect_diff_with_warnings(
+            &create_pk_schema(Some(true)),
+            &create_pk_schema(Some(false)),
+        );
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_sqlite_strict_autoincrement_detects_change() {
+        let service = SchemaDiffDetectorService::new().with_options(sqlite_options(true));
+
+        let diff = service.detect_diff(&create_pk_schema(None), &create_pk_schema(Some(true)));
+
+        assert!(diff.sqlite_strict_autoincrement);
+        assert_eq!(diff.modified_tables.len(), 1);
+        assert!(matches!(
+            diff.modified_tables[0].modified_columns[0].changes[0],
+            ColumnChange::AutoIncrementChanged { .. }
+        ));
+    }
+
+    #[test]
+    fn test_sqlite_detects_auto_increment_on_non_primary_key() {
+        let service = SchemaDiffDetectorService::new().with_options(sqlite_options(false));
+
+        let mut old_schema = create_pk_schema(None);
+        let mut new_schema = create_pk_schema(None);
+        old_schema
+            .tables
+            .get_mut("users")
+            .unwrap()
+            .add_column(Column::new(
+                "seq".to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+        let mut seq = Column::new(
+            "seq".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        );
+        seq.auto_increment = Some(true);
+        new_schema.tables.get_mut("users").unwrap().add_column(seq);
+
+        let diff = service.detect_diff(&old_schema, &new_schema);
+
+        assert_eq!(diff.modified_tables.len(), 1);
+        assert_eq!(
+            diff.modified_tables[0].modified_columns[0].column_name,
+            "seq"
+        );
+    }
+
+    #[test]
+    fn test_postgres_detects_auto_increment_on_integer_primary_key() {
+        let service = SchemaDiffDetectorService::new().with_options(SchemaDiffOptions {
+            dialect: Some(Dialect::PostgreSQL),
+            sqlite_strict_autoincrement: false,
+            ignore_enum_order: false,
+            identifier_case: Default::default(),
+        });
+
+        let diff = service.detect_diff(&create_pk_schema(None), &create_pk_schema(Some(true)));
+
+        assert_eq!(diff.modified_tables.len(), 1);
+    }
+
+    fn create_decimal_schema(precision: u32, scale: u32) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("products".to_string());
+        table.add_column(Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL { precision, scale },
+            false,
+        ));
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_sqlite_ignores_decimal_precision_and_scale_changes() {
+        let service = SchemaDiffDetectorService::new().with_options(sqlite_options(false));
+
+        for (precision, scale) in [(12, 2), (8, 2), (10, 4)] {
+            let diff = service.detect_diff(
+                &create_decimal_schema(10, 2),
+                &create_decimal_schema(precision, scale),
+            );
+            assert!(diff.is_empty(), "DECIMAL({}, {})", precision, scale);
+        }
+
+        let postgres = SchemaDiffDetectorService::new().with_options(SchemaDiffOptions {
+            dialect: Some(Dialect::PostgreSQL),
+            sqlite_strict_autoincrement: false,
+            ignore_enum_order: false,
+            identifier_case: Default::default(),
+        });
+        let diff =
+            postgres.detect_diff(&create_decimal_schema(10, 2), &create_decimal_schema(8, 2));
+        assert_eq!(diff.modified_tables.len(), 1);
+    }
+
+    fn create_country_schema(column_type: ColumnType) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("addresses".to_string());
+        table.add_column(Column::new("country".to_string(), column_type, false));
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_char_varchar_kind_change_with_same_length() {
+        // CHAR は末尾を空白で埋めるため、長さが同じでも VARCHAR / TEXT との変更は型変更として扱う
+        let cases = [
+            (
+                ColumnType::CHAR { length: 2 },
+                ColumnType::VARCHAR { length: 2 },
+            ),
+            (
+                ColumnType::VARCHAR { length: 2 },
+                ColumnType::CHAR { length: 2 },
+            ),
+            (ColumnType::CHAR { length: 2 }, ColumnType::TEXT),
+            (
+                ColumnType::CHAR { length: 2 },
+                ColumnType::CHAR { length: 3 },
+            ),
+        ];
+
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let service = SchemaDiffDetectorService::new().with_options(SchemaDiffOptions {
+                dialect: Some(dialect),
+                sqlite_strict_autoincrement: false,
+                ignore_enum_order: false,
+                identifier_case: Default::default(),
+            });
+            for (old_type, new_type) in &cases {
+                let diff = service.detect_diff(
+                    &create_country_schema(old_type.clone()),
+                    &create_country_schema(new_type.clone()),
+                );
+                assert_eq!(
+                    diff.modified_tables.len(),
+                    1,
+                    "{:?}: {} -> {}",
+                    dialect,
+                    old_type,
+                    new_type
+                );
+                assert_eq!(
+                    diff.modified_tables[0].modified_columns[0].changes,
+                    vec![ColumnChange::TypeChanged {
+                        old_type: old_type.to_string(),
+                        new_type: new_type.to_string(),
+                    }]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_datetime_precision_compares_with_dialect_default() {
+        let timestamp = |precision| ColumnType::TIMESTAMP {
+            with_time_zone: Some(true),
+            precision,
+        };
+        let detect = |dialect, old_precision, new_precision| {
+            SchemaDiffDetectorService::new()
+                .with_options(SchemaDiffOptions {
+                    dialect: Some(dialect),
+                    sqlite_strict_autoincrement: false,
+                    ignore_enum_order: false,
+                    identifier_case: Default::default(),
+                })
+                .detect_diff(
+                    &create_country_schema(timestamp(old_precision)),
+                    &create_country_schema(timestamp(new_precision)),
+                )
+        };
+
+        // 未指定は方言のデフォルト精度（PostgreSQL: 6、MySQL: 0）と同じ型
+        assert!(detect(Dialect::PostgreSQL, None, Some(6)).is_empty());
+        assert!(detect(Dialect::MySQL, Some(0), None).is_empty());
+        assert!(detect(Dialect::SQLite, Some(3), Some(6)).is_empty());
+
+        let diff = detect(Dialect::PostgreSQL, None, Some(3));
+        assert_eq!(
+            diff.modified_tables[0].modified_columns[0].changes,
+            vec![ColumnChange::TypeChanged {
+                old_type: "TIMESTAMP WITH TIME ZONE".to_string(),
+                new_type: "TIMESTAMP(3) WITH TIME ZONE".to_string(),
+            }]
+        );
+        assert_eq!(
+            detect(Dialect::MySQL, None, Some(6)).modified_tables.len(),
+            1
+        );
+    }
+
+    fn create_passthrough_schema(kind: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("customers".to_string());
+        table.add_column(Column::new(
+            "email".to_string(),
+            ColumnType::DialectSpecific {
+                kind: kind.to_string(),
+                params: serde_json::json!({}),
+            },
+            false,
+        ));
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_passthrough_types_compare_by_udt_name() {
+        let service = SchemaDiffDetectorService::new();
+
+        for kind in ["citext", "email_address", "billing.address"] {
+            let diff = service.detect_diff(
+                &create_passthrough_schema(kind),
+                &create_passthrough_schema(kind),
+            );
+            assert!(diff.is_empty(), "{}", kind);
+        }
+
+        let diff = service.detect_diff(
+            &create_passthrough_schema("citext"),
+            &create_passthrough_schema("billing.email"),
+        );
+        assert_eq!(diff.modified_tables.len(), 1);
+        assert!(matches!(
+            &diff.modified_tables[0].modified_columns[0].changes[0],
+            ColumnChange::TypeChanged { old_type, new_type }
+                if old_type == "CITEXT" && new_type == "BILLING.EMAIL"
+        ));
+    }
+
+    fn create_generated_schema(generated: Option<GeneratedColumn>) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("orders".to_string());
+        table.add_column(Column::new(
+            "price".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        let mut total = Column::new(
+            "total".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        );
+        total.generated = generated;
+        table.add_column(total);
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_generated_expression_compares_normalized() {
+        let service = SchemaDiffDetectorService::new();
+
+        // データベースが返す整形済みの式（括弧・空白）は同じ定義とみなす
+        let diff = service.detect_diff(
+            &create_generated_schema(Some(GeneratedColumn::new("(price  * 2)", true))),
+            &create_generated_schema(Some(GeneratedColumn::new("price * 2", true))),
+        );
+        assert!(diff.is_empty());
+
+        let diff = service.detect_diff(
+            &create_generated_schema(Some(GeneratedColumn::new("price * 2", true))),
+            &create_generated_schema(Some(GeneratedColumn::new("price * 3", true))),
+        );
+        assert_eq!(
+            diff.modified_tables[0].modified_columns[0].changes,
+            vec![ColumnChange::GeneratedChanged {
+                old_generated: Some(GeneratedColumn::new("price * 2", true)),
+                new_generated: Some(GeneratedColumn::new("price * 3", true)),
+            }]
+        );
+
+        // STORED / VIRTUAL の切り替えと、通常カラムとの相互変換も変更として検出
+        let diff = service.detect_diff(
+            &create_generated_schema(Some(GeneratedColumn::new("price * 2", true))),
+            &create_generated_schema(Some(GeneratedColumn::new("price * 2", false))),
+        );
+        assert_eq!(diff.modified_tables.len(), 1);
+        let diff = service.detect_diff(
+            &create_generated_schema(None),
+            &create_generated_schema(Some(GeneratedColumn::new("price * 2", true))),
+        );
+        assert_eq!(diff.modified_tables.len(), 1);
+    }
+
+    fn create_commented_schema(
+        table_comment: Option<&str>,
+        column_comment: Option<&str>,
+    ) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.comment = table_comment.map(str::to_string);
+        let mut email = Column::new("email".to_string(), ColumnType::TEXT, false);
+        email.comment = column_comment.map(str::to_string);
+        table.add_column(email);
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_comment_changes() {
+        let service = SchemaDiffDetectorService::new();
+
+        // 空文字列とコメントなしは同じとみなす
+        let diff = service.detect_diff(
+            &create_commented_schema(Some(""), None),
+            &create_commented_schema(None, Some("")),
+        );
+        assert!(diff.is_empty());
+
+        let diff = service.detect_diff(
+            &create_commented_schema(None, None),
+            &create_commented_schema(Some("Registered users"), Some("Login address")),
+        );
+        let table_diff = &diff.modified_tables[0];
+        assert_eq!(
+            table_diff
+                .comment_change
+                .as_ref()
+                .unwrap()
+                .new_comment
+                .as_deref(),
+            Some("Registered users")
+        );
+        assert_eq!(
+            table_diff.modified_columns[0].changes,
+            vec![ColumnChange::CommentChanged {
+                old_comment: None,
+                new_comment: Some("Login address".to_string()),
+            }]
+        );
+    }
 }
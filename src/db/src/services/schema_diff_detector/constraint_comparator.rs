@@ -1,5 +1,6 @@
 // 制約差分検出
 
+use crate::core::schema::{normalize_check_expression, Constraint};
 use crate::core::schema_diff::TableDiff;
 use std::collections::HashSet;
 
@@ -14,17 +15,106 @@ impl SchemaDiffDetectorService {
         table_diff: &mut TableDiff,
     ) {
         // 制約は名前がないため、内容で比較
-        let old_constraints: HashSet<_> = old_table.constraints.iter().collect();
-        let new_constraints: HashSet<_> = new_table.constraints.iter().collect();
+        let old_constraints: HashSet<_> =
+            old_table.constraints.iter().map(comparison_key).collect();
+        let new_constraints: HashSet<_> =
+            new_table.constraints.iter().map(comparison_key).collect();
 
         // 追加された制約
-        for constraint in new_constraints.difference(&old_constraints) {
-            table_diff.added_constraints.push((*constraint).clone());
+        for constraint in &new_table.constraints {
+            if !old_constraints.contains(&comparison_key(constraint)) {
+                table_diff.added_constraints.push(constraint.clone());
+            }
         }
 
         // 削除された制約
-        for constraint in old_constraints.difference(&new_constraints) {
-            table_diff.removed_constraints.push((*constraint).clone());
+        for constraint in &old_table.constraints {
+            if !new_constraints.contains(&comparison_key(constraint)) {
+                table_diff.removed_constraints.push(constraint.clone());
+            }
         }
+
+        // 出力順を実行ごとに安定させる（種類 → 対象カラム、同順位は宣言順）
+        table_diff
+            .added_constraints
+            .sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        table_diff
+            .removed_constraints
+            .sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    }
+}
+
+/// 制約の比較キー
+#[derive(PartialEq, Eq, Hash)]
+enum ConstraintKey<'a> {
+    /// CHECK制約は正規化した式で比較する
+    Check(String),
+    Other(&'a Constraint),
+}
+
+/// 比較キーを取得
+///
+/// CHECK制約の対象カラムは参考情報として扱い、比較には使用しません
+/// （PostgreSQLのイントロスペクションは対象カラムを返すが、
+/// テーブル単位の制約はスキーマ定義でカラムを省略できるため）。
+fn comparison_key(constraint: &Constraint) -> ConstraintKey<'_> {
+    match constraint {
+        Constraint::CHECK {
+            check_expression, ..
+        } => ConstraintKey::Check(normalize_check_expression(check_expression)),
+        other => ConstraintKey::Other(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+    use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+
+    fn table_with_check(columns: Vec<&str>, expression: &str) -> Table {
+        let mut table = Table::new("events".to_string());
+        for column in ["start_date", "end_date"] {
+            table.add_column(Column::new(column.to_string(), ColumnType::DATE, false));
+        }
+        table.add_constraint(Constraint::CHECK {
+            columns: columns.into_iter().map(String::from).collect(),
+            check_expression: expression.to_string(),
+        });
+        table
+    }
+
+    fn detect(old_table: &Table, new_table: &Table) -> TableDiff {
+        let mut table_diff = TableDiff::new("events".to_string());
+        SchemaDiffDetectorService::new().detect_constraint_diff(
+            old_table,
+            new_table,
+            &mut table_diff,
+        );
+        table_diff
+    }
+
+    #[test]
+    fn test_check_compared_by_expression_without_columns() {
+        // データベースから読み込んだ制約はカラムと括弧付きの式を持つ
+        let introspected =
+            table_with_check(vec!["start_date", "end_date"], "(start_date < end_date)");
+        let declared = table_with_check(vec![], "start_date < end_date");
+
+        let table_diff = detect(&introspected, &declared);
+
+        assert!(table_diff.added_constraints.is_empty());
+        assert!(table_diff.removed_constraints.is_empty());
+    }
+
+    #[test]
+    fn test_check_with_changed_expression_is_replaced() {
+        let old_table = table_with_check(vec![], "start_date < end_date");
+        let new_table = table_with_check(vec![], "start_date <= end_date");
+
+        let table_diff = detect(&old_table, &new_table);
+
+        assert_eq!(table_diff.added_constraints.len(), 1);
+        assert_eq!(table_diff.removed_constraints.len(), 1);
     }
 }
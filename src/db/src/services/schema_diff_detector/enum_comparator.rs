@@ -1,8 +1,8 @@
 // ENUM差分検出
 
-use crate::core::schema::{EnumDefinition, Schema};
-use crate::core::schema_diff::{EnumChangeKind, EnumColumnRef, EnumDiff, SchemaDiff};
-use std::collections::HashSet;
+use crate::core::schema::{ColumnType, EnumDefinition, Schema};
+use crate::core::schema_diff::{EnumChangeKind, EnumColumnRef, EnumDiff, RenamedEnum, SchemaDiff};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use super::SchemaDiffDetectorService;
 
@@ -13,29 +13,64 @@ impl SchemaDiffDetectorService {
         new_schema: &Schema,
         diff: &mut SchemaDiff,
     ) {
-        let old_enum_names: HashSet<&String> = old_schema.enums.keys().collect();
-        let new_enum_names: HashSet<&String> = new_schema.enums.keys().collect();
+        let old_enum_names: BTreeSet<&String> = old_schema.enums.keys().collect();
+        let new_enum_names: BTreeSet<&String> = new_schema.enums.keys().collect();
+
+        // リネームされたENUMの旧名を追跡
+        let mut renamed_old_names: HashSet<&str> = HashSet::new();
 
         for enum_name in new_enum_names.difference(&old_enum_names) {
             if let Some(enum_def) = new_schema.enums.get(*enum_name) {
+                // renamed_from がある場合はリネームとして処理（旧名が残っている場合を除く）
+                if let Some(old_enum) = enum_def
+                    .renamed_from
+                    .as_ref()
+                    .filter(|old_name| !new_schema.enums.contains_key(*old_name))
+                    .and_then(|old_name| old_schema.enums.get(old_name))
+                {
+                    diff.renamed_enums.push(RenamedEnum {
+                        old_name: old_enum.name.clone(),
+                        new_enum: enum_def.clone(),
+                    });
+                    renamed_old_names.insert(old_enum.name.as_str());
+                    // 値の変更は、リネーム後の型名で記録
+                    if self.values_changed(old_enum, enum_def) {
+                        let mut enum_diff = self.build_enum_diff(old_enum, enum_def, new_schema);
+                        enum_diff.enum_name = enum_def.name.clone();
+                        diff.modified_enums.push(enum_diff);
+                    }
+                    continue;
+                }
                 diff.added_enums.push(enum_def.clone());
             }
         }
 
         for enum_name in old_enum_names.difference(&new_enum_names) {
-            diff.removed_enums.push((*enum_name).clone());
+            if !renamed_old_names.contains(enum_name.as_str()) {
+                diff.removed_enums.push((*enum_name).clone());
+            }
         }
 
         for enum_name in old_enum_names.intersection(&new_enum_names) {
             let old_enum = old_schema.enums.get(*enum_name).unwrap();
             let new_enum = new_schema.enums.get(*enum_name).unwrap();
-            if old_enum.values != new_enum.values {
-                let enum_diff = self.build_enum_diff(old_enum, new_enum, new_schema);
-                diff.modified_enums.push(enum_diff);
+            if !self.values_changed(old_enum, new_enum) {
+                continue;
             }
+            let enum_diff = self.build_enum_diff(old_enum, new_enum, new_schema);
+            diff.modified_enums.push(enum_diff);
         }
     }
 
+    /// ENUM値が変更されたか
+    fn values_changed(&self, old_enum: &EnumDefinition, new_enum: &EnumDefinition) -> bool {
+        if old_enum.values == new_enum.values {
+            return false;
+        }
+        // 並び順を無視する設定では、値の集合が同じなら差分なしとする
+        !(self.options.ignore_enum_order && Self::same_values(old_enum, new_enum))
+    }
+
     fn build_enum_diff(
         &self,
         old_enum: &EnumDefinition,
@@ -68,8 +103,13 @@ impl SchemaDiffDetectorService {
             idx == old_enum.values.len()
         };
 
-        let change_kind = if removed_values.is_empty() && is_subsequence {
+        // 既存の値の並び順が変わる場合は再作成が必要（並び順を無視する設定を除く）
+        let change_kind = if !removed_values.is_empty() {
+            EnumChangeKind::Recreate
+        } else if is_subsequence || self.options.ignore_enum_order {
             EnumChangeKind::AddOnly
+        } else if added_values.is_empty() {
+            EnumChangeKind::ReorderOnly
         } else {
             EnumChangeKind::Recreate
         };
@@ -87,6 +127,13 @@ impl SchemaDiffDetectorService {
         }
     }
 
+    /// 並び順を除いて同じ値を持つか
+    fn same_values(old_enum: &EnumDefinition, new_enum: &EnumDefinition) -> bool {
+        let old_set: BTreeSet<&String> = old_enum.values.iter().collect();
+        let new_set: BTreeSet<&String> = new_enum.values.iter().collect();
+        old_enum.values.len() == new_enum.values.len() && old_set == new_set
+    }
+
     fn collect_enum_columns(schema: &Schema, enum_name: &str) -> Vec<EnumColumnRef> {
         let mut refs = Vec::new();
         for (table_name, table) in &schema.tables {
@@ -105,10 +152,33 @@ impl SchemaDiffDetectorService {
     }
 }
 
+/// ENUMのリネームを反映した変更前のスキーマ
+///
+/// 旧ENUM型名を参照するカラムを新しい型名に置き換えます。
+/// ENUMのリネームだけで型名が変わったカラムを、変更なしとして比較するために使用します。
+pub(crate) fn apply_enum_renames(schema: &Schema, renamed_enums: &[RenamedEnum]) -> Schema {
+    let renames: HashMap<&str, &str> = renamed_enums
+        .iter()
+        .map(|r| (r.old_name.as_str(), r.new_enum.name.as_str()))
+        .collect();
+    let mut schema = schema.clone();
+    for table in schema.tables.values_mut() {
+        for column in &mut table.columns {
+            if let ColumnType::Enum { name } = &mut column.column_type {
+                if let Some(new_name) = renames.get(name.as_str()) {
+                    *name = new_name.to_string();
+                }
+            }
+        }
+    }
+    schema
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::core::schema::{EnumDefinition, Schema};
-    use crate::services::schema_diff_detector::SchemaDiffDetectorService;
+    use crate::core::schema::{Column, ColumnType, EnumDefinition, Schema, Table};
+    use crate::core::schema_diff::{ColumnChange, EnumChangeKind};
+    use crate::services::schema_diff_detector::{SchemaDiffDetectorService, SchemaDiffOptions};
 
     #[test]
     fn test_detect_enum_added() {
@@ -119,6 +189,7 @@ mod tests {
         schema2.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string()],
+            renamed_from: None,
         });
 
         let diff = service.detect_diff(&schema1, &schema2);
@@ -134,6 +205,7 @@ mod tests {
         schema1.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string()],
+            renamed_from: None,
         });
 
         let schema2 = Schema::new("1.0".to_string());
@@ -151,12 +223,14 @@ mod tests {
         schema1.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string()],
+            renamed_from: None,
         });
 
         let mut schema2 = Schema::new("1.0".to_string());
         schema2.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let diff = service.detect_diff(&schema1, &schema2);
@@ -169,27 +243,182 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_enum_recreate_change() {
+    fn test_detect_enum_renamed() {
         let service = SchemaDiffDetectorService::new();
-        let mut schema1 = Schema::new("1.0".to_string());
-        schema1.add_enum(EnumDefinition {
-            name: "status".to_string(),
-            values: vec!["active".to_string(), "inactive".to_string()],
-        });
+        let (schema1, mut schema2) = status_schemas(&["active"], &["active", "inactive"]);
+        let mut renamed = schema2.enums.remove("status").unwrap();
+        renamed.name = "user_status".to_string();
+        renamed.renamed_from = Some("status".to_string());
+        schema2.add_enum(renamed);
+
+        let diff = service.detect_diff(&schema1, &schema2);
+
+        assert!(diff.added_enums.is_empty());
+        assert!(diff.removed_enums.is_empty());
+        assert_eq!(diff.renamed_enums.len(), 1);
+        assert_eq!(diff.renamed_enums[0].old_name, "status");
+        assert_eq!(diff.renamed_enums[0].new_enum.name, "user_status");
+        // 値の変更はリネーム後の型名で記録
+        assert_eq!(diff.modified_enums.len(), 1);
+        assert_eq!(diff.modified_enums[0].enum_name, "user_status");
+        assert_eq!(diff.modified_enums[0].change_kind, EnumChangeKind::AddOnly);
+    }
 
+    #[test]
+    fn test_detect_enum_renamed_from_missing_is_added() {
+        let service = SchemaDiffDetectorService::new();
+        let schema1 = Schema::new("1.0".to_string());
         let mut schema2 = Schema::new("1.0".to_string());
         schema2.add_enum(EnumDefinition {
-            name: "status".to_string(),
-            values: vec!["inactive".to_string(), "active".to_string()],
+            name: "user_status".to_string(),
+            values: vec!["active".to_string()],
+            renamed_from: Some("status".to_string()),
         });
 
         let diff = service.detect_diff(&schema1, &schema2);
 
-        assert_eq!(diff.modified_enums.len(), 1);
+        assert!(diff.renamed_enums.is_empty());
+        assert_eq!(diff.added_enums.len(), 1);
+    }
+
+    #[test]
+    fn test_enum_rename_is_not_a_column_type_change() {
+        let service = SchemaDiffDetectorService::new();
+        let schema_with = |enum_name: &str, renamed_from: Option<&str>, nullable: bool| {
+            let mut schema = Schema::new("1.0".to_string());
+            schema.add_enum(EnumDefinition {
+                name: enum_name.to_string(),
+                values: vec!["active".to_string()],
+                renamed_from: renamed_from.map(str::to_string),
+            });
+            let mut table = Table::new("users".to_string());
+            table.add_column(Column::new(
+                "status".to_string(),
+                ColumnType::Enum {
+                    name: enum_name.to_string(),
+                },
+                nullable,
+            ));
+            schema.add_table(table);
+            schema
+        };
+
+        let old_schema = schema_with("status", None, false);
+        let diff = service.detect_diff(
+            &old_schema,
+            &schema_with("user_status", Some("status"), false),
+        );
+        assert_eq!(diff.renamed_enums.len(), 1);
+        assert!(diff.modified_tables.is_empty());
+
+        // 他の変更は、リネーム後の型名のカラム同士の差分として記録
+        let diff = service.detect_diff(
+            &old_schema,
+            &schema_with("user_status", Some("status"), true),
+        );
+        let column_diff = &diff.modified_tables[0].modified_columns[0];
+        assert_eq!(column_diff.changes.len(), 1);
         assert!(matches!(
-            diff.modified_enums[0].change_kind,
-            crate::core::schema_diff::EnumChangeKind::Recreate
+            column_diff.changes[0],
+            ColumnChange::NullableChanged { .. }
         ));
+        assert_eq!(
+            column_diff.old_column.column_type,
+            ColumnType::Enum {
+                name: "user_status".to_string()
+            }
+        );
+
+        // リネームがなければ型の変更
+        let diff = service.detect_diff(&old_schema, &schema_with("user_status", None, false));
+        assert!(matches!(
+            diff.modified_tables[0].modified_columns[0].changes[0],
+            ColumnChange::TypeChanged { .. }
+        ));
+    }
+
+    /// status ENUM の値を変更前・変更後で指定したスキーマの組を作成
+    fn status_schemas(old_values: &[&str], new_values: &[&str]) -> (Schema, Schema) {
+        let schema_with = |values: &[&str]| {
+            let mut schema = Schema::new("1.0".to_string());
+            schema.add_enum(EnumDefinition {
+                name: "status".to_string(),
+                values: values.iter().map(|v| v.to_string()).collect(),
+                renamed_from: None,
+            });
+            schema
+        };
+        (schema_with(old_values), schema_with(new_values))
+    }
+
+    fn order_insensitive_service() -> SchemaDiffDetectorService {
+        SchemaDiffDetectorService::new().with_options(SchemaDiffOptions {
+            ignore_enum_order: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_detect_enum_recreate_change() {
+        let service = SchemaDiffDetectorService::new();
+        let (schema1, schema2) = status_schemas(&["active", "inactive"], &["pending", "active"]);
+
+        let diff = service.detect_diff(&schema1, &schema2);
+
+        assert_eq!(diff.modified_enums.len(), 1);
+        assert_eq!(diff.modified_enums[0].change_kind, EnumChangeKind::Recreate);
+    }
+
+    #[test]
+    fn test_detect_enum_reorder_only_change() {
+        let service = SchemaDiffDetectorService::new();
+        let (schema1, schema2) = status_schemas(&["active", "inactive"], &["inactive", "active"]);
+
+        let diff = service.detect_diff(&schema1, &schema2);
+
+        assert_eq!(diff.modified_enums.len(), 1);
+        let enum_diff = &diff.modified_enums[0];
+        assert_eq!(enum_diff.change_kind, EnumChangeKind::ReorderOnly);
+        assert!(enum_diff.change_kind.requires_recreate());
+        assert!(enum_diff.added_values.is_empty());
+        assert!(enum_diff.removed_values.is_empty());
+    }
+
+    #[test]
+    fn test_detect_enum_reorder_and_add_requires_recreate() {
+        let service = SchemaDiffDetectorService::new();
+        let (schema1, schema2) =
+            status_schemas(&["active", "inactive"], &["inactive", "active", "archived"]);
+
+        let diff = service.detect_diff(&schema1, &schema2);
+
+        assert_eq!(diff.modified_enums.len(), 1);
+        assert_eq!(diff.modified_enums[0].change_kind, EnumChangeKind::Recreate);
+        assert_eq!(diff.modified_enums[0].added_values, vec!["archived"]);
+    }
+
+    #[test]
+    fn test_detect_enum_order_opt_out() {
+        let service = order_insensitive_service();
+
+        // 並び替えのみは差分なし
+        let (schema1, schema2) = status_schemas(&["active", "inactive"], &["inactive", "active"]);
+        assert!(service
+            .detect_diff(&schema1, &schema2)
+            .modified_enums
+            .is_empty());
+
+        // 並び替えと追加は、値の追加のみとして扱う
+        let (schema1, schema2) =
+            status_schemas(&["active", "inactive"], &["inactive", "active", "archived"]);
+        let diff = service.detect_diff(&schema1, &schema2);
+        assert_eq!(diff.modified_enums.len(), 1);
+        assert_eq!(diff.modified_enums[0].change_kind, EnumChangeKind::AddOnly);
+
+        // 値の削除は引き続き再作成
+        let (schema1, schema2) = status_schemas(&["active", "inactive"], &["active"]);
+        let diff = service.detect_diff(&schema1, &schema2);
+        assert_eq!(diff.modified_enums[0].change_kind, EnumChangeKind::Recreate);
     }
 
     #[test]
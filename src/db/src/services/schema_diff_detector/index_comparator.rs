@@ -1,50 +1,63 @@
 // インデックス差分検出
 
-use crate::core::schema_diff::{IndexDiff, TableDiff};
+use crate::core::schema::{Index, Table};
+use crate::core::schema_diff::{IndexDiff, RenamedIndex, TableDiff};
 use std::collections::HashSet;
 
+use super::view_comparator::normalize_definition;
 use super::SchemaDiffDetectorService;
 
 impl SchemaDiffDetectorService {
     /// インデックス差分を検出
     pub(crate) fn detect_index_diff(
         &self,
-        old_table: &crate::core::schema::Table,
-        new_table: &crate::core::schema::Table,
+        old_table: &Table,
+        new_table: &Table,
         table_diff: &mut TableDiff,
     ) {
         let old_index_names: HashSet<&String> = old_table.indexes.iter().map(|i| &i.name).collect();
         let new_index_names: HashSet<&String> = new_table.indexes.iter().map(|i| &i.name).collect();
 
-        // 追加されたインデックス
-        for index_name in new_index_names.difference(&old_index_names) {
-            if let Some(index) = new_table.indexes.iter().find(|i| &i.name == *index_name) {
+        // リネームされたインデックスを検出（定義が同一の場合のみ）
+        let renamed = detect_renamed_indexes(old_table, new_table);
+        let renamed_old_names: HashSet<&str> =
+            renamed.iter().map(|r| r.old_name.as_str()).collect();
+        let renamed_new_names: HashSet<&str> =
+            renamed.iter().map(|r| r.new_index.name.as_str()).collect();
+
+        // 追加されたインデックス（宣言順）
+        for index in &new_table.indexes {
+            if !old_index_names.contains(&index.name)
+                && !renamed_new_names.contains(index.name.as_str())
+            {
                 table_diff.added_indexes.push(index.clone());
             }
         }
 
-        // 削除されたインデックス
-        for index_name in old_index_names.difference(&new_index_names) {
-            table_diff.removed_indexes.push((*index_name).clone());
+        // 削除されたインデックス（旧スキーマの宣言順）
+        for index in &old_table.indexes {
+            if !new_index_names.contains(&index.name)
+                && !renamed_old_names.contains(index.name.as_str())
+            {
+                table_diff.removed_indexes.push(index.name.clone());
+            }
         }
 
+        table_diff.renamed_indexes = renamed;
+
         // 変更されたインデックス（同名で内容が異なる）
-        for index_name in old_index_names.intersection(&new_index_names) {
-            let old_index = old_table
-                .indexes
-                .iter()
-                .find(|i| &i.name == *index_name)
-                .unwrap();
-            let new_index = new_table
-                .indexes
-                .iter()
-                .find(|i| &i.name == *index_name)
-                .unwrap();
-
-            // カラムリストまたはユニーク属性が異なる場合は変更とみなす
-            if old_index.columns != new_index.columns || old_index.unique != new_index.unique {
+        for new_index in &new_table.indexes {
+            let Some(old_index) = old_table.indexes.iter().find(|i| i.name == new_index.name)
+            else {
+                continue;
+            };
+            let index_name = &new_index.name;
+
+            // カラムリスト（ソート順・NULL順序・プレフィックス長を含む）、ユニーク属性、
+            // インデックスの種類、または部分インデックスの条件式が異なる場合は変更とみなす
+            if !indexes_equivalent(old_index, new_index) {
                 table_diff.modified_indexes.push(IndexDiff {
-                    index_name: (*index_name).clone(),
+                    index_name: index_name.clone(),
                     old_index: old_index.clone(),
                     new_index: new_index.clone(),
                 });
@@ -52,3 +65,264 @@ impl SchemaDiffDetectorService {
         }
     }
 }
+
+/// リネームされたインデックスを検出
+///
+/// 新しいインデックスの `renamed_from` が旧テーブルに存在するインデックスを指し、
+/// 定義（カラム・ユニーク属性・条件式）が同一であればリネームとみなします。
+/// 定義が異なる場合は削除+作成として扱います。
+/// 明示指定がない場合も、名前だけが異なり定義が同一のインデックスが
+/// 追加側・削除側で1対1に対応するときはリネームとして検出します。
+fn detect_renamed_indexes(old_table: &Table, new_table: &Table) -> Vec<RenamedIndex> {
+    let added: Vec<&Index> = new_table
+        .indexes
+        .iter()
+        .filter(|i| !old_table.indexes.iter().any(|o| o.name == i.name))
+        .collect();
+    let removed: Vec<&Index> = old_table
+        .indexes
+        .iter()
+        .filter(|i| !new_table.indexes.iter().any(|n| n.name == i.name))
+        .collect();
+
+    let mut renamed = Vec::new();
+    let mut used_old: HashSet<&str> = HashSet::new();
+    let mut used_new: HashSet<&str> = HashSet::new();
+
+    // renamed_from による明示的なリネーム
+    for new_index in &added {
+        let Some(ref old_name) = new_index.renamed_from else {
+            continue;
+        };
+        let Some(old_index) = removed.iter().find(|i| &i.name == old_name) else {
+            continue;
+        };
+        if used_old.contains(old_index.name.as_str()) || !indexes_equivalent(old_index, new_index) {
+            continue;
+        }
+        used_old.insert(old_index.name.as_str());
+        used_new.insert(new_index.name.as_str());
+        renamed.push(RenamedIndex {
+            old_name: old_index.name.clone(),
+            old_index: (*old_index).clone(),
+            new_index: (*new_index).clone(),
+        });
+    }
+
+    // 定義が同一で名前だけが異なるインデックスの自動検出（候補が一意な場合のみ）
+    for new_index in &added {
+        if used_new.contains(new_index.name.as_str()) || new_index.renamed_from.is_some() {
+            continue;
+        }
+        let candidates: Vec<&&Index> = removed
+            .iter()
+            .filter(|i| !used_old.contains(i.name.as_str()) && indexes_equivalent(i, new_index))
+            .collect();
+        let [old_index] = candidates.as_slice() else {
+            continue;
+        };
+        let competing = added
+            .iter()
+            .filter(|i| !used_new.contains(i.name.as_str()) && indexes_equivalent(old_index, i))
+            .count();
+        if competing != 1 {
+            continue;
+        }
+        used_old.insert(old_index.name.as_str());
+        used_new.insert(new_index.name.as_str());
+        renamed.push(RenamedIndex {
+            old_name: old_index.name.clone(),
+            old_index: (**old_index).clone(),
+            new_index: (*new_index).clone(),
+        });
+    }
+
+    renamed
+}
+
+/// 2つのインデックスの定義（名前以外）が同一かどうか
+///
+/// カラムリスト（ソート順・NULL順序・プレフィックス長を含む）、ユニーク属性、
+/// インデックスの種類、部分インデックスの条件式を比較します。
+/// 種類の省略とBTREEの明示は同じものとして扱います。
+fn indexes_equivalent(a: &Index, b: &Index) -> bool {
+    a.columns_equivalent(b)
+        && a.unique == b.unique
+        && a.effective_index_type() == b.effective_index_type()
+        && normalized_where_clause(a) == normalized_where_clause(b)
+}
+
+/// 比較用に正規化した部分インデックスの条件式
+///
+/// 空白の違いと、式全体を囲む括弧を無視します
+/// （PostgreSQLは `deleted_at IS NULL` を `(deleted_at IS NULL)` として返す）。
+fn normalized_where_clause(index: &Index) -> Option<String> {
+    let predicate = normalize_expression(index.where_clause.as_deref()?);
+    (!predicate.is_empty()).then_some(predicate)
+}
+
+/// 比較用に式を正規化（空白の違いと式全体を囲む括弧を無視）
+pub(super) fn normalize_expression(expr: &str) -> String {
+    let mut normalized = normalize_definition(expr);
+    while let Some(inner) = strip_enclosing_parens(&normalized) {
+        normalized = inner.trim().to_string();
+    }
+    normalized
+}
+
+/// 式全体が1組の括弧で囲まれている場合に内側を返す
+///
+/// `(a) OR (b)` のように先頭と末尾の括弧が対応しない場合は `None` を返します。
+fn strip_enclosing_parens(expr: &str) -> Option<&str> {
+    let inner = expr.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0usize;
+    let mut in_quote = false;
+    for ch in inner.chars() {
+        match ch {
+            '\'' => in_quote = !in_quote,
+            '(' if !in_quote => depth += 1,
+            ')' if !in_quote => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    (depth == 0).then_some(inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, IndexColumn, IndexType};
+
+    fn table_with_indexes(indexes: Vec<Index>) -> Table {
+        let mut table = Table::new("users".to_string());
+        for column in ["email", "tenant_id"] {
+            table.add_column(Column::new(column.to_string(), ColumnType::TEXT, false));
+        }
+        for index in indexes {
+            table.add_index(index);
+        }
+        table
+    }
+
+    fn email_index(name: &str) -> Index {
+        Index::new(name.to_string(), vec!["email".to_string()], true)
+    }
+
+    fn index_diff(old_table: &Table, new_table: &Table) -> TableDiff {
+        let mut table_diff = TableDiff::new("users".to_string());
+        SchemaDiffDetectorService::new().detect_index_diff(old_table, new_table, &mut table_diff);
+        table_diff
+    }
+
+    #[test]
+    fn test_detect_index_rename_with_renamed_from() {
+        let old_table = table_with_indexes(vec![email_index("idx_email")]);
+        let mut renamed = email_index("users_email_key");
+        renamed.renamed_from = Some("idx_email".to_string());
+        let new_table = table_with_indexes(vec![renamed]);
+
+        let table_diff = index_diff(&old_table, &new_table);
+
+        assert_eq!(table_diff.renamed_indexes.len(), 1);
+        assert_eq!(table_diff.renamed_indexes[0].old_name, "idx_email");
+        assert_eq!(
+            table_diff.renamed_indexes[0].new_index.name,
+            "users_email_key"
+        );
+        assert!(table_diff.added_indexes.is_empty());
+        assert!(table_diff.removed_indexes.is_empty());
+    }
+
+    #[test]
+    fn test_detect_index_rename_without_annotation() {
+        let old_table = table_with_indexes(vec![email_index("idx_email")]);
+        let new_table = table_with_indexes(vec![email_index("users_email_key")]);
+
+        let table_diff = index_diff(&old_table, &new_table);
+
+        assert_eq!(table_diff.renamed_indexes.len(), 1);
+        assert_eq!(table_diff.renamed_indexes[0].old_name, "idx_email");
+        assert!(table_diff.added_indexes.is_empty());
+        assert!(table_diff.removed_indexes.is_empty());
+    }
+
+    #[test]
+    fn test_index_rename_with_changed_definition_is_drop_and_create() {
+        let old_table = table_with_indexes(vec![email_index("idx_email")]);
+        let mut renamed = Index::new(
+            "users_email_key".to_string(),
+            vec!["tenant_id".to_string(), "email".to_string()],
+            true,
+        );
+        renamed.renamed_from = Some("idx_email".to_string());
+        let new_table = table_with_indexes(vec![renamed]);
+
+        let table_diff = index_diff(&old_table, &new_table);
+
+        assert!(table_diff.renamed_indexes.is_empty());
+        assert_eq!(table_diff.added_indexes.len(), 1);
+        assert_eq!(table_diff.removed_indexes, vec!["idx_email".to_string()]);
+    }
+
+    #[test]
+    fn test_ambiguous_index_rename_is_not_detected() {
+        // 同一定義の候補が複数ある場合は推測しない
+        let old_table = table_with_indexes(vec![email_index("idx_email")]);
+        let new_table = table_with_indexes(vec![email_index("email_a"), email_index("email_b")]);
+
+        let table_diff = index_diff(&old_table, &new_table);
+
+        assert!(table_diff.renamed_indexes.is_empty());
+        assert_eq!(table_diff.added_indexes.len(), 2);
+        assert_eq!(table_diff.removed_indexes.len(), 1);
+    }
+
+    fn partial_index(predicate: Option<&str>) -> Index {
+        let index = Index::new("idx".to_string(), vec!["id".to_string()], true);
+        match predicate {
+            Some(predicate) => index.with_where(predicate),
+            None => index,
+        }
+    }
+
+    #[test]
+    fn test_normalized_where_clause() {
+        assert_eq!(normalized_where_clause(&partial_index(None)), None);
+        assert_eq!(normalized_where_clause(&partial_index(Some("  "))), None);
+        assert_eq!(
+            normalized_where_clause(&partial_index(Some("((deleted_at  IS NULL))"))),
+            Some("deleted_at IS NULL".to_string())
+        );
+        // 先頭と末尾の括弧が対応しない場合は外さない
+        assert_eq!(
+            normalized_where_clause(&partial_index(Some("(a > 0) OR (b > 0)"))),
+            Some("(a > 0) OR (b > 0)".to_string())
+        );
+        assert_eq!(
+            normalized_where_clause(&partial_index(Some("(status = ')')"))),
+            Some("status = ')'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_index_type_and_prefix_length_changes_are_modifications() {
+        let body_index =
+            |column: IndexColumn| Index::with_columns("idx_email".to_string(), vec![column], false);
+        let old_table = table_with_indexes(vec![body_index(IndexColumn::new("email"))]);
+
+        let prefixed =
+            table_with_indexes(vec![body_index(IndexColumn::new("email").with_length(191))]);
+        assert_eq!(index_diff(&old_table, &prefixed).modified_indexes.len(), 1);
+
+        let fulltext = table_with_indexes(vec![
+            body_index(IndexColumn::new("email")).with_index_type(IndexType::Fulltext)
+        ]);
+        assert_eq!(index_diff(&old_table, &fulltext).modified_indexes.len(), 1);
+
+        // BTREEの明示は省略時と同じ
+        let btree = table_with_indexes(vec![
+            body_index(IndexColumn::new("email")).with_index_type(IndexType::BTree)
+        ]);
+        assert!(index_diff(&old_table, &btree).modified_indexes.is_empty());
+    }
+}
@@ -10,19 +10,61 @@ mod index_comparator;
 mod table_comparator;
 pub(crate) mod view_comparator;
 
+use crate::core::config::{Config, Dialect, IdentifierCase};
 use crate::core::error::ValidationWarning;
 use crate::core::schema::Schema;
 use crate::core::schema_diff::{RenamedTable, SchemaDiff};
-use std::collections::HashSet;
+use enum_comparator::apply_enum_renames;
+use std::collections::{BTreeSet, HashSet};
+
+/// 差分検出オプション
+///
+/// 方言固有の比較ルールを切り替えるための設定です。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiffOptions {
+    /// 比較対象の方言（Noneの場合は方言固有のルールを適用しない）
+    pub dialect: Option<Dialect>,
+
+    /// SQLiteのAUTOINCREMENTキーワードの有無を厳密に比較するか
+    pub sqlite_strict_autoincrement: bool,
+
+    /// ENUM値の並び順の違いを無視するか（設定 `enum_order_sensitive: false`）
+    pub ignore_enum_order: bool,
+
+    /// 生成SQLの識別子の大文字・小文字の規則（設定 `identifier_case`）
+    pub identifier_case: IdentifierCase,
+}
+
+impl SchemaDiffOptions {
+    /// 設定ファイルからオプションを作成
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            dialect: Some(config.dialect),
+            sqlite_strict_autoincrement: config.sqlite.strict_autoincrement,
+            ignore_enum_order: !config.enum_order_sensitive,
+            identifier_case: config.identifier_case,
+        }
+    }
+}
 
 /// スキーマ差分検出サービス
-#[derive(Debug, Clone)]
-pub struct SchemaDiffDetectorService {}
+#[derive(Debug, Clone, Default)]
+pub struct SchemaDiffDetectorService {
+    options: SchemaDiffOptions,
+}
 
 impl SchemaDiffDetectorService {
     /// 新しいSchemaDiffDetectorServiceを作成
     pub fn new() -> Self {
-        Self {}
+        Self {
+            options: SchemaDiffOptions::default(),
+        }
+    }
+
+    /// 差分検出オプションを設定
+    pub fn with_options(mut self, options: SchemaDiffOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// スキーマ差分を検出
@@ -39,11 +81,23 @@ impl SchemaDiffDetectorService {
         let mut diff = SchemaDiff::new();
 
         diff.enum_recreate_allowed = new_schema.enum_recreate_allowed;
+        diff.sqlite_strict_autoincrement = self.options.sqlite_strict_autoincrement;
+        diff.identifier_case = self.options.identifier_case;
 
         self.detect_enum_diff(old_schema, new_schema, &mut diff);
 
-        let old_table_names: HashSet<&String> = old_schema.tables.keys().collect();
-        let new_table_names: HashSet<&String> = new_schema.tables.keys().collect();
+        // ENUMのリネームで型名が変わっただけのカラムは変更として扱わない
+        let renamed_old_schema;
+        let old_schema = if diff.renamed_enums.is_empty() {
+            old_schema
+        } else {
+            renamed_old_schema = apply_enum_renames(old_schema, &diff.renamed_enums);
+            &renamed_old_schema
+        };
+
+        // BTreeSet で走査し、差分の並びをテーブル名順に固定する
+        let old_table_names: BTreeSet<&String> = old_schema.tables.keys().collect();
+        let new_table_names: BTreeSet<&String> = new_schema.tables.keys().collect();
 
         // リネームされたテーブルの旧名を追跡
         let mut renamed_old_names: HashSet<String> = HashSet::new();
@@ -66,9 +120,10 @@ impl SchemaDiffDetectorService {
             }
         }
 
-        // 削除されたテーブル（リネームされたものを除外）
+        // 削除されたテーブル（リネームされたもの・外部テーブルとして宣言されたものを除外）
         for table_name in old_table_names.difference(&new_table_names) {
-            if !renamed_old_names.contains(*table_name) {
+            if !renamed_old_names.contains(*table_name) && !new_schema.is_external_table(table_name)
+            {
                 diff.removed_tables.push((*table_name).clone());
             }
         }
@@ -111,11 +166,23 @@ impl SchemaDiffDetectorService {
         let mut warnings = Vec::new();
 
         diff.enum_recreate_allowed = new_schema.enum_recreate_allowed;
+        diff.sqlite_strict_autoincrement = self.options.sqlite_strict_autoincrement;
+        diff.identifier_case = self.options.identifier_case;
 
         self.detect_enum_diff(old_schema, new_schema, &mut diff);
 
-        let old_table_names: HashSet<&String> = old_schema.tables.keys().collect();
-        let new_table_names: HashSet<&String> = new_schema.tables.keys().collect();
+        // ENUMのリネームで型名が変わっただけのカラムは変更として扱わない
+        let renamed_old_schema;
+        let old_schema = if diff.renamed_enums.is_empty() {
+            old_schema
+        } else {
+            renamed_old_schema = apply_enum_renames(old_schema, &diff.renamed_enums);
+            &renamed_old_schema
+        };
+
+        // BTreeSet で走査し、差分の並びをテーブル名順に固定する
+        let old_table_names: BTreeSet<&String> = old_schema.tables.keys().collect();
+        let new_table_names: BTreeSet<&String> = new_schema.tables.keys().collect();
 
         // リネームされたテーブルの旧名を追跡
         let mut renamed_old_names: HashSet<String> = HashSet::new();
@@ -138,9 +205,10 @@ impl SchemaDiffDetectorService {
             }
         }
 
-        // 削除されたテーブル（リネームされたものを除外）
+        // 削除されたテーブル（リネームされたもの・外部テーブルとして宣言されたものを除外）
         for table_name in old_table_names.difference(&new_table_names) {
-            if !renamed_old_names.contains(*table_name) {
+            if !renamed_old_names.contains(*table_name) && !new_schema.is_external_table(table_name)
+            {
                 diff.removed_tables.push((*table_name).clone());
             }
         }
@@ -167,26 +235,32 @@ impl SchemaDiffDetectorService {
     }
 }
 
-impl Default for SchemaDiffDetectorService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl crate::services::traits::SchemaDiffDetector for SchemaDiffDetectorService {
     fn detect_diff_with_warnings(
         &self,
         old_schema: &Schema,
         new_schema: &Schema,
+        options: &SchemaDiffOptions,
     ) -> (SchemaDiff, Vec<ValidationWarning>) {
-        self.detect_diff_with_warnings(old_schema, new_schema)
+        self.clone()
+            .with_options(options.clone())
+            .detect_diff_with_warnings(old_schema, new_schema)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::schema::{Column, ColumnType, Table};
+    use crate::core::schema::SchemaBuilder;
+
+    /// `id` カラムだけを持つテーブルのスキーマ
+    fn schema_with_table(name: &str) -> Schema {
+        SchemaBuilder::new("1.0")
+            .table(name, |t| {
+                t.integer("id");
+            })
+            .build_unvalidated()
+    }
 
     #[test]
     fn test_new_service() {
@@ -209,15 +283,7 @@ mod tests {
     fn test_detect_table_added() {
         let service = SchemaDiffDetectorService::new();
         let schema1 = Schema::new("1.0".to_string());
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema2.add_table(table);
+        let schema2 = schema_with_table("users");
 
         let diff = service.detect_diff(&schema1, &schema2);
 
@@ -228,16 +294,7 @@ mod tests {
     #[test]
     fn test_detect_table_removed() {
         let service = SchemaDiffDetectorService::new();
-
-        let mut schema1 = Schema::new("1.0".to_string());
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema1.add_table(table);
-
+        let schema1 = schema_with_table("users");
         let schema2 = Schema::new("1.0".to_string());
 
         let diff = service.detect_diff(&schema1, &schema2);
@@ -246,60 +303,92 @@ mod tests {
         assert_eq!(diff.removed_tables[0], "users");
     }
 
+    #[test]
+    fn test_detect_same_table_name_in_different_schemas() {
+        let service = SchemaDiffDetectorService::new();
+
+        let schema1 = SchemaBuilder::new("1.0")
+            .table_in_schema("app", "users", |_| {})
+            .build_unvalidated();
+        let schema2 = SchemaBuilder::new("1.0")
+            .table_in_schema("app", "users", |_| {})
+            .table_in_schema("audit", "users", |_| {})
+            .build_unvalidated();
+
+        let diff = service.detect_diff(&schema1, &schema2);
+
+        assert_eq!(diff.added_tables.len(), 1);
+        assert_eq!(diff.added_tables[0].name, "audit.users");
+        assert!(diff.modified_tables.is_empty());
+        assert!(diff.removed_tables.is_empty());
+    }
+
+    #[test]
+    fn test_detect_table_declared_external_is_not_removed() {
+        let service = SchemaDiffDetectorService::new();
+
+        let schema1 = schema_with_table("external_users");
+        let schema2 = SchemaBuilder::new("1.0")
+            .external_table("external_users")
+            .build_unvalidated();
+
+        let diff = service.detect_diff(&schema1, &schema2);
+        assert!(diff.removed_tables.is_empty());
+
+        let (diff, _) = service.detect_diff_with_warnings(&schema1, &schema2);
+        assert!(diff.removed_tables.is_empty());
+    }
+
     #[test]
     fn test_detect_table_modified() {
         let service = SchemaDiffDetectorService::new();
 
-        let mut schema1 = Schema::new("1.0".to_string());
-        let mut table1 = Table::new("users".to_string());
-        table1.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema1.add_table(table1);
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut table2 = Table::new("users".to_string());
-        table2.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table2.add_column(Column::new(
-            "name".to_string(),
-            ColumnType::VARCHAR { length: 255 },
-            true,
-        ));
-        schema2.add_table(table2);
+        let schema1 = schema_with_table("users");
+        let schema2 = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id");
+                t.varchar("name", 255).nullable();
+            })
+            .build_unvalidated();
 
         let diff = service.detect_diff(&schema1, &schema2);
         assert_eq!(diff.modified_tables.len(), 1);
         assert_eq!(diff.modified_tables[0].table_name, "users");
     }
 
+    #[test]
+    fn test_metadata_only_changes_are_ignored() {
+        let service = SchemaDiffDetectorService::new();
+
+        let users = |owner: &str| {
+            SchemaBuilder::new("1.0")
+                .table("users", |t| {
+                    t.integer("id").metadata("pii", owner);
+                    t.index("idx_users_id", &["id"]).metadata("owner", owner);
+                    t.metadata("owner", owner);
+                })
+                .build_unvalidated()
+        };
+        let schema1 = users("identity");
+        let schema2 = users("platform");
+
+        assert!(service.detect_diff(&schema1, &schema2).is_empty());
+        let (diff, warnings) = service.detect_diff_with_warnings(&schema1, &schema2);
+        assert!(diff.is_empty());
+        assert!(warnings.is_empty());
+    }
+
     #[test]
     fn test_detect_table_renamed() {
         let service = SchemaDiffDetectorService::new();
 
-        let mut schema1 = Schema::new("1.0".to_string());
-        let mut old_table = Table::new("users".to_string());
-        old_table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema1.add_table(old_table);
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut new_table = Table::new("accounts".to_string());
-        new_table.renamed_from = Some("users".to_string());
-        new_table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema2.add_table(new_table);
+        let schema1 = schema_with_table("users");
+        let schema2 = SchemaBuilder::new("1.0")
+            .table("accounts", |t| {
+                t.renamed_from("users");
+                t.integer("id");
+            })
+            .build_unvalidated();
 
         let diff = service.detect_diff(&schema1, &schema2);
         assert_eq!(diff.renamed_tables.len(), 1);
@@ -313,15 +402,7 @@ mod tests {
     fn test_detect_diff_with_warnings() {
         let service = SchemaDiffDetectorService::new();
         let schema1 = Schema::new("1.0".to_string());
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema2.add_table(table);
+        let schema2 = schema_with_table("users");
 
         let (diff, _warnings) = service.detect_diff_with_warnings(&schema1, &schema2);
         assert_eq!(diff.added_tables.len(), 1);
@@ -331,28 +412,13 @@ mod tests {
     fn test_detect_diff_with_warnings_modified_table() {
         let service = SchemaDiffDetectorService::new();
 
-        let mut schema1 = Schema::new("1.0".to_string());
-        let mut table1 = Table::new("users".to_string());
-        table1.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema1.add_table(table1);
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut table2 = Table::new("users".to_string());
-        table2.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table2.add_column(Column::new(
-            "email".to_string(),
-            ColumnType::VARCHAR { length: 255 },
-            true,
-        ));
-        schema2.add_table(table2);
+        let schema1 = schema_with_table("users");
+        let schema2 = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id");
+                t.varchar("email", 255).nullable();
+            })
+            .build_unvalidated();
 
         let (diff, _warnings) = service.detect_diff_with_warnings(&schema1, &schema2);
         assert_eq!(diff.modified_tables.len(), 1);
@@ -362,24 +428,13 @@ mod tests {
     fn test_detect_diff_with_warnings_renamed_table() {
         let service = SchemaDiffDetectorService::new();
 
-        let mut schema1 = Schema::new("1.0".to_string());
-        let mut old_table = Table::new("users".to_string());
-        old_table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema1.add_table(old_table);
-
-        let mut schema2 = Schema::new("1.0".to_string());
-        let mut new_table = Table::new("accounts".to_string());
-        new_table.renamed_from = Some("users".to_string());
-        new_table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema2.add_table(new_table);
+        let schema1 = schema_with_table("users");
+        let schema2 = SchemaBuilder::new("1.0")
+            .table("accounts", |t| {
+                t.renamed_from("users");
+                t.integer("id");
+            })
+            .build_unvalidated();
 
         let (diff, _warnings) = service.detect_diff_with_warnings(&schema1, &schema2);
         assert_eq!(diff.renamed_tables.len(), 1);
@@ -401,7 +456,12 @@ mod tests {
         let service = SchemaDiffDetectorService::new();
         let s1 = Schema::new("1.0".to_string());
         let s2 = Schema::new("1.0".to_string());
-        let (diff, warnings) = SchemaDiffDetector::detect_diff_with_warnings(&service, &s1, &s2);
+        let (diff, warnings) = SchemaDiffDetector::detect_diff_with_warnings(
+            &service,
+            &s1,
+            &s2,
+            &SchemaDiffOptions::default(),
+        );
         assert!(diff.is_empty());
         assert!(warnings.is_empty());
     }
@@ -1,7 +1,7 @@
 // テーブルレベルの差分検出
 
 use crate::core::error::ValidationWarning;
-use crate::core::schema_diff::TableDiff;
+use crate::core::schema_diff::{CommentChange, TableDiff};
 
 use super::SchemaDiffDetectorService;
 
@@ -23,6 +23,10 @@ impl SchemaDiffDetectorService {
         // 制約の差分を検出
         self.detect_constraint_diff(old_table, new_table, &mut table_diff);
 
+        // コメントの差分を検出（空文字列とコメントなしは同じとみなす）
+        table_diff.comment_change =
+            CommentChange::detect(old_table.comment_text(), new_table.comment_text());
+
         table_diff
     }
 
@@ -44,6 +48,10 @@ impl SchemaDiffDetectorService {
         // 制約の差分を検出
         self.detect_constraint_diff(old_table, new_table, &mut table_diff);
 
+        // コメントの差分を検出（空文字列とコメントなしは同じとみなす）
+        table_diff.comment_change =
+            CommentChange::detect(old_table.comment_text(), new_table.comment_text());
+
         (table_diff, warnings)
     }
 }
@@ -6,12 +6,12 @@
 
 use crate::core::schema::Schema;
 use crate::core::schema_diff::{RenamedView, SchemaDiff, ViewDiff};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 /// ビュー定義の正規化
 ///
-/// 空白・改行・連続スペースの差異のみを除去する最小ルール。
-/// SQL 意味の同一性判定ではなく、表面的な空白差異を無視する。
+/// 空白・改行・連続スペースの差異と末尾のセミコロンのみを除去する最小ルール。
+/// SQL 意味の同一性判定ではなく、表面的な差異を無視する。
 /// シングルクォート内の文字列リテラルは正規化しない。
 /// SQL エスケープクォート（''）はリテラル文字として扱い、クォート状態を変更しない。
 pub fn normalize_definition(definition: &str) -> String {
@@ -62,13 +62,20 @@ pub fn normalize_definition(definition: &str) -> String {
         i += 1;
     }
 
+    // 末尾のセミコロン（`SELECT ... ;` や `;;` を含む）を除去
+    if !in_quote {
+        while result.ends_with(';') || result.ends_with(' ') {
+            result.pop();
+        }
+    }
+
     result
 }
 
 /// ビュー差分の検出
 pub fn detect_view_diff(old_schema: &Schema, new_schema: &Schema, diff: &mut SchemaDiff) {
-    let old_view_names: HashSet<&String> = old_schema.views.keys().collect();
-    let new_view_names: HashSet<&String> = new_schema.views.keys().collect();
+    let old_view_names: BTreeSet<&String> = old_schema.views.keys().collect();
+    let new_view_names: BTreeSet<&String> = new_schema.views.keys().collect();
 
     // リネームされたビューの旧名を追跡
     let mut renamed_old_names: HashSet<String> = HashSet::new();
@@ -94,6 +101,7 @@ pub fn detect_view_diff(old_schema: &Schema, new_schema: &Schema, diff: &mut Sch
                             new_definition: view.definition.clone(),
                             old_view: old_view.clone(),
                             new_view: view.clone(),
+                            auto_rewritten: false,
                         });
                     }
 
@@ -127,6 +135,7 @@ pub fn detect_view_diff(old_schema: &Schema, new_schema: &Schema, diff: &mut Sch
                     new_definition: new_view.definition.clone(),
                     old_view: old_view.clone(),
                     new_view: new_view.clone(),
+                    auto_rewritten: false,
                 });
             }
         }
@@ -228,6 +237,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_definition_strips_trailing_semicolons() {
+        assert_eq!(
+            normalize_definition("SELECT * FROM users;"),
+            "SELECT * FROM users"
+        );
+        assert_eq!(
+            normalize_definition("SELECT * FROM users ;\n;  \n"),
+            "SELECT * FROM users"
+        );
+        // リテラル内のセミコロンは保持
+        assert_eq!(
+            normalize_definition("SELECT * FROM t WHERE v = 'a;'"),
+            "SELECT * FROM t WHERE v = 'a;'"
+        );
+    }
+
     // ===== Task 3.1: 追加/更新/削除/rename の差分抽出 =====
 
     #[test]
@@ -303,7 +329,7 @@ mod tests {
         let mut new = Schema::new("1.0".to_string());
         new.add_view(View::new(
             "active_users".to_string(),
-            "SELECT * FROM users WHERE active = true".to_string(),
+            "SELECT * FROM users\nWHERE active = true;\n".to_string(),
         ));
 
         let mut diff = SchemaDiff::new();
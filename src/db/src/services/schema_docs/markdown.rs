@@ -0,0 +1,504 @@
+// Markdownページの描画
+//
+// テーブル・ビュー・ENUM型のページと一覧ページを描画します。
+// セルや見出しに埋め込む名前・式はMarkdownとして解釈されないようエスケープし、
+// 空のセクションは出力しません。
+
+use super::{
+    column_keys, page_file_stem, render_er_diagram, ENUMS_DIR, GENERATED_HEADER, INDEX_PAGE,
+    TABLES_DIR, VIEWS_DIR,
+};
+use crate::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Schema, Table, View};
+use std::collections::BTreeMap;
+
+/// 一覧ページを描画する
+pub(super) fn render_index_page(schema: &Schema) -> String {
+    let mut page = Page::new("Schema");
+
+    if schema.tables.is_empty() && schema.views.is_empty() && schema.enums.is_empty() {
+        page.paragraph("No tables, views or enums are defined.");
+        return page.finish();
+    }
+
+    // PostgreSQLのスキーマごとにまとめる（スキーマ指定なしのテーブルが先頭）
+    let mut groups: BTreeMap<Option<&str>, Vec<&Table>> = BTreeMap::new();
+    for table in schema.tables.values() {
+        groups
+            .entry(table.schema.as_deref())
+            .or_default()
+            .push(table);
+    }
+    for (group, tables) in groups {
+        let heading = match group {
+            Some(schema_name) => format!("Tables in {}", code(schema_name)),
+            None => "Tables".to_string(),
+        };
+        page.heading(&heading);
+        page.table(
+            &["Table", "Columns", "Comment"],
+            tables.iter().map(|table| {
+                vec![
+                    link(None, TABLES_DIR, &table.name),
+                    table.columns.len().to_string(),
+                    table.comment_text().map(escape_text).unwrap_or_default(),
+                ]
+            }),
+        );
+    }
+
+    if !schema.views.is_empty() {
+        page.heading("Views");
+        page.table(
+            &["View", "Depends On"],
+            schema.views.values().map(|view| {
+                vec![
+                    link(None, VIEWS_DIR, &view.name),
+                    dependency_links(schema, None, view),
+                ]
+            }),
+        );
+    }
+
+    if !schema.enums.is_empty() {
+        page.heading("Enums");
+        page.table(
+            &["Enum", "Values"],
+            schema.enums.values().map(|enum_def| {
+                vec![
+                    link(None, ENUMS_DIR, &enum_def.name),
+                    join_code(&enum_def.values),
+                ]
+            }),
+        );
+    }
+
+    if !schema.external_tables.is_empty() {
+        page.heading("External Tables");
+        page.paragraph("Tables managed outside the schema directory.");
+        page.list(schema.external_tables.iter().map(|name| code(name)));
+    }
+
+    if !schema.tables.is_empty() {
+        page.heading("Entity Relationship Diagram");
+        page.code_block("mermaid", &render_er_diagram(schema));
+    }
+
+    page.finish()
+}
+
+/// テーブルのページを描画する
+pub(super) fn render_table_page(schema: &Schema, table: &Table) -> String {
+    let mut page = Page::new(&table.name);
+    page.back_link();
+    if let Some(comment) = table.comment_text() {
+        page.paragraph(&escape_text(comment));
+    }
+
+    page.heading("Columns");
+    page.table(
+        &[
+            "Name", "Type", "Nullable", "Key", "Default", "Extra", "Comment",
+        ],
+        table.columns.iter().map(|column| {
+            vec![
+                code(&column.name),
+                column_type_cell(schema, &column.column_type),
+                if column.nullable { "YES" } else { "NO" }.to_string(),
+                column_keys(table, &column.name).join(", "),
+                column
+                    .default_value
+                    .as_deref()
+                    .map(code)
+                    .unwrap_or_default(),
+                column_extra(column),
+                column.comment_text().map(escape_text).unwrap_or_default(),
+            ]
+        }),
+    );
+
+    if !table.indexes.is_empty() {
+        page.heading("Indexes");
+        page.table(
+            &["Name", "Columns", "Unique", "Type", "Condition"],
+            table.indexes.iter().map(|index| {
+                let columns: Vec<String> = index
+                    .columns
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect();
+                vec![
+                    code(&index.name),
+                    join_code(&columns),
+                    if index.unique { "YES" } else { "NO" }.to_string(),
+                    index
+                        .index_type
+                        .map(|index_type| index_type.to_string())
+                        .unwrap_or_default(),
+                    index.where_clause.as_deref().map(code).unwrap_or_default(),
+                ]
+            }),
+        );
+    }
+
+    if !table.constraints.is_empty() {
+        page.heading("Constraints");
+        page.table(
+            &["Type", "Columns", "Details"],
+            table.constraints.iter().map(|constraint| {
+                vec![
+                    constraint.kind().replace('_', " "),
+                    join_code(constraint.columns()),
+                    constraint_details(schema, constraint),
+                ]
+            }),
+        );
+    }
+
+    let referenced_by: Vec<Vec<String>> = schema
+        .tables
+        .values()
+        .flat_map(|other| {
+            other
+                .constraints
+                .iter()
+                .filter_map(move |constraint| match constraint {
+                    Constraint::FOREIGN_KEY {
+                        columns,
+                        referenced_table,
+                        referenced_columns,
+                        ..
+                    } if referenced_table == &table.name => Some(vec![
+                        link(Some(TABLES_DIR), TABLES_DIR, &other.name),
+                        join_code(columns),
+                        join_code(referenced_columns),
+                    ]),
+                    _ => None,
+                })
+        })
+        .collect();
+    if !referenced_by.is_empty() {
+        page.heading("Referenced By");
+        page.table(&["Table", "Columns", "Referenced Columns"], referenced_by);
+    }
+
+    let views: Vec<String> = schema
+        .views
+        .values()
+        .filter(|view| view.depends_on.contains(&table.name))
+        .map(|view| link(Some(TABLES_DIR), VIEWS_DIR, &view.name))
+        .collect();
+    if !views.is_empty() {
+        page.heading("Used By Views");
+        page.list(views);
+    }
+
+    page.finish()
+}
+
+/// ビューのページを描画する
+pub(super) fn render_view_page(schema: &Schema, view: &View) -> String {
+    let mut page = Page::new(&view.name);
+    page.back_link();
+
+    if !view.depends_on.is_empty() {
+        page.heading("Depends On");
+        page.list(
+            view.depends_on
+                .iter()
+                .map(|name| object_link(schema, Some(VIEWS_DIR), name)),
+        );
+    }
+
+    page.heading("Definition");
+    page.code_block("sql", view.definition.trim());
+    page.finish()
+}
+
+/// ENUM型のページを描画する
+pub(super) fn render_enum_page(schema: &Schema, enum_def: &EnumDefinition) -> String {
+    let mut page = Page::new(&enum_def.name);
+    page.back_link();
+
+    page.heading("Values");
+    page.list(enum_def.values.iter().map(|value| code(value)));
+
+    let used_by: Vec<Vec<String>> = schema
+        .tables
+        .values()
+        .flat_map(|table| {
+            table
+                .columns
+                .iter()
+                .filter_map(move |column| match &column.column_type {
+                    ColumnType::Enum { name } if name == &enum_def.name => Some(vec![
+                        link(Some(ENUMS_DIR), TABLES_DIR, &table.name),
+                        code(&column.name),
+                    ]),
+                    _ => None,
+                })
+        })
+        .collect();
+    if !used_by.is_empty() {
+        page.heading("Used By");
+        page.table(&["Table", "Column"], used_by);
+    }
+
+    page.finish()
+}
+
+/// 型のセル（定義されたENUM型はそのページへのリンク）
+fn column_type_cell(schema: &Schema, column_type: &ColumnType) -> String {
+    let type_name = code(&column_type.to_string());
+    match column_type {
+        ColumnType::Enum { name } if schema.enums.contains_key(name) => format!(
+            "[{}]({})",
+            type_name,
+            relative_path(Some(TABLES_DIR), ENUMS_DIR, name)
+        ),
+        _ => type_name,
+    }
+}
+
+/// 自動採番・生成カラム・ON UPDATE など、デフォルト値以外のカラムの属性
+fn column_extra(column: &Column) -> String {
+    let mut extras = Vec::new();
+    if column.is_auto_increment() {
+        extras.push("auto increment".to_string());
+    }
+    if let Some(generated) = &column.generated {
+        extras.push(code(&generated.to_sql_clause()));
+    }
+    if column.on_update_current_timestamp {
+        extras.push(code("ON UPDATE CURRENT_TIMESTAMP"));
+    }
+    extras.join("<br>")
+}
+
+/// 制約の詳細（外部キーの参照先・チェック式）
+fn constraint_details(schema: &Schema, constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::FOREIGN_KEY {
+            referenced_table,
+            referenced_columns,
+            on_delete,
+            on_update,
+            ..
+        } => {
+            let target = if schema.tables.contains_key(referenced_table) {
+                link(Some(TABLES_DIR), TABLES_DIR, referenced_table)
+            } else {
+                code(referenced_table)
+            };
+            let mut details = format!("References {} ({})", target, join_code(referenced_columns));
+            if let Some(action) = on_delete {
+                details.push_str(&format!(", ON DELETE {}", action.as_sql()));
+            }
+            if let Some(action) = on_update {
+                details.push_str(&format!(", ON UPDATE {}", action.as_sql()));
+            }
+            details
+        }
+        Constraint::CHECK {
+            check_expression, ..
+        } => code(check_expression),
+        _ => String::new(),
+    }
+}
+
+/// ビューの依存先へのリンク（カンマ区切り）
+fn dependency_links(schema: &Schema, from_dir: Option<&str>, view: &View) -> String {
+    view.depends_on
+        .iter()
+        .map(|name| object_link(schema, from_dir, name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// テーブルまたはビューへのリンク（スキーマ定義にない場合は名前のみ）
+fn object_link(schema: &Schema, from_dir: Option<&str>, name: &str) -> String {
+    if schema.tables.contains_key(name) {
+        link(from_dir, TABLES_DIR, name)
+    } else if schema.views.contains_key(name) {
+        link(from_dir, VIEWS_DIR, name)
+    } else {
+        code(name)
+    }
+}
+
+/// オブジェクトのページへのリンク
+///
+/// `from_dir` はリンク元のページのディレクトリ（一覧ページは `None`）です。
+fn link(from_dir: Option<&str>, dir: &str, name: &str) -> String {
+    format!(
+        "[{}]({})",
+        escape_text(name),
+        relative_path(from_dir, dir, name)
+    )
+}
+
+/// リンク元のページからオブジェクトのページへの相対パス
+fn relative_path(from_dir: Option<&str>, dir: &str, name: &str) -> String {
+    let file_name = format!("{}.md", page_file_stem(name));
+    match from_dir {
+        Some(from) if from == dir => file_name,
+        Some(_) => format!("../{}/{}", dir, file_name),
+        None => format!("{}/{}", dir, file_name),
+    }
+}
+
+/// インラインコード（値に含まれるバッククォートより長い区切りを使う）
+///
+/// 表のセルに収めるため、改行は空白に置き換えます。
+fn code(text: &str) -> String {
+    let text = text.replace("\r\n", " ").replace(['\r', '\n'], " ");
+    let fence = "`".repeat(longest_backtick_run(&text) + 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+/// 値をインラインコードにしてカンマ区切りで連結する
+fn join_code(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| code(value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 名前やコメントがMarkdownの記法として解釈されないようエスケープする
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 表のセルとして出力できるよう、`|` と改行をエスケープする
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\r', '\n'], "<br>")
+}
+
+/// 表の1行（空のセルは空白1つにする）
+fn table_row(cells: impl Iterator<Item = String>) -> String {
+    let mut row = String::from("|");
+    for value in cells {
+        if value.is_empty() {
+            row.push_str(" |");
+        } else {
+            row.push_str(&format!(" {} |", value));
+        }
+    }
+    row
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// ページの組み立て
+///
+/// ブロックは空行で区切り、ファイルは改行1つで終わります。
+struct Page {
+    blocks: Vec<String>,
+}
+
+impl Page {
+    fn new(title: &str) -> Self {
+        Self {
+            blocks: vec![
+                GENERATED_HEADER.to_string(),
+                format!("# {}", escape_text(title)),
+            ],
+        }
+    }
+
+    fn back_link(&mut self) {
+        self.blocks
+            .push(format!("[Back to index](../{})", INDEX_PAGE));
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.blocks.push(format!("## {}", text));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.blocks.push(text.to_string());
+    }
+
+    fn list(&mut self, items: impl IntoIterator<Item = String>) {
+        let lines: Vec<String> = items
+            .into_iter()
+            .map(|item| format!("- {}", item))
+            .collect();
+        self.blocks.push(lines.join("\n"));
+    }
+
+    fn table(&mut self, headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) {
+        let mut lines = vec![
+            table_row(headers.iter().map(|header| header.to_string())),
+            format!("|{}", " --- |".repeat(headers.len())),
+        ];
+        for row in rows {
+            lines.push(table_row(row.iter().map(|value| cell(value))));
+        }
+        self.blocks.push(lines.join("\n"));
+    }
+
+    fn code_block(&mut self, language: &str, content: &str) {
+        let fence = "`".repeat((longest_backtick_run(content) + 1).max(3));
+        self.blocks
+            .push(format!("{}{}\n{}\n{}", fence, language, content, fence));
+    }
+
+    fn finish(self) -> String {
+        format!("{}\n", self.blocks.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_uses_longer_fence_than_content() {
+        assert_eq!(code("id"), "`id`");
+        assert_eq!(code("a`b"), "``a`b``");
+        assert_eq!(code("`quoted`"), "`` `quoted` ``");
+        assert_eq!(code("a\nb"), "`a b`");
+    }
+
+    #[test]
+    fn test_cell_escapes_pipes_and_newlines() {
+        assert_eq!(cell("a | b"), "a \\| b");
+        assert_eq!(cell("line1\nline2\r\nline3"), "line1<br>line2<br>line3");
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("user_id"), "user_id");
+        assert_eq!(escape_text("[draft] *x*"), "\\[draft\\] \\*x\\*");
+        assert_eq!(escape_text("<b>"), "\\<b\\>");
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(relative_path(None, TABLES_DIR, "users"), "tables/users.md");
+        assert_eq!(
+            relative_path(Some(TABLES_DIR), TABLES_DIR, "users"),
+            "users.md"
+        );
+        assert_eq!(
+            relative_path(Some(VIEWS_DIR), TABLES_DIR, "users"),
+            "../tables/users.md"
+        );
+    }
+}
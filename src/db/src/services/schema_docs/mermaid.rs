@@ -0,0 +1,272 @@
+// mermaid の erDiagram の生成
+//
+// テーブルをエンティティ、外部キーをリレーションシップとして出力します。
+// mermaid の識別子に使えない文字を含む名前は、識別子に置き換えたうえで
+// `id["元の名前"]` の別名として元の名前を表示します。
+
+use super::{column_keys, is_unique_column_set};
+use crate::core::schema::{Constraint, Schema};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// スキーマ定義から erDiagram を生成する（コードブロックの中身）
+///
+/// 参照先がスキーマ定義にない外部キー（`external_tables` など）は図に含めません。
+pub fn render_er_diagram(schema: &Schema) -> String {
+    let entity_ids = entity_ids(schema);
+    let mut lines = vec!["erDiagram".to_string()];
+
+    for table in schema.tables.values() {
+        let id = &entity_ids[table.name.as_str()];
+        let entity = if id == &table.name {
+            id.clone()
+        } else {
+            format!("{}[{}]", id, quote(&table.name))
+        };
+        if table.columns.is_empty() {
+            lines.push(format!("    {}", entity));
+            continue;
+        }
+
+        lines.push(format!("    {} {{", entity));
+        for column in &table.columns {
+            let name = attribute_word(&column.name);
+            let mut attribute = format!(
+                "        {} {}",
+                attribute_word(&column.column_type.to_string()),
+                name
+            );
+            let keys = column_keys(table, &column.name);
+            if !keys.is_empty() {
+                attribute.push_str(&format!(" {}", keys.join(", ")));
+            }
+            // 識別子に置き換えたカラム名は、元の名前をコメントとして残す
+            if name != column.name {
+                attribute.push_str(&format!(" {}", quote(&column.name)));
+            }
+            lines.push(attribute);
+        }
+        lines.push("    }".to_string());
+    }
+
+    for table in schema.tables.values() {
+        for constraint in &table.constraints {
+            let Constraint::FOREIGN_KEY {
+                columns,
+                referenced_table,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            let Some(parent_id) = entity_ids.get(referenced_table.as_str()) else {
+                continue;
+            };
+
+            // 外部キーのカラムがNULLを許容する場合、参照先は「0または1」
+            let nullable = columns
+                .iter()
+                .any(|name| table.get_column(name).is_some_and(|column| column.nullable));
+            let parent_side = if nullable { "|o" } else { "||" };
+            // 外部キーのカラムが一意であれば、参照元は「0または1」
+            let child_side = if is_unique_column_set(table, columns) {
+                "o|"
+            } else {
+                "o{"
+            };
+            lines.push(format!(
+                "    {} {}--{} {} : {}",
+                parent_id,
+                parent_side,
+                child_side,
+                entity_ids[table.name.as_str()],
+                quote(&columns.join(", "))
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// テーブル名ごとのエンティティの識別子
+///
+/// 識別子として使える名前はそのまま使い、それ以外は使えない文字を `_` に置き換えます。
+/// 置き換えた識別子が他の名前と重なる場合は `_2`, `_3`... を付けて区別します。
+/// テーブル名の順に割り当てるため、同じスキーマからは常に同じ識別子になります。
+fn entity_ids(schema: &Schema) -> BTreeMap<&str, String> {
+    let mut ids = BTreeMap::new();
+    let mut used = BTreeSet::new();
+    for name in schema.tables.keys() {
+        if is_plain_identifier(name) {
+            ids.insert(name.as_str(), name.clone());
+            used.insert(name.clone());
+        }
+    }
+    for name in schema.tables.keys() {
+        if ids.contains_key(name.as_str()) {
+            continue;
+        }
+        let base = sanitize_identifier(name);
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while used.contains(&id) {
+            id = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        used.insert(id.clone());
+        ids.insert(name.as_str(), id);
+    }
+    ids
+}
+
+/// 英字または `_` で始まり、英数字と `_` のみからなる名前か
+fn is_plain_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 英数字と `_` 以外を `_` に置き換えた識別子（先頭が数字の場合は `_` を付ける）
+fn sanitize_identifier(name: &str) -> String {
+    let id: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        id
+    } else {
+        format!("_{}", id)
+    }
+}
+
+/// 属性の型・名前として使える語にする
+///
+/// 英数字と `_` `-` `(` `)` `[` `]` 以外の文字の連続を `_` 1つに置き換え、末尾の `_` は除きます
+/// （例: `DECIMAL(10, 2)` → `DECIMAL(10_2)`）。
+fn attribute_word(text: &str) -> String {
+    let mut word = String::with_capacity(text.len());
+    let mut replaced = false;
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '(' | ')' | '[' | ']') {
+            word.push(c);
+            replaced = false;
+        } else if !replaced {
+            word.push('_');
+            replaced = true;
+        }
+    }
+    let word = word.trim_end_matches('_');
+    if word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        word.to_string()
+    } else {
+        format!("_{}", word)
+    }
+}
+
+/// 二重引用符で囲んだ文字列（`"` は mermaid のエンティティコード `#quot;` にする）
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "#quot;"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+
+    fn table(name: &str, columns: &[(&str, bool)]) -> Table {
+        let mut table = Table::new(name.to_string());
+        for (column_name, nullable) in columns {
+            table.add_column(Column::new(
+                column_name.to_string(),
+                ColumnType::INTEGER { precision: None },
+                *nullable,
+            ));
+        }
+        table
+    }
+
+    fn foreign_key(columns: &[&str], referenced_table: &str) -> Constraint {
+        Constraint::FOREIGN_KEY {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    #[test]
+    fn test_render_er_diagram_entities_and_relationships() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut users = table("users", &[("id", false)]);
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        schema.add_table(users);
+        let mut posts = table("posts", &[("id", false), ("user_id", false)]);
+        posts.add_constraint(foreign_key(&["user_id"], "users"));
+        schema.add_table(posts);
+        let mut profiles = table("profiles", &[("user_id", true)]);
+        profiles.add_constraint(foreign_key(&["user_id"], "users"));
+        profiles.add_constraint(Constraint::UNIQUE {
+            columns: vec!["user_id".to_string()],
+        });
+        profiles.add_constraint(foreign_key(&["user_id"], "legacy_users"));
+        schema.add_table(profiles);
+
+        let diagram = render_er_diagram(&schema);
+        assert_eq!(
+            diagram,
+            [
+                "erDiagram",
+                "    posts {",
+                "        INTEGER id",
+                "        INTEGER user_id FK",
+                "    }",
+                "    profiles {",
+                "        INTEGER user_id FK, UK",
+                "    }",
+                "    users {",
+                "        INTEGER id PK",
+                "    }",
+                "    users ||--o{ posts : \"user_id\"",
+                "    users |o--o| profiles : \"user_id\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_render_er_diagram_quotes_odd_identifiers() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(table("order items", &[("unit price", false)]));
+        schema.add_table(table("order_items", &[("id", false)]));
+        schema.add_table(table("order-items", &[("id", false)]));
+        schema.add_table(table("2024 \"stats\"", &[("id", false)]));
+
+        let diagram = render_er_diagram(&schema);
+        // 識別子として使える名前はそのまま使い、使えない名前は重ならない識別子と別名にする
+        assert!(diagram.contains("    _2024__stats_[\"2024 #quot;stats#quot;\"] {"));
+        assert!(diagram.contains("    order_items_2[\"order items\"] {"));
+        assert!(diagram.contains("    order_items_3[\"order-items\"] {"));
+        assert!(diagram.contains("    order_items {"));
+        assert!(diagram.contains("        INTEGER unit_price \"unit price\""));
+    }
+
+    #[test]
+    fn test_attribute_word() {
+        let cases = [
+            ("INTEGER", "INTEGER"),
+            ("VARCHAR(255)", "VARCHAR(255)"),
+            ("DECIMAL(10, 2)", "DECIMAL(10_2)"),
+            ("TIMESTAMP WITH TIME ZONE", "TIMESTAMP_WITH_TIME_ZONE"),
+            ("user id", "user_id"),
+            ("1st", "_1st"),
+            ("名前", "_"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(attribute_word(text), expected, "text: {}", text);
+        }
+    }
+}
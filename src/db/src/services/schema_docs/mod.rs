@@ -0,0 +1,231 @@
+// スキーマドキュメントの生成
+//
+// パース済みのスキーマ定義から、データベースに接続せずにMarkdownのドキュメントを生成します。
+// - index.md: テーブル（PostgreSQLのスキーマごと）・ビュー・ENUMの一覧と mermaid の erDiagram
+// - tables/<テーブル名>.md: カラム・インデックス・制約（外部キーは参照先テーブルのページへのリンク）
+// - views/<ビュー名>.md, enums/<ENUM名>.md
+// 同じスキーマからは常に同じ内容を出力し、ページ間のリンクはすべて相対パスです。
+
+mod markdown;
+mod mermaid;
+
+use crate::core::schema::{Constraint, Schema, Table};
+use std::collections::BTreeMap;
+
+pub use mermaid::render_er_diagram;
+
+/// 一覧ページのパス
+pub const INDEX_PAGE: &str = "index.md";
+
+/// テーブルのページを出力するディレクトリ
+pub const TABLES_DIR: &str = "tables";
+
+/// ビューのページを出力するディレクトリ
+pub const VIEWS_DIR: &str = "views";
+
+/// ENUM型のページを出力するディレクトリ
+pub const ENUMS_DIR: &str = "enums";
+
+/// 生成したページを出力するディレクトリ（一覧ページを除く）
+pub const PAGE_DIRS: [&str; 3] = [TABLES_DIR, VIEWS_DIR, ENUMS_DIR];
+
+/// 各ページの先頭に付ける、生成されたファイルであることを示すコメント
+pub const GENERATED_HEADER: &str =
+    "<!-- Generated by `strata docs` from the schema definitions. Do not edit by hand. -->";
+
+/// スキーマ定義からドキュメントを生成する
+///
+/// 返り値は出力ディレクトリからの相対パス（区切り文字は `/`）とページ内容の組です。
+pub fn render_schema_docs(schema: &Schema) -> BTreeMap<String, String> {
+    let mut pages = BTreeMap::new();
+    pages.insert(INDEX_PAGE.to_string(), markdown::render_index_page(schema));
+    for table in schema.tables.values() {
+        pages.insert(
+            page_path(TABLES_DIR, &table.name),
+            markdown::render_table_page(schema, table),
+        );
+    }
+    for view in schema.views.values() {
+        pages.insert(
+            page_path(VIEWS_DIR, &view.name),
+            markdown::render_view_page(schema, view),
+        );
+    }
+    for enum_def in schema.enums.values() {
+        pages.insert(
+            page_path(ENUMS_DIR, &enum_def.name),
+            markdown::render_enum_page(schema, enum_def),
+        );
+    }
+    pages
+}
+
+/// オブジェクトのページの相対パス（例: `tables/users.md`）
+pub fn page_path(dir: &str, name: &str) -> String {
+    format!("{}/{}.md", dir, page_file_stem(name))
+}
+
+/// オブジェクト名からページのファイル名（拡張子なし）を作る
+///
+/// 英数字と `_` `-` `.` はそのまま使い、それ以外の文字は UTF-8 の各バイトを `~xx` と表記します。
+/// ファイル名をそのままリンク先に使えるよう、URLエンコードが必要な文字は含めません。
+pub fn page_file_stem(name: &str) -> String {
+    let mut stem = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+            stem.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                stem.push_str(&format!("~{:02x}", byte));
+            }
+        }
+    }
+    // `.` と `..` はディレクトリを指すため、先頭の `.` もエンコードする
+    if stem.starts_with('.') {
+        stem.replace_range(0..1, "~2e");
+    }
+    stem
+}
+
+/// カラムが属するキーの種類（`PK` / `FK` / `UK`）
+///
+/// `UK` は単一カラムのユニーク制約・ユニークインデックス（部分インデックスを除く）の対象カラムです。
+fn column_keys(table: &Table, column_name: &str) -> Vec<&'static str> {
+    let column = [column_name.to_string()];
+    let mut keys = Vec::new();
+    let in_constraint = |kind: &str| {
+        table
+            .constraints
+            .iter()
+            .any(|c| c.kind() == kind && c.columns().iter().any(|name| name == column_name))
+    };
+    if in_constraint("PRIMARY_KEY") {
+        keys.push("PK");
+    }
+    if in_constraint("FOREIGN_KEY") {
+        keys.push("FK");
+    }
+    let unique = table.constraints.iter().any(|constraint| {
+        matches!(constraint, Constraint::UNIQUE { columns } if columns[..] == column[..])
+    }) || table.indexes.iter().any(|index| {
+        index.unique && index.where_clause.is_none() && index.column_names()[..] == column[..]
+    });
+    if unique {
+        keys.push("UK");
+    }
+    keys
+}
+
+/// カラムの組がテーブル内で一意になるか（プライマリキー・ユニーク制約・ユニークインデックス）
+fn is_unique_column_set(table: &Table, columns: &[String]) -> bool {
+    let sorted = |columns: &[String]| {
+        let mut columns = columns.to_vec();
+        columns.sort();
+        columns
+    };
+    let target = sorted(columns);
+    table.constraints.iter().any(|constraint| {
+        matches!(
+            constraint,
+            Constraint::PRIMARY_KEY { .. } | Constraint::UNIQUE { .. }
+        ) && sorted(constraint.columns()) == target
+    }) || table.indexes.iter().any(|index| {
+        index.unique && index.where_clause.is_none() && sorted(&index.column_names()) == target
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Table, View};
+
+    fn column(name: &str, column_type: ColumnType, nullable: bool) -> Column {
+        Column::new(name.to_string(), column_type, nullable)
+    }
+
+    fn sample_schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut users = Table::new("users".to_string());
+        users.add_column(column("id", ColumnType::INTEGER { precision: None }, false));
+        users.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        schema.add_table(users);
+
+        let mut posts = Table::new("posts".to_string());
+        posts.add_column(column("id", ColumnType::INTEGER { precision: None }, false));
+        posts.add_column(column(
+            "user_id",
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        schema.add_table(posts);
+
+        schema.add_enum(EnumDefinition {
+            name: "status".to_string(),
+            values: vec!["active".to_string()],
+            renamed_from: None,
+        });
+        schema.add_view(View::new(
+            "user posts".to_string(),
+            "SELECT * FROM posts".to_string(),
+        ));
+        schema
+    }
+
+    #[test]
+    fn test_render_schema_docs_pages() {
+        let pages = render_schema_docs(&sample_schema());
+        let paths: Vec<&str> = pages.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "enums/status.md",
+                "index.md",
+                "tables/posts.md",
+                "tables/users.md",
+                "views/user~20posts.md",
+            ]
+        );
+        assert!(pages
+            .values()
+            .all(|page| page.starts_with(GENERATED_HEADER)));
+        // 外部キーは参照先テーブルのページへの相対リンクになる
+        assert!(pages["tables/posts.md"].contains("[users](users.md)"));
+        assert!(pages["tables/users.md"].contains("[posts](posts.md)"));
+        assert!(pages["index.md"].contains("[user posts](views/user~20posts.md)"));
+    }
+
+    #[test]
+    fn test_render_schema_docs_is_deterministic() {
+        assert_eq!(
+            render_schema_docs(&sample_schema()),
+            render_schema_docs(&sample_schema())
+        );
+    }
+
+    #[test]
+    fn test_page_file_stem() {
+        let cases = [
+            ("users", "users"),
+            ("app.users", "app.users"),
+            ("order-items", "order-items"),
+            ("order items", "order~20items"),
+            ("a/b", "a~2fb"),
+            ("ユーザー", "~e3~83~a6~e3~83~bc~e3~82~b6~e3~83~bc"),
+            ("..", "~2e."),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(page_file_stem(name), expected, "name: {}", name);
+        }
+    }
+}
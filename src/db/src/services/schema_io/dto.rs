@@ -3,7 +3,11 @@
 // YAML構造と内部モデルを分離するためのDTO層。
 // 新構文のYAML（テーブル名はキー名、primary_keyは独立フィールド）をサポートします。
 
-use crate::core::schema::{Column, EnumDefinition, Index, ReferentialAction};
+use crate::core::schema::{
+    deserialize_default_value, Column, ColumnLifecycle, ColumnType, EnumDefinition,
+    GeneratedColumn, Index, ReferentialAction,
+};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -35,6 +39,10 @@ pub struct SchemaDto {
     /// ビュー定義のマップ（ビュー名 -> ViewDto）
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub views: BTreeMap<String, ViewDto>,
+
+    /// strata管理外のテーブル名のリスト（FKの参照先としてのみ使用）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_tables: Vec<String>,
 }
 
 /// YAML テーブル定義用DTO
@@ -43,8 +51,14 @@ pub struct SchemaDto {
 /// `name`フィールドを持たず、キー名からテーブル名を取得します。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableDto {
+    /// PostgreSQLのスキーマ名（オプショナル）
+    ///
+    /// 指定した場合、テーブル名は `schema.table` 形式の修飾名として扱います。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
     /// カラム定義（必須）
-    pub columns: Vec<Column>,
+    pub columns: Vec<ColumnDto>,
 
     /// 主キーカラム名のリスト（オプショナル）
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -62,6 +76,165 @@ pub struct TableDto {
     /// リネーム元のテーブル名（オプショナル）
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub renamed_from: Option<String>,
+
+    /// テーブル作成直後にデータを投入するSELECT文（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub populate_from: Option<String>,
+
+    /// ロールバック保護フラグ（デフォルト: false）
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub rollback_protection: bool,
+
+    /// テーブルのコメント（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// 外部ツール向けの任意のメタデータ（オプショナル）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// YAML カラム定義用DTO
+///
+/// `Column` のフィールドに加え、単一カラムの外部キーを表す
+/// `references: <table>.<column>` の省略記法を持ちます。
+/// 省略記法はパース時に `Constraint::FOREIGN_KEY` へ展開されます。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDto {
+    /// カラム名
+    pub name: String,
+
+    /// カラム型
+    #[serde(rename = "type")]
+    pub column_type: ColumnType,
+
+    /// NULL許可フラグ（デフォルト: false = NOT NULL）
+    #[serde(default)]
+    pub nullable: bool,
+
+    /// デフォルト値
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_default_value"
+    )]
+    pub default_value: Option<String>,
+
+    /// 自動増分フラグ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_increment: Option<bool>,
+
+    /// リネーム元のカラム名（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+
+    /// 生成カラム定義（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<GeneratedColumn>,
+
+    /// カラムのコメント（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// 行の更新時に現在時刻を設定するか（MySQL専用）
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub on_update_current_timestamp: bool,
+
+    /// 型変更で使う `USING` 式（PostgreSQL専用、オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_change_using: Option<String>,
+
+    /// 型変更を戻す down.sql で使う `USING` 式（PostgreSQL専用、オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub down_using: Option<String>,
+
+    /// 参照先（`<table>.<column>` 形式、オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<String>,
+
+    /// 参照先レコード削除時のアクション（`references` 指定時のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<ReferentialAction>,
+
+    /// 参照先レコード更新時のアクション（`references` 指定時のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_update: Option<ReferentialAction>,
+
+    /// 外部ツール向けの任意のメタデータ（オプショナル）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+
+    /// expand/contract による移行でのカラムの段階（オプショナル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<ColumnLifecycle>,
+
+    /// `contract_pending` になった日付（generate がスナップショットに記録）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_pending_since: Option<NaiveDate>,
+}
+
+impl ColumnDto {
+    /// `references` を (参照先テーブル, 参照先カラム) に分解する
+    ///
+    /// テーブル名にはドットを含められるよう、最後のドットで分割する。
+    /// 形式が不正な場合は None を返す。
+    pub fn parse_references(references: &str) -> Option<(&str, &str)> {
+        let (table, column) = references.rsplit_once('.')?;
+        if table.is_empty() || column.is_empty() {
+            return None;
+        }
+        Some((table, column))
+    }
+}
+
+impl From<Column> for ColumnDto {
+    fn from(column: Column) -> Self {
+        Self {
+            name: column.name,
+            column_type: column.column_type,
+            nullable: column.nullable,
+            default_value: column.default_value,
+            auto_increment: column.auto_increment,
+            renamed_from: column.renamed_from,
+            generated: column.generated,
+            comment: column.comment,
+            on_update_current_timestamp: column.on_update_current_timestamp,
+            type_change_using: column.type_change_using,
+            down_using: column.down_using,
+            references: None,
+            on_delete: None,
+            on_update: None,
+            metadata: column.metadata,
+            lifecycle: column.lifecycle,
+            contract_pending_since: column.contract_pending_since,
+        }
+    }
+}
+
+impl From<&ColumnDto> for Column {
+    fn from(dto: &ColumnDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            // 非推奨の INTEGER の precision 指定は SMALLINT / BIGINT として扱い、
+            // 書き方の違いで差分が出ないようにする
+            column_type: dto
+                .column_type
+                .normalize_integer_precision()
+                .unwrap_or_else(|| dto.column_type.clone()),
+            nullable: dto.nullable,
+            default_value: dto.default_value.clone(),
+            auto_increment: dto.auto_increment,
+            renamed_from: dto.renamed_from.clone(),
+            generated: dto.generated.clone(),
+            comment: dto.comment.clone(),
+            on_update_current_timestamp: dto.on_update_current_timestamp,
+            type_change_using: dto.type_change_using.clone(),
+            down_using: dto.down_using.clone(),
+            metadata: dto.metadata.clone(),
+            lifecycle: dto.lifecycle,
+            contract_pending_since: dto.contract_pending_since,
+        }
+    }
 }
 
 /// 制約DTO（PRIMARY_KEY以外）
@@ -94,7 +267,8 @@ pub enum ConstraintDto {
     },
     /// チェック制約
     CHECK {
-        /// 対象カラム
+        /// 対象カラム（参考情報。テーブル単位の制約では省略可能で、差分検出では式のみを比較する）
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         columns: Vec<String>,
         /// チェック式
         check_expression: String,
@@ -180,6 +354,7 @@ tables: {}
             enums: BTreeMap::new(),
             tables: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: Vec::new(),
         };
 
         let yaml = serde_saphyr::to_string(&dto).unwrap();
@@ -198,6 +373,7 @@ tables: {}
             enums: BTreeMap::new(),
             tables: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: Vec::new(),
         };
 
         let yaml = serde_saphyr::to_string(&dto).unwrap();
@@ -289,18 +465,40 @@ indexes:
         assert!(dto.indexes[0].unique);
     }
 
+    #[test]
+    fn test_table_dto_deserialize_with_populate_from() {
+        let yaml = r#"
+columns:
+  - name: user_id
+    type:
+      kind: INTEGER
+    nullable: false
+populate_from: |
+  SELECT id FROM users
+"#;
+        let dto: TableDto = serde_saphyr::from_str(yaml).unwrap();
+
+        assert_eq!(dto.populate_from.as_deref(), Some("SELECT id FROM users\n"));
+    }
+
     #[test]
     fn test_table_dto_serialize_skips_empty_fields() {
         let dto = TableDto {
+            schema: None,
             columns: vec![Column::new(
                 "id".to_string(),
                 ColumnType::INTEGER { precision: None },
                 false,
-            )],
+            )
+            .into()],
             primary_key: None,
             indexes: vec![],
             constraints: vec![],
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
         };
 
         let yaml = serde_saphyr::to_string(&dto).unwrap();
@@ -309,20 +507,27 @@ indexes:
         assert!(!yaml.contains("primary_key:"));
         assert!(!yaml.contains("indexes:"));
         assert!(!yaml.contains("constraints:"));
+        assert!(!yaml.contains("populate_from:"));
     }
 
     #[test]
     fn test_table_dto_serialize_includes_primary_key_when_present() {
         let dto = TableDto {
+            schema: None,
             columns: vec![Column::new(
                 "id".to_string(),
                 ColumnType::INTEGER { precision: None },
                 false,
-            )],
+            )
+            .into()],
             primary_key: Some(vec!["id".to_string()]),
             indexes: vec![],
             constraints: vec![],
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
         };
 
         let yaml = serde_saphyr::to_string(&dto).unwrap();
@@ -399,6 +604,21 @@ check_expression: "age >= 0"
         }
     }
 
+    #[test]
+    fn test_constraint_dto_deserialize_check_without_columns() {
+        let yaml = r#"
+type: CHECK
+check_expression: "start_date < end_date"
+"#;
+        let dto: ConstraintDto = serde_saphyr::from_str(yaml).unwrap();
+
+        if let ConstraintDto::CHECK { columns, .. } = dto {
+            assert!(columns.is_empty());
+        } else {
+            panic!("Expected CHECK constraint");
+        }
+    }
+
     #[test]
     fn test_constraint_dto_serialize_foreign_key() {
         let dto = ConstraintDto::FOREIGN_KEY {
@@ -529,20 +749,27 @@ tables:
             enum_recreate_allowed: false,
             enums: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: Vec::new(),
             tables: {
                 let mut tables = BTreeMap::new();
                 tables.insert(
                     "users".to_string(),
                     TableDto {
+                        schema: None,
                         columns: vec![Column::new(
                             "id".to_string(),
                             ColumnType::INTEGER { precision: None },
                             false,
-                        )],
+                        )
+                        .into()],
                         primary_key: Some(vec!["id".to_string()]),
                         indexes: vec![],
                         constraints: vec![],
                         renamed_from: None,
+                        populate_from: None,
+                        rollback_protection: false,
+                        metadata: BTreeMap::new(),
+                        comment: None,
                     },
                 );
                 tables
@@ -563,4 +790,56 @@ tables:
         assert_eq!(parsed_users.columns.len(), original_users.columns.len());
         assert_eq!(parsed_users.primary_key, original_users.primary_key);
     }
+
+    // ======================================
+    // ColumnDto references 省略記法テスト
+    // ======================================
+
+    #[test]
+    fn test_column_dto_deserialize_with_references() {
+        let yaml = r#"
+name: user_id
+type:
+  kind: INTEGER
+nullable: false
+references: users.id
+on_delete: CASCADE
+"#;
+        let dto: ColumnDto = serde_saphyr::from_str(yaml).unwrap();
+
+        assert_eq!(dto.name, "user_id");
+        assert_eq!(dto.references.as_deref(), Some("users.id"));
+        assert_eq!(dto.on_delete, Some(ReferentialAction::Cascade));
+        assert_eq!(dto.on_update, None);
+    }
+
+    #[test]
+    fn test_column_dto_serialize_skips_empty_references() {
+        let dto: ColumnDto = Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        )
+        .into();
+        let yaml = serde_saphyr::to_string(&dto).unwrap();
+
+        assert!(!yaml.contains("references"));
+        assert!(!yaml.contains("on_delete"));
+        assert!(!yaml.contains("on_update"));
+    }
+
+    #[test]
+    fn test_column_dto_parse_references() {
+        assert_eq!(
+            ColumnDto::parse_references("users.id"),
+            Some(("users", "id"))
+        );
+        assert_eq!(
+            ColumnDto::parse_references("app.users.id"),
+            Some(("app.users", "id"))
+        );
+        assert_eq!(ColumnDto::parse_references("users"), None);
+        assert_eq!(ColumnDto::parse_references(".id"), None);
+        assert_eq!(ColumnDto::parse_references("users."), None);
+    }
 }
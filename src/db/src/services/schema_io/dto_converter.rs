@@ -3,8 +3,9 @@
 // Schema ↔ SchemaDto の双方向変換を一元管理するサービス。
 // パース(DTO→Schema)とシリアライズ(Schema→DTO)の整合性を保証します。
 
-use crate::core::schema::{Constraint, Schema, Table, View};
-use crate::services::schema_io::dto::{ConstraintDto, SchemaDto, TableDto, ViewDto};
+use crate::core::config::DEFAULT_POSTGRES_SCHEMA;
+use crate::core::schema::{Column, Constraint, Schema, Table, View};
+use crate::services::schema_io::dto::{ColumnDto, ConstraintDto, SchemaDto, TableDto, ViewDto};
 use std::collections::BTreeMap;
 
 /// DTO変換サービス
@@ -48,6 +49,7 @@ impl DtoConverterService {
                 .collect(),
             tables,
             views,
+            external_tables: schema.external_tables.iter().cloned().collect(),
         }
     }
 
@@ -76,6 +78,8 @@ impl DtoConverterService {
             schema.add_view(view);
         }
 
+        schema.external_tables = dto.external_tables.iter().cloned().collect();
+
         schema
     }
 
@@ -83,24 +87,64 @@ impl DtoConverterService {
     ///
     /// PRIMARY_KEY制約を primary_key フィールドに抽出し、
     /// それ以外の制約を constraints フィールドに変換します。
+    /// 単一カラムの外部キーは、制約の並び順を保てる範囲でカラムの `references` に変換します。
     pub fn table_to_dto(&self, table: &Table) -> TableDto {
+        let shorthand_start = self.shorthand_foreign_key_start(table);
+        let (constraints, shorthand) = table.constraints.split_at(shorthand_start);
+
+        let mut columns: Vec<ColumnDto> =
+            table.columns.iter().cloned().map(ColumnDto::from).collect();
+        for constraint in shorthand {
+            if let Constraint::FOREIGN_KEY {
+                columns: fk_columns,
+                referenced_table,
+                referenced_columns,
+                on_delete,
+                on_update,
+            } = constraint
+            {
+                if let Some(column) = columns.iter_mut().find(|c| c.name == fk_columns[0]) {
+                    column.references =
+                        Some(format!("{}.{}", referenced_table, referenced_columns[0]));
+                    column.on_delete = on_delete.clone();
+                    column.on_update = on_update.clone();
+                }
+            }
+        }
+
         TableDto {
-            columns: table.columns.clone(),
+            schema: table.schema.clone(),
+            columns,
             primary_key: self.extract_primary_key(&table.constraints),
             indexes: table.indexes.clone(),
-            constraints: self.convert_constraints_to_dto(&table.constraints),
+            constraints: self.convert_constraints_to_dto(constraints),
             renamed_from: table.renamed_from.clone(),
+            populate_from: table.populate_from.clone(),
+            rollback_protection: table.rollback_protection,
+            comment: table.comment.clone(),
+            metadata: table.metadata.clone(),
         }
     }
 
     /// TableDto → Table 変換
     ///
     /// テーブル名をキーから取得し、primary_key を Constraint::PRIMARY_KEY に変換します。
+    /// `schema` を指定した場合、キーを `schema.table` 形式の修飾名にします
+    /// （デフォルトスキーマの `public` は指定なしと同じ扱いです）。
     pub fn dto_to_table(&self, name: &str, dto: &TableDto) -> Table {
-        let mut table = Table::new(name.to_string());
+        let mut table = match dto.schema.as_deref() {
+            Some(DEFAULT_POSTGRES_SCHEMA) => Table::new(
+                name.strip_prefix(DEFAULT_POSTGRES_SCHEMA)
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .unwrap_or(name)
+                    .to_string(),
+            ),
+            Some(schema) => Table::new_in_schema(schema.to_string(), name),
+            None => Table::new(name.to_string()),
+        };
 
         // カラムをコピー
-        table.columns = dto.columns.clone();
+        table.columns = dto.columns.iter().map(Column::from).collect();
 
         // インデックスをコピー
         table.indexes = dto.indexes.clone();
@@ -118,9 +162,28 @@ impl DtoConverterService {
             table.add_constraint(constraint);
         }
 
+        // references 省略記法 → Constraint::FOREIGN_KEY 展開（明示的な制約の後にカラム順で追加）
+        for column in &dto.columns {
+            if let Some(constraint) = self.expand_column_reference(column) {
+                table.add_constraint(constraint);
+            }
+        }
+
         // renamed_from をコピー
         table.renamed_from = dto.renamed_from.clone();
 
+        // populate_from をコピー
+        table.populate_from = dto.populate_from.clone();
+
+        // rollback_protection をコピー
+        table.rollback_protection = dto.rollback_protection;
+
+        // comment をコピー
+        table.comment = dto.comment.clone();
+
+        // metadata をコピー
+        table.metadata = dto.metadata.clone();
+
         table
     }
 
@@ -153,6 +216,7 @@ impl DtoConverterService {
                 columns: columns.clone(),
                 check_expression: check_expression.clone(),
             }),
+            _ => None,
         }
     }
 
@@ -202,6 +266,68 @@ impl DtoConverterService {
         view
     }
 
+    /// カラムの `references` を外部キー制約に展開
+    ///
+    /// `references` が未指定、または `<table>.<column>` 形式でない場合は None を返す。
+    /// 形式の検証はパース時に行われる。
+    pub fn expand_column_reference(&self, column: &ColumnDto) -> Option<Constraint> {
+        let (referenced_table, referenced_column) =
+            ColumnDto::parse_references(column.references.as_deref()?)?;
+        Some(Constraint::FOREIGN_KEY {
+            columns: vec![column.name.clone()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec![referenced_column.to_string()],
+            on_delete: column.on_delete.clone(),
+            on_update: column.on_update.clone(),
+        })
+    }
+
+    /// `references` 省略記法で出力する外部キーの開始位置を求める
+    ///
+    /// パース時は省略記法の外部キーを明示的な制約の後にカラム順で追加するため、
+    /// 制約リスト末尾の、カラム順に並んだ単一カラムの外部キーだけを省略記法にする。
+    /// これによりシリアライズ → パースで制約の並び順が変わらない。
+    fn shorthand_foreign_key_start(&self, table: &Table) -> usize {
+        let mut start = table.constraints.len();
+        let mut next_position = usize::MAX;
+        for (index, constraint) in table.constraints.iter().enumerate().rev() {
+            match self.shorthand_column_position(table, constraint) {
+                Some(position) if position < next_position => {
+                    start = index;
+                    next_position = position;
+                }
+                _ => break,
+            }
+        }
+        start
+    }
+
+    /// 省略記法で表現できる外部キーであれば、対象カラムの位置を返す
+    fn shorthand_column_position(&self, table: &Table, constraint: &Constraint) -> Option<usize> {
+        let Constraint::FOREIGN_KEY {
+            columns,
+            referenced_table,
+            referenced_columns,
+            ..
+        } = constraint
+        else {
+            return None;
+        };
+        if columns.len() != 1 || referenced_columns.len() != 1 {
+            return None;
+        }
+
+        // 最後のドットで分割するため、参照先カラム名にドットを含む場合は表現できない
+        let references = format!("{}.{}", referenced_table, referenced_columns[0]);
+        if ColumnDto::parse_references(&references)
+            != Some((referenced_table.as_str(), referenced_columns[0].as_str()))
+        {
+            return None;
+        }
+
+        table.columns.iter().position(|c| c.name == columns[0])
+    }
+
     /// PRIMARY_KEY制約を抽出
     fn extract_primary_key(&self, constraints: &[Constraint]) -> Option<Vec<String>> {
         constraints.iter().find_map(|c| {
@@ -231,7 +357,7 @@ impl Default for DtoConverterService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::schema::{Column, ColumnType, EnumDefinition, Index};
+    use crate::core::schema::{Column, ColumnType, EnumDefinition, Index, ReferentialAction};
 
     // ======================================
     // Task 2.1: DtoConverterService 基本テスト
@@ -283,6 +409,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
         let service = DtoConverterService::new();
 
@@ -300,6 +427,7 @@ mod tests {
             enums: BTreeMap::new(),
             tables: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: Vec::new(),
         };
         let service = DtoConverterService::new();
 
@@ -319,6 +447,7 @@ mod tests {
             enums: BTreeMap::new(),
             tables: BTreeMap::new(),
             views: BTreeMap::new(),
+            external_tables: Vec::new(),
         };
         let service = DtoConverterService::new();
 
@@ -421,15 +550,21 @@ mod tests {
     #[test]
     fn test_dto_to_table_minimal() {
         let dto = TableDto {
+            schema: None,
             columns: vec![Column::new(
                 "id".to_string(),
                 ColumnType::INTEGER { precision: None },
                 false,
-            )],
+            )
+            .into()],
             primary_key: None,
             indexes: vec![],
             constraints: vec![],
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
         };
         let service = DtoConverterService::new();
 
@@ -443,15 +578,21 @@ mod tests {
     #[test]
     fn test_dto_to_table_with_primary_key() {
         let dto = TableDto {
+            schema: None,
             columns: vec![Column::new(
                 "id".to_string(),
                 ColumnType::INTEGER { precision: None },
                 false,
-            )],
+            )
+            .into()],
             primary_key: Some(vec!["id".to_string()]),
             indexes: vec![],
             constraints: vec![],
             renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
         };
         let service = DtoConverterService::new();
 
@@ -462,6 +603,44 @@ mod tests {
         assert_eq!(pk_columns.unwrap(), vec!["id"]);
     }
 
+    #[test]
+    fn test_dto_to_table_with_schema_qualifies_name() {
+        let dto = TableDto {
+            schema: Some("app".to_string()),
+            columns: vec![],
+            primary_key: None,
+            indexes: vec![],
+            constraints: vec![],
+            renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
+        };
+        let service = DtoConverterService::new();
+
+        // キーがスキーマ修飾なしでも、修飾済みでも同じテーブル名になる
+        for key in ["users", "app.users"] {
+            let table = service.dto_to_table(key, &dto);
+            assert_eq!(table.name, "app.users");
+            assert_eq!(table.schema.as_deref(), Some("app"));
+            assert_eq!(table.unqualified_name(), "users");
+        }
+
+        // table_to_dto でスキーマを書き戻す
+        let table = service.dto_to_table("users", &dto);
+        assert_eq!(service.table_to_dto(&table).schema.as_deref(), Some("app"));
+
+        // public はスキーマ指定なしと同じ扱い
+        let public = TableDto {
+            schema: Some("public".to_string()),
+            ..dto
+        };
+        let table = service.dto_to_table("public.users", &public);
+        assert_eq!(table.name, "users");
+        assert_eq!(table.schema, None);
+    }
+
     // ======================================
     // Constraint ↔ ConstraintDto 変換テスト
     // ======================================
@@ -665,6 +844,7 @@ mod tests {
         original.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         // users テーブル
@@ -804,4 +984,139 @@ mod tests {
             restored.get_primary_key_columns()
         );
     }
+
+    // ======================================
+    // references 省略記法テスト
+    // ======================================
+
+    fn integer_column(name: &str) -> Column {
+        Column::new(
+            name.to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        )
+    }
+
+    fn single_foreign_key(column: &str, referenced_table: &str) -> Constraint {
+        Constraint::FOREIGN_KEY {
+            columns: vec![column.to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    #[test]
+    fn test_dto_to_table_expands_references_after_constraints() {
+        let service = DtoConverterService::new();
+        let mut author_id: ColumnDto = integer_column("author_id").into();
+        author_id.references = Some("users.id".to_string());
+        author_id.on_delete = Some(ReferentialAction::Cascade);
+        let mut category_id: ColumnDto = integer_column("category_id").into();
+        category_id.references = Some("categories.id".to_string());
+        let dto = TableDto {
+            schema: None,
+            columns: vec![integer_column("id").into(), author_id, category_id],
+            primary_key: Some(vec!["id".to_string()]),
+            indexes: vec![],
+            constraints: vec![ConstraintDto::UNIQUE {
+                columns: vec!["author_id".to_string()],
+            }],
+            renamed_from: None,
+            populate_from: None,
+            rollback_protection: false,
+            metadata: BTreeMap::new(),
+            comment: None,
+        };
+
+        let table = service.dto_to_table("posts", &dto);
+
+        assert_eq!(table.constraints.len(), 4);
+        assert!(matches!(
+            table.constraints[0],
+            Constraint::PRIMARY_KEY { .. }
+        ));
+        assert!(matches!(table.constraints[1], Constraint::UNIQUE { .. }));
+        assert_eq!(
+            table.constraints[2],
+            Constraint::FOREIGN_KEY {
+                columns: vec!["author_id".to_string()],
+                referenced_table: "users".to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: Some(ReferentialAction::Cascade),
+                on_update: None,
+            }
+        );
+        assert_eq!(
+            table.constraints[3],
+            single_foreign_key("category_id", "categories")
+        );
+    }
+
+    #[test]
+    fn test_table_to_dto_uses_references_for_single_column_foreign_key() {
+        let service = DtoConverterService::new();
+        let mut table = Table::new("posts".to_string());
+        table.add_column(integer_column("id"));
+        table.add_column(integer_column("user_id"));
+        table.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::SetNull),
+            on_update: None,
+        });
+
+        let dto = service.table_to_dto(&table);
+
+        assert!(dto.constraints.is_empty());
+        assert_eq!(dto.columns[0].references, None);
+        assert_eq!(dto.columns[1].references.as_deref(), Some("users.id"));
+        assert_eq!(dto.columns[1].on_delete, Some(ReferentialAction::SetNull));
+        assert_eq!(service.dto_to_table("posts", &dto), table);
+    }
+
+    #[test]
+    fn test_table_to_dto_keeps_foreign_key_before_other_constraints_explicit() {
+        let service = DtoConverterService::new();
+        let mut table = Table::new("posts".to_string());
+        table.add_column(integer_column("user_id"));
+        table.add_column(integer_column("category_id"));
+        table.add_constraint(single_foreign_key("category_id", "categories"));
+        table.add_constraint(single_foreign_key("user_id", "users"));
+        table.add_constraint(Constraint::UNIQUE {
+            columns: vec!["user_id".to_string()],
+        });
+
+        let dto = service.table_to_dto(&table);
+
+        // 省略記法にすると展開時に並び順が変わるため、すべて明示的な制約のまま
+        assert_eq!(dto.constraints.len(), 3);
+        assert!(dto.columns.iter().all(|c| c.references.is_none()));
+        assert_eq!(service.dto_to_table("posts", &dto), table);
+    }
+
+    #[test]
+    fn test_table_to_dto_keeps_composite_foreign_key_explicit() {
+        let service = DtoConverterService::new();
+        let mut table = Table::new("order_items".to_string());
+        table.add_column(integer_column("order_id"));
+        table.add_column(integer_column("tenant_id"));
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["order_id".to_string(), "tenant_id".to_string()],
+            referenced_table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string(), "tenant_id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+
+        let dto = service.table_to_dto(&table);
+
+        assert_eq!(dto.constraints.len(), 1);
+        assert!(dto.columns.iter().all(|c| c.references.is_none()));
+    }
 }
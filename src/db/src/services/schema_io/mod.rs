@@ -6,3 +6,6 @@ pub mod dto;
 pub mod dto_converter;
 pub mod schema_parser;
 pub mod schema_serializer;
+pub mod schema_snapshot;
+pub mod schema_sources;
+pub mod source_lines;
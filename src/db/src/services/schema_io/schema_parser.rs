@@ -5,14 +5,47 @@
 //
 // DTO変換はDtoConverterServiceに委譲しています。
 
-use crate::core::error::IoError;
-use crate::core::schema::Schema;
-use crate::services::schema_io::dto::SchemaDto;
+use crate::adapters::sequence_values::SEQUENCE_VALUES_FILE;
+use crate::core::error::{ErrorLocation, IoError, ValidationError};
+use crate::core::schema::{ColumnType, Schema};
+use crate::services::schema_io::dto::{ColumnDto, ConstraintDto, SchemaDto};
 use crate::services::schema_io::dto_converter::DtoConverterService;
+use crate::services::schema_io::schema_sources::SchemaSources;
+use crate::services::schema_io::source_lines::{self, FileLines};
 use anyhow::Result;
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing::warn;
+
+/// 複数のスキーマファイルで同じテーブル・ENUM・ビューが定義されているエラー
+///
+/// `validate` で定義元ファイルを含む検証エラーとして報告できるよう、
+/// 重複ごとの `ValidationError::Duplicate` と読み込んだファイルの一覧を保持します。
+#[derive(Debug, Clone, Error)]
+#[error(
+    "Duplicate definitions found across schema files:\n{}\n\nRemove one of the definitions, or pass --allow-duplicate-override to let the later file win.",
+    format_duplicates(.errors)
+)]
+pub struct DuplicateDefinitionsError {
+    /// 重複ごとの検証エラー（位置は後から読み込んだ定義元ファイル）
+    pub errors: Vec<ValidationError>,
+    /// 読み込んだスキーマファイル
+    pub schema_files: Vec<PathBuf>,
+}
+
+fn format_duplicates(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|error| match error {
+            ValidationError::Duplicate { message, .. } => format!("  - {}", message),
+            other => format!("  - {}", other),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
 /// スキーマパーサーサービス
 ///
@@ -22,6 +55,10 @@ use std::path::Path;
 pub struct SchemaParserService {
     /// DTO変換サービス
     dto_converter: DtoConverterService,
+    /// 複数ファイルでの重複定義を許可し、後のファイルで上書きするか
+    allow_duplicate_override: bool,
+    /// ディスクの代わりに読み込むファイルの内容（ファイルパス -> 内容）
+    file_contents: HashMap<PathBuf, String>,
 }
 
 impl SchemaParserService {
@@ -29,9 +66,27 @@ impl SchemaParserService {
     pub fn new() -> Self {
         Self {
             dto_converter: DtoConverterService::new(),
+            allow_duplicate_override: false,
+            file_contents: HashMap::new(),
         }
     }
 
+    /// 複数ファイルでの重複定義を許可するかを設定
+    ///
+    /// 許可した場合はファイル名順で後のファイルの定義が採用され、警告が出力されます。
+    pub fn with_allow_duplicate_override(mut self, allow: bool) -> Self {
+        self.allow_duplicate_override = allow;
+        self
+    }
+
+    /// 指定したファイルをディスクではなく指定した内容で読み込む
+    ///
+    /// 書き換え案を適用したスキーマを、ファイルに書き込む前に検証するために使用します。
+    pub fn with_file_contents(mut self, contents: HashMap<PathBuf, String>) -> Self {
+        self.file_contents = contents;
+        self
+    }
+
     /// 指定されたディレクトリからすべてのYAMLファイルを読み込み、統合されたスキーマを返す
     ///
     /// # Arguments
@@ -56,6 +111,16 @@ impl SchemaParserService {
         &self,
         schema_dir: &Path,
     ) -> Result<(Schema, Vec<std::path::PathBuf>)> {
+        let (schema, files, _) = self.parse_schema_directory_with_sources(schema_dir)?;
+        Ok((schema, files))
+    }
+
+    /// 指定されたディレクトリからすべてのYAMLファイルを読み込み、統合されたスキーマ、
+    /// ファイルリスト、各要素の定義元ファイルを返す
+    pub fn parse_schema_directory_with_sources(
+        &self,
+        schema_dir: &Path,
+    ) -> Result<(Schema, Vec<PathBuf>, SchemaSources)> {
         // ディレクトリの存在確認
         if !schema_dir.exists() {
             return Err(IoError::FileNotFound {
@@ -76,39 +141,25 @@ impl SchemaParserService {
 
         // YAMLファイルが存在しない場合は空のスキーマを返す
         if yaml_files.is_empty() {
-            return Ok((Schema::new("1.0".to_string()), Vec::new()));
+            return Ok((
+                Schema::new("1.0".to_string()),
+                Vec::new(),
+                SchemaSources::new(),
+            ));
         }
 
-        // 各YAMLファイルを解析してスキーマをマージ
-        let mut merged_schema = Schema::new("1.0".to_string());
+        // 各YAMLファイルをDTOとして解析
+        // 重複検出のため、DTO→Schema変換の前に定義元ファイルを記録する
         let parsed_files = yaml_files.clone();
+        let mut dtos: Vec<(PathBuf, SchemaDto)> = Vec::new();
+        let mut file_lines: Vec<FileLines> = Vec::new();
         let mut errors: Vec<String> = Vec::new();
 
         for file_path in yaml_files {
-            match self.parse_schema_file(&file_path) {
-                Ok(schema) => {
-                    // バージョンを保持（最初に見つかったバージョンを使用）
-                    if merged_schema.table_count() == 0
-                        && merged_schema.enums.is_empty()
-                        && merged_schema.views.is_empty()
-                    {
-                        merged_schema.version = schema.version;
-                    }
-
-                    // テーブルをマージ
-                    for (table_name, table) in schema.tables {
-                        merged_schema.tables.insert(table_name, table);
-                    }
-
-                    // ENUMをマージ
-                    for (enum_name, enum_def) in schema.enums {
-                        merged_schema.enums.insert(enum_name, enum_def);
-                    }
-
-                    // ビューをマージ
-                    for (view_name, view) in schema.views {
-                        merged_schema.views.insert(view_name, view);
-                    }
+            match self.read_schema_dto(&file_path) {
+                Ok((dto, content)) => {
+                    dtos.push((file_path, dto));
+                    file_lines.push(source_lines::scan(&content));
                 }
                 Err(e) => {
                     errors.push(format!("{:?}: {:#}", file_path, e));
@@ -130,7 +181,52 @@ impl SchemaParserService {
             return Err(anyhow::anyhow!(error_msg));
         }
 
-        Ok((merged_schema, parsed_files))
+        self.check_duplicate_definitions(&dtos)?;
+
+        for message in find_deprecated_integer_precision(&dtos) {
+            warn!("{}", message);
+            eprintln!("Warning: {}", message);
+        }
+
+        // スキーマをマージ
+        let mut merged_schema = Schema::new("1.0".to_string());
+        let mut sources = SchemaSources::new();
+        for ((file_path, dto), lines) in dtos.iter().zip(file_lines) {
+            let schema = self.dto_converter.dto_to_schema(dto);
+            sources.record(file_path, &schema);
+            sources.record_lines(lines);
+
+            // バージョンを保持（最初に見つかったバージョンを使用）
+            if merged_schema.table_count() == 0
+                && merged_schema.enums.is_empty()
+                && merged_schema.views.is_empty()
+            {
+                merged_schema.version = schema.version;
+            }
+
+            // ENUM再作成の許可はいずれかのファイルで指定されていれば有効
+            merged_schema.enum_recreate_allowed |= schema.enum_recreate_allowed;
+
+            // テーブルをマージ
+            for (table_name, table) in schema.tables {
+                merged_schema.tables.insert(table_name, table);
+            }
+
+            // ENUMをマージ
+            for (enum_name, enum_def) in schema.enums {
+                merged_schema.enums.insert(enum_name, enum_def);
+            }
+
+            // ビューをマージ
+            for (view_name, view) in schema.views {
+                merged_schema.views.insert(view_name, view);
+            }
+
+            // 外部テーブル宣言をマージ
+            merged_schema.external_tables.extend(schema.external_tables);
+        }
+
+        Ok((merged_schema, parsed_files, sources))
     }
 
     /// 単一のYAMLファイルを解析してスキーマオブジェクトに変換
@@ -149,26 +245,223 @@ impl SchemaParserService {
     /// - ファイルの読み込みに失敗した場合
     /// - YAMLの解析に失敗した場合
     pub fn parse_schema_file(&self, file_path: &Path) -> Result<Schema> {
-        // ファイルの存在確認
-        if !file_path.exists() {
-            return Err(IoError::FileNotFound {
+        let dto = self.parse_schema_dto(file_path)?;
+
+        // DTOを内部モデルに変換（DtoConverterServiceに委譲）
+        Ok(self.dto_converter.dto_to_schema(&dto))
+    }
+
+    /// ディレクトリ内のYAMLファイルから、解析時に無視されるフィールドを検出
+    ///
+    /// 別バージョンのstrataが出力したスキーマを読み込む際の警告用です。
+    /// 各フィールドは `<ファイル名>: <パス>`（例: `users.yaml: tables.users.columns[0].collation`）で返します。
+    /// 値が `null`・`false`・空の場合は省略時の値と同じとみなし、報告しません。
+    pub fn find_unknown_fields(&self, schema_dir: &Path) -> Result<Vec<String>> {
+        let mut unknown_fields = Vec::new();
+
+        for file_path in self.scan_yaml_files(schema_dir)? {
+            let content = fs::read_to_string(&file_path).map_err(|e| IoError::FileRead {
                 path: file_path.display().to_string(),
-            }
-            .into());
+                cause: e.to_string(),
+            })?;
+            let raw: serde_json::Value = serde_saphyr::from_str(&content)
+                .map_err(|e| self.format_parse_error(&file_path, e))?;
+            let dto = self.parse_schema_dto(&file_path)?;
+            let known = serde_json::to_value(&dto)?;
+
+            let mut paths = Vec::new();
+            collect_unknown_fields(&raw, &known, "", &mut paths);
+
+            let file_name = file_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| file_path.display().to_string());
+            unknown_fields.extend(
+                paths
+                    .into_iter()
+                    .map(|path| format!("{}: {}", file_name, path)),
+            );
         }
 
-        // ファイル内容を読み込み
-        let content = fs::read_to_string(file_path).map_err(|e| IoError::FileRead {
-            path: file_path.display().to_string(),
-            cause: e.to_string(),
-        })?;
+        Ok(unknown_fields)
+    }
+
+    /// 単一のYAMLファイルをDTOとして読み込む
+    fn parse_schema_dto(&self, file_path: &Path) -> Result<SchemaDto> {
+        self.read_schema_dto(file_path).map(|(dto, _)| dto)
+    }
+
+    /// 単一のYAMLファイルをDTOとして解析し、ファイルの内容と合わせて返す
+    ///
+    /// 内容は定義行の取得（`source_lines::scan`）に使用します。
+    fn read_schema_dto(&self, file_path: &Path) -> Result<(SchemaDto, String)> {
+        let content = match self.file_contents.get(file_path) {
+            Some(content) => content.clone(),
+            None => {
+                // ファイルの存在確認
+                if !file_path.exists() {
+                    return Err(IoError::FileNotFound {
+                        path: file_path.display().to_string(),
+                    }
+                    .into());
+                }
+
+                // ファイル内容を読み込み
+                fs::read_to_string(file_path).map_err(|e| IoError::FileRead {
+                    path: file_path.display().to_string(),
+                    cause: e.to_string(),
+                })?
+            }
+        };
 
         // YAMLをDTOにデシリアライズ
         let dto: SchemaDto =
             serde_saphyr::from_str(&content).map_err(|e| self.format_parse_error(file_path, e))?;
 
-        // DTOを内部モデルに変換（DtoConverterServiceに委譲）
-        Ok(self.dto_converter.dto_to_schema(&dto))
+        let problems = self.check_column_references(&dto);
+        if !problems.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Invalid column references in {}:\n{}",
+                file_path.display(),
+                problems
+                    .iter()
+                    .map(|p| format!("  - {}", p))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+
+        Ok((dto, content))
+    }
+
+    /// カラムの `references` 省略記法を検証
+    ///
+    /// - `<table>.<column>` 形式であること
+    /// - `on_delete` / `on_update` は `references` と組み合わせて指定すること
+    /// - 同じ外部キーが `constraints` にも定義されていないこと
+    fn check_column_references(&self, dto: &SchemaDto) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for (table_name, table) in &dto.tables {
+            for column in &table.columns {
+                let Some(references) = &column.references else {
+                    if column.on_delete.is_some() || column.on_update.is_some() {
+                        problems.push(format!(
+                            "Table '{}', column '{}': on_delete / on_update require references",
+                            table_name, column.name
+                        ));
+                    }
+                    continue;
+                };
+
+                let Some((referenced_table, referenced_column)) =
+                    ColumnDto::parse_references(references)
+                else {
+                    problems.push(format!(
+                        "Table '{}', column '{}': references '{}' must be in the form <table>.<column>",
+                        table_name, column.name, references
+                    ));
+                    continue;
+                };
+
+                let duplicated = table.constraints.iter().any(|constraint| {
+                    matches!(
+                        constraint,
+                        ConstraintDto::FOREIGN_KEY {
+                            columns,
+                            referenced_table: table,
+                            referenced_columns,
+                            ..
+                        } if columns == std::slice::from_ref(&column.name)
+                            && table == referenced_table
+                            && referenced_columns.len() == 1
+                            && referenced_columns[0] == referenced_column
+                    )
+                });
+                if duplicated {
+                    problems.push(format!(
+                        "Table '{}', column '{}': references '{}' duplicates a FOREIGN_KEY constraint on the same column; remove one of them",
+                        table_name, column.name, references
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// 複数ファイルにまたがるテーブル・ENUM・ビューの重複定義を検出
+    ///
+    /// 重複がある場合は両方のファイルパスを含むエラーを返します。
+    /// `allow_duplicate_override` が有効な場合は、採用されたファイルを警告として出力します。
+    fn check_duplicate_definitions(&self, dtos: &[(PathBuf, SchemaDto)]) -> Result<()> {
+        // (重複内容, 採用されるファイル, 位置)
+        let mut duplicates: Vec<(String, &Path, ErrorLocation)> = Vec::new();
+        let mut tables: BTreeMap<&str, &Path> = BTreeMap::new();
+        let mut enums: BTreeMap<&str, &Path> = BTreeMap::new();
+        let mut views: BTreeMap<&str, &Path> = BTreeMap::new();
+
+        for (file_path, dto) in dtos {
+            let definitions = [
+                ("Table", &mut tables, dto.tables.keys().collect::<Vec<_>>()),
+                ("Enum", &mut enums, dto.enums.keys().collect()),
+                ("View", &mut views, dto.views.keys().collect()),
+            ];
+            for (kind, origins, names) in definitions {
+                for name in names {
+                    if let Some(previous) = origins.insert(name, file_path) {
+                        let duplicate = format!(
+                            "{} '{}' is defined in both {} and {}",
+                            kind,
+                            name,
+                            previous.display(),
+                            file_path.display()
+                        );
+                        let location = match kind {
+                            "Enum" => ErrorLocation::with_enum(name),
+                            "View" => ErrorLocation::with_view(name),
+                            _ => ErrorLocation::with_table(name.to_string()),
+                        };
+                        duplicates.push((duplicate, file_path, location.with_file(file_path)));
+                    }
+                }
+            }
+        }
+
+        if duplicates.is_empty() {
+            return Ok(());
+        }
+
+        if !self.allow_duplicate_override {
+            let errors = duplicates
+                .into_iter()
+                .map(|(message, _, location)| ValidationError::Duplicate {
+                    message,
+                    location: Some(location),
+                    suggestion: Some(
+                        "Remove one of the definitions, or pass --allow-duplicate-override to let the later file win"
+                            .to_string(),
+                    ),
+                })
+                .collect();
+            return Err(DuplicateDefinitionsError {
+                errors,
+                schema_files: dtos.iter().map(|(path, _)| path.clone()).collect(),
+            }
+            .into());
+        }
+
+        for (duplicate, winner, _) in &duplicates {
+            let message = format!(
+                "{}; using the definition from {}",
+                duplicate,
+                winner.display()
+            );
+            warn!("{}", message);
+            eprintln!("Warning: {}", message);
+        }
+
+        Ok(())
     }
 
     /// serde_saphyrエラーから行番号を抽出
@@ -226,6 +519,14 @@ impl SchemaParserService {
                 continue;
             }
 
+            // export --include-sequence-values の出力はスキーマ定義ではない
+            if path
+                .file_name()
+                .is_some_and(|name| name == SEQUENCE_VALUES_FILE)
+            {
+                continue;
+            }
+
             // .yaml または .yml 拡張子を持つファイルのみを対象
             if let Some(extension) = path.extension() {
                 if extension == "yaml" || extension == "yml" {
@@ -241,6 +542,90 @@ impl SchemaParserService {
     }
 }
 
+/// 非推奨の `INTEGER` の `precision` 指定を検出し、書き換え方を示すメッセージを返す
+///
+/// 読み込み時に SMALLINT / BIGINT に置き換えるため動作は変わりませんが、
+/// YAMLの更新を促します。スナップショットは次回の generate で書き換わるため対象にしません。
+fn find_deprecated_integer_precision(dtos: &[(PathBuf, SchemaDto)]) -> Vec<String> {
+    let mut messages = Vec::new();
+
+    for (file_path, dto) in dtos {
+        for (table_name, table) in &dto.tables {
+            for column in &table.columns {
+                let ColumnType::INTEGER {
+                    precision: Some(precision),
+                } = column.column_type
+                else {
+                    continue;
+                };
+                let replacement = match column.column_type.normalize_integer_precision() {
+                    Some(ColumnType::INTEGER { .. }) | None => {
+                        "`kind: INTEGER` without `precision`".to_string()
+                    }
+                    Some(column_type) => format!("`kind: {}`", column_type),
+                };
+                messages.push(format!(
+                    "{}: column '{}.{}' uses `kind: INTEGER` with `precision: {}`, which is deprecated. Change it to {}; the generated SQL is the same, so the change does not create a migration.",
+                    file_path.display(),
+                    table_name,
+                    column.name,
+                    precision,
+                    replacement
+                ));
+            }
+        }
+    }
+
+    messages
+}
+
+/// 元のYAMLとDTOを再シリアライズした値を比較し、DTOに存在しないフィールドのパスを集める
+fn collect_unknown_fields(
+    raw: &serde_json::Value,
+    known: &serde_json::Value,
+    path: &str,
+    unknown: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    match (raw, known) {
+        (Value::Object(raw_fields), Value::Object(known_fields)) => {
+            for (key, value) in raw_fields {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match known_fields.get(key) {
+                    Some(known_value) => {
+                        collect_unknown_fields(value, known_value, &child, unknown)
+                    }
+                    None if !is_omitted_value(value) => unknown.push(child),
+                    None => {}
+                }
+            }
+        }
+        (Value::Array(raw_items), Value::Array(known_items)) => {
+            for (i, (value, known_value)) in raw_items.iter().zip(known_items).enumerate() {
+                collect_unknown_fields(value, known_value, &format!("{}[{}]", path, i), unknown);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// シリアライズ時に省略される値（省略時の値と同じ）か
+fn is_omitted_value(value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(items) => items.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        _ => false,
+    }
+}
+
 impl Default for SchemaParserService {
     fn default() -> Self {
         Self::new()
@@ -250,6 +635,7 @@ impl Default for SchemaParserService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::schema::{Constraint, ReferentialAction};
     use tempfile::TempDir;
 
     #[test]
@@ -269,11 +655,12 @@ mod tests {
         fs::write(dir.join("schema2.yml"), "test").unwrap();
         fs::write(dir.join("readme.txt"), "test").unwrap();
         fs::write(dir.join("config.json"), "test").unwrap();
+        fs::write(dir.join(SEQUENCE_VALUES_FILE), "test").unwrap();
 
         let service = SchemaParserService::new();
         let yaml_files = service.scan_yaml_files(dir).unwrap();
 
-        // .yaml と .yml のみが収集されるはず
+        // .yaml と .yml のみが収集されるはず（シーケンス値ファイルを除く）
         assert_eq!(yaml_files.len(), 2);
 
         let file_names: Vec<String> = yaml_files
@@ -736,6 +1123,212 @@ views:
         assert!(schema.has_view("recent_posts"));
     }
 
+    /// 2つのファイルを書き出して解析結果を返す
+    fn parse_two_files(
+        service: &SchemaParserService,
+        file1: &str,
+        file2: &str,
+    ) -> (TempDir, Result<Schema>) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("01_first.yaml"), file1).unwrap();
+        fs::write(temp_dir.path().join("02_second.yaml"), file2).unwrap();
+        let result = service.parse_schema_directory(temp_dir.path());
+        (temp_dir, result)
+    }
+
+    const USERS_TABLE: &str = r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+    primary_key:
+      - id
+"#;
+
+    #[test]
+    fn test_parse_directory_merges_external_tables() {
+        let service = SchemaParserService::new();
+        let file2 = r#"
+version: "1.0"
+external_tables: [external_users, auth_accounts]
+tables: {}
+"#;
+        let (_temp_dir, result) = parse_two_files(&service, USERS_TABLE, file2);
+
+        let schema = result.unwrap();
+        assert!(schema.has_table("users"));
+        assert!(schema.is_external_table("external_users"));
+        assert!(schema.is_external_table("auth_accounts"));
+        assert!(!schema.has_table("external_users"));
+    }
+
+    #[test]
+    fn test_parse_directory_rejects_duplicate_table() {
+        let service = SchemaParserService::new();
+        let (temp_dir, result) = parse_two_files(&service, USERS_TABLE, USERS_TABLE);
+
+        let message = result.unwrap_err().to_string();
+        assert!(
+            message.contains("Table 'users' is defined in both"),
+            "{}",
+            message
+        );
+        assert!(message.contains(&temp_dir.path().join("01_first.yaml").display().to_string()));
+        assert!(message.contains(&temp_dir.path().join("02_second.yaml").display().to_string()));
+        assert!(message.contains("--allow-duplicate-override"));
+    }
+
+    #[test]
+    fn test_duplicate_definitions_error_locates_later_file() {
+        let service = SchemaParserService::new();
+        let (temp_dir, result) = parse_two_files(&service, USERS_TABLE, USERS_TABLE);
+
+        let error = result.unwrap_err();
+        let duplicates = error.downcast_ref::<DuplicateDefinitionsError>().unwrap();
+        assert_eq!(duplicates.schema_files.len(), 2);
+        assert_eq!(duplicates.errors.len(), 1);
+        assert!(duplicates.errors[0].is_duplicate());
+        let location = duplicates.errors[0].location().unwrap();
+        assert_eq!(location.table_name(), Some("users"));
+        assert_eq!(
+            location.file(),
+            Some(temp_dir.path().join("02_second.yaml").as_path())
+        );
+    }
+
+    #[test]
+    fn test_parse_directory_rejects_duplicate_table_in_same_file() {
+        let file = r#"
+version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+  users:
+    columns:
+      - name: id
+        type:
+          kind: TEXT
+        nullable: false
+"#;
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("users.yaml"), file).unwrap();
+
+        let service = SchemaParserService::new();
+        let message = format!(
+            "{:#}",
+            service.parse_schema_directory(temp_dir.path()).unwrap_err()
+        );
+        assert!(message.contains("users.yaml"), "{}", message);
+        assert!(message.contains("duplicate"), "{}", message);
+    }
+
+    #[test]
+    fn test_parse_directory_rejects_duplicate_enum() {
+        let file1 = r#"
+version: "1.0"
+enums:
+  status:
+    name: status
+    values: ["active", "inactive"]
+tables: {}
+"#;
+        let file2 = r#"
+version: "1.0"
+enums:
+  status:
+    name: status
+    values: ["active"]
+tables: {}
+"#;
+
+        let service = SchemaParserService::new();
+        let (_temp_dir, result) = parse_two_files(&service, file1, file2);
+
+        let error = result.unwrap_err();
+        let message = error.to_string();
+        assert!(
+            message.contains("Enum 'status' is defined in both"),
+            "{}",
+            message
+        );
+        let duplicates = error.downcast_ref::<DuplicateDefinitionsError>().unwrap();
+        let location = duplicates.errors[0].location().unwrap();
+        assert_eq!(location.enum_name(), Some("status"));
+    }
+
+    #[test]
+    fn test_parse_directory_rejects_duplicate_view() {
+        let view = r#"
+version: "1.0"
+tables: {}
+views:
+  active_users:
+    name: active_users
+    definition: "SELECT * FROM users"
+"#;
+
+        let service = SchemaParserService::new();
+        let (_temp_dir, result) = parse_two_files(&service, view, view);
+
+        let message = result.unwrap_err().to_string();
+        assert!(
+            message.contains("View 'active_users' is defined in both"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_parse_directory_allow_duplicate_override_uses_later_file() {
+        let file2 = USERS_TABLE.replace("kind: INTEGER", "kind: TEXT");
+
+        let service = SchemaParserService::new().with_allow_duplicate_override(true);
+        let (_temp_dir, result) = parse_two_files(&service, USERS_TABLE, &file2);
+
+        let schema = result.unwrap();
+        let users = schema.get_table("users").unwrap();
+        assert_eq!(
+            users.get_column("id").unwrap().column_type,
+            crate::core::schema::ColumnType::TEXT
+        );
+    }
+
+    #[test]
+    fn test_parse_directory_records_source_lines_outside_schema() {
+        let file2 = format!("\n\n{}", USERS_TABLE);
+
+        let service = SchemaParserService::new().with_allow_duplicate_override(true);
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("01_first.yaml"), USERS_TABLE).unwrap();
+        fs::write(temp_dir.path().join("02_second.yaml"), &file2).unwrap();
+        let (schema, _, sources) = service
+            .parse_schema_directory_with_sources(temp_dir.path())
+            .unwrap();
+
+        // 後から読み込んだファイルの行を記録する
+        let sources = sources.relative_to(temp_dir.path());
+        assert_eq!(sources.table_line("users"), Some(6));
+        assert_eq!(sources.column_line("users", "id"), Some(8));
+        assert_eq!(
+            sources.column_source("users", "id").as_deref(),
+            Some("02_second.yaml:8")
+        );
+
+        // 定義行はスキーマに含まれない（行がずれても同じスキーマになる）
+        let first = service
+            .parse_schema_file(&temp_dir.path().join("01_first.yaml"))
+            .unwrap();
+        assert_eq!(schema, first);
+    }
+
     #[test]
     fn test_extract_line_from_error_format() {
         let service = SchemaParserService::new();
@@ -761,4 +1354,217 @@ views:
             error_msg
         );
     }
+
+    #[test]
+    fn test_parse_column_references_shorthand() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_file = temp_dir.path().join("schema.yaml");
+        let content = r#"
+version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+        references: users.id
+        on_delete: CASCADE
+    primary_key:
+      - id
+"#;
+        fs::write(&schema_file, content).unwrap();
+
+        let service = SchemaParserService::new();
+        let schema = service.parse_schema_file(&schema_file).unwrap();
+
+        let posts = schema.get_table("posts").unwrap();
+        assert!(posts.constraints.contains(&Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: None,
+        }));
+    }
+
+    fn parse_posts_with_user_id(user_id_extra: &str, constraints: &str) -> String {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_file = temp_dir.path().join("schema.yaml");
+        let content = format!(
+            r#"
+version: "1.0"
+tables:
+  posts:
+    columns:
+      - name: user_id
+        type:
+          kind: INTEGER
+        nullable: false
+{}{}"#,
+            user_id_extra, constraints
+        );
+        fs::write(&schema_file, content).unwrap();
+
+        SchemaParserService::new()
+            .parse_schema_file(&schema_file)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_parse_rejects_references_duplicating_foreign_key_constraint() {
+        let error_msg = parse_posts_with_user_id(
+            "        references: users.id\n",
+            r#"    constraints:
+      - type: FOREIGN_KEY
+        columns:
+          - user_id
+        referenced_table: users
+        referenced_columns:
+          - id
+"#,
+        );
+
+        assert!(
+            error_msg.contains("Invalid column references")
+                && error_msg.contains("duplicates a FOREIGN_KEY constraint"),
+            "{}",
+            error_msg
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_references() {
+        let error_msg = parse_posts_with_user_id("        references: users\n", "");
+
+        assert!(
+            error_msg.contains("must be in the form <table>.<column>"),
+            "{}",
+            error_msg
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_on_delete_without_references() {
+        let error_msg = parse_posts_with_user_id("        on_delete: CASCADE\n", "");
+
+        assert!(
+            error_msg.contains("on_delete / on_update require references"),
+            "{}",
+            error_msg
+        );
+    }
+
+    #[test]
+    fn test_find_deprecated_integer_precision() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("counters.yaml");
+        fs::write(
+            &path,
+            r#"version: "1.0"
+tables:
+  counters:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: small
+        type:
+          kind: INTEGER
+          precision: 2
+      - name: total
+        type:
+          kind: INTEGER
+          precision: 8
+      - name: odd
+        type:
+          kind: INTEGER
+          precision: 16
+      - name: explicit
+        type:
+          kind: BIGINT
+    primary_key: [id]
+"#,
+        )
+        .unwrap();
+
+        let service = SchemaParserService::new();
+        let dto = service.parse_schema_dto(&path).unwrap();
+        let messages = find_deprecated_integer_precision(&[(path.clone(), dto)]);
+
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("'counters.small'"));
+        assert!(messages[0].contains("`kind: SMALLINT`"));
+        assert!(messages[1].contains("'counters.total'"));
+        assert!(messages[1].contains("`precision: 8`"));
+        assert!(messages[1].contains("`kind: BIGINT`"));
+        assert!(messages[2].contains("'counters.odd'"));
+        assert!(messages[2].contains("`kind: INTEGER` without `precision`"));
+
+        // 読み込んだスキーマでは明示的な型に置き換わっている
+        let schema = service.parse_schema_directory(temp_dir.path()).unwrap();
+        let table = schema.get_table("counters").unwrap();
+        assert_eq!(
+            table.get_column("small").unwrap().column_type,
+            ColumnType::SMALLINT
+        );
+        assert_eq!(
+            table.get_column("total").unwrap().column_type,
+            ColumnType::BIGINT
+        );
+        assert_eq!(
+            table.get_column("odd").unwrap().column_type,
+            ColumnType::INTEGER { precision: None }
+        );
+    }
+
+    #[test]
+    fn test_find_unknown_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("users.yaml"),
+            r#"version: "1.0"
+generated_by: strata 9.9
+enum_recreate_allowed: false
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+        nullable: false
+        collation: C
+      - name: email
+        type:
+          kind: VARCHAR
+          length: 255
+        unused_flag: false
+    primary_key: [id]
+    indexes: []
+    partitioning:
+      by: id
+"#,
+        )
+        .unwrap();
+
+        let service = SchemaParserService::new();
+        let unknown = service.find_unknown_fields(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            unknown,
+            vec![
+                "users.yaml: generated_by",
+                "users.yaml: tables.users.columns[0].collation",
+                "users.yaml: tables.users.partitioning",
+            ]
+        );
+        // 無視されるフィールドがあってもスキーマとして読み込める
+        let schema = service.parse_schema_directory(temp_dir.path()).unwrap();
+        assert_eq!(schema.get_table("users").unwrap().columns.len(), 2);
+    }
 }
@@ -157,6 +157,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let service = SchemaSerializerService::new();
@@ -250,10 +251,78 @@ mod tests {
         let service = SchemaSerializerService::new();
         let yaml = service.serialize_to_string(&schema).unwrap();
 
-        // FOREIGN_KEYはconstraintsに出力される
+        // 単一カラムのFOREIGN_KEYはカラムの references として出力される
+        assert!(yaml.contains("references: users.id"));
+        assert!(!yaml.contains("constraints:"));
+        assert!(!yaml.contains("type: FOREIGN_KEY"));
+    }
+
+    #[test]
+    fn test_serialize_composite_foreign_key_constraint() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("order_items".to_string());
+        for name in ["order_id", "tenant_id"] {
+            table.add_column(Column::new(
+                name.to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+        }
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["order_id".to_string(), "tenant_id".to_string()],
+            referenced_table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string(), "tenant_id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        schema.add_table(table);
+
+        let yaml = SchemaSerializerService::new()
+            .serialize_to_string(&schema)
+            .unwrap();
+
+        // 複数カラムのFOREIGN_KEYはconstraintsに出力される
         assert!(yaml.contains("constraints:"));
         assert!(yaml.contains("type: FOREIGN_KEY"));
+        assert!(yaml.contains("referenced_table: orders"));
+        assert!(!yaml.contains("references:"));
+    }
+
+    #[test]
+    fn test_serialize_foreign_key_keeps_constraint_order() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("posts".to_string());
+        for name in ["user_id", "category_id"] {
+            table.add_column(Column::new(
+                name.to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+        }
+        let foreign_key = |column: &str, referenced_table: &str| Constraint::FOREIGN_KEY {
+            columns: vec![column.to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        };
+        // UNIQUEより前の外部キーと、カラム順に並んでいない外部キーは明示的な制約のまま
+        table.add_constraint(foreign_key("user_id", "users"));
+        table.add_constraint(Constraint::UNIQUE {
+            columns: vec!["user_id".to_string()],
+        });
+        table.add_constraint(foreign_key("category_id", "categories"));
+        table.add_constraint(foreign_key("user_id", "authors"));
+        schema.add_table(table);
+
+        let yaml = SchemaSerializerService::new()
+            .serialize_to_string(&schema)
+            .unwrap();
+
         assert!(yaml.contains("referenced_table: users"));
+        assert!(yaml.contains("referenced_table: categories"));
+        assert!(yaml.contains("references: authors.id"));
+        assert_eq!(yaml.matches("type: FOREIGN_KEY").count(), 2);
     }
 
     #[test]
@@ -439,6 +508,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         // usersテーブル
@@ -0,0 +1,224 @@
+// スキーマスナップショットの読み書き
+//
+// generate が前回のスキーマ状態として使う `.schema_snapshot.yaml` を扱います。
+// - 書き込みは同じディレクトリの一時ファイルに書いてからリネームするため、
+//   中断しても古い内容か新しい内容のどちらかが必ず残る
+// - 先頭に `snapshot_format` を記録し、読み込み時にこのバージョンが知らない形式を拒否する
+//   （記録のない古いスナップショットは形式 1 として読み込む）
+// - 解析に失敗した場合は、スナップショットを作り直す方法をエラーに含める
+
+use crate::core::error::IoError;
+use crate::core::schema::Schema;
+use crate::services::schema_io::schema_parser::SchemaParserService;
+use crate::services::schema_io::schema_serializer::SchemaSerializerService;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// スキーマスナップショットのファイル名
+pub const SNAPSHOT_FILE: &str = ".schema_snapshot.yaml";
+
+/// このバージョンが書き込むスナップショットの形式
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// スナップショットの読み込みエラー
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    /// このバージョンより新しい strata が書き込んだ形式
+    #[error("Schema snapshot {path} was written by a newer strata (snapshot_format: {format}, supported: {SNAPSHOT_FORMAT}). Please upgrade strata.")]
+    UnsupportedFormat {
+        /// スナップショットのパス
+        path: String,
+        /// 記録されていた形式
+        format: u32,
+    },
+
+    /// スナップショットを解析できない（書き込みの中断で途中までしかない場合など）
+    #[error("Failed to parse schema snapshot {path}: {cause}\nThe file may have been truncated by an interrupted command. Restore it from version control, or rebuild it from the database with `strata export` followed by `strata baseline --force`.")]
+    Corrupt {
+        /// スナップショットのパス
+        path: String,
+        /// 解析エラー
+        cause: String,
+    },
+}
+
+/// 形式の確認のために先頭のキーだけを読み込む
+#[derive(Debug, Deserialize)]
+struct SnapshotHeader {
+    #[serde(default)]
+    snapshot_format: Option<u32>,
+}
+
+/// スキーマをスナップショットの内容に変換（先頭に `snapshot_format` を付ける）
+pub fn render_snapshot(schema: &Schema) -> Result<String> {
+    let yaml = SchemaSerializerService::new()
+        .serialize_to_string(schema)
+        .with_context(|| "Failed to serialize schema snapshot")?;
+    Ok(format!("snapshot_format: {}\n{}", SNAPSHOT_FORMAT, yaml))
+}
+
+/// スキーマをスナップショットとして書き込む
+pub fn write_snapshot(path: &Path, schema: &Schema) -> Result<()> {
+    write_snapshot_content(path, &render_snapshot(schema)?)
+}
+
+/// `render_snapshot` で作成した内容をアトミックに書き込む
+///
+/// 同じディレクトリの一時ファイルに書き込んで同期してから置き換えるため、
+/// 途中で中断しても既存のスナップショットが壊れることはありません。
+pub fn write_snapshot_content(path: &Path, content: &str) -> Result<()> {
+    let temp_path = temp_path(path);
+    let result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(IoError::FileWrite {
+            path: path.display().to_string(),
+            cause: e.to_string(),
+        })
+        .with_context(|| format!("Failed to write schema snapshot: {:?}", path));
+    }
+    Ok(())
+}
+
+/// スナップショットを読み込む
+///
+/// 新しい strata が書き込んだ形式の場合は `SnapshotError::UnsupportedFormat`、
+/// 解析できない場合は `SnapshotError::Corrupt` を返します。
+pub fn read_snapshot(path: &Path) -> Result<Schema> {
+    let content = fs::read_to_string(path).map_err(|e| IoError::FileRead {
+        path: path.display().to_string(),
+        cause: e.to_string(),
+    })?;
+    let corrupt = |cause: String| SnapshotError::Corrupt {
+        path: path.display().to_string(),
+        cause,
+    };
+
+    // スキーマとして解析する前に形式を確認する（将来の形式は解析できるとは限らない）
+    let header: SnapshotHeader =
+        serde_saphyr::from_str(&content).map_err(|e| corrupt(e.to_string()))?;
+    if let Some(format) = header.snapshot_format.filter(|f| *f > SNAPSHOT_FORMAT) {
+        return Err(SnapshotError::UnsupportedFormat {
+            path: path.display().to_string(),
+            format,
+        }
+        .into());
+    }
+
+    let files = [(path.to_path_buf(), content)].into_iter().collect();
+    SchemaParserService::new()
+        .with_file_contents(files)
+        .parse_schema_file(path)
+        .map_err(|e| corrupt(format!("{:#}", e)).into())
+}
+
+/// 書き込み途中のスナップショットの一時ファイルのパス
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| SNAPSHOT_FILE.to_string());
+    path.with_file_name(format!("{}.tmp", file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+    use tempfile::TempDir;
+
+    fn users_schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_write_and_read_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+
+        write_snapshot(&path, &users_schema()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("snapshot_format: 1\n"), "{}", content);
+        assert!(!temp_path(&path).exists());
+        let schema = read_snapshot(&path).unwrap();
+        assert!(schema.tables.contains_key("users"));
+    }
+
+    #[test]
+    fn test_write_snapshot_replaces_existing_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+        write_snapshot(&path, &Schema::new("1.0".to_string())).unwrap();
+
+        write_snapshot(&path, &users_schema()).unwrap();
+
+        assert!(read_snapshot(&path).unwrap().tables.contains_key("users"));
+    }
+
+    #[test]
+    fn test_read_snapshot_without_format_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+        fs::write(
+            &path,
+            "version: \"1.0\"\ntables:\n  users:\n    columns:\n      - name: id\n        type:\n          kind: INTEGER\n        nullable: false\n",
+        )
+        .unwrap();
+
+        assert!(read_snapshot(&path).unwrap().tables.contains_key("users"));
+    }
+
+    #[test]
+    fn test_read_snapshot_rejects_newer_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+        fs::write(
+            &path,
+            "snapshot_format: 2\nversion: \"1.0\"\ntables:\n  users:\n    shape: unknown\n",
+        )
+        .unwrap();
+
+        let error = read_snapshot(&path).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<SnapshotError>(),
+            Some(SnapshotError::UnsupportedFormat { format: 2, .. })
+        ));
+        assert!(error.to_string().contains("Please upgrade strata"));
+    }
+
+    #[test]
+    fn test_read_truncated_snapshot_suggests_recovery() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SNAPSHOT_FILE);
+        let content = render_snapshot(&users_schema()).unwrap();
+        let truncated = &content[..content.find("type:").unwrap() + 3];
+        fs::write(&path, truncated).unwrap();
+
+        let error = read_snapshot(&path).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<SnapshotError>(),
+            Some(SnapshotError::Corrupt { .. })
+        ));
+        let message = error.to_string();
+        assert!(message.contains("strata export"), "{}", message);
+        assert!(message.contains("strata baseline --force"), "{}", message);
+    }
+}
@@ -0,0 +1,521 @@
+// スキーマ要素の定義元ファイル
+//
+// ディレクトリから読み込んだスキーマについて、テーブル・ENUM・ビューが
+// どのファイルで定義されているか（と、分かる場合はその行番号）を保持します。
+// 検証結果の位置情報へのファイルパス付与、generate のサマリと破壊的変更の一覧への
+// 定義元の表示、変更ファイルに関係する要素の絞り込み（`validate --changed-since`）に使用します。
+// スキーマ本体とは別に保持するため、スナップショットや差分検出には含まれません。
+
+use crate::core::config::IdentifierCase;
+use crate::core::error::{ErrorLocation, ValidationResult};
+use crate::core::schema::{ColumnType, Constraint, Schema};
+use crate::services::schema_io::source_lines::{FileLines, TableLines};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// スキーマ要素の定義元ファイル
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaSources {
+    tables: HashMap<String, PathBuf>,
+    enums: HashMap<String, PathBuf>,
+    views: HashMap<String, PathBuf>,
+    /// 定義行（行番号を取得できた要素のみ）
+    lines: FileLines,
+}
+
+impl SchemaSources {
+    /// 空のSchemaSourcesを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 1ファイル分のスキーマの定義元を記録
+    ///
+    /// 同名の要素が既に記録されている場合は後から記録したファイルで上書きする
+    /// （`--allow-duplicate-override` のマージ順と同じ）。
+    pub fn record(&mut self, file: &Path, schema: &Schema) {
+        for name in schema.tables.keys() {
+            self.tables.insert(name.clone(), file.to_path_buf());
+        }
+        for name in schema.enums.keys() {
+            self.enums.insert(name.clone(), file.to_path_buf());
+        }
+        for name in schema.views.keys() {
+            self.views.insert(name.clone(), file.to_path_buf());
+        }
+    }
+
+    /// 1ファイル分のスキーマ要素の定義行を記録
+    ///
+    /// `record` と同じく、同名の要素は後から記録したファイルの行で上書きする。
+    pub fn record_lines(&mut self, lines: FileLines) {
+        self.lines.tables.extend(lines.tables);
+        self.lines.enums.extend(lines.enums);
+        self.lines.views.extend(lines.views);
+    }
+
+    /// 定義元ファイルのパスを `base` からの相対パスに変換
+    ///
+    /// `base` 配下にないパスはそのまま残す。
+    pub fn relative_to(&self, base: &Path) -> Self {
+        let relative = |files: &HashMap<String, PathBuf>| {
+            files
+                .iter()
+                .map(|(name, file)| {
+                    let file = file.strip_prefix(base).unwrap_or(file).to_path_buf();
+                    (name.clone(), file)
+                })
+                .collect()
+        };
+        Self {
+            tables: relative(&self.tables),
+            enums: relative(&self.enums),
+            views: relative(&self.views),
+            lines: self.lines.clone(),
+        }
+    }
+
+    /// テーブル名を identifier_case に従って変換
+    ///
+    /// 生成SQL（変換後の名前）から定義元ファイルを引けるようにします。
+    pub fn with_identifier_case(&self, case: IdentifierCase) -> Self {
+        Self {
+            tables: self
+                .tables
+                .iter()
+                .map(|(name, file)| (case.apply(name), file.clone()))
+                .collect(),
+            enums: self.enums.clone(),
+            views: self.views.clone(),
+            lines: FileLines {
+                tables: self
+                    .lines
+                    .tables
+                    .iter()
+                    .map(|(name, lines)| (case.apply(name), fold_table_lines(lines, case)))
+                    .collect(),
+                ..self.lines.clone()
+            },
+        }
+    }
+
+    /// テーブルの定義元ファイル
+    pub fn table_file(&self, name: &str) -> Option<&Path> {
+        self.tables.get(name).map(PathBuf::as_path)
+    }
+
+    /// ENUMの定義元ファイル
+    pub fn enum_file(&self, name: &str) -> Option<&Path> {
+        self.enums.get(name).map(PathBuf::as_path)
+    }
+
+    /// ビューの定義元ファイル
+    pub fn view_file(&self, name: &str) -> Option<&Path> {
+        self.views.get(name).map(PathBuf::as_path)
+    }
+
+    /// テーブルの定義行
+    pub fn table_line(&self, name: &str) -> Option<usize> {
+        self.table_lines(name).map(|lines| lines.line)
+    }
+
+    /// カラムの定義行
+    pub fn column_line(&self, table: &str, column: &str) -> Option<usize> {
+        self.table_lines(table)?.columns.get(column).copied()
+    }
+
+    /// インデックスの定義行
+    pub fn index_line(&self, table: &str, index: &str) -> Option<usize> {
+        self.table_lines(table)?.indexes.get(index).copied()
+    }
+
+    /// 制約の定義行
+    pub fn constraint_line(&self, table: &str, constraint: &Constraint) -> Option<usize> {
+        self.table_lines(table)?.constraint_line(constraint)
+    }
+
+    /// ENUMの定義行
+    pub fn enum_line(&self, name: &str) -> Option<usize> {
+        self.lines.enums.get(name).copied()
+    }
+
+    /// ビューの定義行
+    pub fn view_line(&self, name: &str) -> Option<usize> {
+        self.lines.views.get(name).copied()
+    }
+
+    fn table_lines(&self, name: &str) -> Option<&TableLines> {
+        self.lines.tables.get(name)
+    }
+
+    /// テーブルの定義元（`schema/orders.yaml:3` 形式、行が不明な場合はファイルのみ）
+    pub fn table_source(&self, name: &str) -> Option<String> {
+        describe(self.table_file(name)?, self.table_line(name))
+    }
+
+    /// カラムの定義元（カラムの行が不明な場合はテーブルの行）
+    pub fn column_source(&self, table: &str, column: &str) -> Option<String> {
+        let line = self
+            .column_line(table, column)
+            .or_else(|| self.table_line(table));
+        describe(self.table_file(table)?, line)
+    }
+
+    /// インデックスの定義元（インデックスの行が不明な場合はテーブルの行）
+    pub fn index_source(&self, table: &str, index: &str) -> Option<String> {
+        let line = self
+            .index_line(table, index)
+            .or_else(|| self.table_line(table));
+        describe(self.table_file(table)?, line)
+    }
+
+    /// 制約の定義元（制約の行が不明な場合はテーブルの行）
+    pub fn constraint_source(&self, table: &str, constraint: &Constraint) -> Option<String> {
+        let line = self
+            .constraint_line(table, constraint)
+            .or_else(|| self.table_line(table));
+        describe(self.table_file(table)?, line)
+    }
+
+    /// ENUMの定義元
+    pub fn enum_source(&self, name: &str) -> Option<String> {
+        describe(self.enum_file(name)?, self.enum_line(name))
+    }
+
+    /// ビューの定義元
+    pub fn view_source(&self, name: &str) -> Option<String> {
+        describe(self.view_file(name)?, self.view_line(name))
+    }
+
+    /// 位置情報が指す要素の定義行
+    ///
+    /// カラムを指す位置情報でカラムの行が不明な場合は、テーブルの行を返す。
+    pub fn line_for_location(&self, location: &ErrorLocation) -> Option<usize> {
+        if let Some(view) = location.view_name() {
+            self.view_line(view)
+        } else if let Some(enum_name) = location.enum_name() {
+            self.enum_line(enum_name)
+        } else {
+            let table = location.table_name()?;
+            location
+                .column
+                .as_deref()
+                .and_then(|column| self.column_line(table, column))
+                .or_else(|| self.table_line(table))
+        }
+    }
+
+    /// 位置情報が指す要素の定義元ファイル
+    pub fn file_for_location(&self, location: &ErrorLocation) -> Option<&Path> {
+        if let Some(view) = location.view_name() {
+            self.view_file(view)
+        } else if let Some(enum_name) = location.enum_name() {
+            self.enum_file(enum_name)
+        } else {
+            self.table_file(location.table_name()?)
+        }
+    }
+
+    /// 検証結果の位置情報に定義元ファイルと定義行を設定
+    ///
+    /// 既にファイルが設定されている位置情報は変更しない。
+    pub fn attach_files(&self, result: &mut ValidationResult) {
+        let locations = result
+            .errors
+            .iter_mut()
+            .filter_map(|error| error.location_mut())
+            .chain(
+                result
+                    .warnings
+                    .iter_mut()
+                    .filter_map(|warning| warning.location.as_mut()),
+            );
+        for location in locations {
+            if location.file.is_none() {
+                location.file = self.file_for_location(location).map(Path::to_path_buf);
+                if location.file.is_some() && location.line.is_none() {
+                    location.line = self.line_for_location(location);
+                }
+            }
+        }
+    }
+
+    /// 変更されたファイルの影響を受ける要素を収集
+    ///
+    /// 変更ファイルで定義された要素に加え、それらを参照する要素
+    /// （外部キーの参照元テーブル、ENUMを使うテーブル、依存するビュー）も含める。
+    /// どのファイルにも定義されていない要素への参照は、変更ファイルから
+    /// 削除された可能性があるため影響ありとみなす。
+    ///
+    /// # Returns
+    ///
+    /// 影響を受ける要素の位置キー（`ErrorLocation::table` と同じ形式）
+    pub fn affected_by(
+        &self,
+        schema: &Schema,
+        changed_files: &HashSet<PathBuf>,
+    ) -> HashSet<String> {
+        let changed = |file: Option<&Path>| file.is_none_or(|f| changed_files.contains(f));
+        let mut affected = HashSet::new();
+
+        for (name, file) in &self.enums {
+            if changed_files.contains(file) {
+                affected.insert(format!("{}{}", ErrorLocation::ENUM_PREFIX, name));
+            }
+        }
+
+        for (table_name, table) in &schema.tables {
+            let references_changed = table.constraints.iter().any(|constraint| {
+                matches!(constraint, Constraint::FOREIGN_KEY { referenced_table, .. }
+                    if changed(self.table_file(referenced_table)))
+            }) || table.columns.iter().any(|column| {
+                matches!(&column.column_type, ColumnType::Enum { name }
+                    if changed(self.enum_file(name)))
+            });
+
+            if references_changed || changed(self.table_file(table_name)) {
+                affected.insert(table_name.clone());
+            }
+        }
+
+        for (view_name, view) in &schema.views {
+            let depends_on_changed = view.depends_on.iter().any(|dependency| {
+                changed(
+                    self.table_file(dependency)
+                        .or_else(|| self.view_file(dependency)),
+                )
+            });
+
+            if depends_on_changed || changed(self.view_file(view_name)) {
+                affected.insert(format!("{}{}", ErrorLocation::VIEW_PREFIX, view_name));
+            }
+        }
+
+        affected
+    }
+}
+
+/// 定義元を `ファイル:行` 形式で表す
+fn describe(file: &Path, line: Option<usize>) -> Option<String> {
+    Some(match line {
+        Some(line) => format!("{}:{}", file.display(), line),
+        None => file.display().to_string(),
+    })
+}
+
+/// テーブル内の要素名を identifier_case に従って変換
+fn fold_table_lines(lines: &TableLines, case: IdentifierCase) -> TableLines {
+    let fold_keys = |map: &HashMap<String, usize>| {
+        map.iter()
+            .map(|(name, line)| (case.apply(name), *line))
+            .collect()
+    };
+    let mut folded = lines.clone();
+    folded.columns = fold_keys(&lines.columns);
+    folded.indexes = fold_keys(&lines.indexes);
+    for constraint in &mut folded.constraints {
+        constraint.columns = constraint.columns.iter().map(|c| case.apply(c)).collect();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::ValidationError;
+    use crate::core::schema::{Column, EnumDefinition, Table, View};
+
+    fn table_with_fk(name: &str, referenced_table: &str) -> Table {
+        let mut table = Table::new(name.to_string());
+        table.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        table
+    }
+
+    /// users.yaml / posts.yaml / status.yaml / views.yaml の4ファイル構成
+    fn sample() -> (Schema, SchemaSources) {
+        let mut sources = SchemaSources::new();
+        let mut merged = Schema::new("1.0".to_string());
+
+        let mut users = Schema::new("1.0".to_string());
+        users.add_table(Table::new("users".to_string()));
+        sources.record(Path::new("users.yaml"), &users);
+
+        let mut posts = Schema::new("1.0".to_string());
+        posts.add_table(table_with_fk("posts", "users"));
+        let mut comments = table_with_fk("comments", "deleted_table");
+        comments.add_column(Column::new(
+            "state".to_string(),
+            ColumnType::Enum {
+                name: "status".to_string(),
+            },
+            false,
+        ));
+        posts.add_table(comments);
+        sources.record(Path::new("posts.yaml"), &posts);
+
+        let mut status = Schema::new("1.0".to_string());
+        status.add_enum(EnumDefinition {
+            name: "status".to_string(),
+            values: vec!["open".to_string()],
+            renamed_from: None,
+        });
+        sources.record(Path::new("status.yaml"), &status);
+
+        let mut views = Schema::new("1.0".to_string());
+        let mut view = View::new("active_users".to_string(), "SELECT 1".to_string());
+        view.depends_on = vec!["users".to_string()];
+        views.add_view(view);
+        sources.record(Path::new("views.yaml"), &views);
+
+        for schema in [users, posts, status, views] {
+            merged.tables.extend(schema.tables);
+            merged.enums.extend(schema.enums);
+            merged.views.extend(schema.views);
+        }
+        (merged, sources)
+    }
+
+    #[test]
+    fn test_file_for_location() {
+        let (_, sources) = sample();
+
+        assert_eq!(
+            sources.file_for_location(&ErrorLocation::with_table("posts".to_string())),
+            Some(Path::new("posts.yaml"))
+        );
+        assert_eq!(
+            sources.file_for_location(&ErrorLocation::with_enum("status")),
+            Some(Path::new("status.yaml"))
+        );
+        assert_eq!(
+            sources.file_for_location(&ErrorLocation::with_view("active_users")),
+            Some(Path::new("views.yaml"))
+        );
+        assert_eq!(
+            sources.file_for_location(&ErrorLocation::with_table("missing".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attach_files() {
+        let (_, sources) = sample();
+        let mut result = ValidationResult::new();
+        result.add_error(ValidationError::Reference {
+            message: "broken".to_string(),
+            location: Some(ErrorLocation::with_table_and_column("posts", "user_id")),
+            suggestion: None,
+        });
+        result.add_error(ValidationError::Constraint {
+            message: "explicit".to_string(),
+            location: Some(ErrorLocation::with_enum("status").with_file("other.yaml")),
+            suggestion: None,
+        });
+
+        sources.attach_files(&mut result);
+
+        assert_eq!(
+            result.errors[0].location().unwrap().file(),
+            Some(Path::new("posts.yaml"))
+        );
+        assert_eq!(
+            result.errors[1].location().unwrap().file(),
+            Some(Path::new("other.yaml"))
+        );
+    }
+
+    #[test]
+    fn test_attach_files_sets_lines() {
+        let (_, mut sources) = sample();
+        sources.record_lines(crate::services::schema_io::source_lines::scan(
+            "tables:\n  posts:\n    columns:\n      - name: id\n      - name: user_id\n",
+        ));
+        let mut result = ValidationResult::new();
+        result.add_error(ValidationError::Reference {
+            message: "broken".to_string(),
+            location: Some(ErrorLocation::with_table_and_column("posts", "user_id")),
+            suggestion: None,
+        });
+        result.add_error(ValidationError::Reference {
+            message: "unknown column".to_string(),
+            location: Some(ErrorLocation::with_table_and_column("posts", "missing")),
+            suggestion: None,
+        });
+
+        sources.attach_files(&mut result);
+
+        let location = result.errors[0].location().unwrap();
+        assert_eq!(location.line, Some(5));
+        assert_eq!(
+            location.format(),
+            " (file: posts.yaml, table: posts, column: user_id, line: 5)"
+        );
+        // カラムの行が分からない場合はテーブルの行
+        assert_eq!(result.errors[1].location().unwrap().line, Some(2));
+    }
+
+    #[test]
+    fn test_sources_follow_identifier_case() {
+        let (_, mut sources) = sample();
+        sources.record_lines(crate::services::schema_io::source_lines::scan(
+            "tables:\n  Posts:\n    columns:\n      - name: UserId\n",
+        ));
+        sources.record(Path::new("posts.yaml"), &{
+            let mut schema = Schema::new("1.0".to_string());
+            schema.add_table(Table::new("Posts".to_string()));
+            schema
+        });
+
+        let folded = sources.with_identifier_case(IdentifierCase::Lower);
+
+        assert_eq!(
+            folded.column_source("posts", "userid").as_deref(),
+            Some("posts.yaml:4")
+        );
+        assert_eq!(folded.table_source("users").as_deref(), Some("users.yaml"));
+    }
+
+    #[test]
+    fn test_affected_by_includes_cross_file_references() {
+        let (schema, sources) = sample();
+
+        // users.yaml の変更は posts の外部キーと active_users ビューにも影響する
+        let changed = HashSet::from([PathBuf::from("users.yaml")]);
+        let affected = sources.affected_by(&schema, &changed);
+        assert!(affected.contains("users"));
+        assert!(affected.contains("posts"));
+        assert!(affected.contains("view:active_users"));
+        assert!(!affected.contains("enum:status"));
+
+        // ENUMの変更はそれを使うテーブルに影響する
+        let changed = HashSet::from([PathBuf::from("status.yaml")]);
+        let affected = sources.affected_by(&schema, &changed);
+        assert!(affected.contains("enum:status"));
+        assert!(affected.contains("comments"));
+        assert!(!affected.contains("users"));
+        assert!(!affected.contains("posts"));
+    }
+
+    #[test]
+    fn test_affected_by_treats_undefined_reference_as_changed() {
+        let (schema, sources) = sample();
+
+        // comments は どこにも定義されていない deleted_table を参照している
+        let changed = HashSet::from([PathBuf::from("views.yaml")]);
+        let affected = sources.affected_by(&schema, &changed);
+        assert!(affected.contains("comments"));
+        assert!(affected.contains("view:active_users"));
+        assert!(!affected.contains("posts"));
+    }
+}
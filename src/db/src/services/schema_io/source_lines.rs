@@ -0,0 +1,309 @@
+// スキーマ要素の定義行
+//
+// スキーマファイルのYAMLを位置情報付き（serde_saphyr::Spanned）で読み直し、
+// テーブル・カラム・インデックス・制約・ENUM・ビューが定義されている行番号を取得します。
+// 位置情報はスキーマ本体とは別に保持するため、スナップショットや差分検出には影響しません。
+
+use crate::core::schema::{normalize_check_expression, qualify_table_name, Constraint};
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_saphyr::Spanned;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// 1ファイル分のスキーマ要素の定義行（1始まり）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileLines {
+    pub(crate) tables: HashMap<String, TableLines>,
+    pub(crate) enums: HashMap<String, usize>,
+    pub(crate) views: HashMap<String, usize>,
+}
+
+/// テーブルとその要素の定義行
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableLines {
+    /// テーブル名（YAMLのキー）の行
+    pub line: usize,
+    /// カラム名 -> 行
+    pub columns: HashMap<String, usize>,
+    /// インデックス名 -> 行
+    pub indexes: HashMap<String, usize>,
+    /// 制約（名前がないため内容で照合する）
+    pub constraints: Vec<ConstraintLine>,
+}
+
+/// 制約の定義行
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintLine {
+    /// 制約の種類（`Constraint::kind` と同じ表記）
+    pub kind: String,
+    /// 対象カラム
+    pub columns: Vec<String>,
+    /// CHECK制約の式
+    pub check_expression: Option<String>,
+    /// 定義行
+    pub line: usize,
+}
+
+impl ConstraintLine {
+    /// この行が指定した制約の定義か
+    ///
+    /// CHECK制約は差分検出と同じく正規化した式で照合します。
+    pub fn matches(&self, constraint: &Constraint) -> bool {
+        if self.kind != constraint.kind() {
+            return false;
+        }
+        match constraint {
+            Constraint::CHECK {
+                check_expression, ..
+            } => self.check_expression.as_deref().is_some_and(|expression| {
+                normalize_check_expression(expression)
+                    == normalize_check_expression(check_expression)
+            }),
+            _ => self.columns == constraint.columns(),
+        }
+    }
+}
+
+impl TableLines {
+    /// 制約の定義行
+    pub fn constraint_line(&self, constraint: &Constraint) -> Option<usize> {
+        self.constraints
+            .iter()
+            .find(|entry| entry.matches(constraint))
+            .map(|entry| entry.line)
+    }
+}
+
+/// YAMLからスキーマ要素の定義行を取得
+///
+/// 行番号は補助的な情報のため、位置情報付きで読み込めない場合は空の結果を返します
+/// （スキーマとしての妥当性は通常の解析で検証済み）。
+pub fn scan(content: &str) -> FileLines {
+    let Ok(document) = serde_saphyr::from_str::<SpannedSchema>(content) else {
+        return FileLines::default();
+    };
+
+    let tables = document
+        .tables
+        .0
+        .into_iter()
+        .map(|(key, table)| {
+            let name = match &table.schema {
+                Some(schema) => qualify_table_name(schema, &key.value),
+                None => key.value.clone(),
+            };
+            (name, table.into_lines(line_of(&key)))
+        })
+        .collect();
+    let named_lines = |entries: Entries<IgnoredAny>| {
+        entries
+            .0
+            .into_iter()
+            .map(|(key, _)| {
+                let line = line_of(&key);
+                (key.value, line)
+            })
+            .collect()
+    };
+
+    FileLines {
+        tables,
+        enums: named_lines(document.enums),
+        views: named_lines(document.views),
+    }
+}
+
+fn line_of<T>(spanned: &Spanned<T>) -> usize {
+    spanned.referenced.line() as usize
+}
+
+#[derive(Deserialize)]
+struct SpannedSchema {
+    #[serde(default)]
+    tables: Entries<SpannedTable>,
+    #[serde(default)]
+    enums: Entries<IgnoredAny>,
+    #[serde(default)]
+    views: Entries<IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct SpannedTable {
+    #[serde(default)]
+    schema: Option<String>,
+    #[serde(default)]
+    columns: Vec<Spanned<SpannedColumn>>,
+    #[serde(default)]
+    indexes: Vec<Spanned<SpannedIndex>>,
+    #[serde(default)]
+    constraints: Vec<Spanned<SpannedConstraint>>,
+}
+
+#[derive(Deserialize)]
+struct SpannedColumn {
+    name: String,
+    #[serde(default)]
+    references: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpannedIndex {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SpannedConstraint {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    columns: Vec<String>,
+    #[serde(default)]
+    check_expression: Option<String>,
+}
+
+impl SpannedTable {
+    fn into_lines(self, line: usize) -> TableLines {
+        let mut lines = TableLines {
+            line,
+            ..TableLines::default()
+        };
+        for constraint in self.constraints {
+            let constraint_line = line_of(&constraint);
+            let constraint = constraint.value;
+            lines.constraints.push(ConstraintLine {
+                kind: constraint.kind,
+                columns: constraint.columns,
+                check_expression: constraint.check_expression,
+                line: constraint_line,
+            });
+        }
+        for column in self.columns {
+            let column_line = line_of(&column);
+            // `references` 省略記法の外部キーはカラムの行で定義されている
+            if column.value.references.is_some() {
+                lines.constraints.push(ConstraintLine {
+                    kind: "FOREIGN_KEY".to_string(),
+                    columns: vec![column.value.name.clone()],
+                    check_expression: None,
+                    line: column_line,
+                });
+            }
+            lines.columns.insert(column.value.name, column_line);
+        }
+        for index in self.indexes {
+            let index_line = line_of(&index);
+            lines.indexes.insert(index.value.name, index_line);
+        }
+        lines
+    }
+}
+
+/// キーの位置情報を保持したままマップを読み込む（`Spanned` はマップのキーとして比較できないため）
+struct Entries<V>(Vec<(Spanned<String>, V)>);
+
+impl<V> Default for Entries<V> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Entries<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for EntriesVisitor<V> {
+            type Value = Entries<V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a mapping")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"version: "1.0"
+enums:
+  status:
+    name: status
+    values: [active, archived]
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: team_id
+        type:
+          kind: INTEGER
+        references: teams.id
+    indexes:
+      - name: idx_users_team
+        columns: [team_id]
+    constraints:
+      - type: CHECK
+        check_expression: "id > 0"
+  events:
+    schema: audit
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+views:
+  active_users:
+    definition: SELECT id FROM users
+"#;
+
+    #[test]
+    fn test_scan_records_lines_of_each_object() {
+        let lines = scan(SCHEMA);
+
+        assert_eq!(lines.enums.get("status"), Some(&3));
+        assert_eq!(lines.views.get("active_users"), Some(&29));
+
+        let users = &lines.tables["users"];
+        assert_eq!(users.line, 7);
+        assert_eq!(users.columns.get("id"), Some(&9));
+        assert_eq!(users.columns.get("team_id"), Some(&12));
+        assert_eq!(users.indexes.get("idx_users_team"), Some(&17));
+        assert_eq!(
+            users.constraint_line(&Constraint::CHECK {
+                columns: vec!["id".to_string()],
+                check_expression: "(id > 0)".to_string(),
+            }),
+            Some(20)
+        );
+        assert_eq!(
+            users.constraint_line(&Constraint::FOREIGN_KEY {
+                columns: vec!["team_id".to_string()],
+                referenced_table: "teams".to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: None,
+                on_update: None,
+            }),
+            Some(12)
+        );
+
+        // schema を指定したテーブルは修飾名で記録する
+        assert_eq!(lines.tables["audit.events"].line, 22);
+    }
+
+    #[test]
+    fn test_scan_returns_empty_for_unreadable_yaml() {
+        assert_eq!(scan("tables: ["), FileLines::default());
+    }
+}
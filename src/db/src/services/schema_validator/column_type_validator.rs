@@ -2,6 +2,7 @@
 
 use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
 use crate::core::schema::{ColumnType, Schema};
+use std::collections::BTreeSet;
 
 /// 既知のColumnType kind値（大文字）
 const KNOWN_COLUMN_TYPES: &[&str] = &[
@@ -27,9 +28,12 @@ const KNOWN_COLUMN_TYPES: &[&str] = &[
 ///
 /// - DECIMAL型の精度とスケールの検証
 /// - CHAR型の長さの検証
+/// - TIMESTAMP/TIME型の秒の小数部の桁数の検証
 /// - ENUM参照の存在確認
+/// - 使用中のパススルー型（citext・DOMAIN・複合型など）の一覧通知
 pub fn validate_column_types(schema: &Schema) -> ValidationResult {
     let mut result = ValidationResult::new();
+    let mut passthrough_types = BTreeSet::new();
 
     for (table_name, table) in &schema.tables {
         for column in &table.columns {
@@ -80,6 +84,8 @@ pub fn validate_column_types(schema: &Schema) -> ValidationResult {
                         ));
                     }
                 } else {
+                    passthrough_types.insert(kind.as_str());
+
                     // 未知の型名の場合、データベース実行時まで検証されない旨の警告を出す
                     result.add_warning(ValidationWarning::dialect_specific(
                         format!(
@@ -93,6 +99,16 @@ pub fn validate_column_types(schema: &Schema) -> ValidationResult {
         }
     }
 
+    if !passthrough_types.is_empty() {
+        result.add_warning(ValidationWarning::info(
+            format!(
+                "Passthrough types in use (outside portable type coverage): {}",
+                passthrough_types.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+            None,
+        ));
+    }
+
     result
 }
 
@@ -229,6 +245,27 @@ fn validate_column_type_internal(
                 });
             }
         }
+        ColumnType::TIMESTAMP {
+            precision: Some(precision),
+            ..
+        }
+        | ColumnType::TIME {
+            precision: Some(precision),
+            ..
+        } if *precision > 6 => {
+            // 秒の小数部の桁数の範囲チェック（0-6、MySQL/PostgreSQL 共通の上限）
+            result.add_error(ValidationError::Constraint {
+                message: format!(
+                    "{} type in column '{}.{}' has fractional seconds precision ({}) exceeding maximum (6)",
+                    column_type, table_name, column_name, precision
+                ),
+                location: Some(ErrorLocation::with_table_and_column(
+                    table_name,
+                    column_name,
+                )),
+                suggestion: Some("Set precision between 0 and 6".to_string()),
+            });
+        }
         // 他の型は追加のバリデーション不要
         _ => {}
     }
@@ -236,6 +273,7 @@ fn validate_column_type_internal(
 
 #[cfg(test)]
 mod tests {
+    use crate::core::error::WarningKind;
     use crate::core::schema::{Column, ColumnType, Constraint, Table};
 
     use super::*;
@@ -458,6 +496,37 @@ mod tests {
             .contains("length (70000) exceeding maximum (65535)"));
     }
 
+    #[test]
+    fn test_validate_timestamp_precision_range() {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut table = Table::new("events".to_string());
+        table.add_column(Column::new(
+            "created_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: Some(6),
+            },
+            false,
+        ));
+        table.add_column(Column::new(
+            "starts_at".to_string(),
+            ColumnType::TIME {
+                with_time_zone: None,
+                precision: Some(9), // precision > 6 はエラー
+            },
+            false,
+        ));
+        schema.add_table(table);
+
+        let result = validate_column_types(&schema);
+
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("TIME(9) type in column 'events.starts_at' has fractional seconds precision (9) exceeding maximum (6)"));
+    }
+
     #[test]
     fn test_validate_varchar_type_valid() {
         let mut schema = Schema::new("1.0".to_string());
@@ -527,6 +596,44 @@ mod tests {
         assert!(result.warnings[0].message.contains("not validated"));
     }
 
+    #[test]
+    fn test_validate_passthrough_types_info() {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut table = Table::new("customers".to_string());
+        for (name, kind) in [
+            ("email", "citext"),
+            ("contact", "email_address"),
+            ("address", "billing.address"),
+            ("alt_email", "citext"),
+        ] {
+            table.add_column(Column::new(
+                name.to_string(),
+                ColumnType::DialectSpecific {
+                    kind: kind.to_string(),
+                    params: serde_json::json!({}),
+                },
+                true,
+            ));
+        }
+        schema.add_table(table);
+
+        let result = validate_column_types(&schema);
+
+        assert!(result.is_valid());
+        let infos: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| w.kind == WarningKind::Info)
+            .collect();
+        assert_eq!(infos.len(), 1);
+        assert_eq!(
+            infos[0].message,
+            "Passthrough types in use (outside portable type coverage): billing.address, citext, email_address"
+        );
+        assert!(infos[0].format().starts_with("Info: "));
+    }
+
     #[test]
     fn test_validate_mysql_enum_with_values_no_warning() {
         // MySQL ENUM with values is a valid dialect-specific type
@@ -1,8 +1,9 @@
 // 制約の検証（PK, FK, UNIQUE）
 
+use super::schema_fixes::{is_plain_name, FixContext, LineEdit, SchemaFix};
 use super::validation_helpers::check_column_exists;
-use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
-use crate::core::schema::{Constraint, Schema};
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
+use crate::core::schema::{Column, Constraint, Schema, Table};
 
 /// プライマリキーの存在確認
 pub fn validate_primary_keys(schema: &Schema) -> ValidationResult {
@@ -16,7 +17,7 @@ pub fn validate_primary_keys(schema: &Schema) -> ValidationResult {
 
         if !has_primary_key && !table.columns.is_empty() {
             result.add_error(ValidationError::Constraint {
-                message: format!("Table '{}' has no primary key defined", table_name),
+                message: missing_primary_key_message(table_name),
                 location: Some(ErrorLocation::with_table(table_name.clone())),
                 suggestion: Some("Add a PRIMARY KEY constraint".to_string()),
             });
@@ -26,6 +27,88 @@ pub fn validate_primary_keys(schema: &Schema) -> ValidationResult {
     result
 }
 
+fn missing_primary_key_message(table_name: &str) -> String {
+    format!("Table '{}' has no primary key defined", table_name)
+}
+
+/// 主キーがないテーブルの修正案（`primary_key:` を追加する）
+///
+/// 主キーにするカラムが一意に決まる場合のみ作成します。
+/// - NOT NULLの `id` カラム
+/// - `id` がなければ、NOT NULLの自動増分カラムが1つだけの場合はそのカラム
+///
+/// `primary_key:` は `columns:` ブロックの直後に追加します。
+pub fn primary_key_fixes(context: &FixContext) -> Vec<SchemaFix> {
+    let mut fixes = Vec::new();
+
+    for (table_name, table) in &context.schema.tables {
+        let has_primary_key = table
+            .constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::PRIMARY_KEY { .. }));
+        if has_primary_key || table.columns.is_empty() {
+            continue;
+        }
+        let Some(column) = primary_key_candidate(table) else {
+            continue;
+        };
+        let Some((file, text, line)) = context.table_text(table_name) else {
+            continue;
+        };
+        if line >= text.lines.len()
+            || !text.is_block_key(line)
+            || text.child_key(line, "primary_key").is_some()
+        {
+            continue;
+        }
+        let Some(columns_line) = text.child_key(line, "columns") else {
+            continue;
+        };
+        let Some(indent) = text.child_indent(line) else {
+            continue;
+        };
+
+        fixes.push(SchemaFix {
+            file: file.to_path_buf(),
+            description: format!(
+                "Add primary_key: [{}] to table '{}'",
+                column.name, table_name
+            ),
+            error: missing_primary_key_message(table_name),
+            safe: true,
+            edit: LineEdit {
+                start: text.block_end(columns_line),
+                remove: 0,
+                insert: vec![format!(
+                    "{}primary_key: [{}]",
+                    " ".repeat(indent),
+                    column.name
+                )],
+            },
+        });
+    }
+
+    fixes
+}
+
+/// 主キーにするカラム（一意に決まらない場合はNone）
+fn primary_key_candidate(table: &Table) -> Option<&Column> {
+    let candidate = match table.get_column("id") {
+        Some(id) => Some(id),
+        None => {
+            let mut auto_increment = table
+                .columns
+                .iter()
+                .filter(|column| column.auto_increment == Some(true));
+            match (auto_increment.next(), auto_increment.next()) {
+                (Some(column), None) => Some(column),
+                _ => None,
+            }
+        }
+    };
+    candidate.filter(|column| !column.nullable && is_plain_name(&column.name))
+}
+
 /// 制約のカラム/テーブル参照整合性検証
 pub fn validate_constraint_references(schema: &Schema) -> ValidationResult {
     let mut result = ValidationResult::new();
@@ -33,55 +116,34 @@ pub fn validate_constraint_references(schema: &Schema) -> ValidationResult {
     for (table_name, table) in &schema.tables {
         for constraint in &table.constraints {
             match constraint {
-                Constraint::PRIMARY_KEY { columns }
-                | Constraint::UNIQUE { columns }
-                | Constraint::CHECK { columns, .. } => {
-                    for column_name in columns {
-                        check_column_exists(
-                            table,
-                            table_name,
-                            column_name,
-                            &mut result,
-                            "Constraint references",
-                        );
-                    }
-                }
                 Constraint::FOREIGN_KEY {
                     columns,
                     referenced_table,
                     referenced_columns,
                     ..
                 } => {
-                    // 外部キーのソースカラム存在確認
-                    for column_name in columns {
-                        check_column_exists(
-                            table,
-                            table_name,
-                            column_name,
-                            &mut result,
-                            "Foreign key constraint references",
-                        );
-                    }
-
-                    // カラム数の一致確認
-                    if columns.len() != referenced_columns.len() {
-                        result.add_error(ValidationError::Constraint {
-                            message: format!(
-                                "Foreign key constraint in table '{}' has {} column(s) but references {} column(s)",
-                                table_name,
-                                columns.len(),
-                                referenced_columns.len()
-                            ),
-                            location: Some(ErrorLocation::with_table(table_name.clone())),
-                            suggestion: Some(
-                                "Ensure the number of columns matches the number of referenced columns"
-                                    .to_string(),
-                            ),
-                        });
-                    }
+                    validate_foreign_key_columns(
+                        table,
+                        table_name,
+                        columns,
+                        referenced_columns,
+                        &mut result,
+                    );
 
                     // 参照先テーブルの存在確認
-                    if !schema.has_table(referenced_table) {
+                    // 外部テーブルは存在するものとして扱い、カラムは検証できないため警告に留める
+                    if schema.is_external_table(referenced_table)
+                        && !schema.has_table(referenced_table)
+                    {
+                        result.add_warning(ValidationWarning::foreign_key_reference(
+                            format!(
+                                "Foreign key constraint references external table '{}'; referenced column(s) {} cannot be verified",
+                                referenced_table,
+                                referenced_columns.join(", ")
+                            ),
+                            Some(ErrorLocation::with_table(table_name.clone())),
+                        ));
+                    } else if !schema.has_table(referenced_table) {
                         result.add_error(ValidationError::Reference {
                             message: format!(
                                 "Foreign key constraint references table '{}' which does not exist",
@@ -91,6 +153,7 @@ pub fn validate_constraint_references(schema: &Schema) -> ValidationResult {
                                 table: Some(table_name.clone()),
                                 column: None,
                                 line: None,
+                                file: None,
                             }),
                             suggestion: Some(format!("Define table '{}'", referenced_table)),
                         });
@@ -113,6 +176,18 @@ pub fn validate_constraint_references(schema: &Schema) -> ValidationResult {
                         }
                     }
                 }
+                // PRIMARY KEY / UNIQUE / CHECK などは対象カラムの存在のみ確認する
+                _ => {
+                    for column_name in constraint.columns() {
+                        check_column_exists(
+                            table,
+                            table_name,
+                            column_name,
+                            &mut result,
+                            "Constraint references",
+                        );
+                    }
+                }
             }
         }
     }
@@ -120,6 +195,142 @@ pub fn validate_constraint_references(schema: &Schema) -> ValidationResult {
     result
 }
 
+/// 外部キーの参照元側の検証（参照先テーブルを必要としない部分）
+///
+/// - ソースカラムの存在確認
+/// - ソースカラム数と参照先カラム数の一致確認
+pub fn validate_foreign_key_columns(
+    table: &Table,
+    table_name: &str,
+    columns: &[String],
+    referenced_columns: &[String],
+    result: &mut ValidationResult,
+) {
+    for column_name in columns {
+        check_column_exists(
+            table,
+            table_name,
+            column_name,
+            result,
+            "Foreign key constraint references",
+        );
+    }
+
+    if columns.len() != referenced_columns.len() {
+        result.add_error(ValidationError::Constraint {
+            message: foreign_key_column_count_message(
+                table_name,
+                columns.len(),
+                referenced_columns.len(),
+            ),
+            location: Some(ErrorLocation::with_table(table_name.to_string())),
+            suggestion: Some(
+                "Ensure the number of columns matches the number of referenced columns".to_string(),
+            ),
+        });
+    }
+}
+
+fn foreign_key_column_count_message(
+    table_name: &str,
+    column_count: usize,
+    referenced_count: usize,
+) -> String {
+    format!(
+        "Foreign key constraint in table '{}' has {} column(s) but references {} column(s)",
+        table_name, column_count, referenced_count
+    )
+}
+
+/// 参照先カラムが空の外部キーの修正案（参照先テーブルの主キーを指定する）
+///
+/// SQLで参照先カラムを省略した外部キーが主キーを参照するのと同じ解釈です。
+/// 参照先カラムが指定されている場合は、どちらが誤りか決められないため作成しません。
+/// 次の場合のみ、`referenced_columns: []` の行を書き換えます。
+/// - 参照先テーブルが定義されていて、主キーのカラム数が外部キーのカラム数と同じ
+/// - 同じテーブルに同じカラムの外部キーが他にない（定義行を特定できる）
+pub fn foreign_key_column_fixes(context: &FixContext) -> Vec<SchemaFix> {
+    let mut fixes = Vec::new();
+
+    for (table_name, table) in &context.schema.tables {
+        for constraint in &table.constraints {
+            let Constraint::FOREIGN_KEY {
+                columns,
+                referenced_table,
+                referenced_columns,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            if columns.is_empty() || !referenced_columns.is_empty() {
+                continue;
+            }
+            let Some(primary_key) = context
+                .schema
+                .get_table(referenced_table)
+                .and_then(|referenced| referenced.get_primary_key_columns())
+                .filter(|primary_key| primary_key.len() == columns.len())
+                .filter(|primary_key| primary_key.iter().all(|name| is_plain_name(name)))
+            else {
+                continue;
+            };
+            let same_columns = table
+                .constraints
+                .iter()
+                .filter(|other| other.kind() == "FOREIGN_KEY" && other.columns() == columns)
+                .count();
+            if same_columns != 1 {
+                continue;
+            }
+
+            let Some((file, text, _)) = context.table_text(table_name) else {
+                continue;
+            };
+            let Some(line) = context
+                .sources
+                .constraint_line(table_name, constraint)
+                .and_then(|line| line.checked_sub(1))
+                .filter(|&line| line < text.lines.len())
+            else {
+                continue;
+            };
+            let Some(target) = text.child_key(line, "referenced_columns") else {
+                continue;
+            };
+            let original = text.lines[target];
+            if text.content_without_comment(target) != "referenced_columns: []" {
+                continue;
+            }
+
+            let list = primary_key.join(", ");
+            fixes.push(SchemaFix {
+                file: file.to_path_buf(),
+                description: format!(
+                    "Set referenced_columns: [{}] (the primary key of '{}') on the foreign key ({}) of table '{}'",
+                    list,
+                    referenced_table,
+                    columns.join(", "),
+                    table_name
+                ),
+                error: foreign_key_column_count_message(table_name, columns.len(), 0),
+                safe: true,
+                edit: LineEdit {
+                    start: target,
+                    remove: 1,
+                    insert: vec![original.replacen(
+                        "referenced_columns: []",
+                        &format!("referenced_columns: [{}]", list),
+                        1,
+                    )],
+                },
+            });
+        }
+    }
+
+    fixes
+}
+
 /// CHECK制約のexpressionが空でないことを検証
 pub fn validate_check_expressions(schema: &Schema) -> ValidationResult {
     let mut result = ValidationResult::new();
@@ -149,8 +360,6 @@ pub fn validate_check_expressions(schema: &Schema) -> ValidationResult {
 
 /// 同一テーブル内に同じカラム構成のUNIQUE制約が重複していないか検証
 pub fn validate_duplicate_unique_constraints(schema: &Schema) -> ValidationResult {
-    use crate::core::error::ValidationWarning;
-
     let mut result = ValidationResult::new();
 
     for (table_name, table) in &schema.tables {
@@ -208,7 +417,10 @@ pub fn validate_referential_integrity(schema: &Schema) -> Vec<ValidationError> {
                 ..
             } = constraint
             {
-                // Check if referenced table exists
+                // Check if referenced table exists (external tables are assumed to exist)
+                if schema.is_external_table(referenced_table) {
+                    continue;
+                }
                 if !schema.has_table(referenced_table) {
                     errors.push(ValidationError::Reference {
                         message: format!(
@@ -320,6 +532,35 @@ mod tests {
             .any(|e| e.to_string().contains("does not exist")));
     }
 
+    #[test]
+    fn test_validate_constraint_references_external_table_warns() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.external_tables.insert("external_users".to_string());
+        let mut table = Table::new("posts".to_string());
+        table.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "external_users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        schema.add_table(table);
+
+        let result = validate_constraint_references(&schema);
+
+        assert!(result.is_valid());
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.warnings[0]
+            .message
+            .contains("external table 'external_users'"));
+        assert!(validate_referential_integrity(&schema).is_empty());
+    }
+
     #[test]
     fn test_validate_constraint_references_invalid_pk_column() {
         let mut schema = Schema::new("1.0".to_string());
@@ -569,4 +810,147 @@ mod tests {
 
         assert!(result.is_valid());
     }
+
+    #[test]
+    fn test_primary_key_fix_adds_primary_key_after_columns() {
+        let before = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: email
+        type:
+          kind: VARCHAR
+          length: 255
+    indexes: []
+"#;
+        let after = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: email
+        type:
+          kind: VARCHAR
+          length: 255
+    primary_key: [id]
+    indexes: []
+"#;
+
+        let (fixes, fixed) = super::super::schema_fixes::fix_yaml(before, primary_key_fixes);
+
+        assert_eq!(fixed, after);
+        assert_eq!(fixes.len(), 1);
+        assert!(fixes[0].safe);
+        assert_eq!(fixes[0].error, "Table 'users' has no primary key defined");
+    }
+
+    #[test]
+    fn test_primary_key_fix_uses_single_auto_increment_column() {
+        let before = "version: \"1.0\"\ntables:\n  events:\n    columns:\n    - name: event_id\n      type: {kind: BIGINT}\n      auto_increment: true\n    - name: payload\n      type: {kind: TEXT}\n";
+        let after = "version: \"1.0\"\ntables:\n  events:\n    columns:\n    - name: event_id\n      type: {kind: BIGINT}\n      auto_increment: true\n    - name: payload\n      type: {kind: TEXT}\n    primary_key: [event_id]\n";
+
+        let (_, fixed) = super::super::schema_fixes::fix_yaml(before, primary_key_fixes);
+
+        assert_eq!(fixed, after);
+    }
+
+    #[test]
+    fn test_primary_key_fix_skips_ambiguous_tables() {
+        // 主キーにするカラムが決まらない
+        let no_candidate =
+            "version: \"1.0\"\ntables:\n  tags:\n    columns:\n    - name: label\n      type: {kind: TEXT}\n";
+        // idがNULL許可
+        let nullable_id = "version: \"1.0\"\ntables:\n  tags:\n    columns:\n    - name: id\n      type: {kind: INTEGER}\n      nullable: true\n";
+        // フロースタイルのテーブル定義
+        let flow =
+            "version: \"1.0\"\ntables:\n  tags: {columns: [{name: id, type: {kind: INTEGER}}]}\n";
+
+        for before in [no_candidate, nullable_id, flow] {
+            let (fixes, fixed) = super::super::schema_fixes::fix_yaml(before, primary_key_fixes);
+            assert!(fixes.is_empty(), "{}", before);
+            assert_eq!(fixed, before);
+        }
+    }
+
+    #[test]
+    fn test_foreign_key_fix_fills_referenced_primary_key() {
+        let before = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type: {kind: INTEGER}
+    primary_key: [id]
+  posts:
+    columns:
+      - name: id
+        type: {kind: INTEGER}
+      - name: user_id
+        type: {kind: INTEGER}
+    primary_key: [id]
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [user_id]
+        referenced_table: users
+        referenced_columns: []  # TODO
+        on_delete: CASCADE
+"#;
+        let after = before.replace(
+            "referenced_columns: []  # TODO",
+            "referenced_columns: [id]  # TODO",
+        );
+
+        let (fixes, fixed) = super::super::schema_fixes::fix_yaml(before, foreign_key_column_fixes);
+
+        assert_eq!(fixed, after);
+        assert_eq!(
+            fixes[0].error,
+            "Foreign key constraint in table 'posts' has 1 column(s) but references 0 column(s)"
+        );
+        assert!(fixes[0].safe);
+    }
+
+    #[test]
+    fn test_foreign_key_fix_skips_listed_referenced_columns() {
+        // 参照先カラムが指定されている場合はどちらが誤りか決められない
+        let listed = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type: {kind: INTEGER}
+      - name: tenant_id
+        type: {kind: INTEGER}
+    primary_key: [id, tenant_id]
+  posts:
+    columns:
+      - name: id
+        type: {kind: INTEGER}
+      - name: user_id
+        type: {kind: INTEGER}
+    primary_key: [id]
+    constraints:
+      - type: FOREIGN_KEY
+        columns: [user_id]
+        referenced_table: users
+        referenced_columns: [id, tenant_id]
+"#;
+        // 主キーのカラム数が外部キーと異なる
+        let composite = listed.replace(
+            "referenced_columns: [id, tenant_id]",
+            "referenced_columns: []",
+        );
+
+        for before in [listed.to_string(), composite] {
+            let (fixes, fixed) =
+                super::super::schema_fixes::fix_yaml(&before, foreign_key_column_fixes);
+            assert!(fixes.is_empty(), "{}", before);
+            assert_eq!(fixed, before);
+        }
+    }
 }
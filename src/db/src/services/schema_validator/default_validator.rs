@@ -0,0 +1,408 @@
+// デフォルト値のバリデーション
+//
+// デフォルト値の式が同じテーブルの他のカラムを参照していないかを検証します。
+// - PostgreSQL / SQLite はカラムを参照するデフォルト式を受け付けないためエラー
+// - MySQL 8.0.13 以降は括弧で囲んだ式であれば許可されるため警告に留める
+//
+// また、現在日時のデフォルト値（CURRENT_TIMESTAMP など）が日時型のカラムにのみ
+// 指定されているかを方言ごとに検証します。
+
+use crate::adapters::database_introspector::extract_columns_from_sqlite_check;
+use crate::core::config::Dialect;
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
+use crate::core::schema::{ColumnType, Schema};
+
+/// デフォルト値の式が参照するカラムの検証
+///
+/// 方言が指定されていない場合は検証しません。
+pub fn validate_default_expressions(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let Some(dialect) = dialect else {
+        return result;
+    };
+
+    for (table_name, table) in &schema.tables {
+        let column_names: Vec<String> = table.columns.iter().map(|c| c.name.clone()).collect();
+
+        for column in &table.columns {
+            let Some(ref default_value) = column.default_value else {
+                continue;
+            };
+            let references = extract_columns_from_sqlite_check(default_value, &column_names);
+            if references.is_empty() {
+                continue;
+            }
+
+            let location = Some(ErrorLocation::with_table_and_column(
+                table_name,
+                &column.name,
+            ));
+            let referenced = references.join(", ");
+
+            match dialect {
+                Dialect::MySQL if is_parenthesized(default_value) => {
+                    result.add_warning(ValidationWarning::compatibility(
+                        format!(
+                            "Default value of column '{}' in table '{}' references column(s) {}: {}. This requires MySQL 8.0.13 or later and is not portable to PostgreSQL or SQLite",
+                            column.name, table_name, referenced, default_value
+                        ),
+                        location,
+                    ));
+                }
+                Dialect::MySQL => {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Default value of column '{}' in table '{}' references column(s) {}, but MySQL only accepts expression defaults enclosed in parentheses: {}",
+                            column.name, table_name, referenced, default_value
+                        ),
+                        location,
+                        suggestion: Some(format!(
+                            "Write the default value as \"({})\"",
+                            default_value.trim()
+                        )),
+                    });
+                }
+                Dialect::PostgreSQL | Dialect::SQLite => {
+                    let dialect_name = if dialect == Dialect::PostgreSQL {
+                        "PostgreSQL"
+                    } else {
+                        "SQLite"
+                    };
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Default value of column '{}' in table '{}' references column(s) {}, which {} does not allow in a DEFAULT expression: {}",
+                            column.name, table_name, referenced, dialect_name, default_value
+                        ),
+                        location,
+                        suggestion: Some(
+                            "Use a generated column (GENERATED ALWAYS AS (...)) or compute the value in the application instead of a default value".to_string(),
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// 現在日時のデフォルト値を指定したカラムの型の検証
+///
+/// `CURRENT_TIMESTAMP`（および `now()` などの同等の表記）を指定できる型は方言ごとに異なります。
+/// - MySQL: TIMESTAMP / DATETIME（それ以外の型ではCREATE TABLEがエラーになる）
+/// - PostgreSQL: TIMESTAMP / DATE / TIME（日時型への代入キャストが行われる）
+/// - SQLite: TIMESTAMP / DATETIME（`YYYY-MM-DD HH:MM:SS` の文字列が格納される）
+///
+/// 方言が指定されていない場合は検証しません。
+pub fn validate_current_timestamp_defaults(
+    schema: &Schema,
+    dialect: Option<Dialect>,
+) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let Some(dialect) = dialect else {
+        return result;
+    };
+
+    for (table_name, table) in &schema.tables {
+        for column in &table.columns {
+            if !column.has_current_timestamp_default()
+                || accepts_current_timestamp(&column.column_type, dialect)
+            {
+                continue;
+            }
+
+            let suggestion = match (&column.column_type, dialect) {
+                (ColumnType::DATE, Dialect::MySQL) => {
+                    "Use \"(CURRENT_DATE)\" as the default value for a DATE column".to_string()
+                }
+                (ColumnType::DATE, _) => {
+                    "Use CURRENT_DATE as the default value for a DATE column".to_string()
+                }
+                (ColumnType::TIME { .. }, Dialect::MySQL) => {
+                    "Use \"(CURRENT_TIME)\" as the default value for a TIME column".to_string()
+                }
+                (ColumnType::TIME { .. }, _) => {
+                    "Use CURRENT_TIME as the default value for a TIME column".to_string()
+                }
+                _ => "Change the column type to TIMESTAMP, or remove the default value".to_string(),
+            };
+            let (dialect_name, allowed_types) = current_timestamp_types(dialect);
+            result.add_error(ValidationError::Constraint {
+                message: format!(
+                    "Column '{}' in table '{}' has default value {}, but {} only accepts CURRENT_TIMESTAMP on {} columns (column type: {})",
+                    column.name,
+                    table_name,
+                    column.default_value.as_deref().unwrap_or_default(),
+                    dialect_name,
+                    allowed_types,
+                    column.column_type
+                ),
+                location: Some(ErrorLocation::with_table_and_column(
+                    table_name,
+                    &column.name,
+                )),
+                suggestion: Some(suggestion),
+            });
+        }
+    }
+
+    result
+}
+
+/// 方言が `CURRENT_TIMESTAMP` のデフォルト値を受け付ける型かどうか
+fn accepts_current_timestamp(column_type: &ColumnType, dialect: Dialect) -> bool {
+    match column_type {
+        ColumnType::TIMESTAMP { .. } => true,
+        ColumnType::DATE | ColumnType::TIME { .. } => dialect == Dialect::PostgreSQL,
+        ColumnType::DialectSpecific { kind, .. } => {
+            let kind = kind.to_ascii_uppercase();
+            match dialect {
+                Dialect::MySQL | Dialect::SQLite => kind == "DATETIME" || kind == "TIMESTAMP",
+                Dialect::PostgreSQL => kind.starts_with("TIMESTAMP") || kind.starts_with("TIME"),
+            }
+        }
+        _ => false,
+    }
+}
+
+/// エラーメッセージ用の方言名と `CURRENT_TIMESTAMP` を受け付ける型の一覧
+fn current_timestamp_types(dialect: Dialect) -> (&'static str, &'static str) {
+    match dialect {
+        Dialect::PostgreSQL => ("PostgreSQL", "TIMESTAMP, DATE or TIME"),
+        Dialect::MySQL => ("MySQL", "TIMESTAMP or DATETIME"),
+        Dialect::SQLite => ("SQLite", "TIMESTAMP or DATETIME"),
+    }
+}
+
+/// 式全体が1組の括弧で囲まれているか
+///
+/// `(a) + (b)` のように先頭と末尾の括弧が対応しない場合は false を返します。
+fn is_parenthesized(expr: &str) -> bool {
+    let Some(inner) = expr
+        .trim()
+        .strip_prefix('(')
+        .and_then(|e| e.strip_suffix(')'))
+    else {
+        return false;
+    };
+
+    let mut depth = 0usize;
+    let mut in_quote = false;
+    for ch in inner.chars() {
+        match ch {
+            '\'' => in_quote = !in_quote,
+            '(' if !in_quote => depth += 1,
+            ')' if !in_quote => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+
+    fn schema_with_default(default_value: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("products".to_string());
+        table.add_column(Column::new(
+            "price".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            false,
+        ));
+        let mut tax = Column::new(
+            "tax".to_string(),
+            ColumnType::DECIMAL {
+                precision: 10,
+                scale: 2,
+            },
+            true,
+        );
+        tax.default_value = Some(default_value.to_string());
+        table.add_column(tax);
+        schema.add_table(table);
+        schema
+    }
+
+    fn validate(default_value: &str, dialect: Dialect) -> ValidationResult {
+        validate_default_expressions(&schema_with_default(default_value), Some(dialect))
+    }
+
+    #[test]
+    fn test_literal_and_function_defaults_are_allowed() {
+        for default_value in ["0", "'price'", "(0.2 * 100)"] {
+            for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+                let result = validate(default_value, dialect);
+                assert!(result.is_valid(), "{} on {}", default_value, dialect);
+                assert!(
+                    result.warnings.is_empty(),
+                    "{} on {}",
+                    default_value,
+                    dialect
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_column_reference_is_rejected_on_postgres_and_sqlite() {
+        for dialect in [Dialect::PostgreSQL, Dialect::SQLite] {
+            let result = validate("(price * 0.2)", dialect);
+            assert_eq!(result.error_count(), 1);
+            let message = result.errors[0].to_string();
+            assert!(
+                message.contains("references column(s) price"),
+                "{}",
+                message
+            );
+            assert!(result.errors[0]
+                .suggestion()
+                .unwrap()
+                .contains("generated column"));
+        }
+    }
+
+    #[test]
+    fn test_column_reference_on_mysql_requires_parentheses() {
+        let result = validate("(price * 0.2)", Dialect::MySQL);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("MySQL 8.0.13"));
+
+        let result = validate("price * 0.2", Dialect::MySQL);
+        assert_eq!(result.error_count(), 1);
+        assert_eq!(
+            result.errors[0].suggestion(),
+            Some("Write the default value as \"(price * 0.2)\"")
+        );
+
+        // 先頭と末尾の括弧が対応していなければ括弧で囲まれた式ではない
+        let result = validate("(price) * (0.2)", Dialect::MySQL);
+        assert_eq!(result.error_count(), 1);
+    }
+
+    #[test]
+    fn test_without_dialect_skips_validation() {
+        let result = validate_default_expressions(&schema_with_default("price * 0.2"), None);
+        assert!(result.is_valid());
+        assert!(result.warnings.is_empty());
+    }
+
+    fn schema_with_current_timestamp(column_type: ColumnType, default_value: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("events".to_string());
+        let mut column = Column::new("created_at".to_string(), column_type, false);
+        column.default_value = Some(default_value.to_string());
+        table.add_column(column);
+        schema.add_table(table);
+        schema
+    }
+
+    fn validate_current_timestamp(
+        column_type: ColumnType,
+        default_value: &str,
+        dialect: Dialect,
+    ) -> ValidationResult {
+        validate_current_timestamp_defaults(
+            &schema_with_current_timestamp(column_type, default_value),
+            Some(dialect),
+        )
+    }
+
+    fn datetime_type() -> ColumnType {
+        ColumnType::DialectSpecific {
+            kind: "DATETIME".to_string(),
+            params: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_current_timestamp_on_temporal_types_is_allowed() {
+        let timestamp = ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: Some(6),
+        };
+        let cases = [
+            (Dialect::MySQL, timestamp.clone()),
+            (Dialect::MySQL, datetime_type()),
+            (Dialect::PostgreSQL, timestamp.clone()),
+            (Dialect::PostgreSQL, ColumnType::DATE),
+            (
+                Dialect::PostgreSQL,
+                ColumnType::TIME {
+                    with_time_zone: None,
+                    precision: None,
+                },
+            ),
+            (Dialect::SQLite, timestamp),
+            (Dialect::SQLite, datetime_type()),
+        ];
+        for (dialect, column_type) in cases {
+            for default_value in ["CURRENT_TIMESTAMP", "now()", "datetime('now')"] {
+                let result =
+                    validate_current_timestamp(column_type.clone(), default_value, dialect);
+                assert!(
+                    result.is_valid(),
+                    "{} on {:?} ({})",
+                    default_value,
+                    column_type,
+                    dialect
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_current_timestamp_on_non_temporal_types_is_rejected() {
+        for dialect in [Dialect::MySQL, Dialect::PostgreSQL, Dialect::SQLite] {
+            for column_type in [
+                ColumnType::VARCHAR { length: 255 },
+                ColumnType::INTEGER { precision: None },
+                ColumnType::TEXT,
+            ] {
+                let result =
+                    validate_current_timestamp(column_type.clone(), "CURRENT_TIMESTAMP", dialect);
+                assert_eq!(result.error_count(), 1, "{:?} ({})", column_type, dialect);
+                assert!(result.errors[0]
+                    .to_string()
+                    .contains("only accepts CURRENT_TIMESTAMP"));
+                assert_eq!(
+                    result.errors[0].suggestion(),
+                    Some("Change the column type to TIMESTAMP, or remove the default value")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_current_timestamp_on_date_suggests_current_date() {
+        let result =
+            validate_current_timestamp(ColumnType::DATE, "CURRENT_TIMESTAMP", Dialect::MySQL);
+        assert_eq!(result.error_count(), 1);
+        assert_eq!(
+            result.errors[0].suggestion(),
+            Some("Use \"(CURRENT_DATE)\" as the default value for a DATE column")
+        );
+
+        let result = validate_current_timestamp(ColumnType::DATE, "now()", Dialect::SQLite);
+        assert_eq!(result.error_count(), 1);
+        assert_eq!(
+            result.errors[0].suggestion(),
+            Some("Use CURRENT_DATE as the default value for a DATE column")
+        );
+    }
+
+    #[test]
+    fn test_current_timestamp_is_not_checked_without_dialect() {
+        let schema = schema_with_current_timestamp(ColumnType::TEXT, "CURRENT_TIMESTAMP");
+        assert!(validate_current_timestamp_defaults(&schema, None).is_valid());
+    }
+}
@@ -1,8 +1,9 @@
 // 方言固有の検証
 
 use crate::core::config::Dialect;
-use crate::core::error::{ErrorLocation, ValidationWarning};
-use crate::core::schema::{ColumnType, Schema};
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
+use crate::core::schema::{ColumnType, Constraint, Schema};
+use crate::core::server_version::{ServerVersion, MYSQL_CHECK_ENFORCEMENT_VERSION};
 
 /// 方言固有の警告を生成
 ///
@@ -19,6 +20,54 @@ pub fn generate_dialect_warnings(schema: &Schema, dialect: &Dialect) -> Vec<Vali
 
     for (table_name, table) in &schema.tables {
         for column in &table.columns {
+            // SQLiteは日時をTEXTとして保存するため、秒の小数部の桁数は無視される
+            if let ColumnType::TIMESTAMP {
+                precision: Some(_), ..
+            }
+            | ColumnType::TIME {
+                precision: Some(_), ..
+            } = &column.column_type
+            {
+                if matches!(dialect, Dialect::SQLite) {
+                    warnings.push(ValidationWarning::dialect_specific(
+                        format!(
+                            "{} in column '{}.{}' will be stored as TEXT in SQLite (fractional seconds precision is ignored).",
+                            column.column_type, table_name, column.name
+                        ),
+                        Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
+                    ));
+                }
+            }
+
+            // ON UPDATE CURRENT_TIMESTAMP はMySQLの TIMESTAMP / DATETIME カラムのみ対応
+            if column.on_update_current_timestamp {
+                let location = Some(ErrorLocation::with_table_and_column(
+                    table_name,
+                    &column.name,
+                ));
+                match dialect {
+                    Dialect::MySQL if !is_mysql_datetime_type(&column.column_type) => {
+                        warnings.push(ValidationWarning::compatibility(
+                            format!(
+                                "on_update_current_timestamp in column '{}.{}' requires a TIMESTAMP or DATETIME column in MySQL, but the column is {}.",
+                                table_name, column.name, column.column_type
+                            ),
+                            location,
+                        ));
+                    }
+                    Dialect::MySQL => {}
+                    Dialect::PostgreSQL | Dialect::SQLite => {
+                        warnings.push(ValidationWarning::dialect_specific(
+                            format!(
+                                "on_update_current_timestamp in column '{}.{}' is only supported in MySQL and will be ignored (use a trigger to update the column).",
+                                table_name, column.name
+                            ),
+                            location,
+                        ));
+                    }
+                }
+            }
+
             match &column.column_type {
                 ColumnType::DECIMAL { precision, scale } => {
                     // SQLiteでは精度損失の警告
@@ -76,27 +125,25 @@ pub fn generate_dialect_warnings(schema: &Schema, dialect: &Dialect) -> Vec<Vali
                         ));
                     }
                 }
-                ColumnType::TIME { with_time_zone } => {
-                    // MySQLとSQLiteではタイムゾーン情報が失われる警告
-                    if *with_time_zone == Some(true) {
-                        if matches!(dialect, Dialect::MySQL) {
-                            warnings.push(ValidationWarning::dialect_specific(
-                                format!(
-                                    "TIME WITH TIME ZONE in column '{}.{}' will be stored as TIME in MySQL (timezone information will be lost).",
-                                    table_name, column.name
-                                ),
-                                Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
-                            ));
-                        }
-                        if matches!(dialect, Dialect::SQLite) {
-                            warnings.push(ValidationWarning::precision_loss(
-                                format!(
-                                    "TIME WITH TIME ZONE in column '{}.{}' will be stored as TEXT in SQLite (timezone information will be lost).",
-                                    table_name, column.name
-                                ),
-                                Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
-                            ));
-                        }
+                // MySQLとSQLiteではタイムゾーン情報が失われる警告
+                ColumnType::TIME { with_time_zone, .. } if *with_time_zone == Some(true) => {
+                    if matches!(dialect, Dialect::MySQL) {
+                        warnings.push(ValidationWarning::dialect_specific(
+                            format!(
+                                "TIME WITH TIME ZONE in column '{}.{}' will be stored as TIME in MySQL (timezone information will be lost).",
+                                table_name, column.name
+                            ),
+                            Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
+                        ));
+                    }
+                    if matches!(dialect, Dialect::SQLite) {
+                        warnings.push(ValidationWarning::precision_loss(
+                            format!(
+                                "TIME WITH TIME ZONE in column '{}.{}' will be stored as TEXT in SQLite (timezone information will be lost).",
+                                table_name, column.name
+                            ),
+                            Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
+                        ));
                     }
                 }
                 ColumnType::DATE => {
@@ -114,17 +161,230 @@ pub fn generate_dialect_warnings(schema: &Schema, dialect: &Dialect) -> Vec<Vali
                 _ => {}
             }
         }
+
+        // NULL順序はPostgreSQLのみ対応（MySQL/SQLiteではソート順のみ出力される）
+        let dialect_name = match dialect {
+            Dialect::MySQL => Some("MySQL"),
+            Dialect::SQLite => Some("SQLite"),
+            Dialect::PostgreSQL => None,
+        };
+        if let Some(dialect_name) = dialect_name {
+            for index in &table.indexes {
+                for column in &index.columns {
+                    if let Some(nulls) = column.nulls {
+                        warnings.push(ValidationWarning::dialect_specific(
+                            format!(
+                                "{} on column '{}' of index '{}.{}' is not supported in {} and will be ignored (only ASC/DESC is applied).",
+                                nulls.as_sql(), column.name, table_name, index.name, dialect_name
+                            ),
+                            Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
+                        ));
+                    }
+                }
+            }
+        }
     }
 
     warnings
 }
 
+/// MySQLのサポート対象バージョンに応じた警告を生成
+///
+/// `mysql.minimum_version` がCHECK制約を強制しないバージョンの場合、
+/// スキーマ内のCHECK制約ごとに警告を生成します。
+///
+/// # Arguments
+///
+/// * `schema` - 検証対象のスキーマ
+/// * `minimum_version` - サポート対象とする最も古いサーバーバージョン
+///
+/// # Returns
+///
+/// バージョン依存の警告のリスト
+pub fn generate_mysql_version_warnings(
+    schema: &Schema,
+    minimum_version: &ServerVersion,
+) -> Vec<ValidationWarning> {
+    let mut warnings = Vec::new();
+    if minimum_version.enforces_check_constraints() {
+        return warnings;
+    }
+
+    let (major, minor, patch) = MYSQL_CHECK_ENFORCEMENT_VERSION;
+    for (table_name, table) in &schema.tables {
+        for constraint in &table.constraints {
+            if let Constraint::CHECK {
+                check_expression, ..
+            } = constraint
+            {
+                warnings.push(ValidationWarning::compatibility(
+                    format!(
+                        "CHECK ({}) on table '{}' will be parsed but not enforced on MySQL {} (mysql.minimum_version); CHECK constraints require MySQL {}.{}.{} or later.",
+                        check_expression, table_name, minimum_version, major, minor, patch
+                    ),
+                    Some(ErrorLocation::with_table(table_name.clone())),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// テーブルのスキーマ指定を検証
+///
+/// - PostgreSQL以外の方言で `schema` が指定されていないか確認
+/// - スキーマ名が空でなく `.` を含まないか確認
+/// - PostgreSQLで、`schema` なしのテーブル名に `.` が含まれていないか確認
+///   （生成SQLでスキーマ修飾名として解釈されるため）
+/// - PostgreSQLで、`renamed_from` がスキーマをまたいでいないか確認
+pub fn validate_table_schemas(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        let location = Some(ErrorLocation::with_table(table_name.clone()));
+        match &table.schema {
+            Some(schema_name) => {
+                if let Some(dialect) = dialect.filter(|d| !matches!(d, Dialect::PostgreSQL)) {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Table '{}' sets schema '{}', but schema-qualified tables are only supported in PostgreSQL (current: {})",
+                            table_name, schema_name, dialect
+                        ),
+                        location: location.clone(),
+                        suggestion: Some(
+                            "Remove 'schema' from the table definition or switch to PostgreSQL"
+                                .to_string(),
+                        ),
+                    });
+                }
+                if schema_name.is_empty() || schema_name.contains('.') {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Table '{}' has an invalid schema name '{}'",
+                            table_name, schema_name
+                        ),
+                        location: location.clone(),
+                        suggestion: Some("Use a non-empty schema name without '.'".to_string()),
+                    });
+                }
+            }
+            None => {
+                if matches!(dialect, Some(Dialect::PostgreSQL)) && table_name.contains('.') {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Table name '{}' contains '.', which PostgreSQL reads as a schema-qualified name, but no schema is set",
+                            table_name
+                        ),
+                        location: location.clone(),
+                        suggestion: Some(
+                            "Set 'schema' on the table, or remove '.' from the table name"
+                                .to_string(),
+                        ),
+                    });
+                }
+            }
+        }
+
+        if !matches!(dialect, Some(Dialect::PostgreSQL)) {
+            continue;
+        }
+        if let Some(renamed_from) = &table.renamed_from {
+            let old_schema = renamed_from.split_once('.').map(|(schema, _)| schema);
+            if old_schema != table.schema.as_deref() {
+                let suggestion = match &table.schema {
+                    Some(schema_name) => format!(
+                        "Qualify renamed_from with the same schema (e.g. '{}.{}')",
+                        schema_name,
+                        renamed_from
+                            .split_once('.')
+                            .map_or(renamed_from.as_str(), |(_, name)| name)
+                    ),
+                    None => "Use an unqualified table name in renamed_from".to_string(),
+                };
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Table '{}' is renamed from '{}' in a different schema; renamed_from cannot move a table between schemas",
+                        table_name, renamed_from
+                    ),
+                    location,
+                    suggestion: Some(suggestion),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// MySQLで ON UPDATE CURRENT_TIMESTAMP を指定できる型（TIMESTAMP / DATETIME）かどうか
+fn is_mysql_datetime_type(column_type: &ColumnType) -> bool {
+    match column_type {
+        ColumnType::TIMESTAMP { .. } => true,
+        ColumnType::DialectSpecific { kind, .. } => kind.eq_ignore_ascii_case("DATETIME"),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::core::schema::{Column, Table};
+    use crate::core::schema::{Column, Index, IndexColumn, NullsOrder, SortOrder, Table};
 
     use super::*;
 
+    fn schema_qualified_table_schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new_in_schema("app".to_string(), "users");
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_validate_table_schemas_rejects_schema_outside_postgres() {
+        let schema = schema_qualified_table_schema();
+
+        assert!(validate_table_schemas(&schema, Some(Dialect::PostgreSQL)).is_valid());
+        for dialect in [Dialect::MySQL, Dialect::SQLite] {
+            let result = validate_table_schemas(&schema, Some(dialect));
+            assert_eq!(result.error_count(), 1);
+            assert!(result.errors[0]
+                .to_string()
+                .contains("schema-qualified tables are only supported in PostgreSQL"));
+        }
+    }
+
+    #[test]
+    fn test_validate_table_schemas_rejects_dotted_name_without_schema() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("app.users".to_string()));
+
+        let result = validate_table_schemas(&schema, Some(Dialect::PostgreSQL));
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0].to_string().contains("no schema is set"));
+        // PostgreSQL以外では識別子としてそのままクォートされるため許容
+        assert!(validate_table_schemas(&schema, Some(Dialect::MySQL)).is_valid());
+    }
+
+    #[test]
+    fn test_validate_table_schemas_rejects_cross_schema_rename() {
+        let mut schema = schema_qualified_table_schema();
+        schema.tables.get_mut("app.users").unwrap().renamed_from = Some("members".to_string());
+
+        let result = validate_table_schemas(&schema, Some(Dialect::PostgreSQL));
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("cannot move a table between schemas"));
+
+        schema.tables.get_mut("app.users").unwrap().renamed_from = Some("app.members".to_string());
+        assert!(validate_table_schemas(&schema, Some(Dialect::PostgreSQL)).is_valid());
+    }
+
     #[test]
     fn test_generate_dialect_warnings_sqlite_decimal() {
         let mut schema = Schema::new("1.0".to_string());
@@ -217,6 +477,7 @@ mod tests {
             "start_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: Some(true),
+                precision: None,
             },
             false,
         ));
@@ -230,6 +491,32 @@ mod tests {
             .contains("timezone information will be lost"));
     }
 
+    #[test]
+    fn test_generate_dialect_warnings_sqlite_datetime_precision() {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut table = Table::new("events".to_string());
+        table.add_column(Column::new(
+            "created_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: Some(6),
+            },
+            false,
+        ));
+        schema.add_table(table);
+
+        assert!(generate_dialect_warnings(&schema, &Dialect::MySQL).is_empty());
+
+        let warnings = generate_dialect_warnings(&schema, &Dialect::SQLite);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "TIMESTAMP(6) in column 'events.created_at' will be stored as TEXT in SQLite (fractional seconds precision is ignored)."
+        );
+    }
+
     #[test]
     fn test_generate_dialect_warnings_sqlite_time_with_tz() {
         let mut schema = Schema::new("1.0".to_string());
@@ -239,6 +526,7 @@ mod tests {
             "start_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: Some(true),
+                precision: None,
             },
             false,
         ));
@@ -338,6 +626,7 @@ mod tests {
             "start_time".to_string(),
             ColumnType::TIME {
                 with_time_zone: None,
+                precision: None,
             },
             false,
         ));
@@ -350,4 +639,109 @@ mod tests {
             "TIME without TZ should not generate warnings for MySQL"
         );
     }
+
+    #[test]
+    fn test_generate_dialect_warnings_index_nulls_ordering() {
+        let mut schema = Schema::new("1.0".to_string());
+
+        let mut table = Table::new("events".to_string());
+        table.add_column(Column::new(
+            "created_at".to_string(),
+            ColumnType::TIMESTAMP {
+                with_time_zone: None,
+                precision: None,
+            },
+            true,
+        ));
+        table.add_index(Index::with_columns(
+            "idx_events_created_at".to_string(),
+            vec![IndexColumn::new("created_at")
+                .with_order(SortOrder::Desc)
+                .with_nulls(NullsOrder::Last)],
+            false,
+        ));
+        schema.add_table(table);
+
+        for dialect in [Dialect::MySQL, Dialect::SQLite] {
+            let warnings = generate_dialect_warnings(&schema, &dialect);
+            assert_eq!(warnings.len(), 1, "{:?}", dialect);
+            assert!(warnings[0].message.contains("NULLS LAST"));
+            assert!(warnings[0].message.contains("idx_events_created_at"));
+        }
+
+        assert!(generate_dialect_warnings(&schema, &Dialect::PostgreSQL).is_empty());
+    }
+
+    fn schema_with_check() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(Column::new(
+            "age".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.add_constraint(Constraint::CHECK {
+            columns: vec!["age".to_string()],
+            check_expression: "age >= 0".to_string(),
+        });
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_generate_mysql_version_warnings_check_below_8_0_16() {
+        let schema = schema_with_check();
+        let warnings =
+            generate_mysql_version_warnings(&schema, &ServerVersion::parse("5.7").unwrap());
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("age >= 0"));
+        assert!(warnings[0].message.contains("not enforced on MySQL 5.7.0"));
+        assert!(warnings[0].message.contains("8.0.16"));
+    }
+
+    #[test]
+    fn test_generate_mysql_version_warnings_check_enforced() {
+        let schema = schema_with_check();
+        let warnings =
+            generate_mysql_version_warnings(&schema, &ServerVersion::parse("8.0.16").unwrap());
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_generate_dialect_warnings_on_update_current_timestamp() {
+        let schema_with = |column_type: ColumnType| {
+            let mut schema = Schema::new("1.0".to_string());
+            let mut table = Table::new("users".to_string());
+            let mut column = Column::new("updated_at".to_string(), column_type, false);
+            column.on_update_current_timestamp = true;
+            table.add_column(column);
+            schema.add_table(table);
+            schema
+        };
+        let timestamp = ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision: None,
+        };
+        let on_update_warnings = |schema: &Schema, dialect: Dialect| {
+            generate_dialect_warnings(schema, &dialect)
+                .into_iter()
+                .filter(|w| w.message.contains("on_update_current_timestamp"))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(on_update_warnings(&schema_with(timestamp.clone()), Dialect::MySQL).is_empty());
+        for dialect in [Dialect::PostgreSQL, Dialect::SQLite] {
+            let warnings = on_update_warnings(&schema_with(timestamp.clone()), dialect);
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].message.contains("only supported in MySQL"));
+        }
+
+        let warnings = on_update_warnings(&schema_with(ColumnType::DATE), Dialect::MySQL);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0]
+            .message
+            .contains("requires a TIMESTAMP or DATETIME column"));
+    }
 }
@@ -1,7 +1,7 @@
 // ENUM定義の検証
 
 use crate::core::config::Dialect;
-use crate::core::error::{ValidationError, ValidationResult};
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
 use crate::core::schema::Schema;
 
 /// ENUM定義の検証
@@ -9,6 +9,7 @@ use crate::core::schema::Schema;
 /// - PostgreSQL以外の方言でENUMが定義されていないか確認
 /// - ENUM値が空でないか確認
 /// - ENUM値に重複がないか確認
+/// - `renamed_from` が既存のENUMや他のENUMのリネーム元と重複していないか確認
 pub fn validate_enums(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
     let mut result = ValidationResult::new();
 
@@ -31,7 +32,7 @@ pub fn validate_enums(schema: &Schema, dialect: Option<Dialect>) -> ValidationRe
         if enum_def.values.is_empty() {
             result.add_error(ValidationError::Constraint {
                 message: format!("ENUM '{}' has no values defined", enum_def.name),
-                location: None,
+                location: Some(ErrorLocation::with_enum(&enum_def.name)),
                 suggestion: Some("Define at least one ENUM value".to_string()),
             });
             continue;
@@ -42,13 +43,55 @@ pub fn validate_enums(schema: &Schema, dialect: Option<Dialect>) -> ValidationRe
             if !seen.insert(value) {
                 result.add_error(ValidationError::Constraint {
                     message: format!("ENUM '{}' has duplicate value '{}'", enum_def.name, value),
-                    location: None,
+                    location: Some(ErrorLocation::with_enum(&enum_def.name)),
                     suggestion: Some("Remove duplicate values".to_string()),
                 });
             }
         }
     }
 
+    // リネーム元の検証
+    let mut rename_sources: std::collections::BTreeMap<&str, Vec<&str>> =
+        std::collections::BTreeMap::new();
+    for enum_def in schema.enums.values() {
+        let Some(old_name) = &enum_def.renamed_from else {
+            continue;
+        };
+        rename_sources
+            .entry(old_name.as_str())
+            .or_default()
+            .push(enum_def.name.as_str());
+        if schema.enums.contains_key(old_name) {
+            result.add_error(ValidationError::Constraint {
+                message: format!(
+                    "name collision: renamed_from '{}' of ENUM '{}' conflicts with an existing ENUM",
+                    old_name, enum_def.name
+                ),
+                location: Some(ErrorLocation::with_enum(&enum_def.name)),
+                suggestion: Some(format!(
+                    "Remove the existing ENUM '{}' or change the renamed_from value",
+                    old_name
+                )),
+            });
+        }
+    }
+    for (old_name, new_names) in &rename_sources {
+        if new_names.len() > 1 {
+            result.add_error(ValidationError::Constraint {
+                message: format!(
+                    "duplicate rename: ENUM '{}' is renamed to multiple ENUMs ({})",
+                    old_name,
+                    new_names.join(", ")
+                ),
+                location: Some(ErrorLocation::with_enum(new_names[0])),
+                suggestion: Some(
+                    "Each ENUM can only be renamed once. Remove duplicate renamed_from attributes."
+                        .to_string(),
+                ),
+            });
+        }
+    }
+
     result
 }
 
@@ -57,12 +100,39 @@ mod tests {
     use super::*;
     use crate::core::schema::EnumDefinition;
 
+    #[test]
+    fn test_validate_enums_renamed_from_conflicts() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_enum(EnumDefinition {
+            name: "state".to_string(),
+            values: vec!["active".to_string()],
+            renamed_from: None,
+        });
+        for name in ["status", "user_status"] {
+            schema.add_enum(EnumDefinition {
+                name: name.to_string(),
+                values: vec!["active".to_string()],
+                renamed_from: Some("state".to_string()),
+            });
+        }
+
+        let result = validate_enums(&schema, None);
+
+        let messages: Vec<String> = result.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{:?}", messages);
+        assert!(messages[0].contains("name collision: renamed_from 'state' of ENUM 'status'"));
+        assert!(messages[2].contains(
+            "duplicate rename: ENUM 'state' is renamed to multiple ENUMs (status, user_status)"
+        ));
+    }
+
     #[test]
     fn test_validate_enums_empty_values() {
         let mut schema = Schema::new("1.0".to_string());
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec![],
+            renamed_from: None,
         });
 
         let result = validate_enums(&schema, None);
@@ -80,6 +150,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "active".to_string()],
+            renamed_from: None,
         });
 
         let result = validate_enums(&schema, None);
@@ -97,6 +168,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string()],
+            renamed_from: None,
         });
 
         let result = validate_enums(&schema, Some(Dialect::MySQL));
@@ -114,6 +186,7 @@ mod tests {
         schema.add_enum(EnumDefinition {
             name: "status".to_string(),
             values: vec!["active".to_string(), "inactive".to_string()],
+            renamed_from: None,
         });
 
         let result = validate_enums(&schema, Some(Dialect::PostgreSQL));
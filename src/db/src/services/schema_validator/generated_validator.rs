@@ -0,0 +1,148 @@
+// 生成カラムのバリデーション
+//
+// `generated` を指定したカラムの定義を検証します。
+// - 生成カラムは値を式から算出するため、デフォルト値・自動増分とは併用できない
+// - PostgreSQL の VIRTUAL 生成カラムは PostgreSQL 18 以降でのみ利用できるため警告に留める
+
+use crate::core::config::Dialect;
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
+use crate::core::schema::Schema;
+
+/// 生成カラム定義の検証
+pub fn validate_generated_columns(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        for column in &table.columns {
+            let Some(ref generated) = column.generated else {
+                continue;
+            };
+            let location = || {
+                Some(ErrorLocation::with_table_and_column(
+                    table_name,
+                    &column.name,
+                ))
+            };
+
+            if generated.expression.trim().is_empty() {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Generated column '{}' in table '{}' has an empty expression",
+                        column.name, table_name
+                    ),
+                    location: location(),
+                    suggestion: Some(
+                        "Write the expression in 'generated.expression' or remove 'generated'"
+                            .to_string(),
+                    ),
+                });
+            }
+
+            if column.default_value.is_some() {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Generated column '{}' in table '{}' cannot have a default value",
+                        column.name, table_name
+                    ),
+                    location: location(),
+                    suggestion: Some("Remove 'default_value' or 'generated'".to_string()),
+                });
+            }
+
+            if column.is_auto_increment() {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Generated column '{}' in table '{}' cannot be auto_increment",
+                        column.name, table_name
+                    ),
+                    location: location(),
+                    suggestion: Some("Remove 'auto_increment' or 'generated'".to_string()),
+                });
+            }
+
+            if matches!(dialect, Some(Dialect::PostgreSQL)) && !generated.stored {
+                result.add_warning(ValidationWarning::compatibility(
+                    format!(
+                        "Generated column '{}' in table '{}' is VIRTUAL, which requires PostgreSQL 18 or later",
+                        column.name, table_name
+                    ),
+                    location(),
+                ));
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, GeneratedColumn, Table};
+
+    fn schema_with_generated(configure: impl FnOnce(&mut Column)) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("orders".to_string());
+        table.add_column(Column::new(
+            "price".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        let mut total = Column::new(
+            "total".to_string(),
+            ColumnType::INTEGER { precision: None },
+            true,
+        );
+        total.generated = Some(GeneratedColumn::new("price * 2", true));
+        configure(&mut total);
+        table.add_column(total);
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_stored_generated_column_is_valid() {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let result = validate_generated_columns(&schema_with_generated(|_| {}), Some(dialect));
+            assert!(result.is_valid(), "{}", dialect);
+            assert!(result.warnings.is_empty(), "{}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_default_value_with_generated_is_rejected() {
+        let schema = schema_with_generated(|c| c.default_value = Some("0".to_string()));
+        let result = validate_generated_columns(&schema, None);
+
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("cannot have a default value"));
+    }
+
+    #[test]
+    fn test_auto_increment_and_empty_expression_are_rejected() {
+        let schema = schema_with_generated(|c| {
+            c.auto_increment = Some(true);
+            c.generated = Some(GeneratedColumn::new("  ", true));
+        });
+        let result = validate_generated_columns(&schema, None);
+
+        assert_eq!(result.error_count(), 2);
+    }
+
+    #[test]
+    fn test_virtual_generated_column_warns_on_postgres() {
+        let schema = schema_with_generated(|c| {
+            c.generated = Some(GeneratedColumn::new("price * 2", false));
+        });
+
+        let result = validate_generated_columns(&schema, Some(Dialect::PostgreSQL));
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("PostgreSQL 18"));
+
+        let result = validate_generated_columns(&schema, Some(Dialect::MySQL));
+        assert!(result.warnings.is_empty());
+    }
+}
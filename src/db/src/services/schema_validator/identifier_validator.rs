@@ -0,0 +1,346 @@
+// 識別子の検証
+//
+// 生成SQLに埋め込むテーブル名・カラム名・インデックス名・ENUM名・ビュー名を検証します。
+// - クォートしても安全に扱えない制御文字（NUL・改行など）を拒否
+// - 数字で始まる名前を拒否
+// - 英数字・アンダースコアと設定 `identifiers.allowed_characters` 以外の文字を拒否
+// - 方言ごとの最大長（PostgreSQL 63バイト、MySQL 64文字）を超える名前を拒否
+//
+// クォート文字（" や `）は sql_quote のエスケープで安全に扱えますが、
+// 意図しない名前を早期に検出するため、設定で許可しない限り拒否します。
+
+use crate::adapters::sql_quote::find_control_character;
+use crate::core::config::{Dialect, IdentifierConfig};
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
+use crate::core::schema::Schema;
+
+/// 識別子の検証
+///
+/// `renamed_from` に指定された旧名も生成SQLに埋め込まれるため対象とする。
+/// テーブル名・ビュー名・ENUM名の `app.users` のようなスキーマ修飾は、`.` で区切った
+/// それぞれの部分を検証する。最大長は方言が指定されている場合のみ検証する。
+pub fn validate_identifiers(
+    schema: &Schema,
+    dialect: Option<Dialect>,
+    config: &IdentifierConfig,
+) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let checker = IdentifierChecker { dialect, config };
+
+    for (table_name, table) in &schema.tables {
+        let location = || ErrorLocation::with_table(table_name.clone());
+        checker.check_qualified(&mut result, "Table name", table_name, location);
+        if let Some(old_name) = &table.renamed_from {
+            checker.check_qualified(&mut result, "Table renamed_from", old_name, location);
+        }
+
+        for column in &table.columns {
+            let location = || ErrorLocation::with_table_and_column(table_name, &column.name);
+            checker.check(&mut result, "Column name", &column.name, location);
+            if let Some(old_name) = &column.renamed_from {
+                checker.check(&mut result, "Column renamed_from", old_name, location);
+            }
+        }
+
+        for index in &table.indexes {
+            checker.check(&mut result, "Index name", &index.name, location);
+        }
+    }
+
+    for enum_def in schema.enums.values() {
+        let location = || ErrorLocation::with_enum(&enum_def.name);
+        checker.check_qualified(&mut result, "ENUM name", &enum_def.name, location);
+        if let Some(old_name) = &enum_def.renamed_from {
+            checker.check_qualified(&mut result, "ENUM renamed_from", old_name, location);
+        }
+    }
+
+    for (view_name, view) in &schema.views {
+        let location = || ErrorLocation::with_view(view_name);
+        checker.check_qualified(&mut result, "View name", view_name, location);
+        if let Some(old_name) = &view.renamed_from {
+            checker.check_qualified(&mut result, "View renamed_from", old_name, location);
+        }
+    }
+
+    result
+}
+
+/// 方言ごとの識別子の最大長（方言名・最大長・単位）
+///
+/// PostgreSQLはバイト数（NAMEDATALEN - 1、超えた分は警告なしに切り詰められる）、
+/// MySQLは文字数で制限される。SQLiteには制限がない。
+fn max_identifier_length(dialect: Dialect) -> Option<(&'static str, usize, &'static str)> {
+    match dialect {
+        Dialect::PostgreSQL => Some(("PostgreSQL", 63, "bytes")),
+        Dialect::MySQL => Some(("MySQL", 64, "characters")),
+        Dialect::SQLite => None,
+    }
+}
+
+struct IdentifierChecker<'a> {
+    dialect: Option<Dialect>,
+    config: &'a IdentifierConfig,
+}
+
+impl IdentifierChecker<'_> {
+    /// スキーマ修飾を含みうる名前を `.` で区切って検証
+    fn check_qualified(
+        &self,
+        result: &mut ValidationResult,
+        kind: &str,
+        name: &str,
+        location: impl Fn() -> ErrorLocation,
+    ) {
+        if find_control_character(name).is_some() {
+            // 制御文字は名前全体を1つのエラーとして報告する
+            self.check(result, kind, name, location);
+            return;
+        }
+        for part in name.split('.') {
+            self.check_part(result, kind, name, part, &location);
+        }
+    }
+
+    /// 名前を1つの識別子として検証
+    fn check(
+        &self,
+        result: &mut ValidationResult,
+        kind: &str,
+        name: &str,
+        location: impl Fn() -> ErrorLocation,
+    ) {
+        self.check_part(result, kind, name, name, &location);
+    }
+
+    /// 識別子の1つの部分を検証し、最初に見つかった問題をSyntaxエラーとして追加
+    fn check_part(
+        &self,
+        result: &mut ValidationResult,
+        kind: &str,
+        name: &str,
+        part: &str,
+        location: &impl Fn() -> ErrorLocation,
+    ) {
+        let Some((problem, suggestion)) = self.find_problem(part) else {
+            return;
+        };
+        result.add_error(ValidationError::Syntax {
+            message: format!("{} '{}' {}", kind, name.escape_debug(), problem),
+            location: Some(location()),
+            suggestion: Some(suggestion),
+        });
+    }
+
+    /// 識別子の問題（エラーメッセージの述部と修正方法）を返す
+    fn find_problem(&self, part: &str) -> Option<(String, String)> {
+        if let Some(c) = find_control_character(part) {
+            return Some((
+                format!("contains a control character (U+{:04X})", c as u32),
+                "Remove NUL, newline, tab and other control characters from the name".to_string(),
+            ));
+        }
+        if part.is_empty() {
+            return Some((
+                "has an empty name part".to_string(),
+                "Remove the leading, trailing or doubled '.' from the name".to_string(),
+            ));
+        }
+        if part.starts_with(|c: char| c.is_ascii_digit()) {
+            return Some((
+                "starts with a digit".to_string(),
+                "Start the name with a letter or underscore".to_string(),
+            ));
+        }
+        if let Some(c) = part.chars().find(|&c| !self.config.allows(c)) {
+            return Some((
+                format!("contains '{}', which is not allowed in identifiers", c),
+                format!(
+                    "Use only letters, digits and underscores, or add '{}' to identifiers.allowed_characters in the config file",
+                    c
+                ),
+            ));
+        }
+        if let Some((dialect_name, max, unit)) = self.dialect.and_then(max_identifier_length) {
+            let length = if unit == "bytes" {
+                part.len()
+            } else {
+                part.chars().count()
+            };
+            if length > max {
+                return Some((
+                    format!(
+                        "is {} {} long, which exceeds the {} limit of {} {}",
+                        length, unit, dialect_name, max, unit
+                    ),
+                    format!("Shorten the name to at most {} {}", max, unit),
+                ));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Index, Table};
+
+    fn schema_with_table(table_name: &str, column_name: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new(table_name.to_string());
+        table.add_column(Column::new(
+            column_name.to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(table);
+        schema
+    }
+
+    fn validate(schema: &Schema) -> ValidationResult {
+        validate_identifiers(schema, None, &IdentifierConfig::default())
+    }
+
+    #[test]
+    fn test_validate_identifiers_rejects_quote_characters_by_default() {
+        let result = validate(&schema_with_table(r#"users"; DROP TABLE x; --"#, "a`b"));
+        assert_eq!(result.error_count(), 2);
+        assert!(result.errors.iter().all(|e| e.is_syntax()));
+        assert!(result.errors[0]
+            .to_string()
+            .contains(r#"contains '"', which is not allowed in identifiers"#));
+        assert!(result.errors[1]
+            .to_string()
+            .contains("contains '`', which is not allowed in identifiers"));
+        assert!(result.errors[0]
+            .suggestion()
+            .unwrap()
+            .contains("identifiers.allowed_characters"));
+    }
+
+    #[test]
+    fn test_validate_identifiers_allows_configured_characters() {
+        let config = IdentifierConfig {
+            allowed_characters: "\"`-".to_string(),
+        };
+        let schema = schema_with_table(r#"user"s"#, "a`b-c");
+        assert!(validate_identifiers(&schema, None, &config).is_valid());
+        // 設定しても制御文字は許可しない
+        let schema = schema_with_table("users", "a\nb");
+        assert_eq!(
+            validate_identifiers(&schema, None, &config).error_count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_validate_identifiers_allows_letters_digits_and_underscore() {
+        for name in ["users", "_tmp", "order_items2", "ユーザー"] {
+            assert!(
+                validate(&schema_with_table(name, "id")).is_valid(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_identifiers_rejects_leading_digit() {
+        let result = validate(&schema_with_table("users", "2fa_secret"));
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("Column name '2fa_secret' starts with a digit"));
+    }
+
+    #[test]
+    fn test_validate_identifiers_checks_each_part_of_qualified_name() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new_in_schema("app".to_string(), "users");
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(table);
+        assert!(validate(&schema).is_valid());
+
+        let result = validate(&schema_with_table("app.1users", "id"));
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0].to_string().contains("starts with a digit"));
+    }
+
+    #[test]
+    fn test_validate_identifiers_max_length_per_dialect() {
+        let config = IdentifierConfig::default();
+        let name_64 = "a".repeat(64);
+        let schema = schema_with_table("users", &name_64);
+
+        let result = validate_identifiers(&schema, Some(Dialect::PostgreSQL), &config);
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("is 64 bytes long, which exceeds the PostgreSQL limit of 63 bytes"));
+        assert!(validate_identifiers(&schema, Some(Dialect::MySQL), &config).is_valid());
+        assert!(validate_identifiers(&schema, Some(Dialect::SQLite), &config).is_valid());
+        assert!(validate_identifiers(&schema, None, &config).is_valid());
+
+        let schema = schema_with_table("users", &"a".repeat(65));
+        let result = validate_identifiers(&schema, Some(Dialect::MySQL), &config);
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("exceeds the MySQL limit of 64 characters"));
+
+        // PostgreSQLはバイト数、MySQLは文字数で数える
+        let schema = schema_with_table("users", &"あ".repeat(30));
+        assert!(!validate_identifiers(&schema, Some(Dialect::PostgreSQL), &config).is_valid());
+        assert!(validate_identifiers(&schema, Some(Dialect::MySQL), &config).is_valid());
+    }
+
+    #[test]
+    fn test_validate_identifiers_rejects_control_characters() {
+        for name in [
+            "users\0",
+            "users\n-- x",
+            "users\r",
+            "us\ters",
+            "users\u{1b}",
+        ] {
+            let result = validate(&schema_with_table(name, "id"));
+            assert_eq!(result.error_count(), 1, "{:?}", name);
+            let error = &result.errors[0];
+            assert!(error.is_syntax());
+            assert!(error.to_string().contains("Table name"));
+            // メッセージ内の名前は制御文字をエスケープして出力する
+            assert!(error
+                .to_string()
+                .contains(&format!("'{}'", name.escape_debug())));
+        }
+    }
+
+    #[test]
+    fn test_validate_identifiers_reports_column_and_index_location() {
+        let mut schema = schema_with_table("users", "na\nme");
+        let table = schema.tables.get_mut("users").unwrap();
+        table.add_index(Index::new(
+            "idx\0users".to_string(),
+            vec!["na\nme".to_string()],
+            false,
+        ));
+
+        let result = validate(&schema);
+        assert_eq!(result.error_count(), 2);
+        assert!(result.errors[0]
+            .to_string()
+            .contains(r"Column name 'na\nme' contains a control character (U+000A)"));
+        assert_eq!(
+            result.errors[0].location().and_then(|l| l.column.clone()),
+            Some("na\nme".to_string())
+        );
+        assert!(result.errors[1]
+            .to_string()
+            .contains(r"Index name 'idx\0users' contains a control character (U+0000)"));
+    }
+}
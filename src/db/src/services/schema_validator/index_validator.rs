@@ -1,8 +1,9 @@
 // インデックスの検証
 
 use super::validation_helpers::check_column_exists;
-use crate::core::error::ValidationResult;
-use crate::core::schema::Schema;
+use crate::core::config::Dialect;
+use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
+use crate::core::schema::{IndexType, Schema};
 
 /// インデックスのカラム参照整合性検証
 pub fn validate_index_references(schema: &Schema) -> ValidationResult {
@@ -10,11 +11,11 @@ pub fn validate_index_references(schema: &Schema) -> ValidationResult {
 
     for (table_name, table) in &schema.tables {
         for index in &table.indexes {
-            for column_name in &index.columns {
+            for column in &index.columns {
                 check_column_exists(
                     table,
                     table_name,
-                    column_name,
+                    &column.name,
                     &mut result,
                     &format!("Index '{}' references", index.name),
                 );
@@ -25,9 +26,150 @@ pub fn validate_index_references(schema: &Schema) -> ValidationResult {
     result
 }
 
+/// 部分インデックスの条件式（`where`）の検証
+///
+/// - MySQLは部分インデックスに対応していないため、条件式があればエラー
+/// - 条件式が空、またはステートメント区切り（`;`）を含む場合はエラー
+pub fn validate_index_predicates(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        for index in &table.indexes {
+            let Some(ref predicate) = index.where_clause else {
+                continue;
+            };
+
+            if matches!(dialect, Some(Dialect::MySQL)) {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Index '{}' on table '{}' has a WHERE clause, but MySQL does not support partial indexes",
+                        index.name, table_name
+                    ),
+                    location: Some(ErrorLocation::with_table(table_name.clone())),
+                    suggestion: Some(
+                        "Remove 'where' from the index, or use a generated column to emulate the partial index".to_string(),
+                    ),
+                });
+            }
+
+            if predicate.trim().is_empty() {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Index '{}' on table '{}' has an empty WHERE clause",
+                        index.name, table_name
+                    ),
+                    location: Some(ErrorLocation::with_table(table_name.clone())),
+                    suggestion: Some("Write a condition in 'where' or remove it".to_string()),
+                });
+            } else if predicate.contains(';') {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "WHERE clause of index '{}' on table '{}' contains a semicolon: {}",
+                        index.name, table_name, predicate
+                    ),
+                    location: Some(ErrorLocation::with_table(table_name.clone())),
+                    suggestion: Some("Remove the semicolon from the WHERE clause".to_string()),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// インデックスの種類とプレフィックス長の検証
+///
+/// - プレフィックス長はMySQLのみ対応（0はどの方言でもエラー）
+/// - FULLTEXTはMySQL、GIN / GISTはPostgreSQLのみ対応し、HASHはSQLiteでは使用できない
+/// - FULLTEXTインデックスはユニークにできない
+pub fn validate_index_options(schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        for index in &table.indexes {
+            for column in &index.columns {
+                let Some(length) = column.length else {
+                    continue;
+                };
+                if length == 0 {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Index '{}' on table '{}' has a prefix length of 0 for column '{}'",
+                            index.name, table_name, column.name
+                        ),
+                        location: Some(ErrorLocation::with_table_and_column(
+                            table_name,
+                            &column.name,
+                        )),
+                        suggestion: Some(
+                            "Set 'length' to a positive number of characters or remove it"
+                                .to_string(),
+                        ),
+                    });
+                } else if let Some(dialect @ (Dialect::PostgreSQL | Dialect::SQLite)) = dialect {
+                    result.add_error(ValidationError::Constraint {
+                        message: format!(
+                            "Index '{}' on table '{}' has a prefix length for column '{}', but {:?} does not support prefix indexes",
+                            index.name, table_name, column.name, dialect
+                        ),
+                        location: Some(ErrorLocation::with_table_and_column(
+                            table_name,
+                            &column.name,
+                        )),
+                        suggestion: Some(
+                            "Remove 'length' from the index column, or index an expression instead"
+                                .to_string(),
+                        ),
+                    });
+                }
+            }
+
+            let Some(index_type) = index.index_type else {
+                continue;
+            };
+            if index_type == IndexType::Fulltext && index.unique {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Index '{}' on table '{}' is FULLTEXT and cannot be unique",
+                        index.name, table_name
+                    ),
+                    location: Some(ErrorLocation::with_table(table_name.clone())),
+                    suggestion: Some(
+                        "Remove 'unique' or use a BTREE index for uniqueness".to_string(),
+                    ),
+                });
+            }
+            if let Some(dialect) = dialect.filter(|d| !index_type_supported(index_type, *d)) {
+                result.add_error(ValidationError::Constraint {
+                    message: format!(
+                        "Index '{}' on table '{}' has type {}, which {:?} does not support",
+                        index.name, table_name, index_type, dialect
+                    ),
+                    location: Some(ErrorLocation::with_table(table_name.clone())),
+                    suggestion: Some(
+                        "Remove 'type' from the index to use the default BTREE index".to_string(),
+                    ),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// 方言がインデックスの種類に対応しているか
+fn index_type_supported(index_type: IndexType, dialect: Dialect) -> bool {
+    match index_type {
+        IndexType::BTree => true,
+        IndexType::Hash => dialect != Dialect::SQLite,
+        IndexType::Fulltext => dialect == Dialect::MySQL,
+        IndexType::Gin | IndexType::Gist => dialect == Dialect::PostgreSQL,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::core::schema::{Column, ColumnType, Index, Table};
+    use crate::core::schema::{Column, ColumnType, Index, IndexColumn, Table};
 
     use super::*;
 
@@ -81,4 +223,122 @@ mod tests {
 
         assert!(result.is_valid());
     }
+
+    #[test]
+    fn test_validate_index_predicates() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("orders".to_string());
+        table.add_index(
+            Index::new(
+                "idx_orders_customer_id".to_string(),
+                vec!["customer_id".to_string()],
+                true,
+            )
+            .with_where("deleted_at IS NULL"),
+        );
+        schema.add_table(table);
+
+        assert!(validate_index_predicates(&schema, Some(Dialect::PostgreSQL)).is_valid());
+        assert!(validate_index_predicates(&schema, Some(Dialect::SQLite)).is_valid());
+        let result = validate_index_predicates(&schema, Some(Dialect::MySQL));
+        assert!(!result.is_valid());
+        assert!(result.errors[0]
+            .to_string()
+            .contains("MySQL does not support partial indexes"));
+    }
+
+    #[test]
+    fn test_validate_index_predicates_malformed() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("orders".to_string());
+        table.add_index(
+            Index::new("idx_empty".to_string(), vec!["id".to_string()], false).with_where(" "),
+        );
+        table.add_index(
+            Index::new("idx_injected".to_string(), vec!["id".to_string()], false)
+                .with_where("id > 0; DROP TABLE orders"),
+        );
+        schema.add_table(table);
+
+        let result = validate_index_predicates(&schema, None);
+
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].to_string().contains("empty WHERE clause"));
+        assert!(result.errors[1].to_string().contains("semicolon"));
+    }
+
+    #[test]
+    fn test_validate_index_options_by_dialect() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("posts".to_string());
+        table.add_index(Index::with_columns(
+            "idx_body".to_string(),
+            vec![IndexColumn::new("body").with_length(191)],
+            false,
+        ));
+        table.add_index(
+            Index::new(
+                "idx_body_fulltext".to_string(),
+                vec!["body".to_string()],
+                false,
+            )
+            .with_index_type(IndexType::Fulltext),
+        );
+        schema.add_table(table);
+
+        assert!(validate_index_options(&schema, Some(Dialect::MySQL)).is_valid());
+        assert!(validate_index_options(&schema, None).is_valid());
+
+        let postgres = validate_index_options(&schema, Some(Dialect::PostgreSQL));
+        assert_eq!(postgres.errors.len(), 2);
+        assert!(postgres.errors[0]
+            .to_string()
+            .contains("PostgreSQL does not support prefix indexes"));
+        assert!(postgres.errors[1]
+            .to_string()
+            .contains("has type FULLTEXT, which PostgreSQL does not support"));
+
+        let sqlite = validate_index_options(&schema, Some(Dialect::SQLite));
+        assert_eq!(sqlite.errors.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_index_options_postgres_types() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("documents".to_string());
+        table.add_index(
+            Index::new("idx_tags".to_string(), vec!["tags".to_string()], false)
+                .with_index_type(IndexType::Gin),
+        );
+        schema.add_table(table);
+
+        assert!(validate_index_options(&schema, Some(Dialect::PostgreSQL)).is_valid());
+        assert!(!validate_index_options(&schema, Some(Dialect::MySQL)).is_valid());
+    }
+
+    #[test]
+    fn test_validate_index_options_malformed() {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("posts".to_string());
+        table.add_index(Index::with_columns(
+            "idx_zero".to_string(),
+            vec![IndexColumn::new("body").with_length(0)],
+            false,
+        ));
+        table.add_index(
+            Index::new(
+                "idx_unique_fulltext".to_string(),
+                vec!["body".to_string()],
+                true,
+            )
+            .with_index_type(IndexType::Fulltext),
+        );
+        schema.add_table(table);
+
+        let result = validate_index_options(&schema, Some(Dialect::MySQL));
+
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].to_string().contains("prefix length of 0"));
+        assert!(result.errors[1].to_string().contains("cannot be unique"));
+    }
 }
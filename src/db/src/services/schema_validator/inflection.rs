@@ -0,0 +1,258 @@
+// 英単語の単数形・複数形変換
+//
+// 命名規約チェック用の簡易的な変換です。snake_case の名前は最後の単語のみを変換し、
+// 規則で扱えない単語は設定の `naming.inflections` で例外として指定します。
+
+use std::collections::BTreeMap;
+
+/// 不規則変化する単語（単数形, 複数形）
+const IRREGULARS: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("foot", "feet"),
+    ("tooth", "teeth"),
+    ("ox", "oxen"),
+    ("analysis", "analyses"),
+    ("axis", "axes"),
+    ("crisis", "crises"),
+    ("criterion", "criteria"),
+    ("leaf", "leaves"),
+    ("life", "lives"),
+    ("wife", "wives"),
+    ("knife", "knives"),
+    ("half", "halves"),
+    ("movie", "movies"),
+    ("cookie", "cookies"),
+    ("zombie", "zombies"),
+    ("quiz", "quizzes"),
+];
+
+/// 単数形と複数形が同じ単語
+const UNCOUNTABLES: &[&str] = &[
+    "data",
+    "metadata",
+    "information",
+    "equipment",
+    "feedback",
+    "news",
+    "series",
+    "species",
+    "sheep",
+    "fish",
+    "staff",
+    "media",
+    "money",
+    "audio",
+];
+
+/// `-us` / `-as` で終わり、複数形が `-es` になる単語
+const SIBILANT_SINGULARS: &[&str] = &[
+    "status", "bus", "campus", "virus", "bonus", "census", "alias", "canvas",
+];
+
+/// 単数形・複数形の変換器
+#[derive(Debug, Clone, Copy)]
+pub struct Inflector<'a> {
+    /// 単数形から複数形への例外（設定の `naming.inflections`）
+    exceptions: &'a BTreeMap<String, String>,
+}
+
+impl<'a> Inflector<'a> {
+    /// 例外の対応表を指定して作成
+    pub fn new(exceptions: &'a BTreeMap<String, String>) -> Self {
+        Self { exceptions }
+    }
+
+    /// 複数形に変換
+    pub fn pluralize(&self, name: &str) -> String {
+        if let Some(plural) = self.exceptions.get(name) {
+            return plural.clone();
+        }
+        if self.exceptions.values().any(|plural| plural == name) {
+            return name.to_string();
+        }
+        let (prefix, word) = split_last_word(name);
+        format!("{}{}", prefix, self.pluralize_word(word))
+    }
+
+    /// 単数形に変換
+    pub fn singularize(&self, name: &str) -> String {
+        if let Some((singular, _)) = self.exceptions.iter().find(|(_, plural)| *plural == name) {
+            return singular.clone();
+        }
+        if self.exceptions.contains_key(name) {
+            return name.to_string();
+        }
+        let (prefix, word) = split_last_word(name);
+        format!("{}{}", prefix, self.singularize_word(word))
+    }
+
+    fn pluralize_word(&self, word: &str) -> String {
+        if let Some(plural) = self.exceptions.get(word) {
+            return plural.clone();
+        }
+        let lower = word.to_ascii_lowercase();
+        if self.exceptions.values().any(|plural| *plural == lower) || is_uncountable(&lower) {
+            return word.to_string();
+        }
+        if let Some((_, plural)) = IRREGULARS.iter().find(|(singular, _)| *singular == lower) {
+            return plural.to_string();
+        }
+        if IRREGULARS.iter().any(|(_, plural)| *plural == lower) {
+            return word.to_string();
+        }
+
+        if ["s", "x", "z", "ch", "sh"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+        {
+            return format!("{}es", word);
+        }
+        if let Some(stem) = lower.strip_suffix('y') {
+            if stem.ends_with(|c: char| !is_vowel(c)) {
+                return format!("{}ies", &word[..word.len() - 1]);
+            }
+        }
+        format!("{}s", word)
+    }
+
+    fn singularize_word(&self, word: &str) -> String {
+        if let Some((singular, _)) = self.exceptions.iter().find(|(_, plural)| *plural == word) {
+            return singular.clone();
+        }
+        let lower = word.to_ascii_lowercase();
+        if self.exceptions.contains_key(&lower) || is_uncountable(&lower) {
+            return word.to_string();
+        }
+        if let Some((singular, _)) = IRREGULARS.iter().find(|(_, plural)| *plural == lower) {
+            return singular.to_string();
+        }
+        if IRREGULARS.iter().any(|(singular, _)| *singular == lower) {
+            return word.to_string();
+        }
+
+        let keep = |len: usize| word[..len].to_string();
+        if SIBILANT_SINGULARS.iter().any(|s| lower.ends_with(s)) {
+            return word.to_string();
+        }
+        if let Some(stem) = lower.strip_suffix("es") {
+            if SIBILANT_SINGULARS.iter().any(|s| stem.ends_with(s)) {
+                return keep(stem.len());
+            }
+        }
+        if ["ss", "us", "is"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+        {
+            return word.to_string();
+        }
+        if let Some(stem) = lower.strip_suffix("ies") {
+            if stem.ends_with(|c: char| !is_vowel(c)) {
+                return format!("{}y", keep(stem.len()));
+            }
+        }
+        if ["sses", "xes", "ches", "shes"]
+            .iter()
+            .any(|suffix| lower.ends_with(suffix))
+        {
+            return keep(word.len() - 2);
+        }
+        if lower.len() > 1 && lower.ends_with('s') {
+            return keep(word.len() - 1);
+        }
+        word.to_string()
+    }
+}
+
+/// snake_case の名前を最後の単語とそれ以前に分割（`order_items` → (`order_`, `items`)）
+fn split_last_word(name: &str) -> (&str, &str) {
+    match name.rfind('_') {
+        Some(pos) => name.split_at(pos + 1),
+        None => ("", name),
+    }
+}
+
+fn is_uncountable(word: &str) -> bool {
+    UNCOUNTABLES.contains(&word)
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_exceptions() -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    #[test]
+    fn test_regular_nouns() {
+        let exceptions = no_exceptions();
+        let inflector = Inflector::new(&exceptions);
+
+        for (singular, plural) in [
+            ("user", "users"),
+            ("category", "categories"),
+            ("address", "addresses"),
+            ("box", "boxes"),
+            ("branch", "branches"),
+            ("wish", "wishes"),
+            ("status", "statuses"),
+            ("day", "days"),
+            ("house", "houses"),
+            ("order_item", "order_items"),
+        ] {
+            assert_eq!(inflector.pluralize(singular), plural);
+            assert_eq!(inflector.singularize(plural), singular);
+            // 単数形はそのまま
+            assert_eq!(inflector.singularize(singular), singular);
+        }
+    }
+
+    #[test]
+    fn test_irregular_and_uncountable_nouns() {
+        let exceptions = no_exceptions();
+        let inflector = Inflector::new(&exceptions);
+
+        for (singular, plural) in [
+            ("person", "people"),
+            ("child", "children"),
+            ("analysis", "analyses"),
+            ("movie", "movies"),
+            ("sales_person", "sales_people"),
+        ] {
+            assert_eq!(inflector.pluralize(singular), plural);
+            assert_eq!(inflector.singularize(plural), singular);
+            assert_eq!(inflector.pluralize(plural), plural);
+        }
+
+        for word in ["data", "metadata", "news", "series", "audit_data"] {
+            assert_eq!(inflector.pluralize(word), word);
+            assert_eq!(inflector.singularize(word), word);
+        }
+    }
+
+    #[test]
+    fn test_exceptions_override_rules() {
+        let exceptions = BTreeMap::from([
+            ("cactus".to_string(), "cacti".to_string()),
+            ("media_item".to_string(), "media".to_string()),
+        ]);
+        let inflector = Inflector::new(&exceptions);
+
+        assert_eq!(inflector.pluralize("cactus"), "cacti");
+        assert_eq!(inflector.singularize("cacti"), "cactus");
+        assert_eq!(inflector.pluralize("garden_cactus"), "garden_cacti");
+        assert_eq!(inflector.singularize("garden_cacti"), "garden_cactus");
+        // 名前全体に一致する例外は単語単位の規則より優先
+        assert_eq!(inflector.singularize("media"), "media_item");
+        assert_eq!(inflector.pluralize("media_item"), "media");
+    }
+}
@@ -0,0 +1,281 @@
+// 単一ファイル検証（エディタ連携向け）
+//
+// 1ファイル分の部分スキーマを検証する際、他ファイルに定義されている可能性のある
+// 参照（外部キーの参照先、ENUM、ビューの依存先）を保留扱いにします。
+
+use super::constraint_validator;
+use crate::core::error::{ErrorLocation, ValidationResult};
+use crate::core::schema::{ColumnType, Constraint, EnumDefinition, Schema};
+
+/// 単一ファイルでは判定できないため保留した検証ルール
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeferredRule {
+    /// 外部キーの参照先テーブル
+    ForeignKeyTarget,
+    /// ENUM型の参照
+    EnumReference,
+    /// ビューの依存先
+    ViewDependency,
+}
+
+impl DeferredRule {
+    /// ルール名（出力用）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeferredRule::ForeignKeyTarget => "foreign_key_target",
+            DeferredRule::EnumReference => "enum_reference",
+            DeferredRule::ViewDependency => "view_dependency",
+        }
+    }
+}
+
+/// 保留した検証
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredCheck {
+    /// 保留したルール
+    pub rule: DeferredRule,
+    /// 参照先の名前（テーブル名・ENUM名・ビュー名）
+    pub target: String,
+    /// 説明
+    pub message: String,
+    /// 参照元の位置
+    pub location: ErrorLocation,
+}
+
+/// 他ファイルへの参照を保留扱いにした検証用スキーマを作成
+///
+/// 返されるスキーマでは、ファイル内に定義のない参照先への外部キーとビューの依存先を取り除き、
+/// 未定義のENUMには空のプレースホルダーを定義します。取り除いた外部キーの参照元側の検証は
+/// `result` に追加します。
+///
+/// # Returns
+///
+/// (検証用スキーマ, 保留した検証のリスト)
+pub fn defer_cross_file_references(
+    schema: &Schema,
+    result: &mut ValidationResult,
+) -> (Schema, Vec<DeferredCheck>) {
+    let mut local = schema.clone();
+    let mut deferred = Vec::new();
+
+    for (table_name, table) in local.tables.iter_mut() {
+        table.constraints.retain(|constraint| {
+            let Constraint::FOREIGN_KEY {
+                referenced_table,
+                referenced_columns,
+                ..
+            } = constraint
+            else {
+                return true;
+            };
+            if schema.has_table(referenced_table) || schema.is_external_table(referenced_table) {
+                return true;
+            }
+            deferred.push(DeferredCheck {
+                rule: DeferredRule::ForeignKeyTarget,
+                target: referenced_table.clone(),
+                message: format!(
+                    "Foreign key target '{}({})' is not defined in this file",
+                    referenced_table,
+                    referenced_columns.join(", ")
+                ),
+                location: ErrorLocation::with_table(table_name.clone()),
+            });
+            false
+        });
+
+        for column in &table.columns {
+            if let ColumnType::Enum { name } = &column.column_type {
+                if !schema.enums.contains_key(name) {
+                    deferred.push(DeferredCheck {
+                        rule: DeferredRule::EnumReference,
+                        target: name.clone(),
+                        message: format!("ENUM '{}' is not defined in this file", name),
+                        location: ErrorLocation::with_table_and_column(table_name, &column.name),
+                    });
+                }
+            }
+        }
+    }
+
+    // 取り除いた外部キーも参照元側（カラムの存在・カラム数）はファイル内で検証できる
+    for (table_name, table) in &schema.tables {
+        for constraint in &table.constraints {
+            if let Constraint::FOREIGN_KEY {
+                columns,
+                referenced_table,
+                referenced_columns,
+                ..
+            } = constraint
+            {
+                if !schema.has_table(referenced_table)
+                    && !schema.is_external_table(referenced_table)
+                {
+                    constraint_validator::validate_foreign_key_columns(
+                        table,
+                        table_name,
+                        columns,
+                        referenced_columns,
+                        result,
+                    );
+                }
+            }
+        }
+    }
+
+    for check in &deferred {
+        if check.rule == DeferredRule::EnumReference && !local.enums.contains_key(&check.target) {
+            local.enums.insert(
+                check.target.clone(),
+                EnumDefinition {
+                    name: check.target.clone(),
+                    values: Vec::new(),
+                    renamed_from: None,
+                },
+            );
+        }
+    }
+
+    for (view_name, view) in local.views.iter_mut() {
+        view.depends_on.retain(|dep| {
+            if schema.tables.contains_key(dep) || schema.views.contains_key(dep) {
+                return true;
+            }
+            deferred.push(DeferredCheck {
+                rule: DeferredRule::ViewDependency,
+                target: dep.clone(),
+                message: format!("View dependency '{}' is not defined in this file", dep),
+                location: ErrorLocation::with_view(view_name),
+            });
+            false
+        });
+    }
+
+    (local, deferred)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, Table, View};
+
+    fn posts_schema() -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut posts = Table::new("posts".to_string());
+        posts.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_column(Column::new(
+            "user_id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        posts.add_column(Column::new(
+            "status".to_string(),
+            ColumnType::Enum {
+                name: "post_status".to_string(),
+            },
+            false,
+        ));
+        posts.add_constraint(Constraint::PRIMARY_KEY {
+            columns: vec!["id".to_string()],
+        });
+        posts.add_constraint(Constraint::FOREIGN_KEY {
+            columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        schema.add_table(posts);
+
+        let mut view = View::new(
+            "post_authors".to_string(),
+            "SELECT * FROM posts JOIN users ON users.id = posts.user_id".to_string(),
+        );
+        view.depends_on = vec!["posts".to_string(), "users".to_string()];
+        schema.add_view(view);
+        schema
+    }
+
+    #[test]
+    fn test_defer_cross_file_references() {
+        let schema = posts_schema();
+        let mut result = ValidationResult::new();
+
+        let (local, deferred) = defer_cross_file_references(&schema, &mut result);
+
+        let rules: Vec<(DeferredRule, &str)> = deferred
+            .iter()
+            .map(|d| (d.rule, d.target.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (DeferredRule::ForeignKeyTarget, "users"),
+                (DeferredRule::EnumReference, "post_status"),
+                (DeferredRule::ViewDependency, "users"),
+            ]
+        );
+        assert!(result.is_valid());
+
+        // 保留した参照は検証用スキーマから取り除かれる
+        let posts = local.get_table("posts").unwrap();
+        assert!(!posts
+            .constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::FOREIGN_KEY { .. })));
+        assert_eq!(local.views["post_authors"].depends_on, vec!["posts"]);
+        assert!(local.enums.contains_key("post_status"));
+    }
+
+    #[test]
+    fn test_deferred_foreign_key_still_checks_source_columns() {
+        let mut schema = posts_schema();
+        schema
+            .tables
+            .get_mut("posts")
+            .unwrap()
+            .add_constraint(Constraint::FOREIGN_KEY {
+                columns: vec!["author_id".to_string()],
+                referenced_table: "authors".to_string(),
+                referenced_columns: vec!["id".to_string(), "tenant_id".to_string()],
+                on_delete: None,
+                on_update: None,
+            });
+        let mut result = ValidationResult::new();
+
+        defer_cross_file_references(&schema, &mut result);
+
+        assert_eq!(result.error_count(), 2);
+        assert!(result.errors[0].to_string().contains("author_id"));
+        assert!(result.errors[1]
+            .to_string()
+            .contains("has 1 column(s) but references 2 column(s)"));
+    }
+
+    #[test]
+    fn test_references_defined_in_file_are_not_deferred() {
+        let mut schema = posts_schema();
+        let mut users = Table::new("users".to_string());
+        users.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        schema.add_table(users);
+        schema.add_enum(EnumDefinition {
+            name: "post_status".to_string(),
+            values: vec!["draft".to_string()],
+            renamed_from: None,
+        });
+        let mut result = ValidationResult::new();
+
+        let (local, deferred) = defer_cross_file_references(&schema, &mut result);
+
+        assert!(deferred.is_empty());
+        assert_eq!(local, schema);
+    }
+}
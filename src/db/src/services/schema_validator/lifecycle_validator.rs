@@ -0,0 +1,98 @@
+// カラムのライフサイクル（expand/contract）のバリデーション
+//
+// expand/contract による移行では、新旧のアプリケーションが同時に動作する。
+// - `expand` のカラムは、まだ値を書き込まないアプリケーションからのINSERTを受け付ける必要がある
+// - `contract_pending` のカラムは、もう値を書き込まないアプリケーションからのINSERTを受け付ける必要がある
+// どちらも NOT NULL でデフォルト値がない場合はINSERTが失敗するため警告する。
+
+use crate::core::error::{ErrorLocation, ValidationResult, ValidationWarning};
+use crate::core::schema::{Column, ColumnLifecycle, Schema};
+
+/// カラムのライフサイクルの検証
+pub fn validate_column_lifecycle(schema: &Schema) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        for column in &table.columns {
+            let reason = match column.lifecycle {
+                Some(ColumnLifecycle::Expand) => "do not write it yet",
+                Some(ColumnLifecycle::ContractPending) => "no longer write it",
+                _ => continue,
+            };
+            if !requires_value_on_insert(column) {
+                continue;
+            }
+            result.add_warning(ValidationWarning::compatibility(
+                format!(
+                    "Column '{}.{}' is marked 'lifecycle: {}' but is NOT NULL without a default; inserts from application versions that {} will fail",
+                    table_name,
+                    column.name,
+                    column.lifecycle.unwrap(),
+                    reason
+                ),
+                Some(ErrorLocation::with_table_and_column(table_name, &column.name)),
+            ));
+        }
+    }
+
+    result
+}
+
+/// INSERT時に値の指定が必須のカラムかどうか
+fn requires_value_on_insert(column: &Column) -> bool {
+    !column.nullable
+        && column.default_value.is_none()
+        && !column.is_auto_increment()
+        && !column.is_generated()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{ColumnType, Table};
+
+    fn schema_with(column: Column) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("users".to_string());
+        table.add_column(column);
+        schema.add_table(table);
+        schema
+    }
+
+    fn column(lifecycle: ColumnLifecycle, nullable: bool) -> Column {
+        let mut column = Column::new("email".to_string(), ColumnType::TEXT, nullable);
+        column.lifecycle = Some(lifecycle);
+        column
+    }
+
+    #[test]
+    fn test_not_null_expand_and_contract_pending_columns_warn() {
+        for (lifecycle, expected) in [
+            (ColumnLifecycle::Expand, "do not write it yet"),
+            (ColumnLifecycle::ContractPending, "no longer write it"),
+        ] {
+            let result = validate_column_lifecycle(&schema_with(column(lifecycle, false)));
+
+            assert!(result.is_valid());
+            assert_eq!(result.warning_count(), 1, "{}", lifecycle);
+            assert!(result.warnings[0].message.contains("users.email"));
+            assert!(result.warnings[0].message.contains(expected));
+        }
+    }
+
+    #[test]
+    fn test_nullable_default_and_active_columns_do_not_warn() {
+        let mut with_default = column(ColumnLifecycle::Expand, false);
+        with_default.default_value = Some("''".to_string());
+
+        for column in [
+            column(ColumnLifecycle::Expand, true),
+            column(ColumnLifecycle::ContractPending, true),
+            column(ColumnLifecycle::Active, false),
+            with_default,
+        ] {
+            let result = validate_column_lifecycle(&schema_with(column));
+            assert!(result.warnings.is_empty());
+        }
+    }
+}
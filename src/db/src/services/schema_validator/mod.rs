@@ -5,30 +5,106 @@
 
 mod column_type_validator;
 mod constraint_validator;
+mod default_validator;
 mod dialect_validator;
 mod enum_validator;
+mod generated_validator;
+mod identifier_validator;
 mod index_validator;
+mod inflection;
+mod isolated_validator;
+mod lifecycle_validator;
+mod naming_validator;
 mod rename_validator;
+mod schema_builder;
+pub mod schema_fixes;
 mod table_validator;
 mod validation_helpers;
 mod view_validator;
 
-use crate::core::config::Dialect;
+use crate::core::config::{Dialect, IdentifierConfig, NamingConfig};
 use crate::core::error::{ValidationError, ValidationResult, ValidationWarning};
 use crate::core::schema::Schema;
+use crate::core::server_version::ServerVersion;
+
+pub use isolated_validator::{DeferredCheck, DeferredRule};
+pub use schema_builder::SchemaBuilderExt;
+
+/// 検証のスコープ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationScope {
+    /// スキーマ全体（全ファイルをマージしたスキーマ）
+    Full,
+    /// 単一ファイル（他ファイルへの参照の検証は保留する）
+    IsolatedFile,
+}
+
+/// スコープ付き検証の結果
+#[derive(Debug, Clone)]
+pub struct ScopedValidationResult {
+    /// 実行した検証の結果
+    pub result: ValidationResult,
+    /// 保留した検証（`ValidationScope::IsolatedFile` のみ）
+    pub deferred: Vec<DeferredCheck>,
+}
 
 /// スキーマバリデーターサービス
 ///
 /// スキーマ定義の検証を行います。
 #[derive(Debug, Clone)]
 pub struct SchemaValidatorService {
-    // 将来的な拡張のためのフィールドを予約
+    /// サポート対象とするMySQLの最も古いバージョン（`mysql.minimum_version`）
+    mysql_minimum_version: Option<ServerVersion>,
+
+    /// テーブルに必須のメタデータキー（`required_metadata`）
+    required_metadata: Vec<String>,
+
+    /// 命名規約チェックの設定（`naming`）
+    naming: NamingConfig,
+
+    /// 識別子の検証設定（`identifiers`）
+    identifiers: IdentifierConfig,
 }
 
 impl SchemaValidatorService {
     /// 新しいSchemaValidatorServiceを作成
     pub fn new() -> Self {
-        Self {}
+        Self {
+            mysql_minimum_version: None,
+            required_metadata: Vec::new(),
+            naming: NamingConfig::default(),
+            identifiers: IdentifierConfig::default(),
+        }
+    }
+
+    /// サポート対象とするMySQLの最も古いバージョンを設定
+    ///
+    /// MySQL方言での検証時に、このバージョンで強制されないCHECK制約を警告します。
+    pub fn with_mysql_minimum_version(mut self, version: Option<ServerVersion>) -> Self {
+        self.mysql_minimum_version = version;
+        self
+    }
+
+    /// テーブルに必須のメタデータキーを設定
+    pub fn with_required_metadata(mut self, keys: Vec<String>) -> Self {
+        self.required_metadata = keys;
+        self
+    }
+
+    /// 命名規約チェックの設定
+    ///
+    /// `enabled` の場合、テーブル名・外部キーカラム名の規約違反を警告します。
+    pub fn with_naming(mut self, naming: NamingConfig) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// 識別子の検証設定
+    ///
+    /// `allowed_characters` の文字を、英数字とアンダースコアに加えて識別子に使用できるようにします。
+    pub fn with_identifiers(mut self, identifiers: IdentifierConfig) -> Self {
+        self.identifiers = identifiers;
+        self
     }
 
     /// スキーマ定義の全体的な検証を実行
@@ -54,15 +130,66 @@ impl SchemaValidatorService {
         self.validate_internal(schema, Some(dialect))
     }
 
+    /// スコープを指定して検証を実行
+    ///
+    /// `ValidationScope::IsolatedFile` では1ファイル分の部分スキーマを受け取り、
+    /// ファイル内で完結する検証のみを実行します。ファイル内に定義のない外部キーの参照先・
+    /// ENUM・ビューの依存先の検証は `deferred` として返します。
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - 検証対象のスキーマ（IsolatedFileでは部分スキーマ）
+    /// * `dialect` - データベース方言
+    /// * `scope` - 検証のスコープ
+    pub fn validate_scoped(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+        scope: ValidationScope,
+    ) -> ScopedValidationResult {
+        match scope {
+            ValidationScope::Full => ScopedValidationResult {
+                result: self.validate_internal(schema, dialect),
+                deferred: Vec::new(),
+            },
+            ValidationScope::IsolatedFile => {
+                let mut result = ValidationResult::new();
+                let (local, deferred) =
+                    isolated_validator::defer_cross_file_references(schema, &mut result);
+                // ENUM定義の検証は保留用のプレースホルダーを含まない元のスキーマで行う
+                result.merge(self.validate_rules(&local, schema, dialect));
+                ScopedValidationResult { result, deferred }
+            }
+        }
+    }
+
     fn validate_internal(&self, schema: &Schema, dialect: Option<Dialect>) -> ValidationResult {
+        self.validate_rules(schema, schema, dialect)
+    }
+
+    /// 検証ルールを実行
+    ///
+    /// `enum_source` はENUM定義自体の検証対象（通常は `schema` と同じ）
+    fn validate_rules(
+        &self,
+        schema: &Schema,
+        enum_source: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
         let mut result = ValidationResult::new();
 
+        // 識別子の検証（制御文字・使用できない文字・方言ごとの最大長）
+        result.merge(self.validate_identifiers(enum_source, dialect, &self.identifiers));
+
         // カテゴリ別に検証を実行（Task 5.1）
-        result.merge(self.validate_enums(schema, dialect));
+        result.merge(self.validate_enums(enum_source, dialect));
 
         // ビュー定義の検証
         result.merge(self.validate_views(schema));
 
+        // 外部テーブル宣言の検証
+        result.merge(self.validate_external_tables(schema));
+
         // 空のスキーマは有効
         if schema.table_count() == 0 && schema.enums.is_empty() && schema.view_count() == 0 {
             return result;
@@ -71,18 +198,55 @@ impl SchemaValidatorService {
         // テーブル構造の検証
         result.merge_all([
             self.validate_table_structure(schema),
+            self.validate_populate_from(schema),
             self.validate_duplicate_column_names(schema),
             self.validate_column_types(schema),
+            self.validate_default_expressions(schema, dialect),
+            self.validate_current_timestamp_defaults(schema, dialect),
+            self.validate_generated_columns(schema, dialect),
+            self.validate_column_lifecycle(schema),
             self.validate_primary_keys(schema),
             self.validate_index_references(schema),
+            self.validate_index_predicates(schema, dialect),
+            self.validate_index_options(schema, dialect),
+            self.validate_table_schemas(schema, dialect),
             self.validate_constraint_references(schema),
             self.validate_check_expressions(schema),
             self.validate_duplicate_unique_constraints(schema),
+            self.validate_required_metadata(schema, &self.required_metadata),
         ]);
 
+        if self.naming.enabled {
+            result.merge(self.validate_naming_conventions(schema, &self.naming));
+        }
+
+        if let (Some(Dialect::MySQL), Some(minimum_version)) =
+            (dialect, self.mysql_minimum_version.as_ref())
+        {
+            for warning in
+                dialect_validator::generate_mysql_version_warnings(schema, minimum_version)
+            {
+                result.add_warning(warning);
+            }
+        }
+
         result
     }
 
+    /// 識別子の検証
+    ///
+    /// テーブル・カラム・インデックス・ENUM・ビューの名前が、制御文字や英数字・アンダースコア・
+    /// `identifiers.allowed_characters` 以外の文字を含まず、数字で始まらないことを確認します。
+    /// 方言が指定されている場合は最大長（PostgreSQL 63バイト、MySQL 64文字）も確認します。
+    pub fn validate_identifiers(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+        identifiers: &IdentifierConfig,
+    ) -> ValidationResult {
+        identifier_validator::validate_identifiers(schema, dialect, identifiers)
+    }
+
     /// ENUM定義の検証
     ///
     /// - PostgreSQL以外の方言でENUMが定義されていないか確認
@@ -97,30 +261,43 @@ impl SchemaValidatorService {
         table_validator::validate_table_structure(schema)
     }
 
-    /// 重複カラム名の検証
-    fn validate_duplicate_column_names(&self, schema: &Schema) -> ValidationResult {
-        let mut result = ValidationResult::new();
+    /// populate_from の検証（SELECT文のみ許可）
+    pub fn validate_populate_from(&self, schema: &Schema) -> ValidationResult {
+        table_validator::validate_populate_from(schema)
+    }
 
-        for (table_name, table) in &schema.tables {
-            let mut seen = std::collections::HashSet::new();
-            for column in &table.columns {
-                if !seen.insert(&column.name) {
-                    result.add_error(ValidationError::Constraint {
-                        message: format!(
-                            "Table '{}' has duplicate column name '{}'",
-                            table_name, column.name
-                        ),
-                        location: Some(crate::core::error::ErrorLocation::with_table_and_column(
-                            table_name,
-                            &column.name,
-                        )),
-                        suggestion: Some("Remove the duplicate column definition".to_string()),
-                    });
-                }
-            }
-        }
+    /// 必須メタデータキーの検証
+    ///
+    /// 指定したキーが `metadata` にないテーブルをエラーとして報告します。
+    pub fn validate_required_metadata(
+        &self,
+        schema: &Schema,
+        required_keys: &[String],
+    ) -> ValidationResult {
+        table_validator::validate_required_metadata(schema, required_keys)
+    }
 
-        result
+    /// 命名規約の検証
+    ///
+    /// 外部キーカラム名・テーブル名の単数形/複数形・中間テーブル名の規約違反を警告として報告します。
+    pub fn validate_naming_conventions(
+        &self,
+        schema: &Schema,
+        naming: &NamingConfig,
+    ) -> ValidationResult {
+        naming_validator::validate_naming_conventions(schema, naming)
+    }
+
+    /// 外部テーブル宣言の検証
+    ///
+    /// `external_tables` と `tables` の両方に同じテーブルが定義されていないか確認します。
+    pub fn validate_external_tables(&self, schema: &Schema) -> ValidationResult {
+        table_validator::validate_external_tables(schema)
+    }
+
+    /// 重複カラム名の検証
+    fn validate_duplicate_column_names(&self, schema: &Schema) -> ValidationResult {
+        table_validator::validate_duplicate_column_names(schema)
     }
 
     /// カラム型の検証
@@ -132,6 +309,49 @@ impl SchemaValidatorService {
         column_type_validator::validate_column_types(schema)
     }
 
+    /// デフォルト値の式の検証
+    ///
+    /// 同じテーブルのカラムを参照するデフォルト値を、PostgreSQL・SQLiteではエラー、
+    /// MySQLでは括弧で囲まれていれば警告として報告します。
+    pub fn validate_default_expressions(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        default_validator::validate_default_expressions(schema, dialect)
+    }
+
+    /// 現在日時のデフォルト値の検証
+    ///
+    /// `CURRENT_TIMESTAMP`（および `now()` などの同等の表記）を日時型以外のカラムに
+    /// 指定している場合を、方言ごとに許可される型に基づいてエラーとして報告します。
+    pub fn validate_current_timestamp_defaults(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        default_validator::validate_current_timestamp_defaults(schema, dialect)
+    }
+
+    /// 生成カラムの検証
+    ///
+    /// デフォルト値・自動増分との併用をエラー、PostgreSQLのVIRTUAL生成カラムを警告として報告します。
+    pub fn validate_generated_columns(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        generated_validator::validate_generated_columns(schema, dialect)
+    }
+
+    /// カラムのライフサイクルの検証
+    ///
+    /// `lifecycle: expand` / `contract_pending` のカラムが NOT NULL でデフォルト値がない場合、
+    /// 新旧のアプリケーションが同時に動作する間にINSERTが失敗するため警告として報告します。
+    pub fn validate_column_lifecycle(&self, schema: &Schema) -> ValidationResult {
+        lifecycle_validator::validate_column_lifecycle(schema)
+    }
+
     /// プライマリキーの存在確認
     pub fn validate_primary_keys(&self, schema: &Schema) -> ValidationResult {
         constraint_validator::validate_primary_keys(schema)
@@ -142,6 +362,40 @@ impl SchemaValidatorService {
         index_validator::validate_index_references(schema)
     }
 
+    /// 部分インデックスの条件式の検証
+    ///
+    /// MySQLでは部分インデックスを定義できないため、条件式をエラーとして報告します。
+    pub fn validate_index_predicates(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        index_validator::validate_index_predicates(schema, dialect)
+    }
+
+    /// インデックスの種類とプレフィックス長の検証
+    ///
+    /// 方言が対応していない種類（SQLiteのFULLTEXTなど）や、MySQL以外での
+    /// プレフィックス長をエラーとして報告します。
+    pub fn validate_index_options(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        index_validator::validate_index_options(schema, dialect)
+    }
+
+    /// テーブルのスキーマ指定の検証
+    ///
+    /// `schema` はPostgreSQL専用のため、他の方言ではエラーとして報告します。
+    pub fn validate_table_schemas(
+        &self,
+        schema: &Schema,
+        dialect: Option<Dialect>,
+    ) -> ValidationResult {
+        dialect_validator::validate_table_schemas(schema, dialect)
+    }
+
     /// 制約のカラム/テーブル参照整合性検証
     pub fn validate_constraint_references(&self, schema: &Schema) -> ValidationResult {
         constraint_validator::validate_constraint_references(schema)
@@ -246,13 +500,62 @@ impl crate::services::traits::SchemaValidator for SchemaValidatorService {
     ) -> ValidationResult {
         self.validate_renames_with_old_schema(old_schema, new_schema)
     }
+
+    fn validate_required_metadata(
+        &self,
+        schema: &Schema,
+        required_keys: &[String],
+    ) -> ValidationResult {
+        self.validate_required_metadata(schema, required_keys)
+    }
+
+    fn validate_naming_conventions(
+        &self,
+        schema: &Schema,
+        naming: &NamingConfig,
+    ) -> ValidationResult {
+        self.validate_naming_conventions(schema, naming)
+    }
+
+    fn validate_external_tables(&self, schema: &Schema) -> ValidationResult {
+        self.validate_external_tables(schema)
+    }
+
+    fn validate_index_predicates(&self, schema: &Schema, dialect: Dialect) -> ValidationResult {
+        self.validate_index_predicates(schema, Some(dialect))
+    }
+
+    fn validate_default_expressions(&self, schema: &Schema, dialect: Dialect) -> ValidationResult {
+        self.validate_default_expressions(schema, Some(dialect))
+    }
+
+    fn validate_current_timestamp_defaults(
+        &self,
+        schema: &Schema,
+        dialect: Dialect,
+    ) -> ValidationResult {
+        self.validate_current_timestamp_defaults(schema, Some(dialect))
+    }
+
+    fn validate_identifiers(
+        &self,
+        schema: &Schema,
+        dialect: Dialect,
+        identifiers: &IdentifierConfig,
+    ) -> ValidationResult {
+        self.validate_identifiers(schema, Some(dialect), identifiers)
+    }
+
+    fn validate_generated_columns(&self, schema: &Schema, dialect: Dialect) -> ValidationResult {
+        self.validate_generated_columns(schema, Some(dialect))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::config::Dialect;
-    use crate::core::schema::{Column, ColumnType, Constraint, EnumDefinition, Index, Table};
+    use crate::core::schema::{ColumnType, SchemaBuilder};
 
     #[test]
     fn test_new_service() {
@@ -267,28 +570,16 @@ mod tests {
 
     #[test]
     fn test_validate_collects_all_errors() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        // Error 1: ENUM with no values
-        schema.add_enum(EnumDefinition {
-            name: "empty_enum".to_string(),
-            values: vec![],
-        });
-
-        // Error 2: Table without primary key
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        // Error 3: Index referencing non-existent column
-        table.add_index(Index::new(
-            "idx_email".to_string(),
-            vec!["nonexistent".to_string()],
-            false,
-        ));
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            // Error 1: ENUM with no values
+            .enum_type("empty_enum", &[])
+            // Error 2: Table without primary key
+            .table("users", |t| {
+                t.integer("id");
+                // Error 3: Index referencing non-existent column
+                t.index("idx_email", &["nonexistent"]);
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
@@ -298,22 +589,73 @@ mod tests {
         assert!(result.error_count() >= 3);
     }
 
+    /// 単一ファイル検証用の部分スキーマ（usersは別ファイルに定義されている想定）
+    fn create_isolated_file_schema() -> Schema {
+        SchemaBuilder::new("1.0")
+            .table("posts", |t| {
+                t.integer("id");
+                t.integer("id");
+                t.integer("user_id");
+                t.decimal("price", 5, 10);
+                t.index("idx_missing", &["missing"]);
+                t.check("", &["price"]);
+                t.foreign_key(&["user_id"], "users", &["id"]);
+            })
+            .build_unvalidated()
+    }
+
+    #[test]
+    fn test_validate_scoped_isolated_file_runs_local_rules() {
+        let validator = SchemaValidatorService::new();
+        let scoped = validator.validate_scoped(
+            &create_isolated_file_schema(),
+            Some(Dialect::PostgreSQL),
+            ValidationScope::IsolatedFile,
+        );
+
+        let messages: Vec<String> = scoped.result.errors.iter().map(|e| e.to_string()).collect();
+        let has = |needle: &str| messages.iter().any(|m| m.contains(needle));
+        assert!(has("duplicate column name 'id'"), "{:?}", messages);
+        assert!(
+            has("scale (10) greater than precision (5)"),
+            "{:?}",
+            messages
+        );
+        assert!(has("'missing'"), "{:?}", messages);
+        assert!(has("check_expression"), "{:?}", messages);
+        assert!(has("primary key"), "{:?}", messages);
+        // 他ファイルへの参照はエラーにならない
+        assert!(!has("users"), "{:?}", messages);
+
+        assert_eq!(scoped.deferred.len(), 1);
+        assert_eq!(scoped.deferred[0].rule, DeferredRule::ForeignKeyTarget);
+        assert_eq!(scoped.deferred[0].target, "users");
+    }
+
+    #[test]
+    fn test_validate_scoped_full_reports_cross_file_references() {
+        let validator = SchemaValidatorService::new();
+        let scoped = validator.validate_scoped(
+            &create_isolated_file_schema(),
+            Some(Dialect::PostgreSQL),
+            ValidationScope::Full,
+        );
+
+        assert!(scoped.deferred.is_empty());
+        assert!(scoped.result.errors.iter().any(|e| e
+            .to_string()
+            .contains("references table 'users' which does not exist")));
+    }
+
     #[test]
     fn test_validate_with_dialect_returns_warnings_separately() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("products".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        // UUID type will generate warning for SQLite
-        table.add_column(Column::new("uuid".to_string(), ColumnType::UUID, false));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("products", |t| {
+                t.integer("id").primary_key();
+                // UUID type will generate warning for SQLite
+                t.uuid("uuid");
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
 
@@ -328,31 +670,40 @@ mod tests {
         assert!(warnings.iter().any(|w| w.message.contains("UUID")));
     }
 
+    #[test]
+    fn test_validate_mysql_minimum_version_warns_on_check() {
+        let schema = SchemaBuilder::new("1.0")
+            .table("products", |t| {
+                t.integer("id").primary_key();
+                t.check("id > 0", &["id"]);
+            })
+            .build_unvalidated();
+
+        let validator = SchemaValidatorService::new()
+            .with_mysql_minimum_version(ServerVersion::parse("5.7.44"));
+
+        let result = validator.validate_with_dialect(&schema, Dialect::MySQL);
+        assert!(result.is_valid());
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("not enforced on MySQL 5.7.44")));
+
+        // MySQL以外の方言では警告しない
+        let result = validator.validate_with_dialect(&schema, Dialect::PostgreSQL);
+        assert!(result.warnings.is_empty());
+    }
+
     #[test]
     fn test_each_validation_category_is_independently_testable() {
         // This test demonstrates that each validation category can be tested independently
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_column(Column::new(
-            "email".to_string(),
-            ColumnType::VARCHAR { length: 255 },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        table.add_index(Index::new(
-            "idx_email".to_string(),
-            vec!["email".to_string()],
-            false,
-        ));
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                t.varchar("email", 255);
+                t.index("idx_email", &["email"]);
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
 
@@ -373,7 +724,7 @@ mod tests {
 
     #[test]
     fn test_validate_empty_schema() {
-        let schema = Schema::new("1.0".to_string());
+        let schema = SchemaBuilder::new("1.0").build_unvalidated();
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
 
@@ -383,12 +734,10 @@ mod tests {
 
     #[test]
     fn test_validate_table_without_columns() {
-        let mut schema = Schema::new("1.0".to_string());
-        let table = Table::new("empty_table".to_string());
-        schema.add_table(table);
-
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate(&schema);
+        let result = SchemaBuilder::new("1.0")
+            .table("empty_table", |_| {})
+            .build()
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result.error_count() > 0);
@@ -396,18 +745,12 @@ mod tests {
 
     #[test]
     fn test_validate_table_without_primary_key() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        schema.add_table(table);
-
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate(&schema);
+        let result = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id");
+            })
+            .build()
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result.error_count() > 0);
@@ -415,18 +758,11 @@ mod tests {
 
     #[test]
     fn test_validate_valid_schema() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id").primary_key();
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
@@ -436,34 +772,56 @@ mod tests {
     }
 
     #[test]
-    fn test_validate_enum_empty_values() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        schema.add_enum(EnumDefinition {
-            name: "status".to_string(),
-            values: vec![],
-        });
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_column(Column::new(
-            "status".to_string(),
-            ColumnType::Enum {
-                name: "status".to_string(),
-            },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
+    fn test_build_returns_schema_for_every_column_type() {
+        let schema = SchemaBuilder::new("1.0")
+            .enum_type("status", &["active", "inactive"])
+            .table("orgs", |t| {
+                t.integer("id").primary_key();
+            })
+            .table("everything", |t| {
+                t.bigint("id").primary_key().auto_increment();
+                t.integer("org_id");
+                t.smallint("rank").default_value("0");
+                t.varchar("email", 255).nullable().unique();
+                t.char("country", 2);
+                t.text("bio").nullable();
+                t.boolean("active").default_value("true");
+                t.timestamp("created_at").default_value("CURRENT_TIMESTAMP");
+                t.timestamp_tz("synced_at").nullable();
+                t.date("born_on").nullable();
+                t.time("wakes_at").nullable();
+                t.decimal("balance", 10, 2);
+                t.float("ratio");
+                t.double("score");
+                t.json("settings").nullable();
+                t.jsonb("attributes").nullable();
+                t.blob("avatar").nullable();
+                t.uuid("external_id");
+                t.enum_column("status", "status");
+                t.dialect_specific("ip_address", "INET").nullable();
+                t.foreign_key(&["org_id"], "orgs", &["id"])
+                    .on_delete_cascade();
+                t.index("idx_everything_status", &["status", "created_at"]);
+                t.check("rank >= 0", &["rank"]);
+            })
+            .build_for_dialect(Dialect::PostgreSQL)
+            .unwrap();
+
+        let table = schema.get_table("everything").unwrap();
+        assert_eq!(table.columns.len(), 20);
+        assert_eq!(table.constraints.len(), 4);
+    }
 
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate(&schema);
+    #[test]
+    fn test_validate_enum_empty_values() {
+        let result = SchemaBuilder::new("1.0")
+            .enum_type("status", &[])
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                t.enum_column("status", "status");
+            })
+            .build()
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result
@@ -474,33 +832,14 @@ mod tests {
 
     #[test]
     fn test_validate_enum_duplicate_values() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        schema.add_enum(EnumDefinition {
-            name: "status".to_string(),
-            values: vec!["active".to_string(), "active".to_string()],
-        });
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_column(Column::new(
-            "status".to_string(),
-            ColumnType::Enum {
-                name: "status".to_string(),
-            },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
-
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate(&schema);
+        let result = SchemaBuilder::new("1.0")
+            .enum_type("status", &["active", "active"])
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                t.enum_column("status", "status");
+            })
+            .build()
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result
@@ -511,28 +850,13 @@ mod tests {
 
     #[test]
     fn test_validate_enum_reference_missing() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_column(Column::new(
-            "status".to_string(),
-            ColumnType::Enum {
-                name: "status".to_string(),
-            },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
-
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate(&schema);
+        let result = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                t.enum_column("status", "status");
+            })
+            .build()
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result
@@ -543,33 +867,14 @@ mod tests {
 
     #[test]
     fn test_validate_enum_non_postgres_dialect() {
-        let mut schema = Schema::new("1.0".to_string());
-
-        schema.add_enum(EnumDefinition {
-            name: "status".to_string(),
-            values: vec!["active".to_string()],
-        });
-
-        let mut table = Table::new("users".to_string());
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-        table.add_column(Column::new(
-            "status".to_string(),
-            ColumnType::Enum {
-                name: "status".to_string(),
-            },
-            false,
-        ));
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-        schema.add_table(table);
-
-        let validator = SchemaValidatorService::new();
-        let result = validator.validate_with_dialect(&schema, Dialect::MySQL);
+        let result = SchemaBuilder::new("1.0")
+            .enum_type("status", &["active"])
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                t.enum_column("status", "status");
+            })
+            .build_for_dialect(Dialect::MySQL)
+            .unwrap_err();
 
         assert!(!result.is_valid());
         assert!(result
@@ -581,26 +886,12 @@ mod tests {
     #[test]
     fn test_validate_dialect_specific_type_skip_validation() {
         // DialectSpecific バリアントは検証をスキップする（データベースに委譲）
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-
-        // PostgreSQL SERIAL型（方言固有型）
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::DialectSpecific {
-                kind: "SERIAL".to_string(),
-                params: serde_json::Value::Null,
-            },
-            false,
-        ));
-
-        // プライマリキーを追加（必須）
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                // PostgreSQL SERIAL型（方言固有型）
+                t.dialect_specific("id", "SERIAL").primary_key();
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
@@ -613,33 +904,21 @@ mod tests {
     #[test]
     fn test_validate_dialect_specific_type_with_params() {
         // パラメータ付きDialectSpecific型（MySQL ENUM）も検証スキップ
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::INTEGER { precision: None },
-            false,
-        ));
-
-        // MySQL ENUM型（パラメータ付き方言固有型）
-        table.add_column(Column::new(
-            "status".to_string(),
-            ColumnType::DialectSpecific {
-                kind: "ENUM".to_string(),
-                params: serde_json::json!({
-                    "values": ["active", "inactive", "pending"]
-                }),
-            },
-            false,
-        ));
-
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                t.integer("id").primary_key();
+                // MySQL ENUM型（パラメータ付き方言固有型）
+                t.column(
+                    "status",
+                    ColumnType::DialectSpecific {
+                        kind: "ENUM".to_string(),
+                        params: serde_json::json!({
+                            "values": ["active", "inactive", "pending"]
+                        }),
+                    },
+                );
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
@@ -653,24 +932,12 @@ mod tests {
     fn test_validate_dialect_specific_type_invalid_kind() {
         // 無効な型名（INVALID_TYPE）でも検証をスキップ
         // データベース実行時にエラーが検出される
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::DialectSpecific {
-                kind: "INVALID_TYPE".to_string(), // 存在しない型
-                params: serde_json::Value::Null,
-            },
-            false,
-        ));
-
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                // 存在しない型
+                t.dialect_specific("id", "INVALID_TYPE").primary_key();
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
@@ -683,51 +950,18 @@ mod tests {
     #[test]
     fn test_validate_mixed_common_and_dialect_specific_types() {
         // 共通型と方言固有型の混在スキーマ
-        let mut schema = Schema::new("1.0".to_string());
-
-        let mut table = Table::new("users".to_string());
-
-        // 方言固有型（PostgreSQL SERIAL）
-        table.add_column(Column::new(
-            "id".to_string(),
-            ColumnType::DialectSpecific {
-                kind: "SERIAL".to_string(),
-                params: serde_json::Value::Null,
-            },
-            false,
-        ));
-
-        // 共通型（VARCHAR）
-        table.add_column(Column::new(
-            "username".to_string(),
-            ColumnType::VARCHAR { length: 50 },
-            false,
-        ));
-
-        // 方言固有型（PostgreSQL INET）
-        table.add_column(Column::new(
-            "ip_address".to_string(),
-            ColumnType::DialectSpecific {
-                kind: "INET".to_string(),
-                params: serde_json::Value::Null,
-            },
-            true,
-        ));
-
-        // 共通型（TIMESTAMP）
-        table.add_column(Column::new(
-            "created_at".to_string(),
-            ColumnType::TIMESTAMP {
-                with_time_zone: Some(true),
-            },
-            false,
-        ));
-
-        table.add_constraint(Constraint::PRIMARY_KEY {
-            columns: vec!["id".to_string()],
-        });
-
-        schema.add_table(table);
+        let schema = SchemaBuilder::new("1.0")
+            .table("users", |t| {
+                // 方言固有型（PostgreSQL SERIAL）
+                t.dialect_specific("id", "SERIAL").primary_key();
+                // 共通型（VARCHAR）
+                t.varchar("username", 50);
+                // 方言固有型（PostgreSQL INET）
+                t.dialect_specific("ip_address", "INET").nullable();
+                // 共通型（TIMESTAMP）
+                t.timestamp_tz("created_at");
+            })
+            .build_unvalidated();
 
         let validator = SchemaValidatorService::new();
         let result = validator.validate(&schema);
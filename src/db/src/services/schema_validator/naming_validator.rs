@@ -0,0 +1,371 @@
+// 命名規約の検証
+//
+// `naming.enabled: true` のときに、外部キーカラム名・テーブル名の単数形/複数形・
+// 中間テーブル名の規約違反を警告として報告します。
+
+use super::inflection::Inflector;
+use crate::core::config::{NamingConfig, TableNameStyle};
+use crate::core::error::{ErrorLocation, ValidationResult, ValidationWarning};
+use crate::core::schema::{Constraint, Schema, Table};
+
+/// 単一カラムの外部キー
+struct SingleColumnForeignKey<'a> {
+    column: &'a str,
+    referenced_table: &'a str,
+    referenced_column: &'a str,
+}
+
+/// 命名規約の検証
+pub fn validate_naming_conventions(schema: &Schema, config: &NamingConfig) -> ValidationResult {
+    let mut result = ValidationResult::new();
+    let inflector = Inflector::new(&config.inflections);
+
+    for (table_name, table) in &schema.tables {
+        let foreign_keys = single_column_foreign_keys(table);
+        let junction_references = junction_references(table, &foreign_keys);
+
+        validate_foreign_key_columns(&mut result, table_name, &foreign_keys, config, &inflector);
+
+        match junction_references {
+            Some(references) => {
+                if config.junction_tables {
+                    validate_junction_table_name(&mut result, table_name, references);
+                }
+            }
+            None => validate_table_name_style(&mut result, table_name, config, &inflector),
+        }
+    }
+
+    result
+}
+
+/// 外部キーカラム名がテンプレートに従っているか検証
+///
+/// 自己参照や、同じテーブルを複数の外部キーで参照する場合（`author_id` / `editor_id` など）は
+/// 役割を表す名前を付けるのが一般的なため対象外とします。
+fn validate_foreign_key_columns(
+    result: &mut ValidationResult,
+    table_name: &str,
+    foreign_keys: &[SingleColumnForeignKey<'_>],
+    config: &NamingConfig,
+    inflector: &Inflector<'_>,
+) {
+    for foreign_key in foreign_keys {
+        if foreign_key.referenced_table == table_name {
+            continue;
+        }
+        let references_to_same_table = foreign_keys
+            .iter()
+            .filter(|fk| fk.referenced_table == foreign_key.referenced_table)
+            .count();
+        if references_to_same_table > 1 {
+            continue;
+        }
+
+        let expected = config
+            .foreign_key_column
+            .replace("{table}", foreign_key.referenced_table)
+            .replace(
+                "{singular_table}",
+                &inflector.singularize(foreign_key.referenced_table),
+            )
+            .replace("{column}", foreign_key.referenced_column);
+        if foreign_key.column != expected {
+            result.add_warning(ValidationWarning::naming_convention(
+                format!(
+                    "Foreign key column '{}.{}' references '{}' but does not follow the naming convention '{}' (expected '{}')",
+                    table_name,
+                    foreign_key.column,
+                    foreign_key.referenced_table,
+                    config.foreign_key_column,
+                    expected
+                ),
+                Some(ErrorLocation::with_table_and_column(
+                    table_name,
+                    foreign_key.column,
+                )),
+            ));
+        }
+    }
+}
+
+/// テーブル名が単数形/複数形の規約に従っているか検証
+fn validate_table_name_style(
+    result: &mut ValidationResult,
+    table_name: &str,
+    config: &NamingConfig,
+    inflector: &Inflector<'_>,
+) {
+    let (style, expected) = match config.table_names {
+        TableNameStyle::Plural => (
+            "plural",
+            inflector.pluralize(&inflector.singularize(table_name)),
+        ),
+        TableNameStyle::Singular => ("singular", inflector.singularize(table_name)),
+        TableNameStyle::Any => return,
+    };
+    if expected != table_name {
+        result.add_warning(ValidationWarning::naming_convention(
+            format!(
+                "Table '{}' does not follow the {} table name convention (expected '{}')",
+                table_name, style, expected
+            ),
+            Some(ErrorLocation::with_table(table_name.to_string())),
+        ));
+    }
+}
+
+/// 中間テーブル名が参照先テーブル名をアルファベット順に連結した形か検証
+fn validate_junction_table_name(
+    result: &mut ValidationResult,
+    table_name: &str,
+    mut references: [&str; 2],
+) {
+    references.sort_unstable();
+    let expected = references.join("_");
+    if expected != table_name {
+        result.add_warning(ValidationWarning::naming_convention(
+            format!(
+                "Junction table '{}' links '{}' and '{}' but does not follow the naming convention (expected '{}')",
+                table_name, references[0], references[1], expected
+            ),
+            Some(ErrorLocation::with_table(table_name.to_string())),
+        ));
+    }
+}
+
+fn single_column_foreign_keys(table: &Table) -> Vec<SingleColumnForeignKey<'_>> {
+    table
+        .constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::FOREIGN_KEY {
+                columns,
+                referenced_table,
+                referenced_columns,
+                ..
+            } if columns.len() == 1 && referenced_columns.len() == 1 => {
+                Some(SingleColumnForeignKey {
+                    column: &columns[0],
+                    referenced_table,
+                    referenced_column: &referenced_columns[0],
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// 中間テーブルであれば参照先の2テーブルを返す
+///
+/// 主キーがちょうど2カラムで、それぞれが異なるテーブルへの外部キーであるものを中間テーブルとみなします。
+fn junction_references<'a>(
+    table: &Table,
+    foreign_keys: &[SingleColumnForeignKey<'a>],
+) -> Option<[&'a str; 2]> {
+    let primary_key = table.get_primary_key_columns()?;
+    let [first, second] = primary_key.as_slice() else {
+        return None;
+    };
+    let referenced_table = |column: &str| {
+        foreign_keys
+            .iter()
+            .find(|fk| fk.column == column)
+            .map(|fk| fk.referenced_table)
+    };
+    let references = [referenced_table(first)?, referenced_table(second)?];
+    (references[0] != references[1]).then_some(references)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType};
+    use std::collections::BTreeMap;
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        let mut table = Table::new(name.to_string());
+        for column in columns {
+            table.add_column(Column::new(
+                column.to_string(),
+                ColumnType::INTEGER { precision: None },
+                false,
+            ));
+        }
+        table
+    }
+
+    fn foreign_key(column: &str, referenced_table: &str) -> Constraint {
+        Constraint::FOREIGN_KEY {
+            columns: vec![column.to_string()],
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn primary_key(columns: &[&str]) -> Constraint {
+        Constraint::PRIMARY_KEY {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn enabled() -> NamingConfig {
+        NamingConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    fn warning_messages(schema: &Schema, config: &NamingConfig) -> Vec<String> {
+        validate_naming_conventions(schema, config)
+            .warnings
+            .into_iter()
+            .map(|w| w.message)
+            .collect()
+    }
+
+    fn blog_schema(post_fk_column: &str, referenced_table: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut users = table(referenced_table, &["id"]);
+        users.add_constraint(primary_key(&["id"]));
+        schema.add_table(users);
+
+        let mut posts = table("posts", &["id", post_fk_column]);
+        posts.add_constraint(primary_key(&["id"]));
+        posts.add_constraint(foreign_key(post_fk_column, referenced_table));
+        schema.add_table(posts);
+        schema
+    }
+
+    #[test]
+    fn test_conforming_schema_has_no_warnings() {
+        let schema = blog_schema("user_id", "users");
+        assert!(warning_messages(&schema, &enabled()).is_empty());
+
+        // 不規則変化する名前も単数形にしてから比較する
+        let schema = blog_schema("person_id", "people");
+        assert!(warning_messages(&schema, &enabled()).is_empty());
+    }
+
+    #[test]
+    fn test_foreign_key_column_mismatch() {
+        let schema = blog_schema("users_id", "users");
+        let messages = warning_messages(&schema, &enabled());
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("'posts.users_id'"), "{}", messages[0]);
+        assert!(
+            messages[0].contains("expected 'user_id'"),
+            "{}",
+            messages[0]
+        );
+
+        let result = validate_naming_conventions(&schema, &enabled());
+        assert_eq!(
+            result.warnings[0].kind,
+            crate::core::error::WarningKind::NamingConvention
+        );
+    }
+
+    #[test]
+    fn test_foreign_key_column_template_override() {
+        let schema = blog_schema("user_id", "users");
+        let config = NamingConfig {
+            foreign_key_column: "{table}_{column}".to_string(),
+            ..enabled()
+        };
+        let messages = warning_messages(&schema, &config);
+        assert_eq!(messages.len(), 1);
+        assert!(
+            messages[0].contains("expected 'users_id'"),
+            "{}",
+            messages[0]
+        );
+    }
+
+    #[test]
+    fn test_foreign_keys_with_roles_are_skipped() {
+        let mut schema = blog_schema("user_id", "users");
+        let posts = schema.tables.get_mut("posts").unwrap();
+        posts.constraints.clear();
+        posts.add_constraint(primary_key(&["id"]));
+        posts.add_constraint(foreign_key("author_id", "users"));
+        posts.add_constraint(foreign_key("editor_id", "users"));
+        posts.add_constraint(foreign_key("parent_id", "posts"));
+
+        assert!(warning_messages(&schema, &enabled()).is_empty());
+    }
+
+    #[test]
+    fn test_table_name_style() {
+        let schema = blog_schema("user_id", "user");
+        let messages = warning_messages(&schema, &enabled());
+        assert_eq!(messages.len(), 1);
+        assert!(
+            messages[0].contains(
+                "Table 'user' does not follow the plural table name convention (expected 'users')"
+            ),
+            "{}",
+            messages[0]
+        );
+
+        let singular = NamingConfig {
+            table_names: TableNameStyle::Singular,
+            ..enabled()
+        };
+        let messages = warning_messages(&schema, &singular);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("expected 'post'"), "{}", messages[0]);
+
+        let any = NamingConfig {
+            table_names: TableNameStyle::Any,
+            ..enabled()
+        };
+        assert!(warning_messages(&schema, &any).is_empty());
+    }
+
+    #[test]
+    fn test_inflection_exceptions_from_config() {
+        let schema = blog_schema("cactus_id", "cacti");
+        let messages = warning_messages(&schema, &enabled());
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages.iter().any(|m| m.contains("expected 'cacti_id'")));
+        assert!(messages.iter().any(|m| m.contains("expected 'cactis'")));
+
+        let config = NamingConfig {
+            inflections: BTreeMap::from([("cactus".to_string(), "cacti".to_string())]),
+            ..enabled()
+        };
+        assert!(warning_messages(&schema, &config).is_empty());
+    }
+
+    #[test]
+    fn test_junction_table_name() {
+        let mut schema = blog_schema("user_id", "users");
+        let mut tags = table("tags", &["id"]);
+        tags.add_constraint(primary_key(&["id"]));
+        schema.add_table(tags);
+
+        let mut junction = table("tags_posts", &["tag_id", "post_id"]);
+        junction.add_constraint(primary_key(&["tag_id", "post_id"]));
+        junction.add_constraint(foreign_key("tag_id", "tags"));
+        junction.add_constraint(foreign_key("post_id", "posts"));
+        schema.add_table(junction);
+
+        let messages = warning_messages(&schema, &enabled());
+        assert_eq!(messages.len(), 1, "{:?}", messages);
+        assert!(
+            messages[0].contains("Junction table 'tags_posts'")
+                && messages[0].contains("expected 'posts_tags'"),
+            "{}",
+            messages[0]
+        );
+
+        let config = NamingConfig {
+            junction_tables: false,
+            ..enabled()
+        };
+        assert!(warning_messages(&schema, &config).is_empty());
+    }
+}
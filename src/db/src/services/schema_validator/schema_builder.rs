@@ -0,0 +1,54 @@
+// スキーマビルダーの検証付きビルド
+//
+// strata-core の SchemaBuilder は検証サービスに依存できないため、
+// SchemaValidatorService で検証する build をここで拡張トレイトとして提供します。
+
+use super::SchemaValidatorService;
+use crate::core::config::Dialect;
+use crate::core::error::ValidationResult;
+use crate::core::schema::{Schema, SchemaBuilder};
+
+/// SchemaBuilder に検証付きの `build` を追加するトレイト
+///
+/// # Examples
+///
+/// ```
+/// use strata_db::{SchemaBuilder, SchemaBuilderExt};
+///
+/// let schema = SchemaBuilder::new("1.0")
+///     .table("users", |t| {
+///         t.integer("id").primary_key();
+///         t.varchar("email", 255).nullable().unique();
+///     })
+///     .build()
+///     .unwrap();
+/// assert!(schema.has_table("users"));
+///
+/// // プライマリキーのないテーブルは検証エラーになる
+/// let errors = SchemaBuilder::new("1.0")
+///     .table("logs", |t| {
+///         t.text("message");
+///     })
+///     .build()
+///     .unwrap_err();
+/// assert!(!errors.is_valid());
+/// ```
+pub trait SchemaBuilderExt {
+    /// `SchemaValidatorService::validate` で検証してスキーマを返す
+    fn build(self) -> Result<Schema, ValidationResult>;
+
+    /// 方言を指定して検証し、スキーマを返す
+    fn build_for_dialect(self, dialect: Dialect) -> Result<Schema, ValidationResult>;
+}
+
+impl SchemaBuilderExt for SchemaBuilder {
+    fn build(self) -> Result<Schema, ValidationResult> {
+        let validator = SchemaValidatorService::new();
+        self.build_with(|schema| validator.validate(schema))
+    }
+
+    fn build_for_dialect(self, dialect: Dialect) -> Result<Schema, ValidationResult> {
+        let validator = SchemaValidatorService::new();
+        self.build_with(|schema| validator.validate_with_dialect(schema, dialect))
+    }
+}
@@ -0,0 +1,429 @@
+// スキーマファイルの修正案（validate --suggest-fixes / --apply-fixes）
+//
+// 機械的に直せる検証エラーについて、スキーマファイル（YAML）の書き換えを修正案として作成します。
+// - 修正案の作成は対応する検証の隣に置く（constraint_validator.rs、table_validator.rs）
+// - 書き換えは行単位で行い、ファイルの他の部分の書式やコメントはそのまま残す
+// - どう直すべきか一意に決まらない場合や、YAMLの書き方を解釈できない場合は修正案を作らない
+// - 行の追加・並べ替えだけで済む修正案を「安全」とし、--apply-fixes はそれだけを適用する
+
+use super::{constraint_validator, table_validator};
+use crate::core::schema::Schema;
+use crate::services::schema_io::schema_sources::SchemaSources;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 差分に含める前後の行数
+const CONTEXT_LINES: usize = 3;
+
+/// スキーマファイルの修正案
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaFix {
+    /// 書き換えるスキーマファイル
+    pub file: PathBuf,
+    /// 修正内容の説明
+    pub description: String,
+    /// この修正で解消する検証エラーのメッセージ
+    pub error: String,
+    /// 行の追加・並べ替えのみで、書かれている内容を削除しないか
+    pub safe: bool,
+    /// 行の書き換え
+    #[serde(skip)]
+    pub edit: LineEdit,
+}
+
+/// 行の書き換え
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEdit {
+    /// 書き換える最初の行（0始まり。追加のみの場合はこの行の前に挿入する）
+    pub start: usize,
+    /// 削除する行数
+    pub remove: usize,
+    /// 挿入する行
+    pub insert: Vec<String>,
+}
+
+/// 修正案の作成に使う情報
+#[derive(Debug, Clone, Copy)]
+pub struct FixContext<'a> {
+    /// 読み込んだスキーマ
+    pub schema: &'a Schema,
+    /// 各要素の定義元ファイルと行
+    pub sources: &'a SchemaSources,
+    /// スキーマファイルの内容（`sources` と同じパス -> 内容）
+    pub contents: &'a HashMap<PathBuf, String>,
+}
+
+impl<'a> FixContext<'a> {
+    /// 新しいFixContextを作成
+    pub fn new(
+        schema: &'a Schema,
+        sources: &'a SchemaSources,
+        contents: &'a HashMap<PathBuf, String>,
+    ) -> Self {
+        Self {
+            schema,
+            sources,
+            contents,
+        }
+    }
+
+    /// テーブルを定義しているファイルとその内容、テーブル名の行（0始まり）
+    pub(super) fn table_text(&self, table_name: &str) -> Option<(&'a Path, YamlText<'a>, usize)> {
+        let file = self.sources.table_file(table_name)?;
+        let content = self.contents.get(file)?;
+        let line = self.sources.table_line(table_name)?.checked_sub(1)?;
+        Some((file, YamlText::new(content), line))
+    }
+}
+
+/// 対応しているすべての検証エラーの修正案を作成
+///
+/// ファイルごと、書き換える行の順に並べて返します。
+pub fn suggest_fixes(context: &FixContext) -> Vec<SchemaFix> {
+    let mut fixes = Vec::new();
+    fixes.extend(constraint_validator::primary_key_fixes(context));
+    fixes.extend(constraint_validator::foreign_key_column_fixes(context));
+    fixes.extend(table_validator::duplicate_column_fixes(context));
+    fixes.sort_by(|a, b| (&a.file, a.edit.start).cmp(&(&b.file, b.edit.start)));
+    fixes
+}
+
+/// 修正案を適用したファイルの内容
+///
+/// 同じファイルへの修正案のうち、先の修正案と書き換える行が重なるものは適用しません。
+pub fn apply_fixes(content: &str, fixes: &[&SchemaFix]) -> String {
+    let text = YamlText::new(content);
+    let mut lines: Vec<String> = text.lines.iter().map(|line| line.to_string()).collect();
+    for edit in non_overlapping(fixes).iter().rev() {
+        lines.splice(
+            edit.start..edit.start + edit.remove,
+            edit.insert.iter().cloned(),
+        );
+    }
+    text.join(&lines)
+}
+
+/// 修正案を適用した場合の差分（unified diff形式）
+///
+/// `path` は差分のヘッダーに表示するファイルパスです。修正案がない場合は空文字列を返します。
+pub fn unified_diff(path: &Path, content: &str, fixes: &[&SchemaFix]) -> String {
+    let edits = non_overlapping(fixes);
+    if edits.is_empty() {
+        return String::new();
+    }
+    let lines = YamlText::new(content).lines;
+    let path = path.display().to_string().replace('\\', "/");
+    let mut output = format!("--- a/{}\n+++ b/{}\n", path, path);
+
+    // 前後の行が重なる書き換えは1つのハンクにまとめる
+    let mut hunks: Vec<Vec<&LineEdit>> = Vec::new();
+    for edit in edits {
+        match hunks.last_mut() {
+            Some(hunk)
+                if hunk.last().is_some_and(|last| {
+                    edit.start <= last.start + last.remove + 2 * CONTEXT_LINES
+                }) =>
+            {
+                hunk.push(edit)
+            }
+            _ => hunks.push(vec![edit]),
+        }
+    }
+
+    let mut offset: isize = 0;
+    for hunk in hunks {
+        let first = hunk[0];
+        let last = hunk[hunk.len() - 1];
+        let old_start = first.start.saturating_sub(CONTEXT_LINES);
+        let old_end = (last.start + last.remove + CONTEXT_LINES).min(lines.len());
+
+        let mut body = Vec::new();
+        let mut position = old_start;
+        let mut new_len = 0;
+        for edit in &hunk {
+            for line in &lines[position..edit.start] {
+                body.push(format!(" {}", line));
+                new_len += 1;
+            }
+            for line in &lines[edit.start..edit.start + edit.remove] {
+                body.push(format!("-{}", line));
+            }
+            for line in &edit.insert {
+                body.push(format!("+{}", line));
+                new_len += 1;
+            }
+            position = edit.start + edit.remove;
+        }
+        for line in &lines[position..old_end] {
+            body.push(format!(" {}", line));
+            new_len += 1;
+        }
+
+        let old_len = old_end - old_start;
+        let new_start = (old_start as isize + offset) as usize;
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_len),
+            hunk_range(new_start, new_len)
+        ));
+        for line in body {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        offset += hunk
+            .iter()
+            .map(|edit| edit.insert.len() as isize - edit.remove as isize)
+            .sum::<isize>();
+    }
+
+    output
+}
+
+/// ハンクヘッダーの範囲（空の範囲は直前の行番号で表す）
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, len)
+    }
+}
+
+/// 書き換える行が重ならない修正案の書き換え（行順）
+fn non_overlapping<'a>(fixes: &[&'a SchemaFix]) -> Vec<&'a LineEdit> {
+    let mut edits: Vec<&LineEdit> = fixes.iter().map(|fix| &fix.edit).collect();
+    edits.sort_by_key(|edit| edit.start);
+    let mut result: Vec<&LineEdit> = Vec::new();
+    for edit in edits {
+        let overlaps = result.last().is_some_and(|last| {
+            edit.start < last.start + last.remove
+                || (edit.start == last.start && last.remove > 0 && edit.remove > 0)
+        });
+        if !overlaps {
+            result.push(edit);
+        }
+    }
+    result
+}
+
+/// 行単位で扱うYAMLファイルの内容
+#[derive(Debug, Clone)]
+pub(super) struct YamlText<'a> {
+    pub(super) lines: Vec<&'a str>,
+    line_ending: &'static str,
+    trailing_newline: bool,
+}
+
+impl<'a> YamlText<'a> {
+    pub(super) fn new(content: &'a str) -> Self {
+        Self {
+            lines: content.lines().collect(),
+            line_ending: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+        }
+    }
+
+    fn join(&self, lines: &[String]) -> String {
+        let mut content = lines.join(self.line_ending);
+        if self.trailing_newline && !lines.is_empty() {
+            content.push_str(self.line_ending);
+        }
+        content
+    }
+
+    /// 行のインデント（先頭の空白の数）
+    pub(super) fn indent(&self, index: usize) -> usize {
+        let line = self.lines[index];
+        line.len() - line.trim_start().len()
+    }
+
+    /// 空行またはコメント行か
+    fn is_blank(&self, index: usize) -> bool {
+        let trimmed = self.lines[index].trim();
+        trimmed.is_empty() || trimmed.starts_with('#')
+    }
+
+    /// 行の内容（シーケンスの `- ` を除く）
+    pub(super) fn content(&self, index: usize) -> &'a str {
+        let trimmed = self.lines[index].trim();
+        trimmed.strip_prefix("- ").unwrap_or(trimmed).trim_start()
+    }
+
+    /// 値を持たないキー（`key:`）の行か
+    ///
+    /// フロースタイル（`users: {columns: [...]}`）の行は位置を特定できないため対象外とします。
+    pub(super) fn is_block_key(&self, index: usize) -> bool {
+        let content = self.content_without_comment(index);
+        content.ends_with(':') && !content.contains(": ")
+    }
+
+    /// 行末のコメントを除いた行の内容
+    pub(super) fn content_without_comment(&self, index: usize) -> &'a str {
+        let content = self.content(index);
+        content.split(" #").next().unwrap_or(content).trim_end()
+    }
+
+    /// 指定した行から始まるブロックの終わり（ブロックの最後の行の次の行）
+    ///
+    /// 指定した行より深くインデントされた行（キーの行では同じインデントのシーケンス項目も）を
+    /// ブロックに含めます。ブロックの後ろの空行・コメント行は含めません。
+    pub(super) fn block_end(&self, start: usize) -> usize {
+        let base = self.indent(start);
+        let is_item = self.lines[start].trim_start().starts_with("- ");
+        let mut end = start + 1;
+        for index in start + 1..self.lines.len() {
+            if self.is_blank(index) {
+                continue;
+            }
+            let indent = self.indent(index);
+            let nested_item =
+                !is_item && indent == base && self.lines[index].trim_start().starts_with("- ");
+            if indent > base || nested_item {
+                end = index + 1;
+            } else {
+                break;
+            }
+        }
+        end
+    }
+
+    /// ブロック内の直下のキーの行
+    ///
+    /// シーケンス項目のブロックでは `- key:` の行自体も対象にします。
+    pub(super) fn child_key(&self, start: usize, key: &str) -> Option<usize> {
+        let prefix = format!("{}:", key);
+        let end = self.block_end(start);
+        let child_indent = self.child_indent(start)?;
+        if self.lines[start].trim_start().starts_with("- ")
+            && self.content(start).starts_with(&prefix)
+        {
+            return Some(start);
+        }
+        (start + 1..end).find(|&index| {
+            !self.is_blank(index)
+                && self.indent(index) == child_indent
+                && self.lines[index].trim_start().starts_with(&prefix)
+        })
+    }
+
+    /// ブロック直下の行のインデント
+    pub(super) fn child_indent(&self, start: usize) -> Option<usize> {
+        let line = self.lines[start];
+        if let Some(rest) = line.trim_start().strip_prefix("- ") {
+            return Some(line.len() - rest.trim_start().len());
+        }
+        (start + 1..self.block_end(start))
+            .find(|&index| !self.is_blank(index))
+            .map(|index| self.indent(index))
+            .filter(|&indent| indent > self.indent(start))
+    }
+}
+
+/// YAMLにそのまま書ける名前か（引用符が必要な名前の修正案は作らない）
+pub(super) fn is_plain_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// YAMLを1つのスキーマファイルとして読み込み、修正案とそれを適用した内容を返す（テスト用）
+#[cfg(test)]
+pub(super) fn fix_yaml(
+    content: &str,
+    generator: fn(&FixContext) -> Vec<SchemaFix>,
+) -> (Vec<SchemaFix>, String) {
+    use crate::services::schema_io::schema_parser::SchemaParserService;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("schema.yaml");
+    std::fs::write(&path, content).unwrap();
+    let (schema, _, sources) = SchemaParserService::new()
+        .parse_schema_directory_with_sources(dir.path())
+        .unwrap();
+    let contents = HashMap::from([(path, content.to_string())]);
+
+    let fixes = generator(&FixContext::new(&schema, &sources, &contents));
+    let fixed = apply_fixes(content, &fixes.iter().collect::<Vec<_>>());
+    (fixes, fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(start: usize, remove: usize, insert: &[&str], safe: bool) -> SchemaFix {
+        SchemaFix {
+            file: PathBuf::from("schema/users.yaml"),
+            description: String::new(),
+            error: String::new(),
+            safe,
+            edit: LineEdit {
+                start,
+                remove,
+                insert: insert.iter().map(|line| line.to_string()).collect(),
+            },
+        }
+    }
+
+    const CONTENT: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+
+    #[test]
+    fn test_apply_fixes_inserts_and_removes_lines() {
+        let insert = fix(2, 0, &["x"], true);
+        let remove = fix(7, 2, &[], false);
+
+        assert_eq!(
+            apply_fixes(CONTENT, &[&remove, &insert]),
+            "a\nb\nx\nc\nd\ne\nf\ng\nj\n"
+        );
+        assert_eq!(
+            apply_fixes("a\r\nb\r\n", &[&fix(1, 0, &["x"], true)]),
+            "a\r\nx\r\nb\r\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_merges_nearby_edits_into_one_hunk() {
+        let insert = fix(2, 0, &["x"], true);
+        let remove = fix(7, 2, &[], false);
+
+        assert_eq!(
+            unified_diff(Path::new("schema/users.yaml"), CONTENT, &[&insert, &remove]),
+            "--- a/schema/users.yaml\n+++ b/schema/users.yaml\n\
+             @@ -1,10 +1,9 @@\n a\n b\n+x\n c\n d\n e\n f\n g\n-h\n-i\n j\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_separates_distant_edits() {
+        let content: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+        let first = fix(1, 0, &["x"], true);
+        let second = fix(15, 1, &[], false);
+
+        let diff = unified_diff(Path::new("a.yaml"), &content, &[&first, &second]);
+
+        assert!(diff.contains("@@ -1,4 +1,5 @@\n 1\n+x\n 2\n 3\n 4\n"));
+        assert!(diff.contains("@@ -13,7 +14,6 @@\n 13\n 14\n 15\n-16\n 17\n 18\n 19\n"));
+    }
+
+    #[test]
+    fn test_block_end_and_child_key() {
+        let text = YamlText::new(
+            "tables:\n  users:\n    columns:\n    - name: id\n      type:\n        kind: INTEGER\n\n    # comment\n  posts:\n    columns: []\n",
+        );
+
+        assert_eq!(text.block_end(1), 6);
+        assert_eq!(text.block_end(2), 6);
+        assert_eq!(text.block_end(3), 6);
+        assert_eq!(text.child_key(1, "columns"), Some(2));
+        assert_eq!(text.child_key(3, "name"), Some(3));
+        assert_eq!(text.child_key(3, "type"), Some(4));
+        assert!(text.is_block_key(1));
+        assert!(!text.is_block_key(9));
+    }
+}
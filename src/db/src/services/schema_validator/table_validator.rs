@@ -1,7 +1,10 @@
 // テーブル構造の検証
 
+use super::schema_fixes::{FixContext, LineEdit, SchemaFix};
+use crate::adapters::sql_generator::validate_populate_expression;
 use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
 use crate::core::schema::Schema;
+use std::collections::HashSet;
 
 /// テーブル構造の検証（カラムの存在確認）
 pub fn validate_table_structure(schema: &Schema) -> ValidationResult {
@@ -19,3 +22,307 @@ pub fn validate_table_structure(schema: &Schema) -> ValidationResult {
 
     result
 }
+
+/// 重複カラム名の検証
+pub fn validate_duplicate_column_names(schema: &Schema) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        let mut seen = HashSet::new();
+        for column in &table.columns {
+            if !seen.insert(&column.name) {
+                result.add_error(ValidationError::Constraint {
+                    message: duplicate_column_message(table_name, &column.name),
+                    location: Some(ErrorLocation::with_table_and_column(
+                        table_name,
+                        &column.name,
+                    )),
+                    suggestion: Some("Remove the duplicate column definition".to_string()),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+fn duplicate_column_message(table_name: &str, column_name: &str) -> String {
+    format!(
+        "Table '{}' has duplicate column name '{}'",
+        table_name, column_name
+    )
+}
+
+/// 重複カラムの修正案（後の定義を削除する）
+///
+/// どちらを残すか決められないため、すべての定義が同じ内容の場合のみ作成します。
+/// 書かれている内容を削除するため、安全な修正案としては扱いません。
+pub fn duplicate_column_fixes(context: &FixContext) -> Vec<SchemaFix> {
+    let mut fixes = Vec::new();
+
+    for (table_name, table) in &context.schema.tables {
+        let mut reported = HashSet::new();
+        for column in &table.columns {
+            let definitions: Vec<_> = table
+                .columns
+                .iter()
+                .filter(|other| other.name == column.name)
+                .collect();
+            if definitions.len() < 2
+                || !reported.insert(&column.name)
+                || definitions.iter().any(|other| *other != column)
+            {
+                continue;
+            }
+
+            // 行の記録は後の定義で上書きされるため、後の定義の行になる
+            let Some((file, text, _)) = context.table_text(table_name) else {
+                continue;
+            };
+            let Some(line) = context
+                .sources
+                .column_line(table_name, &column.name)
+                .and_then(|line| line.checked_sub(1))
+                .filter(|&line| line < text.lines.len())
+            else {
+                continue;
+            };
+            let end = text.block_end(line);
+            let names_column = (line..end).any(|index| {
+                text.content_without_comment(index) == format!("name: {}", column.name)
+            });
+            if !text.lines[line].trim_start().starts_with("- ") || !names_column {
+                continue;
+            }
+
+            fixes.push(SchemaFix {
+                file: file.to_path_buf(),
+                description: format!(
+                    "Remove the repeated definition of column '{}' from table '{}'",
+                    column.name, table_name
+                ),
+                error: duplicate_column_message(table_name, &column.name),
+                safe: false,
+                edit: LineEdit {
+                    start: line,
+                    remove: end - line,
+                    insert: Vec::new(),
+                },
+            });
+        }
+    }
+
+    fixes
+}
+
+/// populate_from の検証
+///
+/// - SELECT / WITH で始まる単一の問い合わせであることを確認
+/// - DML/DDLキーワードやセミコロンが含まれていないことを確認
+pub fn validate_populate_from(schema: &Schema) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        if let Some(select) = &table.populate_from {
+            if let Err(error) = validate_populate_expression(table_name, select) {
+                result.add_error(error);
+            }
+        }
+    }
+
+    result
+}
+
+/// 必須メタデータキーの検証
+///
+/// `required_keys` のうち、テーブルの `metadata` に含まれないキーをエラーとして報告します。
+pub fn validate_required_metadata(schema: &Schema, required_keys: &[String]) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for (table_name, table) in &schema.tables {
+        let missing: Vec<&str> = required_keys
+            .iter()
+            .filter(|key| !table.metadata.contains_key(key.as_str()))
+            .map(String::as_str)
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+
+        result.add_error(ValidationError::Constraint {
+            message: format!(
+                "Table '{}' is missing required metadata: {}",
+                table_name,
+                missing.join(", ")
+            ),
+            location: Some(ErrorLocation::with_table(table_name.clone())),
+            suggestion: Some(format!(
+                "Add the keys under 'metadata:' in the table definition (required_metadata: [{}])",
+                required_keys.join(", ")
+            )),
+        });
+    }
+
+    result
+}
+
+/// 外部テーブル宣言の検証
+///
+/// `external_tables` に宣言したテーブルが `tables` にも定義されていないことを確認します。
+pub fn validate_external_tables(schema: &Schema) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    for table_name in &schema.external_tables {
+        if schema.has_table(table_name) {
+            result.add_error(ValidationError::Constraint {
+                message: format!(
+                    "Table '{}' is declared in external_tables but is also defined in tables",
+                    table_name
+                ),
+                location: Some(ErrorLocation::with_table(table_name.clone())),
+                suggestion: Some(
+                    "Remove it from external_tables, or remove its table definition".to_string(),
+                ),
+            });
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table};
+
+    fn schema_with_populate(select: &str) -> Schema {
+        let mut schema = Schema::new("1.0".to_string());
+        let mut table = Table::new("reports".to_string());
+        table.add_column(Column::new(
+            "id".to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        ));
+        table.populate_from = Some(select.to_string());
+        schema.add_table(table);
+        schema
+    }
+
+    #[test]
+    fn test_validate_populate_from_valid() {
+        let result = validate_populate_from(&schema_with_populate("SELECT id FROM users"));
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_validate_populate_from_rejects_ddl() {
+        let result = validate_populate_from(&schema_with_populate(
+            "SELECT id FROM users; DROP TABLE users",
+        ));
+        assert!(!result.is_valid());
+        assert_eq!(
+            result.errors[0].location().and_then(|l| l.table.clone()),
+            Some("reports".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_required_metadata() {
+        let mut schema = schema_with_populate("SELECT 1");
+        let required = vec!["owner".to_string(), "ticket".to_string()];
+
+        let result = validate_required_metadata(&schema, &required);
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("Table 'reports' is missing required metadata: owner, ticket"));
+
+        let table = schema.tables.get_mut("reports").unwrap();
+        table
+            .metadata
+            .insert("owner".to_string(), "analytics".to_string());
+        table
+            .metadata
+            .insert("ticket".to_string(), "DATA-7".to_string());
+        assert!(validate_required_metadata(&schema, &required).is_valid());
+        assert!(validate_required_metadata(&schema, &[]).is_valid());
+    }
+
+    #[test]
+    fn test_validate_external_tables_rejects_defined_table() {
+        let mut schema = schema_with_populate("SELECT 1");
+        schema.external_tables.insert("external_users".to_string());
+        assert!(validate_external_tables(&schema).is_valid());
+
+        schema.external_tables.insert("reports".to_string());
+        let result = validate_external_tables(&schema);
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("Table 'reports' is declared in external_tables"));
+    }
+
+    #[test]
+    fn test_duplicate_column_fix_removes_identical_repeated_definition() {
+        let before = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: email
+        type:
+          kind: TEXT
+      # copied by mistake
+      - name: id
+        type:
+          kind: INTEGER
+    primary_key: [id]
+"#;
+        let after = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: email
+        type:
+          kind: TEXT
+      # copied by mistake
+    primary_key: [id]
+"#;
+
+        let (fixes, fixed) = super::super::schema_fixes::fix_yaml(before, duplicate_column_fixes);
+
+        assert_eq!(fixed, after);
+        assert_eq!(fixes.len(), 1);
+        assert!(!fixes[0].safe);
+        assert_eq!(
+            fixes[0].error,
+            "Table 'users' has duplicate column name 'id'"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_column_fix_skips_differing_definitions() {
+        let before = r#"version: "1.0"
+tables:
+  users:
+    columns:
+      - name: id
+        type:
+          kind: INTEGER
+      - name: id
+        type:
+          kind: BIGINT
+    primary_key: [id]
+"#;
+
+        let (fixes, fixed) = super::super::schema_fixes::fix_yaml(before, duplicate_column_fixes);
+
+        assert!(fixes.is_empty());
+        assert_eq!(fixed, before);
+    }
+}
@@ -1,12 +1,15 @@
 // ビュー定義の検証
 //
 // ビュー名の命名規則・衝突チェック、depends_on 依存検証・循環検出、
-// definition の妥当性検証を行います。
+// definition の妥当性検証と、definition が参照するテーブルの存在確認を行います。
 
 use std::collections::HashMap;
 
-use crate::core::error::{ErrorLocation, ValidationError, ValidationResult};
+use crate::core::error::{
+    ErrorLocation, ValidationError, ValidationResult, ValidationWarning, WarningKind,
+};
 use crate::core::schema::Schema;
+use crate::services::view_definition::referenced_tables;
 
 /// ビュー定義の検証
 ///
@@ -15,6 +18,7 @@ use crate::core::schema::Schema;
 /// - definition が空でないか確認
 /// - depends_on の参照先が tables/views に存在するか検証
 /// - 依存グラフの循環を検出
+/// - definition の FROM / JOIN 句が参照するテーブルがスキーマにない場合は警告
 pub fn validate_views(schema: &Schema) -> ValidationResult {
     let mut result = ValidationResult::new();
 
@@ -26,6 +30,7 @@ pub fn validate_views(schema: &Schema) -> ValidationResult {
     result.merge(validate_view_definitions(schema));
     result.merge(validate_view_depends_on(schema));
     result.merge(validate_view_dependency_cycle(schema));
+    result.merge(validate_view_references(schema));
 
     result
 }
@@ -94,6 +99,52 @@ fn validate_view_depends_on(schema: &Schema) -> ValidationResult {
     result
 }
 
+/// definition が参照するテーブルの存在確認
+///
+/// 字句解析による推定のため警告とする。スキーマ修飾された名前は、
+/// スキーマ定義で使われていないスキーマ（システムカタログなど）であれば対象外とする。
+fn validate_view_references(schema: &Schema) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    let known: Vec<&str> = schema
+        .tables
+        .keys()
+        .chain(schema.views.keys())
+        .chain(schema.external_tables.iter())
+        .map(|name| name.as_str())
+        .collect();
+    let is_known = |name: &str| known.iter().any(|k| k.eq_ignore_ascii_case(name));
+    let is_known_schema = |prefix: &str| {
+        known.iter().any(|k| {
+            k.rsplit_once('.')
+                .is_some_and(|(schema_name, _)| schema_name.eq_ignore_ascii_case(prefix))
+        })
+    };
+
+    for (view_name, view) in &schema.views {
+        for table in referenced_tables(&view.definition) {
+            if is_known(&table) {
+                continue;
+            }
+            if let Some((prefix, _)) = table.rsplit_once('.') {
+                if !is_known_schema(prefix) {
+                    continue;
+                }
+            }
+            result.add_warning(ValidationWarning::new(
+                format!(
+                    "View '{}' reads from '{}', which is not defined as a table or view in the schema",
+                    view_name, table
+                ),
+                Some(ErrorLocation::with_view(view_name)),
+                WarningKind::PossibleTypo,
+            ));
+        }
+    }
+
+    result
+}
+
 /// 依存グラフの循環検出（Tarjan's SCC ベース）
 ///
 /// 強連結成分（SCC）を検出し、サイズ2以上のSCCまたは自己参照を
@@ -154,7 +205,7 @@ fn validate_view_dependency_cycle(schema: &Schema) -> ValidationResult {
                     "Circular dependency detected among views: [{}]",
                     cycle_views.join(", ")
                 ),
-                location: None,
+                location: Some(ErrorLocation::with_view(cycle_views[0])),
                 suggestion: Some("Remove circular depends_on references between views".to_string()),
             });
         }
@@ -526,4 +577,24 @@ mod tests {
             .iter()
             .any(|e| e.to_string().contains("Circular dependency")));
     }
+
+    #[test]
+    fn test_definition_reading_undefined_table_warns() {
+        let mut schema = Schema::new("1.0".to_string());
+        schema.add_table(Table::new("users".to_string()));
+        schema
+            .external_tables
+            .insert("billing_accounts".to_string());
+        schema.add_view(View::new(
+            "user_orders".to_string(),
+            "SELECT u.id FROM users u JOIN ordres o ON o.user_id = u.id JOIN billing_accounts b ON b.user_id = u.id JOIN pg_catalog.pg_class c ON true".to_string(),
+        ));
+
+        let result = validate_views(&schema);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(result.warnings[0]
+            .message
+            .contains("reads from 'ordres', which is not defined"));
+    }
 }
@@ -0,0 +1,474 @@
+// シードファイル
+//
+// マイグレーションの適用後に投入する参照データ（ロール・国コードなど）を seeds/ ディレクトリから読み込みます。
+// - `*.sql`: ステートメントをそのまま実行する
+// - `*.yaml` / `*.yml`: テーブル名と行の一覧から、方言ごとの INSERT を生成する
+//
+// シードはマイグレーションのように適用履歴を記録しないため、何度実行しても同じ結果になる必要があります。
+// YAMLから生成する INSERT は、既存の行と衝突した場合に無視するか更新します。
+// ファイルはファイル名の順（`01_roles.yaml`, `02_countries.sql` ...）に実行します。
+
+use crate::adapters::sql_quote::{quote_identifier, quote_string_literal, quote_table_postgres};
+use crate::core::config::{Dialect, SqlOutputConfig};
+use crate::services::migration_files::sql_output::read_sql_file;
+use crate::services::migration_files::sql_parser::split_sql_statements;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// シードファイルを置くディレクトリ（プロジェクトのルートから）
+pub const SEEDS_DIR: &str = "seeds";
+
+/// シードファイルの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedFileKind {
+    /// SQLファイル
+    Sql,
+    /// テーブルと行を定義したYAMLファイル
+    Yaml,
+}
+
+/// シードファイル
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedFile {
+    /// ファイル名（例: `01_roles.yaml`）
+    pub name: String,
+    /// ファイルのパス
+    pub path: PathBuf,
+    /// ファイルの種類
+    pub kind: SeedFileKind,
+}
+
+impl SeedFile {
+    /// ファイル名または拡張子を除いたファイル名が一致するか（`--only` の指定）
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name
+            || Path::new(&self.name).file_stem().and_then(|s| s.to_str()) == Some(name)
+    }
+
+    /// 実行するステートメントを読み込む
+    ///
+    /// SQLファイルは `sql_output` 設定に従って正規化してから分割します。
+    pub fn statements(
+        &self,
+        dialect: Dialect,
+        sql_output: &SqlOutputConfig,
+    ) -> Result<Vec<String>> {
+        match self.kind {
+            SeedFileKind::Sql => Ok(split_sql_statements(&read_sql_file(
+                &self.path, sql_output,
+            )?)),
+            SeedFileKind::Yaml => {
+                let content = fs::read_to_string(&self.path)
+                    .with_context(|| format!("Failed to read seed file: {:?}", self.path))?;
+                let seed = YamlSeed::from_yaml(&content)
+                    .with_context(|| format!("Invalid seed file '{}'", self.name))?;
+                SeedStatementBuilder::new(dialect).build_statements(&seed)
+            }
+        }
+    }
+}
+
+/// シードディレクトリのファイルをファイル名の順に読み込む
+///
+/// `.sql` / `.yaml` / `.yml` 以外のファイル（README など）とサブディレクトリは無視します。
+pub fn load_seed_files(dir: &Path) -> Result<Vec<SeedFile>> {
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read seeds directory: {:?}", dir))?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let kind = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("sql") => SeedFileKind::Sql,
+            Some("yaml" | "yml") => SeedFileKind::Yaml,
+            _ => continue,
+        };
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        files.push(SeedFile {
+            name: name.to_string(),
+            path: path.clone(),
+            kind,
+        });
+    }
+
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+/// 既存の行と衝突した場合の動作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnConflict {
+    /// 既存の行をそのまま残す（デフォルト）
+    #[default]
+    Ignore,
+    /// `key` 以外のカラムをシードの値で更新する
+    Update,
+}
+
+/// YAMLのシード定義
+///
+/// ```yaml
+/// table: roles
+/// on_conflict: update
+/// key: [id]
+/// rows:
+///   - id: 1
+///     name: admin
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlSeed {
+    /// 投入先のテーブル名（PostgreSQLでは `schema.table` も可）
+    pub table: String,
+    /// 既存の行と衝突した場合の動作
+    #[serde(default)]
+    pub on_conflict: OnConflict,
+    /// 衝突を判定するカラム（主キーまたはユニーク制約。`on_conflict: update` では必須）
+    #[serde(default)]
+    pub key: Vec<String>,
+    /// 投入する行（カラム名と値のマップ）
+    #[serde(default)]
+    pub rows: Vec<BTreeMap<String, serde_json::Value>>,
+}
+
+impl YamlSeed {
+    /// YAMLから読み込み、内容を検証
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let seed: Self =
+            serde_saphyr::from_str(content).with_context(|| "Failed to parse seed YAML")?;
+
+        if seed.table.trim().is_empty() {
+            return Err(anyhow!("'table' must not be empty"));
+        }
+        if seed.on_conflict == OnConflict::Update && seed.key.is_empty() {
+            return Err(anyhow!(
+                "'on_conflict: update' requires 'key' (the primary key or unique columns)"
+            ));
+        }
+        for (index, row) in seed.rows.iter().enumerate() {
+            if row.is_empty() {
+                return Err(anyhow!("Row {} has no columns", index + 1));
+            }
+            if let Some(column) = seed.key.iter().find(|column| !row.contains_key(*column)) {
+                return Err(anyhow!(
+                    "Row {} has no value for key column '{}'",
+                    index + 1,
+                    column
+                ));
+            }
+            if let Some((column, _)) = row.iter().find(|(_, value)| {
+                matches!(
+                    value,
+                    serde_json::Value::Array(_) | serde_json::Value::Object(_)
+                )
+            }) {
+                return Err(anyhow!(
+                    "Row {} column '{}' must be a string, number, boolean or null",
+                    index + 1,
+                    column
+                ));
+            }
+        }
+
+        Ok(seed)
+    }
+}
+
+/// YAMLのシード定義から方言ごとの INSERT を生成
+///
+/// 識別子と文字列はすべて sql_quote の関数を通して埋め込みます。
+#[derive(Debug, Clone)]
+pub struct SeedStatementBuilder {
+    dialect: Dialect,
+}
+
+impl SeedStatementBuilder {
+    /// 新しいSeedStatementBuilderを作成
+    pub fn new(dialect: Dialect) -> Self {
+        Self { dialect }
+    }
+
+    /// 行ごとの INSERT を生成
+    ///
+    /// - `on_conflict: ignore`: PostgreSQL `ON CONFLICT DO NOTHING` / MySQL `INSERT IGNORE` / SQLite `INSERT OR IGNORE`
+    /// - `on_conflict: update`: PostgreSQL・SQLite `ON CONFLICT (key) DO UPDATE` / MySQL `ON DUPLICATE KEY UPDATE`
+    pub fn build_statements(&self, seed: &YamlSeed) -> Result<Vec<String>> {
+        seed.rows
+            .iter()
+            .map(|row| self.build_insert(seed, row))
+            .collect()
+    }
+
+    fn build_insert(
+        &self,
+        seed: &YamlSeed,
+        row: &BTreeMap<String, serde_json::Value>,
+    ) -> Result<String> {
+        let columns: Vec<String> = row.keys().map(|c| self.quote_identifier(c)).collect();
+        let values = row
+            .values()
+            .map(|value| self.literal(value))
+            .collect::<Result<Vec<_>>>()?;
+        let table = self.quote_table(&seed.table);
+        let column_list = columns.join(", ");
+        let value_list = values.join(", ");
+
+        // 更新するカラム（key 以外）がない場合は、衝突した行を残すだけでよい
+        let updates: Vec<&String> = row
+            .keys()
+            .filter(|column| !seed.key.contains(column))
+            .collect();
+        let on_conflict = if updates.is_empty() {
+            OnConflict::Ignore
+        } else {
+            seed.on_conflict
+        };
+
+        let sql = match (self.dialect, on_conflict) {
+            (Dialect::PostgreSQL, OnConflict::Ignore) => format!(
+                "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT DO NOTHING",
+                table, column_list, value_list
+            ),
+            (Dialect::MySQL, OnConflict::Ignore) => format!(
+                "INSERT IGNORE INTO {} ({}) VALUES ({})",
+                table, column_list, value_list
+            ),
+            (Dialect::SQLite, OnConflict::Ignore) => format!(
+                "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+                table, column_list, value_list
+            ),
+            (Dialect::PostgreSQL | Dialect::SQLite, OnConflict::Update) => {
+                let excluded = match self.dialect {
+                    Dialect::PostgreSQL => "EXCLUDED",
+                    _ => "excluded",
+                };
+                let assignments: Vec<String> = updates
+                    .iter()
+                    .map(|column| {
+                        let quoted = self.quote_identifier(column);
+                        format!("{} = {}.{}", quoted, excluded, quoted)
+                    })
+                    .collect();
+                let key: Vec<String> = seed.key.iter().map(|c| self.quote_identifier(c)).collect();
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) DO UPDATE SET {}",
+                    table,
+                    column_list,
+                    value_list,
+                    key.join(", "),
+                    assignments.join(", ")
+                )
+            }
+            (Dialect::MySQL, OnConflict::Update) => {
+                let assignments: Vec<String> = updates
+                    .iter()
+                    .map(|column| {
+                        let quoted = self.quote_identifier(column);
+                        format!("{} = VALUES({})", quoted, quoted)
+                    })
+                    .collect();
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({}) ON DUPLICATE KEY UPDATE {}",
+                    table,
+                    column_list,
+                    value_list,
+                    assignments.join(", ")
+                )
+            }
+        };
+        Ok(sql)
+    }
+
+    /// YAMLの値をSQLリテラルにする
+    fn literal(&self, value: &serde_json::Value) -> Result<String> {
+        match value {
+            serde_json::Value::Null => Ok("NULL".to_string()),
+            // SQLite の TRUE / FALSE は 3.23 以降のため、整数で書く
+            serde_json::Value::Bool(value) => Ok(match (self.dialect, value) {
+                (Dialect::SQLite, true) => "1".to_string(),
+                (Dialect::SQLite, false) => "0".to_string(),
+                (_, true) => "TRUE".to_string(),
+                (_, false) => "FALSE".to_string(),
+            }),
+            serde_json::Value::Number(number) => Ok(number.to_string()),
+            serde_json::Value::String(text) => Ok(quote_string_literal(self.dialect, text)),
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => Err(anyhow!(
+                "Seed values must be a string, number, boolean or null"
+            )),
+        }
+    }
+
+    fn quote_table(&self, name: &str) -> String {
+        match self.dialect {
+            Dialect::PostgreSQL => quote_table_postgres(name),
+            _ => self.quote_identifier(name),
+        }
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        quote_identifier(self.dialect, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ROLES: &str = r#"
+table: roles
+on_conflict: update
+key: [id]
+rows:
+  - id: 1
+    name: admin
+    builtin: true
+  - id: 2
+    name: "O'Brien \\ team"
+    builtin: false
+    description: null
+"#;
+
+    #[test]
+    fn test_build_statements_on_conflict_update() {
+        let seed = YamlSeed::from_yaml(ROLES).unwrap();
+        let cases = [
+            (
+                Dialect::PostgreSQL,
+                [
+                    r#"INSERT INTO "roles" ("builtin", "id", "name") VALUES (TRUE, 1, 'admin') ON CONFLICT ("id") DO UPDATE SET "builtin" = EXCLUDED."builtin", "name" = EXCLUDED."name""#,
+                    r#"INSERT INTO "roles" ("builtin", "description", "id", "name") VALUES (FALSE, NULL, 2, 'O''Brien \ team') ON CONFLICT ("id") DO UPDATE SET "builtin" = EXCLUDED."builtin", "description" = EXCLUDED."description", "name" = EXCLUDED."name""#,
+                ],
+            ),
+            (
+                Dialect::MySQL,
+                [
+                    r#"INSERT INTO `roles` (`builtin`, `id`, `name`) VALUES (TRUE, 1, 'admin') ON DUPLICATE KEY UPDATE `builtin` = VALUES(`builtin`), `name` = VALUES(`name`)"#,
+                    r#"INSERT INTO `roles` (`builtin`, `description`, `id`, `name`) VALUES (FALSE, NULL, 2, 'O''Brien \\ team') ON DUPLICATE KEY UPDATE `builtin` = VALUES(`builtin`), `description` = VALUES(`description`), `name` = VALUES(`name`)"#,
+                ],
+            ),
+            (
+                Dialect::SQLite,
+                [
+                    r#"INSERT INTO "roles" ("builtin", "id", "name") VALUES (1, 1, 'admin') ON CONFLICT ("id") DO UPDATE SET "builtin" = excluded."builtin", "name" = excluded."name""#,
+                    r#"INSERT INTO "roles" ("builtin", "description", "id", "name") VALUES (0, NULL, 2, 'O''Brien \ team') ON CONFLICT ("id") DO UPDATE SET "builtin" = excluded."builtin", "description" = excluded."description", "name" = excluded."name""#,
+                ],
+            ),
+        ];
+        for (dialect, expected) in cases {
+            let statements = SeedStatementBuilder::new(dialect)
+                .build_statements(&seed)
+                .unwrap();
+            assert_eq!(statements, expected, "dialect: {:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_build_statements_on_conflict_ignore() {
+        let seed = YamlSeed::from_yaml("table: app.countries\nrows:\n  - code: JP\n").unwrap();
+        let cases = [
+            (
+                Dialect::PostgreSQL,
+                r#"INSERT INTO "app"."countries" ("code") VALUES ('JP') ON CONFLICT DO NOTHING"#,
+            ),
+            (
+                Dialect::MySQL,
+                "INSERT IGNORE INTO `app.countries` (`code`) VALUES ('JP')",
+            ),
+            (
+                Dialect::SQLite,
+                r#"INSERT OR IGNORE INTO "app.countries" ("code") VALUES ('JP')"#,
+            ),
+        ];
+        for (dialect, expected) in cases {
+            let statements = SeedStatementBuilder::new(dialect)
+                .build_statements(&seed)
+                .unwrap();
+            assert_eq!(statements, vec![expected.to_string()]);
+        }
+    }
+
+    #[test]
+    fn test_update_with_only_key_columns_ignores_conflicts() {
+        let seed = YamlSeed::from_yaml(
+            "table: tags\non_conflict: update\nkey: [name]\nrows:\n  - name: new\n",
+        )
+        .unwrap();
+        let statements = SeedStatementBuilder::new(Dialect::PostgreSQL)
+            .build_statements(&seed)
+            .unwrap();
+        assert_eq!(
+            statements,
+            vec![r#"INSERT INTO "tags" ("name") VALUES ('new') ON CONFLICT DO NOTHING"#]
+        );
+    }
+
+    #[test]
+    fn test_from_yaml_rejects_invalid_seeds() {
+        let cases = [
+            ("table: ''\nrows: []\n", "'table' must not be empty"),
+            (
+                "table: roles\non_conflict: update\nrows: []\n",
+                "requires 'key'",
+            ),
+            (
+                "table: roles\nkey: [id]\nrows:\n  - name: admin\n",
+                "Row 1 has no value for key column 'id'",
+            ),
+            (
+                "table: roles\nrows:\n  - id: 1\n    tags: [a, b]\n",
+                "Row 1 column 'tags' must be a string, number, boolean or null",
+            ),
+            ("table: roles\nrows:\n  - {}\n", "Row 1 has no columns"),
+        ];
+        for (yaml, expected) in cases {
+            let err = YamlSeed::from_yaml(yaml).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(expected),
+                "yaml: {:?}, error: {:#}",
+                yaml,
+                err
+            );
+        }
+        assert!(YamlSeed::from_yaml("table: roles\nunknown: 1\n").is_err());
+    }
+
+    #[test]
+    fn test_load_seed_files_sorted_by_name() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "02_countries.sql",
+            "01_roles.yaml",
+            "10_tags.yml",
+            "README.md",
+        ] {
+            fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+        fs::create_dir(temp_dir.path().join("archive")).unwrap();
+
+        let files = load_seed_files(temp_dir.path()).unwrap();
+        let names: Vec<(&str, SeedFileKind)> =
+            files.iter().map(|f| (f.name.as_str(), f.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("01_roles.yaml", SeedFileKind::Yaml),
+                ("02_countries.sql", SeedFileKind::Sql),
+                ("10_tags.yml", SeedFileKind::Yaml),
+            ]
+        );
+        assert!(files[0].matches("01_roles.yaml"));
+        assert!(files[0].matches("01_roles"));
+        assert!(!files[0].matches("roles"));
+    }
+}
@@ -4,12 +4,15 @@
 // 各サービスの公開インターフェースを抽象化します。
 
 use anyhow::Result;
+use std::collections::BTreeMap;
 
-use crate::core::config::Dialect;
+use crate::core::config::{Dialect, IdentifierConfig, NamingConfig};
 use crate::core::destructive_change_report::DestructiveChangeReport;
 use crate::core::error::{ValidationResult, ValidationWarning};
+use crate::core::policy_report::PolicyReport;
 use crate::core::schema::Schema;
 use crate::core::schema_diff::SchemaDiff;
+use crate::services::schema_diff_detector::SchemaDiffOptions;
 
 /// スキーマ差分検出サービスのトレイト
 pub trait SchemaDiffDetector {
@@ -18,6 +21,7 @@ pub trait SchemaDiffDetector {
         &self,
         old_schema: &Schema,
         new_schema: &Schema,
+        options: &SchemaDiffOptions,
     ) -> (SchemaDiff, Vec<ValidationWarning>);
 }
 
@@ -29,6 +33,47 @@ pub trait SchemaValidator {
         old_schema: &Schema,
         new_schema: &Schema,
     ) -> ValidationResult;
+
+    /// 必須メタデータキーの検証
+    fn validate_required_metadata(
+        &self,
+        schema: &Schema,
+        required_keys: &[String],
+    ) -> ValidationResult;
+
+    /// 命名規約の検証
+    fn validate_naming_conventions(
+        &self,
+        schema: &Schema,
+        naming: &NamingConfig,
+    ) -> ValidationResult;
+
+    /// 外部テーブル宣言の検証
+    fn validate_external_tables(&self, schema: &Schema) -> ValidationResult;
+
+    /// 部分インデックスの条件式の検証
+    fn validate_index_predicates(&self, schema: &Schema, dialect: Dialect) -> ValidationResult;
+
+    /// デフォルト値の式の検証
+    fn validate_default_expressions(&self, schema: &Schema, dialect: Dialect) -> ValidationResult;
+
+    /// 現在日時のデフォルト値の検証
+    fn validate_current_timestamp_defaults(
+        &self,
+        schema: &Schema,
+        dialect: Dialect,
+    ) -> ValidationResult;
+
+    /// 生成カラムの検証
+    fn validate_generated_columns(&self, schema: &Schema, dialect: Dialect) -> ValidationResult;
+
+    /// 識別子の検証
+    fn validate_identifiers(
+        &self,
+        schema: &Schema,
+        dialect: Dialect,
+        identifiers: &IdentifierConfig,
+    ) -> ValidationResult;
 }
 
 /// マイグレーション生成サービスのトレイト
@@ -63,12 +108,19 @@ pub trait MigrationGenerator {
     ) -> Result<(String, ValidationResult)>;
 
     /// マイグレーションメタデータを生成
+    #[allow(clippy::too_many_arguments)]
     fn generate_migration_metadata(
         &self,
         version: &str,
         description: &str,
         dialect: Dialect,
         checksum: &str,
+        up_sql_checksum: Option<String>,
+        statement_terminator: Option<String>,
         destructive_changes: DestructiveChangeReport,
+        policy: Option<PolicyReport>,
+        rollback_protected_tables: Vec<String>,
+        touched_tables: Option<Vec<String>>,
+        metadata: BTreeMap<String, String>,
     ) -> Result<String>;
 }
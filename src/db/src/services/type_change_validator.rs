@@ -6,7 +6,7 @@ use crate::core::config::Dialect;
 use crate::core::error::{ErrorLocation, ValidationError, ValidationResult, ValidationWarning};
 use crate::core::schema::ColumnType;
 use crate::core::schema_diff::{ColumnChange, ColumnDiff};
-use crate::core::type_category::{TypeCategory, TypeConversionResult};
+use crate::core::type_category::{DecimalChange, TypeCategory, TypeConversionResult};
 
 /// 型変更検証サービス
 ///
@@ -62,12 +62,28 @@ impl TypeChangeValidator {
             match self.validate_type_compatibility(old_type, new_type, table_name, column_name) {
                 Ok(Some(warning)) => result.add_warning(warning),
                 Ok(None) => {}
+                // PostgreSQL では YAML の USING 式（type_change_using）で変換できる
+                Err(error) if matches!(dialect, Dialect::PostgreSQL) => {
+                    match column_diff.up_using() {
+                        Some(_) => {
+                            if let Some(warning) = self.validate_down_using(column_diff, table_name)
+                            {
+                                result.add_warning(warning);
+                            }
+                        }
+                        None => result.add_error(self.require_type_change_using(
+                            error,
+                            column_diff,
+                            table_name,
+                        )),
+                    }
+                }
                 Err(error) => result.add_error(error),
             }
 
             // 精度損失の検証（同一カテゴリ内でのサイズ縮小）
             if let Some(warning) =
-                self.validate_precision_loss(old_type, new_type, table_name, column_name)
+                self.validate_precision_loss(old_type, new_type, table_name, column_name, dialect)
             {
                 result.add_warning(warning);
             }
@@ -87,6 +103,7 @@ impl TypeChangeValidator {
     /// 型互換性の検証
     ///
     /// カテゴリ間の変換ルールに基づいて警告またはエラーを返します。
+    #[allow(clippy::result_large_err)]
     fn validate_type_compatibility(
         &self,
         old_type: &ColumnType,
@@ -126,6 +143,68 @@ impl TypeChangeValidator {
         }
     }
 
+    /// USING 式がない互換性のない型変更のエラーに、追加すべきキーを示す
+    fn require_type_change_using(
+        &self,
+        error: ValidationError,
+        column_diff: &ColumnDiff,
+        table_name: &str,
+    ) -> ValidationError {
+        let ValidationError::TypeConversion {
+            message, location, ..
+        } = error
+        else {
+            return error;
+        };
+        let column_name = &column_diff.column_name;
+        let reused = if column_diff.old_column.type_change_using.is_some() {
+            " (the existing expression was already used by an earlier migration, so change it to the one for this type change)"
+        } else {
+            ""
+        };
+        let message = format!(
+            "{}; add `type_change_using: \"<expression>\"` to column '{}.{}' in the schema YAML with the PostgreSQL expression that converts the current value{}",
+            message, table_name, column_name, reused
+        );
+        let suggestion = Some(format!(
+            "Add `type_change_using` to column '{}.{}' (e.g. `type_change_using: \"NULLIF({}, '')::integer\"`), and `down_using` for down.sql if the reverse conversion also needs one",
+            table_name, column_name, column_name
+        ));
+        ValidationError::TypeConversion {
+            message,
+            location,
+            suggestion,
+        }
+    }
+
+    /// down.sql の逆方向の型変更にも USING 式が必要かの検証
+    fn validate_down_using(
+        &self,
+        column_diff: &ColumnDiff,
+        table_name: &str,
+    ) -> Option<ValidationWarning> {
+        let old_category = TypeCategory::from_column_type(&column_diff.old_column.column_type);
+        let new_category = TypeCategory::from_column_type(&column_diff.new_column.column_type);
+        if column_diff.down_using().is_some()
+            || new_category.conversion_result(&old_category) != TypeConversionResult::Error
+        {
+            return None;
+        }
+        Some(ValidationWarning::compatibility(
+            format!(
+                "{} → {} in down.sql has no USING expression; add `down_using` to column '{}.{}' or edit down.sql before rolling back",
+                column_diff.new_column.column_type,
+                column_diff.old_column.column_type,
+                table_name,
+                column_diff.column_name
+            ),
+            Some(ErrorLocation::with_table_and_column(
+                table_name,
+                &column_diff.column_name,
+            )),
+        ))
+    }
+
     /// 精度損失の検証
     ///
     /// 同一カテゴリ内でのサイズ縮小を検出します。
@@ -135,6 +214,7 @@ impl TypeChangeValidator {
         new_type: &ColumnType,
         table_name: &str,
         column_name: &str,
+        dialect: &Dialect,
     ) -> Option<ValidationWarning> {
         let location = Some(ErrorLocation::with_table_and_column(
             table_name,
@@ -142,85 +222,93 @@ impl TypeChangeValidator {
         ));
 
         match (old_type, new_type) {
-            // VARCHAR サイズ縮小
-            (ColumnType::VARCHAR { length: old_len }, ColumnType::VARCHAR { length: new_len })
-                if new_len < old_len =>
-            {
-                let message = format!(
-                    "VARCHAR({}) → VARCHAR({}) may cause data truncation",
-                    old_len, new_len
-                );
+            // 可変長 → 固定長: 短い値は末尾が空白で埋められ、長い値は切り詰められる
+            (
+                ColumnType::VARCHAR { .. } | ColumnType::TEXT,
+                ColumnType::CHAR { length: new_len },
+            ) => {
+                let may_truncate = match old_type {
+                    ColumnType::VARCHAR { length: old_len } => new_len < old_len,
+                    _ => true,
+                };
+                let message = if may_truncate {
+                    format!(
+                        "{} → {} pads shorter values with trailing spaces and may cause data truncation",
+                        old_type, new_type
+                    )
+                } else {
+                    format!(
+                        "{} → {} pads shorter values with trailing spaces",
+                        old_type, new_type
+                    )
+                };
                 Some(ValidationWarning::precision_loss(message, location))
             }
 
-            // CHAR サイズ縮小
-            (ColumnType::CHAR { length: old_len }, ColumnType::CHAR { length: new_len })
-                if new_len < old_len =>
-            {
-                let message = format!(
-                    "CHAR({}) → CHAR({}) may cause data truncation",
-                    old_len, new_len
-                );
+            // VARCHAR / CHAR サイズ縮小（CHAR → VARCHAR を含む）
+            (
+                ColumnType::VARCHAR { length: old_len } | ColumnType::CHAR { length: old_len },
+                ColumnType::VARCHAR { length: new_len } | ColumnType::CHAR { length: new_len },
+            ) if new_len < old_len => {
+                let message = format!("{} → {} may cause data truncation", old_type, new_type);
                 Some(ValidationWarning::precision_loss(message, location))
             }
 
-            // DECIMAL 精度縮小
-            (
-                ColumnType::DECIMAL {
-                    precision: old_prec,
-                    scale: old_scale,
-                },
-                ColumnType::DECIMAL {
-                    precision: new_prec,
-                    scale: new_scale,
-                },
-            ) if new_prec < old_prec || new_scale < old_scale => {
+            // DECIMAL 精度・スケール縮小（整数部・小数部の桁数で判定）
+            (ColumnType::DECIMAL { .. }, ColumnType::DECIMAL { .. }) => {
+                let reason = DecimalChange::between(old_type, new_type)?.reason()?;
                 let message = format!(
-                    "DECIMAL({}, {}) → DECIMAL({}, {}) may cause precision loss",
-                    old_prec, old_scale, new_prec, new_scale
+                    "{} → {} may cause precision loss: {}",
+                    old_type, new_type, reason
                 );
                 Some(ValidationWarning::precision_loss(message, location))
             }
 
-            // INTEGER 精度縮小 (precision指定がある場合)
+            // TIMESTAMP / TIME の秒の小数部の桁数縮小（未指定は方言のデフォルト精度）
             (
-                ColumnType::INTEGER {
-                    precision: Some(old_prec),
+                ColumnType::TIMESTAMP {
+                    precision: old_prec,
+                    ..
                 },
-                ColumnType::INTEGER {
-                    precision: Some(new_prec),
+                ColumnType::TIMESTAMP {
+                    precision: new_prec,
+                    ..
                 },
-            ) if new_prec < old_prec => {
-                let message = format!(
-                    "INTEGER({}) → INTEGER({}) may cause overflow",
-                    old_prec, new_prec
-                );
-                Some(ValidationWarning::precision_loss(message, location))
-            }
-
-            // BIGINT → INTEGER (8 → 4バイト)
-            (
-                ColumnType::INTEGER { precision: Some(8) },
-                ColumnType::INTEGER { precision: Some(4) },
             )
             | (
-                ColumnType::INTEGER { precision: Some(8) },
-                ColumnType::INTEGER { precision: None },
+                ColumnType::TIME {
+                    precision: old_prec,
+                    ..
+                },
+                ColumnType::TIME {
+                    precision: new_prec,
+                    ..
+                },
             ) => {
-                let message = "BIGINT → INTEGER may cause overflow for large values".to_string();
-                Some(ValidationWarning::precision_loss(message, location))
+                let default = dialect.default_datetime_precision();
+                match (old_prec.or(default), new_prec.or(default)) {
+                    (Some(old_prec), Some(new_prec)) if new_prec < old_prec => {
+                        let message = format!(
+                            "{} → {} truncates fractional seconds beyond {} digit(s)",
+                            old_type, new_type, new_prec
+                        );
+                        Some(ValidationWarning::precision_loss(message, location))
+                    }
+                    _ => None,
+                }
             }
 
-            // INTEGER → SMALLINT (4 → 2バイト)
-            (
-                ColumnType::INTEGER { precision: Some(4) },
-                ColumnType::INTEGER { precision: Some(2) },
-            )
-            | (
-                ColumnType::INTEGER { precision: None },
-                ColumnType::INTEGER { precision: Some(2) },
-            ) => {
-                let message = "INTEGER → SMALLINT may cause overflow for large values".to_string();
+            // 整数型の縮小（BIGINT → INTEGER、INTEGER → SMALLINT など）
+            _ if matches!(
+                (old_type.integer_width(), new_type.integer_width()),
+                (Some(old_width), Some(new_width)) if new_width < old_width
+            ) =>
+            {
+                let message = format!(
+                    "{} → {} may cause overflow for large values",
+                    integer_type_name(old_type),
+                    integer_type_name(new_type)
+                );
                 Some(ValidationWarning::precision_loss(message, location))
             }
 
@@ -368,6 +456,14 @@ impl Default for TypeChangeValidator {
     }
 }
 
+/// 整数型の名前（非推奨の `INTEGER` の `precision` 指定も対応する型の名前にする）
+fn integer_type_name(column_type: &ColumnType) -> String {
+    column_type
+        .normalize_integer_precision()
+        .unwrap_or_else(|| column_type.clone())
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,6 +523,7 @@ mod tests {
             ColumnType::INTEGER { precision: None },
             ColumnType::TIMESTAMP {
                 with_time_zone: None,
+                precision: None,
             },
         );
 
@@ -454,6 +551,76 @@ mod tests {
         assert_eq!(result.error_count(), 1);
     }
 
+    #[test]
+    fn test_error_conversion_postgres_names_type_change_using() {
+        let validator = TypeChangeValidator::new();
+        let diff = create_column_diff(
+            "data",
+            ColumnType::JSONB,
+            ColumnType::INTEGER { precision: None },
+        );
+
+        let result = validator.validate_type_changes("documents", &[diff], &Dialect::PostgreSQL);
+
+        assert_eq!(result.error_count(), 1);
+        let message = result.errors[0].to_string();
+        assert!(
+            message
+                .contains("add `type_change_using: \"<expression>\"` to column 'documents.data'"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_type_change_using_allows_incompatible_conversion_on_postgres() {
+        let validator = TypeChangeValidator::new();
+        let mut diff = create_column_diff(
+            "data",
+            ColumnType::JSONB,
+            ColumnType::INTEGER { precision: None },
+        );
+        diff.new_column.type_change_using = Some("(data->>'count')::integer".to_string());
+
+        let result =
+            validator.validate_type_changes("documents", &[diff.clone()], &Dialect::PostgreSQL);
+
+        // 逆方向（INTEGER → JSONB）にも USING 式が必要なため警告
+        assert!(result.is_valid());
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.warnings[0].message.contains("add `down_using`"));
+
+        diff.new_column.down_using = Some("jsonb_build_object('count', data)".to_string());
+        let result =
+            validator.validate_type_changes("documents", &[diff.clone()], &Dialect::PostgreSQL);
+        assert!(result.is_valid());
+        assert_eq!(result.warning_count(), 0);
+
+        // MySQL では USING 式を使わないため、エラーのまま
+        let result = validator.validate_type_changes("documents", &[diff], &Dialect::MySQL);
+        assert_eq!(result.error_count(), 1);
+        assert!(!result.errors[0].to_string().contains("type_change_using"));
+    }
+
+    #[test]
+    fn test_type_change_using_from_previous_migration_is_not_reused() {
+        let validator = TypeChangeValidator::new();
+        let mut diff = create_column_diff(
+            "data",
+            ColumnType::JSONB,
+            ColumnType::INTEGER { precision: None },
+        );
+        diff.old_column.type_change_using = Some("data::text".to_string());
+        diff.new_column.type_change_using = Some("data::text".to_string());
+
+        let result = validator.validate_type_changes("documents", &[diff], &Dialect::PostgreSQL);
+
+        assert_eq!(result.error_count(), 1);
+        assert!(result.errors[0]
+            .to_string()
+            .contains("already used by an earlier migration"));
+    }
+
     #[test]
     fn test_safe_conversion_boolean_to_numeric() {
         let validator = TypeChangeValidator::new();
@@ -534,6 +701,57 @@ mod tests {
         assert!(result.warnings[0].message.contains("precision loss"));
     }
 
+    #[test]
+    fn test_decimal_scale_increase_without_precision_warns_overflow() {
+        let validator = TypeChangeValidator::new();
+        let decimal = |precision, scale| ColumnType::DECIMAL { precision, scale };
+
+        let diff = create_column_diff("price", decimal(10, 2), decimal(10, 4));
+        let result = validator.validate_type_changes("products", &[diff], &Dialect::PostgreSQL);
+        assert_eq!(result.warning_count(), 1);
+        assert!(result.warnings[0]
+            .message
+            .contains("DECIMAL(10, 2) → DECIMAL(10, 4) may cause precision loss: existing values may overflow"));
+
+        // 整数部・小数部ともに拡大する場合は警告なし
+        let diff = create_column_diff("price", decimal(10, 2), decimal(12, 4));
+        let result = validator.validate_type_changes("products", &[diff], &Dialect::PostgreSQL);
+        assert_eq!(result.warning_count(), 0);
+    }
+
+    #[test]
+    fn test_timestamp_precision_decrease_warns() {
+        let validator = TypeChangeValidator::new();
+        let timestamp = |precision| ColumnType::TIMESTAMP {
+            with_time_zone: None,
+            precision,
+        };
+        let validate = |old_precision, new_precision, dialect| {
+            let diff = create_column_diff(
+                "created_at",
+                timestamp(old_precision),
+                timestamp(new_precision),
+            );
+            validator.validate_type_changes("events", &[diff], &dialect)
+        };
+
+        // 精度の拡大は安全
+        assert_eq!(
+            validate(Some(3), Some(6), Dialect::MySQL).warning_count(),
+            0
+        );
+        assert_eq!(validate(None, Some(3), Dialect::MySQL).warning_count(), 0);
+
+        // 精度の縮小は秒の小数部が切り捨てられる（PostgreSQL の未指定は 6 桁）
+        let result = validate(None, Some(3), Dialect::PostgreSQL);
+        assert_eq!(result.warning_count(), 1);
+        assert_eq!(
+            result.warnings[0].message,
+            "TIMESTAMP → TIMESTAMP(3) truncates fractional seconds beyond 3 digit(s)"
+        );
+        assert_eq!(validate(Some(6), None, Dialect::MySQL).warning_count(), 1);
+    }
+
     #[test]
     fn test_precision_loss_bigint_to_integer() {
         let validator = TypeChangeValidator::new();
@@ -567,6 +785,97 @@ mod tests {
         assert_eq!(result.warning_count(), 0);
     }
 
+    #[test]
+    fn test_char_to_varchar_is_safe() {
+        let validator = TypeChangeValidator::new();
+        let diffs = vec![
+            create_column_diff(
+                "country",
+                ColumnType::CHAR { length: 2 },
+                ColumnType::VARCHAR { length: 2 },
+            ),
+            create_column_diff("code", ColumnType::CHAR { length: 2 }, ColumnType::TEXT),
+        ];
+
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL, Dialect::SQLite] {
+            let result = validator.validate_type_changes("addresses", &diffs, &dialect);
+
+            // 固定長 → 可変長はデータを失わない
+            assert!(result.is_valid());
+            assert_eq!(result.warning_count(), 0, "{:?}", dialect);
+        }
+    }
+
+    #[test]
+    fn test_varchar_to_char_warns_about_padding() {
+        let validator = TypeChangeValidator::new();
+        let diff = create_column_diff(
+            "country",
+            ColumnType::VARCHAR { length: 2 },
+            ColumnType::CHAR { length: 2 },
+        );
+
+        let result = validator.validate_type_changes("addresses", &[diff], &Dialect::PostgreSQL);
+
+        assert!(result.is_valid());
+        assert_eq!(result.warning_count(), 1);
+        assert_eq!(
+            result.warnings[0].message,
+            "VARCHAR(2) → CHAR(2) pads shorter values with trailing spaces"
+        );
+
+        let diffs = vec![
+            create_column_diff(
+                "country",
+                ColumnType::VARCHAR { length: 3 },
+                ColumnType::CHAR { length: 2 },
+            ),
+            create_column_diff("code", ColumnType::TEXT, ColumnType::CHAR { length: 2 }),
+        ];
+        let result = validator.validate_type_changes("addresses", &diffs, &Dialect::PostgreSQL);
+
+        assert_eq!(result.warning_count(), 2);
+        assert!(result
+            .warnings
+            .iter()
+            .all(|w| w.message.contains("trailing spaces") && w.message.contains("truncation")));
+    }
+
+    #[test]
+    fn test_char_length_changes() {
+        let validator = TypeChangeValidator::new();
+        let diffs = vec![
+            create_column_diff(
+                "country",
+                ColumnType::CHAR { length: 2 },
+                ColumnType::CHAR { length: 3 },
+            ),
+            create_column_diff(
+                "region",
+                ColumnType::CHAR { length: 3 },
+                ColumnType::CHAR { length: 2 },
+            ),
+            create_column_diff(
+                "code",
+                ColumnType::CHAR { length: 3 },
+                ColumnType::VARCHAR { length: 2 },
+            ),
+        ];
+
+        let result = validator.validate_type_changes("addresses", &diffs, &Dialect::PostgreSQL);
+
+        // 拡大は警告なし、縮小は CHAR → VARCHAR を含めて警告
+        assert!(result.is_valid());
+        let messages: Vec<&str> = result.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "CHAR(3) → CHAR(2) may cause data truncation",
+                "CHAR(3) → VARCHAR(2) may cause data truncation",
+            ]
+        );
+    }
+
     // ==========================================
     // 複合テスト
     // ==========================================
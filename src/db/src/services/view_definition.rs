@@ -0,0 +1,385 @@
+// ビュー定義の字句解析
+//
+// ビュー定義（SELECT文）を、文字列リテラル・コメント・クォート識別子を区別する
+// 簡易的な字句解析で走査し、FROM / JOIN 句のテーブル参照を取り出します。
+// SQLを完全に解析するものではなく、リネームの反映やビューの参照先の検証に使用します。
+
+/// ビュー定義の字句
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// 識別子（キーワードを含む）
+    Ident,
+    /// 記号1文字
+    Punct(char),
+    /// 文字列リテラル・数値など
+    Other,
+}
+
+/// 字句と定義文字列中の位置
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    /// 識別子の値（クォートを除いたもの）
+    pub(crate) value: String,
+    /// ダブルクォート・バッククォートで囲まれた識別子の場合はその文字
+    pub(crate) quote: Option<char>,
+    /// 定義文字列中の開始位置（バイト）
+    pub(crate) start: usize,
+    /// 定義文字列中の終了位置（バイト）
+    pub(crate) end: usize,
+}
+
+impl Token {
+    pub(crate) fn is_ident(&self) -> bool {
+        self.kind == TokenKind::Ident
+    }
+
+    pub(crate) fn is_punct(&self, ch: char) -> bool {
+        self.kind == TokenKind::Punct(ch)
+    }
+
+    pub(crate) fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_ident() && self.quote.is_none() && self.value.eq_ignore_ascii_case(keyword)
+    }
+
+    /// 識別子が名前に一致するか（クォートなしは大文字・小文字を区別しない）
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        self.is_ident()
+            && match self.quote {
+                Some(_) => self.value == name,
+                None => self.value.eq_ignore_ascii_case(name),
+            }
+    }
+
+    /// 元のクォートを保ったまま名前を置き換えた文字列
+    pub(crate) fn replaced_with(&self, name: &str) -> String {
+        match self.quote {
+            Some(quote) => {
+                let escaped = name.replace(quote, &format!("{}{}", quote, quote));
+                format!("{}{}{}", quote, escaped, quote)
+            }
+            None => name.to_string(),
+        }
+    }
+}
+
+/// テーブル参照の後に別名として扱わないキーワード
+const NON_ALIAS_KEYWORDS: &[&str] = &[
+    "where",
+    "join",
+    "inner",
+    "left",
+    "right",
+    "full",
+    "cross",
+    "natural",
+    "outer",
+    "on",
+    "using",
+    "group",
+    "order",
+    "having",
+    "limit",
+    "offset",
+    "fetch",
+    "union",
+    "except",
+    "intersect",
+    "window",
+    "for",
+    "lateral",
+    "select",
+    "and",
+    "or",
+];
+
+/// ビュー定義を字句に分割する
+///
+/// 文字列リテラル・コメントは識別子として扱わず、ダブルクォートとバッククォートは
+/// クォート識別子として扱う。
+pub(crate) fn tokenize(definition: &str) -> Vec<Token> {
+    let chars: Vec<(usize, char)> = definition.char_indices().collect();
+    let len = chars.len();
+    let offset = |i: usize| chars.get(i).map_or(definition.len(), |(pos, _)| *pos);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        let (start, ch) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch == '-' && next == Some('-') {
+            while i < len && chars[i].1 != '\n' {
+                i += 1;
+            }
+        } else if ch == '/' && next == Some('*') {
+            i += 2;
+            while i < len && !(chars[i].1 == '*' && chars.get(i + 1).map(|(_, c)| *c) == Some('/'))
+            {
+                i += 1;
+            }
+            i = (i + 2).min(len);
+        } else if ch == '\'' || ch == '"' || ch == '`' {
+            // クォート内の同じ文字の連続はエスケープとして扱う
+            let mut value = String::new();
+            i += 1;
+            while i < len {
+                let c = chars[i].1;
+                if c == ch {
+                    if chars.get(i + 1).map(|(_, c)| *c) == Some(ch) {
+                        value.push(ch);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                value.push(c);
+                i += 1;
+            }
+            let (kind, quote) = if ch == '\'' {
+                (TokenKind::Other, None)
+            } else {
+                (TokenKind::Ident, Some(ch))
+            };
+            tokens.push(Token {
+                kind,
+                value,
+                quote,
+                start,
+                end: offset(i),
+            });
+        } else if ch.is_alphabetic() || ch == '_' {
+            let begin = i;
+            while i < len
+                && (chars[i].1.is_alphanumeric() || chars[i].1 == '_' || chars[i].1 == '$')
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident,
+                value: chars[begin..i].iter().map(|(_, c)| *c).collect(),
+                quote: None,
+                start,
+                end: offset(i),
+            });
+        } else if ch.is_ascii_digit() {
+            while i < len && (chars[i].1.is_alphanumeric() || chars[i].1 == '.') {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Other,
+                value: String::new(),
+                quote: None,
+                start,
+                end: offset(i),
+            });
+        } else {
+            i += 1;
+            tokens.push(Token {
+                kind: TokenKind::Punct(ch),
+                value: String::new(),
+                quote: None,
+                start,
+                end: offset(i),
+            });
+        }
+    }
+
+    tokens
+}
+
+/// FROM / JOIN 句のテーブル参照
+#[derive(Debug)]
+pub(crate) struct TableRef {
+    /// テーブル名（schema.table の場合は schema）の先頭の字句の位置
+    pub(crate) start_index: usize,
+    /// テーブル名の字句の位置
+    pub(crate) name_index: usize,
+    /// 別名の字句の位置
+    pub(crate) alias_index: Option<usize>,
+}
+
+/// FROM / JOIN 句からテーブル参照を収集する
+///
+/// `EXTRACT(YEAR FROM x)` のような関数呼び出しの括弧内の FROM と、
+/// `FROM generate_series(...)` のようなテーブル関数は対象外とする。
+pub(crate) fn collect_table_refs(tokens: &[Token]) -> Vec<TableRef> {
+    let mut refs = Vec::new();
+    // 括弧ごとに、サブクエリ（SELECT / WITH で始まる）かどうかを積む
+    let mut query_parens = vec![true];
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if tokens[i].is_punct('(') {
+            let is_query = tokens
+                .get(i + 1)
+                .is_some_and(|next| next.is_keyword("select") || next.is_keyword("with"));
+            query_parens.push(is_query);
+            i += 1;
+            continue;
+        }
+        if tokens[i].is_punct(')') {
+            if query_parens.len() > 1 {
+                query_parens.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let in_query = query_parens.last().copied().unwrap_or(true);
+        if !in_query || !(tokens[i].is_keyword("from") || tokens[i].is_keyword("join")) {
+            i += 1;
+            continue;
+        }
+        let is_from = tokens[i].is_keyword("from");
+        i += 1;
+
+        // FROM a, b のようなカンマ区切りのテーブル参照も収集する
+        loop {
+            while i < tokens.len()
+                && (tokens[i].is_keyword("only") || tokens[i].is_keyword("lateral"))
+            {
+                i += 1;
+            }
+            if i >= tokens.len() || !tokens[i].is_ident() {
+                break;
+            }
+            // schema.table の場合は最後の識別子をテーブル名とする
+            let start_index = i;
+            let mut name_index = i;
+            while name_index + 2 < tokens.len()
+                && tokens[name_index + 1].is_punct('.')
+                && tokens[name_index + 2].is_ident()
+            {
+                name_index += 2;
+            }
+            i = name_index + 1;
+            if i < tokens.len() && tokens[i].is_punct('(') {
+                break;
+            }
+
+            let mut alias_index = None;
+            if i < tokens.len() && tokens[i].is_keyword("as") {
+                i += 1;
+            }
+            if i < tokens.len()
+                && tokens[i].is_ident()
+                && !NON_ALIAS_KEYWORDS
+                    .iter()
+                    .any(|keyword| tokens[i].is_keyword(keyword))
+            {
+                alias_index = Some(i);
+                i += 1;
+            }
+            refs.push(TableRef {
+                start_index,
+                name_index,
+                alias_index,
+            });
+
+            if is_from && i < tokens.len() && tokens[i].is_punct(',') {
+                i += 1;
+                continue;
+            }
+            break;
+        }
+    }
+
+    refs
+}
+
+/// WITH 句で定義された共通テーブル式の名前を収集する
+fn collect_cte_names(tokens: &[Token]) -> Vec<&Token> {
+    let mut names = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if !token.is_ident() {
+            continue;
+        }
+        // name AS ( ... ) または name (col, ...) AS ( ... )
+        let mut next = index + 1;
+        if tokens.get(next).is_some_and(|t| t.is_punct('(')) {
+            while next < tokens.len() && !tokens[next].is_punct(')') {
+                next += 1;
+            }
+            next += 1;
+        }
+        if tokens.get(next).is_some_and(|t| t.is_keyword("as"))
+            && tokens.get(next + 1).is_some_and(|t| t.is_punct('('))
+            && index > 0
+            && (tokens[index - 1].is_keyword("with")
+                || tokens[index - 1].is_keyword("recursive")
+                || tokens[index - 1].is_punct(','))
+        {
+            names.push(token);
+        }
+    }
+    names
+}
+
+/// ビュー定義が FROM / JOIN 句で参照するテーブル・ビューの名前（出現順、重複なし）
+///
+/// `schema.table` は `.` でつないだ名前を返す。共通テーブル式の名前は含めない。
+pub fn referenced_tables(definition: &str) -> Vec<String> {
+    let tokens = tokenize(definition);
+    let cte_names = collect_cte_names(&tokens);
+    let mut tables = Vec::new();
+
+    for table_ref in collect_table_refs(&tokens) {
+        let name = &tokens[table_ref.name_index];
+        if table_ref.start_index == table_ref.name_index
+            && cte_names.iter().any(|cte| name.matches(&cte.value))
+        {
+            continue;
+        }
+        let qualified = tokens[table_ref.start_index..=table_ref.name_index]
+            .iter()
+            .filter(|token| token.is_ident())
+            .map(|token| token.value.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        if !tables.contains(&qualified) {
+            tables.push(qualified);
+        }
+    }
+
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_tables_from_joins_and_comma_list() {
+        let tables = referenced_tables(
+            "SELECT u.id, o.total FROM users u, teams JOIN orders AS o ON o.user_id = u.id WHERE u.team_id = teams.id",
+        );
+        assert_eq!(tables, vec!["users", "teams", "orders"]);
+    }
+
+    #[test]
+    fn test_referenced_tables_skips_function_from_and_table_functions() {
+        let tables = referenced_tables(
+            "SELECT EXTRACT(YEAR FROM created_at) AS year, s.n FROM events, generate_series(1, 3) AS s(n)",
+        );
+        assert_eq!(tables, vec!["events"]);
+    }
+
+    #[test]
+    fn test_referenced_tables_includes_subqueries_and_skips_ctes() {
+        let tables = referenced_tables(
+            "WITH recent AS (SELECT * FROM orders WHERE created_at > now()) SELECT * FROM recent JOIN (SELECT id FROM users) u ON u.id = recent.user_id",
+        );
+        assert_eq!(tables, vec!["orders", "users"]);
+    }
+
+    #[test]
+    fn test_referenced_tables_keeps_schema_and_ignores_literals() {
+        let tables = referenced_tables(
+            "SELECT 'FROM fake' AS note /* FROM other */ FROM \"app\".\"Users\" -- JOIN ignored\n",
+        );
+        assert_eq!(tables, vec!["app.Users"]);
+    }
+}
@@ -0,0 +1,518 @@
+// ビュー定義へのリネーム反映
+//
+// テーブル・カラムのリネームと同じ変更で、ビュー定義が旧名を参照したままになっていないかを検出します。
+// ビュー定義は `view_definition` の字句解析で走査し、
+// FROM / JOIN 句のテーブル参照と別名から、修飾されたカラム参照の参照先を解決します。
+// 修飾されていないカラム参照は、参照先のテーブルが1つに定まる場合のみ書き換えの対象とします。
+
+use crate::core::schema::Schema;
+use crate::core::schema_diff::{SchemaDiff, ViewDiff};
+use crate::services::view_definition::{collect_table_refs, tokenize, Token};
+use std::collections::HashMap;
+
+/// 旧名を参照したままのビュー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleView {
+    /// ビュー名
+    pub view_name: String,
+
+    /// 旧名のまま残っている参照（例: `column 'users.name' -> 'full_name'`）
+    pub references: Vec<String>,
+
+    /// テキストの書き換えでは解決できない理由
+    pub blockers: Vec<String>,
+
+    /// 識別子を新しい名前に書き換えたビュー定義
+    pub rewritten_definition: String,
+}
+
+impl StaleView {
+    /// 書き換えで解決できるか
+    pub fn can_rewrite(&self) -> bool {
+        self.blockers.is_empty()
+    }
+}
+
+/// 差分に含まれるリネームを参照するビューを検出
+///
+/// 新しいスキーマのビューを名前順に走査し、旧名のテーブル・カラムを参照しているものを返します。
+/// この変更で追加・リネームされるビューは書き換えの対象にせず、`blockers` に理由を記録します。
+pub fn find_stale_views(
+    old_schema: &Schema,
+    new_schema: &Schema,
+    diff: &SchemaDiff,
+) -> Vec<StaleView> {
+    let renames = Renames::from_diff(diff);
+    if renames.is_empty() {
+        return Vec::new();
+    }
+
+    let mut stale_views = Vec::new();
+    for (view_name, view) in &new_schema.views {
+        let Some(mut stale) = analyze_definition(&view.definition, &renames, old_schema) else {
+            continue;
+        };
+        stale.view_name = view_name.clone();
+        if !old_schema.views.contains_key(view_name) {
+            stale.blockers.push(
+                "the view is created in this change; write its definition with the new names"
+                    .to_string(),
+            );
+        }
+        stale_views.push(stale);
+    }
+    stale_views
+}
+
+/// 書き換えたビュー定義を差分に反映
+///
+/// 既に変更として検出されているビューは定義を差し替え、それ以外は変更として追加します。
+/// いずれも `auto_rewritten` を立て、SQL生成時にレビューを促すコメントを出力させます。
+pub fn apply_rewrites(
+    diff: &mut SchemaDiff,
+    old_schema: &Schema,
+    new_schema: &Schema,
+    stale_views: &[StaleView],
+) {
+    let table_renames: HashMap<String, String> = diff
+        .renamed_tables
+        .iter()
+        .map(|renamed| (renamed.old_name.clone(), renamed.new_table.name.clone()))
+        .collect();
+
+    for stale in stale_views.iter().filter(|stale| stale.can_rewrite()) {
+        let (Some(old_view), Some(new_view)) = (
+            old_schema.views.get(&stale.view_name),
+            new_schema.views.get(&stale.view_name),
+        ) else {
+            continue;
+        };
+
+        let mut rewritten_view = new_view.clone();
+        rewritten_view.definition = stale.rewritten_definition.clone();
+        for dependency in &mut rewritten_view.depends_on {
+            if let Some(new_name) = table_renames.get(dependency) {
+                *dependency = new_name.clone();
+            }
+        }
+
+        if let Some(view_diff) = diff
+            .modified_views
+            .iter_mut()
+            .find(|view_diff| view_diff.view_name == stale.view_name)
+        {
+            view_diff.new_definition = rewritten_view.definition.clone();
+            view_diff.new_view = rewritten_view;
+            view_diff.auto_rewritten = true;
+        } else {
+            diff.modified_views.push(ViewDiff {
+                view_name: stale.view_name.clone(),
+                old_definition: old_view.definition.clone(),
+                new_definition: rewritten_view.definition.clone(),
+                old_view: old_view.clone(),
+                new_view: rewritten_view,
+                auto_rewritten: true,
+            });
+        }
+    }
+}
+
+/// 差分に含まれるテーブル・カラムのリネーム
+#[derive(Debug, Default)]
+struct Renames {
+    /// 旧テーブル名 → 新テーブル名
+    tables: Vec<(String, String)>,
+    /// テーブル名 → (旧カラム名, 新カラム名)
+    columns: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Renames {
+    fn from_diff(diff: &SchemaDiff) -> Self {
+        let mut renames = Self::default();
+        for renamed in &diff.renamed_tables {
+            renames
+                .tables
+                .push((renamed.old_name.clone(), renamed.new_table.name.clone()));
+        }
+        for table_diff in &diff.modified_tables {
+            for renamed in &table_diff.renamed_columns {
+                renames
+                    .columns
+                    .entry(table_diff.table_name.clone())
+                    .or_default()
+                    .push((renamed.old_name.clone(), renamed.new_column.name.clone()));
+            }
+        }
+        renames
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.columns.is_empty()
+    }
+
+    /// 旧テーブル名に一致する場合は新テーブル名を返す
+    fn renamed_table(&self, token: &Token) -> Option<&str> {
+        self.tables
+            .iter()
+            .find(|(old, _)| token.matches(old))
+            .map(|(_, new)| new.as_str())
+    }
+
+    /// テーブル参照（旧名・新名のどちらでもよい）を現在のテーブル名に解決する
+    fn current_table_name(&self, name: &Token) -> String {
+        if let Some(new_name) = self.renamed_table(name) {
+            return new_name.to_string();
+        }
+        self.columns
+            .keys()
+            .find(|table| name.matches(table))
+            .cloned()
+            .unwrap_or_else(|| name.value.clone())
+    }
+
+    fn renamed_column(&self, table: &str, token: &Token) -> Option<(&str, &str)> {
+        self.columns.get(table).and_then(|columns| {
+            columns
+                .iter()
+                .find(|(old, _)| token.matches(old))
+                .map(|(old, new)| (old.as_str(), new.as_str()))
+        })
+    }
+}
+
+/// ビュー定義を解析し、旧名の参照があれば書き換え結果を返す
+fn analyze_definition(
+    definition: &str,
+    renames: &Renames,
+    old_schema: &Schema,
+) -> Option<StaleView> {
+    let tokens = tokenize(definition);
+    let table_refs = collect_table_refs(&tokens);
+
+    // テーブル名・別名 → 現在のテーブル名
+    let mut qualifiers: Vec<(usize, String)> = Vec::new();
+    let mut referenced_tables: Vec<String> = Vec::new();
+    let mut table_positions = Vec::new();
+    let mut alias_positions = Vec::new();
+    for table_ref in &table_refs {
+        let table = renames.current_table_name(&tokens[table_ref.name_index]);
+        qualifiers.push((table_ref.name_index, table.clone()));
+        if let Some(alias_index) = table_ref.alias_index {
+            qualifiers.push((alias_index, table.clone()));
+            alias_positions.push(alias_index);
+        }
+        table_positions.push(table_ref.name_index);
+        if !referenced_tables.contains(&table) {
+            referenced_tables.push(table);
+        }
+    }
+    let resolve_qualifier = |token: &Token| {
+        qualifiers
+            .iter()
+            .rev()
+            .find(|(index, _)| {
+                let name = &tokens[*index];
+                match name.quote {
+                    Some(_) => token.matches(&name.value),
+                    None => name.value.eq_ignore_ascii_case(&token.value),
+                }
+            })
+            .map(|(_, table)| table.clone())
+    };
+
+    let mut replacements: Vec<(usize, usize, String)> = Vec::new();
+    let mut references: Vec<String> = Vec::new();
+    let mut blockers: Vec<String> = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if !token.is_ident() {
+            continue;
+        }
+        let after_dot = index > 0 && tokens[index - 1].is_punct('.');
+        let before_dot = tokens.get(index + 1).is_some_and(|next| next.is_punct('.'));
+
+        // テーブル名（FROM / JOIN 句、または table.column の修飾子）
+        if table_positions.contains(&index) || (before_dot && !after_dot) {
+            if let Some(new_name) = renames.renamed_table(token) {
+                let is_alias = table_refs.iter().any(|table_ref| {
+                    table_ref
+                        .alias_index
+                        .is_some_and(|alias| tokens[alias].matches(&token.value))
+                });
+                if table_positions.contains(&index) || !is_alias {
+                    note(
+                        &mut references,
+                        format!("table '{}' -> '{}'", token.value, new_name),
+                    );
+                    replacements.push((token.start, token.end, token.replaced_with(new_name)));
+                }
+            }
+            continue;
+        }
+        if alias_positions.contains(&index) {
+            continue;
+        }
+
+        // 修飾されたカラム参照（alias.column / table.column）
+        if after_dot {
+            let Some(qualifier) = index.checked_sub(2).map(|q| &tokens[q]) else {
+                continue;
+            };
+            if let Some(table) = resolve_qualifier(qualifier) {
+                if let Some((old, new)) = renames.renamed_column(&table, token) {
+                    note(
+                        &mut references,
+                        format!("column '{}.{}' -> '{}'", table, old, new),
+                    );
+                    replacements.push((token.start, token.end, token.replaced_with(new)));
+                }
+            }
+            continue;
+        }
+
+        // 修飾されていないカラム参照（関数名・出力カラムの別名を除く）
+        let is_function = tokens.get(index + 1).is_some_and(|next| next.is_punct('('));
+        let is_output_alias = index > 0 && tokens[index - 1].is_keyword("as");
+        if is_function || is_output_alias {
+            continue;
+        }
+        let candidates: Vec<(&String, &str, &str)> = referenced_tables
+            .iter()
+            .filter_map(|table| {
+                renames
+                    .renamed_column(table, token)
+                    .map(|(old, new)| (table, old, new))
+            })
+            .collect();
+        let Some(&(table, old, new)) = candidates.first() else {
+            continue;
+        };
+        let owners: Vec<&String> = referenced_tables
+            .iter()
+            .filter(|referenced| {
+                *referenced == table
+                    || old_schema
+                        .tables
+                        .get(old_table_name(renames, referenced))
+                        .is_some_and(|old_table| {
+                            old_table
+                                .columns
+                                .iter()
+                                .any(|column| token.matches(&column.name))
+                        })
+            })
+            .collect();
+        if owners.len() > 1 {
+            note(
+                &mut blockers,
+                format!(
+                    "column '{}' is ambiguous between {}; qualify it with a table name or alias",
+                    token.value,
+                    owners
+                        .iter()
+                        .map(|owner| format!("'{}'", owner))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+            continue;
+        }
+        note(
+            &mut references,
+            format!("column '{}.{}' -> '{}'", table, old, new),
+        );
+        replacements.push((token.start, token.end, token.replaced_with(new)));
+    }
+
+    if references.is_empty() && blockers.is_empty() {
+        return None;
+    }
+
+    let mut rewritten_definition = definition.to_string();
+    replacements.sort_by_key(|(start, _, _)| std::cmp::Reverse(*start));
+    for (start, end, replacement) in replacements {
+        rewritten_definition.replace_range(start..end, &replacement);
+    }
+
+    Some(StaleView {
+        view_name: String::new(),
+        references,
+        blockers,
+        rewritten_definition,
+    })
+}
+
+/// 重複しないようにメッセージを追加する
+fn note(list: &mut Vec<String>, message: String) {
+    if !list.contains(&message) {
+        list.push(message);
+    }
+}
+
+/// 現在のテーブル名から変更前のテーブル名を求める
+fn old_table_name<'a>(renames: &'a Renames, table: &'a str) -> &'a str {
+    renames
+        .tables
+        .iter()
+        .find(|(_, new)| new == table)
+        .map_or(table, |(old, _)| old.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, ColumnType, Table, View};
+    use crate::core::schema_diff::{RenamedColumn, RenamedTable, TableDiff};
+
+    fn column(name: &str) -> Column {
+        Column::new(
+            name.to_string(),
+            ColumnType::INTEGER { precision: None },
+            false,
+        )
+    }
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        let mut table = Table::new(name.to_string());
+        for name in columns {
+            table.columns.push(column(name));
+        }
+        table
+    }
+
+    fn rename_column(diff: &mut SchemaDiff, table: &str, old: &str, new: &str) {
+        let mut table_diff = TableDiff::new(table.to_string());
+        table_diff.renamed_columns.push(RenamedColumn {
+            old_name: old.to_string(),
+            old_column: column(old),
+            new_column: column(new),
+            changes: Vec::new(),
+        });
+        diff.modified_tables.push(table_diff);
+    }
+
+    fn schemas(definition: &str) -> (Schema, Schema) {
+        let mut old_schema = Schema::new("1.0".to_string());
+        old_schema.add_table(table("users", &["id", "name", "team_id"]));
+        old_schema.add_table(table("teams", &["id", "name"]));
+        old_schema.add_table(table("orders", &["id", "user_id", "total"]));
+        old_schema.add_view(View::new("report".to_string(), definition.to_string()));
+        let new_schema = old_schema.clone();
+        (old_schema, new_schema)
+    }
+
+    fn find_one(definition: &str, diff: &SchemaDiff) -> StaleView {
+        let (old_schema, new_schema) = schemas(definition);
+        let mut stale = find_stale_views(&old_schema, &new_schema, diff);
+        assert_eq!(stale.len(), 1, "{:?}", stale);
+        stale.remove(0)
+    }
+
+    #[test]
+    fn test_rewrites_aliased_column_in_join_and_where() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "users", "team_id", "group_id");
+
+        let stale = find_one(
+            "SELECT u.id, t.name FROM users u JOIN teams AS t ON t.id = u.team_id WHERE u.team_id IS NOT NULL",
+            &diff,
+        );
+
+        assert!(stale.can_rewrite());
+        assert_eq!(
+            stale.rewritten_definition,
+            "SELECT u.id, t.name FROM users u JOIN teams AS t ON t.id = u.group_id WHERE u.group_id IS NOT NULL"
+        );
+        assert_eq!(
+            stale.references,
+            vec!["column 'users.team_id' -> 'group_id'"]
+        );
+    }
+
+    #[test]
+    fn test_rewrites_renamed_table_and_qualifiers() {
+        let mut diff = SchemaDiff::new();
+        diff.renamed_tables.push(RenamedTable {
+            old_name: "orders".to_string(),
+            new_table: table("purchases", &["id", "user_id", "total"]),
+        });
+
+        let stale = find_one(
+            "SELECT users.id, orders.total FROM users INNER JOIN orders ON orders.user_id = users.id WHERE orders.total > 100",
+            &diff,
+        );
+
+        assert_eq!(
+            stale.rewritten_definition,
+            "SELECT users.id, purchases.total FROM users INNER JOIN purchases ON purchases.user_id = users.id WHERE purchases.total > 100"
+        );
+    }
+
+    #[test]
+    fn test_keeps_literals_comments_and_output_aliases() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "orders", "total", "amount");
+
+        let stale = find_one(
+            "-- total per order\nSELECT id, total AS total FROM orders WHERE note <> 'total'",
+            &diff,
+        );
+
+        assert_eq!(
+            stale.rewritten_definition,
+            "-- total per order\nSELECT id, amount AS total FROM orders WHERE note <> 'total'"
+        );
+    }
+
+    #[test]
+    fn test_unqualified_column_shared_by_joined_tables_is_ambiguous() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "users", "name", "full_name");
+
+        let stale = find_one(
+            "SELECT u.id FROM users u JOIN teams t ON t.id = u.team_id WHERE name = 'admin'",
+            &diff,
+        );
+
+        assert!(!stale.can_rewrite());
+        assert!(stale.blockers[0].contains("'name' is ambiguous"));
+    }
+
+    #[test]
+    fn test_quoted_identifiers_keep_their_quotes() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "users", "name", "full_name");
+
+        let stale = find_one(r#"SELECT "u"."name" FROM "users" AS "u""#, &diff);
+
+        assert_eq!(
+            stale.rewritten_definition,
+            r#"SELECT "u"."full_name" FROM "users" AS "u""#
+        );
+    }
+
+    #[test]
+    fn test_view_without_old_names_is_not_reported() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "users", "name", "full_name");
+
+        let (old_schema, new_schema) = schemas("SELECT id, total FROM orders");
+
+        assert!(find_stale_views(&old_schema, &new_schema, &diff).is_empty());
+    }
+
+    #[test]
+    fn test_apply_rewrites_marks_view_diff() {
+        let mut diff = SchemaDiff::new();
+        rename_column(&mut diff, "users", "name", "full_name");
+        let (old_schema, new_schema) = schemas("SELECT id, name FROM users");
+
+        let stale = find_stale_views(&old_schema, &new_schema, &diff);
+        apply_rewrites(&mut diff, &old_schema, &new_schema, &stale);
+
+        assert_eq!(diff.modified_views.len(), 1);
+        let view_diff = &diff.modified_views[0];
+        assert!(view_diff.auto_rewritten);
+        assert_eq!(view_diff.old_definition, "SELECT id, name FROM users");
+        assert_eq!(view_diff.new_definition, "SELECT id, full_name FROM users");
+    }
+}